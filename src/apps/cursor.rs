@@ -1,8 +1,9 @@
 use anyhow::{Context, Result};
 use console::style;
-use std::process::Command;
 use tokio::fs;
 
+use crate::apps::editor::{add_monorepo_folder, spawn_workspace};
+use crate::apps::render::destination_path;
 use crate::workspace::{Repository, TemplateManager, WorkspaceConfig};
 
 pub async fn open_with_cursor(
@@ -36,13 +37,9 @@ pub async fn open_with_cursor(
 
     // Apply variable substitution
     let workspace_content = template_manager.substitute_variables(&template_content, &variables);
+    let workspace_content = add_monorepo_folder(config, repo, workspace_content);
 
-    // Generate a unique workspace file name
-    let workspace_name = format!(
-        "vibe-{}-{}.code-workspace",
-        config.workspace.name, repo.name
-    );
-    let workspace_path = cursor_integration.workspace_dir.join(&workspace_name);
+    let workspace_path = destination_path(config, "cursor", repo)?;
 
     // Create workspace directory if it doesn't exist
     fs::create_dir_all(&cursor_integration.workspace_dir)
@@ -71,7 +68,7 @@ pub async fn open_with_cursor(
     );
 
     // Try to open with Cursor
-    let result = Command::new("cursor").arg(&workspace_path).spawn();
+    let result = spawn_workspace(config, repo, "cursor", &workspace_path);
 
     match result {
         Ok(_) => {
@@ -102,11 +99,7 @@ pub async fn cleanup_cursor_config(config: &WorkspaceConfig, repo: &Repository)
     }
 
     // Generate the workspace file name that would have been created
-    let workspace_name = format!(
-        "vibe-{}-{}.code-workspace",
-        config.workspace.name, repo.name
-    );
-    let workspace_path = cursor_integration.workspace_dir.join(&workspace_name);
+    let workspace_path = destination_path(config, "cursor", repo)?;
 
     if workspace_path.exists() {
         fs::remove_file(&workspace_path).await.with_context(|| {
@@ -0,0 +1,173 @@
+//! Opening a file at a line and column in an editor, and the workspace
+//! files shared by VS Code and its forks
+//!
+//! Editors disagree on how to take a location: VS Code and its forks use
+//! `--goto file:line:column`, Zed, Sublime Text and Helix accept
+//! `file:line:column`, and terminal editors take `+line file`. Unknown
+//! editors just get the file.
+
+use anyhow::{Context, Result};
+use std::ffi::OsString;
+use std::path::Path;
+use tokio::process::Child;
+
+use crate::utils::direnv;
+use crate::utils::platform::resolve_command;
+use crate::workspace::{repo_abs_path, Repository, WorkspaceConfig};
+
+/// Arguments that make `editor` open `file` at `line` and `column`
+pub fn location_args(editor: &str, file: &Path, line: u64, column: u64) -> Vec<OsString> {
+    let name = Path::new(editor)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let with_location = || OsString::from(format!("{}:{line}:{column}", file.display()));
+
+    match name.as_str() {
+        "code" | "code-insiders" | "codium" | "cursor" | "windsurf" => {
+            vec!["--goto".into(), with_location()]
+        }
+        "zed" | "subl" | "sublime_text" | "hx" | "helix" => vec![with_location()],
+        "vi" | "vim" | "nvim" | "nano" | "emacs" | "emacsclient" | "micro" | "kak" => {
+            vec![format!("+{line}").into(), file.into()]
+        }
+        _ => vec![file.into()],
+    }
+}
+
+/// Open `file` at `line` and `column` in `editor`, loading the `.envrc` of
+/// `dir` through direnv when `enable_direnv` is set
+pub async fn open_at_location(
+    editor: &str,
+    dir: &Path,
+    file: &Path,
+    line: u64,
+    column: u64,
+    enable_direnv: bool,
+) -> Result<()> {
+    let status = direnv::command(resolve_command(editor), dir, enable_direnv)
+        .args(location_args(editor, file, line, column))
+        .current_dir(dir)
+        .status()
+        .await
+        .with_context(|| format!("Failed to execute editor: {editor}"))?;
+
+    if !status.success() {
+        anyhow::bail!("Editor command failed with status: {status}");
+    }
+    Ok(())
+}
+
+/// Start `program` on the workspace file at `workspace_path` for `repo`.
+/// Editors launched outside a shell miss the repository's direnv
+/// environment, which language servers and tasks rely on, so the launch goes
+/// through direnv when it's enabled.
+pub fn spawn_workspace(
+    config: &WorkspaceConfig,
+    repo: &Repository,
+    program: &str,
+    workspace_path: &Path,
+) -> std::io::Result<Child> {
+    let enable_direnv = config.preferences.as_ref().is_some_and(|p| p.enable_direnv);
+    direnv::command(
+        resolve_command(program),
+        &repo_abs_path(config, repo),
+        enable_direnv,
+    )
+    .arg(workspace_path)
+    .spawn()
+}
+
+/// File name of the `.code-workspace` file written for `repo`. Subproject
+/// and `owner/repo` names would otherwise name a subdirectory.
+pub fn workspace_file_name(config: &WorkspaceConfig, repo: &Repository) -> String {
+    format!(
+        "vibe-{}-{}.code-workspace",
+        config.workspace.name,
+        repo.name.replace('/', "-")
+    )
+}
+
+/// Add the monorepo as a second folder when `repo` is a subproject view, so
+/// the editor shows the package and the rest of the repository side by
+/// side. Content that isn't a JSON workspace with `folders` is left alone.
+pub fn add_monorepo_folder(config: &WorkspaceConfig, repo: &Repository, content: String) -> String {
+    let Some((monorepo, _)) = config.find_subproject(&repo.name) else {
+        return content;
+    };
+    let Ok(mut workspace) = serde_json::from_str::<serde_json::Value>(&content) else {
+        return content;
+    };
+    let Some(folders) = workspace
+        .get_mut("folders")
+        .and_then(|folders| folders.as_array_mut())
+    else {
+        return content;
+    };
+    folders.push(serde_json::json!({
+        "name": monorepo.name,
+        "path": repo_abs_path(config, monorepo),
+    }));
+    serde_json::to_string_pretty(&workspace).unwrap_or(content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(editor: &str) -> Vec<String> {
+        location_args(editor, Path::new("/src/api/lib.rs"), 12, 5)
+            .into_iter()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn test_location_args() {
+        assert_eq!(args("code"), ["--goto", "/src/api/lib.rs:12:5"]);
+        assert_eq!(
+            args("/usr/local/bin/cursor"),
+            ["--goto", "/src/api/lib.rs:12:5"]
+        );
+        assert_eq!(args("zed"), ["/src/api/lib.rs:12:5"]);
+        assert_eq!(args("nvim"), ["+12", "/src/api/lib.rs"]);
+        assert_eq!(args("unknown-editor"), ["/src/api/lib.rs"]);
+    }
+
+    #[test]
+    fn test_add_monorepo_folder() {
+        let mut config = WorkspaceConfig::default();
+        config.workspace.name = "ws".to_string();
+        config.workspace.root = "/work".into();
+        let mut monorepo = Repository::new("mono", "mono");
+        monorepo
+            .subprojects
+            .push(crate::workspace::config::Subproject {
+                name: "web".to_string(),
+                path: "packages/web".into(),
+            });
+        config.repositories.push(monorepo.clone());
+        let view = monorepo.subproject_view(&monorepo.subprojects[0]);
+        assert_eq!(
+            workspace_file_name(&config, &view),
+            "vibe-ws-mono-web.code-workspace"
+        );
+
+        let content = r#"{"folders": [{"name": "mono/web", "path": "/work/mono/packages/web"}]}"#;
+        let workspace: serde_json::Value =
+            serde_json::from_str(&add_monorepo_folder(&config, &view, content.to_string()))
+                .unwrap();
+        assert_eq!(workspace["folders"][1]["name"], "mono");
+        assert_eq!(workspace["folders"][1]["path"], "/work/mono");
+
+        // The monorepo itself and non-JSON templates are unchanged
+        assert_eq!(
+            add_monorepo_folder(&config, &monorepo, content.to_string()),
+            content
+        );
+        assert_eq!(
+            add_monorepo_folder(&config, &view, "// {".to_string()),
+            "// {"
+        );
+    }
+}
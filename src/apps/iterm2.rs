@@ -1,15 +1,16 @@
 use anyhow::{Context, Result};
 use console::style;
 use std::path::PathBuf;
-use std::process::Command;
 use tokio::fs;
+use tokio::process::Command;
 
+use crate::apps::render::destination_path;
 use crate::workspace::templates::DEFAULT_ITERMOCIL_TEMPLATE;
-use crate::workspace::{Repository, TemplateManager, WorkspaceConfig};
+use crate::workspace::{repo_abs_path, Repository, TemplateManager, WorkspaceConfig};
 
 /// Check if iTermocil is installed on the system
 fn is_itermocil_available() -> bool {
-    Command::new("which")
+    std::process::Command::new("which")
         .arg("itermocil")
         .output()
         .map(|output| output.status.success())
@@ -105,9 +106,7 @@ pub async fn open_with_iterm2_options(
     // Apply variable substitution
     let profile_content = template_manager.substitute_variables(&template_content, &variables);
 
-    // Generate a unique profile file name
-    let profile_name = format!("vibe-{}-{}.json", config.workspace.name, repo.name);
-    let profile_path = iterm2_integration.config_dir.join(&profile_name);
+    let profile_path = destination_path(config, "iterm2", repo)?;
 
     // Create config directory if it doesn't exist
     let config_dir = &iterm2_integration.config_dir;
@@ -186,7 +185,7 @@ tell application "iTerm2"
 end tell"#,
         profile_name,
         profile_guid,
-        config.workspace.root.join(&repo.path).display(),
+        repo_abs_path(config, repo).display(),
         repo.name,
         repo.branch.as_deref().unwrap_or("main")
     );
@@ -194,7 +193,8 @@ end tell"#,
     let result = Command::new("osascript")
         .arg("-e")
         .arg(&applescript)
-        .output();
+        .output()
+        .await;
 
     match result {
         Ok(output) => {
@@ -224,7 +224,7 @@ end tell"#,
             println!("2. Look for profile '{profile_name}' or '{profile_guid}'");
             println!(
                 "3. Or manually navigate to: {}",
-                config.workspace.root.join(&repo.path).display()
+                repo_abs_path(config, repo).display()
             );
         }
     }
@@ -245,8 +245,7 @@ pub async fn cleanup_iterm2_config(config: &WorkspaceConfig, repo: &Repository)
     }
 
     // Generate the profile file name that would have been created
-    let profile_name = format!("vibe-{}-{}.json", config.workspace.name, repo.name);
-    let profile_path = iterm2_integration.config_dir.join(&profile_name);
+    let profile_path = destination_path(config, "iterm2", repo)?;
 
     if profile_path.exists() {
         fs::remove_file(&profile_path).await.with_context(|| {
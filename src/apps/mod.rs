@@ -1,9 +1,12 @@
 pub mod app_manager;
 pub mod cursor;
+pub mod editor;
 pub mod installer_ui;
 pub mod iterm2;
 pub mod package_manager;
 pub mod registry;
+pub mod render;
+pub mod system;
 pub mod vscode;
 pub mod warp;
 pub mod wezterm;
@@ -16,3 +19,91 @@ pub use vscode::{cleanup_vscode_config, open_with_vscode};
 pub use warp::{cleanup_warp_config, open_with_warp};
 pub use wezterm::{cleanup_wezterm_config, open_with_wezterm_options};
 pub use windsurf::{cleanup_windsurf_config, open_with_windsurf};
+
+/// Check if an app is available on the system
+pub async fn is_app_available(app_name: &str) -> bool {
+    match app_name {
+        "vscode" => {
+            // Check if VS Code is available
+            tokio::process::Command::new("code")
+                .arg("--version")
+                .output()
+                .await
+                .map(|output| output.status.success())
+                .unwrap_or(false)
+        }
+        "warp" => {
+            // Check if Warp is available
+            #[cfg(target_os = "macos")]
+            {
+                tokio::fs::metadata("/Applications/Warp.app").await.is_ok()
+            }
+            #[cfg(not(target_os = "macos"))]
+            {
+                false
+            }
+        }
+        "iterm2" => {
+            // Check if iTerm2 is available
+            #[cfg(target_os = "macos")]
+            {
+                tokio::fs::metadata("/Applications/iTerm.app").await.is_ok()
+            }
+            #[cfg(not(target_os = "macos"))]
+            {
+                false
+            }
+        }
+        "wezterm" => {
+            // Check if WezTerm is available
+            tokio::process::Command::new("wezterm")
+                .arg("--version")
+                .output()
+                .await
+                .map(|output| output.status.success())
+                .unwrap_or(false)
+        }
+        "cursor" => {
+            // Check if Cursor is available
+            #[cfg(target_os = "macos")]
+            {
+                tokio::fs::metadata("/Applications/Cursor.app")
+                    .await
+                    .is_ok()
+            }
+            #[cfg(not(target_os = "macos"))]
+            {
+                // Try command line for other platforms
+                tokio::process::Command::new("cursor")
+                    .arg("--version")
+                    .output()
+                    .await
+                    .map(|output| output.status.success())
+                    .unwrap_or(false)
+            }
+        }
+        "windsurf" => {
+            // Check if Windsurf is available
+            #[cfg(target_os = "macos")]
+            {
+                tokio::fs::metadata("/Applications/Windsurf.app")
+                    .await
+                    .is_ok()
+            }
+            #[cfg(not(target_os = "macos"))]
+            {
+                // Try command line for other platforms
+                tokio::process::Command::new("windsurf")
+                    .arg("--version")
+                    .output()
+                    .await
+                    .map(|output| output.status.success())
+                    .unwrap_or(false)
+            }
+        }
+        app if system::is_pseudo_app(app) => {
+            crate::utils::platform::Platform::current() != crate::utils::platform::Platform::Unknown
+        }
+        _ => false,
+    }
+}
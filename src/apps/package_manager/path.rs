@@ -4,6 +4,7 @@ use std::process::Stdio;
 use tokio::process::Command;
 
 use super::{PackageManager, PackageManagerType};
+use crate::utils::platform::resolve_command;
 
 /// Path-based package detection (checks if binary exists on PATH)
 pub struct PathManager;
@@ -15,14 +16,17 @@ impl PathManager {
 
     /// Check if a binary exists on PATH and return its location
     pub async fn which(&self, binary: &str) -> Result<Option<String>> {
-        let output = Command::new("which")
+        let finder = if cfg!(windows) { "where" } else { "which" };
+        let output = Command::new(finder)
             .arg(binary)
             .output()
             .await
-            .context("Failed to run which command")?;
+            .with_context(|| format!("Failed to run {finder} command"))?;
 
         if output.status.success() && !output.stdout.is_empty() {
-            let path = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            // `where` lists every match; the first is what PATH resolves to
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            let path = stdout.lines().next().unwrap_or_default().trim().to_string();
             Ok(Some(path))
         } else {
             Ok(None)
@@ -40,7 +44,7 @@ impl PathManager {
             return Ok(None);
         }
 
-        let mut cmd = Command::new(binary);
+        let mut cmd = Command::new(resolve_command(binary));
         for arg in args {
             cmd.arg(arg);
         }
@@ -0,0 +1,178 @@
+//! Rendering an app's template for a repository without launching the app,
+//! for `vibe apps template render` and the `render_template` MCP tool. The
+//! launchers write to [`destination_path`], so a render shows exactly what
+//! opening the repository would produce.
+
+use anyhow::Result;
+use serde::Serialize;
+use std::path::PathBuf;
+
+use crate::apps::editor::{add_monorepo_folder, workspace_file_name};
+use crate::output::exit::CommandError;
+use crate::workspace::{Repository, TemplateManager, WorkspaceConfig};
+
+/// Apps that open repositories from a rendered template
+pub const TEMPLATE_APPS: &[&str] = &["warp", "iterm2", "vscode", "wezterm", "cursor", "windsurf"];
+
+/// Names listed in "not found" hints before the rest are summarized
+const MAX_LISTED: usize = 30;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RenderedTemplate {
+    pub app: String,
+    pub template: String,
+    pub repository: String,
+    /// File opening the repository with `app` writes
+    pub destination: PathBuf,
+    /// Whether `destination` exists now, so opening would overwrite it
+    pub destination_exists: bool,
+    pub content: String,
+}
+
+/// Render `app`'s template for the repository named `repo_name`: `template`,
+/// or else the one the repository is configured with, or the app's default.
+/// Unknown apps, repositories, and templates are errors naming the valid ones.
+pub async fn render_template(
+    config: &WorkspaceConfig,
+    template_manager: &TemplateManager,
+    app: &str,
+    template: Option<&str>,
+    repo_name: &str,
+) -> Result<RenderedTemplate> {
+    if !TEMPLATE_APPS.contains(&app) {
+        return Err(CommandError::Usage(format!(
+            "App '{app}' has no templates. Apps with templates: {}",
+            TEMPLATE_APPS.join(", ")
+        ))
+        .into());
+    }
+    let repo = find_repository(config, repo_name)?;
+
+    let template = match template {
+        Some(template) => template.to_string(),
+        None => repo
+            .get_app_template(app)
+            .map(str::to_string)
+            .unwrap_or_else(|| default_template(config, app)),
+    };
+    let available = template_manager.list_templates(app).await?;
+    if !available.contains(&template) {
+        let hint = if available.is_empty() {
+            format!("{app} has no templates; run 'vibe apps template update-defaults'")
+        } else {
+            format!("Templates for {app}: {}", list(&available))
+        };
+        return Err(CommandError::not_found_with_hint("Template", template, hint).into());
+    }
+
+    let content = template_manager.load_template(app, &template).await?;
+    let variables = TemplateManager::create_variables(config, &repo);
+    let mut content = template_manager.substitute_variables(&content, &variables);
+    if matches!(app, "vscode" | "cursor" | "windsurf") {
+        content = add_monorepo_folder(config, &repo, content);
+    }
+
+    let destination = destination_path(config, app, &repo)?;
+    Ok(RenderedTemplate {
+        app: app.to_string(),
+        template,
+        repository: repo.name.clone(),
+        destination_exists: destination.exists(),
+        destination,
+        content,
+    })
+}
+
+/// File the `app` launcher writes its rendered template to for `repo`
+pub fn destination_path(config: &WorkspaceConfig, app: &str, repo: &Repository) -> Result<PathBuf> {
+    let apps = &config.apps;
+    let not_configured = || CommandError::Usage(format!("{app} integration is not configured"));
+    let file_name =
+        |extension: &str| format!("vibe-{}-{}.{extension}", config.workspace.name, repo.name);
+    let editor_file = || workspace_file_name(config, repo);
+    let path = match app {
+        "warp" => apps
+            .warp
+            .as_ref()
+            .map(|a| a.config_dir.join(file_name("yaml"))),
+        "iterm2" => apps
+            .iterm2
+            .as_ref()
+            .map(|a| a.config_dir.join(file_name("json"))),
+        "wezterm" => apps
+            .wezterm
+            .as_ref()
+            .map(|a| a.config_dir.join(file_name("lua"))),
+        "vscode" => apps
+            .vscode
+            .as_ref()
+            .map(|a| a.workspace_dir.join(editor_file())),
+        "cursor" => apps
+            .cursor
+            .as_ref()
+            .map(|a| a.workspace_dir.join(editor_file())),
+        "windsurf" => apps
+            .windsurf
+            .as_ref()
+            .map(|a| a.workspace_dir.join(editor_file())),
+        _ => None,
+    };
+    path.ok_or_else(|| not_configured().into())
+}
+
+/// The repository or `<repo>:<subproject>` named `name`
+fn find_repository(config: &WorkspaceConfig, name: &str) -> Result<Repository> {
+    if let Some(repo) = config.get_repository_flexible(name) {
+        return Ok(repo.clone());
+    }
+    if let Some((repo, subproject)) = config.find_subproject(name) {
+        return Ok(repo.subproject_view(subproject));
+    }
+    let names: Vec<String> = config.repositories.iter().map(|r| r.name.clone()).collect();
+    let hint = if names.is_empty() {
+        "No repositories are configured".to_string()
+    } else {
+        format!("Repositories: {}", list(&names))
+    };
+    Err(CommandError::not_found_with_hint("Repository", name, hint).into())
+}
+
+fn default_template(config: &WorkspaceConfig, app: &str) -> String {
+    let apps = &config.apps;
+    let configured = match app {
+        "warp" => apps.warp.as_ref().map(|a| &a.default_template),
+        "iterm2" => apps.iterm2.as_ref().map(|a| &a.default_template),
+        "wezterm" => apps.wezterm.as_ref().map(|a| &a.default_template),
+        "vscode" => apps.vscode.as_ref().map(|a| &a.default_template),
+        "cursor" => apps.cursor.as_ref().map(|a| &a.default_template),
+        "windsurf" => apps.windsurf.as_ref().map(|a| &a.default_template),
+        _ => None,
+    };
+    configured.cloned().unwrap_or_else(|| "default".to_string())
+}
+
+/// `names` joined with commas, the tail summarized past [`MAX_LISTED`]
+fn list(names: &[String]) -> String {
+    if names.len() <= MAX_LISTED {
+        return names.join(", ");
+    }
+    format!(
+        "{}, and {} more",
+        names[..MAX_LISTED].join(", "),
+        names.len() - MAX_LISTED
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_list_summarizes_long_lists() {
+        let names: Vec<String> = (0..32).map(|i| format!("repo{i}")).collect();
+        assert_eq!(list(&names[..2]), "repo0, repo1");
+        let listed = list(&names);
+        assert!(listed.contains("repo29, and 2 more"));
+        assert!(!listed.contains("repo30"));
+    }
+}
@@ -0,0 +1,140 @@
+//! Pseudo-apps every repository can be opened with, without configuration:
+//! the system file manager and the remote's web page
+
+use anyhow::{Context, Result};
+use std::path::Path;
+use tokio::process::Command;
+
+use crate::output::exit::CommandError;
+use crate::utils::platform::{self, Platform};
+
+/// Name that opens the repository's remote in the browser
+pub const BROWSER_APP: &str = "browser";
+
+/// All pseudo-app names: `finder` and `files` open the file manager
+pub const PSEUDO_APPS: &[&str] = &["finder", "files", "browser"];
+
+/// Whether `app` is a pseudo-app rather than a configurable app
+pub fn is_pseudo_app(app: &str) -> bool {
+    PSEUDO_APPS.contains(&app)
+}
+
+/// Open `path` in the platform's file manager
+pub async fn open_in_file_manager(path: &Path) -> Result<()> {
+    let program = match Platform::current() {
+        Platform::MacOS => "open",
+        Platform::Linux => "xdg-open",
+        Platform::Windows => "explorer",
+        Platform::Unknown => anyhow::bail!("No file manager is known for this platform"),
+    };
+
+    Command::new(program)
+        .arg(path)
+        .spawn()
+        .with_context(|| format!("Failed to run {program}"))?;
+    Ok(())
+}
+
+/// Open the web page of the repository at `path`, using its `origin`
+/// remote or `configured_url` when the checkout has none
+pub async fn open_in_browser(
+    repo_name: &str,
+    path: &Path,
+    configured_url: Option<&str>,
+) -> Result<String> {
+    let remote = match origin_url(path).await {
+        Some(remote) => remote,
+        None => configured_url.map(str::to_string).ok_or_else(|| {
+            CommandError::Usage(format!(
+                "Repository '{repo_name}' has no remote to open in the browser"
+            ))
+        })?,
+    };
+    let url = web_url(&remote).ok_or_else(|| {
+        CommandError::Usage(format!(
+            "Remote '{remote}' of '{repo_name}' has no web page to open"
+        ))
+    })?;
+
+    platform::open_uri(&url).context("Failed to open the browser")?;
+    Ok(url)
+}
+
+async fn origin_url(path: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .args(["remote", "get-url", "origin"])
+        .current_dir(path)
+        .output()
+        .await
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let url = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!url.is_empty()).then_some(url)
+}
+
+/// Web page for a git remote URL: ssh and scp-like remotes become https,
+/// credentials and ports are dropped, and `.git` is stripped. Local paths
+/// and `file://` remotes have no web page.
+pub fn web_url(remote: &str) -> Option<String> {
+    let remote = remote.trim();
+    let (host, path) = if let Some((scheme, rest)) = remote.split_once("://") {
+        if !matches!(scheme, "https" | "http" | "ssh" | "git" | "git+ssh") {
+            return None;
+        }
+        let (authority, path) = rest.split_once('/')?;
+        let host = authority.rsplit('@').next()?;
+        let host = host.split(':').next()?;
+        (host, path)
+    } else {
+        // scp-like: [user@]host:owner/repo.git
+        let (authority, path) = remote.split_once(':')?;
+        if authority.contains('/') || path.starts_with('/') || path.starts_with('\\') {
+            return None;
+        }
+        (authority.rsplit('@').next()?, path)
+    };
+
+    let path = path.trim_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    if host.is_empty() || path.is_empty() {
+        return None;
+    }
+    Some(format!("https://{host}/{path}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_web_url() {
+        for remote in [
+            "https://github.com/acme/api.git",
+            "https://token@github.com/acme/api",
+            "git@github.com:acme/api.git",
+            "ssh://git@github.com:22/acme/api.git",
+            "git://github.com/acme/api.git/",
+        ] {
+            assert_eq!(
+                web_url(remote).as_deref(),
+                Some("https://github.com/acme/api"),
+                "{remote}"
+            );
+        }
+        assert_eq!(
+            web_url("git@gitlab.com:group/sub/project.git").as_deref(),
+            Some("https://gitlab.com/group/sub/project")
+        );
+
+        for remote in [
+            "/srv/git/api.git",
+            "file:///srv/git/api.git",
+            "../api",
+            "C:\\repos\\api",
+        ] {
+            assert_eq!(web_url(remote), None, "{remote}");
+        }
+    }
+}
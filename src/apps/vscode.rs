@@ -1,8 +1,9 @@
 use anyhow::{Context, Result};
 use console::style;
-use std::process::Command;
 use tokio::fs;
 
+use crate::apps::editor::{add_monorepo_folder, spawn_workspace};
+use crate::apps::render::destination_path;
 use crate::workspace::{Repository, TemplateManager, WorkspaceConfig};
 
 pub async fn open_with_vscode(
@@ -36,13 +37,9 @@ pub async fn open_with_vscode(
 
     // Apply variable substitution
     let workspace_content = template_manager.substitute_variables(&template_content, &variables);
+    let workspace_content = add_monorepo_folder(config, repo, workspace_content);
 
-    // Generate a unique workspace file name
-    let workspace_name = format!(
-        "vibe-{}-{}.code-workspace",
-        config.workspace.name, repo.name
-    );
-    let workspace_path = vscode_integration.workspace_dir.join(&workspace_name);
+    let workspace_path = destination_path(config, "vscode", repo)?;
 
     // Create workspace directory if it doesn't exist
     fs::create_dir_all(&vscode_integration.workspace_dir)
@@ -71,7 +68,7 @@ pub async fn open_with_vscode(
     );
 
     // Try to open with VS Code
-    let result = Command::new("code").arg(&workspace_path).spawn();
+    let result = spawn_workspace(config, repo, "code", &workspace_path);
 
     match result {
         Ok(_) => {
@@ -105,11 +102,7 @@ pub async fn cleanup_vscode_config(config: &WorkspaceConfig, repo: &Repository)
     }
 
     // Generate the workspace file name that would have been created
-    let workspace_name = format!(
-        "vibe-{}-{}.code-workspace",
-        config.workspace.name, repo.name
-    );
-    let workspace_path = vscode_integration.workspace_dir.join(&workspace_name);
+    let workspace_path = destination_path(config, "vscode", repo)?;
 
     if workspace_path.exists() {
         fs::remove_file(&workspace_path).await.with_context(|| {
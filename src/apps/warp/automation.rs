@@ -1,5 +1,5 @@
 use anyhow::{Context, Result};
-use std::process::Command;
+use tokio::process::Command;
 
 /// Platform-specific automation for launching Warp configurations
 pub enum PlatformAutomation {
@@ -61,6 +61,7 @@ impl MacOSAutomation {
             .arg("-e")
             .arg(&apple_script)
             .output()
+            .await
             .context("Failed to execute AppleScript")?;
 
         if !output.status.success() {
@@ -74,7 +75,7 @@ impl MacOSAutomation {
     fn is_available(&self) -> bool {
         // Check if we're on macOS and osascript is available
         cfg!(target_os = "macos")
-            && Command::new("which")
+            && std::process::Command::new("which")
                 .arg("osascript")
                 .output()
                 .map(|output| output.status.success())
@@ -134,6 +135,7 @@ impl WindowsAutomation {
         let output = Command::new("powershell")
             .args(["-NoProfile", "-Command", &powershell_script])
             .output()
+            .await
             .context("Failed to execute PowerShell script")?;
 
         if !output.status.success() {
@@ -147,7 +149,7 @@ impl WindowsAutomation {
     fn is_available(&self) -> bool {
         // Check if we're on Windows and PowerShell is available
         cfg!(target_os = "windows")
-            && Command::new("powershell")
+            && std::process::Command::new("powershell")
                 .args(["-Command", "echo test"])
                 .output()
                 .map(|output| output.status.success())
@@ -168,6 +170,7 @@ impl LinuxAutomation {
         let search_output = Command::new("xdotool")
             .args(["search", "--name", "Warp"])
             .output()
+            .await
             .context("Failed to search for Warp window")?;
 
         if !search_output.status.success() || search_output.stdout.is_empty() {
@@ -186,6 +189,7 @@ impl LinuxAutomation {
         Command::new("xdotool")
             .args(["windowactivate", window_id])
             .output()
+            .await
             .context("Failed to activate Warp window")?;
 
         // Small delay to ensure window is active
@@ -195,6 +199,7 @@ impl LinuxAutomation {
         Command::new("xdotool")
             .args(["key", "ctrl+l"])
             .output()
+            .await
             .context("Failed to send Ctrl+L")?;
 
         // Small delay for palette to open
@@ -204,6 +209,7 @@ impl LinuxAutomation {
         Command::new("xdotool")
             .args(["type", config_name])
             .output()
+            .await
             .context("Failed to type configuration name")?;
 
         // Small delay before pressing Enter
@@ -213,6 +219,7 @@ impl LinuxAutomation {
         Command::new("xdotool")
             .args(["key", "Return"])
             .output()
+            .await
             .context("Failed to send Enter key")?;
 
         Ok(())
@@ -221,7 +228,7 @@ impl LinuxAutomation {
     fn is_available(&self) -> bool {
         // Check if we're on Linux and xdotool is available
         cfg!(target_os = "linux")
-            && Command::new("which")
+            && std::process::Command::new("which")
                 .arg("xdotool")
                 .output()
                 .map(|output| output.status.success())
@@ -1,15 +1,16 @@
 use anyhow::{Context, Result};
 use console::style;
 use std::path::PathBuf;
-use std::process::Command;
 use tokio::fs;
+use tokio::process::Command;
 
+use crate::apps::render::destination_path;
 use crate::workspace::templates::DEFAULT_WEZTERMOCIL_TEMPLATE;
 use crate::workspace::{Repository, TemplateManager, WorkspaceConfig};
 
 /// Check if weztermocil is installed on the system
 fn is_weztermocil_available() -> bool {
-    Command::new("which")
+    std::process::Command::new("which")
         .arg("weztermocil")
         .output()
         .map(|output| output.status.success())
@@ -98,9 +99,7 @@ pub async fn open_with_wezterm_options(
     // Apply variable substitution
     let config_content = template_manager.substitute_variables(&template_content, &variables);
 
-    // Generate a unique config file name
-    let config_name = format!("vibe-{}-{}.lua", config.workspace.name, repo.name);
-    let config_path = wezterm_integration.config_dir.join(&config_name);
+    let config_path = destination_path(config, "wezterm", repo)?;
 
     // Create config directory if it doesn't exist
     fs::create_dir_all(&wezterm_integration.config_dir)
@@ -192,8 +191,7 @@ pub async fn cleanup_wezterm_config(config: &WorkspaceConfig, repo: &Repository)
     }
 
     // Clean up Lua config
-    let config_name = format!("vibe-{}-{}.lua", config.workspace.name, repo.name);
-    let config_path = wezterm_integration.config_dir.join(&config_name);
+    let config_path = destination_path(config, "wezterm", repo)?;
 
     if config_path.exists() {
         fs::remove_file(&config_path).await.with_context(|| {
@@ -294,7 +292,7 @@ async fn create_and_launch_weztermocil_layout(
     );
 
     // Launch weztermocil with our layout and wait for it to complete
-    let result = Command::new("weztermocil").arg(&layout_name).output();
+    let result = Command::new("weztermocil").arg(&layout_name).output().await;
 
     match result {
         Ok(output) => {
@@ -1,9 +1,12 @@
 use anyhow::{Context, Result};
 use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use tokio_rusqlite::{params, Connection};
 
+use crate::worktree::metadata::WorktreeMetadata;
+
 /// Cached git status information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CachedGitStatus {
@@ -51,10 +54,22 @@ impl From<CachedGitStatus> for crate::workspace::operations::GitStatus {
             unstaged: cached.unstaged,
             untracked: cached.untracked,
             remote_url: cached.remote_url,
+            upstream_behind: None,
+            compare_ref: None,
         }
     }
 }
 
+/// The newest commit on a repository's checked-out branch, shown in the
+/// open picker's preview
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedLastCommit {
+    pub repository_name: String,
+    pub subject: String,
+    pub committed_at: DateTime<Utc>,
+    pub last_updated: DateTime<Utc>,
+}
+
 /// Fast SQLite-based cache for git status information
 pub struct GitStatusCache {
     db_path: PathBuf,
@@ -80,6 +95,11 @@ impl GitStatusCache {
         }
     }
 
+    /// Location of the cache database
+    pub fn db_path(&self) -> &Path {
+        &self.db_path
+    }
+
     /// Initialize the cache database with required tables
     pub async fn initialize(&self) -> Result<()> {
         let conn = Connection::open(&self.db_path)
@@ -117,6 +137,72 @@ impl GitStatusCache {
                 [],
             )?;
 
+            conn.execute(
+                r#"
+                CREATE TABLE IF NOT EXISTS pull_requests (
+                    query TEXT PRIMARY KEY,  -- filter and group the dashboard was built for
+                    dashboard TEXT NOT NULL, -- JSON pull request dashboard
+                    fetched_at TEXT NOT NULL -- ISO 8601 datetime
+                )
+                "#,
+                [],
+            )?;
+
+            conn.execute(
+                r#"
+                CREATE TABLE IF NOT EXISTS last_commits (
+                    repository_name TEXT PRIMARY KEY,
+                    subject TEXT NOT NULL,
+                    committed_at TEXT NOT NULL, -- ISO 8601 datetime
+                    last_updated TEXT NOT NULL  -- ISO 8601 datetime
+                )
+                "#,
+                [],
+            )?;
+
+            // Task metadata never expires; it lives as long as the worktree
+            conn.execute(
+                r#"
+                CREATE TABLE IF NOT EXISTS worktree_metadata (
+                    repo TEXT NOT NULL,       -- main checkout path
+                    branch TEXT NOT NULL,
+                    description TEXT,
+                    link TEXT,
+                    updated_at TEXT NOT NULL, -- ISO 8601 datetime
+                    base_branch TEXT,
+                    base_commit TEXT,
+                    expires_at TEXT,          -- ISO 8601 datetime
+                    task_id TEXT,
+                    created_at TEXT,          -- ISO 8601 datetime
+                    pull_request TEXT,        -- URL opened by `vibe task finish`
+                    PRIMARY KEY (repo, branch)
+                )
+                "#,
+                [],
+            )?;
+
+            // Databases created before base tracking, expiry, adoption, or
+            // `vibe task` lack those columns
+            let columns = conn
+                .prepare("SELECT name FROM pragma_table_info('worktree_metadata')")?
+                .query_map([], |row| row.get::<_, String>(0))?
+                .collect::<Result<Vec<_>, _>>()?;
+            for column in [
+                "base_branch",
+                "base_commit",
+                "expires_at",
+                "task_id",
+                "created_at",
+                "pull_request",
+            ] {
+                if !columns.iter().any(|existing| existing == column) {
+                    conn.execute(
+                        &format!("ALTER TABLE worktree_metadata ADD COLUMN {column} TEXT"),
+                        [],
+                    )?;
+                }
+            }
+
             Ok(())
         })
         .await
@@ -223,10 +309,262 @@ impl GitStatusCache {
         Ok(result)
     }
 
+    /// Cache a pull request dashboard for `query`
+    pub async fn cache_pull_requests(&self, query: &str, dashboard_json: &str) -> Result<()> {
+        let conn = Connection::open(&self.db_path).await?;
+        let query = query.to_string();
+        let dashboard_json = dashboard_json.to_string();
+
+        conn.call(move |conn| {
+            conn.execute(
+                r#"
+                INSERT OR REPLACE INTO pull_requests (query, dashboard, fetched_at)
+                VALUES (?1, ?2, ?3)
+                "#,
+                params![query, dashboard_json, Utc::now().to_rfc3339()],
+            )?;
+            Ok(())
+        })
+        .await
+        .context("Failed to cache pull requests")?;
+
+        Ok(())
+    }
+
+    /// Get the cached pull request dashboard JSON for `query` if it's still
+    /// valid (within TTL)
+    pub async fn get_pull_requests(&self, query: &str) -> Result<Option<String>> {
+        let conn = Connection::open(&self.db_path).await?;
+        let query = query.to_string();
+        let ttl_minutes = self.cache_ttl_minutes;
+
+        let result = conn
+            .call(move |conn| {
+                let row = conn.query_row(
+                    "SELECT dashboard, fetched_at FROM pull_requests WHERE query = ?1",
+                    params![query],
+                    |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)),
+                );
+
+                match row {
+                    Ok((dashboard, fetched_at)) => {
+                        let fresh = DateTime::parse_from_rfc3339(&fetched_at)
+                            .map(|fetched_at| {
+                                Utc::now().signed_duration_since(fetched_at)
+                                    <= Duration::minutes(ttl_minutes)
+                            })
+                            .unwrap_or(false);
+                        Ok(fresh.then_some(dashboard))
+                    }
+                    Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+                    Err(e) => Err(tokio_rusqlite::Error::Rusqlite(e)),
+                }
+            })
+            .await
+            .context("Failed to get cached pull requests")?;
+
+        Ok(result)
+    }
+
+    /// Cache the newest commit of a repository
+    pub async fn cache_last_commit(&self, commit: &CachedLastCommit) -> Result<()> {
+        let conn = Connection::open(&self.db_path).await?;
+        let commit = commit.clone();
+
+        conn.call(move |conn| {
+            conn.execute(
+                r#"
+                INSERT OR REPLACE INTO last_commits
+                (repository_name, subject, committed_at, last_updated)
+                VALUES (?1, ?2, ?3, ?4)
+                "#,
+                params![
+                    commit.repository_name,
+                    commit.subject,
+                    commit.committed_at.to_rfc3339(),
+                    commit.last_updated.to_rfc3339()
+                ],
+            )?;
+            Ok(())
+        })
+        .await
+        .context("Failed to cache last commit")?;
+
+        Ok(())
+    }
+
+    /// Every cached last commit, however old, keyed by repository name.
+    /// Callers refresh entries older than [`Self::is_expired`] themselves.
+    pub async fn get_last_commits(&self) -> Result<HashMap<String, CachedLastCommit>> {
+        let conn = Connection::open(&self.db_path).await?;
+
+        let commits = conn
+            .call(move |conn| {
+                let mut stmt = conn.prepare(
+                    "SELECT repository_name, subject, committed_at, last_updated FROM last_commits",
+                )?;
+                let rows = stmt.query_map([], |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, String>(2)?,
+                        row.get::<_, String>(3)?,
+                    ))
+                })?;
+
+                let mut commits = HashMap::new();
+                for row in rows {
+                    let (repository_name, subject, committed_at, last_updated) = row?;
+                    let (Some(committed_at), Some(last_updated)) = (
+                        parse_time(Some(committed_at)),
+                        parse_time(Some(last_updated)),
+                    ) else {
+                        continue;
+                    };
+                    commits.insert(
+                        repository_name.clone(),
+                        CachedLastCommit {
+                            repository_name,
+                            subject,
+                            committed_at,
+                            last_updated,
+                        },
+                    );
+                }
+                Ok(commits)
+            })
+            .await
+            .context("Failed to get cached last commits")?;
+
+        Ok(commits)
+    }
+
+    /// Whether an entry cached at `last_updated` is past the TTL
+    pub fn is_expired(&self, last_updated: DateTime<Utc>) -> bool {
+        Utc::now().signed_duration_since(last_updated) > Duration::minutes(self.cache_ttl_minutes)
+    }
+
+    /// Store task metadata for a worktree branch; empty metadata removes it
+    pub async fn set_worktree_metadata(
+        &self,
+        repo: &str,
+        branch: &str,
+        metadata: &WorktreeMetadata,
+    ) -> Result<()> {
+        let conn = Connection::open(&self.db_path).await?;
+        let repo = repo.to_string();
+        let branch = branch.to_string();
+        let metadata = metadata.clone();
+
+        conn.call(move |conn| {
+            if metadata.is_empty() {
+                conn.execute(
+                    "DELETE FROM worktree_metadata WHERE repo = ?1 AND branch = ?2",
+                    params![repo, branch],
+                )?;
+            } else {
+                conn.execute(
+                    r#"
+                    INSERT OR REPLACE INTO worktree_metadata
+                        (repo, branch, description, link, updated_at, base_branch, base_commit,
+                         expires_at, task_id, created_at, pull_request)
+                    VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+                    "#,
+                    params![
+                        repo,
+                        branch,
+                        metadata.description,
+                        metadata.link,
+                        Utc::now().to_rfc3339(),
+                        metadata.base_branch,
+                        metadata.base_commit,
+                        metadata.expires_at.map(|deadline| deadline.to_rfc3339()),
+                        metadata.task_id,
+                        metadata.created_at.map(|created| created.to_rfc3339()),
+                        metadata.pull_request
+                    ],
+                )?;
+            }
+            Ok(())
+        })
+        .await
+        .context("Failed to store worktree metadata")?;
+
+        Ok(())
+    }
+
+    /// Task metadata for every annotated branch of `repo`
+    pub async fn get_worktree_metadata(
+        &self,
+        repo: &str,
+    ) -> Result<HashMap<String, WorktreeMetadata>> {
+        let conn = Connection::open(&self.db_path).await?;
+        let repo = repo.to_string();
+
+        let metadata = conn
+            .call(move |conn| {
+                let mut stmt = conn.prepare(
+                    r#"
+                    SELECT branch, description, link, base_branch, base_commit, expires_at,
+                           task_id, created_at, pull_request
+                    FROM worktree_metadata WHERE repo = ?1
+                    "#,
+                )?;
+                let rows = stmt.query_map(params![repo], |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        WorktreeMetadata {
+                            description: row.get(1)?,
+                            link: row.get(2)?,
+                            base_branch: row.get(3)?,
+                            base_commit: row.get(4)?,
+                            expires_at: parse_time(row.get(5)?),
+                            task_id: row.get(6)?,
+                            created_at: parse_time(row.get(7)?),
+                            pull_request: row.get(8)?,
+                        },
+                    ))
+                })?;
+                Ok(rows.collect::<Result<HashMap<_, _>, _>>()?)
+            })
+            .await
+            .context("Failed to read worktree metadata")?;
+
+        Ok(metadata)
+    }
+
+    /// Number of annotated worktree branches per repository
+    pub async fn count_worktree_metadata(&self) -> Result<HashMap<String, usize>> {
+        let conn = Connection::open(&self.db_path).await?;
+
+        let counts = conn
+            .call(|conn| {
+                let mut stmt =
+                    conn.prepare("SELECT repo, COUNT(*) FROM worktree_metadata GROUP BY repo")?;
+                let rows = stmt.query_map([], |row| {
+                    Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)? as usize))
+                })?;
+                Ok(rows.collect::<Result<HashMap<_, _>, _>>()?)
+            })
+            .await
+            .context("Failed to count worktree metadata")?;
+
+        Ok(counts)
+    }
+
     /// Get all cached git statuses (for batch operations)
     pub async fn get_all_git_statuses(&self) -> Result<Vec<CachedGitStatus>> {
+        self.read_git_statuses(Some(self.cache_ttl_minutes)).await
+    }
+
+    /// Get every cached git status, however old, for when the repositories
+    /// themselves can't be read
+    pub async fn get_last_known_git_statuses(&self) -> Result<Vec<CachedGitStatus>> {
+        self.read_git_statuses(None).await
+    }
+
+    async fn read_git_statuses(&self, ttl_minutes: Option<i64>) -> Result<Vec<CachedGitStatus>> {
         let conn = Connection::open(&self.db_path).await?;
-        let ttl_minutes = self.cache_ttl_minutes;
 
         let statuses = conn
             .call(move |conn| {
@@ -265,7 +603,7 @@ impl GitStatusCache {
                     let status = status_result?;
                     // Only include valid (within TTL) cached statuses
                     let age = now.signed_duration_since(status.last_updated);
-                    if age <= Duration::minutes(ttl_minutes) {
+                    if ttl_minutes.is_none_or(|ttl| age <= Duration::minutes(ttl)) {
                         statuses.push(status);
                     }
                 }
@@ -311,6 +649,10 @@ impl GitStatusCache {
                 "DELETE FROM git_status WHERE repository_name = ?1",
                 params![repo_name],
             )?;
+            conn.execute(
+                "DELETE FROM last_commits WHERE repository_name = ?1",
+                params![repo_name],
+            )?;
             Ok(())
         })
         .await
@@ -363,6 +705,13 @@ pub struct GitCacheStats {
     pub ttl_minutes: usize,
 }
 
+/// An RFC 3339 timestamp stored in a TEXT column
+fn parse_time(value: Option<String>) -> Option<DateTime<Utc>> {
+    value
+        .and_then(|value| DateTime::parse_from_rfc3339(&value).ok())
+        .map(|time| time.with_timezone(&Utc))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -405,6 +754,101 @@ mod tests {
         assert!(!cached.clean);
     }
 
+    #[tokio::test]
+    async fn test_pull_request_cache_expires() {
+        let temp_dir = tempdir().unwrap();
+        let db_path = temp_dir.path().join("test_git_status.db");
+
+        let cache = GitStatusCache::new(&db_path);
+        cache.initialize().await.unwrap();
+        assert!(cache.get_pull_requests("all").await.unwrap().is_none());
+
+        cache.cache_pull_requests("all", "{}").await.unwrap();
+        assert_eq!(
+            cache.get_pull_requests("all").await.unwrap().as_deref(),
+            Some("{}")
+        );
+        assert!(cache.get_pull_requests("mine").await.unwrap().is_none());
+
+        let expired = GitStatusCache::with_ttl(&db_path, -1);
+        assert!(expired.get_pull_requests("all").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_last_commits() {
+        let temp_dir = tempdir().unwrap();
+        let cache = GitStatusCache::new(temp_dir.path().join("test_git_status.db"));
+        cache.initialize().await.unwrap();
+
+        let committed_at = Utc::now() - Duration::hours(3);
+        cache
+            .cache_last_commit(&CachedLastCommit {
+                repository_name: "api".to_string(),
+                subject: "Fix login".to_string(),
+                committed_at,
+                last_updated: Utc::now() - Duration::minutes(10),
+            })
+            .await
+            .unwrap();
+
+        let commits = cache.get_last_commits().await.unwrap();
+        let commit = &commits["api"];
+        assert_eq!(commit.subject, "Fix login");
+        assert_eq!(commit.committed_at.timestamp(), committed_at.timestamp());
+        // Old entries are still returned, for the caller to refresh
+        assert!(cache.is_expired(commit.last_updated));
+
+        cache.invalidate_repository("api").await.unwrap();
+        assert!(cache.get_last_commits().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_worktree_metadata() {
+        let temp_dir = tempdir().unwrap();
+        let cache = GitStatusCache::new(temp_dir.path().join("test_git_status.db"));
+        cache.initialize().await.unwrap();
+
+        // Initializing an existing database is a no-op
+        cache.initialize().await.unwrap();
+
+        let metadata = WorktreeMetadata {
+            description: Some("Fix OAuth refresh".to_string()),
+            link: None,
+            base_branch: Some("main".to_string()),
+            base_commit: Some("3848f9d0c1".to_string()),
+            expires_at: parse_time(Some("2026-03-01T12:00:00Z".to_string())),
+            task_id: Some("fix-auth".to_string()),
+            created_at: parse_time(Some("2026-02-20T09:30:00Z".to_string())),
+            pull_request: Some("https://github.com/acme/api/pull/7".to_string()),
+        };
+        cache
+            .set_worktree_metadata("/repo", "vibe-ws/fix-auth", &metadata)
+            .await
+            .unwrap();
+        cache
+            .set_worktree_metadata("/other", "vibe-ws/fix-auth", &metadata)
+            .await
+            .unwrap();
+
+        let stored = cache.get_worktree_metadata("/repo").await.unwrap();
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored["vibe-ws/fix-auth"], metadata);
+
+        cache
+            .set_worktree_metadata("/repo", "vibe-ws/fix-auth", &WorktreeMetadata::default())
+            .await
+            .unwrap();
+        assert!(cache
+            .get_worktree_metadata("/repo")
+            .await
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            cache.get_worktree_metadata("/other").await.unwrap().len(),
+            1
+        );
+    }
+
     #[tokio::test]
     async fn test_cache_ttl_expiration() {
         let temp_dir = tempdir().unwrap();
@@ -433,5 +877,11 @@ mod tests {
         // Should return None because the cache entry is expired
         let cached = cache.get_git_status("test-repo").await.unwrap();
         assert!(cached.is_none());
+        assert!(cache.get_all_git_statuses().await.unwrap().is_empty());
+
+        // ...but it is still the last known status
+        let last_known = cache.get_last_known_git_statuses().await.unwrap();
+        assert_eq!(last_known.len(), 1);
+        assert_eq!(last_known[0].repository_name, "test-repo");
     }
 }
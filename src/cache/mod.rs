@@ -1,8 +1,12 @@
 pub mod git_status_cache;
 pub mod repository_cache;
+pub mod snapshot;
 
-pub use git_status_cache::GitStatusCache;
-pub use repository_cache::RepositoryCache;
+pub use git_status_cache::{CachedGitStatus, CachedLastCommit, GitStatusCache};
+pub use repository_cache::{
+    AppLaunch, CachedActivity, CachedContributors, CachedDiskUsage, CachedProjectTypes,
+    CachedRepoListing, CachedRepository, RepositoryCache,
+};
 
 use anyhow::Result;
 use std::path::Path;
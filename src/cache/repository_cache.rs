@@ -1,6 +1,7 @@
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::PathBuf;
 use tokio_rusqlite::{params, Connection};
 
@@ -13,6 +14,28 @@ pub struct CachedRepository {
     pub last_updated: DateTime<Utc>,
     pub path_exists: bool,
     pub is_git_repo: bool,
+    /// Whether the checkout's `.gitattributes` uses Git LFS
+    #[serde(default)]
+    pub uses_lfs: bool,
+}
+
+impl CachedRepository {
+    /// Current state of a configured repository, read from disk
+    pub async fn from_config(
+        repo: &crate::workspace::Repository,
+        workspace_root: &std::path::Path,
+    ) -> Self {
+        let full_path = crate::workspace::config::resolve_repo_path(workspace_root, &repo.path);
+        Self {
+            name: repo.name.clone(),
+            path: repo.path.clone(),
+            configured_apps: repo.apps.keys().cloned().collect(),
+            last_updated: Utc::now(),
+            path_exists: full_path.exists(),
+            is_git_repo: full_path.join(".git").exists(),
+            uses_lfs: crate::git::lfs::uses_lfs(&full_path).await,
+        }
+    }
 }
 
 /// Fast SQLite-based cache for repository metadata
@@ -35,16 +58,15 @@ impl RepositoryCache {
             .context("Failed to open repository cache database")?;
 
         conn.call(move |conn| {
-            // Check if we need to migrate from old schema
+            // Check if we need to migrate from the old schema, whose
+            // `exists` column is now `path_exists`
             let has_old_schema = conn
-                .prepare("SELECT sql FROM sqlite_master WHERE type='table' AND name='repositories'")
+                .prepare("SELECT name FROM pragma_table_info('repositories')")
                 .and_then(|mut stmt| {
-                    stmt.query_row([], |row| {
-                        let sql: String = row.get(0)?;
-                        Ok(sql.contains("exists INTEGER"))
-                    })
+                    stmt.query_map([], |row| row.get::<_, String>(0))?
+                        .collect::<Result<Vec<_>, _>>()
                 })
-                .unwrap_or(false);
+                .is_ok_and(|columns| columns.iter().any(|column| column == "exists"));
 
             if has_old_schema {
                 // Drop old table and recreate with new schema
@@ -59,12 +81,22 @@ impl RepositoryCache {
                     configured_apps TEXT NOT NULL, -- JSON array of app names
                     last_updated TEXT NOT NULL,    -- ISO 8601 datetime
                     path_exists INTEGER NOT NULL,       -- boolean as integer
-                    is_git_repo INTEGER NOT NULL   -- boolean as integer
+                    is_git_repo INTEGER NOT NULL,  -- boolean as integer
+                    uses_lfs INTEGER               -- boolean as integer, NULL when unknown
                 )
                 "#,
                 [],
             )?;
 
+            // Databases created before LFS detection lack the uses_lfs column
+            let columns = conn
+                .prepare("SELECT name FROM pragma_table_info('repositories')")?
+                .query_map([], |row| row.get::<_, String>(0))?
+                .collect::<Result<Vec<_>, _>>()?;
+            if !columns.iter().any(|column| column == "uses_lfs") {
+                conn.execute("ALTER TABLE repositories ADD COLUMN uses_lfs INTEGER", [])?;
+            }
+
             // Create index for faster lookups
             conn.execute(
                 "CREATE INDEX IF NOT EXISTS idx_repositories_path ON repositories(path)",
@@ -76,6 +108,107 @@ impl RepositoryCache {
                 [],
             )?;
 
+            conn.execute(
+                r#"
+                CREATE TABLE IF NOT EXISTS disk_usage (
+                    name TEXT PRIMARY KEY,
+                    fingerprint TEXT NOT NULL,  -- changes when the checkout's mtimes do
+                    usage TEXT NOT NULL,        -- JSON disk usage report
+                    computed_at TEXT NOT NULL   -- ISO 8601 datetime
+                )
+                "#,
+                [],
+            )?;
+
+            conn.execute(
+                r#"
+                CREATE TABLE IF NOT EXISTS activity (
+                    name TEXT PRIMARY KEY,
+                    fingerprint TEXT NOT NULL,  -- branch tips, window, and author filter
+                    activity TEXT NOT NULL,     -- JSON activity summary
+                    computed_at TEXT NOT NULL   -- ISO 8601 datetime
+                )
+                "#,
+                [],
+            )?;
+
+            conn.execute(
+                r#"
+                CREATE TABLE IF NOT EXISTS contributors (
+                    name TEXT PRIMARY KEY,
+                    fingerprint TEXT NOT NULL,  -- HEAD and the start of the window
+                    authors TEXT NOT NULL,      -- JSON array of authors and their commits
+                    computed_at TEXT NOT NULL   -- ISO 8601 datetime
+                )
+                "#,
+                [],
+            )?;
+
+            conn.execute(
+                r#"
+                CREATE TABLE IF NOT EXISTS license_files (
+                    hash TEXT PRIMARY KEY,      -- matcher version and sha256 of the file
+                    license TEXT                -- SPDX identifier, NULL when unrecognized
+                )
+                "#,
+                [],
+            )?;
+
+            conn.execute(
+                r#"
+                CREATE TABLE IF NOT EXISTS app_launches (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    repo TEXT NOT NULL,
+                    app TEXT NOT NULL,
+                    source TEXT NOT NULL,       -- cli, menu, mcp, or uri
+                    launched_at TEXT NOT NULL   -- ISO 8601 datetime
+                )
+                "#,
+                [],
+            )?;
+
+            conn.execute(
+                "CREATE INDEX IF NOT EXISTS idx_app_launches_launched_at ON app_launches(launched_at)",
+                [],
+            )?;
+
+            conn.execute(
+                r#"
+                CREATE TABLE IF NOT EXISTS repo_listings (
+                    owner TEXT PRIMARY KEY,     -- lowercased GitHub user or organization
+                    kind TEXT NOT NULL,         -- org or user
+                    etag TEXT,                  -- ETag of the last validation probe
+                    updated_at TEXT,            -- newest updated_at among the listed repos
+                    repositories TEXT NOT NULL, -- JSON array of repositories
+                    fetched_at TEXT NOT NULL    -- ISO 8601 datetime
+                )
+                "#,
+                [],
+            )?;
+
+            conn.execute(
+                r#"
+                CREATE TABLE IF NOT EXISTS protected_branches (
+                    github_repo TEXT PRIMARY KEY, -- lowercased owner/name
+                    branches TEXT NOT NULL,       -- JSON array of branch names
+                    fetched_at TEXT NOT NULL      -- ISO 8601 datetime
+                )
+                "#,
+                [],
+            )?;
+
+            conn.execute(
+                r#"
+                CREATE TABLE IF NOT EXISTS project_types (
+                    name TEXT PRIMARY KEY,
+                    fingerprint TEXT NOT NULL,  -- changes when a manifest's mtime does
+                    types TEXT NOT NULL,        -- JSON array of project types
+                    detected_at TEXT NOT NULL   -- ISO 8601 datetime
+                )
+                "#,
+                [],
+            )?;
+
             Ok(())
         })
         .await
@@ -97,8 +230,8 @@ impl RepositoryCache {
             conn.execute(
                 r#"
                 INSERT OR REPLACE INTO repositories 
-                (name, path, configured_apps, last_updated, path_exists, is_git_repo)
-                VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+                (name, path, configured_apps, last_updated, path_exists, is_git_repo, uses_lfs)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
                 "#,
                 params![
                     repo.name,
@@ -106,7 +239,8 @@ impl RepositoryCache {
                     apps_json,
                     last_updated,
                     repo.path_exists as i32,
-                    repo.is_git_repo as i32
+                    repo.is_git_repo as i32,
+                    repo.uses_lfs as i32
                 ],
             )?;
 
@@ -126,7 +260,7 @@ impl RepositoryCache {
         let result = conn
             .call(move |conn| {
                 let mut stmt = conn.prepare(
-                    "SELECT name, path, configured_apps, last_updated, path_exists, is_git_repo 
+                    "SELECT name, path, configured_apps, last_updated, path_exists, is_git_repo, uses_lfs 
                      FROM repositories WHERE name = ?1",
                 )?;
 
@@ -159,6 +293,7 @@ impl RepositoryCache {
                         last_updated,
                         path_exists: row.get::<_, i32>(4)? != 0,
                         is_git_repo: row.get::<_, i32>(5)? != 0,
+                        uses_lfs: row.get::<_, Option<i32>>(6)?.unwrap_or(0) != 0,
                     })
                 });
 
@@ -182,7 +317,7 @@ impl RepositoryCache {
             .call(move |conn| {
                 let mut stmt = conn.prepare(
                     r#"
-                    SELECT name, path, configured_apps, last_updated, path_exists, is_git_repo 
+                    SELECT name, path, configured_apps, last_updated, path_exists, is_git_repo, uses_lfs 
                     FROM repositories 
                     WHERE json_array_length(configured_apps) > 0 
                     AND path_exists = 1
@@ -219,6 +354,7 @@ impl RepositoryCache {
                         last_updated,
                         path_exists: row.get::<_, i32>(4)? != 0,
                         is_git_repo: row.get::<_, i32>(5)? != 0,
+                        uses_lfs: row.get::<_, Option<i32>>(6)?.unwrap_or(0) != 0,
                     })
                 })?;
 
@@ -235,6 +371,46 @@ impl RepositoryCache {
         Ok(repositories)
     }
 
+    /// Recorded Git LFS use by repository name, for repositories where it is known
+    pub async fn get_lfs_flags(&self) -> Result<HashMap<String, bool>> {
+        let conn = Connection::open(&self.db_path).await?;
+
+        let flags = conn
+            .call(move |conn| {
+                let mut stmt = conn.prepare(
+                    "SELECT name, uses_lfs FROM repositories WHERE uses_lfs IS NOT NULL",
+                )?;
+                let flags = stmt
+                    .query_map([], |row| {
+                        Ok((row.get::<_, String>(0)?, row.get::<_, i32>(1)? != 0))
+                    })?
+                    .collect::<Result<HashMap<_, _>, _>>()?;
+                Ok(flags)
+            })
+            .await
+            .context("Failed to get cached LFS flags")?;
+
+        Ok(flags)
+    }
+
+    /// Get the names of all cached repositories, for shell completion
+    pub async fn get_repository_names(&self) -> Result<Vec<String>> {
+        let conn = Connection::open(&self.db_path).await?;
+
+        let names = conn
+            .call(move |conn| {
+                let mut stmt = conn.prepare("SELECT name FROM repositories ORDER BY name")?;
+                let names = stmt
+                    .query_map([], |row| row.get::<_, String>(0))?
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(names)
+            })
+            .await
+            .context("Failed to get cached repository names")?;
+
+        Ok(names)
+    }
+
     /// Update cache for all repositories in workspace config
     pub async fn refresh_from_config(
         &self,
@@ -242,16 +418,7 @@ impl RepositoryCache {
         workspace_root: &std::path::Path,
     ) -> Result<()> {
         for repo in repositories {
-            let full_path = workspace_root.join(&repo.path);
-            let cached_repo = CachedRepository {
-                name: repo.name.clone(),
-                path: repo.path.clone(),
-                configured_apps: repo.apps.keys().cloned().collect(),
-                last_updated: Utc::now(),
-                path_exists: full_path.exists(),
-                is_git_repo: full_path.join(".git").exists(),
-            };
-
+            let cached_repo = CachedRepository::from_config(repo, workspace_root).await;
             self.cache_repository(&cached_repo).await?;
         }
 
@@ -277,6 +444,22 @@ impl RepositoryCache {
                 "DELETE FROM repositories WHERE name NOT IN (SELECT name FROM current_repos)",
                 [],
             )?;
+            conn.execute(
+                "DELETE FROM disk_usage WHERE name NOT IN (SELECT name FROM current_repos)",
+                [],
+            )?;
+            conn.execute(
+                "DELETE FROM activity WHERE name NOT IN (SELECT name FROM current_repos)",
+                [],
+            )?;
+            conn.execute(
+                "DELETE FROM contributors WHERE name NOT IN (SELECT name FROM current_repos)",
+                [],
+            )?;
+            conn.execute(
+                "DELETE FROM project_types WHERE name NOT IN (SELECT name FROM current_repos)",
+                [],
+            )?;
 
             Ok(())
         })
@@ -286,6 +469,538 @@ impl RepositoryCache {
         Ok(())
     }
 
+    /// Cached disk usage for a repository, if any
+    pub async fn get_disk_usage(&self, name: &str) -> Result<Option<CachedDiskUsage>> {
+        let conn = Connection::open(&self.db_path).await?;
+        let name = name.to_string();
+
+        let result = conn
+            .call(move |conn| {
+                let mut stmt = conn.prepare(
+                    "SELECT name, fingerprint, usage, computed_at FROM disk_usage WHERE name = ?1",
+                )?;
+
+                let usage = stmt.query_row(params![name], |row| {
+                    let computed_at_str: String = row.get(3)?;
+                    let computed_at = DateTime::parse_from_rfc3339(&computed_at_str)
+                        .map_err(|e| {
+                            rusqlite::Error::FromSqlConversionFailure(
+                                3,
+                                rusqlite::types::Type::Text,
+                                Box::new(e),
+                            )
+                        })?
+                        .with_timezone(&Utc);
+
+                    Ok(CachedDiskUsage {
+                        name: row.get(0)?,
+                        fingerprint: row.get(1)?,
+                        usage_json: row.get(2)?,
+                        computed_at,
+                    })
+                });
+
+                match usage {
+                    Ok(usage) => Ok(Some(usage)),
+                    Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+                    Err(e) => Err(tokio_rusqlite::Error::Rusqlite(e)),
+                }
+            })
+            .await
+            .context("Failed to get cached disk usage")?;
+
+        Ok(result)
+    }
+
+    /// Cache disk usage for a repository, replacing any previous entry
+    pub async fn cache_disk_usage(&self, usage: &CachedDiskUsage) -> Result<()> {
+        let conn = Connection::open(&self.db_path).await?;
+        let usage = usage.clone();
+
+        conn.call(move |conn| {
+            conn.execute(
+                r#"
+                INSERT OR REPLACE INTO disk_usage (name, fingerprint, usage, computed_at)
+                VALUES (?1, ?2, ?3, ?4)
+                "#,
+                params![
+                    usage.name,
+                    usage.fingerprint,
+                    usage.usage_json,
+                    usage.computed_at.to_rfc3339()
+                ],
+            )?;
+            Ok(())
+        })
+        .await
+        .context("Failed to cache disk usage")?;
+
+        Ok(())
+    }
+
+    /// Cached project types of every repository, by name
+    pub async fn get_project_types(&self) -> Result<HashMap<String, CachedProjectTypes>> {
+        let conn = Connection::open(&self.db_path).await?;
+
+        let entries = conn
+            .call(move |conn| {
+                let mut stmt = conn
+                    .prepare("SELECT name, fingerprint, types, detected_at FROM project_types")?;
+                let entries = stmt
+                    .query_map([], |row| {
+                        let types_json: String = row.get(2)?;
+                        let project_types = serde_json::from_str(&types_json).map_err(|e| {
+                            rusqlite::Error::FromSqlConversionFailure(
+                                2,
+                                rusqlite::types::Type::Text,
+                                Box::new(e),
+                            )
+                        })?;
+                        let detected_at_str: String = row.get(3)?;
+                        let detected_at = DateTime::parse_from_rfc3339(&detected_at_str)
+                            .map_err(|e| {
+                                rusqlite::Error::FromSqlConversionFailure(
+                                    3,
+                                    rusqlite::types::Type::Text,
+                                    Box::new(e),
+                                )
+                            })?
+                            .with_timezone(&Utc);
+
+                        Ok(CachedProjectTypes {
+                            name: row.get(0)?,
+                            fingerprint: row.get(1)?,
+                            project_types,
+                            detected_at,
+                        })
+                    })?
+                    .map(|entry| entry.map(|entry| (entry.name.clone(), entry)))
+                    .collect::<Result<HashMap<_, _>, _>>()?;
+                Ok(entries)
+            })
+            .await
+            .context("Failed to get cached project types")?;
+
+        Ok(entries)
+    }
+
+    /// Cache the project types of a repository, replacing any previous entry
+    pub async fn cache_project_types(&self, entry: &CachedProjectTypes) -> Result<()> {
+        let conn = Connection::open(&self.db_path).await?;
+        let entry = entry.clone();
+        let types_json = serde_json::to_string(&entry.project_types)?;
+
+        conn.call(move |conn| {
+            conn.execute(
+                r#"
+                INSERT OR REPLACE INTO project_types (name, fingerprint, types, detected_at)
+                VALUES (?1, ?2, ?3, ?4)
+                "#,
+                params![
+                    entry.name,
+                    entry.fingerprint,
+                    types_json,
+                    entry.detected_at.to_rfc3339()
+                ],
+            )?;
+            Ok(())
+        })
+        .await
+        .context("Failed to cache project types")?;
+
+        Ok(())
+    }
+
+    /// Cached activity summary for a repository, if any
+    pub async fn get_activity(&self, name: &str) -> Result<Option<CachedActivity>> {
+        let conn = Connection::open(&self.db_path).await?;
+        let name = name.to_string();
+
+        let result = conn
+            .call(move |conn| {
+                let activity = conn.query_row(
+                    "SELECT name, fingerprint, activity, computed_at FROM activity WHERE name = ?1",
+                    params![name],
+                    |row| {
+                        let computed_at_str: String = row.get(3)?;
+                        let computed_at = DateTime::parse_from_rfc3339(&computed_at_str)
+                            .map_err(|e| {
+                                rusqlite::Error::FromSqlConversionFailure(
+                                    3,
+                                    rusqlite::types::Type::Text,
+                                    Box::new(e),
+                                )
+                            })?
+                            .with_timezone(&Utc);
+
+                        Ok(CachedActivity {
+                            name: row.get(0)?,
+                            fingerprint: row.get(1)?,
+                            activity_json: row.get(2)?,
+                            computed_at,
+                        })
+                    },
+                );
+
+                match activity {
+                    Ok(activity) => Ok(Some(activity)),
+                    Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+                    Err(e) => Err(tokio_rusqlite::Error::Rusqlite(e)),
+                }
+            })
+            .await
+            .context("Failed to get cached activity")?;
+
+        Ok(result)
+    }
+
+    /// Cache the activity summary for a repository, replacing any previous entry
+    pub async fn cache_activity(&self, activity: &CachedActivity) -> Result<()> {
+        let conn = Connection::open(&self.db_path).await?;
+        let activity = activity.clone();
+
+        conn.call(move |conn| {
+            conn.execute(
+                r#"
+                INSERT OR REPLACE INTO activity (name, fingerprint, activity, computed_at)
+                VALUES (?1, ?2, ?3, ?4)
+                "#,
+                params![
+                    activity.name,
+                    activity.fingerprint,
+                    activity.activity_json,
+                    activity.computed_at.to_rfc3339()
+                ],
+            )?;
+            Ok(())
+        })
+        .await
+        .context("Failed to cache activity")?;
+
+        Ok(())
+    }
+
+    /// Cached commit authors for a repository, if any
+    pub async fn get_contributors(&self, name: &str) -> Result<Option<CachedContributors>> {
+        let conn = Connection::open(&self.db_path).await?;
+        let name = name.to_string();
+
+        let result = conn
+            .call(move |conn| {
+                let contributors = conn.query_row(
+                    "SELECT name, fingerprint, authors, computed_at FROM contributors WHERE name = ?1",
+                    params![name],
+                    |row| {
+                        let computed_at_str: String = row.get(3)?;
+                        let computed_at = DateTime::parse_from_rfc3339(&computed_at_str)
+                            .map_err(|e| {
+                                rusqlite::Error::FromSqlConversionFailure(
+                                    3,
+                                    rusqlite::types::Type::Text,
+                                    Box::new(e),
+                                )
+                            })?
+                            .with_timezone(&Utc);
+
+                        Ok(CachedContributors {
+                            name: row.get(0)?,
+                            fingerprint: row.get(1)?,
+                            authors_json: row.get(2)?,
+                            computed_at,
+                        })
+                    },
+                );
+
+                match contributors {
+                    Ok(contributors) => Ok(Some(contributors)),
+                    Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+                    Err(e) => Err(tokio_rusqlite::Error::Rusqlite(e)),
+                }
+            })
+            .await
+            .context("Failed to get cached contributors")?;
+
+        Ok(result)
+    }
+
+    /// Cache the commit authors of a repository, replacing any previous entry
+    pub async fn cache_contributors(&self, contributors: &CachedContributors) -> Result<()> {
+        let conn = Connection::open(&self.db_path).await?;
+        let contributors = contributors.clone();
+
+        conn.call(move |conn| {
+            conn.execute(
+                r#"
+                INSERT OR REPLACE INTO contributors (name, fingerprint, authors, computed_at)
+                VALUES (?1, ?2, ?3, ?4)
+                "#,
+                params![
+                    contributors.name,
+                    contributors.fingerprint,
+                    contributors.authors_json,
+                    contributors.computed_at.to_rfc3339()
+                ],
+            )?;
+            Ok(())
+        })
+        .await
+        .context("Failed to cache contributors")?;
+
+        Ok(())
+    }
+
+    /// Cached repository listing for a GitHub user or organization, if any
+    pub async fn get_repo_listing(&self, owner: &str) -> Result<Option<CachedRepoListing>> {
+        let conn = Connection::open(&self.db_path).await?;
+        let owner = owner.to_lowercase();
+
+        let result = conn
+            .call(move |conn| {
+                let listing = conn.query_row(
+                    "SELECT owner, kind, etag, updated_at, repositories, fetched_at \
+                     FROM repo_listings WHERE owner = ?1",
+                    params![owner],
+                    |row| {
+                        let fetched_at_str: String = row.get(5)?;
+                        let fetched_at = DateTime::parse_from_rfc3339(&fetched_at_str)
+                            .map_err(|e| {
+                                rusqlite::Error::FromSqlConversionFailure(
+                                    5,
+                                    rusqlite::types::Type::Text,
+                                    Box::new(e),
+                                )
+                            })?
+                            .with_timezone(&Utc);
+
+                        Ok(CachedRepoListing {
+                            owner: row.get(0)?,
+                            kind: row.get(1)?,
+                            etag: row.get(2)?,
+                            updated_at: row.get(3)?,
+                            repositories_json: row.get(4)?,
+                            fetched_at,
+                        })
+                    },
+                );
+
+                match listing {
+                    Ok(listing) => Ok(Some(listing)),
+                    Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+                    Err(e) => Err(tokio_rusqlite::Error::Rusqlite(e)),
+                }
+            })
+            .await
+            .context("Failed to get cached repository listing")?;
+
+        Ok(result)
+    }
+
+    /// Cache the repository listing for a user or organization, replacing any
+    /// previous entry
+    pub async fn cache_repo_listing(&self, listing: &CachedRepoListing) -> Result<()> {
+        let conn = Connection::open(&self.db_path).await?;
+        let listing = listing.clone();
+
+        conn.call(move |conn| {
+            conn.execute(
+                r#"
+                INSERT OR REPLACE INTO repo_listings
+                (owner, kind, etag, updated_at, repositories, fetched_at)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+                "#,
+                params![
+                    listing.owner.to_lowercase(),
+                    listing.kind,
+                    listing.etag,
+                    listing.updated_at,
+                    listing.repositories_json,
+                    listing.fetched_at.to_rfc3339()
+                ],
+            )?;
+            Ok(())
+        })
+        .await
+        .context("Failed to cache repository listing")?;
+
+        Ok(())
+    }
+
+    /// Branches GitHub reported protected for `github_repo` (`owner/name`)
+    /// and when that was asked, if it has been
+    pub async fn get_protected_branches(
+        &self,
+        github_repo: &str,
+    ) -> Result<Option<(Vec<String>, DateTime<Utc>)>> {
+        let conn = Connection::open(&self.db_path).await?;
+        let github_repo = github_repo.to_lowercase();
+
+        let result = conn
+            .call(move |conn| {
+                let row = conn.query_row(
+                    "SELECT branches, fetched_at FROM protected_branches WHERE github_repo = ?1",
+                    params![github_repo],
+                    |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)),
+                );
+                match row {
+                    Ok(row) => Ok(Some(row)),
+                    Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+                    Err(e) => Err(tokio_rusqlite::Error::Rusqlite(e)),
+                }
+            })
+            .await
+            .context("Failed to get cached protected branches")?;
+
+        let Some((branches, fetched_at)) = result else {
+            return Ok(None);
+        };
+        let branches =
+            serde_json::from_str(&branches).context("Failed to parse cached protected branches")?;
+        let fetched_at = DateTime::parse_from_rfc3339(&fetched_at)
+            .context("Failed to parse cached protected branches timestamp")?
+            .with_timezone(&Utc);
+        Ok(Some((branches, fetched_at)))
+    }
+
+    /// Remember the branches GitHub reports protected for `github_repo`
+    pub async fn cache_protected_branches(
+        &self,
+        github_repo: &str,
+        branches: &[String],
+        fetched_at: DateTime<Utc>,
+    ) -> Result<()> {
+        let conn = Connection::open(&self.db_path).await?;
+        let github_repo = github_repo.to_lowercase();
+        let branches = serde_json::to_string(branches)?;
+
+        conn.call(move |conn| {
+            conn.execute(
+                "INSERT OR REPLACE INTO protected_branches (github_repo, branches, fetched_at) \
+                 VALUES (?1, ?2, ?3)",
+                params![github_repo, branches, fetched_at.to_rfc3339()],
+            )?;
+            Ok(())
+        })
+        .await
+        .context("Failed to cache protected branches")?;
+
+        Ok(())
+    }
+
+    /// License identified for a license file with this hash. `Some(None)`
+    /// means the file was read before and matched no known license.
+    pub async fn get_file_license(&self, hash: &str) -> Result<Option<Option<String>>> {
+        let conn = Connection::open(&self.db_path).await?;
+        let hash = hash.to_string();
+
+        let result = conn
+            .call(move |conn| {
+                let license = conn.query_row(
+                    "SELECT license FROM license_files WHERE hash = ?1",
+                    params![hash],
+                    |row| row.get::<_, Option<String>>(0),
+                );
+                match license {
+                    Ok(license) => Ok(Some(license)),
+                    Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+                    Err(e) => Err(tokio_rusqlite::Error::Rusqlite(e)),
+                }
+            })
+            .await
+            .context("Failed to get cached file license")?;
+
+        Ok(result)
+    }
+
+    /// Remember the license identified for a license file with this hash
+    pub async fn cache_file_license(&self, hash: &str, license: Option<&str>) -> Result<()> {
+        let conn = Connection::open(&self.db_path).await?;
+        let hash = hash.to_string();
+        let license = license.map(str::to_string);
+
+        conn.call(move |conn| {
+            conn.execute(
+                "INSERT OR REPLACE INTO license_files (hash, license) VALUES (?1, ?2)",
+                params![hash, license],
+            )?;
+            Ok(())
+        })
+        .await
+        .context("Failed to cache file license")?;
+
+        Ok(())
+    }
+
+    /// Record a successful app launch
+    pub async fn record_app_launch(&self, launch: &AppLaunch) -> Result<()> {
+        let conn = Connection::open(&self.db_path).await?;
+        let launch = launch.clone();
+
+        conn.call(move |conn| {
+            conn.execute(
+                r#"
+                INSERT INTO app_launches (repo, app, source, launched_at)
+                VALUES (?1, ?2, ?3, ?4)
+                "#,
+                params![
+                    launch.repo,
+                    launch.app,
+                    launch.source,
+                    launch.launched_at.to_rfc3339()
+                ],
+            )?;
+            Ok(())
+        })
+        .await
+        .context("Failed to record app launch")?;
+
+        Ok(())
+    }
+
+    /// App launches at or after `since` (all launches when `None`), oldest first
+    pub async fn get_app_launches(&self, since: Option<DateTime<Utc>>) -> Result<Vec<AppLaunch>> {
+        let conn = Connection::open(&self.db_path).await?;
+        // RFC 3339 timestamps in UTC sort lexicographically
+        let since = since.map(|since| since.to_rfc3339()).unwrap_or_default();
+
+        let launches = conn
+            .call(move |conn| {
+                let mut stmt = conn.prepare(
+                    r#"
+                    SELECT repo, app, source, launched_at FROM app_launches
+                    WHERE launched_at >= ?1
+                    ORDER BY launched_at
+                    "#,
+                )?;
+
+                let rows = stmt.query_map(params![since], |row| {
+                    let launched_at_str: String = row.get(3)?;
+                    let launched_at = DateTime::parse_from_rfc3339(&launched_at_str)
+                        .map_err(|e| {
+                            rusqlite::Error::FromSqlConversionFailure(
+                                3,
+                                rusqlite::types::Type::Text,
+                                Box::new(e),
+                            )
+                        })?
+                        .with_timezone(&Utc);
+
+                    Ok(AppLaunch {
+                        repo: row.get(0)?,
+                        app: row.get(1)?,
+                        source: row.get(2)?,
+                        launched_at,
+                    })
+                })?;
+
+                rows.collect::<Result<Vec<_>, _>>()
+                    .map_err(tokio_rusqlite::Error::Rusqlite)
+            })
+            .await
+            .context("Failed to get app launches")?;
+
+        Ok(launches)
+    }
+
     /// Get cache statistics
     pub async fn get_stats(&self) -> Result<CacheStats> {
         let conn = Connection::open(&self.db_path).await?;
@@ -323,6 +1038,67 @@ impl RepositoryCache {
     }
 }
 
+/// Disk usage measured for a repository, valid while `fingerprint` matches
+#[derive(Debug, Clone)]
+pub struct CachedDiskUsage {
+    pub name: String,
+    pub fingerprint: String,
+    pub usage_json: String,
+    pub computed_at: DateTime<Utc>,
+}
+
+/// Project types detected for a repository, valid while `fingerprint`
+/// matches
+#[derive(Debug, Clone)]
+pub struct CachedProjectTypes {
+    pub name: String,
+    pub fingerprint: String,
+    pub project_types: Vec<String>,
+    pub detected_at: DateTime<Utc>,
+}
+
+/// Activity summary for a repository, valid while `fingerprint` matches
+#[derive(Debug, Clone)]
+pub struct CachedActivity {
+    pub name: String,
+    pub fingerprint: String,
+    pub activity_json: String,
+    pub computed_at: DateTime<Utc>,
+}
+
+/// Commit authors of a repository over a window, valid while `fingerprint`
+/// matches
+#[derive(Debug, Clone)]
+pub struct CachedContributors {
+    pub name: String,
+    pub fingerprint: String,
+    pub authors_json: String,
+    pub computed_at: DateTime<Utc>,
+}
+
+/// Repositories listed for a GitHub user or organization, valid while a
+/// probe of the listing still matches `etag` or `updated_at`
+#[derive(Debug, Clone)]
+pub struct CachedRepoListing {
+    pub owner: String,
+    /// `org` or `user`
+    pub kind: String,
+    pub etag: Option<String>,
+    pub updated_at: Option<String>,
+    pub repositories_json: String,
+    pub fetched_at: DateTime<Utc>,
+}
+
+/// One successful launch of a repository in an app
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppLaunch {
+    pub repo: String,
+    pub app: String,
+    /// Where the launch came from: `cli`, `menu`, `mcp`, or `uri`
+    pub source: String,
+    pub launched_at: DateTime<Utc>,
+}
+
 /// Cache statistics for monitoring and debugging
 #[derive(Debug, Clone)]
 pub struct CacheStats {
@@ -352,6 +1128,7 @@ mod tests {
             last_updated: Utc::now(),
             path_exists: true,
             is_git_repo: true,
+            uses_lfs: true,
         };
 
         cache.cache_repository(&repo).await.unwrap();
@@ -368,5 +1145,175 @@ mod tests {
         let repos_with_apps = cache.get_repositories_with_apps().await.unwrap();
         assert_eq!(repos_with_apps.len(), 1);
         assert_eq!(repos_with_apps[0].name, "test-repo");
+
+        let flags = cache.get_lfs_flags().await.unwrap();
+        assert_eq!(flags.get("test-repo"), Some(&true));
+    }
+
+    #[tokio::test]
+    async fn test_disk_usage_cache() {
+        let temp_dir = tempdir().unwrap();
+        let cache = RepositoryCache::new(temp_dir.path().join("test_repos.db"));
+        cache.initialize().await.unwrap();
+
+        assert!(cache.get_disk_usage("api").await.unwrap().is_none());
+
+        let usage = CachedDiskUsage {
+            name: "api".to_string(),
+            fingerprint: "abc".to_string(),
+            usage_json: "{}".to_string(),
+            computed_at: Utc::now(),
+        };
+        cache.cache_disk_usage(&usage).await.unwrap();
+        let cached = cache.get_disk_usage("api").await.unwrap().unwrap();
+        assert_eq!(cached.fingerprint, "abc");
+
+        cache
+            .cleanup_stale_entries(&["web".to_string()])
+            .await
+            .unwrap();
+        assert!(cache.get_disk_usage("api").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_activity_cache() {
+        let temp_dir = tempdir().unwrap();
+        let cache = RepositoryCache::new(temp_dir.path().join("test_repos.db"));
+        cache.initialize().await.unwrap();
+
+        assert!(cache.get_activity("api").await.unwrap().is_none());
+        let activity = CachedActivity {
+            name: "api".to_string(),
+            fingerprint: "abc".to_string(),
+            activity_json: "{}".to_string(),
+            computed_at: Utc::now(),
+        };
+        cache.cache_activity(&activity).await.unwrap();
+        let cached = cache.get_activity("api").await.unwrap().unwrap();
+        assert_eq!(cached.fingerprint, "abc");
+
+        cache
+            .cleanup_stale_entries(&["web".to_string()])
+            .await
+            .unwrap();
+        assert!(cache.get_activity("api").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_contributors_cache() {
+        let temp_dir = tempdir().unwrap();
+        let cache = RepositoryCache::new(temp_dir.path().join("test_repos.db"));
+        cache.initialize().await.unwrap();
+
+        assert!(cache.get_contributors("api").await.unwrap().is_none());
+        let contributors = CachedContributors {
+            name: "api".to_string(),
+            fingerprint: "abc:2024-01-01".to_string(),
+            authors_json: "[]".to_string(),
+            computed_at: Utc::now(),
+        };
+        cache.cache_contributors(&contributors).await.unwrap();
+        let cached = cache.get_contributors("api").await.unwrap().unwrap();
+        assert_eq!(cached.fingerprint, "abc:2024-01-01");
+
+        cache
+            .cleanup_stale_entries(&["web".to_string()])
+            .await
+            .unwrap();
+        assert!(cache.get_contributors("api").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_repo_listing_cache() {
+        let temp_dir = tempdir().unwrap();
+        let cache = RepositoryCache::new(temp_dir.path().join("test_repos.db"));
+        cache.initialize().await.unwrap();
+
+        assert!(cache.get_repo_listing("acme").await.unwrap().is_none());
+        let mut listing = CachedRepoListing {
+            owner: "Acme".to_string(),
+            kind: "org".to_string(),
+            etag: None,
+            updated_at: Some("2024-01-01T00:00:00Z".to_string()),
+            repositories_json: "[]".to_string(),
+            fetched_at: Utc::now(),
+        };
+        cache.cache_repo_listing(&listing).await.unwrap();
+
+        listing.etag = Some("W/\"abc\"".to_string());
+        cache.cache_repo_listing(&listing).await.unwrap();
+        let cached = cache.get_repo_listing("ACME").await.unwrap().unwrap();
+        assert_eq!(cached.owner, "acme");
+        assert_eq!(cached.etag.as_deref(), Some("W/\"abc\""));
+        assert_eq!(cached.updated_at, listing.updated_at);
+    }
+
+    #[tokio::test]
+    async fn test_protected_branches_cache() {
+        let temp_dir = tempdir().unwrap();
+        let cache = RepositoryCache::new(temp_dir.path().join("test_repos.db"));
+        cache.initialize().await.unwrap();
+
+        assert!(cache
+            .get_protected_branches("acme/api")
+            .await
+            .unwrap()
+            .is_none());
+        let fetched_at = Utc::now();
+        cache
+            .cache_protected_branches("Acme/API", &["main".to_string()], fetched_at)
+            .await
+            .unwrap();
+        let (branches, cached_at) = cache
+            .get_protected_branches("acme/api")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(branches, vec!["main"]);
+        assert_eq!(cached_at.timestamp(), fetched_at.timestamp());
+    }
+
+    #[tokio::test]
+    async fn test_file_license_cache() {
+        let temp_dir = tempdir().unwrap();
+        let cache = RepositoryCache::new(temp_dir.path().join("test_repos.db"));
+        cache.initialize().await.unwrap();
+
+        assert_eq!(cache.get_file_license("a").await.unwrap(), None);
+        cache.cache_file_license("a", Some("MIT")).await.unwrap();
+        cache.cache_file_license("b", None).await.unwrap();
+        assert_eq!(
+            cache.get_file_license("a").await.unwrap(),
+            Some(Some("MIT".to_string()))
+        );
+        assert_eq!(cache.get_file_license("b").await.unwrap(), Some(None));
+    }
+
+    #[tokio::test]
+    async fn test_app_launches() {
+        let temp_dir = tempdir().unwrap();
+        let cache = RepositoryCache::new(temp_dir.path().join("test_repos.db"));
+        cache.initialize().await.unwrap();
+
+        let now = Utc::now();
+        for (app, days_ago) in [("vscode", 10), ("warp", 1)] {
+            cache
+                .record_app_launch(&AppLaunch {
+                    repo: "api".to_string(),
+                    app: app.to_string(),
+                    source: "cli".to_string(),
+                    launched_at: now - chrono::Duration::days(days_ago),
+                })
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(cache.get_app_launches(None).await.unwrap().len(), 2);
+        let recent = cache
+            .get_app_launches(Some(now - chrono::Duration::days(7)))
+            .await
+            .unwrap();
+        assert_eq!(recent.len(), 1);
+        assert_eq!(recent[0].app, "warp");
     }
 }
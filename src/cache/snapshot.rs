@@ -0,0 +1,397 @@
+//! Portable snapshots of the repository and git status caches
+//!
+//! `vibe cache export` writes every row of the cache tables to one gzipped
+//! JSON file, and `vibe cache import` loads it into another machine's cache,
+//! so CI images and containers start warm. Rows keep their original
+//! `last_updated`/`fetched_at` timestamps, so TTLs count from when an entry
+//! was first recorded, not from the import. Worktree metadata and app launch
+//! history describe one machine and are left out.
+//!
+//! Rows are written in primary key order with sorted columns, and the gzip
+//! header carries no timestamp, so exporting the same data twice produces
+//! the same bytes.
+
+use anyhow::{bail, Context, Result};
+use flate2::read::GzDecoder;
+use flate2::{Compression, GzBuilder};
+use rusqlite::types::{Value, ValueRef};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+use std::path::Path;
+use tokio_rusqlite::Connection;
+
+use super::{GitStatusCache, RepositoryCache};
+
+/// Identifies a file as a cache snapshot
+const SNAPSHOT_FORMAT: &str = "vibe-cache-snapshot";
+
+/// Bumped whenever a snapshotted table changes shape; older vibes refuse
+/// newer snapshots instead of importing columns they don't know
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+const REPOSITORY_DB: &str = "repositories.db";
+const GIT_STATUS_DB: &str = "git_status.db";
+
+/// Tables included in a snapshot, per database file
+const SNAPSHOT_TABLES: &[(&str, &[&str])] = &[
+    (
+        REPOSITORY_DB,
+        &[
+            "repositories",
+            "disk_usage",
+            "activity",
+            "contributors",
+            "license_files",
+            "repo_listings",
+            "protected_branches",
+            "project_types",
+        ],
+    ),
+    (GIT_STATUS_DB, &["git_status", "pull_requests"]),
+];
+
+/// Column name to value
+type Row = BTreeMap<String, serde_json::Value>;
+
+/// Database file name to table name to rows
+type Databases = BTreeMap<String, BTreeMap<String, Vec<Row>>>;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Snapshot {
+    format: String,
+    version: u32,
+    databases: Databases,
+}
+
+/// Rows exported or imported, per table
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SnapshotSummary {
+    pub tables: BTreeMap<String, usize>,
+}
+
+impl SnapshotSummary {
+    pub fn total(&self) -> usize {
+        self.tables.values().sum()
+    }
+
+    fn from_databases(databases: &Databases) -> Self {
+        Self {
+            tables: databases
+                .values()
+                .flatten()
+                .map(|(table, rows)| (table.clone(), rows.len()))
+                .collect(),
+        }
+    }
+}
+
+/// Write the caches in `cache_dir` to a snapshot at `file`
+pub async fn export_snapshot(cache_dir: &Path, file: &Path) -> Result<SnapshotSummary> {
+    initialize(cache_dir).await?;
+
+    let mut databases = Databases::new();
+    for (db, tables) in SNAPSHOT_TABLES {
+        let conn = open(cache_dir, db).await?;
+        let rows = conn
+            .call(move |conn| {
+                let mut by_table = BTreeMap::new();
+                for table in tables.iter() {
+                    by_table.insert(table.to_string(), read_table(conn, table)?);
+                }
+                Ok(by_table)
+            })
+            .await
+            .with_context(|| format!("Failed to read {db}"))?;
+        databases.insert(db.to_string(), rows);
+    }
+
+    let summary = SnapshotSummary::from_databases(&databases);
+    let bytes = encode(&Snapshot {
+        format: SNAPSHOT_FORMAT.to_string(),
+        version: SNAPSHOT_VERSION,
+        databases,
+    })?;
+    tokio::fs::write(file, bytes)
+        .await
+        .with_context(|| format!("Failed to write {}", file.display()))?;
+    Ok(summary)
+}
+
+/// Replace the cached entries in `cache_dir` with those of the snapshot at
+/// `file`. With `if_empty`, nothing is imported (and `None` returned) when
+/// any snapshotted table already has entries.
+pub async fn import_snapshot(
+    cache_dir: &Path,
+    file: &Path,
+    if_empty: bool,
+) -> Result<Option<SnapshotSummary>> {
+    let bytes = tokio::fs::read(file)
+        .await
+        .with_context(|| format!("Failed to read {}", file.display()))?;
+    let snapshot =
+        decode(&bytes).with_context(|| format!("{} is not a cache snapshot", file.display()))?;
+    check_compatible(&snapshot)?;
+
+    initialize(cache_dir).await?;
+    if if_empty && !is_empty(cache_dir).await? {
+        return Ok(None);
+    }
+
+    let summary = SnapshotSummary::from_databases(&snapshot.databases);
+    for (db, tables) in snapshot.databases {
+        let conn = open(cache_dir, &db).await?;
+        conn.call(move |conn| {
+            let tx = conn.transaction()?;
+            for (table, rows) in &tables {
+                tx.execute(&format!("DELETE FROM {table}"), [])?;
+                for row in rows {
+                    insert_row(&tx, table, row)?;
+                }
+            }
+            tx.commit()?;
+            Ok(())
+        })
+        .await
+        .with_context(|| format!("Failed to import into {db}"))?;
+    }
+    Ok(Some(summary))
+}
+
+/// Refuse snapshots from another format version, or with tables this
+/// version of vibe doesn't snapshot. An unknown column fails its insert,
+/// which rolls back that database.
+fn check_compatible(snapshot: &Snapshot) -> Result<()> {
+    if snapshot.format != SNAPSHOT_FORMAT {
+        bail!("Not a cache snapshot (format '{}')", snapshot.format);
+    }
+    if snapshot.version != SNAPSHOT_VERSION {
+        bail!(
+            "Cache snapshot version {} can't be imported by this vibe, which reads version {}; \
+             export it again with the same vibe version",
+            snapshot.version,
+            SNAPSHOT_VERSION
+        );
+    }
+    for (db, tables) in &snapshot.databases {
+        let Some((_, known)) = SNAPSHOT_TABLES.iter().find(|(name, _)| name == db) else {
+            bail!("Cache snapshot contains unknown database '{db}'");
+        };
+        if let Some(table) = tables.keys().find(|table| !known.contains(&table.as_str())) {
+            bail!("Cache snapshot contains unknown table '{table}' in {db}");
+        }
+    }
+    Ok(())
+}
+
+/// Whether every snapshotted table in `cache_dir` is empty
+async fn is_empty(cache_dir: &Path) -> Result<bool> {
+    for (db, tables) in SNAPSHOT_TABLES {
+        let conn = open(cache_dir, db).await?;
+        let rows = conn
+            .call(move |conn| {
+                let mut rows: i64 = 0;
+                for table in tables.iter() {
+                    rows +=
+                        conn.query_row(&format!("SELECT COUNT(*) FROM {table}"), [], |row| {
+                            row.get::<_, i64>(0)
+                        })?;
+                }
+                Ok(rows)
+            })
+            .await
+            .with_context(|| format!("Failed to read {db}"))?;
+        if rows > 0 {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// Create the cache databases and their tables, migrating older ones
+async fn initialize(cache_dir: &Path) -> Result<()> {
+    tokio::fs::create_dir_all(cache_dir)
+        .await
+        .with_context(|| format!("Failed to create {}", cache_dir.display()))?;
+    RepositoryCache::new(cache_dir.join(REPOSITORY_DB))
+        .initialize()
+        .await?;
+    GitStatusCache::new(cache_dir.join(GIT_STATUS_DB))
+        .initialize()
+        .await?;
+    Ok(())
+}
+
+async fn open(cache_dir: &Path, db: &str) -> Result<Connection> {
+    Connection::open(cache_dir.join(db))
+        .await
+        .with_context(|| format!("Failed to open {db}"))
+}
+
+/// Every row of `table`, ordered by its first (primary key) column
+fn read_table(conn: &rusqlite::Connection, table: &str) -> rusqlite::Result<Vec<Row>> {
+    let mut stmt = conn.prepare(&format!("SELECT * FROM {table} ORDER BY 1"))?;
+    let columns: Vec<String> = stmt.column_names().into_iter().map(String::from).collect();
+    let rows = stmt.query_map([], |row| {
+        let mut values = Row::new();
+        for (i, column) in columns.iter().enumerate() {
+            let value = match row.get_ref(i)? {
+                ValueRef::Null => serde_json::Value::Null,
+                ValueRef::Integer(n) => n.into(),
+                ValueRef::Real(n) => n.into(),
+                ValueRef::Text(text) => String::from_utf8_lossy(text).into_owned().into(),
+                ValueRef::Blob(_) => {
+                    return Err(rusqlite::Error::InvalidColumnType(
+                        i,
+                        column.clone(),
+                        rusqlite::types::Type::Blob,
+                    ))
+                }
+            };
+            values.insert(column.clone(), value);
+        }
+        Ok(values)
+    })?;
+    rows.collect()
+}
+
+fn insert_row(tx: &rusqlite::Transaction, table: &str, row: &Row) -> rusqlite::Result<()> {
+    let columns: Vec<&str> = row.keys().map(String::as_str).collect();
+    let placeholders: Vec<String> = (1..=columns.len()).map(|i| format!("?{i}")).collect();
+    let values: Vec<Value> = row
+        .values()
+        .map(|value| match value {
+            serde_json::Value::Null => Value::Null,
+            serde_json::Value::Number(n) => match n.as_i64() {
+                Some(n) => Value::Integer(n),
+                None => Value::Real(n.as_f64().unwrap_or_default()),
+            },
+            serde_json::Value::String(text) => Value::Text(text.clone()),
+            other => Value::Text(other.to_string()),
+        })
+        .collect();
+    tx.execute(
+        &format!(
+            "INSERT INTO {table} ({}) VALUES ({})",
+            columns.join(", "),
+            placeholders.join(", ")
+        ),
+        rusqlite::params_from_iter(values),
+    )?;
+    Ok(())
+}
+
+/// Gzipped JSON, with no timestamp or file name in the gzip header
+fn encode(snapshot: &Snapshot) -> Result<Vec<u8>> {
+    let json = serde_json::to_vec(snapshot)?;
+    let mut encoder = GzBuilder::new().write(Vec::new(), Compression::default());
+    encoder.write_all(&json)?;
+    Ok(encoder.finish()?)
+}
+
+fn decode(bytes: &[u8]) -> Result<Snapshot> {
+    let mut json = Vec::new();
+    GzDecoder::new(bytes).read_to_end(&mut json)?;
+    Ok(serde_json::from_slice(&json)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::CachedGitStatus;
+    use chrono::{Duration, Utc};
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    fn status(name: &str, age: Duration) -> CachedGitStatus {
+        CachedGitStatus {
+            repository_name: name.to_string(),
+            path: PathBuf::from(format!("/ws/{name}")),
+            branch: Some("main".to_string()),
+            clean: true,
+            ahead: 1,
+            behind: 0,
+            staged: 0,
+            unstaged: 0,
+            untracked: 2,
+            remote_url: None,
+            last_updated: Utc::now() - age,
+        }
+    }
+
+    async fn warm_cache(dir: &Path) {
+        initialize(dir).await.unwrap();
+        let cache = GitStatusCache::new(dir.join(GIT_STATUS_DB));
+        // Inserted out of order; the export sorts them
+        for status in [
+            status("web", Duration::minutes(1)),
+            status("api", Duration::days(2)),
+        ] {
+            cache.cache_git_status(&status).await.unwrap();
+        }
+        RepositoryCache::new(dir.join(REPOSITORY_DB))
+            .cache_protected_branches("acme/api", &["main".to_string()], Utc::now())
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_round_trip_is_byte_stable() {
+        let temp = TempDir::new().unwrap();
+        let source = temp.path().join("source");
+        warm_cache(&source).await;
+
+        let first = temp.path().join("first.gz");
+        let summary = export_snapshot(&source, &first).await.unwrap();
+        assert_eq!(summary.tables["git_status"], 2);
+        assert_eq!(summary.total(), 3);
+
+        let again = temp.path().join("again.gz");
+        export_snapshot(&source, &again).await.unwrap();
+        assert_eq!(
+            std::fs::read(&first).unwrap(),
+            std::fs::read(&again).unwrap()
+        );
+
+        let target = temp.path().join("target");
+        let imported = import_snapshot(&target, &first, false).await.unwrap();
+        assert_eq!(imported, Some(summary));
+        let round_trip = temp.path().join("round-trip.gz");
+        export_snapshot(&target, &round_trip).await.unwrap();
+        assert_eq!(
+            std::fs::read(&first).unwrap(),
+            std::fs::read(&round_trip).unwrap()
+        );
+
+        // Imported entries keep their age, so the stale one stays expired
+        let fresh = GitStatusCache::new(target.join(GIT_STATUS_DB))
+            .get_all_git_statuses()
+            .await
+            .unwrap();
+        assert_eq!(fresh.len(), 1);
+        assert_eq!(fresh[0].repository_name, "web");
+    }
+
+    #[tokio::test]
+    async fn test_import_guards() {
+        let temp = TempDir::new().unwrap();
+        let source = temp.path().join("source");
+        warm_cache(&source).await;
+        let file = temp.path().join("snapshot.gz");
+        export_snapshot(&source, &file).await.unwrap();
+
+        // A warm cache isn't replaced with --if-empty
+        assert_eq!(import_snapshot(&source, &file, true).await.unwrap(), None);
+        let empty = temp.path().join("empty");
+        assert!(import_snapshot(&empty, &file, true)
+            .await
+            .unwrap()
+            .is_some());
+
+        let mut snapshot = decode(&std::fs::read(&file).unwrap()).unwrap();
+        snapshot.version = SNAPSHOT_VERSION + 1;
+        std::fs::write(&file, encode(&snapshot).unwrap()).unwrap();
+        let err = import_snapshot(&empty, &file, false).await.unwrap_err();
+        assert!(err.to_string().contains("version"));
+    }
+}
@@ -0,0 +1,169 @@
+//! Read-only git queries behind a swappable backend
+//!
+//! Status, branch, ahead/behind, worktree and merge-base lookups go through
+//! [`GitBackend`]. The default [`SubprocessBackend`] shells out to `git`; with
+//! the `native-git` feature, `preferences.git_backend: native` answers the
+//! same queries in-process through libgit2, which avoids a process spawn per
+//! query and keeps working when `git` isn't on PATH.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use tracing::debug;
+
+#[cfg(feature = "native-git")]
+pub mod native;
+pub mod subprocess;
+
+#[cfg(feature = "native-git")]
+pub use native::NativeBackend;
+pub use subprocess::SubprocessBackend;
+
+/// `preferences.git_backend` in config.yaml
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GitBackendKind {
+    /// Run the `git` executable
+    #[default]
+    Subprocess,
+    /// libgit2, when built with the `native-git` feature
+    Native,
+}
+
+/// One line of `git status --porcelain=v1`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatusEntry {
+    /// Two-letter `XY` code: index state, then worktree state
+    pub code: String,
+    /// Path relative to the repository root; for renames, the new path
+    pub path: String,
+}
+
+impl StatusEntry {
+    pub fn is_untracked(&self) -> bool {
+        self.code == "??"
+    }
+
+    /// Whether the index differs from HEAD
+    pub fn is_staged(&self) -> bool {
+        matches!(self.code.as_bytes()[0], b'A' | b'M' | b'D' | b'R' | b'T')
+    }
+
+    /// Whether the working tree differs from the index
+    pub fn is_unstaged(&self) -> bool {
+        matches!(self.code.as_bytes()[1], b'M' | b'D' | b'R' | b'T')
+    }
+}
+
+/// One worktree from `git worktree list --porcelain`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorktreeEntry {
+    pub path: PathBuf,
+    /// HEAD commit SHA; empty for a bare repository
+    pub head: String,
+    /// Checked-out branch without the `refs/heads/` prefix
+    pub branch: Option<String>,
+    pub detached: bool,
+    pub bare: bool,
+}
+
+/// Read-only git queries used for status and worktree reporting
+#[async_trait]
+pub trait GitBackend: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    /// Changed and untracked paths, like `git status --porcelain`
+    async fn status(&self, repo: &Path) -> Result<Vec<StatusEntry>>;
+
+    /// Short name of the checked-out branch, `"HEAD"` when detached, or
+    /// `None` on an unborn branch
+    async fn head_branch(&self, repo: &Path) -> Result<Option<String>>;
+
+    /// Short name of the current branch's upstream, e.g. `origin/main`
+    async fn upstream(&self, repo: &Path) -> Result<Option<String>>;
+
+    /// Commits `(ahead, behind)` of `head` relative to `base`, or `None` when
+    /// either revision doesn't resolve
+    async fn ahead_behind(
+        &self,
+        repo: &Path,
+        base: &str,
+        head: &str,
+    ) -> Result<Option<(usize, usize)>>;
+
+    /// URL of `origin`, or of the first remote when there is no `origin`
+    async fn remote_url(&self, repo: &Path) -> Result<Option<String>>;
+
+    /// The main worktree followed by linked worktrees
+    async fn worktrees(&self, repo: &Path) -> Result<Vec<WorktreeEntry>>;
+
+    /// Best common ancestor of two revisions
+    async fn merge_base(&self, repo: &Path, a: &str, b: &str) -> Result<Option<String>>;
+}
+
+/// A backend of the given kind, falling back to the subprocess backend when
+/// native support isn't compiled in
+pub fn backend_for(kind: GitBackendKind) -> Arc<dyn GitBackend> {
+    match kind {
+        GitBackendKind::Subprocess => Arc::new(SubprocessBackend),
+        #[cfg(feature = "native-git")]
+        GitBackendKind::Native => Arc::new(NativeBackend),
+        #[cfg(not(feature = "native-git"))]
+        GitBackendKind::Native => {
+            tracing::warn!(
+                "git_backend 'native' requires the native-git feature; using subprocess"
+            );
+            Arc::new(SubprocessBackend)
+        }
+    }
+}
+
+static BACKEND: RwLock<Option<Arc<dyn GitBackend>>> = RwLock::new(None);
+
+/// Select the backend from the user's preferences
+pub fn set_backend(kind: GitBackendKind) {
+    let backend = backend_for(kind);
+    debug!("Using {} git backend", backend.name());
+    *BACKEND.write().unwrap() = Some(backend);
+}
+
+/// The active backend
+pub fn current() -> Arc<dyn GitBackend> {
+    BACKEND
+        .read()
+        .unwrap()
+        .clone()
+        .unwrap_or_else(|| Arc::new(SubprocessBackend))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(code: &str) -> StatusEntry {
+        StatusEntry {
+            code: code.to_string(),
+            path: "file.txt".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_status_entry_classification() {
+        assert!(entry("M ").is_staged());
+        assert!(!entry("M ").is_unstaged());
+        assert!(entry("AM").is_staged() && entry("AM").is_unstaged());
+        assert!(entry(" D").is_unstaged());
+        assert!(entry("??").is_untracked());
+        assert!(!entry("??").is_staged() && !entry("??").is_unstaged());
+        assert!(!entry("UU").is_staged());
+    }
+
+    #[test]
+    fn test_backend_kind_parse() {
+        let kind: GitBackendKind = serde_yaml::from_str("native").unwrap();
+        assert_eq!(kind, GitBackendKind::Native);
+        assert_eq!(GitBackendKind::default(), GitBackendKind::Subprocess);
+    }
+}
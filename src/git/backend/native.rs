@@ -0,0 +1,288 @@
+//! [`GitBackend`] answered in-process through libgit2
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use git2::{
+    BranchType, ErrorCode, Repository, Status, StatusEntry as Git2StatusEntry, StatusOptions,
+};
+use std::path::{Path, PathBuf};
+
+use super::{GitBackend, StatusEntry, WorktreeEntry};
+
+pub struct NativeBackend;
+
+/// Open the repository containing `path` and run `f` on a blocking thread
+async fn with_repo<T, F>(path: &Path, f: F) -> Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce(&Repository) -> Result<T> + Send + 'static,
+{
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        let repo = Repository::discover(&path)
+            .with_context(|| format!("Failed to open git repository: {}", path.display()))?;
+        f(&repo)
+    })
+    .await
+    .context("Git task failed")?
+}
+
+#[async_trait]
+impl GitBackend for NativeBackend {
+    fn name(&self) -> &'static str {
+        "native"
+    }
+
+    async fn status(&self, repo: &Path) -> Result<Vec<StatusEntry>> {
+        with_repo(repo, |repo| {
+            let mut options = StatusOptions::new();
+            options
+                .include_untracked(true)
+                .include_ignored(false)
+                .renames_head_to_index(true);
+            let statuses = repo
+                .statuses(Some(&mut options))
+                .context("Failed to get repository status")?;
+            let mut entries: Vec<StatusEntry> = statuses
+                .iter()
+                .filter_map(|e| porcelain_entry(&e))
+                .collect();
+            // git lists untracked paths after tracked changes
+            entries.sort_by_key(|e| e.is_untracked());
+            Ok(entries)
+        })
+        .await
+    }
+
+    async fn head_branch(&self, repo: &Path) -> Result<Option<String>> {
+        with_repo(repo, head_branch).await
+    }
+
+    async fn upstream(&self, repo: &Path) -> Result<Option<String>> {
+        with_repo(repo, |repo| {
+            let Some(branch) = head_branch(repo)?.filter(|b| b != "HEAD") else {
+                return Ok(None);
+            };
+            let upstream = repo
+                .find_branch(&branch, BranchType::Local)
+                .and_then(|b| b.upstream());
+            Ok(match upstream {
+                Ok(upstream) => upstream.name()?.map(str::to_string),
+                Err(_) => None,
+            })
+        })
+        .await
+    }
+
+    async fn ahead_behind(
+        &self,
+        repo: &Path,
+        base: &str,
+        head: &str,
+    ) -> Result<Option<(usize, usize)>> {
+        let (base, head) = (base.to_string(), head.to_string());
+        with_repo(repo, move |repo| {
+            let (Some(base), Some(head)) = (commit_id(repo, &base), commit_id(repo, &head)) else {
+                return Ok(None);
+            };
+            Ok(Some(repo.graph_ahead_behind(head, base)?))
+        })
+        .await
+    }
+
+    async fn remote_url(&self, repo: &Path) -> Result<Option<String>> {
+        with_repo(repo, |repo| {
+            let remotes = repo.remotes()?;
+            let name = if remotes.iter().flatten().any(|name| name == "origin") {
+                "origin".to_string()
+            } else {
+                match remotes.get(0) {
+                    Some(name) => name.to_string(),
+                    None => return Ok(None),
+                }
+            };
+            let remote = repo.find_remote(&name)?;
+            Ok(remote.url().map(str::to_string))
+        })
+        .await
+    }
+
+    async fn worktrees(&self, repo: &Path) -> Result<Vec<WorktreeEntry>> {
+        with_repo(repo, |repo| {
+            // Linked worktrees report the main repository through commondir
+            let main = if repo.is_worktree() {
+                let commondir = std::fs::read_to_string(repo.path().join("commondir"))
+                    .context("Failed to read worktree commondir")?;
+                Repository::open(repo.path().join(commondir.trim()))?
+            } else {
+                Repository::open(repo.path())?
+            };
+
+            let mut worktrees = vec![worktree_entry(&main)?];
+            let mut linked = Vec::new();
+            for name in main.worktrees()?.iter().flatten() {
+                let worktree = main.find_worktree(name)?;
+                match Repository::open_from_worktree(&worktree) {
+                    Ok(repo) => linked.push(worktree_entry(&repo)?),
+                    // Prunable worktrees whose directory is gone
+                    Err(_) => linked.push(WorktreeEntry {
+                        path: worktree.path().to_path_buf(),
+                        head: String::new(),
+                        branch: None,
+                        detached: false,
+                        bare: false,
+                    }),
+                }
+            }
+            linked.sort_by(|a, b| a.path.cmp(&b.path));
+            worktrees.extend(linked);
+            Ok(worktrees)
+        })
+        .await
+    }
+
+    async fn merge_base(&self, repo: &Path, a: &str, b: &str) -> Result<Option<String>> {
+        let (a, b) = (a.to_string(), b.to_string());
+        with_repo(repo, move |repo| {
+            let (Some(a), Some(b)) = (commit_id(repo, &a), commit_id(repo, &b)) else {
+                return Ok(None);
+            };
+            Ok(repo.merge_base(a, b).ok().map(|oid| oid.to_string()))
+        })
+        .await
+    }
+}
+
+fn head_branch(repo: &Repository) -> Result<Option<String>> {
+    let head = match repo.head() {
+        Ok(head) => head,
+        Err(e) if e.code() == ErrorCode::UnbornBranch => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    if !head.is_branch() {
+        return Ok(Some("HEAD".to_string()));
+    }
+    Ok(head.shorthand().map(str::to_string))
+}
+
+fn commit_id(repo: &Repository, rev: &str) -> Option<git2::Oid> {
+    repo.revparse_single(rev)
+        .and_then(|object| object.peel_to_commit())
+        .map(|commit| commit.id())
+        .ok()
+}
+
+/// Map libgit2 status flags onto a porcelain `XY` entry
+fn porcelain_entry(entry: &Git2StatusEntry) -> Option<StatusEntry> {
+    let flags = entry.status();
+    if flags.contains(Status::IGNORED) {
+        return None;
+    }
+
+    let code = if flags.contains(Status::CONFLICTED) {
+        "UU".to_string()
+    } else if flags.contains(Status::WT_NEW) && !flags.intersects(index_flags()) {
+        "??".to_string()
+    } else {
+        let x = if flags.contains(Status::INDEX_NEW) {
+            'A'
+        } else if flags.contains(Status::INDEX_MODIFIED) {
+            'M'
+        } else if flags.contains(Status::INDEX_DELETED) {
+            'D'
+        } else if flags.contains(Status::INDEX_RENAMED) {
+            'R'
+        } else if flags.contains(Status::INDEX_TYPECHANGE) {
+            'T'
+        } else {
+            ' '
+        };
+        let y = if flags.contains(Status::WT_MODIFIED) {
+            'M'
+        } else if flags.contains(Status::WT_DELETED) {
+            'D'
+        } else if flags.contains(Status::WT_RENAMED) {
+            'R'
+        } else if flags.contains(Status::WT_TYPECHANGE) {
+            'T'
+        } else {
+            ' '
+        };
+        format!("{x}{y}")
+    };
+
+    // Renames are reported under their new path, like git status
+    let path = entry
+        .head_to_index()
+        .filter(|_| flags.contains(Status::INDEX_RENAMED))
+        .and_then(|delta| delta.new_file().path().map(Path::to_path_buf))
+        .map(|path| path.to_string_lossy().to_string())
+        .or_else(|| entry.path().map(str::to_string))?;
+
+    Some(StatusEntry { code, path })
+}
+
+fn index_flags() -> Status {
+    Status::INDEX_NEW
+        | Status::INDEX_MODIFIED
+        | Status::INDEX_DELETED
+        | Status::INDEX_RENAMED
+        | Status::INDEX_TYPECHANGE
+}
+
+fn worktree_entry(repo: &Repository) -> Result<WorktreeEntry> {
+    if repo.is_bare() {
+        return Ok(WorktreeEntry {
+            path: canonical(repo.path()),
+            head: String::new(),
+            branch: None,
+            detached: false,
+            bare: true,
+        });
+    }
+
+    let workdir = repo
+        .workdir()
+        .context("Repository has no working directory")?;
+    let (head, branch, detached) = match repo.head() {
+        Ok(head) => {
+            let id = head
+                .peel_to_commit()
+                .map(|c| c.id().to_string())
+                .unwrap_or_default();
+            if head.is_branch() {
+                (id, head.shorthand().map(str::to_string), false)
+            } else {
+                (id, None, true)
+            }
+        }
+        // Unborn branch: git lists the branch with a zero HEAD
+        Err(e) if e.code() == ErrorCode::UnbornBranch => {
+            let branch = repo
+                .find_reference("HEAD")
+                .ok()
+                .and_then(|r| r.symbolic_target().map(str::to_string))
+                .map(|target| {
+                    target
+                        .strip_prefix("refs/heads/")
+                        .unwrap_or(&target)
+                        .to_string()
+                });
+            (git2::Oid::zero().to_string(), branch, false)
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    Ok(WorktreeEntry {
+        path: canonical(workdir),
+        head,
+        branch,
+        detached,
+        bare: false,
+    })
+}
+
+/// Resolve symlinks and drop the trailing separator, matching git's output
+fn canonical(path: &Path) -> PathBuf {
+    crate::utils::fs::canonicalize(path).unwrap_or_else(|_| path.components().collect())
+}
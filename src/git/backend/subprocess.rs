@@ -0,0 +1,212 @@
+//! [`GitBackend`] that runs the `git` executable
+
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use std::path::{Path, PathBuf};
+use std::process::Output;
+use tokio::process::Command;
+
+use crate::output::redact;
+
+use super::{GitBackend, StatusEntry, WorktreeEntry};
+
+pub struct SubprocessBackend;
+
+async fn run(repo: &Path, args: &[&str]) -> Result<Output> {
+    Command::new("git")
+        .args(args)
+        .current_dir(repo)
+        .output()
+        .await
+        .with_context(|| {
+            format!(
+                "Failed to execute git command: {}",
+                redact::command_line("git", args)
+            )
+        })
+}
+
+/// Trimmed stdout, or `None` when git exits unsuccessfully
+async fn run_optional(repo: &Path, args: &[&str]) -> Result<Option<String>> {
+    let output = run(repo, args).await?;
+    if !output.status.success() {
+        return Ok(None);
+    }
+    Ok(Some(
+        String::from_utf8_lossy(&output.stdout).trim().to_string(),
+    ))
+}
+
+async fn run_checked(repo: &Path, args: &[&str]) -> Result<String> {
+    let output = run(repo, args).await?;
+    if !output.status.success() {
+        bail!(
+            "Git command failed: {}\nError: {}",
+            redact::command_line("git", args),
+            redact::scrub(&String::from_utf8_lossy(&output.stderr))
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+#[async_trait]
+impl GitBackend for SubprocessBackend {
+    fn name(&self) -> &'static str {
+        "subprocess"
+    }
+
+    async fn status(&self, repo: &Path) -> Result<Vec<StatusEntry>> {
+        let output = run(repo, &["status", "--porcelain=v1", "-z"]).await?;
+        if !output.status.success() {
+            bail!(
+                "Git status failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+        Ok(parse_porcelain_z(&output.stdout))
+    }
+
+    async fn head_branch(&self, repo: &Path) -> Result<Option<String>> {
+        run_optional(repo, &["rev-parse", "--abbrev-ref", "HEAD"]).await
+    }
+
+    async fn upstream(&self, repo: &Path) -> Result<Option<String>> {
+        run_optional(repo, &["rev-parse", "--abbrev-ref", "@{u}"]).await
+    }
+
+    async fn ahead_behind(
+        &self,
+        repo: &Path,
+        base: &str,
+        head: &str,
+    ) -> Result<Option<(usize, usize)>> {
+        let range = format!("{base}...{head}");
+        let Some(counts) =
+            run_optional(repo, &["rev-list", "--count", "--left-right", &range]).await?
+        else {
+            return Ok(None);
+        };
+
+        let mut counts = counts.split_whitespace().map(|n| n.parse().unwrap_or(0));
+        let behind = counts.next().unwrap_or(0);
+        let ahead = counts.next().unwrap_or(0);
+        Ok(Some((ahead, behind)))
+    }
+
+    async fn remote_url(&self, repo: &Path) -> Result<Option<String>> {
+        let remotes = run_checked(repo, &["remote"]).await?;
+        let remote = if remotes.lines().any(|name| name == "origin") {
+            "origin"
+        } else {
+            match remotes.lines().next() {
+                Some(name) => name,
+                None => return Ok(None),
+            }
+        };
+        run_optional(repo, &["config", "--get", &format!("remote.{remote}.url")]).await
+    }
+
+    async fn worktrees(&self, repo: &Path) -> Result<Vec<WorktreeEntry>> {
+        let output = run_checked(repo, &["worktree", "list", "--porcelain"]).await?;
+        Ok(parse_worktree_list(&output))
+    }
+
+    async fn merge_base(&self, repo: &Path, a: &str, b: &str) -> Result<Option<String>> {
+        run_optional(repo, &["merge-base", a, b]).await
+    }
+}
+
+/// Parse `git status --porcelain=v1 -z` output
+pub fn parse_porcelain_z(output: &[u8]) -> Vec<StatusEntry> {
+    let output = String::from_utf8_lossy(output);
+    let mut fields = output.split('\0');
+    let mut entries = Vec::new();
+
+    while let Some(field) = fields.next() {
+        if field.len() < 3 {
+            continue;
+        }
+        let code = &field[0..2];
+        // Renames and copies are followed by a field holding the source path
+        if code.contains(['R', 'C']) {
+            fields.next();
+        }
+        entries.push(StatusEntry {
+            code: code.to_string(),
+            path: field[3..].to_string(),
+        });
+    }
+
+    entries
+}
+
+/// Parse `git worktree list --porcelain` output
+pub fn parse_worktree_list(output: &str) -> Vec<WorktreeEntry> {
+    let mut worktrees = Vec::new();
+    let mut current: Option<WorktreeEntry> = None;
+
+    for line in output.lines() {
+        if let Some(path) = line.strip_prefix("worktree ") {
+            worktrees.extend(current.take());
+            current = Some(WorktreeEntry {
+                path: PathBuf::from(path),
+                head: String::new(),
+                branch: None,
+                detached: false,
+                bare: false,
+            });
+            continue;
+        }
+
+        let Some(entry) = current.as_mut() else {
+            continue;
+        };
+        if let Some(head) = line.strip_prefix("HEAD ") {
+            entry.head = head.to_string();
+        } else if let Some(branch) = line.strip_prefix("branch ") {
+            entry.branch = Some(
+                branch
+                    .strip_prefix("refs/heads/")
+                    .unwrap_or(branch)
+                    .to_string(),
+            );
+        } else if line == "detached" {
+            entry.detached = true;
+        } else if line == "bare" {
+            entry.bare = true;
+        }
+    }
+    worktrees.extend(current);
+
+    worktrees
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_porcelain_z_skips_rename_source() {
+        let entries = parse_porcelain_z(b"R  new.txt\0old.txt\0 M edited.txt\0?? dir/\0");
+        let codes: Vec<_> = entries
+            .iter()
+            .map(|e| (e.code.as_str(), e.path.as_str()))
+            .collect();
+        assert_eq!(
+            codes,
+            vec![("R ", "new.txt"), (" M", "edited.txt"), ("??", "dir/")]
+        );
+    }
+
+    #[test]
+    fn test_parse_worktree_list() {
+        let output = "worktree /repo\nHEAD abc123\nbranch refs/heads/main\n\n\
+                      worktree /repo/.worktrees/fix\nHEAD def456\ndetached\n";
+        let worktrees = parse_worktree_list(output);
+        assert_eq!(worktrees.len(), 2);
+        assert_eq!(worktrees[0].branch.as_deref(), Some("main"));
+        assert_eq!(worktrees[0].head, "abc123");
+        assert!(worktrees[1].detached);
+        assert_eq!(worktrees[1].branch, None);
+    }
+}
@@ -0,0 +1,575 @@
+//! Read-only branch inventory for `vibe git branches report`
+//!
+//! Lists local and remote-tracking branches with their last commit, flags
+//! branches older than a threshold, and notes which are fully merged into
+//! the repository's default branch. Nothing here writes to a repository,
+//! so the report is safe to run anywhere; its JSON form is stable for tools
+//! that act on it.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, TimeZone, Utc};
+use console::style;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::process::Command;
+use tokio::sync::Semaphore;
+
+use crate::display_println;
+use crate::output::delimited::Tabular;
+use crate::output::theme::{self, Role};
+
+/// Default age, in days, after which a branch counts as stale
+pub const DEFAULT_STALE_DAYS: u64 = 90;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BranchKind {
+    Local,
+    Remote,
+}
+
+/// One branch and its last commit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BranchEntry {
+    /// Short name, e.g. `feature/login` or `origin/feature/login`
+    pub name: String,
+    /// Full ref, e.g. `refs/remotes/origin/feature/login`
+    pub full_ref: String,
+    pub kind: BranchKind,
+    /// Remote name for remote-tracking branches
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote: Option<String>,
+    pub author: String,
+    pub last_commit: DateTime<Utc>,
+    pub age_days: u64,
+    pub stale: bool,
+    /// Fully merged into the default branch
+    pub merged: bool,
+    /// The default branch itself, locally or on a remote
+    pub is_default: bool,
+}
+
+/// Branches of one repository, oldest first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepoBranches {
+    pub repository: String,
+    pub path: PathBuf,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_branch: Option<String>,
+    pub branches: Vec<BranchEntry>,
+}
+
+impl RepoBranches {
+    pub fn stale_count(&self) -> usize {
+        self.branches.iter().filter(|b| b.stale).count()
+    }
+
+    pub fn merged_count(&self) -> usize {
+        self.branches.iter().filter(|b| b.merged).count()
+    }
+}
+
+/// Counts across the whole workspace
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BranchTotals {
+    pub repositories: usize,
+    pub branches: usize,
+    pub local: usize,
+    pub remote: usize,
+    pub stale: usize,
+    pub merged: usize,
+    /// Stale and merged: the safest candidates for deletion
+    pub stale_and_merged: usize,
+}
+
+/// The full `vibe git branches report` result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BranchReport {
+    pub stale_days: u64,
+    pub generated_at: DateTime<Utc>,
+    pub repositories: Vec<RepoBranches>,
+    pub totals: BranchTotals,
+}
+
+/// One branch and the repository it belongs to
+#[derive(Debug, Serialize)]
+pub struct BranchRow<'a> {
+    pub repository: &'a str,
+    #[serde(flatten)]
+    pub branch: &'a BranchEntry,
+}
+
+impl Tabular for BranchRow<'_> {
+    const FIELDS: &'static [&'static str] = &[
+        "repository",
+        "name",
+        "full_ref",
+        "kind",
+        "remote",
+        "author",
+        "last_commit",
+        "age_days",
+        "stale",
+        "merged",
+        "is_default",
+    ];
+}
+
+impl BranchReport {
+    pub fn new(
+        repositories: Vec<RepoBranches>,
+        stale_days: u64,
+        generated_at: DateTime<Utc>,
+    ) -> Self {
+        let mut totals = BranchTotals {
+            repositories: repositories.len(),
+            ..Default::default()
+        };
+        for branch in repositories.iter().flat_map(|r| &r.branches) {
+            totals.branches += 1;
+            match branch.kind {
+                BranchKind::Local => totals.local += 1,
+                BranchKind::Remote => totals.remote += 1,
+            }
+            totals.stale += usize::from(branch.stale);
+            totals.merged += usize::from(branch.merged);
+            totals.stale_and_merged += usize::from(branch.stale && branch.merged);
+        }
+        Self {
+            stale_days,
+            generated_at,
+            repositories,
+            totals,
+        }
+    }
+
+    /// One row per branch, for CSV and TSV export
+    pub fn rows(&self) -> Vec<BranchRow<'_>> {
+        self.repositories
+            .iter()
+            .flat_map(|repo| {
+                repo.branches.iter().map(|branch| BranchRow {
+                    repository: &repo.repository,
+                    branch,
+                })
+            })
+            .collect()
+    }
+
+    pub fn print_report(&self) {
+        if self.repositories.is_empty() {
+            display_println!("{} No repositories found", style("ℹ").yellow());
+            return;
+        }
+
+        for repo in &self.repositories {
+            display_println!(
+                "\n{} {} {}",
+                style("📁").blue(),
+                style(&repo.repository).cyan().bold(),
+                theme::paint(
+                    Role::Dim,
+                    format!(
+                        "(default: {}) {} branches, {} stale, {} merged",
+                        repo.default_branch.as_deref().unwrap_or("unknown"),
+                        repo.branches.len(),
+                        repo.stale_count(),
+                        repo.merged_count()
+                    )
+                )
+            );
+
+            let name_width = repo
+                .branches
+                .iter()
+                .map(|b| b.name.len())
+                .max()
+                .unwrap_or(0)
+                .max("Branch".len());
+            let author_width = repo
+                .branches
+                .iter()
+                .map(|b| b.author.len())
+                .max()
+                .unwrap_or(0)
+                .clamp("Author".len(), 24);
+            display_println!(
+                "  {}",
+                style(format!(
+                    "{:>6}  {:<6}  {:<name_width$}  {:<author_width$}  {:<10}  Flags",
+                    "Age", "Kind", "Branch", "Author", "Last commit"
+                ))
+                .bold()
+            );
+
+            for branch in &repo.branches {
+                let kind = match branch.kind {
+                    BranchKind::Local => "local",
+                    BranchKind::Remote => "remote",
+                };
+                let author: String = branch.author.chars().take(author_width).collect();
+                let row = format!(
+                    "{:>5}d  {kind:<6}  {:<name_width$}  {author:<author_width$}  {}",
+                    branch.age_days,
+                    branch.name,
+                    branch.last_commit.format("%Y-%m-%d")
+                );
+                let flags = branch_flags(branch);
+                if branch.stale {
+                    display_println!("  {}  {flags}", theme::paint(Role::Warning, row));
+                } else {
+                    display_println!("  {row}  {flags}");
+                }
+            }
+        }
+
+        let totals = &self.totals;
+        display_println!();
+        display_println!(
+            "{}",
+            style(format!(
+                "Total: {} branches ({} local, {} remote) in {} repositories; \
+                 {} stale (>{} days), {} merged, {} stale and merged",
+                totals.branches,
+                totals.local,
+                totals.remote,
+                totals.repositories,
+                totals.stale,
+                self.stale_days,
+                totals.merged,
+                totals.stale_and_merged
+            ))
+            .bold()
+        );
+    }
+}
+
+fn branch_flags(branch: &BranchEntry) -> String {
+    let mut flags = Vec::new();
+    if branch.is_default {
+        flags.push("default");
+    }
+    if branch.stale {
+        flags.push("stale");
+    }
+    if branch.merged {
+        flags.push("merged");
+    }
+    flags.join(" ")
+}
+
+/// Collect branches of `repos`, at most `max_parallel` at a time. Results
+/// keep the order of `repos`; unreadable repositories become warnings.
+pub async fn collect_branch_reports(
+    repos: Vec<(String, PathBuf)>,
+    stale_days: u64,
+    max_parallel: usize,
+    now: DateTime<Utc>,
+) -> (Vec<RepoBranches>, Vec<String>) {
+    let semaphore = Arc::new(Semaphore::new(max_parallel.max(1)));
+    let mut handles = Vec::with_capacity(repos.len());
+
+    for (name, path) in repos {
+        let semaphore = Arc::clone(&semaphore);
+        let task_name = name.clone();
+        let handle = tokio::spawn(async move {
+            let _permit = semaphore.acquire_owned().await.ok();
+            read_repo_branches(task_name, path, stale_days, now).await
+        });
+        handles.push((name, handle));
+    }
+
+    let mut reports = Vec::with_capacity(handles.len());
+    let mut warnings = Vec::new();
+    for (name, handle) in handles {
+        match handle.await {
+            Ok(Ok(report)) => reports.push(report),
+            Ok(Err(e)) => warnings.push(format!("Failed to read branches of {name}: {e}")),
+            Err(e) => warnings.push(format!("Branch task for {name} failed: {e}")),
+        }
+    }
+    (reports, warnings)
+}
+
+/// Read one repository's branches with `git for-each-ref`
+pub async fn read_repo_branches(
+    name: String,
+    path: PathBuf,
+    stale_days: u64,
+    now: DateTime<Utc>,
+) -> Result<RepoBranches> {
+    let refs = git_output(
+        &path,
+        &[
+            "for-each-ref",
+            "--format=%(refname)%00%(symref)%00%(committerdate:unix)%00%(authorname)",
+            "refs/heads",
+            "refs/remotes",
+        ],
+    )
+    .await?;
+    let refs = parse_refs(&refs);
+
+    let default_ref = default_branch_ref(&refs);
+    let merged: HashSet<String> = match &default_ref {
+        Some(default_ref) => git_output(
+            &path,
+            &[
+                "for-each-ref",
+                "--format=%(refname)",
+                "--merged",
+                default_ref,
+                "refs/heads",
+                "refs/remotes",
+            ],
+        )
+        .await?
+        .lines()
+        .map(str::to_string)
+        .collect(),
+        None => HashSet::new(),
+    };
+    let default_branch = default_ref.as_deref().map(short_branch_name);
+
+    let mut branches: Vec<BranchEntry> = refs
+        .into_iter()
+        .filter(|r| r.symref.is_empty())
+        .map(|r| {
+            let (kind, remote, short) = classify_ref(&r.full_ref);
+            let is_default = default_branch.as_deref().is_some_and(|default| {
+                let branch = match &remote {
+                    Some(remote) => short.strip_prefix(&format!("{remote}/")).unwrap_or(&short),
+                    None => &short,
+                };
+                branch == default
+            });
+            let age_days = (now - r.last_commit).num_days().max(0) as u64;
+            BranchEntry {
+                merged: !is_default && merged.contains(&r.full_ref),
+                name: short,
+                full_ref: r.full_ref,
+                kind,
+                remote,
+                author: r.author,
+                last_commit: r.last_commit,
+                age_days,
+                stale: age_days > stale_days,
+                is_default,
+            }
+        })
+        .collect();
+    branches.sort_by(|a, b| {
+        a.last_commit
+            .cmp(&b.last_commit)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+
+    Ok(RepoBranches {
+        repository: name,
+        path,
+        default_branch,
+        branches,
+    })
+}
+
+/// A ref line from `git for-each-ref`
+#[derive(Debug, PartialEq, Eq)]
+struct RefLine {
+    full_ref: String,
+    symref: String,
+    last_commit: DateTime<Utc>,
+    author: String,
+}
+
+fn parse_refs(output: &str) -> Vec<RefLine> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\0');
+            let full_ref = fields.next()?.to_string();
+            let symref = fields.next()?.to_string();
+            let timestamp = fields.next()?.parse().ok()?;
+            let author = fields.next().unwrap_or_default().to_string();
+            Some(RefLine {
+                full_ref,
+                symref,
+                last_commit: Utc.timestamp_opt(timestamp, 0).single()?,
+                author,
+            })
+        })
+        .collect()
+}
+
+/// `(kind, remote, short name)` for a full ref
+fn classify_ref(full_ref: &str) -> (BranchKind, Option<String>, String) {
+    if let Some(short) = full_ref.strip_prefix("refs/heads/") {
+        return (BranchKind::Local, None, short.to_string());
+    }
+    let short = full_ref
+        .strip_prefix("refs/remotes/")
+        .unwrap_or(full_ref)
+        .to_string();
+    let remote = short.split_once('/').map(|(remote, _)| remote.to_string());
+    (BranchKind::Remote, remote, short)
+}
+
+/// `main` from `refs/remotes/origin/main` or `refs/heads/main`
+fn short_branch_name(full_ref: &str) -> String {
+    match classify_ref(full_ref) {
+        (BranchKind::Local, _, short) => short,
+        (BranchKind::Remote, Some(remote), short) => short
+            .strip_prefix(&format!("{remote}/"))
+            .unwrap_or(&short)
+            .to_string(),
+        (BranchKind::Remote, None, short) => short,
+    }
+}
+
+/// The ref merges are measured against: `origin/HEAD` when it is set,
+/// otherwise a local `main` or `master`
+fn default_branch_ref(refs: &[RefLine]) -> Option<String> {
+    if let Some(target) = refs
+        .iter()
+        .find(|r| r.full_ref == "refs/remotes/origin/HEAD")
+        .map(|r| r.symref.clone())
+        .filter(|target| !target.is_empty())
+    {
+        return Some(target);
+    }
+    ["refs/heads/main", "refs/heads/master"]
+        .into_iter()
+        .find(|candidate| refs.iter().any(|r| r.full_ref == *candidate))
+        .map(str::to_string)
+}
+
+async fn git_output(path: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(path)
+        .output()
+        .await
+        .context("Failed to run git")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "git {} failed: {}",
+            args.first().unwrap_or(&""),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command as StdCommand;
+    use tempfile::TempDir;
+
+    fn git(dir: &Path, args: &[&str], date: &str) {
+        let output = StdCommand::new("git")
+            .args([
+                "-c",
+                "user.name=Test User",
+                "-c",
+                "user.email=test@example.com",
+                "-c",
+                "commit.gpgsign=false",
+            ])
+            .args(args)
+            .env("GIT_AUTHOR_DATE", date)
+            .env("GIT_COMMITTER_DATE", date)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "git {args:?} failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    #[test]
+    fn test_parse_refs() {
+        let output = "refs/heads/main\0\x001700000000\0Ada\n\
+                      refs/remotes/origin/HEAD\0refs/remotes/origin/main\x001700000000\0Ada\n\
+                      garbage\n";
+        let refs = parse_refs(output);
+        assert_eq!(refs.len(), 2);
+        assert_eq!(refs[0].full_ref, "refs/heads/main");
+        assert_eq!(refs[0].author, "Ada");
+        assert_eq!(refs[1].symref, "refs/remotes/origin/main");
+    }
+
+    #[test]
+    fn test_classify_ref() {
+        assert_eq!(
+            classify_ref("refs/heads/feature/login"),
+            (BranchKind::Local, None, "feature/login".to_string())
+        );
+        assert_eq!(
+            classify_ref("refs/remotes/upstream/fix/a"),
+            (
+                BranchKind::Remote,
+                Some("upstream".to_string()),
+                "upstream/fix/a".to_string()
+            )
+        );
+        assert_eq!(short_branch_name("refs/remotes/origin/trunk"), "trunk");
+    }
+
+    #[tokio::test]
+    async fn test_read_repo_branches_flags_stale_and_merged() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = temp_dir.path();
+        let old = "2020-01-01T00:00:00Z";
+        let recent = "2024-06-01T00:00:00Z";
+        git(repo, &["init", "-q", "-b", "main"], old);
+        git(repo, &["commit", "-q", "--allow-empty", "-m", "init"], old);
+        git(repo, &["branch", "merged-old"], old);
+        git(repo, &["checkout", "-q", "-b", "wip"], recent);
+        git(
+            repo,
+            &["commit", "-q", "--allow-empty", "-m", "wip"],
+            recent,
+        );
+        git(repo, &["checkout", "-q", "main"], recent);
+
+        let now = Utc.with_ymd_and_hms(2024, 7, 1, 0, 0, 0).unwrap();
+        let report = read_repo_branches("app".to_string(), repo.to_path_buf(), 90, now)
+            .await
+            .unwrap();
+
+        assert_eq!(report.default_branch.as_deref(), Some("main"));
+        let find = |name: &str| report.branches.iter().find(|b| b.name == name).unwrap();
+
+        let main = find("main");
+        assert!(main.is_default && main.stale && !main.merged);
+
+        let merged_old = find("merged-old");
+        assert!(merged_old.stale && merged_old.merged && !merged_old.is_default);
+        assert_eq!(merged_old.author, "Test User");
+
+        let wip = find("wip");
+        assert!(!wip.stale && !wip.merged);
+        assert_eq!(wip.age_days, 30);
+
+        // Oldest first
+        assert_eq!(report.branches.last().unwrap().name, "wip");
+
+        let totals = BranchReport::new(vec![report], 90, now).totals;
+        assert_eq!(
+            totals,
+            BranchTotals {
+                repositories: 1,
+                branches: 3,
+                local: 3,
+                remote: 0,
+                stale: 2,
+                merged: 1,
+                stale_and_merged: 1,
+            }
+        );
+    }
+}
@@ -1,13 +1,20 @@
 use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
 use console::style;
 use inquire::Confirm;
-use std::path::PathBuf;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
 use tracing::{info, warn};
 
-use crate::git::provider::github_cli::GitHubCliProvider;
+use crate::cache::{CachedRepoListing, RepositoryCache};
+use crate::git::provider::github_cli::{
+    is_rate_limited, GitHubCliProvider, ListingProbe, OwnerKind, RepositoryListing,
+};
 use crate::git::{GitConfig, Repository};
-use crate::workspace::install::RepositoryInstaller;
+use crate::utils::git::extract_host_from_url;
+use crate::workspace::disk_usage::format_bytes;
+use crate::workspace::install::{RepositoryInstaller, DEFAULT_HOST};
 use crate::workspace::manager::WorkspaceManager;
 
 /// Options for bulk cloning operations
@@ -18,6 +25,8 @@ pub struct BulkCloneOptions {
     pub skip_existing: bool,
     pub custom_path: Option<PathBuf>,
     pub force: bool, // Skip confirmation prompts
+    /// Re-list the target's repositories instead of reusing the cached list
+    pub refresh: bool,
 }
 
 impl Default for BulkCloneOptions {
@@ -28,6 +37,7 @@ impl Default for BulkCloneOptions {
             skip_existing: true,
             custom_path: None,
             force: false,
+            refresh: false,
         }
     }
 }
@@ -41,11 +51,40 @@ pub enum TargetType {
     Unknown,
 }
 
-/// Result of filtering repositories before cloning
-#[derive(Debug, Clone)]
-pub struct FilterResult {
-    pub to_clone: Vec<Repository>,
+/// What a bulk clone would do, resolved before anything is cloned
+#[derive(Debug, Clone, Serialize)]
+pub struct ClonePlan {
+    pub target: String,
+    pub total_discovered: usize,
+    pub to_clone: Vec<PlannedClone>,
     pub skipped: Vec<SkippedRepository>,
+    /// When the repository list was fetched, if it came from the cache
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cached_at: Option<DateTime<Utc>>,
+}
+
+/// Repository that would be cloned, and where
+#[derive(Debug, Clone, Serialize)]
+pub struct PlannedClone {
+    pub name: String,
+    pub url: String,
+    pub destination: PathBuf,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size_kb: Option<u64>,
+    #[serde(skip)]
+    pub repository: Repository,
+}
+
+impl ClonePlan {
+    /// Sum of the sizes the provider reported, and whether every
+    /// repository to clone had one
+    pub fn estimated_size_kb(&self) -> Option<(u64, bool)> {
+        let known: Vec<u64> = self.to_clone.iter().filter_map(|p| p.size_kb).collect();
+        if known.is_empty() {
+            return None;
+        }
+        Some((known.iter().sum(), known.len() == self.to_clone.len()))
+    }
 }
 
 /// Result of a bulk clone operation
@@ -60,25 +99,37 @@ pub struct BulkCloneResult {
 }
 
 /// Repository that was skipped during bulk cloning
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct SkippedRepository {
     pub name: String,
+    #[serde(flatten)]
     pub reason: SkipReason,
 }
 
 /// Reason why a repository was skipped
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "reason", content = "detail", rename_all = "snake_case")]
 #[allow(dead_code)]
 pub enum SkipReason {
-    #[allow(dead_code)]
     AlreadyExists(PathBuf),
-    #[allow(dead_code)]
     ExcludedByPattern(String),
     NotIncludedByPattern,
     Fork,
     Archived,
 }
 
+impl SkipReason {
+    pub fn describe(&self) -> String {
+        match self {
+            SkipReason::AlreadyExists(path) => format!("already exists at {}", path.display()),
+            SkipReason::ExcludedByPattern(pattern) => format!("excluded by '{pattern}'"),
+            SkipReason::NotIncludedByPattern => "not matched by --include".to_string(),
+            SkipReason::Fork => "fork".to_string(),
+            SkipReason::Archived => "archived".to_string(),
+        }
+    }
+}
+
 /// Repository that failed to clone
 #[derive(Debug, Clone)]
 pub struct FailedRepository {
@@ -101,6 +152,7 @@ pub struct BulkCloneProgress {
 pub enum CloneStatus {
     Discovering,
     Confirming,
+    RateLimited,
     Cloning,
     AddingToWorkspace,
     Complete,
@@ -141,10 +193,7 @@ impl BulkCloneCommand {
         workspace_manager: &mut WorkspaceManager,
         git_config: &GitConfig,
     ) -> Result<BulkCloneResult> {
-        let github_cli =
-            GitHubCliProvider::new().context("Failed to initialize GitHub CLI provider")?;
-
-        // Step 1: Discover repositories
+        // Steps 1 and 2: Discover and filter repositories
         Self::report_progress(BulkCloneProgress {
             current: 0,
             total: 0,
@@ -152,29 +201,27 @@ impl BulkCloneCommand {
             status: CloneStatus::Discovering,
         });
 
-        let repositories = Self::discover_repositories(&github_cli, &target).await?;
-
-        if repositories.is_empty() {
-            anyhow::bail!("No repositories found for '{}'", target);
-        }
-
-        // Step 2: Filter repositories
-        let filter_result =
-            Self::filter_repositories(&repositories, &options, workspace_manager, git_config)?;
+        let plan = Self::plan(
+            &target,
+            &options,
+            workspace_manager.get_workspace_root(),
+            git_config,
+        )
+        .await?;
 
-        if filter_result.to_clone.is_empty() {
+        if plan.to_clone.is_empty() {
             anyhow::bail!("No repositories remaining after filtering");
         }
 
         // Step 3: Show confirmation unless forced
         if !options.force {
-            Self::show_confirmation(&filter_result, &target, repositories.len())?;
+            Self::show_confirmation(&plan)?;
         }
 
         // Step 4: Clone repositories in serial
         let result = Self::clone_repositories_serial(
-            filter_result.to_clone,
-            filter_result.skipped,
+            plan.to_clone.into_iter().map(|p| p.repository).collect(),
+            plan.skipped,
             options,
             workspace_manager,
             git_config,
@@ -187,68 +234,214 @@ impl BulkCloneCommand {
         Ok(result)
     }
 
-    /// Discover all repositories for a target (user or organization)
+    /// Resolve what cloning `target` would do without cloning anything
+    pub async fn plan(
+        target: &str,
+        options: &BulkCloneOptions,
+        workspace_root: &Path,
+        git_config: &GitConfig,
+    ) -> Result<ClonePlan> {
+        let github_cli = GitHubCliProvider::new()
+            .context("Failed to initialize GitHub CLI provider")?
+            .on_rate_limit(|wait| {
+                Self::report_progress(BulkCloneProgress {
+                    current: 0,
+                    total: 0,
+                    current_repo: format!(
+                        "Rate limited by GitHub, retrying in {}s...",
+                        wait.as_secs()
+                    ),
+                    status: CloneStatus::RateLimited,
+                })
+            });
+        let (repositories, cached_at) =
+            Self::discover_repositories(&github_cli, target, options.refresh).await?;
+
+        if repositories.is_empty() {
+            anyhow::bail!("No repositories found for '{}'", target);
+        }
+
+        let mut plan =
+            Self::filter_repositories(target, &repositories, options, workspace_root, git_config);
+        plan.cached_at = cached_at;
+        Ok(plan)
+    }
+
+    /// Discover all repositories for a target (user or organization), reusing
+    /// the cached list while GitHub reports it unchanged. Also returns when
+    /// the list was fetched if it came from the cache.
     async fn discover_repositories(
         github_cli: &GitHubCliProvider,
         target: &str,
-    ) -> Result<Vec<Repository>> {
-        // Try as organization first, then as user
-        match github_cli.get_organization_repositories(target).await {
-            Ok(repos) => {
+        refresh: bool,
+    ) -> Result<(Vec<Repository>, Option<DateTime<Utc>>)> {
+        let cache = Self::listing_cache().await;
+
+        if let (Some(cache), false) = (&cache, refresh) {
+            if let Some(cached) = Self::revalidate_listing(github_cli, cache, target).await? {
                 info!(
-                    "Found {} repositories for organization '{}'",
-                    repos.len(),
+                    "Using {} cached repositories for '{}'",
+                    cached.0.len(),
                     target
                 );
-                Ok(repos)
+                return Ok((cached.0, Some(cached.1)));
             }
-            Err(_) => {
-                // Try as user
-                match github_cli.get_user_repositories(target).await {
-                    Ok(repos) => {
-                        info!("Found {} repositories for user '{}'", repos.len(), target);
-                        Ok(repos)
-                    }
-                    Err(e) => {
-                        anyhow::bail!("Failed to find repositories for '{}': {}", target, e);
-                    }
+        }
+
+        // Try as organization first, then as user
+        let (kind, listing) = match github_cli
+            .list_repositories(OwnerKind::Organization, target)
+            .await
+        {
+            Ok(listing) => (OwnerKind::Organization, listing),
+            Err(e) if is_rate_limited(&e) => return Err(e),
+            Err(_) => match github_cli.list_repositories(OwnerKind::User, target).await {
+                Ok(listing) => (OwnerKind::User, listing),
+                Err(e) if is_rate_limited(&e) => return Err(e),
+                Err(e) => {
+                    anyhow::bail!("Failed to find repositories for '{}': {}", target, e);
                 }
+            },
+        };
+        info!(
+            "Found {} repositories for {} '{}'",
+            listing.repositories.len(),
+            kind.as_str(),
+            target
+        );
+
+        if let Some(cache) = &cache {
+            if let Err(e) =
+                Self::store_listing(cache, target, kind, &listing, None, Utc::now()).await
+            {
+                warn!("Failed to cache repository list for '{}': {}", target, e);
+            }
+        }
+
+        Ok((listing.repositories, None))
+    }
+
+    /// Repository cache holding org and user listings, if it can be opened
+    async fn listing_cache() -> Option<RepositoryCache> {
+        let cache_dir = crate::workspace::constants::get_cache_dir();
+        let cache = RepositoryCache::new(cache_dir.join("repositories.db"));
+        let opened = async {
+            tokio::fs::create_dir_all(&cache_dir).await?;
+            cache.initialize().await
+        };
+        match opened.await {
+            Ok(()) => Some(cache),
+            Err(e) => {
+                warn!("Repository list cache unavailable: {}", e);
+                None
             }
         }
     }
 
+    /// The cached listing for `target` and when it was fetched, if GitHub
+    /// reports it unchanged. A rate limit on the check falls back to the
+    /// cached listing rather than failing.
+    async fn revalidate_listing(
+        github_cli: &GitHubCliProvider,
+        cache: &RepositoryCache,
+        target: &str,
+    ) -> Result<Option<(Vec<Repository>, DateTime<Utc>)>> {
+        let Some(cached) = cache.get_repo_listing(target).await? else {
+            return Ok(None);
+        };
+        let (Some(kind), Ok(repositories)) = (
+            OwnerKind::parse(&cached.kind),
+            serde_json::from_str::<Vec<Repository>>(&cached.repositories_json),
+        ) else {
+            return Ok(None);
+        };
+
+        match github_cli
+            .probe_repository_listing(kind, target, cached.etag.as_deref())
+            .await
+        {
+            Ok(ListingProbe::NotModified) => Ok(Some((repositories, cached.fetched_at))),
+            Ok(ListingProbe::Changed { etag, updated_at }) if updated_at == cached.updated_at => {
+                // Same contents under a new ETag; remember it so the next check is a 304
+                let listing = RepositoryListing {
+                    repositories,
+                    updated_at,
+                };
+                Self::store_listing(cache, target, kind, &listing, etag, cached.fetched_at).await?;
+                Ok(Some((listing.repositories, cached.fetched_at)))
+            }
+            Ok(ListingProbe::Changed { .. }) => Ok(None),
+            Err(e) if is_rate_limited(&e) => {
+                warn!(
+                    "{}; using repositories for '{}' cached at {}",
+                    e, target, cached.fetched_at
+                );
+                Ok(Some((repositories, cached.fetched_at)))
+            }
+            Err(e) => {
+                warn!(
+                    "Failed to check cached repositories for '{}': {}",
+                    target, e
+                );
+                Ok(None)
+            }
+        }
+    }
+
+    async fn store_listing(
+        cache: &RepositoryCache,
+        target: &str,
+        kind: OwnerKind,
+        listing: &RepositoryListing,
+        etag: Option<String>,
+        fetched_at: DateTime<Utc>,
+    ) -> Result<()> {
+        cache
+            .cache_repo_listing(&CachedRepoListing {
+                owner: target.to_string(),
+                kind: kind.as_str().to_string(),
+                etag,
+                updated_at: listing.updated_at.clone(),
+                repositories_json: serde_json::to_string(&listing.repositories)?,
+                fetched_at,
+            })
+            .await
+    }
+
     /// Filter repositories based on patterns and existing state
     fn filter_repositories(
+        target: &str,
         repositories: &[Repository],
         options: &BulkCloneOptions,
-        workspace_manager: &WorkspaceManager,
+        workspace_root: &Path,
         git_config: &GitConfig,
-    ) -> Result<FilterResult> {
+    ) -> ClonePlan {
         let mut to_clone = Vec::new();
         let mut skipped = Vec::new();
-        let workspace_root = workspace_manager.get_workspace_root();
 
         for repo in repositories {
-            // Check if already exists locally
-            if options.skip_existing {
-                // Parse the repository URL to get org and repo name
-                let repo_path = match Self::parse_git_url(&repo.url) {
-                    Ok((org, repo_name)) => {
-                        Self::calculate_install_path(workspace_root, git_config, &org, &repo_name)
-                    }
-                    Err(_) => {
-                        // Fallback to just using the repo name if URL parsing fails
-                        workspace_root.join(&repo.name)
-                    }
-                };
-
-                if repo_path.exists() {
-                    skipped.push(SkippedRepository {
-                        name: repo.full_name.clone(),
-                        reason: SkipReason::AlreadyExists(repo_path),
-                    });
-                    continue;
+            // Parse the repository URL to get org and repo name
+            let repo_path = match Self::parse_git_url(&repo.url) {
+                Ok((org, repo_name)) => Self::calculate_install_path(
+                    workspace_root,
+                    git_config,
+                    &repo.url,
+                    &org,
+                    &repo_name,
+                ),
+                Err(_) => {
+                    // Fallback to just using the repo name if URL parsing fails
+                    workspace_root.join(&repo.name)
                 }
+            };
+
+            // Check if already exists locally
+            if options.skip_existing && repo_path.exists() {
+                skipped.push(SkippedRepository {
+                    name: repo.full_name.clone(),
+                    reason: SkipReason::AlreadyExists(repo_path),
+                });
+                continue;
             }
 
             // Apply exclude patterns
@@ -289,101 +482,112 @@ impl BulkCloneCommand {
                 }
             }
 
-            to_clone.push(repo.clone());
+            to_clone.push(PlannedClone {
+                name: repo.full_name.clone(),
+                url: repo.url.clone(),
+                destination: repo_path,
+                size_kb: repo.size_kb,
+                repository: repo.clone(),
+            });
         }
 
-        Ok(FilterResult { to_clone, skipped })
+        ClonePlan {
+            target: target.to_string(),
+            total_discovered: repositories.len(),
+            to_clone,
+            skipped,
+            cached_at: None,
+        }
     }
 
-    /// Show confirmation dialog for bulk clone operation
-    fn show_confirmation(
-        filter_result: &FilterResult,
-        target: &str,
-        total_discovered: usize,
-    ) -> Result<()> {
-        let repositories = &filter_result.to_clone;
-        let skipped = &filter_result.skipped;
-
+    /// Print what a bulk clone would clone and skip, with destinations and totals
+    pub fn print_plan(plan: &ClonePlan) {
         println!(
             "\n{} {} {}",
             style("📋").blue(),
-            style("Bulk Clone Summary").cyan().bold(),
-            style(format!("- GitHub target '{}'", target)).dim()
-        );
-
-        println!(
-            "Total repositories discovered: {}",
-            style(total_discovered).blue().bold()
+            style("Bulk Clone Plan").cyan().bold(),
+            style(format!("- GitHub target '{}'", plan.target)).dim()
         );
+        if let Some(cached_at) = plan.cached_at {
+            println!(
+                "{}",
+                style(format!(
+                    "Using the repository list fetched {}; pass --refresh to re-list",
+                    cached_at
+                        .with_timezone(&chrono::Local)
+                        .format("%Y-%m-%d %H:%M")
+                ))
+                .dim()
+            );
+        }
 
-        // Show skipped repositories if any
-        if !skipped.is_empty() {
-            let existing_count = skipped
+        if !plan.to_clone.is_empty() {
+            let name_width = plan
+                .to_clone
                 .iter()
-                .filter(|s| matches!(s.reason, SkipReason::AlreadyExists(_)))
-                .count();
-
-            if existing_count > 0 {
+                .map(|p| p.name.len())
+                .max()
+                .unwrap_or(0);
+            println!("\n{} To clone:", style("🔽").blue());
+            for planned in &plan.to_clone {
+                let size = planned
+                    .size_kb
+                    .map(|kb| format!("  {}", style(format_bytes(kb * 1024)).dim()))
+                    .unwrap_or_default();
                 println!(
-                    "{} Already exist locally: {}",
-                    style("✅").green(),
-                    style(existing_count).green().bold()
+                    "  {}  {}{}",
+                    style(format!("{:<name_width$}", planned.name)).cyan(),
+                    style(planned.destination.display()).dim(),
+                    size
                 );
             }
+        }
 
-            let other_skipped = skipped.len() - existing_count;
-            if other_skipped > 0 {
+        if !plan.skipped.is_empty() {
+            let name_width = plan.skipped.iter().map(|s| s.name.len()).max().unwrap_or(0);
+            println!("\n{} Skipped:", style("⏭️").yellow());
+            for skipped in &plan.skipped {
                 println!(
-                    "{} Skipped (patterns/filters): {}",
-                    style("⏭️").yellow(),
-                    style(other_skipped).yellow().bold()
+                    "  {}  {}",
+                    style(format!("{:<name_width$}", skipped.name)).yellow(),
+                    style(skipped.reason.describe()).dim()
                 );
             }
         }
 
-        if repositories.is_empty() {
-            anyhow::bail!("No repositories to clone after filtering");
-        }
+        let existing_count = plan
+            .skipped
+            .iter()
+            .filter(|s| matches!(s.reason, SkipReason::AlreadyExists(_)))
+            .count();
+        let filtered_count = plan.skipped.len() - existing_count;
 
         println!(
-            "{} {} {}",
-            style("📦").blue(),
-            style("To confirm clone in bulk:").cyan(),
-            style(repositories.len()).green().bold()
+            "\n{} to clone, {} already exist, {} filtered out ({} discovered)",
+            style(plan.to_clone.len()).green().bold(),
+            style(existing_count).green(),
+            style(filtered_count).yellow(),
+            style(plan.total_discovered).blue()
         );
 
-        // Show sample repositories that will be cloned
-        println!(
-            "\n{} Sample repositories to clone (showing first 8):",
-            style("🔽").blue()
-        );
-        for (i, repo) in repositories.iter().take(8).enumerate() {
-            let lang = repo.language.as_deref().unwrap_or("unknown");
-            let stars = if repo.stars > 0 {
-                format!(" {}", style(format!("⭐ {}", repo.stars)).dim())
-            } else {
-                String::new()
-            };
-
-            println!(
-                "  {}. {}{} [{}]",
-                i + 1,
-                style(&repo.full_name).cyan(),
-                stars,
-                style(lang).dim()
-            );
-        }
-
-        if repositories.len() > 8 {
-            println!("  ... and {} more repositories", repositories.len() - 8);
+        match plan.estimated_size_kb() {
+            Some((kb, true)) => println!("💾 Estimated size: {}", format_bytes(kb * 1024)),
+            Some((kb, false)) => {
+                println!("💾 Estimated size: at least {}", format_bytes(kb * 1024))
+            }
+            None => {}
         }
+        let estimated_time_min = (plan.to_clone.len() as f64 * 0.5).ceil() as usize; // ~30s per repo
+        println!("⏱️  Estimated time: {} minutes", estimated_time_min);
+    }
 
-        // Calculate estimated size (rough approximation)
-        let estimated_size_mb = repositories.len() * 15; // Rough estimate of 15MB per repo
-        let estimated_time_min = (repositories.len() as f64 * 0.5).ceil() as usize; // ~30s per repo
+    /// Show confirmation dialog for bulk clone operation
+    fn show_confirmation(plan: &ClonePlan) -> Result<()> {
+        Self::print_plan(plan);
 
-        println!("\n💾 Estimated size: ~{} MB", estimated_size_mb);
-        println!("⏱️  Estimated time: {} minutes", estimated_time_min);
+        if plan.to_clone.is_empty() {
+            anyhow::bail!("No repositories to clone after filtering");
+        }
 
         println!(
             "\n{} {}",
@@ -393,7 +597,7 @@ impl BulkCloneCommand {
 
         let proceed = Confirm::new(&format!(
             "Proceed with bulk cloning {} repositories?",
-            repositories.len()
+            plan.to_clone.len()
         ))
         .with_default(false)
         .with_help_message("This operation cannot be easily undone")
@@ -490,7 +694,7 @@ impl BulkCloneCommand {
         );
 
         // Clone without opening or running install commands (fast bulk mode)
-        let installed = installer
+        let mut installed = installer
             .install_from_url_with_options(
                 &repo.url, None,  // Use default path
                 false, // don't open
@@ -498,6 +702,10 @@ impl BulkCloneCommand {
             )
             .await
             .context("Failed to clone repository")?;
+        installed.repository.license = repo
+            .license
+            .as_deref()
+            .and_then(crate::workspace::licenses::normalize);
 
         // Add to workspace configuration (but skip app configuration)
         workspace_manager
@@ -510,8 +718,17 @@ impl BulkCloneCommand {
 
     /// Report progress during bulk clone operation
     fn report_progress(progress: BulkCloneProgress) {
+        if !crate::output::show_progress() {
+            return;
+        }
+
         if progress.total == 0 {
-            print!("\r🔍 {}", progress.current_repo);
+            let icon = match progress.status {
+                CloneStatus::RateLimited => "⏳",
+                _ => "🔍",
+            };
+            // Pad so a shorter message fully covers the previous one
+            print!("\r{} {:<60}", icon, progress.current_repo);
         } else {
             let percent = (progress.current as f64 / progress.total as f64 * 100.0) as usize;
             let bar_length = 20;
@@ -521,6 +738,7 @@ impl BulkCloneCommand {
             let status_icon = match progress.status {
                 CloneStatus::Discovering => "🔍",
                 CloneStatus::Confirming => "❓",
+                CloneStatus::RateLimited => "⏳",
                 CloneStatus::Cloning => "📦",
                 CloneStatus::AddingToWorkspace => "➕",
                 CloneStatus::Complete => "✅",
@@ -592,14 +810,14 @@ impl BulkCloneCommand {
     fn calculate_install_path(
         workspace_root: &std::path::Path,
         git_config: &GitConfig,
+        url: &str,
         org: &str,
         repo: &str,
     ) -> std::path::PathBuf {
-        if git_config.standardize_paths {
-            workspace_root.join(org).join(repo)
-        } else {
-            workspace_root.join(repo)
-        }
+        let host = extract_host_from_url(url).unwrap_or_else(|| DEFAULT_HOST.to_string());
+        git_config
+            .clone_layout
+            .destination(workspace_root, &host, org, repo)
     }
 
     /// Display summary of bulk clone operation
@@ -655,7 +873,10 @@ impl BulkCloneCommand {
 
         let minutes = result.duration.as_secs() / 60;
         let seconds = result.duration.as_secs() % 60;
-        println!("⏱️  Total time: {}m {}s", minutes, seconds);
+        println!(
+            "⏱️  Total time: {}",
+            crate::output::stable_time(format!("{minutes}m {seconds}s"))
+        );
 
         if result.total_cloned > 0 {
             println!("\n{} Next steps:", style("💡").yellow());
@@ -668,3 +889,68 @@ impl BulkCloneCommand {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn repo(name: &str, size_kb: Option<u64>) -> Repository {
+        Repository {
+            id: format!("acme/{name}"),
+            name: name.to_string(),
+            full_name: format!("acme/{name}"),
+            description: None,
+            url: format!("https://github.com/acme/{name}.git"),
+            ssh_url: format!("git@github.com:acme/{name}.git"),
+            stars: 0,
+            language: None,
+            license: None,
+            topics: Vec::new(),
+            size_kb,
+        }
+    }
+
+    #[test]
+    fn test_filter_repositories_builds_plan() {
+        let root = TempDir::new().unwrap();
+        std::fs::create_dir_all(root.path().join("acme/existing")).unwrap();
+        let repositories = vec![
+            repo("api", Some(2048)),
+            repo("web", None),
+            repo("existing", Some(10)),
+            repo("old-tool", Some(10)),
+            repo("docs", Some(10)),
+        ];
+        let options = BulkCloneOptions {
+            exclude_patterns: vec!["old-*".to_string()],
+            include_patterns: vec!["api".to_string(), "web".to_string(), "existing".to_string()],
+            ..Default::default()
+        };
+
+        let plan = BulkCloneCommand::filter_repositories(
+            "acme",
+            &repositories,
+            &options,
+            root.path(),
+            &GitConfig::default(),
+        );
+
+        assert_eq!(plan.total_discovered, 5);
+        let names: Vec<_> = plan.to_clone.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["acme/api", "acme/web"]);
+        assert_eq!(plan.to_clone[0].destination, root.path().join("acme/api"));
+        assert_eq!(plan.estimated_size_kb(), Some((2048, false)));
+
+        let reasons: Vec<_> = plan.skipped.iter().map(|s| &s.reason).collect();
+        assert!(matches!(reasons[0], SkipReason::AlreadyExists(_)));
+        assert!(matches!(reasons[1], SkipReason::ExcludedByPattern(p) if p == "old-*"));
+        assert!(matches!(reasons[2], SkipReason::NotIncludedByPattern));
+
+        let json = serde_json::to_value(&plan).unwrap();
+        assert_eq!(json["skipped"][1]["reason"], "excluded_by_pattern");
+        assert_eq!(json["skipped"][1]["detail"], "old-*");
+        assert_eq!(json["skipped"][2]["reason"], "not_included_by_pattern");
+        assert_eq!(json["to_clone"][0]["size_kb"], 2048);
+    }
+}
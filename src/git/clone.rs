@@ -1,14 +1,21 @@
 use anyhow::Result;
 use colored::*;
 use console::style;
-use inquire::{Confirm, Select};
-use std::path::PathBuf;
+use inquire::validator::Validation;
+use inquire::{Confirm, Select, Text};
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
 
+use crate::display_println;
 use crate::git::bulk_clone::{BulkCloneCommand, BulkCloneOptions};
 use crate::git::provider::github_cli::GitHubCliProvider;
-use crate::git::{GitConfig, Repository};
-use crate::workspace::install::RepositoryInstaller;
+use crate::git::{GitConfig, GitError, OnCollision, Repository};
+use crate::output::exit::CommandError;
+use crate::utils::git::normalize_git_url;
+use crate::workspace::config::repo_abs_path;
+use crate::workspace::install::{remote_of, same_remote, CloneTarget, RepositoryInstaller};
 use crate::workspace::manager::WorkspaceManager;
+use crate::workspace::WorkspaceConfig;
 
 pub struct CloneCommand;
 
@@ -27,15 +34,28 @@ impl CloneCommand {
         // Create installer
         let installer = RepositoryInstaller::new(workspace_root, git_config.clone());
 
+        // Resolve a taken name or directory before cloning
+        let target = installer.target(&url, path)?;
+        let target =
+            match resolve_collision(&installer, target, &url, workspace_manager, git_config).await?
+            {
+                Destination::Clone(target) => target,
+                Destination::Existing(path) => return Ok(path),
+            };
+
         // Clone repository
-        let installed = installer
-            .install_from_url_with_options(&url, path, open, install)
-            .await?;
+        let installed = installer.install_to(&url, &target, open, install).await?;
 
         // Add to workspace configuration
         workspace_manager
             .add_repository(installed.repository.clone())
             .await?;
+        workspace_manager.remember_clone(&url).await;
+        crate::ui::prompts::offer_hook_sync(
+            workspace_manager,
+            std::slice::from_ref(&installed.repository.name),
+        )
+        .await?;
 
         // Execute post-install actions
         if !installed.post_install_actions.is_empty() {
@@ -44,12 +64,12 @@ impl CloneCommand {
                 .await?;
         }
 
-        println!(
+        display_println!(
             "\n{} Repository successfully added to workspace!",
             "🎉".green()
         );
 
-        println!("Path: {}", installed.path.display().to_string().cyan());
+        display_println!("Path: {}", installed.path.display().to_string().cyan());
 
         Ok(installed.path)
     }
@@ -72,14 +92,9 @@ impl CloneCommand {
         )
         .await?;
 
-        // Extract repository name from path
-        let repo_name = cloned_path
-            .file_name()
-            .and_then(|n| n.to_str())
-            .ok_or_else(|| anyhow::anyhow!("Could not determine repository name"))?;
-
         // Run interactive post-clone workflow
-        Self::interactive_post_clone_workflow(repo_name, workspace_manager).await?;
+        let repo_name = configured_name(workspace_manager, &cloned_path)?;
+        Self::interactive_post_clone_workflow(&repo_name, workspace_manager).await?;
 
         Ok(cloned_path)
     }
@@ -89,18 +104,19 @@ impl CloneCommand {
         workspace_manager: &mut WorkspaceManager,
         git_config: &GitConfig,
     ) -> Result<()> {
-        println!(
+        display_println!(
             "\n{} Selected: {}",
             "✅".green(),
             repo.full_name.cyan().bold()
         );
 
         // Use HTTPS URL by default (more universal than SSH)
-        let _cloned_path =
+        let cloned_path =
             Self::execute(repo.url, None, false, false, workspace_manager, git_config).await?;
 
         // Run interactive post-clone workflow
-        Self::interactive_post_clone_workflow(&repo.name, workspace_manager).await?;
+        let repo_name = configured_name(workspace_manager, &cloned_path)?;
+        Self::interactive_post_clone_workflow(&repo_name, workspace_manager).await?;
 
         Ok(())
     }
@@ -110,7 +126,7 @@ impl CloneCommand {
         repo_name: &str,
         workspace_manager: &mut WorkspaceManager,
     ) -> Result<()> {
-        println!("\n{} Repository cloned successfully!", style("🎉").green());
+        display_println!("\n{} Repository cloned successfully!", style("🎉").green());
 
         // Step 1: Ask if they want to configure apps
         let configure_apps = Confirm::new(&format!(
@@ -155,7 +171,7 @@ impl CloneCommand {
             ("iterm2", "iTerm2 - Terminal emulator"),
         ];
 
-        println!(
+        display_println!(
             "\n{} Select an application to configure for this repository:",
             style("📱").green()
         );
@@ -180,7 +196,7 @@ impl CloneCommand {
             .configure_app_for_repo(repo_name, app_name, "default")
             .await?;
 
-        println!(
+        display_println!(
             "{} Configured {} for {}",
             style("✅").green(),
             style(app_name).blue(),
@@ -198,11 +214,11 @@ impl CloneCommand {
         // Get the repository configuration
         if let Some(repo_info) = workspace_manager.get_repository(repo_name) {
             if repo_info.apps.is_empty() {
-                println!(
+                display_println!(
                     "{} No apps configured for this repository",
                     style("⚠️").yellow()
                 );
-                println!("   Configure apps first using the configuration workflow");
+                display_println!("   Configure apps first using the configuration workflow");
                 return Ok(());
             }
 
@@ -222,14 +238,14 @@ impl CloneCommand {
                 .open_repo_with_app(repo_name, &app_to_use)
                 .await?;
 
-            println!(
+            display_println!(
                 "{} Opened {} with {}",
                 style("🚀").green(),
                 style(repo_name).cyan().bold(),
                 style(&app_to_use).blue()
             );
         } else {
-            println!(
+            display_println!(
                 "{} Repository '{}' not found in workspace",
                 style("❌").red(),
                 repo_name
@@ -244,12 +260,14 @@ impl CloneCommand {
 pub struct EnhancedCloneCommand;
 
 impl EnhancedCloneCommand {
-    /// Execute clone with automatic detection of user/org patterns
+    /// Execute clone with automatic detection of user/org patterns. A single
+    /// cloned repository is added to `group` when one is given.
     pub async fn execute_with_detection(
         url_or_target: String,
         app: Option<String>,
         no_configure: bool,
         no_open: bool,
+        group: Option<String>,
         workspace_manager: &mut WorkspaceManager,
         git_config: &GitConfig,
     ) -> Result<()> {
@@ -265,6 +283,7 @@ impl EnhancedCloneCommand {
                     app,
                     no_configure,
                     no_open,
+                    group,
                     workspace_manager,
                     git_config,
                 )
@@ -273,6 +292,12 @@ impl EnhancedCloneCommand {
 
             // Potential user/org name - check if it exists
             (false, false) => {
+                if group.is_some() {
+                    return Err(CommandError::Usage(
+                        "--group needs a repository URL or owner/repo".to_string(),
+                    )
+                    .into());
+                }
                 Self::detect_and_route(
                     url_or_target,
                     app,
@@ -286,6 +311,98 @@ impl EnhancedCloneCommand {
         }
     }
 
+    /// Clone a repository, configure an app with the default template, and open
+    /// it without prompting. A repository that is already in the workspace is
+    /// opened instead of being cloned again. Returns the repository name.
+    pub async fn execute_non_interactive(
+        url: String,
+        app: Option<String>,
+        configure: bool,
+        open: bool,
+        workspace_manager: &mut WorkspaceManager,
+        git_config: &GitConfig,
+    ) -> Result<String> {
+        let repo_name = match Self::find_cloned_repository(&url, workspace_manager) {
+            Some(name) => {
+                display_println!(
+                    "{} '{}' is already in the workspace, skipping clone",
+                    style("ℹ️").blue(),
+                    style(&name).cyan()
+                );
+                name
+            }
+            None => Self::clone_single(url, None, workspace_manager, git_config).await?,
+        };
+
+        if configure {
+            if let Some(app_name) = &app {
+                let already_configured = workspace_manager
+                    .list_apps_for_repo(&repo_name)?
+                    .iter()
+                    .any(|(configured, _)| configured == app_name);
+
+                if !already_configured {
+                    workspace_manager
+                        .configure_app_for_repo(&repo_name, app_name, "default")
+                        .await?;
+                    display_println!(
+                        "{} Configured {} for {}",
+                        style("✓").green().bold(),
+                        style(app_name).blue(),
+                        style(&repo_name).cyan()
+                    );
+                }
+            }
+        }
+
+        if open {
+            let configured_apps = workspace_manager.list_apps_for_repo(&repo_name)?;
+            let app_to_use = match app {
+                Some(app_name) => Some(app_name),
+                None if configured_apps.len() == 1 => Some(configured_apps[0].0.clone()),
+                None => None,
+            };
+
+            match app_to_use {
+                Some(app_name) => {
+                    workspace_manager
+                        .open_repo_with_app(&repo_name, &app_name)
+                        .await?;
+                    display_println!(
+                        "{} Opened {} with {}",
+                        style("🚀").green(),
+                        style(&repo_name).cyan().bold(),
+                        style(&app_name).blue()
+                    );
+                }
+                None => {
+                    display_println!(
+                        "{} No app to open '{}' with; specify one with app=<app>",
+                        style("⚠️").yellow(),
+                        style(&repo_name).cyan()
+                    );
+                }
+            }
+        }
+
+        Ok(repo_name)
+    }
+
+    /// Find a workspace repository that was cloned from `url`
+    fn find_cloned_repository(url: &str, workspace_manager: &WorkspaceManager) -> Option<String> {
+        let target = clone_url_key(url);
+
+        workspace_manager
+            .list_repositories()
+            .iter()
+            .find(|repo| {
+                repo.url
+                    .as_deref()
+                    .is_some_and(|repo_url| clone_url_key(repo_url) == target)
+            })
+            .map(|repo| repo.name.clone())
+    }
+
     /// Detect if target is user/org and route accordingly
     async fn detect_and_route(
         target: String,
@@ -295,13 +412,13 @@ impl EnhancedCloneCommand {
         workspace_manager: &mut WorkspaceManager,
         git_config: &GitConfig,
     ) -> Result<()> {
-        println!("🔍 Analyzing '{}'...", style(&target).cyan());
+        display_println!("🔍 Analyzing '{}'...", style(&target).cyan());
 
         // Initialize GitHub CLI provider
         let github_cli = match GitHubCliProvider::new() {
             Ok(cli) => cli,
             Err(_) => {
-                println!(
+                display_println!(
                     "{} GitHub CLI not available, searching repositories...",
                     style("⚠️").yellow()
                 );
@@ -315,7 +432,7 @@ impl EnhancedCloneCommand {
                 // Get repository count
                 match github_cli.count_repositories(&target).await {
                     Ok(0) => {
-                        println!(
+                        display_println!(
                             "{} '{}' has no public repositories.",
                             style("ℹ️").blue(),
                             style(&target).cyan()
@@ -335,7 +452,7 @@ impl EnhancedCloneCommand {
                         .await
                     }
                     Err(_) => {
-                        println!(
+                        display_println!(
                             "{} Failed to count repositories for '{}', searching instead...",
                             style("⚠️").yellow(),
                             style(&target).cyan()
@@ -345,15 +462,15 @@ impl EnhancedCloneCommand {
                 }
             }
             Ok(false) => {
-                println!(
+                display_println!(
                     "🔍 '{}' not found as a GitHub user or organization.",
                     &target
                 );
-                println!("🔍 Searching repositories for '{}'...", &target);
+                display_println!("🔍 Searching repositories for '{}'...", &target);
                 Self::fallback_to_search(target, workspace_manager, git_config).await
             }
             Err(_) => {
-                println!(
+                display_println!(
                     "{} Failed to check GitHub, searching repositories instead...",
                     style("⚠️").yellow()
                 );
@@ -372,7 +489,7 @@ impl EnhancedCloneCommand {
         workspace_manager: &mut WorkspaceManager,
         git_config: &GitConfig,
     ) -> Result<()> {
-        println!(
+        display_println!(
             "✅ Found GitHub target '{}' with {} repositories",
             style(&target).cyan().bold(),
             style(repo_count).green().bold()
@@ -396,7 +513,7 @@ impl EnhancedCloneCommand {
                 Self::fallback_to_search(target, workspace_manager, git_config).await
             }
             _ => {
-                println!("{} Operation cancelled", style("ℹ️").blue());
+                display_println!("{} Operation cancelled", style("ℹ️").blue());
                 Ok(())
             }
         }
@@ -414,11 +531,12 @@ impl EnhancedCloneCommand {
             skip_existing: true,
             custom_path: None,
             force: false, // Always show confirmation in interactive mode
+            refresh: false,
         };
 
         match BulkCloneCommand::execute(target, options, workspace_manager, git_config).await {
             Ok(result) => {
-                println!(
+                display_println!(
                     "{} Bulk clone completed: {} successful, {} failed",
                     style("✅").green().bold(),
                     result.total_cloned,
@@ -427,7 +545,7 @@ impl EnhancedCloneCommand {
                 Ok(())
             }
             Err(e) => {
-                println!("{} Bulk clone failed: {}", style("❌").red(), e);
+                display_println!("{} Bulk clone failed: {}", style("❌").red(), e);
                 Err(e)
             }
         }
@@ -451,26 +569,441 @@ impl EnhancedCloneCommand {
         app: Option<String>,
         no_configure: bool,
         no_open: bool,
+        group: Option<String>,
         workspace_manager: &mut WorkspaceManager,
         git_config: &GitConfig,
     ) -> Result<()> {
         use crate::ui::workflows::{execute_workflow, CloneWorkflow};
 
+        // Fail before cloning rather than after
+        if let Some(group) = &group {
+            if !workspace_manager
+                .config()
+                .groups
+                .iter()
+                .any(|g| &g.name == group)
+            {
+                return Err(CommandError::not_found("Group", group.as_str()).into());
+            }
+        }
+
         // Use existing workflow system if not skipping steps
         if !no_configure || !no_open {
             let workflow = Box::new(CloneWorkflow {
                 url: url.clone(),
                 app: app.clone(),
+                group,
+                skip_configure: no_configure,
+                on_collision: git_config.on_collision,
             });
 
             execute_workflow(workflow, workspace_manager).await?;
         } else {
             // Just clone without workflow
-            let _cloned_path =
-                CloneCommand::execute(url, None, false, false, workspace_manager, git_config)
-                    .await?;
+            Self::clone_single(url, group.as_deref(), workspace_manager, git_config).await?;
         }
 
         Ok(())
     }
+
+    /// Clone one repository without prompting and add it to `group`,
+    /// returning its configured name
+    async fn clone_single(
+        url: String,
+        group: Option<&str>,
+        workspace_manager: &mut WorkspaceManager,
+        git_config: &GitConfig,
+    ) -> Result<String> {
+        let cloned_path =
+            CloneCommand::execute(url, None, false, false, workspace_manager, git_config).await?;
+        let repo_name = configured_name(workspace_manager, &cloned_path)?;
+        if let Some(group) = group {
+            workspace_manager
+                .add_repo_to_group(&repo_name, group)
+                .await?;
+        }
+        Ok(repo_name)
+    }
+}
+
+/// Name of the configured repository at `path`, such as a clone's
+/// destination, which a collision resolution may have renamed
+pub fn configured_name(workspace_manager: &WorkspaceManager, path: &Path) -> Result<String> {
+    let config = workspace_manager.config();
+    config
+        .repositories
+        .iter()
+        .find(|repo| repo_abs_path(config, repo) == path)
+        .map(|repo| repo.name.clone())
+        .ok_or_else(|| anyhow::anyhow!("Could not determine repository name"))
+}
+
+/// What is in the way of cloning to a target
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Collision {
+    /// A configured repository already has the target's name or directory
+    Configured {
+        name: String,
+        path: PathBuf,
+        same_remote: bool,
+    },
+    /// The directory exists but isn't a configured repository
+    Directory {
+        path: PathBuf,
+        remote: Option<String>,
+        same_remote: bool,
+    },
+}
+
+impl Collision {
+    fn describe(&self) -> String {
+        match self {
+            Collision::Configured { name, path, .. } => format!(
+                "a repository named '{name}' is already configured at {}",
+                path.display()
+            ),
+            Collision::Directory {
+                path,
+                remote: Some(remote),
+                ..
+            } => format!("{} already holds a clone of {remote}", path.display()),
+            Collision::Directory { path, .. } => format!("{} already exists", path.display()),
+        }
+    }
+
+    /// The existing clone, when it is a clone of the requested URL
+    fn same_repository(&self) -> Option<&Path> {
+        match self {
+            Collision::Configured {
+                path,
+                same_remote: true,
+                ..
+            }
+            | Collision::Directory {
+                path,
+                same_remote: true,
+                ..
+            } => Some(path),
+            _ => None,
+        }
+    }
+
+    /// The error `--on-collision fail` stops with
+    fn into_error(self, target: &CloneTarget, git_config: &GitConfig) -> anyhow::Error {
+        match self {
+            Collision::Configured { name, path, .. } => GitError::NameCollision { name, path },
+            Collision::Directory {
+                path,
+                remote: Some(existing),
+                same_remote: false,
+            } => GitError::PathCollision {
+                path,
+                existing,
+                suggestion: if target.custom {
+                    None
+                } else {
+                    git_config.clone_layout.more_nested()
+                },
+            },
+            Collision::Directory { path, .. } => GitError::RepositoryExists { path },
+        }
+        .into()
+    }
+}
+
+/// The configured repository or directory in the way of `target`, if any
+async fn find_collision(
+    target: &CloneTarget,
+    url: &str,
+    config: &WorkspaceConfig,
+) -> Option<Collision> {
+    if let Some(repo) = config
+        .repositories
+        .iter()
+        .find(|repo| repo.name == target.name || repo_abs_path(config, repo) == target.path)
+    {
+        return Some(Collision::Configured {
+            name: repo.name.clone(),
+            path: repo_abs_path(config, repo),
+            same_remote: repo
+                .url
+                .as_deref()
+                .is_some_and(|existing| same_remote(existing, url)),
+        });
+    }
+    if target.path.exists() {
+        let remote = remote_of(&target.path).await;
+        return Some(Collision::Directory {
+            path: target.path.clone(),
+            same_remote: remote
+                .as_deref()
+                .is_some_and(|existing| same_remote(existing, url)),
+            remote,
+        });
+    }
+    None
+}
+
+/// Why `name` can't be used for a clone into `parent`, if it can't
+fn name_taken(config: &WorkspaceConfig, parent: &Path, name: &str) -> Option<String> {
+    let path = parent.join(name);
+    if name.is_empty() || name.contains(['/', '\\']) || name == "." || name == ".." {
+        Some("Enter a directory name without slashes".to_string())
+    } else if let Some(repo) = config
+        .repositories
+        .iter()
+        .find(|repo| repo.name == name || repo_abs_path(config, repo) == path)
+    {
+        Some(format!("'{}' is already configured", repo.name))
+    } else if path.exists() {
+        Some(format!("{} already exists", path.display()))
+    } else {
+        None
+    }
+}
+
+/// How a collision is resolved
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Resolution {
+    Namespace,
+    Rename(String),
+    OpenExisting,
+    Fail,
+}
+
+/// Where a clone goes once collisions are resolved
+enum Destination {
+    Clone(CloneTarget),
+    /// An existing clone of the same repository
+    Existing(PathBuf),
+}
+
+/// Resolve a taken name or directory for `target` with
+/// `git_config.on_collision`, or by asking when it is unset and stdin is a
+/// terminal
+async fn resolve_collision(
+    installer: &RepositoryInstaller,
+    target: CloneTarget,
+    url: &str,
+    workspace_manager: &mut WorkspaceManager,
+    git_config: &GitConfig,
+) -> Result<Destination> {
+    let Some(collision) = find_collision(&target, url, workspace_manager.config()).await else {
+        return Ok(Destination::Clone(target));
+    };
+    let namespaced = match installer.namespaced(&target) {
+        Some(namespaced)
+            if find_collision(&namespaced, url, workspace_manager.config())
+                .await
+                .is_none() =>
+        {
+            Some(namespaced)
+        }
+        _ => None,
+    };
+
+    let resolution = match git_config.on_collision {
+        Some(OnCollision::Namespace) => Resolution::Namespace,
+        Some(OnCollision::Fail) => Resolution::Fail,
+        Some(OnCollision::OpenExisting) => Resolution::OpenExisting,
+        None if std::io::stdin().is_terminal() => prompt_resolution(
+            &collision,
+            &target,
+            namespaced.as_ref(),
+            workspace_manager.config(),
+        )?,
+        None => Resolution::Fail,
+    };
+
+    match resolution {
+        Resolution::Namespace => namespaced.map(Destination::Clone).ok_or_else(|| {
+            CommandError::Usage(format!(
+                "Can't clone {url}: {}, and there is no free namespaced directory for it",
+                collision.describe()
+            ))
+            .into()
+        }),
+        Resolution::Rename(name) => Ok(Destination::Clone(installer.renamed(&target, &name))),
+        Resolution::OpenExisting => {
+            let Some(path) = collision.same_repository().map(Path::to_path_buf) else {
+                return Err(CommandError::Usage(format!(
+                    "Can't open the existing repository instead of cloning {url}: {}, which is a different repository",
+                    collision.describe()
+                ))
+                .into());
+            };
+            if let Collision::Directory { .. } = collision {
+                // A clone vibe doesn't know yet joins the workspace as-is
+                workspace_manager
+                    .add_repository(installer.create_repository_config(&target.name, url, &path))
+                    .await?;
+            }
+            display_println!(
+                "{} {} is already cloned at {}, using it",
+                style("ℹ️").blue(),
+                url,
+                style(path.display()).cyan()
+            );
+            Ok(Destination::Existing(path))
+        }
+        Resolution::Fail => Err(collision.into_error(&target, git_config)),
+    }
+}
+
+/// Ask how to resolve `collision`, offering only the resolutions that apply
+fn prompt_resolution(
+    collision: &Collision,
+    target: &CloneTarget,
+    namespaced: Option<&CloneTarget>,
+    config: &WorkspaceConfig,
+) -> Result<Resolution> {
+    display_println!(
+        "{} Can't clone to {}: {}",
+        style("⚠️").yellow(),
+        style(target.path.display()).cyan(),
+        collision.describe()
+    );
+
+    let mut options = Vec::new();
+    if let Some(path) = collision.same_repository() {
+        options.push((
+            format!("Open the existing clone at {}", path.display()),
+            Resolution::OpenExisting,
+        ));
+    }
+    if let Some(namespaced) = namespaced {
+        options.push((
+            format!(
+                "Clone to {} as '{}'",
+                namespaced.path.display(),
+                namespaced.name
+            ),
+            Resolution::Namespace,
+        ));
+    }
+    options.push((
+        "Choose another directory name".to_string(),
+        Resolution::Rename(String::new()),
+    ));
+    options.push(("Cancel".to_string(), Resolution::Fail));
+
+    let labels: Vec<String> = options.iter().map(|(label, _)| label.clone()).collect();
+    let choice = Select::new("How should this clone be resolved?", labels)
+        .with_help_message("The chosen name is also the repository's name in the workspace")
+        .raw_prompt()?;
+    match options.swap_remove(choice.index).1 {
+        Resolution::Rename(_) => {}
+        resolution => return Ok(resolution),
+    }
+
+    let parent = target
+        .path
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_default();
+    let config = config.clone();
+    let validator_parent = parent.clone();
+    let name = Text::new("Directory name:")
+        .with_default(&format!("{}-{}", target.repo, target.owner))
+        .with_help_message(&format!("Cloned into {}", parent.display()))
+        .with_validator(move |name: &str| {
+            Ok(match name_taken(&config, &validator_parent, name.trim()) {
+                Some(reason) => Validation::Invalid(reason.into()),
+                None => Validation::Valid,
+            })
+        })
+        .prompt()?;
+    Ok(Resolution::Rename(name.trim().to_string()))
+}
+
+/// Comparable form of a clone URL, so SSH/HTTPS and `.git` variants match
+fn clone_url_key(url: &str) -> String {
+    normalize_git_url(url.trim())
+        .trim_end_matches('/')
+        .trim_end_matches(".git")
+        .to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workspace::Repository as ConfigRepository;
+    use tempfile::TempDir;
+
+    fn target(root: &Path, name: &str, dir: &str) -> CloneTarget {
+        CloneTarget {
+            name: name.to_string(),
+            path: root.join(dir),
+            custom: false,
+            host: "github.com".to_string(),
+            owner: "acme".to_string(),
+            repo: "api".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_find_collision_checks_config_and_disk() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        let mut config = WorkspaceConfig::default();
+        config.workspace.root = root.to_path_buf();
+        let mut api = ConfigRepository::new("api", "api");
+        api.url = Some("https://github.com/globex/api".to_string());
+        config.repositories.push(api);
+        let url = "https://github.com/acme/api";
+
+        // The configured `api` sits where a flat clone would go
+        let collision = find_collision(&target(root, "acme/api", "api"), url, &config)
+            .await
+            .unwrap();
+        assert_eq!(
+            collision,
+            Collision::Configured {
+                name: "api".to_string(),
+                path: root.join("api"),
+                same_remote: false
+            }
+        );
+        assert_eq!(collision.same_repository(), None);
+
+        let same = find_collision(
+            &target(root, "api", "elsewhere"),
+            "git@github.com:globex/api.git",
+            &config,
+        )
+        .await
+        .unwrap();
+        assert_eq!(same.same_repository(), Some(root.join("api").as_path()));
+
+        std::fs::create_dir_all(root.join("acme/api")).unwrap();
+        let collision = find_collision(&target(root, "acme/api", "acme/api"), url, &config)
+            .await
+            .unwrap();
+        assert_eq!(
+            collision.describe(),
+            format!("{} already exists", root.join("acme/api").display())
+        );
+        assert!(
+            find_collision(&target(root, "acme-api", "acme-api"), url, &config)
+                .await
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_name_taken() {
+        let dir = TempDir::new().unwrap();
+        let mut config = WorkspaceConfig::default();
+        config.workspace.root = dir.path().to_path_buf();
+        config
+            .repositories
+            .push(ConfigRepository::new("api", "api"));
+        std::fs::create_dir(dir.path().join("web")).unwrap();
+
+        assert!(name_taken(&config, dir.path(), "api").is_some());
+        assert!(name_taken(&config, dir.path(), "web").is_some());
+        assert!(name_taken(&config, dir.path(), "a/b").is_some());
+        assert!(name_taken(&config, dir.path(), "").is_some());
+        assert_eq!(name_taken(&config, dir.path(), "api-acme"), None);
+    }
 }
@@ -0,0 +1,599 @@
+//! Managed git hooks for `vibe git hooks`
+//!
+//! The `hooks` section of the workspace config maps hook names to scripts in
+//! the hooks directory. Syncing a repository copies each script to
+//! `<git dir>/hooks/<name>.vibe-managed` and installs a small wrapper as
+//! `<name>` that first runs the repository's own hook, moved aside to
+//! `<name>.vibe-local`, and then the managed script. Removing the managed
+//! hooks puts the original hooks back. Repositories that set `core.hooksPath`
+//! are left alone, since their hooks usually live in tracked files.
+//!
+//! A hook counts as in sync only while both the `.vibe-managed` copy and the
+//! wrapper match, byte for byte, what sync would write now. A script edited
+//! in the hooks directory or a wrapper changed by hand shows as drifted until
+//! the next sync.
+
+use anyhow::{Context, Result};
+use console::style;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::output::theme::{self, Role};
+use crate::utils::platform::resolve_command;
+use crate::{display_eprintln, display_println};
+
+/// First line after the shebang of every wrapper vibe installs
+const MANAGED_MARKER: &str = "# vibe-workspace managed hook";
+
+/// Suffix of the copied managed script
+const MANAGED_SUFFIX: &str = ".vibe-managed";
+
+/// Suffix of a pre-existing hook moved aside by sync
+const LOCAL_SUFFIX: &str = ".vibe-local";
+
+/// Hooks git feeds on stdin, which the wrapper buffers for both scripts
+const STDIN_HOOKS: [&str; 6] = [
+    "pre-push",
+    "pre-receive",
+    "post-receive",
+    "post-rewrite",
+    "proc-receive",
+    "reference-transaction",
+];
+
+/// A configured hook and the script it installs
+#[derive(Debug, Clone)]
+pub struct ManagedHook {
+    pub name: String,
+    pub content: Vec<u8>,
+}
+
+/// Read the configured hook scripts, resolving relative paths against
+/// `hooks_dir`
+pub fn load_managed_hooks(
+    hooks: &BTreeMap<String, PathBuf>,
+    hooks_dir: &Path,
+) -> Result<Vec<ManagedHook>> {
+    hooks
+        .iter()
+        .map(|(name, path)| {
+            if name.is_empty() || name.contains(['/', '\\']) || name.contains('.') {
+                anyhow::bail!("Invalid hook name '{name}'");
+            }
+            let path = crate::utils::fs::expand_tilde(path);
+            let source = if path.is_absolute() {
+                path
+            } else {
+                hooks_dir.join(path)
+            };
+            let content = fs::read(&source).with_context(|| {
+                format!("Failed to read {name} hook script {}", source.display())
+            })?;
+            Ok(ManagedHook {
+                name: name.clone(),
+                content,
+            })
+        })
+        .collect()
+}
+
+/// How a managed hook is installed in a repository
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HookState {
+    InSync,
+    /// Installed, but the script or wrapper differs from the managed source
+    Drifted,
+    NotInstalled,
+}
+
+/// One hook's state in one repository
+#[derive(Debug, Clone, Serialize)]
+pub struct HookStatus {
+    pub hook: String,
+    pub state: HookState,
+    /// Whether the repository's own hook runs first
+    pub chained: bool,
+}
+
+/// Managed hook states for one repository
+#[derive(Debug, Clone, Serialize)]
+pub struct RepoHooks {
+    pub repository: String,
+    pub hooks: Vec<HookStatus>,
+    /// Why the repository was left alone
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skipped: Option<String>,
+}
+
+impl RepoHooks {
+    pub fn drifted(&self) -> impl Iterator<Item = &HookStatus> {
+        self.hooks.iter().filter(|h| h.state != HookState::InSync)
+    }
+}
+
+/// What sync or remove did in one repository
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HookAction {
+    Synced,
+    Removed,
+    Skipped,
+    Failed,
+}
+
+/// Result of syncing or removing managed hooks in one repository
+#[derive(Debug, Clone, Serialize)]
+pub struct RepoHookResult {
+    pub repository: String,
+    pub action: HookAction,
+    /// Hooks installed or removed
+    pub hooks: Vec<String>,
+    /// Skip reason or error
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+impl RepoHookResult {
+    fn new(
+        repository: &str,
+        action: HookAction,
+        hooks: Vec<String>,
+        detail: Option<String>,
+    ) -> Self {
+        Self {
+            repository: repository.to_string(),
+            action,
+            hooks,
+            detail,
+        }
+    }
+}
+
+/// Install `hooks` in each `(name, path)` repository
+pub fn sync_repositories(
+    targets: &[(String, PathBuf)],
+    hooks: &[ManagedHook],
+) -> Vec<RepoHookResult> {
+    let names: Vec<String> = hooks.iter().map(|hook| hook.name.clone()).collect();
+    targets
+        .iter()
+        .map(|(name, path)| match repo_hooks_dir(path) {
+            Ok(Ok(hooks_dir)) => match sync_hooks(&hooks_dir, hooks) {
+                Ok(()) => RepoHookResult::new(name, HookAction::Synced, names.clone(), None),
+                Err(e) => RepoHookResult::new(
+                    name,
+                    HookAction::Failed,
+                    Vec::new(),
+                    Some(format!("{e:#}")),
+                ),
+            },
+            Ok(Err(reason)) => {
+                RepoHookResult::new(name, HookAction::Skipped, Vec::new(), Some(reason))
+            }
+            Err(e) => {
+                RepoHookResult::new(name, HookAction::Failed, Vec::new(), Some(format!("{e:#}")))
+            }
+        })
+        .collect()
+}
+
+/// Remove managed hooks from each `(name, path)` repository
+pub fn remove_from_repositories(targets: &[(String, PathBuf)]) -> Vec<RepoHookResult> {
+    targets
+        .iter()
+        .map(|(name, path)| match repo_hooks_dir(path) {
+            Ok(Ok(hooks_dir)) => match remove_hooks(&hooks_dir) {
+                Ok(removed) => RepoHookResult::new(name, HookAction::Removed, removed, None),
+                Err(e) => RepoHookResult::new(
+                    name,
+                    HookAction::Failed,
+                    Vec::new(),
+                    Some(format!("{e:#}")),
+                ),
+            },
+            Ok(Err(reason)) => {
+                RepoHookResult::new(name, HookAction::Skipped, Vec::new(), Some(reason))
+            }
+            Err(e) => {
+                RepoHookResult::new(name, HookAction::Failed, Vec::new(), Some(format!("{e:#}")))
+            }
+        })
+        .collect()
+}
+
+/// Managed hook states in each `(name, path)` repository
+pub fn repository_statuses(targets: &[(String, PathBuf)], hooks: &[ManagedHook]) -> Vec<RepoHooks> {
+    targets
+        .iter()
+        .map(|(name, path)| {
+            let (hooks, skipped) = match repo_hooks_dir(path) {
+                Ok(Ok(hooks_dir)) => (hook_statuses(&hooks_dir, hooks), None),
+                Ok(Err(reason)) => (Vec::new(), Some(reason)),
+                Err(e) => (Vec::new(), Some(format!("{e:#}"))),
+            };
+            RepoHooks {
+                repository: name.clone(),
+                hooks,
+                skipped,
+            }
+        })
+        .collect()
+}
+
+/// Print sync or remove results, one line per repository
+pub fn print_results(results: &[RepoHookResult]) {
+    for result in results {
+        match result.action {
+            HookAction::Synced | HookAction::Removed => {
+                let verb = if result.action == HookAction::Synced {
+                    "installed"
+                } else {
+                    "removed"
+                };
+                let hooks = if result.hooks.is_empty() {
+                    "none".to_string()
+                } else {
+                    result.hooks.join(", ")
+                };
+                display_println!(
+                    "  {} {}: {} {}",
+                    style("✓").green(),
+                    result.repository,
+                    verb,
+                    hooks
+                )
+            }
+            HookAction::Skipped => display_println!(
+                "  {} {}: {}",
+                style("-").dim(),
+                result.repository,
+                style(format!(
+                    "skipped, {}",
+                    result.detail.as_deref().unwrap_or("")
+                ))
+                .dim()
+            ),
+            HookAction::Failed => display_eprintln!(
+                "  {} {}: {}",
+                style("✗").red(),
+                result.repository,
+                result.detail.as_deref().unwrap_or("failed")
+            ),
+        }
+    }
+}
+
+/// Print the state of each managed hook per repository
+pub fn print_statuses(repos: &[RepoHooks]) {
+    for repo in repos {
+        if let Some(reason) = &repo.skipped {
+            display_println!(
+                "{} {}",
+                style(&repo.repository).cyan(),
+                style(format!("(skipped: {reason})")).dim()
+            );
+            continue;
+        }
+        display_println!("{}", style(&repo.repository).cyan());
+        for hook in &repo.hooks {
+            let state = match hook.state {
+                HookState::InSync => theme::paint(Role::Success, "in sync"),
+                HookState::Drifted => theme::paint(Role::Warning, "drifted"),
+                HookState::NotInstalled => theme::paint(Role::Dim, "not installed"),
+            };
+            let chained = if hook.chained {
+                " (chains repository hook)"
+            } else {
+                ""
+            };
+            display_println!("  {:<20} {}{}", hook.hook, state, chained);
+        }
+    }
+}
+
+/// The hooks directory git uses for `repo`, or the reason vibe won't manage it
+pub fn repo_hooks_dir(repo: &Path) -> Result<std::result::Result<PathBuf, String>> {
+    if !repo.exists() {
+        return Ok(Err("not cloned".to_string()));
+    }
+
+    let hooks_path = Command::new(resolve_command("git"))
+        .args(["config", "--get", "core.hooksPath"])
+        .current_dir(repo)
+        .output()
+        .context("Failed to run git config")?;
+    let hooks_path = String::from_utf8_lossy(&hooks_path.stdout)
+        .trim()
+        .to_string();
+    if !hooks_path.is_empty() {
+        return Ok(Err(format!("core.hooksPath is set to {hooks_path}")));
+    }
+
+    let output = Command::new(resolve_command("git"))
+        .args(["rev-parse", "--git-common-dir"])
+        .current_dir(repo)
+        .output()
+        .context("Failed to run git rev-parse")?;
+    if !output.status.success() {
+        return Ok(Err("not a git repository".to_string()));
+    }
+    let git_dir = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+    Ok(Ok(repo.join(git_dir).join("hooks")))
+}
+
+/// The wrapper installed as `name`
+fn wrapper_script(name: &str) -> String {
+    let local = format!("\"$hook_dir/{name}{LOCAL_SUFFIX}\"");
+    let managed = format!("\"$hook_dir/{name}{MANAGED_SUFFIX}\"");
+    let mut script = format!(
+        "#!/bin/sh\n{MANAGED_MARKER}\n\
+         # Runs this repository's own {name} hook, then the workspace's.\n\
+         # Edit the managed script in vibe's hooks directory, not this file.\n\
+         hook_dir=$(dirname \"$0\")\n"
+    );
+    if STDIN_HOOKS.contains(&name) {
+        script.push_str(&format!(
+            "input=$(cat)\n\
+             replay() {{ [ -n \"$input\" ] && printf '%s\\n' \"$input\"; }}\n\
+             if [ -x {local} ]; then\n    replay | {local} \"$@\" || exit $?\nfi\n\
+             replay | exec {managed} \"$@\"\n"
+        ));
+    } else {
+        script.push_str(&format!(
+            "if [ -x {local} ]; then\n    {local} \"$@\" || exit $?\nfi\n\
+             exec {managed} \"$@\"\n"
+        ));
+    }
+    script
+}
+
+fn is_managed_wrapper(path: &Path) -> bool {
+    fs::read_to_string(path)
+        .map(|content| content.lines().nth(1) == Some(MANAGED_MARKER))
+        .unwrap_or(false)
+}
+
+fn write_executable(path: &Path, content: &[u8]) -> Result<()> {
+    fs::write(path, content).with_context(|| format!("Failed to write {}", path.display()))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(path, fs::Permissions::from_mode(0o755))
+            .with_context(|| format!("Failed to make {} executable", path.display()))?;
+    }
+    Ok(())
+}
+
+/// Names of managed hooks installed in `hooks_dir`
+fn installed_hook_names(hooks_dir: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(hooks_dir) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            entry
+                .file_name()
+                .to_str()
+                .and_then(|name| name.strip_suffix(MANAGED_SUFFIX))
+                .map(str::to_string)
+        })
+        .collect();
+    names.sort();
+    names
+}
+
+/// State of each managed hook in `hooks_dir`
+pub fn hook_statuses(hooks_dir: &Path, hooks: &[ManagedHook]) -> Vec<HookStatus> {
+    hooks
+        .iter()
+        .map(|hook| {
+            let wrapper = hooks_dir.join(&hook.name);
+            let managed = hooks_dir.join(format!("{}{MANAGED_SUFFIX}", hook.name));
+            let state = if !is_managed_wrapper(&wrapper) || !managed.exists() {
+                HookState::NotInstalled
+            } else if fs::read(&managed).ok().as_deref() != Some(hook.content.as_slice())
+                || fs::read_to_string(&wrapper).ok() != Some(wrapper_script(&hook.name))
+            {
+                HookState::Drifted
+            } else {
+                HookState::InSync
+            };
+            HookStatus {
+                hook: hook.name.clone(),
+                state,
+                chained: hooks_dir
+                    .join(format!("{}{LOCAL_SUFFIX}", hook.name))
+                    .exists(),
+            }
+        })
+        .collect()
+}
+
+/// Install `hooks` into `hooks_dir`, chaining any existing hooks, and remove
+/// managed hooks that are no longer configured
+pub fn sync_hooks(hooks_dir: &Path, hooks: &[ManagedHook]) -> Result<()> {
+    fs::create_dir_all(hooks_dir)
+        .with_context(|| format!("Failed to create {}", hooks_dir.display()))?;
+
+    for hook in hooks {
+        let wrapper = hooks_dir.join(&hook.name);
+        let local = hooks_dir.join(format!("{}{LOCAL_SUFFIX}", hook.name));
+        if wrapper.exists() && !is_managed_wrapper(&wrapper) {
+            if local.exists() {
+                anyhow::bail!(
+                    "{} was replaced after vibe chained {}; merge them by hand",
+                    wrapper.display(),
+                    local.display()
+                );
+            }
+            fs::rename(&wrapper, &local)
+                .with_context(|| format!("Failed to move aside {}", wrapper.display()))?;
+        }
+        write_executable(
+            &hooks_dir.join(format!("{}{MANAGED_SUFFIX}", hook.name)),
+            &hook.content,
+        )?;
+        write_executable(&wrapper, wrapper_script(&hook.name).as_bytes())?;
+    }
+
+    for name in installed_hook_names(hooks_dir) {
+        if !hooks.iter().any(|hook| hook.name == name) {
+            remove_hook(hooks_dir, &name)?;
+        }
+    }
+    Ok(())
+}
+
+/// Remove every managed hook from `hooks_dir`, restoring chained hooks.
+/// Returns the names of the removed hooks.
+pub fn remove_hooks(hooks_dir: &Path) -> Result<Vec<String>> {
+    let names = installed_hook_names(hooks_dir);
+    for name in &names {
+        remove_hook(hooks_dir, name)?;
+    }
+    Ok(names)
+}
+
+fn remove_hook(hooks_dir: &Path, name: &str) -> Result<()> {
+    let wrapper = hooks_dir.join(name);
+    if is_managed_wrapper(&wrapper) {
+        fs::remove_file(&wrapper)
+            .with_context(|| format!("Failed to remove {}", wrapper.display()))?;
+    }
+    let managed = hooks_dir.join(format!("{name}{MANAGED_SUFFIX}"));
+    if managed.exists() {
+        fs::remove_file(&managed)
+            .with_context(|| format!("Failed to remove {}", managed.display()))?;
+    }
+    let local = hooks_dir.join(format!("{name}{LOCAL_SUFFIX}"));
+    if local.exists() && !wrapper.exists() {
+        fs::rename(&local, &wrapper)
+            .with_context(|| format!("Failed to restore {}", wrapper.display()))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn hook(name: &str, content: &str) -> ManagedHook {
+        ManagedHook {
+            name: name.to_string(),
+            content: content.as_bytes().to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_sync_chains_existing_hook_and_remove_restores_it() {
+        let temp = TempDir::new().unwrap();
+        let hooks_dir = temp.path();
+        fs::write(hooks_dir.join("pre-commit"), "#!/bin/sh\necho local\n").unwrap();
+
+        let hooks = vec![hook("pre-commit", "#!/bin/sh\necho managed\n")];
+        sync_hooks(hooks_dir, &hooks).unwrap();
+        assert_eq!(
+            fs::read_to_string(hooks_dir.join("pre-commit.vibe-local")).unwrap(),
+            "#!/bin/sh\necho local\n"
+        );
+        let statuses = hook_statuses(hooks_dir, &hooks);
+        assert_eq!(statuses[0].state, HookState::InSync);
+        assert!(statuses[0].chained);
+
+        // Syncing again keeps the chained hook instead of wrapping the wrapper
+        sync_hooks(hooks_dir, &hooks).unwrap();
+        assert!(fs::read_to_string(hooks_dir.join("pre-commit.vibe-local"))
+            .unwrap()
+            .contains("echo local"));
+
+        assert_eq!(remove_hooks(hooks_dir).unwrap(), vec!["pre-commit"]);
+        assert_eq!(
+            fs::read_to_string(hooks_dir.join("pre-commit")).unwrap(),
+            "#!/bin/sh\necho local\n"
+        );
+        assert!(!hooks_dir.join("pre-commit.vibe-managed").exists());
+    }
+
+    #[test]
+    fn test_drift_and_unconfigured_hooks() {
+        let temp = TempDir::new().unwrap();
+        let hooks_dir = temp.path();
+        sync_hooks(
+            hooks_dir,
+            &[hook("pre-commit", "v1"), hook("commit-msg", "lint")],
+        )
+        .unwrap();
+
+        let hooks = vec![hook("pre-commit", "v2")];
+        assert_eq!(
+            hook_statuses(hooks_dir, &hooks)[0].state,
+            HookState::Drifted
+        );
+
+        sync_hooks(hooks_dir, &hooks).unwrap();
+        assert_eq!(hook_statuses(hooks_dir, &hooks)[0].state, HookState::InSync);
+        assert!(!hooks_dir.join("commit-msg").exists());
+        assert!(!hooks_dir.join("commit-msg.vibe-managed").exists());
+    }
+
+    #[test]
+    fn test_stdin_hooks_replay_input() {
+        assert!(wrapper_script("pre-push").contains("input=$(cat)"));
+        assert!(!wrapper_script("pre-commit").contains("input=$(cat)"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_wrapper_runs_local_then_managed() {
+        let temp = TempDir::new().unwrap();
+        let hooks_dir = temp.path();
+        let log = hooks_dir.join("log");
+        write_executable(
+            &hooks_dir.join("pre-push"),
+            format!("#!/bin/sh\necho \"local $1 $(cat)\" >> {}\n", log.display()).as_bytes(),
+        )
+        .unwrap();
+        let hooks = vec![hook(
+            "pre-push",
+            &format!(
+                "#!/bin/sh\necho \"managed $1 $(cat)\" >> {}\n",
+                log.display()
+            ),
+        )];
+        sync_hooks(hooks_dir, &hooks).unwrap();
+
+        let mut child = Command::new(hooks_dir.join("pre-push"))
+            .arg("origin")
+            .stdin(std::process::Stdio::piped())
+            .spawn()
+            .unwrap();
+        {
+            use std::io::Write;
+            child.stdin.take().unwrap().write_all(b"refs\n").unwrap();
+        }
+        assert!(child.wait().unwrap().success());
+        assert_eq!(
+            fs::read_to_string(&log).unwrap(),
+            "local origin refs\nmanaged origin refs\n"
+        );
+    }
+
+    #[test]
+    fn test_load_managed_hooks_resolves_relative_paths() {
+        let temp = TempDir::new().unwrap();
+        fs::write(temp.path().join("lint.sh"), "#!/bin/sh\n").unwrap();
+        let mut config = BTreeMap::new();
+        config.insert("commit-msg".to_string(), PathBuf::from("lint.sh"));
+        let hooks = load_managed_hooks(&config, temp.path()).unwrap();
+        assert_eq!(hooks[0].content, b"#!/bin/sh\n");
+
+        config.insert("../evil".to_string(), PathBuf::from("lint.sh"));
+        assert!(load_managed_hooks(&config, temp.path()).is_err());
+    }
+}
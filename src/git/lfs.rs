@@ -0,0 +1,194 @@
+//! Git LFS detection for clones and diagnostics
+//!
+//! A repository uses LFS when its root `.gitattributes` routes paths through
+//! `filter=lfs`. Cloning one without `git-lfs` installed, or without the LFS
+//! filter configured, leaves small pointer files where the real content
+//! should be, so clones check for this and either pull the content or say
+//! how to.
+
+use anyhow::{Context, Result};
+use colored::*;
+use std::path::Path;
+use tokio::process::Command;
+use tracing::Instrument;
+
+use crate::display_println;
+use crate::git::pull_requests::github_repo_from_url;
+use crate::output::timings;
+use crate::utils::platform::resolve_command;
+use crate::workspace::operations::execute_git_command;
+
+/// Command that installs `git lfs` on this platform
+pub fn install_hint() -> &'static str {
+    if cfg!(target_os = "macos") {
+        "brew install git-lfs"
+    } else if cfg!(target_os = "windows") {
+        "winget install GitHub.GitLFS"
+    } else {
+        "sudo apt install git-lfs"
+    }
+}
+
+/// Whether `.gitattributes` contents send any path through the LFS filter
+pub fn attributes_use_lfs(contents: &str) -> bool {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('#'))
+        .any(|line| {
+            line.split_whitespace()
+                .skip(1)
+                .any(|attr| attr == "filter=lfs")
+        })
+}
+
+/// Whether the checkout at `repo` uses LFS, going by its root `.gitattributes`
+pub async fn uses_lfs(repo: &Path) -> bool {
+    tokio::fs::read_to_string(repo.join(".gitattributes"))
+        .await
+        .is_ok_and(|contents| attributes_use_lfs(&contents))
+}
+
+/// Version line of `git lfs`, or `None` when it isn't installed
+pub async fn lfs_version() -> Option<String> {
+    let output = Command::new(resolve_command("git"))
+        .args(["lfs", "version"])
+        .output()
+        .await
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Whether the LFS smudge filter is configured, which `git lfs install` does
+pub async fn filter_configured(repo: &Path) -> bool {
+    execute_git_command(repo, &["config", "--get", "filter.lfs.smudge"])
+        .await
+        .is_ok_and(|value| !value.is_empty())
+}
+
+/// Ask GitHub whether a repository uses LFS before cloning it. `None` when
+/// the URL isn't on GitHub or `gh` can't answer.
+pub async fn github_repo_uses_lfs(url: &str) -> Option<bool> {
+    let repo = github_repo_from_url(url)?;
+    let output = Command::new(resolve_command("gh"))
+        .args([
+            "api",
+            "-H",
+            "Accept: application/vnd.github.raw",
+            &format!("repos/{repo}/contents/.gitattributes"),
+        ])
+        .output()
+        .instrument(timings::phase(
+            timings::PROVIDER,
+            format!("gh api repos/{repo}/contents/.gitattributes"),
+        ))
+        .await
+        .ok()?;
+    if output.status.success() {
+        return Some(attributes_use_lfs(&String::from_utf8_lossy(&output.stdout)));
+    }
+    // No .gitattributes at all
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    stderr.contains("HTTP 404").then_some(false)
+}
+
+/// Run `git lfs install` and `git lfs pull` in `repo`
+pub async fn pull(repo: &Path) -> Result<()> {
+    execute_git_command(repo, &["lfs", "install"])
+        .await
+        .context("git lfs install failed")?;
+    execute_git_command(repo, &["lfs", "pull"])
+        .await
+        .context("git lfs pull failed")?;
+    Ok(())
+}
+
+/// Warn before cloning a GitHub repository that uses LFS when `git lfs`
+/// is missing
+pub async fn warn_before_clone(url: &str) {
+    if lfs_version().await.is_some() {
+        return;
+    }
+    if github_repo_uses_lfs(url).await == Some(true) {
+        display_println!(
+            "{} {}",
+            "⚠️".yellow(),
+            "This repository uses Git LFS, but git-lfs is not installed. Large files will be cloned as pointer files."
+                .yellow()
+                .bold()
+        );
+        display_println!("   Install it first: {}", install_hint().cyan());
+    }
+}
+
+/// After cloning `repo`, make sure LFS content was downloaded. With
+/// `auto_pull` the content is pulled when `git lfs` is installed but the
+/// clone didn't run its filter; otherwise the commands are printed.
+pub async fn check_after_clone(repo: &Path, auto_pull: bool) {
+    if !uses_lfs(repo).await {
+        return;
+    }
+
+    if lfs_version().await.is_none() {
+        display_println!(
+            "{} {}",
+            "⚠️".yellow(),
+            "This repository uses Git LFS, but git-lfs is not installed. Large files are pointer files until you fetch them."
+                .yellow()
+                .bold()
+        );
+        display_println!(
+            "   Install git-lfs with {}, then run {} in {}",
+            install_hint().cyan(),
+            "git lfs install && git lfs pull".cyan(),
+            repo.display()
+        );
+        return;
+    }
+
+    if filter_configured(repo).await {
+        return;
+    }
+
+    if auto_pull {
+        display_println!("{} Downloading Git LFS content...", "📦".cyan());
+        match pull(repo).await {
+            Ok(()) => display_println!("{} Git LFS content downloaded", "✅".green()),
+            Err(e) => display_println!("{} {e:#}", "⚠️".yellow()),
+        }
+    } else {
+        display_println!(
+            "{} {}",
+            "⚠️".yellow(),
+            "This repository uses Git LFS, but the LFS filter isn't set up, so large files are pointer files."
+                .yellow()
+                .bold()
+        );
+        display_println!(
+            "   Run {} in {}, or set preferences.lfs_auto_pull: true",
+            "git lfs install && git lfs pull".cyan(),
+            repo.display()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_attributes_use_lfs() {
+        assert!(attributes_use_lfs(
+            "*.psd filter=lfs diff=lfs merge=lfs -text\n"
+        ));
+        assert!(attributes_use_lfs(
+            "*.rs text\n  assets/** filter=lfs -text\n"
+        ));
+        assert!(!attributes_use_lfs("# *.psd filter=lfs\n*.sh eol=lf\n"));
+        assert!(!attributes_use_lfs("filter=lfs\n"));
+        assert!(!attributes_use_lfs(""));
+    }
+}
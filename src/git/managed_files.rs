@@ -0,0 +1,673 @@
+//! Shared configuration files for `vibe git files sync`
+//!
+//! The `managed_files` section of the workspace config lists source files in
+//! the managed files directory and where each one goes in every repository.
+//! `copy` replaces the destination, `symlink` points it at the source, and
+//! `append-block` keeps the source between delimiter lines inside an existing
+//! file such as `.gitignore`. The opening delimiter records a hash of the
+//! content vibe wrote, so a block edited in the repository since the last
+//! sync is reported as a conflict instead of being overwritten.
+//!
+//! A dry run plans every file the same way and reports a line diff of what
+//! would change, but writes nothing.
+
+use anyhow::{Context, Result};
+use console::style;
+use serde::Serialize;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use crate::output::theme::{self, Role};
+use crate::workspace::config::{ManagedFile, ManagedFileStrategy};
+use crate::{display_eprintln, display_println};
+
+const BLOCK_BEGIN: &str = "# >>> vibe managed:";
+const BLOCK_END: &str = "# <<< vibe managed:";
+
+/// A configured file with its source content
+#[derive(Debug, Clone)]
+pub struct LoadedFile {
+    pub source: PathBuf,
+    pub destination: PathBuf,
+    pub strategy: ManagedFileStrategy,
+    pub content: String,
+}
+
+impl LoadedFile {
+    /// Name identifying this file's block in `append-block` destinations
+    fn block_name(&self) -> String {
+        self.source
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    }
+}
+
+/// Read the configured sources, resolving relative paths against
+/// `managed_dir`
+pub fn load_managed_files(files: &[ManagedFile], managed_dir: &Path) -> Result<Vec<LoadedFile>> {
+    files
+        .iter()
+        .map(|file| {
+            let destination = &file.destination;
+            if destination.as_os_str().is_empty()
+                || destination
+                    .components()
+                    .any(|c| !matches!(c, Component::Normal(_)))
+            {
+                anyhow::bail!(
+                    "Invalid managed file destination '{}': use a path inside the repository",
+                    destination.display()
+                );
+            }
+            let path = crate::utils::fs::expand_tilde(&file.source);
+            let source = if path.is_absolute() {
+                path
+            } else {
+                managed_dir.join(path)
+            };
+            let content = fs::read_to_string(&source)
+                .with_context(|| format!("Failed to read managed file {}", source.display()))?;
+            Ok(LoadedFile {
+                source,
+                destination: destination.clone(),
+                strategy: file.strategy,
+                content,
+            })
+        })
+        .collect()
+}
+
+/// What syncing does, or would do, to one destination
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileAction {
+    Created,
+    Updated,
+    Unchanged,
+    /// Left alone because the repository changed vibe's content
+    Conflict,
+    Failed,
+}
+
+/// One managed file in one repository
+#[derive(Debug, Clone, Serialize)]
+pub struct FileChange {
+    pub destination: PathBuf,
+    pub action: FileAction,
+    /// Diff of the change, filled in for dry runs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diff: Option<String>,
+    /// Conflict or error
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+/// Managed file changes for one repository
+#[derive(Debug, Clone, Serialize)]
+pub struct RepoFiles {
+    pub repository: String,
+    pub files: Vec<FileChange>,
+    /// Why the repository was left alone
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skipped: Option<String>,
+}
+
+impl RepoFiles {
+    /// Whether any file conflicted or failed
+    pub fn has_problems(&self) -> bool {
+        self.files
+            .iter()
+            .any(|f| matches!(f.action, FileAction::Conflict | FileAction::Failed))
+    }
+}
+
+/// Sync `files` into each `(name, path)` repository, only reporting the
+/// changes when `dry_run` is set
+pub fn sync_repositories(
+    targets: &[(String, PathBuf)],
+    files: &[LoadedFile],
+    dry_run: bool,
+) -> Vec<RepoFiles> {
+    targets
+        .iter()
+        .map(|(name, path)| {
+            if !path.exists() {
+                return RepoFiles {
+                    repository: name.clone(),
+                    files: Vec::new(),
+                    skipped: Some("not cloned".to_string()),
+                };
+            }
+            RepoFiles {
+                repository: name.clone(),
+                files: files
+                    .iter()
+                    .map(|file| sync_file(path, file, dry_run))
+                    .collect(),
+                skipped: None,
+            }
+        })
+        .collect()
+}
+
+/// Sync one file into the repository at `repo`
+pub fn sync_file(repo: &Path, file: &LoadedFile, dry_run: bool) -> FileChange {
+    let target = repo.join(&file.destination);
+    let planned = match file.strategy {
+        ManagedFileStrategy::Copy => plan_copy(&target, file),
+        ManagedFileStrategy::AppendBlock => plan_block(&target, file),
+        ManagedFileStrategy::Symlink => plan_symlink(&target, file),
+    };
+    let mut change = FileChange {
+        destination: file.destination.clone(),
+        action: FileAction::Failed,
+        diff: None,
+        detail: None,
+    };
+    match planned {
+        Ok(Plan::Unchanged) => change.action = FileAction::Unchanged,
+        Ok(Plan::Conflict(reason)) => {
+            change.action = FileAction::Conflict;
+            change.detail = Some(reason);
+        }
+        Ok(Plan::Write {
+            old,
+            new,
+            created,
+            write,
+        }) => {
+            let result = if dry_run {
+                change.diff = Some(line_diff(old.as_deref().unwrap_or(""), &new));
+                Ok(())
+            } else {
+                write(&target)
+            };
+            match result {
+                Ok(()) if created => change.action = FileAction::Created,
+                Ok(()) => change.action = FileAction::Updated,
+                Err(e) => change.detail = Some(format!("{e:#}")),
+            }
+        }
+        Err(e) => change.detail = Some(format!("{e:#}")),
+    }
+    change
+}
+
+type Writer = Box<dyn FnOnce(&Path) -> Result<()>>;
+
+enum Plan {
+    Unchanged,
+    Conflict(String),
+    Write {
+        old: Option<String>,
+        new: String,
+        created: bool,
+        write: Writer,
+    },
+}
+
+fn write_file(content: String) -> Writer {
+    Box::new(move |target: &Path| {
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        fs::write(target, content).with_context(|| format!("Failed to write {}", target.display()))
+    })
+}
+
+fn read_existing(target: &Path) -> Result<Option<String>> {
+    match fs::read_to_string(target) {
+        Ok(content) => Ok(Some(content)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e).with_context(|| format!("Failed to read {}", target.display())),
+    }
+}
+
+fn plan_copy(target: &Path, file: &LoadedFile) -> Result<Plan> {
+    // Writing through a link would change the file it points to
+    if target.is_symlink() {
+        return Ok(Plan::Conflict("destination is a symlink".to_string()));
+    }
+    let old = read_existing(target)?;
+    if old.as_deref() == Some(file.content.as_str()) {
+        return Ok(Plan::Unchanged);
+    }
+    Ok(Plan::Write {
+        created: old.is_none(),
+        old,
+        new: file.content.clone(),
+        write: write_file(file.content.clone()),
+    })
+}
+
+fn plan_symlink(target: &Path, file: &LoadedFile) -> Result<Plan> {
+    if let Ok(link) = fs::read_link(target) {
+        if link == file.source {
+            return Ok(Plan::Unchanged);
+        }
+        return Ok(Plan::Conflict(format!(
+            "symlink points to {}",
+            link.display()
+        )));
+    }
+    if target.exists() {
+        return Ok(Plan::Conflict("a file already exists".to_string()));
+    }
+    let source = file.source.clone();
+    Ok(Plan::Write {
+        old: None,
+        new: format!("-> {}\n", source.display()),
+        created: true,
+        write: Box::new(move |target: &Path| {
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            symlink(&source, target).with_context(|| format!("Failed to link {}", target.display()))
+        }),
+    })
+}
+
+#[cfg(unix)]
+fn symlink(source: &Path, target: &Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(source, target)
+}
+
+#[cfg(windows)]
+fn symlink(source: &Path, target: &Path) -> std::io::Result<()> {
+    std::os::windows::fs::symlink_file(source, target)
+}
+
+fn plan_block(target: &Path, file: &LoadedFile) -> Result<Plan> {
+    // Writing through a link would change the file it points to
+    if target.is_symlink() {
+        return Ok(Plan::Conflict("destination is a symlink".to_string()));
+    }
+    let old = read_existing(target)?;
+    let name = file.block_name();
+    let block = render_block(&name, &file.content);
+    let existing = old.as_deref().unwrap_or("");
+
+    let new = match find_block(existing, &name) {
+        Some(Ok(found)) => {
+            if content_hash(&found.body) != found.hash {
+                return Ok(Plan::Conflict(format!(
+                    "managed block '{name}' was edited in the repository"
+                )));
+            }
+            if found.body == normalized(&file.content) {
+                return Ok(Plan::Unchanged);
+            }
+            format!(
+                "{}{}{}",
+                &existing[..found.start],
+                block,
+                &existing[found.end..]
+            )
+        }
+        Some(Err(reason)) => return Ok(Plan::Conflict(reason)),
+        None if existing.is_empty() => block,
+        None => {
+            let separator = if existing.ends_with("\n\n") {
+                ""
+            } else if existing.ends_with('\n') {
+                "\n"
+            } else {
+                "\n\n"
+            };
+            format!("{existing}{separator}{block}")
+        }
+    };
+    Ok(Plan::Write {
+        created: old.is_none(),
+        write: write_file(new.clone()),
+        old,
+        new,
+    })
+}
+
+/// `content` ending in exactly one newline
+fn normalized(content: &str) -> String {
+    format!("{}\n", content.trim_end_matches('\n'))
+}
+
+fn render_block(name: &str, content: &str) -> String {
+    let body = normalized(content);
+    format!(
+        "{BLOCK_BEGIN} {name} {:016x} >>>\n{body}{BLOCK_END} {name} <<<\n",
+        content_hash(&body)
+    )
+}
+
+struct FoundBlock {
+    /// Byte range of the block, delimiters included
+    start: usize,
+    end: usize,
+    hash: u64,
+    body: String,
+}
+
+/// Locate the block for `name`, or why it can't be updated safely
+fn find_block(content: &str, name: &str) -> Option<std::result::Result<FoundBlock, String>> {
+    let begin_prefix = format!("{BLOCK_BEGIN} {name} ");
+    let end_line = format!("{BLOCK_END} {name} <<<");
+
+    let mut offset = 0;
+    let mut begin: Option<(usize, u64)> = None;
+    let mut body_start = 0;
+    for line in content.split_inclusive('\n') {
+        let trimmed = line.trim_end();
+        let line_start = offset;
+        offset += line.len();
+        match begin {
+            None => {
+                if let Some(rest) = trimmed.strip_prefix(&begin_prefix) {
+                    let hash = rest
+                        .strip_suffix(" >>>")
+                        .and_then(|hash| u64::from_str_radix(hash, 16).ok());
+                    let Some(hash) = hash else {
+                        return Some(Err(format!(
+                            "managed block '{name}' has a damaged opening line"
+                        )));
+                    };
+                    begin = Some((line_start, hash));
+                    body_start = offset;
+                }
+            }
+            Some((start, hash)) => {
+                if trimmed == end_line {
+                    return Some(Ok(FoundBlock {
+                        start,
+                        end: offset,
+                        hash,
+                        body: content[body_start..line_start].to_string(),
+                    }));
+                }
+            }
+        }
+    }
+    begin.map(|_| {
+        Err(format!(
+            "managed block '{name}' is missing its closing line"
+        ))
+    })
+}
+
+/// FNV-1a, which unlike std's hashers is stable across releases
+fn content_hash(content: &str) -> u64 {
+    content.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Lines removed from `old` prefixed with `-` and lines added in `new` with
+/// `+`, with one line of context around each change
+fn line_diff(old: &str, new: &str) -> String {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // Longest common subsequence table, from the end of both inputs
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut ops = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            ops.push((' ', old[i]));
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            ops.push(('-', old[i]));
+            i += 1;
+        } else {
+            ops.push(('+', new[j]));
+            j += 1;
+        }
+    }
+
+    let near_change = |index: usize| {
+        let from = index.saturating_sub(1);
+        let to = (index + 1).min(ops.len() - 1);
+        ops[from..=to].iter().any(|(op, _)| *op != ' ')
+    };
+    let mut diff = String::new();
+    let mut skipped = false;
+    for (index, (op, line)) in ops.iter().enumerate() {
+        if near_change(index) {
+            if skipped && !diff.is_empty() {
+                diff.push_str("  ...\n");
+            }
+            skipped = false;
+            diff.push_str(&format!("{op} {line}\n"));
+        } else {
+            skipped = true;
+        }
+    }
+    diff
+}
+
+/// Print each repository's changes, with diffs for dry runs
+pub fn print_results(repos: &[RepoFiles], dry_run: bool) {
+    for repo in repos {
+        if let Some(reason) = &repo.skipped {
+            display_println!(
+                "{} {}",
+                style(&repo.repository).cyan(),
+                style(format!("(skipped: {reason})")).dim()
+            );
+            continue;
+        }
+        display_println!("{}", style(&repo.repository).cyan());
+        for file in &repo.files {
+            let destination = file.destination.display();
+            match file.action {
+                FileAction::Created | FileAction::Updated => {
+                    let verb = match (file.action, dry_run) {
+                        (FileAction::Created, true) => "would create",
+                        (FileAction::Created, false) => "created",
+                        (_, true) => "would update",
+                        (_, false) => "updated",
+                    };
+                    display_println!("  {} {destination}: {verb}", style("✓").green());
+                    for line in file.diff.iter().flat_map(|diff| diff.lines()) {
+                        let role = match line.chars().next() {
+                            Some('+') => Role::Success,
+                            Some('-') => Role::Error,
+                            _ => Role::Dim,
+                        };
+                        display_println!("      {}", theme::paint(role, line));
+                    }
+                }
+                FileAction::Unchanged => display_println!(
+                    "  {} {destination}: {}",
+                    style("-").dim(),
+                    style("up to date").dim()
+                ),
+                FileAction::Conflict => display_println!(
+                    "  {} {destination}: {}",
+                    style("!").yellow(),
+                    theme::paint(
+                        Role::Warning,
+                        format!(
+                            "conflict, {}",
+                            file.detail.as_deref().unwrap_or("local changes")
+                        )
+                    )
+                ),
+                FileAction::Failed => display_eprintln!(
+                    "  {} {destination}: {}",
+                    style("✗").red(),
+                    file.detail.as_deref().unwrap_or("failed")
+                ),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn loaded(
+        source: &str,
+        destination: &str,
+        strategy: ManagedFileStrategy,
+        content: &str,
+    ) -> LoadedFile {
+        LoadedFile {
+            source: PathBuf::from(source),
+            destination: PathBuf::from(destination),
+            strategy,
+            content: content.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_append_block_is_idempotent() {
+        let repo = TempDir::new().unwrap();
+        let gitignore = repo.path().join(".gitignore");
+        fs::write(&gitignore, "target/\n").unwrap();
+        let file = loaded(
+            "gitignore",
+            ".gitignore",
+            ManagedFileStrategy::AppendBlock,
+            ".env\n",
+        );
+
+        let change = sync_file(repo.path(), &file, false);
+        assert_eq!(change.action, FileAction::Updated);
+        let synced = fs::read_to_string(&gitignore).unwrap();
+        assert!(synced.starts_with("target/\n\n# >>> vibe managed: gitignore "));
+        assert!(synced.ends_with(".env\n# <<< vibe managed: gitignore <<<\n"));
+
+        let change = sync_file(repo.path(), &file, false);
+        assert_eq!(change.action, FileAction::Unchanged);
+        assert_eq!(fs::read_to_string(&gitignore).unwrap(), synced);
+
+        // A new source replaces the block in place
+        let file = loaded(
+            "gitignore",
+            ".gitignore",
+            ManagedFileStrategy::AppendBlock,
+            ".env\n*.log\n",
+        );
+        fs::write(&gitignore, format!("{synced}dist/\n")).unwrap();
+        assert_eq!(
+            sync_file(repo.path(), &file, false).action,
+            FileAction::Updated
+        );
+        let updated = fs::read_to_string(&gitignore).unwrap();
+        assert!(updated.contains(".env\n*.log\n# <<< vibe managed: gitignore <<<\ndist/\n"));
+    }
+
+    #[test]
+    fn test_edited_block_is_a_conflict() {
+        let repo = TempDir::new().unwrap();
+        let gitignore = repo.path().join(".gitignore");
+        let file = loaded(
+            "gitignore",
+            ".gitignore",
+            ManagedFileStrategy::AppendBlock,
+            ".env\n",
+        );
+        sync_file(repo.path(), &file, false);
+
+        let edited = fs::read_to_string(&gitignore)
+            .unwrap()
+            .replace(".env\n", ".env\nlocal/\n");
+        fs::write(&gitignore, &edited).unwrap();
+
+        let file = loaded(
+            "gitignore",
+            ".gitignore",
+            ManagedFileStrategy::AppendBlock,
+            "*.log\n",
+        );
+        let change = sync_file(repo.path(), &file, false);
+        assert_eq!(change.action, FileAction::Conflict);
+        assert_eq!(fs::read_to_string(&gitignore).unwrap(), edited);
+    }
+
+    #[test]
+    fn test_dry_run_reports_diff_without_writing() {
+        let repo = TempDir::new().unwrap();
+        let target = repo.path().join(".editorconfig");
+        fs::write(&target, "root = true\nindent = 2\n").unwrap();
+        let file = loaded(
+            "editorconfig",
+            ".editorconfig",
+            ManagedFileStrategy::Copy,
+            "root = true\nindent = 4\n",
+        );
+
+        let change = sync_file(repo.path(), &file, true);
+        assert_eq!(change.action, FileAction::Updated);
+        assert_eq!(
+            change.diff.as_deref(),
+            Some("  root = true\n- indent = 2\n+ indent = 4\n")
+        );
+        assert_eq!(
+            fs::read_to_string(&target).unwrap(),
+            "root = true\nindent = 2\n"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlink_does_not_replace_files() {
+        let repo = TempDir::new().unwrap();
+        let source = repo.path().join("shared");
+        fs::write(&source, "x").unwrap();
+        let file = loaded(
+            source.to_str().unwrap(),
+            "linked",
+            ManagedFileStrategy::Symlink,
+            "x",
+        );
+
+        assert_eq!(
+            sync_file(repo.path(), &file, false).action,
+            FileAction::Created
+        );
+        assert_eq!(
+            sync_file(repo.path(), &file, false).action,
+            FileAction::Unchanged
+        );
+
+        let file = loaded(
+            source.to_str().unwrap(),
+            "shared",
+            ManagedFileStrategy::Symlink,
+            "x",
+        );
+        assert_eq!(
+            sync_file(repo.path(), &file, false).action,
+            FileAction::Conflict
+        );
+    }
+
+    #[test]
+    fn test_destination_must_stay_in_repository() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("a"), "").unwrap();
+        let file = |destination: &str| ManagedFile {
+            source: PathBuf::from("a"),
+            destination: PathBuf::from(destination),
+            strategy: ManagedFileStrategy::Copy,
+        };
+        assert!(load_managed_files(&[file("../outside")], dir.path()).is_err());
+        assert!(load_managed_files(&[file("/etc/passwd")], dir.path()).is_err());
+        assert!(load_managed_files(&[file(".github/CODEOWNERS")], dir.path()).is_ok());
+    }
+}
@@ -1,10 +1,21 @@
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
+pub mod backend;
+pub mod branches;
 pub mod bulk_clone;
 pub mod clone;
+pub mod hooks;
+pub mod lfs;
+pub mod managed_files;
+pub mod protection;
 pub mod provider;
+pub mod pull_requests;
+pub mod query;
+pub mod ref_backup;
+pub mod repo_lock;
 pub mod search;
+pub mod upstream;
 
 pub use clone::CloneCommand;
 pub use search::SearchCommand;
@@ -21,6 +32,9 @@ pub struct Repository {
     pub language: Option<String>,
     pub license: Option<String>, // License key (e.g., "mit", "apache-2.0")
     pub topics: Vec<String>,
+    /// Size in KB as reported by the provider, when it reports one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size_kb: Option<u64>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -62,21 +76,134 @@ pub struct SearchQuery {
     pub sort: SortMethod,
 }
 
+/// `preferences.clone_layout` in config.yaml: where clones land under the
+/// workspace root
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CloneLayout {
+    /// `{root}/{repo}`
+    Flat,
+    /// `{root}/{owner}/{repo}`
+    #[default]
+    Owner,
+    /// `{root}/{host}/{owner}/{repo}`, like ghq
+    HostOwner,
+}
+
+impl CloneLayout {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            CloneLayout::Flat => "flat",
+            CloneLayout::Owner => "owner",
+            CloneLayout::HostOwner => "host-owner",
+        }
+    }
+
+    /// Where a clone of `host`/`owner`/`repo` goes under `root`
+    pub fn destination(self, root: &Path, host: &str, owner: &str, repo: &str) -> PathBuf {
+        match self {
+            CloneLayout::Flat => root.join(repo),
+            CloneLayout::Owner => root.join(owner).join(repo),
+            CloneLayout::HostOwner => root.join(host).join(owner).join(repo),
+        }
+    }
+
+    /// The next layout that keeps same-named repositories apart, if any
+    pub fn more_nested(self) -> Option<CloneLayout> {
+        match self {
+            CloneLayout::Flat => Some(CloneLayout::Owner),
+            CloneLayout::Owner => Some(CloneLayout::HostOwner),
+            CloneLayout::HostOwner => None,
+        }
+    }
+}
+
+/// `preferences.clone_protocol` in config.yaml: which protocol clones of
+/// configured remotes use, whatever protocol the URL was recorded with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CloneProtocol {
+    Https,
+    Ssh,
+}
+
+impl CloneProtocol {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            CloneProtocol::Https => "https",
+            CloneProtocol::Ssh => "ssh",
+        }
+    }
+
+    /// `url` rewritten to this protocol. URLs that aren't `https://`,
+    /// `ssh://`, or scp-like `git@host:path`, such as local paths, and
+    /// `ssh://` URLs with a port are returned unchanged.
+    pub fn apply(self, url: &str) -> String {
+        let Some((host, path)) = split_remote(url) else {
+            return url.to_string();
+        };
+        match self {
+            CloneProtocol::Https => format!("https://{host}/{path}"),
+            CloneProtocol::Ssh => format!("git@{host}:{path}"),
+        }
+    }
+}
+
+/// `--on-collision`: what cloning does when the repository's name is
+/// already configured or its directory already exists
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum OnCollision {
+    /// Clone one layout level deeper, e.g. `acme/api` instead of `api`
+    Namespace,
+    /// Stop with an error
+    Fail,
+    /// Open the existing repository when it's a clone of the same URL
+    OpenExisting,
+}
+
+/// Host and repository path of a remote URL
+fn split_remote(url: &str) -> Option<(String, String)> {
+    let url = url.trim();
+    if url.contains("://") {
+        let parsed = url::Url::parse(url).ok()?;
+        if !matches!(parsed.scheme(), "https" | "http" | "ssh") || parsed.port().is_some() {
+            return None;
+        }
+        let host = parsed.host_str()?.to_string();
+        let path = parsed.path().trim_start_matches('/').to_string();
+        return (!path.is_empty()).then_some((host, path));
+    }
+    let (host, path) = url.split_once(':')?;
+    let host = host.rsplit('@').next()?;
+    // A single letter is a Windows drive, not a host
+    (host.len() > 1 && !host.contains('/') && !path.is_empty())
+        .then(|| (host.to_string(), path.trim_start_matches('/').to_string()))
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GitConfig {
     pub default_clone_location: PathBuf,
-    pub standardize_paths: bool,
+    pub clone_layout: CloneLayout,
+    /// Run `git lfs install && git lfs pull` after cloning when needed
+    pub lfs_auto_pull: bool,
     pub auto_install_dependencies: bool,
     pub search_providers: Vec<String>,
+    /// How a single clone whose name or directory is taken is resolved;
+    /// unset asks when interactive and fails otherwise
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_collision: Option<OnCollision>,
 }
 
 impl Default for GitConfig {
     fn default() -> Self {
         Self {
             default_clone_location: dirs::home_dir().unwrap_or_default().join("Workspace"),
-            standardize_paths: true,
+            clone_layout: CloneLayout::default(),
+            lfs_auto_pull: false,
             auto_install_dependencies: false,
             search_providers: vec!["github_cli".to_string()],
+            on_collision: None,
         }
     }
 }
@@ -86,6 +213,16 @@ pub enum GitError {
     #[error("Repository already exists at {path}")]
     RepositoryExists { path: PathBuf },
 
+    #[error("{} already holds a different repository ({existing}){}", .path.display(), collision_hint(*.suggestion))]
+    PathCollision {
+        path: PathBuf,
+        existing: String,
+        suggestion: Option<CloneLayout>,
+    },
+
+    #[error("A repository named '{name}' is already configured at {}", .path.display())]
+    NameCollision { name: String, path: PathBuf },
+
     #[error("Invalid Git URL: {url}")]
     InvalidUrl { url: String },
 
@@ -103,4 +240,59 @@ pub enum GitError {
         provider: String,
         source: anyhow::Error,
     },
+
+    #[error("GitHub API rate limit exceeded{}", reset_hint(*.reset_at))]
+    RateLimited {
+        reset_at: Option<chrono::DateTime<chrono::Utc>>,
+    },
+}
+
+fn reset_hint(reset_at: Option<chrono::DateTime<chrono::Utc>>) -> String {
+    match reset_at {
+        Some(reset_at) => format!(
+            "; resets at {}",
+            reset_at.with_timezone(&chrono::Local).format("%H:%M:%S")
+        ),
+        None => "; wait a few minutes before trying again".to_string(),
+    }
+}
+
+fn collision_hint(suggestion: Option<CloneLayout>) -> String {
+    suggestion
+        .map(|layout| {
+            format!(
+                "; set preferences.clone_layout to '{}' to keep same-named repositories apart",
+                layout.as_str()
+            )
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clone_protocol_rewrites_remotes() {
+        for url in [
+            "https://github.com/acme/api.git",
+            "git@github.com:acme/api.git",
+            "ssh://git@github.com/acme/api.git",
+        ] {
+            assert_eq!(
+                CloneProtocol::Https.apply(url),
+                "https://github.com/acme/api.git"
+            );
+            assert_eq!(CloneProtocol::Ssh.apply(url), "git@github.com:acme/api.git");
+        }
+
+        for url in [
+            "/srv/git/api.git",
+            "C:\\repos\\api",
+            "ssh://git@example.com:2222/acme/api.git",
+            "file:///srv/git/api.git",
+        ] {
+            assert_eq!(CloneProtocol::Ssh.apply(url), url);
+        }
+    }
 }
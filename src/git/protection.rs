@@ -0,0 +1,265 @@
+//! Branches that destructive git commands leave alone
+//!
+//! A branch is protected when it matches one of the configured
+//! `protected_branches` patterns (`main`, `release/*`, ...) or, when
+//! `check_github_protection` is on, when GitHub marks it protected. Code
+//! that deletes or force-updates a branch asks [`BranchProtection`] first
+//! and reports the branch as skipped instead. `--include-protected` with
+//! `--force` overrides a pattern, never a protection GitHub enforces.
+
+use chrono::{Duration, Utc};
+use glob::Pattern;
+use std::collections::HashSet;
+use std::fmt;
+use std::path::Path;
+use tokio::process::Command;
+use tracing::{debug, Instrument};
+
+use crate::cache::RepositoryCache;
+use crate::git::pull_requests::github_repo_from_url;
+use crate::output::timings;
+use crate::utils::platform::resolve_command;
+use crate::workspace::operations::execute_git_command;
+
+/// Patterns protected when none are configured
+pub const DEFAULT_PROTECTED_BRANCHES: &[&str] = &["main", "master", "release/*"];
+
+/// How long GitHub's answer is reused before asking again
+const REMOTE_CACHE_HOURS: i64 = 24;
+
+/// Why a branch is protected
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BranchProtectionReason {
+    /// Matches a `protected_branches` pattern
+    Pattern(String),
+    /// GitHub reports a branch protection rule for it
+    Remote,
+}
+
+impl BranchProtectionReason {
+    /// Whether `--include-protected --force` may act on the branch anyway
+    pub fn overridable(&self) -> bool {
+        matches!(self, Self::Pattern(_))
+    }
+}
+
+impl fmt::Display for BranchProtectionReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Pattern(pattern) => {
+                write!(f, "it matches protected_branches pattern '{pattern}'")
+            }
+            Self::Remote => write!(f, "it is protected on GitHub"),
+        }
+    }
+}
+
+/// Returned when an operation would delete or overwrite a protected branch
+#[derive(Debug, thiserror::Error)]
+#[error(
+    "Refusing to touch branch '{branch}': {reason}{}",
+    override_hint(reason)
+)]
+pub struct ProtectedBranchError {
+    pub branch: String,
+    pub reason: BranchProtectionReason,
+}
+
+fn override_hint(reason: &BranchProtectionReason) -> &'static str {
+    if reason.overridable() {
+        " (use --include-protected --force to override)"
+    } else {
+        ""
+    }
+}
+
+/// The protected branches of one repository
+#[derive(Debug, Clone, Default)]
+pub struct BranchProtection {
+    patterns: Vec<(String, Option<Pattern>)>,
+    remote: HashSet<String>,
+}
+
+impl BranchProtection {
+    /// Protection from configured patterns; an entry that isn't a valid
+    /// glob only matches itself
+    pub fn new(patterns: &[String]) -> Self {
+        Self {
+            patterns: patterns
+                .iter()
+                .map(|pattern| (pattern.clone(), Pattern::new(pattern).ok()))
+                .collect(),
+            remote: HashSet::new(),
+        }
+    }
+
+    /// Also protect the branches GitHub reports protected
+    pub fn with_remote(mut self, branches: impl IntoIterator<Item = String>) -> Self {
+        self.remote.extend(branches);
+        self
+    }
+
+    /// Why `branch` is protected, if it is. GitHub's protection wins over a
+    /// pattern since it can't be overridden.
+    pub fn reason(&self, branch: &str) -> Option<BranchProtectionReason> {
+        if self.remote.contains(branch) {
+            return Some(BranchProtectionReason::Remote);
+        }
+        self.patterns
+            .iter()
+            .find(|(raw, pattern)| match pattern {
+                Some(pattern) => pattern.matches(branch),
+                None => raw == branch,
+            })
+            .map(|(raw, _)| BranchProtectionReason::Pattern(raw.clone()))
+    }
+
+    /// `Err` when `branch` is protected and the protection isn't overridden;
+    /// `include_protected` only counts together with `force`
+    pub fn check(
+        &self,
+        branch: &str,
+        include_protected: bool,
+        force: bool,
+    ) -> Result<(), ProtectedBranchError> {
+        match self.reason(branch) {
+            Some(reason) if !(include_protected && force && reason.overridable()) => {
+                Err(ProtectedBranchError {
+                    branch: branch.to_string(),
+                    reason,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Protection for the repository at `repo`: `patterns`, plus what GitHub
+/// reports when `check_remote` is set and `origin` is on GitHub
+pub async fn load(repo: &Path, patterns: &[String], check_remote: bool) -> BranchProtection {
+    let protection = BranchProtection::new(patterns);
+    if !check_remote {
+        return protection;
+    }
+    match github_protected_branches(repo).await {
+        Some(branches) => protection.with_remote(branches),
+        None => protection,
+    }
+}
+
+/// Branches GitHub marks protected for `origin` of `repo`, from the cache
+/// when asked within the last day. `None` when `origin` isn't on GitHub or
+/// `gh` can't answer.
+pub async fn github_protected_branches(repo: &Path) -> Option<Vec<String>> {
+    let origin = execute_git_command(repo, &["remote", "get-url", "origin"])
+        .await
+        .ok()?;
+    let github_repo = github_repo_from_url(origin.trim())?;
+
+    let cache = protection_cache().await;
+    if let Some(cache) = &cache {
+        if let Ok(Some((branches, fetched_at))) = cache.get_protected_branches(&github_repo).await {
+            if Utc::now() - fetched_at < Duration::hours(REMOTE_CACHE_HOURS) {
+                return Some(branches);
+            }
+        }
+    }
+
+    let branches = fetch_github_protected_branches(&github_repo).await?;
+    if let Some(cache) = &cache {
+        if let Err(e) = cache
+            .cache_protected_branches(&github_repo, &branches, Utc::now())
+            .await
+        {
+            debug!(
+                "Failed to cache protected branches of {}: {}",
+                github_repo, e
+            );
+        }
+    }
+    Some(branches)
+}
+
+async fn fetch_github_protected_branches(github_repo: &str) -> Option<Vec<String>> {
+    let output = Command::new(resolve_command("gh"))
+        .args([
+            "api",
+            "--paginate",
+            &format!("repos/{github_repo}/branches?protected=true"),
+            "--jq",
+            ".[].name",
+        ])
+        .output()
+        .instrument(timings::phase(
+            timings::PROVIDER,
+            format!("gh api repos/{github_repo}/branches"),
+        ))
+        .await
+        .ok()?;
+    if !output.status.success() {
+        debug!(
+            "gh could not list protected branches of {}: {}",
+            github_repo,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+        return None;
+    }
+    Some(
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(str::to_string)
+            .collect(),
+    )
+}
+
+async fn protection_cache() -> Option<RepositoryCache> {
+    let cache_dir = crate::workspace::constants::get_cache_dir();
+    let cache = RepositoryCache::new(cache_dir.join("repositories.db"));
+    tokio::fs::create_dir_all(&cache_dir).await.ok()?;
+    cache.initialize().await.ok()?;
+    Some(cache)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn defaults() -> Vec<String> {
+        DEFAULT_PROTECTED_BRANCHES
+            .iter()
+            .map(|p| p.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_patterns_match_branches() {
+        let protection = BranchProtection::new(&defaults());
+        assert_eq!(
+            protection.reason("main"),
+            Some(BranchProtectionReason::Pattern("main".to_string()))
+        );
+        assert_eq!(
+            protection.reason("release/1.2"),
+            Some(BranchProtectionReason::Pattern("release/*".to_string()))
+        );
+        assert_eq!(protection.reason("vibe-ws/main"), None);
+        assert_eq!(protection.reason("feature/release"), None);
+    }
+
+    #[test]
+    fn test_override_applies_to_patterns_only() {
+        let protection = BranchProtection::new(&defaults()).with_remote(["develop".to_string()]);
+
+        assert!(protection.check("main", true, false).is_err());
+        assert!(protection.check("main", false, true).is_err());
+        assert!(protection.check("main", true, true).is_ok());
+
+        let err = protection.check("develop", true, true).unwrap_err();
+        assert_eq!(err.reason, BranchProtectionReason::Remote);
+        assert!(!err.to_string().contains("--include-protected"));
+
+        assert!(protection.check("feature/x", false, false).is_ok());
+    }
+}
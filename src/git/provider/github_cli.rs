@@ -1,14 +1,48 @@
 use anyhow::{Context, Result};
 use async_trait::async_trait;
+use chrono::{DateTime, TimeZone, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::PathBuf;
+use std::process::Output;
+use std::sync::Arc;
+use std::time::Duration;
 use tokio::process::Command;
+use tracing::{warn, Instrument};
 
 use crate::git::{GitError, Repository, SearchQuery};
+use crate::output::timings;
 use crate::utils::git::is_github_cli_available;
 
 use super::SearchProvider;
 
+/// How many times a rate-limited `gh api` call is retried before giving up
+const RATE_LIMIT_RETRIES: u32 = 3;
+
+/// Longest single wait for a rate limit to lift; later resets fail right away
+const MAX_RATE_LIMIT_WAIT: Duration = Duration::from_secs(90);
+
+/// First backoff step when GitHub doesn't say how long to wait
+const RATE_LIMIT_BACKOFF: Duration = Duration::from_secs(10);
+
+/// jq filter that maps the REST repository objects to what
+/// `parse_repository_list` reads
+const REPOSITORY_FIELDS: &str = r#".[] | {
+    fullName: .full_name,
+    name: .name,
+    description: .description,
+    url: .clone_url,
+    sshUrl: .ssh_url,
+    stargazersCount: .stargazers_count,
+    language: .language,
+    fork: .fork,
+    archived: .archived,
+    topics: .topics,
+    size: .size,
+    license: .license.spdx_id,
+    updatedAt: .updated_at
+}"#;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GitHubCliConfig {
     pub executable_path: Option<PathBuf>,
@@ -26,9 +60,72 @@ impl Default for GitHubCliConfig {
     }
 }
 
+/// Whether a repository owner is a user or an organization
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OwnerKind {
+    User,
+    Organization,
+}
+
+impl OwnerKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OwnerKind::User => "user",
+            OwnerKind::Organization => "org",
+        }
+    }
+
+    pub fn parse(kind: &str) -> Option<Self> {
+        match kind {
+            "user" => Some(OwnerKind::User),
+            "org" => Some(OwnerKind::Organization),
+            _ => None,
+        }
+    }
+
+    fn repos_endpoint(&self, owner: &str) -> String {
+        match self {
+            OwnerKind::User => format!("users/{owner}/repos"),
+            OwnerKind::Organization => format!("orgs/{owner}/repos"),
+        }
+    }
+}
+
+/// Every repository of a user or organization, with the newest `updated_at`
+/// among them so a cached copy can be checked with [`ListingProbe`]
+#[derive(Debug, Clone)]
+pub struct RepositoryListing {
+    pub repositories: Vec<Repository>,
+    pub updated_at: Option<String>,
+}
+
+/// Whether an owner's repositories changed since they were listed
+#[derive(Debug, Clone, PartialEq)]
+pub enum ListingProbe {
+    /// GitHub answered 304 to the cached ETag
+    NotModified,
+    /// The most recently updated repository, and the ETag to send next time
+    Changed {
+        etag: Option<String>,
+        updated_at: Option<String>,
+    },
+}
+
+/// Rate limit reported by a failed `gh api` call
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateLimitHit {
+    /// Secondary limits guard against bursts and never report a reset time
+    pub secondary: bool,
+    pub reset_at: Option<DateTime<Utc>>,
+    pub retry_after: Option<Duration>,
+}
+
+type RateLimitNotifier = Arc<dyn Fn(Duration) + Send + Sync>;
+
 pub struct GitHubCliProvider {
     gh_path: PathBuf,
     config: GitHubCliConfig,
+    on_rate_limit: Option<RateLimitNotifier>,
 }
 
 impl GitHubCliProvider {
@@ -46,16 +143,102 @@ impl GitHubCliProvider {
             .clone()
             .unwrap_or_else(|| PathBuf::from("gh"));
 
-        Ok(Self { gh_path, config })
+        Ok(Self {
+            gh_path,
+            config,
+            on_rate_limit: None,
+        })
+    }
+
+    /// Call `notify` with the wait before each retry of a rate-limited request
+    pub fn on_rate_limit(mut self, notify: impl Fn(Duration) + Send + Sync + 'static) -> Self {
+        self.on_rate_limit = Some(Arc::new(notify));
+        self
     }
 
-    /// Get the currently authenticated GitHub username
-    pub async fn get_username(&self) -> Result<String> {
+    /// Run `gh api` with `args`, waiting out rate limits with jittered
+    /// backoff. Other failures are returned as the unsuccessful output; a
+    /// rate limit that outlasts the retries becomes [`GitError::RateLimited`].
+    async fn gh_api(&self, args: &[&str]) -> Result<Output> {
+        let endpoint = args
+            .iter()
+            .find(|arg| !arg.starts_with('-') && arg.contains('/'))
+            .or(args.first())
+            .copied()
+            .unwrap_or_default();
+        let mut attempt = 0;
+
+        loop {
+            let output = Command::new(&self.gh_path)
+                .arg("api")
+                .args(args)
+                .output()
+                .instrument(timings::phase(
+                    timings::PROVIDER,
+                    format!("gh api {endpoint}"),
+                ))
+                .await
+                .context("Failed to run gh api")?;
+
+            if output.status.success() {
+                return Ok(output);
+            }
+            let Some(mut hit) = detect_rate_limit(
+                &String::from_utf8_lossy(&output.stderr),
+                &String::from_utf8_lossy(&output.stdout),
+            ) else {
+                return Ok(output);
+            };
+            if hit.reset_at.is_none() && !hit.secondary {
+                hit.reset_at = self.core_reset_at().await;
+            }
+
+            let wait = if attempt < RATE_LIMIT_RETRIES {
+                rate_limit_wait(&hit, attempt, Utc::now(), jitter())
+            } else {
+                None
+            };
+            let Some(wait) = wait else {
+                return Err(GitError::RateLimited {
+                    reset_at: hit.reset_at,
+                }
+                .into());
+            };
+
+            warn!(
+                "GitHub rate limit hit on {endpoint}, retrying in {}s",
+                wait.as_secs()
+            );
+            if let Some(notify) = &self.on_rate_limit {
+                notify(wait);
+            }
+            tokio::time::sleep(wait).await;
+            attempt += 1;
+        }
+    }
+
+    /// When the core REST rate limit resets. Querying it doesn't count
+    /// against the limit.
+    async fn core_reset_at(&self) -> Option<DateTime<Utc>> {
         let output = Command::new(&self.gh_path)
-            .args(["api", "user", "--jq", ".login"])
+            .args(["api", "rate_limit", "--jq", ".resources.core.reset"])
             .output()
+            .instrument(timings::phase(timings::PROVIDER, "gh api rate_limit"))
             .await
-            .context("Failed to get GitHub username")?;
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let epoch = String::from_utf8_lossy(&output.stdout)
+            .trim()
+            .parse()
+            .ok()?;
+        Utc.timestamp_opt(epoch, 0).single()
+    }
+
+    /// Get the currently authenticated GitHub username
+    pub async fn get_username(&self) -> Result<String> {
+        let output = self.gh_api(&["user", "--jq", ".login"]).await?;
 
         if !output.status.success() {
             let error_msg = String::from_utf8_lossy(&output.stderr);
@@ -76,11 +259,7 @@ impl GitHubCliProvider {
 
     /// Get the organizations the authenticated user belongs to
     pub async fn get_user_organizations(&self) -> Result<Vec<String>> {
-        let output = Command::new(&self.gh_path)
-            .args(["api", "user/orgs", "--jq", ".[].login"])
-            .output()
-            .await
-            .context("Failed to get GitHub organizations")?;
+        let output = self.gh_api(&["user/orgs", "--jq", ".[].login"]).await?;
 
         if !output.status.success() {
             // Organizations query might fail if user has no orgs, which is fine
@@ -101,47 +280,36 @@ impl GitHubCliProvider {
 
     /// Check if a repository exists for the given owner and name
     pub async fn repository_exists(&self, owner: &str, repo_name: &str) -> Result<bool> {
-        let output = Command::new(&self.gh_path)
-            .args(["api", &format!("repos/{owner}/{repo_name}")])
-            .output()
-            .await
-            .context("Failed to check repository existence")?;
+        let output = self
+            .gh_api(&[&format!("repos/{owner}/{repo_name}")])
+            .await?;
 
         // If the repository exists, the command will succeed
         // If it doesn't exist, it will fail with 404
         Ok(output.status.success())
     }
 
-    /// Get all repositories for a specific user
-    pub async fn get_user_repositories(&self, username: &str) -> Result<Vec<Repository>> {
-        let output = Command::new(&self.gh_path)
-            .args([
-                "api",
-                &format!("users/{}/repos", username),
+    /// Get all repositories for a user or organization
+    pub async fn list_repositories(
+        &self,
+        kind: OwnerKind,
+        owner: &str,
+    ) -> Result<RepositoryListing> {
+        let output = self
+            .gh_api(&[
+                &kind.repos_endpoint(owner),
                 "--paginate",
                 "--jq",
-                r#".[] | {
-                    fullName: .full_name,
-                    name: .name,
-                    description: .description,
-                    url: .clone_url,
-                    sshUrl: .ssh_url,
-                    stargazersCount: .stargazers_count,
-                    language: .language,
-                    fork: .fork,
-                    archived: .archived,
-                    topics: .topics
-                }"#,
+                REPOSITORY_FIELDS,
             ])
-            .output()
-            .await
-            .context("Failed to get user repositories")?;
+            .await?;
 
         if !output.status.success() {
             let error_msg = String::from_utf8_lossy(&output.stderr);
             anyhow::bail!(
-                "Failed to get user repositories for '{}': {}",
-                username,
+                "Failed to get {} repositories for '{}': {}",
+                kind.as_str(),
+                owner,
                 error_msg
             );
         }
@@ -149,62 +317,58 @@ impl GitHubCliProvider {
         self.parse_repository_list(&output.stdout).await
     }
 
-    /// Get all repositories for an organization
-    pub async fn get_organization_repositories(&self, org: &str) -> Result<Vec<Repository>> {
-        let output = Command::new(&self.gh_path)
-            .args([
-                "api",
-                &format!("orgs/{}/repos", org),
-                "--paginate",
-                "--jq",
-                r#".[] | {
-                    fullName: .full_name,
-                    name: .name,
-                    description: .description,
-                    url: .clone_url,
-                    sshUrl: .ssh_url,
-                    stargazersCount: .stargazers_count,
-                    language: .language,
-                    fork: .fork,
-                    archived: .archived,
-                    topics: .topics
-                }"#,
-            ])
-            .output()
-            .await
-            .context("Failed to get organization repositories")?;
-
-        if !output.status.success() {
-            let error_msg = String::from_utf8_lossy(&output.stderr);
-            anyhow::bail!(
-                "Failed to get organization repositories for '{}': {}",
-                org,
-                error_msg
-            );
+    /// Check whether an owner's repositories changed since `etag` was
+    /// recorded, by fetching only the most recently updated one. GitHub
+    /// doesn't count a 304 answer against the rate limit.
+    pub async fn probe_repository_listing(
+        &self,
+        kind: OwnerKind,
+        owner: &str,
+        etag: Option<&str>,
+    ) -> Result<ListingProbe> {
+        let endpoint = format!("{}?per_page=1&sort=updated", kind.repos_endpoint(owner));
+        let header = etag.map(|etag| format!("If-None-Match: {etag}"));
+        let mut args = vec!["--include", endpoint.as_str()];
+        if let Some(header) = &header {
+            args.extend(["-H", header.as_str()]);
+        }
+        let output = self.gh_api(&args).await?;
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let (status, headers, body) = split_included_response(&stdout);
+        match status {
+            Some(304) => Ok(ListingProbe::NotModified),
+            Some(200) => {
+                #[derive(Deserialize)]
+                struct Newest {
+                    updated_at: Option<String>,
+                }
+                let newest: Vec<Newest> =
+                    serde_json::from_str(body).context("Failed to parse repository listing")?;
+                Ok(ListingProbe::Changed {
+                    etag: headers.get("etag").cloned(),
+                    updated_at: newest.into_iter().next().and_then(|n| n.updated_at),
+                })
+            }
+            _ => anyhow::bail!(
+                "Failed to check repositories for '{}': {}",
+                owner,
+                String::from_utf8_lossy(&output.stderr)
+            ),
         }
-
-        self.parse_repository_list(&output.stdout).await
     }
 
     /// Check if a target exists as either a user or organization
     pub async fn user_or_org_exists(&self, target: &str) -> Result<bool> {
         // Try user first
-        let user_check = Command::new(&self.gh_path)
-            .args(["api", &format!("users/{}", target)])
-            .output()
-            .await
-            .context("Failed to check user existence")?;
+        let user_check = self.gh_api(&[&format!("users/{target}")]).await?;
 
         if user_check.status.success() {
             return Ok(true);
         }
 
         // Try organization
-        let org_check = Command::new(&self.gh_path)
-            .args(["api", &format!("orgs/{}", target)])
-            .output()
-            .await
-            .context("Failed to check organization existence")?;
+        let org_check = self.gh_api(&[&format!("orgs/{target}")]).await?;
 
         Ok(org_check.status.success())
     }
@@ -215,22 +379,14 @@ impl GitHubCliProvider {
         target: &str,
     ) -> Result<crate::git::bulk_clone::TargetType> {
         // Try organization first (more likely to have multiple repos)
-        let org_check = Command::new(&self.gh_path)
-            .args(["api", &format!("orgs/{}", target)])
-            .output()
-            .await
-            .context("Failed to check organization")?;
+        let org_check = self.gh_api(&[&format!("orgs/{target}")]).await?;
 
         if org_check.status.success() {
             return Ok(crate::git::bulk_clone::TargetType::Organization);
         }
 
         // Try user
-        let user_check = Command::new(&self.gh_path)
-            .args(["api", &format!("users/{}", target)])
-            .output()
-            .await
-            .context("Failed to check user")?;
+        let user_check = self.gh_api(&[&format!("users/{target}")]).await?;
 
         if user_check.status.success() {
             Ok(crate::git::bulk_clone::TargetType::User)
@@ -244,6 +400,7 @@ impl GitHubCliProvider {
         // Try as organization first
         match self.count_organization_repositories(target).await {
             Ok(count) => Ok(count),
+            Err(e) if is_rate_limited(&e) => Err(e),
             Err(_) => {
                 // Try as user
                 self.count_user_repositories(target).await
@@ -253,16 +410,9 @@ impl GitHubCliProvider {
 
     /// Count repositories for a user
     async fn count_user_repositories(&self, username: &str) -> Result<usize> {
-        let output = Command::new(&self.gh_path)
-            .args([
-                "api",
-                &format!("users/{}/repos", username),
-                "--jq",
-                "length",
-            ])
-            .output()
-            .await
-            .context("Failed to count user repositories")?;
+        let output = self
+            .gh_api(&[&format!("users/{username}/repos"), "--jq", "length"])
+            .await?;
 
         if !output.status.success() {
             let error_msg = String::from_utf8_lossy(&output.stderr);
@@ -285,11 +435,9 @@ impl GitHubCliProvider {
 
     /// Count repositories for an organization
     async fn count_organization_repositories(&self, org: &str) -> Result<usize> {
-        let output = Command::new(&self.gh_path)
-            .args(["api", &format!("orgs/{}/repos", org), "--jq", "length"])
-            .output()
-            .await
-            .context("Failed to count organization repositories")?;
+        let output = self
+            .gh_api(&[&format!("orgs/{org}/repos"), "--jq", "length"])
+            .await?;
 
         if !output.status.success() {
             let error_msg = String::from_utf8_lossy(&output.stderr);
@@ -310,7 +458,7 @@ impl GitHubCliProvider {
             .context("Failed to parse repository count")
     }
 
-    async fn parse_repository_list(&self, output: &[u8]) -> Result<Vec<Repository>> {
+    async fn parse_repository_list(&self, output: &[u8]) -> Result<RepositoryListing> {
         #[derive(Deserialize)]
         struct RepoData {
             #[serde(rename = "fullName")]
@@ -331,14 +479,19 @@ impl GitHubCliProvider {
             archived: bool,
             #[serde(default)]
             topics: Vec<String>,
+            #[serde(default)]
+            size: Option<u64>,
+            #[serde(default)]
+            license: Option<String>,
+            #[serde(rename = "updatedAt", default)]
+            updated_at: Option<String>,
         }
 
         let output_str = String::from_utf8_lossy(output);
-        if output_str.trim().is_empty() {
-            return Ok(Vec::new());
-        }
-
-        let mut repositories = Vec::new();
+        let mut listing = RepositoryListing {
+            repositories: Vec::new(),
+            updated_at: None,
+        };
 
         // Parse line by line since GitHub CLI outputs one JSON object per line
         for line in output_str.lines() {
@@ -350,6 +503,11 @@ impl GitHubCliProvider {
             let repo_data: RepoData = serde_json::from_str(line)
                 .with_context(|| format!("Failed to parse repository data: {}", line))?;
 
+            // Timestamps are all UTC in the same format, so they sort as strings
+            if repo_data.updated_at > listing.updated_at {
+                listing.updated_at = repo_data.updated_at;
+            }
+
             let repository = Repository {
                 id: repo_data.full_name.clone(),
                 name: repo_data.name,
@@ -359,14 +517,15 @@ impl GitHubCliProvider {
                 ssh_url: repo_data.ssh_url,
                 stars: repo_data.stars,
                 language: repo_data.language,
-                license: None, // Not available in this endpoint
+                license: repo_data.license.filter(|l| l != "NOASSERTION"),
                 topics: repo_data.topics,
+                size_kb: repo_data.size,
             };
 
-            repositories.push(repository);
+            listing.repositories.push(repository);
         }
 
-        Ok(repositories)
+        Ok(listing)
     }
 
     async fn parse_search_results(&self, output: &[u8]) -> Result<Vec<Repository>> {
@@ -430,6 +589,7 @@ impl GitHubCliProvider {
                         }
                     }),
                     topics: vec![], // Topics not returned in search results
+                    size_kb: None,
                 };
 
             repositories.push(repo);
@@ -448,6 +608,10 @@ impl GitHubCliProvider {
                 "name,description,url,sshUrl,stargazerCount,primaryLanguage,repositoryTopics",
             ])
             .output()
+            .instrument(timings::phase(
+                timings::PROVIDER,
+                format!("gh repo view {repo_name}"),
+            ))
             .await
             .context("Failed to execute gh repo view")?;
 
@@ -490,6 +654,7 @@ impl GitHubCliProvider {
             language: details.primary_language.map(|l| l.name),
             license: None, // License not available in repo view, only in search
             topics: details.topics,
+            size_kb: None,
         })
     }
 }
@@ -537,6 +702,7 @@ impl SearchProvider for GitHubCliProvider {
 
         let output = cmd
             .output()
+            .instrument(timings::phase(timings::PROVIDER, "gh search repos"))
             .await
             .context("Failed to execute gh search repos")?;
 
@@ -562,3 +728,165 @@ impl SearchProvider for GitHubCliProvider {
         "github_cli"
     }
 }
+
+/// Whether `error` is GitHub refusing requests because of a rate limit
+pub fn is_rate_limited(error: &anyhow::Error) -> bool {
+    matches!(
+        error.downcast_ref::<GitError>(),
+        Some(GitError::RateLimited { .. })
+    )
+}
+
+/// Rate limit described by `gh api`'s error output, reading the reset and
+/// retry headers when the call used `--include`
+pub fn detect_rate_limit(stderr: &str, stdout: &str) -> Option<RateLimitHit> {
+    let message = stderr.to_lowercase();
+    let secondary = message.contains("secondary rate limit") || message.contains("abuse detection");
+    if !secondary && !message.contains("rate limit exceeded") && !message.contains("http 429") {
+        return None;
+    }
+
+    let (_, headers, _) = split_included_response(stdout);
+    let reset_at = headers
+        .get("x-ratelimit-reset")
+        .and_then(|reset| reset.parse().ok())
+        .and_then(|epoch| Utc.timestamp_opt(epoch, 0).single())
+        .filter(|_| !secondary);
+    let retry_after = headers
+        .get("retry-after")
+        .and_then(|seconds| seconds.parse().ok())
+        .map(Duration::from_secs);
+
+    Some(RateLimitHit {
+        secondary,
+        reset_at,
+        retry_after,
+    })
+}
+
+/// Status, lowercased headers, and body of `gh api --include` output. Output
+/// without a status line is all body.
+fn split_included_response(stdout: &str) -> (Option<u16>, HashMap<String, String>, &str) {
+    let mut headers = HashMap::new();
+    if !stdout.starts_with("HTTP/") {
+        return (None, headers, stdout);
+    }
+
+    let (head, body) = stdout
+        .split_once("\r\n\r\n")
+        .or_else(|| stdout.split_once("\n\n"))
+        .unwrap_or((stdout, ""));
+    let mut lines = head.lines();
+    let status = lines
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .and_then(|code| code.parse().ok());
+    for line in lines {
+        if let Some((name, value)) = line.split_once(':') {
+            headers.insert(name.trim().to_lowercase(), value.trim().to_string());
+        }
+    }
+
+    (status, headers, body)
+}
+
+/// How long to wait before retry `attempt`, or `None` when the limit lasts
+/// longer than is worth waiting for
+fn rate_limit_wait(
+    hit: &RateLimitHit,
+    attempt: u32,
+    now: DateTime<Utc>,
+    jitter: Duration,
+) -> Option<Duration> {
+    let wait = match (hit.retry_after, hit.reset_at) {
+        (Some(retry_after), _) => retry_after,
+        (None, Some(reset_at)) => (reset_at - now).to_std().unwrap_or_default(),
+        (None, None) => RATE_LIMIT_BACKOFF * 2u32.pow(attempt),
+    };
+    (wait <= MAX_RATE_LIMIT_WAIT).then_some(wait + jitter)
+}
+
+/// Up to two seconds of jitter, so parallel invocations don't retry in step
+fn jitter() -> Duration {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.subsec_nanos())
+        .unwrap_or_default();
+    Duration::from_millis(u64::from(nanos % 2000))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_rate_limit() {
+        assert_eq!(detect_rate_limit("gh: Not Found (HTTP 404)", ""), None);
+
+        let primary = detect_rate_limit(
+            "gh: API rate limit exceeded for user ID 1. (HTTP 403)",
+            "HTTP/2.0 403 Forbidden\r\nX-Ratelimit-Remaining: 0\r\n\
+             X-Ratelimit-Reset: 1700000000\r\n\r\n{}",
+        )
+        .unwrap();
+        assert!(!primary.secondary);
+        assert_eq!(primary.reset_at.unwrap().timestamp(), 1_700_000_000);
+
+        let secondary = detect_rate_limit(
+            "gh: You have exceeded a secondary rate limit. Please wait a few minutes \
+             before you try again. (HTTP 403)",
+            "HTTP/2.0 403 Forbidden\nRetry-After: 60\nX-Ratelimit-Reset: 1700000000\n\n{}",
+        )
+        .unwrap();
+        assert!(secondary.secondary);
+        assert_eq!(secondary.reset_at, None);
+        assert_eq!(secondary.retry_after, Some(Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn test_rate_limit_wait() {
+        let now = Utc::now();
+        let hit = RateLimitHit {
+            secondary: true,
+            reset_at: None,
+            retry_after: None,
+        };
+        let jitter = Duration::from_millis(500);
+        assert_eq!(
+            rate_limit_wait(&hit, 0, now, jitter),
+            Some(RATE_LIMIT_BACKOFF + jitter)
+        );
+        assert_eq!(
+            rate_limit_wait(&hit, 2, now, jitter),
+            Some(RATE_LIMIT_BACKOFF * 4 + jitter)
+        );
+        assert_eq!(rate_limit_wait(&hit, 4, now, jitter), None);
+
+        let resets_soon = RateLimitHit {
+            secondary: false,
+            reset_at: Some(now + chrono::Duration::seconds(30)),
+            retry_after: None,
+        };
+        assert_eq!(
+            rate_limit_wait(&resets_soon, 0, now, jitter),
+            Some(Duration::from_secs(30) + jitter)
+        );
+        let resets_later = RateLimitHit {
+            reset_at: Some(now + chrono::Duration::minutes(40)),
+            ..resets_soon
+        };
+        assert_eq!(rate_limit_wait(&resets_later, 0, now, jitter), None);
+    }
+
+    #[test]
+    fn test_split_included_response() {
+        let (status, headers, body) =
+            split_included_response("HTTP/2.0 304 Not Modified\r\nEtag: W/\"abc\"\r\n\r\n");
+        assert_eq!(status, Some(304));
+        assert_eq!(headers["etag"], "W/\"abc\"");
+        assert_eq!(body, "");
+
+        let (status, headers, body) = split_included_response("[]");
+        assert_eq!((status, headers.len(), body), (None, 0, "[]"));
+    }
+}
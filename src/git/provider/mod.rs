@@ -5,6 +5,7 @@ pub mod github_cli;
 pub mod vector_store;
 
 pub use github_cli::GitHubCliProvider;
+pub use vector_store::VectorStoreProvider;
 
 use super::{Repository, SearchQuery};
 
@@ -21,6 +22,7 @@ impl ProviderFactory {
     pub fn create_provider(name: &str) -> Result<Box<dyn SearchProvider>> {
         match name {
             "github_cli" => Ok(Box::new(GitHubCliProvider::new()?)),
+            "vector_store" => Ok(Box::new(VectorStoreProvider::new())),
             _ => anyhow::bail!("Unknown search provider: {}", name),
         }
     }
@@ -1,28 +1,637 @@
-// Future implementation for local semantic vector store
-// This is a placeholder for future development
+//! Local store of repositories seen in searches and clones
+//!
+//! Each repository is kept with a term vector built from its name,
+//! description, topics, and language, so suggestions can be ranked by
+//! similarity without network access. No embedding backend ships with vibe;
+//! vectors are weighted bags of words, ranked by TF-IDF cosine similarity.
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use console::style;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use tracing::warn;
 
+use crate::display_println;
 use crate::git::{Repository, SearchQuery};
+use crate::output::theme::{self, Role};
+use crate::utils::git::{extract_repo_name_from_url, normalize_git_url};
+use crate::workspace::config::Repository as WorkspaceRepository;
 
 use super::SearchProvider;
 
-pub struct VectorStoreProvider;
+/// File name of the store inside the cache directory
+pub const STORE_FILE: &str = "suggestions.json";
+
+/// Oldest entries are dropped beyond this many repositories
+const MAX_ENTRIES: usize = 2000;
+
+/// Words too common to say anything about a repository
+const STOP_WORDS: &[&str] = &[
+    "a", "an", "and", "are", "for", "from", "in", "is", "it", "of", "on", "or", "the", "this",
+    "to", "with", "your",
+];
+
+/// Term weights for one repository
+pub type TermVector = BTreeMap<String, f32>;
+
+/// How a stored repository was last encountered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SeenVia {
+    Search,
+    Clone,
+}
+
+/// A repository remembered from a search or clone
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredRepository {
+    pub repository: Repository,
+    pub seen_via: SeenVia,
+    pub last_seen: DateTime<Utc>,
+    pub vector: TermVector,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct StoreFile {
+    #[serde(default)]
+    repositories: Vec<StoredRepository>,
+}
+
+/// Where a suggestion came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SuggestionSource {
+    /// Already in the workspace
+    Workspace,
+    /// Returned by an earlier search
+    Search,
+    /// Cloned earlier, but no longer in the workspace
+    Clone,
+}
+
+impl From<SeenVia> for SuggestionSource {
+    fn from(seen: SeenVia) -> Self {
+        match seen {
+            SeenVia::Search => SuggestionSource::Search,
+            SeenVia::Clone => SuggestionSource::Clone,
+        }
+    }
+}
+
+/// A ranked match for `vibe suggest`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Suggestion {
+    pub name: String,
+    pub source: SuggestionSource,
+    pub score: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+/// Repositories seen in earlier searches and clones, persisted as JSON
+pub struct VectorStore {
+    path: PathBuf,
+    entries: BTreeMap<String, StoredRepository>,
+}
+
+impl VectorStore {
+    /// Location of the store in the cache directory
+    pub fn default_path() -> PathBuf {
+        crate::workspace::constants::get_cache_dir().join(STORE_FILE)
+    }
+
+    /// A store with nothing in it, for ranking workspace repositories alone
+    pub fn empty() -> Self {
+        Self {
+            path: PathBuf::new(),
+            entries: BTreeMap::new(),
+        }
+    }
+
+    /// Load the store at `path`; a missing or unreadable file gives an empty store
+    pub async fn load(path: &Path) -> Self {
+        let entries = match tokio::fs::read_to_string(path).await {
+            Ok(contents) => match serde_json::from_str::<StoreFile>(&contents) {
+                Ok(file) => file
+                    .repositories
+                    .into_iter()
+                    .map(|entry| (store_key(&entry.repository), entry))
+                    .collect(),
+                Err(e) => {
+                    warn!(
+                        "Ignoring unreadable suggestion store {}: {}",
+                        path.display(),
+                        e
+                    );
+                    BTreeMap::new()
+                }
+            },
+            Err(_) => BTreeMap::new(),
+        };
+
+        Self {
+            path: path.to_path_buf(),
+            entries,
+        }
+    }
+
+    /// Write the store back, keeping the most recently seen entries
+    pub async fn save(&mut self) -> Result<()> {
+        if self.entries.len() > MAX_ENTRIES {
+            let mut by_age: Vec<_> = self
+                .entries
+                .iter()
+                .map(|(key, entry)| (entry.last_seen, key.clone()))
+                .collect();
+            by_age.sort();
+            for (_, key) in by_age.into_iter().take(self.entries.len() - MAX_ENTRIES) {
+                self.entries.remove(&key);
+            }
+        }
+
+        if let Some(parent) = self.path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let file = StoreFile {
+            repositories: self.entries.values().cloned().collect(),
+        };
+        let temp = self.path.with_extension("json.tmp");
+        tokio::fs::write(&temp, serde_json::to_vec(&file)?)
+            .await
+            .with_context(|| format!("Failed to write {}", temp.display()))?;
+        tokio::fs::rename(&temp, &self.path)
+            .await
+            .with_context(|| format!("Failed to write {}", self.path.display()))?;
+        Ok(())
+    }
+
+    /// Delete the store at `path`; returns whether there was one
+    pub async fn clear(path: &Path) -> Result<bool> {
+        match tokio::fs::remove_file(path).await {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(e).with_context(|| format!("Failed to delete {}", path.display())),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Remember search results. A repository that was cloned keeps that
+    /// status; its metadata is refreshed.
+    pub fn record_search_results(&mut self, repositories: &[Repository]) {
+        let now = Utc::now();
+        for repository in repositories {
+            let key = store_key(repository);
+            let seen_via = self
+                .entries
+                .get(&key)
+                .map_or(SeenVia::Search, |entry| entry.seen_via);
+            self.entries.insert(
+                key,
+                StoredRepository {
+                    vector: repository_vector(repository),
+                    repository: repository.clone(),
+                    seen_via,
+                    last_seen: now,
+                },
+            );
+        }
+    }
+
+    /// Remember a clone of `url`, reusing metadata from an earlier search
+    pub fn record_clone(&mut self, url: &str) {
+        let url = normalize_git_url(url.trim());
+        let full_name = full_name_from_url(&url);
+        let key = full_name.to_lowercase();
+
+        let entry = self.entries.entry(key).or_insert_with(|| {
+            let name = extract_repo_name_from_url(&url).unwrap_or_else(|| full_name.clone());
+            let repository = Repository {
+                id: full_name.clone(),
+                name,
+                full_name: full_name.clone(),
+                description: None,
+                url: url.clone(),
+                ssh_url: String::new(),
+                stars: 0,
+                language: None,
+                license: None,
+                topics: Vec::new(),
+                size_kb: None,
+            };
+            StoredRepository {
+                vector: repository_vector(&repository),
+                repository,
+                seen_via: SeenVia::Clone,
+                last_seen: Utc::now(),
+            }
+        });
+        entry.seen_via = SeenVia::Clone;
+        entry.last_seen = Utc::now();
+    }
+
+    /// Stored repositories most similar to `query`, best first
+    pub fn search(&self, query: &str, limit: usize) -> Vec<&StoredRepository> {
+        let entries: Vec<_> = self.entries.values().collect();
+        let vectors: Vec<_> = entries.iter().map(|entry| &entry.vector).collect();
+        rank(query, &vectors)
+            .into_iter()
+            .take(limit)
+            .map(|(index, _)| entries[index])
+            .collect()
+    }
+
+    /// Rank workspace repositories together with stored ones. Stored
+    /// repositories that are in the workspace are merged into the workspace
+    /// entry, lending it their description and topics.
+    pub fn suggest(
+        &self,
+        query: &str,
+        workspace: &[WorkspaceRepository],
+        limit: usize,
+    ) -> Vec<Suggestion> {
+        let mut candidates = Vec::new();
+        let mut in_workspace = HashSet::new();
+
+        for repo in workspace {
+            let stored = repo
+                .url
+                .as_deref()
+                .and_then(|url| self.entries.get(&full_name_from_url(url).to_lowercase()));
+            let mut vector = term_vector(&[(&repo.name, 3.0), (&repo.path.to_string_lossy(), 1.0)]);
+            if let Some(stored) = stored {
+                in_workspace.insert(store_key(&stored.repository));
+                for (term, weight) in &stored.vector {
+                    *vector.entry(term.clone()).or_default() += weight;
+                }
+            }
+            candidates.push((
+                Suggestion {
+                    name: repo.name.clone(),
+                    source: SuggestionSource::Workspace,
+                    score: 0.0,
+                    description: stored.and_then(|s| s.repository.description.clone()),
+                    url: repo.url.clone(),
+                },
+                vector,
+            ));
+        }
+
+        for (key, entry) in &self.entries {
+            if in_workspace.contains(key) {
+                continue;
+            }
+            candidates.push((
+                Suggestion {
+                    name: entry.repository.full_name.clone(),
+                    source: entry.seen_via.into(),
+                    score: 0.0,
+                    description: entry.repository.description.clone(),
+                    url: Some(entry.repository.url.clone()),
+                },
+                entry.vector.clone(),
+            ));
+        }
+
+        let vectors: Vec<_> = candidates.iter().map(|(_, vector)| vector).collect();
+        rank(query, &vectors)
+            .into_iter()
+            .take(limit)
+            .map(|(index, score)| Suggestion {
+                score,
+                ..candidates[index].0.clone()
+            })
+            .collect()
+    }
+}
+
+/// Table of suggestions, best first
+pub fn print_suggestions(query: &str, suggestions: &[Suggestion]) {
+    if suggestions.is_empty() {
+        display_println!(
+            "{} Nothing in the workspace or earlier searches matches '{}'",
+            style("ℹ").yellow(),
+            query
+        );
+        return;
+    }
+
+    let name_width = suggestions
+        .iter()
+        .map(|s| s.name.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("Repository".len());
+    display_println!(
+        "{}",
+        style(format!(
+            "{:>5}  {:<name_width$}  {:<9}  Description",
+            "Score", "Repository", "Source"
+        ))
+        .bold()
+    );
+    for suggestion in suggestions {
+        let source = match suggestion.source {
+            SuggestionSource::Workspace => theme::paint(Role::Success, "workspace"),
+            SuggestionSource::Search => theme::paint(Role::Dim, "search"),
+            SuggestionSource::Clone => theme::paint(Role::Warning, "clone"),
+        };
+        display_println!(
+            "{:>5.2}  {:<name_width$}  {:<9}  {}",
+            suggestion.score,
+            suggestion.name,
+            source,
+            suggestion.description.as_deref().unwrap_or_default()
+        );
+    }
+}
+
+fn store_key(repository: &Repository) -> String {
+    repository.full_name.to_lowercase()
+}
+
+/// `owner/name` from a clone URL, or the whole URL for unusual hosts
+fn full_name_from_url(url: &str) -> String {
+    let trimmed = url.trim_end_matches('/').trim_end_matches(".git");
+    let mut parts = trimmed.rsplit(['/', ':']);
+    match (parts.next(), parts.next()) {
+        (Some(name), Some(owner)) if !name.is_empty() && !owner.is_empty() => {
+            format!("{owner}/{name}")
+        }
+        _ => trimmed.to_string(),
+    }
+}
+
+/// Lowercase words of `text`, split on punctuation and case changes, with
+/// stop words and plural `s` dropped
+pub fn tokenize(text: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut previous_lower = false;
+    for c in text.chars() {
+        if (!c.is_alphanumeric() || (c.is_uppercase() && previous_lower)) && !current.is_empty() {
+            words.push(std::mem::take(&mut current));
+        }
+        if c.is_alphanumeric() {
+            current.extend(c.to_lowercase());
+        }
+        previous_lower = c.is_lowercase();
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+
+    words
+        .into_iter()
+        .filter(|word| word.len() > 1 && !STOP_WORDS.contains(&word.as_str()))
+        .map(|word| match word.strip_suffix('s') {
+            Some(stem) if stem.len() > 2 && !stem.ends_with('s') => stem.to_string(),
+            _ => word,
+        })
+        .collect()
+}
+
+/// Bag of words over several fields, each word weighted by its field
+pub fn term_vector(fields: &[(&str, f32)]) -> TermVector {
+    let mut vector = TermVector::new();
+    for (text, weight) in fields {
+        for word in tokenize(text) {
+            *vector.entry(word).or_default() += weight;
+        }
+    }
+    vector
+}
+
+fn repository_vector(repository: &Repository) -> TermVector {
+    let topics = repository.topics.join(" ");
+    term_vector(&[
+        (&repository.full_name, 3.0),
+        (repository.description.as_deref().unwrap_or_default(), 1.0),
+        (&topics, 2.0),
+        (repository.language.as_deref().unwrap_or_default(), 1.0),
+    ])
+}
+
+/// Indexes of `documents` with a positive TF-IDF cosine similarity to
+/// `query`, best first
+fn rank(query: &str, documents: &[&TermVector]) -> Vec<(usize, f32)> {
+    let query_terms: HashSet<String> = tokenize(query).into_iter().collect();
+    if query_terms.is_empty() || documents.is_empty() {
+        return Vec::new();
+    }
+
+    let mut document_frequency: HashMap<&str, usize> = HashMap::new();
+    for document in documents {
+        for term in document.keys() {
+            *document_frequency.entry(term).or_default() += 1;
+        }
+    }
+    let total = documents.len() as f32;
+    let idf = |term: &str| {
+        let df = document_frequency.get(term).copied().unwrap_or(0) as f32;
+        ((total + 1.0) / (df + 1.0)).ln() + 1.0
+    };
+
+    let query_norm = query_terms
+        .iter()
+        .map(|term| idf(term).powi(2))
+        .sum::<f32>()
+        .sqrt();
+
+    let mut scored: Vec<(usize, f32)> = documents
+        .iter()
+        .enumerate()
+        .filter_map(|(index, document)| {
+            let dot: f32 = query_terms
+                .iter()
+                .filter_map(|term| document.get(term).map(|w| w * idf(term).powi(2)))
+                .sum();
+            if dot <= 0.0 {
+                return None;
+            }
+            let norm = document
+                .iter()
+                .map(|(term, w)| (w * idf(term)).powi(2))
+                .sum::<f32>()
+                .sqrt();
+            Some((index, dot / (norm * query_norm)))
+        })
+        .collect();
+    scored.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+    scored
+}
+
+/// Searches the local store, so results are available offline
+pub struct VectorStoreProvider {
+    path: PathBuf,
+}
+
+impl VectorStoreProvider {
+    pub fn new() -> Self {
+        Self {
+            path: VectorStore::default_path(),
+        }
+    }
+}
+
+impl Default for VectorStoreProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 #[async_trait]
 impl SearchProvider for VectorStoreProvider {
-    async fn search(&self, _query: &SearchQuery) -> Result<Vec<Repository>> {
-        // Future implementation
-        anyhow::bail!("Vector store search not yet implemented")
+    async fn search(&self, query: &SearchQuery) -> Result<Vec<Repository>> {
+        let store = VectorStore::load(&self.path).await;
+        let text = query
+            .keywords
+            .iter()
+            .chain(&query.tags)
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(" ");
+        Ok(store
+            .search(&text, query.limit.unwrap_or(20))
+            .into_iter()
+            .map(|entry| entry.repository.clone())
+            .filter(|repo| {
+                query.language.as_ref().is_none_or(|language| {
+                    repo.language
+                        .as_ref()
+                        .is_some_and(|l| l.eq_ignore_ascii_case(language))
+                })
+            })
+            .filter(|repo| {
+                query.organization.as_ref().is_none_or(|org| {
+                    repo.full_name
+                        .split('/')
+                        .next()
+                        .is_some_and(|owner| owner.eq_ignore_ascii_case(org))
+                })
+            })
+            .collect())
     }
 
-    async fn get_repository(&self, _id: &str) -> Result<Repository> {
-        // Future implementation
-        anyhow::bail!("Vector store repository lookup not yet implemented")
+    async fn get_repository(&self, id: &str) -> Result<Repository> {
+        let store = VectorStore::load(&self.path).await;
+        store
+            .entries
+            .values()
+            .find(|entry| {
+                entry.repository.id == id || entry.repository.full_name.eq_ignore_ascii_case(id)
+            })
+            .map(|entry| entry.repository.clone())
+            .ok_or_else(|| anyhow::anyhow!("Repository '{id}' is not in the suggestion store"))
     }
 
     fn name(&self) -> &str {
         "vector_store"
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn repo(full_name: &str, description: &str, topics: &[&str]) -> Repository {
+        Repository {
+            id: full_name.to_string(),
+            name: full_name.split('/').next_back().unwrap().to_string(),
+            full_name: full_name.to_string(),
+            description: Some(description.to_string()),
+            url: format!("https://github.com/{full_name}"),
+            ssh_url: format!("git@github.com:{full_name}.git"),
+            stars: 0,
+            language: Some("Rust".to_string()),
+            license: None,
+            topics: topics.iter().map(|t| t.to_string()).collect(),
+            size_kb: None,
+        }
+    }
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(
+            tokenize("The fastest HTTP servers for tokio-based apps"),
+            vec!["fastest", "http", "server", "tokio", "based", "app"]
+        );
+        assert_eq!(tokenize("vibeWorkspace"), vec!["vibe", "workspace"]);
+        assert_eq!(tokenize("css class"), vec!["css", "class"]);
+    }
+
+    #[test]
+    fn test_full_name_from_url() {
+        assert_eq!(
+            full_name_from_url("https://github.com/tokio-rs/axum.git"),
+            "tokio-rs/axum"
+        );
+        assert_eq!(full_name_from_url("git@gitlab.com:me/tool"), "me/tool");
+    }
+
+    #[tokio::test]
+    async fn test_store_ranks_and_round_trips() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(STORE_FILE);
+
+        let mut store = VectorStore::load(&path).await;
+        assert!(store.is_empty());
+        store.record_search_results(&[
+            repo(
+                "tokio-rs/axum",
+                "Web framework built on tokio",
+                &["http", "web"],
+            ),
+            repo("serde-rs/json", "JSON serialization", &["json", "serde"]),
+            repo("clap-rs/clap", "Command line argument parser", &["cli"]),
+        ]);
+        store.record_clone("git@github.com:clap-rs/clap.git");
+        store.save().await.unwrap();
+
+        let store = VectorStore::load(&path).await;
+        assert_eq!(store.len(), 3);
+        let names: Vec<_> = store
+            .search("web frameworks", 5)
+            .iter()
+            .map(|entry| entry.repository.full_name.clone())
+            .collect();
+        assert_eq!(names, vec!["tokio-rs/axum"]);
+
+        let workspace = vec![WorkspaceRepository {
+            name: "clap".to_string(),
+            path: PathBuf::from("clap"),
+            url: Some("https://github.com/clap-rs/clap".to_string()),
+            upstream: None,
+            license: None,
+            branch: None,
+            apps: Default::default(),
+            worktree_config: None,
+            subprojects: Vec::new(),
+            exclude: Vec::new(),
+            compare_ref: None,
+        }];
+        let suggestions = store.suggest("argument parser", &workspace, 5);
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].name, "clap");
+        assert_eq!(suggestions[0].source, SuggestionSource::Workspace);
+
+        let suggestions = store.suggest("json", &workspace, 5);
+        assert_eq!(suggestions[0].name, "serde-rs/json");
+        assert_eq!(suggestions[0].source, SuggestionSource::Search);
+
+        assert!(VectorStore::clear(&path).await.unwrap());
+        assert!(!VectorStore::clear(&path).await.unwrap());
+    }
+}
@@ -0,0 +1,521 @@
+//! Open pull requests across the workspace for `vibe git prs`
+//!
+//! Runs `gh pr list --json` for every repository with a GitHub remote and
+//! merges the results into one dashboard. Repositories without a GitHub
+//! remote, or where `gh` fails, are reported as skipped with the reason.
+//! Dashboards are cached for a few minutes so the interactive menu can
+//! show an open pull request count without network calls.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use console::style;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::process::Command;
+use tokio::sync::Semaphore;
+use tracing::Instrument;
+
+use crate::display_println;
+use crate::git::backend;
+use crate::output::theme::{self, Role};
+use crate::output::timings;
+use crate::utils::platform::resolve_command;
+
+/// Fields requested from `gh pr list`
+const PR_FIELDS: &str =
+    "number,title,author,createdAt,url,isDraft,reviewDecision,statusCheckRollup";
+
+/// Most pull requests fetched per repository
+const PR_LIMIT: &str = "100";
+
+/// Which open pull requests to list
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PrFilter {
+    #[default]
+    All,
+    /// Authored by the signed-in GitHub user
+    Mine,
+    /// Waiting on the signed-in user's review
+    ReviewRequested,
+}
+
+impl PrFilter {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PrFilter::All => "all",
+            PrFilter::Mine => "mine",
+            PrFilter::ReviewRequested => "review-requested",
+        }
+    }
+
+    fn gh_args(self) -> &'static [&'static str] {
+        match self {
+            PrFilter::All => &[],
+            PrFilter::Mine => &["--author", "@me"],
+            PrFilter::ReviewRequested => &["--search", "review-requested:@me"],
+        }
+    }
+
+    /// Cache key for a dashboard built with this filter and group
+    pub fn cache_key(self, group: Option<&str>) -> String {
+        format!("{}:{}", self.as_str(), group.unwrap_or("*"))
+    }
+}
+
+/// Combined state of a pull request's checks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChecksState {
+    Passing,
+    Failing,
+    Pending,
+    /// No checks reported
+    None,
+}
+
+impl ChecksState {
+    fn label(self) -> &'static str {
+        match self {
+            ChecksState::Passing => "passing",
+            ChecksState::Failing => "failing",
+            ChecksState::Pending => "pending",
+            ChecksState::None => "-",
+        }
+    }
+}
+
+/// One open pull request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PullRequest {
+    pub repository: String,
+    /// `owner/name` on GitHub
+    pub github_repo: String,
+    pub number: u64,
+    pub title: String,
+    pub author: String,
+    pub url: String,
+    pub created_at: DateTime<Utc>,
+    pub is_draft: bool,
+    pub checks: ChecksState,
+    /// GitHub's review decision, e.g. `approved` or `changes_requested`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub review: Option<String>,
+}
+
+/// A repository left out of the dashboard
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkippedRepo {
+    pub repository: String,
+    pub reason: String,
+}
+
+/// Open pull requests across the workspace, oldest first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrDashboard {
+    pub filter: PrFilter,
+    pub fetched_at: DateTime<Utc>,
+    pub pull_requests: Vec<PullRequest>,
+    pub skipped: Vec<SkippedRepo>,
+    /// Whether this came from the cache rather than GitHub
+    #[serde(default)]
+    pub cached: bool,
+}
+
+impl PrDashboard {
+    pub fn print_table(&self) {
+        if self.pull_requests.is_empty() {
+            display_println!("{} No open pull requests", style("ℹ").yellow());
+        } else {
+            let width = |f: fn(&PullRequest) -> usize, header: &str, max: usize| {
+                self.pull_requests
+                    .iter()
+                    .map(f)
+                    .max()
+                    .unwrap_or(0)
+                    .clamp(header.len(), max)
+            };
+            let repo_width = width(|pr| pr.repository.len(), "Repository", 24);
+            let title_width = width(|pr| pr.title.chars().count(), "Title", 50);
+            let author_width = width(|pr| pr.author.len(), "Author", 16);
+
+            display_println!(
+                "{}",
+                style(format!(
+                    "{:<repo_width$}  {:>6}  {:<title_width$}  {:<author_width$}  {:>5}  {:<8}  Review",
+                    "Repository", "PR", "Title", "Author", "Age", "Checks"
+                ))
+                .bold()
+            );
+            for pr in &self.pull_requests {
+                let mut title: String = pr.title.chars().take(title_width).collect();
+                if pr.is_draft && title.chars().count() + 8 <= title_width {
+                    title.push_str(" (draft)");
+                }
+                let checks = format!("{:<8}", pr.checks.label());
+                let checks = match pr.checks {
+                    ChecksState::Passing => theme::paint(Role::Success, checks),
+                    ChecksState::Failing => theme::paint(Role::Error, checks),
+                    ChecksState::Pending => theme::paint(Role::Warning, checks),
+                    ChecksState::None => theme::paint(Role::Dim, checks),
+                };
+                display_println!(
+                    "{:<repo_width$}  {:>6}  {:<title_width$}  {:<author_width$}  {:>4}d  {}  {}",
+                    truncate(&pr.repository, repo_width),
+                    format!("#{}", pr.number),
+                    title,
+                    truncate(&pr.author, author_width),
+                    (self.fetched_at - pr.created_at).num_days().max(0),
+                    checks,
+                    pr.review.as_deref().map(review_label).unwrap_or("-")
+                );
+            }
+        }
+
+        display_println!();
+        display_println!(
+            "{}",
+            style(format!(
+                "{} open pull requests ({}){}",
+                self.pull_requests.len(),
+                self.filter.as_str(),
+                if self.cached {
+                    format!(", cached {}", self.fetched_at.format("%H:%M"))
+                } else {
+                    String::new()
+                }
+            ))
+            .bold()
+        );
+
+        if !self.skipped.is_empty() {
+            display_println!();
+            display_println!("{}", theme::paint(Role::Dim, "Skipped:"));
+            for skipped in &self.skipped {
+                display_println!(
+                    "  {}",
+                    theme::paint(
+                        Role::Dim,
+                        format!("{}: {}", skipped.repository, skipped.reason)
+                    )
+                );
+            }
+        }
+    }
+}
+
+fn truncate(value: &str, width: usize) -> String {
+    value.chars().take(width).collect()
+}
+
+fn review_label(review: &str) -> &str {
+    match review {
+        "approved" => "approved",
+        "changes_requested" => "changes requested",
+        "review_required" => "review required",
+        other => other,
+    }
+}
+
+/// `owner/name` from a GitHub remote URL in HTTPS, SSH, or scp-like form
+pub fn github_repo_from_url(url: &str) -> Option<String> {
+    let rest = url
+        .strip_prefix("https://github.com/")
+        .or_else(|| url.strip_prefix("http://github.com/"))
+        .or_else(|| url.strip_prefix("git@github.com:"))
+        .or_else(|| url.strip_prefix("ssh://git@github.com/"))?;
+    let rest = rest.trim_end_matches('/');
+    let rest = rest.strip_suffix(".git").unwrap_or(rest);
+    let mut parts = rest.split('/');
+    let (owner, name) = (parts.next()?, parts.next()?);
+    if owner.is_empty() || name.is_empty() || parts.next().is_some() {
+        return None;
+    }
+    Some(format!("{owner}/{name}"))
+}
+
+/// Whether `gh` can be run at all
+pub async fn is_gh_available() -> bool {
+    Command::new(resolve_command("gh"))
+        .arg("--version")
+        .output()
+        .await
+        .map(|output| output.status.success())
+        .unwrap_or(false)
+}
+
+/// A repository to query: name, checkout path, and configured URL
+pub type PrTarget = (String, PathBuf, Option<String>);
+
+/// List open pull requests for `repos`, at most `max_parallel` at a time
+pub async fn collect_pull_requests(
+    repos: Vec<PrTarget>,
+    filter: PrFilter,
+    max_parallel: usize,
+) -> PrDashboard {
+    let semaphore = Arc::new(Semaphore::new(max_parallel.max(1)));
+    let mut handles = Vec::with_capacity(repos.len());
+
+    for (name, path, url) in repos {
+        let semaphore = Arc::clone(&semaphore);
+        let task_name = name.clone();
+        let handle = tokio::spawn(async move {
+            let _permit = semaphore.acquire_owned().await.ok();
+            list_repo_pull_requests(&task_name, path, url, filter).await
+        });
+        handles.push((name, handle));
+    }
+
+    let mut pull_requests = Vec::new();
+    let mut skipped = Vec::new();
+    for (name, handle) in handles {
+        let result = match handle.await {
+            Ok(result) => result,
+            Err(e) => Err(anyhow::anyhow!("task failed: {e}")),
+        };
+        match result {
+            Ok(prs) => pull_requests.extend(prs),
+            Err(reason) => skipped.push(SkippedRepo {
+                repository: name,
+                reason: reason.to_string(),
+            }),
+        }
+    }
+    pull_requests.sort_by_key(|pr| pr.created_at);
+
+    PrDashboard {
+        filter,
+        fetched_at: Utc::now(),
+        pull_requests,
+        skipped,
+        cached: false,
+    }
+}
+
+async fn list_repo_pull_requests(
+    name: &str,
+    path: PathBuf,
+    url: Option<String>,
+    filter: PrFilter,
+) -> Result<Vec<PullRequest>> {
+    let url = match url {
+        Some(url) => Some(url),
+        None if path.exists() => backend::current().remote_url(&path).await.ok().flatten(),
+        None => None,
+    };
+    let github_repo = url
+        .as_deref()
+        .and_then(github_repo_from_url)
+        .context("no GitHub remote")?;
+
+    let output = Command::new(resolve_command("gh"))
+        .args(["pr", "list", "--repo", &github_repo, "--state", "open"])
+        .args(["--limit", PR_LIMIT, "--json", PR_FIELDS])
+        .args(filter.gh_args())
+        .output()
+        .instrument(timings::phase(
+            timings::PROVIDER,
+            format!("gh pr list --repo {github_repo}"),
+        ))
+        .await
+        .context("failed to run gh")?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!(
+            "gh failed: {}",
+            stderr.lines().next().unwrap_or("unknown error").trim()
+        );
+    }
+
+    parse_pr_list(name, &github_repo, &String::from_utf8_lossy(&output.stdout))
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GhPullRequest {
+    number: u64,
+    title: String,
+    #[serde(default)]
+    author: Option<GhAuthor>,
+    created_at: DateTime<Utc>,
+    url: String,
+    #[serde(default)]
+    is_draft: bool,
+    #[serde(default)]
+    review_decision: Option<String>,
+    #[serde(default)]
+    status_check_rollup: Option<Vec<GhCheck>>,
+}
+
+#[derive(Deserialize)]
+struct GhAuthor {
+    login: String,
+}
+
+/// A check run (`status`/`conclusion`) or commit status (`state`)
+#[derive(Deserialize)]
+struct GhCheck {
+    #[serde(default)]
+    status: Option<String>,
+    #[serde(default)]
+    conclusion: Option<String>,
+    #[serde(default)]
+    state: Option<String>,
+}
+
+fn parse_pr_list(repository: &str, github_repo: &str, json: &str) -> Result<Vec<PullRequest>> {
+    let prs: Vec<GhPullRequest> =
+        serde_json::from_str(json).context("unexpected gh pr list output")?;
+    Ok(prs
+        .into_iter()
+        .map(|pr| PullRequest {
+            repository: repository.to_string(),
+            github_repo: github_repo.to_string(),
+            number: pr.number,
+            title: pr.title,
+            author: pr.author.map(|a| a.login).unwrap_or_default(),
+            url: pr.url,
+            created_at: pr.created_at,
+            is_draft: pr.is_draft,
+            checks: checks_state(pr.status_check_rollup.as_deref().unwrap_or_default()),
+            review: pr
+                .review_decision
+                .filter(|decision| !decision.is_empty())
+                .map(|decision| decision.to_lowercase()),
+        })
+        .collect())
+}
+
+fn checks_state(checks: &[GhCheck]) -> ChecksState {
+    if checks.is_empty() {
+        return ChecksState::None;
+    }
+    let mut pending = false;
+    for check in checks {
+        let result = check
+            .conclusion
+            .as_deref()
+            .filter(|c| !c.is_empty())
+            .or(check.state.as_deref())
+            .unwrap_or_default();
+        match result {
+            "FAILURE" | "ERROR" | "CANCELLED" | "TIMED_OUT" | "ACTION_REQUIRED"
+            | "STARTUP_FAILURE" => return ChecksState::Failing,
+            "SUCCESS" | "NEUTRAL" | "SKIPPED" => {}
+            _ => pending = true,
+        }
+        if check.status.as_deref().is_some_and(|s| s != "COMPLETED") {
+            pending = true;
+        }
+    }
+    if pending {
+        ChecksState::Pending
+    } else {
+        ChecksState::Passing
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_github_repo_from_url() {
+        for url in [
+            "https://github.com/toolprint/vibe.git",
+            "https://github.com/toolprint/vibe",
+            "git@github.com:toolprint/vibe.git",
+            "ssh://git@github.com/toolprint/vibe.git",
+        ] {
+            assert_eq!(github_repo_from_url(url).as_deref(), Some("toolprint/vibe"));
+        }
+        assert_eq!(github_repo_from_url("https://gitlab.com/a/b.git"), None);
+        assert_eq!(github_repo_from_url("https://github.com/a"), None);
+    }
+
+    #[test]
+    fn test_parse_pr_list() {
+        let json = r#"[
+            {"number": 7, "title": "Add thing", "author": {"login": "ada"},
+             "createdAt": "2024-06-01T10:00:00Z", "url": "https://github.com/o/r/pull/7",
+             "isDraft": true, "reviewDecision": "CHANGES_REQUESTED",
+             "statusCheckRollup": [
+                {"__typename": "CheckRun", "status": "COMPLETED", "conclusion": "SUCCESS"},
+                {"__typename": "StatusContext", "state": "PENDING"}
+             ]},
+            {"number": 8, "title": "Fix", "author": null,
+             "createdAt": "2024-06-02T10:00:00Z", "url": "https://github.com/o/r/pull/8",
+             "isDraft": false, "reviewDecision": "", "statusCheckRollup": []}
+        ]"#;
+        let prs = parse_pr_list("app", "o/r", json).unwrap();
+        assert_eq!(prs.len(), 2);
+        assert_eq!(prs[0].author, "ada");
+        assert!(prs[0].is_draft);
+        assert_eq!(prs[0].checks, ChecksState::Pending);
+        assert_eq!(prs[0].review.as_deref(), Some("changes_requested"));
+        assert_eq!(prs[1].checks, ChecksState::None);
+        assert_eq!(prs[1].review, None);
+    }
+
+    #[test]
+    fn test_checks_state() {
+        let check = |status: Option<&str>, conclusion: Option<&str>| GhCheck {
+            status: status.map(str::to_string),
+            conclusion: conclusion.map(str::to_string),
+            state: None,
+        };
+        assert_eq!(
+            checks_state(&[check(Some("COMPLETED"), Some("SUCCESS"))]),
+            ChecksState::Passing
+        );
+        assert_eq!(
+            checks_state(&[
+                check(Some("IN_PROGRESS"), None),
+                check(Some("COMPLETED"), Some("FAILURE"))
+            ]),
+            ChecksState::Failing
+        );
+        assert_eq!(
+            checks_state(&[check(Some("QUEUED"), Some(""))]),
+            ChecksState::Pending
+        );
+    }
+
+    #[tokio::test]
+    async fn test_repositories_without_github_remote_are_skipped() {
+        let dashboard = collect_pull_requests(
+            vec![
+                (
+                    "lab".to_string(),
+                    PathBuf::from("/nonexistent/lab"),
+                    Some("https://gitlab.com/a/lab.git".to_string()),
+                ),
+                (
+                    "local".to_string(),
+                    PathBuf::from("/nonexistent/local"),
+                    None,
+                ),
+            ],
+            PrFilter::All,
+            2,
+        )
+        .await;
+        assert!(dashboard.pull_requests.is_empty());
+        let reasons: Vec<_> = dashboard
+            .skipped
+            .iter()
+            .map(|s| (s.repository.as_str(), s.reason.as_str()))
+            .collect();
+        assert_eq!(
+            reasons,
+            [("lab", "no GitHub remote"), ("local", "no GitHub remote")]
+        );
+    }
+
+    #[test]
+    fn test_cache_key() {
+        assert_eq!(PrFilter::Mine.cache_key(Some("work")), "mine:work");
+        assert_eq!(PrFilter::All.cache_key(None), "all:*");
+    }
+}
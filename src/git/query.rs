@@ -0,0 +1,316 @@
+//! Read-only git commands run on behalf of agents
+//!
+//! The `repo_git_query` MCP tool runs one git subcommand in one repository.
+//! Only [`DEFAULT_SUBCOMMANDS`] and the subcommands added under
+//! `safety.git_query_allow` may run. `branch` is always run with `--list`,
+//! and options that write files or reach outside the repository are refused,
+//! as are paths that are absolute or climb out with `..`, since `git diff`
+//! quietly switches to comparing arbitrary files when given one.
+//! Arguments are passed to git directly, never through a shell. Shell
+//! metacharacters are still rejected, because they are never needed by a
+//! read-only query and usually mean a caller expected a shell. Output is cut
+//! off at [`MAX_OUTPUT_BYTES`] and the command at [`TIMEOUT`].
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::path::{Component, Path};
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncReadExt};
+use tokio::process::Command;
+
+use crate::output::redact;
+
+/// Subcommands every query may use
+pub const DEFAULT_SUBCOMMANDS: &[&str] = &["log", "show", "diff", "blame", "rev-parse", "branch"];
+
+/// Bytes of stdout returned before the output is truncated
+pub const MAX_OUTPUT_BYTES: usize = 256 * 1024;
+
+/// Bytes of stderr returned
+const MAX_STDERR_BYTES: usize = 8 * 1024;
+
+/// How long a query may run before it is killed
+pub const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Characters a shell would interpret
+const SHELL_METACHARACTERS: &[char] = &[';', '|', '&', '$', '`', '<', '>', '\n', '\r', '\0'];
+
+/// Options refused for every subcommand: they write files, read files
+/// outside the repository, or run external programs
+const FORBIDDEN_OPTIONS: &[&str] = &[
+    "--output",
+    "--no-index",
+    "--ext-diff",
+    "--exec",
+    "--contents",
+    "--orderfile",
+    "--ignore-revs-file",
+    "-O",
+];
+
+/// Options that make `git branch` change branches instead of listing them
+const BRANCH_WRITE_OPTIONS: &[&str] = &[
+    "-d",
+    "-D",
+    "--delete",
+    "-m",
+    "-M",
+    "--move",
+    "-c",
+    "-C",
+    "--copy",
+    "-f",
+    "--force",
+    "-u",
+    "--set-upstream-to",
+    "--unset-upstream",
+    "--edit-description",
+    "-t",
+    "--track",
+    "--no-track",
+    "--create-reflog",
+];
+
+/// Why a query was refused or stopped; the code leads the message so agents
+/// can tell the cases apart
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum QueryError {
+    #[error("subcommand_not_allowed: '{0}' is not an allowed read-only git subcommand")]
+    Subcommand(String),
+    #[error("shell_metacharacters: argument '{0}' contains shell metacharacters")]
+    Metacharacters(String),
+    #[error("argument_not_allowed: '{0}' is not allowed in a read-only query")]
+    Argument(String),
+    #[error("path_outside_repository: '{0}' points outside the repository")]
+    OutsidePath(String),
+    #[error("timed_out: git {0} ran longer than {1} seconds")]
+    TimedOut(String, u64),
+}
+
+/// What a query printed
+#[derive(Debug, Clone, Serialize)]
+pub struct QueryOutput {
+    pub repository: String,
+    /// The command as run, e.g. `git log --oneline -5`
+    pub command: String,
+    pub exit_code: Option<i32>,
+    pub stdout: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub stderr: String,
+    /// Whether stdout was cut off at `limit_bytes`
+    pub truncated: bool,
+    pub limit_bytes: usize,
+}
+
+/// Check `subcommand` and `args` against the allow-list, returning the
+/// arguments to pass to git
+pub fn validate(
+    subcommand: &str,
+    args: &[String],
+    extra: &[String],
+) -> Result<Vec<String>, QueryError> {
+    let allowed =
+        DEFAULT_SUBCOMMANDS.contains(&subcommand) || extra.iter().any(|s| s == subcommand);
+    if !allowed || subcommand.starts_with('-') {
+        return Err(QueryError::Subcommand(subcommand.to_string()));
+    }
+
+    for arg in args {
+        if arg.contains(SHELL_METACHARACTERS) {
+            return Err(QueryError::Metacharacters(arg.clone()));
+        }
+        if !arg.starts_with('-') {
+            let path = Path::new(arg);
+            if path.has_root() || path.components().any(|c| c == Component::ParentDir) {
+                return Err(QueryError::OutsidePath(arg.clone()));
+            }
+            continue;
+        }
+        let option = arg.split('=').next().unwrap_or(arg);
+        // Short options also take their value attached, as in `-Ofile`
+        let short = if arg.starts_with("--") {
+            option
+        } else {
+            arg.get(..2).unwrap_or(arg)
+        };
+        if FORBIDDEN_OPTIONS.contains(&option)
+            || FORBIDDEN_OPTIONS.contains(&short)
+            || (subcommand == "branch" && BRANCH_WRITE_OPTIONS.contains(&option))
+        {
+            return Err(QueryError::Argument(arg.clone()));
+        }
+    }
+
+    let mut command = vec![subcommand.to_string()];
+    if subcommand == "branch" && !args.iter().any(|arg| arg == "--list" || arg == "-l") {
+        command.push("--list".to_string());
+    }
+    command.extend(args.iter().cloned());
+    Ok(command)
+}
+
+/// Run a validated query in `repo`
+pub async fn run(
+    repository: &str,
+    repo: &Path,
+    command: &[String],
+    limit_bytes: usize,
+    timeout: Duration,
+) -> Result<QueryOutput> {
+    let display = redact::command_line(
+        "git",
+        &command.iter().map(String::as_str).collect::<Vec<_>>(),
+    );
+    let mut child = Command::new("git")
+        .args(["--no-pager", "-c", "color.ui=never"])
+        .args(command)
+        .current_dir(repo)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .with_context(|| format!("Failed to run {display}"))?;
+    let stdout = child.stdout.take().context("git stdout was not captured")?;
+    let stderr = child.stderr.take().context("git stderr was not captured")?;
+
+    // Reading stops at the limit and drops the pipe, so git exits rather
+    // than block on a full pipe
+    let finished = tokio::time::timeout(timeout, async {
+        let (stdout, stderr) = tokio::try_join!(
+            read_capped(stdout, limit_bytes),
+            read_capped(stderr, MAX_STDERR_BYTES)
+        )?;
+        let status = child.wait().await?;
+        anyhow::Ok((stdout, stderr, status))
+    })
+    .await;
+    let Ok(finished) = finished else {
+        return Err(QueryError::TimedOut(command.join(" "), timeout.as_secs()).into());
+    };
+    let ((stdout, truncated), (stderr, _), status) = finished?;
+
+    Ok(QueryOutput {
+        repository: repository.to_string(),
+        command: display,
+        exit_code: status.code(),
+        stdout: String::from_utf8_lossy(&stdout).into_owned(),
+        stderr: redact::scrub(&String::from_utf8_lossy(&stderr)).into_owned(),
+        truncated,
+        limit_bytes,
+    })
+}
+
+/// Read up to `limit` bytes, and whether there was more
+async fn read_capped(
+    reader: impl AsyncRead + Unpin,
+    limit: usize,
+) -> std::io::Result<(Vec<u8>, bool)> {
+    let mut buffer = Vec::new();
+    reader
+        .take(limit as u64 + 1)
+        .read_to_end(&mut buffer)
+        .await?;
+    let truncated = buffer.len() > limit;
+    buffer.truncate(limit);
+    Ok((buffer, truncated))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_validate_allows_read_only_queries() {
+        assert_eq!(
+            validate("log", &args(&["--oneline", "-5", "HEAD@{1}"]), &[]).unwrap(),
+            args(&["log", "--oneline", "-5", "HEAD@{1}"])
+        );
+        assert_eq!(
+            validate("diff", &args(&["main..HEAD", "--", "src/lib.rs"]), &[]).unwrap(),
+            args(&["diff", "main..HEAD", "--", "src/lib.rs"])
+        );
+        assert_eq!(
+            validate("branch", &args(&["-a"]), &[]).unwrap(),
+            args(&["branch", "--list", "-a"])
+        );
+        assert_eq!(
+            validate("shortlog", &args(&["-sn"]), &args(&["shortlog"])).unwrap(),
+            args(&["shortlog", "-sn"])
+        );
+    }
+
+    #[test]
+    fn test_validate_rejects_with_codes() {
+        let code = |subcommand: &str, arguments: &[&str]| {
+            validate(subcommand, &args(arguments), &[])
+                .unwrap_err()
+                .to_string()
+                .split(':')
+                .next()
+                .unwrap()
+                .to_string()
+        };
+        assert_eq!(code("push", &[]), "subcommand_not_allowed");
+        assert_eq!(code("shortlog", &[]), "subcommand_not_allowed");
+        assert_eq!(code("--exec-path", &[]), "subcommand_not_allowed");
+        assert_eq!(code("log", &["HEAD; rm -rf /"]), "shell_metacharacters");
+        assert_eq!(code("show", &["$(whoami)"]), "shell_metacharacters");
+        assert_eq!(code("diff", &["--output=/tmp/x"]), "argument_not_allowed");
+        assert_eq!(
+            code("diff", &["--no-index", "/etc/passwd"]),
+            "argument_not_allowed"
+        );
+        assert_eq!(
+            code("diff", &["/tmp/secret", "/dev/null"]),
+            "path_outside_repository"
+        );
+        assert_eq!(
+            code("log", &["--", "src/../../secret"]),
+            "path_outside_repository"
+        );
+        assert_eq!(
+            code("blame", &["--contents", "/tmp/secret", "f"]),
+            "argument_not_allowed"
+        );
+        assert_eq!(
+            code("blame", &["--contents=secret", "f"]),
+            "argument_not_allowed"
+        );
+        assert_eq!(code("diff", &["-O/tmp/order"]), "argument_not_allowed");
+        assert_eq!(code("branch", &["-D", "main"]), "argument_not_allowed");
+        assert_eq!(
+            code("branch", &["--set-upstream-to=origin/x"]),
+            "argument_not_allowed"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_truncates_output() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let status = Command::new("git")
+            .args(["init", "--quiet"])
+            .current_dir(dir.path())
+            .status()
+            .await
+            .unwrap();
+        assert!(status.success());
+
+        let command = validate("rev-parse", &args(&["--git-dir", "--show-toplevel"]), &[]).unwrap();
+        let full = run("api", dir.path(), &command, MAX_OUTPUT_BYTES, TIMEOUT)
+            .await
+            .unwrap();
+        assert_eq!(full.exit_code, Some(0));
+        assert!(!full.truncated);
+        assert!(full.stdout.starts_with(".git\n"));
+
+        let cut = run("api", dir.path(), &command, 3, TIMEOUT).await.unwrap();
+        assert!(cut.truncated);
+        assert_eq!(cut.stdout, ".gi");
+    }
+}
@@ -0,0 +1,273 @@
+//! Backups of branches taken before vibe deletes them
+//!
+//! Removing a worktree with its branch, `vibe worktree cleanup` with branch
+//! deletion, and `vibe git sync --prune` first save each branch they are about
+//! to delete as `refs/vibe/backup/<timestamp>/<branch>`. That costs one ref,
+//! needs no checkout, and keeps the commits from being garbage collected.
+//! Remote-tracking branches dropped by a prune are saved as
+//! `remotes/<remote>/<branch>`. `vibe git refs restore` recreates a branch
+//! from its newest backup, and `vibe git refs gc` deletes old backups.
+//! Setting `safety.ref_backups: false` turns backups off.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::output::exit::CommandError;
+use crate::workspace::operations::execute_git_command;
+
+/// Namespace holding the backup refs
+pub const BACKUP_PREFIX: &str = "refs/vibe/backup/";
+
+/// Timestamp component of a backup ref; sorts chronologically and is a valid
+/// ref name
+const TIMESTAMP_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+static ENABLED: AtomicBool = AtomicBool::new(true);
+
+/// Turn backups on or off, from `safety.ref_backups`
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// A branch saved under [`BACKUP_PREFIX`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RefBackup {
+    /// Name of the deleted branch; `remotes/<remote>/<branch>` for a
+    /// remote-tracking branch
+    pub branch: String,
+    pub sha: String,
+    /// Full name of the backup ref
+    pub backup_ref: String,
+}
+
+/// Backups `vibe git refs gc` deleted from one repository
+#[derive(Debug, Clone, Serialize)]
+pub struct PrunedBackups {
+    pub name: String,
+    pub backups: Vec<RefBackup>,
+}
+
+impl RefBackup {
+    fn parse(backup_ref: &str, sha: &str) -> Option<Self> {
+        let (_, branch) = backup_ref.strip_prefix(BACKUP_PREFIX)?.split_once('/')?;
+        Some(Self {
+            branch: branch.to_string(),
+            sha: sha.to_string(),
+            backup_ref: backup_ref.to_string(),
+        })
+    }
+
+    /// When the backup was taken; `None` for a ref vibe did not name
+    pub fn created_at(&self) -> Option<DateTime<Utc>> {
+        let (timestamp, _) = self
+            .backup_ref
+            .strip_prefix(BACKUP_PREFIX)?
+            .split_once('/')?;
+        NaiveDateTime::parse_from_str(timestamp, TIMESTAMP_FORMAT)
+            .ok()
+            .map(|time| time.and_utc())
+    }
+
+    /// Whether this backs up `branch`, either the local branch itself or a
+    /// remote-tracking branch of that name
+    fn is_for(&self, branch: &str) -> bool {
+        self.branch == branch
+            || self
+                .branch
+                .strip_prefix("remotes/")
+                .and_then(|tracking| tracking.split_once('/'))
+                .is_some_and(|(_, name)| name == branch)
+    }
+}
+
+/// Save local `branch` before it is deleted; `None` when backups are off
+pub async fn backup_branch(repo: &Path, branch: &str) -> Result<Option<RefBackup>> {
+    backup(repo, &format!("refs/heads/{branch}"), branch).await
+}
+
+/// Save the remote-tracking branches `git fetch --prune` is about to delete
+pub async fn backup_pruned_branches(repo: &Path) -> Result<Vec<RefBackup>> {
+    let mut backups = Vec::new();
+    if !enabled() {
+        return Ok(backups);
+    }
+    let Some(remote) = fetch_remote(repo).await else {
+        return Ok(backups);
+    };
+    let output = execute_git_command(repo, &["remote", "prune", "--dry-run", &remote])
+        .await
+        .with_context(|| format!("Failed to list branches pruned from {remote}"))?;
+    for tracking in would_prune(&output) {
+        let name = format!("remotes/{tracking}");
+        if let Some(backup) = backup(repo, &format!("refs/{name}"), &name).await? {
+            backups.push(backup);
+        }
+    }
+    Ok(backups)
+}
+
+async fn backup(repo: &Path, reference: &str, name: &str) -> Result<Option<RefBackup>> {
+    if !enabled() {
+        return Ok(None);
+    }
+    let sha = execute_git_command(
+        repo,
+        &["rev-parse", "--verify", &format!("{reference}^{{commit}}")],
+    )
+    .await?;
+    let backup_ref = format!(
+        "{BACKUP_PREFIX}{}/{name}",
+        Utc::now().format(TIMESTAMP_FORMAT)
+    );
+    execute_git_command(repo, &["update-ref", &backup_ref, &sha])
+        .await
+        .with_context(|| format!("Failed to back up {name}"))?;
+    Ok(Some(RefBackup {
+        branch: name.to_string(),
+        sha,
+        backup_ref,
+    }))
+}
+
+/// The remote a plain `git fetch` fetches: the current branch's, else origin
+async fn fetch_remote(repo: &Path) -> Option<String> {
+    let remotes = execute_git_command(repo, &["remote"]).await.ok()?;
+    let branch_remote = match execute_git_command(repo, &["symbolic-ref", "--short", "HEAD"]).await
+    {
+        Ok(branch) => execute_git_command(repo, &["config", &format!("branch.{branch}.remote")])
+            .await
+            .ok(),
+        Err(_) => None,
+    };
+    let remote = branch_remote.unwrap_or_else(|| "origin".to_string());
+    remotes.lines().any(|r| r == remote).then_some(remote)
+}
+
+/// Remote-tracking branches, as `<remote>/<branch>`, in the output of
+/// `git remote prune --dry-run`
+fn would_prune(output: &str) -> impl Iterator<Item = &str> {
+    output.lines().filter_map(|line| {
+        line.trim()
+            .strip_prefix("* [would prune] ")
+            .map(str::trim)
+            .filter(|tracking| !tracking.is_empty())
+    })
+}
+
+/// All backups in `repo`, oldest first
+pub async fn list(repo: &Path) -> Result<Vec<RefBackup>> {
+    let output = execute_git_command(
+        repo,
+        &[
+            "for-each-ref",
+            "--format=%(objectname) %(refname)",
+            BACKUP_PREFIX,
+        ],
+    )
+    .await?;
+    Ok(output
+        .lines()
+        .filter_map(|line| {
+            let (sha, reference) = line.split_once(' ')?;
+            RefBackup::parse(reference, sha)
+        })
+        .collect())
+}
+
+/// Recreate `branch` from its newest backup
+pub async fn restore(repo: &Path, branch: &str) -> Result<RefBackup> {
+    let backups = list(repo).await?;
+    // A local branch's own backup wins over a remote-tracking one
+    let backup = backups
+        .iter()
+        .rev()
+        .find(|backup| backup.branch == branch)
+        .or_else(|| backups.iter().rev().find(|backup| backup.is_for(branch)))
+        .cloned()
+        .ok_or_else(|| {
+            CommandError::not_found_with_hint(
+                "Branch backup",
+                branch,
+                "Backups are taken when vibe deletes a branch",
+            )
+        })?;
+
+    if execute_git_command(
+        repo,
+        &[
+            "rev-parse",
+            "--verify",
+            "--quiet",
+            &format!("refs/heads/{branch}"),
+        ],
+    )
+    .await
+    .is_ok()
+    {
+        return Err(CommandError::Usage(format!("Branch '{branch}' already exists")).into());
+    }
+    execute_git_command(repo, &["branch", branch, &backup.sha])
+        .await
+        .with_context(|| format!("Failed to recreate branch {branch}"))?;
+    Ok(backup)
+}
+
+/// Delete backups taken more than `older_than` ago
+pub async fn gc(repo: &Path, older_than: Duration) -> Result<Vec<RefBackup>> {
+    let cutoff = Utc::now() - older_than;
+    let mut pruned = Vec::new();
+    for backup in list(repo).await? {
+        if backup.created_at().is_some_and(|time| time < cutoff) {
+            execute_git_command(repo, &["update-ref", "-d", &backup.backup_ref, &backup.sha])
+                .await?;
+            pruned.push(backup);
+        }
+    }
+    Ok(pruned)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backup_ref_parsing() {
+        let backup =
+            RefBackup::parse("refs/vibe/backup/20240102T030405Z/feature/login", "abc123").unwrap();
+        assert_eq!(backup.branch, "feature/login");
+        assert_eq!(
+            backup.created_at().unwrap().to_rfc3339(),
+            "2024-01-02T03:04:05+00:00"
+        );
+        assert!(backup.is_for("feature/login"));
+
+        let tracking = RefBackup::parse(
+            "refs/vibe/backup/20240102T030405Z/remotes/origin/main",
+            "abc",
+        )
+        .unwrap();
+        assert!(tracking.is_for("main"));
+        assert!(!tracking.is_for("origin"));
+
+        let foreign = RefBackup::parse("refs/vibe/backup/manual/topic", "abc").unwrap();
+        assert_eq!(foreign.created_at(), None);
+    }
+
+    #[test]
+    fn test_would_prune() {
+        let output = "Pruning origin\nURL: git@example.com:org/api.git\n \
+                      * [would prune] origin/feature/old\n \
+                      * [would prune] origin/fix";
+        assert_eq!(
+            would_prune(output).collect::<Vec<_>>(),
+            ["origin/feature/old", "origin/fix"]
+        );
+    }
+}
@@ -0,0 +1,350 @@
+//! Per-repository advisory lock for operations that change a repository
+//!
+//! `vibe git sync`, `vibe git exec` with a command that writes, and worktree
+//! create, remove, and cleanup hold `.git/vibe.lock` while they run, so two
+//! vibe processes never interleave git commands in one repository. The file
+//! records the holder's PID and operation; a lock whose process is gone is
+//! stale and taken over. Another operation waits up to
+//! `preferences.lock_timeout_secs` for it and then gives up with
+//! [`RepoLocked`]. Linked worktrees share their main repository's lock, and
+//! a process that already holds a lock can take it again.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use tokio::io::AsyncWriteExt;
+#[cfg(not(unix))]
+use tokio::process::Command;
+use tracing::debug;
+
+/// Lock file name inside the repository's git directory
+pub const LOCK_FILE: &str = "vibe.lock";
+
+/// Seconds to wait for a held lock unless `preferences.lock_timeout_secs`
+/// says otherwise
+pub const DEFAULT_TIMEOUT_SECS: u64 = 10;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// How long a lock file may stay unreadable, while its holder writes it,
+/// before it counts as stale
+const WRITE_GRACE: Duration = Duration::from_secs(2);
+
+static TIMEOUT_SECS: AtomicU64 = AtomicU64::new(DEFAULT_TIMEOUT_SECS);
+
+/// Set how long [`acquire`] waits for a held lock
+pub fn set_timeout(timeout: Duration) {
+    TIMEOUT_SECS.store(timeout.as_secs(), Ordering::Relaxed);
+}
+
+/// How long [`acquire`] waits for a held lock
+pub fn timeout() -> Duration {
+    Duration::from_secs(TIMEOUT_SECS.load(Ordering::Relaxed))
+}
+
+/// Contents of a lock file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LockHolder {
+    pub pid: u32,
+    pub operation: String,
+    pub acquired_at: DateTime<Utc>,
+}
+
+/// Another vibe process kept the lock past the timeout
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("locked by {operation} (pid {pid})")]
+pub struct RepoLocked {
+    pub operation: String,
+    pub pid: u32,
+}
+
+/// A held lock, released when dropped
+#[must_use = "the lock is released as soon as it is dropped"]
+#[derive(Debug)]
+pub struct RepoLock {
+    /// The lock file to remove on release; `None` when this process already
+    /// held the lock or the directory is not a repository
+    path: Option<PathBuf>,
+}
+
+impl Drop for RepoLock {
+    fn drop(&mut self) {
+        if let Some(path) = &self.path {
+            if let Err(e) = std::fs::remove_file(path) {
+                debug!("Failed to release {}: {}", path.display(), e);
+            }
+        }
+    }
+}
+
+/// Lock `repo` for `operation`, waiting up to [`timeout`] for another
+/// process to release it
+pub async fn acquire(repo: &Path, operation: &str) -> Result<RepoLock> {
+    acquire_within(repo, operation, timeout()).await
+}
+
+/// [`acquire`] with an explicit timeout
+pub async fn acquire_within(repo: &Path, operation: &str, timeout: Duration) -> Result<RepoLock> {
+    let Some(git_dir) = common_git_dir(repo) else {
+        return Ok(RepoLock { path: None });
+    };
+    let path = git_dir.join(LOCK_FILE);
+    let holder = LockHolder {
+        pid: std::process::id(),
+        operation: operation.to_string(),
+        acquired_at: Utc::now(),
+    };
+    let contents = serde_json::to_string(&holder)?;
+    let started = Instant::now();
+
+    loop {
+        match create_lock_file(&path, &contents).await {
+            Ok(()) => return Ok(RepoLock { path: Some(path) }),
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => {}
+            Err(e) => {
+                return Err(e).with_context(|| format!("Failed to create {}", path.display()))
+            }
+        }
+
+        let current = match tokio::fs::read_to_string(&path).await {
+            Ok(current) => current,
+            // Released between our attempt and the read
+            Err(e) if e.kind() == ErrorKind::NotFound => continue,
+            Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+        };
+        match serde_json::from_str::<LockHolder>(&current) {
+            Ok(other) if other.pid == holder.pid => return Ok(RepoLock { path: None }),
+            Ok(other) if !process_alive(other.pid).await => {
+                debug!(
+                    "Taking over stale lock {} from {} (pid {})",
+                    path.display(),
+                    other.operation,
+                    other.pid
+                );
+                remove_stale(&path, &current).await;
+                continue;
+            }
+            Ok(other) => {
+                if started.elapsed() >= timeout {
+                    return Err(RepoLocked {
+                        operation: other.operation,
+                        pid: other.pid,
+                    }
+                    .into());
+                }
+            }
+            Err(_) => {
+                if modified_ago(&path)
+                    .await
+                    .is_some_and(|age| age > WRITE_GRACE)
+                {
+                    debug!("Removing unreadable lock {}", path.display());
+                    remove_stale(&path, &current).await;
+                    continue;
+                }
+            }
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+async fn create_lock_file(path: &Path, contents: &str) -> std::io::Result<()> {
+    let mut file = tokio::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+        .await?;
+    file.write_all(contents.as_bytes()).await?;
+    file.sync_all().await
+}
+
+/// Remove the lock file at `path` if it still holds `stale`
+///
+/// Two waiters can find the same stale lock, and the slower one must not
+/// delete the lock the faster one has created since. The file is first
+/// renamed to a name only this call uses, so no other process can replace it
+/// underneath us, and then checked: a lock that is no longer the stale one is
+/// put back.
+async fn remove_stale(path: &Path, stale: &str) {
+    static TAKEOVERS: AtomicU64 = AtomicU64::new(0);
+    let claimed = path.with_extension(format!(
+        "lock.stale-{}-{}",
+        std::process::id(),
+        TAKEOVERS.fetch_add(1, Ordering::Relaxed)
+    ));
+    if let Err(e) = tokio::fs::rename(path, &claimed).await {
+        if e.kind() != ErrorKind::NotFound {
+            debug!("Failed to claim {}: {}", path.display(), e);
+        }
+        return;
+    }
+    let still_stale = tokio::fs::read_to_string(&claimed)
+        .await
+        .is_ok_and(|contents| contents == stale);
+    // A hard link, unlike a rename, fails rather than replace a lock
+    // created in the meantime
+    if !still_stale {
+        if let Err(e) = tokio::fs::hard_link(&claimed, path).await {
+            debug!("Failed to restore {}: {}", path.display(), e);
+        }
+    }
+    if let Err(e) = tokio::fs::remove_file(&claimed).await {
+        debug!("Failed to remove {}: {}", claimed.display(), e);
+    }
+}
+
+async fn modified_ago(path: &Path) -> Option<Duration> {
+    let modified = tokio::fs::metadata(path).await.ok()?.modified().ok()?;
+    modified.elapsed().ok()
+}
+
+/// The git directory shared by `repo` and its linked worktrees
+fn common_git_dir(repo: &Path) -> Option<PathBuf> {
+    let dot_git = repo.join(".git");
+    if dot_git.is_dir() {
+        return Some(dot_git);
+    }
+    // A linked worktree: `.git` is a file pointing at its own git directory,
+    // whose `commondir` points back at the main repository's
+    let contents = std::fs::read_to_string(&dot_git).ok()?;
+    let git_dir = repo.join(contents.strip_prefix("gitdir:")?.trim());
+    match std::fs::read_to_string(git_dir.join("commondir")) {
+        Ok(common) => Some(git_dir.join(common.trim())),
+        Err(_) => Some(git_dir),
+    }
+}
+
+/// Whether a process with `pid` is running; unknown counts as running
+#[cfg(unix)]
+async fn process_alive(pid: u32) -> bool {
+    let Ok(pid) = libc::pid_t::try_from(pid) else {
+        return false;
+    };
+    // SAFETY: signal 0 only checks that the process exists and may be
+    // signalled; nothing is delivered
+    if unsafe { libc::kill(pid, 0) } == 0 {
+        return true;
+    }
+    // A process owned by another user refuses the signal but exists
+    std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
+
+/// Whether a process with `pid` is running; unknown counts as running
+#[cfg(not(unix))]
+async fn process_alive(pid: u32) -> bool {
+    match Command::new("tasklist")
+        .args(["/FI", &format!("PID eq {pid}"), "/NH"])
+        .output()
+        .await
+    {
+        Ok(output) => String::from_utf8_lossy(&output.stdout).contains(&pid.to_string()),
+        Err(_) => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn repo() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        std::fs::create_dir(dir.path().join(".git")).unwrap();
+        dir
+    }
+
+    fn holder(repo: &Path) -> LockHolder {
+        let contents = std::fs::read_to_string(repo.join(".git").join(LOCK_FILE)).unwrap();
+        serde_json::from_str(&contents).unwrap()
+    }
+
+    fn git_dir_entries(repo: &Path) -> Vec<String> {
+        std::fs::read_dir(repo.join(".git"))
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect()
+    }
+
+    fn write_holder(repo: &Path, pid: u32) {
+        let holder = LockHolder {
+            pid,
+            operation: "sync".to_string(),
+            acquired_at: Utc::now(),
+        };
+        std::fs::write(
+            repo.join(".git").join(LOCK_FILE),
+            serde_json::to_string(&holder).unwrap(),
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_lock_is_released_on_drop_and_reentrant() {
+        let repo = repo();
+        let lock_file = repo.path().join(".git").join(LOCK_FILE);
+
+        let outer = acquire(repo.path(), "sync").await.unwrap();
+        assert_eq!(holder(repo.path()).operation, "sync");
+        let inner = acquire(repo.path(), "worktree remove").await.unwrap();
+        drop(inner);
+        assert!(lock_file.exists());
+        drop(outer);
+        assert!(!lock_file.exists());
+    }
+
+    #[tokio::test]
+    async fn test_live_holder_times_out() {
+        let repo = repo();
+        // pid 1 is always running and never this test
+        write_holder(repo.path(), 1);
+
+        let error = acquire_within(repo.path(), "exec", Duration::ZERO)
+            .await
+            .unwrap_err();
+        let locked = error.downcast_ref::<RepoLocked>().unwrap();
+        assert_eq!(locked.to_string(), "locked by sync (pid 1)");
+    }
+
+    #[tokio::test]
+    async fn test_stale_lock_is_taken_over() {
+        let repo = repo();
+        // Above any kernel's pid_max
+        write_holder(repo.path(), 999_999_999);
+
+        let _lock = acquire_within(repo.path(), "exec", Duration::ZERO)
+            .await
+            .unwrap();
+        let holder = holder(repo.path());
+        assert_eq!(holder.pid, std::process::id());
+        assert_eq!(holder.operation, "exec");
+    }
+
+    #[tokio::test]
+    async fn test_slower_waiter_keeps_the_new_lock() {
+        let repo = repo();
+        let lock_file = repo.path().join(".git").join(LOCK_FILE);
+        write_holder(repo.path(), 999_999_999);
+        let stale = std::fs::read_to_string(&lock_file).unwrap();
+
+        // Both waiters saw the stale lock; the faster one took it over
+        let lock = acquire_within(repo.path(), "exec", Duration::ZERO)
+            .await
+            .unwrap();
+        remove_stale(&lock_file, &stale).await;
+
+        assert_eq!(holder(repo.path()).operation, "exec");
+        assert_eq!(git_dir_entries(repo.path()), [LOCK_FILE]);
+        drop(lock);
+        assert!(!lock_file.exists());
+    }
+
+    #[tokio::test]
+    async fn test_process_alive() {
+        assert!(process_alive(std::process::id()).await);
+        assert!(!process_alive(999_999_999).await);
+    }
+}
@@ -8,6 +8,9 @@ use super::{GitConfig, Repository, SearchQuery};
 use crate::ui::workflows::{execute_workflow, CloneWorkflow};
 use crate::workspace::manager::WorkspaceManager;
 
+/// Earlier search results offered alongside new ones
+const PREVIOUSLY_SEEN_LIMIT: usize = 5;
+
 pub struct SearchEngine {
     providers: Vec<Box<dyn SearchProvider>>,
 }
@@ -90,8 +93,12 @@ impl SearchCommand {
 
         let engine = SearchEngine::new(config)?;
         let results = engine.search(&search_query).await?;
+        workspace_manager.remember_search_results(&results).await;
+        let seen = workspace_manager
+            .previously_seen_repositories(query, &results, PREVIOUSLY_SEEN_LIMIT)
+            .await;
 
-        if results.is_empty() {
+        if results.is_empty() && seen.is_empty() {
             println!(
                 "{} No repositories found for '{}'",
                 style("❌").red(),
@@ -107,13 +114,16 @@ impl SearchCommand {
         );
 
         // Display and select repository
-        let selected_repo = Self::display_interactive_results(&results, workspace_manager)?;
+        let selected_repo = Self::display_interactive_results(&results, &seen, workspace_manager)?;
 
         if let Some(repo) = selected_repo {
             // Use workflow system for seamless clone + configure + open experience
             let workflow = CloneWorkflow {
                 url: repo.url.clone(),
                 app: None, // Let user choose during workflow
+                group: None,
+                skip_configure: false,
+                on_collision: None,
             };
 
             execute_workflow(Box::new(workflow), workspace_manager).await?;
@@ -158,8 +168,12 @@ impl SearchCommand {
 
         let engine = SearchEngine::new(config)?;
         let results = engine.search(&search_query).await?;
+        workspace_manager.remember_search_results(&results).await;
+        let seen = workspace_manager
+            .previously_seen_repositories(&query, &results, PREVIOUSLY_SEEN_LIMIT)
+            .await;
 
-        if results.is_empty() {
+        if results.is_empty() && seen.is_empty() {
             println!(
                 "{} No repositories found for '{}'",
                 style("❌").red(),
@@ -177,13 +191,16 @@ impl SearchCommand {
         );
 
         // Display and select repository
-        let selected_repo = Self::display_interactive_results(&results, workspace_manager)?;
+        let selected_repo = Self::display_interactive_results(&results, &seen, workspace_manager)?;
 
         if let Some(repo) = selected_repo {
             // Use workflow system for seamless clone + configure + open experience
             let workflow = CloneWorkflow {
                 url: repo.url.clone(),
                 app: None, // Let user choose during workflow
+                group: None,
+                skip_configure: false,
+                on_collision: None,
             };
 
             execute_workflow(Box::new(workflow), workspace_manager).await?;
@@ -196,11 +213,13 @@ impl SearchCommand {
 
     fn display_interactive_results(
         results: &[Repository],
+        seen: &[Repository],
         workspace_manager: &WorkspaceManager,
     ) -> Result<Option<Repository>> {
-        let items: Vec<String> = results
+        let items: Vec<String> = seen
             .iter()
-            .map(|repo| {
+            .map(Self::format_previously_seen)
+            .chain(results.iter().map(|repo| {
                 let stars = if repo.stars > 0 {
                     format!("⭐ {} ", Self::format_stars(repo.stars))
                 } else {
@@ -231,7 +250,7 @@ impl SearchCommand {
                     "{}{}{}{} - {}",
                     stars, repo.full_name, lang, license, truncated_desc
                 )
-            })
+            }))
             .collect();
 
         let selection = Select::with_theme(&ColorfulTheme::default())
@@ -241,7 +260,22 @@ impl SearchCommand {
             .max_length(workspace_manager.get_git_search_results_page_size())
             .interact_opt()?;
 
-        Ok(selection.map(|i| results[i].clone()))
+        Ok(selection.map(|i| seen.iter().chain(results).nth(i).unwrap().clone()))
+    }
+
+    /// List entry for a repository remembered from an earlier search
+    fn format_previously_seen(repo: &Repository) -> String {
+        let desc = repo.description.as_deref().unwrap_or("No description");
+        let truncated_desc = if desc.chars().count() > 40 {
+            let truncated: String = desc.chars().take(40).collect();
+            format!("{truncated}...")
+        } else {
+            desc.to_string()
+        };
+        format!(
+            "🕘 {} - {} (previously seen)",
+            repo.full_name, truncated_desc
+        )
     }
 
     fn format_stars(count: u32) -> String {
@@ -294,8 +328,12 @@ impl SearchCommand {
 
         let engine = SearchEngine::new(config)?;
         let results = engine.search(&search_query).await?;
+        workspace_manager.remember_search_results(&results).await;
+        let seen = workspace_manager
+            .previously_seen_repositories(&query, &results, PREVIOUSLY_SEEN_LIMIT)
+            .await;
 
-        if results.is_empty() {
+        if results.is_empty() && seen.is_empty() {
             println!(
                 "{} No repositories found for '{}'",
                 style("❌").red(),
@@ -313,13 +351,16 @@ impl SearchCommand {
         );
 
         // Display enhanced results with workflow integration
-        let selected_repo = Self::display_enhanced_results(&results, workspace_manager)?;
+        let selected_repo = Self::display_enhanced_results(&results, &seen, workspace_manager)?;
 
         if let Some(repo) = selected_repo {
             // Use workflow system for complete clone + configure + open experience
             let workflow = CloneWorkflow {
                 url: repo.url.clone(),
                 app: None, // User will be prompted to configure during workflow
+                group: None,
+                skip_configure: false,
+                on_collision: None,
             };
 
             execute_workflow(Box::new(workflow), workspace_manager).await?;
@@ -333,12 +374,13 @@ impl SearchCommand {
     /// Enhanced results display with more repository information
     fn display_enhanced_results(
         results: &[Repository],
+        seen: &[Repository],
         workspace_manager: &WorkspaceManager,
     ) -> Result<Option<Repository>> {
-        let items: Vec<String> = results
+        let items: Vec<String> = seen
             .iter()
-            .enumerate()
-            .map(|(i, repo)| {
+            .map(Self::format_previously_seen)
+            .chain(results.iter().enumerate().map(|(i, repo)| {
                 let stars = if repo.stars > 0 {
                     format!("⭐ {} ", Self::format_stars(repo.stars))
                 } else {
@@ -375,7 +417,7 @@ impl SearchCommand {
                     license,
                     truncated_desc
                 )
-            })
+            }))
             .collect();
 
         let selection = Select::with_theme(&ColorfulTheme::default())
@@ -385,6 +427,6 @@ impl SearchCommand {
             .max_length(workspace_manager.get_git_search_results_page_size())
             .interact_opt()?;
 
-        Ok(selection.map(|i| results[i].clone()))
+        Ok(selection.map(|i| seen.iter().chain(results).nth(i).unwrap().clone()))
     }
 }
@@ -0,0 +1,280 @@
+//! Keeping forks in step with the repository they were forked from
+//!
+//! A repository counts as a fork when it has an `upstream` remote. GitHub
+//! forks without one are found through `gh repo view --json parent`, so the
+//! remote can be added. Syncing fetches `upstream` and moves the default
+//! branch forward only when that is a fast-forward; a default branch with
+//! commits of its own is reported and left alone.
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tokio::process::Command;
+use tracing::Instrument;
+
+use crate::git::backend::GitBackend;
+use crate::git::pull_requests::github_repo_from_url;
+use crate::output::timings;
+use crate::utils::platform::resolve_command;
+use crate::workspace::operations::execute_git_command;
+
+/// Name of the remote that points at the parent of a fork
+pub const UPSTREAM_REMOTE: &str = "upstream";
+
+/// What syncing a fork's default branch from upstream did
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "result", rename_all = "snake_case")]
+pub enum UpstreamSync {
+    UpToDate {
+        branch: String,
+    },
+    FastForwarded {
+        branch: String,
+        commits: usize,
+    },
+    /// The local branch has commits upstream doesn't, so it was not touched
+    Diverged {
+        branch: String,
+        ahead: usize,
+        behind: usize,
+    },
+}
+
+/// URL of the `upstream` remote, if there is one
+pub async fn upstream_url(repo: &Path) -> Option<String> {
+    execute_git_command(
+        repo,
+        &["config", "--get", &format!("remote.{UPSTREAM_REMOTE}.url")],
+    )
+    .await
+    .ok()
+    .filter(|url| !url.is_empty())
+}
+
+/// Clone URL of the repository a GitHub fork was forked from, asked of `gh`
+pub async fn github_parent_url(origin_url: &str) -> Option<String> {
+    #[derive(Deserialize)]
+    struct View {
+        parent: Option<Parent>,
+    }
+    #[derive(Deserialize)]
+    struct Parent {
+        name: String,
+        owner: Owner,
+    }
+    #[derive(Deserialize)]
+    struct Owner {
+        login: String,
+    }
+
+    let repo = github_repo_from_url(origin_url)?;
+    let output = Command::new(resolve_command("gh"))
+        .args(["repo", "view", &repo, "--json", "parent"])
+        .output()
+        .instrument(timings::phase(
+            timings::PROVIDER,
+            format!("gh repo view {repo}"),
+        ))
+        .await
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let parent = serde_json::from_slice::<View>(&output.stdout)
+        .ok()?
+        .parent?;
+    Some(format!(
+        "https://github.com/{}/{}.git",
+        parent.owner.login, parent.name
+    ))
+}
+
+/// Add `url` as the `upstream` remote
+pub async fn add_upstream_remote(repo: &Path, url: &str) -> Result<()> {
+    execute_git_command(repo, &["remote", "add", UPSTREAM_REMOTE, url]).await?;
+    Ok(())
+}
+
+/// The branch upstream treats as its default: `upstream/HEAD` or
+/// `origin/HEAD` when set, otherwise `main` or `master` if upstream has it
+pub async fn default_branch(repo: &Path) -> Option<String> {
+    for head in [
+        format!("refs/remotes/{UPSTREAM_REMOTE}/HEAD"),
+        "refs/remotes/origin/HEAD".to_string(),
+    ] {
+        if let Ok(target) =
+            execute_git_command(repo, &["symbolic-ref", "--short", "--quiet", &head]).await
+        {
+            if let Some((_, branch)) = target.split_once('/') {
+                return Some(branch.to_string());
+            }
+        }
+    }
+
+    for candidate in ["main", "master"] {
+        let remote_ref = format!("refs/remotes/{UPSTREAM_REMOTE}/{candidate}");
+        if execute_git_command(repo, &["rev-parse", "--verify", "--quiet", &remote_ref])
+            .await
+            .is_ok()
+        {
+            return Some(candidate.to_string());
+        }
+    }
+    None
+}
+
+/// Commits the local `main` or `master` is behind the same branch on
+/// `upstream`, from refs already fetched. `None` when the repository has no
+/// upstream remote, or neither branch exists on both sides.
+pub async fn upstream_behind(backend: &dyn GitBackend, repo: &Path) -> Result<Option<usize>> {
+    for candidate in ["main", "master"] {
+        if let Some((_, behind)) = backend
+            .ahead_behind(
+                repo,
+                &format!("refs/remotes/{UPSTREAM_REMOTE}/{candidate}"),
+                &format!("refs/heads/{candidate}"),
+            )
+            .await?
+        {
+            return Ok(Some(behind));
+        }
+    }
+    Ok(None)
+}
+
+/// Fetch `upstream` and fast-forward the default branch to it
+pub async fn sync_default_branch(backend: &dyn GitBackend, repo: &Path) -> Result<UpstreamSync> {
+    execute_git_command(repo, &["fetch", UPSTREAM_REMOTE]).await?;
+
+    let branch = default_branch(repo)
+        .await
+        .ok_or_else(|| anyhow!("could not tell which branch is upstream's default"))?;
+    let remote_ref = format!("refs/remotes/{UPSTREAM_REMOTE}/{branch}");
+    let local_ref = format!("refs/heads/{branch}");
+    let (ahead, behind) = backend
+        .ahead_behind(repo, &remote_ref, &local_ref)
+        .await?
+        .ok_or_else(|| anyhow!("no local branch '{branch}' to sync"))?;
+
+    if behind == 0 {
+        return Ok(UpstreamSync::UpToDate { branch });
+    }
+    if ahead > 0 {
+        return Ok(UpstreamSync::Diverged {
+            branch,
+            ahead,
+            behind,
+        });
+    }
+
+    if backend.head_branch(repo).await?.as_deref() == Some(branch.as_str()) {
+        execute_git_command(repo, &["merge", "--ff-only", &remote_ref]).await?;
+    } else {
+        // Refuses anything but a fast-forward, and branches checked out
+        // in another worktree
+        execute_git_command(repo, &["fetch", ".", &format!("{remote_ref}:{local_ref}")]).await?;
+    }
+
+    Ok(UpstreamSync::FastForwarded {
+        branch,
+        commits: behind,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::backend::subprocess::SubprocessBackend;
+    use std::process::Command as StdCommand;
+    use tempfile::TempDir;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let output = StdCommand::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "git {args:?}: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    fn commit(dir: &Path, file: &str) {
+        std::fs::write(dir.join(file), file).unwrap();
+        git(dir, &["add", "."]);
+        git(dir, &["commit", "-qm", file]);
+    }
+
+    /// A parent repository and a fork of it with `upstream` pointing back
+    fn fork_setup() -> (TempDir, std::path::PathBuf, std::path::PathBuf) {
+        let temp = TempDir::new().unwrap();
+        let parent = temp.path().join("parent");
+        std::fs::create_dir(&parent).unwrap();
+        git(&parent, &["init", "-q", "-b", "main"]);
+        git(&parent, &["config", "user.email", "test@example.com"]);
+        git(&parent, &["config", "user.name", "Test"]);
+        commit(&parent, "a");
+
+        let fork = temp.path().join("fork");
+        git(
+            temp.path(),
+            &["clone", "-q", parent.to_str().unwrap(), "fork"],
+        );
+        git(&fork, &["config", "user.email", "test@example.com"]);
+        git(&fork, &["config", "user.name", "Test"]);
+        git(
+            &fork,
+            &["remote", "add", "upstream", parent.to_str().unwrap()],
+        );
+        (temp, parent, fork)
+    }
+
+    #[tokio::test]
+    async fn test_sync_fast_forwards_default_branch() {
+        let (_temp, parent, fork) = fork_setup();
+        commit(&parent, "b");
+        commit(&parent, "c");
+
+        let backend = SubprocessBackend;
+        assert_eq!(upstream_url(&fork).await, parent.to_str().map(String::from));
+        assert_eq!(
+            sync_default_branch(&backend, &fork).await.unwrap(),
+            UpstreamSync::FastForwarded {
+                branch: "main".to_string(),
+                commits: 2
+            }
+        );
+        assert!(fork.join("c").exists());
+        assert_eq!(upstream_behind(&backend, &fork).await.unwrap(), Some(0));
+    }
+
+    #[tokio::test]
+    async fn test_sync_reports_diverged_branch() {
+        let (_temp, parent, fork) = fork_setup();
+        commit(&parent, "b");
+        commit(&fork, "local");
+
+        let backend = SubprocessBackend;
+        assert_eq!(
+            sync_default_branch(&backend, &fork).await.unwrap(),
+            UpstreamSync::Diverged {
+                branch: "main".to_string(),
+                ahead: 1,
+                behind: 1
+            }
+        );
+        assert!(!fork.join("b").exists());
+    }
+
+    #[tokio::test]
+    async fn test_not_a_fork() {
+        let (_temp, parent, _fork) = fork_setup();
+        assert_eq!(upstream_url(&parent).await, None);
+        assert_eq!(
+            upstream_behind(&SubprocessBackend, &parent).await.unwrap(),
+            None
+        );
+    }
+}
@@ -7,7 +7,8 @@
 use anyhow::Result;
 use clap::{Parser, Subcommand};
 use console::style;
-use std::path::PathBuf;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
 
 mod apps;
 mod cache;
@@ -21,8 +22,23 @@ mod utils;
 mod workspace;
 mod worktree;
 
+use output::exit::{exit_code_for, BatchSummary, CommandError};
+use output::redact;
+use output::CommandResult;
+use ui::prompts::{confirm_destructive, DestructiveAction};
 use ui::{prompts, state::VibeState};
-use workspace::WorkspaceManager;
+use workspace::launch_stats::LaunchSource;
+use workspace::manager::{
+    print_bulk_configure_summary, print_group_open_summary, AppConfigureOutcome, GroupOpenOutcome,
+    SyncOutcome,
+};
+use workspace::project_types::ProjectType;
+use workspace::{repo_abs_path, CleanMode, WorkspaceManager};
+use worktree::display::{
+    open_worktree_in_editor, open_worktree_shell, print_cleanup_report, print_exec_report,
+    print_status_compact, print_status_table, print_worktrees_compact, print_worktrees_table,
+};
+use worktree::exec::ExecOutcome;
 
 #[derive(Parser)]
 #[command(name = "vibe")]
@@ -46,6 +62,22 @@ struct Cli {
     #[arg(short, long, global = true)]
     verbose: bool,
 
+    /// Suppress informational output (-q); repeat to also silence warnings (-qq)
+    #[arg(short, long, global = true, action = clap::ArgAction::Count, conflicts_with = "verbose")]
+    quiet: u8,
+
+    /// Disable colored output (also honors the NO_COLOR environment variable)
+    #[arg(long, global = true)]
+    no_color: bool,
+
+    /// Log-friendly output without colors, emoji, or icons
+    #[arg(long, global = true)]
+    plain: bool,
+
+    /// Write DEBUG-level logs to this file (rotated daily)
+    #[arg(long, global = true, value_name = "PATH")]
+    log_file: Option<PathBuf>,
+
     /// Configuration file path
     #[arg(short, long, global = true)]
     config: Option<PathBuf>,
@@ -53,6 +85,19 @@ struct Cli {
     /// Override workspace root directory
     #[arg(short, long, global = true)]
     root: Option<PathBuf>,
+
+    /// Use the workspace root even when it looks unmounted (e.g. it was deleted)
+    #[arg(long, global = true)]
+    force_online: bool,
+
+    /// Print a single JSON result to stdout; progress output goes to stderr
+    #[arg(long, global = true)]
+    json: bool,
+
+    /// Print how long config loading, caches, git, provider calls, and
+    /// rendering took, plus the slowest operations, to stderr at the end
+    #[arg(long, global = true)]
+    timings: bool,
 }
 
 #[derive(Subcommand)]
@@ -82,6 +127,10 @@ enum Commands {
         /// Skip opening after create
         #[arg(long)]
         no_open: bool,
+
+        /// Add the new repository to this group, using the group's default app
+        #[arg(long)]
+        group: Option<String>,
     },
 
     /// Manage workspace configuration
@@ -96,21 +145,114 @@ enum Commands {
         command: GitCommands,
     },
 
+    /// Start a task in a worktree, open its pull request, and clean up once merged
+    Task {
+        #[command(subcommand)]
+        command: TaskCommands,
+    },
+
     /// Interactive recent repository selector (1-9)
-    Launch,
+    Launch {
+        /// Open the recent repository in this slot (1-9) without prompting
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=9))]
+        slot: Option<u8>,
+
+        /// Reopen the most recent repository with its last-used app
+        #[arg(long, conflicts_with = "slot")]
+        last: bool,
+    },
 
     /// Open repository with configured app
     Open {
-        /// Repository name
-        repo: String,
+        /// Repository name, or <repo>:<subproject> for a package of a monorepo
+        #[arg(required_unless_present = "group", conflicts_with = "group")]
+        repo: Option<String>,
+
+        /// Open every repository in this group, one after another
+        #[arg(short, long, requires = "app")]
+        group: Option<String>,
+
+        /// With --group, configure the app with its default template where it's missing
+        #[arg(long, requires = "group")]
+        configure_missing: bool,
 
-        /// App to open with (warp, iterm2, vscode, wezterm, cursor, windsurf)
+        /// App to open with (warp, iterm2, vscode, wezterm, cursor, windsurf), or
+        /// finder/files for the file manager and browser for the remote's web page
         #[arg(short, long)]
         app: Option<String>,
 
         /// Disable iTermocil for iTerm2 (use Dynamic Profiles instead)
         #[arg(long)]
         no_itermocil: bool,
+
+        /// Don't fetch the repository in the background (overrides preferences.fetch_on_open)
+        #[arg(long)]
+        no_fetch: bool,
+
+        /// Print the repository's absolute path instead of opening it
+        #[arg(long, conflicts_with_all = ["group", "app"])]
+        print_path: bool,
+    },
+
+    /// Print the absolute path of a repository or worktree task, for `cd "$(vibe path api)"`
+    Path {
+        /// Repository name or worktree task ID
+        target: String,
+    },
+
+    /// Suggest workspace repositories and earlier search results matching a description, offline
+    Suggest {
+        /// Free-text description, e.g. "web framework for tokio"
+        #[arg(required = true, num_args = 1..)]
+        query: Vec<String>,
+
+        /// Maximum number of suggestions
+        #[arg(short, long, default_value = "10")]
+        limit: usize,
+
+        /// Output format: table, json
+        #[arg(short, long, default_value = "table")]
+        format: String,
+    },
+
+    /// Search file contents across repositories (ripgrep, or git grep without it)
+    Grep {
+        /// Regular expression to search for
+        pattern: String,
+
+        /// Filter by group name
+        #[arg(short, long)]
+        group: Option<String>,
+
+        /// Only search one monorepo package, as <repo>:<subproject>
+        #[arg(short, long, conflicts_with = "group")]
+        subproject: Option<String>,
+
+        /// Only search repositories of this project type: rust, node, python, go
+        #[arg(long = "type", value_name = "TYPE", conflicts_with = "subproject")]
+        project_type: Option<ProjectType>,
+
+        /// Only search files matching this glob, e.g. '*.rs'
+        #[arg(long)]
+        glob: Option<String>,
+
+        /// Matches shown per repository
+        #[arg(short = 'm', long, default_value_t = workspace::search::DEFAULT_MAX_PER_REPO)]
+        max_results: usize,
+
+        /// Pick a match and open it in the configured editor
+        #[arg(long)]
+        open: bool,
+
+        /// Output format: table, json
+        #[arg(short, long, default_value = "table")]
+        format: String,
+    },
+
+    /// Inspect a single repository
+    Repo {
+        #[command(subcommand)]
+        command: RepoCommands,
     },
 
     /// Clone, configure, and open a repository in one command
@@ -130,6 +272,10 @@ enum Commands {
         #[arg(long)]
         no_open: bool,
 
+        /// Add the cloned repository to this group, using the group's default app
+        #[arg(long, conflicts_with_all = ["all", "as_name"])]
+        group: Option<String>,
+
         /// Clone all repositories for user/org (bulk mode)
         #[arg(long)]
         all: bool,
@@ -145,6 +291,40 @@ enum Commands {
         /// Skip confirmation prompts for bulk operations
         #[arg(long, requires = "all")]
         force: bool,
+
+        /// Show which repositories bulk cloning would clone or skip, without cloning
+        #[arg(long, requires = "all")]
+        dry_run: bool,
+
+        /// Output format for --dry-run: table, json
+        #[arg(long, default_value = "table", requires = "dry_run")]
+        format: String,
+
+        /// Re-list the user's or org's repositories instead of reusing the cached list
+        #[arg(long, requires = "all")]
+        refresh: bool,
+
+        /// Use the repository as a template: copy it without history into a new repository with this name
+        #[arg(long = "as", value_name = "NAME", conflicts_with = "all")]
+        as_name: Option<String>,
+
+        /// With --as, create a private GitHub repository for the result and push to it
+        #[arg(long, requires = "as_name")]
+        create_remote: bool,
+
+        /// With --as, set a template placeholder (NAME=VALUE, repeatable)
+        #[arg(long = "var", value_name = "NAME=VALUE", requires = "as_name")]
+        vars: Vec<String>,
+
+        /// What to do when the repository's name is configured or its directory exists (default: ask, or fail when not interactive)
+        #[arg(long, value_enum, conflicts_with_all = ["all", "as_name"])]
+        on_collision: Option<git::OnCollision>,
+    },
+
+    /// Clone and configure the repositories of a manifest from `vibe config export-manifest`
+    RestoreWorkspace {
+        /// Manifest file to restore
+        manifest: PathBuf,
     },
 
     /// Run first-time setup wizard
@@ -156,6 +336,9 @@ enum Commands {
 
     /// Run as MCP (Model Context Protocol) server
     Mcp {
+        #[command(subcommand)]
+        command: Option<McpCommands>,
+
         /// Use HTTP transport on specified port
         #[arg(long, conflicts_with = "stdio")]
         port: Option<u16>,
@@ -165,2268 +348,5340 @@ enum Commands {
         stdio: bool,
     },
 
-    /// Show getting started guide
-    Guide,
-}
-
-#[derive(Subcommand)]
-enum AppsCommands {
-    /// Configure app integration for a repository
-    Configure {
-        /// Repository name
-        repo: String,
-
-        /// App to configure (warp, iterm2, vscode, wezterm, cursor, windsurf)
-        app: String,
+    /// Interactive tutorial that runs real commands, resuming where you left off
+    Guide {
+        /// Jump to one chapter
+        #[arg(long, value_enum)]
+        topic: Option<ui::guide::Topic>,
 
-        /// Template to use
-        #[arg(short, long)]
-        template: Option<String>,
+        /// Forget which steps are done and start over
+        #[arg(long, conflicts_with = "topic")]
+        reset: bool,
     },
 
-    /// Show app configurations
-    Show {
-        /// Filter by repository name
-        #[arg(long)]
-        repo: Option<String>,
-
-        /// Filter by app name
-        #[arg(long)]
-        app: Option<String>,
+    /// Manage the vibe:// URL scheme
+    Uri {
+        #[command(subcommand)]
+        command: UriCommands,
     },
 
-    /// Manage app templates
-    Template {
+    /// Inspect the troubleshooting log file
+    Logs {
         #[command(subcommand)]
-        command: TemplateCommands,
+        command: LogsCommands,
     },
 
-    /// Install developer tools and applications
-    Install,
-}
-
-#[derive(Subcommand)]
-enum TemplateCommands {
-    /// List available templates
-    List {
-        /// App to list templates for
-        app: String,
+    /// Workspace statistics
+    Stats {
+        #[command(subcommand)]
+        command: StatsCommands,
     },
 
-    /// Create a new template
-    Create {
-        /// App to create template for
-        app: String,
-
-        /// Template name
-        name: String,
-
-        /// Source file for template content
-        #[arg(short, long)]
-        from_file: Option<PathBuf>,
+    /// Workspace-wide compliance scans
+    Scan {
+        #[command(subcommand)]
+        command: ScanCommands,
     },
 
-    /// Delete a template
-    Delete {
-        /// App to delete template from
-        app: String,
+    /// Recent activity across the workspace, with undo for removals
+    History {
+        #[command(subcommand)]
+        command: Option<HistoryCommands>,
 
-        /// Template name
-        name: String,
+        /// Number of events to show
+        #[arg(short, long, default_value = "20")]
+        limit: usize,
     },
 
-    /// Update default templates with current bundled versions
-    UpdateDefaults {
-        /// Only update specific app's default template
-        #[arg(short, long)]
-        app: Option<String>,
+    /// Removed repositories and worktrees, restorable until they expire
+    Trash {
+        #[command(subcommand)]
+        command: TrashCommands,
+    },
 
-        /// Skip confirmation prompt
-        #[arg(short, long)]
-        force: bool,
+    /// Commands run on vibe events, configured under `hooks.events`
+    Hooks {
+        #[command(subcommand)]
+        command: EventHooksCommands,
     },
-}
 
-#[derive(Subcommand)]
-enum ConfigCommands {
-    /// Initialize a new workspace configuration
-    Init {
-        /// Workspace name
-        #[arg(short, long)]
-        name: Option<String>,
+    /// Git commits, merges, authors, and new branches per repository over recent days
+    Activity {
+        /// Days to cover, ending today
+        #[arg(short, long, default_value_t = workspace::activity::DEFAULT_DAYS)]
+        days: u32,
 
-        /// Workspace root directory
+        /// Only include repositories in this group
         #[arg(short, long)]
-        root: Option<PathBuf>,
+        group: Option<String>,
 
-        /// Enable auto-discovery of repositories
+        /// Only count commits by this author (name or email); `me` means git config user.email
         #[arg(short, long)]
-        auto_discover: bool,
+        author: Option<String>,
+
+        /// Output format: table, json
+        #[arg(short, long, default_value = "table")]
+        format: String,
     },
 
-    /// Edit workspace configuration with default editor
-    Edit {
-        /// Open editor directly without prompts
-        #[arg(short, long)]
-        direct: bool,
+    /// Manage local caches
+    Cache {
+        #[command(subcommand)]
+        command: CacheCommands,
     },
 
-    /// Show current workspace configuration
-    Show {
-        /// Output format: yaml (default), json, pretty
-        #[arg(short, long, default_value = "yaml")]
+    /// Diagnose the environment: git, gh, config, caches, and apps
+    Doctor {
+        /// Output format: table, json
+        #[arg(short, long, default_value = "table")]
         format: String,
-
-        /// Show only a specific section: workspace, repositories, groups, apps, claude_agents
-        #[arg(short, long)]
-        section: Option<String>,
     },
 
-    /// Validate workspace configuration
-    Validate {
-        /// Check if all repository paths exist
-        #[arg(short, long)]
-        check_paths: bool,
+    /// Print a shell completion script (bash, zsh, fish, elvish, powershell)
+    Completions {
+        /// Shell to generate completions for
+        shell: clap_complete::Shell,
+    },
 
-        /// Check if all remote URLs are accessible
-        #[arg(short, long)]
-        check_remotes: bool,
+    /// Print the `vcd` shell function that changes into a repository (bash, zsh, fish)
+    ShellInit {
+        /// Shell to print the function for
+        shell: clap_complete::Shell,
+    },
 
-        /// Validate app integrations
-        #[arg(short, long)]
-        check_apps: bool,
+    /// Print completion candidates (called by the completion scripts)
+    #[command(name = "__complete", hide = true)]
+    Complete {
+        /// What to complete
+        kind: CompletionKind,
     },
+}
 
-    /// Factory reset - clear all configuration and reinitialize
-    Reset {
-        /// Skip confirmation prompts
-        #[arg(long)]
-        force: bool,
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum CompletionKind {
+    Repos,
+    Apps,
+    Worktrees,
+}
+
+#[derive(Subcommand)]
+enum LogsCommands {
+    /// Print the latest log entries
+    Show {
+        /// Number of lines to show
+        #[arg(short, long, default_value = "50")]
+        tail: usize,
     },
 
-    /// Create backup archive of all configuration files
-    Backup {
-        /// Output directory for backup file
-        #[arg(short, long)]
-        output: Option<PathBuf>,
+    /// Print the path of the current log file
+    Path,
+}
 
-        /// Custom backup name (default: timestamp)
-        #[arg(short, long)]
-        name: Option<String>,
+#[derive(Subcommand)]
+enum HistoryCommands {
+    /// Reverse a removal: restore a worktree (with its uncommitted changes) or a repository entry
+    Undo {
+        /// Event id from `vibe history`
+        id: i64,
     },
+}
+
+#[derive(Subcommand)]
+enum TrashCommands {
+    /// List what is in the trash, oldest first
+    List,
 
-    /// Restore configuration from backup archive
+    /// Put a removed repository entry or worktree back
     Restore {
-        /// Backup file to restore from
-        #[arg(short, long)]
-        backup: Option<PathBuf>,
+        /// Item id from `vibe trash list`
+        id: String,
+    },
 
-        /// Skip confirmation prompts
-        #[arg(long)]
-        force: bool,
+    /// Permanently delete items from the trash
+    Empty {
+        /// Only items removed longer ago than this, e.g. 30d or 2w
+        #[arg(long, value_name = "AGE")]
+        older_than: Option<String>,
+
+        /// Skip the confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
     },
 }
 
 #[derive(Subcommand)]
-enum WorktreeCommands {
-    /// Create a new worktree for parallel development
-    Create {
-        /// Task identifier to create worktree for
+enum TaskCommands {
+    /// Create the task's worktree (or reuse it) and open it in the worktree editor
+    Start {
+        /// Repository to work in
+        repo: String,
+
+        /// Task identifier, used for the branch name
         task_id: String,
 
-        /// Base branch to create worktree from
+        /// Branch to start from
         #[arg(short, long)]
         base_branch: Option<String>,
 
-        /// Force creation even if branch exists
+        /// What the task is about; the default pull request title
         #[arg(short, long)]
-        force: bool,
+        description: Option<String>,
 
-        /// Custom path for the worktree (overrides default)
+        /// Ticket or issue URL for the task
         #[arg(short, long)]
-        path: Option<PathBuf>,
+        link: Option<String>,
 
-        /// Storage mode: local (within repo) or global (centralized)
+        /// Editor to open the worktree in instead of the configured default_editor
         #[arg(short, long)]
-        mode: Option<String>,
+        editor: Option<String>,
 
-        /// Open worktree in editor after creation
-        #[arg(short, long)]
-        open: bool,
+        /// Only print the path, without opening the worktree
+        #[arg(long, conflicts_with = "editor")]
+        no_open: bool,
+    },
 
-        /// Editor command to use (overrides default)
+    /// Push the task's branch and open a pull request for it with gh
+    Finish {
+        /// Task identifier
+        task_id: String,
+
+        /// Repository of the task, when several have a task by that id
         #[arg(long)]
-        editor: Option<String>,
-    },
+        repo: Option<String>,
 
-    /// List all git worktrees with status
-    List {
-        /// Show only worktrees with the specified prefix
+        /// Pull request title instead of the worktree.task.pr_title template
         #[arg(short, long)]
-        prefix: Option<String>,
+        title: Option<String>,
 
-        /// Show detailed information
+        /// Pull request body instead of the worktree.task.pr_body template
         #[arg(short, long)]
-        verbose: bool,
-
-        /// Output format: table, json, compact
-        #[arg(short, long, default_value = "table")]
-        format: String,
+        body: Option<String>,
 
-        /// Show only worktrees with uncommitted changes
-        #[arg(short, long)]
-        dirty_only: bool,
+        /// Open the pull request as a draft
+        #[arg(long)]
+        draft: bool,
     },
 
-    /// Remove a worktree
-    Remove {
-        /// Task ID, branch name, or worktree path to remove
-        target: String,
+    /// Check the task's branch merged, then remove its worktree and branch
+    Done {
+        /// Task identifier
+        task_id: String,
 
-        /// Force removal even with uncommitted changes
+        /// Repository of the task, when several have a task by that id
+        #[arg(long)]
+        repo: Option<String>,
+
+        /// Clean up even if the branch isn't merged, discarding uncommitted changes
         #[arg(short, long)]
         force: bool,
+    },
+}
 
-        /// Also delete the branch after removing worktree
+#[derive(Subcommand)]
+enum EventHooksCommands {
+    /// Run an event's command with a made-up payload and show how it went
+    Test {
+        /// repo_cloned, sync_completed, worktree_created, worktree_removed, or cleanup_completed
+        event: workspace::events::HookEvent,
+    },
+}
+
+#[derive(Subcommand)]
+enum BranchesCommands {
+    /// List local and remote branches with age, author, and merge state (read-only)
+    Report {
+        /// Branches whose last commit is older than this many days are stale
+        #[arg(long, default_value_t = git::branches::DEFAULT_STALE_DAYS)]
+        stale_days: u64,
+
+        /// Filter by group name
         #[arg(short, long)]
-        delete_branch: bool,
+        group: Option<String>,
 
-        /// Skip confirmation prompts
-        #[arg(long)]
-        yes: bool,
+        /// Output format: table, json, csv, tsv
+        #[arg(short, long, default_value = "table")]
+        format: String,
+
+        /// Columns for csv and tsv output, comma-separated (default: all)
+        #[arg(long, value_delimiter = ',')]
+        fields: Vec<String>,
     },
+}
 
-    /// Show repository worktree health overview
-    Status {
-        /// Branch name to show detailed status for (shows summary if not specified)
-        branch: Option<String>,
+#[derive(Subcommand)]
+enum RepoCommands {
+    /// Show the toolchain versions a repository pins and whether PATH has them
+    Env {
+        /// Repository name
+        name: String,
 
-        /// Show detailed status for all worktrees
+        /// Output format: table, json
+        #[arg(short, long, default_value = "table")]
+        format: String,
+    },
+
+    /// Score repositories on uncommitted work, sync state, stale branches, and activity, worst first
+    Health {
+        /// Only score repositories in this group
         #[arg(short, long)]
-        all: bool,
+        group: Option<String>,
 
-        /// Output format: table, json, compact (for detailed view)
+        /// Output format: table, json
         #[arg(short, long, default_value = "table")]
         format: String,
+    },
+}
 
-        /// Show only files that have changed (forces detailed view)
+#[derive(Subcommand)]
+enum CacheCommands {
+    /// Delete cached data; everything unless a scope is given
+    Clear {
+        /// Only the repositories remembered from searches and clones for `vibe suggest`
         #[arg(long)]
-        files_only: bool,
+        suggestions: bool,
     },
 
-    /// Clean up merged worktrees
-    Clean {
-        /// Show what would be done without executing
-        #[arg(short, long)]
-        dry_run: bool,
+    /// Write the repository and git status caches to a compressed snapshot file
+    Export {
+        /// Snapshot file to write
+        file: PathBuf,
+    },
 
-        /// Force cleanup even with uncommitted changes
-        #[arg(short, long)]
-        force: bool,
+    /// Replace the repository and git status caches with a snapshot from `vibe cache export`
+    Import {
+        /// Snapshot file to read
+        file: PathBuf,
 
-        /// Minimum age in hours before cleanup
+        /// Only import when the caches are empty, keeping a warm local cache
         #[arg(long)]
-        age: Option<u64>,
+        if_empty: bool,
+    },
+}
 
-        /// Skip confirmation prompts
+#[derive(Subcommand)]
+enum FilesCommands {
+    /// Write the configured managed files into repositories
+    Sync {
+        /// Show per-repository diffs without writing anything
         #[arg(long)]
-        yes: bool,
-    },
+        dry_run: bool,
 
-    /// Open a worktree in configured editor
-    Open {
-        /// Task ID, branch name, or worktree path to open
-        target: String,
+        /// Target repositories (comma-separated)
+        #[arg(long)]
+        repos: Option<String>,
 
-        /// Editor command to use (overrides default)
+        /// Filter by group name
         #[arg(short, long)]
-        editor: Option<String>,
+        group: Option<String>,
     },
+}
 
-    /// Merge worktree changes to feature branch
-    Merge {
-        /// Task ID, branch name, or worktree path to merge
-        target: String,
-
-        /// Merge strategy: squash, rebase, standard
-        // Default is 'standard' to preserve git history and commit attribution
-        // See: https://gist.github.com/mitchellh/319019b1b8aac9110fcfb1862e0c97fb
-        #[arg(short, long, default_value = "standard")]
-        strategy: String,
+#[derive(Subcommand)]
+enum RefsCommands {
+    /// Recreate a deleted branch from its most recent backup
+    Restore {
+        /// Repository name
+        repo: String,
 
-        /// Skip confirmation prompts
-        #[arg(long)]
-        yes: bool,
+        /// Branch to recreate
+        branch: String,
     },
 
-    /// Backup worktree to remote repository
-    Backup {
-        /// Task ID, branch name, or worktree path to backup
-        target: String,
+    /// Delete branch backups taken longer ago than an age
+    Gc {
+        /// Age of the backups to delete, e.g. 30d or 2w
+        #[arg(long, value_name = "AGE", default_value = "30d")]
+        older_than: String,
 
-        /// Clean up worktree after successful backup
-        #[arg(long)]
-        cleanup_after: bool,
+        /// Filter by group name
+        #[arg(short, long)]
+        group: Option<String>,
+    },
+}
 
-        /// Skip confirmation prompts
+#[derive(Subcommand)]
+enum HooksCommands {
+    /// Install the configured hooks, chaining any hooks a repository already has
+    Sync {
+        /// Target repositories (comma-separated)
         #[arg(long)]
-        yes: bool,
-    },
+        repos: Option<String>,
 
-    /// Show conflict analysis for worktree
-    Conflicts {
-        /// Task ID, branch name, or worktree path to analyze
-        target: String,
+        /// Filter by group name
+        #[arg(short, long)]
+        group: Option<String>,
+    },
 
-        /// Show compact format (summary only)
-        #[arg(long)]
-        compact: bool,
+    /// Show whether each repository's hooks match the managed scripts
+    Status {
+        /// Filter by group name
+        #[arg(short, long)]
+        group: Option<String>,
 
-        /// Output format: table, json, compact
+        /// Output format: table, json
         #[arg(short, long, default_value = "table")]
         format: String,
     },
 
-    /// Manage worktree configuration
-    Config {
-        #[command(subcommand)]
-        action: WorktreeConfigCommands,
+    /// Remove managed hooks and restore the hooks they chained
+    Remove {
+        /// Target repositories (comma-separated)
+        #[arg(long)]
+        repos: Option<String>,
+
+        /// Filter by group name
+        #[arg(short, long)]
+        group: Option<String>,
     },
 }
 
 #[derive(Subcommand)]
-enum WorktreeConfigCommands {
-    /// Show current configuration
-    Show {
-        /// Show configuration for specific repository
-        #[arg(short = 'R', long)]
-        repository: Option<String>,
+enum StatsCommands {
+    /// Disk usage per repository, with reclaimable space and cleanup suggestions
+    Disk {
+        /// Only show the N largest repositories
+        #[arg(long)]
+        top: Option<usize>,
 
-        /// Output format: yaml, json, summary
-        #[arg(short, long, default_value = "summary")]
-        format: String,
-    },
+        /// Also measure linked worktrees and detect merged ones
+        #[arg(long)]
+        include_worktrees: bool,
 
-    /// Set configuration values
-    Set {
-        /// Configuration key (e.g., prefix, base_dir, cleanup.age_threshold_hours)
-        key: String,
+        /// Ignore cached sizes and walk every repository again
+        #[arg(long)]
+        refresh: bool,
 
-        /// Configuration value
-        value: String,
+        /// Output format: table, json, csv, tsv
+        #[arg(short, long, default_value = "table")]
+        format: String,
 
-        /// Apply to specific repository only
-        #[arg(short = 'R', long)]
-        repository: Option<String>,
+        /// Columns for csv and tsv output, comma-separated (default: all)
+        #[arg(long, value_delimiter = ',')]
+        fields: Vec<String>,
     },
 
-    /// Reset configuration to defaults
-    Reset {
-        /// Reset specific key only
+    /// Commits per author and repository, with aliases from preferences.author_aliases
+    Contributors {
+        /// Start of the window: a date (2024-01-01) or an age such as 90d, 3mo, or 1y
+        #[arg(long, default_value = workspace::contributors::DEFAULT_SINCE)]
+        since: String,
+
+        /// Only count repositories in this group
         #[arg(short, long)]
-        key: Option<String>,
+        group: Option<String>,
 
-        /// Reset configuration for specific repository
-        #[arg(short = 'R', long)]
-        repository: Option<String>,
+        /// Output format: table, json, csv, tsv
+        #[arg(short, long, default_value = "table")]
+        format: String,
+
+        /// Columns for csv and tsv output, comma-separated (default: all)
+        #[arg(long, value_delimiter = ',')]
+        fields: Vec<String>,
     },
 
-    /// Validate configuration
-    Validate,
+    /// App launches per repository and app, from local launch history
+    Usage {
+        /// Report windows in days (comma-separated)
+        #[arg(long, value_delimiter = ',', default_values_t = workspace::launch_stats::DEFAULT_WINDOWS)]
+        days: Vec<u32>,
 
-    /// Show configuration help and environment variables
-    Info,
+        /// Only show launches of this repository
+        #[arg(long)]
+        repo: Option<String>,
+
+        /// Output format: table, json, csv, tsv
+        #[arg(short, long, default_value = "table")]
+        format: String,
+
+        /// Columns for csv and tsv output, comma-separated (default: all)
+        #[arg(long, value_delimiter = ',')]
+        fields: Vec<String>,
+    },
 }
 
 #[derive(Subcommand)]
-enum GitCommands {
-    /// Scan workspace for git repositories
-    Scan {
-        /// Directory to scan for repositories
-        path: Option<PathBuf>,
+enum ScanCommands {
+    /// Licenses per repository, flagging missing and deny-listed ones
+    Licenses {
+        /// Only scan repositories in this group
+        #[arg(short, long)]
+        group: Option<String>,
 
-        /// Maximum depth to scan
-        #[arg(long, default_value = "3")]
-        depth: usize,
+        /// SPDX identifiers to deny, added to preferences.denied_licenses (comma-separated)
+        #[arg(long, value_delimiter = ',')]
+        deny: Vec<String>,
 
-        /// Add newly found repositories to config
-        #[arg(short, long)]
-        import: bool,
+        /// Output format: table, json, csv, tsv
+        #[arg(short, long, default_value = "table")]
+        format: String,
 
-        /// Re-clone missing repositories from config
-        #[arg(long)]
-        restore: bool,
+        /// Columns for csv and tsv output, comma-separated (default: all)
+        #[arg(long, value_delimiter = ',')]
+        fields: Vec<String>,
+    },
+}
 
-        /// Remove missing repositories from config
+#[derive(Subcommand)]
+enum McpCommands {
+    /// Check every tool's schema and call the read-only tools against this workspace
+    Validate {
+        /// Also call the mutating tools, in a temporary sandbox workspace or as a dry run
         #[arg(long)]
-        clean: bool,
+        deep: bool,
     },
+}
 
-    /// Discover git repositories in directory structure (deprecated: use scan)
-    Discover {
-        /// Directory to scan for repositories
-        path: Option<PathBuf>,
+#[derive(Subcommand)]
+enum UriCommands {
+    /// Register vibe as the operating system handler for vibe:// URLs
+    Register,
 
-        /// Maximum depth to scan
-        #[arg(short, long, default_value = "3")]
-        depth: usize,
+    /// Remove the vibe:// URL handler from the operating system
+    Unregister,
 
-        /// Auto-add discovered repositories to config
-        #[arg(short, long)]
-        auto_add: bool,
+    /// Show which application the operating system uses for vibe:// URLs
+    Status,
+
+    /// Handle a vibe:// URL (invoked by the operating system)
+    Handle {
+        /// The vibe:// URL to handle
+        uri: String,
     },
+}
 
-    /// Show status across all repositories
-    Status {
-        /// Show only repositories with changes
-        #[arg(short, long)]
-        dirty_only: bool,
+#[derive(Subcommand)]
+enum AppsCommands {
+    /// Configure app integration for a repository, or with --all-repos or --group for many
+    Configure {
+        /// Repository name; leave out with --all-repos or --group
+        repo: Option<String>,
 
-        /// Output format: table, json, compact
-        #[arg(short, long, default_value = "table")]
-        format: String,
+        /// App to configure (warp, iterm2, vscode, wezterm, cursor, windsurf)
+        app: Option<String>,
 
-        /// Filter by group name
+        /// Template to use
         #[arg(short, long)]
-        group: Option<String>,
-    },
-
-    /// Execute git commands across repositories
-    Exec {
-        /// Git command to execute
-        command: String,
+        template: Option<String>,
 
-        /// Target repositories (comma-separated)
-        #[arg(short, long)]
-        repos: Option<String>,
+        /// Configure the app for every repository in the workspace
+        #[arg(long, conflicts_with = "group")]
+        all_repos: bool,
 
-        /// Target group
+        /// Configure the app for every repository in this group
         #[arg(short, long)]
         group: Option<String>,
 
-        /// Run in parallel
-        #[arg(short, long)]
-        parallel: bool,
+        /// With --all-repos or --group, replace existing configurations of the app
+        #[arg(long)]
+        overwrite: bool,
     },
 
-    /// Sync repositories (fetch and pull)
-    Sync {
-        /// Only fetch, don't pull
+    /// Show app configurations
+    Show {
+        /// Filter by repository name
+        #[arg(long)]
+        repo: Option<String>,
+
+        /// Filter by app name
+        #[arg(long)]
+        app: Option<String>,
+    },
+
+    /// Manage app templates
+    Template {
+        #[command(subcommand)]
+        command: TemplateCommands,
+    },
+
+    /// Install developer tools and applications
+    Install,
+}
+
+#[derive(Subcommand)]
+enum TemplateCommands {
+    /// List available templates
+    List {
+        /// App to list templates for
+        app: String,
+    },
+
+    /// Create a new template
+    Create {
+        /// App to create template for
+        app: String,
+
+        /// Template name
+        name: String,
+
+        /// Source file for template content
         #[arg(short, long)]
-        fetch_only: bool,
+        from_file: Option<PathBuf>,
+    },
 
-        /// Prune remote tracking branches
+    /// Delete a template
+    Delete {
+        /// App to delete template from
+        app: String,
+
+        /// Template name
+        name: String,
+    },
+
+    /// Print what a template renders to for a repository, without launching the app
+    Render {
+        /// App the template belongs to
+        app: String,
+
+        /// Repository to render for
+        repository: String,
+
+        /// Template to render (default: the repository's, else the app's default)
         #[arg(short, long)]
-        prune: bool,
+        template: Option<String>,
+    },
 
-        /// Auto-commit dirty changes to dirty/{timestamp} branch before sync
+    /// Install a template pack from a git repository or directory
+    Install {
+        /// Git URL, owner/repo on GitHub, or local directory of the pack
+        source: String,
+
+        /// Don't record the pack in the configuration
+        #[arg(long)]
+        no_save: bool,
+    },
+
+    /// List installed template packs
+    Packs,
+
+    /// Remove a template pack and every template it installed
+    UninstallPack {
+        /// Pack name
+        name: String,
+    },
+
+    /// Update default templates with current bundled versions
+    UpdateDefaults {
+        /// Only update specific app's default template
         #[arg(short, long)]
-        save_dirty: bool,
+        app: Option<String>,
 
-        /// Target group
+        /// Skip confirmation prompt
         #[arg(short, long)]
-        group: Option<String>,
+        force: bool,
     },
+}
 
-    /// Clone a repository to the workspace
-    Clone {
-        /// Repository URL or identifier
-        url: String,
+#[derive(Subcommand)]
+enum ConfigCommands {
+    /// Initialize a new workspace configuration
+    Init {
+        /// Workspace name
+        #[arg(short, long)]
+        name: Option<String>,
 
-        /// Override default clone location
+        /// Workspace root directory
         #[arg(short, long)]
-        path: Option<PathBuf>,
+        root: Option<PathBuf>,
 
-        /// Open in configured editor after cloning
+        /// Enable auto-discovery of repositories
         #[arg(short, long)]
-        open: bool,
+        auto_discover: bool,
+    },
 
-        /// Run post-install commands (npm install, etc.)
+    /// Edit workspace configuration with default editor
+    Edit {
+        /// Open editor directly without prompts
         #[arg(short, long)]
-        install: bool,
+        direct: bool,
     },
 
-    /// Search for repositories interactively
-    Search,
+    /// Show current workspace configuration
+    Show {
+        /// Output format: yaml (default), json, pretty
+        #[arg(short, long, default_value = "yaml")]
+        format: String,
 
-    /// Reset repository configuration (clear all tracked repositories)
+        /// Show only a specific section: workspace, repositories, groups, apps, claude_agents
+        #[arg(short, long)]
+        section: Option<String>,
+
+        /// Show tokens, passwords, and other secrets instead of masking them (terminal only)
+        #[arg(long)]
+        show_secrets: bool,
+    },
+
+    /// Validate workspace configuration
+    Validate {
+        /// Check if all repository paths exist
+        #[arg(long)]
+        check_paths: bool,
+
+        /// Check if all remote URLs are accessible
+        #[arg(long)]
+        check_remotes: bool,
+
+        /// Validate app integrations
+        #[arg(long)]
+        check_apps: bool,
+    },
+
+    /// Factory reset - clear all configuration and reinitialize
     Reset {
-        /// Skip confirmation prompt
+        /// Skip confirmation prompts
         #[arg(long)]
         force: bool,
+
+        /// List every file and cache database that would be deleted, with
+        /// sizes, without deleting anything
+        #[arg(long, conflicts_with = "force")]
+        dry_run: bool,
+
+        /// Output format for --dry-run: table, json
+        #[arg(long, default_value = "table", requires = "dry_run")]
+        format: String,
     },
 
-    /// Manage git worktrees for parallel development
-    Worktree {
-        #[command(subcommand)]
-        action: WorktreeCommands,
+    /// Create backup archive of all configuration files
+    Backup {
+        /// Output directory for backup file
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Custom backup name (default: timestamp)
+        #[arg(short, long)]
+        name: Option<String>,
+
+        /// Encrypt the archive with a passphrase (from VIBE_BACKUP_PASSPHRASE or
+        /// prompted), producing a .tgz.age file
+        #[arg(long)]
+        encrypt: bool,
     },
-}
 
-/// Handle worktree subcommands
-async fn handle_worktree_command(
-    command: WorktreeCommands,
-    workspace_manager: &WorkspaceManager,
-    verbose: bool,
-) -> Result<()> {
-    use crate::worktree::config::WorktreeMode;
-    use crate::worktree::{CreateOptions, RemoveOptions, WorktreeManager};
-    use colored::*;
+    /// Restore configuration from backup archive, asking for the passphrase of
+    /// encrypted (.tgz.age) backups
+    Restore {
+        /// Backup file to restore from
+        #[arg(short, long)]
+        backup: Option<PathBuf>,
 
-    // Get the current repository root
-    let current_dir = std::env::current_dir()?;
-    let git_root = find_git_repository_root(&current_dir).await?;
+        /// Skip confirmation prompts
+        #[arg(long)]
+        force: bool,
+    },
 
-    match command {
-        WorktreeCommands::Create {
-            task_id,
-            base_branch,
-            force,
-            path,
-            mode,
-            open,
-            editor,
-        } => {
-            // Handle mode override for create command
-            let custom_config = if let Some(mode_str) = &mode {
-                use crate::worktree::config::WorktreeConfig;
-                let mut config = WorktreeConfig::load_with_overrides().unwrap_or_default();
-                config.mode = match mode_str.to_lowercase().as_str() {
-                    "global" => WorktreeMode::Global,
-                    "local" => WorktreeMode::Local,
-                    _ => {
-                        eprintln!("❌ Invalid mode '{}'. Use 'local' or 'global'", mode_str);
-                        return Ok(());
-                    }
-                };
-                Some(config)
-            } else {
-                None
-            };
+    /// Write the repositories, groups, and app templates to a portable manifest
+    ExportManifest {
+        /// File to write the manifest to (default: print it)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
 
-            // Create worktree manager with optional custom config
-            let worktree_manager = WorktreeManager::new(git_root.clone(), custom_config).await?;
-            let options = CreateOptions {
-                task_id: task_id.clone(),
-                base_branch,
-                force,
-                custom_path: path,
-            };
+    /// Move ~/.toolprint/vibe-workspace into the XDG config, data, cache, and state directories
+    MigrateHome {
+        /// Show what would move without moving anything
+        #[arg(long)]
+        dry_run: bool,
+    },
 
-            println!("Creating worktree for task: {}", task_id.cyan());
-            let worktree_info = worktree_manager
-                .create_worktree_with_options(options)
-                .await?;
+    /// Share groups, hooks, managed files, and templates through a git repository
+    Sync {
+        #[command(subcommand)]
+        command: ConfigSyncCommands,
+    },
 
-            println!("✅ Created worktree:");
-            println!("  Branch: {}", worktree_info.branch.yellow());
-            println!(
-                "  Path: {}",
-                worktree_info.path.display().to_string().blue()
-            );
+    /// Manage repository entries in the configuration
+    Repo {
+        #[command(subcommand)]
+        command: ConfigRepoCommands,
+    },
+}
 
-            if open {
-                let editor_cmd = editor.unwrap_or_else(|| "code".to_string());
-                open_worktree_in_editor(&worktree_info.path, &editor_cmd).await?;
-            }
-        }
+#[derive(Subcommand)]
+enum ConfigRepoCommands {
+    /// Remove a repository from the configuration, keeping its entry in the trash
+    Remove {
+        /// Repository name
+        name: String,
+    },
 
-        // For all other commands, create a default worktree manager
-        other_command => {
-            let worktree_manager = WorktreeManager::new(git_root, None).await?;
+    /// Re-add entries `vibe git scan --clean` removed, listing them without names
+    ReaddRemoved {
+        /// Entries to re-add
+        names: Vec<String>,
 
-            match other_command {
-                WorktreeCommands::Create { .. } => unreachable!(), // Already handled above
+        /// Re-add every removed entry
+        #[arg(long, conflicts_with = "names")]
+        all: bool,
+    },
 
-                WorktreeCommands::List {
-                    prefix,
-                    verbose,
-                    format,
-                    dirty_only,
-                } => {
-                    let worktrees = worktree_manager.list_worktrees().await?;
-                    let filtered_worktrees =
-                        filter_worktrees(worktrees, prefix.as_deref(), dirty_only);
+    /// Leave a repository out of workspace-wide sync, exec, and status runs
+    Exclude {
+        /// Repository name
+        name: String,
 
-                    match format.as_str() {
-                        "json" => print_worktrees_json(&filtered_worktrees)?,
-                        "compact" => print_worktrees_compact(&filtered_worktrees),
-                        _ => print_worktrees_table(&filtered_worktrees, verbose),
-                    }
-                }
+        /// Operations to exclude it from: sync, exec, status (default: all)
+        operations: Vec<workspace::BulkOperation>,
+    },
 
-                WorktreeCommands::Remove {
-                    target,
-                    force,
-                    delete_branch,
-                    yes,
-                } => {
-                    if !yes && !force {
-                        let confirmation = prompt_for_confirmation(&format!(
-                            "Remove worktree '{}'{}?",
-                            target,
-                            if delete_branch {
-                                " and delete branch"
-                            } else {
-                                ""
-                            }
-                        ))?;
+    /// Include an excluded repository in workspace-wide runs again
+    Include {
+        /// Repository name
+        name: String,
 
-                        if !confirmation {
-                            println!("Cancelled.");
-                            return Ok(());
-                        }
-                    }
+        /// Operations to include it in again: sync, exec, status (default: all)
+        operations: Vec<workspace::BulkOperation>,
+    },
 
-                    let options = RemoveOptions {
-                        target: target.clone(),
-                        force,
-                        delete_branch,
-                    };
+    /// List configured repositories with their detected project types
+    List {
+        /// Filter by group name
+        #[arg(short, long)]
+        group: Option<String>,
 
-                    println!("Removing worktree: {}", target.yellow());
-                    worktree_manager
-                        .remove_worktree_with_options(options)
-                        .await?;
-                    println!("✅ Worktree removed successfully");
-                }
+        /// Only repositories of this project type: rust, node, python, go
+        #[arg(long = "type", value_name = "TYPE")]
+        project_type: Option<ProjectType>,
 
-                WorktreeCommands::Status {
-                    branch,
-                    all: _,
-                    format,
-                    files_only,
-                } => {
-                    let worktrees = worktree_manager.list_worktrees().await?;
+        /// Output format: table, json, csv, tsv
+        #[arg(short, long, default_value = "table")]
+        format: String,
 
-                    // If a specific branch is requested or files_only, show detailed status
-                    if branch.is_some() || files_only {
-                        let target_worktrees = if let Some(branch_name) = branch {
-                            worktrees
-                                .into_iter()
-                                .filter(|w| w.branch == branch_name)
-                                .collect()
-                        } else {
-                            worktrees
-                        };
+        /// Columns for csv and tsv output, comma-separated (default: all)
+        #[arg(long, value_delimiter = ',')]
+        fields: Vec<String>,
+    },
+}
 
-                        if target_worktrees.is_empty() {
-                            println!("No matching worktrees found");
-                            return Ok(());
-                        }
+#[derive(Subcommand)]
+enum ConfigSyncCommands {
+    /// Clone the repository the shared configuration lives in
+    Setup {
+        /// Git URL of the config repository
+        url: String,
 
-                        match format.as_str() {
-                            "json" => print_status_json(&target_worktrees, files_only)?,
-                            "compact" => print_status_compact(&target_worktrees, files_only),
-                            _ => print_status_table(&target_worktrees, files_only),
-                        }
-                    } else {
-                        // Show repository summary by default
-                        print_repository_worktree_summary(&worktrees, &format, verbose)?;
-                    }
-                }
+        /// Replace a sync repository that is already set up
+        #[arg(long)]
+        force: bool,
+    },
 
-                WorktreeCommands::Clean {
-                    dry_run,
-                    force,
-                    age,
-                    yes,
-                } => {
-                    use crate::worktree::cleanup::{
-                        CleanupOptions, CleanupStrategy, WorktreeCleanup,
-                    };
+    /// Merge the shared configuration into the local one
+    Pull,
 
-                    let cleanup_options = CleanupOptions {
-                        strategy: CleanupStrategy::Discard,
-                        min_age_hours: age,
-                        force,
-                        dry_run,
-                        auto_confirm: yes,
-                        branch_prefix_filter: Some(worktree_manager.get_config().prefix.clone()),
-                        merged_only: true, // Default to merged only for safety
-                        min_merge_confidence: 0.7,
-                    };
+    /// Merge both ways and commit the result to the config repository
+    Push,
 
-                    let cleanup = WorktreeCleanup::new(
-                        worktree_manager.get_config().clone(),
-                        worktree_manager.get_operations(),
-                    );
+    /// Show which entries differ between the local and shared configuration
+    Status,
+}
 
-                    println!(
-                        "🧹 {} worktree cleanup...",
-                        if dry_run { "Simulating" } else { "Starting" }
-                    );
+#[derive(Subcommand)]
+enum WorktreeCommands {
+    /// Create a new worktree for parallel development
+    Create {
+        /// Task identifier to create worktree for
+        task_id: String,
 
-                    let report = cleanup.cleanup_worktrees(cleanup_options).await?;
+        /// Base branch to create worktree from
+        #[arg(short, long)]
+        base_branch: Option<String>,
 
-                    // Display results
-                    print_cleanup_report(&report);
-                }
+        /// Force creation even if branch exists
+        #[arg(short, long)]
+        force: bool,
 
-                WorktreeCommands::Open { target, editor } => {
-                    // Use the new resolution logic that tries task_id first, then path, then branch
-                    let worktree = worktree_manager.resolve_worktree_target(&target).await?;
+        /// With --force, recreate a branch matching a protected_branches pattern
+        #[arg(long, requires = "force")]
+        include_protected: bool,
 
-                    let editor_cmd = editor.unwrap_or_else(|| "code".to_string());
-                    open_worktree_in_editor(&worktree.path, &editor_cmd).await?;
-                }
+        /// Custom path for the worktree (overrides default)
+        #[arg(short, long)]
+        path: Option<PathBuf>,
 
-                WorktreeCommands::Merge {
-                    target,
-                    strategy,
-                    yes,
-                } => {
-                    use crate::worktree::cleanup::{
-                        CleanupOptions, CleanupStrategy, WorktreeCleanup,
-                    };
+        /// Storage mode: local (within repo) or global (centralized)
+        #[arg(short, long)]
+        mode: Option<String>,
 
-                    // Resolve the worktree target
-                    let worktree = worktree_manager.resolve_worktree_target(&target).await?;
+        /// Open worktree in editor after creation
+        #[arg(short, long)]
+        open: bool,
 
-                    // Validate merge strategy
-                    let merge_strategy = match strategy.to_lowercase().as_str() {
-                        "squash" => CleanupStrategy::MergeToFeature,
-                        "rebase" => CleanupStrategy::MergeToFeature, // TODO: Add specific rebase strategy if needed
-                        "standard" => CleanupStrategy::MergeToFeature, // TODO: Add specific standard merge strategy if needed
-                        _ => {
-                            eprintln!(
-                                "❌ Invalid merge strategy '{}'. Use: squash, rebase, or standard",
-                                strategy
-                            );
-                            return Ok(());
-                        }
-                    };
+        /// Editor command to use (overrides default)
+        #[arg(long)]
+        editor: Option<String>,
 
-                    // Prompt for confirmation if not auto-confirmed
-                    if !yes {
-                        let confirmation = format!(
-                            "Merge worktree '{}' using {} strategy?",
-                            worktree.branch, strategy
-                        );
-                        if !prompt_for_confirmation(&confirmation)? {
-                            println!("❌ Merge cancelled by user");
-                            return Ok(());
-                        }
-                    }
+        /// What the task is about, shown in listings and status
+        #[arg(long)]
+        description: Option<String>,
 
-                    let cleanup_options = CleanupOptions {
-                        strategy: merge_strategy,
-                        min_age_hours: None, // No age restriction for explicit merge
-                        force: false,
-                        dry_run: false,
-                        auto_confirm: true,
-                        branch_prefix_filter: None, // Target specific worktree
-                        merged_only: false,         // Allow merging unmerged branches
-                        min_merge_confidence: 0.0,  // Allow any confidence for explicit merge
-                    };
+        /// Ticket, issue, or pull request URL for the task
+        #[arg(long)]
+        link: Option<String>,
 
-                    let cleanup = WorktreeCleanup::new(
-                        worktree_manager.get_config().clone(),
-                        worktree_manager.get_operations(),
-                    );
+        /// Time-box the worktree: flag it once this long has passed (e.g. 3d, 12h)
+        #[arg(long, value_name = "DURATION")]
+        expires: Option<String>,
+    },
 
-                    println!(
-                        "🔀 Merging worktree '{}' using {} strategy...",
-                        worktree.branch, strategy
-                    );
+    /// List all git worktrees with status
+    List {
+        /// Show only worktrees with the specified prefix
+        #[arg(short, long)]
+        prefix: Option<String>,
 
-                    // Use branch name to filter to this specific worktree
-                    let cleanup_options = CleanupOptions {
-                        branch_prefix_filter: Some(worktree.branch.clone()), // Target specific branch
-                        ..cleanup_options
-                    };
+        /// Show detailed information
+        #[arg(short, long)]
+        verbose: bool,
 
-                    let report = cleanup.cleanup_worktrees(cleanup_options).await?;
+        /// Output format: table, json, compact, csv, tsv
+        #[arg(short, long, default_value = "table")]
+        format: String,
 
-                    if report.cleaned_count > 0 {
-                        println!("✅ Successfully merged worktree '{}'", worktree.branch);
-                    } else if report.failed_count > 0 {
-                        println!("❌ Merge failed:");
-                        for result in &report.worktree_results {
-                            if let Some(ref error) = result.error {
-                                println!("   {}", error);
-                            }
-                        }
-                    } else {
-                        println!("⚠️ No action taken - worktree may not meet merge criteria");
-                    }
-                }
+        /// Columns for csv and tsv output, comma-separated (default: all)
+        #[arg(long, value_delimiter = ',')]
+        fields: Vec<String>,
 
-                WorktreeCommands::Backup {
-                    target,
-                    cleanup_after,
-                    yes,
-                } => {
-                    use crate::worktree::cleanup::{
-                        CleanupOptions, CleanupStrategy, WorktreeCleanup,
-                    };
+        /// Show only worktrees with uncommitted changes
+        #[arg(short, long)]
+        dirty_only: bool,
+    },
 
-                    // Resolve the worktree target
-                    let worktree = worktree_manager.resolve_worktree_target(&target).await?;
+    /// Remove a worktree
+    Remove {
+        /// Task ID, branch name, or worktree path to remove
+        target: String,
 
-                    // Prompt for confirmation if not auto-confirmed
-                    if !yes {
-                        let confirmation = format!(
-                            "Backup worktree '{}' to remote{}?",
-                            worktree.branch,
-                            if cleanup_after {
-                                " and clean up afterward"
-                            } else {
-                                ""
-                            }
-                        );
-                        if !prompt_for_confirmation(&confirmation)? {
-                            println!("❌ Backup cancelled by user");
-                            return Ok(());
-                        }
-                    }
+        /// Force removal even with uncommitted changes
+        #[arg(short, long)]
+        force: bool,
 
-                    let cleanup_options = CleanupOptions {
-                        strategy: CleanupStrategy::BackupToOrigin,
-                        min_age_hours: None, // No age restriction for explicit backup
-                        force: false,
-                        dry_run: false,
-                        auto_confirm: true,
-                        branch_prefix_filter: None, // Target specific worktree
-                        merged_only: false,         // Allow backing up unmerged branches
-                        min_merge_confidence: 0.0,  // Allow any confidence for explicit backup
-                    };
+        /// Also delete the branch after removing worktree
+        #[arg(short, long)]
+        delete_branch: bool,
 
-                    let cleanup = WorktreeCleanup::new(
-                        worktree_manager.get_config().clone(),
-                        worktree_manager.get_operations(),
-                    );
+        /// With --force, delete a branch matching a protected_branches
+        /// pattern; branches GitHub protects are always kept
+        #[arg(long, requires = "force")]
+        include_protected: bool,
 
-                    println!("💾 Backing up worktree '{}' to remote...", worktree.branch);
+        /// Skip confirmation prompts
+        #[arg(long)]
+        yes: bool,
+    },
 
-                    // Use branch name to filter to this specific worktree
-                    let cleanup_options = CleanupOptions {
-                        branch_prefix_filter: Some(worktree.branch.clone()), // Target specific branch
-                        ..cleanup_options
-                    };
+    /// Show repository worktree health overview
+    Status {
+        /// Branch name to show detailed status for (defaults to the current
+        /// worktree when run from one, otherwise shows a summary)
+        branch: Option<String>,
 
-                    let report = cleanup.cleanup_worktrees(cleanup_options).await?;
+        /// Show detailed status for all worktrees
+        #[arg(short, long)]
+        all: bool,
 
-                    if report.cleaned_count > 0 {
-                        println!("✅ Successfully backed up worktree '{}'", worktree.branch);
+        /// Output format: table, json, compact (for detailed view)
+        #[arg(short, long, default_value = "table")]
+        format: String,
 
-                        // If cleanup_after is requested and backup was successful, remove the worktree
-                        if cleanup_after {
-                            println!("🧹 Cleaning up worktree after successful backup...");
-                            match worktree_manager
-                                .remove_worktree(target.clone(), false)
-                                .await
-                            {
-                                Ok(_) => println!("✅ Worktree cleaned up successfully"),
-                                Err(e) => {
-                                    println!("⚠️ Backup successful but cleanup failed: {}", e)
-                                }
-                            }
-                        }
-                    } else if report.failed_count > 0 {
-                        println!("❌ Backup failed:");
-                        for result in &report.worktree_results {
-                            if let Some(ref error) = result.error {
-                                println!("   {}", error);
-                            }
-                        }
-                    } else {
-                        println!("⚠️ No action taken - worktree may not meet backup criteria");
-                    }
-                }
+        /// Show only files that have changed (forces detailed view)
+        #[arg(long)]
+        files_only: bool,
+    },
 
-                WorktreeCommands::Conflicts {
-                    target,
-                    compact,
-                    format,
-                } => {
-                    use crate::worktree::merge_detection::detect_worktree_merge_status;
+    /// Clean up merged worktrees
+    Clean {
+        /// Show what would be done without executing
+        #[arg(short, long)]
+        dry_run: bool,
 
-                    // Resolve the worktree target
-                    let worktree = worktree_manager.resolve_worktree_target(&target).await?;
+        /// Force cleanup even with uncommitted changes
+        #[arg(short, long)]
+        force: bool,
 
-                    println!(
-                        "🔍 Analyzing conflicts for worktree '{}'...",
-                        worktree.branch
-                    );
+        /// Minimum age in hours before cleanup
+        #[arg(long)]
+        age: Option<u64>,
 
-                    // Analyze merge conflicts using the existing merge detection system
-                    let config = worktree_manager.get_config().merge_detection.clone();
-                    let merge_info_result =
-                        detect_worktree_merge_status(&worktree.path, &worktree.branch, &config)
-                            .await;
-                    let merge_info = match merge_info_result {
-                        Ok(info) => Some(info),
-                        Err(_) => None,
-                    };
+        /// Also delete orphaned directories left in the worktree base dir
+        #[arg(long)]
+        orphans: bool,
 
-                    match format.as_str() {
-                        "json" => {
-                            let json_output = serde_json::json!({
-                                "worktree": {
-                                    "branch": worktree.branch,
-                                    "path": worktree.path,
-                                    "task_id": worktree.task_id
-                                },
-                                "merge_info": merge_info,
-                                "analysis": {
-                                    "has_conflicts": !merge_info.as_ref().map_or(true, |info| info.is_merged),
-                                    "confidence": merge_info.as_ref().map_or(0.0, |info| info.confidence),
-                                    "detection_method": merge_info.as_ref().map_or("none", |info| &info.detection_method)
-                                }
-                            });
-                            println!("{}", serde_json::to_string_pretty(&json_output)?);
-                        }
-                        "compact" => match &merge_info {
-                            Some(info) if info.is_merged => {
-                                println!(
-                                    "✅ No conflicts - branch appears merged ({}% confidence)",
-                                    (info.confidence * 100.0) as u8
-                                );
-                            }
-                            Some(info) => {
-                                println!(
-                                    "⚠️ Potential conflicts detected ({}% confidence via {})",
-                                    (info.confidence * 100.0) as u8,
-                                    info.detection_method
-                                );
-                                if let Some(ref details) = info.details {
-                                    println!("   {}", details);
-                                }
-                            }
-                            None => {
-                                println!("❓ Unable to determine merge status - manual conflict check recommended");
-                            }
-                        },
-                        _ => {
-                            // Default table format
-                            println!("Conflict Analysis for '{}'", worktree.branch);
-                            println!("{}", "─".repeat(50));
+        /// Only clean worktrees past their --expires deadline
+        #[arg(long)]
+        expired_only: bool,
 
-                            match &merge_info {
-                                Some(info) => {
-                                    println!(
-                                        "Status:           {}",
-                                        if info.is_merged {
-                                            "✅ Merged"
-                                        } else {
-                                            "⚠️ Not merged"
-                                        }
-                                    );
-                                    println!("Detection Method: {}", info.detection_method);
-                                    println!(
-                                        "Confidence:       {}%",
-                                        (info.confidence * 100.0) as u8
-                                    );
-                                    if let Some(ref details) = info.details {
-                                        println!("Details:          {}", details);
-                                    }
+        /// Skip confirmation prompts
+        #[arg(long)]
+        yes: bool,
+    },
 
-                                    if !info.is_merged {
-                                        println!("\n🔧 Recommended Actions:");
-                                        println!(
-                                            "  • Review changes: git diff main..{}",
-                                            worktree.branch
-                                        );
-                                        println!(
-                                            "  • Check for conflicts: git merge-tree main {}",
-                                            worktree.branch
-                                        );
-                                        println!("  • Consider merge or rebase before cleanup");
-                                    }
-                                }
-                                None => {
-                                    println!("Status:           ❓ Unknown");
-                                    println!("Recommendation:   Manual conflict analysis needed");
-                                    println!("\n🔧 Manual Steps:");
-                                    println!("  • git status");
-                                    println!("  • git diff main");
-                                    println!("  • git log --oneline main..{}", worktree.branch);
-                                }
-                            }
+    /// Run a command in one worktree or in all of them
+    #[command(
+        after_help = "Examples:\n  vibe git worktree exec auth-12 -- cargo test\n  vibe git worktree exec --all --parallel -- cargo test\n  vibe git worktree exec --all --dirty-only -- sh -c 'git diff --stat'"
+    )]
+    Exec {
+        /// Task ID, branch name, or worktree path to run in
+        #[arg(required_unless_present = "all", conflicts_with = "all")]
+        target: Option<String>,
 
-                            if !compact {
-                                println!("\n📊 Worktree Status:");
-                                println!("Branch:           {}", worktree.branch);
-                                println!("Path:             {}", worktree.path.display());
-                                if let Some(ref task_id) = worktree.task_id {
-                                    println!("Task ID:          {}", task_id);
-                                }
-                                println!(
-                                    "Clean:            {}",
-                                    if worktree.status.is_clean {
-                                        "✅"
-                                    } else {
-                                        "❌"
-                                    }
-                                );
-                                println!(
-                                    "Uncommitted:      {}",
-                                    worktree.status.uncommitted_changes.len()
-                                );
-                                println!(
-                                    "Unpushed:         {}",
-                                    worktree.status.unpushed_commits.len()
-                                );
-                            }
-                        }
-                    }
-                }
+        /// Run in every worktree of the repository
+        #[arg(short, long)]
+        all: bool,
 
-                WorktreeCommands::Config { action } => {
-                    handle_worktree_config_command(action, workspace_manager).await?;
-                }
-            }
-        }
-    }
+        /// With --all, only worktrees whose branch has this prefix
+        #[arg(long, requires = "all")]
+        prefix: Option<String>,
 
-    Ok(())
-}
+        /// With --all, only worktrees with uncommitted changes
+        #[arg(long, requires = "all")]
+        dirty_only: bool,
 
-/// Find git repository root from current directory
-async fn find_git_repository_root(start_dir: &std::path::Path) -> Result<PathBuf> {
-    let mut current = start_dir.to_path_buf();
+        /// Run in several worktrees at once, printing each output when done
+        #[arg(short, long)]
+        parallel: bool,
 
-    loop {
-        if current.join(".git").exists() {
-            return Ok(current);
-        }
+        /// Stop starting new runs after the first failure
+        #[arg(long)]
+        fail_fast: bool,
 
-        match current.parent() {
-            Some(parent) => current = parent.to_path_buf(),
-            None => return Err(anyhow::anyhow!("Not in a git repository")),
-        }
-    }
-}
+        /// Output format: table, json
+        #[arg(short, long, default_value = "table")]
+        format: String,
 
-/// Open a worktree in the specified editor
-async fn open_worktree_in_editor(path: &std::path::Path, editor: &str) -> Result<()> {
-    use anyhow::Context;
-    use tokio::process::Command;
+        /// Command and arguments, after `--`
+        #[arg(last = true, required = true)]
+        command: Vec<String>,
+    },
 
-    println!("Opening worktree in {}: {}", editor, path.display());
+    /// Set or clear the description and link of a worktree
+    Annotate {
+        /// Task ID, branch name, or worktree path to annotate
+        target: String,
 
-    let status = Command::new(editor)
-        .arg(path)
-        .status()
-        .await
-        .with_context(|| format!("Failed to execute editor: {}", editor))?;
+        /// What the task is about (empty string clears it)
+        #[arg(long)]
+        description: Option<String>,
 
-    if !status.success() {
-        return Err(anyhow::anyhow!(
-            "Editor command failed with status: {}",
-            status
-        ));
-    }
+        /// Ticket, issue, or pull request URL (empty string clears it)
+        #[arg(long)]
+        link: Option<String>,
+    },
 
-    println!("✅ Successfully opened worktree in {}", editor);
-    Ok(())
-}
+    /// Register a worktree created with plain `git worktree add`
+    #[command(
+        after_help = "Examples:\n  vibe git worktree adopt ../hotfix hotfix-login\n  vibe git worktree adopt spike spike-cache --rename-branch --relocate"
+    )]
+    Adopt {
+        /// Branch name or worktree path of the worktree to adopt
+        path_or_branch: String,
 
-/// Filter worktrees based on criteria
-fn filter_worktrees(
-    worktrees: Vec<crate::worktree::status::WorktreeInfo>,
-    prefix: Option<&str>,
-    dirty_only: bool,
-) -> Vec<crate::worktree::status::WorktreeInfo> {
-    worktrees
-        .into_iter()
-        .filter(|w| {
-            if let Some(prefix) = prefix {
-                if !w.branch.starts_with(prefix) {
-                    return false;
-                }
-            }
+        /// Task ID to record for the worktree
+        task_id: String,
 
-            if dirty_only && w.status.is_clean {
+        /// Rename the branch to the configured prefix plus the task ID
+        #[arg(long)]
+        rename_branch: bool,
+
+        /// Move the worktree under the managed worktree directory
+        #[arg(long)]
+        relocate: bool,
+    },
+
+    /// Push back the deadline of a time-boxed worktree
+    #[command(after_help = "Examples:\n  vibe git worktree extend spike-cache 2d")]
+    Extend {
+        /// Task ID, branch name, or worktree path to extend
+        target: String,
+
+        /// How much longer to allow (e.g. 2d, 12h, 1w); counts from now if
+        /// the deadline already passed
+        duration: String,
+    },
+
+    /// Open a worktree in configured editor
+    Open {
+        /// Task ID, branch name, or worktree path to open
+        target: String,
+
+        /// Editor command to use (overrides default)
+        #[arg(short, long, conflicts_with_all = ["shell", "print_path"])]
+        editor: Option<String>,
+
+        /// Start a shell inside the worktree instead of an editor, with
+        /// VIBE_TASK_ID, VIBE_WORKTREE_BRANCH, and VIBE_REPO_NAME set
+        #[arg(long, visible_alias = "terminal-here", conflicts_with = "print_path")]
+        shell: bool,
+
+        /// Print the worktree's absolute path instead of opening it
+        #[arg(long)]
+        print_path: bool,
+    },
+
+    /// Merge worktree changes into the branch it was created from
+    Merge {
+        /// Task ID, branch name, or worktree path to merge
+        target: String,
+
+        /// Merge strategy: squash, rebase, standard
+        // Default is 'standard' to preserve git history and commit attribution
+        // See: https://gist.github.com/mitchellh/319019b1b8aac9110fcfb1862e0c97fb
+        #[arg(short, long, default_value = "standard")]
+        strategy: String,
+
+        /// Skip confirmation prompts
+        #[arg(long)]
+        yes: bool,
+    },
+
+    /// Backup worktree to remote repository
+    Backup {
+        /// Task ID, branch name, or worktree path to backup
+        target: String,
+
+        /// Clean up worktree after successful backup
+        #[arg(long)]
+        cleanup_after: bool,
+
+        /// Skip confirmation prompts
+        #[arg(long)]
+        yes: bool,
+    },
+
+    /// Show conflict analysis for worktree
+    Conflicts {
+        /// Task ID, branch name, or worktree path to analyze
+        target: String,
+
+        /// Show compact format (summary only)
+        #[arg(long)]
+        compact: bool,
+
+        /// Output format: table, json, compact
+        #[arg(short, long, default_value = "table")]
+        format: String,
+    },
+
+    /// Resolve the conflicts of a merge file by file, resuming where a
+    /// previous run stopped
+    Resolve {
+        /// Task ID, branch name, or worktree path whose merge to resolve
+        target: String,
+
+        /// Abort the merge instead, restoring the state before it
+        #[arg(long)]
+        abort: bool,
+    },
+
+    /// Manage worktree configuration
+    Config {
+        #[command(subcommand)]
+        action: WorktreeConfigCommands,
+    },
+}
+
+#[derive(Subcommand)]
+enum WorktreeConfigCommands {
+    /// Show current configuration
+    Show {
+        /// Show configuration for specific repository
+        #[arg(short = 'R', long)]
+        repository: Option<String>,
+
+        /// Output format: yaml, json, summary
+        #[arg(short, long, default_value = "summary")]
+        format: String,
+    },
+
+    /// Set configuration values
+    Set {
+        /// Configuration key (e.g., prefix, base_dir, cleanup.age_threshold_hours)
+        key: String,
+
+        /// Configuration value
+        value: String,
+
+        /// Apply to specific repository only
+        #[arg(short = 'R', long)]
+        repository: Option<String>,
+    },
+
+    /// Reset configuration to defaults
+    Reset {
+        /// Reset specific key only
+        #[arg(short, long)]
+        key: Option<String>,
+
+        /// Reset configuration for specific repository
+        #[arg(short = 'R', long)]
+        repository: Option<String>,
+    },
+
+    /// Validate configuration
+    Validate,
+
+    /// Show configuration help and environment variables
+    Info,
+}
+
+#[derive(Subcommand)]
+enum GitCommands {
+    /// Scan workspace for git repositories
+    Scan {
+        /// Directory to scan for repositories
+        path: Option<PathBuf>,
+
+        /// Maximum depth to scan
+        #[arg(long, default_value = "3")]
+        depth: usize,
+
+        /// Add newly found repositories to config
+        #[arg(short, long)]
+        import: bool,
+
+        /// Re-clone missing repositories from config
+        #[arg(long)]
+        restore: bool,
+
+        /// Re-clone only these missing repositories (comma-separated; implies --restore)
+        #[arg(long, value_delimiter = ',', value_name = "NAMES")]
+        restore_only: Vec<String>,
+
+        /// Remove missing repositories from config, after listing them and
+        /// asking; repositories on unmounted volumes are kept
+        #[arg(long)]
+        clean: bool,
+
+        /// Remove with --clean without asking
+        #[arg(long, requires = "clean")]
+        yes: bool,
+
+        /// Record pnpm and Cargo workspace packages as subprojects of repositories that have none
+        #[arg(long)]
+        subprojects: bool,
+    },
+
+    /// Discover git repositories in directory structure (deprecated: use scan)
+    Discover {
+        /// Directory to scan for repositories
+        path: Option<PathBuf>,
+
+        /// Maximum depth to scan
+        #[arg(short, long, default_value = "3")]
+        depth: usize,
+
+        /// Auto-add discovered repositories to config
+        #[arg(short, long)]
+        auto_add: bool,
+    },
+
+    /// Show status across all repositories
+    Status {
+        /// Show only repositories with changes
+        #[arg(short, long)]
+        dirty_only: bool,
+
+        /// Output format: table, json, compact, csv, tsv
+        #[arg(short, long, default_value = "table")]
+        format: String,
+
+        /// Columns for csv and tsv output, comma-separated (default: all)
+        #[arg(long, value_delimiter = ',')]
+        fields: Vec<String>,
+
+        /// Filter by group name
+        #[arg(short, long)]
+        group: Option<String>,
+
+        /// Bucket repositories by dir, group, org, language, or none
+        /// (default: preferences.status_group_by, else org)
+        #[arg(long, value_name = "GROUPING")]
+        group_by: Option<workspace::config::StatusGroupBy>,
+    },
+
+    /// Inspect branches across repositories
+    Branches {
+        #[command(subcommand)]
+        command: BranchesCommands,
+    },
+
+    /// Distribute the workspace's managed git hooks to repositories
+    Hooks {
+        #[command(subcommand)]
+        command: HooksCommands,
+    },
+
+    /// Keep shared files such as .gitignore snippets in sync across repositories
+    Files {
+        #[command(subcommand)]
+        command: FilesCommands,
+    },
+
+    /// Restore or prune the backups vibe takes of branches before deleting them
+    Refs {
+        #[command(subcommand)]
+        command: RefsCommands,
+    },
+
+    /// List open pull requests across repositories via the GitHub CLI
+    Prs {
+        /// Only pull requests you authored
+        #[arg(long, conflicts_with = "review_requested")]
+        mine: bool,
+
+        /// Only pull requests waiting on your review
+        #[arg(long)]
+        review_requested: bool,
+
+        /// Filter by group name
+        #[arg(short, long)]
+        group: Option<String>,
+
+        /// Ignore the cached dashboard and query GitHub again
+        #[arg(long)]
+        refresh: bool,
+
+        /// Output format: table, json
+        #[arg(short, long, default_value = "table")]
+        format: String,
+    },
+
+    /// Execute git commands across repositories
+    Exec {
+        /// Git command to execute
+        command: String,
+
+        /// Target repositories (comma-separated)
+        #[arg(long)]
+        repos: Option<String>,
+
+        /// Target group
+        #[arg(short, long)]
+        group: Option<String>,
+
+        /// Only repositories of this project type: rust, node, python, go
+        #[arg(long = "type", value_name = "TYPE")]
+        project_type: Option<ProjectType>,
+
+        /// Run in parallel
+        #[arg(short, long)]
+        parallel: bool,
+    },
+
+    /// Sync repositories (fetch and pull)
+    Sync {
+        /// Only fetch, don't pull
+        #[arg(short, long)]
+        fetch_only: bool,
+
+        /// Prune remote tracking branches
+        #[arg(short, long)]
+        prune: bool,
+
+        /// Auto-commit dirty changes to dirty/{timestamp} branch before sync
+        #[arg(short, long)]
+        save_dirty: bool,
+
+        /// Fast-forward each fork's default branch from its upstream remote
+        #[arg(long, conflicts_with_all = ["fetch_only", "prune", "save_dirty"])]
+        upstream: bool,
+
+        /// Target group
+        #[arg(short, long)]
+        group: Option<String>,
+
+        /// Only repositories of this project type: rust, node, python, go
+        #[arg(long = "type", value_name = "TYPE")]
+        project_type: Option<ProjectType>,
+    },
+
+    /// Clone a repository to the workspace
+    Clone {
+        /// Repository URL or identifier
+        url: String,
+
+        /// Override default clone location
+        #[arg(short, long)]
+        path: Option<PathBuf>,
+
+        /// Open in configured editor after cloning
+        #[arg(short, long)]
+        open: bool,
+
+        /// Run post-install commands (npm install, etc.)
+        #[arg(short, long)]
+        install: bool,
+
+        /// What to do when the repository's name is configured or its directory exists (default: ask, or fail when not interactive)
+        #[arg(long, value_enum)]
+        on_collision: Option<git::OnCollision>,
+    },
+
+    /// Search for repositories interactively
+    Search,
+
+    /// Reset repository configuration (clear all tracked repositories)
+    Reset {
+        /// Skip confirmation prompt
+        #[arg(long)]
+        force: bool,
+
+        /// List the config entries and cache databases that would be
+        /// cleared, without changing anything
+        #[arg(long, conflicts_with = "force")]
+        dry_run: bool,
+
+        /// Output format for --dry-run: table, json
+        #[arg(long, default_value = "table", requires = "dry_run")]
+        format: String,
+    },
+
+    /// Manage git worktrees for parallel development
+    Worktree {
+        #[command(subcommand)]
+        action: WorktreeCommands,
+    },
+}
+
+/// Handle `vibe task` subcommands
+async fn handle_task_command(
+    command: TaskCommands,
+    workspace_manager: &WorkspaceManager,
+) -> Result<()> {
+    use worktree::task::{self, DoneOptions, FinishOptions, StartOptions};
+
+    match command {
+        TaskCommands::Start {
+            repo,
+            task_id,
+            base_branch,
+            description,
+            link,
+            editor,
+            no_open,
+        } => {
+            let options = StartOptions {
+                repo,
+                task_id,
+                base_branch,
+                description,
+                link,
+                open: !no_open && !output::is_json(),
+                editor,
+            };
+            let started = task::start(workspace_manager, options).await?;
+            if output::is_json() {
+                CommandResult::success(&started).emit()?;
+            } else {
+                started.print();
+            }
+        }
+        TaskCommands::Finish {
+            task_id,
+            repo,
+            title,
+            body,
+            draft,
+        } => {
+            let options = FinishOptions {
+                task_id,
+                repo,
+                title,
+                body,
+                draft,
+            };
+            let finished = task::finish(workspace_manager, options).await?;
+            if output::is_json() {
+                let mut result = CommandResult::success(&finished);
+                if finished.uncommitted_changes {
+                    result = result.with_warnings(vec![
+                        "The worktree has uncommitted changes; they were not pushed".to_string(),
+                    ]);
+                }
+                result.emit()?;
+            } else {
+                finished.print();
+            }
+        }
+        TaskCommands::Done {
+            task_id,
+            repo,
+            force,
+        } => {
+            let options = DoneOptions {
+                task_id,
+                repo,
+                force,
+            };
+            let done = task::done(workspace_manager, options).await?;
+            if output::is_json() {
+                CommandResult::success(&done).emit()?;
+            } else {
+                done.print();
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Handle worktree subcommands
+async fn handle_worktree_command(
+    command: WorktreeCommands,
+    workspace_manager: &WorkspaceManager,
+    verbose: bool,
+) -> Result<()> {
+    use crate::worktree::config::WorktreeMode;
+    use crate::worktree::{CreateOptions, RemoveOptions, WorktreeManager};
+    use colored::*;
+
+    // Get the current repository root, resolving linked worktrees back to
+    // the primary checkout
+    let current_dir = std::env::current_dir()?;
+    let location = utils::git::locate_repository(&current_dir)?;
+    let git_root = location.root.clone();
+
+    match command {
+        WorktreeCommands::Create {
+            task_id,
+            base_branch,
+            force,
+            include_protected,
+            path,
+            mode,
+            open,
+            editor,
+            description,
+            link,
+            expires,
+        } => {
+            use crate::worktree::metadata::{parse_duration, WorktreeMetadata};
+
+            let mut metadata = WorktreeMetadata::new(description, link)?;
+            if let Some(expires) = &expires {
+                metadata.expires_at = Some(chrono::Utc::now() + parse_duration(expires)?);
+            }
+
+            // Handle mode override for create command
+            let custom_config = if let Some(mode_str) = &mode {
+                use crate::worktree::config::WorktreeConfig;
+                let mut config = WorktreeConfig::load_with_overrides().unwrap_or_default();
+                config.mode = match mode_str.to_lowercase().as_str() {
+                    "global" => WorktreeMode::Global,
+                    "local" => WorktreeMode::Local,
+                    _ => {
+                        eprintln!("❌ Invalid mode '{}'. Use 'local' or 'global'", mode_str);
+                        return Ok(());
+                    }
+                };
+                Some(config)
+            } else {
+                None
+            };
+
+            // Create worktree manager with optional custom config
+            let worktree_manager = WorktreeManager::new(git_root.clone(), custom_config).await?;
+            let options = CreateOptions {
+                task_id: task_id.clone(),
+                base_branch,
+                force,
+                include_protected,
+                custom_path: path,
+                metadata,
+            };
+
+            println!("Creating worktree for task: {}", task_id.cyan());
+            let worktree_info = worktree_manager
+                .create_worktree_with_options(options)
+                .await?;
+
+            println!("✅ Created worktree:");
+            println!("  Branch: {}", worktree_info.branch.yellow());
+            println!(
+                "  Path: {}",
+                worktree_info.path.display().to_string().blue()
+            );
+            if let Some(commit) = &worktree_info.metadata.base_commit {
+                let mut freshness = Vec::new();
+                if let Some(age) = worktree::base_refresh::commit_age(&git_root, commit).await {
+                    freshness.push(format!(
+                        "committed {} ago",
+                        worktree::display::format_age(age)
+                    ));
+                }
+                if let Some(refresh) = &worktree_info.base_refresh {
+                    freshness.push(refresh.describe());
+                }
+                let short = &commit[..commit.len().min(7)];
+                let base = match &worktree_info.metadata.base_branch {
+                    Some(branch) => format!("{branch} at {short}"),
+                    None => short.to_string(),
+                };
+                if freshness.is_empty() {
+                    println!("  Base: {base}");
+                } else {
+                    println!("  Base: {base} ({})", freshness.join("; "));
+                }
+            }
+            if let Some(description) = &worktree_info.metadata.description {
+                println!("  Task: {description}");
+            }
+            if let Some(link) = &worktree_info.metadata.link {
+                println!("  Link: {}", link.underline());
+            }
+            if let Some(expiry) = worktree_info.metadata.expiry_label(chrono::Utc::now()) {
+                println!("  Time box: {expiry}");
+            }
+            match utils::direnv::allow_new_worktree(
+                &worktree_info.path,
+                workspace_manager.is_direnv_auto_allow_enabled(),
+            )
+            .await
+            {
+                Ok(true) => println!("  direnv: allowed .envrc"),
+                Ok(false) => {}
+                Err(e) => eprintln!("⚠️  {e}"),
+            }
+            let worktree_path = worktree_info.path.clone();
+            tokio::task::spawn_blocking(move || {
+                workspace::project_env::probe(&task_id, &worktree_path)
+            })
+            .await?
+            .print_warnings();
+
+            if open {
+                let editor_cmd = editor.unwrap_or_else(|| "code".to_string());
+                open_worktree_in_editor(
+                    &worktree_info.path,
+                    &editor_cmd,
+                    workspace_manager.is_direnv_enabled(),
+                )
+                .await?;
+            }
+        }
+
+        // For all other commands, create a default worktree manager
+        other_command => {
+            let worktree_manager = WorktreeManager::new(git_root.clone(), None).await?;
+
+            match other_command {
+                WorktreeCommands::Create { .. } => unreachable!(), // Already handled above
+
+                WorktreeCommands::List {
+                    prefix,
+                    verbose,
+                    format,
+                    fields,
+                    dirty_only,
+                } => {
+                    let worktrees = worktree_manager.list_worktrees().await?;
+                    let filtered_worktrees =
+                        filter_worktrees(worktrees, prefix.as_deref(), dirty_only);
+
+                    if output::is_json() {
+                        return CommandResult::success(filtered_worktrees).emit();
+                    }
+                    if output::delimited::print(&filtered_worktrees, &format, &fields)? {
+                        return Ok(());
+                    }
+
+                    match format.as_str() {
+                        "json" => print_worktrees_json(&filtered_worktrees)?,
+                        "compact" => print_worktrees_compact(&filtered_worktrees),
+                        _ => print_worktrees_table(&filtered_worktrees, verbose),
+                    }
+                }
+
+                WorktreeCommands::Remove {
+                    target,
+                    force,
+                    delete_branch,
+                    include_protected,
+                    yes,
+                } => {
+                    if !yes && !force {
+                        let prompt = format!(
+                            "Remove worktree '{}'{}?",
+                            target,
+                            if delete_branch {
+                                " and delete branch"
+                            } else {
+                                ""
+                            }
+                        );
+                        let action = DestructiveAction::new(&prompt);
+
+                        if !confirm_destructive(action, workspace_manager.get_confirmation_level())?
+                        {
+                            println!("Cancelled.");
+                            return Ok(());
+                        }
+                    }
+
+                    let options = RemoveOptions {
+                        target: target.clone(),
+                        force,
+                        delete_branch,
+                        include_protected,
+                    };
+
+                    println!("Removing worktree: {}", target.yellow());
+                    let outcome = worktree_manager
+                        .remove_worktree_with_options(options)
+                        .await?;
+                    println!("✅ Worktree removed successfully");
+                    if let Some(reason) = outcome.protected_branch {
+                        println!("⚠️  Branch skipped (protected): {reason}");
+                    }
+                    if let Some(backup) = outcome.backup {
+                        println!(
+                            "💾 Branch backed up as {}; 'vibe git refs restore {} {}' brings it back",
+                            backup.backup_ref,
+                            git_root.file_name().unwrap_or_default().to_string_lossy(),
+                            backup.branch
+                        );
+                    }
+                    if let Some(id) = outcome.trash_id {
+                        println!(
+                            "🗑️  In the trash as {id}; 'vibe trash restore {id}' brings it back"
+                        );
+                    }
+                }
+
+                WorktreeCommands::Status {
+                    branch,
+                    all,
+                    format,
+                    files_only,
+                } => {
+                    let worktrees = worktree_manager.list_worktrees().await?;
+
+                    // Run from inside a linked worktree, default to that task
+                    let origin = location
+                        .worktree
+                        .as_ref()
+                        .filter(|_| branch.is_none() && !all);
+
+                    // If a specific branch is requested or files_only, show detailed status
+                    if branch.is_some() || files_only || all || origin.is_some() {
+                        let target_worktrees = if let Some(branch_name) = branch {
+                            worktrees
+                                .into_iter()
+                                .filter(|w| w.branch == branch_name)
+                                .collect()
+                        } else if let Some(origin) = origin {
+                            worktrees
+                                .into_iter()
+                                .filter(|w| utils::fs::paths_equal(&w.path, origin))
+                                .collect()
+                        } else {
+                            worktrees
+                        };
+
+                        if target_worktrees.is_empty() {
+                            println!("No matching worktrees found");
+                            return Ok(());
+                        }
+
+                        match format.as_str() {
+                            "json" => print_status_json(&target_worktrees, files_only)?,
+                            "compact" => print_status_compact(&target_worktrees, files_only),
+                            _ => print_status_table(&target_worktrees, files_only),
+                        }
+                    } else {
+                        // Show repository summary by default
+                        let orphans = worktree_manager.find_orphaned_worktrees().await?;
+                        print_repository_worktree_summary(&worktrees, &orphans, &format, verbose)?;
+                    }
+                }
+
+                WorktreeCommands::Clean {
+                    dry_run,
+                    force,
+                    age,
+                    orphans,
+                    expired_only,
+                    yes,
+                } => {
+                    use crate::worktree::cleanup::{CleanupOptions, WorktreeCleanup};
+
+                    let cleanup_options = CleanupOptions {
+                        min_age_hours: age,
+                        force,
+                        dry_run,
+                        auto_confirm: yes,
+                        include_orphans: orphans,
+                        expired_only,
+                        // A passed deadline selects the worktree, merged or not
+                        merged_only: !expired_only,
+                        ..merged_cleanup_options(&worktree_manager, workspace_manager)
+                    };
+
+                    let cleanup = WorktreeCleanup::new(
+                        worktree_manager.get_config().clone(),
+                        worktree_manager.get_operations(),
+                    );
+
+                    println!(
+                        "🧹 {} worktree cleanup...",
+                        if dry_run { "Simulating" } else { "Starting" }
+                    );
+
+                    let report = cleanup.cleanup_worktrees(cleanup_options).await?;
+
+                    // Display results
+                    print_cleanup_report(&report);
+                }
+
+                WorktreeCommands::Exec {
+                    target,
+                    all,
+                    prefix,
+                    dirty_only,
+                    parallel,
+                    fail_fast,
+                    format,
+                    command,
+                } => {
+                    use crate::worktree::exec::{run_in_worktrees, ExecOptions};
+
+                    let worktrees = match (target, all) {
+                        (_, true) => filter_worktrees(
+                            worktree_manager.list_worktrees().await?,
+                            prefix.as_deref(),
+                            dirty_only,
+                        ),
+                        (Some(target), false) => {
+                            vec![worktree_manager.resolve_worktree_target(&target).await?]
+                        }
+                        (None, false) => {
+                            return Err(
+                                CommandError::Usage("Pass a worktree or --all".to_string()).into()
+                            )
+                        }
+                    };
+                    if worktrees.is_empty() {
+                        println!("No worktrees match");
+                        return Ok(());
+                    }
+
+                    let json = output::is_json() || format == "json";
+                    let options = ExecOptions {
+                        parallel,
+                        fail_fast,
+                        capture: json,
+                        direnv: workspace_manager.is_direnv_enabled(),
+                    };
+                    let report = run_in_worktrees(&worktrees, &command, &options).await?;
+
+                    if output::is_json() {
+                        let errors = report
+                            .results
+                            .iter()
+                            .filter(|result| result.outcome == ExecOutcome::Failed)
+                            .map(|result| match (&result.error, result.exit_code) {
+                                (Some(error), _) => format!("{}: {error}", result.label()),
+                                (None, Some(code)) => {
+                                    format!("{}: exited with status {code}", result.label())
+                                }
+                                (None, None) => format!("{}: terminated by signal", result.label()),
+                            })
+                            .collect();
+                        CommandResult::success(&report).with_errors(errors).emit()?;
+                    } else if format == "json" {
+                        println!("{}", serde_json::to_string_pretty(&report)?);
+                    } else {
+                        print_exec_report(&report);
+                    }
+                    report.summary().into_result()?;
+                }
+
+                WorktreeCommands::Annotate {
+                    target,
+                    description,
+                    link,
+                } => {
+                    if description.is_none() && link.is_none() {
+                        return Err(CommandError::Usage(
+                            "Pass --description and/or --link (an empty value clears it)"
+                                .to_string(),
+                        )
+                        .into());
+                    }
+
+                    let worktree = worktree_manager
+                        .annotate_worktree(&target, description, link)
+                        .await?;
+
+                    if output::is_json() {
+                        return CommandResult::success(worktree).emit();
+                    }
+
+                    println!("✅ Updated {}", worktree.branch.yellow());
+                    match &worktree.metadata.description {
+                        Some(description) => println!("  Task: {description}"),
+                        None => println!("  Task: {}", "(none)".dimmed()),
+                    }
+                    match &worktree.metadata.link {
+                        Some(link) => println!("  Link: {}", link.underline()),
+                        None => println!("  Link: {}", "(none)".dimmed()),
+                    }
+                }
+
+                WorktreeCommands::Adopt {
+                    path_or_branch,
+                    task_id,
+                    rename_branch,
+                    relocate,
+                } => {
+                    let adopted = worktree_manager
+                        .adopt_worktree(worktree::AdoptOptions {
+                            target: path_or_branch,
+                            task_id,
+                            rename_branch,
+                            relocate,
+                        })
+                        .await?;
+
+                    if output::is_json() {
+                        return CommandResult::success(adopted).emit();
+                    }
+
+                    let worktree = &adopted.worktree;
+                    println!(
+                        "✅ Adopted {} as task {}",
+                        worktree.branch.yellow(),
+                        worktree.task_id.as_deref().unwrap_or_default().cyan()
+                    );
+                    if let Some(previous) = &adopted.previous_branch {
+                        println!("  Branch: {previous} → {}", worktree.branch);
+                    }
+                    if let Some(previous) = &adopted.previous_path {
+                        println!(
+                            "  Moved: {} → {}",
+                            previous.display(),
+                            worktree.path.display()
+                        );
+                    } else {
+                        println!("  Path: {}", worktree.path.display());
+                    }
+                    if let Some(base) = &worktree.metadata.base_branch {
+                        println!("  Base: {base}");
+                    }
+                }
+
+                WorktreeCommands::Extend { target, duration } => {
+                    let by = crate::worktree::metadata::parse_duration(&duration)?;
+                    let worktree = worktree_manager.extend_worktree(&target, by).await?;
+
+                    if output::is_json() {
+                        return CommandResult::success(worktree).emit();
+                    }
+
+                    println!("✅ Extended {}", worktree.branch.yellow());
+                    if let Some(expiry) = worktree.metadata.expiry_label(chrono::Utc::now()) {
+                        println!("  Time box: {expiry}");
+                    }
+                }
+
+                WorktreeCommands::Open {
+                    target,
+                    editor,
+                    shell,
+                    print_path,
+                } => {
+                    // Use the new resolution logic that tries task_id first, then path, then branch
+                    let worktree = worktree_manager.resolve_worktree_target(&target).await?;
+
+                    if print_path {
+                        println!("{}", worktree.path.display());
+                        return Ok(());
+                    }
+                    if shell {
+                        let repo_name = workspace_manager
+                            .list_repositories()
+                            .iter()
+                            .find(|repo| {
+                                repo_abs_path(workspace_manager.get_config(), repo) == git_root
+                            })
+                            .map(|repo| repo.name.clone())
+                            .or_else(|| {
+                                git_root
+                                    .file_name()
+                                    .map(|name| name.to_string_lossy().into_owned())
+                            })
+                            .unwrap_or_default();
+                        return open_worktree_shell(
+                            &worktree,
+                            &repo_name,
+                            workspace_manager.is_direnv_enabled(),
+                        )
+                        .await;
+                    }
+
+                    let editor_cmd = editor.unwrap_or_else(|| "code".to_string());
+                    open_worktree_in_editor(
+                        &worktree.path,
+                        &editor_cmd,
+                        workspace_manager.is_direnv_enabled(),
+                    )
+                    .await?;
+                }
+
+                WorktreeCommands::Merge {
+                    target,
+                    strategy,
+                    yes,
+                } => {
+                    use crate::worktree::cleanup::CleanupStrategy;
+
+                    // Resolve the worktree target
+                    let worktree = worktree_manager.resolve_worktree_target(&target).await?;
+
+                    // Validate merge strategy
+                    let merge_strategy = match strategy.to_lowercase().as_str() {
+                        "squash" => CleanupStrategy::MergeToFeature,
+                        "rebase" => CleanupStrategy::MergeToFeature, // TODO: Add specific rebase strategy if needed
+                        "standard" => CleanupStrategy::MergeToFeature, // TODO: Add specific standard merge strategy if needed
+                        _ => {
+                            eprintln!(
+                                "❌ Invalid merge strategy '{}'. Use: squash, rebase, or standard",
+                                strategy
+                            );
+                            return Ok(());
+                        }
+                    };
+
+                    // Prompt for confirmation if not auto-confirmed
+                    if !yes {
+                        let confirmation = format!(
+                            "Merge worktree '{}' using {} strategy?",
+                            worktree.branch, strategy
+                        );
+                        if !prompt_for_confirmation(&confirmation)? {
+                            println!("❌ Merge cancelled by user");
+                            return Ok(());
+                        }
+                    }
+
+                    println!(
+                        "🔀 Merging worktree '{}' using {} strategy...",
+                        worktree.branch, strategy
+                    );
+
+                    let report = worktree_manager
+                        .merge_worktree(&worktree, merge_strategy)
+                        .await?;
+
+                    if report.cleaned_count > 0 {
+                        println!("✅ Successfully merged worktree '{}'", worktree.branch);
+                    } else if report.failed_count > 0 {
+                        println!("❌ Merge failed:");
+                        for result in &report.worktree_results {
+                            println!("   {}", result.error.as_ref().unwrap_or(&result.reason));
+                        }
+                        if let Some(repo) = find_worktree_merge(&worktree_manager, &worktree).await
+                        {
+                            offer_conflict_resolution(&repo, &target).await?;
+                        }
+                    } else {
+                        println!("⚠️ No action taken - worktree may not meet merge criteria");
+                    }
+                }
+
+                WorktreeCommands::Resolve { target, abort } => {
+                    let worktree = worktree_manager.resolve_worktree_target(&target).await?;
+                    let Some(repo) = find_worktree_merge(&worktree_manager, &worktree).await else {
+                        return Err(CommandError::Usage(format!(
+                            "No merge in progress for worktree '{}'",
+                            worktree.branch
+                        ))
+                        .into());
+                    };
+
+                    if abort {
+                        worktree::resolve::abort_merge(&repo).await?;
+                        if output::is_json() {
+                            CommandResult::success(serde_json::json!({
+                                "repository": repo,
+                                "aborted": true,
+                            }))
+                            .emit()?;
+                        } else {
+                            println!("✅ Merge aborted in {}", repo.display());
+                        }
+                    } else if output::is_json() {
+                        let conflicts = worktree::resolve::conflicted_files(&repo).await?;
+                        CommandResult::success(serde_json::json!({
+                            "repository": repo,
+                            "conflicts": conflicts,
+                        }))
+                        .emit()?;
+                    } else {
+                        resolve_merge_conflicts(&repo, &target).await?;
+                    }
+                }
+
+                WorktreeCommands::Backup {
+                    target,
+                    cleanup_after,
+                    yes,
+                } => {
+                    use crate::worktree::cleanup::{
+                        CleanupOptions, CleanupStrategy, WorktreeCleanup,
+                    };
+
+                    // Resolve the worktree target
+                    let worktree = worktree_manager.resolve_worktree_target(&target).await?;
+
+                    // Prompt for confirmation if not auto-confirmed
+                    if !yes {
+                        let confirmation = format!(
+                            "Backup worktree '{}' to remote{}?",
+                            worktree.branch,
+                            if cleanup_after {
+                                " and clean up afterward"
+                            } else {
+                                ""
+                            }
+                        );
+                        if !prompt_for_confirmation(&confirmation)? {
+                            println!("❌ Backup cancelled by user");
+                            return Ok(());
+                        }
+                    }
+
+                    let cleanup_options = CleanupOptions {
+                        strategy: CleanupStrategy::BackupToOrigin,
+                        min_age_hours: None, // No age restriction for explicit backup
+                        force: false,
+                        dry_run: false,
+                        auto_confirm: true,
+                        confirmations: Default::default(),
+                        branch_prefix_filter: None, // Target specific worktree
+                        merged_only: false,         // Allow backing up unmerged branches
+                        min_merge_confidence: 0.0,  // Allow any confidence for explicit backup
+                        include_orphans: false,
+                        expired_only: false,
+                    };
+
+                    let cleanup = WorktreeCleanup::new(
+                        worktree_manager.get_config().clone(),
+                        worktree_manager.get_operations(),
+                    );
+
+                    println!("💾 Backing up worktree '{}' to remote...", worktree.branch);
+
+                    // Use branch name to filter to this specific worktree
+                    let cleanup_options = CleanupOptions {
+                        branch_prefix_filter: Some(worktree.branch.clone()), // Target specific branch
+                        ..cleanup_options
+                    };
+
+                    let report = cleanup.cleanup_worktrees(cleanup_options).await?;
+
+                    if report.cleaned_count > 0 {
+                        println!("✅ Successfully backed up worktree '{}'", worktree.branch);
+
+                        // If cleanup_after is requested and backup was successful, remove the worktree
+                        if cleanup_after {
+                            println!("🧹 Cleaning up worktree after successful backup...");
+                            match worktree_manager
+                                .remove_worktree(target.clone(), false)
+                                .await
+                            {
+                                Ok(outcome) => {
+                                    println!("✅ Worktree cleaned up successfully");
+                                    if let Some(id) = outcome.trash_id {
+                                        println!("🗑️  In the trash as {id}; 'vibe trash restore {id}' brings it back");
+                                    }
+                                }
+                                Err(e) => {
+                                    println!("⚠️ Backup successful but cleanup failed: {}", e)
+                                }
+                            }
+                        }
+                    } else if report.failed_count > 0 {
+                        println!("❌ Backup failed:");
+                        for result in &report.worktree_results {
+                            if let Some(ref error) = result.error {
+                                println!("   {}", error);
+                            }
+                        }
+                    } else {
+                        println!("⚠️ No action taken - worktree may not meet backup criteria");
+                    }
+                }
+
+                WorktreeCommands::Conflicts {
+                    target,
+                    compact,
+                    format,
+                } => {
+                    use crate::worktree::merge_detection::detect_worktree_merge_status;
+
+                    // Resolve the worktree target
+                    let worktree = worktree_manager.resolve_worktree_target(&target).await?;
+
+                    println!(
+                        "🔍 Analyzing conflicts for worktree '{}'...",
+                        worktree.branch
+                    );
+
+                    // Analyze merge conflicts using the existing merge detection
+                    // system, checking the branch the worktree came from first
+                    let config = worktree_manager
+                        .get_config()
+                        .merge_detection
+                        .preferring(worktree.metadata.base_branch.as_deref());
+                    let merge_info_result =
+                        detect_worktree_merge_status(&worktree.path, &worktree.branch, &config)
+                            .await;
+                    let merge_info = match merge_info_result {
+                        Ok(info) => Some(info),
+                        Err(_) => None,
+                    };
+                    // A merge already stopped on conflicts
+                    let merge_in_progress = find_worktree_merge(&worktree_manager, &worktree).await;
+
+                    match format.as_str() {
+                        "json" => {
+                            let unmerged_files = match &merge_in_progress {
+                                Some(repo) => worktree::resolve::conflicted_files(repo).await?,
+                                None => Vec::new(),
+                            };
+                            let json_output = serde_json::json!({
+                                "worktree": {
+                                    "branch": worktree.branch,
+                                    "path": worktree.path,
+                                    "task_id": worktree.task_id
+                                },
+                                "merge_info": merge_info,
+                                "merge_in_progress": merge_in_progress,
+                                "unmerged_files": unmerged_files,
+                                "analysis": {
+                                    "has_conflicts": !merge_info.as_ref().map_or(true, |info| info.is_merged),
+                                    "confidence": merge_info.as_ref().map_or(0.0, |info| info.confidence),
+                                    "detection_method": merge_info.as_ref().map_or("none", |info| &info.detection_method)
+                                }
+                            });
+                            println!("{}", serde_json::to_string_pretty(&json_output)?);
+                        }
+                        "compact" => match &merge_info {
+                            Some(info) if info.is_merged => {
+                                println!(
+                                    "✅ No conflicts - branch appears merged ({}% confidence)",
+                                    (info.confidence * 100.0) as u8
+                                );
+                            }
+                            Some(info) => {
+                                println!(
+                                    "⚠️ Potential conflicts detected ({}% confidence via {})",
+                                    (info.confidence * 100.0) as u8,
+                                    info.detection_method
+                                );
+                                if let Some(ref details) = info.details {
+                                    println!("   {}", details);
+                                }
+                            }
+                            None => {
+                                println!("❓ Unable to determine merge status - manual conflict check recommended");
+                            }
+                        },
+                        _ => {
+                            // Default table format
+                            println!("Conflict Analysis for '{}'", worktree.branch);
+                            println!("{}", "─".repeat(50));
+
+                            match &merge_info {
+                                Some(info) => {
+                                    println!(
+                                        "Status:           {}",
+                                        if info.is_merged {
+                                            "✅ Merged"
+                                        } else {
+                                            "⚠️ Not merged"
+                                        }
+                                    );
+                                    println!("Detection Method: {}", info.detection_method);
+                                    println!(
+                                        "Confidence:       {}%",
+                                        (info.confidence * 100.0) as u8
+                                    );
+                                    if let Some(ref details) = info.details {
+                                        println!("Details:          {}", details);
+                                    }
+
+                                    if !info.is_merged {
+                                        println!("\n🔧 Recommended Actions:");
+                                        println!(
+                                            "  • Review changes: git diff main..{}",
+                                            worktree.branch
+                                        );
+                                        println!(
+                                            "  • Check for conflicts: git merge-tree main {}",
+                                            worktree.branch
+                                        );
+                                        println!("  • Consider merge or rebase before cleanup");
+                                    }
+                                }
+                                None => {
+                                    println!("Status:           ❓ Unknown");
+                                    println!("Recommendation:   Manual conflict analysis needed");
+                                    println!("\n🔧 Manual Steps:");
+                                    println!("  • git status");
+                                    println!("  • git diff main");
+                                    println!("  • git log --oneline main..{}", worktree.branch);
+                                }
+                            }
+
+                            if !compact {
+                                println!("\n📊 Worktree Status:");
+                                println!("Branch:           {}", worktree.branch);
+                                println!("Path:             {}", worktree.path.display());
+                                if let Some(ref task_id) = worktree.task_id {
+                                    println!("Task ID:          {}", task_id);
+                                }
+                                println!(
+                                    "Clean:            {}",
+                                    if worktree.status.is_clean {
+                                        "✅"
+                                    } else {
+                                        "❌"
+                                    }
+                                );
+                                println!(
+                                    "Uncommitted:      {}",
+                                    worktree.status.uncommitted_changes.len()
+                                );
+                                println!(
+                                    "Unpushed:         {}",
+                                    worktree.status.unpushed_commits.len()
+                                );
+                            }
+                        }
+                    }
+
+                    if format != "json" {
+                        if let Some(repo) = merge_in_progress {
+                            offer_conflict_resolution(&repo, &target).await?;
+                        }
+                    }
+                }
+
+                WorktreeCommands::Config { action } => {
+                    handle_worktree_config_command(action, workspace_manager).await?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Print each problem in a config file that doesn't load, then fail
+fn report_config_load_error(config_path: &Path, error: anyhow::Error) -> Result<()> {
+    use workspace::config_loader::ConfigLoadError;
+
+    let Some(load_error) = error
+        .chain()
+        .find_map(|cause| cause.downcast_ref::<ConfigLoadError>())
+    else {
+        return Err(error);
+    };
+
+    if output::is_json() {
+        CommandResult::success(serde_json::json!({
+            "valid": false,
+            "config_errors": load_error.errors,
+        }))
+        .with_errors(load_error.errors.iter().map(ToString::to_string).collect())
+        .emit()?;
+    } else {
+        println!(
+            "{} {} does not load:",
+            style("❌").red().bold(),
+            config_path.display()
+        );
+        for problem in &load_error.errors {
+            println!("  • {problem}");
+        }
+    }
+
+    Err(CommandError::Usage(format!(
+        "{} has {} problem{}",
+        config_path.display(),
+        load_error.errors.len(),
+        if load_error.errors.len() == 1 {
+            ""
+        } else {
+            "s"
+        }
+    ))
+    .into())
+}
+
+/// Print a `--dry-run` preview of `vibe config reset` or `vibe git reset`
+fn print_reset_preview<T: serde::Serialize>(
+    preview: &T,
+    format: &str,
+    print: impl FnOnce(),
+) -> Result<()> {
+    if output::is_json() {
+        CommandResult::success(preview).emit()?;
+    } else if format == "json" {
+        data_println!("{}", serde_json::to_string_pretty(preview)?);
+    } else {
+        print();
+        display_println!();
+        display_println!("{} Dry run: nothing was changed", style("🔍").yellow());
+    }
+    Ok(())
+}
+
+/// Run `vibe config migrate-home` and print where everything lives now
+async fn run_migrate_home(dry_run: bool) -> Result<()> {
+    use workspace::constants::display_path;
+
+    let home = dirs::home_dir().ok_or_else(|| anyhow::anyhow!("No home directory"))?;
+    let report = workspace::home_migration::migrate_home(&home, dry_run).await?;
+
+    if output::is_json() {
+        let warnings = report.warnings.clone();
+        return CommandResult::success(report)
+            .with_warnings(warnings)
+            .emit();
+    }
+
+    let verb = if dry_run { "Would move" } else { "Moved" };
+    for entry in &report.moved {
+        display_println!(
+            "  {} {} {} {}",
+            style(verb).dim(),
+            display_path(&entry.from),
+            style("→").dim(),
+            style(display_path(&entry.to)).cyan()
+        );
+    }
+    for warning in &report.warnings {
+        display_println!("{} {}", style("⚠️").yellow(), warning);
+    }
+
+    display_println!();
+    display_println!("{} Vibe directories:", style("📂").blue());
+    for (name, dir) in [
+        ("config", &report.config),
+        ("data", &report.data),
+        ("cache", &report.cache),
+        ("state", &report.state),
+    ] {
+        display_println!("  {:<7}{}", name, style(display_path(dir)).cyan());
+    }
+    Ok(())
+}
+
+/// Run `vibe doctor`, failing when any check failed
+async fn run_doctor(
+    config_path: &std::path::Path,
+    root: Option<&std::path::Path>,
+    format: &str,
+) -> Result<()> {
+    use workspace::doctor::{self, CheckStatus};
+
+    let report = doctor::run_checks(config_path, root).await;
+
+    if output::is_json() {
+        let collect = |status| {
+            report
+                .checks
+                .iter()
+                .filter(|check| check.status == status)
+                .map(|check| format!("{}: {}", check.id, check.message))
+                .collect()
+        };
+        let (warnings, errors) = (collect(CheckStatus::Warn), collect(CheckStatus::Fail));
+        CommandResult::success(&report)
+            .with_warnings(warnings)
+            .with_errors(errors)
+            .emit()?;
+    } else if format == "json" {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        report.print_report();
+    }
+
+    if report.has_failures() {
+        anyhow::bail!("{} doctor checks failed", report.count(CheckStatus::Fail));
+    }
+    Ok(())
+}
+
+/// `vibe git worktree clean` defaults: discard worktrees that are merged
+/// and carry the configured branch prefix, confirming as configured
+fn merged_cleanup_options(
+    worktree_manager: &worktree::WorktreeManager,
+    workspace_manager: &WorkspaceManager,
+) -> worktree::cleanup::CleanupOptions {
+    worktree::cleanup::CleanupOptions {
+        strategy: worktree::cleanup::CleanupStrategy::Discard,
+        min_age_hours: None,
+        force: false,
+        dry_run: false,
+        auto_confirm: false,
+        confirmations: workspace_manager.get_confirmation_level(),
+        branch_prefix_filter: Some(worktree_manager.get_config().prefix.clone()),
+        merged_only: true, // Default to merged only for safety
+        min_merge_confidence: 0.7,
+        include_orphans: false,
+        expired_only: false,
+    }
+}
+
+/// Report disk usage per repository and offer to reclaim space
+async fn run_disk_stats(
+    workspace_manager: &mut WorkspaceManager,
+    top: Option<usize>,
+    include_worktrees: bool,
+    refresh: bool,
+    format: &str,
+    fields: &[String],
+) -> Result<()> {
+    use workspace::disk_usage::{collect_disk_usage, DiskReport};
+
+    let repos = workspace_manager
+        .list_repositories()
+        .iter()
+        .map(|repo| {
+            (
+                repo.name.clone(),
+                repo_abs_path(workspace_manager.config(), repo),
+            )
+        })
+        .collect();
+    let max_parallel = workspace_manager.get_max_parallel_git();
+
+    let delimited = output::delimited::Delimited::from_format(format).is_some();
+    if format != "json" && !delimited {
+        display_println!("{} Measuring disk usage...", style("📊").blue());
+    }
+    let cache = workspace_manager.get_repository_cache().await?;
+    let (usages, warnings) =
+        collect_disk_usage(repos, include_worktrees, refresh, cache, max_parallel).await;
+    let report = DiskReport::new(usages, top);
+
+    if output::is_json() {
+        return CommandResult::success(&report)
+            .with_warnings(warnings)
+            .emit();
+    }
+    for warning in &warnings {
+        display_warn!("{} {}", style("⚠").yellow(), warning);
+    }
+    if format == "json" {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+    if output::delimited::print(&report.repositories, format, fields)? {
+        return Ok(());
+    }
+
+    report.print_table();
+    if report.reclaimable_bytes == 0 {
+        return Ok(());
+    }
+    if !output::is_interactive() || !std::io::stdin().is_terminal() {
+        report.print_cleanup_commands();
+        return Ok(());
+    }
+
+    const CLEAN: &str = "🧹 Clean merged worktrees";
+    const PRINT: &str = "📋 Print cleanup commands";
+    const DONE: &str = "Done";
+    let mut actions = Vec::new();
+    if report.has_merged_worktrees() {
+        actions.push(CLEAN);
+    }
+    actions.extend([PRINT, DONE]);
+
+    println!();
+    let choice = ui::prompts::handle_prompt_result(
+        inquire::Select::new("Reclaim space:", actions).prompt(),
+    )?;
+    match choice {
+        Some(CLEAN) => {
+            for repo in &report.repositories {
+                if repo.merged_worktrees().next().is_none() {
+                    continue;
+                }
+                println!("\n{} {}", style("🧹").blue(), style(&repo.name).bold());
+                let worktree_manager =
+                    worktree::WorktreeManager::new(repo.path.clone(), None).await?;
+                let cleanup = worktree::cleanup::WorktreeCleanup::new(
+                    worktree_manager.get_config().clone(),
+                    worktree_manager.get_operations(),
+                );
+                let report = cleanup
+                    .cleanup_worktrees(merged_cleanup_options(&worktree_manager, workspace_manager))
+                    .await?;
+                print_cleanup_report(&report);
+            }
+        }
+        Some(PRINT) => report.print_cleanup_commands(),
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Filter worktrees based on criteria
+fn filter_worktrees(
+    worktrees: Vec<crate::worktree::status::WorktreeInfo>,
+    prefix: Option<&str>,
+    dirty_only: bool,
+) -> Vec<crate::worktree::status::WorktreeInfo> {
+    worktrees
+        .into_iter()
+        .filter(|w| {
+            if let Some(prefix) = prefix {
+                if !w.branch.starts_with(prefix) {
+                    return false;
+                }
+            }
+
+            if dirty_only && w.status.is_clean {
                 return false;
             }
 
-            true
-        })
-        .collect()
-}
+            true
+        })
+        .collect()
+}
+
+/// Print repository worktree summary with health overview
+fn print_repository_worktree_summary(
+    worktrees: &[crate::worktree::status::WorktreeInfo],
+    orphans: &[crate::worktree::orphans::OrphanedWorktree],
+    format: &str,
+    verbose: bool,
+) -> Result<()> {
+    use crate::workspace::disk_usage::format_bytes;
+    use crate::worktree::status::RepositoryWorktreeSummary;
+    use colored::*;
+
+    if worktrees.is_empty() && orphans.is_empty() {
+        println!("No worktrees found");
+        return Ok(());
+    }
+
+    // Calculate repository summary
+    let summary = RepositoryWorktreeSummary::from_worktrees(worktrees);
+
+    match format {
+        "json" => {
+            let json_output = serde_json::json!({
+                "health_score": summary.health_score,
+                "health_status": summary.health_description(),
+                "health_icon": summary.health_icon(),
+                "total_worktrees": summary.total_worktrees,
+                "clean_worktrees": summary.clean_worktrees,
+                "dirty_worktrees": summary.dirty_worktrees,
+                "worktrees_with_remote": summary.worktrees_with_remote,
+                "worktrees_with_unpushed": summary.worktrees_with_unpushed,
+                "merged_worktrees": summary.merged_worktrees,
+                "expired_worktrees": summary.expired_worktrees,
+                "no_remote_count": summary.total_worktrees - summary.worktrees_with_remote,
+                "summary_description": summary.summary_description(),
+                "orphaned": orphans
+            });
+            println!("{}", serde_json::to_string_pretty(&json_output)?);
+        }
+        "compact" => {
+            println!(
+                "{} {} ({} health) - {}",
+                summary.health_icon(),
+                "Repository Health".bold(),
+                (summary.health_score * 100.0) as u8,
+                summary.summary_description()
+            );
+            if !orphans.is_empty() {
+                println!("{} {} orphaned", "!".yellow(), orphans.len());
+            }
+        }
+        _ => {
+            // Default table format
+            println!(
+                "\n{} Repository Worktree Overview",
+                summary.health_icon().to_string().bold()
+            );
+
+            println!("{}", "─".repeat(50));
+
+            println!(
+                "{:<20} {}",
+                "Health Status:".dimmed(),
+                format!(
+                    "{} ({}%)",
+                    summary.health_description(),
+                    (summary.health_score * 100.0) as u8
+                )
+                .green()
+            );
+
+            println!(
+                "{:<20} {}",
+                "Total Worktrees:".dimmed(),
+                summary.total_worktrees.to_string().cyan()
+            );
+
+            if summary.clean_worktrees > 0 {
+                println!(
+                    "{:<20} {}",
+                    "Clean:".dimmed(),
+                    summary.clean_worktrees.to_string().green()
+                );
+            }
+
+            if summary.dirty_worktrees > 0 {
+                println!(
+                    "{:<20} {}",
+                    "Dirty:".dimmed(),
+                    summary.dirty_worktrees.to_string().yellow()
+                );
+            }
+
+            if summary.worktrees_with_unpushed > 0 {
+                println!(
+                    "{:<20} {}",
+                    "With Unpushed:".dimmed(),
+                    summary.worktrees_with_unpushed.to_string().red()
+                );
+            }
+
+            if summary.merged_worktrees > 0 {
+                println!(
+                    "{:<20} {}",
+                    "Merged:".dimmed(),
+                    summary.merged_worktrees.to_string().blue()
+                );
+            }
+
+            if summary.expired_worktrees > 0 {
+                println!(
+                    "{:<20} {}",
+                    "Expired:".dimmed(),
+                    summary.expired_worktrees.to_string().red()
+                );
+            }
+
+            let no_remote = summary.total_worktrees - summary.worktrees_with_remote;
+            if no_remote > 0 {
+                println!(
+                    "{:<20} {}",
+                    "No Remote:".dimmed(),
+                    no_remote.to_string().yellow()
+                );
+            }
+
+            if !orphans.is_empty() {
+                println!(
+                    "{:<20} {}",
+                    "Orphaned:".dimmed(),
+                    orphans.len().to_string().red()
+                );
+                for orphan in orphans {
+                    println!(
+                        "  {} {} ({})",
+                        orphan.path.display().to_string().dimmed(),
+                        format_bytes(orphan.size_bytes),
+                        orphan.reason
+                    );
+                }
+                println!(
+                    "  Run {} to delete them",
+                    "vibe git worktree clean --orphans".cyan()
+                );
+            }
+
+            // Show additional verbose information
+            if verbose {
+                println!();
+                println!("{}", "Additional Details:".bold());
+                println!("{}", "─".repeat(25));
+
+                for worktree in worktrees {
+                    let status_indicator = if worktree.status.is_clean {
+                        "✓".green()
+                    } else {
+                        "!".yellow()
+                    };
+
+                    println!(
+                        "{} {:<30} {}",
+                        status_indicator,
+                        worktree.branch.cyan(),
+                        worktree.path.display().to_string().dimmed()
+                    );
+                }
+            }
+
+            println!();
+        }
+    }
+
+    Ok(())
+}
+
+/// Print worktrees in JSON format
+fn print_worktrees_json(worktrees: &[crate::worktree::status::WorktreeInfo]) -> Result<()> {
+    let json = serde_json::to_string_pretty(worktrees)?;
+    println!("{}", json);
+    Ok(())
+}
+
+/// Print status in JSON format
+fn print_status_json(
+    worktrees: &[crate::worktree::status::WorktreeInfo],
+    _files_only: bool,
+) -> Result<()> {
+    let json = serde_json::to_string_pretty(worktrees)?;
+    println!("{}", json);
+    Ok(())
+}
+
+/// Prompt user for confirmation
+/// Where a merge of `worktree` is in progress: in the worktree itself, or in
+/// the checkout `worktree merge` merged into
+async fn find_worktree_merge(
+    worktree_manager: &worktree::WorktreeManager,
+    worktree: &worktree::status::WorktreeInfo,
+) -> Option<PathBuf> {
+    let mut candidates = vec![worktree.path.clone()];
+    if let Some(parent) = worktree.path.parent() {
+        candidates.push(parent.to_path_buf());
+    }
+    if let Ok(root) = worktree_manager.get_git_root().await {
+        candidates.push(root);
+    }
+    worktree::resolve::find_merge(&candidates).await
+}
+
+/// List the conflicts of the merge in `repo` and offer to resolve them
+async fn offer_conflict_resolution(repo: &Path, target: &str) -> Result<()> {
+    let conflicts = worktree::resolve::conflicted_files(repo).await?;
+    if conflicts.is_empty() {
+        return Ok(());
+    }
+    println!(
+        "\n⚠️ {} conflicted files in {}:",
+        conflicts.len(),
+        repo.display()
+    );
+    for file in &conflicts {
+        println!("   {} ({})", file.path, file.describe());
+    }
+    if output::is_interactive()
+        && std::io::stdin().is_terminal()
+        && ui::prompts::prompt_yes_no("Resolve the conflicts now?", true)?
+    {
+        return resolve_merge_conflicts(repo, target).await;
+    }
+    println!(
+        "Run 'vibe git worktree resolve {target}' to resolve them, or add --abort to cancel the merge"
+    );
+    Ok(())
+}
+
+/// Walk through the conflicts of the merge in `repo`, or list them when
+/// there is no terminal to ask in
+async fn resolve_merge_conflicts(repo: &Path, target: &str) -> Result<()> {
+    use ui::conflict_resolver::{resolve_conflicts, ResolveOutcome};
+
+    if !output::is_interactive() || !std::io::stdin().is_terminal() {
+        let conflicts = worktree::resolve::conflicted_files(repo).await?;
+        println!(
+            "⚠️ {} conflicted files in {}:",
+            conflicts.len(),
+            repo.display()
+        );
+        for file in &conflicts {
+            println!("   {} ({})", file.path, file.describe());
+        }
+        println!("Resolving them needs a terminal; add --abort to cancel the merge instead");
+        return Ok(());
+    }
+
+    match resolve_conflicts(repo).await? {
+        ResolveOutcome::Committed => println!("✅ Merge committed in {}", repo.display()),
+        ResolveOutcome::Resolved => println!(
+            "✅ All conflicts resolved. Run 'git commit' in {} to finish the merge",
+            repo.display()
+        ),
+        ResolveOutcome::Incomplete { remaining } => println!(
+            "⏸️  {remaining} files still have conflicts. Run 'vibe git worktree resolve {target}' to continue, or add --abort to cancel the merge"
+        ),
+        ResolveOutcome::Aborted => println!("✅ Merge aborted in {}", repo.display()),
+    }
+    Ok(())
+}
+
+fn prompt_for_confirmation(message: &str) -> Result<bool> {
+    use std::io::{self, Write};
+
+    print!("{} (y/N): ", message);
+    io::stdout().flush()?;
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+
+    Ok(input.trim().to_lowercase() == "y" || input.trim().to_lowercase() == "yes")
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    // Determine output mode based on command
+    let output_mode = match &cli.command {
+        Some(Commands::Mcp { command: None, .. }) => output::OutputMode::Mcp,
+        // Tool output goes to stderr, leaving stdout to the report
+        Some(Commands::Mcp {
+            command: Some(McpCommands::Validate { .. }),
+            ..
+        }) if !cli.json => output::OutputMode::Mcp,
+        // The OS may launch the URL handler without a terminal attached
+        Some(Commands::Uri {
+            command: UriCommands::Handle { .. },
+        }) if !std::io::stdout().is_terminal() => output::OutputMode::NonInteractive,
+        _ if cli.json => output::OutputMode::Json,
+        // Only the path goes to stdout, where the shell captures it
+        Some(Commands::Path { .. })
+        | Some(Commands::Open {
+            print_path: true, ..
+        }) => output::OutputMode::Capture,
+        _ => output::OutputMode::Cli,
+    };
+
+    // File logging is configured before the workspace config is fully loaded
+    let config_path = cli
+        .config
+        .clone()
+        .unwrap_or_else(workspace::constants::get_default_config_path);
+    let logging = workspace::config::LoggingPreferences::read_from_config_file(&config_path);
+    let log_file = (cli.log_file.is_some() || logging.file_enabled)
+        .then(|| log_file_settings(cli.log_file.as_deref()).with_max_files(logging.max_files));
+
+    // Initialize output system (this handles tracing setup)
+    output::init_with_options(
+        output_mode,
+        output::OutputOptions {
+            verbose: cli.verbose,
+            quiet: cli.quiet,
+            no_color: cli.no_color,
+            plain: cli.plain,
+            log_file,
+        },
+    );
+
+    let recording = cli.timings.then(output::timings::Recording::start);
+    let result = run(cli).await;
+    workspace::events::wait_for_pending().await;
+    if let Some(recording) = recording {
+        recording.finish().print();
+    }
+
+    // In JSON mode every invocation ends with exactly one document on stdout
+    if output::is_json() && !output::json_emitted() {
+        match &result {
+            Ok(()) => CommandResult::success(()).emit()?,
+            Err(e) => CommandResult::<()>::failure(format!("{e:#}")).emit()?,
+        }
+    }
+
+    if let Err(e) = result {
+        if !output::is_json() {
+            display_eprintln!("{} {:?}", style("Error:").red().bold(), e);
+        }
+        std::process::exit(exit_code_for(&e).code());
+    }
+
+    Ok(())
+}
+
+async fn run(cli: Cli) -> Result<()> {
+    // Load or create workspace configuration
+    let config_path = cli
+        .config
+        .unwrap_or_else(workspace::constants::get_default_config_path);
+
+    // Completion helpers must stay fast, so they run before the workspace
+    // manager touches any repository
+    match cli.command {
+        Some(Commands::Completions { shell }) => {
+            print_completions(shell);
+            return Ok(());
+        }
+        Some(Commands::Complete { kind }) => {
+            print_completion_candidates(kind, &config_path).await;
+            return Ok(());
+        }
+        Some(Commands::ShellInit { shell }) => {
+            let script = utils::completions::shell_init_script(shell).ok_or_else(|| {
+                anyhow::anyhow!("vibe shell-init supports bash, zsh, and fish, not {shell}")
+            })?;
+            print!("{script}");
+            return Ok(());
+        }
+        // Doctor has to work when the config doesn't load
+        Some(Commands::Doctor { format }) => {
+            return run_doctor(&config_path, cli.root.as_deref(), &format).await;
+        }
+        // Caches are opened by the workspace manager, so clear them first
+        Some(Commands::Cache { command }) => {
+            return match command {
+                CacheCommands::Clear { suggestions } => run_cache_clear(suggestions).await,
+                CacheCommands::Export { file } => run_cache_export(&file).await,
+                CacheCommands::Import { file, if_empty } => run_cache_import(&file, if_empty).await,
+            };
+        }
+        // Moves the config file out from under the workspace manager
+        Some(Commands::Config {
+            command: ConfigCommands::MigrateHome { dry_run },
+        }) => {
+            return run_migrate_home(dry_run).await;
+        }
+        // Validate has to list every problem in a config that doesn't load
+        Some(Commands::Config {
+            command: ConfigCommands::Validate { .. },
+        }) => {
+            if let Err(e) = workspace::config::WorkspaceConfig::load_from_file(&config_path).await {
+                return report_config_load_error(&config_path, e);
+            }
+        }
+        _ => {}
+    }
+
+    let mut workspace_manager =
+        WorkspaceManager::new_with_root_override(config_path.clone(), cli.root).await?;
+    if cli.force_online {
+        workspace_manager.force_online();
+    } else if let Some(offline) = workspace_manager.offline_root() {
+        display_warn!("{} {}", style("⚠️").yellow(), offline.warning());
+    }
+    output::theme::set_theme(workspace_manager.get_theme_preferences());
+    git::backend::set_backend(workspace_manager.get_git_backend());
+    git::repo_lock::set_timeout(workspace_manager.lock_timeout());
+    git::ref_backup::set_enabled(workspace_manager.config().safety.ref_backups);
+    workspace::events::configure(&workspace_manager.config().hooks);
+    workspace::trash::Trash::open_default()
+        .sweep(workspace_manager.trash_retention_days())
+        .await;
+
+    match cli.command {
+        None => {
+            // No command provided, start menu mode
+            prompts::run_menu_mode(&mut workspace_manager).await?;
+        }
+        Some(command) => match command {
+            Commands::Apps { command } => match command {
+                AppsCommands::Configure {
+                    repo,
+                    app,
+                    template,
+                    all_repos,
+                    group,
+                    overwrite,
+                } => {
+                    let template_name = template.as_deref().unwrap_or("default");
+                    if all_repos || group.is_some() {
+                        // The only positional is the app
+                        let app = match (repo, app) {
+                            (Some(app), None) => app,
+                            _ => {
+                                return Err(CommandError::Usage(
+                                    "With --all-repos or --group, give only the app: vibe apps configure --all-repos <app>".to_string(),
+                                )
+                                .into())
+                            }
+                        };
+                        let config = workspace_manager.get_config();
+                        let repo_names: Vec<String> = match &group {
+                            Some(group) => {
+                                if !config.groups.iter().any(|g| &g.name == group) {
+                                    return Err(
+                                        CommandError::not_found("Group", group.as_str()).into()
+                                    );
+                                }
+                                config
+                                    .get_repositories_in_group(group)
+                                    .into_iter()
+                                    .map(|r| r.name.clone())
+                                    .collect()
+                            }
+                            None => config.repositories.iter().map(|r| r.name.clone()).collect(),
+                        };
+                        let results = workspace_manager
+                            .configure_app_for_repos(&repo_names, &app, template_name, overwrite)
+                            .await?;
+                        let summary = BatchSummary {
+                            total: results.len(),
+                            failed: results
+                                .iter()
+                                .filter(|r| r.outcome == AppConfigureOutcome::Failed)
+                                .count(),
+                        };
+                        if output::is_json() {
+                            CommandResult::success(&results).emit()?;
+                        } else {
+                            display_println!(
+                                "{} Configuring {} with template '{}' for {} repositories",
+                                style("📱").blue(),
+                                style(&app).cyan(),
+                                style(template_name).dim(),
+                                results.len()
+                            );
+                            print_bulk_configure_summary(&app, &results);
+                        }
+                        summary.into_result()?;
+                    } else {
+                        let (Some(repo), Some(app)) = (repo, app) else {
+                            return Err(CommandError::Usage(
+                                "Give a repository and an app, or use --all-repos or --group"
+                                    .to_string(),
+                            )
+                            .into());
+                        };
+                        workspace_manager
+                            .configure_app_for_repo(&repo, &app, template_name)
+                            .await?;
+                        display_println!(
+                            "{} Configured {} for repository '{}' with template '{}'",
+                            style("✓").green().bold(),
+                            style(&app).cyan(),
+                            style(&repo).cyan(),
+                            style(template_name).dim()
+                        );
+                    }
+                }
+
+                AppsCommands::Show { repo, app } => {
+                    if let Some(repo_name) = repo {
+                        let apps = workspace_manager.list_apps_for_repo(&repo_name)?;
+                        display_println!(
+                            "{} Apps configured for repository '{}':",
+                            style("📱").blue(),
+                            style(&repo_name).cyan().bold()
+                        );
+                        for (app_name, template) in apps {
+                            display_println!(
+                                "  {} {} (template: {})",
+                                style("→").dim(),
+                                style(&app_name).green(),
+                                style(&template).dim()
+                            );
+                        }
+                    } else if let Some(app_name) = app {
+                        let repos = workspace_manager.list_repos_with_app(&app_name);
+                        display_println!(
+                            "{} Repositories with {} configured:",
+                            style("📱").blue(),
+                            style(&app_name).cyan().bold()
+                        );
+                        for (repo, template) in repos {
+                            display_println!(
+                                "  {} {} (template: {})",
+                                style("→").dim(),
+                                style(&repo.name).green(),
+                                style(&template).dim()
+                            );
+                        }
+                    } else {
+                        // Show all app configurations
+                        workspace_manager.show_app_configurations().await?;
+                    }
+                }
+
+                AppsCommands::Template { command } => match command {
+                    TemplateCommands::List { app } => {
+                        let templates = workspace_manager.list_templates(&app).await?;
+                        let origins = workspace::template_packs::origins(
+                            workspace_manager.get_template_manager().template_root(),
+                            &app,
+                        )
+                        .await?;
+                        display_println!(
+                            "{} Available templates for {}:",
+                            style("📄").blue(),
+                            style(&app).cyan().bold()
+                        );
+                        for template in templates {
+                            match origins.get(&template) {
+                                Some(pack) => display_println!(
+                                    "  {} {} {}",
+                                    style("→").dim(),
+                                    style(&template).green(),
+                                    style(format!("(pack {pack})")).dim()
+                                ),
+                                None => display_println!(
+                                    "  {} {}",
+                                    style("→").dim(),
+                                    style(&template).green()
+                                ),
+                            }
+                        }
+                    }
+
+                    TemplateCommands::Install { source, no_save } => {
+                        let report = workspace_manager
+                            .install_template_pack(&source, !no_save)
+                            .await?;
+                        if output::is_json() {
+                            CommandResult::success(&report).emit()?;
+                        } else {
+                            display_println!(
+                                "{} Installed template pack '{}' ({} templates)",
+                                style("✓").green().bold(),
+                                style(&report.name).cyan(),
+                                report.templates.len()
+                            );
+                            for template in &report.templates {
+                                display_println!(
+                                    "  {} {} {}",
+                                    style("→").dim(),
+                                    template.app,
+                                    style(&template.name).green()
+                                );
+                            }
+                            for template in &report.removed {
+                                display_println!(
+                                    "  {} {} {} (no longer in the pack)",
+                                    style("−").red(),
+                                    template.app,
+                                    template.name
+                                );
+                            }
+                        }
+                    }
+
+                    TemplateCommands::Packs => {
+                        let packs = workspace::template_packs::installed(
+                            workspace_manager.get_template_manager().template_root(),
+                        )
+                        .await?;
+                        if output::is_json() {
+                            CommandResult::success(&packs).emit()?;
+                        } else if packs.is_empty() {
+                            display_println!(
+                                "{} No template packs installed; add one with `vibe apps template install <source>`",
+                                style("ℹ").blue()
+                            );
+                        } else {
+                            for (name, pack) in &packs {
+                                display_println!(
+                                    "{} {} {} templates from {}",
+                                    style("📦").blue(),
+                                    style(name).cyan().bold(),
+                                    pack.templates.len(),
+                                    style(&pack.source).dim()
+                                );
+                            }
+                        }
+                    }
+
+                    TemplateCommands::UninstallPack { name } => {
+                        let removed = workspace_manager.uninstall_template_pack(&name).await?;
+                        if output::is_json() {
+                            CommandResult::success(&removed).emit()?;
+                        } else {
+                            display_println!(
+                                "{} Uninstalled template pack '{}' ({} templates removed)",
+                                style("✓").green().bold(),
+                                style(&name).cyan(),
+                                removed.len()
+                            );
+                        }
+                    }
+
+                    TemplateCommands::Create {
+                        app,
+                        name,
+                        from_file,
+                    } => {
+                        let content = if let Some(file_path) = from_file {
+                            tokio::fs::read_to_string(&file_path).await?
+                        } else {
+                            // Use default template as starting point
+                            workspace_manager.get_default_template(&app).await?
+                        };
+
+                        workspace_manager
+                            .save_template(&app, &name, &content)
+                            .await?;
+                        display_println!(
+                            "{} Created template '{}' for {}",
+                            style("✓").green().bold(),
+                            style(&name).cyan(),
+                            style(&app).cyan()
+                        );
+                    }
+
+                    TemplateCommands::Render {
+                        app,
+                        repository,
+                        template,
+                    } => {
+                        let rendered = apps::render::render_template(
+                            workspace_manager.get_config(),
+                            workspace_manager.get_template_manager(),
+                            &app,
+                            template.as_deref(),
+                            &repository,
+                        )
+                        .await?;
+                        if output::is_json() {
+                            CommandResult::success(&rendered).emit()?;
+                        } else {
+                            // The content alone goes to stdout so it can be piped
+                            eprintln!(
+                                "{} {} template '{}' for {} → {} ({})",
+                                style("📄").blue(),
+                                rendered.app,
+                                rendered.template,
+                                style(&rendered.repository).cyan(),
+                                style(rendered.destination.display()).dim(),
+                                if rendered.destination_exists {
+                                    "exists, opening overwrites it"
+                                } else {
+                                    "not written yet"
+                                }
+                            );
+                            print!("{}", rendered.content);
+                        }
+                    }
+
+                    TemplateCommands::Delete { app, name } => {
+                        workspace_manager.delete_template(&app, &name).await?;
+                        display_println!(
+                            "{} Deleted template '{}' from {}",
+                            style("✓").green().bold(),
+                            style(&name).cyan(),
+                            style(&app).cyan()
+                        );
+                    }
+
+                    TemplateCommands::UpdateDefaults { app, force } => {
+                        let apps_to_update = if let Some(app_name) = app {
+                            vec![app_name]
+                        } else {
+                            vec![
+                                "warp".to_string(),
+                                "iterm2".to_string(),
+                                "wezterm".to_string(),
+                                "vscode".to_string(),
+                                "cursor".to_string(),
+                                "windsurf".to_string(),
+                            ]
+                        };
+
+                        if !force {
+                            display_println!(
+                                "{} This will overwrite existing default templates for: {}",
+                                style("⚠️").yellow(),
+                                apps_to_update.join(", ")
+                            );
+                            print!("Continue? [y/N] ");
+                            use std::io::{self, Write};
+                            io::stdout().flush()?;
 
-/// Print repository worktree summary with health overview
-fn print_repository_worktree_summary(
-    worktrees: &[crate::worktree::status::WorktreeInfo],
-    format: &str,
-    verbose: bool,
-) -> Result<()> {
-    use crate::worktree::status::RepositoryWorktreeSummary;
-    use colored::*;
+                            let mut input = String::new();
+                            io::stdin().read_line(&mut input)?;
 
-    if worktrees.is_empty() {
-        println!("No worktrees found");
-        return Ok(());
-    }
+                            if !input.trim().eq_ignore_ascii_case("y") {
+                                display_println!("{} Update cancelled", style("ℹ️").blue());
+                                return Ok(());
+                            }
+                        }
 
-    // Calculate repository summary
-    let summary = RepositoryWorktreeSummary::from_worktrees(worktrees);
+                        workspace_manager
+                            .update_default_templates(apps_to_update)
+                            .await?;
+                        display_println!(
+                            "{} Updated default templates with current bundled versions",
+                            style("✓").green().bold()
+                        );
+                    }
+                },
 
-    match format {
-        "json" => {
-            let json_output = serde_json::json!({
-                "health_score": summary.health_score,
-                "health_status": summary.health_description(),
-                "health_icon": summary.health_icon(),
-                "total_worktrees": summary.total_worktrees,
-                "clean_worktrees": summary.clean_worktrees,
-                "dirty_worktrees": summary.dirty_worktrees,
-                "worktrees_with_remote": summary.worktrees_with_remote,
-                "worktrees_with_unpushed": summary.worktrees_with_unpushed,
-                "merged_worktrees": summary.merged_worktrees,
-                "no_remote_count": summary.total_worktrees - summary.worktrees_with_remote,
-                "summary_description": summary.summary_description()
-            });
-            println!("{}", serde_json::to_string_pretty(&json_output)?);
-        }
-        "compact" => {
-            println!(
-                "{} {} ({} health) - {}",
-                summary.health_icon(),
-                "Repository Health".bold(),
-                (summary.health_score * 100.0) as u8,
-                summary.summary_description()
-            );
-        }
-        _ => {
-            // Default table format
-            println!(
-                "\n{} Repository Worktree Overview",
-                summary.health_icon().to_string().bold()
-            );
+                AppsCommands::Install => {
+                    // Run the interactive installer
+                    apps::run_interactive_installer().await?;
+                }
+            },
 
-            println!("{}", "─".repeat(50));
+            Commands::Menu => {
+                prompts::run_menu_mode(&mut workspace_manager).await?;
+            }
 
-            println!(
-                "{:<20} {}",
-                "Health Status:".dimmed(),
-                format!(
-                    "{} ({}%)",
-                    summary.health_description(),
-                    (summary.health_score * 100.0) as u8
-                )
-                .green()
-            );
+            Commands::Create {
+                name,
+                app,
+                no_configure,
+                no_open,
+                group,
+            } => {
+                use ui::workflows::{execute_workflow, CreateRepositoryWorkflow};
 
-            println!(
-                "{:<20} {}",
-                "Total Worktrees:".dimmed(),
-                summary.total_worktrees.to_string().cyan()
-            );
+                if let Some(group) = &group {
+                    if !workspace_manager
+                        .config()
+                        .groups
+                        .iter()
+                        .any(|g| &g.name == group)
+                    {
+                        return Err(CommandError::not_found("Group", group.as_str()).into());
+                    }
+                }
 
-            if summary.clean_worktrees > 0 {
-                println!(
-                    "{:<20} {}",
-                    "Clean:".dimmed(),
-                    summary.clean_worktrees.to_string().green()
-                );
-            }
+                // Use workflow system for repository creation
+                let workflow = Box::new(CreateRepositoryWorkflow {
+                    suggested_name: name,
+                    app,
+                    group,
+                    skip_configure: no_configure,
+                    skip_open: no_open,
+                });
 
-            if summary.dirty_worktrees > 0 {
-                println!(
-                    "{:<20} {}",
-                    "Dirty:".dimmed(),
-                    summary.dirty_worktrees.to_string().yellow()
-                );
+                execute_workflow(workflow, &mut workspace_manager).await?;
             }
 
-            if summary.worktrees_with_unpushed > 0 {
-                println!(
-                    "{:<20} {}",
-                    "With Unpushed:".dimmed(),
-                    summary.worktrees_with_unpushed.to_string().red()
-                );
-            }
+            Commands::Config { command } => match command {
+                ConfigCommands::Init {
+                    name,
+                    root,
+                    auto_discover,
+                } => {
+                    workspace_manager
+                        .init_config(name.as_deref(), root.as_deref(), auto_discover)
+                        .await?;
+                }
 
-            if summary.merged_worktrees > 0 {
-                println!(
-                    "{:<20} {}",
-                    "Merged:".dimmed(),
-                    summary.merged_worktrees.to_string().blue()
-                );
-            }
+                ConfigCommands::Edit { direct } => {
+                    workspace_manager.edit_config(direct).await?;
+                }
 
-            let no_remote = summary.total_worktrees - summary.worktrees_with_remote;
-            if no_remote > 0 {
-                println!(
-                    "{:<20} {}",
-                    "No Remote:".dimmed(),
-                    no_remote.to_string().yellow()
-                );
-            }
+                ConfigCommands::Show {
+                    format,
+                    section,
+                    show_secrets,
+                } => {
+                    if show_secrets && !std::io::stdout().is_terminal() {
+                        return Err(CommandError::Usage(
+                            "--show-secrets only prints to a terminal".to_string(),
+                        )
+                        .into());
+                    }
+                    workspace_manager
+                        .show_config(&format, section.as_deref(), show_secrets)
+                        .await?;
+                }
 
-            // Show additional verbose information
-            if verbose {
-                println!();
-                println!("{}", "Additional Details:".bold());
-                println!("{}", "─".repeat(25));
+                ConfigCommands::Validate {
+                    check_paths,
+                    check_remotes,
+                    check_apps,
+                } => {
+                    workspace_manager
+                        .validate_config(check_paths, check_remotes, check_apps)
+                        .await?;
+                }
 
-                for worktree in worktrees {
-                    let status_indicator = if worktree.status.is_clean {
-                        "✓".green()
+                ConfigCommands::Reset {
+                    force,
+                    dry_run,
+                    format,
+                } => {
+                    if dry_run {
+                        let preview = workspace_manager.preview_factory_reset().await?;
+                        print_reset_preview(&preview, &format, || preview.print())?;
                     } else {
-                        "!".yellow()
-                    };
+                        workspace_manager.factory_reset(force).await?;
+                    }
+                }
+
+                ConfigCommands::Backup {
+                    output,
+                    name,
+                    encrypt,
+                } => {
+                    let passphrase = encrypt
+                        .then(workspace::backup_crypto::passphrase_for_encryption)
+                        .transpose()?;
+                    let backup_path = workspace_manager
+                        .create_backup(output, name, passphrase)
+                        .await?;
+                    display_println!(
+                        "{} Backup created successfully: {}",
+                        style("✓").green().bold(),
+                        style(backup_path.display()).cyan()
+                    );
+                }
+
+                ConfigCommands::Restore { backup, force } => {
+                    workspace_manager.restore_from_backup(backup, force).await?;
+                }
+
+                ConfigCommands::ExportManifest { output } => {
+                    let (manifest, skipped) = workspace_manager.export_manifest();
+                    for skip in &skipped {
+                        eprintln!(
+                            "{} Left out {}: {}",
+                            style("⚠️").yellow(),
+                            style(&skip.name).cyan(),
+                            skip.reason
+                        );
+                    }
+                    match output {
+                        Some(path) => {
+                            manifest.save(&path).await?;
+                            if output::is_json() {
+                                CommandResult::success(serde_json::json!({
+                                    "path": path,
+                                    "repositories": manifest.repositories.len(),
+                                    "groups": manifest.groups.len(),
+                                    "skipped": skipped,
+                                }))
+                                .emit()?;
+                            } else {
+                                println!(
+                                    "{} Exported {} repositories and {} groups to {}",
+                                    style("✓").green().bold(),
+                                    manifest.repositories.len(),
+                                    manifest.groups.len(),
+                                    style(path.display()).cyan()
+                                );
+                            }
+                        }
+                        None if output::is_json() => CommandResult::success(&manifest).emit()?,
+                        None => print!("{}", manifest.to_yaml()?),
+                    }
+                }
+
+                ConfigCommands::MigrateHome { .. } => {
+                    unreachable!("handled before workspace setup")
+                }
+
+                ConfigCommands::Sync { command } => {
+                    handle_config_sync_command(command, &mut workspace_manager).await?;
+                }
+
+                ConfigCommands::Repo { command } => match command {
+                    ConfigRepoCommands::ReaddRemoved { names, all } => {
+                        handle_readd_removed(&names, all, &mut workspace_manager).await?;
+                    }
+                    ConfigRepoCommands::Remove { name } => {
+                        let name = workspace_manager
+                            .get_repository_flexible(&name)
+                            .ok_or_else(|| {
+                                CommandError::not_found_with_hint(
+                                    "Repository",
+                                    &name,
+                                    "Run 'vibe config repo list' to see configured repositories",
+                                )
+                            })?
+                            .name
+                            .clone();
+                        let trash_id = workspace_manager.remove_repository(&name).await?;
+                        if output::is_json() {
+                            CommandResult::success(
+                                serde_json::json!({ "name": name, "trash_id": trash_id }),
+                            )
+                            .emit()?;
+                        } else {
+                            display_println!(
+                                "{} Removed {} from the configuration; its checkout is untouched",
+                                style("✓").green(),
+                                style(&name).cyan()
+                            );
+                            if let Some(id) = trash_id {
+                                display_println!(
+                                    "🗑️  In the trash as {id}; 'vibe trash restore {id}' brings it back"
+                                );
+                            }
+                        }
+                    }
+                    ConfigRepoCommands::Exclude { name, operations } => {
+                        handle_bulk_exclusion(&name, &operations, true, &mut workspace_manager)
+                            .await?;
+                    }
+                    ConfigRepoCommands::Include { name, operations } => {
+                        handle_bulk_exclusion(&name, &operations, false, &mut workspace_manager)
+                            .await?;
+                    }
+                    ConfigRepoCommands::List {
+                        group,
+                        project_type,
+                        format,
+                        fields,
+                    } => {
+                        let repos = workspace_manager
+                            .typed_repositories(group.as_deref(), project_type)
+                            .await;
+                        if output::is_json() {
+                            CommandResult::success(&repos).emit()?;
+                        } else if format == "json" {
+                            println!("{}", serde_json::to_string_pretty(&repos)?);
+                        } else if !output::delimited::print(&repos, &format, &fields)? {
+                            workspace::project_types::print_repositories(&repos);
+                        }
+                    }
+                },
+            },
 
-                    println!(
-                        "{} {:<30} {}",
-                        status_indicator,
-                        worktree.branch.cyan(),
-                        worktree.path.display().to_string().dimmed()
-                    );
-                }
+            Commands::Task { command } => {
+                handle_task_command(command, &workspace_manager).await?;
             }
 
-            println!();
-        }
-    }
-
-    Ok(())
-}
+            Commands::Git { command } => match command {
+                GitCommands::Scan {
+                    path,
+                    depth,
+                    import,
+                    restore,
+                    restore_only,
+                    clean,
+                    yes,
+                    subprojects,
+                } => {
+                    // Validate conflicting flags
+                    if (restore || !restore_only.is_empty()) && clean {
+                        return Err(CommandError::Usage(
+                            "Cannot use --restore and --clean together".to_string(),
+                        )
+                        .into());
+                    }
 
-/// Print worktrees in table format
-fn print_worktrees_table(worktrees: &[crate::worktree::status::WorktreeInfo], verbose: bool) {
-    use colored::*;
+                    let scan_path =
+                        path.unwrap_or_else(|| workspace_manager.get_workspace_root().clone());
 
-    if worktrees.is_empty() {
-        println!("No worktrees found");
-        return;
-    }
+                    let mut report = workspace_manager
+                        .scan_repositories(
+                            &scan_path,
+                            depth,
+                            import,
+                            restore,
+                            restore_only,
+                            match (clean, yes) {
+                                (false, _) => CleanMode::Off,
+                                (true, false) => CleanMode::Confirm,
+                                (true, true) => CleanMode::Yes,
+                            },
+                        )
+                        .await?;
+                    if subprojects {
+                        report.subprojects = workspace_manager.detect_subprojects().await?;
+                    }
+                    if let Some(sync) = &report.sync {
+                        let added: Vec<String> = sync
+                            .imported
+                            .iter()
+                            .chain(&sync.restored)
+                            .cloned()
+                            .collect();
+                        prompts::offer_hook_sync(&workspace_manager, &added).await?;
+                    }
 
-    // Calculate optimal TASK ID column width (min 8, max 20)
-    let task_id_width = {
-        let max_task_id_len = worktrees
-            .iter()
-            .map(|w| {
-                if let Some(ref task_id) = w.task_id {
-                    task_id.len()
-                } else {
-                    "(main)".len()
+                    if output::is_json() {
+                        let errors = report
+                            .sync
+                            .iter()
+                            .flat_map(|sync| &sync.failed)
+                            .map(|failure| format!("{}: {}", failure.name, failure.error))
+                            .collect();
+                        CommandResult::success(report).with_errors(errors).emit()?;
+                    }
                 }
-            })
-            .max()
-            .unwrap_or(8);
-
-        // Constrain between 8 and 20 characters
-        std::cmp::max(8, std::cmp::min(20, max_task_id_len))
-    };
-
-    // Header
-    if verbose {
-        println!(
-            "{:<width$} {:<12} {:<20} {:<28} {:<8} {}",
-            "TASK ID".bold(),
-            "STATUS".bold(),
-            "BRANCH".bold(),
-            "PATH".bold(),
-            "AGE".bold(),
-            "HEAD".bold(),
-            width = task_id_width
-        );
-        println!("{}", "─".repeat(task_id_width + 12 + 20 + 28 + 8 + 10 + 6)); // Adjust separator length
-    } else {
-        println!(
-            "{:<width$} {}",
-            "TASK ID".bold(),
-            "STATUS".bold(),
-            width = task_id_width
-        );
-        println!("{}", "─".repeat(task_id_width + 20)); // Adjust separator length
-    }
-
-    for worktree in worktrees {
-        let path_string = worktree.path.to_string_lossy();
-        let path = worktree
-            .path
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or(&path_string);
-
-        // Display task_id or indicate main repository with ellipsis handling
-        let task_id_raw = if let Some(ref task_id) = worktree.task_id {
-            task_id.clone()
-        } else {
-            "(main)".to_string()
-        };
 
-        let task_id_str = if task_id_raw.len() > task_id_width {
-            format!("{}…", &task_id_raw[..task_id_width - 1])
-        } else {
-            task_id_raw
-        };
-
-        let branch = if worktree.branch.len() > 18 {
-            format!("{}…", &worktree.branch[..17])
-        } else {
-            worktree.branch.clone()
-        };
-
-        // For repository summary, we'll calculate this on the entire worktree set
-        // This is a placeholder that will be replaced with repository-level stats
-        let status = format!(
-            "{} {}",
-            worktree.status.status_icon(),
-            worktree.status.status_description()
-        );
+                GitCommands::Discover {
+                    path,
+                    depth,
+                    auto_add,
+                } => {
+                    // Show deprecation warning
+                    display_println!(
+                        "{} The 'discover' command is deprecated. Use 'scan --import' instead.",
+                        style("⚠️").yellow()
+                    );
 
-        if verbose {
-            let age = format_age(worktree.age);
-            let head = if worktree.head.len() > 7 {
-                &worktree.head[..7]
-            } else {
-                &worktree.head
-            };
+                    let scan_path =
+                        path.unwrap_or_else(|| workspace_manager.get_workspace_root().clone());
 
-            // Format the task_id with proper padding, then apply color
-            let task_id_formatted = format!("{:<width$}", task_id_str, width = task_id_width);
-            let task_id_colored = if worktree.task_id.is_some() {
-                task_id_formatted.green()
-            } else {
-                task_id_formatted.dimmed()
-            };
+                    display_println!(
+                        "{} Discovering repositories in {} (depth: {})",
+                        style("🔍").blue(),
+                        style(scan_path.display()).cyan(),
+                        depth
+                    );
 
-            // New order: TASK ID | STATUS | BRANCH | PATH | AGE | HEAD
-            println!(
-                "{} {:<12} {:<20} {:<28} {:<8} {}",
-                task_id_colored,
-                status,
-                branch.yellow(),
-                path.blue(),
-                age.dimmed(),
-                head.dimmed()
-            );
-        } else {
-            // Format the task_id with proper padding, then apply color
-            let task_id_formatted = format!("{:<width$}", task_id_str, width = task_id_width);
-            let task_id_colored = if worktree.task_id.is_some() {
-                task_id_formatted.green()
-            } else {
-                task_id_formatted.dimmed()
-            };
+                    let repos = workspace_manager
+                        .discover_repositories(&scan_path, depth)
+                        .await?;
 
-            // New order: TASK ID | STATUS
-            println!("{} {}", task_id_colored, status);
-        }
-    }
-}
+                    if repos.is_empty() {
+                        display_println!("{} No git repositories found", style("ℹ").yellow());
+                        return Ok(());
+                    }
 
-/// Print worktrees in compact format
-fn print_worktrees_compact(worktrees: &[crate::worktree::status::WorktreeInfo]) {
-    use colored::*;
+                    display_println!(
+                        "\n{} Found {} repositories:",
+                        style("📁").green(),
+                        style(repos.len()).bold()
+                    );
 
-    for worktree in worktrees {
-        // Display task_id or indicate main repository
-        let task_id_display = if let Some(ref task_id) = worktree.task_id {
-            format!("[{}]", task_id).green()
-        } else {
-            "[main]".dimmed()
-        };
+                    for repo in &repos {
+                        display_println!("  {} {}", style("→").dim(), style(repo.display()).cyan());
+                    }
 
-        println!(
-            "{} {} {} {}",
-            worktree.status.status_icon(),
-            task_id_display,
-            worktree.branch.yellow(),
-            worktree.path.display().to_string().blue()
-        );
-    }
-}
+                    if auto_add {
+                        workspace_manager
+                            .add_discovered_repositories(&repos)
+                            .await?;
+                        display_println!(
+                            "\n{} Added repositories to workspace configuration",
+                            style("✓").green().bold()
+                        );
+                    } else {
+                        display_println!(
+                            "\n{} Run with --auto-add to add these to your workspace",
+                            style("💡").yellow()
+                        );
+                    }
+                }
 
-/// Print worktrees in JSON format
-fn print_worktrees_json(worktrees: &[crate::worktree::status::WorktreeInfo]) -> Result<()> {
-    let json = serde_json::to_string_pretty(worktrees)?;
-    println!("{}", json);
-    Ok(())
-}
+                GitCommands::Status {
+                    dirty_only,
+                    format,
+                    fields,
+                    group,
+                    group_by,
+                } => {
+                    if output::is_json() {
+                        match group_by.filter(|g| *g != workspace::config::StatusGroupBy::None) {
+                            Some(group_by) => {
+                                let (grouped, warnings) = workspace_manager
+                                    .collect_grouped_status(dirty_only, group.as_deref(), group_by)
+                                    .await;
+                                CommandResult::success(grouped)
+                                    .with_warnings(warnings)
+                                    .emit()?;
+                            }
+                            None => {
+                                let (statuses, warnings) = workspace_manager
+                                    .collect_status(dirty_only, group.as_deref())
+                                    .await;
+                                CommandResult::success(statuses)
+                                    .with_warnings(warnings)
+                                    .emit()?;
+                            }
+                        }
+                    } else if output::delimited::Delimited::from_format(&format).is_some() {
+                        let (statuses, warnings) = workspace_manager
+                            .collect_status(dirty_only, group.as_deref())
+                            .await;
+                        for warning in &warnings {
+                            display_warn!("{} {}", style("⚠").yellow(), warning);
+                        }
+                        output::delimited::print(&statuses, &format, &fields)?;
+                    } else {
+                        workspace_manager
+                            .show_status_grouped(dirty_only, &format, group.as_deref(), group_by)
+                            .await?;
+                    }
+                }
 
-/// Enhanced status table printing with detailed information
-fn print_detailed_status_table(
-    worktrees: &[crate::worktree::status::WorktreeInfo],
-    show_files: bool,
-) {
-    use crate::worktree::status::RemoteStatus;
-    use colored::*;
+                GitCommands::Branches {
+                    command:
+                        BranchesCommands::Report {
+                            stale_days,
+                            group,
+                            format,
+                            fields,
+                        },
+                } => {
+                    let (report, warnings) = workspace_manager
+                        .collect_branch_report(stale_days, group.as_deref())
+                        .await;
+                    if output::is_json() {
+                        CommandResult::success(&report)
+                            .with_warnings(warnings)
+                            .emit()?;
+                    } else {
+                        for warning in &warnings {
+                            display_warn!("{} {}", style("⚠").yellow(), warning);
+                        }
+                        if format == "json" {
+                            println!("{}", serde_json::to_string_pretty(&report)?);
+                        } else if !output::delimited::print(&report.rows(), &format, &fields)? {
+                            report.print_report();
+                        }
+                    }
+                }
 
-    for (i, worktree) in worktrees.iter().enumerate() {
-        if i > 0 {
-            println!();
-        }
+                GitCommands::Hooks { command } => match command {
+                    HooksCommands::Sync { repos, group } => {
+                        let results = workspace_manager
+                            .sync_hooks(repos.as_deref(), group.as_deref())
+                            .await?;
+                        finish_hook_results(&results)?;
+                    }
+                    HooksCommands::Status { group, format } => {
+                        let repos = workspace_manager.hook_statuses(group.as_deref()).await?;
+                        if output::is_json() {
+                            CommandResult::success(&repos).emit()?;
+                        } else if format == "json" {
+                            println!("{}", serde_json::to_string_pretty(&repos)?);
+                        } else {
+                            git::hooks::print_statuses(&repos);
+                        }
+                    }
+                    HooksCommands::Remove { repos, group } => {
+                        let results = workspace_manager
+                            .remove_hooks(repos.as_deref(), group.as_deref())
+                            .await?;
+                        finish_hook_results(&results)?;
+                    }
+                },
 
-        // Header
-        println!(
-            "{} {}",
-            worktree.status.status_icon().bold(),
-            worktree.branch.cyan().bold()
-        );
-        println!("Path: {}", worktree.path.display().to_string().blue());
+                GitCommands::Files { command } => match command {
+                    FilesCommands::Sync {
+                        dry_run,
+                        repos,
+                        group,
+                    } => {
+                        let results = workspace_manager
+                            .sync_managed_files(repos.as_deref(), group.as_deref(), dry_run)
+                            .await?;
+                        let summary = BatchSummary {
+                            total: results.len(),
+                            failed: results.iter().filter(|r| r.has_problems()).count(),
+                        };
+                        if output::is_json() {
+                            CommandResult::success(&results).emit()?;
+                        } else {
+                            git::managed_files::print_results(&results, dry_run);
+                        }
+                        summary.into_result()?;
+                    }
+                },
 
-        if !worktree.head.is_empty() {
-            let short_head = if worktree.head.len() > 7 {
-                &worktree.head[..7]
-            } else {
-                &worktree.head
-            };
-            println!("HEAD: {}", short_head.dimmed());
-        }
+                GitCommands::Refs { command } => match command {
+                    RefsCommands::Restore { repo, branch } => {
+                        let backup = workspace_manager
+                            .restore_branch_backup(&repo, &branch)
+                            .await?;
+                        if output::is_json() {
+                            CommandResult::success(&backup).emit()?;
+                        } else {
+                            display_println!(
+                                "{} Restored branch {} at {} from {}",
+                                style("✓").green().bold(),
+                                style(&branch).cyan(),
+                                &backup.sha[..backup.sha.len().min(8)],
+                                backup.backup_ref
+                            );
+                        }
+                    }
+                    RefsCommands::Gc { older_than, group } => {
+                        let older_than = worktree::metadata::parse_duration(&older_than)?;
+                        let (pruned, warnings) = workspace_manager
+                            .gc_ref_backups(older_than, group.as_deref())
+                            .await;
+                        if output::is_json() {
+                            CommandResult::success(&pruned)
+                                .with_warnings(warnings)
+                                .emit()?;
+                        } else {
+                            for warning in &warnings {
+                                display_warn!("{} {}", style("⚠").yellow(), warning);
+                            }
+                            let count: usize = pruned.iter().map(|repo| repo.backups.len()).sum();
+                            display_println!(
+                                "{} Deleted {} branch backup{} from {} repositor{}",
+                                style("✓").green().bold(),
+                                count,
+                                if count == 1 { "" } else { "s" },
+                                pruned.len(),
+                                if pruned.len() == 1 { "y" } else { "ies" }
+                            );
+                        }
+                    }
+                },
 
-        println!("Age: {}", format_age(worktree.age).dimmed());
+                GitCommands::Prs {
+                    mine,
+                    review_requested,
+                    group,
+                    refresh,
+                    format,
+                } => {
+                    let filter = if mine {
+                        git::pull_requests::PrFilter::Mine
+                    } else if review_requested {
+                        git::pull_requests::PrFilter::ReviewRequested
+                    } else {
+                        git::pull_requests::PrFilter::All
+                    };
+                    let dashboard = workspace_manager
+                        .collect_pull_requests(filter, group.as_deref(), refresh)
+                        .await?;
+                    if output::is_json() {
+                        CommandResult::success(&dashboard).emit()?;
+                    } else if format == "json" {
+                        println!("{}", serde_json::to_string_pretty(&dashboard)?);
+                    } else {
+                        dashboard.print_table();
+                    }
+                }
 
-        // Remote status
-        match &worktree.status.remote_status {
-            RemoteStatus::NoRemote => {
-                println!("Remote: {}", "No remote tracking".yellow());
-            }
-            RemoteStatus::UpToDate => {
-                println!("Remote: {}", "Up to date".green());
-            }
-            RemoteStatus::Ahead(count) => {
-                println!("Remote: {} {} ahead", "↑".green(), count);
-            }
-            RemoteStatus::Behind(count) => {
-                println!("Remote: {} {} behind", "↓".red(), count);
-            }
-            RemoteStatus::Diverged { ahead, behind } => {
-                println!(
-                    "Remote: {} {} ahead, {} {} behind",
-                    "↑".green(),
-                    ahead,
-                    "↓".red(),
-                    behind
-                );
-            }
-            RemoteStatus::RemoteDeleted => {
-                println!("Remote: {}", "Remote branch deleted".red());
-            }
-        }
+                GitCommands::Exec {
+                    command,
+                    repos,
+                    group,
+                    project_type,
+                    parallel,
+                } => {
+                    if let Some(project_type) = project_type {
+                        workspace_manager.restrict_to_type(project_type).await;
+                    }
+                    workspace_manager
+                        .execute_command(&command, repos.as_deref(), group.as_deref(), parallel)
+                        .await?
+                        .into_result()?;
+                }
 
-        // Merge information
-        if let Some(merge_info) = &worktree.status.merge_info {
-            if merge_info.is_merged {
-                println!(
-                    "Merge Status: {} {} (confidence: {:.0}%)",
-                    "✅".green(),
-                    merge_info.detection_method,
-                    merge_info.confidence * 100.0
-                );
+                GitCommands::Sync {
+                    fetch_only,
+                    prune,
+                    save_dirty,
+                    upstream,
+                    group,
+                    project_type,
+                } => {
+                    if let Some(project_type) = project_type {
+                        workspace_manager.restrict_to_type(project_type).await;
+                    }
+                    let results = if upstream {
+                        workspace_manager.sync_upstream(group.as_deref()).await?
+                    } else {
+                        workspace_manager
+                            .sync_repositories(fetch_only, prune, save_dirty, group.as_deref())
+                            .await?
+                    };
+                    let summary = BatchSummary {
+                        total: results.len(),
+                        failed: results
+                            .iter()
+                            .filter(|result| matches!(result.outcome, SyncOutcome::Failed))
+                            .count(),
+                    };
 
-                if let Some(details) = &merge_info.details {
-                    println!("  Details: {}", details.dimmed());
+                    if output::is_json() {
+                        let mut warnings = Vec::new();
+                        let mut errors = Vec::new();
+                        for result in &results {
+                            match result.outcome {
+                                SyncOutcome::Synced => {}
+                                SyncOutcome::Dirty => warnings
+                                    .push(format!("{}: uncommitted changes, skipped", result.name)),
+                                SyncOutcome::Diverged => warnings.push(format!(
+                                    "{}: {}",
+                                    result.name,
+                                    result.error.as_deref().unwrap_or("diverged from upstream")
+                                )),
+                                SyncOutcome::Protected => {
+                                    warnings.push(format!("{}: skipped (protected)", result.name))
+                                }
+                                SyncOutcome::Locked => warnings.push(format!(
+                                    "{}: {}, skipped",
+                                    result.name,
+                                    result.error.as_deref().unwrap_or("locked")
+                                )),
+                                SyncOutcome::Failed => errors.push(format!(
+                                    "{}: {}",
+                                    result.name,
+                                    result.error.as_deref().unwrap_or("sync failed")
+                                )),
+                            }
+                        }
+                        CommandResult::success(results)
+                            .with_warnings(warnings)
+                            .with_errors(errors)
+                            .emit()?;
+                    }
+                    summary.into_result()?;
                 }
-            } else {
-                println!("Merge Status: {} Not merged", "❌".red());
-            }
-        }
 
-        // Changes summary
-        let changes = vec![
-            (
-                !worktree.status.uncommitted_changes.is_empty(),
-                format!("{} uncommitted", worktree.status.uncommitted_changes.len()),
-            ),
-            (
-                !worktree.status.untracked_files.is_empty(),
-                format!("{} untracked", worktree.status.untracked_files.len()),
-            ),
-            (
-                !worktree.status.unpushed_commits.is_empty(),
-                format!("{} unpushed", worktree.status.unpushed_commits.len()),
-            ),
-        ]
-        .into_iter()
-        .filter(|(has, _)| *has)
-        .map(|(_, desc)| desc)
-        .collect::<Vec<_>>();
+                GitCommands::Clone {
+                    url,
+                    path,
+                    open,
+                    install,
+                    on_collision,
+                } => {
+                    let mut git_config = workspace_manager.git_config();
+                    git_config.on_collision = on_collision;
+                    let _cloned_path = git::CloneCommand::execute(
+                        url,
+                        path,
+                        open,
+                        install,
+                        &mut workspace_manager,
+                        &git_config,
+                    )
+                    .await?;
+                }
 
-        if !changes.is_empty() {
-            println!("Changes: {}", changes.join(", ").yellow());
-        }
+                GitCommands::Search => {
+                    let git_config = workspace_manager.git_config();
+                    git::SearchCommand::execute_interactive(&mut workspace_manager, &git_config)
+                        .await?;
+                }
 
-        // Show files if requested and present
-        if show_files {
-            if !worktree.status.uncommitted_changes.is_empty() {
-                println!("  {} Uncommitted changes:", "📝".dimmed());
-                for file in &worktree.status.uncommitted_changes {
-                    println!("    {}", file);
+                GitCommands::Reset {
+                    force,
+                    dry_run,
+                    format,
+                } => {
+                    if dry_run {
+                        let preview = workspace_manager.preview_repository_reset();
+                        print_reset_preview(&preview, &format, || preview.print())?;
+                    } else {
+                        workspace_manager.reset_repositories(force).await?;
+                    }
                 }
-            }
 
-            if !worktree.status.untracked_files.is_empty() {
-                println!("  {} Untracked files:", "❓".dimmed());
-                for file in worktree.status.untracked_files.iter().take(5) {
-                    println!("    {}", file);
+                GitCommands::Worktree { action } => {
+                    handle_worktree_command(action, &workspace_manager, cli.verbose).await?;
                 }
-                if worktree.status.untracked_files.len() > 5 {
-                    println!(
-                        "    {} ... and {} more",
-                        "⋯".dimmed(),
-                        worktree.status.untracked_files.len() - 5
-                    );
+            },
+
+            Commands::Open {
+                group: Some(group),
+                app: Some(app),
+                configure_missing,
+                no_itermocil,
+                no_fetch,
+                ..
+            } => {
+                let fetch = workspace_manager.is_fetch_on_open_enabled() && !no_fetch;
+                let results = workspace_manager
+                    .open_group(&group, &app, configure_missing, no_itermocil, fetch)
+                    .await?;
+                let summary = BatchSummary {
+                    total: results.len(),
+                    failed: results
+                        .iter()
+                        .filter(|r| r.outcome == GroupOpenOutcome::Failed)
+                        .count(),
+                };
+                if output::is_json() {
+                    CommandResult::success(&results).emit()?;
+                } else {
+                    print_group_open_summary(&group, &app, &results);
                 }
+                summary.into_result()?;
             }
 
-            if !worktree.status.unpushed_commits.is_empty() {
-                println!("  {} Unpushed commits:", "↑".dimmed());
-                for commit in worktree.status.unpushed_commits.iter().take(3) {
-                    println!(
-                        "    {} {} ({})",
-                        commit.id.yellow(),
-                        commit.message,
-                        commit.author.dimmed()
-                    );
-                }
-                if worktree.status.unpushed_commits.len() > 3 {
-                    println!(
-                        "    {} ... and {} more commits",
-                        "⋯".dimmed(),
-                        worktree.status.unpushed_commits.len() - 3
-                    );
+            Commands::Open {
+                repo: Some(repo),
+                print_path: true,
+                ..
+            }
+            | Commands::Path { target: repo } => {
+                let path = resolve_target_path(&workspace_manager, &repo).await?;
+                if output::is_json() {
+                    CommandResult::success(serde_json::json!({ "path": path })).emit()?;
+                } else {
+                    println!("{}", path.display());
                 }
             }
-        }
-    }
-}
-
-/// Print status in table format
-fn print_status_table(worktrees: &[crate::worktree::status::WorktreeInfo], files_only: bool) {
-    print_detailed_status_table(worktrees, files_only);
-}
-
-/// Print status in compact format
-fn print_status_compact(worktrees: &[crate::worktree::status::WorktreeInfo], _files_only: bool) {
-    use colored::*;
-
-    for worktree in worktrees {
-        let mut status_line = format!(
-            "{} {}: {}",
-            worktree.status.status_icon(),
-            worktree.branch,
-            worktree.status.status_description()
-        );
 
-        // Add merge status if available
-        if let Some(merge_info) = &worktree.status.merge_info {
-            if merge_info.is_merged {
-                status_line.push_str(&format!(" [{}]", "merged".green()));
+            Commands::Suggest {
+                query,
+                limit,
+                format,
+            } => {
+                let query = query.join(" ");
+                let suggestions = workspace_manager.suggest_repositories(&query, limit).await;
+                if output::is_json() {
+                    CommandResult::success(&suggestions).emit()?;
+                } else if format == "json" {
+                    println!("{}", serde_json::to_string_pretty(&suggestions)?);
+                } else {
+                    git::provider::vector_store::print_suggestions(&query, &suggestions);
+                }
             }
-        }
-
-        println!("{}", status_line);
-    }
-}
-
-/// Print status in JSON format
-fn print_status_json(
-    worktrees: &[crate::worktree::status::WorktreeInfo],
-    _files_only: bool,
-) -> Result<()> {
-    let json = serde_json::to_string_pretty(worktrees)?;
-    println!("{}", json);
-    Ok(())
-}
-
-/// Format age duration for display
-fn format_age(age: std::time::Duration) -> String {
-    let hours = age.as_secs() / 3600;
-    let days = hours / 24;
-
-    if days > 0 {
-        format!("{}d", days)
-    } else if hours > 0 {
-        format!("{}h", hours)
-    } else {
-        format!("{}m", age.as_secs() / 60)
-    }
-}
-
-/// Print cleanup report
-fn print_cleanup_report(report: &crate::worktree::cleanup::CleanupReport) {
-    use colored::*;
-
-    println!();
-    println!("{} Cleanup Report", "📊".blue());
-    println!("Strategy: {:?}", report.strategy_used);
-    if report.was_dry_run {
-        println!("Mode: {} (no changes made)", "Dry Run".yellow());
-    }
-    println!();
-
-    println!("Results:");
-    println!("  ✅ Cleaned: {}", report.cleaned_count.to_string().green());
-    println!(
-        "  ⚠️  Skipped: {}",
-        report.skipped_count.to_string().yellow()
-    );
-    println!("  ❌ Failed:  {}", report.failed_count.to_string().red());
-    println!("  📊 Total:   {}", report.total_evaluated);
-
-    if !report.worktree_results.is_empty() {
-        println!();
-        println!("Details:");
-
-        for result in &report.worktree_results {
-            let action_icon = match result.action {
-                crate::worktree::cleanup::CleanupAction::Cleaned => "✅",
-                crate::worktree::cleanup::CleanupAction::Skipped => "⚠️",
-                crate::worktree::cleanup::CleanupAction::Failed => "❌",
-                crate::worktree::cleanup::CleanupAction::MergedToFeature => "🔀",
-                crate::worktree::cleanup::CleanupAction::BackedUpToOrigin => "☁️",
-                crate::worktree::cleanup::CleanupAction::StashCreated => "📦",
-            };
 
-            println!(
-                "  {} {} - {}",
-                action_icon,
-                result.branch.cyan(),
-                result.reason
-            );
+            Commands::Grep {
+                pattern,
+                group,
+                subproject,
+                project_type,
+                glob,
+                max_results,
+                open,
+                format,
+            } => {
+                if let Some(project_type) = project_type {
+                    workspace_manager.restrict_to_type(project_type).await;
+                }
+                let options = workspace::search::SearchOptions {
+                    pattern,
+                    glob,
+                    max_per_repo: max_results,
+                };
+                let results = match subproject {
+                    Some(target) => {
+                        workspace_manager
+                            .search_subproject(&target, &options)
+                            .await?
+                    }
+                    None => {
+                        workspace_manager
+                            .search_repositories(group.as_deref(), &options)
+                            .await
+                    }
+                };
+                let summary = BatchSummary {
+                    total: results.len(),
+                    failed: results.iter().filter(|r| r.error.is_some()).count(),
+                };
 
-            if let Some(error) = &result.error {
-                println!("    Error: {}", error.red());
+                if output::is_json() {
+                    let errors = results
+                        .iter()
+                        .filter_map(|r| Some(format!("{}: {}", r.repo, r.error.as_ref()?)))
+                        .collect();
+                    CommandResult::success(&results)
+                        .with_errors(errors)
+                        .emit()?;
+                } else if format == "json" {
+                    println!("{}", serde_json::to_string_pretty(&results)?);
+                } else {
+                    workspace::search::print_results(&results);
+                    if open {
+                        open_search_match(&results, workspace_manager.is_direnv_enabled()).await?;
+                    }
+                }
+                summary.into_result()?;
             }
-        }
-    }
-
-    println!();
-    if report.cleaned_count > 0 && !report.was_dry_run {
-        println!("{} Cleanup completed successfully!", "🎉".green());
-    } else if report.was_dry_run {
-        println!("{} Run without --dry-run to execute changes", "💡".blue());
-    }
-}
-
-/// Prompt user for confirmation
-fn prompt_for_confirmation(message: &str) -> Result<bool> {
-    use std::io::{self, Write};
-
-    print!("{} (y/N): ", message);
-    io::stdout().flush()?;
-
-    let mut input = String::new();
-    io::stdin().read_line(&mut input)?;
-
-    Ok(input.trim().to_lowercase() == "y" || input.trim().to_lowercase() == "yes")
-}
-
-#[tokio::main]
-async fn main() -> Result<()> {
-    let cli = Cli::parse();
 
-    // Determine output mode based on command
-    let output_mode = match &cli.command {
-        Some(Commands::Mcp { .. }) => output::OutputMode::Mcp,
-        _ => output::OutputMode::Cli,
-    };
-
-    // Initialize output system (this handles tracing setup)
-    output::init_with_verbosity(output_mode, cli.verbose);
-
-    // Load or create workspace configuration
-    let config_path = cli
-        .config
-        .unwrap_or_else(workspace::constants::get_default_config_path);
-
-    let mut workspace_manager =
-        WorkspaceManager::new_with_root_override(config_path.clone(), cli.root).await?;
-
-    match cli.command {
-        None => {
-            // No command provided, start menu mode
-            prompts::run_menu_mode(&mut workspace_manager).await?;
-        }
-        Some(command) => match command {
-            Commands::Apps { command } => match command {
-                AppsCommands::Configure {
-                    repo,
-                    app,
-                    template,
-                } => {
-                    let template_name = template.as_deref().unwrap_or("default");
-                    workspace_manager
-                        .configure_app_for_repo(&repo, &app, template_name)
-                        .await?;
-                    display_println!(
-                        "{} Configured {} for repository '{}' with template '{}'",
-                        style("✓").green().bold(),
-                        style(&app).cyan(),
-                        style(&repo).cyan(),
-                        style(template_name).dim()
-                    );
+            Commands::Repo { command } => match command {
+                RepoCommands::Env { name, format } => {
+                    let env = workspace_manager.project_env(&name).await?;
+                    if output::is_json() {
+                        CommandResult::success(&env).emit()?;
+                    } else if format == "json" {
+                        println!("{}", serde_json::to_string_pretty(&env)?);
+                    } else {
+                        env.print_table();
+                    }
                 }
 
-                AppsCommands::Show { repo, app } => {
-                    if let Some(repo_name) = repo {
-                        let apps = workspace_manager.list_apps_for_repo(&repo_name)?;
-                        display_println!(
-                            "{} Apps configured for repository '{}':",
-                            style("📱").blue(),
-                            style(&repo_name).cyan().bold()
-                        );
-                        for (app_name, template) in apps {
-                            display_println!(
-                                "  {} {} (template: {})",
-                                style("→").dim(),
-                                style(&app_name).green(),
-                                style(&template).dim()
-                            );
+                RepoCommands::Health { group, format } => {
+                    let (report, warnings) =
+                        workspace_manager.collect_health(group.as_deref()).await;
+                    if output::is_json() {
+                        CommandResult::success(&report)
+                            .with_warnings(warnings)
+                            .emit()?;
+                    } else {
+                        for warning in &warnings {
+                            display_warn!("{} {}", style("⚠").yellow(), warning);
                         }
-                    } else if let Some(app_name) = app {
-                        let repos = workspace_manager.list_repos_with_app(&app_name);
-                        display_println!(
-                            "{} Repositories with {} configured:",
-                            style("📱").blue(),
-                            style(&app_name).cyan().bold()
-                        );
-                        for (repo, template) in repos {
-                            display_println!(
-                                "  {} {} (template: {})",
-                                style("→").dim(),
-                                style(&repo.name).green(),
-                                style(&template).dim()
-                            );
+                        if format == "json" {
+                            println!("{}", serde_json::to_string_pretty(&report)?);
+                        } else {
+                            report.print_table();
                         }
-                    } else {
-                        // Show all app configurations
-                        workspace_manager.show_app_configurations().await?;
                     }
                 }
+            },
 
-                AppsCommands::Template { command } => match command {
-                    TemplateCommands::List { app } => {
-                        let templates = workspace_manager.list_templates(&app).await?;
-                        display_println!(
-                            "{} Available templates for {}:",
-                            style("📄").blue(),
-                            style(&app).cyan().bold()
-                        );
-                        for template in templates {
-                            display_println!("  {} {}", style("→").dim(), style(&template).green());
-                        }
+            Commands::Open {
+                repo,
+                app,
+                no_itermocil,
+                no_fetch,
+                ..
+            } => {
+                let repo = repo.expect("clap requires a repository without --group");
+                // Find the repository (or monorepo subproject) using flexible lookup
+                let repo_info = workspace_manager.get_open_target(&repo).ok_or_else(|| {
+                    CommandError::NotFound {
+                        kind: "Repository",
+                        name: repo.clone(),
+                        hint: Some("Try 'vibe launch' to see available repositories.".to_string()),
                     }
+                })?;
 
-                    TemplateCommands::Create {
-                        app,
-                        name,
-                        from_file,
-                    } => {
-                        let content = if let Some(file_path) = from_file {
-                            tokio::fs::read_to_string(&file_path).await?
-                        } else {
-                            // Use default template as starting point
-                            workspace_manager.get_default_template(&app).await?
-                        };
+                let repo_name = &repo_info.name;
+                let fetch = workspace_manager.is_fetch_on_open_enabled() && !no_fetch;
+
+                if let Some(app_name) = app {
+                    // Open with specific app
+                    workspace_manager
+                        .open_repo_with_fetch(repo_name, &app_name, no_itermocil, fetch)
+                        .await?;
+                } else {
+                    // Open with preferred app or show available options
+                    let configured_apps = workspace_manager.list_apps_for_repo(repo_name)?;
+
+                    let app_names: Vec<String> =
+                        configured_apps.iter().map(|(app, _)| app.clone()).collect();
+                    let most_used = if app_names.len() > 1 {
+                        workspace_manager.most_used_app(repo_name, &app_names).await
+                    } else {
+                        None
+                    };
 
+                    if configured_apps.len() == 1 {
+                        // Only one app configured, use it
+                        let (app_name, _) = &configured_apps[0];
                         workspace_manager
-                            .save_template(&app, &name, &content)
+                            .open_repo_with_fetch(repo_name, app_name, no_itermocil, fetch)
                             .await?;
+                    } else if let Some(app_name) = most_used {
+                        // Several apps configured, use the one launched most
                         display_println!(
-                            "{} Created template '{}' for {}",
-                            style("✓").green().bold(),
-                            style(&name).cyan(),
-                            style(&app).cyan()
-                        );
-                    }
-
-                    TemplateCommands::Delete { app, name } => {
-                        workspace_manager.delete_template(&app, &name).await?;
-                        display_println!(
-                            "{} Deleted template '{}' from {}",
-                            style("✓").green().bold(),
-                            style(&name).cyan(),
-                            style(&app).cyan()
-                        );
-                    }
-
-                    TemplateCommands::UpdateDefaults { app, force } => {
-                        let apps_to_update = if let Some(app_name) = app {
-                            vec![app_name]
-                        } else {
-                            vec![
-                                "warp".to_string(),
-                                "iterm2".to_string(),
-                                "wezterm".to_string(),
-                                "vscode".to_string(),
-                                "cursor".to_string(),
-                                "windsurf".to_string(),
-                            ]
-                        };
-
-                        if !force {
+                            "{} Opening '{}' with {} (most used; choose another with --app)",
+                            style("→").dim(),
+                            style(repo_name).cyan(),
+                            style(&app_name).green()
+                        );
+                        workspace_manager
+                            .open_repo_with_fetch(repo_name, &app_name, no_itermocil, fetch)
+                            .await?;
+                    } else if configured_apps.len() > 1 {
+                        // Multiple apps configured, show configured options
+                        display_println!(
+                            "{} Multiple apps configured for '{}'. Please specify one:",
+                            style("🤔").yellow(),
+                            style(repo_name).cyan()
+                        );
+                        for (app_name, _template) in &configured_apps {
                             display_println!(
-                                "{} This will overwrite existing default templates for: {}",
-                                style("⚠️").yellow(),
-                                apps_to_update.join(", ")
+                                "  {} vibe open {} --app {}",
+                                style("→").dim(),
+                                repo_name,
+                                style(app_name).green()
                             );
-                            print!("Continue? [y/N] ");
-                            use std::io::{self, Write};
-                            io::stdout().flush()?;
-
-                            let mut input = String::new();
-                            io::stdin().read_line(&mut input)?;
-
-                            if !input.trim().eq_ignore_ascii_case("y") {
-                                display_println!("{} Update cancelled", style("ℹ️").blue());
-                                return Ok(());
-                            }
                         }
-
+                    } else if let Some(defaults) = workspace_manager.group_defaults(repo_name) {
+                        // No apps configured, use the default of the repository's group
+                        display_println!(
+                            "{} Opening '{}' with {} (default for group '{}')",
+                            style("→").dim(),
+                            style(repo_name).cyan(),
+                            style(&defaults.app).green(),
+                            defaults.group
+                        );
                         workspace_manager
-                            .update_default_templates(apps_to_update)
+                            .open_repo_with_fetch(repo_name, &defaults.app, no_itermocil, fetch)
                             .await?;
+                    } else {
+                        // No apps configured, show available apps with basic opening
                         display_println!(
-                            "{} Updated default templates with current bundled versions",
-                            style("✓").green().bold()
+                            "{} No templates configured for '{}'. Available apps for basic opening:",
+                            style("ℹ️").blue(),
+                            style(repo_name).cyan()
                         );
+
+                        let mut available_apps = Vec::new();
+                        for app in &["vscode", "cursor", "warp", "iterm2", "wezterm", "windsurf"] {
+                            if workspace_manager.is_app_available(app).await {
+                                available_apps.push(*app);
+                                display_println!(
+                                    "  {} vibe open {} --app {} {} (basic mode)",
+                                    style("→").dim(),
+                                    repo_name,
+                                    style(app).green(),
+                                    style("").dim()
+                                );
+                            }
+                        }
+
+                        if available_apps.is_empty() {
+                            display_println!(
+                                "{} No supported apps found on this system",
+                                style("⚠️").yellow()
+                            );
+                        } else {
+                            display_println!(
+                                "\n{} Or configure templates for enhanced features: vibe apps configure {} <app>",
+                                style("💡").yellow(),
+                                style(repo_name).cyan()
+                            );
+                        }
                     }
-                },
+                }
+            }
 
-                AppsCommands::Install => {
-                    // Run the interactive installer
-                    apps::run_interactive_installer().await?;
+            Commands::Launch { slot, last } => {
+                let cache_dir = workspace::constants::get_cache_dir();
+                let launcher = ui::quick_launcher::QuickLauncher::new(&cache_dir).await?;
+                match slot.or(last.then_some(1)) {
+                    Some(position) => {
+                        launcher
+                            .quick_launch_recent(&mut workspace_manager, position as usize)
+                            .await?
+                    }
+                    // Use the QuickLauncher for interactive selection
+                    None => launcher.launch(&mut workspace_manager).await?,
                 }
-            },
+            }
 
-            Commands::Menu => {
-                prompts::run_menu_mode(&mut workspace_manager).await?;
+            Commands::RestoreWorkspace { manifest } => {
+                let manifest = workspace::manifest::WorkspaceManifest::load(&manifest).await?;
+                let report = workspace_manager.restore_manifest(&manifest).await?;
+                if output::is_json() {
+                    CommandResult::success(&report).emit()?;
+                } else {
+                    report.print();
+                }
+                BatchSummary {
+                    total: manifest.repositories.len(),
+                    failed: report.failed.len(),
+                }
+                .into_result()?;
             }
 
-            Commands::Create {
-                name,
+            Commands::Clone {
+                url,
                 app,
                 no_configure,
                 no_open,
+                group,
+                all,
+                exclude,
+                include,
+                force,
+                dry_run,
+                format,
+                refresh,
+                as_name,
+                create_remote,
+                vars,
+                on_collision,
             } => {
-                use ui::workflows::{execute_workflow, CreateRepositoryWorkflow};
-
-                // Use workflow system for repository creation
-                let workflow = Box::new(CreateRepositoryWorkflow {
-                    suggested_name: name,
-                    app,
-                    skip_configure: no_configure,
-                    skip_open: no_open,
-                });
+                let mut git_config = workspace_manager.git_config();
+                git_config.on_collision = on_collision;
 
-                execute_workflow(workflow, &mut workspace_manager).await?;
-            }
+                if let Some(name) = as_name {
+                    use repository::template::{
+                        generate_from_template, parse_variable, GenerateOptions,
+                    };
 
-            Commands::Config { command } => match command {
-                ConfigCommands::Init {
-                    name,
-                    root,
-                    auto_discover,
-                } => {
-                    workspace_manager
-                        .init_config(name.as_deref(), root.as_deref(), auto_discover)
+                    let options = GenerateOptions {
+                        template: url,
+                        name: name.clone(),
+                        create_remote,
+                        variables: vars
+                            .iter()
+                            .map(|var| parse_variable(var))
+                            .collect::<Result<_>>()?,
+                    };
+                    generate_from_template(options, &mut workspace_manager).await?;
+                    prompts::offer_hook_sync(&workspace_manager, std::slice::from_ref(&name))
                         .await?;
-                }
 
-                ConfigCommands::Edit { direct } => {
-                    workspace_manager.edit_config(direct).await?;
-                }
+                    if let Some(app) = app {
+                        if !no_configure {
+                            workspace_manager
+                                .configure_app_for_repo(&name, &app, "default")
+                                .await?;
+                        }
+                        if !no_open {
+                            workspace_manager.open_repo_with_app(&name, &app).await?;
+                        }
+                    }
+                } else if all {
+                    use git::bulk_clone::{BulkCloneCommand, BulkCloneOptions};
 
-                ConfigCommands::Show { format, section } => {
-                    workspace_manager
-                        .show_config(&format, section.as_deref())
-                        .await?;
-                }
+                    let exclude_patterns = exclude
+                        .map(|s| s.split(',').map(|p| p.trim().to_string()).collect())
+                        .unwrap_or_default();
 
-                ConfigCommands::Validate {
-                    check_paths,
-                    check_remotes,
-                    check_apps,
-                } => {
-                    workspace_manager
-                        .validate_config(check_paths, check_remotes, check_apps)
+                    let include_patterns = include
+                        .map(|s| s.split(',').map(|p| p.trim().to_string()).collect())
+                        .unwrap_or_default();
+
+                    let options = BulkCloneOptions {
+                        exclude_patterns,
+                        include_patterns,
+                        skip_existing: true,
+                        custom_path: None,
+                        force,
+                        refresh,
+                    };
+
+                    if dry_run {
+                        let plan = BulkCloneCommand::plan(
+                            &url,
+                            &options,
+                            workspace_manager.get_workspace_root(),
+                            &git_config,
+                        )
                         .await?;
-                }
+                        if output::is_json() {
+                            CommandResult::success(&plan).emit()?;
+                        } else if format == "json" {
+                            println!("{}", serde_json::to_string_pretty(&plan)?);
+                        } else {
+                            BulkCloneCommand::print_plan(&plan);
+                        }
+                        return Ok(());
+                    }
 
-                ConfigCommands::Reset { force } => {
-                    workspace_manager.factory_reset(force).await?;
-                }
+                    match BulkCloneCommand::execute(
+                        url,
+                        options,
+                        &mut workspace_manager,
+                        &git_config,
+                    )
+                    .await
+                    {
+                        Ok(result) => {
+                            display_println!(
+                                "{} Bulk clone completed: {} successful, {} failed",
+                                style("✅").green().bold(),
+                                result.total_cloned,
+                                result.failed.len()
+                            );
+                        }
+                        Err(e) => {
+                            display_println!("{} Bulk clone failed: {}", style("❌").red(), e);
+                            std::process::exit(1);
+                        }
+                    }
+                } else {
+                    // Handle single repository cloning with enhanced detection
+                    use git::clone::EnhancedCloneCommand;
 
-                ConfigCommands::Backup { output, name } => {
-                    let backup_path = workspace_manager.create_backup(output, name).await?;
-                    display_println!(
-                        "{} Backup created successfully: {}",
-                        style("✓").green().bold(),
-                        style(backup_path.display()).cyan()
-                    );
+                    match EnhancedCloneCommand::execute_with_detection(
+                        url,
+                        app,
+                        no_configure,
+                        no_open,
+                        group,
+                        &mut workspace_manager,
+                        &git_config,
+                    )
+                    .await
+                    {
+                        Ok(_) => {
+                            display_println!(
+                                "{} Repository operation completed successfully!",
+                                style("✓").green().bold()
+                            );
+                        }
+                        // Usage and lookup errors keep their own exit codes
+                        Err(e) if e.downcast_ref::<CommandError>().is_some() => return Err(e),
+                        Err(e) => {
+                            display_println!("{} Clone operation failed: {}", style("❌").red(), e);
+                            std::process::exit(1);
+                        }
+                    }
                 }
+            }
 
-                ConfigCommands::Restore { backup, force } => {
-                    workspace_manager.restore_from_backup(backup, force).await?;
-                }
-            },
+            Commands::Setup { skip } => {
+                if skip {
+                    let mut state = VibeState::load().unwrap_or_default();
+                    state.complete_setup_wizard();
+                    state.save()?;
+                    display_println!("{} Setup wizard skipped", style("ℹ️").blue());
+                } else {
+                    use ui::workflows::{execute_workflow, SetupWorkspaceWorkflow};
 
-            Commands::Git { command } => match command {
-                GitCommands::Scan {
-                    path,
-                    depth,
-                    import,
-                    restore,
-                    clean,
-                } => {
-                    // Validate conflicting flags
-                    if restore && clean {
-                        anyhow::bail!("Cannot use --restore and --clean together");
-                    }
+                    // Run setup workflow
+                    let workflow = Box::new(SetupWorkspaceWorkflow {
+                        auto_discover: true,
+                    });
 
-                    let scan_path =
-                        path.unwrap_or_else(|| workspace_manager.get_workspace_root().clone());
+                    execute_workflow(workflow, &mut workspace_manager).await?;
 
-                    workspace_manager
-                        .scan_repositories(&scan_path, depth, import, restore, clean)
-                        .await?;
+                    // Mark setup as complete
+                    let mut state = VibeState::load().unwrap_or_default();
+                    state.complete_setup_wizard();
+                    state.save()?;
                 }
+            }
 
-                GitCommands::Discover {
-                    path,
-                    depth,
-                    auto_add,
-                } => {
-                    // Show deprecation warning
-                    display_println!(
-                        "{} The 'discover' command is deprecated. Use 'scan --import' instead.",
-                        style("⚠️").yellow()
+            Commands::Mcp {
+                command: Some(McpCommands::Validate { deep }),
+                ..
+            } => {
+                use std::sync::Arc;
+                use tokio::sync::Mutex;
+
+                workspace_manager.set_launch_source(LaunchSource::Mcp);
+                let shared_workspace = Arc::new(Mutex::new(workspace_manager));
+                let report = mcp::self_test::run(shared_workspace, deep).await?;
+                let failures = report.failures();
+                if output::is_json() {
+                    CommandResult::success(&report)
+                        .with_errors(failures.clone())
+                        .emit()?;
+                } else {
+                    report.print();
+                }
+                if !failures.is_empty() {
+                    anyhow::bail!(
+                        "{} of {} MCP tools failed validation",
+                        report.failed,
+                        report.tools.len()
                     );
+                }
+            }
 
-                    let scan_path =
-                        path.unwrap_or_else(|| workspace_manager.get_workspace_root().clone());
+            Commands::Mcp {
+                command: None,
+                port,
+                stdio: _,
+            } => {
+                use std::sync::Arc;
+                use tokio::sync::Mutex;
 
-                    display_println!(
-                        "{} Discovering repositories in {} (depth: {})",
-                        style("🔍").blue(),
-                        style(scan_path.display()).cyan(),
-                        depth
-                    );
+                // Create shared workspace manager for MCP server
+                workspace_manager.set_launch_source(LaunchSource::Mcp);
+                let shared_workspace = Arc::new(Mutex::new(workspace_manager));
 
-                    let repos = workspace_manager
-                        .discover_repositories(&scan_path, depth)
-                        .await?;
+                if let Some(port_num) = port {
+                    // HTTP transport not implemented in this example
+                    // You would need to implement HTTP transport support
+                    display_eprintln!(
+                        "{} HTTP transport on port {} not yet implemented",
+                        style("⚠️").yellow(),
+                        port_num
+                    );
+                    display_eprintln!(
+                        "{} Use --stdio flag for stdio transport",
+                        style("💡").blue()
+                    );
+                    std::process::exit(1);
+                } else {
+                    // Run with stdio transport (default)
+                    display_eprintln!(
+                        "{} Starting vibe-workspace MCP server (stdio transport)...",
+                        style("🚀").green()
+                    );
 
-                    if repos.is_empty() {
-                        display_println!("{} No git repositories found", style("ℹ").yellow());
-                        return Ok(());
-                    }
+                    let mcp_server = mcp::VibeMCPServer::new(shared_workspace);
+                    mcp_server.run().await?;
+                }
+            }
 
+            Commands::Guide { topic, reset } => {
+                let mut state = ui::state::VibeState::load().unwrap_or_default();
+                if reset {
+                    state.reset_guide();
+                    state.save()?;
+                    display_println!("{} Guide progress cleared", style("✓").green());
+                } else if output::is_interactive() && std::io::stdin().is_terminal() {
+                    ui::guide::run(&workspace_manager, topic).await?;
+                } else {
+                    print_getting_started_guide();
+                    let (done, total) = ui::guide::progress(&state);
                     display_println!(
-                        "\n{} Found {} repositories:",
-                        style("📁").green(),
-                        style(repos.len()).bold()
+                        "{} steps of the interactive guide done; run {} in a terminal to continue",
+                        style(format!("{done} of {total}")).cyan(),
+                        style("vibe guide").cyan()
                     );
+                }
+            }
 
-                    for repo in &repos {
-                        display_println!("  {} {}", style("→").dim(), style(repo.display()).cyan());
-                    }
+            Commands::Uri { command } => {
+                handle_uri_command(command, workspace_manager).await?;
+            }
 
-                    if auto_add {
-                        workspace_manager
-                            .add_discovered_repositories(&repos)
-                            .await?;
-                        display_println!(
-                            "\n{} Added repositories to workspace configuration",
-                            style("✓").green().bold()
-                        );
+            Commands::Stats { command } => match command {
+                StatsCommands::Disk {
+                    top,
+                    include_worktrees,
+                    refresh,
+                    format,
+                    fields,
+                } => {
+                    run_disk_stats(
+                        &mut workspace_manager,
+                        top,
+                        include_worktrees,
+                        refresh,
+                        &format,
+                        &fields,
+                    )
+                    .await?;
+                }
+                StatsCommands::Contributors {
+                    since,
+                    group,
+                    format,
+                    fields,
+                } => {
+                    let since = workspace::contributors::parse_since(
+                        &since,
+                        chrono::Local::now().date_naive(),
+                    )?;
+                    let (report, warnings) = workspace_manager
+                        .collect_contributors(since, group.as_deref())
+                        .await;
+                    if output::is_json() {
+                        CommandResult::success(&report)
+                            .with_warnings(warnings)
+                            .emit()?;
                     } else {
-                        display_println!(
-                            "\n{} Run with --auto-add to add these to your workspace",
-                            style("💡").yellow()
-                        );
+                        for warning in &warnings {
+                            display_warn!("{} {}", style("⚠").yellow(), warning);
+                        }
+                        if format == "json" {
+                            println!("{}", serde_json::to_string_pretty(&report)?);
+                        } else if !output::delimited::print(&report.rows(), &format, &fields)? {
+                            report.print_table();
+                        }
                     }
                 }
-
-                GitCommands::Status {
-                    dirty_only,
+                StatsCommands::Usage {
+                    days,
+                    repo,
                     format,
-                    group,
+                    fields,
                 } => {
-                    workspace_manager
-                        .show_status(dirty_only, &format, group.as_deref())
+                    let now = chrono::Utc::now();
+                    let widest = days.iter().copied().max().unwrap_or(0);
+                    let mut launches = workspace_manager
+                        .get_app_launches(Some(now - chrono::Duration::days(i64::from(widest))))
                         .await?;
+                    if let Some(repo) = &repo {
+                        launches.retain(|launch| &launch.repo == repo);
+                    }
+                    let report = workspace::launch_stats::UsageReport::new(&launches, &days, now);
+                    if output::is_json() {
+                        CommandResult::success(&report).emit()?;
+                    } else if format == "json" {
+                        println!("{}", serde_json::to_string_pretty(&report)?);
+                    } else if !output::delimited::print(&report.rows, &format, &fields)? {
+                        report.print_table();
+                    }
                 }
+            },
 
-                GitCommands::Exec {
-                    command,
-                    repos,
+            Commands::Scan { command } => match command {
+                ScanCommands::Licenses {
                     group,
-                    parallel,
+                    deny,
+                    format,
+                    fields,
                 } => {
-                    workspace_manager
-                        .execute_command(&command, repos.as_deref(), group.as_deref(), parallel)
+                    let (report, warnings) = workspace_manager
+                        .scan_licenses(group.as_deref(), &deny)
                         .await?;
+                    if output::is_json() {
+                        CommandResult::success(&report)
+                            .with_warnings(warnings)
+                            .emit()?;
+                    } else {
+                        for warning in &warnings {
+                            display_warn!("{} {}", style("⚠").yellow(), warning);
+                        }
+                        if format == "json" {
+                            println!("{}", serde_json::to_string_pretty(&report)?);
+                        } else if !output::delimited::print(&report.rows(), &format, &fields)? {
+                            report.print_table();
+                        }
+                    }
+                    if report.denied > 0 {
+                        return Err(CommandError::DeniedLicenses {
+                            repos: report.denied,
+                        }
+                        .into());
+                    }
                 }
+            },
 
-                GitCommands::Sync {
-                    fetch_only,
-                    prune,
-                    save_dirty,
-                    group,
-                } => {
-                    workspace_manager
-                        .sync_repositories(fetch_only, prune, save_dirty, group.as_deref())
-                        .await?;
+            Commands::Activity {
+                days,
+                group,
+                author,
+                format,
+            } => {
+                let (report, warnings) = workspace_manager
+                    .collect_activity(days.max(1), group.as_deref(), author)
+                    .await;
+                if output::is_json() {
+                    CommandResult::success(&report)
+                        .with_warnings(warnings)
+                        .emit()?;
+                } else {
+                    for warning in &warnings {
+                        display_warn!("{} {}", style("⚠").yellow(), warning);
+                    }
+                    if format == "json" {
+                        println!("{}", serde_json::to_string_pretty(&report)?);
+                    } else {
+                        report.print_table();
+                    }
                 }
+            }
 
-                GitCommands::Clone {
-                    url,
-                    path,
-                    open,
-                    install,
-                } => {
-                    let git_config = git::GitConfig::default();
-                    let _cloned_path = git::CloneCommand::execute(
-                        url,
-                        path,
-                        open,
-                        install,
-                        &mut workspace_manager,
-                        &git_config,
-                    )
-                    .await?;
+            Commands::History { command, limit } => {
+                let log = workspace::history::EventLog::open_default();
+                match command {
+                    None => {
+                        let events = log.recent(limit).await?;
+                        if output::is_json() {
+                            CommandResult::success(redact::redacted(&events)?).emit()?;
+                        } else {
+                            workspace::history::print_history(&events);
+                        }
+                    }
+                    Some(HistoryCommands::Undo { id }) => {
+                        let event =
+                            workspace::history::undo(&log, id, &mut workspace_manager).await?;
+                        if output::is_json() {
+                            CommandResult::success(redact::redacted(&event)?).emit()?;
+                        } else {
+                            println!("{} Undid: {}", style("✓").green().bold(), event.summary);
+                        }
+                    }
                 }
+            }
 
-                GitCommands::Search => {
-                    let git_config = git::GitConfig::default();
-                    git::SearchCommand::execute_interactive(&mut workspace_manager, &git_config)
-                        .await?;
+            Commands::Trash { command } => {
+                let trash = workspace::trash::Trash::open_default();
+                match command {
+                    TrashCommands::List => {
+                        let items = trash.list().await?;
+                        if output::is_json() {
+                            CommandResult::success(redact::redacted(&items)?).emit()?;
+                        } else {
+                            workspace::trash::print_items(
+                                &items,
+                                workspace_manager.trash_retention_days(),
+                            );
+                        }
+                    }
+                    TrashCommands::Restore { id } => {
+                        let item =
+                            workspace::trash::restore(&trash, &id, &mut workspace_manager).await?;
+                        if output::is_json() {
+                            CommandResult::success(redact::redacted(&item)?).emit()?;
+                        } else {
+                            display_println!(
+                                "{} Restored: {}",
+                                style("✓").green().bold(),
+                                item.summary
+                            );
+                        }
+                    }
+                    TrashCommands::Empty { older_than, yes } => {
+                        let older_than = older_than
+                            .as_deref()
+                            .map(worktree::metadata::parse_duration)
+                            .transpose()?;
+                        let cutoff = older_than.map(|age| chrono::Utc::now() - age);
+                        let count = trash
+                            .list()
+                            .await?
+                            .iter()
+                            .filter(|item| cutoff.is_none_or(|cutoff| item.removed_at <= cutoff))
+                            .count();
+                        if count == 0 {
+                            if output::is_json() {
+                                CommandResult::success(Vec::<workspace::trash::TrashItem>::new())
+                                    .emit()?;
+                            } else {
+                                display_println!("Nothing to empty");
+                            }
+                            return Ok(());
+                        }
+                        if !yes {
+                            let prompt = format!(
+                                "Permanently delete {count} item{} from the trash?",
+                                if count == 1 { "" } else { "s" }
+                            );
+                            let action = DestructiveAction::new(&prompt);
+                            if !confirm_destructive(
+                                action,
+                                workspace_manager.get_confirmation_level(),
+                            )? {
+                                display_println!("Cancelled.");
+                                return Ok(());
+                            }
+                        }
+                        let deleted = trash.empty(older_than).await?;
+                        if output::is_json() {
+                            CommandResult::success(redact::redacted(&deleted)?).emit()?;
+                        } else {
+                            display_println!(
+                                "{} Deleted {} item{} from the trash",
+                                style("✓").green().bold(),
+                                deleted.len(),
+                                if deleted.len() == 1 { "" } else { "s" }
+                            );
+                        }
+                    }
                 }
+            }
 
-                GitCommands::Reset { force } => {
-                    workspace_manager.reset_repositories(force).await?;
+            Commands::Hooks {
+                command: EventHooksCommands::Test { event },
+            } => {
+                let hooks = &workspace_manager.config().hooks;
+                let Some(command) = hooks.events.get(&event) else {
+                    return Err(CommandError::not_found_with_hint(
+                        "Event hook",
+                        event.as_str(),
+                        "Add a command for it under hooks.events in config.yaml",
+                    )
+                    .into());
+                };
+                let payload = workspace::events::Event::sample(event);
+                let run = workspace::events::run(
+                    command,
+                    &payload,
+                    workspace::events::event_timeout(hooks),
+                )
+                .await?;
+                if output::is_json() {
+                    CommandResult::success(&run).emit()?;
+                } else {
+                    print_hook_run(&payload, &run);
                 }
-
-                GitCommands::Worktree { action } => {
-                    handle_worktree_command(action, &workspace_manager, cli.verbose).await?;
+                if !run.succeeded() {
+                    anyhow::bail!("The {} hook failed", event.as_str());
                 }
-            },
+            }
 
-            Commands::Open {
-                repo,
-                app,
-                no_itermocil,
-            } => {
-                // Find repository using flexible lookup
-                let repo_info = workspace_manager.get_repository_flexible(&repo)
-                    .ok_or_else(|| anyhow::anyhow!("Repository '{}' not found. Try 'vibe launch' to see available repositories.", repo))?;
+            Commands::Logs { command } => {
+                handle_logs_command(command, &log_file_settings(cli.log_file.as_deref()))?;
+            }
+
+            Commands::Completions { .. }
+            | Commands::Complete { .. }
+            | Commands::ShellInit { .. }
+            | Commands::Doctor { .. }
+            | Commands::Cache { .. } => {
+                unreachable!("handled before workspace setup")
+            }
+        },
+    }
+
+    Ok(())
+}
+
+async fn handle_config_sync_command(
+    command: ConfigSyncCommands,
+    workspace_manager: &mut WorkspaceManager,
+) -> Result<()> {
+    use workspace::config_sync;
+
+    let report = match command {
+        ConfigSyncCommands::Setup { url, force } => {
+            let dir = config_sync::setup(&url, force).await?;
+            if output::is_json() {
+                CommandResult::success(serde_json::json!({ "path": dir })).emit()?;
+            } else {
+                println!(
+                    "{} Config sync set up in {}",
+                    style("✓").green().bold(),
+                    style(dir.display()).cyan()
+                );
+                println!(
+                    "   Run {} to see what differs, then {} or {}",
+                    style("vibe config sync status").cyan(),
+                    style("pull").cyan(),
+                    style("push").cyan()
+                );
+            }
+            return Ok(());
+        }
+        ConfigSyncCommands::Status => {
+            let status = config_sync::status(workspace_manager.config()).await?;
+            if output::is_json() {
+                CommandResult::success(&status).emit()?;
+            } else {
+                status.print();
+            }
+            return Ok(());
+        }
+        ConfigSyncCommands::Pull => workspace_manager.pull_shared_config().await?,
+        ConfigSyncCommands::Push => workspace_manager.push_shared_config().await?,
+    };
+
+    if output::is_json() {
+        CommandResult::success(&report).emit()?;
+    } else {
+        report.print();
+        if let Some(commit) = &report.commit {
+            println!("   Committed {}", style(commit).dim());
+        }
+    }
+    Ok(())
+}
+
+/// `vibe config repo readd-removed`: without names or `--all`, list what
+/// `vibe git scan --clean` removed
+async fn handle_readd_removed(
+    names: &[String],
+    all: bool,
+    workspace_manager: &mut WorkspaceManager,
+) -> Result<()> {
+    use workspace::removed_repos;
+
+    if names.is_empty() && !all {
+        let removed = removed_repos::load(&workspace::constants::get_removed_repos_path()).await?;
+        if output::is_json() {
+            return CommandResult::success(&removed).emit();
+        }
+        if removed.is_empty() {
+            println!("{} No removed repositories to re-add", style("ℹ️").blue());
+            return Ok(());
+        }
+        println!("{} Removed by `vibe git scan --clean`:", style("📋").blue());
+        for entry in &removed {
+            println!(
+                "  {} {} {} {}",
+                style(&entry.repository.name).cyan(),
+                style(entry.repository.path.display()).dim(),
+                style(entry.repository.url.as_deref().unwrap_or("(no remote URL)")).dim(),
+                style(entry.removed_at.format("%Y-%m-%d")).dim()
+            );
+        }
+        println!("Re-add with: vibe config repo readd-removed <name>... or --all");
+        return Ok(());
+    }
+
+    let readded = workspace_manager.readd_removed_repositories(names).await?;
+    if output::is_json() {
+        return CommandResult::success(serde_json::json!({ "readded": readded })).emit();
+    }
+    for name in &readded {
+        println!("  {} Re-added {}", style("✓").green(), style(name).cyan());
+    }
+    println!(
+        "{} Re-added {} repositories; `vibe git scan --restore` clones any still missing",
+        style("✓").green().bold(),
+        readded.len()
+    );
+    Ok(())
+}
+
+/// `vibe config repo exclude|include`: change which bulk operations skip a
+/// repository
+async fn handle_bulk_exclusion(
+    name: &str,
+    operations: &[workspace::BulkOperation],
+    exclude: bool,
+    workspace_manager: &mut WorkspaceManager,
+) -> Result<()> {
+    let excluded = workspace_manager
+        .set_bulk_exclusion(name, operations, exclude)
+        .await?;
+    if output::is_json() {
+        return CommandResult::success(serde_json::json!({
+            "repository": name,
+            "exclude": excluded,
+        }))
+        .emit();
+    }
+    if excluded.is_empty() {
+        println!(
+            "{} {} is included in every bulk operation",
+            style("✓").green(),
+            style(name).cyan()
+        );
+    } else {
+        let operations: Vec<&str> = excluded.iter().map(|op| op.as_str()).collect();
+        println!(
+            "{} {} is excluded from workspace-wide {}",
+            style("✓").green(),
+            style(name).cyan(),
+            operations.join(", ")
+        );
+    }
+    Ok(())
+}
+
+/// `vibe grep --open`: pick one of the matches and open it at its line in
+/// the configured editor
+async fn open_search_match(
+    results: &[workspace::search::RepoSearchResult],
+    enable_direnv: bool,
+) -> Result<()> {
+    let hits: Vec<_> = results
+        .iter()
+        .flat_map(|result| result.matches.iter().map(move |found| (result, found)))
+        .collect();
+    if hits.is_empty() || !output::is_interactive() || !std::io::stdin().is_terminal() {
+        return Ok(());
+    }
+
+    let labels: Vec<String> = hits
+        .iter()
+        .map(|(result, found)| {
+            format!(
+                "{}/{}:{}  {}",
+                result.repo,
+                found.file.display(),
+                found.line,
+                found.snippet
+            )
+        })
+        .collect();
+    println!();
+    let Some(choice) = ui::prompts::handle_prompt_result(
+        inquire::Select::new("Open match:", labels).raw_prompt(),
+    )?
+    else {
+        return Ok(());
+    };
+    let (result, found) = hits[choice.index];
+
+    let editor = worktree::WorktreeConfig::load_with_overrides()
+        .unwrap_or_default()
+        .default_editor;
+    apps::editor::open_at_location(
+        &editor,
+        &result.path,
+        &result.path.join(&found.file),
+        found.line,
+        found.column,
+        enable_direnv,
+    )
+    .await
+}
+
+/// `vibe cache clear`: delete the suggestion store, or with no scope every
+/// file in the cache directory
+async fn run_cache_clear(suggestions: bool) -> Result<()> {
+    use git::provider::vector_store::VectorStore;
+
+    if suggestions {
+        if VectorStore::clear(&VectorStore::default_path()).await? {
+            display_println!("{} Cleared remembered repositories", style("✓").green());
+        } else {
+            display_println!("{} No remembered repositories to clear", style("ℹ").blue());
+        }
+        return Ok(());
+    }
+
+    let cache_dir = workspace::constants::get_cache_dir();
+    match tokio::fs::remove_dir_all(&cache_dir).await {
+        Ok(()) => display_println!("{} Cleared {}", style("✓").green(), cache_dir.display()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            display_println!("{} Cache is already empty", style("ℹ").blue())
+        }
+        Err(e) => {
+            return Err(
+                anyhow::Error::new(e).context(format!("Failed to delete {}", cache_dir.display()))
+            )
+        }
+    }
+    Ok(())
+}
 
-                let repo_name = &repo_info.name;
+fn print_hook_run(payload: &workspace::events::Event, run: &workspace::events::HookRun) {
+    display_println!("{} {}", style("→").dim(), run.command);
+    display_println!(
+        "{}",
+        style(
+            serde_json::to_string_pretty(&payload.to_json(chrono::Utc::now())).unwrap_or_default()
+        )
+        .dim()
+    );
+    for stream in [&run.stdout, &run.stderr] {
+        if !stream.trim().is_empty() {
+            display_println!("{}", stream.trim_end());
+        }
+    }
+    if run.succeeded() {
+        display_println!(
+            "{} Exited with 0 after {}ms",
+            style("✓").green(),
+            run.duration_ms
+        );
+    } else if run.timed_out {
+        display_println!(
+            "{} Killed after {}ms: timed out",
+            style("✗").red(),
+            run.duration_ms
+        );
+    } else {
+        display_println!(
+            "{} Exited with {} after {}ms",
+            style("✗").red(),
+            run.exit_code
+                .map(|code| code.to_string())
+                .unwrap_or_else(|| "a signal".to_string()),
+            run.duration_ms
+        );
+    }
+}
 
-                if let Some(app_name) = app {
-                    // Open with specific app
-                    workspace_manager
-                        .open_repo_with_app_options(repo_name, &app_name, no_itermocil)
-                        .await?;
-                } else {
-                    // Open with preferred app or show available options
-                    let configured_apps = workspace_manager.list_apps_for_repo(repo_name)?;
+async fn run_cache_export(file: &Path) -> Result<()> {
+    let cache_dir = workspace::constants::get_cache_dir();
+    let summary = cache::snapshot::export_snapshot(&cache_dir, file).await?;
+    display_println!(
+        "{} Exported {} cached entries to {}",
+        style("✓").green(),
+        summary.total(),
+        file.display()
+    );
+    Ok(())
+}
 
-                    if configured_apps.len() == 1 {
-                        // Only one app configured, use it
-                        let (app_name, _) = &configured_apps[0];
-                        workspace_manager
-                            .open_repo_with_app_options(repo_name, app_name, no_itermocil)
-                            .await?;
-                    } else if configured_apps.len() > 1 {
-                        // Multiple apps configured, show configured options
-                        display_println!(
-                            "{} Multiple apps configured for '{}'. Please specify one:",
-                            style("🤔").yellow(),
-                            style(repo_name).cyan()
-                        );
-                        for (app_name, _template) in &configured_apps {
-                            display_println!(
-                                "  {} vibe open {} --app {}",
-                                style("→").dim(),
-                                repo_name,
-                                style(app_name).green()
-                            );
-                        }
-                    } else {
-                        // No apps configured, show available apps with basic opening
-                        display_println!(
-                            "{} No templates configured for '{}'. Available apps for basic opening:",
-                            style("ℹ️").blue(),
-                            style(repo_name).cyan()
-                        );
+async fn run_cache_import(file: &Path, if_empty: bool) -> Result<()> {
+    let cache_dir = workspace::constants::get_cache_dir();
+    match cache::snapshot::import_snapshot(&cache_dir, file, if_empty).await? {
+        Some(summary) => display_println!(
+            "{} Imported {} cached entries from {}",
+            style("✓").green(),
+            summary.total(),
+            file.display()
+        ),
+        None => display_println!(
+            "{} Cache already has entries; nothing imported",
+            style("ℹ").blue()
+        ),
+    }
+    Ok(())
+}
 
-                        let mut available_apps = Vec::new();
-                        for app in &["vscode", "cursor", "warp", "iterm2", "wezterm", "windsurf"] {
-                            if workspace_manager.is_app_available(app).await {
-                                available_apps.push(*app);
-                                display_println!(
-                                    "  {} vibe open {} --app {} {} (basic mode)",
-                                    style("→").dim(),
-                                    repo_name,
-                                    style(app).green(),
-                                    style("").dim()
-                                );
-                            }
-                        }
+/// The directory of a repository or monorepo subproject, found with the
+/// same flexible lookup as `vibe open`, or else of the worktree with task ID
+/// `target`
+async fn resolve_target_path(
+    workspace_manager: &WorkspaceManager,
+    target: &str,
+) -> Result<PathBuf> {
+    if let Some(repo) = workspace_manager.get_open_target(target) {
+        let path = repo_abs_path(workspace_manager.config(), &repo);
+        if !path.exists() {
+            anyhow::bail!(
+                "Repository '{}' is not cloned at {}",
+                repo.name,
+                path.display()
+            );
+        }
+        return Ok(std::path::absolute(&path)?);
+    }
 
-                        if available_apps.is_empty() {
-                            display_println!(
-                                "{} No supported apps found on this system",
-                                style("⚠️").yellow()
-                            );
-                        } else {
-                            display_println!(
-                                "\n{} Or configure templates for enhanced features: vibe apps configure {} <app>",
-                                style("💡").yellow(),
-                                style(repo_name).cyan()
-                            );
-                        }
-                    }
-                }
-            }
+    let config = worktree::WorktreeConfig::load_with_overrides().unwrap_or_default();
+    for repo in workspace_manager.list_repositories() {
+        let path = repo_abs_path(workspace_manager.config(), repo);
+        if !path.join(".git").exists() {
+            continue;
+        }
+        let Ok(worktree_manager) = worktree::WorktreeManager::new(path, Some(config.clone())).await
+        else {
+            continue;
+        };
+        if let Ok(Some(worktree)) = worktree_manager
+            .get_operations()
+            .find_worktree_by_task_id(target)
+            .await
+        {
+            return Ok(std::path::absolute(&worktree.path)?);
+        }
+    }
 
-            Commands::Launch => {
-                // Use the QuickLauncher for interactive selection
-                let cache_dir = workspace::constants::get_cache_dir();
-                let launcher = ui::quick_launcher::QuickLauncher::new(&cache_dir).await?;
-                launcher.launch(&mut workspace_manager).await?;
-            }
+    Err(CommandError::NotFound {
+        kind: "Repository or worktree",
+        name: target.to_string(),
+        hint: Some("Try 'vibe launch' to see available repositories.".to_string()),
+    }
+    .into())
+}
 
-            Commands::Clone {
-                url,
-                app,
-                no_configure,
-                no_open,
-                all,
-                exclude,
-                include,
-                force,
-            } => {
-                let git_config = git::GitConfig::default();
+/// Print the clap-generated completion script followed by the dynamic
+/// repository/app/worktree completion for shells that support it
+fn print_completions(shell: clap_complete::Shell) {
+    use clap::CommandFactory;
 
-                // Handle bulk cloning mode
-                if all {
-                    use git::bulk_clone::{BulkCloneCommand, BulkCloneOptions};
+    let mut command = Cli::command();
+    clap_complete::generate(shell, &mut command, "vibe", &mut std::io::stdout());
+    if let Some(dynamic) = utils::completions::dynamic_completion_script(shell) {
+        println!("{dynamic}");
+    }
+}
 
-                    let exclude_patterns = exclude
-                        .map(|s| s.split(',').map(|p| p.trim().to_string()).collect())
-                        .unwrap_or_default();
+async fn print_completion_candidates(kind: CompletionKind, config_path: &std::path::Path) {
+    use utils::completions;
 
-                    let include_patterns = include
-                        .map(|s| s.split(',').map(|p| p.trim().to_string()).collect())
-                        .unwrap_or_default();
+    let candidates = match kind {
+        CompletionKind::Repos => completions::complete_repositories(config_path).await,
+        CompletionKind::Apps => completions::SUPPORTED_APPS
+            .iter()
+            .map(|app| app.to_string())
+            .collect(),
+        CompletionKind::Worktrees => {
+            let cwd = std::env::current_dir().unwrap_or_default();
+            completions::complete_worktrees(config_path, &cwd)
+        }
+    };
 
-                    let options = BulkCloneOptions {
-                        exclude_patterns,
-                        include_patterns,
-                        skip_existing: true,
-                        custom_path: None,
-                        force,
-                    };
+    for candidate in candidates {
+        println!("{candidate}");
+    }
+}
 
-                    match BulkCloneCommand::execute(
-                        url,
-                        options,
-                        &mut workspace_manager,
-                        &git_config,
-                    )
-                    .await
-                    {
-                        Ok(result) => {
-                            display_println!(
-                                "{} Bulk clone completed: {} successful, {} failed",
-                                style("✅").green().bold(),
-                                result.total_cloned,
-                                result.failed.len()
-                            );
-                        }
-                        Err(e) => {
-                            display_println!("{} Bulk clone failed: {}", style("❌").red(), e);
-                            std::process::exit(1);
-                        }
-                    }
-                } else {
-                    // Handle single repository cloning with enhanced detection
-                    use git::clone::EnhancedCloneCommand;
+/// The log file location: `--log-file` if given, otherwise the logs directory
+fn log_file_settings(path: Option<&std::path::Path>) -> output::LogFileSettings {
+    path.map(output::LogFileSettings::from_path)
+        .unwrap_or_else(output::LogFileSettings::default_location)
+}
 
-                    match EnhancedCloneCommand::execute_with_detection(
-                        url,
-                        app,
-                        no_configure,
-                        no_open,
-                        &mut workspace_manager,
-                        &git_config,
-                    )
-                    .await
-                    {
-                        Ok(_) => {
-                            display_println!(
-                                "{} Repository operation completed successfully!",
-                                style("✓").green().bold()
-                            );
-                        }
-                        Err(e) => {
-                            display_println!("{} Clone operation failed: {}", style("❌").red(), e);
-                            std::process::exit(1);
-                        }
-                    }
-                }
+/// Handle log file subcommands
+fn handle_logs_command(command: LogsCommands, settings: &output::LogFileSettings) -> Result<()> {
+    let Some(path) = settings.latest_file() else {
+        display_println!(
+            "{} No log files in {}",
+            style("ℹ").yellow(),
+            style(settings.directory.display()).cyan()
+        );
+        display_println!(
+            "  Enable file logging with {} or set {} in your config",
+            style("--log-file <path>").green(),
+            style("preferences.logging.file_enabled: true").green()
+        );
+        return Ok(());
+    };
+
+    match command {
+        LogsCommands::Show { tail } => {
+            let content = std::fs::read_to_string(&path)?;
+            display_println!("{} {}", style("📄").blue(), style(path.display()).dim());
+            for line in output::tail_lines(&content, tail) {
+                data_println!("{line}");
             }
+        }
+        LogsCommands::Path => {
+            data_println!("{}", path.display());
+        }
+    }
 
-            Commands::Setup { skip } => {
-                if skip {
-                    let mut state = VibeState::load().unwrap_or_default();
-                    state.complete_setup_wizard();
-                    state.save()?;
-                    display_println!("{} Setup wizard skipped", style("ℹ️").blue());
-                } else {
-                    use ui::workflows::{execute_workflow, SetupWorkspaceWorkflow};
+    Ok(())
+}
 
-                    // Run setup workflow
-                    let workflow = Box::new(SetupWorkspaceWorkflow {
-                        auto_discover: true,
-                    });
+/// Report hook sync or remove results and fail when any repository failed
+fn finish_hook_results(results: &[git::hooks::RepoHookResult]) -> Result<()> {
+    let summary = BatchSummary {
+        total: results.len(),
+        failed: results
+            .iter()
+            .filter(|r| r.action == git::hooks::HookAction::Failed)
+            .count(),
+    };
+    if output::is_json() {
+        CommandResult::success(results).emit()?;
+    } else {
+        git::hooks::print_results(results);
+    }
+    summary.into_result()
+}
 
-                    execute_workflow(workflow, &mut workspace_manager).await?;
+/// Handle vibe:// URL scheme subcommands
+async fn handle_uri_command(
+    command: UriCommands,
+    mut workspace_manager: WorkspaceManager,
+) -> Result<()> {
+    match command {
+        UriCommands::Register => {
+            let executable = std::env::current_exe()?;
+            let status = uri::register_uri_scheme(&executable)?;
+
+            if let Some(path) = &status.handler_path {
+                display_println!(
+                    "{} Installed {}:// handler: {}",
+                    style("✓").green().bold(),
+                    uri::VIBE_SCHEME,
+                    style(path.display()).cyan()
+                );
+            }
 
-                    // Mark setup as complete
-                    let mut state = VibeState::load().unwrap_or_default();
-                    state.complete_setup_wizard();
-                    state.save()?;
-                }
+            if status.registered {
+                display_println!(
+                    "{} Operating system now routes {}:// URLs to {}",
+                    style("✓").green().bold(),
+                    uri::VIBE_SCHEME,
+                    style(executable.display()).cyan()
+                );
+            } else {
+                display_println!(
+                    "{} Registration was written but the operating system reports: {}",
+                    style("⚠️").yellow(),
+                    status.handler.as_deref().unwrap_or("no handler")
+                );
             }
 
-            Commands::Mcp { port, stdio: _ } => {
-                use std::sync::Arc;
-                use tokio::sync::Mutex;
+            display_println!(
+                "\n{} Test it with: {}",
+                style("💡").yellow(),
+                style(uri::open_uri_command("vibe://help")).cyan()
+            );
+        }
 
-                // Create shared workspace manager for MCP server
-                let shared_workspace = Arc::new(Mutex::new(workspace_manager));
+        UriCommands::Unregister => {
+            uri::unregister_uri_scheme()?;
+            display_println!(
+                "{} Removed {}:// URL handler",
+                style("✓").green().bold(),
+                uri::VIBE_SCHEME
+            );
+        }
 
-                if let Some(port_num) = port {
-                    // HTTP transport not implemented in this example
-                    // You would need to implement HTTP transport support
-                    display_eprintln!(
-                        "{} HTTP transport on port {} not yet implemented",
-                        style("⚠️").yellow(),
-                        port_num
-                    );
-                    display_eprintln!(
-                        "{} Use --stdio flag for stdio transport",
-                        style("💡").blue()
-                    );
-                    std::process::exit(1);
-                } else {
-                    // Run with stdio transport (default)
-                    display_eprintln!(
-                        "{} Starting vibe-workspace MCP server (stdio transport)...",
-                        style("🚀").green()
-                    );
+        UriCommands::Status => {
+            let status = uri::uri_scheme_status()?;
 
-                    let mcp_server = mcp::VibeMCPServer::new(shared_workspace);
-                    mcp_server.run().await?;
+            display_println!("{} {}:// URL scheme", style("🔗").blue(), uri::VIBE_SCHEME);
+            display_println!(
+                "  Status:  {}",
+                if status.registered {
+                    style("registered").green()
+                } else {
+                    style("not registered").yellow()
                 }
+            );
+            display_println!("  Handler: {}", status.handler.as_deref().unwrap_or("none"));
+            if let Some(path) = &status.handler_path {
+                display_println!(
+                    "  Path:    {}{}",
+                    path.display(),
+                    if path.exists() { "" } else { " (missing)" }
+                );
             }
 
-            Commands::Guide => {
-                print_getting_started_guide();
+            if !status.registered {
+                display_println!(
+                    "\n{} Run {} to register vibe",
+                    style("💡").yellow(),
+                    style("vibe uri register").cyan()
+                );
             }
-        },
+        }
+
+        UriCommands::Handle { uri: uri_str } => {
+            use std::sync::Arc;
+            use tokio::sync::Mutex;
+
+            workspace_manager.set_launch_source(LaunchSource::Uri);
+            let git_config = workspace_manager.git_config();
+            let shared_workspace = Arc::new(Mutex::new(workspace_manager));
+            let router =
+                uri::handler::UriRouter::with_default_handlers(shared_workspace, git_config);
+            if let Err(e) = router.handle_uri(&uri_str).await {
+                // Nobody sees stderr when the OS launched us, so surface the error
+                if !output::is_interactive() {
+                    let _ = utils::platform::show_message_dialog("vibe", &e.to_string());
+                }
+                return Err(e);
+            }
+        }
     }
 
     Ok(())
@@ -2557,7 +5812,7 @@ async fn handle_worktree_config_command(
                 // Load repository-specific configuration
                 let repo_path = workspace_manager
                     .get_repository(&repo_name)
-                    .ok_or_else(|| anyhow::anyhow!("Repository '{}' not found", repo_name))?
+                    .ok_or_else(|| CommandError::not_found("Repository", &repo_name))?
                     .path
                     .clone();
                 WorktreeManager::new_with_workspace_manager(workspace_manager, Some(repo_path))
@@ -2684,3 +5939,14 @@ async fn handle_worktree_config_command(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn test_cli_definition() {
+        Cli::command().debug_assert();
+    }
+}
@@ -6,6 +6,7 @@ use serde_json::{json, Value};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
+use crate::apps::render::{render_template, TEMPLATE_APPS};
 use crate::mcp::types::VibeToolHandler;
 use crate::workspace::WorkspaceManager;
 
@@ -212,6 +213,72 @@ impl VibeToolHandler for ListAppTemplatesTool {
     }
 }
 
+/// MCP tool for previewing a template rendered for a repository
+pub struct RenderTemplateTool;
+
+#[async_trait]
+impl VibeToolHandler for RenderTemplateTool {
+    fn tool_name(&self) -> &str {
+        "render_template"
+    }
+
+    fn tool_description(&self) -> &str {
+        "Render an app template for a repository without launching the app, returning the content, the file it would be written to, and whether that file exists"
+    }
+
+    fn input_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "app": {
+                    "type": "string",
+                    "description": "App the template belongs to",
+                    "enum": TEMPLATE_APPS
+                },
+                "repository": {
+                    "type": "string",
+                    "description": "Repository name, or <repo>:<subproject>"
+                },
+                "template": {
+                    "type": "string",
+                    "description": "Template to render (default: the repository's configured template, else the app's default)"
+                }
+            },
+            "required": ["app", "repository"]
+        })
+    }
+
+    async fn handle_call(
+        &self,
+        args: Value,
+        workspace: Arc<Mutex<WorkspaceManager>>,
+    ) -> Result<Value> {
+        let app = args
+            .get("app")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("App name is required"))?;
+
+        let repository = args
+            .get("repository")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Repository name is required"))?;
+
+        let template = args.get("template").and_then(|v| v.as_str());
+
+        let ws = workspace.lock().await;
+        let rendered = render_template(
+            ws.get_config(),
+            ws.get_template_manager(),
+            app,
+            template,
+            repository,
+        )
+        .await?;
+
+        Ok(serde_json::to_value(rendered)?)
+    }
+}
+
 /// MCP tool for creating app templates
 pub struct CreateAppTemplateTool;
 
@@ -8,7 +8,10 @@ use std::sync::Arc;
 use tokio::sync::Mutex;
 
 use crate::mcp::types::VibeToolHandler;
-use crate::workspace::WorkspaceManager;
+use crate::output::redact;
+use crate::workspace::config::WorkspaceConfig;
+use crate::workspace::config_loader::ConfigLoadError;
+use crate::workspace::{repo_abs_path, WorkspaceManager};
 
 /// MCP tool for initializing a new workspace
 pub struct InitWorkspaceTool;
@@ -83,7 +86,7 @@ impl VibeToolHandler for ShowConfigTool {
     }
 
     fn tool_description(&self) -> &str {
-        "Show current workspace configuration"
+        "Show current workspace configuration, with tokens and other secrets masked"
     }
 
     fn input_schema(&self) -> Value {
@@ -122,7 +125,7 @@ impl VibeToolHandler for ShowConfigTool {
 
         // Instead of calling show_config which prints to stdout,
         // we'll get the config data directly and return it
-        let config_data = match section {
+        let mut config_data = match section {
             Some("workspace") => json!({
                 "workspace": {
                     "name": &ws.config().workspace.name,
@@ -153,6 +156,8 @@ impl VibeToolHandler for ShowConfigTool {
             }
         };
 
+        redact::redact_value(&mut config_data);
+
         Ok(json!({
             "format": format,
             "configuration": config_data
@@ -227,7 +232,7 @@ impl VibeToolHandler for ValidateConfigTool {
     }
 
     fn tool_description(&self) -> &str {
-        "Validate workspace configuration"
+        "Validate workspace configuration, listing every bad section or repository entry in config.yaml with its field and line"
     }
 
     fn input_schema(&self) -> Value {
@@ -280,6 +285,23 @@ impl VibeToolHandler for ValidateConfigTool {
         let mut issues = Vec::new();
         let mut warnings = Vec::new();
 
+        // The server keeps the last config that loaded; check the file as it
+        // is on disk now
+        let config_errors = match WorkspaceConfig::load_from_file(ws.get_config_path()).await {
+            Ok(_) => Vec::new(),
+            Err(e) => match e
+                .chain()
+                .find_map(|cause| cause.downcast_ref::<ConfigLoadError>())
+            {
+                Some(load_error) => load_error.errors.clone(),
+                None => {
+                    issues.push(format!("{e:#}"));
+                    Vec::new()
+                }
+            },
+        };
+        issues.extend(config_errors.iter().map(ToString::to_string));
+
         // Basic config validation
         if ws.config().repositories.is_empty() {
             warnings.push("No repositories configured".to_string());
@@ -287,7 +309,7 @@ impl VibeToolHandler for ValidateConfigTool {
 
         if check_paths {
             for repo in &ws.config().repositories {
-                let repo_path = ws.get_workspace_root().join(&repo.path);
+                let repo_path = repo_abs_path(ws.config(), repo);
                 if !repo_path.exists() {
                     issues.push(format!("Repository path not found: {}", repo.name));
                 }
@@ -301,6 +323,7 @@ impl VibeToolHandler for ValidateConfigTool {
         Ok(json!({
             "valid": is_valid,
             "issues": issues,
+            "config_errors": config_errors,
             "warnings": warnings,
             "checks_performed": {
                 "basic": true,
@@ -380,6 +403,11 @@ impl VibeToolHandler for BackupConfigTool {
                 "name": {
                     "type": "string",
                     "description": "Custom backup name (default: timestamp)"
+                },
+                "encrypt": {
+                    "type": "boolean",
+                    "description": "Encrypt the archive with the passphrase in VIBE_BACKUP_PASSPHRASE, producing a .tgz.age file",
+                    "default": false
                 }
             },
             "required": []
@@ -398,8 +426,22 @@ impl VibeToolHandler for BackupConfigTool {
 
         let name = args.get("name").and_then(|v| v.as_str()).map(String::from);
 
-        let ws = workspace.lock().await;
-        let backup_path = ws.create_backup(output, name).await?;
+        let encrypt = args
+            .get("encrypt")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        // Without a terminal this only reads VIBE_BACKUP_PASSPHRASE
+        let passphrase = encrypt
+            .then(crate::workspace::backup_crypto::passphrase_for_encryption)
+            .transpose()?;
+
+        // Only hold the workspace while gathering files, so other tools stay
+        // responsive while the archive is written
+        let mut plan = workspace.lock().await.plan_backup(output, name).await?;
+        if let Some(passphrase) = passphrase {
+            plan = plan.encrypted(passphrase);
+        }
+        let backup_path = plan.write().await?;
 
         Ok(json!({
             "status": "success",
@@ -427,7 +469,7 @@ impl VibeToolHandler for RestoreConfigTool {
             "properties": {
                 "backup": {
                     "type": "string",
-                    "description": "Backup file to restore from"
+                    "description": "Backup file to restore from. Encrypted (.tgz.age) backups need VIBE_BACKUP_PASSPHRASE"
                 },
                 "force": {
                     "type": "boolean",
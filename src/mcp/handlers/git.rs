@@ -5,10 +5,11 @@ use async_trait::async_trait;
 use serde_json::{json, Value};
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::Mutex;
 
 use crate::mcp::types::{GitStatusInfo, VibeToolHandler};
-use crate::workspace::{operations::get_git_status, WorkspaceManager};
+use crate::workspace::{status_snapshot::StatusScope, CleanMode, WorkspaceManager};
 
 /// MCP tool for checking git status across repositories
 pub struct GitStatusTool;
@@ -41,6 +42,12 @@ impl VibeToolHandler for GitStatusTool {
                 "group": {
                     "type": "string",
                     "description": "Filter by repository group"
+                },
+                "max_age_seconds": {
+                    "type": "integer",
+                    "description": "Reuse cached statuses up to this many seconds old instead of re-reading them",
+                    "minimum": 0,
+                    "default": 0
                 }
             },
             "required": []
@@ -65,51 +72,59 @@ impl VibeToolHandler for GitStatusTool {
 
         let group = args.get("group").and_then(|v| v.as_str());
 
+        let max_age = args
+            .get("max_age_seconds")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0);
+
         // Get workspace manager
         let ws = workspace.lock().await;
 
-        // Get all repositories, optionally filtered by group
-        let repos = if let Some(group_name) = group {
-            ws.config().get_repositories_in_group(group_name)
-        } else {
-            ws.list_repositories().iter().collect::<Vec<_>>()
-        };
+        let snapshot = ws
+            .refresh_statuses(StatusScope::Targets(group), Duration::from_secs(max_age))
+            .await;
 
         // Collect status information
         let mut statuses = Vec::new();
-        let workspace_root = ws.get_workspace_root();
-
-        for repo in repos {
-            // Get git status for this repository
-            let repo_path = workspace_root.join(&repo.path);
-            let status = get_git_status(&repo_path).await?;
-
+        for status in snapshot.statuses {
             // Skip clean repositories if dirty_only is set
             if dirty_only && status.clean {
                 continue;
             }
+            let path = ws
+                .config()
+                .get_repository(&status.repository_name)
+                .map(|repo| repo.path.to_string_lossy().to_string())
+                .unwrap_or(status.path);
 
             // Create status info
             let status_info = GitStatusInfo {
-                repository: repo.name.clone(),
-                path: repo.path.to_string_lossy().to_string(),
+                repository: status.repository_name,
+                path,
                 is_dirty: !status.clean,
                 has_staged_changes: status.staged > 0,
                 has_unstaged_changes: status.unstaged > 0,
                 has_untracked_files: status.untracked > 0,
                 ahead: status.ahead,
                 behind: status.behind,
+                compare_ref: status.compare_ref,
             };
 
             statuses.push(status_info);
         }
+        let failures: Vec<Value> = snapshot
+            .failures
+            .iter()
+            .map(|(repository, error)| json!({ "repository": repository, "error": error }))
+            .collect();
 
         // Format the response based on requested format
         match format {
             "json" => Ok(json!({
                 "repositories": statuses,
                 "total": statuses.len(),
-                "dirty_count": statuses.iter().filter(|s| s.is_dirty).count()
+                "dirty_count": statuses.iter().filter(|s| s.is_dirty).count(),
+                "failures": failures
             })),
 
             "table" | "compact" => {
@@ -148,7 +163,8 @@ impl VibeToolHandler for GitStatusTool {
                 Ok(json!({
                     "output": output.join("\n"),
                     "total": statuses.len(),
-                    "dirty_count": statuses.iter().filter(|s| s.is_dirty).count()
+                    "dirty_count": statuses.iter().filter(|s| s.is_dirty).count(),
+                    "failures": failures
                 }))
             }
 
@@ -196,9 +212,19 @@ impl VibeToolHandler for ScanReposTool {
                     "description": "Re-clone missing repositories from config",
                     "default": false
                 },
+                "restore_only": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Re-clone only these missing repositories (implies restore)"
+                },
                 "clean": {
                     "type": "boolean",
-                    "description": "Remove missing repositories from config",
+                    "description": "Remove missing repositories from config, except those on unmounted volumes. Removed entries are saved for `vibe config repo readd-removed`.",
+                    "default": false
+                },
+                "subprojects": {
+                    "type": "boolean",
+                    "description": "Record pnpm and Cargo workspace packages as subprojects of repositories that have none",
                     "default": false
                 }
             },
@@ -225,10 +251,26 @@ impl VibeToolHandler for ScanReposTool {
             .and_then(|v| v.as_bool())
             .unwrap_or(false);
 
+        let restore_only: Vec<String> = args
+            .get("restore_only")
+            .and_then(|v| v.as_array())
+            .map(|names| {
+                names
+                    .iter()
+                    .filter_map(|n| n.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default();
+
         let clean = args.get("clean").and_then(|v| v.as_bool()).unwrap_or(false);
 
+        let subprojects = args
+            .get("subprojects")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
         // Validate conflicting flags
-        if restore && clean {
+        if (restore || !restore_only.is_empty()) && clean {
             return Ok(json!({
                 "status": "error",
                 "message": "Cannot use 'restore' and 'clean' together"
@@ -238,8 +280,25 @@ impl VibeToolHandler for ScanReposTool {
         let mut ws = workspace.lock().await;
         let scan_path = path.unwrap_or_else(|| ws.get_workspace_root().clone());
 
-        ws.scan_repositories(&scan_path, depth, import, restore, clean)
+        let report = ws
+            .scan_repositories(
+                &scan_path,
+                depth,
+                import,
+                restore,
+                restore_only,
+                if clean {
+                    CleanMode::Yes
+                } else {
+                    CleanMode::Off
+                },
+            )
             .await?;
+        let detected = if subprojects {
+            ws.detect_subprojects().await?
+        } else {
+            Vec::new()
+        };
 
         Ok(json!({
             "status": "success",
@@ -248,8 +307,11 @@ impl VibeToolHandler for ScanReposTool {
             "operations": {
                 "import": import,
                 "restore": restore,
-                "clean": clean
-            }
+                "clean": clean,
+                "subprojects": subprojects
+            },
+            "sync": report.sync,
+            "subprojects": detected
         }))
     }
 }
@@ -389,8 +451,8 @@ impl VibeToolHandler for CloneRepoTool {
             .and_then(|v| v.as_bool())
             .unwrap_or(false);
 
-        let git_config = crate::git::GitConfig::default();
         let mut ws = workspace.lock().await;
+        let git_config = ws.git_config();
         let cloned_path = crate::git::CloneCommand::execute(
             url.to_string(),
             path,
@@ -476,6 +538,70 @@ impl VibeToolHandler for ExecGitCommandTool {
     }
 }
 
+/// MCP tool for running one read-only git command in a single repository
+pub struct RepoGitQueryTool;
+
+#[async_trait]
+impl VibeToolHandler for RepoGitQueryTool {
+    fn tool_name(&self) -> &str {
+        "repo_git_query"
+    }
+
+    fn tool_description(&self) -> &str {
+        "Run a read-only git command (log, show, diff, blame, rev-parse, branch --list) in one repository, with capped output"
+    }
+
+    fn input_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "repo": {
+                    "type": "string",
+                    "description": "Repository name"
+                },
+                "subcommand": {
+                    "type": "string",
+                    "description": "Git subcommand: log, show, diff, blame, rev-parse, branch, or one added under safety.git_query_allow"
+                },
+                "args": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Arguments, one per item; they are not passed through a shell",
+                    "default": []
+                }
+            },
+            "required": ["repo", "subcommand"]
+        })
+    }
+
+    async fn handle_call(
+        &self,
+        args: Value,
+        workspace: Arc<Mutex<WorkspaceManager>>,
+    ) -> Result<Value> {
+        let repo = args
+            .get("repo")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Repository name is required"))?;
+        let subcommand = args
+            .get("subcommand")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Git subcommand is required"))?;
+        let arguments = match args.get("args") {
+            None | Some(Value::Null) => Vec::new(),
+            Some(value) => serde_json::from_value::<Vec<String>>(value.clone())
+                .map_err(|_| anyhow::anyhow!("args must be an array of strings"))?,
+        };
+
+        let ws = workspace.lock().await;
+        let output = ws.git_query(repo, subcommand, &arguments).await?;
+
+        let mut result = serde_json::to_value(&output)?;
+        result["status"] = json!("success");
+        Ok(result)
+    }
+}
+
 /// MCP tool for resetting repository configuration
 pub struct ResetGitConfigTool;
 
@@ -4,6 +4,7 @@ pub mod apps;
 pub mod config;
 pub mod git;
 pub mod repos;
+pub mod task;
 pub mod validation;
 pub mod worktree;
 pub mod worktree_help;
@@ -19,18 +20,24 @@ pub use config::{
 // App management handlers
 pub use apps::{
     ConfigureAppTool, CreateAppTemplateTool, DeleteAppTemplateTool, ListAppTemplatesTool,
-    ShowAppsTool, UpdateDefaultTemplatesTool,
+    RenderTemplateTool, ShowAppsTool, UpdateDefaultTemplatesTool,
 };
 
 // Repository operation handlers
-pub use repos::{CloneTool, CreateRepositoryTool, LaunchRepoTool, OpenRepoTool};
+pub use repos::{
+    CloneTool, CreateRepositoryTool, DescribeRepoTool, FileTreeTool, LaunchRepoTool, OpenRepoTool,
+    RepoHealthTool,
+};
 
 // Git operation handlers
 pub use git::{
-    CloneRepoTool, ExecGitCommandTool, GitStatusTool, ResetGitConfigTool, ScanReposTool,
-    SyncReposTool,
+    CloneRepoTool, ExecGitCommandTool, GitStatusTool, RepoGitQueryTool, ResetGitConfigTool,
+    ScanReposTool, SyncReposTool,
 };
 
+// Task workflow handlers
+pub use task::{TaskDoneTool, TaskFinishTool, TaskStartTool};
+
 // Validation handler
 pub use validation::ValidateMcpInterfaceTool;
 
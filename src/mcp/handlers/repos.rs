@@ -9,7 +9,7 @@ use tokio::sync::Mutex;
 use crate::mcp::types::VibeToolHandler;
 use crate::ui::state::VibeState;
 use crate::ui::workflows::{execute_workflow, CloneWorkflow};
-use crate::workspace::WorkspaceManager;
+use crate::workspace::{repo_abs_path, WorkspaceManager};
 
 /// MCP tool for launching a repository
 pub struct LaunchRepoTool;
@@ -71,6 +71,171 @@ impl VibeToolHandler for LaunchRepoTool {
     }
 }
 
+/// MCP tool for describing a repository and its toolchain requirements
+pub struct DescribeRepoTool;
+
+#[async_trait]
+impl VibeToolHandler for DescribeRepoTool {
+    fn tool_name(&self) -> &str {
+        "describe_repo"
+    }
+
+    fn tool_description(&self) -> &str {
+        "Describe a repository: path, remote, configured apps, detected project types, and pinned toolchain versions"
+    }
+
+    fn input_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "repo": {
+                    "type": "string",
+                    "description": "Repository name"
+                }
+            },
+            "required": ["repo"]
+        })
+    }
+
+    async fn handle_call(
+        &self,
+        args: Value,
+        workspace: Arc<Mutex<WorkspaceManager>>,
+    ) -> Result<Value> {
+        let repo = args
+            .get("repo")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Repository name is required"))?;
+
+        let ws = workspace.lock().await;
+        let info = ws
+            .get_repository_flexible(repo)
+            .ok_or_else(|| crate::output::exit::CommandError::not_found("Repository", repo))?
+            .clone();
+        let environment = ws.project_env(&info.name).await?;
+        let project_types = ws
+            .project_types()
+            .await
+            .remove(&info.name)
+            .unwrap_or_default();
+        let mut apps: Vec<&String> = info.apps.keys().collect();
+        apps.sort();
+
+        Ok(json!({
+            "status": "success",
+            "repository": info.name,
+            "path": repo_abs_path(ws.config(), &info).to_string_lossy(),
+            "url": info.url,
+            "branch": info.branch,
+            "apps": apps,
+            "project_types": project_types,
+            "environment": environment
+        }))
+    }
+}
+
+/// MCP tool for listing the files of a repository or monorepo subproject
+pub struct FileTreeTool;
+
+#[async_trait]
+impl VibeToolHandler for FileTreeTool {
+    fn tool_name(&self) -> &str {
+        "file_tree"
+    }
+
+    fn tool_description(&self) -> &str {
+        "List the files of a repository, or of one monorepo subproject, as an indented tree that skips ignored files"
+    }
+
+    fn input_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "repo": {
+                    "type": "string",
+                    "description": "Repository name, or <repo>:<subproject> for a package of a monorepo"
+                },
+                "depth": {
+                    "type": "integer",
+                    "description": "Directory levels to list; deeper directories show a file count",
+                    "default": crate::workspace::file_tree::DEFAULT_DEPTH
+                }
+            },
+            "required": ["repo"]
+        })
+    }
+
+    async fn handle_call(
+        &self,
+        args: Value,
+        workspace: Arc<Mutex<WorkspaceManager>>,
+    ) -> Result<Value> {
+        let repo = args
+            .get("repo")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Repository name is required"))?;
+        let depth = args
+            .get("depth")
+            .and_then(|v| v.as_u64())
+            .map(|depth| depth as usize)
+            .unwrap_or(crate::workspace::file_tree::DEFAULT_DEPTH);
+
+        let ws = workspace.lock().await;
+        let tree = ws.file_tree(repo, depth).await?;
+
+        Ok(json!({
+            "status": "success",
+            "repository": tree.repository,
+            "path": tree.path.to_string_lossy(),
+            "files": tree.files,
+            "tree": tree.tree
+        }))
+    }
+}
+
+/// MCP tool for scoring repository health
+pub struct RepoHealthTool;
+
+#[async_trait]
+impl VibeToolHandler for RepoHealthTool {
+    fn tool_name(&self) -> &str {
+        "repo_health"
+    }
+
+    fn tool_description(&self) -> &str {
+        "Score repository health from dirtiness, unpushed commits, upstream distance, stale branches, missing remotes, and inactivity"
+    }
+
+    fn input_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "group": {
+                    "type": "string",
+                    "description": "Only score repositories in this group"
+                }
+            }
+        })
+    }
+
+    async fn handle_call(
+        &self,
+        args: Value,
+        workspace: Arc<Mutex<WorkspaceManager>>,
+    ) -> Result<Value> {
+        let group = args.get("group").and_then(|v| v.as_str());
+
+        let ws = workspace.lock().await;
+        let (report, warnings) = ws.collect_health(group).await;
+
+        Ok(json!({
+            "status": "success",
+            "report": report,
+            "warnings": warnings
+        }))
+    }
+}
+
 /// MCP tool for opening a repository
 pub struct OpenRepoTool;
 
@@ -90,12 +255,12 @@ impl VibeToolHandler for OpenRepoTool {
             "properties": {
                 "repo": {
                     "type": "string",
-                    "description": "Repository name"
+                    "description": "Repository name, or <repo>:<subproject> for a package of a monorepo"
                 },
                 "app": {
                     "type": "string",
-                    "description": "App to open with",
-                    "enum": ["warp", "iterm2", "vscode", "wezterm", "cursor", "windsurf"]
+                    "description": "App to open with; finder/files open the file manager and browser opens the remote's web page",
+                    "enum": ["warp", "iterm2", "vscode", "wezterm", "cursor", "windsurf", "finder", "files", "browser"]
                 },
                 "no_itermocil": {
                     "type": "boolean",
@@ -254,6 +419,9 @@ impl VibeToolHandler for CloneTool {
             let workflow = Box::new(CloneWorkflow {
                 url: url.to_string(),
                 app,
+                group: None,
+                skip_configure: false,
+                on_collision: None,
             });
 
             let mut ws = workspace.lock().await;
@@ -265,8 +433,8 @@ impl VibeToolHandler for CloneTool {
             }))
         } else {
             // Just clone without workflow
-            let git_config = crate::git::GitConfig::default();
             let mut ws = workspace.lock().await;
+            let git_config = ws.git_config();
             let cloned_path = crate::git::CloneCommand::execute(
                 url.to_string(),
                 None,
@@ -0,0 +1,211 @@
+//! MCP tool handlers for the `vibe task` phases
+//!
+//! Each tool runs one phase of [`crate::worktree::task`] and returns what the
+//! CLI would emit with `--json`. `task_start` never opens an app; the client
+//! gets the worktree path instead.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+use crate::mcp::types::VibeToolHandler;
+use crate::workspace::WorkspaceManager;
+use crate::worktree::task::{self, DoneOptions, FinishOptions, StartOptions};
+
+fn required_str<'a>(args: &'a Value, name: &str) -> Result<&'a str> {
+    args[name]
+        .as_str()
+        .ok_or_else(|| anyhow::anyhow!("{name} is required"))
+}
+
+fn optional_string(args: &Value, name: &str) -> Option<String> {
+    args[name].as_str().map(str::to_string)
+}
+
+/// MCP tool for `vibe task start`
+pub struct TaskStartTool;
+
+#[async_trait]
+impl VibeToolHandler for TaskStartTool {
+    fn tool_name(&self) -> &str {
+        "task_start"
+    }
+
+    fn tool_description(&self) -> &str {
+        "Start a task: create its worktree in a repository, or reuse the existing one, and return the worktree path"
+    }
+
+    fn input_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "repo": {
+                    "type": "string",
+                    "description": "Repository to work in"
+                },
+                "task_id": {
+                    "type": "string",
+                    "description": "Task identifier, used for the branch name"
+                },
+                "base_branch": {
+                    "type": "string",
+                    "description": "Branch to start from (defaults to the current branch)"
+                },
+                "description": {
+                    "type": "string",
+                    "description": "What the task is about; the default pull request title"
+                },
+                "link": {
+                    "type": "string",
+                    "description": "Ticket or issue URL for the task"
+                }
+            },
+            "required": ["repo", "task_id"]
+        })
+    }
+
+    async fn handle_call(
+        &self,
+        args: Value,
+        workspace: Arc<Mutex<WorkspaceManager>>,
+    ) -> Result<Value> {
+        let options = StartOptions {
+            repo: required_str(&args, "repo")?.to_string(),
+            task_id: required_str(&args, "task_id")?.to_string(),
+            base_branch: optional_string(&args, "base_branch"),
+            description: optional_string(&args, "description"),
+            link: optional_string(&args, "link"),
+            open: false,
+            editor: None,
+        };
+
+        let ws = workspace.lock().await;
+        let started = task::start(&ws, options).await?;
+        Ok(json!({
+            "status": "success",
+            "task": started
+        }))
+    }
+}
+
+/// MCP tool for `vibe task finish`
+pub struct TaskFinishTool;
+
+#[async_trait]
+impl VibeToolHandler for TaskFinishTool {
+    fn tool_name(&self) -> &str {
+        "task_finish"
+    }
+
+    fn tool_description(&self) -> &str {
+        "Finish a task: push its branch, open a pull request with gh unless one exists, and record the pull request URL on the worktree"
+    }
+
+    fn input_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "task_id": {
+                    "type": "string",
+                    "description": "Task identifier"
+                },
+                "repo": {
+                    "type": "string",
+                    "description": "Repository of the task, when several have a task by that id"
+                },
+                "title": {
+                    "type": "string",
+                    "description": "Pull request title instead of the configured template"
+                },
+                "body": {
+                    "type": "string",
+                    "description": "Pull request body instead of the configured template"
+                },
+                "draft": {
+                    "type": "boolean",
+                    "description": "Open the pull request as a draft",
+                    "default": false
+                }
+            },
+            "required": ["task_id"]
+        })
+    }
+
+    async fn handle_call(
+        &self,
+        args: Value,
+        workspace: Arc<Mutex<WorkspaceManager>>,
+    ) -> Result<Value> {
+        let options = FinishOptions {
+            task_id: required_str(&args, "task_id")?.to_string(),
+            repo: optional_string(&args, "repo"),
+            title: optional_string(&args, "title"),
+            body: optional_string(&args, "body"),
+            draft: args["draft"].as_bool().unwrap_or(false),
+        };
+
+        let ws = workspace.lock().await;
+        let finished = task::finish(&ws, options).await?;
+        Ok(json!({
+            "status": "success",
+            "task": finished
+        }))
+    }
+}
+
+/// MCP tool for `vibe task done`
+pub struct TaskDoneTool;
+
+#[async_trait]
+impl VibeToolHandler for TaskDoneTool {
+    fn tool_name(&self) -> &str {
+        "task_done"
+    }
+
+    fn tool_description(&self) -> &str {
+        "Wrap up a task: verify its branch merged, then remove its worktree and branch"
+    }
+
+    fn input_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "task_id": {
+                    "type": "string",
+                    "description": "Task identifier"
+                },
+                "repo": {
+                    "type": "string",
+                    "description": "Repository of the task, when several have a task by that id"
+                },
+                "force": {
+                    "type": "boolean",
+                    "description": "Clean up even if the branch isn't merged, discarding uncommitted changes",
+                    "default": false
+                }
+            },
+            "required": ["task_id"]
+        })
+    }
+
+    async fn handle_call(
+        &self,
+        args: Value,
+        workspace: Arc<Mutex<WorkspaceManager>>,
+    ) -> Result<Value> {
+        let options = DoneOptions {
+            task_id: required_str(&args, "task_id")?.to_string(),
+            repo: optional_string(&args, "repo"),
+            force: args["force"].as_bool().unwrap_or(false),
+        };
+
+        let ws = workspace.lock().await;
+        let done = task::done(&ws, options).await?;
+        Ok(json!({
+            "status": "success",
+            "task": done
+        }))
+    }
+}
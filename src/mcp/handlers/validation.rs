@@ -62,6 +62,7 @@ impl VibeToolHandler for ValidateMcpInterfaceTool {
             ("list_app_templates", "vibe apps template list"),
             ("create_app_template", "vibe apps template create"),
             ("delete_app_template", "vibe apps template delete"),
+            ("render_template", "vibe apps template render"),
             (
                 "update_default_templates",
                 "vibe apps template update-defaults",
@@ -70,12 +71,15 @@ impl VibeToolHandler for ValidateMcpInterfaceTool {
             ("launch_repo", "vibe launch"),
             ("open_repo", "vibe open"),
             ("clone", "vibe clone"),
+            ("describe_repo", "vibe repo env"),
+            ("repo_health", "vibe repo health"),
             // Git operation tools
             ("vibe_git_status", "vibe git status"),
             ("scan_repos", "vibe git scan"),
             ("sync_repos", "vibe git sync"),
             ("clone_repo", "vibe git clone"),
             ("exec_git_command", "vibe git exec"),
+            ("repo_git_query", "vibe git exec --repos <repo>"),
             ("reset_git_config", "vibe git reset"),
         ];
 
@@ -101,17 +105,22 @@ impl VibeToolHandler for ValidateMcpInterfaceTool {
             .with_tool(Arc::new(handlers::ListAppTemplatesTool))
             .with_tool(Arc::new(handlers::CreateAppTemplateTool))
             .with_tool(Arc::new(handlers::DeleteAppTemplateTool))
+            .with_tool(Arc::new(handlers::RenderTemplateTool))
             .with_tool(Arc::new(handlers::UpdateDefaultTemplatesTool))
             // Repository operation tools
             .with_tool(Arc::new(handlers::LaunchRepoTool))
             .with_tool(Arc::new(handlers::OpenRepoTool))
             .with_tool(Arc::new(handlers::CloneTool))
+            .with_tool(Arc::new(handlers::DescribeRepoTool))
+            .with_tool(Arc::new(handlers::RepoHealthTool))
+            .with_tool(Arc::new(handlers::FileTreeTool))
             // Git operation tools
             .with_tool(Arc::new(handlers::GitStatusTool))
             .with_tool(Arc::new(handlers::ScanReposTool))
             .with_tool(Arc::new(handlers::SyncReposTool))
             .with_tool(Arc::new(handlers::CloneRepoTool))
             .with_tool(Arc::new(handlers::ExecGitCommandTool))
+            .with_tool(Arc::new(handlers::RepoGitQueryTool))
             .with_tool(Arc::new(handlers::ResetGitConfigTool))
             .build();
 
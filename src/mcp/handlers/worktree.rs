@@ -12,7 +12,9 @@ use tokio::sync::Mutex;
 use tracing::{debug, warn};
 
 use crate::mcp::types::VibeToolHandler;
+use crate::utils::direnv;
 use crate::workspace::WorkspaceManager;
+use crate::worktree::metadata::{parse_duration, WorktreeMetadata};
 use crate::worktree::{
     cleanup::WorktreeCleanup, status::StatusSeverity, CleanupOptions, CleanupStrategy,
     CreateOptions, WorktreeManager,
@@ -52,6 +54,18 @@ impl VibeToolHandler for CreateWorktreeTool {
                 "custom_path": {
                     "type": "string",
                     "description": "Custom path for the worktree (overrides default path calculation)"
+                },
+                "description": {
+                    "type": "string",
+                    "description": "What the task is about, shown when listing worktrees"
+                },
+                "link": {
+                    "type": "string",
+                    "description": "Ticket, issue, or pull request URL for the task"
+                },
+                "expires": {
+                    "type": "string",
+                    "description": "Time-box the worktree, e.g. '3d' or '12h'; past the deadline it is flagged for cleanup"
                 }
             },
             "required": ["task_id"]
@@ -70,6 +84,13 @@ impl VibeToolHandler for CreateWorktreeTool {
         let base_branch = args["base_branch"].as_str().map(|s| s.to_string());
         let force = args["force"].as_bool().unwrap_or(false);
         let custom_path = args["custom_path"].as_str().map(PathBuf::from);
+        let mut metadata = WorktreeMetadata::new(
+            args["description"].as_str().map(str::to_string),
+            args["link"].as_str().map(str::to_string),
+        )?;
+        if let Some(expires) = args["expires"].as_str() {
+            metadata.expires_at = Some(chrono::Utc::now() + parse_duration(expires)?);
+        }
 
         // Get current directory to determine repository
         let current_dir = std::env::current_dir()?;
@@ -81,28 +102,52 @@ impl VibeToolHandler for CreateWorktreeTool {
             Some(current_dir.clone()),
         )
         .await?;
+        let auto_allow_direnv = workspace_guard.is_direnv_auto_allow_enabled();
         drop(workspace_guard);
 
         let options = CreateOptions {
             task_id: task_id.to_string(),
             base_branch,
             force,
+            include_protected: false,
             custom_path,
+            metadata,
         };
 
         debug!("Creating worktree for task: {}", task_id);
 
         match worktree_manager.create_worktree_with_options(options).await {
-            Ok(worktree_info) => Ok(json!({
-                "success": true,
-                "worktree": {
-                    "path": worktree_info.path,
-                    "branch": worktree_info.branch,
-                    "head": worktree_info.head,
-                    "age_seconds": worktree_info.age.as_secs()
-                },
-                "message": format!("Created worktree for task '{}' at {}", task_id, worktree_info.path.display())
-            })),
+            Ok(worktree_info) => {
+                let direnv_allowed = match direnv::allow_new_worktree(
+                    &worktree_info.path,
+                    auto_allow_direnv,
+                )
+                .await
+                {
+                    Ok(allowed) => allowed,
+                    Err(e) => {
+                        warn!("Failed to allow .envrc in new worktree: {}", e);
+                        false
+                    }
+                };
+                Ok(json!({
+                    "success": true,
+                    "direnv_allowed": direnv_allowed,
+                    "worktree": {
+                        "path": worktree_info.path,
+                        "branch": worktree_info.branch,
+                        "head": worktree_info.head,
+                        "age_seconds": worktree_info.age.as_secs(),
+                        "description": worktree_info.metadata.description,
+                        "link": worktree_info.metadata.link,
+                        "base_branch": worktree_info.metadata.base_branch,
+                        "base_commit": worktree_info.metadata.base_commit,
+                        "base_refresh": worktree_info.base_refresh,
+                        "expires_at": worktree_info.metadata.expires_at
+                    },
+                    "message": format!("Created worktree for task '{}' at {}", task_id, worktree_info.path.display())
+                }))
+            }
             Err(e) => {
                 warn!("Failed to create worktree for task '{}': {}", task_id, e);
                 Ok(json!({
@@ -209,6 +254,11 @@ impl VibeToolHandler for ListWorktreesTool {
                     "branch": w.branch,
                     "head": w.head,
                     "is_detached": w.is_detached,
+                    "protected": w.protected,
+                    "description": w.metadata.description,
+                    "link": w.metadata.link,
+                    "base_branch": w.metadata.base_branch,
+                    "expires_at": w.metadata.expires_at,
                     "age_hours": w.age.as_secs() / 3600,
                     "status": {
                         "is_clean": w.status.is_clean,
@@ -276,8 +326,7 @@ impl VibeToolHandler for AnalyzeConflictsTool {
                 },
                 "target_branch": {
                     "type": "string",
-                    "description": "Target branch for merge analysis (default: main)",
-                    "default": "main"
+                    "description": "Target branch for merge analysis (default: the branch the worktree was created from, else the first configured main branch)"
                 },
                 "include_diff": {
                     "type": "boolean",
@@ -298,7 +347,6 @@ impl VibeToolHandler for AnalyzeConflictsTool {
             .as_str()
             .ok_or_else(|| anyhow::anyhow!("branch_name is required"))?;
 
-        let target_branch = args["target_branch"].as_str().unwrap_or("main");
         let include_diff = args["include_diff"].as_bool().unwrap_or(true);
 
         let current_dir = std::env::current_dir()?;
@@ -321,12 +369,25 @@ impl VibeToolHandler for AnalyzeConflictsTool {
             })
             .ok_or_else(|| anyhow::anyhow!("Worktree not found: {}", branch_name))?;
 
+        let target_branch = match args["target_branch"].as_str() {
+            Some(target) => target.to_string(),
+            None => target_worktree
+                .metadata
+                .base_branch
+                .clone()
+                .or_else(|| {
+                    let config = worktree_manager.get_config();
+                    config.merge_detection.main_branches.first().cloned()
+                })
+                .unwrap_or_else(|| "main".to_string()),
+        };
+
         // Get conflict information
         let conflict_analysis = self
             .analyze_potential_conflicts(
                 &target_worktree.path,
                 &target_worktree.branch,
-                target_branch,
+                &target_branch,
                 include_diff,
             )
             .await?;
@@ -494,7 +555,7 @@ impl VibeToolHandler for RecommendCleanupTool {
     }
 
     fn tool_description(&self) -> &str {
-        "Analyze worktrees and provide intelligent cleanup recommendations based on activity and merge status"
+        "Analyze worktrees and provide intelligent cleanup recommendations based on activity, merge status, and expiry deadlines"
     }
 
     fn input_schema(&self) -> Value {
@@ -515,6 +576,11 @@ impl VibeToolHandler for RecommendCleanupTool {
                     "type": "number",
                     "description": "Minimum merge confidence (0.0-1.0) for merged branch cleanup",
                     "default": 0.7
+                },
+                "include_orphans": {
+                    "type": "boolean",
+                    "description": "Also delete orphaned directories that git no longer tracks",
+                    "default": false
                 }
             },
             "required": []
@@ -609,14 +675,28 @@ impl RecommendCleanupTool {
         let mut reasons = Vec::new();
         let mut warnings = Vec::new();
 
+        // A passed deadline makes the worktree a candidate on its own, but
+        // the safety analysis below still applies
+        let now = chrono::Utc::now();
+        let expired = worktree.metadata.is_expired(now);
+        let expiry = worktree
+            .metadata
+            .expiry_label(now)
+            .map(|label| format!("Time box {label}"));
+        let keep_reason = |reason: String| match &expiry {
+            Some(expiry) => format!("{reason}; {expiry}"),
+            None => reason,
+        };
+
         // Age check
-        if worktree.age < min_age {
+        if worktree.age < min_age && !expired {
             return json!({
                 "worktree": worktree.branch,
                 "path": worktree.path,
                 "action": "keep",
-                "reason": format!("Too new ({:.1} hours old)", worktree.age.as_secs_f64() / 3600.0),
-                "safety_score": 0.0
+                "reason": keep_reason(format!("Too new ({:.1} hours old)", worktree.age.as_secs_f64() / 3600.0)),
+                "safety_score": 0.0,
+                "expires_at": worktree.metadata.expires_at
             });
         }
 
@@ -628,14 +708,15 @@ impl RecommendCleanupTool {
             .map(|info| info.is_merged && info.confidence >= min_confidence)
             .unwrap_or(false);
 
-        if require_merged && !is_merged {
+        if require_merged && !is_merged && !expired {
             return json!({
                 "worktree": worktree.branch,
                 "path": worktree.path,
                 "action": "keep",
-                "reason": "Not confirmed as merged",
+                "reason": keep_reason("Not confirmed as merged".to_string()),
                 "safety_score": 0.0,
-                "merge_info": worktree.status.merge_info
+                "merge_info": worktree.status.merge_info,
+                "expires_at": worktree.metadata.expires_at
             });
         }
 
@@ -665,7 +746,7 @@ impl RecommendCleanupTool {
         }
 
         // Determine action
-        let action = if safety_score > 0.8 && is_merged {
+        let action = if safety_score > 0.8 && (is_merged || expired) {
             "safe_cleanup"
         } else if safety_score > 0.5 {
             "careful_cleanup"
@@ -685,6 +766,10 @@ impl RecommendCleanupTool {
             ));
         }
 
+        if let Some(expiry) = expiry {
+            reasons.push(expiry);
+        }
+
         reasons.push(format!(
             "Age: {:.1} days",
             worktree.age.as_secs_f64() / 86400.0
@@ -696,6 +781,8 @@ impl RecommendCleanupTool {
             "action": action,
             "safety_score": safety_score,
             "is_merged": is_merged,
+            "is_expired": expired,
+            "expires_at": worktree.metadata.expires_at,
             "reasons": reasons,
             "warnings": warnings,
             "age_days": worktree.age.as_secs_f64() / 86400.0,
@@ -772,6 +859,7 @@ impl VibeToolHandler for ExecuteCleanupTool {
         let dry_run = args["dry_run"].as_bool().unwrap_or(true);
         let force = args["force"].as_bool().unwrap_or(false);
         let min_confidence = args["min_merge_confidence"].as_f64().unwrap_or(0.7) as f32;
+        let include_orphans = args["include_orphans"].as_bool().unwrap_or(false);
 
         let strategy = match strategy_str {
             "discard" => CleanupStrategy::Discard,
@@ -797,9 +885,12 @@ impl VibeToolHandler for ExecuteCleanupTool {
             force,
             dry_run,
             auto_confirm: true, // AI operations skip interactive prompts
+            confirmations: Default::default(),
             branch_prefix_filter: Some(worktree_manager.get_config().prefix.clone()),
             merged_only: true,
             min_merge_confidence: min_confidence,
+            include_orphans,
+            expired_only: false,
         };
 
         let cleanup = WorktreeCleanup::new(
@@ -824,6 +915,7 @@ impl VibeToolHandler for ExecuteCleanupTool {
                 "action": format!("{:?}", result.action),
                 "reason": result.reason,
                 "error": result.error,
+                "trash_id": result.trash_id,
                 "safety_violations": result.safety_violations.into_iter().map(|v| json!({
                     "type": format!("{:?}", v.violation_type),
                     "description": v.description,
@@ -880,6 +972,46 @@ mod tests {
         assert!(schema["properties"]["min_confidence"].is_object());
     }
 
+    #[test]
+    fn test_recommend_cleanup_reasons_about_expiry() {
+        let now = chrono::Utc::now();
+        let worktree = |expires_at| crate::worktree::status::WorktreeInfo {
+            path: PathBuf::from("/tmp/spike-cache"),
+            branch: "vibe-ws/spike-cache".to_string(),
+            head: String::new(),
+            task_id: Some("spike-cache".to_string()),
+            status: Default::default(),
+            age: std::time::Duration::from_secs(3600),
+            is_detached: false,
+            protected: false,
+            metadata: WorktreeMetadata {
+                expires_at,
+                ..Default::default()
+            },
+            base_refresh: None,
+        };
+        let min_age = std::time::Duration::from_secs(86400);
+
+        // Unmerged and young, but past its deadline
+        let expired = worktree(Some(now - chrono::Duration::hours(5)));
+        let recommendation =
+            RecommendCleanupTool.analyze_worktree_for_cleanup(&expired, min_age, true, 0.7);
+        assert_eq!(recommendation["action"], "safe_cleanup");
+        assert_eq!(recommendation["is_expired"], true);
+        assert_eq!(recommendation["reasons"][0], "Time box expired 5h ago");
+
+        let live = worktree(Some(
+            now + chrono::Duration::hours(47) + chrono::Duration::minutes(30),
+        ));
+        let recommendation =
+            RecommendCleanupTool.analyze_worktree_for_cleanup(&live, min_age, true, 0.7);
+        assert_eq!(recommendation["action"], "keep");
+        assert!(recommendation["reason"]
+            .as_str()
+            .unwrap()
+            .ends_with("Time box expires in 1d 23h"));
+    }
+
     #[tokio::test]
     async fn test_execute_cleanup_tool() {
         let tool = ExecuteCleanupTool;
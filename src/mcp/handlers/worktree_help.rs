@@ -92,7 +92,9 @@ impl WorktreeHelpTool {
         json!({
             "title": "Worktree Configuration",
             "description": "Configuration options for customizing worktree behavior",
-            "configuration_file": "~/.toolprint/vibe-workspace/config.yaml",
+            "configuration_file": crate::workspace::constants::display_path(
+                &crate::workspace::constants::get_default_config_path()
+            ),
             "environment_variables": [
                 {
                     "name": "VIBE_WORKTREE_BASE",
@@ -4,8 +4,12 @@
 //! commands as tools that can be invoked by AI models.
 
 pub mod handlers;
+pub mod refresher;
 pub mod registry;
+pub mod resources;
+pub mod self_test;
 pub mod server;
+pub mod transport;
 pub mod types;
 
 pub use server::VibeMCPServer;
@@ -0,0 +1,402 @@
+//! Background worktree status refresh for long-running MCP sessions
+//!
+//! Agents otherwise see whatever status they last asked for. When
+//! `worktree.refresh_interval_secs` is set, the server re-checks every
+//! repository with linked worktrees on that interval, keeps the results in a
+//! [`WorktreeStatusCache`], and tells subscribed clients when a worktree's
+//! severity changes via `notifications/resources/updated`.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::{mpsc, Mutex, Notify, RwLock};
+use tokio::task::JoinHandle;
+use tracing::{debug, info};
+
+use super::transport::ServerNotification;
+use crate::workspace::WorkspaceManager;
+use crate::worktree::cache::WorktreeStatusCache;
+use crate::worktree::status::{batch_update_worktree_status, StatusSeverity, WorktreeInfo};
+use crate::worktree::{list_workspace_worktrees, RepositoryWorktrees};
+
+/// URI prefix of the per-repository worktree resources
+pub const WORKTREE_URI_PREFIX: &str = "vibe://worktrees/";
+
+/// Tools that create or remove worktrees; refreshing mid-call would race them
+pub const MUTATING_WORKTREE_TOOLS: &[&str] = &["create_worktree", "execute_worktree_cleanup"];
+
+/// Refresh only every Nth pass while running on battery
+const BATTERY_BACKOFF: u64 = 4;
+
+/// Refresh a repository on a network filesystem only every Nth pass
+const NETWORK_FS_BACKOFF: u64 = 4;
+
+/// Filesystem types whose `git status` is slow enough to back off from
+const NETWORK_FS_TYPES: &[&str] = &[
+    "nfs", "nfs4", "cifs", "smb", "smbfs", "smb3", "afpfs", "webdav", "sshfs", "9p", "davfs",
+];
+
+/// Resource URI for a repository's worktrees
+pub fn worktree_uri(repo: &str) -> String {
+    format!("{WORKTREE_URI_PREFIX}{repo}")
+}
+
+/// Counts mutating tool calls in flight so the refresher can wait them out
+#[derive(Debug, Default)]
+pub struct MutationGate {
+    in_flight: AtomicUsize,
+    idle: Notify,
+}
+
+/// Held for the duration of a mutating tool call
+pub struct MutationGuard(Arc<MutationGate>);
+
+impl MutationGate {
+    pub fn enter(self: &Arc<Self>) -> MutationGuard {
+        self.in_flight.fetch_add(1, Ordering::SeqCst);
+        MutationGuard(self.clone())
+    }
+
+    pub fn is_idle(&self) -> bool {
+        self.in_flight.load(Ordering::SeqCst) == 0
+    }
+
+    /// Wait until no mutating call is in flight
+    pub async fn wait_idle(&self) {
+        loop {
+            let notified = self.idle.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+            if self.is_idle() {
+                return;
+            }
+            notified.await;
+        }
+    }
+}
+
+impl Drop for MutationGuard {
+    fn drop(&mut self) {
+        if self.0.in_flight.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.0.idle.notify_waiters();
+        }
+    }
+}
+
+/// State shared between the refresher, the resource handler and tool calls
+#[derive(Debug, Default)]
+pub struct RefreshState {
+    pub gate: Arc<MutationGate>,
+    cache: RwLock<WorktreeStatusCache>,
+    snapshots: RwLock<HashMap<String, Vec<WorktreeInfo>>>,
+    subscriptions: Mutex<HashSet<String>>,
+}
+
+impl RefreshState {
+    /// Store a repository's latest worktrees; true when any severity changed
+    pub async fn record(&self, repo: &str, worktrees: Vec<WorktreeInfo>) -> bool {
+        {
+            let mut cache = self.cache.write().await;
+            for worktree in &worktrees {
+                let _ = cache.insert(worktree.path.clone(), worktree.clone());
+            }
+            cache.cleanup_stale_entries();
+        }
+
+        let mut snapshots = self.snapshots.write().await;
+        let previous = snapshots
+            .get(repo)
+            .map(|w| severities(w))
+            .unwrap_or_default();
+        let changed = previous != severities(&worktrees);
+        if worktrees.is_empty() {
+            snapshots.remove(repo);
+        } else {
+            snapshots.insert(repo.to_string(), worktrees);
+        }
+        changed
+    }
+
+    /// Last refreshed worktrees of a repository
+    pub async fn snapshot(&self, repo: &str) -> Option<Vec<WorktreeInfo>> {
+        self.snapshots.read().await.get(repo).cloned()
+    }
+
+    /// Repositories with refreshed worktrees, sorted by name
+    pub async fn repositories(&self) -> Vec<String> {
+        let mut repos: Vec<_> = self.snapshots.read().await.keys().cloned().collect();
+        repos.sort();
+        repos
+    }
+
+    pub async fn subscribe(&self, uri: String) {
+        self.subscriptions.lock().await.insert(uri);
+    }
+
+    pub async fn unsubscribe(&self, uri: &str) {
+        self.subscriptions.lock().await.remove(uri);
+    }
+
+    pub async fn is_subscribed(&self, uri: &str) -> bool {
+        self.subscriptions.lock().await.contains(uri)
+    }
+}
+
+fn severities(worktrees: &[WorktreeInfo]) -> BTreeMap<PathBuf, StatusSeverity> {
+    worktrees
+        .iter()
+        .map(|w| (w.path.clone(), w.status.severity))
+        .collect()
+}
+
+/// Periodically re-checks worktree status for every workspace repository
+pub struct WorktreeRefresher {
+    workspace: Arc<Mutex<WorkspaceManager>>,
+    state: Arc<RefreshState>,
+    interval: Duration,
+    updates: mpsc::UnboundedSender<ServerNotification>,
+}
+
+impl WorktreeRefresher {
+    pub fn new(
+        workspace: Arc<Mutex<WorkspaceManager>>,
+        state: Arc<RefreshState>,
+        interval: Duration,
+        updates: mpsc::UnboundedSender<ServerNotification>,
+    ) -> Self {
+        Self {
+            workspace,
+            state,
+            interval,
+            updates,
+        }
+    }
+
+    pub fn spawn(self) -> JoinHandle<()> {
+        tokio::spawn(self.run())
+    }
+
+    async fn run(self) {
+        info!(
+            "Refreshing worktree status every {}s",
+            self.interval.as_secs()
+        );
+
+        let mut pass: u64 = 0;
+        loop {
+            let on_battery = tokio::task::spawn_blocking(on_battery_power)
+                .await
+                .unwrap_or(false);
+            let mounts = tokio::task::spawn_blocking(mount_table)
+                .await
+                .unwrap_or_default();
+
+            self.state.gate.wait_idle().await;
+            let listings = {
+                let workspace = self.workspace.lock().await;
+                list_workspace_worktrees(&workspace).await
+            };
+            self.forget_missing(&listings).await;
+
+            for listing in listings {
+                let factor =
+                    backoff_factor(on_battery, is_network_path(&mounts, &listing.repo_path));
+                if pass % factor != 0 {
+                    continue;
+                }
+
+                self.state.gate.wait_idle().await;
+                let repo = listing.repo_name;
+                match batch_update_worktree_status(listing.worktrees).await {
+                    Ok(worktrees) => self.record(&repo, worktrees).await,
+                    Err(e) => debug!("Worktree refresh for {} failed: {}", repo, e),
+                }
+            }
+
+            pass = pass.wrapping_add(1);
+            tokio::time::sleep(self.interval).await;
+        }
+    }
+
+    /// Drop repositories whose last worktree went away since the previous pass
+    async fn forget_missing(&self, listings: &[RepositoryWorktrees]) {
+        for repo in self.state.repositories().await {
+            if !listings.iter().any(|l| l.repo_name == repo) {
+                self.record(&repo, Vec::new()).await;
+            }
+        }
+    }
+
+    async fn record(&self, repo: &str, worktrees: Vec<WorktreeInfo>) {
+        if self.state.record(repo, worktrees).await {
+            let uri = worktree_uri(repo);
+            if self.state.is_subscribed(&uri).await {
+                let _ = self.updates.send(ServerNotification::ResourceUpdated(uri));
+            }
+        }
+    }
+}
+
+/// How many passes to skip between refreshes of one repository
+fn backoff_factor(on_battery: bool, network_fs: bool) -> u64 {
+    let mut factor = 1;
+    if on_battery {
+        factor *= BATTERY_BACKOFF;
+    }
+    if network_fs {
+        factor *= NETWORK_FS_BACKOFF;
+    }
+    factor
+}
+
+/// Whether the machine is running on battery. Only macOS reports this
+#[cfg(target_os = "macos")]
+fn on_battery_power() -> bool {
+    std::process::Command::new("pmset")
+        .args(["-g", "batt"])
+        .output()
+        .map(|output| drawing_from_battery(&String::from_utf8_lossy(&output.stdout)))
+        .unwrap_or(false)
+}
+
+#[cfg(not(target_os = "macos"))]
+fn on_battery_power() -> bool {
+    false
+}
+
+#[cfg_attr(not(any(target_os = "macos", test)), allow(dead_code))]
+fn drawing_from_battery(pmset_output: &str) -> bool {
+    pmset_output
+        .lines()
+        .next()
+        .is_some_and(|line| line.contains("'Battery Power'"))
+}
+
+/// Mount points and their filesystem types
+#[cfg(target_os = "linux")]
+fn mount_table() -> Vec<(PathBuf, String)> {
+    std::fs::read_to_string("/proc/mounts")
+        .map(|mounts| parse_proc_mounts(&mounts))
+        .unwrap_or_default()
+}
+
+#[cfg(target_os = "macos")]
+fn mount_table() -> Vec<(PathBuf, String)> {
+    std::process::Command::new("mount")
+        .output()
+        .map(|output| parse_bsd_mounts(&String::from_utf8_lossy(&output.stdout)))
+        .unwrap_or_default()
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn mount_table() -> Vec<(PathBuf, String)> {
+    Vec::new()
+}
+
+/// `device mountpoint fstype options ...`, spaces escaped as `\040`
+#[cfg_attr(not(any(target_os = "linux", test)), allow(dead_code))]
+fn parse_proc_mounts(mounts: &str) -> Vec<(PathBuf, String)> {
+    mounts
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let mount_point = fields.nth(1)?.replace("\\040", " ");
+            let fs_type = fields.next()?;
+            Some((PathBuf::from(mount_point), fs_type.to_string()))
+        })
+        .collect()
+}
+
+/// `device on mountpoint (fstype, options...)`
+#[cfg_attr(not(any(target_os = "macos", test)), allow(dead_code))]
+fn parse_bsd_mounts(mounts: &str) -> Vec<(PathBuf, String)> {
+    mounts
+        .lines()
+        .filter_map(|line| {
+            let (_, rest) = line.split_once(" on ")?;
+            let (mount_point, options) = rest.rsplit_once(" (")?;
+            let fs_type = options.split([',', ')']).next()?.trim();
+            Some((PathBuf::from(mount_point), fs_type.to_string()))
+        })
+        .collect()
+}
+
+/// Whether the deepest mount containing `path` is a network filesystem
+fn is_network_path(mounts: &[(PathBuf, String)], path: &Path) -> bool {
+    mounts
+        .iter()
+        .filter(|(mount_point, _)| path.starts_with(mount_point))
+        .max_by_key(|(mount_point, _)| mount_point.components().count())
+        .is_some_and(|(_, fs_type)| {
+            let fs_type = fs_type.to_lowercase();
+            NETWORK_FS_TYPES.contains(&fs_type.as_str()) || fs_type.starts_with("fuse.sshfs")
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_network_path_detection() {
+        let mounts = parse_proc_mounts(
+            "/dev/sda1 / ext4 rw 0 0\n\
+             server:/export /mnt/nfs nfs4 rw 0 0\n\
+             user@host:/ /home/me/remote\\040dir fuse.sshfs rw 0 0\n\
+             /dev/sdb1 /mnt/nfs/local ext4 rw 0 0\n",
+        );
+        assert_eq!(mounts.len(), 4);
+        assert!(is_network_path(&mounts, Path::new("/mnt/nfs/repo")));
+        assert!(is_network_path(
+            &mounts,
+            Path::new("/home/me/remote dir/repo")
+        ));
+        assert!(!is_network_path(&mounts, Path::new("/mnt/nfs/local/repo")));
+        assert!(!is_network_path(&mounts, Path::new("/home/me/repo")));
+
+        let mounts = parse_bsd_mounts(
+            "/dev/disk3s1s1 on / (apfs, sealed, local, read-only, journaled)\n\
+             //me@nas/share on /Volumes/share (smbfs, nodev, nosuid, mounted by me)\n",
+        );
+        assert_eq!(mounts[1], (PathBuf::from("/Volumes/share"), "smbfs".into()));
+        assert!(is_network_path(&mounts, Path::new("/Volumes/share/repo")));
+        assert!(!is_network_path(&mounts, Path::new("/Users/me/repo")));
+    }
+
+    #[test]
+    fn test_backoff() {
+        assert!(drawing_from_battery(
+            "Now drawing from 'Battery Power'\n -InternalBattery-0 85%; discharging"
+        ));
+        assert!(!drawing_from_battery("Now drawing from 'AC Power'\n"));
+        assert_eq!(backoff_factor(false, false), 1);
+        assert_eq!(
+            backoff_factor(true, true),
+            BATTERY_BACKOFF * NETWORK_FS_BACKOFF
+        );
+    }
+
+    #[tokio::test]
+    async fn test_mutation_gate() {
+        let gate = Arc::new(MutationGate::default());
+        let guard = gate.enter();
+        assert!(!gate.is_idle());
+
+        let waiter = tokio::spawn({
+            let gate = gate.clone();
+            async move { gate.wait_idle().await }
+        });
+        tokio::task::yield_now().await;
+        assert!(!waiter.is_finished());
+
+        drop(guard);
+        tokio::time::timeout(Duration::from_secs(1), waiter)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(gate.is_idle());
+    }
+}
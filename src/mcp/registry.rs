@@ -5,7 +5,7 @@ use serde_json::Value;
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::Mutex;
-use tracing::info;
+use tracing::{debug, info};
 
 use crate::workspace::WorkspaceManager;
 
@@ -30,7 +30,7 @@ impl ToolRegistry {
     /// * `handler` - The tool handler to register
     pub fn register(&mut self, handler: Arc<dyn VibeToolHandler>) {
         let tool_name = handler.tool_name().to_string();
-        info!("Registering MCP tool: {}", tool_name);
+        debug!("Registering MCP tool: {}", tool_name);
         self.handlers.insert(tool_name, handler);
     }
 
@@ -0,0 +1,125 @@
+//! `vibe://worktrees/{repo}` resources backed by the background refresher
+
+use async_trait::async_trait;
+use serde_json::json;
+use std::sync::Arc;
+use ultrafast_mcp::types::resources::{
+    ListResourceTemplatesRequest, ListResourceTemplatesResponse,
+};
+use ultrafast_mcp::types::roots::{Root, RootOperation};
+use ultrafast_mcp::{
+    ListResourcesRequest, ListResourcesResponse, MCPError, MCPResult, ReadResourceRequest,
+    ReadResourceResponse, Resource, ResourceContent, ResourceHandler, ResourceSubscriptionHandler,
+    ResourceTemplate,
+};
+
+use super::refresher::{worktree_uri, RefreshState, WORKTREE_URI_PREFIX};
+
+const MIME_TYPE: &str = "application/json";
+
+/// Serves refreshed worktree status and tracks which repositories clients
+/// want update notifications for
+pub struct WorktreeResources {
+    state: Arc<RefreshState>,
+}
+
+impl WorktreeResources {
+    pub fn new(state: Arc<RefreshState>) -> Self {
+        Self { state }
+    }
+}
+
+#[async_trait]
+impl ResourceHandler for WorktreeResources {
+    async fn read_resource(&self, request: ReadResourceRequest) -> MCPResult<ReadResourceResponse> {
+        let repo = request
+            .uri
+            .strip_prefix(WORKTREE_URI_PREFIX)
+            .ok_or_else(|| MCPError::not_found(format!("Unknown resource: {}", request.uri)))?;
+        let worktrees = self.state.snapshot(repo).await.ok_or_else(|| {
+            MCPError::not_found(format!("No refreshed worktrees for repository '{repo}'"))
+        })?;
+
+        let body = json!({
+            "repository": repo,
+            "worktrees": worktrees,
+        });
+        let text = serde_json::to_string_pretty(&body)
+            .map_err(|e| MCPError::internal_error(e.to_string()))?;
+
+        Ok(ReadResourceResponse {
+            contents: vec![ResourceContent::Text {
+                uri: request.uri,
+                text,
+                mime_type: Some(MIME_TYPE.to_string()),
+            }],
+        })
+    }
+
+    async fn list_resources(
+        &self,
+        _request: ListResourcesRequest,
+    ) -> MCPResult<ListResourcesResponse> {
+        let resources = self
+            .state
+            .repositories()
+            .await
+            .into_iter()
+            .map(|repo| Resource {
+                uri: worktree_uri(&repo),
+                description: Some(format!("Status of the worktrees of {repo}")),
+                name: format!("{repo} worktrees"),
+                mime_type: Some(MIME_TYPE.to_string()),
+            })
+            .collect();
+
+        Ok(ListResourcesResponse {
+            resources,
+            next_cursor: None,
+        })
+    }
+
+    async fn list_resource_templates(
+        &self,
+        _request: ListResourceTemplatesRequest,
+    ) -> MCPResult<ListResourceTemplatesResponse> {
+        Ok(ListResourceTemplatesResponse {
+            resource_templates: vec![ResourceTemplate {
+                uri_template: format!("{WORKTREE_URI_PREFIX}{{repo}}"),
+                name: "Repository worktrees".to_string(),
+                description: Some(
+                    "Worktree status for a repository, refreshed in the background".to_string(),
+                ),
+                mime_type: Some(MIME_TYPE.to_string()),
+            }],
+            next_cursor: None,
+        })
+    }
+
+    async fn validate_resource_access(
+        &self,
+        _uri: &str,
+        _operation: RootOperation,
+        _roots: &[Root],
+    ) -> MCPResult<()> {
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ResourceSubscriptionHandler for WorktreeResources {
+    async fn subscribe(&self, uri: String) -> MCPResult<()> {
+        self.state.subscribe(uri).await;
+        Ok(())
+    }
+
+    async fn unsubscribe(&self, uri: String) -> MCPResult<()> {
+        self.state.unsubscribe(&uri).await;
+        Ok(())
+    }
+
+    async fn notify_change(&self, _uri: String, _content: serde_json::Value) -> MCPResult<()> {
+        // Updates go out through the transport when the refresher sees them
+        Ok(())
+    }
+}
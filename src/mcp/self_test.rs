@@ -0,0 +1,722 @@
+//! `vibe mcp validate`: a self-test of the MCP tool interface
+//!
+//! The server is built in-process exactly as `vibe mcp` builds it. Every
+//! registered tool's input schema is checked, and the read-only tools are
+//! called against the live workspace with safe arguments. With `--deep` the
+//! mutating tools run too: most against a throwaway sandbox workspace, each
+//! in a fresh one, and the rest only as a dry run.
+
+use anyhow::{Context, Result};
+use console::style;
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::ffi::OsString;
+use std::sync::Arc;
+use std::time::Instant;
+use tempfile::TempDir;
+use tokio::sync::Mutex;
+use ultrafast_mcp::{ListToolsRequest, ToolCall, ToolContent, ToolHandler};
+
+use super::VibeMCPServer;
+use crate::apps::render::TEMPLATE_APPS;
+use crate::workspace::config::{AppIntegrations, VSCodeIntegration, WorkspaceInfo};
+use crate::workspace::constants::VIBE_HOME_ENV;
+use crate::workspace::{Repository, WorkspaceConfig, WorkspaceManager};
+
+/// Tools called against the live workspace on every run
+pub const READ_ONLY_TOOLS: &[&str] = &[
+    "describe_repo",
+    "file_tree",
+    "git_status",
+    "list_app_templates",
+    "list_worktrees",
+    "recommend_worktree_cleanup",
+    "render_template",
+    "repo_git_query",
+    "repo_health",
+    "show_apps",
+    "show_config",
+    "validate_config",
+    "validate_mcp_interface",
+    "worktree_help",
+];
+
+/// Tools called with `--deep`, each against a fresh sandbox workspace
+pub const SANDBOXED_TOOLS: &[&str] = &[
+    "backup_config",
+    "configure_app",
+    "create_app_template",
+    "create_repository",
+    "delete_app_template",
+    "exec_git_command",
+    "init_config",
+    "init_workspace",
+    "reset_config",
+    "reset_git_config",
+    "restore_config",
+    "scan_repos",
+    "update_default_templates",
+];
+
+/// Tools called with `--deep` against the live workspace as a dry run
+pub const DRY_RUN_TOOLS: &[&str] = &["execute_worktree_cleanup"];
+
+/// Tools never called, with the reason
+pub const UNCALLED_TOOLS: &[(&str, &str)] = &[
+    (
+        "analyze_worktree_conflicts",
+        "needs the branch of a worktree",
+    ),
+    ("clone", "clones over the network and opens an app"),
+    ("clone_repo", "clones over the network"),
+    (
+        "create_worktree",
+        "creates a worktree in the current repository",
+    ),
+    ("launch_repo", "opens an app"),
+    ("open_repo", "opens an app"),
+    ("sync_repos", "fetches over the network"),
+    ("task_done", "removes a worktree and its branch"),
+    ("task_finish", "pushes and opens a pull request"),
+    ("task_start", "creates a worktree in a repository"),
+];
+
+/// Name of the repository, workspace, and template sandboxed calls create
+const SANDBOX_NAME: &str = "vibe-self-test";
+
+/// Types a JSON schema may name
+const SCHEMA_TYPES: &[&str] = &[
+    "object", "array", "string", "integer", "number", "boolean", "null",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckOutcome {
+    Passed,
+    Failed,
+    Skipped,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ToolCheck {
+    pub tool: String,
+    pub outcome: CheckOutcome,
+    /// Why the tool failed or was skipped
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// How long the call took, for tools that were called
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+}
+
+impl ToolCheck {
+    fn skipped(tool: &str, reason: impl Into<String>) -> Self {
+        Self {
+            tool: tool.to_string(),
+            outcome: CheckOutcome::Skipped,
+            detail: Some(reason.into()),
+            duration_ms: None,
+        }
+    }
+
+    fn failed(tool: &str, detail: impl Into<String>) -> Self {
+        Self {
+            tool: tool.to_string(),
+            outcome: CheckOutcome::Failed,
+            detail: Some(detail.into()),
+            duration_ms: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SelfTestReport {
+    pub deep: bool,
+    pub passed: usize,
+    pub failed: usize,
+    pub skipped: usize,
+    /// One entry per registered tool, by name
+    pub tools: Vec<ToolCheck>,
+}
+
+impl SelfTestReport {
+    fn new(deep: bool, tools: Vec<ToolCheck>) -> Self {
+        let count = |outcome| tools.iter().filter(|t| t.outcome == outcome).count();
+        Self {
+            deep,
+            passed: count(CheckOutcome::Passed),
+            failed: count(CheckOutcome::Failed),
+            skipped: count(CheckOutcome::Skipped),
+            tools,
+        }
+    }
+
+    /// `tool: detail` for each failed tool
+    pub fn failures(&self) -> Vec<String> {
+        self.tools
+            .iter()
+            .filter(|t| t.outcome == CheckOutcome::Failed)
+            .map(|t| format!("{}: {}", t.tool, t.detail.as_deref().unwrap_or("failed")))
+            .collect()
+    }
+
+    /// Print one line per tool and a summary. Uses `println!` because tool
+    /// output is routed to stderr while the self-test runs.
+    pub fn print(&self) {
+        let width = self.tools.iter().map(|t| t.tool.len()).max().unwrap_or(0);
+        for check in &self.tools {
+            let mark = match check.outcome {
+                CheckOutcome::Passed => style("✓").green(),
+                CheckOutcome::Failed => style("✗").red(),
+                CheckOutcome::Skipped => style("-").dim(),
+            };
+            let duration = check
+                .duration_ms
+                .map(|ms| format!("{ms:>6} ms"))
+                .unwrap_or_else(|| " ".repeat(9));
+            let detail = check.detail.as_deref().unwrap_or("");
+            let detail = match check.outcome {
+                CheckOutcome::Failed => style(detail).red(),
+                _ => style(detail).dim(),
+            };
+            println!("{mark} {:<width$}  {duration}  {detail}", check.tool);
+        }
+        println!();
+        println!(
+            "{} passed, {} failed, {} skipped",
+            style(self.passed).green(),
+            style(self.failed).red(),
+            style(self.skipped).dim()
+        );
+        if !self.deep {
+            println!(
+                "{}",
+                style("Mutating tools were skipped; run with --deep to call them in a sandbox")
+                    .dim()
+            );
+        }
+    }
+}
+
+/// Check every registered tool, calling the read-only ones against
+/// `workspace` and, with `deep`, the mutating ones too
+pub async fn run(workspace: Arc<Mutex<WorkspaceManager>>, deep: bool) -> Result<SelfTestReport> {
+    let server = VibeMCPServer::new(workspace.clone());
+    let mut tools = server
+        .list_tools(ListToolsRequest { cursor: None })
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to list MCP tools: {e}"))?
+        .tools;
+    tools.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let in_git_repo = std::env::current_dir()
+        .map(|dir| dir.ancestors().any(|dir| dir.join(".git").exists()))
+        .unwrap_or(false);
+
+    let mut checks = Vec::new();
+    for tool in tools {
+        let name = tool.name.as_str();
+        let schema_errors = check_schema(&tool.input_schema);
+        if !schema_errors.is_empty() {
+            checks.push(ToolCheck::failed(
+                name,
+                format!("invalid schema: {}", schema_errors.join("; ")),
+            ));
+            continue;
+        }
+
+        let check = if READ_ONLY_TOOLS.contains(&name) {
+            let args = live_arguments(name, &*workspace.lock().await, in_git_repo);
+            match args {
+                Ok(args) => call(&server, name, &tool.input_schema, args).await,
+                Err(reason) => ToolCheck::skipped(name, reason),
+            }
+        } else if !deep && (SANDBOXED_TOOLS.contains(&name) || DRY_RUN_TOOLS.contains(&name)) {
+            ToolCheck::skipped(name, "mutating; run with --deep")
+        } else if DRY_RUN_TOOLS.contains(&name) {
+            if in_git_repo {
+                let args = json!({ "dry_run": true });
+                call(&server, name, &tool.input_schema, args).await
+            } else {
+                ToolCheck::skipped(name, "run inside a git repository")
+            }
+        } else if SANDBOXED_TOOLS.contains(&name) {
+            call_in_sandbox(name, &tool.input_schema).await
+        } else if let Some((_, reason)) = UNCALLED_TOOLS.iter().find(|(tool, _)| *tool == name) {
+            ToolCheck::skipped(name, *reason)
+        } else {
+            ToolCheck::skipped(name, "not classified for the self-test")
+        };
+        checks.push(check);
+    }
+
+    Ok(SelfTestReport::new(deep, checks))
+}
+
+/// Safe arguments for calling read-only `tool` against `workspace`, or why
+/// it can't be called
+fn live_arguments(
+    tool: &str,
+    workspace: &WorkspaceManager,
+    in_git_repo: bool,
+) -> std::result::Result<Value, String> {
+    let config = workspace.get_config();
+    let first_repo = || {
+        config
+            .repositories
+            .first()
+            .map(|repo| repo.name.clone())
+            .ok_or_else(|| "no repositories are configured".to_string())
+    };
+    match tool {
+        "describe_repo" => Ok(json!({ "repo": first_repo()? })),
+        "file_tree" => Ok(json!({ "repo": first_repo()?, "depth": 1 })),
+        "repo_git_query" => Ok(json!({
+            "repo": first_repo()?,
+            "subcommand": "rev-parse",
+            "args": ["--git-dir"]
+        })),
+        "list_app_templates" => Ok(json!({ "app": "vscode" })),
+        "render_template" => config
+            .repositories
+            .iter()
+            .find_map(|repo| {
+                let app = repo
+                    .apps
+                    .keys()
+                    .find(|app| TEMPLATE_APPS.contains(&app.as_str()))?;
+                Some(json!({ "app": app, "repository": repo.name }))
+            })
+            .ok_or_else(|| "no repository has a templated app configured".to_string()),
+        "list_worktrees" | "recommend_worktree_cleanup" if !in_git_repo => {
+            Err("run inside a git repository".to_string())
+        }
+        _ => Ok(json!({})),
+    }
+}
+
+/// Call `tool` on `server` with `args`, which must fit `schema`
+async fn call(server: &VibeMCPServer, tool: &str, schema: &Value, args: Value) -> ToolCheck {
+    let argument_errors = check_arguments(schema, &args);
+    if !argument_errors.is_empty() {
+        return ToolCheck::failed(
+            tool,
+            format!(
+                "self-test arguments don't fit the schema: {}",
+                argument_errors.join("; ")
+            ),
+        );
+    }
+
+    let started = Instant::now();
+    let result = server
+        .handle_tool_call(ToolCall {
+            name: tool.to_string(),
+            arguments: Some(args),
+        })
+        .await;
+    let duration_ms = Some(started.elapsed().as_millis() as u64);
+
+    let failure = match result {
+        Err(e) => Some(e.to_string()),
+        Ok(result) => {
+            let text = result
+                .content
+                .iter()
+                .filter_map(|content| match content {
+                    ToolContent::Text { text } => Some(text.as_str()),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            if result.is_error == Some(true) {
+                Some(text.trim_start_matches("Error: ").to_string())
+            } else {
+                reported_error(&text)
+            }
+        }
+    };
+
+    ToolCheck {
+        tool: tool.to_string(),
+        outcome: if failure.is_some() {
+            CheckOutcome::Failed
+        } else {
+            CheckOutcome::Passed
+        },
+        detail: failure,
+        duration_ms,
+    }
+}
+
+/// The message of a result that reports an error instead of failing the call
+fn reported_error(text: &str) -> Option<String> {
+    let result: Value = serde_json::from_str(text).ok()?;
+    let failed = result["status"] == "error" || result["success"] == false;
+    failed.then(|| {
+        result["message"]
+            .as_str()
+            .or(result["error"].as_str())
+            .unwrap_or("the tool reported an error")
+            .to_string()
+    })
+}
+
+/// Call mutating `tool` against a fresh sandbox workspace
+async fn call_in_sandbox(tool: &str, schema: &Value) -> ToolCheck {
+    let prepared = async {
+        let sandbox = Sandbox::create().await?;
+        let args = sandbox.arguments(tool).await?;
+        anyhow::Ok((sandbox, args))
+    }
+    .await;
+    match prepared {
+        Ok((sandbox, args)) => {
+            let server = VibeMCPServer::new(sandbox.workspace.clone());
+            call(&server, tool, schema, args).await
+        }
+        Err(e) => ToolCheck::failed(tool, format!("could not set up the sandbox: {e:#}")),
+    }
+}
+
+/// An empty repository at `path`
+#[cfg(feature = "native-git")]
+fn init_repository(path: &std::path::Path) -> Result<()> {
+    git2::Repository::init(path)?;
+    Ok(())
+}
+
+/// An empty repository at `path`
+#[cfg(not(feature = "native-git"))]
+fn init_repository(path: &std::path::Path) -> Result<()> {
+    std::fs::create_dir_all(path)?;
+    let status = std::process::Command::new("git")
+        .args(["init", "-q"])
+        .current_dir(path)
+        .status()?;
+    anyhow::ensure!(status.success(), "git init exited with {status}");
+    Ok(())
+}
+
+/// A throwaway workspace with one empty repository. `VIBE_HOME` points into
+/// it until it's dropped, so templates, backups, and caches stay inside.
+struct Sandbox {
+    dir: TempDir,
+    workspace: Arc<Mutex<WorkspaceManager>>,
+    previous_home: Option<OsString>,
+}
+
+impl Sandbox {
+    async fn create() -> Result<Self> {
+        let dir = TempDir::new().context("Failed to create a sandbox directory")?;
+        let previous_home = std::env::var_os(VIBE_HOME_ENV);
+        std::env::set_var(VIBE_HOME_ENV, dir.path().join("home"));
+
+        let root = dir.path().join("workspace");
+        init_repository(&root.join(SANDBOX_NAME))
+            .context("Failed to create the sandbox repository")?;
+
+        let config_path = crate::workspace::constants::get_default_config_path();
+        let templates_dir = crate::workspace::constants::get_templates_dir();
+        let config = WorkspaceConfig {
+            workspace: WorkspaceInfo {
+                name: SANDBOX_NAME.to_string(),
+                root: root.clone(),
+                auto_discover: false,
+            },
+            repositories: vec![Repository::new(SANDBOX_NAME, SANDBOX_NAME)],
+            apps: AppIntegrations {
+                github: None,
+                warp: None,
+                iterm2: None,
+                vscode: Some(VSCodeIntegration {
+                    enabled: true,
+                    workspace_dir: dir.path().join("vscode"),
+                    template_dir: templates_dir.join("vscode"),
+                    default_template: "default".to_string(),
+                }),
+                wezterm: None,
+                cursor: None,
+                windsurf: None,
+            },
+            ..WorkspaceConfig::default()
+        };
+        config.save_to_file(&config_path).await?;
+
+        let manager = WorkspaceManager::new(config_path).await?;
+        manager
+            .get_template_manager()
+            .init_default_templates()
+            .await?;
+
+        Ok(Self {
+            dir,
+            workspace: Arc::new(Mutex::new(manager)),
+            previous_home,
+        })
+    }
+
+    /// Arguments for calling `tool` in the sandbox, after creating anything
+    /// the call needs
+    async fn arguments(&self, tool: &str) -> Result<Value> {
+        let root = self.dir.path().join("workspace");
+        let args = match tool {
+            "configure_app" => json!({ "repo": SANDBOX_NAME, "app": "vscode" }),
+            "create_app_template" => {
+                json!({ "app": "vscode", "name": SANDBOX_NAME, "content": "{}" })
+            }
+            "create_repository" => json!({
+                "name": "created",
+                "owner": SANDBOX_NAME,
+                "skip_github_check": true,
+                "no_configure": true,
+                "no_open": true
+            }),
+            "delete_app_template" => {
+                let ws = self.workspace.lock().await;
+                ws.get_template_manager()
+                    .save_template("vscode", SANDBOX_NAME, "{}")
+                    .await?;
+                json!({ "app": "vscode", "name": SANDBOX_NAME })
+            }
+            "exec_git_command" => json!({ "command": "status" }),
+            "init_config" | "init_workspace" => {
+                json!({ "name": SANDBOX_NAME, "root": root.to_string_lossy() })
+            }
+            "restore_config" => {
+                let backup = self
+                    .workspace
+                    .lock()
+                    .await
+                    .create_backup(Some(self.dir.path().join("backups")), None, None)
+                    .await?;
+                json!({ "backup": backup.to_string_lossy(), "force": true })
+            }
+            "scan_repos" => json!({ "import": true }),
+            "reset_config" | "reset_git_config" | "update_default_templates" => {
+                json!({ "force": true })
+            }
+            _ => json!({}),
+        };
+        Ok(args)
+    }
+}
+
+impl Drop for Sandbox {
+    fn drop(&mut self) {
+        match &self.previous_home {
+            Some(home) => std::env::set_var(VIBE_HOME_ENV, home),
+            None => std::env::remove_var(VIBE_HOME_ENV),
+        }
+    }
+}
+
+/// Problems with a tool's input schema, empty when it is a usable JSON
+/// schema for an object of arguments
+pub fn check_schema(schema: &Value) -> Vec<String> {
+    let mut errors = Vec::new();
+    if schema["type"] != "object" {
+        errors.push("the top level must have type 'object'".to_string());
+    }
+    check_node(schema, "schema", &mut errors);
+    errors
+}
+
+fn check_node(node: &Value, path: &str, errors: &mut Vec<String>) {
+    let Some(node) = node.as_object() else {
+        errors.push(format!("{path} is not an object"));
+        return;
+    };
+
+    let types: Vec<&str> = match node.get("type") {
+        None => Vec::new(),
+        Some(Value::String(name)) => vec![name.as_str()],
+        Some(Value::Array(names)) => names.iter().filter_map(Value::as_str).collect(),
+        Some(other) => {
+            errors.push(format!("{path}.type is {other}, not a type name"));
+            Vec::new()
+        }
+    };
+    for name in &types {
+        if !SCHEMA_TYPES.contains(name) {
+            errors.push(format!("{path}.type '{name}' is not a JSON schema type"));
+        }
+    }
+
+    if let Some(properties) = node.get("properties") {
+        match properties.as_object() {
+            Some(properties) => {
+                for (name, property) in properties {
+                    check_node(property, &format!("{path}.properties.{name}"), errors);
+                }
+            }
+            None => errors.push(format!("{path}.properties is not an object")),
+        }
+    }
+
+    if let Some(required) = node.get("required") {
+        match required.as_array() {
+            Some(required) => {
+                for name in required {
+                    match name.as_str() {
+                        Some(name) if node["properties"].get(name).is_some() => {}
+                        Some(name) => errors.push(format!(
+                            "{path}.required names '{name}', which is not a property"
+                        )),
+                        None => errors.push(format!("{path}.required has non-string {name}")),
+                    }
+                }
+            }
+            None => errors.push(format!("{path}.required is not an array")),
+        }
+    }
+
+    if let Some(items) = node.get("items") {
+        check_node(items, &format!("{path}.items"), errors);
+    }
+
+    let choices = match node.get("enum") {
+        None => None,
+        Some(Value::Array(choices)) if !choices.is_empty() => Some(choices),
+        Some(_) => {
+            errors.push(format!("{path}.enum is not a non-empty array"));
+            None
+        }
+    };
+    for choice in choices.into_iter().flatten() {
+        if !fits_types(choice, &types) {
+            errors.push(format!("{path}.enum value {choice} doesn't match its type"));
+        }
+    }
+
+    if let Some(default) = node.get("default") {
+        if !fits_types(default, &types) {
+            errors.push(format!("{path}.default {default} doesn't match its type"));
+        }
+        if choices.is_some_and(|choices| !choices.contains(default)) {
+            errors.push(format!(
+                "{path}.default {default} is not one of its enum values"
+            ));
+        }
+    }
+}
+
+/// Whether `value` is one of `types`; anything fits an untyped schema
+fn fits_types(value: &Value, types: &[&str]) -> bool {
+    types.is_empty()
+        || types.iter().any(|name| match *name {
+            "object" => value.is_object(),
+            "array" => value.is_array(),
+            "string" => value.is_string(),
+            "integer" => value.is_i64() || value.is_u64(),
+            "number" => value.is_number(),
+            "boolean" => value.is_boolean(),
+            "null" => value.is_null(),
+            _ => false,
+        })
+}
+
+/// Problems with calling a tool with `args` under `schema`: missing required
+/// arguments, unknown ones, and values of the wrong type or not in the enum
+fn check_arguments(schema: &Value, args: &Value) -> Vec<String> {
+    let empty = Map::new();
+    let properties = schema["properties"].as_object().unwrap_or(&empty);
+    let mut errors = Vec::new();
+    for name in schema["required"].as_array().into_iter().flatten() {
+        let name = name.as_str().unwrap_or_default();
+        if args.get(name).is_none() {
+            errors.push(format!("'{name}' is required"));
+        }
+    }
+    for (name, value) in args.as_object().unwrap_or(&empty) {
+        let Some(property) = properties.get(name) else {
+            errors.push(format!("'{name}' is not a property"));
+            continue;
+        };
+        let types: Vec<&str> = match &property["type"] {
+            Value::String(name) => vec![name.as_str()],
+            Value::Array(names) => names.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        if !fits_types(value, &types) {
+            errors.push(format!("'{name}' should be {}", types.join(" or ")));
+        }
+        if let Some(choices) = property["enum"].as_array() {
+            if !choices.contains(value) {
+                errors.push(format!("'{name}' is not one of its enum values"));
+            }
+        }
+    }
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registered_tools_are_classified_and_valid() {
+        for (name, _, schema) in VibeMCPServer::build_registry().list_tools() {
+            let classified = READ_ONLY_TOOLS.contains(&name.as_str())
+                || SANDBOXED_TOOLS.contains(&name.as_str())
+                || DRY_RUN_TOOLS.contains(&name.as_str())
+                || UNCALLED_TOOLS.iter().any(|(tool, _)| *tool == name);
+            assert!(classified, "{name} is not classified for the self-test");
+            assert_eq!(check_schema(&schema), Vec::<String>::new(), "{name}");
+        }
+    }
+
+    #[test]
+    fn test_check_schema_finds_broken_schemas() {
+        let errors = check_schema(&json!({
+            "type": "object",
+            "properties": {
+                "format": { "type": "string", "enum": ["json", "table"], "default": "yaml" },
+                "depth": { "type": "integer", "default": "3" },
+                "mode": { "type": "text" }
+            },
+            "required": ["repo"]
+        }));
+        assert_eq!(
+            errors,
+            [
+                "schema.properties.depth.default \"3\" doesn't match its type",
+                "schema.properties.format.default \"yaml\" is not one of its enum values",
+                "schema.properties.mode.type 'text' is not a JSON schema type",
+                "schema.required names 'repo', which is not a property",
+            ]
+        );
+        assert!(!check_schema(&json!({ "properties": {} })).is_empty());
+    }
+
+    #[test]
+    fn test_check_arguments() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "app": { "type": "string", "enum": ["vscode", "warp"] },
+                "force": { "type": "boolean" }
+            },
+            "required": ["app"]
+        });
+        assert!(check_arguments(&schema, &json!({ "app": "warp", "force": true })).is_empty());
+        assert_eq!(
+            check_arguments(&schema, &json!({ "force": "yes", "extra": 1 })),
+            [
+                "'app' is required",
+                "'extra' is not a property",
+                "'force' should be boolean",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reported_errors_fail_the_call() {
+        assert_eq!(
+            reported_error(r#"{"status": "error", "message": "boom"}"#).as_deref(),
+            Some("boom")
+        );
+        assert!(reported_error(r#"{"status": "success"}"#).is_none());
+        assert!(reported_error("plain text").is_none());
+    }
+}
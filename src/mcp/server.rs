@@ -4,22 +4,30 @@ use anyhow::{anyhow, Result};
 use async_trait::async_trait;
 use serde_json::Value;
 use std::sync::Arc;
-use tokio::sync::Mutex;
-use tracing::info;
+use std::time::Duration;
+use tokio::sync::{mpsc, Mutex};
+use tracing::{info, warn};
 use ultrafast_mcp::{
-    ListToolsRequest, ListToolsResponse, MCPResult, ServerCapabilities, ServerInfo, Tool, ToolCall,
-    ToolContent, ToolHandler, ToolResult, ToolsCapability, UltraFastServer,
+    ListToolsRequest, ListToolsResponse, MCPResult, ResourcesCapability, ServerCapabilities,
+    ServerInfo, Tool, ToolCall, ToolContent, ToolHandler, ToolResult, ToolsCapability,
+    UltraFastServer,
 };
 
+use crate::output::timings::Recording;
+use crate::workspace::config_watcher::ConfigWatcher;
 use crate::workspace::WorkspaceManager;
 
 use super::handlers;
+use super::refresher::{RefreshState, WorktreeRefresher, MUTATING_WORKTREE_TOOLS};
 use super::registry::{ToolRegistry, ToolRegistryBuilder};
+use super::resources::WorktreeResources;
+use super::transport::{NotifyingStdioTransport, ServerNotification};
 
 /// MCP server for vibe-workspace
 pub struct VibeMCPServer {
     registry: ToolRegistry,
     workspace_manager: Arc<Mutex<WorkspaceManager>>,
+    refresh_state: Arc<RefreshState>,
 }
 
 impl VibeMCPServer {
@@ -30,11 +38,12 @@ impl VibeMCPServer {
         Self {
             registry,
             workspace_manager,
+            refresh_state: Arc::new(RefreshState::default()),
         }
     }
 
     /// Builds the tool registry with all available tools
-    fn build_registry() -> ToolRegistry {
+    pub(crate) fn build_registry() -> ToolRegistry {
         ToolRegistryBuilder::new()
             // Configuration management tools
             .with_tool(Arc::new(handlers::InitWorkspaceTool))
@@ -50,18 +59,23 @@ impl VibeMCPServer {
             .with_tool(Arc::new(handlers::ListAppTemplatesTool))
             .with_tool(Arc::new(handlers::CreateAppTemplateTool))
             .with_tool(Arc::new(handlers::DeleteAppTemplateTool))
+            .with_tool(Arc::new(handlers::RenderTemplateTool))
             .with_tool(Arc::new(handlers::UpdateDefaultTemplatesTool))
             // Repository operation tools
             .with_tool(Arc::new(handlers::LaunchRepoTool))
             .with_tool(Arc::new(handlers::OpenRepoTool))
             .with_tool(Arc::new(handlers::CloneTool))
             .with_tool(Arc::new(handlers::CreateRepositoryTool))
+            .with_tool(Arc::new(handlers::DescribeRepoTool))
+            .with_tool(Arc::new(handlers::RepoHealthTool))
+            .with_tool(Arc::new(handlers::FileTreeTool))
             // Git operation tools
             .with_tool(Arc::new(handlers::GitStatusTool))
             .with_tool(Arc::new(handlers::ScanReposTool))
             .with_tool(Arc::new(handlers::SyncReposTool))
             .with_tool(Arc::new(handlers::CloneRepoTool))
             .with_tool(Arc::new(handlers::ExecGitCommandTool))
+            .with_tool(Arc::new(handlers::RepoGitQueryTool))
             .with_tool(Arc::new(handlers::ResetGitConfigTool))
             // Validation tool
             .with_tool(Arc::new(handlers::ValidateMcpInterfaceTool))
@@ -72,11 +86,22 @@ impl VibeMCPServer {
             .with_tool(Arc::new(handlers::RecommendCleanupTool))
             .with_tool(Arc::new(handlers::ExecuteCleanupTool))
             .with_tool(Arc::new(handlers::WorktreeHelpTool))
+            // Task workflow tools
+            .with_tool(Arc::new(handlers::TaskStartTool))
+            .with_tool(Arc::new(handlers::TaskFinishTool))
+            .with_tool(Arc::new(handlers::TaskDoneTool))
             .build()
     }
 
     /// Creates and configures the UltraFast MCP server
     pub fn create_server(self) -> UltraFastServer {
+        self.build_server(false, false)
+    }
+
+    /// Builds the server, exposing worktree resources when the background
+    /// refresher is running. `list_changed` announces that the resource list
+    /// changes when the config is reloaded.
+    fn build_server(self, worktree_resources: bool, list_changed: bool) -> UltraFastServer {
         let server_info = ServerInfo {
             name: "vibe-workspace".to_string(),
             version: env!("CARGO_PKG_VERSION").to_string(),
@@ -93,21 +118,82 @@ impl VibeMCPServer {
             tools: Some(ToolsCapability {
                 list_changed: Some(false),
             }),
+            resources: worktree_resources.then_some(ResourcesCapability {
+                subscribe: Some(true),
+                list_changed: Some(list_changed),
+            }),
             ..Default::default()
         };
 
-        UltraFastServer::new(server_info, capabilities).with_tool_handler(Arc::new(self))
+        let resources = Arc::new(WorktreeResources::new(self.refresh_state.clone()));
+        let server =
+            UltraFastServer::new(server_info, capabilities).with_tool_handler(Arc::new(self));
+        if worktree_resources {
+            server
+                .with_resource_handler(resources.clone())
+                .with_subscription_handler(resources)
+        } else {
+            server
+        }
     }
 
     /// Runs the MCP server
     pub async fn run(self) -> Result<()> {
         info!("Starting vibe-workspace MCP server");
 
-        let server = self.create_server();
-        server
-            .run_stdio()
-            .await
-            .map_err(|e| anyhow!("MCP server error: {}", e))
+        let (refresh_interval, config_watcher) = {
+            let workspace = self.workspace_manager.lock().await;
+            let config_watcher = if workspace.config().watch_config() {
+                match ConfigWatcher::spawn(workspace.get_config_path()) {
+                    Ok(watcher) => Some(watcher),
+                    Err(e) => {
+                        warn!("Not watching the config file: {e:#}");
+                        None
+                    }
+                }
+            } else {
+                None
+            };
+            (
+                workspace.config().worktree.refresh_interval_secs,
+                config_watcher,
+            )
+        };
+        let watching = config_watcher.is_some();
+
+        let (updates_tx, updates_rx) = mpsc::unbounded_channel();
+        // Without worktree resources there is nothing to notify clients about
+        let reloader = config_watcher.map(|watcher| {
+            tokio::spawn(reload_on_change(
+                watcher,
+                self.workspace_manager.clone(),
+                refresh_interval.is_some().then(|| updates_tx.clone()),
+            ))
+        });
+
+        let result = match refresh_interval {
+            Some(secs) => {
+                let refresher = WorktreeRefresher::new(
+                    self.workspace_manager.clone(),
+                    self.refresh_state.clone(),
+                    Duration::from_secs(secs),
+                    updates_tx,
+                )
+                .spawn();
+
+                let server = self.build_server(true, watching);
+                let result = server
+                    .run_with_transport(Box::new(NotifyingStdioTransport::new(updates_rx)))
+                    .await;
+                refresher.abort();
+                result
+            }
+            None => self.create_server().run_stdio().await,
+        };
+        if let Some(reloader) = reloader {
+            reloader.abort();
+        }
+        result.map_err(|e| anyhow!("MCP server error: {}", e))
     }
 }
 
@@ -115,17 +201,32 @@ impl VibeMCPServer {
 #[async_trait]
 impl ToolHandler for VibeMCPServer {
     async fn handle_tool_call(&self, call: ToolCall) -> MCPResult<ToolResult> {
+        // Keep the background refresher away from worktrees being changed
+        let _mutation = MUTATING_WORKTREE_TOOLS
+            .contains(&call.name.as_str())
+            .then(|| self.refresh_state.gate.enter());
+
+        let mut arguments = call
+            .arguments
+            .unwrap_or(Value::Object(serde_json::Map::new()));
+        let include_timings = arguments
+            .as_object_mut()
+            .and_then(|args| args.remove(INCLUDE_TIMINGS))
+            .and_then(|value| value.as_bool())
+            .unwrap_or(false);
+        let recording = include_timings.then(Recording::start);
+
         // Delegate to our registry
-        match self
+        let result = self
             .registry
-            .handle_call(
-                &call.name,
-                call.arguments
-                    .unwrap_or(Value::Object(serde_json::Map::new())),
-                self.workspace_manager.clone(),
-            )
-            .await
-        {
+            .handle_call(&call.name, arguments, self.workspace_manager.clone())
+            .await;
+        let result = match recording {
+            Some(recording) => result.map(|value| with_timings(value, recording)),
+            None => result,
+        };
+
+        match result {
             Ok(result) => {
                 // Convert our result to MCP ToolResult
                 let content = if let Some(text) = result.as_str() {
@@ -159,7 +260,7 @@ impl ToolHandler for VibeMCPServer {
             .map(|(name, description, schema)| Tool {
                 name,
                 description,
-                input_schema: schema,
+                input_schema: with_timings_option(schema),
                 output_schema: None,
                 annotations: None,
             })
@@ -172,6 +273,66 @@ impl ToolHandler for VibeMCPServer {
     }
 }
 
+/// Reload the workspace config each time the file changes. A rejected
+/// config is logged and the previous one kept; a reloaded one may add or
+/// remove repositories, so clients are told the resource list changed.
+async fn reload_on_change(
+    mut watcher: ConfigWatcher,
+    workspace: Arc<Mutex<WorkspaceManager>>,
+    updates: Option<mpsc::UnboundedSender<ServerNotification>>,
+) {
+    while watcher.changed().await {
+        let reloaded = workspace.lock().await.reload_config().await;
+        match reloaded {
+            Ok(true) => {
+                if let Some(updates) = &updates {
+                    let _ = updates.send(ServerNotification::ResourceListChanged);
+                }
+            }
+            Ok(false) => {}
+            Err(e) => warn!(
+                "config.yaml changed but was not reloaded, keeping the previous configuration: {e:#}"
+            ),
+        }
+    }
+}
+
+/// Argument every tool accepts to get a `timings` report with its result
+const INCLUDE_TIMINGS: &str = "include_timings";
+
+/// Advertise the `include_timings` argument in a tool's input schema
+fn with_timings_option(mut schema: Value) -> Value {
+    if let Some(schema) = schema.as_object_mut() {
+        let properties = schema
+            .entry("properties")
+            .or_insert_with(|| Value::Object(serde_json::Map::new()));
+        if let Some(properties) = properties.as_object_mut() {
+            properties.insert(
+                INCLUDE_TIMINGS.to_string(),
+                serde_json::json!({
+                    "type": "boolean",
+                    "description": "Attach a timings report (per-phase counts and durations, slowest operations) to the result",
+                    "default": false
+                }),
+            );
+        }
+    }
+    schema
+}
+
+/// Attach the timings of a tool call to its result, wrapping results that
+/// aren't objects
+fn with_timings(result: Value, recording: Recording) -> Value {
+    let timings = serde_json::to_value(recording.finish()).unwrap_or(Value::Null);
+    match result {
+        Value::Object(mut object) => {
+            object.insert("timings".to_string(), timings);
+            Value::Object(object)
+        }
+        other => serde_json::json!({ "result": other, "timings": timings }),
+    }
+}
+
 /// Builder for creating an MCP server with custom configuration
 pub struct MCPServerBuilder {
     #[allow(dead_code)]
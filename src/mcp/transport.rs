@@ -0,0 +1,136 @@
+//! Stdio transport that can push server notifications
+//!
+//! The server loop owns its transport and only ever asks it for the next
+//! incoming message, so the stock stdio transport has no way to send
+//! anything unprompted. This one reads stdin on its own task and, while
+//! waiting for a request, writes any queued notifications to stdout.
+
+use async_trait::async_trait;
+use serde_json::json;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, BufWriter, Stdout};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tracing::trace;
+use ultrafast_mcp::protocol::{JsonRpcMessage, JsonRpcRequest};
+use ultrafast_mcp_transport::{Result, Transport, TransportError};
+
+const RESOURCE_UPDATED: &str = "notifications/resources/updated";
+const RESOURCE_LIST_CHANGED: &str = "notifications/resources/list_changed";
+
+/// Notifications the server sends without being asked
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ServerNotification {
+    /// The resource with this URI has new contents
+    ResourceUpdated(String),
+    /// Resources may have been added or removed
+    ResourceListChanged,
+}
+
+pub struct NotifyingStdioTransport {
+    incoming: mpsc::Receiver<std::io::Result<String>>,
+    updates: mpsc::UnboundedReceiver<ServerNotification>,
+    stdout: BufWriter<Stdout>,
+    reader: JoinHandle<()>,
+}
+
+impl NotifyingStdioTransport {
+    /// `updates` carries the notifications to send
+    pub fn new(updates: mpsc::UnboundedReceiver<ServerNotification>) -> Self {
+        let (tx, incoming) = mpsc::channel(32);
+        let reader = tokio::spawn(async move {
+            let mut lines = BufReader::new(tokio::io::stdin()).lines();
+            loop {
+                let line = match lines.next_line().await {
+                    Ok(Some(line)) => Ok(line),
+                    Ok(None) => break,
+                    Err(e) => Err(e),
+                };
+                let failed = line.is_err();
+                if tx.send(line).await.is_err() || failed {
+                    break;
+                }
+            }
+        });
+
+        Self {
+            incoming,
+            updates,
+            stdout: BufWriter::new(tokio::io::stdout()),
+            reader,
+        }
+    }
+
+    async fn write_line(&mut self, line: &str) -> Result<()> {
+        trace!("Sending message: {}", line);
+        let network_error = |e: std::io::Error| TransportError::NetworkError {
+            message: format!("Failed to write to stdout: {e}"),
+        };
+        self.stdout
+            .write_all(line.as_bytes())
+            .await
+            .map_err(network_error)?;
+        self.stdout.write_all(b"\n").await.map_err(network_error)?;
+        self.stdout.flush().await.map_err(network_error)
+    }
+}
+
+#[async_trait]
+impl Transport for NotifyingStdioTransport {
+    async fn send_message(&mut self, message: JsonRpcMessage) -> Result<()> {
+        let line =
+            serde_json::to_string(&message).map_err(|e| TransportError::SerializationError {
+                message: format!("Failed to serialize message: {e}"),
+            })?;
+        self.write_line(&line).await
+    }
+
+    async fn receive_message(&mut self) -> Result<JsonRpcMessage> {
+        loop {
+            tokio::select! {
+                line = self.incoming.recv() => {
+                    let line = match line {
+                        Some(Ok(line)) => line,
+                        Some(Err(e)) => {
+                            return Err(TransportError::NetworkError {
+                                message: format!("Failed to read line from stdin: {e}"),
+                            })
+                        }
+                        None => return Err(TransportError::ConnectionClosed),
+                    };
+                    let line = line.trim();
+                    if line.is_empty() {
+                        continue;
+                    }
+                    return serde_json::from_str(line).map_err(|e| {
+                        TransportError::SerializationError {
+                            message: format!("Failed to parse message: {e}"),
+                        }
+                    });
+                }
+                Some(update) = self.updates.recv() => {
+                    let (method, params) = match update {
+                        ServerNotification::ResourceUpdated(uri) => {
+                            (RESOURCE_UPDATED, Some(json!({ "uri": uri })))
+                        }
+                        ServerNotification::ResourceListChanged => (RESOURCE_LIST_CHANGED, None),
+                    };
+                    let notification = JsonRpcMessage::Notification(JsonRpcRequest::notification(
+                        method.to_string(),
+                        params,
+                    ));
+                    self.send_message(notification).await?;
+                }
+            }
+        }
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        self.reader.abort();
+        self.stdout
+            .flush()
+            .await
+            .map_err(|e| TransportError::NetworkError {
+                message: format!("Failed to flush stdout: {e}"),
+            })
+    }
+}
@@ -66,6 +66,9 @@ pub struct GitStatusInfo {
     pub has_untracked_files: bool,
     pub ahead: usize,
     pub behind: usize,
+    /// Ref `ahead` and `behind` count against, when not the branch on origin
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compare_ref: Option<String>,
 }
 
 /// App configuration result
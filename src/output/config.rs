@@ -2,7 +2,15 @@
 
 use console::Term;
 use tracing::Level;
-use tracing_subscriber::EnvFilter;
+use tracing_subscriber::filter::{LevelFilter, Targets};
+use tracing_subscriber::prelude::*;
+use tracing_subscriber::{fmt, EnvFilter};
+
+use super::log_file::{LogFileSettings, SESSION_TARGET};
+use super::timings::{self, TimingsLayer};
+
+/// Test-only switch for reproducible output, see [`OutputConfig::set_deterministic`]
+const DETERMINISTIC_ENV: &str = "VIBE_DETERMINISTIC";
 
 /// Output mode for the application
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -11,6 +19,15 @@ pub enum OutputMode {
     Cli,
     /// MCP server mode - everything to stderr except protocol messages
     Mcp,
+    /// Invoked without a terminal (e.g. by the OS URL handler) - display to
+    /// stdout on a best-effort basis, no colors, and no interactive prompts
+    NonInteractive,
+    /// Structured output (`--json`) - a single JSON document on stdout,
+    /// decorative display output and logs to stderr
+    Json,
+    /// Output captured by the shell (`vibe path`) - only the result on
+    /// stdout, display output and logs to stderr
+    Capture,
 }
 
 /// Configuration for the output system
@@ -19,15 +36,20 @@ pub struct OutputConfig {
     mode: OutputMode,
     color_enabled: bool,
     log_level: Level,
+    quiet: u8,
+    plain: bool,
+    deterministic: bool,
+    log_file: Option<LogFileSettings>,
 }
 
 impl OutputConfig {
     /// Create a new output configuration
     pub fn new(mode: OutputMode) -> Self {
-        // Detect color support
+        // Detect color support on the stream display output is written to
         let color_enabled = match mode {
-            OutputMode::Cli => Term::stdout().features().colors_supported(),
-            OutputMode::Mcp => false, // Disable colors in MCP mode
+            OutputMode::Cli => detect_colors(&Term::stdout()),
+            OutputMode::Json | OutputMode::Capture => detect_colors(&Term::stderr()),
+            OutputMode::Mcp | OutputMode::NonInteractive => false,
         };
 
         // Determine log level from environment or defaults
@@ -39,12 +61,18 @@ impl OutputConfig {
                 "warn" => Level::WARN,
                 "error" => Level::ERROR,
                 _ => match mode {
-                    OutputMode::Cli => Level::INFO,
+                    OutputMode::Cli
+                    | OutputMode::NonInteractive
+                    | OutputMode::Json
+                    | OutputMode::Capture => Level::INFO,
                     OutputMode::Mcp => Level::WARN, // Less verbose in MCP mode
                 },
             },
             Err(_) => match mode {
-                OutputMode::Cli => Level::INFO,
+                OutputMode::Cli
+                | OutputMode::NonInteractive
+                | OutputMode::Json
+                | OutputMode::Capture => Level::INFO,
                 OutputMode::Mcp => Level::WARN,
             },
         };
@@ -53,6 +81,10 @@ impl OutputConfig {
             mode,
             color_enabled,
             log_level,
+            quiet: 0,
+            plain: false,
+            deterministic: false,
+            log_file: None,
         }
     }
 
@@ -66,9 +98,37 @@ impl OutputConfig {
         self.color_enabled
     }
 
-    /// Get the current log level
-    pub fn log_level(&self) -> Level {
-        self.log_level
+    /// Disable colors regardless of terminal support (`--no-color`)
+    pub fn disable_colors(&mut self) {
+        self.color_enabled = false;
+    }
+
+    /// Strip colors, emoji, and icons from display output (`--plain`)
+    pub fn set_plain(&mut self) {
+        self.plain = true;
+        self.color_enabled = false;
+    }
+
+    /// Check if display output should be free of emoji and icons
+    pub fn is_plain(&self) -> bool {
+        self.plain
+    }
+
+    /// Make output reproducible across runs (`VIBE_DETERMINISTIC`): plain
+    /// output, sorted listings, placeholder times, sequential execution
+    pub fn set_deterministic(&mut self) {
+        self.deterministic = true;
+        self.set_plain();
+    }
+
+    /// Check if output must be reproducible across runs
+    pub fn is_deterministic(&self) -> bool {
+        self.deterministic
+    }
+
+    /// Check if the user can be prompted for input
+    pub fn is_interactive(&self) -> bool {
+        matches!(self.mode, OutputMode::Cli | OutputMode::Json)
     }
 
     /// Set verbose mode (DEBUG level)
@@ -76,30 +136,162 @@ impl OutputConfig {
         self.log_level = Level::DEBUG;
     }
 
+    /// Set quiet mode: level 1 hides informational display output and logs,
+    /// level 2 also hides warnings. Ignored in MCP mode.
+    pub fn set_quiet(&mut self, level: u8) {
+        if self.mode == OutputMode::Mcp || level == 0 {
+            return;
+        }
+
+        self.quiet = level;
+        self.log_level = if level >= 2 {
+            Level::ERROR
+        } else {
+            Level::WARN
+        };
+    }
+
+    /// Check if informational display output is suppressed
+    pub fn is_quiet(&self) -> bool {
+        self.quiet > 0
+    }
+
+    /// Check if warnings on stderr are suppressed as well (`-qq`)
+    pub fn hides_warnings(&self) -> bool {
+        self.quiet >= 2
+    }
+
+    /// Also write DEBUG-level logs to a rolling file
+    pub fn set_log_file(&mut self, settings: LogFileSettings) {
+        self.log_file = Some(settings);
+    }
+
+    /// Get the log file settings, if file logging is enabled
+    pub fn log_file(&self) -> Option<&LogFileSettings> {
+        self.log_file.as_ref()
+    }
+
     /// Initialize the tracing subscriber based on configuration
     pub fn init_tracing(&self) {
-        let builder = tracing_subscriber::fmt()
-            .with_env_filter(EnvFilter::from_default_env().add_directive(self.log_level.into()))
-            .with_target(false)
-            .with_level(true);
+        // The session header is only meant for the log file, and phase
+        // spans only for the timings layer
+        let console_filter = EnvFilter::from_default_env()
+            .add_directive(self.log_level.into())
+            .add_directive(
+                format!("{SESSION_TARGET}=off")
+                    .parse()
+                    .expect("valid directive"),
+            )
+            .add_directive(
+                format!("{}=off", timings::TARGET)
+                    .parse()
+                    .expect("valid directive"),
+            );
 
-        match self.mode {
-            OutputMode::Cli => {
+        let layer = fmt::layer().with_target(false).with_level(true);
+        let console_layer = match self.mode {
+            OutputMode::Cli | OutputMode::Json | OutputMode::Capture => {
                 // In CLI mode, logs go to stderr with colors if supported
-                builder
+                layer
                     .with_ansi(self.color_enabled)
                     .with_writer(std::io::stderr)
-                    .init();
+                    .boxed()
+            }
+            OutputMode::NonInteractive => {
+                // Without a terminal there is nobody to read colored logs
+                layer.with_ansi(false).with_writer(std::io::stderr).boxed()
             }
             OutputMode::Mcp => {
                 // In MCP mode, everything goes to stderr without colors
-                builder
+                layer
                     .with_ansi(false)
                     .with_writer(std::io::stderr)
                     .without_time() // Simpler format for MCP
                     .compact() // More compact format
-                    .init();
+                    .boxed()
             }
-        }
+        };
+
+        // A broken log directory should never stop the command itself
+        let file_layer = self
+            .log_file
+            .as_ref()
+            .and_then(|settings| match settings.appender() {
+                Ok(appender) => Some(
+                    fmt::layer()
+                        .with_ansi(false)
+                        .with_thread_ids(true)
+                        .with_writer(appender)
+                        .with_filter(LevelFilter::DEBUG),
+                ),
+                Err(e) => {
+                    eprintln!("Warning: file logging disabled: {e:#}");
+                    None
+                }
+            });
+
+        tracing_subscriber::registry()
+            .with(console_layer.with_filter(console_filter))
+            .with(file_layer)
+            .with(
+                TimingsLayer.with_filter(Targets::new().with_target(timings::TARGET, Level::TRACE)),
+            )
+            .init();
+    }
+}
+
+/// `VIBE_DETERMINISTIC` is set to a non-empty value other than `0`
+pub(crate) fn deterministic_requested() -> bool {
+    std::env::var_os(DETERMINISTIC_ENV).is_some_and(|value| !value.is_empty() && value != "0")
+}
+
+/// Colors are only emitted to a terminal, and never when `NO_COLOR` is set
+/// to a non-empty value (https://no-color.org)
+fn detect_colors(term: &Term) -> bool {
+    let no_color = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+    !no_color && term.is_term() && term.features().colors_supported()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quiet_levels() {
+        let mut config = OutputConfig::new(OutputMode::Cli);
+        config.set_quiet(1);
+        assert!(config.is_quiet());
+        assert!(!config.hides_warnings());
+        assert_eq!(config.log_level, Level::WARN);
+
+        config.set_quiet(2);
+        assert!(config.hides_warnings());
+        assert_eq!(config.log_level, Level::ERROR);
+    }
+
+    #[test]
+    fn test_plain_disables_colors() {
+        let mut config = OutputConfig::new(OutputMode::Cli);
+        config.set_plain();
+        assert!(config.is_plain());
+        assert!(!config.colors_enabled());
+    }
+
+    #[test]
+    fn test_deterministic_implies_plain() {
+        let mut config = OutputConfig::new(OutputMode::Cli);
+        config.set_deterministic();
+        assert!(config.is_deterministic());
+        assert!(config.is_plain());
+        assert!(!config.colors_enabled());
+    }
+
+    #[test]
+    fn test_quiet_ignored_in_mcp_mode() {
+        let mut config = OutputConfig::new(OutputMode::Mcp);
+        let log_level = config.log_level;
+        config.set_quiet(2);
+        assert!(!config.is_quiet());
+        assert_eq!(config.log_level, log_level);
     }
 }
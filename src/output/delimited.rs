@@ -0,0 +1,198 @@
+//! CSV and TSV export of tabular command output
+//!
+//! Commands that accept `--format csv` or `--format tsv` serialize their rows
+//! with serde, so the header row uses the same field names as `--json`.
+//! Nested fields are named with dots, e.g. `status.ahead_count`. Lists are
+//! joined with `;` and objects are written as compact JSON. `--fields`
+//! chooses and orders the columns.
+
+use anyhow::Result;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::output::exit::CommandError;
+
+/// A delimited export format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Delimited {
+    Csv,
+    Tsv,
+}
+
+impl Delimited {
+    /// The delimited format named by a `--format` value, if any
+    pub fn from_format(format: &str) -> Option<Self> {
+        match format {
+            "csv" => Some(Self::Csv),
+            "tsv" => Some(Self::Tsv),
+            _ => None,
+        }
+    }
+
+    fn separator(self) -> char {
+        match self {
+            Self::Csv => ',',
+            Self::Tsv => '\t',
+        }
+    }
+
+    fn escape(self, cell: &str) -> String {
+        match self {
+            Self::Csv if cell.contains([',', '"', '\n', '\r']) => {
+                format!("\"{}\"", cell.replace('"', "\"\""))
+            }
+            Self::Csv => cell.to_string(),
+            // TSV has no quoting, so separators and line breaks are escaped
+            Self::Tsv => cell
+                .replace('\\', "\\\\")
+                .replace('\t', "\\t")
+                .replace('\n', "\\n")
+                .replace('\r', "\\r"),
+        }
+    }
+}
+
+/// Rows a command can export as CSV or TSV
+pub trait Tabular: Serialize {
+    /// Field names that can be exported, in default column order
+    const FIELDS: &'static [&'static str];
+}
+
+/// Print `rows` when `format` is csv or tsv. Returns false for any other
+/// format, which `fields` doesn't apply to.
+pub fn print<T: Tabular>(rows: &[T], format: &str, fields: &[String]) -> Result<bool> {
+    let Some(delimited) = Delimited::from_format(format) else {
+        if !fields.is_empty() {
+            return Err(
+                CommandError::Usage("--fields requires --format csv or tsv".to_string()).into(),
+            );
+        }
+        return Ok(false);
+    };
+    let out = render(rows, delimited, fields)?;
+    crate::data_println!("{}", out.strip_suffix('\n').unwrap_or(&out));
+    Ok(true)
+}
+
+/// `rows` with a header row. `fields` chooses and orders the columns; when
+/// empty, every field in [`Tabular::FIELDS`] is exported.
+pub fn render<T: Tabular>(rows: &[T], delimited: Delimited, fields: &[String]) -> Result<String> {
+    let columns: Vec<&str> = if fields.is_empty() {
+        T::FIELDS.to_vec()
+    } else {
+        fields.iter().map(|field| field.trim()).collect()
+    };
+    if let Some(unknown) = columns.iter().find(|field| !T::FIELDS.contains(field)) {
+        return Err(CommandError::Usage(format!(
+            "Unknown field '{unknown}'. Valid fields: {}",
+            T::FIELDS.join(", ")
+        ))
+        .into());
+    }
+
+    let separator = delimited.separator().to_string();
+    let line = |cells: Vec<String>| {
+        let cells: Vec<String> = cells.iter().map(|cell| delimited.escape(cell)).collect();
+        format!("{}\n", cells.join(&separator))
+    };
+    let mut out = line(columns.iter().map(|field| field.to_string()).collect());
+    for row in rows {
+        let value = serde_json::to_value(row)?;
+        out.push_str(&line(
+            columns
+                .iter()
+                .map(|field| lookup(&value, field).map(cell).unwrap_or_default())
+                .collect(),
+        ));
+    }
+    Ok(out)
+}
+
+/// The value at a dotted `path`, e.g. `status.ahead_count`
+fn lookup<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.').try_fold(value, |value, key| value.get(key))
+}
+
+fn cell(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        Value::Array(items) => items.iter().map(cell).collect::<Vec<_>>().join(";"),
+        Value::Object(_) => value.to_string(),
+        Value::Bool(_) | Value::Number(_) => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Row {
+        name: &'static str,
+        notes: Option<&'static str>,
+        tags: Vec<&'static str>,
+        status: Status,
+    }
+
+    #[derive(Serialize)]
+    struct Status {
+        ahead: usize,
+    }
+
+    impl Tabular for Row {
+        const FIELDS: &'static [&'static str] = &["name", "notes", "tags", "status.ahead"];
+    }
+
+    fn rows() -> Vec<Row> {
+        vec![
+            Row {
+                name: "api",
+                notes: Some("say \"hi\", then\nleave"),
+                tags: vec!["rust", "web"],
+                status: Status { ahead: 2 },
+            },
+            Row {
+                name: "web\tapp",
+                notes: None,
+                tags: Vec::new(),
+                status: Status { ahead: 0 },
+            },
+        ]
+    }
+
+    #[test]
+    fn test_csv_quotes_and_flattens() {
+        assert_eq!(
+            render(&rows(), Delimited::Csv, &[]).unwrap(),
+            "name,notes,tags,status.ahead\n\
+             api,\"say \"\"hi\"\", then\nleave\",rust;web,2\n\
+             web\tapp,,,0\n"
+        );
+    }
+
+    #[test]
+    fn test_tsv_escapes_tabs_and_newlines() {
+        assert_eq!(
+            render(&rows(), Delimited::Tsv, &[]).unwrap(),
+            "name\tnotes\ttags\tstatus.ahead\n\
+             api\tsay \"hi\", then\\nleave\trust;web\t2\n\
+             web\\tapp\t\t\t0\n"
+        );
+    }
+
+    #[test]
+    fn test_fields_select_columns() {
+        let fields = vec!["status.ahead".to_string(), "name".to_string()];
+        assert_eq!(
+            render(&rows(), Delimited::Csv, &fields).unwrap(),
+            "status.ahead,name\n2,api\n0,web\tapp\n"
+        );
+
+        let error = render(&rows(), Delimited::Csv, &["branch".to_string()]).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Unknown field 'branch'. Valid fields: name, notes, tags, status.ahead"
+        );
+    }
+}
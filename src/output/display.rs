@@ -1,22 +1,89 @@
 //! Display macros for user-facing terminal output
 
+/// Remove emoji and pictographic icons from `text` for log-friendly output.
+///
+/// Status marks are kept readable as `[ok]`, `[x]`, `[!]`, and `[i]`,
+/// and the space that usually follows an icon is dropped with it.
+pub fn strip_icons(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        let replacement = match c {
+            '✓' | '✔' | '✅' => Some("[ok]"),
+            '✗' | '✘' | '❌' => Some("[x]"),
+            '⚠' => Some("[!]"),
+            'ℹ' => Some("[i]"),
+            c if is_icon(c) => None,
+            c => {
+                result.push(c);
+                continue;
+            }
+        };
+
+        // Swallow variation selectors and joiners that belong to this icon
+        while chars
+            .peek()
+            .is_some_and(|next| matches!(next, '\u{FE0F}' | '\u{200D}') || is_icon(*next))
+        {
+            chars.next();
+        }
+
+        match replacement {
+            Some(mark) => result.push_str(mark),
+            None => {
+                if chars.peek() == Some(&' ') {
+                    chars.next();
+                }
+            }
+        }
+    }
+
+    result
+}
+
+fn is_icon(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x2600..=0x27BF     // Miscellaneous symbols and dingbats
+            | 0x2B00..=0x2BFF // Miscellaneous symbols and arrows (⭐, ⬆)
+            | 0x1F000..=0x1FAFF // Emoji and pictographs
+            | 0xFE0F
+            | 0x200D
+    )
+}
+
 /// Print user-facing output without newline
-/// Routes to stdout in CLI mode, stderr in MCP mode
+/// Routes to stdout in CLI mode, stderr in MCP mode; suppressed by `--quiet`
 #[macro_export]
 macro_rules! display_print {
     ($($arg:tt)*) => {{
-        use $crate::output::{current_mode, writer::write_output};
-        let _ = write_output(current_mode(), true, format_args!($($arg)*));
+        use $crate::output::{current_mode, is_quiet, writer::write_output};
+        if !is_quiet() {
+            let _ = write_output(current_mode(), true, format_args!($($arg)*));
+        }
     }};
 }
 
 /// Print user-facing output with newline
-/// Routes to stdout in CLI mode, stderr in MCP mode
+/// Routes to stdout in CLI mode, stderr in MCP mode; suppressed by `--quiet`
 #[macro_export]
 macro_rules! display_println {
     () => {
         $crate::display_print!("\n")
     };
+    ($($arg:tt)*) => {{
+        use $crate::output::{current_mode, is_quiet, writer::writeln_output};
+        if !is_quiet() {
+            let _ = writeln_output(current_mode(), true, format_args!($($arg)*));
+        }
+    }};
+}
+
+/// Print output the user explicitly asked for (e.g. `--format json`)
+/// Routed like `display_println!` but never suppressed by `--quiet`
+#[macro_export]
+macro_rules! data_println {
     ($($arg:tt)*) => {{
         use $crate::output::{current_mode, writer::writeln_output};
         let _ = writeln_output(current_mode(), true, format_args!($($arg)*));
@@ -44,3 +111,32 @@ macro_rules! display_eprintln {
         eprintln!($($arg)*);
     }};
 }
+
+/// Print a user-facing warning with newline
+/// Routes to stderr like `display_eprintln!`; suppressed by `-qq`
+#[macro_export]
+macro_rules! display_warn {
+    ($($arg:tt)*) => {{
+        if !$crate::output::hides_warnings() {
+            eprintln!($($arg)*);
+        }
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_icons() {
+        assert_eq!(
+            strip_icons("🔍 Analyzing repository status..."),
+            "Analyzing repository status..."
+        );
+        assert_eq!(strip_icons("  ⚠️ Skipping repo"), "  [!] Skipping repo");
+        assert_eq!(strip_icons("✓ Configured warp"), "[ok] Configured warp");
+        assert_eq!(strip_icons("❌ Failed"), "[x] Failed");
+        assert_eq!(strip_icons("  → frontend"), "  → frontend");
+        assert_eq!(strip_icons("plain text"), "plain text");
+    }
+}
@@ -0,0 +1,199 @@
+//! Process exit codes
+//!
+//! | Code | Meaning                                              |
+//! |------|------------------------------------------------------|
+//! | 0    | Success                                              |
+//! | 1    | Any other failure                                    |
+//! | 2    | Usage or configuration error                         |
+//! | 3    | Repository or worktree not found                     |
+//! | 4    | Partial failure: some repositories in a batch failed |
+//! |      | or have deny-listed licenses                         |
+//! | 5    | A required external tool is not installed            |
+//! | 130  | Cancelled by the user                                |
+
+use inquire::InquireError;
+
+use crate::git::GitError;
+use crate::uri::UriError;
+use crate::workspace::config_loader::ConfigLoadError;
+
+/// Nonzero exit codes; a command that returns `Ok` exits 0
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitCode {
+    Failure = 1,
+    Usage = 2,
+    NotFound = 3,
+    PartialFailure = 4,
+    ToolMissing = 5,
+    Cancelled = 130,
+}
+
+impl ExitCode {
+    pub fn code(self) -> i32 {
+        self as i32
+    }
+}
+
+/// Errors that carry a specific exit code through `anyhow` chains
+#[derive(Debug, thiserror::Error)]
+pub enum CommandError {
+    #[error("{0}")]
+    Usage(String),
+
+    #[error("{kind} '{name}' not found{}", hint.as_ref().map(|hint| format!(". {hint}")).unwrap_or_default())]
+    NotFound {
+        kind: &'static str,
+        name: String,
+        hint: Option<String>,
+    },
+
+    #[error("{failed} of {total} repositories failed")]
+    PartialFailure { failed: usize, total: usize },
+
+    #[error("{repos} repositories have deny-listed licenses")]
+    DeniedLicenses { repos: usize },
+
+    #[error("'{tool}' is not installed")]
+    ToolMissing { tool: String },
+
+    #[error("Cancelled")]
+    Cancelled,
+}
+
+impl CommandError {
+    pub fn not_found(kind: &'static str, name: impl Into<String>) -> Self {
+        Self::NotFound {
+            kind,
+            name: name.into(),
+            hint: None,
+        }
+    }
+
+    /// [`CommandError::not_found`] that tells how to find a valid name
+    pub fn not_found_with_hint(
+        kind: &'static str,
+        name: impl Into<String>,
+        hint: impl Into<String>,
+    ) -> Self {
+        Self::NotFound {
+            kind,
+            name: name.into(),
+            hint: Some(hint.into()),
+        }
+    }
+
+    pub fn exit_code(&self) -> ExitCode {
+        match self {
+            CommandError::Usage(_) => ExitCode::Usage,
+            CommandError::NotFound { .. } => ExitCode::NotFound,
+            CommandError::PartialFailure { .. } | CommandError::DeniedLicenses { .. } => {
+                ExitCode::PartialFailure
+            }
+            CommandError::ToolMissing { .. } => ExitCode::ToolMissing,
+            CommandError::Cancelled => ExitCode::Cancelled,
+        }
+    }
+}
+
+/// How many repositories a multi-repository command touched and how many failed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BatchSummary {
+    pub total: usize,
+    pub failed: usize,
+}
+
+impl BatchSummary {
+    /// Turn any failures into a partial-failure error
+    pub fn into_result(self) -> anyhow::Result<()> {
+        if self.failed > 0 {
+            Err(CommandError::PartialFailure {
+                failed: self.failed,
+                total: self.total,
+            }
+            .into())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Map an error chain onto an exit code using the first typed error found
+pub fn exit_code_for(error: &anyhow::Error) -> ExitCode {
+    error
+        .chain()
+        .find_map(|cause| {
+            if let Some(error) = cause.downcast_ref::<CommandError>() {
+                return Some(error.exit_code());
+            }
+            if let Some(error) = cause.downcast_ref::<GitError>() {
+                return match error {
+                    GitError::InvalidUrl { .. } => Some(ExitCode::Usage),
+                    GitError::GitHubCliNotFound => Some(ExitCode::ToolMissing),
+                    GitError::NoSearchResults { .. } => Some(ExitCode::NotFound),
+                    _ => None,
+                };
+            }
+            if cause.is::<UriError>()
+                || cause.is::<serde_yaml::Error>()
+                || cause.is::<ConfigLoadError>()
+            {
+                return Some(ExitCode::Usage);
+            }
+            if let Some(error) = cause.downcast_ref::<InquireError>() {
+                return match error {
+                    InquireError::OperationCanceled | InquireError::OperationInterrupted => {
+                        Some(ExitCode::Cancelled)
+                    }
+                    _ => None,
+                };
+            }
+            None
+        })
+        .unwrap_or(ExitCode::Failure)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn test_typed_errors_map_through_context() {
+        let error = anyhow::Error::from(CommandError::not_found("Repository", "api"))
+            .context("Failed to open repository");
+        assert_eq!(exit_code_for(&error), ExitCode::NotFound);
+
+        let error: anyhow::Error = GitError::GitHubCliNotFound.into();
+        assert_eq!(exit_code_for(&error), ExitCode::ToolMissing);
+
+        let error: anyhow::Error = InquireError::OperationInterrupted.into();
+        assert_eq!(exit_code_for(&error), ExitCode::Cancelled);
+    }
+
+    #[test]
+    fn test_config_parse_error_is_usage() {
+        let error = serde_yaml::from_str::<Vec<String>>("{")
+            .context("Failed to parse config file")
+            .unwrap_err();
+        assert_eq!(exit_code_for(&error), ExitCode::Usage);
+    }
+
+    #[test]
+    fn test_untyped_error_is_generic_failure() {
+        let error = anyhow::anyhow!("something broke");
+        assert_eq!(exit_code_for(&error), ExitCode::Failure);
+    }
+
+    #[test]
+    fn test_not_found_hint() {
+        let error = CommandError::NotFound {
+            kind: "Repository",
+            name: "api".to_string(),
+            hint: Some("Try 'vibe launch'".to_string()),
+        };
+        assert_eq!(
+            error.to_string(),
+            "Repository 'api' not found. Try 'vibe launch'"
+        );
+    }
+}
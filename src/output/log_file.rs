@@ -0,0 +1,142 @@
+//! Rotating troubleshooting log file, independent of console verbosity
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+
+/// Tracing target for the session header written at the top of each run
+pub const SESSION_TARGET: &str = "vibe_session";
+
+const LOG_FILE_PREFIX: &str = "vibe";
+const LOG_FILE_SUFFIX: &str = "log";
+const DEFAULT_MAX_FILES: usize = 7;
+
+/// Where and how the rolling log file is written
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogFileSettings {
+    pub directory: PathBuf,
+    pub prefix: String,
+    pub max_files: usize,
+}
+
+impl LogFileSettings {
+    /// Daily `vibe.<date>.log` files in the default logs directory
+    pub fn default_location() -> Self {
+        Self {
+            directory: crate::workspace::constants::get_logs_dir(),
+            prefix: LOG_FILE_PREFIX.to_string(),
+            max_files: DEFAULT_MAX_FILES,
+        }
+    }
+
+    /// Log next to an explicit path; `~/logs/debug.log` rotates as
+    /// `~/logs/debug.<date>.log`
+    pub fn from_path(path: &Path) -> Self {
+        let directory = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+        let prefix = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_else(|| LOG_FILE_PREFIX.to_string());
+
+        Self {
+            directory,
+            prefix,
+            max_files: DEFAULT_MAX_FILES,
+        }
+    }
+
+    pub fn with_max_files(mut self, max_files: usize) -> Self {
+        self.max_files = max_files.max(1);
+        self
+    }
+
+    /// Build the daily rolling appender, creating the directory if needed
+    pub fn appender(&self) -> Result<RollingFileAppender> {
+        std::fs::create_dir_all(&self.directory).with_context(|| {
+            format!(
+                "Failed to create log directory: {}",
+                self.directory.display()
+            )
+        })?;
+
+        RollingFileAppender::builder()
+            .rotation(Rotation::DAILY)
+            .filename_prefix(&self.prefix)
+            .filename_suffix(LOG_FILE_SUFFIX)
+            .max_log_files(self.max_files)
+            .build(&self.directory)
+            .context("Failed to create rolling log file")
+    }
+
+    /// The most recently written log file, if any
+    pub fn latest_file(&self) -> Option<PathBuf> {
+        let prefix = format!("{}.", self.prefix);
+        let suffix = format!(".{LOG_FILE_SUFFIX}");
+
+        std::fs::read_dir(&self.directory)
+            .ok()?
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| {
+                let name = entry.file_name().to_string_lossy().into_owned();
+                name.starts_with(&prefix) && name.ends_with(&suffix)
+            })
+            .filter_map(|entry| {
+                let modified = entry.metadata().ok()?.modified().ok()?;
+                Some((modified, entry.path()))
+            })
+            .max()
+            .map(|(_, path)| path)
+    }
+}
+
+/// The last `count` lines of `content`
+pub fn tail_lines(content: &str, count: usize) -> Vec<&str> {
+    let lines: Vec<&str> = content.lines().collect();
+    lines[lines.len().saturating_sub(count)..].to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_from_path() {
+        let settings = LogFileSettings::from_path(Path::new("/tmp/logs/debug.log"));
+        assert_eq!(settings.directory, PathBuf::from("/tmp/logs"));
+        assert_eq!(settings.prefix, "debug");
+
+        let settings = LogFileSettings::from_path(Path::new("trace.log"));
+        assert_eq!(settings.directory, PathBuf::from("."));
+        assert_eq!(settings.prefix, "trace");
+    }
+
+    #[test]
+    fn test_latest_file_matches_prefix() {
+        let temp_dir = TempDir::new().unwrap();
+        let settings = LogFileSettings {
+            directory: temp_dir.path().to_path_buf(),
+            prefix: "vibe".to_string(),
+            max_files: 3,
+        };
+        assert!(settings.latest_file().is_none());
+
+        std::fs::write(temp_dir.path().join("other.2024-01-01.log"), "x").unwrap();
+        std::fs::write(temp_dir.path().join("vibe.2024-01-01.log"), "x").unwrap();
+
+        assert_eq!(
+            settings.latest_file(),
+            Some(temp_dir.path().join("vibe.2024-01-01.log"))
+        );
+    }
+
+    #[test]
+    fn test_tail_lines() {
+        assert_eq!(tail_lines("a\nb\nc\n", 2), vec!["b", "c"]);
+        assert_eq!(tail_lines("a\nb", 10), vec!["a", "b"]);
+        assert!(tail_lines("", 5).is_empty());
+    }
+}
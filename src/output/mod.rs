@@ -5,11 +5,21 @@
 //! (where everything except protocol messages goes to stderr).
 
 mod config;
+pub mod delimited;
 mod display;
+pub mod exit;
+mod log_file;
 mod logging;
+pub mod redact;
+mod result;
+pub mod table;
+pub mod theme;
+pub mod timings;
 pub(crate) mod writer;
 
 pub use config::{OutputConfig, OutputMode};
+pub use log_file::{tail_lines, LogFileSettings};
+pub use result::{json_emitted, CommandResult};
 
 use once_cell::sync::OnceCell;
 use std::sync::RwLock;
@@ -18,19 +28,55 @@ static OUTPUT_CONFIG: OnceCell<RwLock<OutputConfig>> = OnceCell::new();
 
 /// Initialize the output system with the specified mode
 pub fn init(mode: OutputMode) {
-    init_with_verbosity(mode, false);
+    init_with_options(mode, OutputOptions::default());
 }
 
-/// Initialize the output system with the specified mode and verbosity
-pub fn init_with_verbosity(mode: OutputMode, verbose: bool) {
+/// Global flags that shape output, as given on the command line
+#[derive(Debug, Clone, Default)]
+pub struct OutputOptions {
+    pub verbose: bool,
+    /// Number of `-q` flags
+    pub quiet: u8,
+    pub no_color: bool,
+    pub plain: bool,
+    /// Write a rolling DEBUG-level log file in addition to console logs
+    pub log_file: Option<LogFileSettings>,
+}
+
+/// Initialize the output system with the specified mode and global flags
+pub fn init_with_options(mode: OutputMode, options: OutputOptions) {
     let mut config = OutputConfig::new(mode);
-    if verbose {
+    if options.verbose {
         config.set_verbose();
     }
+    config.set_quiet(options.quiet);
+    if options.no_color {
+        config.disable_colors();
+    }
+    if options.plain {
+        config.set_plain();
+    }
+    if config::deterministic_requested() {
+        config.set_deterministic();
+    }
+    if let Some(settings) = options.log_file {
+        config.set_log_file(settings);
+    }
 
     // Initialize tracing based on mode
     config.init_tracing();
 
+    if config.log_file().is_some() {
+        log_session_header();
+    }
+
+    // Both styling crates are used across the codebase, so make them agree
+    // with the single decision taken here
+    let colors = config.colors_enabled();
+    console::set_colors_enabled(colors);
+    console::set_colors_enabled_stderr(colors);
+    colored::control::set_override(colors);
+
     // Store config globally
     OUTPUT_CONFIG
         .set(RwLock::new(config))
@@ -51,3 +97,91 @@ pub fn current_mode() -> OutputMode {
         OutputMode::Cli
     }
 }
+
+/// Check if the current process may prompt the user for input
+pub fn is_interactive() -> bool {
+    if let Some(config) = OUTPUT_CONFIG.get() {
+        config.read().unwrap().is_interactive()
+    } else {
+        true
+    }
+}
+
+/// Check if commands should emit structured JSON instead of printing
+pub fn is_json() -> bool {
+    current_mode() == OutputMode::Json
+}
+
+/// Check if informational display output is suppressed (`--quiet`)
+pub fn is_quiet() -> bool {
+    if let Some(config) = OUTPUT_CONFIG.get() {
+        config.read().unwrap().is_quiet()
+    } else {
+        false
+    }
+}
+
+/// Check if warnings on stderr are suppressed as well (`-qq`)
+pub fn hides_warnings() -> bool {
+    if let Some(config) = OUTPUT_CONFIG.get() {
+        config.read().unwrap().hides_warnings()
+    } else {
+        false
+    }
+}
+
+/// Check if spinners and progress bars should be rendered
+pub fn show_progress() -> bool {
+    current_mode() == OutputMode::Cli && !is_quiet() && !is_deterministic()
+}
+
+/// Check if display output should be free of emoji and icons (`--plain`)
+pub fn is_plain() -> bool {
+    if let Some(config) = OUTPUT_CONFIG.get() {
+        config.read().unwrap().is_plain()
+    } else {
+        false
+    }
+}
+
+/// Check if output must be reproducible across runs (`VIBE_DETERMINISTIC`,
+/// meant for integration tests and snapshots only)
+pub fn is_deterministic() -> bool {
+    if let Some(config) = OUTPUT_CONFIG.get() {
+        config.read().unwrap().is_deterministic()
+    } else {
+        config::deterministic_requested()
+    }
+}
+
+/// Placeholder that replaces durations and timestamps in deterministic mode
+pub const TIME_PLACEHOLDER: &str = "<time>";
+
+/// `text` unchanged, or [`TIME_PLACEHOLDER`] in deterministic mode
+pub fn stable_time(text: String) -> String {
+    if is_deterministic() {
+        TIME_PLACEHOLDER.to_string()
+    } else {
+        text
+    }
+}
+
+/// Sort `items` by `key` in deterministic mode; otherwise leave the order as is
+pub fn sort_if_deterministic<T, K: Ord>(items: &mut [T], key: impl FnMut(&T) -> K) {
+    if is_deterministic() {
+        items.sort_by_key(key);
+    }
+}
+
+/// Record what was run at the top of each session in the log file
+fn log_session_header() {
+    let command = redact::scrub(&std::env::args().collect::<Vec<_>>().join(" ")).into_owned();
+    tracing::info!(
+        target: log_file::SESSION_TARGET,
+        version = env!("CARGO_PKG_VERSION"),
+        os = std::env::consts::OS,
+        arch = std::env::consts::ARCH,
+        command = %command,
+        "session started"
+    );
+}
@@ -0,0 +1,95 @@
+//! Result envelope for structured (`--json`) command output
+
+use anyhow::Result;
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static EMITTED: AtomicBool = AtomicBool::new(false);
+
+/// The single JSON document a command writes to stdout in `--json` mode
+#[derive(Debug, Clone, Serialize)]
+pub struct CommandResult<T> {
+    pub ok: bool,
+    pub data: Option<T>,
+    pub warnings: Vec<String>,
+    pub errors: Vec<String>,
+}
+
+impl<T: Serialize> CommandResult<T> {
+    /// A successful result carrying `data`
+    pub fn success(data: T) -> Self {
+        Self {
+            ok: true,
+            data: Some(data),
+            warnings: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    /// A failed result with no data
+    pub fn failure(error: impl Into<String>) -> Self {
+        Self {
+            ok: false,
+            data: None,
+            warnings: Vec::new(),
+            errors: vec![error.into()],
+        }
+    }
+
+    /// Attach non-fatal warnings
+    pub fn with_warnings(mut self, warnings: Vec<String>) -> Self {
+        self.warnings.extend(warnings);
+        self
+    }
+
+    /// Attach errors; any error marks the result as not ok
+    pub fn with_errors(mut self, errors: Vec<String>) -> Self {
+        self.ok &= errors.is_empty();
+        self.errors.extend(errors);
+        self
+    }
+
+    /// Write the result to stdout as pretty-printed JSON
+    pub fn emit(&self) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        println!("{json}");
+        EMITTED.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+/// Whether a command has already written its JSON document
+pub fn json_emitted() -> bool {
+    EMITTED.load(Ordering::SeqCst)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_success_serialization() {
+        let result = CommandResult::success(vec!["repo"]).with_warnings(vec!["slow".to_string()]);
+        let value = serde_json::to_value(&result).unwrap();
+
+        assert_eq!(value["ok"], true);
+        assert_eq!(value["data"][0], "repo");
+        assert_eq!(value["warnings"][0], "slow");
+        assert!(value["errors"].as_array().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_errors_mark_result_not_ok() {
+        let result = CommandResult::success(1).with_errors(Vec::new());
+        assert!(result.ok);
+
+        let result = CommandResult::success(1).with_errors(vec!["boom".to_string()]);
+        assert!(!result.ok);
+        assert_eq!(result.data, Some(1));
+
+        let result = CommandResult::<()>::failure("boom");
+        assert!(!result.ok);
+        assert!(result.data.is_none());
+        assert_eq!(result.errors, vec!["boom".to_string()]);
+    }
+}
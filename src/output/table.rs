@@ -0,0 +1,324 @@
+//! Tables that fit the terminal
+//!
+//! Widths are measured as displayed, ignoring ANSI escapes, so colored cells
+//! stay aligned. When the natural widths don't fit, columns are truncated in
+//! their shrink order, and when even their minimum widths don't fit each row
+//! is stacked as `key: value` lines instead.
+
+use console::{measure_text_width, style, truncate_str, Term};
+
+/// Width assumed when stdout isn't a terminal
+pub const DEFAULT_WIDTH: usize = 120;
+
+/// Narrower terminals always get the stacked layout
+pub const MIN_TABLE_WIDTH: usize = 40;
+
+const GAP: usize = 2;
+
+/// Width of the terminal on stdout, or [`DEFAULT_WIDTH`] without one
+pub fn terminal_width() -> usize {
+    Term::stdout()
+        .size_checked()
+        .map(|(_, columns)| columns as usize)
+        .filter(|&columns| columns > 0)
+        .unwrap_or(DEFAULT_WIDTH)
+}
+
+/// `text` cut to at most `width` display columns, ending in `…` when cut
+pub fn truncate(text: &str, width: usize) -> String {
+    if measure_text_width(text) <= width {
+        return text.to_string();
+    }
+    if width == 0 {
+        return String::new();
+    }
+    truncate_str(text, width, "…").into_owned()
+}
+
+/// `text` padded with spaces to `width` display columns
+fn pad(text: &str, width: usize) -> String {
+    let padding = width.saturating_sub(measure_text_width(text));
+    format!("{text}{}", " ".repeat(padding))
+}
+
+/// `pairs` as `key: value` lines with the values aligned, each cut to `width`
+pub fn key_value_lines(pairs: &[(&str, String)], width: usize) -> Vec<String> {
+    let key_width = pairs
+        .iter()
+        .map(|(key, _)| measure_text_width(key) + 1)
+        .max()
+        .unwrap_or(0);
+    pairs
+        .iter()
+        .map(|(key, value)| {
+            truncate(
+                &format!("{} {value}", pad(&format!("{key}:"), key_width)),
+                width,
+            )
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone)]
+pub struct Column {
+    header: String,
+    /// Columns with a lower order are truncated first; `None` keeps the
+    /// column at its natural width
+    shrink_order: Option<u8>,
+    min_width: usize,
+}
+
+impl Column {
+    pub fn new(header: impl Into<String>) -> Self {
+        Self {
+            header: header.into(),
+            shrink_order: None,
+            min_width: 0,
+        }
+    }
+
+    /// Let the column be truncated down to `min_width`, before columns with
+    /// a higher `order`
+    pub fn shrink(mut self, order: u8, min_width: usize) -> Self {
+        self.shrink_order = Some(order);
+        self.min_width = min_width;
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Row {
+    cells: Vec<String>,
+    /// Lines printed under the row, indented past the first column
+    notes: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Table {
+    columns: Vec<Column>,
+    rows: Vec<Row>,
+}
+
+impl Table {
+    pub fn new(columns: Vec<Column>) -> Self {
+        Self {
+            columns,
+            rows: Vec::new(),
+        }
+    }
+
+    /// Add a row of cells, which may contain ANSI styling
+    pub fn add_row(&mut self, cells: Vec<String>) {
+        self.add_row_with_notes(cells, Vec::new());
+    }
+
+    /// Add a row followed by `notes` lines, such as a description
+    pub fn add_row_with_notes(&mut self, cells: Vec<String>, notes: Vec<String>) {
+        debug_assert_eq!(cells.len(), self.columns.len());
+        self.rows.push(Row { cells, notes });
+    }
+
+    /// The table as lines no wider than `width`
+    pub fn render(&self, width: usize) -> Vec<String> {
+        match self.fit(width) {
+            Some(widths) => self.render_columns(&widths, width),
+            None => self.render_stacked(width),
+        }
+    }
+
+    /// Column widths that fit in `width`, or `None` when the table should
+    /// be stacked
+    fn fit(&self, width: usize) -> Option<Vec<usize>> {
+        if width < MIN_TABLE_WIDTH {
+            return None;
+        }
+        let mut widths: Vec<usize> = self
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(i, column)| {
+                self.rows
+                    .iter()
+                    .map(|row| measure_text_width(&row.cells[i]))
+                    .chain([measure_text_width(&column.header)])
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+        let minimums: Vec<usize> = self
+            .columns
+            .iter()
+            .zip(&widths)
+            .map(|(column, &natural)| match column.shrink_order {
+                Some(_) => column.min_width.min(natural),
+                None => natural,
+            })
+            .collect();
+
+        let gaps = GAP * self.columns.len().saturating_sub(1);
+        if minimums.iter().sum::<usize>() + gaps > width {
+            return None;
+        }
+        let mut excess = (widths.iter().sum::<usize>() + gaps).saturating_sub(width);
+
+        let mut order: Vec<usize> = (0..self.columns.len())
+            .filter(|&i| self.columns[i].shrink_order.is_some())
+            .collect();
+        order.sort_by_key(|&i| self.columns[i].shrink_order);
+        for i in order {
+            if excess == 0 {
+                break;
+            }
+            let cut = excess.min(widths[i] - minimums[i]);
+            widths[i] -= cut;
+            excess -= cut;
+        }
+        Some(widths)
+    }
+
+    fn render_columns(&self, widths: &[usize], width: usize) -> Vec<String> {
+        let line = |cells: Vec<String>| -> String {
+            let last = cells.len().saturating_sub(1);
+            let cells: Vec<String> = cells
+                .iter()
+                .enumerate()
+                .map(|(i, cell)| {
+                    let cell = truncate(cell, widths[i]);
+                    if i == last {
+                        cell
+                    } else {
+                        pad(&cell, widths[i])
+                    }
+                })
+                .collect();
+            cells.join(&" ".repeat(GAP))
+        };
+
+        let headers = self
+            .columns
+            .iter()
+            .map(|column| style(&column.header).bold().to_string())
+            .collect();
+        let total = widths.iter().sum::<usize>() + GAP * widths.len().saturating_sub(1);
+        let mut lines = vec![line(headers), "─".repeat(total)];
+
+        let indent = widths.first().map_or(0, |first| first + GAP);
+        for row in &self.rows {
+            lines.push(line(row.cells.clone()));
+            for note in &row.notes {
+                let note = truncate(note, width.saturating_sub(indent));
+                lines.push(format!("{}{note}", " ".repeat(indent)));
+            }
+        }
+        lines
+    }
+
+    fn render_stacked(&self, width: usize) -> Vec<String> {
+        let mut lines = Vec::new();
+        for (i, row) in self.rows.iter().enumerate() {
+            if i > 0 {
+                lines.push(String::new());
+            }
+            let pairs: Vec<(&str, String)> = self
+                .columns
+                .iter()
+                .zip(&row.cells)
+                .filter(|(_, cell)| !cell.is_empty())
+                .map(|(column, cell)| (column.header.as_str(), cell.clone()))
+                .collect();
+            lines.extend(key_value_lines(&pairs, width));
+            lines.extend(row.notes.iter().map(|note| truncate(note, width)));
+        }
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plain(lines: Vec<String>) -> Vec<String> {
+        lines
+            .iter()
+            .map(|line| console::strip_ansi_codes(line).into_owned())
+            .collect()
+    }
+
+    fn table() -> Table {
+        let mut table = Table::new(vec![
+            Column::new("NAME").shrink(2, 6),
+            Column::new("STATE"),
+            Column::new("PATH").shrink(1, 8),
+        ]);
+        table.add_row(vec![
+            "worktree-one".to_string(),
+            style("clean").green().to_string(),
+            "/home/user/projects/worktrees/one".to_string(),
+        ]);
+        table.add_row_with_notes(
+            vec![
+                "two".to_string(),
+                "dirty".to_string(),
+                "/tmp/two".to_string(),
+            ],
+            vec!["Fix the login flow".to_string()],
+        );
+        table
+    }
+
+    #[test]
+    fn test_colored_cells_stay_aligned() {
+        let lines = plain(table().render(80));
+        assert_eq!(lines[1], "─".repeat(54));
+        assert_eq!(
+            [&lines[..1], &lines[2..]].concat(),
+            [
+                "NAME          STATE  PATH",
+                "worktree-one  clean  /home/user/projects/worktrees/one",
+                "two           dirty  /tmp/two",
+                "              Fix the login flow",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_low_priority_columns_are_truncated_first() {
+        let lines = plain(table().render(44));
+        assert_eq!(lines[2], "worktree-one  clean  /home/user/projects/wo…");
+        assert!(lines.iter().all(|line| measure_text_width(line) <= 44));
+
+        // Once PATH is down to its minimum, NAME gives up width too
+        let mut long = table();
+        long.rows[0].cells[0] = "a-much-longer-worktree-name".to_string();
+        let lines = plain(long.render(MIN_TABLE_WIDTH));
+        assert!(lines
+            .iter()
+            .all(|line| measure_text_width(line) <= MIN_TABLE_WIDTH));
+        assert_eq!(lines[2], "a-much-longer-worktree…  clean  /home/u…");
+    }
+
+    #[test]
+    fn test_stacks_below_the_minimum_width() {
+        let lines = plain(table().render(30));
+        assert_eq!(
+            lines,
+            [
+                "NAME:  worktree-one",
+                "STATE: clean",
+                "PATH:  /home/user/projects/wo…",
+                "",
+                "NAME:  two",
+                "STATE: dirty",
+                "PATH:  /tmp/two",
+                "Fix the login flow",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_truncate_counts_wide_characters() {
+        assert_eq!(truncate("日本語テキスト", 7), "日本語…");
+        assert_eq!(truncate("short", 10), "short");
+        assert_eq!(measure_text_width(&truncate("✅ Clean and tidy", 8)), 8);
+    }
+}
@@ -0,0 +1,267 @@
+//! Icons and semantic colors for display output
+//!
+//! Display code asks for an [`Icon`] or a [`Role`] instead of hard-coding
+//! emoji and colors, so `preferences.theme` can swap glyph sets and restyle
+//! text for terminals where the defaults are unreadable.
+
+use console::{Style, StyledObject};
+use serde::{Deserialize, Serialize};
+use std::sync::RwLock;
+
+/// Which glyph set icons are drawn from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IconMode {
+    #[default]
+    Emoji,
+    /// Bracketed ASCII marks; decorative icons are dropped
+    Ascii,
+    /// Glyphs from a patched Nerd Font
+    Nerdfont,
+}
+
+/// Style overrides for the semantic roles, written in console's dotted
+/// notation, e.g. `"magenta.bold"`, `"bright.white"`, or `"214"`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ThemeColors {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub success: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub warning: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dim: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accent: Option<String>,
+}
+
+/// `preferences.theme` in config.yaml
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ThemePreferences {
+    #[serde(default)]
+    pub icons: IconMode,
+    #[serde(default)]
+    pub colors: ThemeColors,
+}
+
+/// Semantic text styles
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    Success,
+    Warning,
+    Error,
+    Dim,
+    Accent,
+}
+
+impl Role {
+    fn default_style(self) -> &'static str {
+        match self {
+            Role::Success => "green",
+            Role::Warning => "yellow",
+            Role::Error => "red",
+            Role::Dim => "dim",
+            Role::Accent => "cyan",
+        }
+    }
+
+    fn override_in(self, colors: &ThemeColors) -> Option<&str> {
+        match self {
+            Role::Success => colors.success.as_deref(),
+            Role::Warning => colors.warning.as_deref(),
+            Role::Error => colors.error.as_deref(),
+            Role::Dim => colors.dim.as_deref(),
+            Role::Accent => colors.accent.as_deref(),
+        }
+    }
+}
+
+/// Icons used across menus and status output
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Icon {
+    // Status marks, always rendered
+    Success,
+    Warning,
+    Error,
+    Info,
+    Attention,
+    Locked,
+    // Decorative icons, dropped in ASCII mode
+    Rocket,
+    Folder,
+    New,
+    Download,
+    Apps,
+    Repos,
+    Worktree,
+    Settings,
+    Report,
+    Merge,
+    Cloud,
+    Package,
+    Celebrate,
+    Tip,
+    Edit,
+    Unknown,
+}
+
+impl Icon {
+    /// The glyph for this icon in `mode`
+    pub fn glyph(self, mode: IconMode) -> &'static str {
+        match mode {
+            IconMode::Emoji => self.emoji(),
+            IconMode::Ascii => self.ascii(),
+            IconMode::Nerdfont => self.nerdfont(),
+        }
+    }
+
+    fn emoji(self) -> &'static str {
+        match self {
+            Icon::Success => "✅",
+            Icon::Warning => "⚠️",
+            Icon::Error => "❌",
+            Icon::Info => "ℹ️",
+            Icon::Attention => "⚡",
+            Icon::Locked => "🔒",
+            Icon::Rocket => "🚀",
+            Icon::Folder => "📂",
+            Icon::New => "🆕",
+            Icon::Download => "📥",
+            Icon::Apps => "⚙️",
+            Icon::Repos => "🔀",
+            Icon::Worktree => "🌳",
+            Icon::Settings => "⚙️",
+            Icon::Report => "📊",
+            Icon::Merge => "🔀",
+            Icon::Cloud => "☁️",
+            Icon::Package => "📦",
+            Icon::Celebrate => "🎉",
+            Icon::Tip => "💡",
+            Icon::Edit => "📝",
+            Icon::Unknown => "❓",
+        }
+    }
+
+    fn ascii(self) -> &'static str {
+        match self {
+            Icon::Success => "[ok]",
+            Icon::Warning => "[!]",
+            Icon::Error => "[x]",
+            Icon::Info => "[i]",
+            Icon::Attention => "[*]",
+            Icon::Locked => "[locked]",
+            _ => "",
+        }
+    }
+
+    fn nerdfont(self) -> &'static str {
+        match self {
+            Icon::Success => "\u{f00c}",
+            Icon::Warning => "\u{f071}",
+            Icon::Error => "\u{f00d}",
+            Icon::Info => "\u{f05a}",
+            Icon::Attention => "\u{f0e7}",
+            Icon::Locked => "\u{f023}",
+            Icon::Rocket => "\u{f135}",
+            Icon::Folder => "\u{f07c}",
+            Icon::New => "\u{f067}",
+            Icon::Download => "\u{f019}",
+            Icon::Apps => "\u{f00a}",
+            Icon::Repos => "\u{f126}",
+            Icon::Worktree => "\u{f1bb}",
+            Icon::Settings => "\u{f013}",
+            Icon::Report => "\u{f080}",
+            Icon::Merge => "\u{f419}",
+            Icon::Cloud => "\u{f0c2}",
+            Icon::Package => "\u{f187}",
+            Icon::Celebrate => "\u{f091}",
+            Icon::Tip => "\u{f0eb}",
+            Icon::Edit => "\u{f040}",
+            Icon::Unknown => "\u{f128}",
+        }
+    }
+}
+
+static THEME: RwLock<Option<ThemePreferences>> = RwLock::new(None);
+
+/// Apply the theme from the user's preferences
+pub fn set_theme(preferences: ThemePreferences) {
+    *THEME.write().unwrap() = Some(preferences);
+}
+
+/// The active icon mode; `--plain` always uses ASCII
+pub fn icon_mode() -> IconMode {
+    if super::is_plain() {
+        return IconMode::Ascii;
+    }
+    THEME
+        .read()
+        .unwrap()
+        .as_ref()
+        .map(|theme| theme.icons)
+        .unwrap_or_default()
+}
+
+/// The glyph for `icon` in the active theme (may be empty in ASCII mode)
+pub fn icon(icon: Icon) -> &'static str {
+    icon.glyph(icon_mode())
+}
+
+/// `text` prefixed with `icon`, without a stray space when the icon is empty
+pub fn labeled(icon: Icon, text: &str) -> String {
+    match self::icon(icon) {
+        "" => text.to_string(),
+        glyph => format!("{glyph} {text}"),
+    }
+}
+
+/// The console style for `role` in the active theme
+pub fn style_for(role: Role) -> Style {
+    let theme = THEME.read().unwrap();
+    let spec = theme
+        .as_ref()
+        .and_then(|theme| role.override_in(&theme.colors))
+        .unwrap_or(role.default_style());
+    Style::from_dotted_str(spec)
+}
+
+/// Style `value` for `role` in the active theme
+pub fn paint<D>(role: Role, value: D) -> StyledObject<D> {
+    style_for(role).apply_to(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ascii_mode_keeps_status_marks_and_drops_decoration() {
+        assert_eq!(Icon::Success.glyph(IconMode::Ascii), "[ok]");
+        assert_eq!(Icon::Warning.glyph(IconMode::Ascii), "[!]");
+        assert_eq!(Icon::Attention.glyph(IconMode::Ascii), "[*]");
+        assert_eq!(Icon::Rocket.glyph(IconMode::Ascii), "");
+        assert_eq!(Icon::Success.glyph(IconMode::Emoji), "✅");
+    }
+
+    #[test]
+    fn test_role_overrides() {
+        let colors = ThemeColors {
+            dim: Some("white".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(Role::Dim.override_in(&colors), Some("white"));
+        assert_eq!(Role::Warning.override_in(&colors), None);
+    }
+
+    #[test]
+    fn test_theme_preferences_parse() {
+        let theme: ThemePreferences =
+            serde_yaml::from_str("icons: nerdfont\ncolors:\n  warning: magenta.bold\n").unwrap();
+        assert_eq!(theme.icons, IconMode::Nerdfont);
+        assert_eq!(theme.colors.warning.as_deref(), Some("magenta.bold"));
+
+        let theme: ThemePreferences = serde_yaml::from_str("{}").unwrap();
+        assert_eq!(theme.icons, IconMode::Emoji);
+    }
+}
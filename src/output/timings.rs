@@ -0,0 +1,338 @@
+//! Per-command timing (`--timings`)
+//!
+//! Major phases open a span through [`phase`] on the [`TARGET`] tracing
+//! target. [`TimingsLayer`] notes how long each of those spans stayed open,
+//! but only while a [`Recording`] is active, so the spans cost next to
+//! nothing otherwise. Finishing a recording gives a [`TimingReport`] with
+//! counts and cumulative durations per phase plus the slowest single git and
+//! provider operations. Nested spans are counted in every phase they belong
+//! to, and recordings that overlap (concurrent MCP tool calls) both see each
+//! other's operations.
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id};
+use tracing::{Span, Subscriber};
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+use crate::display_eprintln;
+
+/// Tracing target of phase spans
+pub const TARGET: &str = "vibe::timings";
+
+/// Loading the workspace configuration
+pub const CONFIG: &str = "config";
+/// Opening the SQLite caches
+pub const CACHE: &str = "cache";
+/// A git command or status read in one repository
+pub const GIT: &str = "git";
+/// A call to a hosting provider through `gh`
+pub const PROVIDER: &str = "provider";
+/// Printing results
+pub const RENDER: &str = "render";
+
+/// How many operations the slowest list keeps
+const SLOWEST_LIMIT: usize = 10;
+
+/// Number of active recordings
+static RECORDINGS: AtomicUsize = AtomicUsize::new(0);
+static OPERATIONS: Mutex<Vec<Operation>> = Mutex::new(Vec::new());
+
+/// Open a span timing one operation of `phase`, e.g. a git command in one
+/// repository. `.instrument()` it onto a future, or `.entered()` it for
+/// synchronous work.
+pub fn phase(phase: &'static str, detail: impl fmt::Display) -> Span {
+    tracing::trace_span!(target: TARGET, "phase", phase, detail = %detail)
+}
+
+/// One finished phase span
+#[derive(Debug, Clone)]
+struct Operation {
+    phase: String,
+    detail: String,
+    duration: Duration,
+}
+
+/// Collects timings from the moment it is started until it is finished
+pub struct Recording {
+    started: Instant,
+    /// Index of the first operation that belongs to this recording
+    first: usize,
+}
+
+impl Recording {
+    pub fn start() -> Self {
+        let mut operations = OPERATIONS.lock().unwrap();
+        if RECORDINGS.fetch_add(1, Ordering::SeqCst) == 0 {
+            operations.clear();
+        }
+        Self {
+            started: Instant::now(),
+            first: operations.len(),
+        }
+    }
+
+    /// Stop recording and summarize what was timed
+    pub fn finish(self) -> TimingReport {
+        let operations = OPERATIONS.lock().unwrap();
+        TimingReport::new(&operations[self.first..], self.started.elapsed())
+    }
+}
+
+impl Drop for Recording {
+    fn drop(&mut self) {
+        let mut operations = OPERATIONS.lock().unwrap();
+        if RECORDINGS.fetch_sub(1, Ordering::SeqCst) == 1 {
+            operations.clear();
+        }
+    }
+}
+
+fn is_recording() -> bool {
+    RECORDINGS.load(Ordering::Relaxed) > 0
+}
+
+/// Counts and cumulative duration of one phase
+#[derive(Debug, Clone, Serialize)]
+pub struct PhaseTiming {
+    pub phase: String,
+    pub count: usize,
+    pub total_ms: f64,
+}
+
+/// A single timed operation
+#[derive(Debug, Clone, Serialize)]
+pub struct OperationTiming {
+    pub phase: String,
+    pub detail: String,
+    pub duration_ms: f64,
+}
+
+/// Where the time of a command went
+#[derive(Debug, Clone, Serialize)]
+pub struct TimingReport {
+    pub total_ms: f64,
+    /// Phases by cumulative duration, longest first
+    pub phases: Vec<PhaseTiming>,
+    /// The slowest git and provider operations, slowest first
+    pub slowest: Vec<OperationTiming>,
+}
+
+impl TimingReport {
+    fn new(operations: &[Operation], elapsed: Duration) -> Self {
+        let mut totals: HashMap<&str, (usize, Duration)> = HashMap::new();
+        for operation in operations {
+            let entry = totals.entry(&operation.phase).or_default();
+            entry.0 += 1;
+            entry.1 += operation.duration;
+        }
+        let mut phases: Vec<_> = totals
+            .into_iter()
+            .map(|(phase, (count, total))| PhaseTiming {
+                phase: phase.to_string(),
+                count,
+                total_ms: millis(total),
+            })
+            .collect();
+        phases.sort_by(|a, b| {
+            b.total_ms
+                .total_cmp(&a.total_ms)
+                .then_with(|| a.phase.cmp(&b.phase))
+        });
+
+        let mut slowest: Vec<_> = operations
+            .iter()
+            .filter(|operation| operation.phase == GIT || operation.phase == PROVIDER)
+            .collect();
+        slowest.sort_by(|a, b| b.duration.cmp(&a.duration));
+        let slowest = slowest
+            .into_iter()
+            .take(SLOWEST_LIMIT)
+            .map(|operation| OperationTiming {
+                phase: operation.phase.clone(),
+                detail: operation.detail.clone(),
+                duration_ms: millis(operation.duration),
+            })
+            .collect();
+
+        Self {
+            total_ms: millis(elapsed),
+            phases,
+            slowest,
+        }
+    }
+
+    /// Print the report to stderr, out of the way of command output
+    pub fn print(&self) {
+        display_eprintln!();
+        display_eprintln!("Timings (total {})", format_ms(self.total_ms));
+        if self.phases.is_empty() {
+            display_eprintln!("  No phases were timed");
+            return;
+        }
+
+        display_eprintln!("  {:<10} {:>7} {:>10}", "PHASE", "COUNT", "TOTAL");
+        for phase in &self.phases {
+            display_eprintln!(
+                "  {:<10} {:>7} {:>10}",
+                phase.phase,
+                phase.count,
+                format_ms(phase.total_ms)
+            );
+        }
+
+        if !self.slowest.is_empty() {
+            display_eprintln!();
+            display_eprintln!("Slowest operations");
+            for operation in &self.slowest {
+                display_eprintln!(
+                    "  {:>10}  {:<8} {}",
+                    format_ms(operation.duration_ms),
+                    operation.phase,
+                    operation.detail
+                );
+            }
+        }
+    }
+}
+
+fn millis(duration: Duration) -> f64 {
+    (duration.as_secs_f64() * 10_000.0).round() / 10.0
+}
+
+fn format_ms(ms: f64) -> String {
+    if ms >= 1000.0 {
+        format!("{:.2}s", ms / 1000.0)
+    } else {
+        format!("{ms:.1}ms")
+    }
+}
+
+/// Tracing layer that times phase spans while a [`Recording`] is active
+pub struct TimingsLayer;
+
+/// Stored on a phase span when it opens
+struct Started {
+    phase: String,
+    detail: String,
+    at: Instant,
+}
+
+#[derive(Default)]
+struct PhaseFields {
+    phase: String,
+    detail: String,
+}
+
+impl Visit for PhaseFields {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        match field.name() {
+            "phase" => self.phase = value.to_string(),
+            "detail" => self.detail = value.to_string(),
+            _ => {}
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        match field.name() {
+            "phase" => self.phase = format!("{value:?}"),
+            "detail" => self.detail = format!("{value:?}"),
+            _ => {}
+        }
+    }
+}
+
+impl<S> Layer<S> for TimingsLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        if !is_recording() || attrs.metadata().target() != TARGET {
+            return;
+        }
+        let mut fields = PhaseFields::default();
+        attrs.record(&mut fields);
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(Started {
+                phase: fields.phase,
+                detail: fields.detail,
+                at: Instant::now(),
+            });
+        }
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        let Some(started) = span.extensions_mut().remove::<Started>() else {
+            return;
+        };
+        if is_recording() {
+            OPERATIONS.lock().unwrap().push(Operation {
+                phase: started.phase,
+                detail: started.detail,
+                duration: started.at.elapsed(),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn operation(phase: &str, detail: &str, ms: u64) -> Operation {
+        Operation {
+            phase: phase.to_string(),
+            detail: detail.to_string(),
+            duration: Duration::from_millis(ms),
+        }
+    }
+
+    #[test]
+    fn test_report_totals_phases_and_ranks_slowest() {
+        let report = TimingReport::new(
+            &[
+                operation(CONFIG, "load", 5),
+                operation(GIT, "api: status", 30),
+                operation(GIT, "web: status", 70),
+                operation(PROVIDER, "gh repo list", 50),
+                operation(RENDER, "status summary", 2),
+            ],
+            Duration::from_millis(200),
+        );
+
+        assert_eq!(report.total_ms, 200.0);
+        let phases: Vec<_> = report
+            .phases
+            .iter()
+            .map(|p| (p.phase.as_str(), p.count, p.total_ms))
+            .collect();
+        assert_eq!(
+            phases,
+            vec![
+                (GIT, 2, 100.0),
+                (PROVIDER, 1, 50.0),
+                (CONFIG, 1, 5.0),
+                (RENDER, 1, 2.0)
+            ]
+        );
+
+        let slowest: Vec<_> = report.slowest.iter().map(|o| o.detail.as_str()).collect();
+        assert_eq!(slowest, vec!["web: status", "gh repo list", "api: status"]);
+    }
+
+    #[test]
+    fn test_format_ms() {
+        assert_eq!(format_ms(12.34), "12.3ms");
+        assert_eq!(format_ms(1500.0), "1.50s");
+    }
+}
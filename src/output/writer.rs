@@ -1,6 +1,7 @@
 //! Low-level writing logic for output routing
 
 use super::config::OutputMode;
+use super::display::strip_icons;
 use std::io::{self, Write};
 
 /// Write output based on the current mode and output type
@@ -9,6 +10,27 @@ pub fn write_output(
     is_display: bool,
     args: std::fmt::Arguments,
 ) -> io::Result<()> {
+    if is_display && super::is_plain() {
+        let text = strip_icons(&args.to_string());
+        return write_routed(mode, is_display, format_args!("{text}"));
+    }
+    write_routed(mode, is_display, args)
+}
+
+/// Write output with newline based on the current mode and output type
+pub fn writeln_output(
+    mode: OutputMode,
+    is_display: bool,
+    args: std::fmt::Arguments,
+) -> io::Result<()> {
+    if is_display && super::is_plain() {
+        let text = strip_icons(&args.to_string());
+        return writeln_routed(mode, is_display, format_args!("{text}"));
+    }
+    writeln_routed(mode, is_display, args)
+}
+
+fn write_routed(mode: OutputMode, is_display: bool, args: std::fmt::Arguments) -> io::Result<()> {
     match (mode, is_display) {
         // In CLI mode, display goes to stdout, logs go to stderr
         (OutputMode::Cli, true) => {
@@ -19,20 +41,27 @@ pub fn write_output(
             eprint!("{args}");
             io::stderr().flush()
         }
-        // In MCP mode, everything goes to stderr
-        (OutputMode::Mcp, _) => {
+        // In MCP and JSON modes, stdout is reserved for the structured document,
+        // and in capture mode for the result
+        (OutputMode::Mcp | OutputMode::Json | OutputMode::Capture, _) => {
             eprint!("{args}");
             io::stderr().flush()
         }
+        // Without a terminal stdout may be closed, so never panic on write
+        (OutputMode::NonInteractive, true) => {
+            let mut stdout = io::stdout().lock();
+            stdout.write_fmt(args)?;
+            stdout.flush()
+        }
+        (OutputMode::NonInteractive, false) => {
+            let mut stderr = io::stderr().lock();
+            stderr.write_fmt(args)?;
+            stderr.flush()
+        }
     }
 }
 
-/// Write output with newline based on the current mode and output type
-pub fn writeln_output(
-    mode: OutputMode,
-    is_display: bool,
-    args: std::fmt::Arguments,
-) -> io::Result<()> {
+fn writeln_routed(mode: OutputMode, is_display: bool, args: std::fmt::Arguments) -> io::Result<()> {
     match (mode, is_display) {
         // In CLI mode, display goes to stdout, logs go to stderr
         (OutputMode::Cli, true) => {
@@ -43,10 +72,22 @@ pub fn writeln_output(
             eprintln!("{args}");
             io::stderr().flush()
         }
-        // In MCP mode, everything goes to stderr
-        (OutputMode::Mcp, _) => {
+        // In MCP and JSON modes, stdout is reserved for the structured document,
+        // and in capture mode for the result
+        (OutputMode::Mcp | OutputMode::Json | OutputMode::Capture, _) => {
             eprintln!("{args}");
             io::stderr().flush()
         }
+        // Without a terminal stdout may be closed, so never panic on write
+        (OutputMode::NonInteractive, true) => {
+            let mut stdout = io::stdout().lock();
+            writeln!(stdout, "{args}")?;
+            stdout.flush()
+        }
+        (OutputMode::NonInteractive, false) => {
+            let mut stderr = io::stderr().lock();
+            writeln!(stderr, "{args}")?;
+            stderr.flush()
+        }
     }
 }
@@ -159,9 +159,14 @@ impl RepositoryCreator {
             name: repo_name.to_string(),
             path: PathBuf::from(owner).join(repo_name),
             url: Some(format!("https://github.com/{owner}/{repo_name}")),
+            upstream: None,
+            license: None,
             branch: Some("main".to_string()),
             apps: std::collections::HashMap::new(),
             worktree_config: None,
+            subprojects: Vec::new(),
+            exclude: Vec::new(),
+            compare_ref: None,
         };
 
         workspace_manager.add_repository(repository_config).await?;
@@ -1,3 +1,4 @@
 pub mod create;
+pub mod template;
 
 pub use create::*;
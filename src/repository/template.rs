@@ -0,0 +1,506 @@
+//! Generate a new repository from a template repository
+//!
+//! `vibe clone <template> --as <name>` copies the template's latest tree
+//! without its history, replaces `{{placeholder}}` markers in file contents
+//! and file names, and registers the result as a new workspace repository.
+//! A `template.vibe.yaml` at the template root declares extra placeholders
+//! with their defaults and paths to leave alone; it is removed from the
+//! generated repository. `project_name` and `project_name_snake` are always
+//! defined from the new name.
+
+use anyhow::{Context, Result};
+use console::style;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tokio::process::Command;
+use walkdir::WalkDir;
+
+use crate::display_println;
+use crate::git::GitError;
+use crate::repository::RepositoryCreator;
+use crate::workspace::{Repository, WorkspaceManager};
+
+/// Template settings file at the root of a template repository
+pub const MANIFEST_FILE: &str = "template.vibe.yaml";
+
+/// Files whose first bytes contain a NUL are treated as binary
+const BINARY_SNIFF_LEN: usize = 8000;
+
+/// Contents of `template.vibe.yaml`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TemplateManifest {
+    /// Placeholder names and their default values
+    #[serde(default)]
+    pub variables: BTreeMap<String, String>,
+
+    /// Glob patterns, relative to the template root, never substituted
+    #[serde(default)]
+    pub exclude: Vec<String>,
+}
+
+impl TemplateManifest {
+    /// Read the manifest in `root`, or the defaults when there is none
+    pub fn load(root: &Path) -> Result<Self> {
+        let path = root.join(MANIFEST_FILE);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_yaml::from_str(&content).with_context(|| format!("Invalid {MANIFEST_FILE}"))
+    }
+
+    /// Placeholder values for a repository called `project_name`: the
+    /// built-ins, then the manifest defaults, then `overrides`
+    pub fn variables_for(
+        &self,
+        project_name: &str,
+        overrides: &[(String, String)],
+    ) -> BTreeMap<String, String> {
+        let mut variables = BTreeMap::new();
+        variables.insert("project_name".to_string(), project_name.to_string());
+        variables.insert(
+            "project_name_snake".to_string(),
+            project_name.replace(['-', '.'], "_").to_lowercase(),
+        );
+        for (name, default) in &self.variables {
+            variables.insert(name.clone(), substitute(default, &variables));
+        }
+        for (name, value) in overrides {
+            variables.insert(name.clone(), value.clone());
+        }
+        variables
+    }
+}
+
+/// Parse a `--var NAME=VALUE` argument
+pub fn parse_variable(arg: &str) -> Result<(String, String)> {
+    let (name, value) = arg
+        .split_once('=')
+        .filter(|(name, _)| !name.trim().is_empty())
+        .with_context(|| format!("Invalid --var '{arg}': expected NAME=VALUE"))?;
+    Ok((name.trim().to_string(), value.to_string()))
+}
+
+/// Replace `{{name}}` and `{{ name }}` for every known variable in `text`.
+/// Unknown placeholders are left as they are.
+pub fn substitute(text: &str, variables: &BTreeMap<String, String>) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let replaced = after.find("}}").and_then(|end| {
+            variables
+                .get(after[..end].trim())
+                .map(|value| (value, end + 2))
+        });
+        match replaced {
+            Some((value, consumed)) => {
+                output.push_str(value);
+                rest = &after[consumed..];
+            }
+            None => {
+                output.push_str("{{");
+                rest = after;
+            }
+        }
+    }
+    output.push_str(rest);
+    output
+}
+
+/// What a substitution pass changed
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct RenderReport {
+    /// Files whose contents changed
+    pub files_updated: usize,
+    /// Files and directories renamed
+    pub paths_renamed: usize,
+    /// Files left alone because they look binary
+    pub binary_skipped: usize,
+}
+
+/// Substitute `variables` in the contents and names of every file under
+/// `root`, skipping `.git`, binary files, and paths matching `exclude`.
+/// Running it again over its own output changes nothing.
+pub fn render_tree(
+    root: &Path,
+    variables: &BTreeMap<String, String>,
+    exclude: &[String],
+) -> Result<RenderReport> {
+    let patterns = exclude
+        .iter()
+        .map(|pattern| {
+            glob::Pattern::new(pattern)
+                .with_context(|| format!("Invalid exclude pattern '{pattern}'"))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let excluded = |relative: &Path| patterns.iter().any(|p| p.matches_path(relative));
+
+    let mut report = RenderReport::default();
+    // Walk in reverse pre-order so children come before their directory, and
+    // renaming a directory never moves a path that is still to be visited
+    let entries: Vec<_> = WalkDir::new(root)
+        .min_depth(1)
+        .into_iter()
+        .filter_entry(|entry| entry.file_name() != ".git")
+        .collect::<std::result::Result<_, _>>()
+        .context("Failed to walk the template")?;
+
+    for entry in entries.into_iter().rev() {
+        let path = entry.path();
+        let relative = path.strip_prefix(root).unwrap_or(path);
+        if excluded(relative) {
+            continue;
+        }
+
+        if entry.file_type().is_file() {
+            let bytes = std::fs::read(path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            let text = match std::str::from_utf8(&bytes) {
+                Ok(text) if !bytes[..bytes.len().min(BINARY_SNIFF_LEN)].contains(&0) => text,
+                _ => {
+                    report.binary_skipped += 1;
+                    continue;
+                }
+            };
+            let rendered = substitute(text, variables);
+            if rendered != text {
+                std::fs::write(path, rendered)
+                    .with_context(|| format!("Failed to write {}", path.display()))?;
+                report.files_updated += 1;
+            }
+        }
+
+        let name = entry.file_name().to_string_lossy();
+        let renamed = substitute(&name, variables);
+        if renamed != name {
+            let target = path.with_file_name(&renamed);
+            if target.exists() {
+                anyhow::bail!(
+                    "Cannot rename {} to {}: it already exists",
+                    relative.display(),
+                    renamed
+                );
+            }
+            std::fs::rename(path, &target)
+                .with_context(|| format!("Failed to rename {}", path.display()))?;
+            report.paths_renamed += 1;
+        }
+    }
+
+    Ok(report)
+}
+
+/// Options for generating a repository from a template
+#[derive(Debug, Clone)]
+pub struct GenerateOptions {
+    /// Template URL or GitHub shorthand (`owner/repo`)
+    pub template: String,
+    /// Name of the new repository
+    pub name: String,
+    /// Create a private GitHub repository and push to it
+    pub create_remote: bool,
+    /// `--var` overrides
+    pub variables: Vec<(String, String)>,
+}
+
+/// Clone `options.template` without history into a new workspace
+/// repository. Returns the new repository's path.
+pub async fn generate_from_template(
+    options: GenerateOptions,
+    workspace_manager: &mut WorkspaceManager,
+) -> Result<PathBuf> {
+    let creator = RepositoryCreator::new(workspace_manager.get_workspace_root().clone());
+    creator.validate_repository_name(&options.name)?;
+    if workspace_manager.get_repository(&options.name).is_some() {
+        anyhow::bail!(
+            "A repository named '{}' is already in the workspace",
+            options.name
+        );
+    }
+
+    let target = workspace_manager.get_workspace_root().join(&options.name);
+    if target.exists() {
+        return Err(GitError::RepositoryExists { path: target }.into());
+    }
+
+    let url = template_url(&options.template);
+    display_println!(
+        "{} Generating {} from template {}",
+        style("📦").cyan(),
+        style(&options.name).cyan().bold(),
+        style(&url).dim()
+    );
+    run_git(
+        None,
+        &["clone", "--depth", "1", &url, &target.to_string_lossy()],
+    )
+    .await
+    .map_err(|e| GitError::CloneFailed {
+        message: format!("{e:#}"),
+    })?;
+
+    // Everything after the clone works on the new directory, so clean it up
+    // if any step fails
+    match render_new_repository(&target, &options).await {
+        Ok(report) => {
+            display_println!(
+                "{} Filled in placeholders: {} file{} updated, {} renamed, {} binary skipped",
+                style("✓").green(),
+                report.files_updated,
+                if report.files_updated == 1 { "" } else { "s" },
+                report.paths_renamed,
+                report.binary_skipped
+            );
+        }
+        Err(e) => {
+            let _ = tokio::fs::remove_dir_all(&target).await;
+            return Err(e);
+        }
+    }
+
+    let remote_url = if options.create_remote {
+        Some(create_remote(&target, &options.name).await?)
+    } else {
+        None
+    };
+
+    workspace_manager
+        .add_repository(Repository {
+            name: options.name.clone(),
+            path: PathBuf::from(&options.name),
+            url: remote_url,
+            upstream: None,
+            license: None,
+            branch: Some("main".to_string()),
+            apps: std::collections::HashMap::new(),
+            worktree_config: None,
+            subprojects: Vec::new(),
+            exclude: Vec::new(),
+            compare_ref: None,
+        })
+        .await?;
+
+    display_println!(
+        "{} Repository '{}' created from template at {}",
+        style("✅").green().bold(),
+        style(&options.name).cyan(),
+        target.display()
+    );
+    Ok(target)
+}
+
+/// Drop the template's history, fill in placeholders, and commit the result
+/// as the first commit of a fresh repository
+async fn render_new_repository(target: &Path, options: &GenerateOptions) -> Result<RenderReport> {
+    tokio::fs::remove_dir_all(target.join(".git"))
+        .await
+        .context("Failed to remove the template's history")?;
+
+    let report = {
+        let target = target.to_path_buf();
+        let name = options.name.clone();
+        let overrides = options.variables.clone();
+        tokio::task::spawn_blocking(move || -> Result<RenderReport> {
+            let manifest = TemplateManifest::load(&target)?;
+            let variables = manifest.variables_for(&name, &overrides);
+            let report = render_tree(&target, &variables, &manifest.exclude)?;
+            let manifest_path = target.join(MANIFEST_FILE);
+            if manifest_path.exists() {
+                std::fs::remove_file(&manifest_path)
+                    .with_context(|| format!("Failed to remove {MANIFEST_FILE}"))?;
+            }
+            Ok(report)
+        })
+        .await??
+    };
+
+    run_git(Some(target), &["init", "-q", "-b", "main"]).await?;
+    run_git(Some(target), &["add", "-A"]).await?;
+    run_git(
+        Some(target),
+        &[
+            "commit",
+            "-q",
+            "-m",
+            &format!("Initial commit from template {}", options.template),
+        ],
+    )
+    .await?;
+    Ok(report)
+}
+
+/// Create a private GitHub repository for `path`, push to it, and return its
+/// URL
+async fn create_remote(path: &Path, name: &str) -> Result<String> {
+    if !crate::utils::git::is_github_cli_available() {
+        return Err(GitError::GitHubCliNotFound.into());
+    }
+    let output = Command::new("gh")
+        .args(["repo", "create", name, "--private", "--source"])
+        .arg(path)
+        .args(["--remote", "origin", "--push"])
+        .output()
+        .await
+        .context("Failed to run gh repo create")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "gh repo create failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    let remote = run_git(Some(path), &["remote", "get-url", "origin"]).await?;
+    display_println!(
+        "{} Created GitHub repository {}",
+        style("✓").green(),
+        style(&remote).cyan()
+    );
+    Ok(remote)
+}
+
+/// GitHub shorthand (`owner/repo`) as a clone URL; anything else unchanged
+pub(crate) fn template_url(template: &str) -> String {
+    let is_shorthand = template.split('/').count() == 2
+        && !template.contains(':')
+        && !template.starts_with('.')
+        && !Path::new(template).exists();
+    if is_shorthand {
+        format!("https://github.com/{template}.git")
+    } else {
+        template.to_string()
+    }
+}
+
+async fn run_git(dir: Option<&Path>, args: &[&str]) -> Result<String> {
+    let mut command = Command::new("git");
+    command.args(args);
+    if let Some(dir) = dir {
+        command.current_dir(dir);
+    }
+    let output = command
+        .output()
+        .await
+        .with_context(|| format!("Failed to run git {}", args[0]))?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "git {} failed: {}",
+            args[0],
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn variables(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_substitute() {
+        let vars = variables(&[("project_name", "my-service")]);
+        assert_eq!(
+            substitute("name = \"{{project_name}}\" # {{ project_name }}", &vars),
+            "name = \"my-service\" # my-service"
+        );
+        // Unknown and unterminated placeholders stay as written
+        assert_eq!(
+            substitute("{{other}} {{project_name", &vars),
+            "{{other}} {{project_name"
+        );
+        assert_eq!(substitute("${{ github.ref }}", &vars), "${{ github.ref }}");
+    }
+
+    #[test]
+    fn test_variables_for() {
+        let manifest = TemplateManifest {
+            variables: variables(&[("crate_name", "{{project_name_snake}}"), ("port", "8080")]),
+            exclude: Vec::new(),
+        };
+        let vars = manifest.variables_for("My-Service", &[("port".into(), "9000".into())]);
+        assert_eq!(vars["project_name"], "My-Service");
+        assert_eq!(vars["crate_name"], "my_service");
+        assert_eq!(vars["port"], "9000");
+    }
+
+    #[test]
+    fn test_render_tree_skips_binary_and_is_idempotent() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("src/{{project_name_snake}}")).unwrap();
+        std::fs::write(
+            root.join("src/{{project_name_snake}}/mod.rs"),
+            "// {{project_name}}\n",
+        )
+        .unwrap();
+        std::fs::write(root.join("README.md"), "# {{project_name}}\n").unwrap();
+        let binary = b"\x89PNG\r\n\x1a\n\0{{project_name}}".to_vec();
+        std::fs::write(root.join("logo.png"), &binary).unwrap();
+        std::fs::write(root.join("keep.txt"), "{{project_name}}").unwrap();
+        std::fs::create_dir_all(root.join(".git")).unwrap();
+        std::fs::write(root.join(".git/config"), "{{project_name}}").unwrap();
+
+        let vars = TemplateManifest::default().variables_for("my-app", &[]);
+        let exclude = vec!["keep.txt".to_string()];
+        let report = render_tree(root, &vars, &exclude).unwrap();
+        assert_eq!(
+            report,
+            RenderReport {
+                files_updated: 2,
+                paths_renamed: 1,
+                binary_skipped: 1,
+            }
+        );
+        assert_eq!(
+            std::fs::read_to_string(root.join("src/my_app/mod.rs")).unwrap(),
+            "// my-app\n"
+        );
+        assert_eq!(std::fs::read(root.join("logo.png")).unwrap(), binary);
+        assert_eq!(
+            std::fs::read_to_string(root.join("keep.txt")).unwrap(),
+            "{{project_name}}"
+        );
+        assert_eq!(
+            std::fs::read_to_string(root.join(".git/config")).unwrap(),
+            "{{project_name}}"
+        );
+
+        let again = render_tree(root, &vars, &exclude).unwrap();
+        assert_eq!(again.files_updated, 0);
+        assert_eq!(again.paths_renamed, 0);
+    }
+
+    #[test]
+    fn test_parse_variable() {
+        assert_eq!(
+            parse_variable("port=9000").unwrap(),
+            ("port".to_string(), "9000".to_string())
+        );
+        assert_eq!(parse_variable("empty=").unwrap().1, "");
+        assert!(parse_variable("novalue").is_err());
+        assert!(parse_variable("=x").is_err());
+    }
+
+    #[test]
+    fn test_template_url() {
+        assert_eq!(
+            template_url("acme/service-template"),
+            "https://github.com/acme/service-template.git"
+        );
+        assert_eq!(
+            template_url("git@github.com:acme/t.git"),
+            "git@github.com:acme/t.git"
+        );
+    }
+}
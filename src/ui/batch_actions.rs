@@ -0,0 +1,442 @@
+//! Batch actions over a multi-selection of repositories
+//!
+//! Every prompt runs before anything is executed, so ESC at any step leaves
+//! the workspace untouched.
+
+use anyhow::Result;
+use console::style;
+use inquire::{Confirm, MultiSelect, Select, Text};
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::output::exit::BatchSummary;
+use crate::ui::prompts::handle_prompt_result;
+use crate::ui::repo_picker;
+use crate::workspace::config::RepositoryGroup;
+use crate::workspace::manager::SyncOutcome;
+use crate::workspace::WorkspaceManager;
+use crate::{display_eprintln, display_print, display_println};
+
+/// An action that can be applied to every selected repository
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchAction {
+    Sync,
+    Fetch,
+    Open,
+    ConfigureApp,
+    AddToGroup,
+    Remove,
+}
+
+impl BatchAction {
+    pub const ALL: [BatchAction; 6] = [
+        BatchAction::Sync,
+        BatchAction::Fetch,
+        BatchAction::Open,
+        BatchAction::ConfigureApp,
+        BatchAction::AddToGroup,
+        BatchAction::Remove,
+    ];
+}
+
+impl fmt::Display for BatchAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            BatchAction::Sync => "🔄 Sync (fetch and pull)",
+            BatchAction::Fetch => "📥 Fetch only",
+            BatchAction::Open => "🚀 Open in app",
+            BatchAction::ConfigureApp => "⚙️  Configure an app",
+            BatchAction::AddToGroup => "👥 Add to group",
+            BatchAction::Remove => "🗑️  Remove from workspace",
+        };
+        f.write_str(label)
+    }
+}
+
+/// Everything needed to run an action, gathered before execution starts
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum BatchPlan {
+    Sync { fetch_only: bool },
+    Open { app: String },
+    ConfigureApp { app: String, template: String },
+    AddToGroup { group: String },
+    Remove,
+}
+
+/// Outcome of a batch action for one repository
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchResult {
+    pub name: String,
+    pub error: Option<String>,
+}
+
+impl BatchResult {
+    fn from_result(name: &str, result: Result<()>) -> Self {
+        Self {
+            name: name.to_string(),
+            error: result.err().map(|e| e.to_string()),
+        }
+    }
+}
+
+/// Count the failures in a set of batch results
+pub fn summarize(results: &[BatchResult]) -> BatchSummary {
+    BatchSummary {
+        total: results.len(),
+        failed: results.iter().filter(|r| r.error.is_some()).count(),
+    }
+}
+
+/// Interactive flow: pick repositories, pick an action, then run it on each
+pub async fn run_batch_actions(workspace_manager: &mut WorkspaceManager) -> Result<()> {
+    if workspace_manager.list_repositories().is_empty() {
+        display_println!(
+            "{} No repositories configured. Run 'vibe git scan' first.",
+            style("ℹ").yellow()
+        );
+        return Ok(());
+    }
+
+    let git_statuses = repo_picker::load_cached_git_statuses().await;
+    let items = repo_picker::items_for_workspace(workspace_manager.config(), &git_statuses);
+
+    let Some(selected) = handle_prompt_result(
+        MultiSelect::new("Select repositories:", items)
+            .with_page_size(workspace_manager.get_repository_list_page_size())
+            .with_help_message(
+                "Space to select, → to select all, Enter to confirm • ESC to go back",
+            )
+            .prompt(),
+    )?
+    else {
+        return Ok(());
+    };
+    if selected.is_empty() {
+        display_println!("{} No repositories selected", style("ℹ").yellow());
+        return Ok(());
+    }
+    let repo_names: Vec<String> = selected.into_iter().map(|item| item.name).collect();
+
+    let Some(action) = handle_prompt_result(
+        Select::new(
+            &format!("Action for {} repositories:", repo_names.len()),
+            BatchAction::ALL.to_vec(),
+        )
+        .with_help_message("ESC to go back")
+        .prompt(),
+    )?
+    else {
+        return Ok(());
+    };
+
+    let Some(plan) = plan_action(workspace_manager, action, repo_names.len()).await? else {
+        return Ok(());
+    };
+
+    let results = execute_plan(workspace_manager, &plan, &repo_names).await?;
+    print_batch_summary(&results);
+
+    Ok(())
+}
+
+/// Ask for whatever the action needs. Returns `None` if the user backs out.
+async fn plan_action(
+    workspace_manager: &WorkspaceManager,
+    action: BatchAction,
+    count: usize,
+) -> Result<Option<BatchPlan>> {
+    let plan = match action {
+        BatchAction::Sync => Some(BatchPlan::Sync { fetch_only: false }),
+        BatchAction::Fetch => Some(BatchPlan::Sync { fetch_only: true }),
+        BatchAction::Open => select_app(workspace_manager, "Open with:")
+            .await?
+            .map(|app| BatchPlan::Open { app }),
+        BatchAction::ConfigureApp => {
+            let Some(app) = select_app(workspace_manager, "App to configure:").await? else {
+                return Ok(None);
+            };
+            select_template(workspace_manager, &app)
+                .await?
+                .map(|template| BatchPlan::ConfigureApp { app, template })
+        }
+        BatchAction::AddToGroup => {
+            select_or_name_group(workspace_manager)?.map(|group| BatchPlan::AddToGroup { group })
+        }
+        BatchAction::Remove => {
+            let confirmed = handle_prompt_result(
+                Confirm::new(&format!(
+                    "Remove {count} repositories from the workspace? Files on disk are kept."
+                ))
+                .with_default(false)
+                .prompt(),
+            )?;
+            (confirmed == Some(true)).then_some(BatchPlan::Remove)
+        }
+    };
+
+    Ok(plan)
+}
+
+async fn select_app(workspace_manager: &WorkspaceManager, message: &str) -> Result<Option<String>> {
+    let apps = workspace_manager.get_available_apps().await;
+    if apps.is_empty() {
+        display_println!("{} No supported apps are installed", style("ℹ").yellow());
+        return Ok(None);
+    }
+
+    handle_prompt_result(
+        Select::new(message, apps)
+            .with_page_size(workspace_manager.get_app_selection_page_size())
+            .with_help_message("ESC to go back")
+            .prompt(),
+    )
+}
+
+async fn select_template(
+    workspace_manager: &WorkspaceManager,
+    app: &str,
+) -> Result<Option<String>> {
+    let templates = workspace_manager.list_templates(app).await?;
+    match templates.len() {
+        0 => {
+            display_println!(
+                "{} No templates found for {}. Run 'vibe apps template update-defaults' first.",
+                style("ℹ").yellow(),
+                app
+            );
+            Ok(None)
+        }
+        1 => Ok(templates.into_iter().next()),
+        _ => handle_prompt_result(
+            Select::new("Template:", templates)
+                .with_help_message("ESC to go back")
+                .prompt(),
+        ),
+    }
+}
+
+fn select_or_name_group(workspace_manager: &WorkspaceManager) -> Result<Option<String>> {
+    const NEW_GROUP: &str = "➕ New group";
+
+    let mut options: Vec<String> = workspace_manager
+        .config()
+        .groups
+        .iter()
+        .map(|group| group.name.clone())
+        .collect();
+    options.push(NEW_GROUP.to_string());
+
+    let Some(choice) = handle_prompt_result(
+        Select::new("Add to group:", options)
+            .with_help_message("ESC to go back")
+            .prompt(),
+    )?
+    else {
+        return Ok(None);
+    };
+    if choice != NEW_GROUP {
+        return Ok(Some(choice));
+    }
+
+    let name = handle_prompt_result(
+        Text::new("Group name:")
+            .with_help_message("ESC to go back")
+            .prompt(),
+    )?;
+    let Some(name) = name.map(|name| name.trim().to_string()) else {
+        return Ok(None);
+    };
+    if let Err(reason) = workspace_manager.config().validate_new_group_name(&name) {
+        display_println!("{} {}", style("❌").red(), reason);
+        return Ok(None);
+    }
+
+    Ok(Some(name))
+}
+
+async fn execute_plan(
+    workspace_manager: &mut WorkspaceManager,
+    plan: &BatchPlan,
+    repo_names: &[String],
+) -> Result<Vec<BatchResult>> {
+    let mut results = Vec::new();
+
+    match plan {
+        BatchPlan::Sync { fetch_only } => {
+            for result in workspace_manager
+                .sync_named_repositories(repo_names, *fetch_only, false)
+                .await?
+            {
+                let error = match result.outcome {
+                    SyncOutcome::Synced => None,
+                    SyncOutcome::Dirty => Some("uncommitted changes, skipped".to_string()),
+                    SyncOutcome::Diverged
+                    | SyncOutcome::Protected
+                    | SyncOutcome::Locked
+                    | SyncOutcome::Failed => result.error,
+                };
+                results.push(BatchResult {
+                    name: result.name,
+                    error,
+                });
+            }
+        }
+        BatchPlan::Open { app } => {
+            for name in repo_names {
+                display_print!("{} {}... ", style("→").dim(), style(name).cyan());
+                let result = workspace_manager.open_repo_with_app(name, app).await;
+                results.push(report(name, result));
+            }
+        }
+        BatchPlan::ConfigureApp { app, template } => {
+            for name in repo_names {
+                display_print!("{} {}... ", style("→").dim(), style(name).cyan());
+                let result = workspace_manager
+                    .configure_app_for_repo(name, app, template)
+                    .await;
+                results.push(report(name, result));
+            }
+        }
+        BatchPlan::AddToGroup { group } => {
+            add_to_group(
+                &mut workspace_manager.config_mut().groups,
+                group,
+                repo_names,
+            );
+            workspace_manager.save_config().await?;
+            display_println!(
+                "{} Added {} repositories to group {}",
+                style("✓").green(),
+                repo_names.len(),
+                style(group).cyan()
+            );
+            results.extend(
+                repo_names
+                    .iter()
+                    .map(|name| BatchResult::from_result(name, Ok(()))),
+            );
+        }
+        BatchPlan::Remove => {
+            for name in repo_names {
+                display_print!("{} {}... ", style("→").dim(), style(name).cyan());
+                let result = workspace_manager.remove_repository(name).await;
+                let trash_id = result.as_ref().ok().cloned().flatten();
+                results.push(report(name, result.map(|_| ())));
+                if let Some(id) = trash_id {
+                    display_println!("  {}", style(format!("In the trash as {id}")).dim());
+                }
+            }
+        }
+    }
+
+    Ok(results)
+}
+
+/// Print the per-repository mark for a finished step and record its result
+fn report(name: &str, result: Result<()>) -> BatchResult {
+    match &result {
+        Ok(()) => display_println!("{}", style("✓").green()),
+        Err(e) => {
+            display_println!("{}", style("✗").red());
+            display_eprintln!("  Error: {e}");
+        }
+    }
+    BatchResult::from_result(name, result)
+}
+
+/// Add repositories to a group, creating it if needed and skipping members
+fn add_to_group(groups: &mut Vec<RepositoryGroup>, group: &str, repo_names: &[String]) {
+    let index = match groups.iter().position(|g| g.name == group) {
+        Some(index) => index,
+        None => {
+            groups.push(RepositoryGroup {
+                name: group.to_string(),
+                repos: Vec::new(),
+                apps: HashMap::new(),
+                default_app: None,
+                default_template: None,
+            });
+            groups.len() - 1
+        }
+    };
+
+    let members = &mut groups[index].repos;
+    for name in repo_names {
+        if !members.contains(name) {
+            members.push(name.clone());
+        }
+    }
+}
+
+fn print_batch_summary(results: &[BatchResult]) {
+    let summary = summarize(results);
+    display_println!();
+    if summary.failed == 0 {
+        display_println!(
+            "{} {} of {} repositories succeeded",
+            style("✓").green().bold(),
+            summary.total,
+            summary.total
+        );
+        return;
+    }
+
+    display_println!(
+        "{} {} of {} repositories failed:",
+        style("✗").red().bold(),
+        summary.failed,
+        summary.total
+    );
+    for result in results {
+        if let Some(error) = &result.error {
+            display_println!(
+                "  {} {}: {}",
+                style("✗").red(),
+                style(&result.name).cyan(),
+                error
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summarize_counts_failures() {
+        let results = vec![
+            BatchResult::from_result("api", Ok(())),
+            BatchResult::from_result("web", Err(anyhow::anyhow!("boom"))),
+            BatchResult::from_result("docs", Ok(())),
+        ];
+
+        assert_eq!(
+            summarize(&results),
+            BatchSummary {
+                total: 3,
+                failed: 1
+            }
+        );
+        assert_eq!(results[1].error.as_deref(), Some("boom"));
+    }
+
+    #[test]
+    fn test_add_to_group_creates_group_and_skips_members() {
+        let mut groups = vec![RepositoryGroup {
+            name: "frontend".to_string(),
+            repos: vec!["web".to_string()],
+            apps: HashMap::new(),
+            default_app: None,
+            default_template: None,
+        }];
+        let names = |list: &[&str]| list.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+
+        add_to_group(&mut groups, "frontend", &names(&["web", "mobile"]));
+        add_to_group(&mut groups, "backend", &names(&["api"]));
+
+        assert_eq!(groups[0].repos, names(&["web", "mobile"]));
+        assert_eq!(groups[1].name, "backend");
+        assert_eq!(groups[1].repos, names(&["api"]));
+    }
+}
@@ -0,0 +1,155 @@
+//! Walking through the conflicted files of a merge one at a time
+//!
+//! After each action the remaining conflicts are read from the index again,
+//! so files resolved outside the flow (in an editor, or by another tool)
+//! drop out on their own. Skipped files stay conflicted for the next run.
+
+use anyhow::Result;
+use console::style;
+use inquire::Select;
+use std::collections::HashSet;
+use std::fmt;
+use std::path::Path;
+
+use crate::display_println;
+use crate::ui::prompts::{handle_prompt_result, prompt_yes_no};
+use crate::worktree::resolve::{self, ConflictedFile, Resolution};
+
+/// How the resolution session ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResolveOutcome {
+    /// Every conflict was resolved and the merge committed
+    Committed,
+    /// Every conflict was resolved but the merge is not committed yet
+    Resolved,
+    /// Some files still have conflicts
+    Incomplete {
+        remaining: usize,
+    },
+    Aborted,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Choice {
+    Resolve(Resolution),
+    Skip,
+    Abort,
+}
+
+/// A menu entry, labelled with the branch each side comes from
+struct Entry {
+    choice: Choice,
+    label: String,
+}
+
+impl fmt::Display for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.label)
+    }
+}
+
+fn side_label(action: &str, branch: &str, present: bool) -> String {
+    if present {
+        format!("{action} ({branch})")
+    } else {
+        format!("{action} ({branch}, deletes the file)")
+    }
+}
+
+fn entries(file: &ConflictedFile, ours: &str, theirs: &str) -> Vec<Entry> {
+    let mut entries = Vec::new();
+    if file.ours && file.theirs {
+        entries.push(Entry {
+            choice: Choice::Resolve(Resolution::MergeTool),
+            label: "🔧 Open in merge tool".to_string(),
+        });
+    }
+    entries.extend([
+        Entry {
+            choice: Choice::Resolve(Resolution::Ours),
+            label: side_label("⬅️  Take ours", ours, file.ours),
+        },
+        Entry {
+            choice: Choice::Resolve(Resolution::Theirs),
+            label: side_label("➡️  Take theirs", theirs, file.theirs),
+        },
+        Entry {
+            choice: Choice::Skip,
+            label: "⏭️  Skip for now".to_string(),
+        },
+        Entry {
+            choice: Choice::Abort,
+            label: "🛑 Abort the merge".to_string(),
+        },
+    ]);
+    entries
+}
+
+/// Resolve the conflicts of the merge in progress in `repo` interactively.
+/// ESC stops without touching the remaining files.
+pub async fn resolve_conflicts(repo: &Path) -> Result<ResolveOutcome> {
+    let (ours, theirs) = resolve::merge_sides(repo).await;
+    let mut skipped: HashSet<String> = HashSet::new();
+
+    loop {
+        let remaining = resolve::conflicted_files(repo).await?;
+        if remaining.is_empty() {
+            break;
+        }
+        let pending: Vec<&ConflictedFile> = remaining
+            .iter()
+            .filter(|file| !skipped.contains(&file.path))
+            .collect();
+        let Some(file) = pending.first() else {
+            return Ok(ResolveOutcome::Incomplete {
+                remaining: remaining.len(),
+            });
+        };
+
+        display_println!();
+        display_println!(
+            "{} {} {}",
+            style(format!("[{} left]", remaining.len())).dim(),
+            style(&file.path).cyan().bold(),
+            style(format!("({})", file.describe())).yellow()
+        );
+        let choice = handle_prompt_result(
+            Select::new("Resolve with:", entries(file, &ours, &theirs)).prompt(),
+        )?;
+        let Some(Entry { choice, .. }) = choice else {
+            return Ok(ResolveOutcome::Incomplete {
+                remaining: remaining.len(),
+            });
+        };
+
+        match choice {
+            Choice::Resolve(resolution) => {
+                if resolve::apply(repo, file, resolution).await? {
+                    display_println!("{} Resolved {}", style("✓").green(), file.path);
+                } else {
+                    display_println!("{} {} still has conflicts", style("⚠").yellow(), file.path);
+                }
+            }
+            Choice::Skip => {
+                skipped.insert(file.path.clone());
+            }
+            Choice::Abort => {
+                if prompt_yes_no(
+                    "Abort the merge and discard every resolution made so far?",
+                    false,
+                )? {
+                    resolve::abort_merge(repo).await?;
+                    return Ok(ResolveOutcome::Aborted);
+                }
+            }
+        }
+    }
+
+    display_println!();
+    display_println!("{} All conflicts resolved", style("✓").green().bold());
+    if prompt_yes_no("Commit the merge now?", true)? {
+        resolve::conclude_merge(repo).await?;
+        return Ok(ResolveOutcome::Committed);
+    }
+    Ok(ResolveOutcome::Resolved)
+}
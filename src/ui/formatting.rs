@@ -7,6 +7,9 @@ use crate::workspace::operations::GitStatus;
 /// This module provides unified color schemes and formatting patterns for repository items
 /// Format a timestamp as human-readable time ago
 pub fn format_time_ago(timestamp: &chrono::DateTime<Utc>) -> String {
+    if crate::output::is_deterministic() {
+        return crate::output::TIME_PLACEHOLDER.to_string();
+    }
     let now = Utc::now();
     let duration = now.signed_duration_since(timestamp);
 
@@ -0,0 +1,563 @@
+//! Interactive tutorial for `vibe guide`
+//!
+//! The guide is a sequence of steps grouped into chapters. Each step explains
+//! a concept, offers to run the real vibe command, and checks that it did
+//! what it should, either in the user's workspace or in a practice workspace
+//! that is thrown away when the guide ends. Finished steps are recorded in
+//! [`VibeState`], so the next `vibe guide` picks up where the last one
+//! stopped; `vibe guide --reset` starts over.
+
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use console::style;
+use inquire::{Select, Text};
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+use tokio::process::Command;
+
+use crate::display_println;
+use crate::git::backend;
+use crate::ui::prompts::handle_prompt_result;
+use crate::ui::state::VibeState;
+use crate::utils::completions::SUPPORTED_APPS;
+use crate::workspace::config::{repo_abs_path, Repository, WorkspaceConfig};
+use crate::workspace::constants::VIBE_HOME_ENV;
+use crate::workspace::WorkspaceManager;
+
+/// A chapter of the guide, for `vibe guide --topic`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Topic {
+    /// Tracking repositories: scanning and cloning
+    Workspace,
+    /// Opening repositories in terminals and editors
+    Apps,
+    /// Parallel work in git worktrees
+    Worktrees,
+    /// Letting AI assistants drive vibe over MCP
+    Mcp,
+}
+
+impl Topic {
+    fn title(self) -> &'static str {
+        match self {
+            Topic::Workspace => "Your workspace",
+            Topic::Apps => "Apps",
+            Topic::Worktrees => "Worktrees",
+            Topic::Mcp => "MCP",
+        }
+    }
+}
+
+/// One step of the guide
+#[derive(Debug)]
+pub struct Step {
+    /// Recorded in [`VibeState::guide_progress`] once finished
+    pub id: &'static str,
+    pub topic: Topic,
+    pub title: &'static str,
+    explanation: &'static str,
+}
+
+pub const STEPS: &[Step] = &[
+    Step {
+        id: "scan",
+        topic: Topic::Workspace,
+        title: "Scan your workspace",
+        explanation: "vibe keeps a list of repositories under one workspace root. \
+            Scanning finds the git repositories already in the root and adds them, \
+            so every other command can refer to them by name.",
+    },
+    Step {
+        id: "clone",
+        topic: Topic::Workspace,
+        title: "Clone a repository",
+        explanation: "`vibe clone` takes a URL or GitHub owner/repo, clones it into \
+            the workspace root, and tracks it in one go.",
+    },
+    Step {
+        id: "configure-app",
+        topic: Topic::Apps,
+        title: "Configure an app",
+        explanation: "Apps are the terminals and editors vibe launches. Configuring \
+            one for a repository records which template it opens with, such as the \
+            panes of a Warp layout or the folders of a VS Code workspace.",
+    },
+    Step {
+        id: "open",
+        topic: Topic::Apps,
+        title: "Open a repository",
+        explanation: "`vibe open` launches a repository in its configured app. With \
+            several apps configured, it picks the one you launch most.",
+    },
+    Step {
+        id: "worktree",
+        topic: Topic::Worktrees,
+        title: "Create a worktree",
+        explanation: "A worktree is a second checkout of the same repository on its \
+            own branch, so a task can proceed without stashing the current one. vibe \
+            names the branch after the task and keeps track of it for cleanup.",
+    },
+    Step {
+        id: "mcp",
+        topic: Topic::Mcp,
+        title: "Check the MCP server",
+        explanation: "`vibe mcp` serves your workspace to AI assistants over the Model \
+            Context Protocol; add it to Claude Code with \
+            `claude mcp add vibe -- vibe mcp`. Validating calls every read-only tool \
+            against the workspace, as an assistant would.",
+    },
+];
+
+/// The steps `vibe guide` walks through: the chapter for `topic`, or
+/// everything not yet finished
+pub fn steps_to_run(topic: Option<Topic>, state: &VibeState) -> Vec<&'static Step> {
+    match topic {
+        Some(topic) => {
+            let chapter: Vec<&Step> = STEPS.iter().filter(|step| step.topic == topic).collect();
+            let remaining: Vec<&Step> = chapter
+                .iter()
+                .copied()
+                .filter(|step| !state.is_guide_step_complete(step.id))
+                .collect();
+            // A finished chapter is walked again from the start
+            if remaining.is_empty() {
+                chapter
+            } else {
+                remaining
+            }
+        }
+        None => STEPS
+            .iter()
+            .filter(|step| !state.is_guide_step_complete(step.id))
+            .collect(),
+    }
+}
+
+/// Finished steps out of all, for the non-interactive summary
+pub fn progress(state: &VibeState) -> (usize, usize) {
+    let done = STEPS
+        .iter()
+        .filter(|step| state.is_guide_step_complete(step.id))
+        .count();
+    (done, STEPS.len())
+}
+
+/// Where the guide runs its commands
+struct Target {
+    config: PathBuf,
+    /// The practice workspace, deleted on drop
+    practice: Option<TempDir>,
+}
+
+impl Target {
+    /// A throwaway workspace with its own vibe home, holding one untracked
+    /// repository for the scan step to find
+    async fn practice() -> Result<Self> {
+        let dir = TempDir::new().context("Failed to create a practice workspace")?;
+        let root = dir.path().join("workspace");
+        let sample = root.join("sample");
+        std::fs::create_dir_all(&sample)?;
+        std::fs::write(
+            sample.join("README.md"),
+            "# sample\n\nA practice repository for `vibe guide`.\n",
+        )?;
+        for args in [
+            &["init", "--quiet"][..],
+            &["add", "README.md"],
+            &["commit", "--quiet", "-m", "Initial commit"],
+        ] {
+            let status = Command::new("git")
+                .args([
+                    "-c",
+                    "user.name=vibe guide",
+                    "-c",
+                    "user.email=guide@vibe.invalid",
+                ])
+                .args(args)
+                .current_dir(&sample)
+                .output()
+                .await?
+                .status;
+            anyhow::ensure!(status.success(), "git {} failed", args.join(" "));
+        }
+
+        let config = dir.path().join("config.yaml");
+        std::fs::write(
+            &config,
+            format!(
+                "workspace:\n  name: practice\n  root: {}\n  auto_discover: false\n\
+                 repositories: []\ngroups: []\napps: {{}}\n",
+                serde_json::to_string(&root.to_string_lossy())?
+            ),
+        )?;
+        let target = Self {
+            config,
+            practice: Some(dir),
+        };
+        // Configuring apps needs the bundled templates, which a new vibe home
+        // doesn't have yet
+        let installed = target
+            .command(&["apps", "template", "update-defaults", "--force"])?
+            .output()
+            .await?;
+        anyhow::ensure!(
+            installed.status.success(),
+            "Failed to install templates in the practice workspace"
+        );
+        Ok(target)
+    }
+
+    async fn load(&self) -> Result<WorkspaceConfig> {
+        WorkspaceConfig::load_from_file(&self.config).await
+    }
+
+    /// `vibe <args>` against the target
+    fn command<S: AsRef<std::ffi::OsStr>>(&self, args: &[S]) -> Result<Command> {
+        let mut command = Command::new(std::env::current_exe()?);
+        command.arg("--config").arg(&self.config).args(args);
+        if let Some(practice) = &self.practice {
+            command.env(VIBE_HOME_ENV, practice.path().join("home"));
+        }
+        Ok(command)
+    }
+
+    /// Run `vibe <args>` against the target, in `dir` when given
+    async fn run(&self, args: &[String], dir: Option<&Path>) -> Result<bool> {
+        let mut command = self.command(args)?;
+        if let Some(dir) = dir {
+            command.current_dir(dir);
+        }
+        Ok(command.status().await?.success())
+    }
+}
+
+/// What a step runs, and what to check afterwards
+struct Plan {
+    args: Vec<String>,
+    dir: Option<PathBuf>,
+    check: Check,
+}
+
+enum Check {
+    /// The command succeeded
+    Succeeded,
+    /// At least one repository is tracked
+    HasRepository,
+    /// More repositories are tracked than `before`
+    RepositoryAdded { before: usize },
+    /// `repo` has `app` configured
+    AppConfigured { repo: String, app: String },
+    /// The repository at this path has a linked worktree
+    HasWorktree(PathBuf),
+}
+
+enum Choice {
+    Run,
+    Skip,
+    Quit,
+}
+
+/// Run the interactive guide
+pub async fn run(workspace_manager: &WorkspaceManager, topic: Option<Topic>) -> Result<()> {
+    let mut state = VibeState::load().unwrap_or_default();
+    let steps = steps_to_run(topic, &state);
+    if steps.is_empty() {
+        display_println!(
+            "{} You've finished the guide. Revisit a chapter with {} or start over with {}.",
+            style("🎉").green(),
+            style("vibe guide --topic <topic>").cyan(),
+            style("vibe guide --reset").cyan()
+        );
+        return Ok(());
+    }
+
+    display_println!("{}", style("🚀 vibe guide").cyan().bold());
+    let (done, total) = progress(&state);
+    if done > 0 {
+        display_println!("{}", style(format!("{done} of {total} steps done")).dim());
+    }
+
+    let yours = format!(
+        "Your workspace ({})",
+        workspace_manager.get_workspace_root().display()
+    );
+    let practice = "A practice workspace, deleted when the guide ends".to_string();
+    let Some(choice) = handle_prompt_result(
+        Select::new(
+            "Where should the guide run commands?",
+            vec![yours, practice.clone()],
+        )
+        .prompt(),
+    )?
+    else {
+        return Ok(());
+    };
+    let target = if choice == practice {
+        Target::practice().await?
+    } else {
+        Target {
+            config: workspace_manager.get_config_path().clone(),
+            practice: None,
+        }
+    };
+
+    for (index, step) in steps.iter().enumerate() {
+        display_println!();
+        display_println!(
+            "{} {}",
+            style(format!(
+                "Step {}/{} · {}:",
+                index + 1,
+                steps.len(),
+                step.topic.title()
+            ))
+            .dim(),
+            style(step.title).yellow().bold()
+        );
+        display_println!("{}", step.explanation);
+
+        let Some(plan) = plan_step(step, &target).await? else {
+            display_println!(
+                "{} Track a repository first: {}",
+                style("ℹ").yellow(),
+                style("vibe guide --topic workspace").cyan()
+            );
+            break;
+        };
+        display_println!(
+            "{} {}",
+            style("Runs:").dim(),
+            style(format!("vibe {}", plan.args.join(" "))).cyan()
+        );
+
+        loop {
+            match choose("Run it?")? {
+                Choice::Skip => break,
+                Choice::Quit => return Ok(()),
+                Choice::Run => {}
+            }
+            let succeeded = target.run(&plan.args, plan.dir.as_deref()).await?;
+            match verify(&plan.check, succeeded, &target).await {
+                Ok(()) => {
+                    display_println!("{} {}", style("✓").green(), step.title);
+                    state.complete_guide_step(step.id);
+                    state.save()?;
+                    break;
+                }
+                Err(problem) => {
+                    display_println!("{} {problem}", style("✗").red());
+                }
+            }
+        }
+    }
+
+    let (done, total) = progress(&state);
+    display_println!();
+    display_println!(
+        "{} {done} of {total} steps done. Run {} to continue later.",
+        style("ℹ").blue(),
+        style("vibe guide").cyan()
+    );
+    Ok(())
+}
+
+fn choose(prompt: &str) -> Result<Choice> {
+    const RUN: &str = "Run";
+    const SKIP: &str = "Skip this step";
+    const QUIT: &str = "Quit the guide";
+    Ok(
+        match handle_prompt_result(Select::new(prompt, vec![RUN, SKIP, QUIT]).prompt())? {
+            Some(RUN) => Choice::Run,
+            Some(SKIP) => Choice::Skip,
+            _ => Choice::Quit,
+        },
+    )
+}
+
+/// The command for `step`, asking for what it needs. `None` when the step
+/// needs a tracked repository and there is none.
+async fn plan_step(step: &Step, target: &Target) -> Result<Option<Plan>> {
+    let config = target.load().await?;
+    let plan = match step.id {
+        "scan" => Plan {
+            args: vec!["git".into(), "scan".into(), "--import".into()],
+            dir: None,
+            check: Check::HasRepository,
+        },
+        "clone" => {
+            let url = Text::new("Repository to clone:")
+                .with_default("octocat/Hello-World")
+                .prompt()?;
+            Plan {
+                args: vec![
+                    "clone".into(),
+                    url,
+                    "--no-configure".into(),
+                    "--no-open".into(),
+                ],
+                dir: None,
+                check: Check::RepositoryAdded {
+                    before: config.repositories.len(),
+                },
+            }
+        }
+        "configure-app" => {
+            let Some(repo) = pick_repository(target, &config).await? else {
+                return Ok(None);
+            };
+            let app = Select::new("App to configure:", SUPPORTED_APPS.to_vec()).prompt()?;
+            Plan {
+                args: vec![
+                    "apps".into(),
+                    "configure".into(),
+                    repo.name.clone(),
+                    app.to_string(),
+                ],
+                dir: None,
+                check: Check::AppConfigured {
+                    repo: repo.name,
+                    app: app.to_string(),
+                },
+            }
+        }
+        "open" => {
+            let Some(repo) = pick_repository(target, &config).await? else {
+                return Ok(None);
+            };
+            Plan {
+                args: vec!["open".into(), repo.name, "--no-fetch".into()],
+                dir: None,
+                check: Check::Succeeded,
+            }
+        }
+        "worktree" => {
+            let Some(repo) = pick_repository(target, &config).await? else {
+                return Ok(None);
+            };
+            let task = Text::new("Task name for the worktree:")
+                .with_default("guide-tour")
+                .prompt()?;
+            let path = repo_abs_path(&config, &repo);
+            Plan {
+                args: vec!["git".into(), "worktree".into(), "create".into(), task],
+                dir: Some(path.clone()),
+                check: Check::HasWorktree(path),
+            }
+        }
+        _ => Plan {
+            args: vec!["mcp".into(), "validate".into()],
+            dir: None,
+            check: Check::Succeeded,
+        },
+    };
+    Ok(Some(plan))
+}
+
+/// A tracked repository for a step to work on. The practice workspace
+/// tracks its sample repository first when nothing is tracked yet.
+async fn pick_repository(target: &Target, config: &WorkspaceConfig) -> Result<Option<Repository>> {
+    let mut repositories = config.repositories.clone();
+    if repositories.is_empty() && target.practice.is_some() {
+        display_println!("{}", style("Tracking the practice repository first").dim());
+        target
+            .run(&["git".into(), "scan".into(), "--import".into()], None)
+            .await?;
+        repositories = target.load().await?.repositories;
+    }
+    match repositories.len() {
+        0 => Ok(None),
+        1 => Ok(repositories.pop()),
+        _ => {
+            let names: Vec<String> = repositories.iter().map(|repo| repo.name.clone()).collect();
+            let name = Select::new("Repository:", names).prompt()?;
+            Ok(repositories.into_iter().find(|repo| repo.name == name))
+        }
+    }
+}
+
+/// Whether the step had the effect it should, or what's missing
+async fn verify(check: &Check, succeeded: bool, target: &Target) -> Result<(), String> {
+    if !succeeded {
+        return Err("The command failed; see its output above".to_string());
+    }
+    let config = target
+        .load()
+        .await
+        .map_err(|e| format!("Couldn't read the configuration: {e}"))?;
+    match check {
+        Check::Succeeded => Ok(()),
+        Check::HasRepository => {
+            if config.repositories.is_empty() {
+                Err(
+                    "No repository is tracked yet; is there one under the workspace root?"
+                        .to_string(),
+                )
+            } else {
+                Ok(())
+            }
+        }
+        Check::RepositoryAdded { before } => {
+            if config.repositories.len() > *before {
+                Ok(())
+            } else {
+                Err("No new repository is tracked yet".to_string())
+            }
+        }
+        Check::AppConfigured { repo, app } => {
+            let configured = config
+                .get_repository(repo)
+                .is_some_and(|repo| repo.apps.contains_key(app));
+            if configured {
+                Ok(())
+            } else {
+                Err(format!("{app} isn't configured for {repo} yet"))
+            }
+        }
+        Check::HasWorktree(path) => match backend::current().worktrees(path).await {
+            Ok(worktrees) if worktrees.len() > 1 => Ok(()),
+            Ok(_) => Err("The repository has no linked worktree yet".to_string()),
+            Err(e) => Err(format!("Couldn't list worktrees: {e}")),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_steps_resume_after_finished_ones() {
+        let mut state = VibeState::default();
+        assert_eq!(steps_to_run(None, &state).len(), STEPS.len());
+
+        state.complete_guide_step("scan");
+        state.complete_guide_step("clone");
+        let ids: Vec<&str> = steps_to_run(None, &state).iter().map(|s| s.id).collect();
+        assert_eq!(ids, ["configure-app", "open", "worktree", "mcp"]);
+        assert_eq!(progress(&state), (2, STEPS.len()));
+    }
+
+    #[test]
+    fn test_topic_jumps_to_its_chapter() {
+        let mut state = VibeState::default();
+        let ids: Vec<&str> = steps_to_run(Some(Topic::Apps), &state)
+            .iter()
+            .map(|s| s.id)
+            .collect();
+        assert_eq!(ids, ["configure-app", "open"]);
+
+        state.complete_guide_step("configure-app");
+        let ids: Vec<&str> = steps_to_run(Some(Topic::Apps), &state)
+            .iter()
+            .map(|s| s.id)
+            .collect();
+        assert_eq!(ids, ["open"]);
+
+        // A finished chapter can be walked again
+        state.complete_guide_step("open");
+        assert_eq!(steps_to_run(Some(Topic::Apps), &state).len(), 2);
+        assert!(steps_to_run(None, &state)
+            .iter()
+            .all(|step| step.topic != Topic::Apps));
+    }
+}
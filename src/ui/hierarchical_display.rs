@@ -1,8 +1,14 @@
 use console::style;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
 
-use crate::workspace::operations::get_git_status;
+use crate::output;
+use crate::output::theme::{self, paint, Icon, Role};
+use crate::output::timings;
+use crate::workspace::operations::{collect_git_statuses, GitStatus, StatusTarget};
 use crate::workspace::repo_analyzer::{NonGitFolder, RepoInfo, RepoStatus, WorkspaceAnalysis};
+use crate::workspace::status_groups::{StatusGrouping, StatusRollup};
+use crate::{display_print, display_println};
 
 pub struct DisplayOptions {
     pub show_paths: bool,
@@ -28,10 +34,10 @@ pub fn render_workspace_analysis(analysis: &WorkspaceAnalysis, options: &Display
     let nongit_count = analysis.non_git_folders.len();
 
     // Header with summary
-    println!("{} Workspace Analysis", style("📊").blue().bold());
-    println!("{}", "─".repeat(50));
+    display_println!("{} Workspace Analysis", style("📊").blue().bold());
+    display_println!("{}", "─".repeat(50));
 
-    println!(
+    display_println!(
         "Total repositories: {} | Tracked: {} | New: {} | Missing: {}",
         style(total_repos).bold(),
         if tracked_count > 0 {
@@ -52,10 +58,10 @@ pub fn render_workspace_analysis(analysis: &WorkspaceAnalysis, options: &Display
     );
 
     if nongit_count > 0 {
-        println!("Non-git folders: {}", style(nongit_count).cyan());
+        display_println!("Non-git folders: {}", style(nongit_count).cyan());
     }
 
-    println!();
+    display_println!();
 
     // Render repositories by organization
     render_repositories_by_organization(&analysis.organizations, options);
@@ -79,10 +85,11 @@ fn render_repositories_by_organization(
     org_names.sort();
 
     for org_name in org_names {
-        let repos = &organizations[org_name];
+        let mut repos: Vec<_> = organizations[org_name].iter().collect();
+        output::sort_if_deterministic(&mut repos, |repo| repo.name.clone());
 
         // Organization header
-        println!(
+        display_println!(
             "{} {} ({})",
             style("📁").blue(),
             style(org_name).cyan().bold(),
@@ -93,7 +100,7 @@ fn render_repositories_by_organization(
             render_repository_entry(repo, options);
         }
 
-        println!(); // Add space between organizations
+        display_println!(); // Add space between organizations
     }
 }
 
@@ -110,48 +117,53 @@ fn render_repository_entry(repo: &RepoInfo, options: &DisplayOptions) {
         RepoStatus::Missing => style(&repo.name).red(),
     };
 
-    print!("  {status_icon} {repo_name}");
+    display_print!("  {status_icon} {repo_name}");
+
+    if repo.outside_root {
+        display_print!(" {}", style("[outside root]").magenta());
+    }
 
     if options.show_paths && !options.compact {
-        print!(" {}", style(format!("({})", repo.path.display())).dim());
+        display_print!(" {}", style(format!("({})", repo.path.display())).dim());
     }
 
     if options.show_urls && repo.remote_url.is_some() {
-        print!(" {}", style(repo.remote_url.as_ref().unwrap()).dim());
+        display_print!(" {}", style(repo.remote_url.as_ref().unwrap()).dim());
     }
 
-    println!();
+    display_println!();
 }
 
 fn render_non_git_folders(folders: &[NonGitFolder], options: &DisplayOptions) {
-    println!(
+    display_println!(
         "{} Non-Git Folders ({})",
         style("📁").cyan().bold(),
         style(folders.len()).dim()
     );
 
     for folder in folders {
-        print!("  {} {}", style("📁").cyan(), style(&folder.name).cyan());
+        display_print!("  {} {}", style("📁").cyan(), style(&folder.name).cyan());
 
         if options.show_paths && !options.compact {
-            print!(" {}", style(format!("({})", folder.path.display())).dim());
+            display_print!(" {}", style(format!("({})", folder.path.display())).dim());
         }
 
-        println!();
+        display_println!();
     }
 
-    println!();
+    display_println!();
 }
 
 fn render_actionable_summary(analysis: &WorkspaceAnalysis) {
-    println!("{} Actionable Items", style("💡").yellow().bold());
-    println!("{}", "─".repeat(30));
+    display_println!("{} Actionable Items", style("💡").yellow().bold());
+    display_println!("{}", "─".repeat(30));
 
     let new_repos = analysis.get_new_repos();
-    let missing_repos = analysis.get_missing_repos();
+    let missing_repos = analysis.get_missing_in_root();
+    let missing_outside = analysis.get_missing_outside_root();
 
     if !new_repos.is_empty() {
-        println!(
+        display_println!(
             "• {} new repositories found - use {} to add them to config",
             style(new_repos.len()).yellow().bold(),
             style("--import").green()
@@ -159,7 +171,7 @@ fn render_actionable_summary(analysis: &WorkspaceAnalysis) {
     }
 
     if !missing_repos.is_empty() {
-        println!(
+        display_println!(
             "• {} repositories missing from filesystem - use {} to re-clone or {} to remove from config",
             style(missing_repos.len()).red().bold(),
             style("--restore").green(),
@@ -167,14 +179,21 @@ fn render_actionable_summary(analysis: &WorkspaceAnalysis) {
         );
     }
 
+    if !missing_outside.is_empty() {
+        display_println!(
+            "• {} repositories outside the workspace root are missing - fix or remove their paths in config",
+            style(missing_outside.len()).red().bold()
+        );
+    }
+
     if !analysis.non_git_folders.is_empty() {
-        println!(
+        display_println!(
             "• {} non-git folders found - consider moving to appropriate locations",
             style(analysis.non_git_folders.len()).cyan().bold()
         );
     }
 
-    println!();
+    display_println!();
 }
 
 pub fn render_repository_status_table(repos: &[RepoInfo], title: &str) {
@@ -182,16 +201,16 @@ pub fn render_repository_status_table(repos: &[RepoInfo], title: &str) {
         return;
     }
 
-    println!(
+    display_println!(
         "{} {} ({})",
         style("📊").blue(),
         style(title).bold(),
         style(repos.len()).dim()
     );
-    println!("{}", "─".repeat(50));
+    display_println!("{}", "─".repeat(50));
 
     // Table header
-    println!(
+    display_println!(
         "{:<30} {:<20} {:<15}",
         style("Repository").bold().underlined(),
         style("Organization").bold().underlined(),
@@ -207,7 +226,7 @@ pub fn render_repository_status_table(repos: &[RepoInfo], title: &str) {
 
         let org_name = repo.organization.as_deref().unwrap_or("Other");
 
-        println!(
+        display_println!(
             "{:<30} {:<20} {}",
             style(&repo.name).cyan(),
             style(org_name).dim(),
@@ -215,138 +234,237 @@ pub fn render_repository_status_table(repos: &[RepoInfo], title: &str) {
         );
     }
 
-    println!();
+    display_println!();
 }
 
-// Format for status command output - hierarchical with detailed git status
-pub async fn render_status_summary(analysis: &WorkspaceAnalysis) {
-    let tracked_repos = analysis.get_tracked_repos();
+/// Print git status for tracked repositories in the buckets of `grouping`,
+/// reading at most `max_parallel` repositories at once. Repositories whose
+/// checkout is in `lfs_repos` get an LFS badge. Returns the statuses read,
+/// named after the tracked repositories.
+pub async fn render_status_summary(
+    analysis: &WorkspaceAnalysis,
+    grouping: &StatusGrouping,
+    max_parallel: usize,
+    lfs_repos: &HashSet<PathBuf>,
+) -> Vec<GitStatus> {
+    let mut tracked_repos = analysis.get_tracked_repos();
 
     if tracked_repos.is_empty() {
-        println!("{} No repositories found", style("ℹ").yellow());
-        return;
+        display_println!(
+            "{} No repositories found",
+            paint(Role::Warning, theme::icon(Icon::Info))
+        );
+        return Vec::new();
     }
 
-    println!("{} Repository Status Summary", style("📊").blue().bold());
-    println!("{}", "─".repeat(50));
-
-    // Group by organization for status display
-    let mut org_groups: HashMap<String, Vec<&RepoInfo>> = HashMap::new();
-    for repo in &tracked_repos {
-        let org_name = repo.organization.as_deref().unwrap_or("Other").to_string();
-        org_groups.entry(org_name).or_default().push(repo);
+    display_println!(
+        "{}",
+        style(theme::labeled(Icon::Report, "Repository Status Summary")).bold()
+    );
+    display_println!("{}", "─".repeat(50));
+
+    output::sort_if_deterministic(&mut tracked_repos, |repo| repo.name.clone());
+    let buckets = grouping.bucket(tracked_repos.iter().map(|repo| {
+        (
+            repo.name.as_str(),
+            repo.path.as_path(),
+            repo.remote_url.as_deref(),
+        )
+    }));
+
+    // Read every status up front, once per repository even when it is
+    // listed in several buckets
+    let targets = tracked_repos
+        .iter()
+        .map(|repo| {
+            StatusTarget::new(repo.name.clone(), repo.path.clone()).comparing_to(
+                repo.config_repo
+                    .as_ref()
+                    .and_then(|config| config.compare_ref.clone()),
+            )
+        })
+        .collect();
+    let results = collect_git_statuses(targets, max_parallel).await;
+    let _render = timings::phase(timings::RENDER, "status summary").entered();
+
+    let mut total = StatusRollup::default();
+    let mut total_no_remote = 0;
+    let mut failures = Vec::new();
+    for (repo, result) in tracked_repos.iter().zip(&results) {
+        match &result.status {
+            Ok(status) => {
+                total.add(status);
+                if status.remote_url.is_none() {
+                    total_no_remote += 1;
+                }
+            }
+            // Repositories that can't be analyzed (e.g., not git repos,
+            // permission issues) are reported after the summary
+            Err(e) => failures.push((repo.name.clone(), e)),
+        }
     }
 
-    let mut org_names: Vec<_> = org_groups.keys().collect();
-    org_names.sort();
+    for bucket in &buckets {
+        let mut rollup = StatusRollup::default();
+        for member in &bucket.members {
+            if let Ok(status) = &results[member.index].status {
+                rollup.add(status);
+            }
+        }
 
-    let mut total_clean = 0;
-    let mut total_dirty = 0;
-    let mut total_no_remote = 0;
+        if !bucket.name.is_empty() {
+            display_println!(
+                "{} ({})",
+                theme::labeled(
+                    Icon::Folder,
+                    &paint(Role::Accent, &bucket.name).bold().to_string()
+                ),
+                paint(Role::Dim, format_rollup(bucket.members.len(), &rollup))
+            );
+        }
 
-    for org_name in org_names {
-        let repos = &org_groups[org_name];
+        for member in &bucket.members {
+            let repo = tracked_repos[member.index];
+            let Ok(status) = &results[member.index].status else {
+                continue;
+            };
+            render_status_line(repo, status, &member.also_in, lfs_repos);
+        }
 
-        println!(
-            "{} {} ({})",
-            style("📁").blue(),
-            style(org_name).cyan().bold(),
-            style(format!("{} repos", repos.len())).dim()
+        display_println!();
+    }
+
+    // Summary
+    display_println!(
+        "{}",
+        theme::labeled(
+            Icon::Report,
+            &format!(
+                "{} clean, {} with changes, {} no remote",
+                paint(Role::Success, total.clean),
+                paint(Role::Error, total.dirty),
+                paint(Role::Warning, total_no_remote)
+            )
+        )
+    );
+
+    if !failures.is_empty() {
+        display_println!();
+        display_println!(
+            "{} Could not read status for {} repositories:",
+            paint(Role::Warning, theme::icon(Icon::Warning)),
+            failures.len()
         );
+        for (name, e) in &failures {
+            display_println!(
+                "  {} {}",
+                paint(Role::Accent, name).bold(),
+                paint(Role::Dim, format!("({e})"))
+            );
+        }
+    }
 
-        for repo in repos {
-            // Get detailed git status for each repository
-            match get_git_status(&repo.path).await {
-                Ok(status) => {
-                    if status.clean {
-                        total_clean += 1;
-                    } else {
-                        total_dirty += 1;
-                    }
-
-                    // Count repositories without remotes
-                    if status.remote_url.is_none() {
-                        total_no_remote += 1;
-                    }
-
-                    // Format the detailed status line similar to the original
-                    let mut status_parts = Vec::new();
-
-                    // Repository name - color by git status (red=no remote, yellow=changes, green=clean)
-                    let name_style = if status.remote_url.is_none() {
-                        style(&repo.name).red().bold()
-                    } else if !status.clean {
-                        style(&repo.name).yellow().bold()
-                    } else {
-                        style(&repo.name).green().bold()
-                    };
-                    let name_part = format!("  {name_style}");
-
-                    // Branch information with ahead/behind indicators
-                    if let Some(ref branch) = status.branch {
-                        let branch_display = if status.ahead > 0 || status.behind > 0 {
-                            format!("{} [↑{} ↓{}]", branch, status.ahead, status.behind)
-                        } else {
-                            branch.to_string()
-                        };
-                        status_parts.push(format!("on {}", style(branch_display).white().bold()));
-                    }
-
-                    // Status indicators
-                    let mut indicators = Vec::new();
-                    if status.clean {
-                        indicators.push(style("✓").green().to_string());
-                    } else {
-                        if status.staged > 0 {
-                            indicators.push(format!("{}S", style(status.staged).green()));
-                        }
-                        if status.unstaged > 0 {
-                            indicators.push(format!("{}M", style(status.unstaged).red()));
-                        }
-                        if status.untracked > 0 {
-                            indicators.push(format!("{}?", style(status.untracked).yellow()));
-                        }
-                    }
-
-                    if !indicators.is_empty() {
-                        status_parts.push(format!("[{}]", indicators.join(" ")));
-                    }
-
-                    // Print the complete status line
-                    if status_parts.is_empty() {
-                        println!("{name_part}");
-                    } else {
-                        println!("{} {}", name_part, status_parts.join(" "));
-                    }
-                }
-                Err(e) => {
-                    // Handle repositories that can't be analyzed (e.g., not git repos, permission issues)
-                    println!(
-                        "  {} {} {}",
-                        style("⚠").yellow(),
-                        style(&repo.name).cyan().bold(),
-                        style(format!("({e})")).dim()
-                    );
-                }
-            }
+    tracked_repos
+        .iter()
+        .zip(results)
+        .filter_map(|(repo, result)| {
+            let mut status = result.status.ok()?;
+            status.repository_name = repo.name.clone();
+            Some(status)
+        })
+        .collect()
+}
+
+/// `3 repos: 2 clean, 1 dirty, 1 ahead/behind`, leaving out zero counts
+fn format_rollup(repos: usize, rollup: &StatusRollup) -> String {
+    let mut parts = Vec::new();
+    if rollup.clean > 0 {
+        parts.push(format!("{} clean", rollup.clean));
+    }
+    if rollup.dirty > 0 {
+        parts.push(format!("{} dirty", rollup.dirty));
+    }
+    if rollup.out_of_sync > 0 {
+        parts.push(format!("{} ahead/behind", rollup.out_of_sync));
+    }
+    if parts.is_empty() {
+        format!("{repos} repos")
+    } else {
+        format!("{repos} repos: {}", parts.join(", "))
+    }
+}
+
+/// One repository of the status summary; `also_in` names the other buckets
+/// it is listed in
+fn render_status_line(
+    repo: &RepoInfo,
+    status: &GitStatus,
+    also_in: &[String],
+    lfs_repos: &HashSet<PathBuf>,
+) {
+    let mut status_parts = Vec::new();
+
+    // Repository name - color by git status (red=no remote, yellow=changes, green=clean)
+    let name_role = if status.remote_url.is_none() {
+        Role::Error
+    } else if !status.clean {
+        Role::Warning
+    } else {
+        Role::Success
+    };
+    let name_style = paint(name_role, &repo.name).bold();
+    let name_part = format!("  {name_style}");
+
+    // Branch information with ahead/behind indicators
+    if let Some(branch_display) = status.branch_label() {
+        status_parts.push(format!("on {}", style(branch_display).white().bold()));
+    }
+
+    if let Some(fork) = status.fork_label() {
+        status_parts.push(paint(Role::Accent, fork).to_string());
+    }
+    if lfs_repos.contains(&repo.path) {
+        status_parts.push(paint(Role::Dim, "LFS").to_string());
+    }
+
+    // Status indicators
+    let mut indicators = Vec::new();
+    if status.clean {
+        indicators.push(paint(Role::Success, "✓").to_string());
+    } else {
+        if status.staged > 0 {
+            indicators.push(format!("{}S", paint(Role::Success, status.staged)));
+        }
+        if status.unstaged > 0 {
+            indicators.push(format!("{}M", paint(Role::Error, status.unstaged)));
+        }
+        if status.untracked > 0 {
+            indicators.push(format!("{}?", paint(Role::Warning, status.untracked)));
         }
+    }
 
-        println!();
+    if !indicators.is_empty() {
+        status_parts.push(format!("[{}]", indicators.join(" ")));
     }
 
-    // Summary
-    println!(
-        "{} {} clean, {} with changes, {} no remote",
-        style("📊").blue(),
-        style(total_clean).green(),
-        style(total_dirty).red(),
-        style(total_no_remote).yellow()
-    );
+    // Repositories listed under several groups are marked in each
+    if !also_in.is_empty() {
+        status_parts
+            .push(paint(Role::Dim, format!("(also in {})", also_in.join(", "))).to_string());
+    }
+
+    // Print the complete status line
+    if status_parts.is_empty() {
+        display_println!("{name_part}");
+    } else {
+        display_println!("{} {}", name_part, status_parts.join(" "));
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::workspace::config::{StatusGroupBy, WorkspaceConfig};
     use crate::workspace::repo_analyzer::WorkspaceAnalysis;
 
     #[test]
@@ -369,6 +487,7 @@ mod tests {
     #[tokio::test]
     async fn test_status_summary_with_empty_repos() {
         let analysis = WorkspaceAnalysis::new();
-        render_status_summary(&analysis).await;
+        let grouping = StatusGrouping::new(StatusGroupBy::Org, &WorkspaceConfig::default());
+        render_status_summary(&analysis, &grouping, 4, &HashSet::new()).await;
     }
 }
@@ -0,0 +1,346 @@
+//! Single-keystroke menu prompt
+//!
+//! Renders a select-style menu and reads one key at a time, so shortcut keys
+//! fire without pressing Enter while arrows/Enter keep working. Callers fall
+//! back to a regular `inquire::Select` when [`select`] returns `Ok(None)`,
+//! which happens when stdin/stderr isn't a TTY or raw key reads fail.
+
+use anyhow::Result;
+use console::{style, Key, Term};
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Once;
+
+/// One row of the menu
+#[derive(Debug, Clone)]
+pub struct KeyMenuItem {
+    pub label: String,
+    pub shortcut: Option<char>,
+    pub selectable: bool,
+}
+
+impl KeyMenuItem {
+    pub fn new(label: impl Into<String>, shortcut: Option<char>) -> Self {
+        Self {
+            label: label.into(),
+            shortcut,
+            selectable: true,
+        }
+    }
+
+    /// A non-selectable row such as a separator
+    pub fn separator(label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            shortcut: None,
+            selectable: false,
+        }
+    }
+}
+
+/// What the user picked
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyMenuOutcome {
+    /// Index into the menu items
+    Selected(usize),
+    /// A digit key 1-9
+    Slot(u8),
+    /// One of the extra shortcut keys that have no row of their own
+    Shortcut(char),
+    /// ESC or Ctrl-C
+    Cancelled,
+}
+
+/// Cursor and paging state, kept separate from terminal I/O
+#[derive(Debug)]
+struct MenuState<'a> {
+    items: &'a [KeyMenuItem],
+    cursor: usize,
+    page_size: usize,
+    slots: usize,
+    shortcuts: &'a [char],
+}
+
+impl<'a> MenuState<'a> {
+    fn new(
+        items: &'a [KeyMenuItem],
+        starting_cursor: usize,
+        page_size: usize,
+        slots: usize,
+    ) -> Self {
+        let mut state = Self {
+            items,
+            cursor: starting_cursor.min(items.len().saturating_sub(1)),
+            page_size: page_size.max(1),
+            slots: slots.min(9),
+            shortcuts: &[],
+        };
+        if !state
+            .items
+            .get(state.cursor)
+            .is_some_and(|item| item.selectable)
+        {
+            state.move_cursor(true);
+        }
+        state
+    }
+
+    /// Move to the next selectable row, wrapping around
+    fn move_cursor(&mut self, down: bool) {
+        let len = self.items.len();
+        for step in 1..=len {
+            let index = if down {
+                (self.cursor + step) % len
+            } else {
+                (self.cursor + len - step) % len
+            };
+            if self.items[index].selectable {
+                self.cursor = index;
+                return;
+            }
+        }
+    }
+
+    fn handle_key(&mut self, key: Key) -> Option<KeyMenuOutcome> {
+        match key {
+            Key::ArrowUp | Key::BackTab => self.move_cursor(false),
+            Key::ArrowDown | Key::Tab => self.move_cursor(true),
+            Key::Enter => return Some(KeyMenuOutcome::Selected(self.cursor)),
+            Key::Escape | Key::CtrlC => return Some(KeyMenuOutcome::Cancelled),
+            Key::Char(c) => {
+                if let Some(digit) = c.to_digit(10) {
+                    if (1..=self.slots as u32).contains(&digit) {
+                        return Some(KeyMenuOutcome::Slot(digit as u8));
+                    }
+                }
+                let c = c.to_ascii_lowercase();
+                if let Some(index) = self
+                    .items
+                    .iter()
+                    .position(|item| item.selectable && item.shortcut == Some(c))
+                {
+                    return Some(KeyMenuOutcome::Selected(index));
+                }
+                if self.shortcuts.contains(&c) {
+                    return Some(KeyMenuOutcome::Shortcut(c));
+                }
+            }
+            _ => {}
+        }
+        None
+    }
+
+    /// Rows to show, keeping the cursor inside the page
+    fn visible_range(&self) -> std::ops::Range<usize> {
+        let len = self.items.len();
+        if len <= self.page_size {
+            return 0..len;
+        }
+        let start = self
+            .cursor
+            .saturating_sub(self.page_size / 2)
+            .min(len - self.page_size);
+        start..start + self.page_size
+    }
+}
+
+static CURSOR_HIDDEN: AtomicBool = AtomicBool::new(false);
+static PANIC_HOOK: Once = Once::new();
+
+/// Restores the cursor when the prompt ends, on unwind through `Drop` and
+/// under `panic = "abort"` through a panic hook.
+pub struct TerminalGuard<'a> {
+    term: &'a Term,
+}
+
+impl<'a> TerminalGuard<'a> {
+    pub fn new(term: &'a Term) -> Self {
+        PANIC_HOOK.call_once(|| {
+            let previous = std::panic::take_hook();
+            std::panic::set_hook(Box::new(move |info| {
+                if CURSOR_HIDDEN.swap(false, Ordering::SeqCst) {
+                    let _ = Term::stderr().show_cursor();
+                }
+                previous(info);
+            }));
+        });
+        let _ = term.hide_cursor();
+        CURSOR_HIDDEN.store(true, Ordering::SeqCst);
+        Self { term }
+    }
+}
+
+impl Drop for TerminalGuard<'_> {
+    fn drop(&mut self) {
+        if CURSOR_HIDDEN.swap(false, Ordering::SeqCst) {
+            let _ = self.term.show_cursor();
+        }
+    }
+}
+
+/// Show the menu and wait for a selection. `slots` enables the digit keys
+/// `1..=slots`, and `shortcuts` are extra keys that don't select a row, such
+/// as ones advertised in a header. Returns `Ok(None)` when single-key input isn't available and
+/// the caller should use its regular prompt instead.
+pub fn select(
+    message: &str,
+    items: &[KeyMenuItem],
+    starting_cursor: usize,
+    page_size: usize,
+    slots: usize,
+    shortcuts: &[char],
+    help: &str,
+) -> Result<Option<KeyMenuOutcome>> {
+    let term = Term::stderr();
+    if items.is_empty() || !term.is_term() || !std::io::stdin().is_terminal() {
+        return Ok(None);
+    }
+
+    let mut state = MenuState::new(items, starting_cursor, page_size, slots);
+    state.shortcuts = shortcuts;
+    let guard = TerminalGuard::new(&term);
+    let mut first_key = true;
+
+    let outcome = loop {
+        let lines = render(&term, message, &state, help)?;
+        let key = match term.read_key() {
+            Ok(key) => key,
+            Err(_) if first_key => {
+                term.clear_last_lines(lines)?;
+                return Ok(None);
+            }
+            Err(e) => return Err(e.into()),
+        };
+        first_key = false;
+
+        let outcome = state.handle_key(key);
+        term.clear_last_lines(lines)?;
+        if let Some(outcome) = outcome {
+            break outcome;
+        }
+    };
+    drop(guard);
+
+    if let KeyMenuOutcome::Selected(index) = outcome {
+        term.write_line(&format!(
+            "{} {} {}",
+            style("?").green(),
+            message,
+            style(&items[index].label).cyan()
+        ))?;
+    }
+
+    Ok(Some(outcome))
+}
+
+/// Draw the menu and return how many lines were written
+fn render(term: &Term, message: &str, state: &MenuState, help: &str) -> Result<usize> {
+    let width = term.size().1 as usize;
+    let mut lines = vec![format!("{} {}", style("?").green(), message)];
+
+    for index in state.visible_range() {
+        let item = &state.items[index];
+        let line = if index == state.cursor {
+            format!("{} {}", style(">").cyan(), style(&item.label).cyan())
+        } else {
+            format!("  {}", item.label)
+        };
+        lines.push(line);
+    }
+    lines.push(style(format!("[{help}]")).cyan().dim().to_string());
+
+    for line in &lines {
+        // Truncate so wrapped lines don't throw off clear_last_lines
+        term.write_line(&console::truncate_str(line, width.saturating_sub(1), "…"))?;
+    }
+    Ok(lines.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn menu() -> Vec<KeyMenuItem> {
+        vec![
+            KeyMenuItem::new("(q) Quick", Some('q')),
+            KeyMenuItem::new("(o) Open", Some('o')),
+            KeyMenuItem::separator("──────"),
+            KeyMenuItem::new("[Exit]", None),
+        ]
+    }
+
+    #[test]
+    fn test_shortcut_selects_immediately() {
+        let items = menu();
+        let mut state = MenuState::new(&items, 0, 10, 0);
+        assert_eq!(
+            state.handle_key(Key::Char('o')),
+            Some(KeyMenuOutcome::Selected(1))
+        );
+        assert_eq!(
+            state.handle_key(Key::Char('Q')),
+            Some(KeyMenuOutcome::Selected(0))
+        );
+        assert_eq!(state.handle_key(Key::Char('z')), None);
+    }
+
+    #[test]
+    fn test_digits_only_fire_for_enabled_slots() {
+        let items = menu();
+        let mut state = MenuState::new(&items, 0, 10, 3);
+        assert_eq!(
+            state.handle_key(Key::Char('3')),
+            Some(KeyMenuOutcome::Slot(3))
+        );
+        assert_eq!(state.handle_key(Key::Char('4')), None);
+        assert_eq!(state.handle_key(Key::Char('0')), None);
+    }
+
+    #[test]
+    fn test_extra_shortcuts_yield_to_rows() {
+        let items = menu();
+        let mut state = MenuState::new(&items, 0, 10, 0);
+        state.shortcuts = &['d', 'o'];
+        assert_eq!(
+            state.handle_key(Key::Char('D')),
+            Some(KeyMenuOutcome::Shortcut('d'))
+        );
+        assert_eq!(
+            state.handle_key(Key::Char('o')),
+            Some(KeyMenuOutcome::Selected(1))
+        );
+    }
+
+    #[test]
+    fn test_arrows_skip_separator_and_wrap() {
+        let items = menu();
+        let mut state = MenuState::new(&items, 1, 10, 0);
+        state.handle_key(Key::ArrowDown);
+        assert_eq!(state.cursor, 3);
+        state.handle_key(Key::ArrowDown);
+        assert_eq!(state.cursor, 0);
+        state.handle_key(Key::ArrowUp);
+        assert_eq!(state.cursor, 3);
+        assert_eq!(
+            state.handle_key(Key::Enter),
+            Some(KeyMenuOutcome::Selected(3))
+        );
+        assert_eq!(
+            state.handle_key(Key::Escape),
+            Some(KeyMenuOutcome::Cancelled)
+        );
+    }
+
+    #[test]
+    fn test_visible_range_follows_cursor() {
+        let items: Vec<KeyMenuItem> = (0..10)
+            .map(|i| KeyMenuItem::new(i.to_string(), None))
+            .collect();
+        let mut state = MenuState::new(&items, 0, 4, 0);
+        assert_eq!(state.visible_range(), 0..4);
+        state.cursor = 5;
+        assert_eq!(state.visible_range(), 3..7);
+        state.cursor = 9;
+        assert_eq!(state.visible_range(), 6..10);
+    }
+}
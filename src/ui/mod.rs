@@ -1,9 +1,15 @@
+pub mod batch_actions;
+pub mod conflict_resolver;
 pub mod display;
 pub mod formatting;
+pub mod guide;
 pub mod hierarchical_display;
+pub mod key_menu;
 pub mod prompts;
 pub mod quick_launcher;
+pub mod repo_picker;
 pub mod setup_wizard;
 pub mod smart_menu;
 pub mod state;
 pub mod workflows;
+pub mod worktree_browser;
@@ -2,11 +2,20 @@ use anyhow::{Context, Result};
 use console;
 use console::style;
 use inquire::{Confirm, InquireError, MultiSelect, Select, Text};
+use std::collections::HashMap;
 use std::path::PathBuf;
 
-use crate::git::{GitConfig, SearchCommand};
-use crate::ui::smart_menu::{SmartAction, SmartActionType, SmartMenu};
+use crate::git::pull_requests::PrFilter;
+use crate::git::SearchCommand;
+use crate::output::theme::{self, Icon};
+use crate::ui::key_menu::{self, KeyMenuItem, KeyMenuOutcome};
+use crate::ui::smart_menu::{MenuBadges, SmartAction, SmartActionType, SmartMenu};
 use crate::ui::state::VibeState;
+use crate::ui::{batch_actions, repo_picker, worktree_browser};
+use crate::workspace::config::{repo_abs_path, ConfirmationLevel, RepositoryGroup};
+use crate::workspace::config_watcher::ConfigWatcher;
+use crate::workspace::launch_stats::LaunchSource;
+use crate::workspace::manager::{print_bulk_configure_summary, print_group_open_summary};
 use crate::workspace::WorkspaceManager;
 
 /// Represents a menu option with optional keyboard shortcut
@@ -113,7 +122,7 @@ impl MenuOption {
 /// - Ok(Some(value)) for successful prompts
 /// - Ok(None) for ESC key cancellation (navigation signal)
 /// - Err(error) for other fatal errors
-fn handle_prompt_result<T>(result: Result<T, InquireError>) -> Result<Option<T>> {
+pub(crate) fn handle_prompt_result<T>(result: Result<T, InquireError>) -> Result<Option<T>> {
     match result {
         Ok(value) => Ok(Some(value)),
         Err(InquireError::OperationCanceled) => {
@@ -141,7 +150,7 @@ fn format_navigation_option(text: &str) -> String {
 }
 
 /// Create a menu with standardized navigation options
-fn create_menu_with_navigation(options: Vec<String>, is_main_menu: bool) -> Vec<String> {
+pub(crate) fn create_menu_with_navigation(options: Vec<String>, is_main_menu: bool) -> Vec<String> {
     let mut menu_items = options;
 
     // Add separator
@@ -163,7 +172,7 @@ fn is_navigation_option(selection: &str) -> bool {
 }
 
 /// Extract navigation action from bracketed option
-fn get_navigation_action(selection: &str) -> Option<&str> {
+pub(crate) fn get_navigation_action(selection: &str) -> Option<&str> {
     if is_navigation_option(selection) {
         selection
             .strip_prefix('[')
@@ -173,7 +182,63 @@ fn get_navigation_action(selection: &str) -> Option<&str> {
     }
 }
 
+/// Watch config.yaml while the menu runs, when `preferences.watch_config` is on
+fn start_config_watcher(workspace_manager: &WorkspaceManager) -> Option<ConfigWatcher> {
+    if !workspace_manager.config().watch_config() {
+        return None;
+    }
+    match ConfigWatcher::spawn(workspace_manager.get_config_path()) {
+        Ok(watcher) => Some(watcher),
+        Err(e) => {
+            println!(
+                "{} Not watching the config file: {e:#}",
+                style("⚠️").yellow()
+            );
+            None
+        }
+    }
+}
+
+/// Pick up a config file that changed on disk, keeping the current config
+/// when the new one doesn't load
+async fn reload_config(workspace_manager: &mut WorkspaceManager) {
+    match workspace_manager.reload_config().await {
+        Ok(true) => println!("{} Reloaded config.yaml", style("🔄").cyan()),
+        Ok(false) => {}
+        Err(e) => println!(
+            "{} config.yaml changed but was not reloaded, keeping the previous configuration: {e:#}",
+            style("⚠️").yellow()
+        ),
+    }
+}
+
+/// Pull the shared configuration on menu start, when
+/// `preferences.sync.auto_pull_on_start` is on and sync is set up
+async fn auto_pull_shared_config(workspace_manager: &mut WorkspaceManager) {
+    if !workspace_manager.config().sync_auto_pull_on_start()
+        || !crate::workspace::config_sync::is_set_up()
+    {
+        return;
+    }
+    match workspace_manager.pull_shared_config().await {
+        Ok(report) if !report.pulled.is_empty() => {
+            println!(
+                "{} Pulled {} shared config entries",
+                style("🔄").cyan(),
+                report.pulled.len()
+            );
+        }
+        Ok(_) => {}
+        Err(e) => println!(
+            "{} Could not pull the shared config: {e:#}",
+            style("⚠️").yellow()
+        ),
+    }
+}
+
 pub async fn run_menu_mode(workspace_manager: &mut WorkspaceManager) -> Result<()> {
+    workspace_manager.set_launch_source(LaunchSource::Menu);
+
     // Check for first-time setup
     let smart_menu = SmartMenu::new(workspace_manager).await?;
     if smart_menu.should_show_setup_wizard() {
@@ -196,9 +261,41 @@ pub async fn run_menu_mode(workspace_manager: &mut WorkspaceManager) -> Result<(
         println!();
     }
 
+    auto_pull_shared_config(workspace_manager).await;
+
+    let mut config_watcher = start_config_watcher(workspace_manager);
+    let mut status_refresh: Option<tokio::task::JoinHandle<()>> = None;
+
     loop {
+        if let Some(watcher) = config_watcher.as_mut() {
+            if watcher.take_change() {
+                reload_config(workspace_manager).await;
+            }
+        }
+
+        // Keep the header counts current without holding up the menu
+        if workspace_manager.menu_header_enabled()
+            && status_refresh
+                .as_ref()
+                .is_none_or(|task| task.is_finished())
+        {
+            status_refresh = workspace_manager.spawn_status_cache_refresh().await;
+        }
+        let refreshing = status_refresh
+            .as_ref()
+            .is_some_and(|task| !task.is_finished());
+
         // Reload smart menu to get fresh state
         let smart_menu = SmartMenu::new(workspace_manager).await?;
+        let header = smart_menu.header(refreshing);
+        if let Some(header) = &header {
+            println!("{header}");
+        }
+        let shortcuts: &[char] = if header.is_some() {
+            &MenuBadges::SHORTCUTS
+        } else {
+            &[]
+        };
 
         // Build flat menu options without section headers
         let mut menu_options = Vec::new();
@@ -208,39 +305,48 @@ pub async fn run_menu_mode(workspace_manager: &mut WorkspaceManager) -> Result<(
         if !quick_items.is_empty() {
             menu_options.push(MenuOption::new(
                 'q',
-                "🚀 Quick Launch",
+                &theme::labeled(Icon::Rocket, "Quick Launch"),
                 "Recent repositories (1-9)",
             ));
         }
 
         menu_options.push(MenuOption::new(
             'o',
-            "📂 Open repo",
+            &theme::labeled(Icon::Folder, "Open repo"),
             "Open repository with app",
         ));
         menu_options.push(MenuOption::new(
             'n',
-            "🆕 Create new repo",
+            &theme::labeled(Icon::New, "Create new repo"),
             "Create local repository",
         ));
         menu_options.push(MenuOption::new(
             'c',
-            "📥 Clone GitHub repo",
+            &theme::labeled(Icon::Download, "Clone GitHub repo"),
             "Search and clone from GitHub",
         ));
         menu_options.push(MenuOption::new(
             'a',
-            "⚙️ Manage Apps",
+            &theme::labeled(Icon::Apps, "Manage Apps"),
             "Configure apps for repositories",
         ));
+        let repos_description = match workspace_manager.cached_open_pr_count().await {
+            Some(count) if count > 0 => format!("Repository management ({count} open PRs)"),
+            _ => "Repository management".to_string(),
+        };
         menu_options.push(MenuOption::new(
             'r',
-            "🔀 Manage Repos",
-            "Repository management",
+            &theme::labeled(Icon::Repos, "Manage Repos"),
+            &repos_description,
+        ));
+        menu_options.push(MenuOption::new(
+            'w',
+            &theme::labeled(Icon::Worktree, "Worktrees"),
+            "Browse worktrees across repositories",
         ));
         menu_options.push(MenuOption::new(
             's',
-            "⚙️ Settings",
+            &theme::labeled(Icon::Settings, "Settings"),
             "Configuration and templates",
         ));
 
@@ -250,24 +356,35 @@ pub async fn run_menu_mode(workspace_manager: &mut WorkspaceManager) -> Result<(
             menu_options.push(MenuOption::from_smart_action(action));
         }
 
-        // No need for single-key shortcuts hint - just show the menu directly
-
-        // Create display options for Inquire Select
+        // Create display options for the menu prompt
         let display_options: Vec<String> =
             menu_options.iter().map(|opt| opt.display_label()).collect();
 
         let menu_options_with_nav = create_menu_with_navigation(display_options, true);
-
-        let selection_result = Select::new("What would you like to do?", menu_options_with_nav)
-            .with_starting_cursor(if quick_items.is_empty() { 0 } else { 1 })
-            .with_page_size(workspace_manager.get_main_menu_page_size())
-            .with_help_message("Use arrow keys to navigate • Enter to select • ESC to exit • Type shortcut key+ENTER for quick nav")
-            .prompt();
-
-        let selection = match handle_prompt_result(selection_result)? {
-            Some(selection) => selection,
-            None => {
-                // ESC pressed - exit
+        let starting_cursor = if quick_items.is_empty() { 0 } else { 1 };
+
+        let selection = match prompt_main_menu(
+            workspace_manager,
+            &menu_options,
+            menu_options_with_nav,
+            starting_cursor,
+            quick_items.len(),
+            shortcuts,
+        )? {
+            MainMenuChoice::Selection(selection) => selection,
+            MainMenuChoice::Shortcut(key) => {
+                open_badge_view(workspace_manager, key).await?;
+                println!();
+                continue;
+            }
+            MainMenuChoice::QuickLaunch(slot) => {
+                let item = &quick_items[slot - 1];
+                launch_repository(workspace_manager, &item.repo_name, item.last_app.as_deref())
+                    .await?;
+                println!();
+                continue;
+            }
+            MainMenuChoice::Exit => {
                 println!("👋 Goodbye!");
                 break;
             }
@@ -296,6 +413,96 @@ pub async fn run_menu_mode(workspace_manager: &mut WorkspaceManager) -> Result<(
     Ok(())
 }
 
+/// Result of the main menu prompt
+enum MainMenuChoice {
+    Selection(String),
+    /// Quick launch slot 1-9
+    QuickLaunch(usize),
+    /// A header badge shortcut
+    Shortcut(char),
+    Exit,
+}
+
+/// Open the view behind a header badge from [`MenuBadges::SHORTCUTS`]
+async fn open_badge_view(workspace_manager: &mut WorkspaceManager, key: char) -> Result<()> {
+    match key {
+        'd' => workspace_manager.show_status(true, "compact", None).await?,
+        'b' => {
+            workspace_manager
+                .show_status(false, "compact", None)
+                .await?
+        }
+        'p' => match workspace_manager
+            .collect_pull_requests(PrFilter::All, None, false)
+            .await
+        {
+            Ok(dashboard) => dashboard.print_table(),
+            Err(e) => println!("{} {}", style("✗").red(), e),
+        },
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Prompt for a main menu choice. Uses single-keystroke shortcuts when the
+/// terminal supports raw key reads, otherwise the classic select prompt.
+fn prompt_main_menu(
+    workspace_manager: &WorkspaceManager,
+    menu_options: &[MenuOption],
+    display_options: Vec<String>,
+    starting_cursor: usize,
+    quick_slots: usize,
+    shortcuts: &[char],
+) -> Result<MainMenuChoice> {
+    let page_size = workspace_manager.get_main_menu_page_size();
+
+    let items: Vec<KeyMenuItem> = display_options
+        .iter()
+        .enumerate()
+        .map(|(index, label)| match menu_options.get(index) {
+            Some(option) => KeyMenuItem::new(label.clone(), option.key),
+            None if label.contains(NAVIGATION_SEPARATOR) => KeyMenuItem::separator(label.clone()),
+            None => KeyMenuItem::new(label.clone(), None),
+        })
+        .collect();
+
+    let help = if quick_slots > 0 {
+        "↑↓ to move • Enter to select • press a shortcut key or 1-9 to act immediately • ESC to exit"
+    } else {
+        "↑↓ to move • Enter to select • press a shortcut key to act immediately • ESC to exit"
+    };
+
+    if let Some(outcome) = key_menu::select(
+        "What would you like to do?",
+        &items,
+        starting_cursor,
+        page_size,
+        quick_slots,
+        shortcuts,
+        help,
+    )? {
+        return Ok(match outcome {
+            KeyMenuOutcome::Selected(index) => {
+                MainMenuChoice::Selection(display_options[index].clone())
+            }
+            KeyMenuOutcome::Slot(slot) => MainMenuChoice::QuickLaunch(slot as usize),
+            KeyMenuOutcome::Shortcut(key) => MainMenuChoice::Shortcut(key),
+            KeyMenuOutcome::Cancelled => MainMenuChoice::Exit,
+        });
+    }
+
+    let selection_result = Select::new("What would you like to do?", display_options)
+        .with_starting_cursor(starting_cursor)
+        .with_page_size(page_size)
+        .with_help_message("Use arrow keys to navigate • Enter to select • ESC to exit • Type shortcut key+ENTER for quick nav")
+        .prompt();
+
+    Ok(match handle_prompt_result(selection_result)? {
+        Some(selection) => MainMenuChoice::Selection(selection),
+        None => MainMenuChoice::Exit,
+    })
+}
+
 /// Find menu option by its display label
 fn find_menu_option_by_label<'a>(
     menu_options: &'a [MenuOption],
@@ -305,7 +512,7 @@ fn find_menu_option_by_label<'a>(
 }
 
 async fn search_and_clone_interactive(workspace_manager: &mut WorkspaceManager) -> Result<()> {
-    let git_config = GitConfig::default();
+    let git_config = workspace_manager.git_config();
     SearchCommand::execute_interactive(workspace_manager, &git_config).await?;
     Ok(())
 }
@@ -331,7 +538,7 @@ async fn bulk_clone_interactive(workspace_manager: &mut WorkspaceManager) -> Res
         return Ok(());
     }
 
-    let git_config = GitConfig::default();
+    let git_config = workspace_manager.git_config();
 
     // Use the enhanced clone command to detect and route
     EnhancedCloneCommand::execute_with_detection(
@@ -339,6 +546,7 @@ async fn bulk_clone_interactive(workspace_manager: &mut WorkspaceManager) -> Res
         None,
         true, // no_configure - bulk mode skips app config
         true, // no_open - bulk mode doesn't open repos
+        None,
         workspace_manager,
         &git_config,
     )
@@ -347,7 +555,7 @@ async fn bulk_clone_interactive(workspace_manager: &mut WorkspaceManager) -> Res
     Ok(())
 }
 
-async fn show_status_interactive(workspace_manager: &WorkspaceManager) -> Result<()> {
+async fn show_status_interactive(workspace_manager: &mut WorkspaceManager) -> Result<()> {
     let options = vec![
         "All repositories".to_string(),
         "Only dirty repositories".to_string(),
@@ -380,8 +588,11 @@ async fn show_status_interactive(workspace_manager: &WorkspaceManager) -> Result
             workspace_manager.show_status(true, "table", None).await?;
         }
         "Select group" => {
-            // TODO: Implement group selection
-            println!("Group selection not yet implemented");
+            if let Some(group) = select_group_interactive(workspace_manager).await? {
+                workspace_manager
+                    .show_status(false, "table", Some(&group))
+                    .await?;
+            }
         }
         _ => unreachable!(),
     }
@@ -389,6 +600,273 @@ async fn show_status_interactive(workspace_manager: &WorkspaceManager) -> Result
     Ok(())
 }
 
+/// Menu label for a repository group
+fn format_group_option(name: &str, repo_count: usize) -> String {
+    let noun = if repo_count == 1 { "repo" } else { "repos" };
+    format!("👥 {name} ({repo_count} {noun})")
+}
+
+/// Prompt for a configured group, pre-selecting the last one used.
+/// Offers to create a group when none exist. Returns `None` on ESC or Back.
+async fn select_group_interactive(
+    workspace_manager: &mut WorkspaceManager,
+) -> Result<Option<String>> {
+    if workspace_manager.config().groups.is_empty() {
+        println!("{} No groups defined yet", style("ℹ").yellow());
+        let create = handle_prompt_result(
+            Confirm::new("Create a group now?")
+                .with_default(true)
+                .prompt(),
+        )?;
+        if create != Some(true) {
+            return Ok(None);
+        }
+        if create_group_interactive(workspace_manager).await?.is_none() {
+            return Ok(None);
+        }
+    }
+
+    let groups = &workspace_manager.config().groups;
+    let labels: Vec<String> = groups
+        .iter()
+        .map(|group| format_group_option(&group.name, group.repos.len()))
+        .collect();
+
+    let mut state = VibeState::load().unwrap_or_default();
+    let starting_cursor = state
+        .last_status_group
+        .as_ref()
+        .and_then(|last| groups.iter().position(|group| &group.name == last))
+        .unwrap_or(0);
+
+    let menu_options = create_menu_with_navigation(labels.clone(), false);
+    let choice_result = Select::new("Select group:", menu_options)
+        .with_starting_cursor(starting_cursor)
+        .with_page_size(workspace_manager.get_management_menus_page_size())
+        .with_help_message("Choose a group to show status for • ESC to go back")
+        .prompt();
+
+    let Some(choice) = handle_prompt_result(choice_result)? else {
+        return Ok(None);
+    };
+    let Some(index) = labels.iter().position(|label| label == &choice) else {
+        // Back or separator
+        return Ok(None);
+    };
+
+    let group = groups[index].name.clone();
+    state.last_status_group = Some(group.clone());
+    state.save()?;
+
+    Ok(Some(group))
+}
+
+/// Create a repository group from a name and a set of repositories and save it
+/// to the workspace config. Returns the new group's name, or `None` if cancelled.
+async fn create_group_interactive(
+    workspace_manager: &mut WorkspaceManager,
+) -> Result<Option<String>> {
+    let repo_names: Vec<String> = workspace_manager
+        .list_repositories()
+        .iter()
+        .map(|repo| repo.name.clone())
+        .collect();
+    if repo_names.is_empty() {
+        println!(
+            "{} No repositories configured. Run 'vibe git scan' first.",
+            style("ℹ").yellow()
+        );
+        return Ok(None);
+    }
+
+    let Some(name) = handle_prompt_result(
+        Text::new("Group name:")
+            .with_help_message("ESC to go back")
+            .prompt(),
+    )?
+    else {
+        return Ok(None);
+    };
+    let name = name.trim().to_string();
+    if let Err(reason) = workspace_manager.config().validate_new_group_name(&name) {
+        println!("{} {}", style("❌").red(), reason);
+        return Ok(None);
+    }
+
+    let Some(repos) = handle_prompt_result(
+        MultiSelect::new("Select repositories for the group:", repo_names)
+            .with_page_size(workspace_manager.get_management_menus_page_size())
+            .with_help_message("Space to select, Enter to confirm • ESC to go back")
+            .prompt(),
+    )?
+    else {
+        return Ok(None);
+    };
+    if repos.is_empty() {
+        println!("{} No repositories selected", style("ℹ").yellow());
+        return Ok(None);
+    }
+
+    let repo_count = repos.len();
+    workspace_manager.config_mut().add_group(RepositoryGroup {
+        name: name.clone(),
+        repos,
+        apps: HashMap::new(),
+        default_app: None,
+        default_template: None,
+    });
+    workspace_manager.save_config().await?;
+
+    println!(
+        "{} Created group {} with {} repositories",
+        style("✓").green().bold(),
+        style(&name).cyan(),
+        repo_count
+    );
+
+    Ok(Some(name))
+}
+
+/// Pick one of the configured groups, or `None` when there are none or ESC
+/// was pressed
+fn select_group(workspace_manager: &WorkspaceManager, prompt: &str) -> Result<Option<String>> {
+    let names: Vec<String> = workspace_manager
+        .config()
+        .groups
+        .iter()
+        .map(|group| group.name.clone())
+        .collect();
+    if names.is_empty() {
+        println!(
+            "{} No groups configured. Create one first.",
+            style("ℹ").yellow()
+        );
+        return Ok(None);
+    }
+    handle_prompt_result(
+        Select::new(prompt, names)
+            .with_page_size(workspace_manager.get_management_menus_page_size())
+            .with_help_message("ESC to go back")
+            .prompt(),
+    )
+}
+
+async fn add_to_group_interactive(workspace_manager: &mut WorkspaceManager) -> Result<()> {
+    let Some(group) = select_group(workspace_manager, "Add repositories to group:")? else {
+        return Ok(());
+    };
+    let members = workspace_manager
+        .config()
+        .groups
+        .iter()
+        .find(|g| g.name == group)
+        .map(|g| g.repos.clone())
+        .unwrap_or_default();
+    let candidates: Vec<String> = workspace_manager
+        .list_repositories()
+        .iter()
+        .map(|repo| repo.name.clone())
+        .filter(|name| !members.contains(name))
+        .collect();
+    if candidates.is_empty() {
+        println!(
+            "{} Every repository is already in {}",
+            style("ℹ").yellow(),
+            style(&group).cyan()
+        );
+        return Ok(());
+    }
+
+    let Some(repos) = handle_prompt_result(
+        MultiSelect::new("Select repositories to add:", candidates)
+            .with_page_size(workspace_manager.get_management_menus_page_size())
+            .with_help_message("Space to select, Enter to confirm • ESC to go back")
+            .prompt(),
+    )?
+    else {
+        return Ok(());
+    };
+    for repo in &repos {
+        workspace_manager.add_repo_to_group(repo, &group).await?;
+    }
+    println!(
+        "{} Added {} repositories to {}",
+        style("✓").green().bold(),
+        repos.len(),
+        style(&group).cyan()
+    );
+    Ok(())
+}
+
+async fn remove_from_group_interactive(workspace_manager: &mut WorkspaceManager) -> Result<()> {
+    let Some(group) = select_group(workspace_manager, "Remove repositories from group:")? else {
+        return Ok(());
+    };
+    let members = workspace_manager
+        .config()
+        .groups
+        .iter()
+        .find(|g| g.name == group)
+        .map(|g| g.repos.clone())
+        .unwrap_or_default();
+    if members.is_empty() {
+        println!(
+            "{} {} has no repositories",
+            style("ℹ").yellow(),
+            style(&group).cyan()
+        );
+        return Ok(());
+    }
+
+    let Some(repos) = handle_prompt_result(
+        MultiSelect::new("Select repositories to remove:", members)
+            .with_page_size(workspace_manager.get_management_menus_page_size())
+            .with_help_message("Space to select, Enter to confirm • ESC to go back")
+            .prompt(),
+    )?
+    else {
+        return Ok(());
+    };
+    if let Some(g) = workspace_manager
+        .config_mut()
+        .groups
+        .iter_mut()
+        .find(|g| g.name == group)
+    {
+        g.repos.retain(|name| !repos.contains(name));
+    }
+    workspace_manager.save_config().await?;
+    println!(
+        "{} Removed {} repositories from {}",
+        style("✓").green().bold(),
+        repos.len(),
+        style(&group).cyan()
+    );
+    Ok(())
+}
+
+async fn delete_group_interactive(workspace_manager: &mut WorkspaceManager) -> Result<()> {
+    let Some(group) = select_group(workspace_manager, "Delete group:")? else {
+        return Ok(());
+    };
+    let prompt = format!("Delete group '{group}'? Its repositories stay configured");
+    let action = DestructiveAction::new(&prompt);
+    if !confirm_destructive(action, workspace_manager.get_confirmation_level())? {
+        return Ok(());
+    }
+    workspace_manager
+        .config_mut()
+        .groups
+        .retain(|g| g.name != group);
+    workspace_manager.save_config().await?;
+    println!(
+        "{} Deleted group {}",
+        style("✓").green().bold(),
+        style(&group).cyan()
+    );
+    Ok(())
+}
+
 async fn discover_repositories_interactive(workspace_manager: &mut WorkspaceManager) -> Result<()> {
     // First, show options menu
     let options = vec![
@@ -626,9 +1104,7 @@ async fn execute_command_interactive(workspace_manager: &WorkspaceManager) -> Re
     Ok(())
 }
 
-async fn manage_groups_interactive(_workspace_manager: &WorkspaceManager) -> Result<()> {
-    println!("🚧 Group management coming soon!");
-
+async fn manage_groups_interactive(workspace_manager: &mut WorkspaceManager) -> Result<()> {
     let actions = vec![
         "Create new group".to_string(),
         "Add repositories to group".to_string(),
@@ -637,10 +1113,29 @@ async fn manage_groups_interactive(_workspace_manager: &WorkspaceManager) -> Res
     ];
 
     let menu_options = create_menu_with_navigation(actions, false);
-    let _action = Select::new("Group management:", menu_options).prompt()?;
+    let action_result = Select::new("Group management:", menu_options)
+        .with_help_message("Choose group action • ESC to go back")
+        .prompt();
 
-    // TODO: Implement group management functionality
-    println!("This feature will be implemented in a future release");
+    let Some(action) = handle_prompt_result(action_result)? else {
+        return Ok(());
+    };
+
+    match action.as_str() {
+        "Create new group" => {
+            create_group_interactive(workspace_manager).await?;
+        }
+        "Add repositories to group" => {
+            add_to_group_interactive(workspace_manager).await?;
+        }
+        "Remove repositories from group" => {
+            remove_from_group_interactive(workspace_manager).await?;
+        }
+        "Delete group" => {
+            delete_group_interactive(workspace_manager).await?;
+        }
+        _ => {}
+    }
 
     Ok(())
 }
@@ -713,15 +1208,16 @@ async fn configure_apps_for_repositories_interactive(
     }
 
     // Select repository to configure
-    let repo_names: Vec<&str> = config
-        .repositories
-        .iter()
-        .map(|r| r.name.as_str())
-        .collect();
-
-    let repo_name = Select::new("Select repository to configure:", repo_names)
-        .prompt()?
-        .to_string();
+    let git_statuses = repo_picker::load_cached_git_statuses().await;
+    let items = repo_picker::items_for_workspace(config, &git_statuses);
+    let repo_name = match handle_prompt_result(repo_picker::pick_repository(
+        "Select repository to configure:",
+        items,
+        workspace_manager.get_repository_list_page_size(),
+    ))? {
+        Some(item) => item.name,
+        None => return Ok(()),
+    };
 
     // Get current app configuration state
     let current_state = workspace_manager.get_current_app_states(&repo_name)?;
@@ -928,6 +1424,25 @@ async fn configure_apps_for_repositories_interactive(
         });
     }
 
+    // Removing app configurations deletes their generated files
+    let removed: Vec<&str> = app_selections
+        .iter()
+        .filter(|selection| !selection.selected && selection.currently_configured)
+        .map(|selection| selection.app.as_str())
+        .collect();
+    if !removed.is_empty() {
+        let prompt = format!(
+            "Remove {} configuration from '{}'?",
+            removed.join(", "),
+            repo_name
+        );
+        let level = workspace_manager.get_confirmation_level();
+        if !confirm_destructive(DestructiveAction::new(&prompt), level)? {
+            println!("{} No changes made", console::style("ℹ️").blue());
+            return Ok(());
+        }
+    }
+
     // Apply the configuration changes
     let changes = workspace_manager
         .configure_multiple_apps(&repo_name, app_selections)
@@ -960,6 +1475,23 @@ async fn configure_apps_for_repositories_interactive(
 
 /// Enhanced repository launcher with contextual configuration options
 async fn launch_repository_with_cache(workspace_manager: &mut WorkspaceManager) -> Result<()> {
+    // Offer to open a whole group when any are defined
+    if !workspace_manager.config().groups.is_empty() {
+        let choice = handle_prompt_result(
+            Select::new(
+                "Open:",
+                vec!["A repository".to_string(), "A whole group".to_string()],
+            )
+            .with_help_message("ESC to go back")
+            .prompt(),
+        )?;
+        match choice.as_deref() {
+            Some("A whole group") => return open_group_interactive(workspace_manager).await,
+            Some(_) => {}
+            None => return Ok(()),
+        }
+    }
+
     // Check for unconfigured repositories first
     let repos = workspace_manager.list_repositories();
     let unconfigured_repos: Vec<String> = repos
@@ -1006,10 +1538,108 @@ async fn launch_repository_with_cache(workspace_manager: &mut WorkspaceManager)
     Ok(())
 }
 
+/// Offer to install the workspace's managed git hooks in newly added
+/// repositories, when any are configured and the session is interactive
+pub async fn offer_hook_sync(
+    workspace_manager: &WorkspaceManager,
+    repo_names: &[String],
+) -> Result<()> {
+    use std::io::IsTerminal;
+
+    let hooks = &workspace_manager.config().hooks.git;
+    if repo_names.is_empty()
+        || hooks.is_empty()
+        || !crate::output::is_interactive()
+        || !std::io::stdin().is_terminal()
+    {
+        return Ok(());
+    }
+
+    let hook_names: Vec<&str> = hooks.keys().map(String::as_str).collect();
+    if !prompt_yes_no(
+        &format!(
+            "Install the workspace git hooks ({}) in {}?",
+            hook_names.join(", "),
+            repo_names.join(", ")
+        ),
+        true,
+    )? {
+        return Ok(());
+    }
+
+    let results = workspace_manager
+        .sync_hooks(Some(&repo_names.join(",")), None)
+        .await?;
+    crate::git::hooks::print_results(&results);
+    Ok(())
+}
+
+/// Pick a group and an app, then open every repository in the group
+async fn open_group_interactive(workspace_manager: &mut WorkspaceManager) -> Result<()> {
+    let groups: Vec<String> = workspace_manager
+        .config()
+        .groups
+        .iter()
+        .map(|g| g.name.clone())
+        .collect();
+    let Some(group) = handle_prompt_result(
+        Select::new("Group to open:", groups)
+            .with_help_message("ESC to go back")
+            .prompt(),
+    )?
+    else {
+        return Ok(());
+    };
+
+    let apps = workspace_manager.get_available_apps().await;
+    if apps.is_empty() {
+        println!(
+            "{} No supported apps found on this system",
+            style("⚠️").yellow()
+        );
+        return Ok(());
+    }
+    let Some(app) = handle_prompt_result(
+        Select::new("Open with:", apps)
+            .with_help_message("ESC to go back")
+            .prompt(),
+    )?
+    else {
+        return Ok(());
+    };
+
+    let missing: Vec<String> = workspace_manager
+        .config()
+        .get_repositories_in_group(&group)
+        .into_iter()
+        .filter(|repo| !repo.is_app_enabled(&app))
+        .map(|repo| repo.name.clone())
+        .collect();
+    let configure_missing = !missing.is_empty()
+        && prompt_yes_no(
+            &format!(
+                "{} not configured for {}. Configure with the default template?",
+                app,
+                missing.join(", ")
+            ),
+            true,
+        )?;
+
+    let fetch = workspace_manager.is_fetch_on_open_enabled();
+    let results = workspace_manager
+        .open_group(&group, &app, configure_missing, false, fetch)
+        .await?;
+    print_group_open_summary(&group, &app, &results);
+    println!();
+    Ok(())
+}
+
 async fn manage_repos_interactive(workspace_manager: &mut WorkspaceManager) -> Result<()> {
     loop {
         let actions = vec![
             "Show repository status".to_string(),
+            "Show open pull requests".to_string(),
+            "Batch actions on multiple repositories".to_string(),
             "Configure apps for repositories".to_string(),
             "Scan workspace for repos".to_string(),
             "Fetch and pull from remote".to_string(),
@@ -1044,6 +1674,19 @@ async fn manage_repos_interactive(workspace_manager: &mut WorkspaceManager) -> R
             "Show repository status" => {
                 show_status_interactive(workspace_manager).await?;
             }
+            "Show open pull requests" => {
+                match workspace_manager
+                    .collect_pull_requests(PrFilter::All, None, false)
+                    .await
+                {
+                    Ok(dashboard) => dashboard.print_table(),
+                    Err(e) => println!("{} {}", style("✗").red(), e),
+                }
+                println!();
+            }
+            "Batch actions on multiple repositories" => {
+                batch_actions::run_batch_actions(workspace_manager).await?;
+            }
             "Configure apps for repositories" => {
                 configure_apps_for_repositories_interactive(workspace_manager).await?;
             }
@@ -1148,7 +1791,7 @@ async fn factory_reset_interactive(workspace_manager: &mut WorkspaceManager) ->
         );
 
         // Create backup with timestamped name
-        match workspace_manager.create_backup(None, None).await {
+        match workspace_manager.create_backup(None, None, None).await {
             Ok(backup_path) => {
                 println!(
                     "{} Backup created: {}",
@@ -1214,15 +1857,26 @@ async fn create_backup_interactive(workspace_manager: &WorkspaceManager) -> Resu
         .prompt()?;
 
     let custom_name = if use_custom_name {
-        let name_input = Text::new("Backup name (without .tgz extension):").prompt()?;
+        let name_input = Text::new("Backup name (without extension):").prompt()?;
         Some(name_input)
     } else {
         None
     };
 
+    // Backups can hold tokens from app configs, so offer to encrypt them
+    let encrypt = Confirm::new("Encrypt the backup with a passphrase?")
+        .with_default(false)
+        .with_help_message("Recommended when the backup is synced to cloud storage")
+        .prompt()?;
+    let passphrase = if encrypt {
+        Some(crate::workspace::backup_crypto::passphrase_for_encryption()?)
+    } else {
+        None
+    };
+
     // Create the backup
     match workspace_manager
-        .create_backup(output_dir, custom_name)
+        .create_backup(output_dir, custom_name, passphrase)
         .await
     {
         Ok(backup_path) => {
@@ -1257,6 +1911,110 @@ pub fn prompt_yes_no(prompt: &str, default: bool) -> Result<bool> {
         .context("Failed to get user confirmation")
 }
 
+/// A destructive operation to confirm with [`confirm_destructive`]
+#[derive(Debug, Clone, Copy)]
+pub struct DestructiveAction<'a> {
+    prompt: &'a str,
+    phrase: Option<&'a str>,
+}
+
+impl<'a> DestructiveAction<'a> {
+    pub fn new(prompt: &'a str) -> Self {
+        Self {
+            prompt,
+            phrase: None,
+        }
+    }
+
+    /// Require typing `phrase` first. Unlike the yes/no prompts this is asked
+    /// at every level, including `minimal`.
+    pub fn with_phrase(mut self, phrase: &'a str) -> Self {
+        self.phrase = Some(phrase);
+        self
+    }
+}
+
+/// Number of yes/no prompts asked at a confirmation level
+fn yes_no_prompt_count(level: ConfirmationLevel) -> usize {
+    match level {
+        ConfirmationLevel::Strict => 2,
+        ConfirmationLevel::Normal => 1,
+        ConfirmationLevel::Minimal => 0,
+    }
+}
+
+/// Confirm a destructive operation according to the user's confirmation
+/// level. ESC counts as no, and so does running without a terminal; callers
+/// skip this entirely when `--yes`/`--force` was passed.
+pub fn confirm_destructive(action: DestructiveAction, level: ConfirmationLevel) -> Result<bool> {
+    use std::io::IsTerminal;
+
+    let prompts = yes_no_prompt_count(level);
+    if action.phrase.is_none() && prompts == 0 {
+        return Ok(true);
+    }
+
+    if !std::io::stdin().is_terminal() {
+        eprintln!(
+            "{} Not confirmed: '{}' needs a terminal. Re-run with --yes or --force to proceed.",
+            style("⚠️").yellow(),
+            action.prompt
+        );
+        return Ok(false);
+    }
+
+    if let Some(phrase) = action.phrase {
+        let typed = handle_prompt_result(
+            Text::new(&format!("Type '{phrase}' to confirm:"))
+                .with_help_message(action.prompt)
+                .prompt(),
+        )?;
+        if typed.as_deref() != Some(phrase) {
+            return Ok(false);
+        }
+    }
+
+    let messages = [
+        action.prompt,
+        "Are you absolutely sure? This cannot be undone.",
+    ];
+    for message in messages.iter().take(prompts) {
+        let answer = handle_prompt_result(Confirm::new(message).with_default(false).prompt())?;
+        if answer != Some(true) {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+/// Before a destructive confirmation, offer to print everything the
+/// operation would change. False when the user cancels here; without a
+/// terminal nothing is asked and [`confirm_destructive`] decides.
+pub fn offer_full_details(show_details: impl FnOnce()) -> Result<bool> {
+    use std::io::IsTerminal;
+
+    const CONTINUE: &str = "Continue";
+    const DETAILS: &str = "Show full details";
+    const CANCEL: &str = "Cancel";
+
+    if !std::io::stdin().is_terminal() {
+        return Ok(true);
+    }
+    let choice = handle_prompt_result(
+        Select::new("Review before confirming?", vec![CONTINUE, DETAILS, CANCEL]).prompt(),
+    )?;
+    match choice {
+        Some(DETAILS) => {
+            show_details();
+            println!();
+            Ok(true)
+        }
+        Some(CONTINUE) => Ok(true),
+        _ => Ok(false),
+    }
+}
+
 /// Prompt user to select an app
 pub fn prompt_app_selection() -> Result<String> {
     let apps = vec!["vscode", "warp", "iterm2", "wezterm", "cursor", "windsurf"];
@@ -1299,6 +2057,7 @@ async fn handle_smart_action(
             let workflow = Box::new(CreateRepositoryWorkflow {
                 suggested_name: None,
                 app: None,
+                group: None,
                 skip_configure: false,
                 skip_open: false,
             });
@@ -1335,7 +2094,7 @@ async fn launch_repository(
 ) -> Result<()> {
     // Get repository info and clone the path
     let repo_path = workspace_manager
-        .get_repository(repo_name)
+        .get_open_target(repo_name)
         .ok_or_else(|| anyhow::anyhow!("Repository '{}' not found", repo_name))?
         .path
         .clone();
@@ -1378,6 +2137,15 @@ async fn launch_repository(
         .open_repo_with_app(repo_name, &app_to_use)
         .await?;
 
+    // The file manager and browser report for themselves and aren't
+    // remembered as the repository's app
+    if crate::apps::system::is_pseudo_app(&app_to_use) {
+        let mut state = VibeState::load().unwrap_or_default();
+        state.add_recent_repo(repo_name.to_string(), repo_path, None);
+        state.save()?;
+        return Ok(());
+    }
+
     // Update state with this access
     let mut state = VibeState::load().unwrap_or_default();
     state.add_recent_repo(repo_name.to_string(), repo_path, Some(app_to_use.clone()));
@@ -1465,10 +2233,8 @@ async fn configure_apps_for_repos(
 async fn cleanup_missing_repos(workspace_manager: &mut WorkspaceManager) -> Result<()> {
     let repos = workspace_manager.list_repositories();
     let mut missing = Vec::new();
-    let workspace_root = workspace_manager.get_workspace_root();
-
     for repo in repos {
-        let full_path = workspace_root.join(&repo.path);
+        let full_path = repo_abs_path(workspace_manager.config(), repo);
         if !full_path.exists() {
             missing.push(repo.name.clone());
         }
@@ -1491,7 +2257,14 @@ async fn cleanup_missing_repos(workspace_manager: &mut WorkspaceManager) -> Resu
 
     if prompt_yes_no("\nRemove these from configuration?", true)? {
         for name in missing {
-            workspace_manager.remove_repository(&name).await?;
+            if let Some(id) = workspace_manager.remove_repository(&name).await? {
+                println!(
+                    "  {} {} is in the trash as {}",
+                    style("•").dim(),
+                    name,
+                    style(id).dim()
+                );
+            }
         }
         println!(
             "{} Cleaned up missing repositories",
@@ -1661,10 +2434,11 @@ async fn restore_backup_interactive(workspace_manager: &mut WorkspaceManager) ->
     for backup in &backups {
         let size_mb = backup.size as f64 / (1024.0 * 1024.0);
         println!(
-            "  {} {} ({:.1} MB)",
+            "  {} {} ({:.1} MB{})",
             style("→").dim(),
             backup.display_name,
-            size_mb
+            size_mb,
+            if backup.encrypted { ", encrypted" } else { "" }
         );
     }
     println!();
@@ -1709,6 +2483,7 @@ async fn handle_menu_option_action(
                     let workflow = Box::new(CreateRepositoryWorkflow {
                         suggested_name: None,
                         app: None,
+                        group: None,
                         skip_configure: false,
                         skip_open: false,
                     });
@@ -1727,6 +2502,10 @@ async fn handle_menu_option_action(
                     manage_repos_interactive(workspace_manager).await?;
                     Ok(true)
                 }
+                'w' => {
+                    worktree_browser::run_worktree_browser(workspace_manager).await?;
+                    Ok(true)
+                }
                 's' => {
                     configure_vibes_interactive(workspace_manager).await?;
                     Ok(true)
@@ -1960,30 +2739,146 @@ async fn manage_specific_app_interactive(
     Ok(())
 }
 
+/// Pick a template for `app_name`, or create one from the default template
+async fn select_app_template(
+    workspace_manager: &mut WorkspaceManager,
+    app_name: &str,
+) -> Result<String> {
+    let templates = workspace_manager.list_templates(app_name).await?;
+    let mut template_choices = if templates.is_empty() {
+        vec!["default".to_string()]
+    } else {
+        templates
+    };
+    template_choices.push("Create new template...".to_string());
+
+    let selected_template = Select::new(
+        &format!("Select template for {}:", app_name),
+        template_choices,
+    )
+    .prompt()?;
+
+    if selected_template != "Create new template..." {
+        return Ok(selected_template);
+    }
+    let template_name = Text::new("Template name:").prompt()?;
+    println!(
+        "📝 Creating template '{}' from default template",
+        template_name
+    );
+    let default_content = workspace_manager.get_default_template(app_name).await?;
+    workspace_manager
+        .save_template(app_name, &template_name, &default_content)
+        .await?;
+    println!("✅ Template created");
+    Ok(template_name)
+}
+
+/// Apply one app and template to several repositories at once
+async fn configure_app_for_many_repositories_interactive(
+    workspace_manager: &mut WorkspaceManager,
+    app_name: &str,
+) -> Result<()> {
+    let repositories = &workspace_manager.get_config().repositories;
+    let names: Vec<String> = repositories.iter().map(|repo| repo.name.clone()).collect();
+    let labels: Vec<String> = repositories
+        .iter()
+        .map(|repo| {
+            if repo.is_app_enabled(app_name) {
+                format!(
+                    "{} (template: {})",
+                    repo.name,
+                    repo.get_app_template(app_name).unwrap_or("default")
+                )
+            } else {
+                repo.name.clone()
+            }
+        })
+        .collect();
+
+    let Some(selected) = handle_prompt_result(
+        MultiSelect::new(
+            &format!("Select repositories to configure {app_name} for:"),
+            labels,
+        )
+        .with_page_size(workspace_manager.get_repository_list_page_size())
+        .with_help_message("Space to select, → to select all, Enter to confirm • ESC to go back")
+        .raw_prompt(),
+    )?
+    else {
+        return Ok(());
+    };
+    let repo_names: Vec<String> = selected
+        .iter()
+        .map(|option| names[option.index].clone())
+        .collect();
+    if repo_names.is_empty() {
+        println!("{} No repositories selected", style("ℹ").yellow());
+        return Ok(());
+    }
+
+    let template = select_app_template(workspace_manager, app_name).await?;
+
+    let already_configured = repo_names
+        .iter()
+        .filter_map(|name| workspace_manager.get_config().get_repository(name))
+        .filter(|repo| {
+            repo.is_app_enabled(app_name)
+                && repo.get_app_template(app_name).unwrap_or("default") != template
+        })
+        .count();
+    let overwrite = already_configured > 0
+        && prompt_yes_no(
+            &format!(
+                "{already_configured} of the selected repositories already configure {app_name} with another template. Replace it?"
+            ),
+            false,
+        )?;
+
+    let results = workspace_manager
+        .configure_app_for_repos(&repo_names, app_name, &template, overwrite)
+        .await?;
+    println!();
+    print_bulk_configure_summary(app_name, &results);
+    Ok(())
+}
+
 /// Configure specific app for repositories
 async fn configure_app_for_repositories_interactive(
     workspace_manager: &mut WorkspaceManager,
     app_name: &str,
 ) -> Result<()> {
-    let config = workspace_manager.get_config();
-    if config.repositories.is_empty() {
+    if workspace_manager.get_config().repositories.is_empty() {
         println!("❌ No repositories configured in workspace");
         return Ok(());
     }
 
-    // Select repository to configure
-    let repo_names: Vec<&str> = config
-        .repositories
-        .iter()
-        .map(|r| r.name.as_str())
-        .collect();
+    let scope = Select::new(
+        &format!("Configure {app_name} for:"),
+        vec!["One repository", "Multiple repositories"],
+    )
+    .prompt();
+    match handle_prompt_result(scope)? {
+        Some("Multiple repositories") => {
+            return configure_app_for_many_repositories_interactive(workspace_manager, app_name)
+                .await;
+        }
+        Some(_) => {}
+        None => return Ok(()),
+    }
 
-    let repo_name = Select::new(
+    // Select repository to configure
+    let config = workspace_manager.get_config();
+    let git_statuses = repo_picker::load_cached_git_statuses().await;
+    let items = repo_picker::items_for_workspace(config, &git_statuses);
+    let repo_name = match handle_prompt_result(repo_picker::pick_repository(
         &format!("Select repository to configure {} for:", app_name),
-        repo_names,
-    )
-    .prompt()?
-    .to_string();
+        items,
+        workspace_manager.get_repository_list_page_size(),
+    ))? {
+        Some(item) => item.name,
+        None => return Ok(()),
+    };
 
     // Get current app configuration state for this specific app
     let current_state = workspace_manager.get_current_app_states(&repo_name)?;
@@ -2034,35 +2929,7 @@ async fn configure_app_for_repositories_interactive(
 
     match action {
         "Configure with template" | "Change template" => {
-            let templates = workspace_manager.list_templates(app_name).await?;
-            let mut template_choices = if templates.is_empty() {
-                vec!["default".to_string()]
-            } else {
-                templates
-            };
-            template_choices.push("Create new template...".to_string());
-
-            let selected_template = Select::new(
-                &format!("Select template for {}:", app_name),
-                template_choices,
-            )
-            .prompt()?;
-
-            let final_template = if selected_template == "Create new template..." {
-                let template_name = Text::new("Template name:").prompt()?;
-                println!(
-                    "📝 Creating template '{}' from default template",
-                    template_name
-                );
-                let default_content = workspace_manager.get_default_template(app_name).await?;
-                workspace_manager
-                    .save_template(app_name, &template_name, &default_content)
-                    .await?;
-                println!("✅ Template created");
-                template_name
-            } else {
-                selected_template
-            };
+            let final_template = select_app_template(workspace_manager, app_name).await?;
 
             // Apply single app configuration
             let app_selection = crate::workspace::AppSelection {
@@ -2087,6 +2954,12 @@ async fn configure_app_for_repositories_interactive(
             }
         }
         "Remove configuration" => {
+            let prompt = format!("Remove {app_name} configuration from '{repo_name}'?");
+            let level = workspace_manager.get_confirmation_level();
+            if !confirm_destructive(DestructiveAction::new(&prompt), level)? {
+                return Ok(());
+            }
+
             let app_selection = crate::workspace::AppSelection {
                 app: app_name.to_string(),
                 selected: false,
@@ -2304,10 +3177,35 @@ async fn show_app_status_interactive(
 mod tests {
     // Note: Interactive tests are difficult to automate
     // These would typically be manual/integration tests
+    use super::*;
 
     #[test]
     fn test_module_compiles() {
         // Basic compilation test
         assert!(true);
     }
+
+    #[test]
+    fn test_confirmation_levels_prompt_counts() {
+        assert_eq!(yes_no_prompt_count(ConfirmationLevel::Strict), 2);
+        assert_eq!(yes_no_prompt_count(ConfirmationLevel::Normal), 1);
+        assert_eq!(yes_no_prompt_count(ConfirmationLevel::Minimal), 0);
+    }
+
+    #[test]
+    fn test_minimal_skips_confirmation_without_phrase() {
+        let action = DestructiveAction::new("Remove worktree 'x'?");
+        assert!(confirm_destructive(action, ConfirmationLevel::Minimal).unwrap());
+    }
+
+    #[test]
+    fn test_confirmations_preference_parses() {
+        use crate::workspace::config::Preferences;
+
+        let preferences: Preferences = serde_yaml::from_str("confirmations: strict").unwrap();
+        assert_eq!(preferences.confirmations, ConfirmationLevel::Strict);
+
+        let preferences: Preferences = serde_yaml::from_str("{}").unwrap();
+        assert_eq!(preferences.confirmations, ConfirmationLevel::Normal);
+    }
 }
@@ -1,11 +1,13 @@
 use anyhow::Result;
 use console::style;
-use inquire::{InquireError, Select};
+use inquire::InquireError;
 use std::collections::HashMap;
 
 use crate::cache::{GitStatusCache, RepositoryCache};
-use crate::ui::formatting;
+use crate::output::exit::CommandError;
 use crate::ui::state::VibeState;
+use crate::ui::{formatting, repo_picker};
+use crate::workspace::status_snapshot::{StatusScope, STATUS_CACHE_TTL};
 use crate::workspace::{operations::GitStatus, WorkspaceManager};
 
 /// Enhanced repository launcher with caching
@@ -83,22 +85,25 @@ impl QuickLauncher {
             .collect();
 
         // Load git status from cache (optional - don't block if missing)
-        let git_statuses = self
-            .git_cache
-            .get_all_git_statuses()
+        let git_status_map: HashMap<String, GitStatus> = workspace_manager
+            .plan_status_refresh(StatusScope::All, STATUS_CACHE_TTL)
             .await
-            .unwrap_or_default();
-        let git_status_map: HashMap<String, GitStatus> = git_statuses
-            .into_iter()
-            .map(|cached| (cached.repository_name.clone(), cached.into()))
-            .collect();
+            .cached()
+            .by_name();
 
         // Get available apps on system for unconfigured repos
         let available_apps = workspace_manager.get_available_apps().await;
 
-        // Create universal launch items with ALL repositories
+        // Create universal launch items with ALL repositories and their subprojects
         let launch_items: Vec<UniversalLaunchItem> = all_repos
             .iter()
+            .flat_map(|repo| {
+                std::iter::once(repo.clone()).chain(
+                    repo.subprojects
+                        .iter()
+                        .map(|subproject| repo.subproject_view(subproject)),
+                )
+            })
             .map(|repo| {
                 let git_status = git_status_map.get(&repo.name).cloned();
                 let recent_rank = recent_names.get(&repo.name).cloned();
@@ -142,14 +147,18 @@ impl QuickLauncher {
         let mut sorted_items = launch_items;
         sorted_items.sort_by(|a, b| a.name.cmp(&b.name));
 
-        // Create display options for all repositories
-        let mut display_options = Vec::new();
-        let mut item_map = std::collections::HashMap::new();
-
-        // Add all repositories in alphabetical order
-        for item in &sorted_items {
-            display_options.push(item.display_string.clone());
-            item_map.insert(item.display_string.clone(), item);
+        // Picker items carry the inline git status and group tags
+        let mut picker_items =
+            repo_picker::items_with_subprojects(workspace_manager.get_config(), &git_status_map);
+        for picker_item in &mut picker_items {
+            if let Some(item) = sorted_items
+                .iter()
+                .find(|item| item.name == picker_item.name)
+            {
+                if item.has_configured_apps && picker_item.detail.is_none() {
+                    picker_item.detail = Some(format!("📋[{}]", item.configured_apps.len()));
+                }
+            }
         }
 
         // Show selection UI with updated messaging
@@ -160,13 +169,22 @@ impl QuickLauncher {
             available_apps.len()
         );
 
-        // Repository selection
-        let selected_display_result = Select::new("Repository:", display_options.clone())
-            .with_help_message("Use arrow keys to navigate, type to filter • ESC to exit")
-            .with_page_size(workspace_manager.get_quick_launch_page_size())
-            .prompt();
+        // Repository selection, previewing the highlighted repository
+        let preview = repo_picker::RepoPreview::load(
+            workspace_manager.get_config(),
+            workspace_manager.picker_preview_lines(),
+            &self.git_cache,
+            &self.repo_cache,
+        )
+        .await;
+        let selected_result = repo_picker::pick_repository_with_preview(
+            "Repository:",
+            picker_items,
+            workspace_manager.get_quick_launch_page_size(),
+            preview,
+        );
 
-        let selected_display = match selected_display_result {
+        let selected = match selected_result {
             Ok(value) => value,
             Err(InquireError::OperationCanceled) => {
                 println!("{} Repository selection cancelled", style("ℹ️").blue());
@@ -175,12 +193,10 @@ impl QuickLauncher {
             Err(error) => return Err(anyhow::Error::from(error)),
         };
 
-        let selected_item = item_map.get(&selected_display).copied().ok_or_else(|| {
-            anyhow::anyhow!(
-                "Selected repository '{}' not found in item map",
-                selected_display
-            )
-        })?;
+        let selected_item = sorted_items
+            .iter()
+            .find(|item| item.name == selected.name)
+            .ok_or_else(|| anyhow::anyhow!("Selected repository '{}' not found", selected.name))?;
 
         // Handle app selection and launch
         self.launch_universal_repository(workspace_manager, selected_item)
@@ -199,7 +215,7 @@ impl QuickLauncher {
         workspace_manager.smart_open_repository(&item.name).await?;
 
         // Update recent repositories state with the last app chosen
-        if let Some(repo_info) = workspace_manager.get_repository(&item.name) {
+        if let Some(repo_info) = workspace_manager.get_open_target(&item.name) {
             let mut user_state = VibeState::load().unwrap_or_default();
             user_state.add_recent_repo(
                 item.name.clone(),
@@ -214,48 +230,72 @@ impl QuickLauncher {
         Ok(())
     }
 
-    /// Quick launch from recent repos (position 1-9) - uses immediate selection
-    #[allow(dead_code)]
+    /// Quick launch from recent repos (position 1-9) - uses immediate selection.
+    /// An empty slot is a not-found error; a slot whose repository has left the
+    /// workspace falls back to the interactive picker.
     pub async fn quick_launch_recent(
         &self,
         workspace_manager: &mut WorkspaceManager,
         position: usize,
     ) -> Result<()> {
         let user_state = VibeState::load().unwrap_or_default();
-        let recent_repos = user_state.get_recent_repos(9);
-
-        if let Some(recent_repo) = recent_repos.get(position - 1) {
-            let repo_name = &recent_repo.repo_id;
-            let default_app = "vscode".to_string();
-            let last_app = recent_repo.last_app.as_ref().unwrap_or(&default_app);
-
-            // Immediate opening with saved app - NO choice menu
-            workspace_manager
-                .open_repo_with_app_options(repo_name, last_app, false)
-                .await?;
-
-            // Update access tracking
-            let mut updated_state = VibeState::load().unwrap_or_default();
-            updated_state.add_recent_repo(
-                repo_name.clone(),
-                recent_repo.path.clone(),
-                Some(last_app.clone()),
-            );
-            if let Err(e) = updated_state.save() {
-                eprintln!("Warning: Failed to save recent repositories: {e}");
+        let Some(recent_repo) = position
+            .checked_sub(1)
+            .and_then(|index| user_state.get_recent_repos(9).get(index))
+        else {
+            return Err(CommandError::NotFound {
+                kind: "Recent repository slot",
+                name: position.to_string(),
+                hint: Some("Try 'vibe launch' to pick a repository.".to_string()),
             }
+            .into());
+        };
 
-            println!(
-                "{} Opened {} with {} (quick launch #{})",
-                style("🚀").green(),
-                style(repo_name).cyan().bold(),
-                style(last_app).blue(),
-                position
+        let repo_name = &recent_repo.repo_id;
+        if workspace_manager.get_open_target(repo_name).is_none() {
+            eprintln!(
+                "{} Recent repository '{}' is no longer in the workspace",
+                style("⚠").yellow(),
+                repo_name
             );
-        } else {
-            anyhow::bail!("No repository found at position {}", position);
+            return self.launch(workspace_manager).await;
+        }
+
+        let last_app = recent_repo
+            .last_app
+            .clone()
+            .or_else(|| {
+                workspace_manager
+                    .group_defaults(repo_name)
+                    .map(|defaults| defaults.app)
+            })
+            .or_else(|| user_state.user_preferences.default_app.clone())
+            .unwrap_or_else(|| "vscode".to_string());
+
+        // Immediate opening with saved app - NO choice menu
+        workspace_manager
+            .open_repo_with_app_options(repo_name, &last_app, false)
+            .await?;
+
+        // Update access tracking
+        let mut updated_state = VibeState::load().unwrap_or_default();
+        updated_state.add_recent_repo(
+            repo_name.clone(),
+            recent_repo.path.clone(),
+            Some(last_app.clone()),
+        );
+        if let Err(e) = updated_state.save() {
+            eprintln!("Warning: Failed to save recent repositories: {e}");
         }
 
+        println!(
+            "{} Opened {} with {} (quick launch #{})",
+            style("🚀").green(),
+            style(repo_name).cyan().bold(),
+            style(&last_app).blue(),
+            position
+        );
+
         Ok(())
     }
 
@@ -288,43 +328,6 @@ impl QuickLauncher {
         Ok(())
     }
 
-    /// Update git status cache in background for specific repositories
-    #[allow(dead_code)]
-    pub async fn update_git_status_cache(
-        &self,
-        workspace_manager: &WorkspaceManager,
-        repo_names: &[String],
-    ) -> Result<()> {
-        for repo_name in repo_names {
-            if let Some(repo_config) = workspace_manager
-                .config()
-                .repositories
-                .iter()
-                .find(|r| r.name == *repo_name)
-            {
-                let repo_path = workspace_manager
-                    .config()
-                    .workspace
-                    .root
-                    .join(&repo_config.path);
-
-                match crate::workspace::operations::get_git_status(&repo_path).await {
-                    Ok(git_status) => {
-                        let cached_status = git_status.into();
-                        if let Err(e) = self.git_cache.cache_git_status(&cached_status).await {
-                            eprintln!("Warning: Failed to cache git status for {repo_name}: {e}");
-                        }
-                    }
-                    Err(e) => {
-                        eprintln!("Warning: Failed to get git status for {repo_name}: {e}");
-                    }
-                }
-            }
-        }
-
-        Ok(())
-    }
-
     /// Get cache statistics for monitoring
     #[allow(dead_code)]
     pub async fn get_cache_stats(&self) -> Result<CacheStatistics> {
@@ -0,0 +1,873 @@
+use chrono::{DateTime, Utc};
+use console::{style, Alignment, Key, Term};
+use fuzzy_matcher::skim::SkimMatcherV2;
+use fuzzy_matcher::FuzzyMatcher;
+use inquire::{InquireError, Select};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::io::{BufRead, IsTerminal};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Sender};
+use tracing::debug;
+
+use crate::cache::{CachedLastCommit, GitStatusCache, RepositoryCache};
+use crate::ui::formatting;
+use crate::ui::key_menu::TerminalGuard;
+use crate::workspace::config::resolve_repo_path;
+use crate::workspace::operations::{execute_git_command, GitStatus};
+use crate::workspace::WorkspaceConfig;
+use crate::{display_eprint, display_eprintln};
+
+/// Lines of the open picker's preview unless `preferences.picker_preview_lines`
+/// says otherwise
+pub const DEFAULT_PREVIEW_LINES: usize = 5;
+
+/// Narrowest terminal that fits the preview beside the list; narrower ones
+/// show it below the list instead
+const SIDE_PREVIEW_MIN_WIDTH: usize = 90;
+
+const HELP: &str = "Type to fuzzy-filter by name, path, or group • ESC to go back";
+
+/// A repository entry in the fuzzy picker
+#[derive(Debug, Clone)]
+pub struct RepoPickerItem {
+    pub name: String,
+    pub path: PathBuf,
+    /// Group names the repository belongs to, matched like tags
+    pub tags: Vec<String>,
+    pub git_status: Option<GitStatus>,
+    /// Extra label shown after the name (e.g. configured app count)
+    pub detail: Option<String>,
+}
+
+impl RepoPickerItem {
+    /// Matchable text: name, path, and tags
+    fn search_fields(&self) -> impl Iterator<Item = String> + '_ {
+        [self.name.clone(), self.path.display().to_string()]
+            .into_iter()
+            .chain(self.tags.iter().cloned())
+    }
+}
+
+impl fmt::Display for RepoPickerItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "📁 {}",
+            formatting::get_repo_name_color(&self.name, self.git_status.as_ref())
+        )?;
+
+        if let Some(status) = &self.git_status {
+            let indicators = formatting::format_git_status_indicators(status);
+            if !indicators.is_empty() {
+                write!(f, " {indicators}")?;
+            }
+        }
+        if let Some(detail) = &self.detail {
+            write!(f, " {detail}")?;
+        }
+        if !self.tags.is_empty() {
+            write!(
+                f,
+                " {}",
+                console::style(format!("#{}", self.tags.join(" #"))).dim()
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Build picker items for every repository in the workspace
+pub fn items_for_workspace(
+    config: &WorkspaceConfig,
+    git_statuses: &HashMap<String, GitStatus>,
+) -> Vec<RepoPickerItem> {
+    let mut items: Vec<RepoPickerItem> = config
+        .repositories
+        .iter()
+        .map(|repo| RepoPickerItem {
+            name: repo.name.clone(),
+            path: repo.path.clone(),
+            tags: config
+                .groups
+                .iter()
+                .filter(|group| group.repos.contains(&repo.name))
+                .map(|group| group.name.clone())
+                .collect(),
+            git_status: git_statuses.get(&repo.name).cloned(),
+            detail: None,
+        })
+        .collect();
+
+    items.sort_by(|a, b| a.name.cmp(&b.name));
+    items
+}
+
+/// Picker items for every repository plus an `<repo>/<subproject>` entry
+/// for each monorepo subproject, for pickers that open what they select.
+/// Subprojects show their repository's git status and groups.
+pub fn items_with_subprojects(
+    config: &WorkspaceConfig,
+    git_statuses: &HashMap<String, GitStatus>,
+) -> Vec<RepoPickerItem> {
+    let mut items = items_for_workspace(config, git_statuses);
+    let subproject_items: Vec<RepoPickerItem> = items
+        .iter()
+        .filter_map(|item| Some((item, config.get_repository(&item.name)?)))
+        .flat_map(|(item, repo)| {
+            repo.subprojects.iter().map(move |subproject| {
+                let view = repo.subproject_view(subproject);
+                RepoPickerItem {
+                    name: view.name,
+                    path: view.path,
+                    tags: item.tags.clone(),
+                    git_status: item.git_status.clone(),
+                    detail: Some("📦".to_string()),
+                }
+            })
+        })
+        .collect();
+    items.extend(subproject_items);
+    items.sort_by(|a, b| a.name.cmp(&b.name));
+    items
+}
+
+/// Load the cached git status of every repository, keyed by repository name.
+/// The picker must never wait on git, so a missing cache yields no statuses.
+pub async fn load_cached_git_statuses() -> HashMap<String, GitStatus> {
+    let cache =
+        GitStatusCache::new(crate::workspace::constants::get_cache_dir().join("git_status.db"));
+    if cache.initialize().await.is_err() {
+        return HashMap::new();
+    }
+
+    cache
+        .get_all_git_statuses()
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .map(|cached| (cached.repository_name.clone(), cached.into()))
+        .collect()
+}
+
+/// Best fuzzy score of `input` against the item's name, path, and tags
+fn score_item(matcher: &SkimMatcherV2, input: &str, item: &RepoPickerItem) -> Option<i64> {
+    if input.is_empty() {
+        return Some(0);
+    }
+
+    item.search_fields()
+        .filter_map(|field| matcher.fuzzy_match(&field, input))
+        .max()
+}
+
+/// Prompt for a repository with a fuzzy filter over name, path, and tags.
+///
+/// Returns the same errors as an inquire prompt, so ESC surfaces as
+/// `InquireError::OperationCanceled` for `handle_prompt_result`. When stdin
+/// is not a terminal a numbered list is read from stdin instead.
+pub fn pick_repository(
+    message: &str,
+    items: Vec<RepoPickerItem>,
+    page_size: usize,
+) -> Result<RepoPickerItem, InquireError> {
+    if !std::io::stdin().is_terminal() {
+        return pick_from_plain_list(message, items);
+    }
+
+    let matcher = SkimMatcherV2::default();
+    let scorer =
+        |input: &str, item: &RepoPickerItem, _: &str, _: usize| score_item(&matcher, input, item);
+
+    Select::new(message, items)
+        .with_scorer(&scorer)
+        .with_page_size(page_size)
+        .with_help_message(HELP)
+        .prompt()
+}
+
+/// Cached details of the highlighted repository in the open picker: branch
+/// and changes from its git status, the last commit, configured apps, and
+/// the description from cached GitHub listings
+pub struct RepoPreview {
+    /// Lines the preview may use; 0 hides it
+    lines: usize,
+    root: PathBuf,
+    git_cache: GitStatusCache,
+    apps: HashMap<String, Vec<String>>,
+    descriptions: HashMap<String, String>,
+    last_commits: HashMap<String, CachedLastCommit>,
+}
+
+impl RepoPreview {
+    fn new(lines: usize, root: PathBuf, git_cache: GitStatusCache) -> Self {
+        Self {
+            lines,
+            root,
+            git_cache,
+            apps: HashMap::new(),
+            descriptions: HashMap::new(),
+            last_commits: HashMap::new(),
+        }
+    }
+
+    /// Read everything the preview shows from the configuration and caches,
+    /// so moving through the picker never waits on git or the network
+    pub async fn load(
+        config: &WorkspaceConfig,
+        lines: usize,
+        git_cache: &GitStatusCache,
+        repo_cache: &RepositoryCache,
+    ) -> Self {
+        let mut preview = Self::new(
+            lines,
+            config.workspace.root.clone(),
+            GitStatusCache::new(git_cache.db_path()),
+        );
+        if lines == 0 {
+            return preview;
+        }
+
+        preview.last_commits = git_cache.get_last_commits().await.unwrap_or_default();
+        let remotes =
+            crate::workspace::status_groups::cached_remote_repositories(config, repo_cache).await;
+        for repo in &config.repositories {
+            let mut apps: Vec<String> = repo.apps.keys().cloned().collect();
+            apps.sort();
+            let description = remotes
+                .get(&repo.name)
+                .and_then(|remote| remote.description.clone())
+                .filter(|description| !description.is_empty());
+
+            let names = std::iter::once(repo.name.clone()).chain(
+                repo.subprojects
+                    .iter()
+                    .map(|subproject| repo.subproject_view(subproject).name),
+            );
+            for name in names {
+                if let Some(description) = &description {
+                    preview
+                        .descriptions
+                        .insert(name.clone(), description.clone());
+                }
+                preview.apps.insert(name, apps.clone());
+            }
+        }
+        preview
+    }
+
+    /// Whether `name`'s last commit is missing or older than the cache TTL
+    fn needs_refresh(&self, name: &str) -> bool {
+        self.last_commits
+            .get(name)
+            .is_none_or(|commit| self.git_cache.is_expired(commit.last_updated))
+    }
+
+    /// Read `item`'s last commit in the background, cache it, and send it to
+    /// `events`. False when there's no tokio runtime to read it on.
+    fn spawn_refresh(&self, item: &RepoPickerItem, events: Sender<PickerEvent>) -> bool {
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return false;
+        };
+        let name = item.name.clone();
+        let path = resolve_repo_path(&self.root, &item.path);
+        let cache = GitStatusCache::new(self.git_cache.db_path());
+        runtime.spawn(async move {
+            let commit = read_last_commit(&name, &path).await;
+            if let Some(commit) = &commit {
+                if let Err(e) = cache.cache_last_commit(commit).await {
+                    debug!("Failed to cache last commit of {}: {}", name, e);
+                }
+            }
+            let _ = events.send(PickerEvent::LastCommit(name, commit));
+        });
+        true
+    }
+
+    /// Preview lines for `item`; `loading` while its last commit is read
+    fn render(&self, item: &RepoPickerItem, loading: bool) -> Vec<String> {
+        let label = |name: &str| style(format!("{name:<8}")).dim().to_string();
+        let mut lines = Vec::new();
+
+        match &item.git_status {
+            Some(status) => {
+                let mut branch = status
+                    .branch
+                    .clone()
+                    .unwrap_or_else(|| "detached HEAD".to_string());
+                if status.ahead > 0 {
+                    branch.push_str(&format!(" ↑{}", status.ahead));
+                }
+                if status.behind > 0 {
+                    branch.push_str(&format!(" ↓{}", status.behind));
+                }
+                lines.push(format!("{} {branch}", label("Branch")));
+                lines.push(format!("{} {}", label("Changes"), change_summary(status)));
+            }
+            None => lines.push(format!("{} {}", label("Status"), style("not cached").dim())),
+        }
+
+        let commit = match self.last_commits.get(&item.name) {
+            Some(commit) => format!(
+                "{} {}",
+                commit.subject,
+                style(format!(
+                    "({})",
+                    formatting::format_time_ago(&commit.committed_at)
+                ))
+                .dim()
+            ),
+            None if loading => style("loading…").dim().to_string(),
+            None => style("none").dim().to_string(),
+        };
+        lines.push(format!("{} {commit}", label("Commit")));
+
+        let apps = match self.apps.get(&item.name) {
+            Some(apps) if !apps.is_empty() => apps.join(", "),
+            _ => style("none configured").dim().to_string(),
+        };
+        lines.push(format!("{} {apps}", label("Apps")));
+
+        if let Some(description) = self.descriptions.get(&item.name) {
+            lines.push(format!("{} {description}", label("About")));
+        }
+
+        lines.truncate(self.lines);
+        lines
+    }
+}
+
+/// "2 staged, 1 modified" or "clean"
+fn change_summary(status: &GitStatus) -> String {
+    let parts: Vec<String> = [
+        (status.staged, "staged"),
+        (status.unstaged, "modified"),
+        (status.untracked, "untracked"),
+    ]
+    .into_iter()
+    .filter(|(count, _)| *count > 0)
+    .map(|(count, kind)| format!("{count} {kind}"))
+    .collect();
+    if parts.is_empty() {
+        "clean".to_string()
+    } else {
+        parts.join(", ")
+    }
+}
+
+/// Subject and date of the newest commit checked out at `path`
+async fn read_last_commit(name: &str, path: &Path) -> Option<CachedLastCommit> {
+    let output = execute_git_command(path, &["log", "-1", "--format=%ct%x09%s"])
+        .await
+        .ok()?;
+    let (timestamp, subject) = output.split_once('\t')?;
+    Some(CachedLastCommit {
+        repository_name: name.to_string(),
+        subject: subject.to_string(),
+        committed_at: DateTime::from_timestamp(timestamp.parse().ok()?, 0)?,
+        last_updated: Utc::now(),
+    })
+}
+
+/// What the preview picker's event loop waits on
+enum PickerEvent {
+    Key(std::io::Result<Key>),
+    LastCommit(String, Option<CachedLastCommit>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PickerOutcome {
+    /// Index into the picker items
+    Selected(usize),
+    /// ESC or Ctrl-C
+    Cancelled,
+}
+
+/// Filter, cursor, and paging state of the preview picker, kept separate
+/// from terminal I/O
+struct PickerState<'a> {
+    items: &'a [RepoPickerItem],
+    matcher: SkimMatcherV2,
+    input: String,
+    /// Indexes of the items matching `input`, best match first
+    matches: Vec<usize>,
+    /// Position in `matches`
+    cursor: usize,
+    page_size: usize,
+}
+
+impl<'a> PickerState<'a> {
+    fn new(items: &'a [RepoPickerItem], page_size: usize) -> Self {
+        let mut state = Self {
+            items,
+            matcher: SkimMatcherV2::default(),
+            input: String::new(),
+            matches: Vec::new(),
+            cursor: 0,
+            page_size: page_size.max(1),
+        };
+        state.refilter();
+        state
+    }
+
+    fn refilter(&mut self) {
+        let mut scored: Vec<(i64, usize)> = self
+            .items
+            .iter()
+            .enumerate()
+            .filter_map(|(index, item)| {
+                Some((score_item(&self.matcher, &self.input, item)?, index))
+            })
+            .collect();
+        scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        self.matches = scored.into_iter().map(|(_, index)| index).collect();
+        self.cursor = 0;
+    }
+
+    fn highlighted(&self) -> Option<&'a RepoPickerItem> {
+        self.matches
+            .get(self.cursor)
+            .map(|&index| &self.items[index])
+    }
+
+    fn handle_key(&mut self, key: Key) -> Option<PickerOutcome> {
+        let len = self.matches.len();
+        match key {
+            Key::ArrowUp | Key::BackTab if len > 0 => self.cursor = (self.cursor + len - 1) % len,
+            Key::ArrowDown | Key::Tab if len > 0 => self.cursor = (self.cursor + 1) % len,
+            Key::Enter => {
+                return self
+                    .matches
+                    .get(self.cursor)
+                    .map(|&index| PickerOutcome::Selected(index))
+            }
+            Key::Escape | Key::CtrlC => return Some(PickerOutcome::Cancelled),
+            Key::Backspace => {
+                if self.input.pop().is_some() {
+                    self.refilter();
+                }
+            }
+            Key::Char(c) if !c.is_control() => {
+                self.input.push(c);
+                self.refilter();
+            }
+            _ => {}
+        }
+        None
+    }
+
+    /// Positions in `matches` to show, keeping the cursor inside the page
+    fn visible_range(&self) -> std::ops::Range<usize> {
+        let len = self.matches.len();
+        if len <= self.page_size {
+            return 0..len;
+        }
+        let start = self
+            .cursor
+            .saturating_sub(self.page_size / 2)
+            .min(len - self.page_size);
+        start..start + self.page_size
+    }
+}
+
+/// [`pick_repository`] with a preview of the highlighted repository beside
+/// the list, or below it on terminals narrower than 90 columns. A last
+/// commit that isn't cached or has expired is read in the background while
+/// the preview shows "loading…". Falls back to [`pick_repository`] when
+/// the preview is turned off or keys can't be read one at a time.
+pub fn pick_repository_with_preview(
+    message: &str,
+    items: Vec<RepoPickerItem>,
+    page_size: usize,
+    mut preview: RepoPreview,
+) -> Result<RepoPickerItem, InquireError> {
+    let term = Term::stderr();
+    if preview.lines == 0 || items.is_empty() || !term.is_term() || !std::io::stdin().is_terminal()
+    {
+        return pick_repository(message, items, page_size);
+    }
+
+    match run_preview_picker(&term, message, &items, page_size, &mut preview)? {
+        Some(PickerOutcome::Selected(index)) => {
+            Ok(items.into_iter().nth(index).expect("index in range"))
+        }
+        Some(PickerOutcome::Cancelled) => Err(InquireError::OperationCanceled),
+        None => pick_repository(message, items, page_size),
+    }
+}
+
+/// The picker's event loop. `Ok(None)` when the first key read fails.
+fn run_preview_picker(
+    term: &Term,
+    message: &str,
+    items: &[RepoPickerItem],
+    page_size: usize,
+    preview: &mut RepoPreview,
+) -> std::io::Result<Option<PickerOutcome>> {
+    let mut state = PickerState::new(items, page_size);
+    let (events, receiver) = mpsc::channel();
+
+    // Keys are read one per request, so none is swallowed after the picker
+    // closes, while background loads redraw the preview in between
+    let (request_key, key_requests) = mpsc::channel::<()>();
+    let key_events = events.clone();
+    std::thread::spawn(move || {
+        let term = Term::stderr();
+        while key_requests.recv().is_ok() {
+            if key_events.send(PickerEvent::Key(term.read_key())).is_err() {
+                break;
+            }
+        }
+    });
+    let _ = request_key.send(());
+
+    let guard = TerminalGuard::new(term);
+    let mut requested = HashSet::new();
+    let mut loading = HashSet::new();
+    let mut first_key = true;
+
+    let outcome = loop {
+        if let Some(item) = state.highlighted() {
+            if preview.needs_refresh(&item.name)
+                && requested.insert(item.name.clone())
+                && preview.spawn_refresh(item, events.clone())
+            {
+                loading.insert(item.name.clone());
+            }
+        }
+
+        let lines = render_picker(term, message, &state, preview, &loading)?;
+        let Ok(event) = receiver.recv() else {
+            term.clear_last_lines(lines)?;
+            return Ok(None);
+        };
+        term.clear_last_lines(lines)?;
+
+        match event {
+            PickerEvent::Key(Ok(key)) => {
+                first_key = false;
+                if let Some(outcome) = state.handle_key(key) {
+                    break outcome;
+                }
+                let _ = request_key.send(());
+            }
+            PickerEvent::Key(Err(_)) if first_key => return Ok(None),
+            PickerEvent::Key(Err(e)) => return Err(e),
+            PickerEvent::LastCommit(name, commit) => {
+                loading.remove(&name);
+                if let Some(commit) = commit {
+                    preview.last_commits.insert(name, commit);
+                }
+            }
+        }
+    };
+    drop(guard);
+
+    if let PickerOutcome::Selected(index) = outcome {
+        term.write_line(&format!(
+            "{} {} {}",
+            style("?").green(),
+            message,
+            style(&items[index].name).cyan()
+        ))?;
+    }
+    Ok(Some(outcome))
+}
+
+/// Draw the picker and return how many lines were written
+fn render_picker(
+    term: &Term,
+    message: &str,
+    state: &PickerState,
+    preview: &RepoPreview,
+    loading: &HashSet<String>,
+) -> std::io::Result<usize> {
+    let width = (term.size().1 as usize).saturating_sub(1);
+
+    let mut list: Vec<String> = state
+        .visible_range()
+        .map(|position| {
+            let item = &state.items[state.matches[position]];
+            if position == state.cursor {
+                format!("{} {item}", style(">").cyan())
+            } else {
+                format!("  {item}")
+            }
+        })
+        .collect();
+    if list.is_empty() {
+        list.push(style("  No matching repositories").dim().to_string());
+    }
+    let details = state
+        .highlighted()
+        .map(|item| preview.render(item, loading.contains(&item.name)))
+        .unwrap_or_default();
+
+    let mut lines = vec![format!(
+        "{} {} {}",
+        style("?").green(),
+        message,
+        state.input
+    )];
+    lines.extend(layout(list, details, width));
+    lines.push(style(format!("[{HELP}]")).cyan().dim().to_string());
+
+    for line in &lines {
+        // Truncate so wrapped lines don't throw off clear_last_lines
+        term.write_line(&console::truncate_str(line, width, "…"))?;
+    }
+    Ok(lines.len())
+}
+
+/// The preview beside the list when `width` fits both, otherwise below it
+fn layout(list: Vec<String>, preview: Vec<String>, width: usize) -> Vec<String> {
+    if preview.is_empty() {
+        return list;
+    }
+    if width < SIDE_PREVIEW_MIN_WIDTH {
+        let mut lines = list;
+        lines.push(style("─".repeat(width.min(40))).dim().to_string());
+        lines.extend(preview.into_iter().map(|line| format!("  {line}")));
+        return lines;
+    }
+
+    let list_width = width / 2;
+    (0..list.len().max(preview.len()))
+        .map(|row| {
+            let left = list.get(row).map(String::as_str).unwrap_or("");
+            let right = preview.get(row).map(String::as_str).unwrap_or("");
+            format!(
+                "{} {} {right}",
+                console::pad_str(left, list_width, Alignment::Left, Some("…")),
+                style("│").dim()
+            )
+        })
+        .collect()
+}
+
+/// Simple numbered list for non-interactive stdin; an empty answer or EOF cancels
+fn pick_from_plain_list(
+    message: &str,
+    items: Vec<RepoPickerItem>,
+) -> Result<RepoPickerItem, InquireError> {
+    display_eprintln!("{message}");
+    for (index, item) in items.iter().enumerate() {
+        display_eprintln!("  {:>3}. {}", index + 1, item.name);
+    }
+    display_eprint!("Enter a number or name: ");
+
+    let mut answer = String::new();
+    std::io::stdin().lock().read_line(&mut answer)?;
+
+    select_from_answer(items, answer.trim())
+}
+
+fn select_from_answer(
+    items: Vec<RepoPickerItem>,
+    answer: &str,
+) -> Result<RepoPickerItem, InquireError> {
+    if answer.is_empty() {
+        return Err(InquireError::OperationCanceled);
+    }
+
+    let position = match answer.parse::<usize>() {
+        Ok(number) => number.checked_sub(1).filter(|index| *index < items.len()),
+        Err(_) => items.iter().position(|item| item.name == answer),
+    };
+
+    position
+        .map(|index| items.into_iter().nth(index).expect("index in range"))
+        .ok_or_else(|| InquireError::Custom(format!("No repository matches '{answer}'").into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(name: &str, path: &str, tags: &[&str]) -> RepoPickerItem {
+        RepoPickerItem {
+            name: name.to_string(),
+            path: PathBuf::from(path),
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            git_status: None,
+            detail: None,
+        }
+    }
+
+    #[test]
+    fn test_score_matches_name_path_and_tags() {
+        let matcher = SkimMatcherV2::default();
+        let repo = item("vibe-workspace", "toolprint/vibe-workspace", &["tools"]);
+
+        assert!(score_item(&matcher, "vbws", &repo).is_some());
+        assert!(score_item(&matcher, "toolprint", &repo).is_some());
+        assert!(score_item(&matcher, "tools", &repo).is_some());
+        assert!(score_item(&matcher, "zzz", &repo).is_none());
+        assert_eq!(score_item(&matcher, "", &repo), Some(0));
+    }
+
+    #[test]
+    fn test_select_from_answer() {
+        let items = || vec![item("api", "org/api", &[]), item("web", "org/web", &[])];
+
+        assert_eq!(select_from_answer(items(), "2").unwrap().name, "web");
+        assert_eq!(select_from_answer(items(), "api").unwrap().name, "api");
+        assert!(matches!(
+            select_from_answer(items(), ""),
+            Err(InquireError::OperationCanceled)
+        ));
+        assert!(select_from_answer(items(), "3").is_err());
+        assert!(select_from_answer(items(), "0").is_err());
+    }
+
+    #[test]
+    fn test_picker_filters_and_wraps() {
+        let items = vec![
+            item("api", "org/api", &[]),
+            item("web", "org/web", &["frontend"]),
+            item("worker", "org/worker", &[]),
+        ];
+        let mut state = PickerState::new(&items, 10);
+        assert_eq!(state.matches, [0, 1, 2]);
+
+        state.handle_key(Key::ArrowUp);
+        assert_eq!(state.highlighted().unwrap().name, "worker");
+        state.handle_key(Key::ArrowDown);
+        assert_eq!(state.highlighted().unwrap().name, "api");
+
+        state.handle_key(Key::Char('f'));
+        state.handle_key(Key::Char('r'));
+        assert_eq!(state.matches, [1]);
+        assert_eq!(
+            state.handle_key(Key::Enter),
+            Some(PickerOutcome::Selected(1))
+        );
+
+        state.handle_key(Key::Char('z'));
+        assert!(state.highlighted().is_none());
+        assert_eq!(state.handle_key(Key::Enter), None);
+        state.handle_key(Key::Backspace);
+        assert_eq!(state.matches, [1]);
+        assert_eq!(
+            state.handle_key(Key::Escape),
+            Some(PickerOutcome::Cancelled)
+        );
+    }
+
+    fn plain(lines: Vec<String>) -> Vec<String> {
+        lines
+            .iter()
+            .map(|line| console::strip_ansi_codes(line).trim_end().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_preview_shows_cached_details() {
+        let mut preview = RepoPreview::new(5, PathBuf::new(), GitStatusCache::new("unused.db"));
+        preview.apps.insert(
+            "api".to_string(),
+            vec!["cursor".to_string(), "warp".to_string()],
+        );
+        preview
+            .descriptions
+            .insert("api".to_string(), "Public API".to_string());
+
+        let mut api = item("api", "org/api", &[]);
+        api.git_status = Some(GitStatus {
+            repository_name: "api".to_string(),
+            path: "org/api".to_string(),
+            branch: Some("main".to_string()),
+            clean: false,
+            ahead: 1,
+            behind: 0,
+            staged: 2,
+            unstaged: 0,
+            untracked: 1,
+            remote_url: None,
+            upstream_behind: None,
+            compare_ref: None,
+        });
+        assert_eq!(
+            plain(preview.render(&api, true)),
+            [
+                "Branch   main ↑1",
+                "Changes  2 staged, 1 untracked",
+                "Commit   loading…",
+                "Apps     cursor, warp",
+                "About    Public API",
+            ]
+        );
+
+        preview.last_commits.insert(
+            "api".to_string(),
+            CachedLastCommit {
+                repository_name: "api".to_string(),
+                subject: "Fix login".to_string(),
+                committed_at: Utc::now(),
+                last_updated: Utc::now(),
+            },
+        );
+        preview.lines = 3;
+        let lines = plain(preview.render(&api, false));
+        assert_eq!(lines.len(), 3);
+        assert!(lines[2].starts_with("Commit   Fix login ("), "{lines:?}");
+        assert!(!preview.needs_refresh("api"));
+        assert!(preview.needs_refresh("web"));
+
+        let web = item("web", "org/web", &[]);
+        assert_eq!(
+            plain(preview.render(&web, false)),
+            [
+                "Status   not cached",
+                "Commit   none",
+                "Apps     none configured"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_preview_moves_below_list_on_narrow_terminals() {
+        let list = || vec!["> api".to_string(), "  web".to_string()];
+        let preview = || vec!["Branch main".to_string()];
+
+        let wide = plain(layout(list(), preview(), 100));
+        assert_eq!(wide.len(), 2);
+        assert!(wide[0].starts_with("> api ") && wide[0].ends_with("│ Branch main"));
+        assert!(wide[1].ends_with("│"));
+
+        let narrow = plain(layout(list(), preview(), 60));
+        assert_eq!(narrow[..2], list());
+        assert_eq!(narrow[3], "  Branch main");
+    }
+
+    #[test]
+    fn test_items_with_subprojects() {
+        use crate::workspace::config::{RepositoryGroup, Subproject};
+        use crate::workspace::Repository;
+
+        let mut config = WorkspaceConfig::default();
+        let mut mono = Repository::new("mono", "mono");
+        mono.subprojects.push(Subproject {
+            name: "web".to_string(),
+            path: PathBuf::from("apps/web"),
+        });
+        config.repositories = vec![mono, Repository::new("api", "api")];
+        config.groups.push(RepositoryGroup {
+            name: "frontend".to_string(),
+            repos: vec!["mono".to_string()],
+            apps: HashMap::new(),
+            default_app: None,
+            default_template: None,
+        });
+
+        let items = items_with_subprojects(&config, &HashMap::new());
+        let names: Vec<&str> = items.iter().map(|item| item.name.as_str()).collect();
+        assert_eq!(names, ["api", "mono", "mono/web"]);
+        assert_eq!(items[2].path, PathBuf::from("mono/apps/web"));
+        assert_eq!(items[2].tags, ["frontend"]);
+
+        // Pickers that configure repositories only list the repositories
+        assert_eq!(items_for_workspace(&config, &HashMap::new()).len(), 2);
+    }
+}
@@ -2,11 +2,16 @@
 //! Provides clear explanations and guided workflow
 
 use anyhow::Result;
+use clap_complete::Shell;
 use console::style;
 use inquire::{Select, Text};
+use std::collections::HashMap;
 use std::path::PathBuf;
 
-use crate::workspace::WorkspaceManager;
+use crate::utils::completions;
+use crate::workspace::config::RepositoryGroup;
+use crate::workspace::layouts::{self, DetectedLayout};
+use crate::workspace::{repo_abs_path, WorkspaceManager};
 use crate::{display_println, ui::prompts::prompt_yes_no};
 
 /// Run the enhanced setup wizard
@@ -14,8 +19,13 @@ pub async fn run_enhanced_setup_wizard(workspace_manager: &mut WorkspaceManager)
     // Show welcome message with clear explanation
     show_welcome_message();
 
-    // Step 1: Choose workspace root and discover existing repos
-    let (workspace_root, discovered_repos) = choose_workspace_root(workspace_manager).await?;
+    // Step 1: Offer existing layouts found on disk, otherwise choose a
+    // workspace root and discover repos in it
+    let detected_layout = detect_existing_layout()?;
+    let (workspace_root, discovered_repos) = match &detected_layout {
+        Some(layout) => (layout.root.clone(), layout.repos.clone()),
+        None => choose_workspace_root(workspace_manager).await?,
+    };
 
     // Initialize workspace with chosen root
     let workspace_name = workspace_root
@@ -53,12 +63,101 @@ pub async fn run_enhanced_setup_wizard(workspace_manager: &mut WorkspaceManager)
         run_existing_repos_workflow_with_discovered(workspace_manager, &discovered_repos).await?;
     }
 
+    let mut summary = ImportSummary {
+        repos: discovered_repos.len(),
+        groups: 0,
+    };
+    if let Some(layout) = &detected_layout {
+        summary.groups = import_org_groups(workspace_manager, layout).await?;
+    }
+
+    // Step 3: Offer shell completions
+    offer_shell_completions()?;
+
     // Show next steps
-    show_next_steps();
+    show_next_steps(&summary);
 
     Ok(())
 }
 
+/// What the wizard imported, reported in the final summary
+struct ImportSummary {
+    repos: usize,
+    groups: usize,
+}
+
+/// Offer existing multi-repo layouts found on disk. Returns `None` when none
+/// are found or the user skips to choose a directory themselves.
+fn detect_existing_layout() -> Result<Option<DetectedLayout>> {
+    const SKIP: &str = "Skip - choose a directory myself";
+
+    let Some(home) = dirs::home_dir() else {
+        return Ok(None);
+    };
+    let detected = layouts::detect_layouts(&home, layouts::ghq_root(&home));
+    if detected.is_empty() {
+        return Ok(None);
+    }
+
+    display_println!(
+        "{} {}",
+        style("🔎 Existing Repositories Detected").yellow().bold(),
+        style("(repositories are grouped by owner/organization)").dim()
+    );
+
+    let mut options: Vec<String> = detected.iter().map(|layout| layout.to_string()).collect();
+    options.push(SKIP.to_string());
+
+    let selection = Select::new("Import an existing layout?", options)
+        .with_help_message("The chosen directory becomes your workspace root")
+        .prompt()?;
+
+    Ok(detected
+        .into_iter()
+        .find(|layout| layout.to_string() == selection))
+}
+
+/// Create one group per owner/organization of an imported layout. Returns the
+/// number of groups created.
+async fn import_org_groups(
+    workspace_manager: &mut WorkspaceManager,
+    layout: &DetectedLayout,
+) -> Result<usize> {
+    let mut imported = 0;
+
+    for (org, paths) in &layout.orgs {
+        let repos: Vec<String> = workspace_manager
+            .list_repositories()
+            .iter()
+            .filter(|repo| paths.contains(&repo_abs_path(workspace_manager.config(), repo)))
+            .map(|repo| repo.name.clone())
+            .collect();
+        if repos.is_empty() {
+            continue;
+        }
+
+        workspace_manager.config_mut().add_group(RepositoryGroup {
+            name: DetectedLayout::org_group_name(org),
+            repos,
+            apps: HashMap::new(),
+            default_app: None,
+            default_template: None,
+        });
+        imported += 1;
+    }
+
+    if imported > 0 {
+        workspace_manager.save_config().await?;
+        display_println!(
+            "{} Created {} groups from organizations",
+            style("✓").green().bold(),
+            imported
+        );
+    }
+
+    Ok(imported)
+}
+
 /// Display welcome message with clear explanation
 fn show_welcome_message() {
     display_println!("\n{}", style("Welcome to Vibe Workspace! 🚀").cyan().bold());
@@ -470,9 +569,64 @@ async fn preview_workspace_directory(
     Ok((confirmed, repos))
 }
 
+/// Offer to install tab completions for the user's shell
+fn offer_shell_completions() -> Result<()> {
+    let Some(shell) = completions::detect_shell() else {
+        return Ok(());
+    };
+
+    display_println!("\n{}", style("⌨️  Shell Completions").yellow().bold());
+
+    if !prompt_yes_no(
+        &format!("Install tab completions for {shell} (commands, repositories, worktrees)?"),
+        true,
+    )? {
+        return Ok(());
+    }
+
+    match completions::install_completion(shell) {
+        Ok(path) => {
+            display_println!(
+                "{} Installed completions to {}",
+                style("✓").green().bold(),
+                style(path.display()).cyan()
+            );
+            if shell == Shell::Zsh {
+                display_println!(
+                    "  Add this line to your ~/.zshrc: {}",
+                    style(format!("source {}", path.display())).cyan()
+                );
+            } else {
+                display_println!("  Open a new shell to start using them");
+            }
+        }
+        Err(e) => {
+            display_println!(
+                "{} Could not install completions: {}",
+                style("⚠").yellow(),
+                e
+            );
+            display_println!(
+                "  You can install them later with: {}",
+                style(format!("vibe completions {shell}")).cyan()
+            );
+        }
+    }
+
+    Ok(())
+}
+
 /// Show next steps after setup
-fn show_next_steps() {
+fn show_next_steps(summary: &ImportSummary) {
     display_println!("\n{}", style("✨ Setup complete!").green().bold());
+    if summary.repos > 0 {
+        display_println!(
+            "{} Imported {} repositories and {} groups",
+            style("📦").blue(),
+            style(summary.repos).cyan(),
+            style(summary.groups).cyan()
+        );
+    }
     display_println!("\n{}", style("Getting Started:").yellow().bold());
 
     display_println!("\n{} Quick commands:", style("📚").blue());
@@ -507,9 +661,6 @@ fn show_next_steps() {
         style("vibe").cyan().bold()
     );
 
-    display_println!(
-        "\nFor more help: {}",
-        style("vibe guide getting-started").dim()
-    );
+    display_println!("\nFor a hands-on tour: {}", style("vibe guide").dim());
     display_println!("\nEnjoy using Vibe! 🚀");
 }
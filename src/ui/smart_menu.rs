@@ -1,9 +1,11 @@
 use anyhow::Result;
+use console::style;
 use std::path::PathBuf;
 
 use crate::ui::formatting;
 use crate::ui::state::VibeState;
-use crate::workspace::WorkspaceManager;
+use crate::workspace::status_snapshot::{StatusScope, STATUS_CACHE_TTL};
+use crate::workspace::{repo_abs_path, WorkspaceManager};
 
 /// Represents a smart action that can be taken based on context
 #[derive(Debug, Clone)]
@@ -42,10 +44,75 @@ pub struct QuickLaunchItem {
     pub access_count: u32,
 }
 
+/// Counts shown above the main menu, read from the caches so the menu
+/// doesn't wait on git or GitHub
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MenuBadges {
+    pub repos: usize,
+    pub dirty: usize,
+    pub behind: usize,
+    pub worktrees: usize,
+    /// Only known while a pull request dashboard is cached
+    pub open_prs: Option<usize>,
+}
+
+impl MenuBadges {
+    /// Header shortcut keys without a menu row of their own: dirty
+    /// repositories, repositories behind upstream, and open pull requests
+    pub const SHORTCUTS: [char; 3] = ['d', 'b', 'p'];
+
+    /// Render the badges on one line, each with the key that opens its view
+    pub fn render(&self, refreshing: bool) -> String {
+        let badge = |count: usize, noun: &str, key: char, flag: bool| {
+            let count = if flag && count > 0 {
+                style(count).yellow().bold().to_string()
+            } else {
+                style(count).bold().to_string()
+            };
+            format!("{count} {noun} {}", style(format!("({key})")).dim())
+        };
+
+        let mut badges = vec![
+            badge(self.repos, plural(self.repos, "repo", "repos"), 'r', false),
+            badge(self.dirty, "dirty", 'd', true),
+            badge(self.behind, "behind", 'b', true),
+            badge(
+                self.worktrees,
+                plural(self.worktrees, "worktree", "worktrees"),
+                'w',
+                false,
+            ),
+        ];
+        if let Some(open_prs) = self.open_prs {
+            badges.push(badge(
+                open_prs,
+                plural(open_prs, "open PR", "open PRs"),
+                'p',
+                false,
+            ));
+        }
+
+        let mut line = badges.join(&style(" · ").dim().to_string());
+        if refreshing {
+            line.push_str(&style(" (refreshing…)").dim().to_string());
+        }
+        line
+    }
+}
+
+fn plural<'a>(count: usize, one: &'a str, many: &'a str) -> &'a str {
+    if count == 1 {
+        one
+    } else {
+        many
+    }
+}
+
 /// Analyzes workspace state to provide smart menu options
 pub struct SmartMenu {
     workspace_state: WorkspaceState,
     user_state: VibeState,
+    badges: Option<MenuBadges>,
 }
 
 /// Current state of the workspace
@@ -65,13 +132,40 @@ impl SmartMenu {
     pub async fn new(workspace_manager: &WorkspaceManager) -> Result<Self> {
         let user_state = VibeState::load().unwrap_or_default();
         let workspace_state = Self::analyze_workspace(workspace_manager).await?;
+        let badges = if workspace_manager.menu_header_enabled() {
+            Some(Self::collect_badges(workspace_manager).await)
+        } else {
+            None
+        };
 
         Ok(Self {
             workspace_state,
             user_state,
+            badges,
         })
     }
 
+    /// Header counts from the git status, worktree, and pull request caches
+    async fn collect_badges(manager: &WorkspaceManager) -> MenuBadges {
+        let refresh = manager
+            .plan_status_refresh(StatusScope::All, STATUS_CACHE_TTL)
+            .await;
+        let statuses = refresh.cached();
+        MenuBadges {
+            repos: manager.list_repositories().len(),
+            dirty: statuses.dirty_count(),
+            behind: statuses.behind_count(),
+            worktrees: manager.cached_worktree_count().await,
+            open_prs: manager.cached_open_pr_count().await,
+        }
+    }
+
+    /// The header line above the main menu, unless `preferences.menu_header`
+    /// turned it off
+    pub fn header(&self, refreshing: bool) -> Option<String> {
+        self.badges.as_ref().map(|badges| badges.render(refreshing))
+    }
+
     /// Analyze the current workspace state
     async fn analyze_workspace(manager: &WorkspaceManager) -> Result<WorkspaceState> {
         let repos = manager.list_repositories();
@@ -86,9 +180,8 @@ impl SmartMenu {
 
         // Find missing repos (in config but not on disk)
         let mut missing_repos = Vec::new();
-        let workspace_root = manager.get_workspace_root();
         for repo in repos {
-            let full_path = workspace_root.join(&repo.path);
+            let full_path = repo_abs_path(manager.config(), repo);
             if !full_path.exists() {
                 missing_repos.push(repo.name.clone());
             }
@@ -235,6 +328,31 @@ impl SmartMenu {
 
         // Return top 5 actions
         actions.truncate(5);
+
+        // The file manager and browser need no configuration, so the most
+        // recent repository always gets both on top of the five
+        if let Some(recent) = self.user_state.get_recent_repos(1).first() {
+            if !self.workspace_state.missing_repos.contains(&recent.repo_id) {
+                actions.push(SmartAction {
+                    label: format!("📁 Show {} in the file manager", recent.repo_id),
+                    description: "Open the repository folder".to_string(),
+                    action_type: SmartActionType::OpenWithPreferred(
+                        recent.repo_id.clone(),
+                        "finder".to_string(),
+                    ),
+                    priority: 30,
+                });
+                actions.push(SmartAction {
+                    label: format!("🌐 Open {} on the web", recent.repo_id),
+                    description: "Open the repository's remote in the browser".to_string(),
+                    action_type: SmartActionType::OpenWithPreferred(
+                        recent.repo_id.clone(),
+                        "browser".to_string(),
+                    ),
+                    priority: 30,
+                });
+            }
+        }
         actions
     }
 
@@ -402,4 +520,28 @@ mod tests {
         assert_eq!(actions[0].priority, 100);
         assert_eq!(actions[1].priority, 50);
     }
+
+    #[test]
+    fn test_menu_badges_render() {
+        let badges = MenuBadges {
+            repos: 12,
+            dirty: 3,
+            behind: 0,
+            worktrees: 1,
+            open_prs: None,
+        };
+        assert_eq!(
+            console::strip_ansi_codes(&badges.render(false)),
+            "12 repos (r) · 3 dirty (d) · 0 behind (b) · 1 worktree (w)"
+        );
+
+        let badges = MenuBadges {
+            open_prs: Some(2),
+            ..badges
+        };
+        assert_eq!(
+            console::strip_ansi_codes(&badges.render(true)),
+            "12 repos (r) · 3 dirty (d) · 0 behind (b) · 1 worktree (w) · 2 open PRs (p) (refreshing…)"
+        );
+    }
 }
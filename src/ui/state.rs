@@ -4,8 +4,22 @@ use std::collections::HashMap;
 use std::fs;
 use std::io::Write;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 
-use anyhow::Result;
+use anyhow::{Context, Result};
+
+use crate::display_warn;
+
+/// Schema version written by this build. Files from newer builds keep their
+/// version and any fields this build doesn't know about.
+pub const STATE_VERSION: u32 = 1;
+
+/// Set once a corrupt state file has been reported, so the warning shows up
+/// a single time per process
+static CORRUPT_WARNED: AtomicBool = AtomicBool::new(false);
+
+/// Distinguishes temp files written by concurrent saves within one process
+static TEMP_COUNTER: AtomicUsize = AtomicUsize::new(0);
 
 /// Represents a recently accessed repository
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -24,6 +38,7 @@ pub struct RecentRepo {
 
 /// User preferences for the vibe workspace
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct UserPreferences {
     /// Default app to use when none is specified
     pub default_app: Option<String>,
@@ -35,6 +50,9 @@ pub struct UserPreferences {
     pub max_recent_repos: usize,
     /// Whether to show hints in the interface
     pub show_hints: bool,
+    /// Fields written by other versions of vibe, kept as-is
+    #[serde(flatten)]
+    extra: serde_json::Map<String, serde_json::Value>,
 }
 
 impl Default for UserPreferences {
@@ -45,6 +63,7 @@ impl Default for UserPreferences {
             auto_open_last_repo: false,
             max_recent_repos: 10,
             show_hints: true,
+            extra: serde_json::Map::new(),
         }
     }
 }
@@ -53,17 +72,35 @@ impl Default for UserPreferences {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VibeState {
     /// List of recently accessed repositories
+    #[serde(default)]
     pub recent_repos: Vec<RecentRepo>,
     /// Last used app per repository
+    #[serde(default)]
     pub last_used_apps: HashMap<String, String>,
     /// User preferences
+    #[serde(default)]
     pub user_preferences: UserPreferences,
     /// Groups of repositories for batch operations
+    #[serde(default)]
     pub repo_groups: HashMap<String, Vec<String>>,
+    /// Group last picked in the interactive status menu
+    #[serde(default)]
+    pub last_status_group: Option<String>,
+    /// Ids of the `vibe guide` steps finished so far
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub guide_progress: Vec<String>,
     /// First run timestamp (for setup wizard)
     pub first_run: Option<DateTime<Utc>>,
     /// Version of the state file format
+    #[serde(default = "default_version")]
     pub version: u32,
+    /// Fields written by other versions of vibe, kept as-is
+    #[serde(flatten)]
+    extra: serde_json::Map<String, serde_json::Value>,
+}
+
+fn default_version() -> u32 {
+    STATE_VERSION
 }
 
 impl Default for VibeState {
@@ -73,8 +110,11 @@ impl Default for VibeState {
             last_used_apps: HashMap::new(),
             user_preferences: UserPreferences::default(),
             repo_groups: HashMap::new(),
+            last_status_group: None,
+            guide_progress: Vec::new(),
             first_run: Some(Utc::now()),
-            version: 1,
+            version: STATE_VERSION,
+            extra: serde_json::Map::new(),
         }
     }
 }
@@ -83,18 +123,51 @@ impl VibeState {
     /// Load state from the default location
     pub fn load() -> Result<Self> {
         let state_path = Self::default_state_path()?;
-        if state_path.exists() {
-            Self::load_from_path(&state_path)
-        } else {
-            Ok(Self::default())
-        }
+        Self::load_from_path(&state_path)
     }
 
-    /// Load state from a specific path
+    /// Load state from a specific path. A missing file yields defaults; a
+    /// file that doesn't parse is moved aside to `<name>.corrupt-<timestamp>`
+    /// and defaults are used in its place.
     pub fn load_from_path(path: &Path) -> Result<Self> {
-        let content = fs::read_to_string(path)?;
-        let state: VibeState = serde_json::from_str(&content)?;
-        Ok(state)
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+        };
+
+        match serde_json::from_str::<VibeState>(&content) {
+            Ok(mut state) => {
+                state.version = state.version.max(STATE_VERSION);
+                Ok(state)
+            }
+            Err(e) => {
+                let quarantined = Self::quarantine(path);
+                if !CORRUPT_WARNED.swap(true, Ordering::Relaxed) {
+                    match &quarantined {
+                        Some(moved) => display_warn!(
+                            "⚠️  {} could not be read ({e}); moved it to {} and started from defaults",
+                            path.display(),
+                            moved.display()
+                        ),
+                        None => display_warn!(
+                            "⚠️  {} could not be read ({e}); starting from defaults",
+                            path.display()
+                        ),
+                    }
+                }
+                Ok(Self::default())
+            }
+        }
+    }
+
+    /// Move a corrupt state file out of the way, returning where it went
+    fn quarantine(path: &Path) -> Option<PathBuf> {
+        let file_name = path.file_name()?.to_string_lossy();
+        let timestamp = Utc::now().format("%Y%m%d%H%M%S");
+        let target = path.with_file_name(format!("{file_name}.corrupt-{timestamp}"));
+        fs::rename(path, &target).ok()?;
+        Some(target)
     }
 
     /// Save state to the default location
@@ -103,7 +176,9 @@ impl VibeState {
         self.save_to_path(&state_path)
     }
 
-    /// Save state to a specific path
+    /// Save state to a specific path. The state is written to a temp file
+    /// next to `path` and renamed over it, so concurrent saves never leave a
+    /// torn file behind; the last rename wins.
     pub fn save_to_path(&self, path: &Path) -> Result<()> {
         // Ensure the parent directory exists
         if let Some(parent) = path.parent() {
@@ -111,8 +186,26 @@ impl VibeState {
         }
 
         let json = serde_json::to_string_pretty(self)?;
-        let mut file = fs::File::create(path)?;
-        file.write_all(json.as_bytes())?;
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "state.json".to_string());
+        let temp = path.with_file_name(format!(
+            ".{file_name}.tmp-{}-{}",
+            std::process::id(),
+            TEMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+
+        let written = (|| -> std::io::Result<()> {
+            let mut file = fs::File::create(&temp)?;
+            file.write_all(json.as_bytes())?;
+            file.sync_all()?;
+            fs::rename(&temp, path)
+        })();
+        if let Err(e) = written {
+            let _ = fs::remove_file(&temp);
+            return Err(e).with_context(|| format!("Failed to write {}", path.display()));
+        }
         Ok(())
     }
 
@@ -180,6 +273,22 @@ impl VibeState {
         self.first_run = None;
     }
 
+    /// Record a finished `vibe guide` step
+    pub fn complete_guide_step(&mut self, id: &str) {
+        if !self.is_guide_step_complete(id) {
+            self.guide_progress.push(id.to_string());
+        }
+    }
+
+    pub fn is_guide_step_complete(&self, id: &str) -> bool {
+        self.guide_progress.iter().any(|done| done == id)
+    }
+
+    /// Forget `vibe guide` progress, so it starts from the first step
+    pub fn reset_guide(&mut self) {
+        self.guide_progress.clear();
+    }
+
     /// Add a repository group
     pub fn add_repo_group(&mut self, name: String, repos: Vec<String>) {
         self.repo_groups.insert(name, repos);
@@ -267,4 +376,100 @@ mod tests {
         assert_eq!(state.get_repo_group("backend").unwrap().len(), 2);
         assert!(state.get_repo_group("nonexistent").is_none());
     }
+
+    #[test]
+    fn test_last_status_group_defaults_for_older_state_files() {
+        let dir = tempdir().unwrap();
+        let state_path = dir.path().join("state.json");
+
+        let mut value = serde_json::to_value(VibeState::default()).unwrap();
+        value.as_object_mut().unwrap().remove("last_status_group");
+        fs::write(&state_path, value.to_string()).unwrap();
+        let loaded = VibeState::load_from_path(&state_path).unwrap();
+        assert!(loaded.last_status_group.is_none());
+
+        let mut state = loaded;
+        state.last_status_group = Some("frontend".to_string());
+        state.save_to_path(&state_path).unwrap();
+        let loaded = VibeState::load_from_path(&state_path).unwrap();
+        assert_eq!(loaded.last_status_group.as_deref(), Some("frontend"));
+    }
+
+    #[test]
+    fn test_truncated_state_is_moved_aside() {
+        let dir = tempdir().unwrap();
+        let state_path = dir.path().join("state.json");
+
+        let mut state = VibeState::default();
+        state.add_recent_repo("api".to_string(), PathBuf::from("/api"), None);
+        let json = serde_json::to_string_pretty(&state).unwrap();
+        fs::write(&state_path, &json[..json.len() / 2]).unwrap();
+
+        let loaded = VibeState::load_from_path(&state_path).unwrap();
+        assert!(loaded.recent_repos.is_empty());
+        assert!(!state_path.exists());
+        let moved: Vec<String> = fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(moved.len(), 1);
+        assert!(moved[0].starts_with("state.json.corrupt-"));
+
+        // Missing files load as defaults too
+        assert!(VibeState::load_from_path(&state_path).is_ok());
+    }
+
+    #[test]
+    fn test_unknown_fields_survive_round_trip() {
+        let dir = tempdir().unwrap();
+        let state_path = dir.path().join("state.json");
+
+        let mut value = serde_json::to_value(VibeState::default()).unwrap();
+        value["version"] = serde_json::json!(STATE_VERSION + 1);
+        value["pinned_repos"] = serde_json::json!(["api"]);
+        value["user_preferences"]["theme"] = serde_json::json!("dark");
+        fs::write(&state_path, value.to_string()).unwrap();
+
+        let mut state = VibeState::load_from_path(&state_path).unwrap();
+        state.add_recent_repo("web".to_string(), PathBuf::from("/web"), None);
+        state.save_to_path(&state_path).unwrap();
+
+        let saved: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&state_path).unwrap()).unwrap();
+        assert_eq!(saved["version"], STATE_VERSION + 1);
+        assert_eq!(saved["pinned_repos"], serde_json::json!(["api"]));
+        assert_eq!(saved["user_preferences"]["theme"], "dark");
+        assert_eq!(saved["recent_repos"][0]["repo_id"], "web");
+    }
+
+    #[test]
+    fn test_concurrent_saves_never_tear() {
+        let dir = tempdir().unwrap();
+        let state_path = dir.path().join("state.json");
+        VibeState::default().save_to_path(&state_path).unwrap();
+
+        let writers: Vec<_> = (0..8)
+            .map(|i| {
+                let path = state_path.clone();
+                std::thread::spawn(move || {
+                    let mut state = VibeState::default();
+                    for j in 0..20 {
+                        state.add_recent_repo(format!("repo-{i}-{j}"), PathBuf::from("/r"), None);
+                        state.save_to_path(&path).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for _ in 0..200 {
+            let content = fs::read_to_string(&state_path).unwrap();
+            serde_json::from_str::<VibeState>(&content).unwrap();
+        }
+        for writer in writers {
+            writer.join().unwrap();
+        }
+
+        let loaded = VibeState::load_from_path(&state_path).unwrap();
+        assert_eq!(loaded.recent_repos.len(), 10);
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
 }
@@ -1,13 +1,11 @@
 use anyhow::Result;
 use console::style;
 
+use crate::output::exit::CommandError;
 use crate::ui::prompts::{prompt_app_selection, prompt_yes_no};
 use crate::ui::state::VibeState;
 use crate::workspace::WorkspaceManager;
-use crate::{
-    display_println,
-    git::{CloneCommand, GitConfig},
-};
+use crate::{display_println, git::CloneCommand};
 
 /// Represents the next action in a workflow
 pub enum NextAction {
@@ -31,10 +29,60 @@ pub trait Workflow: Send + Sync {
     fn description(&self) -> String;
 }
 
+/// Add a newly cloned or created repository to `group`. Without an explicit
+/// `app`, the app the group defaults to is configured for the repository,
+/// unless `skip_configure` is set. Returns the app to open it with.
+async fn join_group(
+    manager: &mut WorkspaceManager,
+    repo_name: &str,
+    group: Option<&str>,
+    app: Option<String>,
+    skip_configure: bool,
+) -> Result<Option<String>> {
+    let Some(group) = group else {
+        return Ok(app);
+    };
+    manager.add_repo_to_group(repo_name, group).await?;
+    display_println!(
+        "{} Added '{}' to group '{}'",
+        style("✓").green().bold(),
+        style(repo_name).cyan(),
+        group
+    );
+
+    if app.is_some() {
+        return Ok(app);
+    }
+    let Some(defaults) = manager.group_defaults(repo_name) else {
+        return Ok(None);
+    };
+    if !skip_configure {
+        let template = defaults.template.as_deref().unwrap_or("default");
+        let results = manager
+            .configure_app_for_repos(&[repo_name.to_string()], &defaults.app, template, false)
+            .await?;
+        if let Some(error) = results.into_iter().find_map(|result| result.error) {
+            anyhow::bail!("Failed to configure {}: {}", defaults.app, error);
+        }
+        display_println!(
+            "{} Configured {} (default for group '{}')",
+            style("✓").green().bold(),
+            style(&defaults.app).cyan(),
+            defaults.group
+        );
+    }
+    Ok(Some(defaults.app))
+}
+
 /// Clone and open workflow
 pub struct CloneWorkflow {
     pub url: String,
     pub app: Option<String>,
+    /// Group to add the cloned repository to
+    pub group: Option<String>,
+    pub skip_configure: bool,
+    /// `--on-collision`, when given
+    pub on_collision: Option<crate::git::OnCollision>,
 }
 
 impl Workflow for CloneWorkflow {
@@ -46,7 +94,8 @@ impl Workflow for CloneWorkflow {
             display_println!("{} Cloning repository...", style("📥").blue());
 
             // Clone the repository
-            let git_config = GitConfig::default();
+            let mut git_config = manager.git_config();
+            git_config.on_collision = self.on_collision;
             let cloned_path = CloneCommand::execute(
                 self.url.clone(),
                 None,
@@ -57,12 +106,7 @@ impl Workflow for CloneWorkflow {
             )
             .await?;
 
-            // Extract repo name
-            let repo_name = cloned_path
-                .file_name()
-                .and_then(|n| n.to_str())
-                .ok_or_else(|| anyhow::anyhow!("Could not determine repository name"))?
-                .to_string();
+            let repo_name = crate::git::clone::configured_name(manager, &cloned_path)?;
 
             display_println!(
                 "{} Repository '{}' cloned successfully!",
@@ -70,10 +114,19 @@ impl Workflow for CloneWorkflow {
                 style(&repo_name).cyan()
             );
 
+            let app = join_group(
+                manager,
+                &repo_name,
+                self.group.as_deref(),
+                self.app.clone(),
+                self.skip_configure,
+            )
+            .await?;
+
             // Continue to app configuration (optional)
             Ok(NextAction::Continue(Box::new(ConfigureAppWorkflow {
                 repo_name,
-                suggested_app: self.app.clone(),
+                suggested_app: app,
                 open_after: true,
                 force_configure: false, // Allow skipping configuration
             })))
@@ -235,7 +288,7 @@ impl Workflow for OpenRepositoryWorkflow {
             // Get repo info
             let repo = manager
                 .get_repository(&self.repo_name)
-                .ok_or_else(|| anyhow::anyhow!("Repository '{}' not found", self.repo_name))?;
+                .ok_or_else(|| CommandError::not_found("Repository", &self.repo_name))?;
 
             let repo_path = repo.path.clone();
 
@@ -245,10 +298,16 @@ impl Workflow for OpenRepositoryWorkflow {
             } else {
                 // Get configured apps
                 let apps = manager.list_apps_for_repo(&self.repo_name)?;
-                if apps.is_empty() {
-                    "vscode".to_string() // Default fallback
+                if let Some((app, _)) = apps.first() {
+                    app.clone()
+                } else if let Some(defaults) = manager.group_defaults(&self.repo_name) {
+                    defaults.app
                 } else {
-                    apps[0].0.clone()
+                    VibeState::load()
+                        .unwrap_or_default()
+                        .user_preferences
+                        .default_app
+                        .unwrap_or_else(|| "vscode".to_string()) // Default fallback
                 }
             };
 
@@ -367,6 +426,8 @@ impl Workflow for ConfigureDefaultAppWorkflow {
 pub struct CreateRepositoryWorkflow {
     pub suggested_name: Option<String>,
     pub app: Option<String>,
+    /// Group to add the new repository to
+    pub group: Option<String>,
     pub skip_configure: bool,
     pub skip_open: bool,
 }
@@ -482,27 +543,7 @@ impl Workflow for CreateRepositoryWorkflow {
                 .create_local_repository(&selected_owner, &repo_name, manager)
                 .await
             {
-                Ok(_path) => {
-                    // Check if we should skip configuration and/or opening
-                    if self.skip_configure && self.skip_open {
-                        // Skip both - workflow complete
-                        Ok(NextAction::Complete)
-                    } else if self.skip_configure {
-                        // Skip configuration but still open
-                        Ok(NextAction::Continue(Box::new(OpenRepositoryWorkflow {
-                            repo_name: repo_name.clone(),
-                            preferred_app: self.app.clone(),
-                        })))
-                    } else {
-                        // Continue to app configuration (which will handle opening)
-                        Ok(NextAction::Continue(Box::new(ConfigureAppWorkflow {
-                            repo_name: repo_name.clone(),
-                            suggested_app: self.app.clone(),
-                            open_after: !self.skip_open,
-                            force_configure: false, // Allow skipping configuration
-                        })))
-                    }
-                }
+                Ok(_path) => self.after_create(manager, repo_name).await,
                 Err(e) => {
                     display_println!("{} Failed to create repository: {}", style("❌").red(), e);
                     Ok(NextAction::Complete)
@@ -521,6 +562,42 @@ impl Workflow for CreateRepositoryWorkflow {
 }
 
 impl CreateRepositoryWorkflow {
+    /// Join the requested group, then configure and open the new repository
+    async fn after_create(
+        &self,
+        manager: &mut WorkspaceManager,
+        repo_name: String,
+    ) -> Result<NextAction> {
+        let app = join_group(
+            manager,
+            &repo_name,
+            self.group.as_deref(),
+            self.app.clone(),
+            self.skip_configure,
+        )
+        .await?;
+
+        // Check if we should skip configuration and/or opening
+        if self.skip_configure && self.skip_open {
+            // Skip both - workflow complete
+            Ok(NextAction::Complete)
+        } else if self.skip_configure {
+            // Skip configuration but still open
+            Ok(NextAction::Continue(Box::new(OpenRepositoryWorkflow {
+                repo_name,
+                preferred_app: app,
+            })))
+        } else {
+            // Continue to app configuration (which will handle opening)
+            Ok(NextAction::Continue(Box::new(ConfigureAppWorkflow {
+                repo_name,
+                suggested_app: app,
+                open_after: !self.skip_open,
+                force_configure: false, // Allow skipping configuration
+            })))
+        }
+    }
+
     async fn create_without_github_integration(
         &self,
         manager: &mut WorkspaceManager,
@@ -549,27 +626,7 @@ impl CreateRepositoryWorkflow {
             .create_local_repository(&current_user, &repo_name, manager)
             .await
         {
-            Ok(_path) => {
-                // Check if we should skip configuration and/or opening
-                if self.skip_configure && self.skip_open {
-                    // Skip both - workflow complete
-                    Ok(NextAction::Complete)
-                } else if self.skip_configure {
-                    // Skip configuration but still open
-                    Ok(NextAction::Continue(Box::new(OpenRepositoryWorkflow {
-                        repo_name: repo_name.clone(),
-                        preferred_app: self.app.clone(),
-                    })))
-                } else {
-                    // Continue to app configuration (which will handle opening)
-                    Ok(NextAction::Continue(Box::new(ConfigureAppWorkflow {
-                        repo_name: repo_name.clone(),
-                        suggested_app: self.app.clone(),
-                        open_after: !self.skip_open,
-                        force_configure: false, // Allow skipping configuration
-                    })))
-                }
-            }
+            Ok(_path) => self.after_create(manager, repo_name).await,
             Err(e) => {
                 display_println!("{} Failed to create repository: {}", style("❌").red(), e);
                 Ok(NextAction::Complete)
@@ -623,6 +680,9 @@ mod tests {
         let workflow = CloneWorkflow {
             url: "https://github.com/user/repo".to_string(),
             app: Some("vscode".to_string()),
+            group: None,
+            skip_configure: false,
+            on_collision: None,
         };
 
         assert_eq!(
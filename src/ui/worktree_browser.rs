@@ -0,0 +1,331 @@
+//! Interactive worktree browser for the main menu
+//!
+//! Lists the worktrees of every configured repository, so it works no matter
+//! which directory vibe was started from.
+
+use anyhow::Result;
+use console::style;
+use inquire::{Confirm, Select, Text};
+use std::collections::HashMap;
+
+use crate::ui::prompts::{
+    confirm_destructive, create_menu_with_navigation, get_navigation_action, handle_prompt_result,
+    DestructiveAction,
+};
+use crate::ui::repo_picker;
+use crate::utils::direnv;
+use crate::workspace::config::resolve_repo_path;
+use crate::workspace::WorkspaceManager;
+use crate::worktree::display::{open_worktree_in_editor, print_status_table};
+use crate::worktree::status::WorktreeInfo;
+use crate::worktree::{
+    list_workspace_worktrees, CleanupStrategy, RemoveOptions, RepositoryWorktrees, WorktreeManager,
+};
+
+const CREATE_WORKTREE: &str = "➕ Create new worktree";
+
+/// A worktree together with the repository it belongs to
+struct BrowserEntry {
+    repo_name: String,
+    repo_path: std::path::PathBuf,
+    worktree: WorktreeInfo,
+}
+
+impl BrowserEntry {
+    fn label(&self) -> String {
+        format_entry_label(&self.repo_name, &self.worktree)
+    }
+}
+
+/// Menu label: status icon, repository, task (or branch), and status summary
+fn format_entry_label(repo_name: &str, worktree: &WorktreeInfo) -> String {
+    let name = worktree.task_id.as_deref().unwrap_or(&worktree.branch);
+    format!(
+        "{} {} › {} {}",
+        worktree.status.status_icon(),
+        repo_name,
+        name,
+        style(worktree.status.status_description()).dim()
+    )
+}
+
+fn flatten(listings: Vec<RepositoryWorktrees>) -> Vec<BrowserEntry> {
+    listings
+        .into_iter()
+        .flat_map(|listing| {
+            let repo_name = listing.repo_name;
+            let repo_path = listing.repo_path;
+            listing
+                .worktrees
+                .into_iter()
+                .map(move |worktree| BrowserEntry {
+                    repo_name: repo_name.clone(),
+                    repo_path: repo_path.clone(),
+                    worktree,
+                })
+        })
+        .collect()
+}
+
+/// Browse worktrees across all configured repositories
+pub async fn run_worktree_browser(workspace_manager: &WorkspaceManager) -> Result<()> {
+    if workspace_manager.list_repositories().is_empty() {
+        println!(
+            "{} No repositories configured. Run 'vibe git scan' first.",
+            style("ℹ").yellow()
+        );
+        return Ok(());
+    }
+
+    loop {
+        println!("{} Scanning worktrees...", style("🔍").blue());
+        let entries = flatten(list_workspace_worktrees(workspace_manager).await);
+        if entries.is_empty() {
+            println!("{} No worktrees in this workspace yet", style("ℹ").yellow());
+        }
+
+        let labels: Vec<String> = entries.iter().map(BrowserEntry::label).collect();
+        let mut options = labels.clone();
+        options.push(CREATE_WORKTREE.to_string());
+
+        let choice_result =
+            Select::new("🌳 Worktrees:", create_menu_with_navigation(options, false))
+                .with_page_size(workspace_manager.get_management_menus_page_size())
+                .with_help_message("Select a worktree for actions • ESC to go back")
+                .prompt();
+
+        let Some(choice) = handle_prompt_result(choice_result)? else {
+            break;
+        };
+        if get_navigation_action(&choice) == Some("Back") {
+            break;
+        }
+
+        if choice == CREATE_WORKTREE {
+            create_worktree_interactive(workspace_manager).await?;
+        } else if let Some(index) = labels.iter().position(|label| label == &choice) {
+            worktree_actions(workspace_manager, &entries[index]).await?;
+        }
+
+        println!();
+    }
+
+    Ok(())
+}
+
+/// Per-worktree action menu
+async fn worktree_actions(
+    workspace_manager: &WorkspaceManager,
+    entry: &BrowserEntry,
+) -> Result<()> {
+    let actions = vec![
+        "📂 Open in editor".to_string(),
+        "📊 Show detailed status".to_string(),
+        "🔀 Merge".to_string(),
+        "🗑️  Remove".to_string(),
+    ];
+
+    let action_result = Select::new(
+        &format!("{} › {}:", entry.repo_name, entry.worktree.branch),
+        create_menu_with_navigation(actions, false),
+    )
+    .with_page_size(workspace_manager.get_management_menus_page_size())
+    .with_help_message("Choose worktree action • ESC to go back")
+    .prompt();
+
+    let Some(action) = handle_prompt_result(action_result)? else {
+        return Ok(());
+    };
+
+    let manager = WorktreeManager::new(entry.repo_path.clone(), None).await?;
+    let worktree = &entry.worktree;
+
+    match action.as_str() {
+        "📂 Open in editor" => {
+            let Some(editor) = handle_prompt_result(
+                Text::new("Editor or app command:")
+                    .with_default(&manager.get_config().default_editor)
+                    .with_help_message("ESC to go back")
+                    .prompt(),
+            )?
+            else {
+                return Ok(());
+            };
+            open_worktree_in_editor(
+                &worktree.path,
+                editor.trim(),
+                workspace_manager.is_direnv_enabled(),
+            )
+            .await?;
+        }
+        "📊 Show detailed status" => {
+            print_status_table(std::slice::from_ref(worktree), true);
+        }
+        "🔀 Merge" => {
+            if !confirm(&format!("Merge worktree '{}'?", worktree.branch))? {
+                return Ok(());
+            }
+
+            println!("🔀 Merging worktree '{}'...", worktree.branch);
+            let report = manager
+                .merge_worktree(worktree, CleanupStrategy::MergeToFeature)
+                .await?;
+
+            if report.cleaned_count > 0 {
+                println!("✅ Successfully merged worktree '{}'", worktree.branch);
+            } else if report.failed_count > 0 {
+                println!("❌ Merge failed:");
+                for result in &report.worktree_results {
+                    if let Some(ref error) = result.error {
+                        println!("   {error}");
+                    }
+                }
+            } else {
+                println!("⚠️ No action taken - worktree may not meet merge criteria");
+            }
+        }
+        "🗑️  Remove" => {
+            let prompt = format!("Remove worktree '{}'?", worktree.branch);
+            let level = workspace_manager.get_confirmation_level();
+            if !confirm_destructive(DestructiveAction::new(&prompt), level)? {
+                return Ok(());
+            }
+            let delete_branch = handle_prompt_result(
+                Confirm::new("Also delete the branch?")
+                    .with_default(false)
+                    .prompt(),
+            )?;
+            let Some(delete_branch) = delete_branch else {
+                return Ok(());
+            };
+
+            let outcome = manager
+                .remove_worktree_with_options(RemoveOptions {
+                    target: worktree.path.display().to_string(),
+                    force: false,
+                    delete_branch,
+                    include_protected: false,
+                })
+                .await?;
+            println!("✅ Worktree removed successfully");
+            if let Some(reason) = outcome.protected_branch {
+                println!(
+                    "⚠️  Branch '{}' skipped (protected): {reason}",
+                    worktree.branch
+                );
+            }
+            if let Some(id) = outcome.trash_id {
+                println!("🗑️  In the trash as {id}; 'vibe trash restore {id}' brings it back");
+            }
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+/// Create a worktree in a repository picked from the workspace
+async fn create_worktree_interactive(workspace_manager: &WorkspaceManager) -> Result<()> {
+    let items = repo_picker::items_for_workspace(workspace_manager.config(), &HashMap::new());
+    let Some(repo) = handle_prompt_result(repo_picker::pick_repository(
+        "Create worktree in:",
+        items,
+        workspace_manager.get_repository_list_page_size(),
+    ))?
+    else {
+        return Ok(());
+    };
+
+    let Some(task_id) = handle_prompt_result(
+        Text::new("Task ID:")
+            .with_help_message("Used for the branch and directory name • ESC to go back")
+            .prompt(),
+    )?
+    else {
+        return Ok(());
+    };
+    let task_id = task_id.trim().to_string();
+    if task_id.is_empty() {
+        println!("{} Task ID cannot be empty", style("❌").red());
+        return Ok(());
+    }
+
+    let repo_path = resolve_repo_path(workspace_manager.get_workspace_root(), &repo.path);
+    let manager = WorktreeManager::new(repo_path, None).await?;
+    let info = manager.create_worktree(task_id).await?;
+
+    println!("✅ Created worktree:");
+    println!("  Branch: {}", style(&info.branch).yellow());
+    println!("  Path: {}", style(info.path.display()).blue());
+    match direnv::allow_new_worktree(&info.path, workspace_manager.is_direnv_auto_allow_enabled())
+        .await
+    {
+        Ok(true) => println!("  direnv: allowed .envrc"),
+        Ok(false) => {}
+        Err(e) => println!("{} {e}", style("⚠️").yellow()),
+    }
+
+    Ok(())
+}
+
+/// Confirmation defaulting to no; ESC counts as no
+fn confirm(message: &str) -> Result<bool> {
+    let answer = handle_prompt_result(Confirm::new(message).with_default(false).prompt())?;
+    Ok(answer == Some(true))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::worktree::status::WorktreeStatus;
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    fn worktree(branch: &str, task_id: Option<&str>) -> WorktreeInfo {
+        WorktreeInfo {
+            path: PathBuf::from("/tmp").join(branch),
+            branch: branch.to_string(),
+            head: String::new(),
+            task_id: task_id.map(str::to_string),
+            status: WorktreeStatus::new(),
+            age: Duration::from_secs(0),
+            is_detached: false,
+            protected: false,
+            metadata: Default::default(),
+            base_refresh: None,
+        }
+    }
+
+    #[test]
+    fn test_entry_label_prefers_task_id() {
+        let label = format_entry_label("api", &worktree("vibe-ws/login", Some("login")));
+        assert!(label.contains("api › login"));
+
+        let label = format_entry_label("api", &worktree("experiment", None));
+        assert!(label.contains("api › experiment"));
+    }
+
+    #[test]
+    fn test_flatten_keeps_repository_for_each_worktree() {
+        let listings = vec![
+            RepositoryWorktrees {
+                repo_name: "api".to_string(),
+                repo_path: PathBuf::from("/ws/api"),
+                worktrees: vec![worktree("a", None), worktree("b", None)],
+            },
+            RepositoryWorktrees {
+                repo_name: "web".to_string(),
+                repo_path: PathBuf::from("/ws/web"),
+                worktrees: vec![worktree("c", None)],
+            },
+        ];
+
+        let entries = flatten(listings);
+        let names: Vec<(&str, &str)> = entries
+            .iter()
+            .map(|e| (e.repo_name.as_str(), e.worktree.branch.as_str()))
+            .collect();
+        assert_eq!(names, vec![("api", "a"), ("api", "b"), ("web", "c")]);
+        assert_eq!(entries[2].repo_path, PathBuf::from("/ws/web"));
+    }
+}
@@ -2,9 +2,13 @@ use anyhow::Result;
 use async_trait::async_trait;
 use std::sync::Arc;
 
+use crate::git::clone::EnhancedCloneCommand;
 use crate::git::{CloneCommand, GitConfig, SearchCommand};
-use crate::uri::{parse_vibe_uri, VibeUri};
+use crate::output::exit::CommandError;
+use crate::uri::schemes::{help_text, SUPPORTED_SCHEMES};
+use crate::uri::{parse_vibe_uri, UriError, VibeUri};
 use crate::workspace::manager::WorkspaceManager;
+use crate::{display_println, output};
 
 #[async_trait]
 pub trait UriHandler: Send + Sync {
@@ -23,6 +27,25 @@ impl UriRouter {
         }
     }
 
+    /// Create a router with every built-in handler registered
+    pub fn with_default_handlers(
+        workspace_manager: Arc<tokio::sync::Mutex<WorkspaceManager>>,
+        git_config: GitConfig,
+    ) -> Self {
+        let mut router = Self::new();
+        router.add_handler(Box::new(GitHubUriHandler::new(
+            workspace_manager.clone(),
+            git_config.clone(),
+        )));
+        router.add_handler(Box::new(CloneUriHandler::new(
+            workspace_manager.clone(),
+            git_config,
+        )));
+        router.add_handler(Box::new(WorkspaceUriHandler::new(workspace_manager)));
+        router.add_handler(Box::new(HelpUriHandler));
+        router
+    }
+
     pub fn add_handler(&mut self, handler: Box<dyn UriHandler>) {
         self.handlers.push(handler);
     }
@@ -36,7 +59,7 @@ impl UriRouter {
             }
         }
 
-        anyhow::bail!("No handler found for URI: {}", uri_str)
+        Err(UriError::UnknownAction { action: uri.action }.into())
     }
 }
 
@@ -73,97 +96,251 @@ impl UriHandler for GitHubUriHandler {
                     CloneCommand::execute(url, None, false, false, &mut manager, &self.git_config)
                         .await?;
                 } else {
-                    anyhow::bail!("Missing repository path in URI");
+                    return Err(UriError::MissingPath {
+                        expected: "vibe://github/install/<org>/<repo>",
+                    }
+                    .into());
                 }
             }
             "search" => {
+                if !output::is_interactive() {
+                    anyhow::bail!("GitHub search needs a terminal; run 'vibe git search' instead");
+                }
+
                 // For URI-based search, we'll just open the interactive search
                 let mut manager = self.workspace_manager.lock().await;
                 SearchCommand::execute_interactive(&mut manager, &self.git_config).await?;
             }
-            _ => anyhow::bail!("Unknown GitHub command: {}", uri.command),
+            _ => {
+                return Err(UriError::UnknownCommand {
+                    action: uri.action.clone(),
+                    command: uri.command.clone(),
+                }
+                .into())
+            }
         }
 
         Ok(())
     }
 }
 
-// Platform-specific URI registration
-pub fn register_uri_scheme(scheme: &str) -> Result<()> {
-    #[cfg(target_os = "macos")]
-    {
-        macos::register_uri_scheme(scheme)
+// Clone-and-open URI Handler
+//
+// Handles `vibe://clone/<host>/<owner>/<repo>?app=<app>&open=<bool>&configure=<bool>`
+pub struct CloneUriHandler {
+    workspace_manager: Arc<tokio::sync::Mutex<WorkspaceManager>>,
+    git_config: GitConfig,
+}
+
+impl CloneUriHandler {
+    pub fn new(
+        workspace_manager: Arc<tokio::sync::Mutex<WorkspaceManager>>,
+        git_config: GitConfig,
+    ) -> Self {
+        Self {
+            workspace_manager,
+            git_config,
+        }
     }
+}
 
-    #[cfg(target_os = "linux")]
-    {
-        linux::register_uri_scheme(scheme)
+#[async_trait]
+impl UriHandler for CloneUriHandler {
+    fn can_handle(&self, uri: &VibeUri) -> bool {
+        uri.action == "clone"
     }
 
-    #[cfg(target_os = "windows")]
-    {
-        windows::register_uri_scheme(scheme)
+    async fn handle(&self, uri: &VibeUri) -> Result<()> {
+        // The host is parsed as the command, the owner/repo as the path
+        let path = uri.params.get("path").ok_or(UriError::MissingPath {
+            expected: "vibe://clone/<host>/<owner>/<repo>",
+        })?;
+        let (app, configure) = clone_app(uri)?;
+        let open = bool_param(uri, "open", true)?;
+
+        let mut manager = self.workspace_manager.lock().await;
+        // A link can come from any web page, so it may only clone from
+        // hosts the user trusts
+        if !manager.allows_clone_link_host(&uri.command) {
+            return Err(UriError::HostNotAllowed {
+                host: uri.command.clone(),
+            }
+            .into());
+        }
+        let url = format!("https://{}/{}", uri.command, path);
+        EnhancedCloneCommand::execute_non_interactive(
+            url,
+            app,
+            configure,
+            open,
+            &mut manager,
+            &self.git_config,
+        )
+        .await?;
+
+        Ok(())
     }
+}
 
-    #[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
-    {
-        anyhow::bail!("URI scheme registration not supported on this platform")
+// Workspace URI Handler
+pub struct WorkspaceUriHandler {
+    workspace_manager: Arc<tokio::sync::Mutex<WorkspaceManager>>,
+}
+
+impl WorkspaceUriHandler {
+    pub fn new(workspace_manager: Arc<tokio::sync::Mutex<WorkspaceManager>>) -> Self {
+        Self { workspace_manager }
     }
 }
 
-// macOS implementation
-#[cfg(target_os = "macos")]
-mod macos {
-    use anyhow::Result;
+#[async_trait]
+impl UriHandler for WorkspaceUriHandler {
+    fn can_handle(&self, uri: &VibeUri) -> bool {
+        uri.action == "workspace"
+    }
 
-    pub fn register_uri_scheme(scheme: &str) -> Result<()> {
-        // For macOS, we need to update the Info.plist of the application
-        // This is typically done during the build/install process
-        // For now, we'll provide instructions
+    async fn handle(&self, uri: &VibeUri) -> Result<()> {
+        let manager = self.workspace_manager.lock().await;
 
-        eprintln!("To register the '{scheme}' URI scheme on macOS:");
-        eprintln!("1. Add the following to your Info.plist:");
-        eprintln!("   <key>CFBundleURLTypes</key>");
-        eprintln!("   <array>");
-        eprintln!("     <dict>");
-        eprintln!("       <key>CFBundleURLSchemes</key>");
-        eprintln!("       <array>");
-        eprintln!("         <string>{scheme}</string>");
-        eprintln!("       </array>");
-        eprintln!("     </dict>");
-        eprintln!("   </array>");
-        eprintln!("2. Rebuild and reinstall the application");
+        match uri.command.as_str() {
+            "list" => {
+                for repo in manager.list_repositories() {
+                    display_println!("{}\t{}", repo.name, repo.path.display());
+                }
+            }
+            "open" => {
+                let name = uri.params.get("path").ok_or(UriError::MissingPath {
+                    expected: "vibe://workspace/open/<repo-name>",
+                })?;
+                let repo = manager
+                    .get_repository_flexible(name)
+                    .ok_or_else(|| CommandError::not_found("Repository", name.as_str()))?;
+                let repo_name = repo.name.clone();
+
+                let app = match uri.params.get("app") {
+                    Some(app) => app.clone(),
+                    None => {
+                        let configured = manager.list_apps_for_repo(&repo_name)?;
+                        match configured.as_slice() {
+                            [(app, _)] => app.clone(),
+                            _ => anyhow::bail!(
+                                "'{}' has {} apps configured; choose one with ?app=<app>",
+                                repo_name,
+                                configured.len()
+                            ),
+                        }
+                    }
+                };
+
+                manager.open_repo_with_app(&repo_name, &app).await?;
+            }
+            _ => {
+                return Err(UriError::UnknownCommand {
+                    action: uri.action.clone(),
+                    command: uri.command.clone(),
+                }
+                .into())
+            }
+        }
 
         Ok(())
     }
 }
 
-// Linux implementation (stub)
-#[cfg(target_os = "linux")]
-mod linux {
-    use anyhow::Result;
+// Help URI Handler
+//
+// Prints the supported URI forms, or shows them in a dialog when the OS
+// launched vibe without a terminal.
+pub struct HelpUriHandler;
+
+#[async_trait]
+impl UriHandler for HelpUriHandler {
+    fn can_handle(&self, uri: &VibeUri) -> bool {
+        uri.action == "help"
+    }
+
+    async fn handle(&self, _uri: &VibeUri) -> Result<()> {
+        if !output::is_interactive()
+            && crate::utils::platform::show_message_dialog("vibe:// URIs", &help_text()).is_ok()
+        {
+            return Ok(());
+        }
+
+        use console::style;
+
+        display_println!("{}", style("Supported vibe:// URIs").cyan().bold());
+        for form in SUPPORTED_SCHEMES {
+            display_println!();
+            display_println!("  {}", style(form.pattern).green());
+            display_println!("    {}", form.description);
+            display_println!("    {} {}", style("e.g.").dim(), style(form.example).dim());
+        }
 
-    pub fn register_uri_scheme(scheme: &str) -> Result<()> {
-        // Linux implementation would create a .desktop file
-        eprintln!(
-            "Linux URI scheme registration not yet implemented for '{}'",
-            scheme
-        );
         Ok(())
     }
 }
 
-// Windows implementation (stub)
-#[cfg(target_os = "windows")]
-mod windows {
-    use anyhow::Result;
+/// The `app` and `configure` parameters of a clone URI. Configuring needs an
+/// app, so an explicit `configure=true` without one is an error.
+fn clone_app(uri: &VibeUri) -> Result<(Option<String>, bool)> {
+    let app = uri.params.get("app").cloned();
+    let configure = bool_param(uri, "configure", true)?;
+    if configure && app.is_none() && uri.params.contains_key("configure") {
+        return Err(UriError::MissingParameter {
+            name: "app",
+            with: "configure=true",
+        }
+        .into());
+    }
+    Ok((app, configure))
+}
 
-    pub fn register_uri_scheme(scheme: &str) -> Result<()> {
-        // Windows implementation would modify the registry
-        eprintln!(
-            "Windows URI scheme registration not yet implemented for '{}'",
-            scheme
+/// Read a boolean query parameter, falling back to `default` when absent
+fn bool_param(uri: &VibeUri, key: &str, default: bool) -> Result<bool> {
+    match uri.params.get(key).map(|value| value.to_lowercase()) {
+        None => Ok(default),
+        Some(value) => match value.as_str() {
+            "true" | "1" | "yes" => Ok(true),
+            "false" | "0" | "no" => Ok(false),
+            _ => anyhow::bail!("Invalid value '{value}' for '{key}': expected true or false"),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bool_param() {
+        let uri =
+            parse_vibe_uri("vibe://clone/github.com/owner/repo?open=false&configure=YES").unwrap();
+        assert!(!bool_param(&uri, "open", true).unwrap());
+        assert!(bool_param(&uri, "configure", false).unwrap());
+        assert!(bool_param(&uri, "missing", true).unwrap());
+
+        let uri = parse_vibe_uri("vibe://clone/github.com/owner/repo?open=maybe").unwrap();
+        assert!(bool_param(&uri, "open", true).is_err());
+    }
+
+    #[test]
+    fn test_clone_app_requires_app_to_configure() {
+        let clone_app_of = |query: &str| {
+            clone_app(
+                &parse_vibe_uri(&format!("vibe://clone/github.com/owner/repo{query}")).unwrap(),
+            )
+        };
+        assert_eq!(clone_app_of("").unwrap(), (None, true));
+        assert_eq!(
+            clone_app_of("?app=cursor&configure=true").unwrap(),
+            (Some("cursor".to_string()), true)
+        );
+        assert_eq!(clone_app_of("?configure=false").unwrap(), (None, false));
+
+        let error = clone_app_of("?configure=true").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Query parameter 'app' is required with configure=true"
         );
-        Ok(())
     }
 }
@@ -1,37 +1,144 @@
-use anyhow::Result;
 use std::collections::HashMap;
 use url::Url;
 
 pub mod handler;
+pub mod registration;
 pub mod schemes;
 
+pub use registration::{
+    open_uri_command, register_uri_scheme, unregister_uri_scheme, uri_scheme_status, VIBE_SCHEME,
+};
 pub use schemes::VibeUri;
 
-pub fn parse_vibe_uri(uri_str: &str) -> Result<VibeUri> {
-    let url = Url::parse(uri_str)?;
+use schemes::{find_action, supported_actions, supported_commands, CommandSpec};
 
-    if url.scheme() != "vibe" {
-        anyhow::bail!(
-            "Invalid URI scheme: expected 'vibe', got '{}'",
-            url.scheme()
-        );
+/// Errors produced while parsing or routing a `vibe://` URI
+#[derive(Debug, thiserror::Error)]
+pub enum UriError {
+    #[error("Invalid URI '{uri}': {source}")]
+    Malformed {
+        uri: String,
+        #[source]
+        source: url::ParseError,
+    },
+
+    #[error("Invalid URI scheme: expected 'vibe', got '{scheme}'")]
+    InvalidScheme { scheme: String },
+
+    #[error(
+        "Missing action in URI. Supported actions: {} (see vibe://help)",
+        supported_actions()
+    )]
+    MissingAction,
+
+    #[error(
+        "Unknown URI action '{action}'. Supported actions: {} (see vibe://help)",
+        supported_actions()
+    )]
+    UnknownAction { action: String },
+
+    #[error(
+        "Missing command for '{action}'. Supported commands: {} (see vibe://help)",
+        supported_commands(action)
+    )]
+    MissingCommand { action: String },
+
+    #[error(
+        "Unknown command '{command}' for '{action}'. Supported commands: {} (see vibe://help)",
+        supported_commands(action)
+    )]
+    UnknownCommand { action: String, command: String },
+
+    #[error("Missing repository path in URI. Expected {expected}")]
+    MissingPath { expected: &'static str },
+
+    #[error("Query parameter '{name}' is required with {with}")]
+    MissingParameter {
+        name: &'static str,
+        with: &'static str,
+    },
+
+    #[error(
+        "vibe://clone links can't clone from '{host}'; add it to preferences.clone_link_hosts to allow it"
+    )]
+    HostNotAllowed { host: String },
+
+    #[error("Query parameter '{name}' is given more than once")]
+    DuplicateParameter { name: String },
+
+    #[error("Invalid percent-encoding in URI path segment '{segment}'")]
+    InvalidEncoding { segment: String },
+}
+
+pub fn parse_vibe_uri(uri_str: &str) -> Result<VibeUri, UriError> {
+    let url = Url::parse(uri_str).map_err(|source| UriError::Malformed {
+        uri: uri_str.to_string(),
+        source,
+    })?;
+
+    if url.scheme() != VIBE_SCHEME {
+        return Err(UriError::InvalidScheme {
+            scheme: url.scheme().to_string(),
+        });
     }
 
-    let host = url
+    let action = url
         .host_str()
-        .ok_or_else(|| anyhow::anyhow!("Missing host in URI"))?;
-    let path_segments: Vec<&str> = url
+        .filter(|host| !host.is_empty())
+        .ok_or(UriError::MissingAction)?;
+    let spec = find_action(action)
+        .ok_or_else(|| UriError::UnknownAction {
+            action: action.to_string(),
+        })?
+        .commands;
+
+    // Path segments arrive percent-encoded; decode them so unicode names survive
+    let path_segments = url
         .path_segments()
-        .ok_or_else(|| anyhow::anyhow!("Invalid URI path"))?
-        .collect();
+        .map(|segments| {
+            segments
+                .filter(|segment| !segment.is_empty())
+                .map(|segment| {
+                    urlencoding::decode(segment)
+                        .map(|decoded| decoded.into_owned())
+                        .map_err(|_| UriError::InvalidEncoding {
+                            segment: segment.to_string(),
+                        })
+                })
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .transpose()?
+        .unwrap_or_default();
 
-    let command = path_segments
-        .first()
-        .ok_or_else(|| anyhow::anyhow!("Missing command in URI path"))?
-        .to_string();
+    let command = path_segments.first().cloned().unwrap_or_default();
+    match spec {
+        CommandSpec::None if !command.is_empty() => {
+            return Err(UriError::UnknownCommand {
+                action: action.to_string(),
+                command,
+            });
+        }
+        CommandSpec::Host | CommandSpec::Fixed(_) if command.is_empty() => {
+            return Err(UriError::MissingCommand {
+                action: action.to_string(),
+            });
+        }
+        CommandSpec::Fixed(commands) if !commands.contains(&command.as_str()) => {
+            return Err(UriError::UnknownCommand {
+                action: action.to_string(),
+                command,
+            });
+        }
+        _ => {}
+    }
 
     let mut params = HashMap::new();
     for (key, value) in url.query_pairs() {
+        if params.contains_key(key.as_ref()) {
+            return Err(UriError::DuplicateParameter {
+                name: key.to_string(),
+            });
+        }
         params.insert(key.to_string(), value.to_string());
     }
 
@@ -42,7 +149,7 @@ pub fn parse_vibe_uri(uri_str: &str) -> Result<VibeUri> {
 
     Ok(VibeUri {
         scheme: url.scheme().to_string(),
-        action: host.to_string(),
+        action: action.to_string(),
         command,
         params,
     })
@@ -63,5 +170,119 @@ mod tests {
         assert_eq!(uri.action, "github");
         assert_eq!(uri.command, "search");
         assert_eq!(uri.params.get("q"), Some(&"rust web".to_string()));
+
+        let uri =
+            parse_vibe_uri("vibe://clone/github.com/owner/repo?app=vscode&open=true").unwrap();
+        assert_eq!(uri.action, "clone");
+        assert_eq!(uri.command, "github.com");
+        assert_eq!(uri.params.get("path"), Some(&"owner/repo".to_string()));
+        assert_eq!(uri.params.get("app"), Some(&"vscode".to_string()));
+    }
+
+    #[test]
+    fn test_parse_help_uri() {
+        for input in ["vibe://help", "vibe://help/"] {
+            let uri = parse_vibe_uri(input).unwrap();
+            assert_eq!(uri.action, "help");
+            assert_eq!(uri.command, "");
+            assert!(uri.params.is_empty());
+        }
+
+        assert!(matches!(
+            parse_vibe_uri("vibe://help/me"),
+            Err(UriError::UnknownCommand { .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_uris() {
+        assert!(matches!(
+            parse_vibe_uri("not a uri"),
+            Err(UriError::Malformed { .. })
+        ));
+        assert!(matches!(
+            parse_vibe_uri("https://github/install/a/b"),
+            Err(UriError::InvalidScheme { scheme }) if scheme == "https"
+        ));
+    }
+
+    #[test]
+    fn test_parse_missing_host() {
+        for input in ["vibe:///install/a/b", "vibe:github", "vibe://"] {
+            assert!(
+                matches!(parse_vibe_uri(input), Err(UriError::MissingAction)),
+                "expected missing action for {input}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_unknown_action_lists_supported_actions() {
+        let err = parse_vibe_uri("vibe://gitlab/install/a/b").unwrap_err();
+        assert!(matches!(err, UriError::UnknownAction { ref action } if action == "gitlab"));
+
+        let message = err.to_string();
+        for action in ["github", "clone", "workspace", "help"] {
+            assert!(message.contains(action), "{message} should list {action}");
+        }
+    }
+
+    #[test]
+    fn test_parse_empty_path() {
+        for input in ["vibe://github", "vibe://github/", "vibe://clone//"] {
+            assert!(
+                matches!(parse_vibe_uri(input), Err(UriError::MissingCommand { .. })),
+                "expected missing command for {input}"
+            );
+        }
+
+        let message = parse_vibe_uri("vibe://github").unwrap_err().to_string();
+        assert!(message.contains("install, search"));
+    }
+
+    #[test]
+    fn test_parse_unknown_command_lists_supported_commands() {
+        let err = parse_vibe_uri("vibe://github/fork/a/b").unwrap_err();
+        assert!(matches!(
+            err,
+            UriError::UnknownCommand { ref action, ref command } if action == "github" && command == "fork"
+        ));
+        assert!(err.to_string().contains("install, search"));
+    }
+
+    #[test]
+    fn test_parse_duplicate_query_params() {
+        assert!(matches!(
+            parse_vibe_uri("vibe://github/search?q=rust&q=go"),
+            Err(UriError::DuplicateParameter { name }) if name == "q"
+        ));
+
+        // Distinct keys are fine
+        assert!(parse_vibe_uri("vibe://github/search?q=rust&sort=stars").is_ok());
+    }
+
+    #[test]
+    fn test_parse_percent_encoded_unicode_repo_name() {
+        let uri = parse_vibe_uri("vibe://github/install/%C3%A9quipe/d%C3%A9p%C3%B4t").unwrap();
+        assert_eq!(uri.params.get("path"), Some(&"équipe/dépôt".to_string()));
+
+        // Raw unicode is percent-encoded by the URL parser and decoded again
+        let uri = parse_vibe_uri("vibe://clone/github.com/owner/日本語").unwrap();
+        assert_eq!(uri.params.get("path"), Some(&"owner/日本語".to_string()));
+
+        // Encoded bytes that are not valid UTF-8 are rejected
+        assert!(matches!(
+            parse_vibe_uri("vibe://github/install/owner/%FF%FE"),
+            Err(UriError::InvalidEncoding { .. })
+        ));
+    }
+
+    #[test]
+    fn test_help_text_lists_every_form() {
+        let text = schemes::help_text();
+        for form in schemes::SUPPORTED_SCHEMES {
+            assert!(text.contains(form.pattern));
+            assert!(text.contains(form.example));
+        }
     }
 }
@@ -0,0 +1,445 @@
+//! OS-level registration of the `vibe://` URL scheme
+//!
+//! Parsing `vibe://` URIs only helps if the operating system routes them to the
+//! `vibe` binary. On macOS URLs are dispatched by Launch Services to an app
+//! bundle, so we generate a minimal AppleScript applet whose `Info.plist`
+//! declares `CFBundleURLTypes`. On Linux desktops they are dispatched through a
+//! `.desktop` entry advertising `x-scheme-handler/vibe`, made the default with
+//! `xdg-mime`. Both handlers forward the URL to `vibe uri handle <url>`.
+
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+
+/// The URL scheme vibe registers with the operating system
+pub const VIBE_SCHEME: &str = "vibe";
+
+/// Current state of the `vibe://` handler as reported by the operating system
+#[derive(Debug, Clone)]
+pub struct RegistrationStatus {
+    /// Whether the OS routes `vibe://` URLs to the handler vibe installed
+    pub registered: bool,
+    /// Handler the OS currently reports for the scheme, if any
+    pub handler: Option<String>,
+    /// Location of the handler vibe installs (app bundle or desktop entry)
+    pub handler_path: Option<PathBuf>,
+}
+
+/// Register `executable` as the handler for `vibe://` URLs and verify the result
+pub fn register_uri_scheme(executable: &Path) -> Result<RegistrationStatus> {
+    #[cfg(target_os = "macos")]
+    {
+        macos::register(executable)?;
+        uri_scheme_status()
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        linux::register(executable)?;
+        uri_scheme_status()
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
+    {
+        let _ = executable;
+        anyhow::bail!("URI scheme registration is not supported on this platform yet")
+    }
+}
+
+/// Remove the `vibe://` handler installed by [`register_uri_scheme`]
+pub fn unregister_uri_scheme() -> Result<()> {
+    #[cfg(target_os = "macos")]
+    {
+        macos::unregister()
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        linux::unregister()
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
+    {
+        anyhow::bail!("URI scheme registration is not supported on this platform yet")
+    }
+}
+
+/// Query the operating system for the current `vibe://` handler
+pub fn uri_scheme_status() -> Result<RegistrationStatus> {
+    #[cfg(target_os = "macos")]
+    {
+        macos::status()
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        linux::status()
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
+    {
+        Ok(RegistrationStatus {
+            registered: false,
+            handler: None,
+            handler_path: None,
+        })
+    }
+}
+
+/// Shell command that asks the OS to open `uri`, for testing the registration
+pub fn open_uri_command(uri: &str) -> String {
+    if cfg!(target_os = "macos") {
+        format!("open '{uri}'")
+    } else if cfg!(target_os = "windows") {
+        format!("start {uri}")
+    } else {
+        format!("xdg-open '{uri}'")
+    }
+}
+
+// macOS implementation
+#[cfg(target_os = "macos")]
+mod macos {
+    use anyhow::{Context, Result};
+    use std::path::{Path, PathBuf};
+    use std::process::Command;
+
+    use super::{RegistrationStatus, VIBE_SCHEME};
+
+    const BUNDLE_NAME: &str = "Vibe URI Handler.app";
+    const BUNDLE_IDENTIFIER: &str = "dev.toolprint.vibe.uri-handler";
+    const LSREGISTER: &str = "/System/Library/Frameworks/CoreServices.framework/Frameworks/LaunchServices.framework/Support/lsregister";
+
+    /// Location of the generated wrapper app bundle
+    pub fn bundle_path() -> Result<PathBuf> {
+        let home = dirs::home_dir().context("Could not determine home directory")?;
+        Ok(home.join("Applications").join(BUNDLE_NAME))
+    }
+
+    pub fn register(executable: &Path) -> Result<()> {
+        let bundle = bundle_path()?;
+        if let Some(parent) = bundle.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        if bundle.exists() {
+            std::fs::remove_dir_all(&bundle)
+                .with_context(|| format!("Failed to replace {}", bundle.display()))?;
+        }
+
+        // Compile a minimal applet that forwards `open location` events to vibe
+        let script = tempfile::Builder::new().suffix(".applescript").tempfile()?;
+        std::fs::write(script.path(), applet_source(executable))?;
+        run("osacompile", &[Path::new("-o"), &bundle, script.path()])
+            .context("Failed to compile URI handler applet")?;
+
+        // Declare the URL scheme and a stable identifier in the bundle's Info.plist
+        let info_plist = bundle.join("Contents").join("Info.plist");
+        let url_types =
+            format!(r#"[{{"CFBundleURLName":"Vibe URL","CFBundleURLSchemes":["{VIBE_SCHEME}"]}}]"#);
+        plutil_replace(
+            &info_plist,
+            "CFBundleIdentifier",
+            "-string",
+            BUNDLE_IDENTIFIER,
+        )?;
+        plutil_replace(&info_plist, "CFBundleURLTypes", "-json", &url_types)?;
+        plutil_replace(&info_plist, "LSUIElement", "-bool", "true")?;
+
+        run(LSREGISTER, &[Path::new("-f"), &bundle])
+            .context("Failed to register app bundle with Launch Services")?;
+
+        // Make the bundle the default even if another app claimed the scheme
+        jxa(&format!(
+            "ObjC.import('CoreServices'); $.LSSetDefaultHandlerForURLScheme($('{VIBE_SCHEME}'), $('{BUNDLE_IDENTIFIER}'))"
+        ))
+        .context("Failed to set default URL scheme handler")?;
+
+        Ok(())
+    }
+
+    pub fn unregister() -> Result<()> {
+        let bundle = bundle_path()?;
+        if bundle.exists() {
+            // Unregistering is best-effort; removing the bundle is what matters
+            let _ = run(LSREGISTER, &[Path::new("-u"), &bundle]);
+            std::fs::remove_dir_all(&bundle)
+                .with_context(|| format!("Failed to remove {}", bundle.display()))?;
+        }
+        Ok(())
+    }
+
+    pub fn status() -> Result<RegistrationStatus> {
+        let bundle = bundle_path()?;
+        let handler = jxa(&format!(
+            "ObjC.import('AppKit'); \
+             var app = $.NSWorkspace.sharedWorkspace.URLForApplicationToOpenURL($.NSURL.URLWithString('{VIBE_SCHEME}://help')); \
+             app.isNil() ? '' : app.path.js"
+        ))
+        .ok()
+        .filter(|path| !path.is_empty());
+
+        let registered = bundle.exists()
+            && handler
+                .as_deref()
+                .map(|path| Path::new(path) == bundle)
+                .unwrap_or(false);
+
+        Ok(RegistrationStatus {
+            registered,
+            handler,
+            handler_path: Some(bundle),
+        })
+    }
+
+    /// AppleScript applet that opens each URL with `vibe uri handle` in Terminal
+    pub fn applet_source(executable: &Path) -> String {
+        let executable = executable
+            .to_string_lossy()
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"");
+        format!(
+            "on open location this_URL\n\
+             \ttell application \"Terminal\"\n\
+             \t\tactivate\n\
+             \t\tdo script (quoted form of \"{executable}\") & \" uri handle \" & quoted form of this_URL\n\
+             \tend tell\n\
+             end open location\n"
+        )
+    }
+
+    fn plutil_replace(plist: &Path, key: &str, kind: &str, value: &str) -> Result<()> {
+        let status = Command::new("plutil")
+            .args(["-replace", key, kind, value])
+            .arg(plist)
+            .status()
+            .context("Failed to run plutil")?;
+        if !status.success() {
+            anyhow::bail!("plutil failed to set {key} in {}", plist.display());
+        }
+        Ok(())
+    }
+
+    fn run(program: &str, args: &[&Path]) -> Result<()> {
+        let output = Command::new(program)
+            .args(args)
+            .output()
+            .with_context(|| format!("Failed to run {program}"))?;
+        if !output.status.success() {
+            anyhow::bail!(
+                "{program} failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        Ok(())
+    }
+
+    fn jxa(script: &str) -> Result<String> {
+        let output = Command::new("osascript")
+            .args(["-l", "JavaScript", "-e", script])
+            .output()
+            .context("Failed to run osascript")?;
+        if !output.status.success() {
+            anyhow::bail!(
+                "osascript failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_applet_source_escapes_executable() {
+            let source = applet_source(Path::new("/Users/me/my \"bin\"/vibe"));
+            assert!(source.contains("on open location this_URL"));
+            assert!(source.contains(r#"quoted form of "/Users/me/my \"bin\"/vibe""#));
+            assert!(source.contains("\" uri handle \""));
+        }
+    }
+}
+
+// Linux implementation
+#[cfg(target_os = "linux")]
+mod linux {
+    use anyhow::{Context, Result};
+    use std::path::{Path, PathBuf};
+    use std::process::Command;
+
+    use super::{RegistrationStatus, VIBE_SCHEME};
+
+    const DESKTOP_FILE_NAME: &str = "vibe-uri-handler.desktop";
+
+    fn mime_type() -> String {
+        format!("x-scheme-handler/{VIBE_SCHEME}")
+    }
+
+    /// Directory where per-user desktop entries live
+    fn applications_dir() -> Result<PathBuf> {
+        let data_dir = dirs::data_dir().context("Could not determine XDG data directory")?;
+        Ok(data_dir.join("applications"))
+    }
+
+    /// Location of the generated desktop entry
+    pub fn desktop_file_path() -> Result<PathBuf> {
+        Ok(applications_dir()?.join(DESKTOP_FILE_NAME))
+    }
+
+    pub fn register(executable: &Path) -> Result<()> {
+        let applications = applications_dir()?;
+        std::fs::create_dir_all(&applications)
+            .with_context(|| format!("Failed to create {}", applications.display()))?;
+
+        let desktop_file = applications.join(DESKTOP_FILE_NAME);
+        std::fs::write(&desktop_file, desktop_entry(executable))
+            .with_context(|| format!("Failed to write {}", desktop_file.display()))?;
+
+        // Refreshing the MIME cache is optional; not every desktop ships the tool
+        let _ = Command::new("update-desktop-database")
+            .arg(&applications)
+            .output();
+
+        let output = Command::new("xdg-mime")
+            .args(["default", DESKTOP_FILE_NAME, &mime_type()])
+            .output()
+            .context("Failed to run xdg-mime (is xdg-utils installed?)")?;
+        if !output.status.success() {
+            anyhow::bail!(
+                "xdg-mime failed to set the default handler: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+
+        Ok(())
+    }
+
+    pub fn unregister() -> Result<()> {
+        let desktop_file = desktop_file_path()?;
+        if desktop_file.exists() {
+            std::fs::remove_file(&desktop_file)
+                .with_context(|| format!("Failed to remove {}", desktop_file.display()))?;
+        }
+
+        // xdg-mime has no way to unset a default, so drop our entry from mimeapps.list
+        if let Some(mimeapps) = dirs::config_dir().map(|dir| dir.join("mimeapps.list")) {
+            if let Ok(contents) = std::fs::read_to_string(&mimeapps) {
+                let updated = remove_scheme_association(&contents);
+                if updated != contents {
+                    std::fs::write(&mimeapps, updated)
+                        .with_context(|| format!("Failed to update {}", mimeapps.display()))?;
+                }
+            }
+        }
+
+        if let Ok(applications) = applications_dir() {
+            let _ = Command::new("update-desktop-database")
+                .arg(&applications)
+                .output();
+        }
+
+        Ok(())
+    }
+
+    pub fn status() -> Result<RegistrationStatus> {
+        let desktop_file = desktop_file_path()?;
+        let handler = Command::new("xdg-mime")
+            .args(["query", "default", &mime_type()])
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+            .filter(|handler| !handler.is_empty());
+
+        let registered = desktop_file.exists() && handler.as_deref() == Some(DESKTOP_FILE_NAME);
+
+        Ok(RegistrationStatus {
+            registered,
+            handler,
+            handler_path: Some(desktop_file),
+        })
+    }
+
+    /// Desktop entry that forwards `vibe://` URLs to `vibe uri handle`
+    pub fn desktop_entry(executable: &Path) -> String {
+        format!(
+            "[Desktop Entry]\n\
+             Type=Application\n\
+             Name=Vibe URI Handler\n\
+             Comment=Open {VIBE_SCHEME}:// links with vibe-workspace\n\
+             Exec={} uri handle %u\n\
+             Terminal=true\n\
+             NoDisplay=true\n\
+             MimeType={};\n",
+            quote_exec_arg(&executable.to_string_lossy()),
+            mime_type()
+        )
+    }
+
+    /// Quote an argument for a desktop entry `Exec` key per the Desktop Entry spec
+    fn quote_exec_arg(arg: &str) -> String {
+        let mut quoted = String::with_capacity(arg.len() + 2);
+        quoted.push('"');
+        for c in arg.chars() {
+            if matches!(c, '"' | '`' | '$' | '\\') {
+                quoted.push('\\');
+            }
+            quoted.push(c);
+        }
+        quoted.push('"');
+        quoted
+    }
+
+    /// Remove `x-scheme-handler/vibe` associations from a mimeapps.list file
+    pub fn remove_scheme_association(contents: &str) -> String {
+        let key = format!("{}=", mime_type());
+        let mut result: String = contents
+            .lines()
+            .filter(|line| !line.trim_start().starts_with(&key))
+            .collect::<Vec<_>>()
+            .join("\n");
+        if contents.ends_with('\n') {
+            result.push('\n');
+        }
+        result
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_desktop_entry_declares_scheme_handler() {
+            let entry = desktop_entry(Path::new("/home/me/.cargo/bin/vibe"));
+            assert!(entry.starts_with("[Desktop Entry]\n"));
+            assert!(entry.contains("Exec=\"/home/me/.cargo/bin/vibe\" uri handle %u\n"));
+            assert!(entry.contains("MimeType=x-scheme-handler/vibe;\n"));
+        }
+
+        #[test]
+        fn test_quote_exec_arg_escapes_reserved_characters() {
+            assert_eq!(quote_exec_arg("/opt/my bin/vibe"), "\"/opt/my bin/vibe\"");
+            assert_eq!(quote_exec_arg("/opt/$HOME/vibe"), "\"/opt/\\$HOME/vibe\"");
+            assert_eq!(quote_exec_arg("a\"b"), "\"a\\\"b\"");
+        }
+
+        #[test]
+        fn test_remove_scheme_association() {
+            let contents = "[Default Applications]\n\
+                            text/html=firefox.desktop\n\
+                            x-scheme-handler/vibe=vibe-uri-handler.desktop\n\
+                            [Added Associations]\n\
+                            x-scheme-handler/vibe=vibe-uri-handler.desktop;\n";
+            let updated = remove_scheme_association(contents);
+            assert_eq!(
+                updated,
+                "[Default Applications]\ntext/html=firefox.desktop\n[Added Associations]\n"
+            );
+
+            // Files without our association are left untouched
+            let untouched = "[Default Applications]\ntext/html=firefox.desktop";
+            assert_eq!(remove_scheme_association(untouched), untouched);
+        }
+    }
+}
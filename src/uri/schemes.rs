@@ -45,19 +45,117 @@ impl VibeUri {
     }
 }
 
+/// A supported `vibe://` URI form with a description and a concrete example
+#[derive(Debug, Clone, Copy)]
+pub struct UriForm {
+    pub pattern: &'static str,
+    pub description: &'static str,
+    pub example: &'static str,
+}
+
 // Supported URI schemes and their descriptions
-pub const SUPPORTED_SCHEMES: &[(&str, &str)] = &[
-    (
-        "vibe://github/install/<org>/<repo>",
-        "Install a GitHub repository",
-    ),
-    (
-        "vibe://github/search?q=<query>",
-        "Search GitHub repositories",
-    ),
-    (
-        "vibe://workspace/open/<repo-name>",
-        "Open a workspace repository",
-    ),
-    ("vibe://workspace/list", "List all workspace repositories"),
+pub const SUPPORTED_SCHEMES: &[UriForm] = &[
+    UriForm {
+        pattern: "vibe://github/install/<org>/<repo>",
+        description: "Install a GitHub repository",
+        example: "vibe://github/install/rust-lang/rust",
+    },
+    UriForm {
+        pattern: "vibe://github/search?q=<query>",
+        description: "Search GitHub repositories",
+        example: "vibe://github/search?q=rust+web",
+    },
+    UriForm {
+        pattern: "vibe://clone/<host>/<owner>/<repo>?app=<app>&open=<bool>&configure=<bool>",
+        description: "Clone a repository from github.com or a host in preferences.clone_link_hosts (or open it if already cloned)",
+        example: "vibe://clone/github.com/toolprint/vibe-workspace?app=vscode",
+    },
+    UriForm {
+        pattern: "vibe://workspace/open/<repo-name>",
+        description: "Open a workspace repository",
+        example: "vibe://workspace/open/vibe-workspace",
+    },
+    UriForm {
+        pattern: "vibe://workspace/list",
+        description: "List all workspace repositories",
+        example: "vibe://workspace/list",
+    },
+    UriForm {
+        pattern: "vibe://help",
+        description: "Show the supported vibe:// URI forms",
+        example: "vibe://help",
+    },
 ];
+
+/// Which commands an action accepts in the first path segment
+#[derive(Debug, Clone, Copy)]
+pub enum CommandSpec {
+    /// The action takes no command (e.g. `vibe://help`)
+    None,
+    /// The first path segment is a git host (e.g. `vibe://clone/github.com/...`)
+    Host,
+    /// One of a fixed set of commands
+    Fixed(&'static [&'static str]),
+}
+
+/// An action (the URI host) understood by the URI router
+#[derive(Debug, Clone, Copy)]
+pub struct UriAction {
+    pub name: &'static str,
+    pub commands: CommandSpec,
+}
+
+pub const SUPPORTED_ACTIONS: &[UriAction] = &[
+    UriAction {
+        name: "github",
+        commands: CommandSpec::Fixed(&["install", "search"]),
+    },
+    UriAction {
+        name: "clone",
+        commands: CommandSpec::Host,
+    },
+    UriAction {
+        name: "workspace",
+        commands: CommandSpec::Fixed(&["open", "list"]),
+    },
+    UriAction {
+        name: "help",
+        commands: CommandSpec::None,
+    },
+];
+
+/// Look up a supported action by name
+pub fn find_action(name: &str) -> Option<&'static UriAction> {
+    SUPPORTED_ACTIONS.iter().find(|action| action.name == name)
+}
+
+/// Comma-separated list of supported action names
+pub fn supported_actions() -> String {
+    SUPPORTED_ACTIONS
+        .iter()
+        .map(|action| action.name)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Human-readable list of the commands accepted by `action`
+pub fn supported_commands(action: &str) -> String {
+    match find_action(action).map(|action| action.commands) {
+        Some(CommandSpec::Fixed(commands)) => commands.join(", "),
+        Some(CommandSpec::Host) => "<host> (e.g. github.com)".to_string(),
+        Some(CommandSpec::None) => "none".to_string(),
+        None => supported_actions(),
+    }
+}
+
+/// Plain-text listing of every supported URI form, for help output and dialogs
+pub fn help_text() -> String {
+    let mut text = String::from("Supported vibe:// URIs:\n");
+    for form in SUPPORTED_SCHEMES {
+        text.push_str(&format!(
+            "\n{}\n  {}\n  e.g. {}\n",
+            form.pattern, form.description, form.example
+        ));
+    }
+    text
+}
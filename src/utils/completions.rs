@@ -0,0 +1,360 @@
+//! Shell completion support
+//!
+//! The static part of each completion script is generated by clap_complete.
+//! The dynamic part below is appended to it and calls the hidden
+//! `vibe __complete <kind>` command for repository, app, and worktree names.
+//! Those lookups read the repository cache and plain files only, never git.
+
+use anyhow::{Context, Result};
+use clap_complete::Shell;
+use std::path::{Path, PathBuf};
+
+use crate::cache::RepositoryCache;
+use crate::workspace::constants;
+use crate::worktree::WorktreeConfig;
+
+/// Apps that can be passed to `--app` and `apps configure`
+pub const SUPPORTED_APPS: &[&str] = &["vscode", "warp", "iterm2", "wezterm", "cursor", "windsurf"];
+
+const BASH_DYNAMIC: &str = r#"
+_vibe_dynamic() {
+    local cur="${COMP_WORDS[COMP_CWORD]}"
+    local kind=""
+    case "${COMP_WORDS[*]:1:COMP_CWORD-1}" in
+        open|path|"apps configure") kind=repos ;;
+        "apps configure "*) kind=apps ;;
+        "git worktree open"|"git worktree remove"|"git worktree annotate"|"git worktree extend") kind=worktrees ;;
+    esac
+    if [[ -n "$kind" && "$cur" != -* ]]; then
+        COMPREPLY=($(compgen -W "$(vibe __complete "$kind" 2>/dev/null)" -- "$cur"))
+        return 0
+    fi
+    _vibe "$@"
+}
+complete -F _vibe_dynamic -o bashdefault -o default vibe
+"#;
+
+const ZSH_DYNAMIC: &str = r#"
+_vibe_dynamic() {
+    local kind=""
+    case "${words[2,CURRENT-1]}" in
+        open|path|"apps configure") kind=repos ;;
+        "apps configure "*) kind=apps ;;
+        "git worktree open"|"git worktree remove"|"git worktree annotate"|"git worktree extend") kind=worktrees ;;
+    esac
+    if [[ -n $kind && $PREFIX != -* ]]; then
+        local -a candidates
+        candidates=(${(f)"$(vibe __complete $kind 2>/dev/null)"})
+        compadd -a candidates
+        return
+    fi
+    _vibe "$@"
+}
+compdef _vibe_dynamic vibe
+"#;
+
+const FISH_DYNAMIC: &str = r#"
+function __vibe_dynamic_kind
+    set -l words (commandline -opc)[2..-1]
+    switch "$words"
+        case open path 'apps configure'
+            test $argv[1] = repos
+        case 'apps configure *'
+            test $argv[1] = apps
+        case 'git worktree open' 'git worktree remove' 'git worktree annotate' 'git worktree extend'
+            test $argv[1] = worktrees
+        case '*'
+            return 1
+    end
+end
+complete -c vibe -n '__vibe_dynamic_kind repos' -f -a '(vibe __complete repos 2>/dev/null)'
+complete -c vibe -n '__vibe_dynamic_kind apps' -f -a '(vibe __complete apps 2>/dev/null)'
+complete -c vibe -n '__vibe_dynamic_kind worktrees' -f -a '(vibe __complete worktrees 2>/dev/null)'
+"#;
+
+/// The dynamic completion snippet appended to the generated script, if the
+/// shell is supported
+pub fn dynamic_completion_script(shell: Shell) -> Option<&'static str> {
+    match shell {
+        Shell::Bash => Some(BASH_DYNAMIC),
+        Shell::Zsh => Some(ZSH_DYNAMIC),
+        Shell::Fish => Some(FISH_DYNAMIC),
+        _ => None,
+    }
+}
+
+const BASH_INIT: &str = r#"# vcd: cd into a vibe repository or worktree
+vcd() {
+    local dir
+    dir="$(command vibe path "$@")" || return
+    cd -- "$dir"
+}
+_vcd() {
+    COMPREPLY=($(compgen -W "$(vibe __complete repos 2>/dev/null)" -- "${COMP_WORDS[COMP_CWORD]}"))
+}
+complete -F _vcd vcd
+"#;
+
+const ZSH_INIT: &str = r#"# vcd: cd into a vibe repository or worktree
+vcd() {
+    local dir
+    dir="$(command vibe path "$@")" || return
+    cd -- "$dir"
+}
+_vcd() {
+    local -a candidates
+    candidates=(${(f)"$(vibe __complete repos 2>/dev/null)"})
+    compadd -a candidates
+}
+(( $+functions[compdef] )) && compdef _vcd vcd
+"#;
+
+const FISH_INIT: &str = r#"# vcd: cd into a vibe repository or worktree
+function vcd --description 'cd into a vibe repository or worktree'
+    set -l dir (command vibe path $argv); or return
+    cd -- $dir
+end
+complete -c vcd -f -a '(vibe __complete repos 2>/dev/null)'
+"#;
+
+/// The `vcd` shell function printed by `vibe shell-init`, if the shell is
+/// supported
+pub fn shell_init_script(shell: Shell) -> Option<&'static str> {
+    match shell {
+        Shell::Bash => Some(BASH_INIT),
+        Shell::Zsh => Some(ZSH_INIT),
+        Shell::Fish => Some(FISH_INIT),
+        _ => None,
+    }
+}
+
+/// The user's login shell, if it is one vibe can install completions for
+pub fn detect_shell() -> Option<Shell> {
+    Shell::from_env().filter(|shell| dynamic_completion_script(*shell).is_some())
+}
+
+/// Where the completion script for `shell` is installed. Bash and fish load
+/// these locations automatically; zsh needs a `source` line in `~/.zshrc`.
+pub fn completion_install_path(shell: Shell) -> Option<PathBuf> {
+    let home = dirs::home_dir()?;
+    match shell {
+        Shell::Bash => Some(home.join(".local/share/bash-completion/completions/vibe")),
+        Shell::Zsh => Some(constants::get_data_dir().join("completions/vibe.zsh")),
+        Shell::Fish => Some(home.join(".config/fish/completions/vibe.fish")),
+        _ => None,
+    }
+}
+
+/// Generate the completion script with the running binary and write it to
+/// the shell's completion directory
+pub fn install_completion(shell: Shell) -> Result<PathBuf> {
+    let path = completion_install_path(shell)
+        .with_context(|| format!("Completions are not supported for {shell}"))?;
+
+    let exe = std::env::current_exe().context("Failed to locate the vibe binary")?;
+    let output = std::process::Command::new(exe)
+        .args(["completions", &shell.to_string()])
+        .output()
+        .context("Failed to generate completions")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "Failed to generate completions: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+    std::fs::write(&path, output.stdout)
+        .with_context(|| format!("Failed to write completions: {}", path.display()))?;
+
+    Ok(path)
+}
+
+/// Repository names from the cache, falling back to the config file when
+/// the cache has not been populated yet, then `<repo>:<subproject>` targets
+pub async fn complete_repositories(config_path: &Path) -> Vec<String> {
+    let cache_path = constants::get_cache_dir().join("repositories.db");
+    let mut names = Vec::new();
+    if cache_path.exists() {
+        if let Ok(cached) = RepositoryCache::new(cache_path)
+            .get_repository_names()
+            .await
+        {
+            names = cached;
+        }
+    }
+    if names.is_empty() {
+        names = repository_names_from_config(config_path);
+    }
+
+    names.extend(subproject_targets_from_config(config_path));
+    names
+}
+
+fn repository_names_from_config(config_path: &Path) -> Vec<String> {
+    read_config_value(config_path)
+        .and_then(|config| {
+            config.get("repositories")?.as_sequence().map(|repos| {
+                repos
+                    .iter()
+                    .filter_map(|repo| repo.get("name")?.as_str().map(str::to_string))
+                    .collect()
+            })
+        })
+        .unwrap_or_default()
+}
+
+fn subproject_targets_from_config(config_path: &Path) -> Vec<String> {
+    let Some(config) = read_config_value(config_path) else {
+        return Vec::new();
+    };
+    let Some(repos) = config.get("repositories").and_then(|r| r.as_sequence()) else {
+        return Vec::new();
+    };
+    repos
+        .iter()
+        .filter_map(|repo| Some((repo.get("name")?.as_str()?, repo.get("subprojects")?)))
+        .flat_map(|(repo, subprojects)| {
+            subprojects
+                .as_sequence()
+                .into_iter()
+                .flatten()
+                .filter_map(move |s| Some(format!("{repo}:{}", s.get("name")?.as_str()?)))
+        })
+        .collect()
+}
+
+/// Task ids (or branch names) of the worktrees of the repository containing
+/// `dir`, read from the `.git/worktrees` metadata
+pub fn complete_worktrees(config_path: &Path, dir: &Path) -> Vec<String> {
+    let prefix = read_config_value(config_path)
+        .and_then(|config| {
+            config
+                .get("worktree")?
+                .get("prefix")?
+                .as_str()
+                .map(str::to_string)
+        })
+        .unwrap_or_else(|| WorktreeConfig::default().prefix);
+
+    let Some(common_dir) = find_git_common_dir(dir) else {
+        return Vec::new();
+    };
+    let Ok(entries) = std::fs::read_dir(common_dir.join("worktrees")) else {
+        return Vec::new();
+    };
+
+    let mut names: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| std::fs::read_to_string(entry.path().join("HEAD")).ok())
+        .filter_map(|head| {
+            let branch = head.trim().strip_prefix("ref: refs/heads/")?.to_string();
+            Some(
+                branch
+                    .strip_prefix(&prefix)
+                    .map(str::to_string)
+                    .unwrap_or(branch),
+            )
+        })
+        .collect();
+    names.sort();
+    names
+}
+
+fn read_config_value(config_path: &Path) -> Option<serde_yaml::Value> {
+    let contents = std::fs::read_to_string(config_path).ok()?;
+    serde_yaml::from_str(&contents).ok()
+}
+
+/// The shared git directory for `dir`, whether it is inside the main
+/// checkout or one of its linked worktrees
+fn find_git_common_dir(dir: &Path) -> Option<PathBuf> {
+    crate::utils::git::locate_repository(dir)
+        .ok()
+        .map(|location| location.common_dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn add_worktree_metadata(common: &Path, name: &str, head: &str) {
+        let dir = common.join("worktrees").join(name);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("HEAD"), head).unwrap();
+    }
+
+    #[test]
+    fn test_complete_worktrees_strips_prefix() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = temp_dir.path().join("repo");
+        let common = repo.join(".git");
+        std::fs::create_dir_all(repo.join("src")).unwrap();
+        add_worktree_metadata(&common, "a", "ref: refs/heads/vibe-ws/login-fix\n");
+        add_worktree_metadata(&common, "b", "ref: refs/heads/experiment\n");
+        add_worktree_metadata(&common, "c", "0123456789abcdef\n");
+
+        let config = temp_dir.path().join("missing.yaml");
+        assert_eq!(
+            complete_worktrees(&config, &repo.join("src")),
+            vec!["experiment".to_string(), "login-fix".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_find_git_common_dir_from_linked_worktree() {
+        let temp_dir = TempDir::new().unwrap();
+        let common = temp_dir.path().join("repo/.git");
+        std::fs::create_dir_all(common.join("worktrees/feature")).unwrap();
+        std::fs::write(common.join("worktrees/feature/commondir"), "../..\n").unwrap();
+
+        let worktree = temp_dir.path().join("feature");
+        std::fs::create_dir_all(&worktree).unwrap();
+        std::fs::write(
+            worktree.join(".git"),
+            format!("gitdir: {}\n", common.join("worktrees/feature").display()),
+        )
+        .unwrap();
+
+        let found = find_git_common_dir(&worktree).unwrap();
+        assert_eq!(
+            found.canonicalize().unwrap(),
+            common.canonicalize().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_repository_names_from_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = temp_dir.path().join("config.yaml");
+        std::fs::write(
+            &config,
+            "repositories:\n- name: api\n  path: api\n- name: web\n  path: web\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            repository_names_from_config(&config),
+            vec!["api".to_string(), "web".to_string()]
+        );
+        assert!(repository_names_from_config(&temp_dir.path().join("missing.yaml")).is_empty());
+    }
+
+    #[test]
+    fn test_subproject_targets_from_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = temp_dir.path().join("config.yaml");
+        std::fs::write(
+            &config,
+            "repositories:\n- name: api\n  path: api\n- name: mono\n  path: mono\n  \
+             subprojects:\n  - name: web\n    path: apps/web\n",
+        )
+        .unwrap();
+
+        assert_eq!(subproject_targets_from_config(&config), vec!["mono:web"]);
+    }
+}
@@ -0,0 +1,131 @@
+//! direnv support for terminals, editors, and worktrees opened by vibe
+//!
+//! Launch configurations start their commands non-interactively, so the
+//! user's direnv shell hook never runs. When `preferences.enable_direnv` is
+//! set and a directory has an `.envrc`, commands are wrapped in
+//! `direnv exec <dir>` to load its environment first.
+
+use anyhow::{Context, Result};
+use std::ffi::OsStr;
+use std::path::Path;
+use tokio::process::Command;
+
+use crate::utils::platform::resolve_command;
+
+/// Eval line that loads the current directory's environment into a shell
+pub const SHELL_PRELUDE: &str = r#"eval "$(direnv export bash)""#;
+
+/// Whether `dir` has an `.envrc` for direnv to load
+pub fn has_envrc(dir: &Path) -> bool {
+    dir.join(".envrc").is_file()
+}
+
+/// Whether commands started in `dir` should go through direnv
+pub fn applies_to(enabled: bool, dir: &Path) -> bool {
+    enabled && has_envrc(dir)
+}
+
+/// Shell command line running `command` with `dir`'s environment loaded
+pub fn wrap_command(dir: &Path, command: &str) -> String {
+    format!(
+        "direnv exec {} {command}",
+        shell_quote(&dir.display().to_string())
+    )
+}
+
+/// Command spawning `program`, wrapped in `direnv exec <dir>` when direnv
+/// applies to `dir`
+pub fn command(program: impl AsRef<OsStr>, dir: &Path, enabled: bool) -> Command {
+    if !applies_to(enabled, dir) {
+        return Command::new(program);
+    }
+    let mut command = Command::new(resolve_command("direnv"));
+    command.arg("exec").arg(dir).arg(program);
+    command
+}
+
+/// Trust `dir`'s `.envrc` so direnv loads it without prompting
+pub async fn allow(dir: &Path) -> Result<()> {
+    let output = Command::new(resolve_command("direnv"))
+        .arg("allow")
+        .arg(dir)
+        .output()
+        .await
+        .context("Failed to run direnv")?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "direnv allow failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+/// Trust a newly created worktree's `.envrc` when `auto_allow` is set, so
+/// the first shell opened there isn't blocked by direnv's prompt. Returns
+/// whether `direnv allow` ran.
+pub async fn allow_new_worktree(path: &Path, auto_allow: bool) -> Result<bool> {
+    if !auto_allow || !has_envrc(path) {
+        return Ok(false);
+    }
+    allow(path).await?;
+    Ok(true)
+}
+
+/// Single-quote `value` for a POSIX shell unless it is plainly safe
+fn shell_quote(value: &str) -> String {
+    let safe = |c: char| c.is_ascii_alphanumeric() || "/._-~+:@".contains(c);
+    if !value.is_empty() && value.chars().all(safe) {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', r"'\''"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_applies_only_when_enabled_with_envrc() {
+        let dir = TempDir::new().unwrap();
+        assert!(!applies_to(true, dir.path()));
+
+        std::fs::write(dir.path().join(".envrc"), "export FOO=1\n").unwrap();
+        assert!(applies_to(true, dir.path()));
+        assert!(!applies_to(false, dir.path()));
+    }
+
+    #[test]
+    fn test_wrap_command_quotes_paths() {
+        assert_eq!(
+            wrap_command(Path::new("/src/app"), "claude"),
+            "direnv exec /src/app claude"
+        );
+        assert_eq!(
+            wrap_command(Path::new("/src/my app's"), "just"),
+            r"direnv exec '/src/my app'\''s' just"
+        );
+    }
+
+    #[test]
+    fn test_command_wraps_program() {
+        let dir = TempDir::new().unwrap();
+        let plain = command("code", dir.path(), true);
+        assert_eq!(plain.as_std().get_program(), "code");
+
+        std::fs::write(dir.path().join(".envrc"), "").unwrap();
+        let wrapped = command("code", dir.path(), true);
+        let args: Vec<_> = wrapped.as_std().get_args().collect();
+        assert_eq!(
+            args,
+            [
+                OsStr::new("exec"),
+                dir.path().as_os_str(),
+                OsStr::new("code")
+            ]
+        );
+    }
+}
@@ -100,6 +100,122 @@ pub fn format_file_size(bytes: u64) -> String {
     }
 }
 
+/// Strip the `\\?\` verbatim prefix that `canonicalize` and some git output
+/// produce on Windows, so paths display and compare like the ones users type
+pub fn strip_verbatim_prefix<P: AsRef<Path>>(path: P) -> PathBuf {
+    let path = path.as_ref();
+    let Some(path_str) = path.to_str() else {
+        return path.to_path_buf();
+    };
+
+    if let Some(unc) = path_str.strip_prefix(r"\\?\UNC\") {
+        return PathBuf::from(format!(r"\\{unc}"));
+    }
+    match path_str.strip_prefix(r"\\?\") {
+        // Only drive paths; other verbatim forms have no plain equivalent
+        Some(rest) if rest.as_bytes().get(1) == Some(&b':') => PathBuf::from(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// `std::fs::canonicalize` without the Windows verbatim prefix
+pub fn canonicalize<P: AsRef<Path>>(path: P) -> std::io::Result<PathBuf> {
+    std::fs::canonicalize(path).map(strip_verbatim_prefix)
+}
+
+/// Render `path` with forward slashes, as .gitignore patterns require
+pub fn to_forward_slashes<P: AsRef<Path>>(path: P) -> String {
+    path.as_ref().to_string_lossy().replace('\\', "/")
+}
+
+/// Comparable form of a path: no verbatim prefix, forward slashes, no
+/// trailing separator, and lowercase on Windows where paths are case-insensitive
+fn comparable(path: &Path) -> String {
+    let path = to_forward_slashes(strip_verbatim_prefix(path));
+    let path = match path.trim_end_matches('/') {
+        "" => path.as_str(),
+        trimmed => trimmed,
+    };
+    if cfg!(windows) {
+        path.to_lowercase()
+    } else {
+        path.to_string()
+    }
+}
+
+/// Whether two paths name the same location, ignoring verbatim prefixes,
+/// separator style, and (on Windows) letter case
+pub fn paths_equal<P: AsRef<Path>, Q: AsRef<Path>>(a: P, b: Q) -> bool {
+    comparable(a.as_ref()) == comparable(b.as_ref())
+}
+
+/// Whether `path` is `base` or inside it, normalized like [`paths_equal`]
+pub fn path_starts_with<P: AsRef<Path>, Q: AsRef<Path>>(path: P, base: Q) -> bool {
+    let path = comparable(path.as_ref());
+    let base = comparable(base.as_ref());
+    path == base
+        || path
+            .strip_prefix(&base)
+            .is_some_and(|rest| rest.starts_with('/') || base.ends_with('/'))
+}
+
+/// Parents of removable and network volumes: `/Volumes/<volume>` on macOS,
+/// `/media/<user>/<volume>`, `/run/media/<user>/<volume>`, and `/mnt/<volume>`
+fn is_volume_parent(dir: &Path) -> bool {
+    let parts: Vec<_> = dir.components().skip(1).collect();
+    let names: Vec<&str> = parts
+        .iter()
+        .filter_map(|c| c.as_os_str().to_str())
+        .collect();
+    matches!(
+        names.as_slice(),
+        ["Volumes"] | ["mnt"] | ["media"] | ["media", _] | ["run", "media", _]
+    )
+}
+
+/// The mount point `path` lives under, when that volume is not mounted:
+/// a removable-media directory that is missing or empty, or an `/etc/fstab`
+/// mount point absent from the mount table. A repository there may only be
+/// on a drive that isn't plugged in.
+pub fn unavailable_mount<P: AsRef<Path>>(path: P) -> Option<PathBuf> {
+    let path = path.as_ref();
+    let mut ancestors: Vec<&Path> = path.ancestors().skip(1).collect();
+    ancestors.reverse();
+    for dir in ancestors {
+        let Some(parent) = dir.parent() else {
+            continue;
+        };
+        if is_volume_parent(parent) {
+            let empty = std::fs::read_dir(dir)
+                .map(|mut entries| entries.next().is_none())
+                .unwrap_or(true);
+            if empty {
+                return Some(dir.to_path_buf());
+            }
+        }
+    }
+
+    let fstab = std::fs::read_to_string("/etc/fstab").ok()?;
+    let mounted = mount_points(&std::fs::read_to_string("/proc/self/mounts").ok()?);
+    mount_points(&fstab)
+        .into_iter()
+        .filter(|mount| mount != Path::new("/") && path.starts_with(mount))
+        .find(|mount| !mounted.contains(mount))
+}
+
+/// Mount points of an fstab-format table such as `/etc/fstab` or
+/// `/proc/self/mounts`, with `\040` unescaped to a space
+fn mount_points(table: &str) -> Vec<PathBuf> {
+    table
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_whitespace().nth(1))
+        .filter(|mount| mount.starts_with('/'))
+        .map(|mount| PathBuf::from(mount.replace("\\040", " ")))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -160,6 +276,40 @@ mod tests {
         assert_eq!(format_file_size(1024 * 1024 * 1024), "1.0 GB");
     }
 
+    #[test]
+    fn test_strip_verbatim_prefix() {
+        assert_eq!(
+            strip_verbatim_prefix(r"\\?\C:\Users\dev\repo"),
+            Path::new(r"C:\Users\dev\repo")
+        );
+        assert_eq!(
+            strip_verbatim_prefix(r"\\?\UNC\server\share\repo"),
+            Path::new(r"\\server\share\repo")
+        );
+        assert_eq!(
+            strip_verbatim_prefix(r"\\?\Volume{1234}\repo"),
+            Path::new(r"\\?\Volume{1234}\repo")
+        );
+        assert_eq!(strip_verbatim_prefix("/home/dev"), Path::new("/home/dev"));
+    }
+
+    #[test]
+    fn test_paths_equal_ignores_separator_style() {
+        assert!(paths_equal(r"\\?\C:\work\repo", "C:/work/repo/"));
+        assert!(paths_equal("/work/repo/", "/work/repo"));
+        assert!(!paths_equal("/work/repo", "/work/repo2"));
+        assert!(path_starts_with(
+            r"C:\work\repo\.worktrees\a",
+            "C:/work/repo"
+        ));
+        assert!(!path_starts_with("/work/repo2", "/work/repo"));
+        assert!(path_starts_with("/work/repo", "/"));
+        assert_eq!(
+            to_forward_slashes(r".worktrees\nested"),
+            ".worktrees/nested"
+        );
+    }
+
     #[tokio::test]
     async fn test_ensure_directory_exists() {
         let temp_dir = TempDir::new().unwrap();
@@ -175,4 +325,27 @@ mod tests {
         let result = ensure_directory_exists(&new_dir).await;
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_volume_mount_points() {
+        for dir in ["/Volumes", "/mnt", "/media", "/media/me", "/run/media/me"] {
+            assert!(is_volume_parent(Path::new(dir)), "{dir}");
+        }
+        for dir in ["/", "/home", "/home/me", "/run/media", "/Volumes/Drive"] {
+            assert!(!is_volume_parent(Path::new(dir)), "{dir}");
+        }
+        assert_eq!(
+            unavailable_mount("/Volumes/vibe-test-missing-drive/code/api"),
+            Some(PathBuf::from("/Volumes/vibe-test-missing-drive"))
+        );
+
+        let table = "# <file system> <mount point> <type>\n\
+                     UUID=1234 / ext4 defaults 0 1\n\
+                     /dev/sdb1 /srv/My\\040Drive ext4 noauto 0 0\n\
+                     none swap swap sw 0 0\n";
+        assert_eq!(
+            mount_points(table),
+            vec![PathBuf::from("/"), PathBuf::from("/srv/My Drive")]
+        );
+    }
 }
@@ -1,7 +1,9 @@
-use anyhow::Result;
-use std::path::Path;
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
+use crate::utils::fs::{canonicalize, strip_verbatim_prefix};
+
 /// Check if git is available on the system
 pub fn is_git_available() -> bool {
     Command::new("git")
@@ -52,6 +54,106 @@ pub fn validate_git_repository<P: AsRef<Path>>(path: P) -> Result<()> {
     Ok(())
 }
 
+/// The repository a command was started in
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepositoryLocation {
+    /// Working directory of the primary checkout
+    pub root: PathBuf,
+    /// Git directory shared by the checkout and its linked worktrees
+    pub common_dir: PathBuf,
+    /// Top-level directory of the linked worktree the command started in
+    pub worktree: Option<PathBuf>,
+}
+
+/// Find the repository containing `start_dir`. From inside a linked
+/// worktree this resolves the `.git` file's `gitdir:` pointer back to the
+/// primary checkout; a submodule is treated as its own repository.
+pub fn locate_repository(start_dir: &Path) -> Result<RepositoryLocation> {
+    let start = strip_verbatim_prefix(start_dir);
+    for dir in start.ancestors() {
+        let dot_git = dir.join(".git");
+        if dot_git.is_dir() {
+            return Ok(RepositoryLocation {
+                root: dir.to_path_buf(),
+                common_dir: dot_git,
+                worktree: None,
+            });
+        }
+        if dot_git.is_file() {
+            return locate_from_git_file(dir, &dot_git);
+        }
+    }
+    anyhow::bail!("Not in a git repository")
+}
+
+fn locate_from_git_file(dir: &Path, dot_git: &Path) -> Result<RepositoryLocation> {
+    let contents = std::fs::read_to_string(dot_git)
+        .with_context(|| format!("Failed to read {}", dot_git.display()))?;
+    let git_dir = contents
+        .lines()
+        .find_map(|line| line.strip_prefix("gitdir:"))
+        .map(str::trim)
+        .with_context(|| format!("No gitdir pointer in {}", dot_git.display()))?;
+    let git_dir = resolve(&dir.join(git_dir));
+
+    // Linked worktrees have `<common>/worktrees/<name>` as their git dir,
+    // with a `commondir` file pointing back at `<common>`
+    let Ok(common) = std::fs::read_to_string(git_dir.join("commondir")) else {
+        // A submodule or separate git dir: a repository rooted here
+        return Ok(RepositoryLocation {
+            root: dir.to_path_buf(),
+            common_dir: git_dir,
+            worktree: None,
+        });
+    };
+
+    let common_dir = resolve(&git_dir.join(common.trim()));
+    let root = primary_checkout(&common_dir).unwrap_or_else(|| dir.to_path_buf());
+    Ok(RepositoryLocation {
+        root,
+        common_dir,
+        worktree: Some(dir.to_path_buf()),
+    })
+}
+
+/// Working directory that owns `common_dir`, or `None` for a bare repository
+fn primary_checkout(common_dir: &Path) -> Option<PathBuf> {
+    // Submodules keep their git dir under the superproject and record
+    // their working directory as core.worktree
+    if let Some(worktree) = read_core_worktree(&common_dir.join("config")) {
+        return Some(resolve(&common_dir.join(worktree)));
+    }
+    if common_dir.file_name()? == ".git" {
+        return common_dir.parent().map(Path::to_path_buf);
+    }
+    None
+}
+
+fn read_core_worktree(config: &Path) -> Option<String> {
+    let contents = std::fs::read_to_string(config).ok()?;
+    let mut in_core = false;
+    for line in contents.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_core = line.eq_ignore_ascii_case("[core]");
+            continue;
+        }
+        if !in_core {
+            continue;
+        }
+        if let Some((key, value)) = line.split_once('=') {
+            if key.trim().eq_ignore_ascii_case("worktree") {
+                return Some(value.trim().trim_matches('"').to_string());
+            }
+        }
+    }
+    None
+}
+
+/// Collapse `..` segments, keeping the path as-is if it doesn't exist
+fn resolve(path: &Path) -> PathBuf {
+    canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
 /// Extract repository name from URL
 pub fn extract_repo_name_from_url(url: &str) -> Option<String> {
     // Handle both SSH and HTTPS URLs
@@ -66,6 +168,19 @@ pub fn extract_repo_name_from_url(url: &str) -> Option<String> {
     None
 }
 
+/// Host of a remote URL: `git@host:org/repo`, `ssh://git@host/org/repo`, or
+/// `https://host/org/repo`. None for `org/repo` shorthands.
+pub fn extract_host_from_url(url: &str) -> Option<String> {
+    let url = url.trim();
+    if let Ok(parsed) = url::Url::parse(url) {
+        return parsed.host_str().map(str::to_string);
+    }
+    // scp-like syntax: [user@]host:path
+    let (host, _) = url.split_once(':')?;
+    let host = host.rsplit('@').next()?;
+    (!host.is_empty() && !host.contains('/')).then(|| host.to_string())
+}
+
 /// Normalize git URL to HTTPS format
 pub fn normalize_git_url(url: &str) -> String {
     if url.starts_with("git@github.com:") {
@@ -86,6 +201,23 @@ pub fn normalize_git_url(url: &str) -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_extract_host_from_url() {
+        assert_eq!(
+            extract_host_from_url("https://github.com/user/repo.git").as_deref(),
+            Some("github.com")
+        );
+        assert_eq!(
+            extract_host_from_url("git@gitlab.example.com:team/repo.git").as_deref(),
+            Some("gitlab.example.com")
+        );
+        assert_eq!(
+            extract_host_from_url("ssh://git@git.example.com/team/repo").as_deref(),
+            Some("git.example.com")
+        );
+        assert_eq!(extract_host_from_url("user/repo"), None);
+    }
+
     #[test]
     fn test_extract_repo_name_from_url() {
         assert_eq!(
@@ -131,4 +263,141 @@ mod tests {
             "https://gitlab.com/user/repo.git"
         );
     }
+
+    fn git(dir: &Path, args: &[&str]) {
+        let output = Command::new("git")
+            .args([
+                "-c",
+                "user.name=Test User",
+                "-c",
+                "user.email=test@example.com",
+                "-c",
+                "commit.gpgsign=false",
+                "-c",
+                "protocol.file.allow=always",
+            ])
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "git {args:?} failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    fn init_repo(dir: &Path) -> PathBuf {
+        std::fs::create_dir_all(dir).unwrap();
+        git(dir, &["init", "--quiet"]);
+        git(
+            dir,
+            &["commit", "--quiet", "--allow-empty", "-m", "Initial"],
+        );
+        canonicalize(dir).unwrap()
+    }
+
+    #[test]
+    fn test_locate_repository_from_main_checkout() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let repo = init_repo(&temp_dir.path().join("repo"));
+        std::fs::create_dir_all(repo.join("src/nested")).unwrap();
+
+        let location = locate_repository(&repo.join("src/nested")).unwrap();
+        assert_eq!(location.root, repo);
+        assert_eq!(location.common_dir, repo.join(".git"));
+        assert_eq!(location.worktree, None);
+    }
+
+    #[test]
+    fn test_locate_repository_from_linked_worktree() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let repo = init_repo(&temp_dir.path().join("repo"));
+        git(
+            &repo,
+            &[
+                "worktree",
+                "add",
+                "--quiet",
+                "-b",
+                "feature",
+                ".worktrees/feature",
+            ],
+        );
+        let worktree = repo.join(".worktrees/feature");
+        std::fs::create_dir_all(worktree.join("src")).unwrap();
+
+        let location = locate_repository(&worktree.join("src")).unwrap();
+        assert_eq!(location.root, repo);
+        assert_eq!(location.common_dir, repo.join(".git"));
+        assert_eq!(location.worktree, Some(worktree));
+    }
+
+    #[test]
+    fn test_locate_repository_from_submodule() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let library = init_repo(&temp_dir.path().join("library"));
+        let repo = init_repo(&temp_dir.path().join("repo"));
+        git(
+            &repo,
+            &[
+                "submodule",
+                "add",
+                "--quiet",
+                library.to_str().unwrap(),
+                "vendor/library",
+            ],
+        );
+        let submodule = repo.join("vendor/library");
+
+        let location = locate_repository(&submodule).unwrap();
+        assert_eq!(location.root, submodule);
+        assert_eq!(
+            location.common_dir,
+            repo.join(".git/modules/vendor/library")
+        );
+        assert_eq!(location.worktree, None);
+    }
+
+    #[test]
+    fn test_locate_repository_from_worktree_of_submodule() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let library = init_repo(&temp_dir.path().join("library"));
+        let repo = init_repo(&temp_dir.path().join("repo"));
+        git(
+            &repo,
+            &[
+                "submodule",
+                "add",
+                "--quiet",
+                library.to_str().unwrap(),
+                "library",
+            ],
+        );
+        let submodule = repo.join("library");
+        git(
+            &submodule,
+            &["worktree", "add", "--quiet", "-b", "fix", "../library-fix"],
+        );
+        let worktree = repo.join("library-fix");
+
+        let location = locate_repository(&worktree).unwrap();
+        assert_eq!(location.root, submodule);
+        assert_eq!(location.worktree, Some(worktree));
+    }
+
+    #[test]
+    fn test_read_core_worktree() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config = temp_dir.path().join("config");
+        std::fs::write(
+            &config,
+            "[core]\n\tbare = false\n\tworktree = ../../../lib\n[remote \"origin\"]\n\tworktree = nope\n",
+        )
+        .unwrap();
+        assert_eq!(read_core_worktree(&config).as_deref(), Some("../../../lib"));
+
+        std::fs::write(&config, "[remote \"origin\"]\n\tworktree = nope\n").unwrap();
+        assert_eq!(read_core_worktree(&config), None);
+    }
 }
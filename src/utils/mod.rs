@@ -1,3 +1,5 @@
+pub mod completions;
+pub mod direnv;
 pub mod fs;
 pub mod git;
 pub mod platform;
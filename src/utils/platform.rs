@@ -1,3 +1,4 @@
+use std::ffi::OsString;
 use std::path::PathBuf;
 
 /// Supported operating system platforms
@@ -124,9 +125,42 @@ fn get_warp_config_dir(platform: &Platform) -> Option<PathBuf> {
     }
 }
 
+/// Resolve a command name to something `Command::new` can spawn. Editors
+/// install `.cmd` shims on Windows (`code.cmd`, `cursor.cmd`) that process
+/// spawning won't find from the bare name, so look them up with `where`.
+pub fn resolve_command(name: &str) -> OsString {
+    if !cfg!(windows) || std::path::Path::new(name).extension().is_some() {
+        return name.into();
+    }
+
+    std::process::Command::new("where")
+        .arg(name)
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| pick_windows_executable(&String::from_utf8_lossy(&output.stdout)))
+        .map(OsString::from)
+        .unwrap_or_else(|| name.into())
+}
+
+/// The first `where` result that can be spawned directly: extensionless
+/// matches (e.g. shell scripts next to the shim) are skipped
+fn pick_windows_executable(where_output: &str) -> Option<String> {
+    where_output
+        .lines()
+        .map(str::trim)
+        .find(|line| {
+            let line = line.to_ascii_lowercase();
+            [".exe", ".cmd", ".bat", ".com"]
+                .iter()
+                .any(|ext| line.ends_with(ext))
+        })
+        .map(str::to_string)
+}
+
 /// Check if a binary is available in PATH
 pub fn is_binary_available(binary_name: &str) -> bool {
-    std::process::Command::new(binary_name)
+    std::process::Command::new(resolve_command(binary_name))
         .arg("--version")
         .output()
         .map(|output| output.status.success())
@@ -234,6 +268,48 @@ pub fn open_uri(uri: &str) -> Result<(), std::io::Error> {
     Ok(())
 }
 
+/// Show a message in a desktop dialog, for processes launched without a terminal
+pub fn show_message_dialog(title: &str, message: &str) -> Result<(), std::io::Error> {
+    let status = match Platform::current() {
+        Platform::MacOS => {
+            let escape = |text: &str| text.replace('\\', "\\\\").replace('"', "\\\"");
+            std::process::Command::new("osascript")
+                .arg("-e")
+                .arg(format!(
+                    "display dialog \"{}\" with title \"{}\" buttons {{\"OK\"}} default button \"OK\"",
+                    escape(message),
+                    escape(title)
+                ))
+                .status()?
+        }
+        Platform::Linux => {
+            if is_binary_available("zenity") {
+                std::process::Command::new("zenity")
+                    .args(["--info", "--no-markup", "--title", title, "--text", message])
+                    .status()?
+            } else {
+                std::process::Command::new("notify-send")
+                    .args([title, message])
+                    .status()?
+            }
+        }
+        Platform::Windows | Platform::Unknown => {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                "Unsupported platform for message dialogs",
+            ));
+        }
+    };
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(std::io::Error::other(format!(
+            "Dialog exited with {status}"
+        )))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -283,4 +359,23 @@ mod tests {
         assert_eq!(shortcuts.command_palette, "CTRL-SHIFT-P");
         assert_eq!(shortcuts.ctrl_modifier, "CTRL");
     }
+
+    #[test]
+    fn test_pick_windows_executable_prefers_spawnable_shim() {
+        let output = "C:\\Program Files\\Microsoft VS Code\\bin\\code\r\n\
+                      C:\\Program Files\\Microsoft VS Code\\bin\\code.cmd\r\n";
+        assert_eq!(
+            pick_windows_executable(output).as_deref(),
+            Some("C:\\Program Files\\Microsoft VS Code\\bin\\code.cmd")
+        );
+        assert_eq!(pick_windows_executable("C:\\tools\\code\r\n"), None);
+    }
+
+    #[test]
+    fn test_resolve_command_keeps_names_off_windows() {
+        if !cfg!(windows) {
+            assert_eq!(resolve_command("code"), OsString::from("code"));
+        }
+        assert_eq!(resolve_command("code.cmd"), OsString::from("code.cmd"));
+    }
 }
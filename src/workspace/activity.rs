@@ -0,0 +1,656 @@
+//! Recent git activity across the workspace for `vibe activity`
+//!
+//! Commits on local branches inside the window are counted per day, along
+//! with merges, the authors involved, and branches created, which come from
+//! the first entry of each branch's reflog. Days are local calendar days.
+//! Summaries are kept in the repository cache, keyed by the repository's
+//! branch tips (HEAD's among them), the window, and the author filter, so a
+//! repeated run reads no history until a branch moves.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Local, NaiveDate, TimeZone, Utc};
+use console::style;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::BTreeSet;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tracing::warn;
+
+use crate::cache::{CachedActivity, RepositoryCache};
+use crate::display_println;
+use crate::output::theme::{self, Role};
+
+use super::operations::execute_git_command;
+
+/// Days covered unless `--days` says otherwise
+pub const DEFAULT_DAYS: u32 = 7;
+
+/// `--author` value that stands for the repository's `user.email`
+pub const AUTHOR_ME: &str = "me";
+
+const SPARK_LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Commits made on one day
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DayCount {
+    pub date: NaiveDate,
+    pub commits: usize,
+}
+
+/// Activity in one repository over the window
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RepoActivity {
+    pub repository: String,
+    pub commits: usize,
+    pub merges: usize,
+    /// Author names, sorted
+    pub authors: Vec<String>,
+    pub branches_created: Vec<String>,
+    /// One entry per day of the window, oldest first
+    pub daily: Vec<DayCount>,
+    /// Read from the cache instead of git
+    #[serde(default)]
+    pub cached: bool,
+}
+
+impl RepoActivity {
+    fn is_active(&self) -> bool {
+        self.commits > 0 || !self.branches_created.is_empty()
+    }
+}
+
+/// Activity summed over every repository
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ActivityTotals {
+    pub repositories: usize,
+    pub active_repositories: usize,
+    pub commits: usize,
+    pub merges: usize,
+    pub authors: Vec<String>,
+    pub branches_created: usize,
+    pub daily: Vec<DayCount>,
+}
+
+/// The full `vibe activity` result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActivityReport {
+    pub days: u32,
+    pub since: NaiveDate,
+    pub until: NaiveDate,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    /// Most commits first
+    pub repositories: Vec<RepoActivity>,
+    pub totals: ActivityTotals,
+}
+
+impl ActivityReport {
+    pub fn new(
+        mut repositories: Vec<RepoActivity>,
+        days: u32,
+        until: NaiveDate,
+        author: Option<String>,
+    ) -> Self {
+        let since = window_start(until, days);
+        let mut totals = ActivityTotals {
+            repositories: repositories.len(),
+            daily: empty_days(since, days),
+            ..Default::default()
+        };
+        let mut authors = BTreeSet::new();
+        for repo in &repositories {
+            totals.active_repositories += usize::from(repo.is_active());
+            totals.commits += repo.commits;
+            totals.merges += repo.merges;
+            totals.branches_created += repo.branches_created.len();
+            authors.extend(repo.authors.iter().cloned());
+            for (total, day) in totals.daily.iter_mut().zip(&repo.daily) {
+                total.commits += day.commits;
+            }
+        }
+        totals.authors = authors.into_iter().collect();
+        repositories.sort_by(|a, b| {
+            b.commits
+                .cmp(&a.commits)
+                .then_with(|| a.repository.cmp(&b.repository))
+        });
+
+        Self {
+            days,
+            since,
+            until,
+            author,
+            repositories,
+            totals,
+        }
+    }
+
+    pub fn print_table(&self) {
+        display_println!(
+            "{} Activity {} to {}{}",
+            style("📈").blue(),
+            self.since.format("%Y-%m-%d"),
+            self.until.format("%Y-%m-%d"),
+            self.author
+                .as_ref()
+                .map(|author| format!(" by {author}"))
+                .unwrap_or_default()
+        );
+        if self.repositories.is_empty() {
+            display_println!("{} No repositories found", style("ℹ").yellow());
+            return;
+        }
+
+        let peak = self
+            .repositories
+            .iter()
+            .flat_map(|r| &r.daily)
+            .map(|d| d.commits)
+            .max()
+            .unwrap_or(0);
+        let name_width = self
+            .repositories
+            .iter()
+            .map(|r| r.repository.len())
+            .max()
+            .unwrap_or(0)
+            .max("Repository".len());
+        let spark_width = (self.days as usize).max("Daily".len());
+
+        display_println!(
+            "{}",
+            style(format!(
+                "{:<name_width$}  {:<spark_width$}  {:>7}  {:>6}  {:>8}  Authors",
+                "Repository", "Daily", "Commits", "Merges", "Branches"
+            ))
+            .bold()
+        );
+        for repo in &self.repositories {
+            let row = format!(
+                "{:<name_width$}  {:<spark_width$}  {:>7}  {:>6}  {:>8}  {}",
+                repo.repository,
+                sparkline(&repo.daily, peak),
+                repo.commits,
+                repo.merges,
+                repo.branches_created.len(),
+                repo.authors.join(", ")
+            );
+            if repo.is_active() {
+                display_println!("{row}");
+            } else {
+                display_println!("{}", theme::paint(Role::Dim, row));
+            }
+        }
+
+        let totals = &self.totals;
+        let total_peak = totals.daily.iter().map(|d| d.commits).max().unwrap_or(0);
+        display_println!(
+            "{}",
+            style(format!(
+                "{:<name_width$}  {:<spark_width$}  {:>7}  {:>6}  {:>8}  {} {}, {} of {} repositories active",
+                "Total",
+                sparkline(&totals.daily, total_peak),
+                totals.commits,
+                totals.merges,
+                totals.branches_created,
+                totals.authors.len(),
+                if totals.authors.len() == 1 { "author" } else { "authors" },
+                totals.active_repositories,
+                totals.repositories
+            ))
+            .bold()
+        );
+    }
+}
+
+/// One bar per day, scaled so `peak` commits fill the cell; idle days are blank
+pub fn sparkline(daily: &[DayCount], peak: usize) -> String {
+    daily
+        .iter()
+        .map(|day| match day.commits {
+            0 => ' ',
+            commits => {
+                let level = (commits * SPARK_LEVELS.len()).div_ceil(peak.max(1));
+                SPARK_LEVELS[level.clamp(1, SPARK_LEVELS.len()) - 1]
+            }
+        })
+        .collect()
+}
+
+/// First day of a `days`-long window ending on `until`
+fn window_start(until: NaiveDate, days: u32) -> NaiveDate {
+    until - Duration::days(i64::from(days.max(1)) - 1)
+}
+
+fn empty_days(since: NaiveDate, days: u32) -> Vec<DayCount> {
+    (0..i64::from(days.max(1)))
+        .map(|offset| DayCount {
+            date: since + Duration::days(offset),
+            commits: 0,
+        })
+        .collect()
+}
+
+/// A commit line from `git log`
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CommitLine {
+    timestamp: i64,
+    author: String,
+    email: String,
+    parents: usize,
+}
+
+fn parse_commits(output: &str) -> Vec<CommitLine> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\0');
+            let timestamp = fields.next()?.parse().ok()?;
+            let author = fields.next()?.to_string();
+            let email = fields.next()?.to_string();
+            let parents = fields.next().unwrap_or_default().split_whitespace().count();
+            Some(CommitLine {
+                timestamp,
+                author,
+                email,
+                parents,
+            })
+        })
+        .collect()
+}
+
+/// When and by whom a branch was created, from the first line of its
+/// reflog: `<old> <new> <name> <<email>> <time> <tz>\t<message>`
+fn parse_branch_creation(first_line: &str) -> Option<(i64, String, String)> {
+    let (entry, message) = first_line.split_once('\t')?;
+    if !message.starts_with("branch: Created from") {
+        return None;
+    }
+    let mut fields = entry.rsplitn(3, ' ');
+    let _tz = fields.next()?;
+    let timestamp = fields.next()?.parse().ok()?;
+    let identity = fields.next()?.splitn(3, ' ').nth(2)?;
+    let (name, email) = identity.split_once(" <")?;
+    Some((
+        timestamp,
+        name.to_string(),
+        email.trim_end_matches('>').to_string(),
+    ))
+}
+
+/// Who `--author` keeps: the email for `me`, or a case-insensitive match
+/// against the name or email
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum AuthorFilter {
+    Email(String),
+    Matching(String),
+}
+
+impl AuthorFilter {
+    fn matches(&self, name: &str, email: &str) -> bool {
+        match self {
+            AuthorFilter::Email(wanted) => email.eq_ignore_ascii_case(wanted),
+            AuthorFilter::Matching(needle) => {
+                let needle = needle.to_lowercase();
+                name.to_lowercase().contains(&needle) || email.to_lowercase().contains(&needle)
+            }
+        }
+    }
+}
+
+/// Summarize `commits` and `created` branches into per-day counts over the
+/// window starting at `since`, dating timestamps with `to_date`
+fn summarize(
+    repository: String,
+    commits: &[CommitLine],
+    created: Vec<(String, i64)>,
+    since: NaiveDate,
+    days: u32,
+    to_date: impl Fn(i64) -> Option<NaiveDate>,
+) -> RepoActivity {
+    let mut daily = empty_days(since, days);
+    let mut authors = BTreeSet::new();
+    let mut counted = 0;
+    let mut merges = 0;
+    for commit in commits {
+        let Some(date) = to_date(commit.timestamp) else {
+            continue;
+        };
+        let Some(day) = daily.iter_mut().find(|day| day.date == date) else {
+            continue;
+        };
+        day.commits += 1;
+        counted += 1;
+        merges += usize::from(commit.parents > 1);
+        authors.insert(commit.author.clone());
+    }
+    let mut branches_created: Vec<String> = created
+        .into_iter()
+        .filter(|(_, timestamp)| {
+            to_date(*timestamp).is_some_and(|date| daily.iter().any(|day| day.date == date))
+        })
+        .map(|(branch, _)| branch)
+        .collect();
+    branches_created.sort();
+
+    RepoActivity {
+        repository,
+        commits: counted,
+        merges,
+        authors: authors.into_iter().collect(),
+        branches_created,
+        daily,
+        cached: false,
+    }
+}
+
+fn local_date(timestamp: i64) -> Option<NaiveDate> {
+    Local
+        .timestamp_opt(timestamp, 0)
+        .single()
+        .map(|time| time.date_naive())
+}
+
+/// Collect activity of `repos` at most `max_parallel` at a time, reusing
+/// cached summaries whose fingerprint still matches. Results keep the order
+/// of `repos`; unreadable repositories become warnings.
+pub async fn collect_activity(
+    repos: Vec<(String, PathBuf)>,
+    days: u32,
+    author: Option<String>,
+    cache: Option<&RepositoryCache>,
+    max_parallel: usize,
+    today: NaiveDate,
+) -> (Vec<RepoActivity>, Vec<String>) {
+    let semaphore = Arc::new(Semaphore::new(max_parallel.max(1)));
+    let mut handles = Vec::with_capacity(repos.len());
+
+    for (name, path) in repos {
+        let cached = match cache {
+            Some(cache) => cache.get_activity(&name).await.unwrap_or_else(|e| {
+                warn!("Failed to read cached activity for {}: {}", name, e);
+                None
+            }),
+            None => None,
+        };
+        let semaphore = Arc::clone(&semaphore);
+        let task_name = name.clone();
+        let author = author.clone();
+        let handle = tokio::spawn(async move {
+            let _permit = semaphore.acquire_owned().await.ok();
+            read_repo_activity(task_name, path, days, author, today, cached).await
+        });
+        handles.push((name, handle));
+    }
+
+    let mut activities = Vec::with_capacity(handles.len());
+    let mut warnings = Vec::new();
+    for (name, handle) in handles {
+        match handle.await {
+            Ok(Ok((activity, fresh))) => {
+                if let (Some(cache), Some(entry)) = (cache, fresh) {
+                    if let Err(e) = cache.cache_activity(&entry).await {
+                        warn!("Failed to cache activity for {}: {}", name, e);
+                    }
+                }
+                activities.push(activity);
+            }
+            Ok(Err(e)) => warnings.push(format!("Failed to read activity of {name}: {e}")),
+            Err(e) => warnings.push(format!("Activity task for {name} failed: {e}")),
+        }
+    }
+    (activities, warnings)
+}
+
+/// Read one repository's activity, returning the cache entry to store when
+/// the cached one was missing or stale
+async fn read_repo_activity(
+    name: String,
+    path: PathBuf,
+    days: u32,
+    author: Option<String>,
+    today: NaiveDate,
+    cached: Option<CachedActivity>,
+) -> Result<(RepoActivity, Option<CachedActivity>)> {
+    if !path.exists() {
+        anyhow::bail!("{} does not exist", path.display());
+    }
+
+    let filter = match author.as_deref() {
+        Some(AUTHOR_ME) => {
+            let email = execute_git_command(&path, &["config", "user.email"])
+                .await
+                .context("--author me needs git config user.email")?;
+            Some(AuthorFilter::Email(email))
+        }
+        Some(author) => Some(AuthorFilter::Matching(author.to_string())),
+        None => None,
+    };
+
+    let tips = execute_git_command(
+        &path,
+        &[
+            "for-each-ref",
+            "--format=%(objectname) %(refname)",
+            "refs/heads",
+        ],
+    )
+    .await?;
+    let since = window_start(today, days);
+    let fingerprint = {
+        let mut hasher = DefaultHasher::new();
+        (&tips, since, days, &filter).hash(&mut hasher);
+        format!("{:016x}", hasher.finish())
+    };
+
+    if let Some(activity) = cached
+        .filter(|c| c.fingerprint == fingerprint)
+        .and_then(|c| serde_json::from_str::<RepoActivity>(&c.activity_json).ok())
+    {
+        return Ok((
+            RepoActivity {
+                repository: name,
+                cached: true,
+                ..activity
+            },
+            None,
+        ));
+    }
+
+    let since_time: DateTime<Utc> = Local
+        .from_local_datetime(&since.and_hms_opt(0, 0, 0).unwrap_or_default())
+        .earliest()
+        .map(|time| time.with_timezone(&Utc))
+        .unwrap_or_else(|| since.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc());
+    let log = execute_git_command(
+        &path,
+        &[
+            "log",
+            "--branches",
+            &format!("--since={}", since_time.to_rfc3339()),
+            "--format=%ct%x00%an%x00%ae%x00%P",
+        ],
+    )
+    .await?;
+    let commits: Vec<CommitLine> = parse_commits(&log)
+        .into_iter()
+        .filter(|c| {
+            filter
+                .as_ref()
+                .is_none_or(|f| f.matches(&c.author, &c.email))
+        })
+        .collect();
+
+    let created = read_branch_creations(&path, &tips)
+        .await
+        .into_iter()
+        .filter(|(_, _, name, email)| filter.as_ref().is_none_or(|f| f.matches(name, email)))
+        .map(|(branch, timestamp, _, _)| (branch, timestamp))
+        .collect();
+
+    let activity = summarize(name.clone(), &commits, created, since, days, local_date);
+    let entry = CachedActivity {
+        name,
+        fingerprint,
+        activity_json: serde_json::to_string(&activity)?,
+        computed_at: Utc::now(),
+    };
+    Ok((activity, Some(entry)))
+}
+
+/// `(branch, created at, creator name, creator email)` for each local branch
+/// in `tips` whose reflog still starts at its creation
+async fn read_branch_creations(path: &Path, tips: &str) -> Vec<(String, i64, String, String)> {
+    let Ok(common_dir) = execute_git_command(path, &["rev-parse", "--git-common-dir"]).await else {
+        return Vec::new();
+    };
+    let common_dir = path.join(common_dir);
+
+    let mut created = Vec::new();
+    for refname in tips
+        .lines()
+        .filter_map(|line| line.split_once(' '))
+        .map(|(_, r)| r)
+    {
+        let Ok(reflog) = tokio::fs::read_to_string(common_dir.join("logs").join(refname)).await
+        else {
+            continue;
+        };
+        let Some((timestamp, name, email)) = reflog.lines().next().and_then(parse_branch_creation)
+        else {
+            continue;
+        };
+        let branch = refname.strip_prefix("refs/heads/").unwrap_or(refname);
+        created.push((branch.to_string(), timestamp, name, email));
+    }
+    created
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, day).unwrap()
+    }
+
+    fn utc_date(timestamp: i64) -> Option<NaiveDate> {
+        Utc.timestamp_opt(timestamp, 0)
+            .single()
+            .map(|t| t.date_naive())
+    }
+
+    fn timestamp(day: u32) -> i64 {
+        date(day)
+            .and_hms_opt(12, 0, 0)
+            .unwrap()
+            .and_utc()
+            .timestamp()
+    }
+
+    #[test]
+    fn test_parse_commits_and_summarize() {
+        let log = format!(
+            "{}\0Ada\0ada@example.com\0aaa\n{}\0Bob\0bob@example.com\0bbb ccc\n{}\0Ada\0ada@example.com\0ddd\n",
+            timestamp(9),
+            timestamp(9),
+            timestamp(3)
+        );
+        let commits = parse_commits(&log);
+        assert_eq!(commits.len(), 3);
+        assert_eq!(commits[1].parents, 2);
+
+        let created = vec![
+            ("feature/login".to_string(), timestamp(8)),
+            ("old".to_string(), timestamp(1)),
+        ];
+        let activity = summarize(
+            "api".to_string(),
+            &commits,
+            created,
+            window_start(date(10), 7),
+            7,
+            utc_date,
+        );
+        assert_eq!(activity.commits, 2);
+        assert_eq!(activity.merges, 1);
+        assert_eq!(activity.authors, ["Ada", "Bob"]);
+        assert_eq!(activity.branches_created, ["feature/login"]);
+        assert_eq!(activity.daily.len(), 7);
+        assert_eq!(activity.daily[0].date, date(4));
+        assert_eq!(activity.daily[5].commits, 2);
+    }
+
+    #[test]
+    fn test_parse_branch_creation() {
+        let line = "0000000000000000000000000000000000000000 1234abcd Ada Lovelace <ada@example.com> 1767225600 +0100\tbranch: Created from main";
+        assert_eq!(
+            parse_branch_creation(line),
+            Some((
+                1767225600,
+                "Ada Lovelace".to_string(),
+                "ada@example.com".to_string()
+            ))
+        );
+        let line = "1234abcd 5678ef90 Ada <ada@example.com> 1767225600 +0100\tcommit: fix";
+        assert_eq!(parse_branch_creation(line), None);
+    }
+
+    #[test]
+    fn test_author_filter() {
+        let me = AuthorFilter::Email("ada@example.com".to_string());
+        assert!(me.matches("Ada", "Ada@Example.com"));
+        assert!(!me.matches("Ada", "ada@work.example"));
+
+        let matching = AuthorFilter::Matching("lovelace".to_string());
+        assert!(matching.matches("Ada Lovelace", "ada@example.com"));
+        assert!(!matching.matches("Bob", "bob@example.com"));
+    }
+
+    #[test]
+    fn test_sparkline_and_totals() {
+        let daily = |counts: &[usize]| -> Vec<DayCount> {
+            counts
+                .iter()
+                .enumerate()
+                .map(|(i, &commits)| DayCount {
+                    date: date(i as u32 + 1),
+                    commits,
+                })
+                .collect()
+        };
+        assert_eq!(sparkline(&daily(&[0, 1, 4, 8]), 8), " ▁▄█");
+
+        let repo = |name: &str, counts: &[usize]| RepoActivity {
+            repository: name.to_string(),
+            commits: counts.iter().sum(),
+            merges: 0,
+            authors: vec![name.to_string()],
+            branches_created: Vec::new(),
+            daily: daily(counts),
+            cached: false,
+        };
+        let report = ActivityReport::new(
+            vec![repo("web", &[0, 0, 1]), repo("api", &[2, 0, 3])],
+            3,
+            date(3),
+            None,
+        );
+        assert_eq!(report.since, date(1));
+        assert_eq!(report.repositories[0].repository, "api");
+        assert_eq!(report.totals.commits, 6);
+        assert_eq!(report.totals.active_repositories, 2);
+        assert_eq!(
+            report
+                .totals
+                .daily
+                .iter()
+                .map(|d| d.commits)
+                .collect::<Vec<_>>(),
+            [2, 0, 4]
+        );
+    }
+}
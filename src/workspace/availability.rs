@@ -0,0 +1,113 @@
+//! A workspace root that is temporarily out of reach
+//!
+//! When the root lives on a network share or a removable volume that isn't
+//! mounted, every configured repository looks deleted. Instead of reporting
+//! each one missing, or offering to drop them all from the config, the
+//! workspace manager treats such a root as offline: sync, exec, and scan
+//! are refused and `vibe git status` shows the last cached statuses.
+//! `--force-online` skips the check for a root that really is gone.
+
+use std::path::{Path, PathBuf};
+
+use super::config::{is_outside_root, WorkspaceConfig};
+use crate::utils::fs::unavailable_mount;
+
+/// Configured repositories under a missing root before it counts as offline
+/// rather than deleted
+const MIN_REPOS_FOR_OFFLINE: usize = 2;
+
+/// The workspace root, found missing while repositories are configured under it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OfflineRoot {
+    pub root: PathBuf,
+    /// Configured repositories under the root
+    pub repos: usize,
+}
+
+impl OfflineRoot {
+    /// `Some` when the root of `config` is missing, or on a volume that
+    /// isn't mounted, while several repositories are configured under it
+    pub fn detect(config: &WorkspaceConfig) -> Option<Self> {
+        let repos = config
+            .repositories
+            .iter()
+            .filter(|repo| !is_outside_root(config, repo))
+            .count();
+        if repos < MIN_REPOS_FOR_OFFLINE || root_reachable(&config.workspace.root) {
+            return None;
+        }
+        Some(Self {
+            root: config.workspace.root.clone(),
+            repos,
+        })
+    }
+
+    /// The one warning printed instead of an error per repository
+    pub fn warning(&self) -> String {
+        format!(
+            "Workspace root {} is unavailable, though {} repositories are configured under it. \
+             Showing cached status; sync, exec, and scan are disabled until it is mounted \
+             (use --force-online if it was deleted).",
+            self.root.display(),
+            self.repos
+        )
+    }
+
+    /// Error for an `operation` that can't run while the root is offline
+    pub fn refuse(&self, operation: &'static str) -> WorkspaceOfflineError {
+        WorkspaceOfflineError {
+            root: self.root.clone(),
+            operation,
+        }
+    }
+}
+
+/// Returned by commands that would act on repositories under an offline root
+#[derive(Debug, thiserror::Error)]
+#[error(
+    "Workspace root {} is unavailable, so {operation} is disabled. Mount it, or use --force-online if it was deleted",
+    root.display()
+)]
+pub struct WorkspaceOfflineError {
+    pub root: PathBuf,
+    pub operation: &'static str,
+}
+
+/// An empty root only counts as unreachable when it is a mount point; a
+/// plain empty directory is a workspace waiting for `scan --restore`
+fn root_reachable(root: &Path) -> bool {
+    root.is_dir() && unavailable_mount(root.join(".")).is_none()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workspace::config::Repository;
+    use tempfile::TempDir;
+
+    fn config(root: PathBuf, repos: &[&str]) -> WorkspaceConfig {
+        let mut config = WorkspaceConfig::default();
+        config.workspace.root = root;
+        for name in repos {
+            config.add_repository(Repository::new(*name, *name));
+        }
+        config
+    }
+
+    #[test]
+    fn test_missing_root_with_repositories_is_offline() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().join("nas");
+
+        let offline = OfflineRoot::detect(&config(root.clone(), &["api", "web"])).unwrap();
+        assert_eq!(offline.repos, 2);
+
+        // A single repository is as likely to have been deleted
+        assert!(OfflineRoot::detect(&config(root.clone(), &["api"])).is_none());
+
+        // An empty directory outside the volume mount points is a workspace
+        // that hasn't been restored yet
+        std::fs::create_dir(&root).unwrap();
+        assert!(OfflineRoot::detect(&config(root, &["api", "web"])).is_none());
+    }
+}
@@ -0,0 +1,214 @@
+//! Passphrase encryption of backup archives
+//!
+//! An encrypted backup is the usual `.tgz` archive wrapped in the age format
+//! with a passphrase (scrypt) recipient. It is named `.tgz.age`, so encrypted
+//! backups can be recognized and listed without the passphrase; their
+//! contents are only looked at once the passphrase is known, after
+//! decrypting to a temporary file.
+
+use age::secrecy::SecretString;
+use age::{DecryptError, Decryptor, Encryptor};
+use anyhow::{anyhow, bail, Context, Result};
+use inquire::{Password, PasswordDisplayMode};
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use tempfile::NamedTempFile;
+
+/// Environment variable holding the backup passphrase, checked before asking
+pub const PASSPHRASE_ENV: &str = "VIBE_BACKUP_PASSPHRASE";
+
+/// File name suffix of plain backups
+pub const PLAIN_SUFFIX: &str = ".tgz";
+
+/// File name suffix of encrypted backups
+pub const ENCRYPTED_SUFFIX: &str = ".tgz.age";
+
+#[derive(Debug, thiserror::Error)]
+pub enum BackupCryptoError {
+    #[error("Wrong passphrase for {}", .0.display())]
+    WrongPassphrase(PathBuf),
+
+    #[error(
+        "{} is encrypted. Set {PASSPHRASE_ENV} or run in a terminal to enter the passphrase",
+        .0.display()
+    )]
+    PassphraseRequired(PathBuf),
+
+    #[error("The backup passphrase can't be empty")]
+    EmptyPassphrase,
+}
+
+/// Whether `path` names an encrypted backup
+pub fn is_encrypted(path: &Path) -> bool {
+    path.file_name()
+        .is_some_and(|name| name.to_string_lossy().ends_with(ENCRYPTED_SUFFIX))
+}
+
+/// Whether `path` names a backup archive, encrypted or not
+pub fn is_backup(path: &Path) -> bool {
+    path.file_name().is_some_and(|name| {
+        let name = name.to_string_lossy();
+        name.ends_with(PLAIN_SUFFIX) || name.ends_with(ENCRYPTED_SUFFIX)
+    })
+}
+
+/// A backup file name without its archive suffix
+pub fn backup_stem(file_name: &str) -> &str {
+    file_name
+        .strip_suffix(ENCRYPTED_SUFFIX)
+        .or_else(|| file_name.strip_suffix(PLAIN_SUFFIX))
+        .unwrap_or(file_name)
+}
+
+fn passphrase_from_env() -> Option<SecretString> {
+    std::env::var(PASSPHRASE_ENV)
+        .ok()
+        .filter(|passphrase| !passphrase.is_empty())
+        .map(SecretString::from)
+}
+
+/// Passphrase for a new encrypted backup, from [`PASSPHRASE_ENV`] or asked
+/// twice in the terminal
+pub fn passphrase_for_encryption() -> Result<SecretString> {
+    if let Some(passphrase) = passphrase_from_env() {
+        return Ok(passphrase);
+    }
+    if !io::stdin().is_terminal() {
+        bail!("Set {PASSPHRASE_ENV} to encrypt a backup without a terminal");
+    }
+    let passphrase = Password::new("Backup passphrase:")
+        .with_display_mode(PasswordDisplayMode::Masked)
+        .with_custom_confirmation_message("Repeat the passphrase:")
+        .with_custom_confirmation_error_message("The passphrases don't match")
+        .prompt()?;
+    if passphrase.is_empty() {
+        return Err(BackupCryptoError::EmptyPassphrase.into());
+    }
+    Ok(SecretString::from(passphrase))
+}
+
+/// Passphrase of the encrypted backup at `path`, from [`PASSPHRASE_ENV`] or
+/// asked in the terminal
+pub fn passphrase_for_decryption(path: &Path) -> Result<SecretString> {
+    if let Some(passphrase) = passphrase_from_env() {
+        return Ok(passphrase);
+    }
+    if !io::stdin().is_terminal() {
+        return Err(BackupCryptoError::PassphraseRequired(path.to_path_buf()).into());
+    }
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let passphrase = Password::new(&format!("Passphrase for {file_name}:"))
+        .with_display_mode(PasswordDisplayMode::Masked)
+        .without_confirmation()
+        .prompt()?;
+    Ok(SecretString::from(passphrase))
+}
+
+/// Encrypt the archive at `src` into `dst`
+pub async fn encrypt_file(src: &Path, dst: &Path, passphrase: SecretString) -> Result<()> {
+    let (src, dst) = (src.to_path_buf(), dst.to_path_buf());
+    tokio::task::spawn_blocking(move || -> Result<()> {
+        let mut input =
+            File::open(&src).with_context(|| format!("Failed to open {}", src.display()))?;
+        let output =
+            File::create(&dst).with_context(|| format!("Failed to create {}", dst.display()))?;
+        let mut writer = Encryptor::with_user_passphrase(passphrase)
+            .wrap_output(BufWriter::new(output))
+            .context("Failed to start encrypting the backup")?;
+        io::copy(&mut input, &mut writer).context("Failed to encrypt the backup")?;
+        writer
+            .finish()
+            .and_then(|mut output| output.flush())
+            .context("Failed to finish encrypting the backup")?;
+        Ok(())
+    })
+    .await?
+}
+
+/// Decrypt the backup at `src` to a temporary archive, removed when the
+/// returned file is dropped
+pub async fn decrypt_to_temp(src: &Path, passphrase: SecretString) -> Result<NamedTempFile> {
+    let src = src.to_path_buf();
+    tokio::task::spawn_blocking(move || -> Result<NamedTempFile> {
+        let input =
+            File::open(&src).with_context(|| format!("Failed to open {}", src.display()))?;
+        let decryptor = Decryptor::new_buffered(BufReader::new(input))
+            .map_err(|e| anyhow!("{} is not an encrypted backup: {e}", src.display()))?;
+        if !decryptor.is_scrypt() {
+            bail!("{} is not encrypted with a passphrase", src.display());
+        }
+
+        let identity = age::scrypt::Identity::new(passphrase);
+        let mut reader = match decryptor.decrypt(std::iter::once(&identity as &dyn age::Identity)) {
+            Ok(reader) => reader,
+            Err(DecryptError::DecryptionFailed) => {
+                return Err(BackupCryptoError::WrongPassphrase(src).into())
+            }
+            Err(e) => bail!("Failed to decrypt {}: {e}", src.display()),
+        };
+
+        let mut archive = tempfile::Builder::new()
+            .prefix("vibe-restore-")
+            .suffix(PLAIN_SUFFIX)
+            .tempfile()
+            .context("Failed to create temporary file")?;
+        io::copy(&mut reader, archive.as_file_mut())
+            .with_context(|| format!("{} is damaged", src.display()))?;
+        Ok(archive)
+    })
+    .await?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_backup_names() {
+        assert!(is_encrypted(Path::new("/b/vibe-backup-1.tgz.age")));
+        assert!(!is_encrypted(Path::new("/b/vibe-backup-1.tgz")));
+        assert!(is_backup(Path::new("/b/vibe-backup-1.tgz")));
+        assert!(!is_backup(Path::new("/b/notes.txt")));
+        assert_eq!(backup_stem("vibe-backup-1.tgz.age"), "vibe-backup-1");
+        assert_eq!(backup_stem("vibe-backup-1.tgz"), "vibe-backup-1");
+    }
+
+    #[tokio::test]
+    async fn test_encrypt_round_trip_and_wrong_passphrase() {
+        let temp = TempDir::new().unwrap();
+        let plain = temp.path().join("backup.tgz");
+        let encrypted = temp.path().join("backup.tgz.age");
+        tokio::fs::write(&plain, b"archive bytes").await.unwrap();
+
+        encrypt_file(
+            &plain,
+            &encrypted,
+            SecretString::from("correct horse".to_string()),
+        )
+        .await
+        .unwrap();
+        assert_ne!(tokio::fs::read(&encrypted).await.unwrap(), b"archive bytes");
+
+        let decrypted =
+            decrypt_to_temp(&encrypted, SecretString::from("correct horse".to_string()))
+                .await
+                .unwrap();
+        assert_eq!(
+            tokio::fs::read(decrypted.path()).await.unwrap(),
+            b"archive bytes"
+        );
+
+        let error = decrypt_to_temp(&encrypted, SecretString::from("battery staple".to_string()))
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<BackupCryptoError>(),
+            Some(BackupCryptoError::WrongPassphrase(_))
+        ));
+    }
+}
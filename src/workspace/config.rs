@@ -1,9 +1,15 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Path, PathBuf};
 use tokio::fs;
 
+use crate::git::backend::GitBackendKind;
+use crate::git::{CloneLayout, CloneProtocol};
+use crate::output::theme::ThemePreferences;
+use crate::utils::fs::expand_tilde;
+use crate::workspace::events::HookEvent;
+use crate::workspace::health::HealthWeights;
 use crate::worktree::config::{
     WorktreeCleanupConfig, WorktreeConfig, WorktreeMergeDetectionConfig, WorktreeMode,
 };
@@ -20,6 +26,101 @@ pub struct WorkspaceConfig {
     pub claude_agents: Option<ClaudeAgentsIntegration>,
     #[serde(default)]
     pub worktree: WorktreeConfig,
+    /// Managed git hooks and commands run on vibe events
+    #[serde(default, skip_serializing_if = "HooksConfig::is_empty")]
+    pub hooks: HooksConfig,
+    /// Shared files synced into every repository by `vibe git files sync`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub managed_files: Vec<ManagedFile>,
+    /// Template packs to install, see [`crate::workspace::template_packs`]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub template_packs: Vec<TemplatePackSource>,
+    /// Safeguards for operations that destroy data
+    #[serde(default, skip_serializing_if = "SafetyConfig::is_default")]
+    pub safety: SafetyConfig,
+}
+
+/// The `safety` section
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SafetyConfig {
+    /// Save a branch under `refs/vibe/backup/` before vibe deletes it, see
+    /// [`crate::git::ref_backup`] (default: true)
+    #[serde(default = "default_true")]
+    pub ref_backups: bool,
+    /// Read-only git subcommands the `repo_git_query` MCP tool may run in
+    /// addition to [`crate::git::query::DEFAULT_SUBCOMMANDS`]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub git_query_allow: Vec<String>,
+}
+
+impl Default for SafetyConfig {
+    fn default() -> Self {
+        Self {
+            ref_backups: true,
+            git_query_allow: Vec::new(),
+        }
+    }
+}
+
+impl SafetyConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// The `hooks` section. Git hook entries sit directly under it, next to
+/// `events`, as they did before event hooks existed.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HooksConfig {
+    /// Shell command run on each event; see [`crate::workspace::events`]
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub events: BTreeMap<HookEvent, String>,
+    /// Seconds an event command may run before it is killed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event_timeout_secs: Option<u64>,
+    /// Managed git hooks: hook name (e.g. `pre-commit`) to a script path,
+    /// relative to the hooks directory unless absolute
+    #[serde(flatten)]
+    pub git: BTreeMap<String, PathBuf>,
+}
+
+impl HooksConfig {
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty() && self.event_timeout_secs.is_none() && self.git.is_empty()
+    }
+}
+
+/// A template pack recorded in the configuration, so a restored workspace
+/// installs it again
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TemplatePackSource {
+    pub name: String,
+    /// Git URL, `owner/repo` on GitHub, or local directory
+    pub source: String,
+}
+
+/// A shared file kept in sync across repositories
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManagedFile {
+    /// Source file, relative to the managed files directory unless absolute
+    pub source: PathBuf,
+    /// Destination relative to each repository's root
+    pub destination: PathBuf,
+    #[serde(default)]
+    pub strategy: ManagedFileStrategy,
+}
+
+/// How a managed file is written into a repository
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ManagedFileStrategy {
+    /// Replace the destination with the source
+    #[default]
+    Copy,
+    /// Keep the source in a delimited block inside the destination
+    AppendBlock,
+    /// Point the destination at the source file
+    Symlink,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -32,12 +133,76 @@ pub struct WorkspaceInfo {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Repository {
     pub name: String,
+    /// Relative to the workspace root, or absolute for a repository that
+    /// lives elsewhere; resolve it with [`repo_abs_path`]
     pub path: PathBuf,
     pub url: Option<String>,
+    /// URL of the repository this one was forked from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upstream: Option<String>,
+    /// SPDX identifier of the license reported by the hosting provider
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
     pub branch: Option<String>,
     pub apps: HashMap<String, AppConfig>,
     #[serde(default)]
     pub worktree_config: Option<RepositoryWorktreeConfig>,
+    /// Packages of a monorepo that can be opened on their own
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub subprojects: Vec<Subproject>,
+    /// Workspace-wide operations that skip this repository; naming it
+    /// explicitly still includes it
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<BulkOperation>,
+    /// Ref ahead/behind counts are taken against, such as `upstream/main`,
+    /// instead of the branch's counterpart on `origin`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compare_ref: Option<String>,
+}
+
+/// Operation over every repository (or a group) that a repository can opt
+/// out of with `exclude`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BulkOperation {
+    /// `vibe git sync`
+    Sync,
+    /// `vibe git exec`
+    Exec,
+    /// `vibe git status` and workspace-wide worktree listings
+    Status,
+}
+
+impl BulkOperation {
+    pub const ALL: [BulkOperation; 3] = [Self::Sync, Self::Exec, Self::Status];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Sync => "sync",
+            Self::Exec => "exec",
+            Self::Status => "status",
+        }
+    }
+}
+
+impl std::str::FromStr for BulkOperation {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|op| op.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| format!("unknown operation '{s}', expected sync, exec, or status"))
+    }
+}
+
+/// A package inside a monorepo, opened as `<repo>/<name>` or `<repo>:<name>`.
+/// Git operations still act on the whole repository.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Subproject {
+    pub name: String,
+    /// Relative to the repository
+    pub path: PathBuf,
 }
 
 /// Repository-specific worktree configuration overrides
@@ -60,6 +225,10 @@ pub struct RepositoryWorktreeConfig {
 
     /// Disable worktree management for this repository
     pub disabled: Option<bool>,
+
+    /// Replace the workspace's protected branch patterns for this repository
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub protected_branches: Option<Vec<String>>,
 }
 
 impl RepositoryWorktreeConfig {
@@ -83,6 +252,15 @@ impl RepositoryWorktreeConfig {
                 .clone()
                 .unwrap_or_else(|| global.merge_detection.clone()),
             status: global.status.clone(), // Always use global status settings
+            refresh_interval_secs: global.refresh_interval_secs,
+            protected_paths: global.protected_paths.clone(),
+            protected_branches: self
+                .protected_branches
+                .clone()
+                .unwrap_or_else(|| global.protected_branches.clone()),
+            check_github_protection: global.check_github_protection,
+            task: global.task.clone(),
+            refresh_base_before_create: global.refresh_base_before_create,
         }
     }
 
@@ -101,6 +279,7 @@ impl Default for RepositoryWorktreeConfig {
             cleanup: None,
             merge_detection: None,
             disabled: None,
+            protected_branches: None,
         }
     }
 }
@@ -114,16 +293,40 @@ pub enum AppConfig {
     },
     WithConfig {
         template: String,
+        /// App-specific settings. Values under keys like `*_token` or
+        /// `*_secret` are masked when the configuration is shown.
         #[serde(default)]
         config: serde_json::Value,
     },
 }
 
+/// Prefix of groups imported from an owner/organization directory during
+/// setup. Reserved so manually created groups never collide with them.
+pub const ORG_GROUP_PREFIX: &str = "org:";
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RepositoryGroup {
     pub name: String,
     pub repos: Vec<String>,
     pub apps: HashMap<String, AppIntegration>,
+    /// App that opens member repositories without an app of their own,
+    /// ahead of the global default app
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_app: Option<String>,
+    /// Template used with `default_app`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_template: Option<String>,
+}
+
+/// The default app a repository gets from its groups
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupDefaults {
+    /// Group the defaults come from
+    pub group: String,
+    pub app: String,
+    pub template: Option<String>,
+    /// Later groups of the repository whose different defaults lost out
+    pub overridden: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -212,10 +415,219 @@ pub struct ClaudeAgentsIntegration {
     pub target_path: PathBuf,
 }
 
+impl ClaudeAgentsIntegration {
+    /// The integration with the default source and target paths
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            source_path: default_claude_agents_source_path(),
+            target_path: default_claude_agents_target_path(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct Preferences {
     #[serde(default)]
     pub page_sizes: PageSizes,
+    #[serde(default)]
+    pub logging: LoggingPreferences,
+    #[serde(default)]
+    pub confirmations: ConfirmationLevel,
+    #[serde(default)]
+    pub theme: ThemePreferences,
+    /// Maximum repositories read concurrently for status (default: CPU count)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_parallel_git: Option<usize>,
+    /// How git is queried for status and worktree information
+    #[serde(default)]
+    pub git_backend: GitBackendKind,
+    /// Where clones land: flat, owner, or host-owner (default: owner)
+    #[serde(default)]
+    pub clone_layout: CloneLayout,
+    /// Clone configured remotes over https or ssh, rewriting URLs recorded
+    /// with the other protocol (default: as recorded)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub clone_protocol: Option<CloneProtocol>,
+    /// Pull Git LFS content after cloning when the clone left pointer files
+    #[serde(default)]
+    pub lfs_auto_pull: bool,
+    /// Load `.envrc` files through `direnv exec` in launched terminals and editors
+    #[serde(default)]
+    pub enable_direnv: bool,
+    /// Run `direnv allow` in new worktrees that contain an `.envrc`
+    #[serde(default)]
+    pub auto_allow_direnv: bool,
+    /// Fetch a repository in the background when opening it
+    #[serde(default)]
+    pub fetch_on_open: bool,
+    /// Reload config.yaml in the menu and MCP server when it changes on disk
+    #[serde(default)]
+    pub watch_config: bool,
+    /// SPDX identifiers `vibe scan licenses` flags; `GPL` also covers `GPL-3.0-only`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub denied_licenses: Vec<String>,
+    /// Hosts besides github.com that `vibe://clone` links may clone from
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub clone_link_hosts: Vec<String>,
+    /// Open the most launched app when `vibe open` finds several configured (default: true)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prefer_most_used_app: Option<bool>,
+    /// Remember search results and clones for `vibe suggest` (default: true)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repo_suggestions: Option<bool>,
+    /// Show repository, status, worktree, and pull request counts above the
+    /// main menu (default: true)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub menu_header: Option<bool>,
+    /// Lines of the highlighted repository's preview in the open picker;
+    /// 0 hides it (default: 5)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub picker_preview_lines: Option<usize>,
+    #[serde(default)]
+    pub sync: SyncPreferences,
+    /// How `vibe git status` buckets repositories when `--group-by` isn't
+    /// given (default: org)
+    #[serde(default)]
+    pub status_group_by: StatusGroupBy,
+    /// How much each problem costs in `vibe repo health`
+    #[serde(default)]
+    pub health_weights: HealthWeights,
+    /// Days removed repositories and worktrees stay in the trash (default: 30)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trash_retention_days: Option<u32>,
+    /// Seconds sync, exec, and worktree changes wait for another vibe
+    /// process's lock on a repository before skipping it (default: 10)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lock_timeout_secs: Option<u64>,
+    /// Other names and emails of commit authors, merged by `vibe stats contributors`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub author_aliases: Vec<AuthorAlias>,
+}
+
+/// One person's identity for `vibe stats contributors`, in `.mailmap` terms:
+/// commits by any of `aliases` count for `author`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuthorAlias {
+    /// `Name <email>` the commits are reported under
+    pub author: String,
+    /// Names, emails, or `Name <email>` identities that are the same person
+    #[serde(default)]
+    pub aliases: Vec<String>,
+}
+
+/// Settings for `vibe config sync`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SyncPreferences {
+    /// Pull the shared configuration when the menu starts
+    #[serde(default)]
+    pub auto_pull_on_start: bool,
+}
+
+/// How much confirmation destructive operations ask for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfirmationLevel {
+    /// Ask twice before any destructive operation
+    Strict,
+    /// Ask once; factory reset also requires typing a phrase
+    #[default]
+    Normal,
+    /// Skip prompts, except the typed phrase for factory reset
+    Minimal,
+}
+
+/// How `vibe git status` buckets repositories
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StatusGroupBy {
+    /// Parent directory under the workspace root
+    Dir,
+    /// Configured groups; a repository in several groups is listed in each
+    Group,
+    /// Owner in the remote URL
+    #[default]
+    Org,
+    /// Primary language from cached provider metadata
+    Language,
+    /// One flat list
+    None,
+}
+
+impl StatusGroupBy {
+    pub const ALL: [StatusGroupBy; 5] = [
+        Self::Dir,
+        Self::Group,
+        Self::Org,
+        Self::Language,
+        Self::None,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Dir => "dir",
+            Self::Group => "group",
+            Self::Org => "org",
+            Self::Language => "language",
+            Self::None => "none",
+        }
+    }
+}
+
+impl std::str::FromStr for StatusGroupBy {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|group_by| group_by.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| {
+                format!("unknown grouping '{s}', expected dir, group, org, language, or none")
+            })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoggingPreferences {
+    /// Write DEBUG-level logs to a daily rotating file in the logs directory
+    #[serde(default)]
+    pub file_enabled: bool,
+    /// How many daily log files to keep
+    #[serde(default = "default_log_max_files")]
+    pub max_files: usize,
+}
+
+impl Default for LoggingPreferences {
+    fn default() -> Self {
+        Self {
+            file_enabled: false,
+            max_files: default_log_max_files(),
+        }
+    }
+}
+
+impl LoggingPreferences {
+    /// Read only the logging preferences from a config file. This runs before
+    /// the output system is initialized, so any problem yields the defaults
+    /// and is reported later by the regular config loading.
+    pub fn read_from_config_file(path: &Path) -> Self {
+        #[derive(Deserialize)]
+        struct PreferencesOnly {
+            #[serde(default)]
+            preferences: Option<Preferences>,
+        }
+
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|contents| serde_yaml::from_str::<PreferencesOnly>(&contents).ok())
+            .and_then(|config| config.preferences)
+            .map(|preferences| preferences.logging)
+            .unwrap_or_default()
+    }
+}
+
+fn default_log_max_files() -> usize {
+    7
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -247,7 +659,7 @@ pub enum AppIntegration {
 
 impl Default for WorkspaceConfig {
     fn default() -> Self {
-        let vibe_dir = super::constants::get_config_dir();
+        let templates_dir = super::constants::get_templates_dir();
 
         Self {
             workspace: WorkspaceInfo {
@@ -268,7 +680,7 @@ impl Default for WorkspaceConfig {
                         .unwrap_or_default()
                         .join(".warp")
                         .join("launch_configurations"),
-                    template_dir: vibe_dir.join("templates").join("warp"),
+                    template_dir: templates_dir.join("warp"),
                     default_template: "default".to_string(),
                 }),
                 iterm2: Some(ITerm2Integration {
@@ -279,7 +691,7 @@ impl Default for WorkspaceConfig {
                         .join("Application Support")
                         .join("iTerm2")
                         .join("DynamicProfiles"),
-                    template_dir: vibe_dir.join("templates").join("iterm2"),
+                    template_dir: templates_dir.join("iterm2"),
                     default_template: "default".to_string(),
                 }),
                 wezterm: Some(WezTermIntegration {
@@ -287,7 +699,7 @@ impl Default for WorkspaceConfig {
                     config_dir: dirs::config_dir()
                         .unwrap_or_else(|| dirs::home_dir().unwrap_or_default().join(".config"))
                         .join("wezterm"),
-                    template_dir: vibe_dir.join("templates").join("wezterm"),
+                    template_dir: templates_dir.join("wezterm"),
                     default_template: "default".to_string(),
                 }),
                 vscode: Some(VSCodeIntegration {
@@ -296,7 +708,7 @@ impl Default for WorkspaceConfig {
                         .unwrap_or_default()
                         .join(".vscode")
                         .join("workspaces"),
-                    template_dir: vibe_dir.join("templates").join("vscode"),
+                    template_dir: templates_dir.join("vscode"),
                     default_template: "default".to_string(),
                 }),
                 cursor: Some(CursorIntegration {
@@ -305,7 +717,7 @@ impl Default for WorkspaceConfig {
                         .unwrap_or_default()
                         .join(".cursor")
                         .join("workspaces"),
-                    template_dir: vibe_dir.join("templates").join("cursor"),
+                    template_dir: templates_dir.join("cursor"),
                     default_template: "default".to_string(),
                 }),
                 windsurf: Some(WindsurfIntegration {
@@ -314,7 +726,7 @@ impl Default for WorkspaceConfig {
                         .unwrap_or_default()
                         .join(".windsurf")
                         .join("workspaces"),
-                    template_dir: vibe_dir.join("templates").join("windsurf"),
+                    template_dir: templates_dir.join("windsurf"),
                     default_template: "default".to_string(),
                 }),
             },
@@ -328,6 +740,10 @@ impl Default for WorkspaceConfig {
                     .join("agents"),
             }),
             worktree: WorktreeConfig::default(),
+            hooks: HooksConfig::default(),
+            managed_files: Vec::new(),
+            template_packs: Vec::new(),
+            safety: SafetyConfig::default(),
         }
     }
 }
@@ -344,7 +760,7 @@ impl WorkspaceConfig {
             .await
             .with_context(|| format!("Failed to read config file: {}", path.display()))?;
 
-        let mut config: Self = serde_yaml::from_str(&contents)
+        let mut config = super::config_loader::parse_config(&contents)
             .with_context(|| format!("Failed to parse config file: {}", path.display()))?;
 
         // Ensure all app integrations are initialized
@@ -353,6 +769,30 @@ impl WorkspaceConfig {
         Ok(config)
     }
 
+    /// Check the settings that have a valid range: page sizes and the
+    /// worktree section
+    pub fn validate(&self) -> Result<()> {
+        if let Some(preferences) = &self.preferences {
+            preferences.page_sizes.validate()?;
+            preferences.health_weights.validate()?;
+        }
+        self.worktree.validate().map_err(anyhow::Error::msg)?;
+        Ok(())
+    }
+
+    /// Whether the menu and MCP server should reload the config file when it
+    /// changes
+    pub fn watch_config(&self) -> bool {
+        self.preferences.as_ref().is_some_and(|p| p.watch_config)
+    }
+
+    /// Whether the menu should pull the shared configuration on start
+    pub fn sync_auto_pull_on_start(&self) -> bool {
+        self.preferences
+            .as_ref()
+            .is_some_and(|p| p.sync.auto_pull_on_start)
+    }
+
     pub async fn save_to_file<P: AsRef<Path>>(&self, path: P) -> Result<()> {
         let path = path.as_ref();
 
@@ -376,6 +816,27 @@ impl WorkspaceConfig {
         self.repositories.iter().find(|repo| repo.name == name)
     }
 
+    /// The subproject `target` names, written `<repo>:<subproject>` or
+    /// `<repo>/<subproject>`. Repository names may themselves contain `/`,
+    /// so every split point is tried.
+    pub fn find_subproject(&self, target: &str) -> Option<(&Repository, &Subproject)> {
+        if let Some((repo, subproject)) = target.split_once(':') {
+            let repo = self.get_repository_flexible(repo)?;
+            return repo
+                .subprojects
+                .iter()
+                .find(|s| s.name == subproject)
+                .map(|s| (repo, s));
+        }
+        target.match_indices('/').find_map(|(index, _)| {
+            let repo = self.get_repository(&target[..index])?;
+            repo.subprojects
+                .iter()
+                .find(|s| s.name == target[index + 1..])
+                .map(|s| (repo, s))
+        })
+    }
+
     /// Get a repository by flexible name lookup (supports owner/repo format)
     pub fn get_repository_flexible(&self, name: &str) -> Option<&Repository> {
         // First try exact match
@@ -440,12 +901,55 @@ impl WorkspaceConfig {
         }
     }
 
+    /// The default app and template for `repo_name` (or the monorepo of a
+    /// subproject) from the first group, in declaration order, that sets one
+    pub fn group_defaults(&self, repo_name: &str) -> Option<GroupDefaults> {
+        let repo_name = self
+            .find_subproject(repo_name)
+            .map_or(repo_name, |(monorepo, _)| monorepo.name.as_str());
+        let mut groups = self.groups.iter().filter(|group| {
+            group.default_app.is_some() && group.repos.iter().any(|name| name == repo_name)
+        });
+
+        let first = groups.next()?;
+        let mut defaults = GroupDefaults {
+            group: first.name.clone(),
+            app: first.default_app.clone()?,
+            template: first.default_template.clone(),
+            overridden: Vec::new(),
+        };
+        defaults.overridden = groups
+            .filter(|group| {
+                group.default_app.as_ref() != Some(&defaults.app)
+                    || group.default_template != defaults.template
+            })
+            .map(|group| group.name.clone())
+            .collect();
+        Some(defaults)
+    }
+
     pub fn add_repository(&mut self, repo: Repository) {
         // Remove existing repository with same name if present
         self.repositories.retain(|r| r.name != repo.name);
         self.repositories.push(repo);
     }
 
+    /// Check that `name` can be used for a new, manually created group
+    pub fn validate_new_group_name(&self, name: &str) -> std::result::Result<(), String> {
+        if name.is_empty() {
+            return Err("Group name cannot be empty".to_string());
+        }
+        if name.starts_with(ORG_GROUP_PREFIX) {
+            return Err(format!(
+                "Group names starting with '{ORG_GROUP_PREFIX}' are reserved for imported organizations"
+            ));
+        }
+        if self.groups.iter().any(|g| g.name == name) {
+            return Err(format!("Group '{name}' already exists"));
+        }
+        Ok(())
+    }
+
     pub fn add_group(&mut self, group: RepositoryGroup) {
         // Remove existing group with same name if present
         self.groups.retain(|g| g.name != group.name);
@@ -455,7 +959,7 @@ impl WorkspaceConfig {
     /// Ensure all app integrations are properly initialized
     /// This method handles migration from older configurations that may not have all apps configured
     pub async fn ensure_app_integrations_initialized(&mut self) -> Result<()> {
-        let vibe_dir = super::constants::get_config_dir();
+        let templates_dir = super::constants::get_templates_dir();
 
         // Ensure WezTerm integration is initialized
         if self.apps.wezterm.is_none() {
@@ -464,7 +968,7 @@ impl WorkspaceConfig {
                 config_dir: dirs::config_dir()
                     .unwrap_or_else(|| dirs::home_dir().unwrap_or_default().join(".config"))
                     .join("wezterm"),
-                template_dir: vibe_dir.join("templates").join("wezterm"),
+                template_dir: templates_dir.join("wezterm"),
                 default_template: "default".to_string(),
             });
         }
@@ -477,7 +981,7 @@ impl WorkspaceConfig {
                     .unwrap_or_default()
                     .join(".warp")
                     .join("launch_configurations"),
-                template_dir: vibe_dir.join("templates").join("warp"),
+                template_dir: templates_dir.join("warp"),
                 default_template: "default".to_string(),
             });
         }
@@ -491,7 +995,7 @@ impl WorkspaceConfig {
                     .join("Application Support")
                     .join("iTerm2")
                     .join("DynamicProfiles"),
-                template_dir: vibe_dir.join("templates").join("iterm2"),
+                template_dir: templates_dir.join("iterm2"),
                 default_template: "default".to_string(),
             });
         }
@@ -503,7 +1007,7 @@ impl WorkspaceConfig {
                     .unwrap_or_default()
                     .join(".vscode")
                     .join("workspaces"),
-                template_dir: vibe_dir.join("templates").join("vscode"),
+                template_dir: templates_dir.join("vscode"),
                 default_template: "default".to_string(),
             });
         }
@@ -515,7 +1019,7 @@ impl WorkspaceConfig {
                     .unwrap_or_default()
                     .join(".cursor")
                     .join("workspaces"),
-                template_dir: vibe_dir.join("templates").join("cursor"),
+                template_dir: templates_dir.join("cursor"),
                 default_template: "default".to_string(),
             });
         }
@@ -527,7 +1031,7 @@ impl WorkspaceConfig {
                     .unwrap_or_default()
                     .join(".windsurf")
                     .join("workspaces"),
-                template_dir: vibe_dir.join("templates").join("windsurf"),
+                template_dir: templates_dir.join("windsurf"),
                 default_template: "default".to_string(),
             });
         }
@@ -579,9 +1083,14 @@ impl Repository {
             name: name.into(),
             path: path.into(),
             url: None,
+            upstream: None,
+            license: None,
             branch: None,
             apps: HashMap::new(),
             worktree_config: None,
+            subprojects: Vec::new(),
+            exclude: Vec::new(),
+            compare_ref: None,
         }
     }
 
@@ -590,6 +1099,11 @@ impl Repository {
         self
     }
 
+    pub fn with_upstream<S: Into<String>>(mut self, upstream: S) -> Self {
+        self.upstream = Some(upstream.into());
+        self
+    }
+
     pub fn with_branch<S: Into<String>>(mut self, branch: S) -> Self {
         self.branch = Some(branch.into());
         self
@@ -630,6 +1144,46 @@ impl Repository {
             _ => None,
         }
     }
+
+    /// Whether workspace-wide runs of `operation` skip this repository
+    pub fn is_excluded_from(&self, operation: BulkOperation) -> bool {
+        self.exclude.contains(&operation)
+    }
+
+    /// This repository narrowed to `subproject`: named `<repo>/<subproject>`,
+    /// with the subdirectory as its path and the repository's apps, so
+    /// launchers open at the subdirectory
+    pub fn subproject_view(&self, subproject: &Subproject) -> Repository {
+        Repository {
+            name: format!("{}/{}", self.name, subproject.name),
+            path: self.path.join(&subproject.path),
+            subprojects: Vec::new(),
+            ..self.clone()
+        }
+    }
+}
+
+/// Where `repo` lives on disk. `Repository.path` is relative to the workspace
+/// root, except for repositories that live elsewhere, which use an absolute
+/// (or `~/`) path.
+pub fn repo_abs_path(config: &WorkspaceConfig, repo: &Repository) -> PathBuf {
+    resolve_repo_path(&config.workspace.root, &repo.path)
+}
+
+/// [`repo_abs_path`] for callers that only have the root
+pub fn resolve_repo_path(root: &Path, path: &Path) -> PathBuf {
+    let path = expand_tilde(path);
+    if path.is_absolute() {
+        path
+    } else {
+        root.join(path)
+    }
+}
+
+/// Whether `repo` lives outside the workspace root, so operations that
+/// assume containment (scan restore and clean) leave it alone
+pub fn is_outside_root(config: &WorkspaceConfig, repo: &Repository) -> bool {
+    !repo_abs_path(config, repo).starts_with(&config.workspace.root)
 }
 
 impl AppConfig {
@@ -643,56 +1197,36 @@ impl AppConfig {
 }
 
 // Default functions for serde
+fn default_true() -> bool {
+    true
+}
+
 fn default_template_name() -> String {
     "default".to_string()
 }
 
 fn default_warp_template_dir() -> PathBuf {
-    dirs::home_dir()
-        .unwrap_or_default()
-        .join(super::constants::CONFIG_DIR_PATH)
-        .join("templates")
-        .join("warp")
+    super::constants::get_app_template_dir("warp")
 }
 
 fn default_iterm2_template_dir() -> PathBuf {
-    dirs::home_dir()
-        .unwrap_or_default()
-        .join(super::constants::CONFIG_DIR_PATH)
-        .join("templates")
-        .join("iterm2")
+    super::constants::get_app_template_dir("iterm2")
 }
 
 fn default_wezterm_template_dir() -> PathBuf {
-    dirs::home_dir()
-        .unwrap_or_default()
-        .join(super::constants::CONFIG_DIR_PATH)
-        .join("templates")
-        .join("wezterm")
+    super::constants::get_app_template_dir("wezterm")
 }
 
 fn default_vscode_template_dir() -> PathBuf {
-    dirs::home_dir()
-        .unwrap_or_default()
-        .join(super::constants::CONFIG_DIR_PATH)
-        .join("templates")
-        .join("vscode")
+    super::constants::get_app_template_dir("vscode")
 }
 
 fn default_cursor_template_dir() -> PathBuf {
-    dirs::home_dir()
-        .unwrap_or_default()
-        .join(super::constants::CONFIG_DIR_PATH)
-        .join("templates")
-        .join("cursor")
+    super::constants::get_app_template_dir("cursor")
 }
 
 fn default_windsurf_template_dir() -> PathBuf {
-    dirs::home_dir()
-        .unwrap_or_default()
-        .join(super::constants::CONFIG_DIR_PATH)
-        .join("templates")
-        .join("windsurf")
+    super::constants::get_app_template_dir("windsurf")
 }
 
 fn default_claude_agents_source_path() -> PathBuf {
@@ -774,3 +1308,59 @@ impl PageSizes {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn group(name: &str, repos: &[&str], app: Option<&str>) -> RepositoryGroup {
+        RepositoryGroup {
+            name: name.to_string(),
+            repos: repos.iter().map(|r| r.to_string()).collect(),
+            apps: HashMap::new(),
+            default_app: app.map(str::to_string),
+            default_template: None,
+        }
+    }
+
+    #[test]
+    fn test_group_defaults_follow_declaration_order() {
+        let config = WorkspaceConfig {
+            groups: vec![
+                group("all", &["api", "web"], None),
+                group("infra", &["api"], Some("warp")),
+                group("frontend", &["api", "web"], Some("cursor")),
+                group("editors", &["web"], Some("cursor")),
+            ],
+            ..Default::default()
+        };
+
+        let api = config.group_defaults("api").unwrap();
+        assert_eq!(api.group, "infra");
+        assert_eq!(api.app, "warp");
+        assert_eq!(api.overridden, ["frontend"]);
+
+        // Groups agreeing on the default don't conflict
+        let web = config.group_defaults("web").unwrap();
+        assert_eq!(web.group, "frontend");
+        assert!(web.overridden.is_empty());
+
+        assert!(config.group_defaults("docs").is_none());
+    }
+
+    #[test]
+    fn test_exclude_round_trips_and_is_omitted_when_empty() {
+        let repo: Repository = serde_yaml::from_str(
+            "name: mirror\npath: mirror\nurl: null\nbranch: null\napps: {}\nexclude: [sync, status]\n",
+        )
+        .unwrap();
+        assert!(repo.is_excluded_from(BulkOperation::Sync));
+        assert!(!repo.is_excluded_from(BulkOperation::Exec));
+        assert!(serde_yaml::to_string(&repo).unwrap().contains("- status"));
+
+        let plain = serde_yaml::to_string(&Repository::new("api", "api")).unwrap();
+        assert!(!plain.contains("exclude"));
+        assert_eq!("Exec".parse::<BulkOperation>(), Ok(BulkOperation::Exec));
+        assert!("fetch".parse::<BulkOperation>().is_err());
+    }
+}
@@ -0,0 +1,424 @@
+//! Loading `config.yaml` with errors that say where the problem is
+//!
+//! serde_yaml stops at the first bad value and reports it against the whole
+//! document ("invalid type: string, expected a sequence at line 42"). The
+//! loader parses the file to a generic YAML value first. When that value
+//! doesn't deserialize, each top-level section, and each repository, group,
+//! and managed file on its own, is deserialized again so every bad entry is
+//! reported with its name, the field inside it, and its line.
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_yaml::Value;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::PathBuf;
+
+use super::config::{
+    AppIntegrations, ClaudeAgentsIntegration, ManagedFile, Preferences, Repository,
+    RepositoryGroup, WorkspaceConfig, WorkspaceInfo,
+};
+use crate::worktree::config::WorktreeConfig;
+
+/// One problem found while loading the config
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ConfigFieldError {
+    /// The section or entry holding the bad value, e.g. `repository 'frontend'`
+    pub context: String,
+    /// Path to the bad value inside the entry, e.g. `apps.vscode`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub field: Option<String>,
+    pub message: String,
+    /// 1-based line in the config file, when it can be found
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+}
+
+impl fmt::Display for ConfigFieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: ", self.context)?;
+        if let Some(field) = &self.field {
+            write!(f, "field '{field}': ")?;
+        }
+        write!(f, "{}", self.message)?;
+        if let Some(line) = self.line {
+            write!(f, " (line {line})")?;
+        }
+        Ok(())
+    }
+}
+
+/// Every problem found in a config file that doesn't load
+#[derive(Debug, thiserror::Error)]
+pub struct ConfigLoadError {
+    pub errors: Vec<ConfigFieldError>,
+}
+
+impl fmt::Display for ConfigLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.errors.as_slice() {
+            [error] => write!(f, "{error}"),
+            errors => {
+                write!(f, "{} problems in config:", errors.len())?;
+                for error in errors {
+                    write!(f, "\n  - {error}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Parse the contents of a config file, collecting every bad entry
+pub fn parse_config(contents: &str) -> Result<WorkspaceConfig, ConfigLoadError> {
+    let value: Value = serde_yaml::from_str(contents).map_err(|e| {
+        let message = e.to_string();
+        ConfigLoadError {
+            errors: vec![ConfigFieldError {
+                context: "YAML syntax".to_string(),
+                field: None,
+                message: message
+                    .split(" at line ")
+                    .next()
+                    .unwrap_or(&message)
+                    .to_string(),
+                line: e.location().map(|location| location.line()),
+            }],
+        }
+    })?;
+
+    match serde_yaml::from_value::<WorkspaceConfig>(value.clone()) {
+        Ok(config) => Ok(config),
+        Err(e) => {
+            let mut errors = diagnose(contents, &value);
+            if errors.is_empty() {
+                errors.push(ConfigFieldError {
+                    context: "config".to_string(),
+                    field: None,
+                    message: e.to_string(),
+                    line: None,
+                });
+            }
+            Err(ConfigLoadError { errors })
+        }
+    }
+}
+
+/// Deserialize each section and entry on its own and report all failures
+fn diagnose(contents: &str, value: &Value) -> Vec<ConfigFieldError> {
+    let Some(sections) = value.as_mapping() else {
+        return vec![ConfigFieldError {
+            context: "config".to_string(),
+            field: None,
+            message: format!("expected mapping, found {}", kind(value)),
+            line: Some(1),
+        }];
+    };
+
+    let mut checker = Checker {
+        contents,
+        errors: Vec::new(),
+    };
+    for section in ["workspace", "repositories", "groups", "apps"] {
+        if !sections.contains_key(section) {
+            checker.errors.push(ConfigFieldError {
+                context: "config".to_string(),
+                field: None,
+                message: format!("missing section '{section}'"),
+                line: None,
+            });
+        }
+    }
+
+    let section = |name: &'static str| sections.get(name).map(|value| (name, value));
+    if let Some((name, value)) = section("workspace") {
+        checker.section::<WorkspaceInfo>(name, value);
+    }
+    if let Some((name, value)) = section("repositories") {
+        checker.entries::<Repository>(name, "repository", value);
+    }
+    if let Some((name, value)) = section("groups") {
+        checker.entries::<RepositoryGroup>(name, "group", value);
+    }
+    if let Some((name, value)) = section("apps") {
+        checker.section::<AppIntegrations>(name, value);
+    }
+    if let Some((name, value)) = section("preferences") {
+        checker.section::<Option<Preferences>>(name, value);
+    }
+    if let Some((name, value)) = section("claude_agents") {
+        checker.section::<Option<ClaudeAgentsIntegration>>(name, value);
+    }
+    if let Some((name, value)) = section("worktree") {
+        checker.section::<WorktreeConfig>(name, value);
+    }
+    if let Some((name, value)) = section("hooks") {
+        checker.section::<BTreeMap<String, PathBuf>>(name, value);
+    }
+    if let Some((name, value)) = section("managed_files") {
+        checker.entries::<ManagedFile>(name, "managed file", value);
+    }
+
+    checker.errors
+}
+
+struct Checker<'a> {
+    contents: &'a str,
+    errors: Vec<ConfigFieldError>,
+}
+
+impl Checker<'_> {
+    /// Deserialize a whole section
+    fn section<T: DeserializeOwned>(&mut self, name: &str, value: &Value) {
+        self.check::<T>(
+            format!("section '{name}'"),
+            vec![Step::Key(name.to_string())],
+            value,
+        );
+    }
+
+    /// Deserialize each entry of a list section on its own, naming entries
+    /// by their `name` field when they have one
+    fn entries<T: DeserializeOwned>(&mut self, name: &str, label: &str, value: &Value) {
+        let Some(entries) = value.as_sequence() else {
+            self.section::<Vec<T>>(name, value);
+            return;
+        };
+        for (index, entry) in entries.iter().enumerate() {
+            let context = match entry.get("name").and_then(Value::as_str) {
+                Some(entry_name) => format!("{label} '{entry_name}'"),
+                None => format!("{name}[{index}]"),
+            };
+            self.check::<T>(
+                context,
+                vec![Step::Key(name.to_string()), Step::Index(index)],
+                entry,
+            );
+        }
+    }
+
+    fn check<T: DeserializeOwned>(&mut self, context: String, mut steps: Vec<Step>, value: &Value) {
+        let Err(e) = serde_path_to_error::deserialize::<_, T>(value.clone()) else {
+            return;
+        };
+
+        let mut bad_value = Some(value);
+        let mut field = Vec::new();
+        for segment in e.path().iter() {
+            let step = match segment {
+                serde_path_to_error::Segment::Seq { index } => {
+                    field.push(format!("[{index}]"));
+                    Step::Index(*index)
+                }
+                serde_path_to_error::Segment::Map { key } => {
+                    field.push(format!(".{key}"));
+                    Step::Key(key.clone())
+                }
+                _ => break,
+            };
+            bad_value = bad_value.and_then(|value| match &step {
+                Step::Index(index) => value.get(*index),
+                Step::Key(key) => value.get(key.as_str()),
+            });
+            steps.push(step);
+        }
+        let field = field.concat();
+        let field = field.strip_prefix('.').unwrap_or(&field);
+
+        self.errors.push(ConfigFieldError {
+            context,
+            field: (!field.is_empty()).then(|| field.to_string()),
+            message: describe(&e.inner().to_string(), bad_value),
+            line: locate(self.contents, &steps),
+        });
+    }
+}
+
+/// Reword serde's messages as "expected X, found Y"
+fn describe(message: &str, value: Option<&Value>) -> String {
+    let noun = |expected: &str| match expected {
+        "a map" => "mapping".to_string(),
+        "a sequence" => "sequence".to_string(),
+        "a boolean" => "boolean".to_string(),
+        other => other.to_string(),
+    };
+
+    if let Some((found, expected)) = message
+        .strip_prefix("invalid type: ")
+        .and_then(|rest| rest.rsplit_once(", expected "))
+    {
+        return format!("expected {}, found {found}", noun(expected));
+    }
+    // Both untagged enums in the config accept `true`/`false` or a mapping
+    if message.starts_with("data did not match any variant of untagged enum") {
+        if let Some(value) = value {
+            return format!("expected boolean or mapping, found {}", kind(value));
+        }
+    }
+    message.to_string()
+}
+
+fn kind(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Sequence(_) => "sequence",
+        Value::Mapping(_) => "mapping",
+        Value::Tagged(_) => "tagged value",
+    }
+}
+
+enum Step {
+    Key(String),
+    Index(usize),
+}
+
+/// The line of the value at `steps` in block-style YAML, or of the deepest
+/// step that could be found. Flow-style collections (`{...}`, `[...]`) are
+/// not looked into.
+fn locate(contents: &str, steps: &[Step]) -> Option<usize> {
+    // (line number, indent, text after the indent)
+    let mut lines: Vec<(usize, usize, String)> = contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| {
+            let trimmed = line.trim();
+            !trimmed.is_empty() && !trimmed.starts_with('#') && trimmed != "---"
+        })
+        .map(|(number, line)| {
+            let text = line.trim_start();
+            (number + 1, line.len() - text.len(), text.to_string())
+        })
+        .collect();
+
+    let is_item = |text: &str| text == "-" || text.starts_with("- ");
+    let mut range = 0..lines.len();
+    let mut found = None;
+
+    for step in steps {
+        let Some(indent) = lines.get(range.start).map(|line| line.1) else {
+            break;
+        };
+        let mut siblings = range.clone().filter(|&i| lines[i].1 == indent);
+        let hit = match step {
+            Step::Key(key) => siblings.find(|&i| key_of(&lines[i].2) == Some(key.as_str())),
+            Step::Index(index) => siblings.filter(|&i| is_item(&lines[i].2)).nth(*index),
+        };
+        let Some(i) = hit else {
+            break;
+        };
+        found = Some(lines[i].0);
+
+        match step {
+            // A sequence under a key may sit at the key's own indent
+            Step::Key(_) => {
+                let end = (i + 1..range.end)
+                    .find(|&j| {
+                        lines[j].1 < indent || (lines[j].1 == indent && !is_item(&lines[j].2))
+                    })
+                    .unwrap_or(range.end);
+                range = i + 1..end;
+            }
+            // The item's first key shares the dash's line; treat it as
+            // indented past the dash
+            Step::Index(_) => {
+                let end = (i + 1..range.end)
+                    .find(|&j| lines[j].1 <= indent)
+                    .unwrap_or(range.end);
+                let rest = lines[i].2[1..].to_string();
+                let text = rest.trim_start();
+                if text.is_empty() {
+                    range = i + 1..end;
+                } else {
+                    lines[i].1 = indent + 1 + rest.len() - text.len();
+                    lines[i].2 = text.to_string();
+                    range = i..end;
+                }
+            }
+        }
+    }
+
+    found
+}
+
+/// The key of a `key: value` or `key:` line
+fn key_of(text: &str) -> Option<&str> {
+    let (key, rest) = text.split_once(':')?;
+    if !rest.is_empty() && !rest.starts_with(' ') {
+        return None;
+    }
+    Some(key.trim().trim_matches(|c| c == '"' || c == '\''))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VALID: &str = "workspace:\n  name: test\n  root: /tmp/ws\n  auto_discover: false\n\
+                         repositories:\n\
+                         - name: frontend\n  path: frontend\n  url: null\n  branch: null\n  apps: {}\n\
+                         groups: []\napps: {}\n";
+
+    #[test]
+    fn test_valid_config_parses() {
+        let config = parse_config(VALID).unwrap();
+        assert_eq!(config.repositories[0].name, "frontend");
+    }
+
+    #[test]
+    fn test_errors_name_entry_field_and_line() {
+        let contents = "workspace:\n  name: test\n  root: /tmp/ws\n  auto_discover: false\n\
+                        repositories:\n\
+                        - name: api\n  path: api\n  apps: {}\n\
+                        - name: frontend\n  path: frontend\n  apps:\n    vscode: code\n\
+                        - name: docs\n  apps: {}\n\
+                        groups: nope\napps: {}\n";
+        let errors = parse_config(contents).unwrap_err().errors;
+
+        assert_eq!(
+            errors[0].to_string(),
+            "repository 'frontend': field 'apps.vscode': expected boolean or mapping, found string (line 12)"
+        );
+        assert_eq!(
+            errors[1].to_string(),
+            "repository 'docs': missing field `path` (line 13)"
+        );
+        assert_eq!(
+            errors[2].to_string(),
+            "section 'groups': expected sequence, found string \"nope\" (line 15)"
+        );
+        assert_eq!(errors.len(), 3);
+    }
+
+    #[test]
+    fn test_syntax_error_has_line() {
+        let error = parse_config("workspace:\n  name: [test\n").unwrap_err();
+        assert_eq!(error.errors.len(), 1);
+        assert_eq!(error.errors[0].context, "YAML syntax");
+        assert!(error.errors[0].line.is_some());
+    }
+
+    #[test]
+    fn test_locate_nested_keys_and_items() {
+        let contents = "a:\n  b:\n  - x: 1\n  - x: 2\n    y:\n      z: 3\n";
+        let steps = [
+            Step::Key("a".to_string()),
+            Step::Key("b".to_string()),
+            Step::Index(1),
+            Step::Key("y".to_string()),
+            Step::Key("z".to_string()),
+        ];
+        assert_eq!(locate(contents, &steps), Some(6));
+        assert_eq!(locate(contents, &steps[..3]), Some(4));
+        // A missing key stops at the deepest step found
+        assert_eq!(
+            locate(
+                contents,
+                &[Step::Key("a".to_string()), Step::Key("q".to_string())]
+            ),
+            Some(1)
+        );
+    }
+}
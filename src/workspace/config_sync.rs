@@ -0,0 +1,855 @@
+//! Sharing part of the configuration through a git repository
+//!
+//! `vibe config sync setup <url>` clones a repository into the data
+//! directory's `sync/`. Only settings that mean the same on every machine are
+//! shared: groups, managed hooks and files, templates, and whether Claude
+//! agents are enabled. Repository paths, app directories, preferences, and
+//! recent-repository state stay local, as do hooks and managed files whose
+//! source is an absolute path. In the repository the settings are
+//! `vibe-config.yaml`, next to `templates/`, `hooks/`, and `managed/` holding
+//! the files those settings point at.
+//!
+//! Both sides are flattened into entries, one per group, hook, managed file,
+//! or file, and merged three-way against hashes of the entries at the last
+//! sync, kept in the clone's `.git` directory. A side that didn't change an
+//! entry since then takes the other's version; entries changed on both sides
+//! are conflicts, decided one at a time. The clone only mirrors the remote:
+//! it is reset to the remote branch before every operation, and a push that
+//! is rejected is rolled back.
+
+use anyhow::{bail, Context, Result};
+use console::style;
+use inquire::Select;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use crate::display_println;
+use crate::output::exit::CommandError;
+use crate::ui::prompts::handle_prompt_result;
+use crate::utils::fs::to_forward_slashes;
+use crate::workspace::config::{
+    ClaudeAgentsIntegration, ManagedFile, RepositoryGroup, WorkspaceConfig,
+};
+use crate::workspace::constants::{
+    get_hooks_dir, get_managed_files_dir, get_sync_dir, get_templates_dir,
+};
+use crate::workspace::operations::execute_git_command;
+
+/// Shared settings file at the root of the sync repository
+pub const SHARED_CONFIG_FILE: &str = "vibe-config.yaml";
+
+/// Entry hashes at the last sync, inside the clone's `.git` directory
+const BASE_FILE: &str = "vibe-sync-base.json";
+
+const GROUP: &str = "group:";
+const HOOK: &str = "hook:";
+const MANAGED_FILE: &str = "managed_file:";
+const CLAUDE_AGENTS: &str = "claude_agents:enabled";
+
+/// Entry key to contents: YAML for settings, bytes for files
+type Entries = BTreeMap<String, Vec<u8>>;
+
+/// Entry key to the SHA-256 of its contents at the last sync
+type Base = BTreeMap<String, String>;
+
+/// Whether `key` is a setting rather than a file
+fn is_setting(key: &str) -> bool {
+    [GROUP, HOOK, MANAGED_FILE, CLAUDE_AGENTS]
+        .iter()
+        .any(|prefix| key.starts_with(prefix))
+}
+
+/// Shared file directories: name in the sync repository and local location
+fn file_dirs() -> [(&'static str, PathBuf); 3] {
+    [
+        ("templates", get_templates_dir()),
+        ("hooks", get_hooks_dir()),
+        ("managed", get_managed_files_dir()),
+    ]
+}
+
+/// The settings that are shared, as stored in [`SHARED_CONFIG_FILE`]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct SharedConfig {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    groups: Vec<RepositoryGroup>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    hooks: BTreeMap<String, PathBuf>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    managed_files: Vec<ManagedFile>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    claude_agents_enabled: Option<bool>,
+}
+
+/// YAML with sorted keys, so unchanged settings always hash the same
+fn encode<T: Serialize>(value: &T) -> Result<Vec<u8>> {
+    Ok(serde_yaml::to_string(&serde_json::to_value(value)?)?.into_bytes())
+}
+
+fn decode_all<T: for<'de> Deserialize<'de>>(entries: &Entries, prefix: &str) -> Result<Vec<T>> {
+    entries
+        .iter()
+        .filter(|(key, _)| key.starts_with(prefix))
+        .map(|(key, value)| {
+            serde_yaml::from_slice(value).with_context(|| format!("Invalid shared entry {key}"))
+        })
+        .collect()
+}
+
+/// Order `items` like `order`, with items it doesn't have last
+fn sort_like<T, K: PartialEq>(items: &mut [T], order: &[T], key: impl Fn(&T) -> K) {
+    items.sort_by_key(|item| {
+        order
+            .iter()
+            .position(|other| key(other) == key(item))
+            .unwrap_or(usize::MAX)
+    });
+}
+
+impl SharedConfig {
+    fn from_config(config: &WorkspaceConfig) -> Self {
+        Self {
+            groups: config.groups.clone(),
+            hooks: config
+                .hooks
+                .git
+                .iter()
+                .filter(|(_, script)| script.is_relative())
+                .map(|(name, script)| (name.clone(), script.clone()))
+                .collect(),
+            managed_files: config
+                .managed_files
+                .iter()
+                .filter(|file| file.source.is_relative())
+                .cloned()
+                .collect(),
+            claude_agents_enabled: config.claude_agents.as_ref().map(|agents| agents.enabled),
+        }
+    }
+
+    fn entries(&self) -> Result<Entries> {
+        let mut entries = Entries::new();
+        for group in &self.groups {
+            entries.insert(format!("{GROUP}{}", group.name), encode(group)?);
+        }
+        for (name, script) in &self.hooks {
+            entries.insert(format!("{HOOK}{name}"), encode(&(name, script))?);
+        }
+        for file in &self.managed_files {
+            entries.insert(
+                format!("{MANAGED_FILE}{}", to_forward_slashes(&file.destination)),
+                encode(file)?,
+            );
+        }
+        if let Some(enabled) = self.claude_agents_enabled {
+            entries.insert(CLAUDE_AGENTS.to_string(), encode(&enabled)?);
+        }
+        Ok(entries)
+    }
+
+    /// The settings in `entries`, ordered like `order` where it has them
+    fn from_entries(entries: &Entries, order: &SharedConfig) -> Result<Self> {
+        let mut groups: Vec<RepositoryGroup> = decode_all(entries, GROUP)?;
+        sort_like(&mut groups, &order.groups, |group| group.name.clone());
+        let mut managed_files: Vec<ManagedFile> = decode_all(entries, MANAGED_FILE)?;
+        sort_like(&mut managed_files, &order.managed_files, |file| {
+            file.destination.clone()
+        });
+        let hooks: Vec<(String, PathBuf)> = decode_all(entries, HOOK)?;
+        let claude_agents_enabled = entries
+            .get(CLAUDE_AGENTS)
+            .map(|value| serde_yaml::from_slice(value))
+            .transpose()
+            .context("Invalid shared entry claude_agents")?;
+        Ok(Self {
+            groups,
+            hooks: hooks.into_iter().collect(),
+            managed_files,
+            claude_agents_enabled,
+        })
+    }
+
+    /// Replace the shared settings of `config`, keeping its local-only ones
+    fn apply_to(self, config: &mut WorkspaceConfig) {
+        config.groups = self.groups;
+        config.hooks.git.retain(|_, script| script.is_absolute());
+        config.hooks.git.extend(self.hooks);
+        let local_only: Vec<ManagedFile> = config
+            .managed_files
+            .drain(..)
+            .filter(|file| file.source.is_absolute())
+            .collect();
+        config.managed_files = self.managed_files;
+        config.managed_files.extend(local_only);
+        if let Some(enabled) = self.claude_agents_enabled {
+            match &mut config.claude_agents {
+                Some(agents) => agents.enabled = enabled,
+                None => config.claude_agents = Some(ClaudeAgentsIntegration::new(enabled)),
+            }
+        }
+    }
+}
+
+/// Add every file under `dir` as `<prefix>/<relative path>`
+fn read_files(entries: &mut Entries, prefix: &str, dir: &Path) -> Result<()> {
+    if !dir.is_dir() {
+        return Ok(());
+    }
+    for entry in WalkDir::new(dir).follow_links(true) {
+        let entry = entry.with_context(|| format!("Failed to read {}", dir.display()))?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let relative = entry.path().strip_prefix(dir).unwrap_or(entry.path());
+        let contents = std::fs::read(entry.path())
+            .with_context(|| format!("Failed to read {}", entry.path().display()))?;
+        entries.insert(
+            format!("{prefix}/{}", to_forward_slashes(relative)),
+            contents,
+        );
+    }
+    Ok(())
+}
+
+/// Bring the files of one side from `before` to `after`
+fn write_files(before: &Entries, after: &Entries, dirs: &[(&str, PathBuf)]) -> Result<()> {
+    let keys: BTreeSet<&String> = before.keys().chain(after.keys()).collect();
+    for key in keys {
+        let Some((dir, relative)) = dirs.iter().find_map(|(prefix, dir)| {
+            key.strip_prefix(prefix)
+                .and_then(|rest| rest.strip_prefix('/'))
+                .map(|relative| (dir, relative))
+        }) else {
+            continue;
+        };
+        let path = dir.join(relative);
+        match (before.get(key), after.get(key)) {
+            (old, Some(new)) if old != Some(new) => {
+                if let Some(parent) = path.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                std::fs::write(&path, new)
+                    .with_context(|| format!("Failed to write {}", path.display()))?;
+            }
+            (Some(_), None) => match std::fs::remove_file(&path) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                    return Err(e).with_context(|| format!("Failed to remove {}", path.display()))
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn hash(contents: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(contents))
+}
+
+fn hashes(entries: &Entries) -> Base {
+    entries
+        .iter()
+        .map(|(key, value)| (key.clone(), hash(value)))
+        .collect()
+}
+
+/// How an entry differs between the two sides
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntryState {
+    /// Changed locally since the last sync; a push shares it
+    Local,
+    /// Changed in the sync repository; a pull brings it in
+    Remote,
+    /// Changed on both sides
+    Conflict,
+}
+
+/// An entry changed on both sides, with each side's contents (None when
+/// that side removed it)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Conflict {
+    pub key: String,
+    pub local: Option<Vec<u8>>,
+    pub remote: Option<Vec<u8>>,
+}
+
+#[derive(Debug, Default)]
+struct Merge {
+    /// Every entry that isn't a conflict, merged
+    merged: Entries,
+    states: BTreeMap<String, EntryState>,
+    conflicts: Vec<Conflict>,
+}
+
+fn merge(local: &Entries, remote: &Entries, base: &Base) -> Merge {
+    let mut result = Merge::default();
+    let keys: BTreeSet<&String> = local.keys().chain(remote.keys()).collect();
+    for key in keys {
+        let (ours, theirs) = (local.get(key), remote.get(key));
+        let base = base.get(key);
+        let (kept, state) = if ours == theirs {
+            (ours, None)
+        } else if ours.map(|value| hash(value)).as_ref() == base {
+            (theirs, Some(EntryState::Remote))
+        } else if theirs.map(|value| hash(value)).as_ref() == base {
+            (ours, Some(EntryState::Local))
+        } else {
+            result.conflicts.push(Conflict {
+                key: key.clone(),
+                local: ours.cloned(),
+                remote: theirs.cloned(),
+            });
+            result.states.insert(key.clone(), EntryState::Conflict);
+            continue;
+        };
+        if let Some(state) = state {
+            result.states.insert(key.clone(), state);
+        }
+        if let Some(value) = kept {
+            result.merged.insert(key.clone(), value.clone());
+        }
+    }
+    result
+}
+
+fn preview(contents: Option<&[u8]>) -> String {
+    const MAX_LINES: usize = 12;
+    let Some(contents) = contents else {
+        return "    (removed)".to_string();
+    };
+    let Ok(text) = std::str::from_utf8(contents) else {
+        return format!("    <{} bytes of binary data>", contents.len());
+    };
+    let mut lines: Vec<String> = text
+        .lines()
+        .take(MAX_LINES)
+        .map(|line| format!("    {line}"))
+        .collect();
+    if text.lines().count() > MAX_LINES {
+        lines.push("    ...".to_string());
+    }
+    lines.join("\n")
+}
+
+/// Ask which side of each conflict to keep, adding the choices to `merged`
+fn resolve_conflicts(conflicts: Vec<Conflict>, merged: &mut Entries) -> Result<()> {
+    if conflicts.is_empty() {
+        return Ok(());
+    }
+    if !crate::output::is_interactive() || !std::io::stdin().is_terminal() {
+        let keys: Vec<&str> = conflicts.iter().map(|c| c.key.as_str()).collect();
+        bail!(
+            "{} entries changed both locally and in the sync repository: {}. Run in a terminal to choose between them",
+            keys.len(),
+            keys.join(", ")
+        );
+    }
+
+    for conflict in conflicts {
+        display_println!();
+        display_println!(
+            "{} {}",
+            style("Conflict:").yellow().bold(),
+            style(&conflict.key).cyan().bold()
+        );
+        display_println!("  {}", style("local").dim());
+        display_println!("{}", preview(conflict.local.as_deref()));
+        display_println!("  {}", style("remote").dim());
+        display_println!("{}", preview(conflict.remote.as_deref()));
+        let choice = handle_prompt_result(
+            Select::new("Keep:", vec!["Local version", "Remote version"]).prompt(),
+        )?
+        .ok_or(CommandError::Cancelled)?;
+        let kept = if choice == "Local version" {
+            conflict.local
+        } else {
+            conflict.remote
+        };
+        if let Some(value) = kept {
+            merged.insert(conflict.key, value);
+        }
+    }
+    Ok(())
+}
+
+/// Whether `vibe config sync setup` has been run
+pub fn is_set_up() -> bool {
+    get_sync_dir().join(".git").exists()
+}
+
+fn sync_repo() -> Result<PathBuf> {
+    let dir = get_sync_dir();
+    if !dir.join(".git").exists() {
+        return Err(CommandError::Usage(
+            "Config sync is not set up. Run `vibe config sync setup <git-url>` first".to_string(),
+        )
+        .into());
+    }
+    Ok(dir)
+}
+
+/// Clone `url` as the sync repository. With `force`, a clone of another
+/// repository is replaced.
+pub async fn setup(url: &str, force: bool) -> Result<PathBuf> {
+    let dir = get_sync_dir();
+    if dir.exists() {
+        let current = execute_git_command(&dir, &["remote", "get-url", "origin"])
+            .await
+            .ok();
+        if current.as_deref() == Some(url) {
+            return Ok(dir);
+        }
+        if !force {
+            return Err(CommandError::Usage(format!(
+                "Config sync is already set up with {}. Use --force to replace it",
+                current.as_deref().unwrap_or("another repository")
+            ))
+            .into());
+        }
+        tokio::fs::remove_dir_all(&dir)
+            .await
+            .with_context(|| format!("Failed to remove {}", dir.display()))?;
+    }
+
+    let parent = dir.parent().context("Sync directory has no parent")?;
+    tokio::fs::create_dir_all(parent).await?;
+    execute_git_command(parent, &["clone", "--quiet", url, &dir.to_string_lossy()])
+        .await
+        .with_context(|| format!("Failed to clone {url}"))?;
+    Ok(dir)
+}
+
+/// Reset the clone to the remote branch, or to empty if the remote has none yet
+async fn refresh(repo: &Path) -> Result<()> {
+    execute_git_command(repo, &["fetch", "--quiet", "origin"]).await?;
+    if execute_git_command(repo, &["rev-parse", "--verify", "-q", "@{u}"])
+        .await
+        .is_ok()
+    {
+        execute_git_command(repo, &["reset", "--hard", "-q", "@{u}"]).await?;
+    } else {
+        // Nothing pushed yet: anything here is left over from a failed push
+        execute_git_command(
+            repo,
+            &["rm", "-r", "-q", "--cached", "--ignore-unmatch", "."],
+        )
+        .await?;
+    }
+    execute_git_command(repo, &["clean", "-fdq"]).await?;
+    Ok(())
+}
+
+/// Both sides and the base of one sync
+struct Snapshot {
+    repo: PathBuf,
+    local_config: SharedConfig,
+    local: Entries,
+    remote_config: SharedConfig,
+    remote: Entries,
+    base: Base,
+}
+
+async fn snapshot(config: &WorkspaceConfig) -> Result<Snapshot> {
+    let repo = sync_repo()?;
+    refresh(&repo).await?;
+    let local_config = SharedConfig::from_config(config);
+    let local_settings = local_config.entries()?;
+
+    tokio::task::spawn_blocking(move || -> Result<Snapshot> {
+        let mut local = local_settings;
+        for (prefix, dir) in file_dirs() {
+            read_files(&mut local, prefix, &dir)?;
+        }
+
+        let shared_file = repo.join(SHARED_CONFIG_FILE);
+        let remote_config: SharedConfig = if shared_file.exists() {
+            let contents = std::fs::read_to_string(&shared_file)?;
+            serde_yaml::from_str(&contents)
+                .with_context(|| format!("Failed to parse {}", shared_file.display()))?
+        } else {
+            SharedConfig::default()
+        };
+        let mut remote = remote_config.entries()?;
+        for (prefix, _) in file_dirs() {
+            read_files(&mut remote, prefix, &repo.join(prefix))?;
+        }
+
+        let base = match std::fs::read_to_string(repo.join(".git").join(BASE_FILE)) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_default(),
+            Err(_) => Base::new(),
+        };
+        Ok(Snapshot {
+            repo,
+            local_config,
+            local,
+            remote_config,
+            remote,
+            base,
+        })
+    })
+    .await?
+}
+
+async fn save_base(repo: &Path, entries: &Entries) -> Result<()> {
+    let base = serde_json::to_string_pretty(&hashes(entries))?;
+    tokio::fs::write(repo.join(".git").join(BASE_FILE), base)
+        .await
+        .context("Failed to record the synced state")
+}
+
+/// Apply `merged` to the local configuration and files, saving the config
+/// to `config_path` when it changed. Returns the keys that changed.
+async fn apply_locally(
+    snapshot: &Snapshot,
+    merged: &Entries,
+    config: &mut WorkspaceConfig,
+    config_path: &Path,
+) -> Result<Vec<String>> {
+    let changed = changed_keys(&snapshot.local, merged);
+    if changed.is_empty() {
+        return Ok(changed);
+    }
+    let (local, merged_files) = (snapshot.local.clone(), merged.clone());
+    tokio::task::spawn_blocking(move || write_files(&local, &merged_files, &file_dirs())).await??;
+
+    if changed.iter().any(|key| is_setting(key)) {
+        SharedConfig::from_entries(merged, &snapshot.local_config)?.apply_to(config);
+        config.save_to_file(config_path).await?;
+    }
+    Ok(changed)
+}
+
+fn changed_keys(before: &Entries, after: &Entries) -> Vec<String> {
+    let keys: BTreeSet<&String> = before.keys().chain(after.keys()).collect();
+    keys.into_iter()
+        .filter(|key| before.get(*key) != after.get(*key))
+        .cloned()
+        .collect()
+}
+
+/// What a pull or push changed
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ConfigSyncReport {
+    /// Entries updated in the local configuration
+    pub pulled: Vec<String>,
+    /// Entries updated in the sync repository
+    pub pushed: Vec<String>,
+    /// The commit a push created
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+}
+
+impl ConfigSyncReport {
+    pub fn print(&self) {
+        if self.pulled.is_empty() && self.pushed.is_empty() {
+            display_println!("{} Shared config is up to date", style("✓").green());
+            return;
+        }
+        for (label, keys) in [("Pulled", &self.pulled), ("Pushed", &self.pushed)] {
+            if keys.is_empty() {
+                continue;
+            }
+            display_println!(
+                "{} {label} {} entries",
+                style("✓").green().bold(),
+                keys.len()
+            );
+            for key in keys {
+                display_println!("  {}", style(key).dim());
+            }
+        }
+    }
+}
+
+/// Merge the sync repository into the local configuration
+pub async fn pull(config: &mut WorkspaceConfig, config_path: &Path) -> Result<ConfigSyncReport> {
+    let snapshot = snapshot(config).await?;
+    let Merge {
+        mut merged,
+        conflicts,
+        ..
+    } = merge(&snapshot.local, &snapshot.remote, &snapshot.base);
+    resolve_conflicts(conflicts, &mut merged)?;
+
+    let pulled = apply_locally(&snapshot, &merged, config, config_path).await?;
+    // Local changes kept over the remote stay pending for the next push
+    save_base(&snapshot.repo, &snapshot.remote).await?;
+    Ok(ConfigSyncReport {
+        pulled,
+        ..Default::default()
+    })
+}
+
+/// Commit subject listing what changed, or how much when that's too long
+fn commit_message(changed: &[String]) -> String {
+    let mut message = if changed.len() <= 3 {
+        format!("Update shared vibe config: {}", changed.join(", "))
+    } else {
+        format!("Update shared vibe config: {} entries", changed.len())
+    };
+    if changed.len() > 3 {
+        message.push_str("\n\n");
+        for key in changed {
+            message.push_str(&format!("- {key}\n"));
+        }
+    }
+    message
+}
+
+/// Put the clone back at `previous`, or back to no commits at all
+async fn roll_back(repo: &Path, previous: Option<&str>) {
+    let result = match previous {
+        Some(previous) => execute_git_command(repo, &["reset", "--hard", "-q", previous]).await,
+        None => {
+            let _ = execute_git_command(repo, &["update-ref", "-d", "HEAD"]).await;
+            execute_git_command(
+                repo,
+                &["rm", "-r", "-q", "--cached", "--ignore-unmatch", "."],
+            )
+            .await
+        }
+    };
+    if let Err(e) = result {
+        tracing::warn!("Failed to roll back the sync repository: {}", e);
+    }
+}
+
+/// Merge both ways and publish the result to the sync repository
+pub async fn push(config: &mut WorkspaceConfig, config_path: &Path) -> Result<ConfigSyncReport> {
+    let snapshot = snapshot(config).await?;
+    let Merge {
+        mut merged,
+        conflicts,
+        ..
+    } = merge(&snapshot.local, &snapshot.remote, &snapshot.base);
+    resolve_conflicts(conflicts, &mut merged)?;
+
+    let pulled = apply_locally(&snapshot, &merged, config, config_path).await?;
+    let pushed = changed_keys(&snapshot.remote, &merged);
+    let repo = &snapshot.repo;
+    if pushed.is_empty() {
+        save_base(repo, &merged).await?;
+        return Ok(ConfigSyncReport {
+            pulled,
+            ..Default::default()
+        });
+    }
+
+    let shared = SharedConfig::from_entries(&merged, &snapshot.remote_config)?;
+    let shared_file = repo.join(SHARED_CONFIG_FILE);
+    tokio::fs::write(&shared_file, encode(&shared)?).await?;
+    let (remote, merged_files, repo_dir) = (snapshot.remote.clone(), merged.clone(), repo.clone());
+    tokio::task::spawn_blocking(move || {
+        let dirs = file_dirs().map(|(prefix, _)| (prefix, repo_dir.join(prefix)));
+        write_files(&remote, &merged_files, &dirs)
+    })
+    .await??;
+
+    let previous = execute_git_command(repo, &["rev-parse", "--verify", "-q", "HEAD"])
+        .await
+        .ok();
+    let published = async {
+        execute_git_command(repo, &["add", "-A"]).await?;
+        execute_git_command(repo, &["commit", "--quiet", "-m", &commit_message(&pushed)]).await?;
+        execute_git_command(repo, &["push", "--quiet", "-u", "origin", "HEAD"]).await
+    }
+    .await;
+    if let Err(e) = published {
+        roll_back(repo, previous.as_deref()).await;
+        return Err(e.context(
+            "Failed to publish the shared config. If the remote changed meanwhile, run `vibe config sync push` again",
+        ));
+    }
+    let commit = execute_git_command(repo, &["rev-parse", "--short", "HEAD"])
+        .await
+        .ok();
+    save_base(repo, &merged).await?;
+    Ok(ConfigSyncReport {
+        pulled,
+        pushed,
+        commit,
+    })
+}
+
+/// How the local configuration and the sync repository differ
+#[derive(Debug, Clone, Serialize)]
+pub struct SyncStatus {
+    pub remote: String,
+    pub entries: BTreeMap<String, EntryState>,
+}
+
+impl SyncStatus {
+    pub fn print(&self) {
+        display_println!(
+            "{} {}",
+            style("Sync repository:").bold(),
+            style(&self.remote).cyan()
+        );
+        if self.entries.is_empty() {
+            display_println!("{} In sync", style("✓").green());
+            return;
+        }
+        for (key, state) in &self.entries {
+            let label = match state {
+                EntryState::Local => style("local change ").green(),
+                EntryState::Remote => style("remote change").blue(),
+                EntryState::Conflict => style("conflict     ").yellow(),
+            };
+            display_println!("  {label}  {key}");
+        }
+        let count = |wanted: EntryState| self.entries.values().filter(|s| **s == wanted).count();
+        display_println!();
+        display_println!(
+            "{} to push, {} to pull, {} conflicts",
+            count(EntryState::Local),
+            count(EntryState::Remote),
+            count(EntryState::Conflict)
+        );
+    }
+}
+
+/// Compare the local configuration with the sync repository
+pub async fn status(config: &WorkspaceConfig) -> Result<SyncStatus> {
+    let snapshot = snapshot(config).await?;
+    let remote = execute_git_command(&snapshot.repo, &["remote", "get-url", "origin"])
+        .await
+        .unwrap_or_default();
+    let Merge { states, .. } = merge(&snapshot.local, &snapshot.remote, &snapshot.base);
+    Ok(SyncStatus {
+        remote: crate::output::redact::scrub(&remote).into_owned(),
+        entries: states,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn entries(items: &[(&str, &str)]) -> Entries {
+        items
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.as_bytes().to_vec()))
+            .collect()
+    }
+
+    #[test]
+    fn test_merge_three_way() {
+        let base = hashes(&entries(&[
+            ("group:web", "a"),
+            ("group:api", "a"),
+            ("hook:pre-commit", "a"),
+            ("templates/vscode/default.json", "a"),
+        ]));
+        let local = entries(&[
+            ("group:web", "local"),
+            ("group:api", "a"),
+            ("templates/vscode/default.json", "local"),
+            ("group:new", "x"),
+        ]);
+        let remote = entries(&[
+            ("group:web", "a"),
+            ("group:api", "remote"),
+            ("hook:pre-commit", "a"),
+            ("templates/vscode/default.json", "remote"),
+        ]);
+
+        let result = merge(&local, &remote, &base);
+        assert_eq!(
+            result.merged,
+            entries(&[
+                ("group:web", "local"),
+                ("group:api", "remote"),
+                ("group:new", "x"),
+            ])
+        );
+        assert_eq!(
+            result.states,
+            BTreeMap::from([
+                ("group:api".to_string(), EntryState::Remote),
+                ("group:new".to_string(), EntryState::Local),
+                ("group:web".to_string(), EntryState::Local),
+                ("hook:pre-commit".to_string(), EntryState::Local),
+                (
+                    "templates/vscode/default.json".to_string(),
+                    EntryState::Conflict
+                ),
+            ])
+        );
+        assert_eq!(
+            result.conflicts,
+            vec![Conflict {
+                key: "templates/vscode/default.json".to_string(),
+                local: Some(b"local".to_vec()),
+                remote: Some(b"remote".to_vec()),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_shared_config_keeps_local_only_settings() {
+        let mut config = WorkspaceConfig {
+            groups: vec![
+                RepositoryGroup {
+                    name: "web".to_string(),
+                    repos: vec!["site".to_string()],
+                    apps: HashMap::new(),
+                    default_app: None,
+                    default_template: None,
+                },
+                RepositoryGroup {
+                    name: "api".to_string(),
+                    repos: vec![],
+                    apps: HashMap::new(),
+                    default_app: None,
+                    default_template: None,
+                },
+            ],
+            ..Default::default()
+        };
+        config
+            .hooks
+            .git
+            .insert("pre-commit".to_string(), PathBuf::from("lint.sh"));
+        config
+            .hooks
+            .git
+            .insert("pre-push".to_string(), PathBuf::from("/opt/hooks/push.sh"));
+        let shared = SharedConfig::from_config(&config);
+        assert_eq!(shared.hooks.len(), 1);
+
+        let mut merged = shared.entries().unwrap();
+        merged.remove("group:web");
+        merged.insert(
+            "group:docs".to_string(),
+            encode(&RepositoryGroup {
+                name: "docs".to_string(),
+                repos: vec![],
+                apps: HashMap::new(),
+                default_app: None,
+                default_template: None,
+            })
+            .unwrap(),
+        );
+        SharedConfig::from_entries(&merged, &shared)
+            .unwrap()
+            .apply_to(&mut config);
+
+        let groups: Vec<&str> = config.groups.iter().map(|g| g.name.as_str()).collect();
+        assert_eq!(groups, vec!["api", "docs"]);
+        assert_eq!(
+            config.hooks.git.get("pre-push"),
+            Some(&PathBuf::from("/opt/hooks/push.sh"))
+        );
+        assert_eq!(
+            config.hooks.git.get("pre-commit"),
+            Some(&PathBuf::from("lint.sh"))
+        );
+    }
+}
@@ -3,7 +3,8 @@ use console::style;
 use std::collections::{HashMap, HashSet};
 use std::path::Path;
 
-use super::config::{Repository, WorkspaceConfig};
+use super::config::{resolve_repo_path, Repository, WorkspaceConfig};
+use crate::display_println;
 
 #[derive(Debug, Clone)]
 pub struct DuplicateRepository {
@@ -49,19 +50,19 @@ impl ValidationReport {
 
     pub fn print_report(&self) {
         if !self.has_issues() {
-            println!("{} No config issues found", style("✅").green());
+            display_println!("{} No config issues found", style("✅").green());
             return;
         }
 
-        println!("{} Config Validation Report", style("⚠️").yellow().bold());
-        println!("{}", "─".repeat(50));
+        display_println!("{} Config Validation Report", style("⚠️").yellow().bold());
+        display_println!("{}", "─".repeat(50));
 
         if !self.duplicates.is_empty() {
-            println!("{} Duplicate Repositories Found", style("🔍").blue());
-            println!();
+            display_println!("{} Duplicate Repositories Found", style("🔍").blue());
+            display_println!();
 
             for (i, duplicate) in self.duplicates.iter().enumerate() {
-                println!(
+                display_println!(
                     "{}. {} ({:?})",
                     i + 1,
                     style("Conflict").red().bold(),
@@ -70,7 +71,7 @@ impl ValidationReport {
 
                 for (j, repo) in duplicate.repositories.iter().enumerate() {
                     let marker = if j == 0 { "→" } else { " " };
-                    println!(
+                    display_println!(
                         "  {} {}: {} ({})",
                         marker,
                         style(&repo.name).cyan(),
@@ -81,37 +82,40 @@ impl ValidationReport {
 
                 match duplicate.recommended_action {
                     RecommendedAction::KeepFirst => {
-                        println!("  {} Keep first entry, remove others", style("💡").yellow());
+                        display_println!(
+                            "  {} Keep first entry, remove others",
+                            style("💡").yellow()
+                        );
                     }
                     RecommendedAction::KeepMostComplete => {
-                        println!("  {} Keep most complete entry", style("💡").yellow());
+                        display_println!("  {} Keep most complete entry", style("💡").yellow());
                     }
                     RecommendedAction::KeepExisting => {
-                        println!(
+                        display_println!(
                             "  {} Keep entry that exists on filesystem",
                             style("💡").yellow()
                         );
                     }
                     RecommendedAction::ManualReview => {
-                        println!("  {} Manual review required", style("⚠️").yellow());
+                        display_println!("  {} Manual review required", style("⚠️").yellow());
                     }
                 }
-                println!();
+                display_println!();
             }
         }
 
         if !self.warnings.is_empty() {
-            println!("{} Warnings", style("⚠️").yellow());
+            display_println!("{} Warnings", style("⚠️").yellow());
             for warning in &self.warnings {
-                println!("  • {warning}");
+                display_println!("  • {warning}");
             }
-            println!();
+            display_println!();
         }
 
-        println!("{} Summary:", style("📊").blue());
-        println!("  Total entries: {}", self.total_repositories);
-        println!("  Unique repositories: {}", self.unique_repositories);
-        println!("  Duplicates found: {}", self.duplicates.len());
+        display_println!("{} Summary:", style("📊").blue());
+        display_println!("  Total entries: {}", self.total_repositories);
+        display_println!("  Unique repositories: {}", self.unique_repositories);
+        display_println!("  Duplicates found: {}", self.duplicates.len());
     }
 }
 
@@ -135,7 +139,7 @@ pub fn validate_config(
         }
 
         // Group by normalized path
-        let full_path = workspace_root.join(&repo.path);
+        let full_path = resolve_repo_path(workspace_root, &repo.path);
         let path_key = normalize_path_for_comparison(&full_path);
         by_path.entry(path_key).or_default().push(repo);
 
@@ -187,12 +191,23 @@ pub fn validate_config(
             warnings.push(format!("Repository '{}' has no URL", repo.name));
         }
 
-        if repo.path.is_absolute() {
+        // Absolute paths are how repositories outside the root are configured
+        if repo.path.is_absolute() && repo.path.starts_with(workspace_root) {
             warnings.push(format!(
                 "Repository '{}' uses absolute path (consider relative)",
                 repo.name
             ));
         }
+
+        if let Some(compare_ref) = &repo.compare_ref {
+            let repo_path = resolve_repo_path(workspace_root, &repo.path);
+            if repo_path.exists() && !ref_exists(&repo_path, compare_ref) {
+                warnings.push(format!(
+                    "Repository '{}' compares against '{}', which doesn't exist in the repository",
+                    repo.name, compare_ref
+                ));
+            }
+        }
     }
 
     let unique_count = calculate_unique_repositories(&config.repositories);
@@ -254,6 +269,17 @@ pub fn deduplicate_config(
     validate_config(config, workspace_root)
 }
 
+/// Whether `reference` names a commit in the repository at `repo_path`
+fn ref_exists(repo_path: &Path, reference: &str) -> bool {
+    std::process::Command::new("git")
+        .args(["rev-parse", "--verify", "--quiet"])
+        .arg(format!("{reference}^{{commit}}"))
+        .current_dir(repo_path)
+        .output()
+        .map(|output| output.status.success())
+        .unwrap_or(false)
+}
+
 fn normalize_git_url(url: &str) -> String {
     // Normalize git URLs for comparison
     let mut normalized = url.to_lowercase();
@@ -320,7 +346,7 @@ fn find_existing_repositories<'a>(
     repos
         .iter()
         .filter(|repo| {
-            let full_path = workspace_root.join(&repo.path);
+            let full_path = resolve_repo_path(workspace_root, &repo.path);
             full_path.join(".git").exists()
         })
         .copied()
@@ -406,4 +432,45 @@ mod tests {
 
         assert_eq!(most_complete.name, "test2");
     }
+
+    #[test]
+    fn test_warns_about_missing_compare_ref() {
+        let root = tempfile::TempDir::new().unwrap();
+        let repo_path = root.path().join("api");
+        std::fs::create_dir_all(&repo_path).unwrap();
+        for args in [
+            &["init", "-q", "-b", "main"][..],
+            &[
+                "-c",
+                "user.name=Test",
+                "-c",
+                "user.email=test@example.com",
+                "commit",
+                "-q",
+                "--allow-empty",
+                "-m",
+                "initial",
+            ],
+        ] {
+            std::process::Command::new("git")
+                .args(args)
+                .current_dir(&repo_path)
+                .output()
+                .unwrap();
+        }
+
+        let mut repo = Repository::new("api".to_string(), PathBuf::from("api"))
+            .with_url("https://github.com/test/api".to_string());
+        let mut config = WorkspaceConfig::default();
+        repo.compare_ref = Some("main".to_string());
+        config.repositories = vec![repo.clone()];
+        let report = validate_config(&config, root.path()).unwrap();
+        assert!(report.warnings.is_empty());
+
+        repo.compare_ref = Some("upstream/main".to_string());
+        config.repositories = vec![repo];
+        let report = validate_config(&config, root.path()).unwrap();
+        assert_eq!(report.warnings.len(), 1);
+        assert!(report.warnings[0].contains("upstream/main"));
+    }
 }
@@ -0,0 +1,132 @@
+//! Watching the config file for changes made outside the running process
+//!
+//! The menu and the MCP server keep a [`WorkspaceManager`] alive for a whole
+//! session, so edits to config.yaml (by hand or by another vibe process)
+//! would otherwise go unnoticed until restart. The watcher follows the
+//! config's directory rather than the file itself, because many editors
+//! replace the file instead of writing it in place, and collapses bursts of
+//! writes into a single change.
+//!
+//! [`WorkspaceManager`]: super::WorkspaceManager
+
+use anyhow::{Context, Result};
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::Path;
+use std::time::Duration;
+use tokio::sync::{mpsc, watch};
+use tokio::task::JoinHandle;
+
+/// Quiet period after the last write before a change is reported
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Reports debounced changes to one config file
+pub struct ConfigWatcher {
+    changes: watch::Receiver<u64>,
+    // Dropping the watcher stops the events
+    _watcher: RecommendedWatcher,
+    debouncer: JoinHandle<()>,
+}
+
+impl ConfigWatcher {
+    /// Start watching `config_path`. Its directory has to exist.
+    pub fn spawn(config_path: &Path) -> Result<Self> {
+        let file_name = config_path
+            .file_name()
+            .context("config path has no file name")?
+            .to_owned();
+        let dir = config_path
+            .parent()
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .unwrap_or(Path::new("."));
+
+        let (events_tx, mut events) = mpsc::unbounded_channel();
+        let mut watcher =
+            notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+                let Ok(event) = event else {
+                    return;
+                };
+                let touches_config = event
+                    .paths
+                    .iter()
+                    .any(|path| path.file_name() == Some(file_name.as_os_str()));
+                if touches_config && !matches!(event.kind, EventKind::Access(_)) {
+                    let _ = events_tx.send(());
+                }
+            })
+            .context("Failed to create config file watcher")?;
+        watcher
+            .watch(dir, RecursiveMode::NonRecursive)
+            .with_context(|| format!("Failed to watch {}", dir.display()))?;
+
+        let (changes_tx, changes) = watch::channel(0);
+        let debouncer = tokio::spawn(async move {
+            while events.recv().await.is_some() {
+                loop {
+                    match tokio::time::timeout(DEBOUNCE, events.recv()).await {
+                        Ok(Some(())) => continue,
+                        Ok(None) => return,
+                        Err(_) => break,
+                    }
+                }
+                changes_tx.send_modify(|generation| *generation += 1);
+            }
+        });
+
+        Ok(Self {
+            changes,
+            _watcher: watcher,
+            debouncer,
+        })
+    }
+
+    /// Whether the file changed since the last call, without waiting
+    pub fn take_change(&mut self) -> bool {
+        let changed = self.changes.has_changed().unwrap_or(false);
+        self.changes.mark_unchanged();
+        changed
+    }
+
+    /// Wait for the next change. `false` once the watcher has stopped.
+    pub async fn changed(&mut self) -> bool {
+        self.changes.changed().await.is_ok()
+    }
+}
+
+impl Drop for ConfigWatcher {
+    fn drop(&mut self) {
+        self.debouncer.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_bursts_of_writes_are_one_change() {
+        let temp = TempDir::new().unwrap();
+        let config = temp.path().join("config.yaml");
+        tokio::fs::write(&config, "a: 1\n").await.unwrap();
+
+        let mut watcher = ConfigWatcher::spawn(&config).unwrap();
+        assert!(!watcher.take_change());
+
+        for n in 2..5 {
+            tokio::fs::write(&config, format!("a: {n}\n"))
+                .await
+                .unwrap();
+        }
+        tokio::fs::write(temp.path().join("other.yaml"), "b: 1\n")
+            .await
+            .unwrap();
+
+        let changed = tokio::time::timeout(Duration::from_secs(5), watcher.changed()).await;
+        assert_eq!(changed.ok(), Some(true));
+        assert!(!watcher.take_change());
+
+        // Nothing else happened afterwards
+        let again = tokio::time::timeout(DEBOUNCE * 3, watcher.changed()).await;
+        assert!(again.is_err());
+    }
+}
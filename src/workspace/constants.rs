@@ -1,16 +1,113 @@
 //! Constants for vibe-workspace configuration paths and settings
+//!
+//! Every file vibe writes lives under one of four directories, resolved in
+//! order from:
+//!
+//! 1. `VIBE_HOME`, which holds everything in a single directory
+//! 2. The XDG layout, once `$XDG_CONFIG_HOME/vibe` exists
+//! 3. The legacy `~/.toolprint/vibe-workspace`, when it exists
+//! 4. The XDG layout for new installs:
+//!    - config: `$XDG_CONFIG_HOME/vibe` (`~/.config/vibe`)
+//!    - data: `$XDG_DATA_HOME/vibe` (`~/.local/share/vibe`) for templates,
+//!      backups, git hook scripts, managed files, and global worktrees
+//!    - cache: `$XDG_CACHE_HOME/vibe` (`~/.cache/vibe`)
+//!    - state: `$XDG_STATE_HOME/vibe` (`~/.local/state/vibe`) for state.json
+//!      and logs
 
-use std::path::PathBuf;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
 
-/// Configuration directory path relative to home directory
-pub const CONFIG_DIR_PATH: &str = ".toolprint/vibe-workspace";
+/// Legacy configuration directory relative to the home directory
+pub const LEGACY_DIR_PATH: &str = ".toolprint/vibe-workspace";
 
-/// Display name for user messages
-pub const CONFIG_DIR_DISPLAY: &str = "~/.toolprint/vibe-workspace";
+/// Overrides every vibe directory with a single location
+pub const VIBE_HOME_ENV: &str = "VIBE_HOME";
+
+/// Directory name under each XDG base directory
+const XDG_APP_DIR: &str = "vibe";
+
+/// Where vibe keeps each kind of file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VibePaths {
+    /// config.yaml
+    pub config: PathBuf,
+    /// Templates, backups, completions, and global worktrees
+    pub data: PathBuf,
+    /// Cache databases
+    pub cache: PathBuf,
+    /// state.json and logs
+    pub state: PathBuf,
+    pub layout: Layout,
+}
+
+/// Which rule chose the directories
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Layout {
+    VibeHome,
+    Xdg,
+    Legacy,
+}
+
+impl VibePaths {
+    /// Everything in one directory, as with `VIBE_HOME` and the legacy layout
+    pub fn single(dir: PathBuf, layout: Layout) -> Self {
+        Self {
+            config: dir.clone(),
+            data: dir.clone(),
+            cache: dir.join("cache"),
+            state: dir,
+            layout,
+        }
+    }
+
+    /// The XDG layout, using the defaults for unset or relative variables
+    pub fn xdg(home: &Path, env: impl Fn(&str) -> Option<OsString>) -> Self {
+        let base = |var: &str, default: &str| {
+            env(var)
+                .map(PathBuf::from)
+                .filter(|path| path.is_absolute())
+                .unwrap_or_else(|| home.join(default))
+                .join(XDG_APP_DIR)
+        };
+        Self {
+            config: base("XDG_CONFIG_HOME", ".config"),
+            data: base("XDG_DATA_HOME", ".local/share"),
+            cache: base("XDG_CACHE_HOME", ".cache"),
+            state: base("XDG_STATE_HOME", ".local/state"),
+            layout: Layout::Xdg,
+        }
+    }
+
+    /// Apply the resolution order to `home` and the given environment
+    pub fn resolve(home: &Path, env: impl Fn(&str) -> Option<OsString>) -> Self {
+        if let Some(dir) = env(VIBE_HOME_ENV).filter(|dir| !dir.is_empty()) {
+            return Self::single(crate::utils::fs::expand_tilde(dir), Layout::VibeHome);
+        }
+
+        let xdg = Self::xdg(home, env);
+        let legacy = home.join(LEGACY_DIR_PATH);
+        if !xdg.config.exists() && legacy.exists() {
+            return Self::single(legacy, Layout::Legacy);
+        }
+        xdg
+    }
+
+    /// Resolve from the process environment
+    pub fn current() -> Self {
+        Self::resolve(&dirs::home_dir().unwrap_or_default(), |var| {
+            std::env::var_os(var)
+        })
+    }
+}
 
 /// Get the configuration directory path
 pub fn get_config_dir() -> PathBuf {
-    dirs::home_dir().unwrap_or_default().join(CONFIG_DIR_PATH)
+    VibePaths::current().config
+}
+
+/// Get the data directory path
+pub fn get_data_dir() -> PathBuf {
+    VibePaths::current().data
 }
 
 /// Get the default config file path
@@ -20,25 +117,138 @@ pub fn get_default_config_path() -> PathBuf {
 
 /// Get the state file path
 pub fn get_state_file_path() -> PathBuf {
-    get_config_dir().join("state.json")
+    VibePaths::current().state.join("state.json")
 }
 
 /// Get the templates directory path
 pub fn get_templates_dir() -> PathBuf {
-    get_config_dir().join("templates")
+    get_data_dir().join("templates")
+}
+
+/// Get the directory holding managed git hook scripts
+pub fn get_hooks_dir() -> PathBuf {
+    get_data_dir().join("hooks")
+}
+
+/// Get the directory holding source files for `vibe git files sync`
+pub fn get_managed_files_dir() -> PathBuf {
+    get_data_dir().join("managed")
+}
+
+/// Get the clone of the repository `vibe config sync` shares settings through
+pub fn get_sync_dir() -> PathBuf {
+    get_data_dir().join("sync")
 }
 
 /// Get the backups directory path
 pub fn get_backups_dir() -> PathBuf {
-    get_config_dir().join("backups")
+    get_data_dir().join("backups")
+}
+
+/// Get the default directory for global-mode worktrees
+pub fn get_worktrees_dir() -> PathBuf {
+    get_data_dir().join("worktrees")
 }
 
 /// Get the cache directory path
 pub fn get_cache_dir() -> PathBuf {
-    get_config_dir().join("cache")
+    VibePaths::current().cache
+}
+
+/// Get the event log database path for `vibe history`
+pub fn get_history_db_path() -> PathBuf {
+    VibePaths::current().state.join("history.db")
+}
+
+/// Get the directory holding removed repository entries and worktrees
+/// until they are restored or expire
+pub fn get_trash_dir() -> PathBuf {
+    get_data_dir().join("trash")
+}
+
+/// Get the file of config entries `vibe git scan --clean` removed
+pub fn get_removed_repos_path() -> PathBuf {
+    get_data_dir().join("removed-repos.yaml")
+}
+
+/// Get the log files directory path
+pub fn get_logs_dir() -> PathBuf {
+    VibePaths::current().state.join("logs")
 }
 
 /// Get app-specific template directory path
 pub fn get_app_template_dir(app_name: &str) -> PathBuf {
     get_templates_dir().join(app_name)
 }
+
+/// `path` with the home directory shortened to `~`, for user messages
+pub fn display_path(path: &Path) -> String {
+    match dirs::home_dir().and_then(|home| path.strip_prefix(home).ok().map(Path::to_path_buf)) {
+        Some(relative) if relative.as_os_str().is_empty() => "~".to_string(),
+        Some(relative) => format!("~/{}", relative.display()),
+        None => path.display().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn env<'a>(vars: &'a [(&'a str, &'a str)]) -> impl Fn(&str) -> Option<OsString> + 'a {
+        move |name| {
+            vars.iter()
+                .find(|(var, _)| *var == name)
+                .map(|(_, value)| OsString::from(value))
+        }
+    }
+
+    #[test]
+    fn test_vibe_home_overrides_everything() {
+        let home = TempDir::new().unwrap();
+        std::fs::create_dir_all(home.path().join(LEGACY_DIR_PATH)).unwrap();
+
+        let paths = VibePaths::resolve(
+            home.path(),
+            env(&[("VIBE_HOME", "/synced/vibe"), ("XDG_CONFIG_HOME", "/xdg")]),
+        );
+        assert_eq!(paths.layout, Layout::VibeHome);
+        assert_eq!(paths.config, PathBuf::from("/synced/vibe"));
+        assert_eq!(paths.cache, PathBuf::from("/synced/vibe/cache"));
+    }
+
+    #[test]
+    fn test_new_install_uses_xdg_defaults() {
+        let home = TempDir::new().unwrap();
+        let paths = VibePaths::resolve(home.path(), env(&[("XDG_CACHE_HOME", "/tmp/cache")]));
+
+        assert_eq!(paths.layout, Layout::Xdg);
+        assert_eq!(paths.config, home.path().join(".config/vibe"));
+        assert_eq!(paths.data, home.path().join(".local/share/vibe"));
+        assert_eq!(paths.cache, PathBuf::from("/tmp/cache/vibe"));
+        assert_eq!(paths.state, home.path().join(".local/state/vibe"));
+    }
+
+    #[test]
+    fn test_relative_xdg_variables_are_ignored() {
+        let home = TempDir::new().unwrap();
+        let paths = VibePaths::xdg(home.path(), env(&[("XDG_CONFIG_HOME", "relative")]));
+        assert_eq!(paths.config, home.path().join(".config/vibe"));
+    }
+
+    #[test]
+    fn test_existing_legacy_directory_is_kept_until_migrated() {
+        let home = TempDir::new().unwrap();
+        let legacy = home.path().join(LEGACY_DIR_PATH);
+        std::fs::create_dir_all(&legacy).unwrap();
+
+        let paths = VibePaths::resolve(home.path(), env(&[]));
+        assert_eq!(paths.layout, Layout::Legacy);
+        assert_eq!(paths.config, legacy);
+        assert_eq!(paths.cache, legacy.join("cache"));
+
+        std::fs::create_dir_all(home.path().join(".config/vibe")).unwrap();
+        let paths = VibePaths::resolve(home.path(), env(&[]));
+        assert_eq!(paths.layout, Layout::Xdg);
+    }
+}
@@ -0,0 +1,551 @@
+//! Commit authors across the workspace for `vibe stats contributors`
+//!
+//! Each repository's authors since the start of the window come from
+//! `git shortlog -sne HEAD`, so the repository's own `.mailmap` applies.
+//! `preferences.author_aliases` then merges identities across repositories,
+//! and identities sharing an email count as one author. Per-repository
+//! counts are kept in the repository cache, keyed by HEAD and the first day
+//! of the window, before aliases are applied, so editing the aliases needs
+//! no new reads.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local, Months, NaiveDate, TimeZone, Utc};
+use console::style;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tracing::warn;
+
+use crate::cache::{CachedContributors, RepositoryCache};
+use crate::display_println;
+use crate::output::delimited::Tabular;
+use crate::output::exit::CommandError;
+use crate::output::theme::{self, Role};
+use crate::worktree::metadata::parse_duration;
+
+use super::config::AuthorAlias;
+use super::operations::execute_git_command;
+
+/// Window covered unless `--since` says otherwise
+pub const DEFAULT_SINCE: &str = "3mo";
+
+/// First day of the window `value` describes, ending `today`: a date
+/// (`2024-01-01`), months or years (`3mo`, `1y`), or a duration such as `90d`
+pub fn parse_since(value: &str, today: NaiveDate) -> Result<NaiveDate> {
+    let value = value.trim();
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Ok(date);
+    }
+    let split = value.find(|c: char| !c.is_ascii_digit()).unwrap_or(0);
+    let (amount, unit) = value.split_at(split);
+    let months = match (amount.parse::<u32>(), unit) {
+        (Ok(n), "mo" | "month" | "months") => Some(n),
+        (Ok(n), "y" | "year" | "years") => n.checked_mul(12),
+        _ => None,
+    };
+    if let Some(months) = months.filter(|&n| n > 0) {
+        return today
+            .checked_sub_months(Months::new(months))
+            .ok_or_else(|| {
+                CommandError::Usage(format!("--since {value} reaches too far back")).into()
+            });
+    }
+    let duration = parse_duration(value).map_err(|_| {
+        CommandError::Usage(format!(
+            "Invalid --since '{value}': use a date (2024-01-01) or an age such as 90d, 3mo, or 1y"
+        ))
+    })?;
+    Ok(today - duration)
+}
+
+/// Commits by one author
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuthorCommits {
+    pub name: String,
+    pub email: String,
+    pub commits: usize,
+}
+
+/// Authors of one repository over the window, most commits first
+#[derive(Debug, Clone, Serialize)]
+pub struct RepoContributors {
+    pub repository: String,
+    pub commits: usize,
+    pub authors: Vec<AuthorCommits>,
+    /// Read from the cache instead of git
+    pub cached: bool,
+}
+
+/// One author's commits across the workspace
+#[derive(Debug, Clone, Serialize)]
+pub struct Contributor {
+    pub name: String,
+    pub email: String,
+    pub commits: usize,
+    /// Repository to commits, for repositories with any
+    pub repositories: BTreeMap<String, usize>,
+}
+
+/// The full `vibe stats contributors` result
+#[derive(Debug, Clone, Serialize)]
+pub struct ContributorReport {
+    pub since: NaiveDate,
+    /// Most commits first
+    pub contributors: Vec<Contributor>,
+    /// Every target repository, with or without commits in the window
+    pub repositories: Vec<RepoContributors>,
+    pub total_commits: usize,
+}
+
+/// Canonical identities by lowercased email and name, from the aliases
+struct Aliases {
+    by_email: HashMap<String, (String, String)>,
+    by_name: HashMap<String, (String, String)>,
+}
+
+impl Aliases {
+    fn new(aliases: &[AuthorAlias]) -> Self {
+        let mut by_email = HashMap::new();
+        let mut by_name = HashMap::new();
+        for alias in aliases {
+            let canonical = split_identity(&alias.author);
+            for identity in std::iter::once(&alias.author).chain(&alias.aliases) {
+                let (name, email) = split_identity(identity);
+                if !email.is_empty() {
+                    by_email.insert(email.to_lowercase(), canonical.clone());
+                } else if identity.contains('@') {
+                    by_email.insert(name.to_lowercase(), canonical.clone());
+                } else {
+                    by_name.insert(name.to_lowercase(), canonical.clone());
+                }
+            }
+        }
+        Self { by_email, by_name }
+    }
+
+    fn resolve(&self, author: &AuthorCommits) -> (String, String) {
+        self.by_email
+            .get(&author.email.to_lowercase())
+            .or_else(|| self.by_name.get(&author.name.to_lowercase()))
+            .cloned()
+            .unwrap_or_else(|| (author.name.clone(), author.email.clone()))
+    }
+}
+
+/// `Name <email>` as name and email; the email is empty without brackets
+fn split_identity(identity: &str) -> (String, String) {
+    match identity.trim().rsplit_once('<') {
+        Some((name, email)) => (
+            name.trim().to_string(),
+            email.trim_end_matches('>').trim().to_string(),
+        ),
+        None => (identity.trim().to_string(), String::new()),
+    }
+}
+
+/// Identities with the same email are one author
+fn author_key(name: &str, email: &str) -> String {
+    if email.is_empty() {
+        name.to_lowercase()
+    } else {
+        email.to_lowercase()
+    }
+}
+
+/// Authors merged by alias and then by email, keeping the name with the most
+/// commits for each
+fn merge_authors(authors: &[AuthorCommits], aliases: &Aliases) -> Vec<AuthorCommits> {
+    let mut merged: BTreeMap<String, (BTreeMap<String, usize>, String, usize)> = BTreeMap::new();
+    for author in authors {
+        let (name, email) = aliases.resolve(author);
+        let entry = merged
+            .entry(author_key(&name, &email))
+            .or_insert_with(|| (BTreeMap::new(), email, 0));
+        *entry.0.entry(name).or_default() += author.commits;
+        entry.2 += author.commits;
+    }
+    let mut merged: Vec<AuthorCommits> = merged
+        .into_values()
+        .map(|(names, email, commits)| AuthorCommits {
+            name: names
+                .iter()
+                .max_by(|a, b| a.1.cmp(b.1).then_with(|| b.0.cmp(a.0)))
+                .map(|(name, _)| name.clone())
+                .unwrap_or_default(),
+            email,
+            commits,
+        })
+        .collect();
+    merged.sort_by(|a, b| b.commits.cmp(&a.commits).then_with(|| a.name.cmp(&b.name)));
+    merged
+}
+
+impl ContributorReport {
+    pub fn new(
+        mut repositories: Vec<RepoContributors>,
+        since: NaiveDate,
+        aliases: &[AuthorAlias],
+    ) -> Self {
+        let aliases = Aliases::new(aliases);
+        let mut contributors: BTreeMap<String, Contributor> = BTreeMap::new();
+        for repo in &mut repositories {
+            repo.authors = merge_authors(&repo.authors, &aliases);
+            for author in &repo.authors {
+                let contributor = contributors
+                    .entry(author_key(&author.name, &author.email))
+                    .or_insert_with(|| Contributor {
+                        name: author.name.clone(),
+                        email: author.email.clone(),
+                        commits: 0,
+                        repositories: BTreeMap::new(),
+                    });
+                contributor.commits += author.commits;
+                contributor
+                    .repositories
+                    .insert(repo.repository.clone(), author.commits);
+            }
+        }
+
+        let mut contributors: Vec<Contributor> = contributors.into_values().collect();
+        contributors.sort_by(|a, b| b.commits.cmp(&a.commits).then_with(|| a.name.cmp(&b.name)));
+        repositories.sort_by(|a, b| {
+            b.commits
+                .cmp(&a.commits)
+                .then_with(|| a.repository.cmp(&b.repository))
+        });
+        let total_commits = repositories.iter().map(|repo| repo.commits).sum();
+
+        Self {
+            since,
+            contributors,
+            repositories,
+            total_commits,
+        }
+    }
+
+    pub fn print_table(&self) {
+        display_println!(
+            "{} Contributors since {}",
+            style("👥").blue(),
+            self.since.format("%Y-%m-%d")
+        );
+        if self.repositories.is_empty() {
+            display_println!("{} No repositories found", style("ℹ").yellow());
+            return;
+        }
+
+        let author_width = self
+            .contributors
+            .iter()
+            .map(|c| c.name.len() + c.email.len() + 3)
+            .max()
+            .unwrap_or(0)
+            .max("Author".len());
+        display_println!(
+            "{}",
+            style(format!(
+                "{:<author_width$}  {:>7}  Repositories",
+                "Author", "Commits"
+            ))
+            .bold()
+        );
+        for contributor in &self.contributors {
+            let mut repos: Vec<(&String, &usize)> = contributor.repositories.iter().collect();
+            repos.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+            let repos: Vec<String> = repos
+                .into_iter()
+                .map(|(repo, commits)| format!("{repo} {commits}"))
+                .collect();
+            display_println!(
+                "{:<author_width$}  {:>7}  {}",
+                format!("{} <{}>", contributor.name, contributor.email),
+                contributor.commits,
+                repos.join(", ")
+            );
+        }
+
+        let active = self.repositories.iter().filter(|r| r.commits > 0).count();
+        display_println!(
+            "{}",
+            style(format!(
+                "{:<author_width$}  {:>7}  {} {}, {} of {} repositories with commits",
+                "Total",
+                self.total_commits,
+                self.contributors.len(),
+                if self.contributors.len() == 1 {
+                    "author"
+                } else {
+                    "authors"
+                },
+                active,
+                self.repositories.len()
+            ))
+            .bold()
+        );
+        let idle: Vec<&str> = self
+            .repositories
+            .iter()
+            .filter(|r| r.commits == 0)
+            .map(|r| r.repository.as_str())
+            .collect();
+        if !idle.is_empty() {
+            display_println!(
+                "{}",
+                theme::paint(Role::Dim, format!("No commits: {}", idle.join(", ")))
+            );
+        }
+    }
+
+    /// One row per author and repository, for CSV and TSV export
+    pub fn rows(&self) -> Vec<ContributorRow<'_>> {
+        self.contributors
+            .iter()
+            .flat_map(|contributor| {
+                contributor
+                    .repositories
+                    .iter()
+                    .map(move |(repository, commits)| ContributorRow {
+                        author: &contributor.name,
+                        email: &contributor.email,
+                        repository,
+                        commits: *commits,
+                    })
+            })
+            .collect()
+    }
+}
+
+/// One author's commits in one repository
+#[derive(Debug, Serialize)]
+pub struct ContributorRow<'a> {
+    pub author: &'a str,
+    pub email: &'a str,
+    pub repository: &'a str,
+    pub commits: usize,
+}
+
+impl Tabular for ContributorRow<'_> {
+    const FIELDS: &'static [&'static str] = &["author", "email", "repository", "commits"];
+}
+
+/// `git shortlog -sne` lines: `  12\tName <email>`
+fn parse_shortlog(output: &str) -> Vec<AuthorCommits> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (count, identity) = line.trim().split_once('\t')?;
+            let (name, email) = split_identity(identity);
+            Some(AuthorCommits {
+                name,
+                email,
+                commits: count.trim().parse().ok()?,
+            })
+        })
+        .collect()
+}
+
+/// Collect the authors of `repos` at most `max_parallel` at a time, reusing
+/// cached counts whose fingerprint still matches. Results keep the order of
+/// `repos`; unreadable repositories become warnings.
+pub async fn collect_contributors(
+    repos: Vec<(String, PathBuf)>,
+    since: NaiveDate,
+    cache: Option<&RepositoryCache>,
+    max_parallel: usize,
+) -> (Vec<RepoContributors>, Vec<String>) {
+    let semaphore = Arc::new(Semaphore::new(max_parallel.max(1)));
+    let mut handles = Vec::with_capacity(repos.len());
+
+    for (name, path) in repos {
+        let cached = match cache {
+            Some(cache) => cache.get_contributors(&name).await.unwrap_or_else(|e| {
+                warn!("Failed to read cached contributors for {}: {}", name, e);
+                None
+            }),
+            None => None,
+        };
+        let semaphore = Arc::clone(&semaphore);
+        let task_name = name.clone();
+        let handle = tokio::spawn(async move {
+            let _permit = semaphore.acquire_owned().await.ok();
+            read_repo_contributors(task_name, path, since, cached).await
+        });
+        handles.push((name, handle));
+    }
+
+    let mut repositories = Vec::with_capacity(handles.len());
+    let mut warnings = Vec::new();
+    for (name, handle) in handles {
+        match handle.await {
+            Ok(Ok((repo, fresh))) => {
+                if let (Some(cache), Some(entry)) = (cache, fresh) {
+                    if let Err(e) = cache.cache_contributors(&entry).await {
+                        warn!("Failed to cache contributors for {}: {}", name, e);
+                    }
+                }
+                repositories.push(repo);
+            }
+            Ok(Err(e)) => warnings.push(format!("Failed to read authors of {name}: {e}")),
+            Err(e) => warnings.push(format!("Contributor task for {name} failed: {e}")),
+        }
+    }
+    (repositories, warnings)
+}
+
+/// Read one repository's authors, returning the cache entry to store when
+/// the cached one was missing or stale
+async fn read_repo_contributors(
+    name: String,
+    path: PathBuf,
+    since: NaiveDate,
+    cached: Option<CachedContributors>,
+) -> Result<(RepoContributors, Option<CachedContributors>)> {
+    if !path.exists() {
+        anyhow::bail!("{} does not exist", path.display());
+    }
+
+    let empty = RepoContributors {
+        repository: name.clone(),
+        commits: 0,
+        authors: Vec::new(),
+        cached: false,
+    };
+    // A repository without commits has no authors
+    let Ok(head) = execute_git_command(&path, &["rev-parse", "--verify", "--quiet", "HEAD"]).await
+    else {
+        return Ok((empty, None));
+    };
+    let fingerprint = format!("{head}:{since}");
+
+    let cached_authors = cached
+        .filter(|c| c.fingerprint == fingerprint)
+        .and_then(|c| serde_json::from_str::<Vec<AuthorCommits>>(&c.authors_json).ok());
+    let (authors, fresh) = match cached_authors {
+        Some(authors) => (authors, None),
+        None => {
+            let since_time: DateTime<Utc> = Local
+                .from_local_datetime(&since.and_hms_opt(0, 0, 0).unwrap_or_default())
+                .earliest()
+                .map(|time| time.with_timezone(&Utc))
+                .unwrap_or_else(|| since.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc());
+            let shortlog = execute_git_command(
+                &path,
+                &[
+                    "shortlog",
+                    "-sne",
+                    &format!("--since={}", since_time.to_rfc3339()),
+                    "HEAD",
+                ],
+            )
+            .await
+            .context("git shortlog failed")?;
+            let authors = parse_shortlog(&shortlog);
+            let entry = CachedContributors {
+                name: name.clone(),
+                fingerprint,
+                authors_json: serde_json::to_string(&authors)?,
+                computed_at: Utc::now(),
+            };
+            (authors, Some(entry))
+        }
+    };
+
+    Ok((
+        RepoContributors {
+            commits: authors.iter().map(|a| a.commits).sum(),
+            authors,
+            cached: fresh.is_none(),
+            ..empty
+        },
+        fresh,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::delimited::{render, Delimited};
+
+    fn author(name: &str, email: &str, commits: usize) -> AuthorCommits {
+        AuthorCommits {
+            name: name.to_string(),
+            email: email.to_string(),
+            commits,
+        }
+    }
+
+    #[test]
+    fn test_parse_since() {
+        let today = NaiveDate::from_ymd_opt(2024, 5, 31).unwrap();
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        assert_eq!(parse_since("3mo", today).unwrap(), date(2024, 2, 29));
+        assert_eq!(parse_since("1y", today).unwrap(), date(2023, 5, 31));
+        assert_eq!(parse_since("2w", today).unwrap(), date(2024, 5, 17));
+        assert_eq!(parse_since("2024-01-01", today).unwrap(), date(2024, 1, 1));
+        assert!(parse_since("soon", today).is_err());
+        assert!(parse_since("0mo", today).is_err());
+    }
+
+    #[test]
+    fn test_parse_shortlog() {
+        let output = "    12\tJane Doe <jane@example.com>\n     3\tbot <bot@ci>\n";
+        assert_eq!(
+            parse_shortlog(output),
+            vec![
+                author("Jane Doe", "jane@example.com", 12),
+                author("bot", "bot@ci", 3)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_report_merges_aliases_across_repositories() {
+        let aliases = vec![AuthorAlias {
+            author: "Jane Doe <jane@example.com>".to_string(),
+            aliases: vec!["jdoe@old.example".to_string(), "J. Doe".to_string()],
+        }];
+        let repos = vec![
+            RepoContributors {
+                repository: "api".to_string(),
+                commits: 6,
+                authors: vec![
+                    author("Jane Doe", "jane@example.com", 2),
+                    author("jane", "jdoe@old.example", 3),
+                    author("Sam", "sam@example.com", 1),
+                ],
+                cached: false,
+            },
+            RepoContributors {
+                repository: "web".to_string(),
+                commits: 4,
+                authors: vec![author("J. Doe", "j@laptop.local", 4)],
+                cached: false,
+            },
+            RepoContributors {
+                repository: "docs".to_string(),
+                commits: 0,
+                authors: Vec::new(),
+                cached: false,
+            },
+        ];
+
+        let report = ContributorReport::new(repos, NaiveDate::default(), &aliases);
+        assert_eq!(report.total_commits, 10);
+        assert_eq!(report.contributors.len(), 2);
+        let jane = &report.contributors[0];
+        assert_eq!(
+            (jane.name.as_str(), jane.email.as_str(), jane.commits),
+            ("Jane Doe", "jane@example.com", 9)
+        );
+        assert_eq!(jane.repositories["api"], 5);
+        assert_eq!(jane.repositories["web"], 4);
+        assert_eq!(report.repositories[0].authors.len(), 2);
+        assert_eq!(report.repositories[2].repository, "docs");
+
+        let csv = render(&report.rows(), Delimited::Csv, &[]).unwrap();
+        assert!(csv.starts_with("author,email,repository,commits\n"));
+        assert!(csv.contains("Jane Doe,jane@example.com,api,5\n"));
+    }
+}
@@ -86,6 +86,7 @@ pub fn get_repository_name<P: AsRef<Path>>(path: P) -> Option<String> {
 }
 
 /// Extract git remote URL if available
+#[cfg(feature = "native-git")]
 pub fn get_remote_url<P: AsRef<Path>>(repo_path: P) -> Result<Option<String>> {
     use git2::Repository;
 
@@ -122,7 +123,25 @@ pub fn get_remote_url<P: AsRef<Path>>(repo_path: P) -> Result<Option<String>> {
     Ok(remote.url().map(|url| url.to_string()))
 }
 
+/// URL of the `upstream` remote, which marks the repository as a fork
+#[cfg(feature = "native-git")]
+pub fn get_upstream_url<P: AsRef<Path>>(repo_path: P) -> Result<Option<String>> {
+    let repo = git2::Repository::open(&repo_path).with_context(|| {
+        format!(
+            "Failed to open git repository: {}",
+            repo_path.as_ref().display()
+        )
+    })?;
+
+    let url = match repo.find_remote("upstream") {
+        Ok(remote) => remote.url().map(|url| url.to_string()),
+        Err(_) => None,
+    };
+    Ok(url)
+}
+
 /// Get the current branch name
+#[cfg(feature = "native-git")]
 pub fn get_current_branch<P: AsRef<Path>>(repo_path: P) -> Result<Option<String>> {
     use git2::Repository;
 
@@ -150,6 +169,59 @@ pub fn get_current_branch<P: AsRef<Path>>(repo_path: P) -> Result<Option<String>
     Ok(branch_name)
 }
 
+/// Trimmed stdout of `git` run in `repo_path`, or `None` when git exits
+/// unsuccessfully or prints nothing
+#[cfg(not(feature = "native-git"))]
+fn git_output(repo_path: &Path, args: &[&str]) -> Result<Option<String>> {
+    let output = std::process::Command::new("git")
+        .args(args)
+        .current_dir(repo_path)
+        .output()
+        .with_context(|| format!("Failed to run git in {}", repo_path.display()))?;
+    if !output.status.success() {
+        return Ok(None);
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    Ok((!stdout.is_empty()).then_some(stdout))
+}
+
+/// Extract git remote URL if available
+#[cfg(not(feature = "native-git"))]
+pub fn get_remote_url<P: AsRef<Path>>(repo_path: P) -> Result<Option<String>> {
+    let repo_path = repo_path.as_ref();
+    let Some(remotes) = git_output(repo_path, &["remote"])? else {
+        debug!("No remotes found for repository: {}", repo_path.display());
+        return Ok(None);
+    };
+
+    // Try the 'origin' remote first, then fall back to the first remote
+    let remote_name = if remotes.lines().any(|name| name == "origin") {
+        "origin"
+    } else {
+        remotes.lines().next().unwrap_or("origin")
+    };
+    git_output(
+        repo_path,
+        &["config", "--get", &format!("remote.{remote_name}.url")],
+    )
+}
+
+/// URL of the `upstream` remote, which marks the repository as a fork
+#[cfg(not(feature = "native-git"))]
+pub fn get_upstream_url<P: AsRef<Path>>(repo_path: P) -> Result<Option<String>> {
+    git_output(
+        repo_path.as_ref(),
+        &["config", "--get", "remote.upstream.url"],
+    )
+}
+
+/// Get the current branch name
+#[cfg(not(feature = "native-git"))]
+pub fn get_current_branch<P: AsRef<Path>>(repo_path: P) -> Result<Option<String>> {
+    // Fails on an unborn branch, which has no commits yet
+    git_output(repo_path.as_ref(), &["rev-parse", "--abbrev-ref", "HEAD"])
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
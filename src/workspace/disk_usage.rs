@@ -0,0 +1,652 @@
+//! Per-repository disk usage for `vibe stats disk`
+//!
+//! Each checkout is walked once and split into its working tree, `.git`
+//! directory, and build caches (`node_modules`, `.venv`, and Cargo `target`
+//! directories). Nested repositories and linked worktrees are skipped by the
+//! walk; worktrees are measured separately on request. Results are kept in
+//! the repository cache and reused until the checkout's fingerprint, built
+//! from the modification times of its top-level entries and git metadata,
+//! changes.
+
+use anyhow::Result;
+use chrono::Utc;
+use console::style;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::UNIX_EPOCH;
+use tokio::sync::Semaphore;
+use tracing::warn;
+
+use crate::cache::{CachedDiskUsage, RepositoryCache};
+use crate::display_println;
+use crate::output::delimited::Tabular;
+use crate::output::theme::{self, Icon, Role};
+use crate::worktree::status::check_worktree_status;
+use crate::worktree::WorktreeManager;
+
+/// Directory names always treated as rebuildable caches
+const CACHE_DIR_NAMES: [&str; 2] = ["node_modules", ".venv"];
+
+/// Merge confidence `vibe git worktree clean` requires before removing a worktree
+const MIN_MERGE_CONFIDENCE: f32 = 0.7;
+
+/// A directory and the bytes of the files under it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DirSize {
+    pub path: PathBuf,
+    pub bytes: u64,
+}
+
+/// A linked worktree and whether its branch has been merged
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorktreeUsage {
+    pub path: PathBuf,
+    pub branch: String,
+    pub bytes: u64,
+    pub merged: bool,
+}
+
+/// Where one repository's disk space goes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepoDiskUsage {
+    pub name: String,
+    pub path: PathBuf,
+    /// Files outside `.git` and build caches
+    pub working_tree_bytes: u64,
+    pub git_bytes: u64,
+    pub build_caches: Vec<DirSize>,
+    /// Linked worktrees, when requested with `--include-worktrees`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub worktrees: Option<Vec<WorktreeUsage>>,
+    /// Whether this came from the cache rather than a fresh walk
+    #[serde(default)]
+    pub cached: bool,
+}
+
+impl Tabular for RepoDiskUsage {
+    const FIELDS: &'static [&'static str] =
+        &["name", "path", "working_tree_bytes", "git_bytes", "cached"];
+}
+
+impl RepoDiskUsage {
+    pub fn build_cache_bytes(&self) -> u64 {
+        self.build_caches.iter().map(|c| c.bytes).sum()
+    }
+
+    pub fn worktree_bytes(&self) -> u64 {
+        self.worktrees.iter().flatten().map(|w| w.bytes).sum()
+    }
+
+    pub fn total_bytes(&self) -> u64 {
+        self.working_tree_bytes + self.git_bytes + self.build_cache_bytes() + self.worktree_bytes()
+    }
+
+    /// Merged worktrees and build caches, which can be removed without
+    /// losing work
+    pub fn reclaimable_bytes(&self) -> u64 {
+        self.build_cache_bytes() + self.merged_worktrees().map(|w| w.bytes).sum::<u64>()
+    }
+
+    pub fn merged_worktrees(&self) -> impl Iterator<Item = &WorktreeUsage> {
+        self.worktrees.iter().flatten().filter(|w| w.merged)
+    }
+
+    /// Shell commands that reclaim space in this repository
+    pub fn cleanup_commands(&self) -> Vec<String> {
+        let path = self.path.display();
+        let mut commands = vec![format!("git -C '{path}' gc --prune=now")];
+        if self.merged_worktrees().next().is_some() {
+            commands.push(format!("(cd '{path}' && vibe git worktree clean)"));
+        }
+        for cache in &self.build_caches {
+            commands.push(format!("rm -rf '{}'", cache.path.display()));
+        }
+        commands
+    }
+}
+
+/// Disk usage across the workspace, largest repositories first
+#[derive(Debug, Clone, Serialize)]
+pub struct DiskReport {
+    pub repositories: Vec<RepoDiskUsage>,
+    /// Repositories measured before `--top` truncated the list
+    pub total_repositories: usize,
+    pub total_bytes: u64,
+    pub reclaimable_bytes: u64,
+}
+
+impl DiskReport {
+    /// Sort by size and keep the `top` largest; totals cover every repository
+    pub fn new(mut repositories: Vec<RepoDiskUsage>, top: Option<usize>) -> Self {
+        repositories.sort_by_key(|repo| std::cmp::Reverse(repo.total_bytes()));
+        let total_repositories = repositories.len();
+        let total_bytes = repositories.iter().map(RepoDiskUsage::total_bytes).sum();
+        let reclaimable_bytes = repositories
+            .iter()
+            .map(RepoDiskUsage::reclaimable_bytes)
+            .sum();
+        if let Some(top) = top {
+            repositories.truncate(top);
+        }
+        Self {
+            repositories,
+            total_repositories,
+            total_bytes,
+            reclaimable_bytes,
+        }
+    }
+
+    pub fn has_merged_worktrees(&self) -> bool {
+        self.repositories
+            .iter()
+            .any(|repo| repo.merged_worktrees().next().is_some())
+    }
+
+    pub fn print_table(&self) {
+        if self.repositories.is_empty() {
+            display_println!("{} No repositories to measure", style("ℹ").yellow());
+            return;
+        }
+
+        let show_worktrees = self.repositories.iter().any(|r| r.worktrees.is_some());
+        let name_width = self
+            .repositories
+            .iter()
+            .map(|r| r.name.len())
+            .max()
+            .unwrap_or(0)
+            .max("Repository".len());
+
+        let columns = |name: &str, repos: &[&RepoDiskUsage]| {
+            let sum = |f: fn(&RepoDiskUsage) -> u64| repos.iter().map(|r| f(r)).sum::<u64>();
+            let mut row = format!(
+                "{name:<name_width$}  {:>10}  {:>10}  {:>10}",
+                format_bytes(sum(|r| r.working_tree_bytes)),
+                format_bytes(sum(|r| r.git_bytes)),
+                format_bytes(sum(RepoDiskUsage::build_cache_bytes))
+            );
+            if show_worktrees {
+                row.push_str(&format!(
+                    "  {:>10}",
+                    format_bytes(sum(RepoDiskUsage::worktree_bytes))
+                ));
+            }
+            row.push_str(&format!(
+                "  {:>10}",
+                format_bytes(sum(RepoDiskUsage::total_bytes))
+            ));
+            (row, sum(RepoDiskUsage::reclaimable_bytes))
+        };
+
+        let mut header = format!(
+            "{:<name_width$}  {:>10}  {:>10}  {:>10}",
+            "Repository", "Working", ".git", "Caches"
+        );
+        if show_worktrees {
+            header.push_str(&format!("  {:>10}", "Worktrees"));
+        }
+        header.push_str(&format!("  {:>10}  {:>11}", "Total", "Reclaimable"));
+        display_println!("{}", style(&header).bold());
+
+        for repo in &self.repositories {
+            let (row, reclaimable) = columns(&repo.name, &[repo]);
+            let reclaimable_text = format!("{:>11}", format_bytes(reclaimable));
+            if reclaimable > 0 {
+                display_println!("{row}  {}", theme::paint(Role::Warning, reclaimable_text));
+            } else {
+                display_println!("{row}  {reclaimable_text}");
+            }
+        }
+
+        let shown: Vec<&RepoDiskUsage> = self.repositories.iter().collect();
+        let (row, reclaimable) = columns("Total", &shown);
+        display_println!(
+            "{}",
+            style(format!("{row}  {:>11}", format_bytes(reclaimable))).bold()
+        );
+
+        if self.total_repositories > self.repositories.len() {
+            display_println!(
+                "{}",
+                theme::paint(
+                    Role::Dim,
+                    format!(
+                        "Top {} of {} repositories; all together: {}, {} reclaimable",
+                        self.repositories.len(),
+                        self.total_repositories,
+                        format_bytes(self.total_bytes),
+                        format_bytes(self.reclaimable_bytes)
+                    )
+                )
+            );
+        }
+    }
+
+    /// Per-repository commands for everything reclaimable
+    pub fn print_cleanup_commands(&self) {
+        for repo in self
+            .repositories
+            .iter()
+            .filter(|r| r.reclaimable_bytes() > 0)
+        {
+            display_println!();
+            display_println!(
+                "{} {} ({} reclaimable)",
+                theme::icon(Icon::Tip),
+                style(&repo.name).bold(),
+                format_bytes(repo.reclaimable_bytes())
+            );
+            for command in repo.cleanup_commands() {
+                display_println!("  {command}");
+            }
+        }
+    }
+}
+
+/// Measure `repos` at most `max_parallel` at a time, reusing cached sizes
+/// whose fingerprint still matches unless `refresh` is set
+pub async fn collect_disk_usage(
+    repos: Vec<(String, PathBuf)>,
+    include_worktrees: bool,
+    refresh: bool,
+    cache: &RepositoryCache,
+    max_parallel: usize,
+) -> (Vec<RepoDiskUsage>, Vec<String>) {
+    let semaphore = Arc::new(Semaphore::new(max_parallel.max(1)));
+    let mut handles = Vec::with_capacity(repos.len());
+
+    for (name, path) in repos {
+        let cached = if refresh {
+            None
+        } else {
+            cache.get_disk_usage(&name).await.unwrap_or_else(|e| {
+                warn!("Failed to read cached disk usage for {}: {}", name, e);
+                None
+            })
+        };
+        let semaphore = Arc::clone(&semaphore);
+        let task_name = name.clone();
+        let handle = tokio::spawn(async move {
+            let _permit = semaphore.acquire_owned().await.ok();
+            measure_repository(task_name, path, include_worktrees, cached).await
+        });
+        handles.push((name, handle));
+    }
+
+    let mut usages = Vec::with_capacity(handles.len());
+    let mut warnings = Vec::new();
+    for (name, handle) in handles {
+        match handle.await {
+            Ok(Ok((usage, fresh))) => {
+                if let Some(entry) = fresh {
+                    if let Err(e) = cache.cache_disk_usage(&entry).await {
+                        warn!("Failed to cache disk usage for {}: {}", name, e);
+                    }
+                }
+                usages.push(usage);
+            }
+            Ok(Err(e)) => warnings.push(format!("Failed to measure {name}: {e}")),
+            Err(e) => warnings.push(format!("Disk usage task for {name} failed: {e}")),
+        }
+    }
+    (usages, warnings)
+}
+
+/// Measure one repository, returning the cache entry to store when the
+/// cached one was missing or stale
+async fn measure_repository(
+    name: String,
+    path: PathBuf,
+    include_worktrees: bool,
+    cached: Option<CachedDiskUsage>,
+) -> Result<(RepoDiskUsage, Option<CachedDiskUsage>)> {
+    if !path.exists() {
+        anyhow::bail!("{} does not exist", path.display());
+    }
+
+    let worktrees = if include_worktrees {
+        let manager = WorktreeManager::new(path.clone(), None).await?;
+        // `git worktree list` always reports the main checkout first
+        manager
+            .list_worktrees()
+            .await?
+            .into_iter()
+            .skip(1)
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    let fingerprint_paths: Vec<PathBuf> = std::iter::once(path.clone())
+        .chain(worktrees.iter().map(|w| w.path.clone()))
+        .collect();
+    let fingerprint = tokio::task::spawn_blocking(move || fingerprint(&fingerprint_paths)).await?;
+
+    if let Some(usage) = cached
+        .filter(|c| c.fingerprint == fingerprint)
+        .and_then(|c| serde_json::from_str::<RepoDiskUsage>(&c.usage_json).ok())
+        .filter(|usage| usage.worktrees.is_some() || !include_worktrees)
+    {
+        return Ok((
+            RepoDiskUsage {
+                worktrees: usage.worktrees.filter(|_| include_worktrees),
+                cached: true,
+                ..usage
+            },
+            None,
+        ));
+    }
+
+    let walk_path = path.clone();
+    let checkout = tokio::task::spawn_blocking(move || measure_checkout(&walk_path)).await?;
+
+    let worktrees = if include_worktrees {
+        let mut usages = Vec::with_capacity(worktrees.len());
+        for worktree in worktrees {
+            let merged = check_worktree_status(&worktree.path)
+                .await
+                .ok()
+                .and_then(|status| status.merge_info)
+                .is_some_and(|info| info.is_merged && info.confidence >= MIN_MERGE_CONFIDENCE);
+            let walk_path = worktree.path.clone();
+            let size = tokio::task::spawn_blocking(move || measure_checkout(&walk_path)).await?;
+            usages.push(WorktreeUsage {
+                path: worktree.path,
+                branch: worktree.branch,
+                bytes: size.total_bytes(),
+                merged,
+            });
+        }
+        Some(usages)
+    } else {
+        None
+    };
+
+    let usage = RepoDiskUsage {
+        name: name.clone(),
+        path,
+        working_tree_bytes: checkout.working_tree_bytes,
+        git_bytes: checkout.git_bytes,
+        build_caches: checkout.build_caches,
+        worktrees,
+        cached: false,
+    };
+    let entry = CachedDiskUsage {
+        name,
+        fingerprint,
+        usage_json: serde_json::to_string(&usage)?,
+        computed_at: Utc::now(),
+    };
+    Ok((usage, Some(entry)))
+}
+
+/// Sizes from walking a single checkout
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct CheckoutSize {
+    pub working_tree_bytes: u64,
+    pub git_bytes: u64,
+    pub build_caches: Vec<DirSize>,
+}
+
+impl CheckoutSize {
+    pub fn total_bytes(&self) -> u64 {
+        self.working_tree_bytes
+            + self.git_bytes
+            + self.build_caches.iter().map(|c| c.bytes).sum::<u64>()
+    }
+}
+
+/// Walk `root`, attributing each file to the working tree, `.git`, or a
+/// build cache. Symlinks are not followed.
+pub fn measure_checkout(root: &Path) -> CheckoutSize {
+    let mut size = CheckoutSize::default();
+    let git_dir = root.join(".git");
+    if git_dir.is_dir() {
+        size.git_bytes = dir_size(&git_dir);
+    }
+    walk_working_tree(root, root, &mut size);
+    size.build_caches
+        .sort_by_key(|cache| std::cmp::Reverse(cache.bytes));
+    size
+}
+
+fn walk_working_tree(root: &Path, dir: &Path, size: &mut CheckoutSize) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        let path = entry.path();
+        if !metadata.is_dir() {
+            size.working_tree_bytes += metadata.len();
+            continue;
+        }
+        if dir == root && entry.file_name() == ".git" {
+            continue;
+        }
+        if is_build_cache(dir, &entry.file_name().to_string_lossy()) {
+            size.build_caches.push(DirSize {
+                bytes: dir_size(&path),
+                path,
+            });
+            continue;
+        }
+        // Nested repositories, submodules, and local-mode worktrees
+        if path.join(".git").exists() {
+            continue;
+        }
+        walk_working_tree(root, &path, size);
+    }
+}
+
+/// `node_modules` and `.venv` anywhere, and `target` next to a Cargo.toml
+fn is_build_cache(parent: &Path, name: &str) -> bool {
+    CACHE_DIR_NAMES.contains(&name) || (name == "target" && parent.join("Cargo.toml").is_file())
+}
+
+/// Total size of the files under `dir`
+pub fn dir_size(dir: &Path) -> u64 {
+    walkdir::WalkDir::new(dir)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| entry.metadata().ok())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+/// Hash of the modification times that change when a checkout's size is
+/// likely to: each root, its top-level entries, and its git metadata
+fn fingerprint(roots: &[PathBuf]) -> String {
+    let mut hasher = DefaultHasher::new();
+    for root in roots {
+        let mut paths = vec![root.clone()];
+        if let Ok(entries) = std::fs::read_dir(root) {
+            paths.extend(entries.flatten().map(|entry| entry.path()));
+        }
+        for git_path in [
+            "index",
+            "HEAD",
+            "packed-refs",
+            "refs/heads",
+            "objects/pack",
+            "worktrees",
+        ] {
+            paths.push(root.join(".git").join(git_path));
+        }
+        paths.sort();
+
+        for path in paths {
+            let modified = std::fs::symlink_metadata(&path)
+                .and_then(|metadata| metadata.modified())
+                .ok()
+                .and_then(|time| time.duration_since(UNIX_EPOCH).ok());
+            path.hash(&mut hasher);
+            modified.hash(&mut hasher);
+        }
+    }
+    format!("{:016x}", hasher.finish())
+}
+
+/// Human-readable size using binary units, e.g. `1.5 GiB`
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(path: &Path, bytes: usize) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, vec![b'x'; bytes]).unwrap();
+    }
+
+    fn usage(name: &str, working: u64, caches: u64, merged_worktree: Option<u64>) -> RepoDiskUsage {
+        RepoDiskUsage {
+            name: name.to_string(),
+            path: PathBuf::from("/ws").join(name),
+            working_tree_bytes: working,
+            git_bytes: 0,
+            build_caches: vec![DirSize {
+                path: PathBuf::from("/ws").join(name).join("node_modules"),
+                bytes: caches,
+            }],
+            worktrees: merged_worktree.map(|bytes| {
+                vec![WorktreeUsage {
+                    path: PathBuf::from("/ws/wt"),
+                    branch: "done".to_string(),
+                    bytes,
+                    merged: true,
+                }]
+            }),
+            cached: false,
+        }
+    }
+
+    #[test]
+    fn test_measure_checkout_splits_sizes() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        write(&root.join(".git/objects/pack/pack-1.pack"), 300);
+        write(&root.join("src/main.rs"), 100);
+        write(&root.join("Cargo.toml"), 10);
+        write(&root.join("target/debug/app"), 500);
+        write(&root.join("web/node_modules/lib/index.js"), 200);
+        // Not a Cargo target directory
+        write(&root.join("docs/target/notes.md"), 5);
+        // Local-mode worktree, measured separately
+        write(&root.join(".worktrees/feature/.git"), 40);
+        write(&root.join(".worktrees/feature/big.bin"), 1000);
+
+        let size = measure_checkout(root);
+        assert_eq!(size.git_bytes, 300);
+        assert_eq!(size.working_tree_bytes, 115);
+        assert_eq!(
+            size.build_caches,
+            vec![
+                DirSize {
+                    path: root.join("target"),
+                    bytes: 500
+                },
+                DirSize {
+                    path: root.join("web/node_modules"),
+                    bytes: 200
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fingerprint_changes_with_top_level_entries() {
+        let temp_dir = TempDir::new().unwrap();
+        let roots = vec![temp_dir.path().to_path_buf()];
+        let before = fingerprint(&roots);
+        assert_eq!(before, fingerprint(&roots));
+
+        write(&temp_dir.path().join("new.txt"), 1);
+        assert_ne!(before, fingerprint(&roots));
+    }
+
+    #[test]
+    fn test_report_sorts_truncates_and_totals() {
+        let report = DiskReport::new(
+            vec![
+                usage("small", 10, 0, None),
+                usage("large", 1000, 50, Some(25)),
+                usage("medium", 100, 5, None),
+            ],
+            Some(2),
+        );
+
+        let names: Vec<_> = report
+            .repositories
+            .iter()
+            .map(|r| r.name.as_str())
+            .collect();
+        assert_eq!(names, ["large", "medium"]);
+        assert_eq!(report.total_repositories, 3);
+        assert_eq!(report.total_bytes, 1075 + 105 + 10);
+        assert_eq!(report.reclaimable_bytes, 75 + 5);
+        assert!(report.has_merged_worktrees());
+    }
+
+    #[test]
+    fn test_cleanup_commands() {
+        let commands = usage("api", 1, 2, Some(3)).cleanup_commands();
+        assert_eq!(
+            commands,
+            [
+                "git -C '/ws/api' gc --prune=now",
+                "(cd '/ws/api' && vibe git worktree clean)",
+                "rm -rf '/ws/api/node_modules'",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024 * 1024), "3.0 GiB");
+    }
+
+    #[tokio::test]
+    async fn test_collect_disk_usage_reuses_cache() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = temp_dir.path().join("repo");
+        write(&repo.join("file.txt"), 64);
+        let cache = RepositoryCache::new(temp_dir.path().join("repositories.db"));
+        cache.initialize().await.unwrap();
+        let repos = vec![("repo".to_string(), repo.clone())];
+
+        let (first, warnings) = collect_disk_usage(repos.clone(), false, false, &cache, 2).await;
+        assert!(warnings.is_empty());
+        assert_eq!(first[0].working_tree_bytes, 64);
+        assert!(!first[0].cached);
+
+        let (second, _) = collect_disk_usage(repos.clone(), false, false, &cache, 2).await;
+        assert!(second[0].cached);
+        assert_eq!(second[0].working_tree_bytes, 64);
+
+        let (refreshed, _) = collect_disk_usage(repos, false, true, &cache, 2).await;
+        assert!(!refreshed[0].cached);
+    }
+}
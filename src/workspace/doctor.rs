@@ -0,0 +1,608 @@
+//! Environment diagnostics for `vibe doctor`
+//!
+//! Each check reports pass, warn, or fail with a remediation hint. Checks
+//! never assume the config file parses, so the report is still useful when
+//! every other command is failing.
+
+use console::style;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use tokio::process::Command;
+
+use super::config::{repo_abs_path, WorkspaceConfig};
+use super::config_loader;
+use super::config_validator;
+use super::constants;
+use crate::display_println;
+use crate::git::lfs;
+use crate::output::theme::{self, Icon, Role};
+use crate::utils::platform::resolve_command;
+
+/// Oldest git that supports every worktree subcommand vibe runs
+/// (`git worktree remove` arrived in 2.17)
+pub const MIN_GIT_VERSION: (u32, u32) = (2, 17);
+
+const CACHE_DATABASES: [&str; 2] = ["repositories.db", "git_status.db"];
+const APPS: [&str; 6] = ["vscode", "cursor", "windsurf", "warp", "iterm2", "wezterm"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Pass,
+    /// Something degrades but vibe still works
+    Warn,
+    /// vibe can't work until this is fixed
+    Fail,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckResult {
+    /// Stable identifier, e.g. `git` or `cache:repositories.db`
+    pub id: String,
+    pub status: CheckStatus,
+    pub message: String,
+    /// What to do about a warning or failure
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+}
+
+impl CheckResult {
+    fn pass(id: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            status: CheckStatus::Pass,
+            message: message.into(),
+            hint: None,
+        }
+    }
+
+    fn warn(id: impl Into<String>, message: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            status: CheckStatus::Warn,
+            message: message.into(),
+            hint: Some(hint.into()),
+        }
+    }
+
+    fn fail(id: impl Into<String>, message: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            status: CheckStatus::Fail,
+            message: message.into(),
+            hint: Some(hint.into()),
+        }
+    }
+}
+
+/// Everything `vibe doctor` found, in a shape suitable for bug reports
+#[derive(Debug, Clone, Serialize)]
+pub struct DoctorReport {
+    pub vibe_version: &'static str,
+    pub os: &'static str,
+    pub arch: &'static str,
+    pub config_path: PathBuf,
+    pub checks: Vec<CheckResult>,
+}
+
+impl DoctorReport {
+    pub fn count(&self, status: CheckStatus) -> usize {
+        self.checks.iter().filter(|c| c.status == status).count()
+    }
+
+    pub fn has_failures(&self) -> bool {
+        self.count(CheckStatus::Fail) > 0
+    }
+
+    pub fn print_report(&self) {
+        display_println!(
+            "{} vibe {} ({}/{})",
+            style("🩺").blue(),
+            self.vibe_version,
+            self.os,
+            self.arch
+        );
+        display_println!();
+
+        for check in &self.checks {
+            let (icon, role) = match check.status {
+                CheckStatus::Pass => (Icon::Success, Role::Success),
+                CheckStatus::Warn => (Icon::Warning, Role::Warning),
+                CheckStatus::Fail => (Icon::Error, Role::Error),
+            };
+            display_println!(
+                "{} {} {}",
+                theme::paint(role, theme::icon(icon)),
+                style(&check.id).bold(),
+                check.message
+            );
+            if let Some(hint) = &check.hint {
+                display_println!(
+                    "   {}",
+                    theme::paint(Role::Dim, theme::labeled(Icon::Tip, hint))
+                );
+            }
+        }
+
+        display_println!();
+        display_println!(
+            "{} passed, {} warnings, {} failed",
+            self.count(CheckStatus::Pass),
+            self.count(CheckStatus::Warn),
+            self.count(CheckStatus::Fail)
+        );
+    }
+}
+
+/// Run every check against the config at `config_path`
+pub async fn run_checks(config_path: &Path, root_override: Option<&Path>) -> DoctorReport {
+    let mut checks = vec![check_git().await];
+    checks.extend(check_config_dirs().await);
+
+    let (config_check, config) = check_config(config_path).await;
+    checks.extend(config_check);
+    let mut config = config.unwrap_or_default();
+    if let Some(root) = root_override {
+        config.workspace.root = root.to_path_buf();
+    }
+
+    checks.push(check_workspace_root(&config).await);
+    checks.push(check_worktree_config(&config));
+    checks.push(check_github_cli().await);
+    checks.push(check_gitlab_cli(&config).await);
+    checks.push(check_git_lfs(&config).await);
+    if let Some(check) = check_direnv(&config).await {
+        checks.push(check);
+    }
+    for db in CACHE_DATABASES {
+        checks.push(check_cache_database(&constants::get_cache_dir().join(db)).await);
+    }
+    for app in APPS {
+        checks.push(check_app(&config, app).await);
+    }
+
+    DoctorReport {
+        vibe_version: env!("CARGO_PKG_VERSION"),
+        os: std::env::consts::OS,
+        arch: std::env::consts::ARCH,
+        config_path: config_path.to_path_buf(),
+        checks,
+    }
+}
+
+/// Run `program args`, or `None` when it isn't installed
+async fn run_tool(program: &str, args: &[&str]) -> Option<std::process::Output> {
+    Command::new(resolve_command(program))
+        .args(args)
+        .output()
+        .await
+        .ok()
+}
+
+async fn check_git() -> CheckResult {
+    let hint = "Install git from https://git-scm.com/downloads";
+    let Some(output) = run_tool("git", &["--version"]).await else {
+        return CheckResult::fail("git", "git is not installed", hint);
+    };
+    let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+
+    match parse_git_version(&version) {
+        Some(found) if found >= MIN_GIT_VERSION => CheckResult::pass("git", version),
+        Some(_) => CheckResult::fail(
+            "git",
+            format!(
+                "{version} is older than the required {}.{}",
+                MIN_GIT_VERSION.0, MIN_GIT_VERSION.1
+            ),
+            "Upgrade git to use worktree commands",
+        ),
+        None => CheckResult::warn(
+            "git",
+            format!("Unrecognized version output: {version}"),
+            "Check that `git --version` runs the git you expect",
+        ),
+    }
+}
+
+/// `(major, minor)` from `git version 2.39.2 (Apple Git-143)` and similar
+pub fn parse_git_version(output: &str) -> Option<(u32, u32)> {
+    let version = output.strip_prefix("git version ")?;
+    let mut parts = version.split(['.', ' ']);
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next()?.parse().ok()?;
+    Some((major, minor))
+}
+
+async fn check_github_cli() -> CheckResult {
+    let Some(output) = run_tool("gh", &["--version"]).await else {
+        return CheckResult::warn(
+            "github-cli",
+            "gh is not installed; GitHub search, bulk clone, and PR merge detection are unavailable",
+            "Install it with `vibe apps install` or from https://cli.github.com",
+        );
+    };
+    let version = String::from_utf8_lossy(&output.stdout);
+    let version = version
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_string();
+
+    match run_tool("gh", &["auth", "status"]).await {
+        Some(auth) if auth.status.success() => {
+            CheckResult::pass("github-cli", format!("{version}, authenticated"))
+        }
+        _ => CheckResult::warn(
+            "github-cli",
+            format!("{version}, not authenticated"),
+            "Run `gh auth login`",
+        ),
+    }
+}
+
+async fn check_gitlab_cli(config: &WorkspaceConfig) -> CheckResult {
+    let gitlab_repos = config
+        .repositories
+        .iter()
+        .filter(|repo| {
+            repo.url
+                .as_deref()
+                .is_some_and(|url| url.contains("gitlab"))
+        })
+        .count();
+
+    if run_tool("glab", &["--version"]).await.is_none() {
+        if gitlab_repos == 0 {
+            return CheckResult::pass("gitlab-cli", "glab not installed (no GitLab repositories)");
+        }
+        return CheckResult::warn(
+            "gitlab-cli",
+            format!("glab is not installed but {gitlab_repos} repositories are on GitLab"),
+            "Install glab from https://gitlab.com/gitlab-org/cli",
+        );
+    }
+
+    match run_tool("glab", &["auth", "status"]).await {
+        Some(auth) if auth.status.success() => {
+            CheckResult::pass("gitlab-cli", "glab installed, authenticated")
+        }
+        _ => CheckResult::warn(
+            "gitlab-cli",
+            "glab installed, not authenticated",
+            "Run `glab auth login`",
+        ),
+    }
+}
+
+async fn check_git_lfs(config: &WorkspaceConfig) -> CheckResult {
+    let mut lfs_repos = 0;
+    for repo in &config.repositories {
+        if lfs::uses_lfs(&repo_abs_path(config, repo)).await {
+            lfs_repos += 1;
+        }
+    }
+
+    let Some(version) = lfs::lfs_version().await else {
+        if lfs_repos == 0 {
+            return CheckResult::pass("git-lfs", "git-lfs not installed (no repositories use LFS)");
+        }
+        return CheckResult::warn(
+            "git-lfs",
+            format!(
+                "git-lfs is not installed but {lfs_repos} repositories use LFS; their large files are pointer files"
+            ),
+            format!(
+                "Install it with `{}`, then run `git lfs install && git lfs pull` in each",
+                lfs::install_hint()
+            ),
+        );
+    };
+
+    let filter = run_tool("git", &["config", "--global", "--get", "filter.lfs.smudge"]).await;
+    if lfs_repos > 0 && !filter.is_some_and(|output| output.status.success()) {
+        return CheckResult::warn(
+            "git-lfs",
+            format!("{version}, but the LFS filter is not set up"),
+            "Run `git lfs install`",
+        );
+    }
+    CheckResult::pass("git-lfs", version)
+}
+
+/// direnv is only checked when one of the direnv preferences is set
+async fn check_direnv(config: &WorkspaceConfig) -> Option<CheckResult> {
+    let preferences = config.preferences.as_ref()?;
+    if !preferences.enable_direnv && !preferences.auto_allow_direnv {
+        return None;
+    }
+
+    Some(match run_tool("direnv", &["version"]).await {
+        Some(output) if output.status.success() => CheckResult::pass(
+            "direnv",
+            format!("direnv {}", String::from_utf8_lossy(&output.stdout).trim()),
+        ),
+        _ => CheckResult::fail(
+            "direnv",
+            "direnv is enabled in preferences but not installed",
+            "Install direnv from https://direnv.net or set preferences.enable_direnv: false",
+        ),
+    })
+}
+
+/// Parse the config file, returning the config for later checks when it loads
+async fn check_config(config_path: &Path) -> (Vec<CheckResult>, Option<WorkspaceConfig>) {
+    let contents = match tokio::fs::read_to_string(config_path).await {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            let check = CheckResult::warn(
+                "config",
+                format!("{} does not exist; using defaults", config_path.display()),
+                "Run `vibe setup` to create a workspace",
+            );
+            return (vec![check], None);
+        }
+        Err(e) => {
+            let check = CheckResult::fail(
+                "config",
+                format!("Cannot read {}: {e}", config_path.display()),
+                "Check the file's permissions",
+            );
+            return (vec![check], None);
+        }
+    };
+
+    let config: WorkspaceConfig = match config_loader::parse_config(&contents) {
+        Ok(config) => config,
+        Err(e) => {
+            let check = CheckResult::fail(
+                "config",
+                format!("{} does not parse: {e}", config_path.display()),
+                "Run `vibe config validate` for every problem, or restore a backup with `vibe config restore`",
+            );
+            return (vec![check], None);
+        }
+    };
+
+    let mut checks = vec![CheckResult::pass(
+        "config",
+        format!(
+            "{} ({} repositories)",
+            config_path.display(),
+            config.repositories.len()
+        ),
+    )];
+
+    if let Some(page_sizes) = config.preferences.as_ref().map(|p| &p.page_sizes) {
+        if let Err(e) = page_sizes.validate() {
+            checks.push(CheckResult::fail(
+                "config:preferences",
+                e.to_string(),
+                "Fix preferences.page_sizes in config.yaml",
+            ));
+        }
+    }
+
+    let workspace_root = crate::utils::fs::expand_tilde(&config.workspace.root);
+    if let Ok(report) = config_validator::validate_config(&config, &workspace_root) {
+        if report.has_issues() {
+            checks.push(CheckResult::warn(
+                "config:repositories",
+                format!(
+                    "{} duplicate entries, {} warnings",
+                    report.duplicates.len(),
+                    report.warnings.len()
+                ),
+                "Run `vibe config validate` for details",
+            ));
+        }
+    }
+
+    (checks, Some(config))
+}
+
+async fn check_workspace_root(config: &WorkspaceConfig) -> CheckResult {
+    let root = crate::utils::fs::expand_tilde(&config.workspace.root);
+    match tokio::fs::metadata(&root).await {
+        Ok(metadata) if metadata.is_dir() => {
+            CheckResult::pass("workspace-root", root.display().to_string())
+        }
+        Ok(_) => CheckResult::fail(
+            "workspace-root",
+            format!("{} is not a directory", root.display()),
+            "Point workspace.root in config.yaml at a directory",
+        ),
+        Err(_) => CheckResult::fail(
+            "workspace-root",
+            format!("{} does not exist", root.display()),
+            "Create the directory or update workspace.root in config.yaml",
+        ),
+    }
+}
+
+fn check_worktree_config(config: &WorkspaceConfig) -> CheckResult {
+    match config.worktree.validate() {
+        Ok(()) => CheckResult::pass(
+            "worktree-config",
+            format!(
+                "{:?} mode, prefix '{}'",
+                config.worktree.mode, config.worktree.prefix
+            ),
+        ),
+        Err(e) => CheckResult::fail(
+            "worktree-config",
+            e,
+            "Fix the worktree section of config.yaml or the VIBE_WORKTREE_* variables",
+        ),
+    }
+}
+
+async fn check_cache_database(path: &Path) -> CheckResult {
+    let id = format!(
+        "cache:{}",
+        path.file_name().unwrap_or_default().to_string_lossy()
+    );
+    if !path.exists() {
+        return CheckResult::pass(id, "not created yet");
+    }
+
+    let hint = format!("Delete {}; it is rebuilt on the next run", path.display());
+    let conn = match tokio_rusqlite::Connection::open(path).await {
+        Ok(conn) => conn,
+        Err(e) => return CheckResult::fail(id, format!("Cannot open: {e}"), hint),
+    };
+    let result = conn
+        .call(|conn| {
+            let mut stmt = conn.prepare("PRAGMA integrity_check")?;
+            let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+            Ok(rows.collect::<Result<Vec<_>, _>>()?)
+        })
+        .await;
+
+    match result {
+        Ok(rows) if rows == ["ok"] => CheckResult::pass(id, "integrity ok"),
+        Ok(rows) => CheckResult::fail(id, rows.join("; "), hint),
+        Err(e) => CheckResult::fail(id, format!("Integrity check failed: {e}"), hint),
+    }
+}
+
+async fn check_app(config: &WorkspaceConfig, app: &str) -> CheckResult {
+    let id = format!("app:{app}");
+    let used_by = config
+        .repositories
+        .iter()
+        .filter(|repo| repo.is_app_enabled(app))
+        .count();
+
+    if crate::apps::is_app_available(app).await {
+        return CheckResult::pass(id, format!("installed, used by {used_by} repositories"));
+    }
+    if used_by == 0 {
+        return CheckResult::pass(id, "not installed (not used by any repository)");
+    }
+    CheckResult::warn(
+        id,
+        format!("not installed but configured for {used_by} repositories"),
+        "Install it with `vibe apps install`",
+    )
+}
+
+/// Whether vibe can write its config, cache, and templates
+async fn check_config_dirs() -> Vec<CheckResult> {
+    let paths = constants::VibePaths::current();
+    let mut checks = Vec::new();
+    for (name, dir) in [
+        ("config", &paths.config),
+        ("data", &paths.data),
+        ("cache", &paths.cache),
+        ("state", &paths.state),
+    ] {
+        let id = format!("dir:{name}");
+        let shown = constants::display_path(dir);
+        if !dir.exists() {
+            checks.push(CheckResult::warn(
+                id,
+                format!("{shown} does not exist"),
+                "Run `vibe setup` to create it",
+            ));
+            continue;
+        }
+
+        let probe = dir.join(".doctor-write-test");
+        let writable = tokio::fs::write(&probe, b"").await.is_ok();
+        let _ = tokio::fs::remove_file(&probe).await;
+        checks.push(if writable {
+            CheckResult::pass(id, format!("{shown} is writable"))
+        } else {
+            CheckResult::fail(
+                id,
+                format!("{shown} is not writable"),
+                format!("Fix the permissions on {}", dir.display()),
+            )
+        });
+    }
+    checks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_git_version() {
+        assert_eq!(parse_git_version("git version 2.43.0"), Some((2, 43)));
+        assert_eq!(
+            parse_git_version("git version 2.39.2 (Apple Git-143)"),
+            Some((2, 39))
+        );
+        assert_eq!(
+            parse_git_version("git version 2.45.1.windows.1"),
+            Some((2, 45))
+        );
+        assert_eq!(parse_git_version("not git"), None);
+    }
+
+    #[tokio::test]
+    async fn test_check_config_reports_parse_errors() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("config.yaml");
+        std::fs::write(&path, "workspace: [unclosed").unwrap();
+
+        let (checks, config) = check_config(&path).await;
+        assert!(config.is_none());
+        assert_eq!(checks[0].status, CheckStatus::Fail);
+        assert!(checks[0].hint.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_check_config_missing_is_a_warning() {
+        let temp_dir = TempDir::new().unwrap();
+        let (checks, config) = check_config(&temp_dir.path().join("config.yaml")).await;
+        assert!(config.is_none());
+        assert_eq!(checks[0].status, CheckStatus::Warn);
+    }
+
+    #[tokio::test]
+    async fn test_check_cache_database_detects_corruption() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let missing = check_cache_database(&temp_dir.path().join("none.db")).await;
+        assert_eq!(missing.status, CheckStatus::Pass);
+
+        let healthy = temp_dir.path().join("healthy.db");
+        crate::cache::RepositoryCache::new(&healthy)
+            .initialize()
+            .await
+            .unwrap();
+        assert_eq!(
+            check_cache_database(&healthy).await.status,
+            CheckStatus::Pass
+        );
+
+        let corrupt = temp_dir.path().join("corrupt.db");
+        std::fs::write(&corrupt, b"this is not a sqlite database at all").unwrap();
+        let result = check_cache_database(&corrupt).await;
+        assert_eq!(result.status, CheckStatus::Fail);
+        assert_eq!(result.id, "cache:corrupt.db");
+    }
+
+    #[test]
+    fn test_report_counts_failures() {
+        let report = DoctorReport {
+            vibe_version: "0.0.0",
+            os: "linux",
+            arch: "x86_64",
+            config_path: PathBuf::from("config.yaml"),
+            checks: vec![
+                CheckResult::pass("git", "git version 2.43.0"),
+                CheckResult::warn("github-cli", "not installed", "install it"),
+            ],
+        };
+        assert!(!report.has_failures());
+
+        let value = serde_json::to_value(&report).unwrap();
+        assert_eq!(value["checks"][1]["status"], "warn");
+        assert!(value["checks"][0].get("hint").is_none());
+    }
+}
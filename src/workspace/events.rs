@@ -0,0 +1,399 @@
+//! User commands run on vibe events (`hooks.events`)
+//!
+//! Each event name in `hooks.events` maps to a shell command (`sh -c`, or
+//! `cmd /C` on Windows). When the event happens, the command starts in the
+//! background with the event as a JSON object on stdin and as environment
+//! variables: `VIBE_EVENT` is the event name, and each payload field is
+//! `VIBE_EVENT_<FIELD>`. The JSON also carries `event` and `at`. Payload
+//! fields by event:
+//!
+//! - `repo_cloned`: `name`, `path`, `url`
+//! - `sync_completed`: `repositories`, `synced`, `failed`, `fetch_only`
+//! - `worktree_created`: `repo`, `branch`, `path`, `task_id`
+//! - `worktree_removed`: `repo`, `branch`, `path`, `branch_deleted`
+//! - `cleanup_completed`: `repo`, `cleaned`, `skipped`, `failed`
+//!
+//! A command that fails or outlives `hooks.event_timeout_secs` is logged and
+//! otherwise ignored; the operation that fired the event has already
+//! succeeded. Before exiting, vibe waits for commands still running.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+use tokio::task::JoinHandle;
+use tracing::{debug, warn};
+
+use super::config::HooksConfig;
+
+/// How long an event command may run when `event_timeout_secs` isn't set
+pub const DEFAULT_EVENT_TIMEOUT_SECS: u64 = 30;
+
+/// Name of the environment variable holding the event name
+pub const EVENT_ENV: &str = "VIBE_EVENT";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HookEvent {
+    RepoCloned,
+    SyncCompleted,
+    WorktreeCreated,
+    WorktreeRemoved,
+    CleanupCompleted,
+}
+
+impl HookEvent {
+    pub const ALL: [HookEvent; 5] = [
+        Self::RepoCloned,
+        Self::SyncCompleted,
+        Self::WorktreeCreated,
+        Self::WorktreeRemoved,
+        Self::CleanupCompleted,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::RepoCloned => "repo_cloned",
+            Self::SyncCompleted => "sync_completed",
+            Self::WorktreeCreated => "worktree_created",
+            Self::WorktreeRemoved => "worktree_removed",
+            Self::CleanupCompleted => "cleanup_completed",
+        }
+    }
+}
+
+impl std::str::FromStr for HookEvent {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|event| event.as_str() == s)
+            .ok_or_else(|| {
+                let names: Vec<&str> = Self::ALL.iter().map(|event| event.as_str()).collect();
+                format!(
+                    "unknown event '{s}' (expected one of: {})",
+                    names.join(", ")
+                )
+            })
+    }
+}
+
+/// An event with its payload
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event {
+    RepoCloned {
+        name: String,
+        path: PathBuf,
+        url: String,
+    },
+    SyncCompleted {
+        repositories: usize,
+        synced: usize,
+        failed: usize,
+        fetch_only: bool,
+    },
+    WorktreeCreated {
+        /// Main checkout of the repository
+        repo: PathBuf,
+        branch: String,
+        path: PathBuf,
+        task_id: String,
+    },
+    WorktreeRemoved {
+        repo: PathBuf,
+        branch: String,
+        path: PathBuf,
+        branch_deleted: bool,
+    },
+    CleanupCompleted {
+        repo: PathBuf,
+        cleaned: usize,
+        skipped: usize,
+        failed: usize,
+    },
+}
+
+impl Event {
+    pub fn kind(&self) -> HookEvent {
+        match self {
+            Self::RepoCloned { .. } => HookEvent::RepoCloned,
+            Self::SyncCompleted { .. } => HookEvent::SyncCompleted,
+            Self::WorktreeCreated { .. } => HookEvent::WorktreeCreated,
+            Self::WorktreeRemoved { .. } => HookEvent::WorktreeRemoved,
+            Self::CleanupCompleted { .. } => HookEvent::CleanupCompleted,
+        }
+    }
+
+    /// A made-up event of `kind`, for `vibe hooks test`
+    pub fn sample(kind: HookEvent) -> Self {
+        let repo = PathBuf::from("/workspace/acme/api");
+        let worktree = PathBuf::from("/workspace/acme/api/.worktrees/vibe-ws/demo");
+        match kind {
+            HookEvent::RepoCloned => Self::RepoCloned {
+                name: "api".to_string(),
+                path: repo,
+                url: "https://github.com/acme/api.git".to_string(),
+            },
+            HookEvent::SyncCompleted => Self::SyncCompleted {
+                repositories: 3,
+                synced: 2,
+                failed: 1,
+                fetch_only: false,
+            },
+            HookEvent::WorktreeCreated => Self::WorktreeCreated {
+                repo,
+                branch: "vibe-ws/demo".to_string(),
+                path: worktree,
+                task_id: "demo".to_string(),
+            },
+            HookEvent::WorktreeRemoved => Self::WorktreeRemoved {
+                repo,
+                branch: "vibe-ws/demo".to_string(),
+                path: worktree,
+                branch_deleted: true,
+            },
+            HookEvent::CleanupCompleted => Self::CleanupCompleted {
+                repo,
+                cleaned: 2,
+                skipped: 1,
+                failed: 0,
+            },
+        }
+    }
+
+    /// The JSON written to the command's stdin
+    pub fn to_json(&self, at: DateTime<Utc>) -> serde_json::Value {
+        let mut value = serde_json::to_value(self).unwrap_or_default();
+        if let Some(object) = value.as_object_mut() {
+            object.insert("at".to_string(), at.to_rfc3339().into());
+        }
+        value
+    }
+
+    /// `VIBE_EVENT` and a `VIBE_EVENT_<FIELD>` variable per payload field
+    pub fn env_vars(&self) -> Vec<(String, String)> {
+        let mut vars = vec![(EVENT_ENV.to_string(), self.kind().as_str().to_string())];
+        if let serde_json::Value::Object(fields) = serde_json::to_value(self).unwrap_or_default() {
+            for (field, value) in fields {
+                if field == "event" {
+                    continue;
+                }
+                let value = match value {
+                    serde_json::Value::String(text) => text,
+                    other => other.to_string(),
+                };
+                vars.push((format!("{EVENT_ENV}_{}", field.to_uppercase()), value));
+            }
+        }
+        vars
+    }
+}
+
+/// How an event command ended
+#[derive(Debug, Clone, Serialize)]
+pub struct HookRun {
+    pub command: String,
+    /// None when the command was killed
+    pub exit_code: Option<i32>,
+    pub timed_out: bool,
+    pub stdout: String,
+    pub stderr: String,
+    pub duration_ms: u64,
+}
+
+impl HookRun {
+    pub fn succeeded(&self) -> bool {
+        self.exit_code == Some(0)
+    }
+}
+
+#[derive(Debug, Default)]
+struct EventHooks {
+    commands: std::collections::BTreeMap<HookEvent, String>,
+    timeout: Duration,
+}
+
+static HOOKS: RwLock<Option<Arc<EventHooks>>> = RwLock::new(None);
+
+/// Commands fired by this process that haven't finished
+static PENDING: Mutex<Vec<JoinHandle<()>>> = Mutex::new(Vec::new());
+
+/// Run the commands of `config` on subsequent events
+pub fn configure(config: &HooksConfig) {
+    let hooks = EventHooks {
+        commands: config.events.clone(),
+        timeout: event_timeout(config),
+    };
+    *HOOKS.write().unwrap() = Some(Arc::new(hooks));
+}
+
+/// The timeout configured in `config`
+pub fn event_timeout(config: &HooksConfig) -> Duration {
+    Duration::from_secs(
+        config
+            .event_timeout_secs
+            .unwrap_or(DEFAULT_EVENT_TIMEOUT_SECS),
+    )
+}
+
+/// Start the command configured for `event`, if any, without waiting for it
+pub fn emit(event: Event) {
+    let Some(hooks) = HOOKS.read().unwrap().clone() else {
+        return;
+    };
+    let Some(command) = hooks.commands.get(&event.kind()).cloned() else {
+        return;
+    };
+    let handle = tokio::spawn(async move {
+        let kind = event.kind().as_str();
+        match run(&command, &event, hooks.timeout).await {
+            Ok(run) if run.succeeded() => debug!("{} hook finished: {}", kind, command),
+            Ok(run) if run.timed_out => warn!(
+                "{} hook timed out after {}s: {}",
+                kind,
+                hooks.timeout.as_secs(),
+                command
+            ),
+            Ok(run) => warn!(
+                "{} hook exited with {:?}: {}: {}",
+                kind,
+                run.exit_code,
+                command,
+                run.stderr.trim()
+            ),
+            Err(e) => warn!("{} hook failed to start: {}: {:#}", kind, command, e),
+        }
+    });
+    if let Ok(mut pending) = PENDING.lock() {
+        pending.retain(|handle| !handle.is_finished());
+        pending.push(handle);
+    }
+}
+
+/// Wait for commands fired by this process; each is bounded by the timeout
+pub async fn wait_for_pending() {
+    let handles: Vec<JoinHandle<()>> = match PENDING.lock() {
+        Ok(mut pending) => pending.drain(..).collect(),
+        Err(_) => return,
+    };
+    for handle in handles {
+        let _ = handle.await;
+    }
+}
+
+/// Run `command` for `event` and wait for it, killing it after `timeout`
+pub async fn run(command: &str, event: &Event, timeout: Duration) -> Result<HookRun> {
+    let started = std::time::Instant::now();
+    let mut child = shell(command)
+        .envs(event.env_vars())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .with_context(|| format!("Failed to run {command}"))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        let json = event.to_json(Utc::now()).to_string();
+        // A command that ignores its input may exit before reading it
+        let _ = stdin.write_all(json.as_bytes()).await;
+    }
+
+    let (exit_code, timed_out, stdout, stderr) =
+        match tokio::time::timeout(timeout, child.wait_with_output()).await {
+            Ok(output) => {
+                let output = output?;
+                (
+                    output.status.code(),
+                    false,
+                    String::from_utf8_lossy(&output.stdout).into_owned(),
+                    String::from_utf8_lossy(&output.stderr).into_owned(),
+                )
+            }
+            // Dropping the child kills it
+            Err(_) => (None, true, String::new(), String::new()),
+        };
+
+    Ok(HookRun {
+        command: command.to_string(),
+        exit_code,
+        timed_out,
+        stdout,
+        stderr,
+        duration_ms: started.elapsed().as_millis() as u64,
+    })
+}
+
+#[cfg(unix)]
+fn shell(command: &str) -> Command {
+    let mut shell = Command::new("sh");
+    shell.arg("-c").arg(command);
+    shell
+}
+
+#[cfg(windows)]
+fn shell(command: &str) -> Command {
+    let mut shell = Command::new("cmd");
+    shell.arg("/C").arg(command);
+    shell
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_payload_as_env_and_json() {
+        let event = Event::sample(HookEvent::WorktreeRemoved);
+        let vars = event.env_vars();
+        assert_eq!(vars[0], ("VIBE_EVENT".into(), "worktree_removed".into()));
+        assert!(vars.contains(&("VIBE_EVENT_BRANCH".into(), "vibe-ws/demo".into())));
+        assert!(vars.contains(&("VIBE_EVENT_BRANCH_DELETED".into(), "true".into())));
+
+        let json = event.to_json(Utc::now());
+        assert_eq!(json["event"], "worktree_removed");
+        assert!(json["at"].is_string());
+    }
+
+    #[test]
+    fn test_events_parse_next_to_git_hooks() {
+        let config: HooksConfig = serde_yaml::from_str(
+            "pre-commit: lint.sh\nevents:\n  sync_completed: tmux refresh-client -S\n",
+        )
+        .unwrap();
+        assert_eq!(config.git["pre-commit"], PathBuf::from("lint.sh"));
+        assert_eq!(
+            config.events[&HookEvent::SyncCompleted],
+            "tmux refresh-client -S"
+        );
+        assert!(serde_yaml::from_str::<HooksConfig>("events:\n  synced: x\n").is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_run_passes_payload_and_times_out() {
+        let event = Event::sample(HookEvent::RepoCloned);
+        let run = run(
+            "read -r json; echo \"$VIBE_EVENT_NAME $json\"",
+            &event,
+            Duration::from_secs(10),
+        )
+        .await
+        .unwrap();
+        assert!(run.succeeded());
+        assert!(run.stdout.starts_with("api {\"at\":"));
+
+        let slow = super::run("sleep 5", &event, Duration::from_millis(100))
+            .await
+            .unwrap();
+        assert!(slow.timed_out);
+        assert!(!slow.succeeded());
+    }
+}
@@ -0,0 +1,136 @@
+//! The file tree of a repository or monorepo subproject, for the
+//! `file_tree` MCP tool
+//!
+//! Files come from `git ls-files`, so ignored files and `.git` never show.
+//! Directories below `depth` are collapsed to a file count.
+
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tokio::process::Command;
+
+use crate::utils::platform::resolve_command;
+
+/// Directory levels listed unless the caller asks for more
+pub const DEFAULT_DEPTH: usize = 3;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FileTree {
+    pub repository: String,
+    pub path: PathBuf,
+    /// Indented listing, directories ending in `/`
+    pub tree: String,
+    pub files: usize,
+}
+
+#[derive(Default)]
+struct Node {
+    dirs: BTreeMap<String, Node>,
+    files: Vec<String>,
+}
+
+impl Node {
+    fn insert(&mut self, path: &str) {
+        match path.split_once('/') {
+            Some((dir, rest)) => self.dirs.entry(dir.to_string()).or_default().insert(rest),
+            None => self.files.push(path.to_string()),
+        }
+    }
+
+    fn file_count(&self) -> usize {
+        self.files.len() + self.dirs.values().map(Node::file_count).sum::<usize>()
+    }
+
+    fn render(&self, depth: usize, indent: usize, out: &mut String) {
+        let pad = "  ".repeat(indent);
+        for (name, dir) in &self.dirs {
+            if depth <= 1 {
+                out.push_str(&format!("{pad}{name}/ ({} files)\n", dir.file_count()));
+            } else {
+                out.push_str(&format!("{pad}{name}/\n"));
+                dir.render(depth - 1, indent + 1, out);
+            }
+        }
+        let mut files = self.files.clone();
+        files.sort();
+        for file in files {
+            out.push_str(&format!("{pad}{file}\n"));
+        }
+    }
+}
+
+/// Render `files` (relative, `/`-separated) down to `depth` directory levels
+pub fn render_tree(files: &[String], depth: usize) -> String {
+    let mut root = Node::default();
+    for file in files {
+        root.insert(file);
+    }
+    let mut out = String::new();
+    root.render(depth.max(1), 0, &mut out);
+    out
+}
+
+/// The tracked and untracked, not ignored, files under `dir`, relative to it
+pub async fn list_files(dir: &Path) -> Result<Vec<String>> {
+    let output = Command::new(resolve_command("git"))
+        .args([
+            "ls-files",
+            "--cached",
+            "--others",
+            "--exclude-standard",
+            "-z",
+        ])
+        .current_dir(dir)
+        .output()
+        .await?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "git ls-files failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    let mut files: Vec<String> = String::from_utf8_lossy(&output.stdout)
+        .split('\0')
+        .filter(|file| !file.is_empty())
+        .map(str::to_string)
+        .collect();
+    files.sort();
+    files.dedup();
+    Ok(files)
+}
+
+/// The file tree of `dir`, labelled `repository`
+pub async fn file_tree(repository: String, dir: PathBuf, depth: usize) -> Result<FileTree> {
+    let files = list_files(&dir).await?;
+    Ok(FileTree {
+        repository,
+        tree: render_tree(&files, depth),
+        files: files.len(),
+        path: dir,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_tree_collapses_below_depth() {
+        let files: Vec<String> = [
+            "Cargo.toml",
+            "src/main.rs",
+            "src/ui/menu.rs",
+            "src/ui/theme.rs",
+        ]
+        .iter()
+        .map(|f| f.to_string())
+        .collect();
+
+        assert_eq!(
+            render_tree(&files, 2),
+            "src/\n  ui/ (2 files)\n  main.rs\nCargo.toml\n"
+        );
+        assert_eq!(render_tree(&files, 1), "src/ (3 files)\nCargo.toml\n");
+    }
+}
@@ -0,0 +1,432 @@
+//! Repository health scores for `vibe repo health`
+//!
+//! The repository counterpart of the worktree summary's health score. Each
+//! repository starts at 100% and loses a weighted share for every problem:
+//! uncommitted changes, unpushed commits, distance behind its upstream, stale
+//! local branches, a missing remote, and a long time since the last commit.
+//! Count-based problems cost half their weight as soon as there is one and
+//! the full weight at a threshold, so a single stray file already shows. The
+//! weights live in `preferences.health_weights`.
+
+use anyhow::{bail, Result};
+use console::style;
+use serde::{Deserialize, Serialize};
+
+use crate::display_println;
+use crate::output::theme::{self, Icon, Role};
+use crate::workspace::operations::GitStatus;
+use crate::worktree::status::StatusSeverity;
+
+/// Uncommitted files that cost the full dirtiness weight
+const DIRTY_FULL_AT: usize = 10;
+/// Unpushed commits that cost the full weight
+const UNPUSHED_FULL_AT: usize = 10;
+/// Commits behind upstream that cost the full weight
+const BEHIND_FULL_AT: usize = 20;
+/// Stale local branches that cost the full weight
+const STALE_BRANCHES_FULL_AT: usize = 5;
+/// Days since the last commit before inactivity costs anything
+const INACTIVE_AFTER_DAYS: u64 = 30;
+/// Days since the last commit that cost the full weight
+const INACTIVE_FULL_AT_DAYS: u64 = 365;
+
+/// Relative weight of each problem in the score
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HealthWeights {
+    pub dirty: f32,
+    pub unpushed: f32,
+    pub behind: f32,
+    pub stale_branches: f32,
+    pub no_remote: f32,
+    pub inactive: f32,
+}
+
+impl Default for HealthWeights {
+    fn default() -> Self {
+        Self {
+            dirty: 0.25,
+            unpushed: 0.2,
+            behind: 0.15,
+            stale_branches: 0.1,
+            no_remote: 0.15,
+            inactive: 0.15,
+        }
+    }
+}
+
+impl HealthWeights {
+    fn all(&self) -> [f32; 6] {
+        [
+            self.dirty,
+            self.unpushed,
+            self.behind,
+            self.stale_branches,
+            self.no_remote,
+            self.inactive,
+        ]
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        let weights = self.all();
+        if weights.iter().any(|w| !w.is_finite() || *w < 0.0) {
+            bail!("preferences.health_weights must not be negative");
+        }
+        if weights.iter().sum::<f32>() <= 0.0 {
+            bail!("preferences.health_weights must not all be zero");
+        }
+        Ok(())
+    }
+}
+
+/// What a repository's score is computed from
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HealthInputs {
+    pub uncommitted: usize,
+    pub unpushed: usize,
+    pub behind: usize,
+    pub stale_branches: usize,
+    pub has_remote: bool,
+    /// None for a repository without commits
+    pub last_commit_days: Option<u64>,
+}
+
+impl HealthInputs {
+    pub fn new(status: &GitStatus, stale_branches: usize, last_commit_days: Option<u64>) -> Self {
+        Self {
+            uncommitted: status.staged + status.unstaged + status.untracked,
+            unpushed: status.ahead,
+            behind: status.behind,
+            stale_branches,
+            has_remote: status.remote_url.is_some(),
+            last_commit_days,
+        }
+    }
+}
+
+/// One problem and what it cost
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HealthFactor {
+    pub factor: &'static str,
+    pub detail: String,
+    /// How bad the problem is, from 0.0 (absent) to 1.0
+    pub penalty: f32,
+    /// Points of the 0-100 score it cost
+    pub points: f32,
+}
+
+/// The score of one repository
+#[derive(Debug, Clone, Serialize)]
+pub struct RepoHealth {
+    pub repository: String,
+    pub path: String,
+    /// 0.0 to 1.0
+    pub score: f32,
+    pub severity: StatusSeverity,
+    /// The problems found, costliest first
+    pub factors: Vec<HealthFactor>,
+}
+
+impl RepoHealth {
+    pub fn icon(&self) -> &'static str {
+        theme::icon(match self.severity {
+            StatusSeverity::Clean => Icon::Success,
+            StatusSeverity::LightWarning => Icon::Warning,
+            StatusSeverity::Warning => Icon::Attention,
+        })
+    }
+}
+
+/// Half the weight for the first occurrence, all of it at `full_at`
+fn graded(count: usize, full_at: usize) -> f32 {
+    if count == 0 {
+        0.0
+    } else {
+        0.5 + 0.5 * (count.min(full_at) as f32 / full_at as f32)
+    }
+}
+
+fn inactivity(days: u64) -> f32 {
+    if days <= INACTIVE_AFTER_DAYS {
+        0.0
+    } else {
+        let span = (INACTIVE_FULL_AT_DAYS - INACTIVE_AFTER_DAYS) as f32;
+        ((days - INACTIVE_AFTER_DAYS) as f32 / span).min(1.0)
+    }
+}
+
+fn severity(score: f32) -> StatusSeverity {
+    if score >= 0.8 {
+        StatusSeverity::Clean
+    } else if score >= 0.5 {
+        StatusSeverity::LightWarning
+    } else {
+        StatusSeverity::Warning
+    }
+}
+
+/// Score `inputs`, returning the score and the problems that cost points
+pub fn score(inputs: &HealthInputs, weights: &HealthWeights) -> (f32, Vec<HealthFactor>) {
+    let candidates = [
+        (
+            "dirty",
+            weights.dirty,
+            graded(inputs.uncommitted, DIRTY_FULL_AT),
+            format!("{} uncommitted", inputs.uncommitted),
+        ),
+        (
+            "unpushed",
+            weights.unpushed,
+            graded(inputs.unpushed, UNPUSHED_FULL_AT),
+            format!("{} unpushed", inputs.unpushed),
+        ),
+        (
+            "behind",
+            weights.behind,
+            graded(inputs.behind, BEHIND_FULL_AT),
+            format!("{} behind", inputs.behind),
+        ),
+        (
+            "stale_branches",
+            weights.stale_branches,
+            graded(inputs.stale_branches, STALE_BRANCHES_FULL_AT),
+            format!("{} stale branches", inputs.stale_branches),
+        ),
+        (
+            "no_remote",
+            weights.no_remote,
+            if inputs.has_remote { 0.0 } else { 1.0 },
+            "no remote".to_string(),
+        ),
+        (
+            "inactive",
+            weights.inactive,
+            inputs.last_commit_days.map_or(0.0, inactivity),
+            format!(
+                "last commit {} days ago",
+                inputs.last_commit_days.unwrap_or(0)
+            ),
+        ),
+    ];
+
+    let total: f32 = weights.all().iter().sum();
+    if total <= 0.0 {
+        return (1.0, Vec::new());
+    }
+    let mut factors: Vec<HealthFactor> = candidates
+        .into_iter()
+        .filter(|(_, weight, penalty, _)| *penalty > 0.0 && *weight > 0.0)
+        .map(|(factor, weight, penalty, detail)| HealthFactor {
+            factor,
+            detail,
+            penalty,
+            points: (weight / total * penalty * 10000.0).round() / 100.0,
+        })
+        .collect();
+    factors.sort_by(|a, b| b.points.total_cmp(&a.points));
+    let lost: f32 = factors.iter().map(|f| f.points).sum();
+    ((1.0 - lost / 100.0).clamp(0.0, 1.0), factors)
+}
+
+/// Score one repository
+pub fn repo_health(
+    repository: String,
+    path: String,
+    inputs: &HealthInputs,
+    weights: &HealthWeights,
+) -> RepoHealth {
+    let (score, factors) = score(inputs, weights);
+    RepoHealth {
+        repository,
+        path,
+        score,
+        severity: severity(score),
+        factors,
+    }
+}
+
+/// Scores across the workspace
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct WorkspaceHealth {
+    pub repositories: usize,
+    /// Mean of the repository scores; 1.0 without repositories
+    pub score: f32,
+    pub healthy: usize,
+    pub needs_attention: usize,
+    pub unhealthy: usize,
+}
+
+/// The full `vibe repo health` result
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthReport {
+    /// Worst first
+    pub repositories: Vec<RepoHealth>,
+    pub workspace: WorkspaceHealth,
+    pub weights: HealthWeights,
+}
+
+impl HealthReport {
+    pub fn new(mut repositories: Vec<RepoHealth>, weights: HealthWeights) -> Self {
+        repositories.sort_by(|a, b| {
+            a.score
+                .total_cmp(&b.score)
+                .then_with(|| a.repository.cmp(&b.repository))
+        });
+        let count = |severity| {
+            repositories
+                .iter()
+                .filter(|r| r.severity == severity)
+                .count()
+        };
+        let workspace = WorkspaceHealth {
+            repositories: repositories.len(),
+            score: if repositories.is_empty() {
+                1.0
+            } else {
+                repositories.iter().map(|r| r.score).sum::<f32>() / repositories.len() as f32
+            },
+            healthy: count(StatusSeverity::Clean),
+            needs_attention: count(StatusSeverity::LightWarning),
+            unhealthy: count(StatusSeverity::Warning),
+        };
+        Self {
+            repositories,
+            workspace,
+            weights,
+        }
+    }
+
+    pub fn print_table(&self) {
+        if self.repositories.is_empty() {
+            display_println!("{}", theme::labeled(Icon::Info, "No repositories to score"));
+            return;
+        }
+
+        let name_width = self
+            .repositories
+            .iter()
+            .map(|r| r.repository.len())
+            .max()
+            .unwrap_or(0)
+            .max("Repository".len());
+        display_println!(
+            "{}",
+            style(format!(
+                "   {:>6}  {:<name_width$}  Issues",
+                "Score", "Repository"
+            ))
+            .bold()
+        );
+        for repo in &self.repositories {
+            let score = format!("{:>5}%", (repo.score * 100.0).round() as u32);
+            let score = match repo.severity {
+                StatusSeverity::Clean => theme::paint(Role::Success, score),
+                StatusSeverity::LightWarning => theme::paint(Role::Warning, score),
+                StatusSeverity::Warning => theme::paint(Role::Error, score),
+            };
+            let issues = if repo.factors.is_empty() {
+                theme::paint(Role::Dim, "-".to_string())
+            } else {
+                theme::paint(
+                    Role::Dim,
+                    repo.factors
+                        .iter()
+                        .map(|f| format!("{} (-{:.0})", f.detail, f.points))
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                )
+            };
+            display_println!(
+                "{} {}  {:<name_width$}  {}",
+                repo.icon(),
+                score,
+                repo.repository,
+                issues
+            );
+        }
+
+        let workspace = &self.workspace;
+        display_println!();
+        display_println!(
+            "{} {}% across {} repositories: {} healthy, {} need attention, {} unhealthy",
+            style("Workspace health:").bold(),
+            (workspace.score * 100.0).round() as u32,
+            workspace.repositories,
+            workspace.healthy,
+            workspace.needs_attention,
+            workspace.unhealthy
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn healthy() -> HealthInputs {
+        HealthInputs {
+            has_remote: true,
+            last_commit_days: Some(2),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_score_clean_repository() {
+        let (score, factors) = score(&healthy(), &HealthWeights::default());
+        assert_eq!(score, 1.0);
+        assert!(factors.is_empty());
+    }
+
+    #[test]
+    fn test_score_breakdown() {
+        let inputs = HealthInputs {
+            uncommitted: 1,
+            has_remote: false,
+            last_commit_days: Some(INACTIVE_FULL_AT_DAYS * 2),
+            ..healthy()
+        };
+        let (score, factors) = score(&inputs, &HealthWeights::default());
+        let names: Vec<&str> = factors.iter().map(|f| f.factor).collect();
+        assert_eq!(names, vec!["no_remote", "inactive", "dirty"]);
+        assert!((factors[0].points - 15.0).abs() < 0.01);
+        assert!((factors[2].points - 13.75).abs() < 0.01);
+        assert!((score - 0.5625).abs() < 0.001);
+        assert_eq!(severity(score), StatusSeverity::LightWarning);
+    }
+
+    #[test]
+    fn test_weights_and_report_order() {
+        let weights = HealthWeights {
+            dirty: 1.0,
+            unpushed: 0.0,
+            behind: 0.0,
+            stale_branches: 0.0,
+            no_remote: 0.0,
+            inactive: 0.0,
+        };
+        weights.validate().unwrap();
+        let dirty = HealthInputs {
+            uncommitted: DIRTY_FULL_AT,
+            ..healthy()
+        };
+        let report = HealthReport::new(
+            vec![
+                repo_health("clean".into(), "clean".into(), &healthy(), &weights),
+                repo_health("dirty".into(), "dirty".into(), &dirty, &weights),
+            ],
+            weights,
+        );
+        assert_eq!(report.repositories[0].repository, "dirty");
+        assert_eq!(report.repositories[0].score, 0.0);
+        assert_eq!(report.workspace.unhealthy, 1);
+        assert_eq!(report.workspace.healthy, 1);
+        assert_eq!(report.workspace.score, 0.5);
+
+        let zero = HealthWeights {
+            dirty: 0.0,
+            ..weights
+        };
+        assert!(zero.validate().is_err());
+    }
+}
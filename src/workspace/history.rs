@@ -0,0 +1,634 @@
+//! Event log for `vibe history`
+//!
+//! Operations worth finding again later append an event here: repositories
+//! opened, worktrees created and removed, cleanups, syncs, and configuration
+//! edits. The log lives in the state directory, so it survives cache resets.
+//! Reversible events carry what is needed to undo them. For a removed
+//! worktree that is its branch and commit, plus an archive of uncommitted
+//! changes kept in the trash; for a removed repository, its configuration
+//! entry. Recording is
+//! best effort and never fails the operation itself. Summaries are scrubbed of
+//! credentials before they are stored; undo payloads are kept whole so undo
+//! restores the exact entry, and are masked when events are printed as JSON.
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Local, Utc};
+use console::style;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::Mutex;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+use tokio_rusqlite::{params, Connection};
+use tracing::{debug, warn};
+
+use crate::display_println;
+use crate::output;
+use crate::output::exit::CommandError;
+use crate::output::redact;
+use crate::output::theme::{paint, Role};
+use crate::utils::platform::resolve_command;
+use crate::workspace::config::Repository;
+use crate::workspace::constants::get_history_db_path;
+use crate::workspace::launch_stats::LaunchSource;
+use crate::workspace::operations::execute_git_command;
+use crate::workspace::trash::Trash;
+use crate::workspace::WorkspaceManager;
+
+/// Where events recorded by this process come from
+static SOURCE: Mutex<LaunchSource> = Mutex::new(LaunchSource::Cli);
+
+/// Tag subsequent events with `source` (the MCP server, the menu, a URI)
+pub fn set_source(source: LaunchSource) {
+    if let Ok(mut current) = SOURCE.lock() {
+        *current = source;
+    }
+}
+
+fn current_source() -> LaunchSource {
+    SOURCE.lock().map(|source| *source).unwrap_or_default()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventKind {
+    RepoOpened,
+    RepoRemoved,
+    WorktreeCreated,
+    WorktreeRemoved,
+    CleanupRun,
+    SyncRun,
+    ConfigEdited,
+}
+
+impl EventKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            EventKind::RepoOpened => "repo_opened",
+            EventKind::RepoRemoved => "repo_removed",
+            EventKind::WorktreeCreated => "worktree_created",
+            EventKind::WorktreeRemoved => "worktree_removed",
+            EventKind::CleanupRun => "cleanup_run",
+            EventKind::SyncRun => "sync_run",
+            EventKind::ConfigEdited => "config_edited",
+        }
+    }
+
+    fn parse(kind: &str) -> Option<Self> {
+        [
+            EventKind::RepoOpened,
+            EventKind::RepoRemoved,
+            EventKind::WorktreeCreated,
+            EventKind::WorktreeRemoved,
+            EventKind::CleanupRun,
+            EventKind::SyncRun,
+            EventKind::ConfigEdited,
+        ]
+        .into_iter()
+        .find(|candidate| candidate.as_str() == kind)
+    }
+}
+
+/// How to reverse an event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum UndoAction {
+    /// Put a removed worktree back at `path`, recreating its branch at `head`
+    /// if the branch was deleted too, then unpack its uncommitted changes
+    RestoreWorktree {
+        repo: PathBuf,
+        path: PathBuf,
+        /// None for a detached worktree
+        branch: Option<String>,
+        head: String,
+        archive: Option<PathBuf>,
+    },
+    /// Add a removed repository back to the workspace configuration
+    RestoreRepository { repository: Box<Repository> },
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HistoryEvent {
+    pub id: i64,
+    pub at: DateTime<Utc>,
+    pub kind: EventKind,
+    /// cli, menu, mcp, or uri
+    pub source: String,
+    pub summary: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub undo: Option<UndoAction>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub undone_at: Option<DateTime<Utc>>,
+}
+
+impl HistoryEvent {
+    pub fn can_undo(&self) -> bool {
+        self.undo.is_some() && self.undone_at.is_none()
+    }
+}
+
+/// The event log database
+pub struct EventLog {
+    db_path: PathBuf,
+}
+
+impl EventLog {
+    pub fn new(db_path: impl Into<PathBuf>) -> Self {
+        Self {
+            db_path: db_path.into(),
+        }
+    }
+
+    /// The log in vibe's state directory
+    pub fn open_default() -> Self {
+        Self::new(get_history_db_path())
+    }
+
+    async fn connect(&self) -> Result<Connection> {
+        if let Some(parent) = self.db_path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let conn = Connection::open(&self.db_path)
+            .await
+            .context("Failed to open history database")?;
+        conn.call(|conn| {
+            conn.execute(
+                r#"
+                CREATE TABLE IF NOT EXISTS events (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    at TEXT NOT NULL,       -- ISO 8601 datetime
+                    kind TEXT NOT NULL,
+                    source TEXT NOT NULL,
+                    summary TEXT NOT NULL,
+                    undo TEXT,              -- JSON UndoAction
+                    undone_at TEXT
+                )
+                "#,
+                [],
+            )?;
+            Ok(())
+        })
+        .await
+        .context("Failed to initialize history database")?;
+        Ok(conn)
+    }
+
+    /// Append an event and return its id
+    pub async fn append(
+        &self,
+        kind: EventKind,
+        source: LaunchSource,
+        summary: &str,
+        undo: Option<&UndoAction>,
+    ) -> Result<i64> {
+        let undo = undo.map(serde_json::to_string).transpose()?;
+        let summary = redact::scrub(summary).into_owned();
+        let conn = self.connect().await?;
+        let id = conn
+            .call(move |conn| {
+                conn.execute(
+                    "INSERT INTO events (at, kind, source, summary, undo) VALUES (?1, ?2, ?3, ?4, ?5)",
+                    params![
+                        Utc::now().to_rfc3339(),
+                        kind.as_str(),
+                        source.as_str(),
+                        summary,
+                        undo
+                    ],
+                )?;
+                Ok(conn.last_insert_rowid())
+            })
+            .await
+            .context("Failed to record history event")?;
+        Ok(id)
+    }
+
+    /// The `limit` most recent events, oldest first
+    pub async fn recent(&self, limit: usize) -> Result<Vec<HistoryEvent>> {
+        let mut events = self
+            .query("ORDER BY id DESC LIMIT ?1", limit as i64)
+            .await?;
+        events.reverse();
+        Ok(events)
+    }
+
+    pub async fn get(&self, id: i64) -> Result<Option<HistoryEvent>> {
+        Ok(self.query("WHERE id = ?1", id).await?.pop())
+    }
+
+    pub async fn mark_undone(&self, id: i64) -> Result<()> {
+        let conn = self.connect().await?;
+        conn.call(move |conn| {
+            conn.execute(
+                "UPDATE events SET undone_at = ?1 WHERE id = ?2",
+                params![Utc::now().to_rfc3339(), id],
+            )?;
+            Ok(())
+        })
+        .await
+        .context("Failed to update history event")?;
+        Ok(())
+    }
+
+    async fn query(&self, clause: &'static str, param: i64) -> Result<Vec<HistoryEvent>> {
+        let conn = self.connect().await?;
+        let rows = conn
+            .call(move |conn| {
+                let mut stmt = conn.prepare(&format!(
+                    "SELECT id, at, kind, source, summary, undo, undone_at FROM events {clause}"
+                ))?;
+                let rows = stmt.query_map(params![param], |row| {
+                    Ok((
+                        row.get::<_, i64>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, String>(2)?,
+                        row.get::<_, String>(3)?,
+                        row.get::<_, String>(4)?,
+                        row.get::<_, Option<String>>(5)?,
+                        row.get::<_, Option<String>>(6)?,
+                    ))
+                })?;
+                Ok(rows.collect::<Result<Vec<_>, _>>()?)
+            })
+            .await
+            .context("Failed to read history")?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|(id, at, kind, source, summary, undo, undone_at)| {
+                Some(HistoryEvent {
+                    id,
+                    at: parse_time(&at)?,
+                    // Events from a newer vibe are skipped
+                    kind: EventKind::parse(&kind)?,
+                    source,
+                    summary,
+                    undo: undo.and_then(|undo| serde_json::from_str(&undo).ok()),
+                    undone_at: undone_at.as_deref().and_then(parse_time),
+                })
+            })
+            .collect())
+    }
+}
+
+fn parse_time(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|time| time.with_timezone(&Utc))
+}
+
+/// Append an event to the default log and return its id; failures are only
+/// logged
+pub async fn record(
+    kind: EventKind,
+    summary: impl AsRef<str>,
+    undo: Option<UndoAction>,
+) -> Option<i64> {
+    let log = EventLog::open_default();
+    match log
+        .append(kind, current_source(), summary.as_ref(), undo.as_ref())
+        .await
+    {
+        Ok(id) => Some(id),
+        Err(e) => {
+            warn!("Failed to record history event: {}", e);
+            None
+        }
+    }
+}
+
+/// Save the modified and untracked files of `worktree` to `archive` so a
+/// forced removal can be undone. None when there is nothing uncommitted.
+pub async fn archive_changes(worktree: &Path, archive: PathBuf) -> Result<Option<PathBuf>> {
+    let tracked = execute_git_command(worktree, &["diff", "--name-only", "-z", "HEAD"]).await?;
+    let untracked = execute_git_command(
+        worktree,
+        &["ls-files", "--others", "--exclude-standard", "-z"],
+    )
+    .await?;
+    // Deleted files come back with the checkout; only existing ones are saved
+    let files: Vec<&str> = tracked
+        .split('\0')
+        .chain(untracked.split('\0'))
+        .filter(|file| !file.is_empty() && worktree.join(file).exists())
+        .collect();
+    if files.is_empty() {
+        return Ok(None);
+    }
+
+    let mut tar = Command::new(resolve_command("tar"))
+        .arg("-czf")
+        .arg(&archive)
+        .arg("-C")
+        .arg(worktree)
+        .args(["--null", "-T", "-"])
+        .stdin(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("Failed to run tar")?;
+    if let Some(mut stdin) = tar.stdin.take() {
+        stdin.write_all(files.join("\0").as_bytes()).await?;
+    }
+    let output = tar.wait_with_output().await?;
+    if !output.status.success() {
+        let _ = tokio::fs::remove_file(&archive).await;
+        bail!(
+            "Failed to archive uncommitted changes: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    debug!(
+        "Archived {} changed files to {}",
+        files.len(),
+        archive.display()
+    );
+    Ok(Some(archive))
+}
+
+/// Reverse event `id` and mark it undone, dropping its trash item
+pub async fn undo(
+    log: &EventLog,
+    id: i64,
+    workspace: &mut WorkspaceManager,
+) -> Result<HistoryEvent> {
+    let event = log
+        .get(id)
+        .await?
+        .ok_or_else(|| CommandError::not_found("History event", id.to_string()))?;
+    if event.undone_at.is_some() {
+        return Err(CommandError::Usage(format!("Event {id} was already undone")).into());
+    }
+    let Some(action) = &event.undo else {
+        return Err(CommandError::Usage(format!(
+            "Event {id} ({}) can't be undone",
+            event.kind.as_str()
+        ))
+        .into());
+    };
+
+    apply(action, workspace).await?;
+    log.mark_undone(id).await?;
+    let trash = Trash::open_default();
+    match trash.find_by_event(id).await {
+        Ok(Some(item)) => {
+            if let Err(e) = trash.delete(&item.id).await {
+                warn!("Failed to delete trash item {}: {}", item.id, e);
+            }
+        }
+        Ok(None) => {}
+        Err(e) => warn!("Failed to read the trash: {}", e),
+    }
+    Ok(event)
+}
+
+/// Carry out `action`, for `vibe history undo` and `vibe trash restore`
+pub async fn apply(action: &UndoAction, workspace: &mut WorkspaceManager) -> Result<()> {
+    match action {
+        UndoAction::RestoreWorktree {
+            repo,
+            path,
+            branch,
+            head,
+            archive,
+        } => restore_worktree(repo, path, branch.as_deref(), head, archive.as_deref()).await,
+        UndoAction::RestoreRepository { repository } => {
+            if workspace.get_repository(&repository.name).is_some() {
+                bail!(
+                    "A repository named '{}' is already configured",
+                    repository.name
+                );
+            }
+            workspace.add_repository(repository.as_ref().clone()).await
+        }
+    }
+}
+
+async fn restore_worktree(
+    repo: &Path,
+    path: &Path,
+    branch: Option<&str>,
+    head: &str,
+    archive: Option<&Path>,
+) -> Result<()> {
+    if path.exists() {
+        bail!("Cannot restore worktree: {} already exists", path.display());
+    }
+    if let Some(archive) = archive {
+        if !archive.exists() {
+            bail!(
+                "The archive of uncommitted changes is gone ({}); it was emptied from the trash",
+                archive.display()
+            );
+        }
+    }
+
+    let path_arg = path.to_string_lossy();
+    match branch {
+        Some(branch) => {
+            let branch_ref = format!("refs/heads/{branch}");
+            let exists =
+                execute_git_command(repo, &["show-ref", "--verify", "--quiet", &branch_ref])
+                    .await
+                    .is_ok();
+            if !exists {
+                execute_git_command(repo, &["branch", branch, head]).await?;
+            }
+            execute_git_command(repo, &["worktree", "add", &path_arg, branch]).await?;
+        }
+        None => {
+            execute_git_command(repo, &["worktree", "add", "--detach", &path_arg, head]).await?;
+        }
+    }
+
+    if let Some(archive) = archive {
+        let output = Command::new(resolve_command("tar"))
+            .arg("-xzf")
+            .arg(archive)
+            .arg("-C")
+            .arg(path)
+            .output()
+            .await
+            .context("Failed to run tar")?;
+        if !output.status.success() {
+            bail!(
+                "Restored the worktree but not its uncommitted changes ({}): {}",
+                archive.display(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        let _ = tokio::fs::remove_file(archive).await;
+    }
+    Ok(())
+}
+
+/// Print events as a table, oldest first
+pub fn print_history(events: &[HistoryEvent]) {
+    if events.is_empty() {
+        display_println!("No history recorded yet");
+        return;
+    }
+
+    display_println!(
+        "{}",
+        paint(
+            Role::Dim,
+            format!("{:>5}  {:<16}  {:<6}  {}", "ID", "WHEN", "SOURCE", "EVENT")
+        )
+    );
+    for event in events {
+        let when = output::stable_time(
+            event
+                .at
+                .with_timezone(&Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string(),
+        );
+        let note = if event.can_undo() {
+            format!(
+                "  {}",
+                style(format!("(undo: vibe history undo {})", event.id)).dim()
+            )
+        } else if event.undone_at.is_some() {
+            format!("  {}", style("(undone)").dim())
+        } else {
+            String::new()
+        };
+        display_println!(
+            "{:>5}  {:<16}  {:<6}  {}{}",
+            event.id,
+            when,
+            event.source,
+            event.summary,
+            note
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_event_log() {
+        let dir = TempDir::new().unwrap();
+        let log = EventLog::new(dir.path().join("state").join("history.db"));
+
+        let undo = UndoAction::RestoreWorktree {
+            repo: PathBuf::from("/src/api"),
+            path: PathBuf::from("/src/api/.worktrees/fix-auth"),
+            branch: Some("vibe-ws/fix-auth".to_string()),
+            head: "3848f9d0c1".to_string(),
+            archive: None,
+        };
+        let opened = log
+            .append(EventKind::RepoOpened, LaunchSource::Cli, "Opened api", None)
+            .await
+            .unwrap();
+        let removed = log
+            .append(
+                EventKind::WorktreeRemoved,
+                LaunchSource::Mcp,
+                "Removed worktree vibe-ws/fix-auth",
+                Some(&undo),
+            )
+            .await
+            .unwrap();
+        assert!(removed > opened);
+
+        let events = log.recent(10).await.unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].kind, EventKind::RepoOpened);
+        assert!(!events[0].can_undo());
+        assert_eq!(events[1].source, "mcp");
+        assert!(events[1].can_undo());
+
+        log.mark_undone(removed).await.unwrap();
+        assert!(!log.get(removed).await.unwrap().unwrap().can_undo());
+        assert_eq!(log.recent(1).await.unwrap()[0].id, removed);
+    }
+
+    fn git(args: &[&str], cwd: &Path) {
+        let status = std::process::Command::new("git")
+            .args(["-c", "user.name=t", "-c", "user.email=t@t"])
+            .args(args)
+            .current_dir(cwd)
+            .output()
+            .unwrap()
+            .status;
+        assert!(status.success(), "git {args:?}");
+    }
+
+    fn tar_file(archive: &Path, dir: &Path, file: &str) {
+        let status = std::process::Command::new("tar")
+            .arg("-czf")
+            .arg(archive)
+            .arg("-C")
+            .arg(dir)
+            .arg(file)
+            .status()
+            .unwrap();
+        assert!(status.success());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_restore_removed_worktree() {
+        let dir = TempDir::new().unwrap();
+        let repo = dir.path().join("repo");
+        let worktree = dir.path().join("repo-task");
+        std::fs::create_dir_all(&repo).unwrap();
+        git(&["init", "-q"], &repo);
+        git(&["commit", "-q", "--allow-empty", "-m", "init"], &repo);
+        git(
+            &[
+                "worktree",
+                "add",
+                "-q",
+                "-b",
+                "task",
+                worktree.to_str().unwrap(),
+            ],
+            &repo,
+        );
+        git(&["commit", "-q", "--allow-empty", "-m", "work"], &worktree);
+        std::fs::write(worktree.join("notes.txt"), "uncommitted").unwrap();
+        let head = execute_git_command(&worktree, &["rev-parse", "HEAD"])
+            .await
+            .unwrap();
+
+        let archive_dir = dir.path().join("archives");
+        std::fs::create_dir_all(&archive_dir).unwrap();
+        let archive = archive_dir.join("task.tar.gz");
+        tar_file(&archive, &worktree, "notes.txt");
+
+        git(
+            &["worktree", "remove", "--force", worktree.to_str().unwrap()],
+            &repo,
+        );
+        git(&["branch", "-D", "task"], &repo);
+
+        restore_worktree(&repo, &worktree, Some("task"), &head, Some(&archive))
+            .await
+            .unwrap();
+        assert_eq!(
+            execute_git_command(&worktree, &["rev-parse", "HEAD"])
+                .await
+                .unwrap(),
+            head
+        );
+        assert_eq!(
+            std::fs::read_to_string(worktree.join("notes.txt")).unwrap(),
+            "uncommitted"
+        );
+        assert!(!archive.exists());
+
+        // Restoring over an existing directory is refused
+        assert!(
+            restore_worktree(&repo, &worktree, Some("task"), &head, None)
+                .await
+                .is_err()
+        );
+    }
+}
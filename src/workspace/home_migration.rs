@@ -0,0 +1,267 @@
+//! `vibe config migrate-home`: move the legacy `~/.toolprint/vibe-workspace`
+//! directory into the XDG layout
+
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use tokio::process::Command;
+
+use super::constants::{self, VibePaths};
+
+/// One entry moved out of the legacy directory
+#[derive(Debug, Clone, Serialize)]
+pub struct MovedEntry {
+    pub from: PathBuf,
+    pub to: PathBuf,
+}
+
+/// Where everything ended up
+#[derive(Debug, Clone, Serialize)]
+pub struct MigrationReport {
+    pub moved: Vec<MovedEntry>,
+    pub config: PathBuf,
+    pub data: PathBuf,
+    pub cache: PathBuf,
+    pub state: PathBuf,
+    /// Problems that didn't stop the migration
+    pub warnings: Vec<String>,
+    pub dry_run: bool,
+}
+
+/// Destination of a top-level legacy entry in the XDG layout
+fn destination(name: &str, target: &VibePaths) -> PathBuf {
+    match name {
+        "config.yaml" => target.config.join(name),
+        "state.json" | "logs" => target.state.join(name),
+        "cache" => target.cache.clone(),
+        _ => target.data.join(name),
+    }
+}
+
+/// Plan the move from `legacy` into `target`, refusing to overwrite anything
+pub async fn plan_migration(legacy: &Path, target: &VibePaths) -> Result<Vec<MovedEntry>> {
+    if !legacy.is_dir() {
+        bail!("Nothing to migrate: {} does not exist", legacy.display());
+    }
+
+    let mut moves = Vec::new();
+    let mut entries = tokio::fs::read_dir(legacy)
+        .await
+        .with_context(|| format!("Failed to read {}", legacy.display()))?;
+    while let Some(entry) = entries.next_entry().await? {
+        let name = entry.file_name().to_string_lossy().to_string();
+        moves.push(MovedEntry {
+            from: entry.path(),
+            to: destination(&name, target),
+        });
+    }
+    moves.sort_by(|a, b| a.from.cmp(&b.from));
+
+    let conflicts: Vec<String> = moves
+        .iter()
+        .filter(|m| m.to.exists())
+        .map(|m| m.to.display().to_string())
+        .collect();
+    if !conflicts.is_empty() {
+        bail!(
+            "Refusing to overwrite existing files: {}",
+            conflicts.join(", ")
+        );
+    }
+
+    Ok(moves)
+}
+
+/// Move the legacy directory into the XDG layout for `home`
+pub async fn migrate_home(home: &Path, dry_run: bool) -> Result<MigrationReport> {
+    if std::env::var_os(constants::VIBE_HOME_ENV).is_some() {
+        bail!(
+            "{} is set; unset it to migrate into the XDG layout",
+            constants::VIBE_HOME_ENV
+        );
+    }
+
+    let legacy = home.join(constants::LEGACY_DIR_PATH);
+    let target = VibePaths::xdg(home, |var| std::env::var_os(var));
+    let moved = plan_migration(&legacy, &target).await?;
+    let mut warnings = Vec::new();
+
+    if !dry_run {
+        for entry in &moved {
+            move_path(&entry.from, &entry.to).await?;
+        }
+        rewrite_config_paths(&target.config.join("config.yaml"), &moved).await?;
+        warnings.extend(repair_worktrees(&target.data.join("worktrees")).await);
+
+        // Leave nothing behind but drop the now-empty directories
+        let _ = tokio::fs::remove_dir(&legacy).await;
+        if let Some(parent) = legacy.parent() {
+            let _ = tokio::fs::remove_dir(parent).await;
+        }
+    }
+
+    Ok(MigrationReport {
+        moved,
+        config: target.config,
+        data: target.data,
+        cache: target.cache,
+        state: target.state,
+        warnings,
+        dry_run,
+    })
+}
+
+/// Rename `from` to `to`, copying when they're on different filesystems
+async fn move_path(from: &Path, to: &Path) -> Result<()> {
+    if let Some(parent) = to.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    if tokio::fs::rename(from, to).await.is_ok() {
+        return Ok(());
+    }
+
+    super::manager::copy_dir(from, to)
+        .await
+        .with_context(|| format!("Failed to copy {} to {}", from.display(), to.display()))?;
+    if from.is_dir() {
+        tokio::fs::remove_dir_all(from).await?;
+    } else {
+        tokio::fs::remove_file(from).await?;
+    }
+    Ok(())
+}
+
+/// Point template and worktree paths saved in config.yaml at their new homes
+async fn rewrite_config_paths(config_path: &Path, moved: &[MovedEntry]) -> Result<()> {
+    let Ok(contents) = tokio::fs::read_to_string(config_path).await else {
+        return Ok(());
+    };
+
+    let mut rewritten = contents.clone();
+    for entry in moved {
+        rewritten = rewritten.replace(
+            &entry.from.display().to_string(),
+            &entry.to.display().to_string(),
+        );
+    }
+    if rewritten != contents {
+        tokio::fs::write(config_path, rewritten)
+            .await
+            .with_context(|| format!("Failed to update {}", config_path.display()))?;
+    }
+    Ok(())
+}
+
+/// Reconnect moved global worktrees (`<repo>/<task>`) with their repositories
+async fn repair_worktrees(worktrees_dir: &Path) -> Vec<String> {
+    let mut warnings = Vec::new();
+    let Ok(mut repos) = tokio::fs::read_dir(worktrees_dir).await else {
+        return warnings;
+    };
+
+    while let Ok(Some(repo)) = repos.next_entry().await {
+        let Ok(mut tasks) = tokio::fs::read_dir(repo.path()).await else {
+            continue;
+        };
+        while let Ok(Some(task)) = tasks.next_entry().await {
+            let worktree = task.path();
+            if !worktree.join(".git").is_file() {
+                continue;
+            }
+            let repaired = Command::new("git")
+                .args(["worktree", "repair"])
+                .current_dir(&worktree)
+                .output()
+                .await
+                .is_ok_and(|output| output.status.success());
+            if !repaired {
+                warnings.push(format!(
+                    "Run `git worktree repair` in {} to reconnect it",
+                    worktree.display()
+                ));
+            }
+        }
+    }
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn xdg(home: &Path) -> VibePaths {
+        VibePaths::xdg(home, |_| None)
+    }
+
+    #[tokio::test]
+    async fn test_plan_maps_entries_to_xdg_roles() {
+        let home = TempDir::new().unwrap();
+        let legacy = home.path().join(constants::LEGACY_DIR_PATH);
+        for dir in ["templates/warp", "cache", "logs", "backups"] {
+            std::fs::create_dir_all(legacy.join(dir)).unwrap();
+        }
+        std::fs::write(legacy.join("config.yaml"), "").unwrap();
+        std::fs::write(legacy.join("state.json"), "{}").unwrap();
+
+        let target = xdg(home.path());
+        let moves = plan_migration(&legacy, &target).await.unwrap();
+        let destination_of = |name: &str| {
+            moves
+                .iter()
+                .find(|m| m.from == legacy.join(name))
+                .map(|m| m.to.clone())
+                .unwrap()
+        };
+
+        assert_eq!(
+            destination_of("config.yaml"),
+            target.config.join("config.yaml")
+        );
+        assert_eq!(
+            destination_of("state.json"),
+            target.state.join("state.json")
+        );
+        assert_eq!(destination_of("logs"), target.state.join("logs"));
+        assert_eq!(destination_of("cache"), target.cache);
+        assert_eq!(destination_of("templates"), target.data.join("templates"));
+        assert_eq!(destination_of("backups"), target.data.join("backups"));
+    }
+
+    #[tokio::test]
+    async fn test_plan_refuses_to_overwrite() {
+        let home = TempDir::new().unwrap();
+        let legacy = home.path().join(constants::LEGACY_DIR_PATH);
+        std::fs::create_dir_all(&legacy).unwrap();
+        std::fs::write(legacy.join("config.yaml"), "").unwrap();
+
+        let target = xdg(home.path());
+        std::fs::create_dir_all(&target.config).unwrap();
+        std::fs::write(target.config.join("config.yaml"), "").unwrap();
+
+        let error = plan_migration(&legacy, &target).await.unwrap_err();
+        assert!(error.to_string().contains("Refusing to overwrite"));
+    }
+
+    #[tokio::test]
+    async fn test_rewrite_config_paths() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = temp_dir.path().join("config.yaml");
+        std::fs::write(
+            &config,
+            "apps:\n  warp:\n    template_dir: /home/me/.toolprint/vibe-workspace/templates/warp\n",
+        )
+        .unwrap();
+
+        let moved = vec![MovedEntry {
+            from: PathBuf::from("/home/me/.toolprint/vibe-workspace/templates"),
+            to: PathBuf::from("/home/me/.local/share/vibe/templates"),
+        }];
+        rewrite_config_paths(&config, &moved).await.unwrap();
+
+        let contents = std::fs::read_to_string(&config).unwrap();
+        assert!(contents.contains("/home/me/.local/share/vibe/templates/warp"));
+    }
+}
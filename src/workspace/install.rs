@@ -3,10 +3,65 @@ use colored::*;
 use std::path::{Path, PathBuf};
 use tokio::process::Command;
 
-use crate::git::{GitConfig, GitError};
+use crate::git::{lfs, GitConfig, GitError};
 use crate::utils::fs::expand_tilde;
-use crate::utils::git::is_git_available;
+use crate::utils::git::{extract_host_from_url, is_git_available};
 use crate::workspace::config::Repository as ConfigRepository;
+use crate::workspace::events::{self, Event};
+use crate::{display_println, display_warn};
+
+/// Host assumed for `org/repo` shorthands
+pub const DEFAULT_HOST: &str = "github.com";
+
+/// The origin of the repository at `path`, when it is a clone of something
+/// other than `url`
+async fn remote_of_other_repository(path: &Path, url: &str) -> Option<String> {
+    remote_of(path)
+        .await
+        .filter(|existing| !same_remote(existing, url))
+}
+
+/// The origin URL of the repository at `path`
+pub async fn remote_of(path: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .args(["remote", "get-url", "origin"])
+        .current_dir(path)
+        .output()
+        .await
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Whether two remote URLs name the same repository, whatever the protocol
+pub fn same_remote(a: &str, b: &str) -> bool {
+    fn key(url: &str) -> (Option<String>, String) {
+        let path = url
+            .trim()
+            .trim_end_matches('/')
+            .trim_end_matches(".git")
+            .to_lowercase();
+        let mut parts = path.rsplit(['/', ':']);
+        let repo = parts.next().unwrap_or_default();
+        let owner = parts.next().unwrap_or_default();
+        (extract_host_from_url(url), format!("{owner}/{repo}"))
+    }
+    key(a) == key(b)
+}
+
+/// Where a clone goes and the name it is configured under
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CloneTarget {
+    pub name: String,
+    pub path: PathBuf,
+    /// Whether the path was chosen rather than taken from the clone layout
+    pub custom: bool,
+    pub host: String,
+    pub owner: String,
+    pub repo: String,
+}
 
 pub struct RepositoryInstaller {
     workspace_root: PathBuf,
@@ -47,23 +102,101 @@ impl RepositoryInstaller {
         open_after_clone: bool,
         run_install_commands: bool,
     ) -> Result<InstalledRepository> {
-        if !is_git_available() {
-            anyhow::bail!("Git is not available on the system");
+        let target = self.target(url, custom_path)?;
+
+        // Check if repository already exists
+        if target.path.exists() {
+            if let Some(existing) = remote_of_other_repository(&target.path, url).await {
+                return Err(GitError::PathCollision {
+                    path: target.path,
+                    existing,
+                    suggestion: if target.custom {
+                        None
+                    } else {
+                        self.git_config.clone_layout.more_nested()
+                    },
+                }
+                .into());
+            }
+            return Err(GitError::RepositoryExists { path: target.path }.into());
         }
 
+        self.install_to(url, &target, open_after_clone, run_install_commands)
+            .await
+    }
+
+    /// Where a clone of `url` goes, `custom_path` or the layout's
+    /// destination, and the name it is configured under
+    pub fn target(&self, url: &str, custom_path: Option<PathBuf>) -> Result<CloneTarget> {
         let (org, repo_name) = self.parse_git_url(url)?;
-        let target_path = if let Some(path) = custom_path {
-            expand_tilde(&path)
-        } else {
-            self.calculate_install_path(&org, &repo_name)
+        let host = extract_host_from_url(url).unwrap_or_else(|| DEFAULT_HOST.to_string());
+        let custom = custom_path.is_some();
+        let path = match custom_path {
+            Some(path) => expand_tilde(&path),
+            None => self.calculate_install_path(&host, &org, &repo_name),
         };
+        Ok(CloneTarget {
+            name: format!("{org}/{repo_name}"),
+            path,
+            custom,
+            host,
+            owner: org,
+            repo: repo_name,
+        })
+    }
 
-        // Check if repository already exists
-        if target_path.exists() {
-            return Err(GitError::RepositoryExists { path: target_path }.into());
+    /// `target` one layout level deeper, named after its path under the
+    /// workspace root (`acme/api`, `github.com/acme/api`). `None` for custom
+    /// paths and the host-owner layout, which have no deeper level.
+    pub fn namespaced(&self, target: &CloneTarget) -> Option<CloneTarget> {
+        if target.custom {
+            return None;
         }
+        let path = self.git_config.clone_layout.more_nested()?.destination(
+            &self.workspace_root,
+            &target.host,
+            &target.owner,
+            &target.repo,
+        );
+        let name = path
+            .strip_prefix(&self.workspace_root)
+            .ok()?
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        Some(CloneTarget {
+            name,
+            path,
+            ..target.clone()
+        })
+    }
 
-        println!(
+    /// `target` in a sibling directory called `name`, configured as `name`
+    pub fn renamed(&self, target: &CloneTarget, name: &str) -> CloneTarget {
+        let parent = target.path.parent().unwrap_or(&self.workspace_root);
+        CloneTarget {
+            name: name.to_string(),
+            path: parent.join(name),
+            custom: true,
+            ..target.clone()
+        }
+    }
+
+    /// Clone `url` to `target`, which must not exist yet
+    pub async fn install_to(
+        &self,
+        url: &str,
+        target: &CloneTarget,
+        open_after_clone: bool,
+        run_install_commands: bool,
+    ) -> Result<InstalledRepository> {
+        if !is_git_available() {
+            anyhow::bail!("Git is not available on the system");
+        }
+        let target_path = target.path.clone();
+
+        display_println!(
             "{} Cloning {} to {}",
             "📦".cyan(),
             url.cyan().bold(),
@@ -78,10 +211,17 @@ impl RepositoryInstaller {
         }
 
         // Clone repository
+        lfs::warn_before_clone(url).await;
         self.clone_repository(url, &target_path).await?;
+        lfs::check_after_clone(&target_path, self.git_config.lfs_auto_pull).await;
+        events::emit(Event::RepoCloned {
+            name: target.repo.clone(),
+            path: target_path.clone(),
+            url: url.to_string(),
+        });
 
         // Create repository config
-        let installed_repo = self.create_repository_config(&org, &repo_name, url, &target_path)?;
+        let installed_repo = self.create_repository_config(&target.name, url, &target_path);
 
         // Determine post-install actions
         let mut post_install_actions = Vec::new();
@@ -101,7 +241,7 @@ impl RepositoryInstaller {
             post_install_actions.push(PostInstallAction::OpenInEditor("vscode".to_string()));
         }
 
-        println!("{} Successfully cloned repository", "✅".green());
+        display_println!("{} Successfully cloned repository", "✅".green());
 
         Ok(InstalledRepository {
             repository: installed_repo,
@@ -175,12 +315,10 @@ impl RepositoryInstaller {
         .into())
     }
 
-    fn calculate_install_path(&self, org: &str, repo: &str) -> PathBuf {
-        if self.git_config.standardize_paths {
-            self.workspace_root.join(org).join(repo)
-        } else {
-            self.workspace_root.join(repo)
-        }
+    fn calculate_install_path(&self, host: &str, org: &str, repo: &str) -> PathBuf {
+        self.git_config
+            .clone_layout
+            .destination(&self.workspace_root, host, org, repo)
     }
 
     async fn clone_repository(&self, url: &str, target_path: &Path) -> Result<()> {
@@ -201,23 +339,23 @@ impl RepositoryInstaller {
         Ok(())
     }
 
-    fn create_repository_config(
-        &self,
-        org: &str,
-        repo_name: &str,
-        url: &str,
-        path: &Path,
-    ) -> Result<ConfigRepository> {
+    /// Configuration entry for a clone of `url` at `path`
+    pub fn create_repository_config(&self, name: &str, url: &str, path: &Path) -> ConfigRepository {
         use std::collections::HashMap;
 
-        Ok(ConfigRepository {
-            name: format!("{org}/{repo_name}"),
+        ConfigRepository {
+            name: name.to_string(),
             path: path.to_path_buf(),
             url: Some(url.to_string()),
+            upstream: None,
+            license: None,
             branch: None, // Will be detected from the actual repository
             apps: HashMap::new(),
             worktree_config: None,
-        })
+            subprojects: Vec::new(),
+            exclude: Vec::new(),
+            compare_ref: None,
+        }
     }
 
     pub async fn execute_post_install_actions(
@@ -228,7 +366,7 @@ impl RepositoryInstaller {
         for action in actions {
             match action {
                 PostInstallAction::RunNpmInstall => {
-                    println!("{} Running npm install...", "📦".cyan());
+                    display_println!("{} Running npm install...", "📦".cyan());
                     let output = Command::new("npm")
                         .arg("install")
                         .current_dir(repo_path)
@@ -236,11 +374,11 @@ impl RepositoryInstaller {
                         .await?;
 
                     if !output.status.success() {
-                        eprintln!("Warning: npm install failed");
+                        display_warn!("Warning: npm install failed");
                     }
                 }
                 PostInstallAction::RunCargoCheck => {
-                    println!("{} Running cargo check...", "🦀".cyan());
+                    display_println!("{} Running cargo check...", "🦀".cyan());
                     let output = Command::new("cargo")
                         .arg("check")
                         .current_dir(repo_path)
@@ -248,11 +386,11 @@ impl RepositoryInstaller {
                         .await?;
 
                     if !output.status.success() {
-                        eprintln!("Warning: cargo check failed");
+                        display_warn!("Warning: cargo check failed");
                     }
                 }
                 PostInstallAction::OpenInEditor(editor) => {
-                    println!("{} Opening in {}...", "📝".cyan(), editor);
+                    display_println!("{} Opening in {}...", "📝".cyan(), editor);
                     // This would integrate with the existing app launching functionality
                     // For now, we'll just print a message
                 }
@@ -261,3 +399,79 @@ impl RepositoryInstaller {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::CloneLayout;
+
+    #[test]
+    fn test_install_path_follows_layout() {
+        let root = Path::new("/work");
+        let path = |layout| {
+            let installer = RepositoryInstaller::new(
+                root.to_path_buf(),
+                GitConfig {
+                    clone_layout: layout,
+                    ..GitConfig::default()
+                },
+            );
+            installer.calculate_install_path("github.com", "acme", "api")
+        };
+        assert_eq!(path(CloneLayout::Flat), root.join("api"));
+        assert_eq!(path(CloneLayout::Owner), root.join("acme/api"));
+        assert_eq!(
+            path(CloneLayout::HostOwner),
+            root.join("github.com/acme/api")
+        );
+    }
+
+    #[test]
+    fn test_namespaced_and_renamed_targets() {
+        let installer = |layout| {
+            RepositoryInstaller::new(
+                PathBuf::from("/work"),
+                GitConfig {
+                    clone_layout: layout,
+                    ..GitConfig::default()
+                },
+            )
+        };
+
+        let flat = installer(CloneLayout::Flat);
+        let target = flat.target("https://github.com/acme/api", None).unwrap();
+        assert_eq!(target.path, Path::new("/work/api"));
+        let namespaced = flat.namespaced(&target).unwrap();
+        assert_eq!(namespaced.name, "acme/api");
+        assert_eq!(namespaced.path, Path::new("/work/acme/api"));
+
+        let owner = installer(CloneLayout::Owner);
+        let target = owner.target("git@github.com:acme/api.git", None).unwrap();
+        let namespaced = owner.namespaced(&target).unwrap();
+        assert_eq!(namespaced.name, "github.com/acme/api");
+        assert_eq!(namespaced.path, Path::new("/work/github.com/acme/api"));
+
+        let renamed = owner.renamed(&target, "acme-api");
+        assert_eq!(renamed.name, "acme-api");
+        assert_eq!(renamed.path, Path::new("/work/acme/acme-api"));
+        // A chosen directory has no deeper level to move to
+        assert_eq!(owner.namespaced(&renamed), None);
+        assert_eq!(installer(CloneLayout::HostOwner).namespaced(&target), None);
+    }
+
+    #[test]
+    fn test_same_remote() {
+        assert!(same_remote(
+            "git@github.com:acme/api.git",
+            "https://github.com/Acme/api"
+        ));
+        assert!(!same_remote(
+            "https://github.com/acme/api",
+            "https://github.com/globex/api"
+        ));
+        assert!(!same_remote(
+            "https://github.com/acme/api",
+            "https://gitlab.com/acme/api"
+        ));
+    }
+}
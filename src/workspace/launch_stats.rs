@@ -0,0 +1,254 @@
+//! App launch history for `vibe stats usage`
+//!
+//! Every successful launch of a repository in an app is recorded in the
+//! repository cache with where it came from. The history stays on this
+//! machine and is removed with the rest of the cache by `vibe config reset`. Besides
+//! the usage report, it picks the default app when `vibe open` finds several
+//! configured apps for a repository.
+
+use chrono::{DateTime, Duration, Utc};
+use console::style;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::cache::AppLaunch;
+use crate::display_println;
+use crate::output::delimited::Tabular;
+use crate::output::theme::{self, Role};
+
+/// Default report windows, in days
+pub const DEFAULT_WINDOWS: [u32; 2] = [7, 30];
+
+/// How far back launches count when picking the most used app
+pub const MOST_USED_WINDOW_DAYS: i64 = 90;
+
+/// Where a launch was started from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LaunchSource {
+    #[default]
+    Cli,
+    Menu,
+    Mcp,
+    Uri,
+}
+
+impl LaunchSource {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LaunchSource::Cli => "cli",
+            LaunchSource::Menu => "menu",
+            LaunchSource::Mcp => "mcp",
+            LaunchSource::Uri => "uri",
+        }
+    }
+}
+
+/// Launches of one repository in one app
+#[derive(Debug, Clone, Serialize)]
+pub struct UsageRow {
+    pub repo: String,
+    pub app: String,
+    /// Launch counts, one per report window
+    pub launches: Vec<usize>,
+    pub last_launched: DateTime<Utc>,
+}
+
+impl Tabular for UsageRow {
+    const FIELDS: &'static [&'static str] = &["repo", "app", "launches", "last_launched"];
+}
+
+/// Launches per repository and app over several windows
+#[derive(Debug, Clone, Serialize)]
+pub struct UsageReport {
+    pub windows_days: Vec<u32>,
+    pub generated_at: DateTime<Utc>,
+    /// Most launched first
+    pub rows: Vec<UsageRow>,
+    /// Launches in the widest window by source (`cli`, `menu`, `mcp`, `uri`)
+    pub by_source: BTreeMap<String, usize>,
+}
+
+impl UsageReport {
+    /// Summarize `launches` for each window, counting back from `now`
+    pub fn new(launches: &[AppLaunch], windows_days: &[u32], now: DateTime<Utc>) -> Self {
+        let mut windows_days = windows_days.to_vec();
+        windows_days.sort_unstable();
+        windows_days.dedup();
+        let widest = windows_days.last().copied().unwrap_or(0);
+        let within = |launch: &AppLaunch, days: u32| {
+            launch.launched_at >= now - Duration::days(i64::from(days))
+        };
+
+        let mut rows: HashMap<(&str, &str), UsageRow> = HashMap::new();
+        let mut by_source = BTreeMap::new();
+        for launch in launches.iter().filter(|l| within(l, widest)) {
+            *by_source.entry(launch.source.clone()).or_insert(0) += 1;
+            let row = rows
+                .entry((&launch.repo, &launch.app))
+                .or_insert_with(|| UsageRow {
+                    repo: launch.repo.clone(),
+                    app: launch.app.clone(),
+                    launches: vec![0; windows_days.len()],
+                    last_launched: launch.launched_at,
+                });
+            for (count, days) in row.launches.iter_mut().zip(&windows_days) {
+                if within(launch, *days) {
+                    *count += 1;
+                }
+            }
+            row.last_launched = row.last_launched.max(launch.launched_at);
+        }
+
+        let mut rows: Vec<UsageRow> = rows.into_values().collect();
+        rows.sort_by(|a, b| {
+            b.launches
+                .last()
+                .cmp(&a.launches.last())
+                .then_with(|| b.last_launched.cmp(&a.last_launched))
+        });
+
+        Self {
+            windows_days,
+            generated_at: now,
+            rows,
+            by_source,
+        }
+    }
+
+    pub fn print_table(&self) {
+        if self.rows.is_empty() {
+            display_println!(
+                "{} No app launches recorded in the last {} days",
+                style("ℹ").yellow(),
+                self.windows_days.last().copied().unwrap_or(0)
+            );
+            return;
+        }
+
+        let repo_width = self
+            .rows
+            .iter()
+            .map(|r| r.repo.len())
+            .max()
+            .unwrap_or(0)
+            .max("Repository".len());
+        let app_width = self
+            .rows
+            .iter()
+            .map(|r| r.app.len())
+            .max()
+            .unwrap_or(0)
+            .max("App".len());
+
+        let mut header = format!("{:<repo_width$}  {:<app_width$}", "Repository", "App");
+        for days in &self.windows_days {
+            header.push_str(&format!("  {:>6}", format!("{days}d")));
+        }
+        header.push_str("  Last used");
+        display_println!("{}", style(&header).bold());
+
+        for row in &self.rows {
+            let mut line = format!("{:<repo_width$}  {:<app_width$}", row.repo, row.app);
+            for count in &row.launches {
+                line.push_str(&format!("  {count:>6}"));
+            }
+            display_println!(
+                "{line}  {}",
+                theme::paint(
+                    Role::Dim,
+                    crate::ui::formatting::format_time_ago(&row.last_launched)
+                )
+            );
+        }
+
+        let sources: Vec<String> = self
+            .by_source
+            .iter()
+            .map(|(source, count)| format!("{source} {count}"))
+            .collect();
+        display_println!();
+        display_println!(
+            "{}",
+            theme::paint(Role::Dim, format!("By source: {}", sources.join(", ")))
+        );
+    }
+}
+
+/// The app in `candidates` launched most often for `repo`, breaking ties by
+/// the most recent launch; `None` when none of them has been launched
+pub fn most_used_app(launches: &[AppLaunch], repo: &str, candidates: &[String]) -> Option<String> {
+    let mut usage: HashMap<&str, (usize, DateTime<Utc>)> = HashMap::new();
+    for launch in launches
+        .iter()
+        .filter(|l| l.repo == repo && candidates.contains(&l.app))
+    {
+        let entry = usage.entry(&launch.app).or_insert((0, launch.launched_at));
+        entry.0 += 1;
+        entry.1 = entry.1.max(launch.launched_at);
+    }
+    usage
+        .into_iter()
+        .max_by_key(|(_, usage)| *usage)
+        .map(|(app, _)| app.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn launch(repo: &str, app: &str, source: &str, days_ago: i64, now: DateTime<Utc>) -> AppLaunch {
+        AppLaunch {
+            repo: repo.to_string(),
+            app: app.to_string(),
+            source: source.to_string(),
+            launched_at: now - Duration::days(days_ago),
+        }
+    }
+
+    #[test]
+    fn test_usage_report_windows() {
+        let now = Utc::now();
+        let launches = vec![
+            launch("api", "vscode", "cli", 20, now),
+            launch("api", "vscode", "menu", 2, now),
+            launch("api", "warp", "cli", 1, now),
+            launch("web", "cursor", "mcp", 60, now),
+        ];
+        let report = UsageReport::new(&launches, &[30, 7, 7], now);
+
+        assert_eq!(report.windows_days, vec![7, 30]);
+        assert_eq!(report.rows.len(), 2);
+        assert_eq!(report.rows[0].app, "vscode");
+        assert_eq!(report.rows[0].launches, vec![1, 2]);
+        assert_eq!(report.rows[1].launches, vec![1, 1]);
+        assert_eq!(report.by_source.get("cli"), Some(&2));
+        assert_eq!(report.by_source.get("mcp"), None);
+    }
+
+    #[test]
+    fn test_most_used_app() {
+        let now = Utc::now();
+        let launches = vec![
+            launch("api", "vscode", "cli", 5, now),
+            launch("api", "warp", "cli", 3, now),
+            launch("api", "warp", "menu", 2, now),
+            launch("api", "cursor", "cli", 1, now),
+            launch("web", "vscode", "cli", 1, now),
+        ];
+        let configured = |apps: &[&str]| apps.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(
+            most_used_app(&launches, "api", &configured(&["vscode", "warp"])).as_deref(),
+            Some("warp")
+        );
+        // Ties go to the most recently launched app
+        assert_eq!(
+            most_used_app(&launches, "api", &configured(&["vscode", "cursor"])).as_deref(),
+            Some("cursor")
+        );
+        assert_eq!(
+            most_used_app(&launches, "web", &configured(&["warp"])),
+            None
+        );
+    }
+}
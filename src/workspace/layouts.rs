@@ -0,0 +1,260 @@
+//! Detection of existing multi-repository layouts for first-time setup
+//!
+//! Probes a few well-known locations (a ghq root, `~/src/github.com`,
+//! `~/Workspace`) with shallow directory reads so the setup wizard can offer
+//! them as a workspace root, grouped by owner/organization.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use super::config::ORG_GROUP_PREFIX;
+
+/// How repositories are arranged under a detected root
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LayoutKind {
+    /// `<root>/<host>/<owner>/<repo>` managed by ghq
+    Ghq,
+    /// `<root>/<host>/<owner>/<repo>`, e.g. `~/src/github.com/org/repo`
+    HostOwnerRepo,
+    /// `<root>/<repo>` or `<root>/<owner>/<repo>`
+    Workspace,
+}
+
+impl LayoutKind {
+    /// Directory depth of the owner level below the root, if the layout has one
+    fn owner_depth(self) -> usize {
+        match self {
+            LayoutKind::Ghq | LayoutKind::HostOwnerRepo => 2,
+            LayoutKind::Workspace => 1,
+        }
+    }
+}
+
+impl fmt::Display for LayoutKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            LayoutKind::Ghq => "ghq root",
+            LayoutKind::HostOwnerRepo => "host/owner/repo tree",
+            LayoutKind::Workspace => "workspace directory",
+        };
+        f.write_str(label)
+    }
+}
+
+/// An existing layout found on disk
+#[derive(Debug, Clone)]
+pub struct DetectedLayout {
+    pub root: PathBuf,
+    pub kind: LayoutKind,
+    pub repos: Vec<PathBuf>,
+    /// Repositories keyed by owner/organization directory name
+    pub orgs: BTreeMap<String, Vec<PathBuf>>,
+}
+
+impl DetectedLayout {
+    /// Group name used when importing an organization
+    pub fn org_group_name(org: &str) -> String {
+        format!("{ORG_GROUP_PREFIX}{org}")
+    }
+}
+
+impl fmt::Display for DetectedLayout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} ({}, {} repos",
+            self.root.display(),
+            self.kind,
+            self.repos.len()
+        )?;
+        if !self.orgs.is_empty() {
+            write!(f, " in {} orgs", self.orgs.len())?;
+        }
+        write!(f, ")")
+    }
+}
+
+/// Probe the well-known layout locations under `home`. Only layouts that
+/// contain at least one repository are returned, each root at most once.
+pub fn detect_layouts(home: &Path, ghq_root: Option<PathBuf>) -> Vec<DetectedLayout> {
+    let mut candidates = Vec::new();
+    if let Some(root) = ghq_root {
+        candidates.push((root, LayoutKind::Ghq));
+    }
+    for src in ["src", "go/src"] {
+        let root = home.join(src);
+        if root.join("github.com").is_dir() {
+            candidates.push((root, LayoutKind::HostOwnerRepo));
+        }
+    }
+    for workspace in ["Workspace", "workspace"] {
+        candidates.push((home.join(workspace), LayoutKind::Workspace));
+    }
+
+    let mut seen = Vec::new();
+    let mut layouts = Vec::new();
+    for (root, kind) in candidates {
+        let Ok(canonical) = root.canonicalize() else {
+            continue;
+        };
+        if seen.contains(&canonical) {
+            continue;
+        }
+        seen.push(canonical);
+
+        if let Some(layout) = scan_layout(&root, kind) {
+            layouts.push(layout);
+        }
+    }
+
+    layouts
+}
+
+/// The ghq root from `$GHQ_ROOT`, `git config ghq.root`, or `~/ghq`
+pub fn ghq_root(home: &Path) -> Option<PathBuf> {
+    if let Some(root) = std::env::var_os("GHQ_ROOT").filter(|root| !root.is_empty()) {
+        return Some(PathBuf::from(root));
+    }
+
+    let configured = std::process::Command::new("git")
+        .args(["config", "--global", "--get", "ghq.root"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .filter(|root| !root.is_empty());
+
+    let root = match configured {
+        Some(root) => match root.strip_prefix("~/") {
+            Some(rest) => home.join(rest),
+            None => PathBuf::from(root),
+        },
+        None => home.join("ghq"),
+    };
+    root.is_dir().then_some(root)
+}
+
+fn scan_layout(root: &Path, kind: LayoutKind) -> Option<DetectedLayout> {
+    let mut repos = Vec::new();
+    let mut orgs: BTreeMap<String, Vec<PathBuf>> = BTreeMap::new();
+    collect_repos(root, 0, kind.owner_depth(), None, &mut repos, &mut orgs);
+
+    if repos.is_empty() {
+        return None;
+    }
+    repos.sort();
+
+    Some(DetectedLayout {
+        root: root.to_path_buf(),
+        kind,
+        repos,
+        orgs,
+    })
+}
+
+/// Walk `dir` down to one level below the owner directories, recording git
+/// repositories and the owner each one sits under
+fn collect_repos(
+    dir: &Path,
+    depth: usize,
+    owner_depth: usize,
+    owner: Option<&str>,
+    repos: &mut Vec<PathBuf>,
+    orgs: &mut BTreeMap<String, Vec<PathBuf>>,
+) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.filter_map(|entry| entry.ok()) {
+        let path = entry.path();
+        let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        if name.starts_with('.') || !path.is_dir() {
+            continue;
+        }
+
+        if path.join(".git").exists() {
+            if let Some(owner) = owner {
+                orgs.entry(owner.to_string())
+                    .or_default()
+                    .push(path.clone());
+            }
+            repos.push(path);
+        } else if depth < owner_depth {
+            let owner = if depth + 1 == owner_depth {
+                Some(name)
+            } else {
+                None
+            };
+            collect_repos(&path, depth + 1, owner_depth, owner, repos, orgs);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn make_repo(path: &Path) {
+        std::fs::create_dir_all(path.join(".git")).unwrap();
+    }
+
+    #[test]
+    fn test_detect_host_owner_repo_layout_groups_by_org() {
+        let home = TempDir::new().unwrap();
+        let src = home.path().join("src/github.com");
+        make_repo(&src.join("toolprint/vibe-workspace"));
+        make_repo(&src.join("toolprint/docs"));
+        make_repo(&src.join("rust-lang/cargo"));
+        std::fs::create_dir_all(src.join("empty-org")).unwrap();
+
+        let layouts = detect_layouts(home.path(), None);
+        assert_eq!(layouts.len(), 1);
+
+        let layout = &layouts[0];
+        assert_eq!(layout.kind, LayoutKind::HostOwnerRepo);
+        assert_eq!(layout.root, home.path().join("src"));
+        assert_eq!(layout.repos.len(), 3);
+        assert_eq!(
+            layout.orgs.keys().collect::<Vec<_>>(),
+            vec!["rust-lang", "toolprint"]
+        );
+        assert_eq!(layout.orgs["toolprint"].len(), 2);
+    }
+
+    #[test]
+    fn test_detect_workspace_layout_with_flat_and_owner_repos() {
+        let home = TempDir::new().unwrap();
+        let workspace = home.path().join("Workspace");
+        make_repo(&workspace.join("scratch"));
+        make_repo(&workspace.join("acme/api"));
+
+        let layouts = detect_layouts(home.path(), None);
+        assert_eq!(layouts.len(), 1);
+        assert_eq!(layouts[0].kind, LayoutKind::Workspace);
+        assert_eq!(layouts[0].repos.len(), 2);
+        assert_eq!(layouts[0].orgs.len(), 1);
+        assert_eq!(layouts[0].orgs["acme"], vec![workspace.join("acme/api")]);
+    }
+
+    #[test]
+    fn test_same_root_is_reported_once() {
+        let home = TempDir::new().unwrap();
+        make_repo(&home.path().join("src/github.com/acme/api"));
+
+        let layouts = detect_layouts(home.path(), Some(home.path().join("src")));
+        assert_eq!(layouts.len(), 1);
+        assert_eq!(layouts[0].kind, LayoutKind::Ghq);
+    }
+
+    #[test]
+    fn test_no_layouts_without_repositories() {
+        let home = TempDir::new().unwrap();
+        std::fs::create_dir_all(home.path().join("Workspace/notes")).unwrap();
+        assert!(detect_layouts(home.path(), None).is_empty());
+    }
+}
@@ -0,0 +1,607 @@
+//! License detection for `vibe scan licenses`
+//!
+//! Each repository's root is searched for license files (`LICENSE*`,
+//! `LICENCE*`, `COPYING*`, `UNLICENSE`). A file is identified by its
+//! `SPDX-License-Identifier` line when it has one, and otherwise by phrases
+//! that only appear in one license text. Identifications are cached by the
+//! file's hash, so unchanged files are not matched again. Repositories
+//! without a recognized license file fall back to the license the hosting
+//! provider reported when they were cloned.
+
+use anyhow::{Context, Result};
+use console::style;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+use crate::cache::RepositoryCache;
+use crate::display_println;
+use crate::output::delimited::Tabular;
+use crate::output::theme::{self, Icon, Role};
+use crate::workspace::config::Repository;
+
+/// Bumped when matching changes, so cached identifications are redone
+const MATCHER_VERSION: &str = "v1";
+
+/// File name prefixes of license files, compared case-insensitively
+const LICENSE_FILE_PREFIXES: [&str; 4] = ["license", "licence", "copying", "unlicense"];
+
+/// SPDX identifiers matched by name, for normalizing provider keys like
+/// `apache-2.0` and `SPDX-License-Identifier` lines
+const KNOWN_LICENSES: [&str; 22] = [
+    "0BSD",
+    "AGPL-3.0",
+    "Apache-2.0",
+    "BSD-2-Clause",
+    "BSD-3-Clause",
+    "BSL-1.0",
+    "CC-BY-4.0",
+    "CC-BY-SA-4.0",
+    "CC0-1.0",
+    "EPL-2.0",
+    "GPL-2.0",
+    "GPL-3.0",
+    "ISC",
+    "LGPL-2.1",
+    "LGPL-3.0",
+    "MIT",
+    "MPL-2.0",
+    "MS-PL",
+    "Unlicense",
+    "UPL-1.0",
+    "WTFPL",
+    "Zlib",
+];
+
+/// Phrases identifying a license text, checked in order against the
+/// lowercased text with whitespace collapsed. Every phrase of an entry must
+/// appear; more specific licenses come before the ones they contain.
+const FINGERPRINTS: &[(&str, &[&str])] = &[
+    ("AGPL-3.0", &["gnu affero general public license", "version 3"]),
+    ("LGPL-3.0", &["gnu lesser general public license", "version 3"]),
+    ("LGPL-2.1", &["gnu lesser general public license", "version 2.1"]),
+    ("GPL-3.0", &["gnu general public license", "version 3"]),
+    ("GPL-2.0", &["gnu general public license", "version 2"]),
+    ("Apache-2.0", &["apache license", "version 2.0"]),
+    ("MPL-2.0", &["mozilla public license", "2.0"]),
+    ("EPL-2.0", &["eclipse public license", "2.0"]),
+    ("BSL-1.0", &["boost software license - version 1.0"]),
+    ("CC0-1.0", &["cc0 1.0 universal"]),
+    (
+        "Unlicense",
+        &["this is free and unencumbered software released into the public domain"],
+    ),
+    (
+        "0BSD",
+        &[
+            "permission to use, copy, modify, and/or distribute this software for any purpose with or without fee is hereby granted",
+            "the software is provided \"as is\" and the author disclaims all warranties",
+        ],
+    ),
+    (
+        "ISC",
+        &["permission to use, copy, modify, and/or distribute this software for any purpose with or without fee is hereby granted, provided that the above copyright notice"],
+    ),
+    (
+        "BSD-3-Clause",
+        &[
+            "redistribution and use in source and binary forms",
+            "neither the name",
+        ],
+    ),
+    (
+        "BSD-2-Clause",
+        &["redistribution and use in source and binary forms"],
+    ),
+    (
+        "MIT",
+        &["permission is hereby granted, free of charge, to any person obtaining a copy"],
+    ),
+    ("Zlib", &["this software is provided 'as-is', without any express or implied warranty"]),
+    ("WTFPL", &["do what the fuck you want to public license"]),
+];
+
+/// Canonical spelling of a known SPDX identifier or provider license key.
+/// `None` for placeholders like GitHub's `NOASSERTION` and `other`.
+pub fn normalize(id: &str) -> Option<String> {
+    let id = id.trim();
+    if id.is_empty() || id.eq_ignore_ascii_case("noassertion") || id.eq_ignore_ascii_case("other") {
+        return None;
+    }
+    let known = KNOWN_LICENSES.iter().find(|known| {
+        id.eq_ignore_ascii_case(known)
+            || id
+                .get(..known.len())
+                .is_some_and(|prefix| prefix.eq_ignore_ascii_case(known))
+                && id[known.len()..].starts_with(['-', '+'])
+    });
+    Some(match known {
+        // Keep suffixes such as `-only` and `-or-later`
+        Some(known) => format!("{known}{}", &id[known.len()..]),
+        None => id.to_string(),
+    })
+}
+
+/// Identify the license in the text of a license file
+pub fn identify(text: &str) -> Option<String> {
+    if let Some(id) = text.lines().find_map(|line| {
+        line.split_once("SPDX-License-Identifier:")
+            .map(|(_, id)| id.trim().trim_end_matches("*/").trim())
+    }) {
+        return normalize(id);
+    }
+
+    let text = text
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase();
+    FINGERPRINTS
+        .iter()
+        .find(|(_, phrases)| phrases.iter().all(|phrase| text.contains(phrase)))
+        .map(|(id, _)| id.to_string())
+}
+
+/// Whether `license` is covered by a deny-list entry: the same identifier,
+/// or one that extends it like `GPL-3.0-only` extends `GPL-3.0` and `GPL`
+pub fn is_denied(license: &str, deny: &[String]) -> bool {
+    deny.iter().any(|entry| {
+        let entry = entry.trim();
+        license.eq_ignore_ascii_case(entry)
+            || license
+                .get(..entry.len())
+                .is_some_and(|prefix| prefix.eq_ignore_ascii_case(entry))
+                && license[entry.len()..].starts_with(['-', '+'])
+    })
+}
+
+/// Where a repository's license was found
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "source", rename_all = "snake_case")]
+pub enum LicenseSource {
+    File { file: String },
+    Provider,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DetectedLicense {
+    pub spdx: String,
+    #[serde(flatten)]
+    pub source: LicenseSource,
+}
+
+/// Overall verdict for one repository
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LicenseStatus {
+    Licensed,
+    /// A license file exists but matched no known license
+    Unrecognized,
+    Missing,
+    Denied,
+}
+
+impl LicenseStatus {
+    fn label(self) -> &'static str {
+        match self {
+            LicenseStatus::Licensed => "licensed",
+            LicenseStatus::Unrecognized => "unrecognized",
+            LicenseStatus::Missing => "missing",
+            LicenseStatus::Denied => "denied",
+        }
+    }
+}
+
+/// Licenses found in one repository
+#[derive(Debug, Clone, Serialize)]
+pub struct RepoLicenses {
+    pub name: String,
+    pub path: PathBuf,
+    pub status: LicenseStatus,
+    pub licenses: Vec<DetectedLicense>,
+    /// License files that matched no known license
+    pub unrecognized_files: Vec<String>,
+    /// Detected licenses on the deny-list
+    pub denied: Vec<String>,
+}
+
+impl RepoLicenses {
+    fn new(
+        name: String,
+        path: PathBuf,
+        licenses: Vec<DetectedLicense>,
+        unrecognized_files: Vec<String>,
+        deny: &[String],
+    ) -> Self {
+        let mut denied: Vec<String> = licenses
+            .iter()
+            .filter(|license| is_denied(&license.spdx, deny))
+            .map(|license| license.spdx.clone())
+            .collect();
+        denied.dedup();
+        let status = if !denied.is_empty() {
+            LicenseStatus::Denied
+        } else if !licenses.is_empty() {
+            LicenseStatus::Licensed
+        } else if !unrecognized_files.is_empty() {
+            LicenseStatus::Unrecognized
+        } else {
+            LicenseStatus::Missing
+        };
+        Self {
+            name,
+            path,
+            status,
+            licenses,
+            unrecognized_files,
+            denied,
+        }
+    }
+
+    /// Distinct SPDX identifiers, in the order found
+    pub fn spdx_ids(&self) -> Vec<&str> {
+        let mut ids: Vec<&str> = Vec::new();
+        for license in &self.licenses {
+            if !ids.contains(&license.spdx.as_str()) {
+                ids.push(&license.spdx);
+            }
+        }
+        ids
+    }
+}
+
+/// Licenses across the workspace
+#[derive(Debug, Clone, Serialize)]
+pub struct LicenseReport {
+    pub repositories: Vec<RepoLicenses>,
+    /// Repositories per license; `none` and `unrecognized` count the others
+    pub summary: BTreeMap<String, usize>,
+    pub deny: Vec<String>,
+    pub missing: usize,
+    pub unrecognized: usize,
+    pub denied: usize,
+}
+
+impl LicenseReport {
+    pub fn new(mut repositories: Vec<RepoLicenses>, deny: Vec<String>) -> Self {
+        repositories.sort_by(|a, b| a.name.cmp(&b.name));
+        let mut summary = BTreeMap::new();
+        for repo in &repositories {
+            let ids = repo.spdx_ids();
+            if ids.is_empty() {
+                let key = match repo.status {
+                    LicenseStatus::Unrecognized => "unrecognized",
+                    _ => "none",
+                };
+                *summary.entry(key.to_string()).or_default() += 1;
+            }
+            for id in ids {
+                *summary.entry(id.to_string()).or_default() += 1;
+            }
+        }
+        let count = |status| repositories.iter().filter(|r| r.status == status).count();
+        Self {
+            missing: count(LicenseStatus::Missing),
+            unrecognized: count(LicenseStatus::Unrecognized),
+            denied: count(LicenseStatus::Denied),
+            summary,
+            deny,
+            repositories,
+        }
+    }
+
+    pub fn print_table(&self) {
+        if self.repositories.is_empty() {
+            display_println!("{}", theme::labeled(Icon::Info, "No repositories to scan"));
+            return;
+        }
+
+        let name_width = self
+            .repositories
+            .iter()
+            .map(|r| r.name.len())
+            .max()
+            .unwrap_or(0)
+            .max("Repository".len());
+        display_println!(
+            "{}",
+            style(format!(
+                "{:<name_width$}  {:<12}  {:<24}  Source",
+                "Repository", "Status", "License"
+            ))
+            .bold()
+        );
+        for repo in &self.repositories {
+            let ids = repo.spdx_ids();
+            let license = if ids.is_empty() {
+                "-".to_string()
+            } else {
+                ids.join(", ")
+            };
+            let source = match repo.licenses.first().map(|l| &l.source) {
+                Some(LicenseSource::File { file }) => file.clone(),
+                Some(LicenseSource::Provider) => "provider".to_string(),
+                None => repo.unrecognized_files.join(", "),
+            };
+            let status = format!("{:<12}", repo.status.label());
+            let status = match repo.status {
+                LicenseStatus::Licensed => theme::paint(Role::Success, status),
+                LicenseStatus::Unrecognized | LicenseStatus::Missing => {
+                    theme::paint(Role::Warning, status)
+                }
+                LicenseStatus::Denied => theme::paint(Role::Error, status),
+            };
+            display_println!(
+                "{:<name_width$}  {}  {:<24}  {}",
+                repo.name,
+                status,
+                license,
+                theme::paint(Role::Dim, source)
+            );
+        }
+
+        display_println!();
+        display_println!("{}", style("By license").bold());
+        for (license, count) in &self.summary {
+            display_println!("  {license:<24} {count}");
+        }
+        display_println!();
+        display_println!(
+            "{} repositories: {} without a license, {} unrecognized, {} deny-listed",
+            self.repositories.len(),
+            self.missing,
+            self.unrecognized,
+            self.denied
+        );
+    }
+
+    /// One row per repository, for CSV and TSV export
+    pub fn rows(&self) -> Vec<LicenseRow<'_>> {
+        self.repositories
+            .iter()
+            .map(|repo| LicenseRow {
+                name: &repo.name,
+                path: &repo.path,
+                status: repo.status,
+                licenses: repo.spdx_ids(),
+                denied: &repo.denied,
+            })
+            .collect()
+    }
+}
+
+/// A repository's verdict with its licenses as SPDX identifiers
+#[derive(Debug, Serialize)]
+pub struct LicenseRow<'a> {
+    pub name: &'a str,
+    pub path: &'a Path,
+    pub status: LicenseStatus,
+    pub licenses: Vec<&'a str>,
+    pub denied: &'a [String],
+}
+
+impl Tabular for LicenseRow<'_> {
+    const FIELDS: &'static [&'static str] = &["name", "path", "status", "licenses", "denied"];
+}
+
+fn is_license_file(name: &str) -> bool {
+    let name = name.to_lowercase();
+    LICENSE_FILE_PREFIXES
+        .iter()
+        .any(|prefix| name.starts_with(prefix))
+}
+
+/// License files at the root of `repo`, sorted by name
+async fn license_files(repo: &Path) -> Result<Vec<PathBuf>> {
+    let mut entries = tokio::fs::read_dir(repo)
+        .await
+        .with_context(|| format!("Failed to read {}", repo.display()))?;
+    let mut files = Vec::new();
+    while let Some(entry) = entries.next_entry().await? {
+        let is_file = entry.file_type().await.is_ok_and(|t| t.is_file());
+        if is_file && is_license_file(&entry.file_name().to_string_lossy()) {
+            files.push(entry.path());
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Identify one license file, through the cache when possible
+async fn identify_file(path: &Path, cache: Option<&RepositoryCache>) -> Result<Option<String>> {
+    let bytes = tokio::fs::read(path)
+        .await
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let hash = format!("{MATCHER_VERSION}:{:x}", Sha256::digest(&bytes));
+
+    if let Some(cache) = cache {
+        match cache.get_file_license(&hash).await {
+            Ok(Some(license)) => return Ok(license),
+            Ok(None) => {}
+            Err(e) => warn!(
+                "Failed to read cached license for {}: {}",
+                path.display(),
+                e
+            ),
+        }
+    }
+
+    let license = identify(&String::from_utf8_lossy(&bytes));
+    if let Some(cache) = cache {
+        if let Err(e) = cache.cache_file_license(&hash, license.as_deref()).await {
+            warn!("Failed to cache license for {}: {}", path.display(), e);
+        }
+    }
+    Ok(license)
+}
+
+/// Find the licenses of one repository checked out at `path`
+pub async fn scan_repository(
+    repo: &Repository,
+    path: &Path,
+    deny: &[String],
+    cache: Option<&RepositoryCache>,
+) -> Result<RepoLicenses> {
+    let mut licenses = Vec::new();
+    let mut unrecognized_files = Vec::new();
+    for file in license_files(path).await? {
+        let file_name = file
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        match identify_file(&file, cache).await? {
+            Some(spdx) => licenses.push(DetectedLicense {
+                spdx,
+                source: LicenseSource::File { file: file_name },
+            }),
+            None => unrecognized_files.push(file_name),
+        }
+    }
+
+    if licenses.is_empty() {
+        if let Some(spdx) = repo.license.as_deref().and_then(normalize) {
+            licenses.push(DetectedLicense {
+                spdx,
+                source: LicenseSource::Provider,
+            });
+        }
+    }
+
+    Ok(RepoLicenses::new(
+        repo.name.clone(),
+        path.to_path_buf(),
+        licenses,
+        unrecognized_files,
+        deny,
+    ))
+}
+
+/// Scan every repository, returning the report and a warning per repository
+/// that couldn't be read
+pub async fn scan_repositories(
+    repos: Vec<(Repository, PathBuf)>,
+    deny: Vec<String>,
+    cache: Option<&RepositoryCache>,
+) -> (LicenseReport, Vec<String>) {
+    let mut scanned = Vec::with_capacity(repos.len());
+    let mut warnings = Vec::new();
+    for (repo, path) in repos {
+        match scan_repository(&repo, &path, &deny, cache).await {
+            Ok(licenses) => scanned.push(licenses),
+            Err(e) => warnings.push(format!("Failed to scan {}: {e:#}", repo.name)),
+        }
+    }
+    (LicenseReport::new(scanned, deny), warnings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::delimited::{render, Delimited};
+    use tempfile::TempDir;
+
+    const MIT: &str = "MIT License\n\nCopyright (c) 2024 Someone\n\nPermission is hereby granted, free of charge, to any person obtaining a copy\nof this software and associated documentation files...";
+
+    #[test]
+    fn test_identify() {
+        assert_eq!(identify(MIT).as_deref(), Some("MIT"));
+        assert_eq!(
+            identify("                                 Apache License\n                           Version 2.0, January 2004").as_deref(),
+            Some("Apache-2.0")
+        );
+        assert_eq!(
+            identify("GNU LESSER GENERAL PUBLIC LICENSE\n Version 3, 29 June 2007").as_deref(),
+            Some("LGPL-3.0")
+        );
+        assert_eq!(
+            identify("Redistribution and use in source and binary forms, with or without\nmodification... Neither the name of the copyright holder").as_deref(),
+            Some("BSD-3-Clause")
+        );
+        assert_eq!(
+            identify("// SPDX-License-Identifier: gpl-3.0-or-later").as_deref(),
+            Some("GPL-3.0-or-later")
+        );
+        assert_eq!(identify("All rights reserved."), None);
+    }
+
+    #[test]
+    fn test_normalize_and_deny() {
+        assert_eq!(normalize("apache-2.0").as_deref(), Some("Apache-2.0"));
+        assert_eq!(normalize("NOASSERTION"), None);
+        assert_eq!(normalize("Custom-1.0").as_deref(), Some("Custom-1.0"));
+
+        let deny = vec!["GPL".to_string(), "AGPL-3.0".to_string()];
+        assert!(is_denied("GPL-3.0-only", &deny));
+        assert!(is_denied("gpl-2.0", &deny));
+        assert!(is_denied("AGPL-3.0", &deny));
+        assert!(!is_denied("LGPL-2.1", &deny));
+        assert!(!is_denied("MIT", &deny));
+    }
+
+    #[tokio::test]
+    async fn test_scan_repositories() {
+        let temp = TempDir::new().unwrap();
+        for (name, files) in [
+            ("mit", vec![("LICENSE", MIT)]),
+            (
+                "gpl",
+                vec![(
+                    "COPYING",
+                    "GNU GENERAL PUBLIC LICENSE\nVersion 3, 29 June 2007",
+                )],
+            ),
+            ("custom", vec![("LICENSE.txt", "All rights reserved.")]),
+            ("bare", vec![]),
+            ("hosted", vec![]),
+        ] {
+            let dir = temp.path().join(name);
+            tokio::fs::create_dir(&dir).await.unwrap();
+            for (file, contents) in files {
+                tokio::fs::write(dir.join(file), contents).await.unwrap();
+            }
+        }
+
+        let cache = RepositoryCache::new(temp.path().join("cache.db"));
+        cache.initialize().await.unwrap();
+
+        let mut hosted = Repository::new("hosted", "hosted");
+        hosted.license = Some("apache-2.0".to_string());
+        let repos = ["mit", "gpl", "custom", "bare"]
+            .into_iter()
+            .map(|name| Repository::new(name, name))
+            .chain([hosted])
+            .map(|repo| {
+                let path = temp.path().join(&repo.path);
+                (repo, path)
+            })
+            .collect();
+
+        let (report, warnings) =
+            scan_repositories(repos, vec!["GPL".to_string()], Some(&cache)).await;
+        assert!(warnings.is_empty());
+        let status = |name: &str| {
+            report
+                .repositories
+                .iter()
+                .find(|r| r.name == name)
+                .unwrap()
+                .status
+        };
+        assert_eq!(status("mit"), LicenseStatus::Licensed);
+        assert_eq!(status("gpl"), LicenseStatus::Denied);
+        assert_eq!(status("custom"), LicenseStatus::Unrecognized);
+        assert_eq!(status("bare"), LicenseStatus::Missing);
+        assert_eq!(status("hosted"), LicenseStatus::Licensed);
+        assert_eq!(report.summary.get("Apache-2.0"), Some(&1));
+        assert_eq!(report.summary.get("none"), Some(&1));
+        assert_eq!(report.denied, 1);
+
+        let csv = render(&report.rows(), Delimited::Csv, &[]).unwrap();
+        assert!(csv.contains("\ngpl,"));
+        assert!(csv
+            .lines()
+            .any(|line| line.ends_with(",denied,GPL-3.0,GPL-3.0")));
+    }
+}
@@ -1,20 +1,76 @@
+use age::secrecy::SecretString;
 use anyhow::{Context, Result};
 use colored::*;
 use console::style;
+use serde::Serialize;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
-use tracing::{info, warn};
-
-use crate::display_println;
-
-use crate::cache::{GitStatusCache, RepositoryCache};
+use tokio::process::Command;
+use tracing::{debug, info, warn, Instrument};
+
+use crate::{data_println, display_eprintln, display_print, display_println, display_warn};
+
+use crate::cache::{AppLaunch, CachedGitStatus, CachedRepository, GitStatusCache, RepositoryCache};
+use crate::git::backend::GitBackendKind;
+use crate::git::branches::{collect_branch_reports, BranchKind, BranchReport, DEFAULT_STALE_DAYS};
+use crate::git::hooks;
+use crate::git::lfs;
+use crate::git::managed_files;
+use crate::git::protection::{self as branch_protection, ProtectedBranchError};
+use crate::git::provider::vector_store::{Suggestion, VectorStore};
+use crate::git::pull_requests::{self, is_gh_available, PrDashboard, PrFilter};
+use crate::git::query;
+use crate::git::ref_backup::{self, RefBackup};
+use crate::git::repo_lock::{self, RepoLock, RepoLocked};
+use crate::git::upstream::{self, UpstreamSync};
+use crate::git::{GitConfig, GitError};
+use crate::output;
+use crate::output::exit::{BatchSummary, CommandError};
+use crate::output::redact;
+use crate::output::theme::ThemePreferences;
+use crate::output::timings;
+use crate::ui::prompts::{confirm_destructive, offer_full_details, DestructiveAction};
+use crate::utils::git::normalize_git_url;
 
 use super::{
-    config::{AppConfig, Repository, WorkspaceConfig},
+    activity::{collect_activity, ActivityReport},
+    availability::OfflineRoot,
+    backup_crypto,
+    config::{
+        is_outside_root, repo_abs_path, AppConfig, BulkOperation, ConfirmationLevel, GroupDefaults,
+        Repository, StatusGroupBy, TemplatePackSource, WorkspaceConfig,
+    },
+    config_sync::{self, ConfigSyncReport},
+    contributors::{collect_contributors, ContributorReport},
     discovery::{
         discover_git_repositories, get_current_branch, get_remote_url, get_repository_name,
+        get_upstream_url,
+    },
+    events::{self, Event},
+    file_tree,
+    health::{self, HealthInputs, HealthReport},
+    history::{self, EventKind, UndoAction},
+    launch_stats::{self, LaunchSource},
+    licenses::{self, LicenseReport},
+    manifest::{self, ManifestRestoreReport, ManifestSkip, WorkspaceManifest},
+    operations::{
+        collect_git_statuses, default_max_parallel_git, execute_git_command,
+        get_git_status_against, GitOperation, GitStatus, StatusTarget,
     },
-    operations::{get_git_status, GitOperation, GitStatus},
+    project_env,
+    project_types::{self, ProjectType},
+    removed_repos,
+    repo_analyzer::{NonGitFolder, RepoInfo, WorkspaceAnalysis},
+    reset_preview::{ClearedRepository, FactoryResetPreview, RepositoryResetPreview, ResetPath},
+    search,
+    status_groups::{GroupedStatus, StatusGrouping},
+    status_snapshot::{RepoStatusSnapshot, StatusRefresh, StatusScope, STATUS_CACHE_TTL},
+    subprojects::{self, SubprojectDetection},
+    sync_operations::{CleanMode, SyncReport},
+    template_packs::{self, PackInstallReport, PackTemplate},
     templates::TemplateManager,
+    trash,
 };
 
 #[derive(Debug, Clone)]
@@ -49,6 +105,10 @@ pub struct BackupInfo {
     pub size: u64,
     pub created: std::time::SystemTime,
     pub display_name: String,
+    /// Whether the archive is passphrase-encrypted (`.tgz.age`)
+    pub encrypted: bool,
+    /// What the archive holds; `None` for encrypted archives, which are only
+    /// opened once the passphrase is known
     pub contents: Option<BackupContents>,
 }
 
@@ -70,32 +130,351 @@ pub struct RepoWithStatus {
     pub display_string: String, // Formatted for display
 }
 
+/// Outcome of syncing a single repository
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncOutcome {
+    Synced,
+    /// Skipped because the working directory has uncommitted changes
+    Dirty,
+    /// The default branch has commits upstream doesn't, so it was not
+    /// fast-forwarded
+    Diverged,
+    /// `--save-dirty` would have created a branch matching a protected
+    /// branch pattern, so the repository was skipped
+    Protected,
+    /// Skipped because another vibe process held the repository's lock
+    Locked,
+    Failed,
+}
+
+/// Per-repository result of `vibe git sync`
+#[derive(Debug, Clone, Serialize)]
+pub struct RepoSyncResult {
+    pub name: String,
+    pub outcome: SyncOutcome,
+    pub error: Option<String>,
+    /// Where a synced repository with a `compare_ref` stands against it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compared: Option<ComparedTo>,
+    /// Remote-tracking branches backed up before pruning deleted them
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub backups: Vec<RefBackup>,
+}
+
+/// Commits ahead of and behind a repository's `compare_ref`
+#[derive(Debug, Clone, Serialize)]
+pub struct ComparedTo {
+    pub compare_ref: String,
+    pub ahead: usize,
+    pub behind: usize,
+}
+
+impl RepoSyncResult {
+    fn failed(name: &str, error: String) -> Self {
+        Self {
+            name: name.to_string(),
+            outcome: SyncOutcome::Failed,
+            error: Some(error),
+            compared: None,
+            backups: Vec::new(),
+        }
+    }
+}
+
+/// Take `repo_path`'s lock for a sync, or the result of skipping it when
+/// another process holds the lock
+async fn lock_for_sync(
+    name: &str,
+    repo_path: &Path,
+) -> std::result::Result<RepoLock, RepoSyncResult> {
+    match repo_lock::acquire(repo_path, "sync").await {
+        Ok(lock) => Ok(lock),
+        Err(e) => match e.downcast_ref::<RepoLocked>() {
+            Some(locked) => {
+                display_println!("{} {locked}, skipped", style("⚠️").yellow());
+                Err(RepoSyncResult {
+                    name: name.to_string(),
+                    outcome: SyncOutcome::Locked,
+                    error: Some(locked.to_string()),
+                    compared: None,
+                    backups: Vec::new(),
+                })
+            }
+            None => {
+                display_println!("{}", style("✗").red());
+                display_eprintln!("  Error: {e}");
+                Err(RepoSyncResult::failed(name, e.to_string()))
+            }
+        },
+    }
+}
+
+/// Ahead/behind against `repo`'s `compare_ref` once it has been synced
+async fn compare_after_sync(repo: &Repository, repo_path: &Path) -> Option<ComparedTo> {
+    let compare_ref = repo.compare_ref.as_deref()?;
+    let status = get_git_status_against(repo_path, Some(compare_ref))
+        .await
+        .ok()?;
+    Some(ComparedTo {
+        compare_ref: compare_ref.to_string(),
+        ahead: status.ahead,
+        behind: status.behind,
+    })
+}
+
+/// Fire `sync_completed` for a finished sync
+fn emit_sync_completed(results: &[RepoSyncResult], fetch_only: bool) {
+    let count = |outcome: SyncOutcome| {
+        results
+            .iter()
+            .filter(|result| result.outcome == outcome)
+            .count()
+    };
+    events::emit(Event::SyncCompleted {
+        repositories: results.len(),
+        synced: count(SyncOutcome::Synced),
+        failed: count(SyncOutcome::Failed),
+        fetch_only,
+    });
+}
+
+/// Pause between launches when opening a group, so terminals and editors
+/// have time to come up in order
+const GROUP_OPEN_DELAY: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// Outcome of opening one repository of a group
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GroupOpenOutcome {
+    Opened,
+    /// Configured with the default template, then opened
+    ConfiguredAndOpened,
+    /// Skipped because the app isn't configured for the repository
+    NotConfigured,
+    Failed,
+}
+
+/// Per-repository result of `vibe open --group`
+#[derive(Debug, Clone, Serialize)]
+pub struct RepoOpenResult {
+    pub name: String,
+    pub outcome: GroupOpenOutcome,
+    pub error: Option<String>,
+}
+
+/// Print which repositories `vibe open --group` opened, skipped, or failed
+pub fn print_group_open_summary(group: &str, app: &str, results: &[RepoOpenResult]) {
+    if results.is_empty() {
+        display_println!(
+            "{} Group '{}' has no repositories",
+            style("ℹ").yellow(),
+            group
+        );
+        return;
+    }
+
+    display_println!();
+    for result in results {
+        match result.outcome {
+            GroupOpenOutcome::Opened => {
+                display_println!("  {} {}", style("✓").green(), result.name)
+            }
+            GroupOpenOutcome::ConfiguredAndOpened => display_println!(
+                "  {} {} {}",
+                style("✓").green(),
+                result.name,
+                style("(configured with the default template)").dim()
+            ),
+            GroupOpenOutcome::NotConfigured => display_println!(
+                "  {} {} {}",
+                style("-").dim(),
+                result.name,
+                style(format!("(skipped: {app} not configured)")).dim()
+            ),
+            GroupOpenOutcome::Failed => display_eprintln!(
+                "  {} {}: {}",
+                style("✗").red(),
+                result.name,
+                result.error.as_deref().unwrap_or("failed")
+            ),
+        }
+    }
+
+    let count = |outcomes: &[GroupOpenOutcome]| {
+        results
+            .iter()
+            .filter(|r| outcomes.contains(&r.outcome))
+            .count()
+    };
+    let skipped = count(&[GroupOpenOutcome::NotConfigured]);
+    display_println!(
+        "\n{} opened, {} skipped, {} failed",
+        count(&[
+            GroupOpenOutcome::Opened,
+            GroupOpenOutcome::ConfiguredAndOpened
+        ]),
+        skipped,
+        count(&[GroupOpenOutcome::Failed])
+    );
+    if skipped > 0 {
+        display_println!(
+            "{} Use --configure-missing to set up {} with its default template",
+            style("💡").yellow(),
+            app
+        );
+    }
+}
+
+/// Outcome of configuring an app for one repository of a bulk pass
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AppConfigureOutcome {
+    Configured,
+    /// Already configured with the requested template
+    Unchanged,
+    /// Skipped because the app is already configured; `--overwrite` replaces it
+    AlreadyConfigured,
+    Failed,
+}
+
+/// Per-repository result of `vibe apps configure --all-repos` or `--group`
+#[derive(Debug, Clone, Serialize)]
+pub struct RepoAppConfigureResult {
+    pub name: String,
+    pub outcome: AppConfigureOutcome,
+    /// Template the repository used before, if the app was configured
+    pub previous_template: Option<String>,
+    pub changes: Vec<String>,
+    pub error: Option<String>,
+}
+
+/// Print what a bulk `vibe apps configure` changed in each repository
+pub fn print_bulk_configure_summary(app: &str, results: &[RepoAppConfigureResult]) {
+    if results.is_empty() {
+        display_println!("{} No repositories to configure", style("ℹ").yellow());
+        return;
+    }
+
+    for result in results {
+        match result.outcome {
+            AppConfigureOutcome::Configured => {
+                display_println!("  {} {}", style("✓").green(), style(&result.name).cyan());
+                for change in &result.changes {
+                    display_println!("      {change}");
+                }
+            }
+            AppConfigureOutcome::Unchanged => display_println!(
+                "  {} {} {}",
+                style("=").dim(),
+                result.name,
+                style("(already uses this template)").dim()
+            ),
+            AppConfigureOutcome::AlreadyConfigured => display_println!(
+                "  {} {} {}",
+                style("-").dim(),
+                result.name,
+                style(format!(
+                    "(skipped: {app} already configured with '{}')",
+                    result.previous_template.as_deref().unwrap_or("default")
+                ))
+                .dim()
+            ),
+            AppConfigureOutcome::Failed => display_eprintln!(
+                "  {} {}: {}",
+                style("✗").red(),
+                result.name,
+                result.error.as_deref().unwrap_or("failed")
+            ),
+        }
+    }
+
+    let count =
+        |outcome: AppConfigureOutcome| results.iter().filter(|r| r.outcome == outcome).count();
+    let skipped = count(AppConfigureOutcome::AlreadyConfigured);
+    display_println!(
+        "\n{} configured, {} unchanged, {} skipped, {} failed",
+        count(AppConfigureOutcome::Configured),
+        count(AppConfigureOutcome::Unchanged),
+        skipped,
+        count(AppConfigureOutcome::Failed)
+    );
+    if skipped > 0 {
+        display_println!(
+            "{} Use --overwrite to replace existing {} configurations",
+            style("💡").yellow(),
+            app
+        );
+    }
+}
+
+/// Result of `vibe git scan`, including any import/restore/clean actions taken
+#[derive(Debug, Clone, Serialize)]
+pub struct ScanReport {
+    pub path: PathBuf,
+    pub repositories: Vec<RepoInfo>,
+    pub non_git_folders: Vec<NonGitFolder>,
+    pub sync: Option<SyncReport>,
+    /// Subprojects recorded by `--subprojects`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub subprojects: Vec<SubprojectDetection>,
+}
+
+impl ScanReport {
+    fn new(path: &Path, analysis: WorkspaceAnalysis, sync: Option<SyncReport>) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            repositories: analysis.repositories,
+            non_git_folders: analysis.non_git_folders,
+            sync,
+            subprojects: Vec::new(),
+        }
+    }
+}
+
 pub struct WorkspaceManager {
     config_path: PathBuf,
     config: WorkspaceConfig,
     template_manager: TemplateManager,
     repo_cache: Option<RepositoryCache>,
     git_cache: Option<GitStatusCache>,
+    /// Recorded with each app launch
+    launch_source: LaunchSource,
+    /// Workspace root given on the command line, kept across config reloads
+    root_override: Option<PathBuf>,
+    /// Set while the workspace root is unreachable
+    offline: Option<OfflineRoot>,
+    /// `--force-online`: never treat the root as offline
+    force_online: bool,
+    /// `--type`: names of the repositories of the requested project type
+    type_filter: Option<HashSet<String>>,
 }
 
 impl WorkspaceManager {
     pub async fn new(config_path: PathBuf) -> Result<Self> {
-        let config = WorkspaceConfig::load_from_file(&config_path).await?;
+        let config = WorkspaceConfig::load_from_file(&config_path)
+            .instrument(timings::phase(timings::CONFIG, config_path.display()))
+            .await?;
 
-        let vibe_dir = super::constants::get_config_dir();
-        let template_manager = TemplateManager::new(vibe_dir.join("templates"));
+        let template_manager = TemplateManager::new(super::constants::get_templates_dir());
 
         // Initialize caches
-        let cache_dir = vibe_dir.join("cache");
+        let cache_dir = super::constants::get_cache_dir();
         let repo_cache = Self::init_repository_cache(&cache_dir).await.ok();
         let git_cache = Self::init_git_status_cache(&cache_dir).await.ok();
 
+        let offline = OfflineRoot::detect(&config);
         Ok(Self {
             config_path,
             config,
             template_manager,
             repo_cache,
             git_cache,
+            launch_source: LaunchSource::default(),
+            root_override: None,
+            offline,
+            force_online: false,
+            type_filter: None,
         })
     }
 
@@ -103,32 +482,130 @@ impl WorkspaceManager {
         config_path: PathBuf,
         root_override: Option<PathBuf>,
     ) -> Result<Self> {
-        let mut config = WorkspaceConfig::load_from_file(&config_path).await?;
+        let mut config = WorkspaceConfig::load_from_file(&config_path)
+            .instrument(timings::phase(timings::CONFIG, config_path.display()))
+            .await?;
 
         // Override the workspace root if specified
-        if let Some(root) = root_override {
-            let expanded_root = crate::utils::fs::expand_tilde(&root);
-            info!("Overriding workspace root to: {}", expanded_root.display());
-            config.workspace.root = expanded_root;
+        let root_override = root_override.map(|root| crate::utils::fs::expand_tilde(&root));
+        if let Some(root) = &root_override {
+            info!("Overriding workspace root to: {}", root.display());
+            config.workspace.root = root.clone();
         }
 
-        let vibe_dir = super::constants::get_config_dir();
-        let template_manager = TemplateManager::new(vibe_dir.join("templates"));
+        let template_manager = TemplateManager::new(super::constants::get_templates_dir());
 
         // Initialize caches
-        let cache_dir = vibe_dir.join("cache");
+        let cache_dir = super::constants::get_cache_dir();
         let repo_cache = Self::init_repository_cache(&cache_dir).await.ok();
         let git_cache = Self::init_git_status_cache(&cache_dir).await.ok();
 
+        let offline = OfflineRoot::detect(&config);
         Ok(Self {
             config_path,
             config,
             template_manager,
             repo_cache,
             git_cache,
+            launch_source: LaunchSource::default(),
+            root_override,
+            offline,
+            force_online: false,
+            type_filter: None,
         })
     }
 
+    /// Re-read the config file after it changed on disk. The new config
+    /// replaces the current one only when it parses and validates; `Ok(false)`
+    /// means nothing changed.
+    pub async fn reload_config(&mut self) -> Result<bool> {
+        let mut config = WorkspaceConfig::load_from_file(&self.config_path)
+            .instrument(timings::phase(timings::CONFIG, self.config_path.display()))
+            .await?;
+        config.validate()?;
+        if let Some(root) = &self.root_override {
+            config.workspace.root = root.clone();
+        }
+
+        // Saves made by this process come back as change events too
+        let unchanged =
+            serde_yaml::to_string(&config).ok() == serde_yaml::to_string(&self.config).ok();
+        if unchanged {
+            return Ok(false);
+        }
+
+        info!("Reloaded configuration from {}", self.config_path.display());
+        self.config = config;
+        if !self.force_online {
+            self.offline = OfflineRoot::detect(&self.config);
+        }
+        Ok(true)
+    }
+
+    /// The workspace root, when it looked unreachable at startup
+    pub fn offline_root(&self) -> Option<&OfflineRoot> {
+        self.offline.as_ref()
+    }
+
+    /// Trust the workspace root even when it looks unreachable
+    /// (`--force-online`), e.g. because it was deleted on purpose
+    pub fn force_online(&mut self) {
+        self.force_online = true;
+        self.offline = None;
+    }
+
+    /// Project types of every configured repository, detecting the ones
+    /// whose cached detection is missing or stale
+    pub async fn project_types(&self) -> HashMap<String, Vec<ProjectType>> {
+        let repos = self
+            .config
+            .repositories
+            .iter()
+            .map(|repo| (repo.name.clone(), repo_abs_path(&self.config, repo)))
+            .collect();
+        project_types::load(repos, self.repo_cache.as_ref()).await
+    }
+
+    /// Configured repositories with their project types, optionally only
+    /// those in `group` or of `project_type`
+    pub async fn typed_repositories(
+        &self,
+        group: Option<&str>,
+        project_type: Option<ProjectType>,
+    ) -> Vec<project_types::TypedRepository> {
+        let mut types = self.project_types().await;
+        self.get_target_repositories(None, group)
+            .into_iter()
+            .map(|repo| project_types::TypedRepository {
+                name: repo.name.clone(),
+                path: repo.path.clone(),
+                project_types: types.remove(&repo.name).unwrap_or_default(),
+            })
+            .filter(|repo| project_type.is_none_or(|t| repo.project_types.contains(&t)))
+            .collect()
+    }
+
+    /// Limit the repositories later bulk operations target to those of
+    /// `project_type` (`--type`)
+    pub async fn restrict_to_type(&mut self, project_type: ProjectType) {
+        let names = self
+            .project_types()
+            .await
+            .into_iter()
+            .filter(|(_, types)| types.contains(&project_type))
+            .map(|(name, _)| name)
+            .collect();
+        self.type_filter = Some(names);
+    }
+
+    /// Refuse `operation` while the workspace root is offline
+    fn ensure_online(&self, operation: &'static str) -> Result<()> {
+        match &self.offline {
+            Some(offline) => Err(offline.refuse(operation).into()),
+            None => Ok(()),
+        }
+    }
+
     pub async fn init_workspace(&mut self, name: &str, root: &Path) -> Result<()> {
         info!("Initializing workspace '{}' in {}", name, root.display());
 
@@ -155,6 +632,9 @@ impl WorkspaceManager {
                 if let Ok(Some(url)) = get_remote_url(&repo_path) {
                     repo = repo.with_url(url);
                 }
+                if let Ok(Some(upstream)) = get_upstream_url(&repo_path) {
+                    repo = repo.with_upstream(upstream);
+                }
 
                 if let Ok(Some(branch)) = get_current_branch(&repo_path) {
                     repo = repo.with_branch(branch);
@@ -201,6 +681,9 @@ impl WorkspaceManager {
             if let Ok(Some(url)) = get_remote_url(repo_path) {
                 repo = repo.with_url(url);
             }
+            if let Ok(Some(upstream)) = get_upstream_url(repo_path) {
+                repo = repo.with_upstream(upstream);
+            }
 
             if let Ok(Some(branch)) = get_current_branch(repo_path) {
                 repo = repo.with_branch(branch);
@@ -218,22 +701,82 @@ impl WorkspaceManager {
         dirty_only: bool,
         format: &str,
         group: Option<&str>,
+    ) -> Result<()> {
+        self.show_status_grouped(dirty_only, format, group, None)
+            .await
+    }
+
+    /// [`Self::show_status`], bucketing repositories by `group_by` instead of
+    /// `preferences.status_group_by`. JSON output is only nested when
+    /// `group_by` is given, so scripts keep the flat list by default.
+    pub async fn show_status_grouped(
+        &self,
+        dirty_only: bool,
+        format: &str,
+        group: Option<&str>,
+        group_by: Option<StatusGroupBy>,
     ) -> Result<()> {
         use super::repo_analyzer::analyze_workspace;
         use crate::ui::hierarchical_display::render_status_summary;
 
+        if self.offline.is_some() && format != "json" {
+            return self.show_offline_status(dirty_only, group).await;
+        }
+
+        if format == "json" {
+            if let Some(group_by) = group_by.filter(|g| *g != StatusGroupBy::None) {
+                let (grouped, warnings) = self
+                    .collect_grouped_status(dirty_only, group, group_by)
+                    .await;
+                for warning in &warnings {
+                    display_warn!("{} {}", style("⚠").yellow(), warning);
+                }
+                let json = serde_json::to_string_pretty(&grouped)
+                    .context("Failed to serialize status to JSON")?;
+                data_println!("{json}");
+                return Ok(());
+            }
+        }
+
         // For JSON and compact formats, use the legacy behavior
         if format == "json" || format == "compact" {
             return self.show_status_legacy(dirty_only, format, group).await;
         }
 
-        println!("{} Analyzing repository status...", style("🔍").blue());
+        // The hierarchical view covers the whole workspace, so a group is
+        // shown as a per-repository list
+        if group.is_some() {
+            return self.show_status_legacy(dirty_only, "compact", group).await;
+        }
+
+        display_println!("{} Analyzing repository status...", style("🔍").blue());
 
         // Analyze workspace to get hierarchical organization
-        let analysis = analyze_workspace(&self.config.workspace.root, &self.config, 3).await?;
+        let mut analysis = analyze_workspace(&self.config.workspace.root, &self.config, 3).await?;
+        analysis.repositories.retain(|repo| {
+            !repo
+                .config_repo
+                .as_ref()
+                .is_some_and(|config| config.is_excluded_from(BulkOperation::Status))
+        });
 
         // Use hierarchical display for status
-        render_status_summary(&analysis).await;
+        let lfs_repos = self.lfs_repository_paths().await;
+        let grouping = StatusGrouping::load(
+            group_by.unwrap_or_else(|| self.status_group_by()),
+            &self.config,
+            self.repo_cache.as_ref(),
+        )
+        .await;
+        let statuses = render_status_summary(
+            &analysis,
+            &grouping,
+            self.get_max_parallel_git(),
+            &lfs_repos,
+        )
+        .await;
+        self.remember_statuses(&statuses).await;
+        self.print_excluded_footnote(None, group, BulkOperation::Status);
 
         // TODO: Add WIP branch detection and out-of-sync tracking branch detection
         // This should scan for:
@@ -248,133 +791,659 @@ impl WorkspaceManager {
         Ok(())
     }
 
-    /// Legacy status implementation for JSON and compact formats
-    async fn show_status_legacy(
+    /// Checkouts that use Git LFS. Flags come from the repository cache;
+    /// repositories it doesn't know yet are checked on disk and recorded.
+    async fn lfs_repository_paths(&self) -> HashSet<PathBuf> {
+        let root = &self.config.workspace.root;
+        let cache = self.repo_cache.as_ref();
+        let flags = match cache {
+            Some(cache) => cache.get_lfs_flags().await.unwrap_or_default(),
+            None => HashMap::new(),
+        };
+
+        let mut paths = HashSet::new();
+        for repo in &self.config.repositories {
+            let uses_lfs = match flags.get(&repo.name) {
+                Some(flag) => *flag,
+                None => {
+                    let cached = CachedRepository::from_config(repo, root).await;
+                    if let Some(cache) = cache {
+                        if let Err(e) = cache.cache_repository(&cached).await {
+                            debug!("Failed to cache {}: {}", repo.name, e);
+                        }
+                    }
+                    cached.uses_lfs
+                }
+            };
+            if uses_lfs {
+                paths.insert(repo_abs_path(&self.config, repo));
+            }
+        }
+        paths
+    }
+
+    /// Collect git status for the target repositories, returning the statuses
+    /// and a warning for each repository whose status could not be read
+    pub async fn collect_status(
         &self,
         dirty_only: bool,
-        format: &str,
         group: Option<&str>,
-    ) -> Result<()> {
-        let repositories = if let Some(group_name) = group {
-            self.config.get_repositories_in_group(group_name)
-        } else {
-            self.config.repositories.iter().collect()
+    ) -> (Vec<GitStatus>, Vec<String>) {
+        if let Some(offline) = &self.offline {
+            return self.last_known_statuses(offline, dirty_only, group).await;
+        }
+
+        let snapshot = self
+            .refresh_statuses(StatusScope::Targets(group), std::time::Duration::ZERO)
+            .await;
+        let warnings = snapshot
+            .failures
+            .iter()
+            .map(|(name, e)| {
+                warn!("Failed to get status for {}: {}", name, e);
+                format!("Failed to get status for {name}: {e}")
+            })
+            .collect();
+        let statuses = snapshot
+            .statuses
+            .into_iter()
+            .filter(|status| !dirty_only || status.is_dirty())
+            .collect();
+        (statuses, warnings)
+    }
+
+    /// The statuses of `scope`, re-reading with bounded concurrency those
+    /// whose cached status is older than `max_staleness` and caching what
+    /// was read. While the workspace root is offline, cached statuses of any
+    /// age are used instead.
+    pub async fn refresh_statuses(
+        &self,
+        scope: StatusScope<'_>,
+        max_staleness: std::time::Duration,
+    ) -> RepoStatusSnapshot {
+        self.plan_status_refresh(scope, max_staleness)
+            .await
+            .run()
+            .await
+    }
+
+    /// [`Self::refresh_statuses`] in two steps: the statuses the cache can
+    /// serve now, and a refresh that can run later or in the background
+    pub async fn plan_status_refresh(
+        &self,
+        scope: StatusScope<'_>,
+        max_staleness: std::time::Duration,
+    ) -> StatusRefresh {
+        let repos: Vec<&Repository> = match scope {
+            StatusScope::All => self.config.repositories.iter().collect(),
+            StatusScope::Targets(group) => {
+                self.get_bulk_targets(None, group, BulkOperation::Status)
+            }
+            StatusScope::Repos(names) => self
+                .config
+                .repositories
+                .iter()
+                .filter(|repo| names.contains(&repo.name))
+                .collect(),
+        };
+        let mut cached: HashMap<String, CachedGitStatus> = match &self.git_cache {
+            Some(cache) => cache
+                .get_last_known_git_statuses()
+                .await
+                .unwrap_or_default()
+                .into_iter()
+                .map(|status| (status.repository_name.clone(), status))
+                .collect(),
+            None => HashMap::new(),
         };
+        let entries = repos
+            .into_iter()
+            .map(|repo| (self.status_target(repo), cached.remove(&repo.name)))
+            .collect();
+        StatusRefresh::new(
+            entries,
+            self.offline.is_none().then_some(max_staleness),
+            self.git_cache
+                .as_ref()
+                .map(|cache| cache.db_path().to_path_buf()),
+            self.get_max_parallel_git(),
+        )
+    }
 
-        if repositories.is_empty() {
-            println!("{} No repositories found", style("ℹ").yellow());
-            return Ok(());
+    /// Keep statuses in the git status cache, so they can be shown while
+    /// the workspace root is offline
+    async fn remember_statuses(&self, statuses: &[GitStatus]) {
+        let Some(cache) = &self.git_cache else {
+            return;
+        };
+        for status in statuses {
+            if let Err(e) = cache.cache_git_status(&status.clone().into()).await {
+                debug!("Failed to cache git status: {}", e);
+            }
         }
+    }
 
-        let mut statuses = Vec::new();
-
-        for repo in repositories {
-            let repo_path = self.config.workspace.root.join(&repo.path);
+    /// The last cached status of each target repository, however old
+    async fn cached_status_by_repo(
+        &self,
+        group: Option<&str>,
+    ) -> Vec<(&Repository, Option<CachedGitStatus>)> {
+        let mut cached: HashMap<String, CachedGitStatus> = match &self.git_cache {
+            Some(cache) => cache
+                .get_last_known_git_statuses()
+                .await
+                .unwrap_or_default()
+                .into_iter()
+                .map(|status| (status.repository_name.clone(), status))
+                .collect(),
+            None => HashMap::new(),
+        };
+        self.get_bulk_targets(None, group, BulkOperation::Status)
+            .into_iter()
+            .map(|repo| {
+                let status = cached.remove(&repo.name);
+                (repo, status)
+            })
+            .collect()
+    }
 
-            match get_git_status(&repo_path).await {
-                Ok(status) => {
+    /// [`Self::collect_status`] while the root is offline: cached statuses,
+    /// with one warning instead of an error per repository
+    async fn last_known_statuses(
+        &self,
+        offline: &OfflineRoot,
+        dirty_only: bool,
+        group: Option<&str>,
+    ) -> (Vec<GitStatus>, Vec<String>) {
+        let mut statuses = Vec::new();
+        let mut uncached = Vec::new();
+        for (repo, cached) in self.cached_status_by_repo(group).await {
+            match cached {
+                Some(cached) => {
+                    let status = GitStatus::from(cached);
                     if !dirty_only || status.is_dirty() {
                         statuses.push(status);
                     }
                 }
-                Err(e) => {
-                    warn!("Failed to get status for {}: {}", repo.name, e);
-                    eprintln!(
-                        "{} Failed to get status for {}: {}",
-                        style("⚠").yellow(),
-                        style(&repo.name).cyan(),
-                        e
-                    );
-                }
+                None => uncached.push(repo.name.clone()),
             }
         }
 
-        if statuses.is_empty() {
-            if dirty_only {
-                println!("{} All repositories are clean", style("✓").green());
-            } else {
-                println!("{} No repositories to display", style("ℹ").yellow());
-            }
+        let mut warnings = vec![format!(
+            "Workspace root {} is offline; statuses are from the cache",
+            offline.root.display()
+        )];
+        if !uncached.is_empty() {
+            warnings.push(format!("No cached status for: {}", uncached.join(", ")));
+        }
+        (statuses, warnings)
+    }
+
+    /// `vibe git status` while the root is offline: the last cached status of
+    /// each repository, labeled with its age
+    async fn show_offline_status(&self, dirty_only: bool, group: Option<&str>) -> Result<()> {
+        let repos = self.cached_status_by_repo(group).await;
+        if repos.is_empty() {
+            display_println!("{} No repositories found", style("ℹ").yellow());
             return Ok(());
         }
 
-        match format {
-            "json" => {
-                let json = serde_json::to_string_pretty(&statuses)
-                    .context("Failed to serialize status to JSON")?;
-                println!("{json}");
-            }
-            "compact" => {
-                for status in &statuses {
-                    let indicator = if status.clean {
-                        "✓".green()
-                    } else {
-                        "●".red()
-                    };
-                    println!("{} {}", indicator, status.repository_name.cyan());
+        for (repo, cached) in repos {
+            match cached {
+                Some(cached) => {
+                    let age = crate::ui::formatting::format_time_ago(&cached.last_updated);
+                    let status = GitStatus::from(cached);
+                    if dirty_only && !status.is_dirty() {
+                        continue;
+                    }
+                    display_println!(
+                        "{} {}",
+                        status.format_status_line(),
+                        style(format!("offline (cached {age})")).dim()
+                    );
                 }
+                None if !dirty_only => display_println!(
+                    "{} {}",
+                    style(&repo.name).cyan().bold(),
+                    style("offline (no cached status)").dim()
+                ),
+                None => {}
             }
-            _ => unreachable!("Legacy status only handles json and compact formats"),
         }
-
+        self.print_excluded_footnote(None, group, BulkOperation::Status);
         Ok(())
     }
 
-    pub async fn execute_command(
+    /// [`Self::collect_status`] with the statuses nested under their
+    /// `group_by` buckets
+    pub async fn collect_grouped_status(
         &self,
-        command: &str,
-        repos: Option<&str>,
+        dirty_only: bool,
         group: Option<&str>,
-        parallel: bool,
-    ) -> Result<()> {
-        let repositories = self.get_target_repositories(repos, group);
-
-        if repositories.is_empty() {
-            println!(
-                "{} No repositories found to execute command on",
-                style("ℹ").yellow()
-            );
-            return Ok(());
-        }
-
-        println!(
-            "{} Executing '{}' on {} repositories...",
-            style("⚡").blue(),
-            style(command).cyan(),
-            repositories.len()
-        );
-
-        let operation = GitOperation::Custom(command.to_string());
+        group_by: StatusGroupBy,
+    ) -> (GroupedStatus, Vec<String>) {
+        let (statuses, warnings) = self.collect_status(dirty_only, group).await;
+        let grouping = StatusGrouping::load(group_by, &self.config, self.repo_cache.as_ref()).await;
+        (grouping.group_statuses(&self.config, &statuses), warnings)
+    }
 
-        if parallel {
-            // Execute commands in parallel
-            let mut tasks = Vec::new();
+    /// Branch inventory for the target repositories, with a warning for
+    /// each repository whose branches could not be read
+    pub async fn collect_branch_report(
+        &self,
+        stale_days: u64,
+        group: Option<&str>,
+    ) -> (BranchReport, Vec<String>) {
+        let repos = self
+            .get_target_repositories(None, group)
+            .into_iter()
+            .map(|repo| (repo.name.clone(), repo_abs_path(&self.config, repo)))
+            .collect();
 
-            for repo in repositories {
-                let repo_path = self.config.workspace.root.join(&repo.path);
-                let operation = operation.clone();
-                let repo_name = repo.name.clone();
+        let now = chrono::Utc::now();
+        let (repositories, warnings) =
+            collect_branch_reports(repos, stale_days, self.get_max_parallel_git(), now).await;
+        (BranchReport::new(repositories, stale_days, now), warnings)
+    }
 
-                let task =
-                    tokio::spawn(async move { (repo_name, operation.execute(&repo_path).await) });
+    /// Git activity over the last `days` days in the target repositories,
+    /// with a warning for each repository whose history could not be read
+    pub async fn collect_activity(
+        &mut self,
+        days: u32,
+        group: Option<&str>,
+        author: Option<String>,
+    ) -> (ActivityReport, Vec<String>) {
+        let repos = self
+            .get_target_repositories(None, group)
+            .into_iter()
+            .map(|repo| (repo.name.clone(), repo_abs_path(&self.config, repo)))
+            .collect();
+        let max_parallel = self.get_max_parallel_git();
+        let today = chrono::Local::now().date_naive();
+
+        let cache = match self.get_repository_cache().await {
+            Ok(cache) => Some(cache),
+            Err(e) => {
+                warn!("Repository cache unavailable, reading all history: {}", e);
+                None
+            }
+        };
+        let (repositories, warnings) =
+            collect_activity(repos, days, author.clone(), cache, max_parallel, today).await;
+        (
+            ActivityReport::new(repositories, days, today, author),
+            warnings,
+        )
+    }
 
-                tasks.push(task);
+    /// Commit authors since `since` in the target repositories, with a
+    /// warning for each repository whose history could not be read
+    pub async fn collect_contributors(
+        &mut self,
+        since: chrono::NaiveDate,
+        group: Option<&str>,
+    ) -> (ContributorReport, Vec<String>) {
+        let repos = self
+            .get_target_repositories(None, group)
+            .into_iter()
+            .map(|repo| (repo.name.clone(), repo_abs_path(&self.config, repo)))
+            .collect();
+        let max_parallel = self.get_max_parallel_git();
+        let aliases = self
+            .config
+            .preferences
+            .as_ref()
+            .map(|p| p.author_aliases.clone())
+            .unwrap_or_default();
+
+        let cache = match self.get_repository_cache().await {
+            Ok(cache) => Some(cache),
+            Err(e) => {
+                warn!("Repository cache unavailable, reading all history: {}", e);
+                None
             }
+        };
+        let (repositories, warnings) =
+            collect_contributors(repos, since, cache, max_parallel).await;
+        (
+            ContributorReport::new(repositories, since, &aliases),
+            warnings,
+        )
+    }
 
-            // Wait for all tasks to complete
-            for task in tasks {
-                match task.await {
-                    Ok((repo_name, result)) => match result {
+    /// Health scores for the target repositories, with a warning for each
+    /// repository that could not be read
+    pub async fn collect_health(&self, group: Option<&str>) -> (HealthReport, Vec<String>) {
+        let targets: Vec<StatusTarget> = self
+            .get_target_repositories(None, group)
+            .into_iter()
+            .map(|repo| self.status_target(repo))
+            .collect();
+        let repos: Vec<(String, PathBuf)> = targets
+            .iter()
+            .map(|target| (target.name.clone(), target.path.clone()))
+            .collect();
+        let weights = self
+            .config
+            .preferences
+            .as_ref()
+            .map(|p| p.health_weights)
+            .unwrap_or_default();
+        let max_parallel = self.get_max_parallel_git();
+        let now = chrono::Utc::now();
+
+        let (branches, mut warnings) =
+            collect_branch_reports(repos.clone(), DEFAULT_STALE_DAYS, max_parallel, now).await;
+        let statuses = collect_git_statuses(targets, max_parallel).await;
+
+        let mut scored = Vec::with_capacity(statuses.len());
+        for result in statuses {
+            let status = match result.status {
+                Ok(status) => status,
+                Err(e) => {
+                    warnings.push(format!("Failed to get status for {}: {}", result.name, e));
+                    continue;
+                }
+            };
+            let stale_branches = branches
+                .iter()
+                .find(|b| b.repository == result.name)
+                .map_or(0, |b| {
+                    b.branches
+                        .iter()
+                        .filter(|branch| {
+                            branch.kind == BranchKind::Local && branch.stale && !branch.is_default
+                        })
+                        .count()
+                });
+            let last_commit_days =
+                execute_git_command(&result.path, &["log", "-1", "--format=%ct"])
+                    .await
+                    .ok()
+                    .and_then(|timestamp| timestamp.parse::<i64>().ok())
+                    .map(|timestamp| (now.timestamp() - timestamp).max(0) as u64 / 86_400);
+            let inputs = HealthInputs::new(&status, stale_branches, last_commit_days);
+            scored.push(health::repo_health(
+                result.name,
+                status.path.clone(),
+                &inputs,
+                &weights,
+            ));
+        }
+        (HealthReport::new(scored, weights), warnings)
+    }
+
+    /// Open pull requests across repositories, served from the git status
+    /// cache unless `refresh` is set or the cached dashboard has expired
+    pub async fn collect_pull_requests(
+        &self,
+        filter: PrFilter,
+        group: Option<&str>,
+        refresh: bool,
+    ) -> Result<PrDashboard> {
+        let key = filter.cache_key(group);
+        if let (false, Some(cache)) = (refresh, &self.git_cache) {
+            if let Ok(Some(json)) = cache.get_pull_requests(&key).await {
+                if let Ok(mut dashboard) = serde_json::from_str::<PrDashboard>(&json) {
+                    dashboard.cached = true;
+                    return Ok(dashboard);
+                }
+            }
+        }
+
+        if !is_gh_available().await {
+            return Err(GitError::GitHubCliNotFound.into());
+        }
+
+        let repos = self
+            .get_target_repositories(None, group)
+            .into_iter()
+            .map(|repo| {
+                (
+                    repo.name.clone(),
+                    repo_abs_path(&self.config, repo),
+                    repo.url.clone(),
+                )
+            })
+            .collect();
+        let dashboard =
+            pull_requests::collect_pull_requests(repos, filter, self.get_max_parallel_git()).await;
+        if let Some(cache) = &self.git_cache {
+            cache
+                .cache_pull_requests(&key, &serde_json::to_string(&dashboard)?)
+                .await?;
+        }
+        Ok(dashboard)
+    }
+
+    /// Open pull request count from a still-valid cached dashboard of all
+    /// repositories, without contacting GitHub
+    pub async fn cached_open_pr_count(&self) -> Option<usize> {
+        let json = self
+            .git_cache
+            .as_ref()?
+            .get_pull_requests(&PrFilter::All.cache_key(None))
+            .await
+            .ok()??;
+        serde_json::from_str::<PrDashboard>(&json)
+            .ok()
+            .map(|dashboard| dashboard.pull_requests.len())
+    }
+
+    /// Whether the main menu shows its header of counts, unless
+    /// `preferences.menu_header` is turned off
+    pub fn menu_header_enabled(&self) -> bool {
+        self.config
+            .preferences
+            .as_ref()
+            .and_then(|p| p.menu_header)
+            .unwrap_or(true)
+    }
+
+    /// Lines of the open picker's repository preview, 0 when
+    /// `preferences.picker_preview_lines` turned it off
+    pub fn picker_preview_lines(&self) -> usize {
+        self.config
+            .preferences
+            .as_ref()
+            .and_then(|p| p.picker_preview_lines)
+            .unwrap_or(crate::ui::repo_picker::DEFAULT_PREVIEW_LINES)
+    }
+
+    /// Days removed repositories and worktrees stay in the trash
+    pub fn trash_retention_days(&self) -> u32 {
+        self.config
+            .preferences
+            .as_ref()
+            .and_then(|p| p.trash_retention_days)
+            .unwrap_or(super::trash::DEFAULT_RETENTION_DAYS)
+    }
+
+    /// How long operations wait for another process's repository lock
+    pub fn lock_timeout(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(
+            self.config
+                .preferences
+                .as_ref()
+                .and_then(|p| p.lock_timeout_secs)
+                .unwrap_or(crate::git::repo_lock::DEFAULT_TIMEOUT_SECS),
+        )
+    }
+
+    /// Worktrees vibe created across configured repositories, from the
+    /// worktree metadata in the cache
+    pub async fn cached_worktree_count(&self) -> usize {
+        let checkouts: Vec<PathBuf> = self
+            .config
+            .repositories
+            .iter()
+            .map(|repo| repo_abs_path(&self.config, repo))
+            .collect();
+        crate::worktree::metadata::count_for_repos(&checkouts).await
+    }
+
+    /// Re-read, in a background task, the statuses of repositories whose
+    /// cached status is missing or expired. Returns `None` when every cached
+    /// status is still valid.
+    pub async fn spawn_status_cache_refresh(&self) -> Option<tokio::task::JoinHandle<()>> {
+        self.git_cache.as_ref()?;
+        let refresh = self
+            .plan_status_refresh(StatusScope::All, STATUS_CACHE_TTL)
+            .await;
+        if refresh.is_fresh() {
+            return None;
+        }
+        Some(tokio::spawn(async move {
+            for (name, e) in refresh.run().await.failures {
+                debug!("Failed to refresh status for {}: {}", name, e);
+            }
+        }))
+    }
+
+    /// Legacy status implementation for JSON and compact formats
+    async fn show_status_legacy(
+        &self,
+        dirty_only: bool,
+        format: &str,
+        group: Option<&str>,
+    ) -> Result<()> {
+        if self
+            .get_bulk_targets(None, group, BulkOperation::Status)
+            .is_empty()
+        {
+            display_println!("{} No repositories found", style("ℹ").yellow());
+            self.print_excluded_footnote(None, group, BulkOperation::Status);
+            return Ok(());
+        }
+
+        let (statuses, warnings) = self.collect_status(dirty_only, group).await;
+        for warning in &warnings {
+            display_warn!("{} {}", style("⚠").yellow(), warning);
+        }
+
+        if statuses.is_empty() {
+            if dirty_only {
+                display_println!("{} All repositories are clean", style("✓").green());
+            } else {
+                display_println!("{} No repositories to display", style("ℹ").yellow());
+            }
+            return Ok(());
+        }
+
+        match format {
+            "json" => {
+                let _render = timings::phase(timings::RENDER, "status json").entered();
+                let json = serde_json::to_string_pretty(&statuses)
+                    .context("Failed to serialize status to JSON")?;
+                data_println!("{json}");
+            }
+            "compact" => {
+                let lfs_repos = self.lfs_repository_paths().await;
+                let _render = timings::phase(timings::RENDER, "status compact").entered();
+                for status in &statuses {
+                    let lfs = if lfs_repos.contains(Path::new(&status.path)) {
+                        " LFS".dimmed().to_string()
+                    } else {
+                        String::new()
+                    };
+                    let indicator = if status.clean {
+                        "✓".green()
+                    } else {
+                        "●".red()
+                    };
+                    match status.fork_label() {
+                        Some(fork) => display_println!(
+                            "{} {} {}{}",
+                            indicator,
+                            status.repository_name.cyan(),
+                            fork.magenta(),
+                            lfs
+                        ),
+                        None => display_println!(
+                            "{} {}{}",
+                            indicator,
+                            status.repository_name.cyan(),
+                            lfs
+                        ),
+                    }
+                }
+            }
+            _ => unreachable!("Legacy status only handles json and compact formats"),
+        }
+        if format != "json" {
+            self.print_excluded_footnote(None, group, BulkOperation::Status);
+        }
+
+        Ok(())
+    }
+
+    pub async fn execute_command(
+        &self,
+        command: &str,
+        repos: Option<&str>,
+        group: Option<&str>,
+        parallel: bool,
+    ) -> Result<BatchSummary> {
+        self.ensure_online("exec")?;
+        let repositories = self.get_bulk_targets(repos, group, BulkOperation::Exec);
+        self.print_excluded_footnote(repos, group, BulkOperation::Exec);
+        let mut summary = BatchSummary {
+            total: repositories.len(),
+            failed: 0,
+        };
+
+        if repositories.is_empty() {
+            display_println!(
+                "{} No repositories found to execute command on",
+                style("ℹ").yellow()
+            );
+            return Ok(summary);
+        }
+
+        display_println!(
+            "{} Executing '{}' on {} repositories...",
+            style("⚡").blue(),
+            style(command).cyan(),
+            repositories.len()
+        );
+
+        let operation = GitOperation::Custom(command.to_string());
+
+        // Deterministic output needs results in a fixed order
+        if parallel && !output::is_deterministic() {
+            // Execute commands in parallel
+            let mut tasks = Vec::new();
+
+            for repo in repositories {
+                let repo_path = repo_abs_path(&self.config, repo);
+                let operation = operation.clone();
+                let repo_name = repo.name.clone();
+
+                let task = tokio::spawn(async move {
+                    (
+                        repo_name,
+                        operation.execute_locked(&repo_path, "exec").await,
+                    )
+                });
+
+                tasks.push(task);
+            }
+
+            // Wait for all tasks to complete
+            for task in tasks {
+                match task.await {
+                    Ok((repo_name, result)) => match result {
                         Ok(output) => {
                             if !output.trim().is_empty() {
-                                println!(
+                                display_println!(
                                     "{} {}:\n{}",
                                     style("✓").green(),
                                     style(&repo_name).cyan(),
                                     output
                                 );
                             } else {
-                                println!(
+                                display_println!(
                                     "{} {} (no output)",
                                     style("✓").green(),
                                     style(&repo_name).cyan()
@@ -382,7 +1451,8 @@ impl WorkspaceManager {
                             }
                         }
                         Err(e) => {
-                            eprintln!(
+                            summary.failed += 1;
+                            display_eprintln!(
                                 "{} {} failed: {}",
                                 style("✗").red(),
                                 style(&repo_name).cyan(),
@@ -391,37 +1461,39 @@ impl WorkspaceManager {
                         }
                     },
                     Err(e) => {
-                        eprintln!("{} Task failed: {}", style("✗").red(), e);
+                        summary.failed += 1;
+                        display_eprintln!("{} Task failed: {}", style("✗").red(), e);
                     }
                 }
             }
         } else {
             // Execute commands sequentially
             for repo in repositories {
-                let repo_path = self.config.workspace.root.join(&repo.path);
+                let repo_path = repo_abs_path(&self.config, repo);
 
-                print!(
+                display_print!(
                     "{} Executing on {}... ",
                     style("→").dim(),
                     style(&repo.name).cyan()
                 );
 
-                match operation.execute(&repo_path).await {
+                match operation.execute_locked(&repo_path, "exec").await {
                     Ok(output) => {
-                        println!("{}", style("✓").green());
+                        display_println!("{}", style("✓").green());
                         if !output.trim().is_empty() {
-                            println!("{output}");
+                            display_println!("{output}");
                         }
                     }
                     Err(e) => {
-                        println!("{}", style("✗").red());
-                        eprintln!("  Error: {e}");
+                        summary.failed += 1;
+                        display_println!("{}", style("✗").red());
+                        display_eprintln!("  Error: {e}");
                     }
                 }
             }
         }
 
-        Ok(())
+        Ok(summary)
     }
 
     fn get_target_repositories(
@@ -429,7 +1501,7 @@ impl WorkspaceManager {
         repos: Option<&str>,
         group: Option<&str>,
     ) -> Vec<&Repository> {
-        if let Some(group_name) = group {
+        let mut targets: Vec<&Repository> = if let Some(group_name) = group {
             self.config.get_repositories_in_group(group_name)
         } else if let Some(repo_names) = repos {
             repo_names
@@ -438,7 +1510,63 @@ impl WorkspaceManager {
                 .collect()
         } else {
             self.config.repositories.iter().collect()
+        };
+        if let Some(names) = &self.type_filter {
+            targets.retain(|repo| names.contains(&repo.name));
+        }
+        targets
+    }
+
+    /// [`Self::get_target_repositories`] without the repositories excluded
+    /// from `operation`, unless they were named in `repos`
+    fn get_bulk_targets(
+        &self,
+        repos: Option<&str>,
+        group: Option<&str>,
+        operation: BulkOperation,
+    ) -> Vec<&Repository> {
+        let mut targets = self.get_target_repositories(repos, group);
+        if repos.is_none() {
+            targets.retain(|repo| !repo.is_excluded_from(operation));
+        }
+        targets
+    }
+
+    /// Repositories [`Self::get_bulk_targets`] leaves out of `operation`
+    pub fn excluded_repositories(
+        &self,
+        repos: Option<&str>,
+        group: Option<&str>,
+        operation: BulkOperation,
+    ) -> Vec<&Repository> {
+        if repos.is_some() {
+            return Vec::new();
+        }
+        self.get_target_repositories(None, group)
+            .into_iter()
+            .filter(|repo| repo.is_excluded_from(operation))
+            .collect()
+    }
+
+    fn print_excluded_footnote(
+        &self,
+        repos: Option<&str>,
+        group: Option<&str>,
+        operation: BulkOperation,
+    ) {
+        let excluded = self.excluded_repositories(repos, group, operation);
+        if excluded.is_empty() {
+            return;
         }
+        let names: Vec<&str> = excluded.iter().map(|repo| repo.name.as_str()).collect();
+        let noun = if excluded.len() == 1 { "repo" } else { "repos" };
+        display_println!(
+            "{} {} {noun} excluded from {}: {}",
+            style("ℹ").dim(),
+            excluded.len(),
+            operation.as_str(),
+            style(names.join(", ")).dim()
+        );
     }
 
     pub fn get_workspace_root(&self) -> &PathBuf {
@@ -458,8 +1586,12 @@ impl WorkspaceManager {
     }
 
     pub async fn add_repository(&mut self, repo: Repository) -> Result<()> {
+        let name = repo.name.clone();
+        let path = repo_abs_path(&self.config, &repo);
         self.config.add_repository(repo);
-        self.save_config().await
+        self.save_config().await?;
+        project_types::load(vec![(name, path)], self.repo_cache.as_ref()).await;
+        Ok(())
     }
 
     pub fn get_config(&self) -> &WorkspaceConfig {
@@ -477,14 +1609,27 @@ impl WorkspaceManager {
         depth: usize,
         import: bool,
         restore: bool,
-        clean: bool,
-    ) -> Result<()> {
+        restore_only: Vec<String>,
+        clean: CleanMode,
+    ) -> Result<ScanReport> {
         use super::config_validator::{deduplicate_config, validate_config};
         use super::repo_analyzer::analyze_workspace;
         use super::sync_operations::{execute_sync_operations, print_sync_summary, SyncOptions};
         use crate::ui::hierarchical_display::{render_workspace_analysis, DisplayOptions};
 
-        println!(
+        // An offline root would make every repository look deleted
+        if scan_path.starts_with(&self.config.workspace.root) {
+            self.ensure_online("scan")?;
+        }
+
+        if let Some(unknown) = restore_only
+            .iter()
+            .find(|name| self.config.get_repository(name).is_none())
+        {
+            return Err(CommandError::not_found("Repository", unknown.as_str()).into());
+        }
+
+        display_println!(
             "{} Scanning repositories in {} (depth: {})",
             style("🔍").blue(),
             style(scan_path.display()).cyan(),
@@ -494,19 +1639,19 @@ impl WorkspaceManager {
         // Validate and clean up config before analysis
         let validation_report = validate_config(&self.config, scan_path)?;
         if validation_report.has_issues() {
-            println!();
+            display_println!();
             validation_report.print_report();
 
             // Ask user if they want to auto-fix duplicates
             if !validation_report.duplicates.is_empty() {
-                println!(
+                display_println!(
                     "{} Auto-fixing duplicate repositories...",
                     style("🔧").blue()
                 );
                 let dedup_report = deduplicate_config(&mut self.config, scan_path)?;
 
                 if dedup_report.duplicates.len() < validation_report.duplicates.len() {
-                    println!(
+                    display_println!(
                         "{} Removed {} duplicate entries",
                         style("✓").green(),
                         validation_report.duplicates.len() - dedup_report.duplicates.len()
@@ -514,7 +1659,7 @@ impl WorkspaceManager {
                     // Save the cleaned config
                     self.save_config().await?;
                 }
-                println!();
+                display_println!();
             }
         }
 
@@ -533,8 +1678,12 @@ impl WorkspaceManager {
         if restore {
             sync_options = sync_options.with_restore();
         }
-        if clean {
-            sync_options = sync_options.with_clean();
+        if !restore_only.is_empty() {
+            sync_options = sync_options.with_restore_only(restore_only);
+        }
+        sync_options = sync_options.with_clean_mode(clean);
+        if self.force_online {
+            sync_options = sync_options.with_force_online();
         }
 
         // Show sync summary if any actions are requested
@@ -542,21 +1691,32 @@ impl WorkspaceManager {
             print_sync_summary(&analysis, &sync_options);
 
             // Execute sync operations
-            execute_sync_operations(scan_path, &mut self.config, &analysis, &sync_options).await?;
+            let sync_report =
+                execute_sync_operations(scan_path, &mut self.config, &analysis, &sync_options)
+                    .await?;
 
             // Save updated config
             self.save_config().await?;
 
             // Re-analyze workspace to show updated state
-            println!();
-            println!("{} Updated workspace state:", style("📊").blue().bold());
-            println!("{}", "─".repeat(30));
+            display_println!();
+            display_println!("{} Updated workspace state:", style("📊").blue().bold());
+            display_println!("{}", "─".repeat(30));
 
             let updated_analysis = analyze_workspace(scan_path, &self.config, depth).await?;
             render_workspace_analysis(&updated_analysis, &display_options);
+            self.project_types().await;
+
+            return Ok(ScanReport::new(
+                scan_path,
+                updated_analysis,
+                Some(sync_report),
+            ));
         }
 
-        Ok(())
+        // Detect project types now so `--type` filters start from a warm cache
+        self.project_types().await;
+        Ok(ScanReport::new(scan_path, analysis, None))
     }
 
     /// Enhanced sync repositories with dirty handling
@@ -566,20 +1726,46 @@ impl WorkspaceManager {
         prune: bool,
         save_dirty: bool,
         group: Option<&str>,
-    ) -> Result<()> {
-        let repositories = if let Some(group_name) = group {
-            self.config.get_repositories_in_group(group_name)
-        } else {
-            self.config.repositories.iter().collect()
-        };
+    ) -> Result<Vec<RepoSyncResult>> {
+        let repositories = self.get_bulk_targets(None, group, BulkOperation::Sync);
+        let results = self
+            .sync_targets(repositories, fetch_only, prune, save_dirty)
+            .await?;
+        self.print_excluded_footnote(None, group, BulkOperation::Sync);
+        Ok(results)
+    }
+
+    /// Sync the named repositories, skipping names that are not configured
+    pub async fn sync_named_repositories(
+        &self,
+        repo_names: &[String],
+        fetch_only: bool,
+        prune: bool,
+    ) -> Result<Vec<RepoSyncResult>> {
+        let repositories = repo_names
+            .iter()
+            .filter_map(|name| self.config.get_repository(name))
+            .collect();
+
+        self.sync_targets(repositories, fetch_only, prune, false)
+            .await
+    }
 
+    async fn sync_targets(
+        &self,
+        repositories: Vec<&Repository>,
+        fetch_only: bool,
+        prune: bool,
+        save_dirty: bool,
+    ) -> Result<Vec<RepoSyncResult>> {
+        self.ensure_online("sync")?;
         if repositories.is_empty() {
-            println!("{} No repositories found", style("ℹ").yellow());
-            return Ok(());
+            display_println!("{} No repositories found", style("ℹ").yellow());
+            return Ok(Vec::new());
         }
 
         let action = if fetch_only { "Fetching" } else { "Syncing" };
-        println!(
+        display_println!(
             "{} {} {} repositories...",
             style("🔄").blue(),
             action,
@@ -587,7 +1773,7 @@ impl WorkspaceManager {
         );
 
         if save_dirty {
-            println!(
+            display_println!(
                 "{} Auto-commit mode enabled - dirty repositories will be committed to dirty/{{timestamp}} branches",
                 style("💾").blue()
             );
@@ -601,72 +1787,375 @@ impl WorkspaceManager {
             operations.push(GitOperation::Pull);
         }
 
+        let mut results = Vec::new();
+
         for repo in repositories {
-            let repo_path = self.config.workspace.root.join(&repo.path);
+            let repo_path = repo_abs_path(&self.config, repo);
 
-            print!("{} {}... ", style("→").dim(), style(&repo.name).cyan());
+            display_print!("{} {}... ", style("→").dim(), style(&repo.name).cyan());
+            let _lock = match lock_for_sync(&repo.name, &repo_path).await {
+                Ok(lock) => lock,
+                Err(skipped) => {
+                    results.push(skipped);
+                    continue;
+                }
+            };
 
             // Handle dirty repositories if save_dirty is enabled
             if save_dirty {
-                if let Err(e) = self.handle_dirty_repository(&repo_path).await {
-                    println!("{} (dirty handling failed: {})", style("⚠️").yellow(), e);
+                if let Err(e) = self.handle_dirty_repository(repo, &repo_path).await {
+                    if let Some(protected) = e.downcast_ref::<ProtectedBranchError>() {
+                        display_println!("{} skipped (protected)", style("⚠️").yellow());
+                        results.push(RepoSyncResult {
+                            name: repo.name.clone(),
+                            outcome: SyncOutcome::Protected,
+                            error: Some(protected.to_string()),
+                            compared: None,
+                            backups: Vec::new(),
+                        });
+                        continue;
+                    }
+                    display_println!("{} (dirty handling failed: {})", style("⚠️").yellow(), e);
+                    results.push(RepoSyncResult::failed(
+                        &repo.name,
+                        format!("dirty handling failed: {e}"),
+                    ));
                     continue;
                 }
             }
 
-            let mut success = true;
+            let mut result = RepoSyncResult {
+                name: repo.name.clone(),
+                outcome: SyncOutcome::Synced,
+                error: None,
+                compared: None,
+                backups: Vec::new(),
+            };
+            if prune {
+                match ref_backup::backup_pruned_branches(&repo_path).await {
+                    Ok(backups) => result.backups = backups,
+                    Err(e) => {
+                        display_println!("{}", style("✗").red());
+                        display_eprintln!("  Error: {e:#}");
+                        results.push(RepoSyncResult::failed(
+                            &repo.name,
+                            format!("ref backup failed: {e:#}"),
+                        ));
+                        continue;
+                    }
+                }
+            }
             for operation in &operations {
                 match operation.execute(&repo_path).await {
                     Ok(_) => {}
                     Err(e) => {
                         if e.to_string().contains("dirty") && !save_dirty {
-                            println!(
+                            display_println!(
                                 "{} (dirty working directory - use --save-dirty to auto-commit)",
                                 style("⚠️").yellow()
                             );
+                            result.outcome = SyncOutcome::Dirty;
                         } else {
-                            println!("{}", style("✗").red());
-                            eprintln!("  Error: {e}");
+                            display_println!("{}", style("✗").red());
+                            display_eprintln!("  Error: {e}");
+                            result = RepoSyncResult::failed(&repo.name, e.to_string());
                         }
-                        success = false;
                         break;
                     }
                 }
             }
 
-            if success {
-                println!("{}", style("✓").green());
+            if result.outcome == SyncOutcome::Synced {
+                result.compared = compare_after_sync(repo, &repo_path).await;
+                match &result.compared {
+                    Some(compared) => display_println!(
+                        "{} {}",
+                        style("✓").green(),
+                        style(format!(
+                            "↑{} ↓{} vs {}",
+                            compared.ahead, compared.behind, compared.compare_ref
+                        ))
+                        .dim()
+                    ),
+                    None => display_println!("{}", style("✓").green()),
+                }
             }
+            if !result.backups.is_empty() {
+                display_println!(
+                    "  {} Backed up {} pruned branch{} under {}",
+                    style("💾").blue(),
+                    result.backups.len(),
+                    if result.backups.len() == 1 { "" } else { "es" },
+                    ref_backup::BACKUP_PREFIX
+                );
+            }
+            results.push(result);
         }
 
-        Ok(())
+        let failed = results
+            .iter()
+            .filter(|result| result.outcome == SyncOutcome::Failed)
+            .count();
+        history::record(
+            EventKind::SyncRun,
+            format!(
+                "{} {} repositories ({} failed)",
+                if fetch_only { "Fetched" } else { "Synced" },
+                results.len(),
+                failed
+            ),
+            None,
+        )
+        .await;
+        emit_sync_completed(&results, fetch_only);
+
+        Ok(results)
     }
 
-    /// Handle dirty repository by creating a dirty/{timestamp} branch
-    async fn handle_dirty_repository(&self, repo_path: &Path) -> Result<()> {
-        use chrono::Utc;
-        use std::process::Command;
-
-        // Check if repository is dirty
-        let status_output = Command::new("git")
-            .args(["status", "--porcelain"])
-            .current_dir(repo_path)
-            .output()?;
-
-        if status_output.stdout.is_empty() {
-            // Repository is clean, nothing to do
-            return Ok(());
+    /// Fast-forward the default branch of every fork among the target
+    /// repositories from its `upstream` remote. The upstream URL comes from
+    /// the remote, the configuration, or for GitHub forks from `gh`, and is
+    /// remembered on the repository entry.
+    pub async fn sync_upstream(&mut self, group: Option<&str>) -> Result<Vec<RepoSyncResult>> {
+        self.ensure_online("sync")?;
+        let targets: Vec<(String, PathBuf, Option<String>, Option<String>)> = self
+            .get_bulk_targets(None, group, BulkOperation::Sync)
+            .into_iter()
+            .map(|repo| {
+                (
+                    repo.name.clone(),
+                    repo_abs_path(&self.config, repo),
+                    repo.url.clone(),
+                    repo.upstream.clone(),
+                )
+            })
+            .collect();
+        if targets.is_empty() {
+            display_println!("{} No repositories found", style("ℹ").yellow());
+            return Ok(Vec::new());
         }
 
-        // Create timestamp for branch name
-        let timestamp = Utc::now().format("%Y%m%d_%H%M%S");
-        let branch_name = format!("dirty/{timestamp}");
+        display_println!(
+            "{} Syncing forks from upstream across {} repositories...",
+            style("🔄").blue(),
+            targets.len()
+        );
 
-        // Get current branch name
-        let current_branch_output = Command::new("git")
+        let gh_available = pull_requests::is_gh_available().await;
+        let backend = crate::git::backend::current();
+        let mut results = Vec::new();
+        let mut not_forks = 0;
+        let mut config_changed = false;
+
+        for (name, repo_path, origin_url, configured) in targets {
+            let mut url = upstream::upstream_url(&repo_path).await;
+
+            if url.is_none() {
+                let candidate = match configured.clone() {
+                    Some(configured) => Some((configured, true)),
+                    None if gh_available => match origin_url {
+                        Some(origin) => upstream::github_parent_url(&origin)
+                            .await
+                            .map(|parent| (parent, false)),
+                        None => None,
+                    },
+                    None => None,
+                };
+                if let Some((candidate, known)) = candidate {
+                    let add = known
+                        || (crate::output::is_interactive()
+                            && crate::ui::prompts::prompt_yes_no(
+                                &format!(
+                                    "{name} is a fork of {candidate}. Add it as the '{}' remote?",
+                                    upstream::UPSTREAM_REMOTE
+                                ),
+                                true,
+                            )?);
+                    if add {
+                        match upstream::add_upstream_remote(&repo_path, &candidate).await {
+                            Ok(()) => url = Some(candidate),
+                            Err(e) => {
+                                display_println!(
+                                    "{} {}... {}",
+                                    style("→").dim(),
+                                    style(&name).cyan(),
+                                    style("✗").red()
+                                );
+                                display_eprintln!("  Error: {e}");
+                                results.push(RepoSyncResult::failed(&name, e.to_string()));
+                                continue;
+                            }
+                        }
+                    } else {
+                        display_println!(
+                            "{} {} is a fork of {} with no '{}' remote, skipped",
+                            style("ℹ").yellow(),
+                            style(&name).cyan(),
+                            candidate,
+                            upstream::UPSTREAM_REMOTE
+                        );
+                    }
+                }
+            }
+
+            let Some(url) = url else {
+                not_forks += 1;
+                continue;
+            };
+            if configured.as_deref() != Some(url.as_str()) {
+                if let Some(repo) = self
+                    .config
+                    .repositories
+                    .iter_mut()
+                    .find(|repo| repo.name == name)
+                {
+                    repo.upstream = Some(url);
+                    config_changed = true;
+                }
+            }
+
+            display_print!("{} {}... ", style("→").dim(), style(&name).cyan());
+            let _lock = match lock_for_sync(&name, &repo_path).await {
+                Ok(lock) => lock,
+                Err(skipped) => {
+                    results.push(skipped);
+                    continue;
+                }
+            };
+            let result = match upstream::sync_default_branch(&*backend, &repo_path).await {
+                Ok(UpstreamSync::UpToDate { branch }) => {
+                    display_println!("{} {branch} up to date", style("✓").green());
+                    RepoSyncResult {
+                        name,
+                        outcome: SyncOutcome::Synced,
+                        error: None,
+                        compared: None,
+                        backups: Vec::new(),
+                    }
+                }
+                Ok(UpstreamSync::FastForwarded { branch, commits }) => {
+                    display_println!(
+                        "{} {branch} fast-forwarded {commits} commit{}",
+                        style("✓").green(),
+                        if commits == 1 { "" } else { "s" }
+                    );
+                    RepoSyncResult {
+                        name,
+                        outcome: SyncOutcome::Synced,
+                        error: None,
+                        compared: None,
+                        backups: Vec::new(),
+                    }
+                }
+                Ok(UpstreamSync::Diverged {
+                    branch,
+                    ahead,
+                    behind,
+                }) => {
+                    let message = format!(
+                        "{branch} has {ahead} commit{} not in upstream and is {behind} behind; not fast-forwarding",
+                        if ahead == 1 { "" } else { "s" }
+                    );
+                    display_println!("{} {message}", style("⚠️").yellow());
+                    RepoSyncResult {
+                        name,
+                        outcome: SyncOutcome::Diverged,
+                        error: Some(message),
+                        compared: None,
+                        backups: Vec::new(),
+                    }
+                }
+                Err(e) => {
+                    display_println!("{}", style("✗").red());
+                    display_eprintln!("  Error: {e}");
+                    RepoSyncResult::failed(&name, e.to_string())
+                }
+            };
+            results.push(result);
+        }
+
+        if config_changed {
+            self.save_config().await?;
+        }
+        if not_forks > 0 {
+            display_println!(
+                "{}",
+                style(format!(
+                    "{not_forks} repositories without an '{}' remote were skipped",
+                    upstream::UPSTREAM_REMOTE
+                ))
+                .dim()
+            );
+        }
+        self.print_excluded_footnote(None, group, BulkOperation::Sync);
+
+        let diverged: Vec<&str> = results
+            .iter()
+            .filter(|result| result.outcome == SyncOutcome::Diverged)
+            .map(|result| result.name.as_str())
+            .collect();
+        if !diverged.is_empty() {
+            display_println!(
+                "{} Not fast-forwarded, merge or rebase by hand: {}",
+                style("⚠️").yellow(),
+                diverged.join(", ")
+            );
+        }
+
+        let failed = results
+            .iter()
+            .filter(|result| result.outcome == SyncOutcome::Failed)
+            .count();
+        history::record(
+            EventKind::SyncRun,
+            format!(
+                "Synced {} forks from upstream ({} failed)",
+                results.len(),
+                failed
+            ),
+            None,
+        )
+        .await;
+        emit_sync_completed(&results, false);
+
+        Ok(results)
+    }
+
+    /// Handle dirty repository by creating a dirty/{timestamp} branch
+    async fn handle_dirty_repository(&self, repo: &Repository, repo_path: &Path) -> Result<()> {
+        use chrono::Utc;
+
+        // Check if repository is dirty
+        let status_output = Command::new("git")
+            .args(["status", "--porcelain"])
+            .current_dir(repo_path)
+            .output()
+            .await?;
+
+        if status_output.stdout.is_empty() {
+            // Repository is clean, nothing to do
+            return Ok(());
+        }
+
+        // Create timestamp for branch name
+        let timestamp = Utc::now().format("%Y%m%d_%H%M%S");
+        let branch_name = format!("dirty/{timestamp}");
+
+        let worktree_config = self.config.get_worktree_config_for_repo(&repo.name);
+        branch_protection::load(
+            repo_path,
+            &worktree_config.protected_branches,
+            worktree_config.check_github_protection,
+        )
+        .await
+        .check(&branch_name, false, false)?;
+
+        // Get current branch name
+        let current_branch_output = Command::new("git")
             .args(["branch", "--show-current"])
             .current_dir(repo_path)
-            .output()?;
+            .output()
+            .await?;
         let current_branch = String::from_utf8_lossy(&current_branch_output.stdout)
             .trim()
             .to_string();
@@ -675,26 +2164,30 @@ impl WorkspaceManager {
         Command::new("git")
             .args(["checkout", "-b", &branch_name])
             .current_dir(repo_path)
-            .output()?;
+            .output()
+            .await?;
 
         // Add all changes
         Command::new("git")
             .args(["add", "-A"])
             .current_dir(repo_path)
-            .output()?;
+            .output()
+            .await?;
 
         // Commit changes
         let commit_message = format!("WIP: auto-saved dirty changes from {current_branch}");
         Command::new("git")
             .args(["commit", "-m", &commit_message])
             .current_dir(repo_path)
-            .output()?;
+            .output()
+            .await?;
 
         // Switch back to original branch
         Command::new("git")
             .args(["checkout", &current_branch])
             .current_dir(repo_path)
-            .output()?;
+            .output()
+            .await?;
 
         Ok(())
     }
@@ -703,6 +2196,157 @@ impl WorkspaceManager {
         self.config.save_to_file(&self.config_path).await
     }
 
+    /// Put entries `vibe git scan --clean` removed back into the config: the
+    /// named ones, or all of them. Entries whose name is configured again
+    /// are dropped from the removed list without being re-added.
+    pub async fn readd_removed_repositories(&mut self, names: &[String]) -> Result<Vec<String>> {
+        let path = super::constants::get_removed_repos_path();
+        let mut removed = removed_repos::load(&path).await?;
+        if let Some(unknown) = names
+            .iter()
+            .find(|name| !removed.iter().any(|r| &r.repository.name == *name))
+        {
+            return Err(CommandError::not_found("Removed repository", unknown.as_str()).into());
+        }
+
+        let (selected, kept): (Vec<_>, Vec<_>) = removed
+            .drain(..)
+            .partition(|r| names.is_empty() || names.contains(&r.repository.name));
+        let mut readded = Vec::new();
+        for entry in selected {
+            let repo = entry.repository;
+            if self.config.get_repository(&repo.name).is_some() {
+                continue;
+            }
+            readded.push(repo.name.clone());
+            self.config.add_repository(repo);
+        }
+
+        self.save_config().await?;
+        removed_repos::save(&path, &kept).await?;
+        Ok(readded)
+    }
+
+    /// Exclude repository `name` from `operations` (every bulk operation when
+    /// empty), or include it again when `exclude` is false. Returns the
+    /// operations it is excluded from afterwards.
+    pub async fn set_bulk_exclusion(
+        &mut self,
+        name: &str,
+        operations: &[BulkOperation],
+        exclude: bool,
+    ) -> Result<Vec<BulkOperation>> {
+        let operations = if operations.is_empty() {
+            &BulkOperation::ALL[..]
+        } else {
+            operations
+        };
+        let repo = self
+            .config
+            .repositories
+            .iter_mut()
+            .find(|repo| repo.name == name)
+            .ok_or_else(|| CommandError::not_found("Repository", name))?;
+
+        if exclude {
+            for operation in operations {
+                if !repo.exclude.contains(operation) {
+                    repo.exclude.push(*operation);
+                }
+            }
+        } else {
+            repo.exclude.retain(|op| !operations.contains(op));
+        }
+        repo.exclude
+            .sort_by_key(|op| BulkOperation::ALL.iter().position(|o| o == op));
+        let excluded = repo.exclude.clone();
+
+        self.save_config().await?;
+        Ok(excluded)
+    }
+
+    /// Describe the workspace portably, with the repositories left out
+    pub fn export_manifest(&self) -> (WorkspaceManifest, Vec<ManifestSkip>) {
+        WorkspaceManifest::from_config(&self.config)
+    }
+
+    /// Clone and configure what `manifest` describes into this workspace
+    pub async fn restore_manifest(
+        &mut self,
+        manifest: &WorkspaceManifest,
+    ) -> Result<ManifestRestoreReport> {
+        let mut report = manifest::restore(manifest, &mut self.config).await?;
+
+        let installed = template_packs::installed(self.template_manager.template_root()).await?;
+        for pack in &self.config.template_packs {
+            if installed.contains_key(&pack.name) {
+                continue;
+            }
+            match template_packs::install(&self.template_manager, &pack.source).await {
+                Ok(_) => report.template_packs.push(pack.name.clone()),
+                Err(e) => report.warnings.push(format!(
+                    "Template pack '{}' from {} wasn't installed: {e:#}",
+                    pack.name, pack.source
+                )),
+            }
+        }
+
+        for (app, template) in manifest::referenced_templates(manifest) {
+            let templates = self.template_manager.list_templates(&app).await?;
+            if !templates.contains(&template) {
+                report.warnings.push(format!(
+                    "Template '{template}' for {app} doesn't exist here; add it to {}",
+                    self.template_manager.get_app_template_dir(&app).display()
+                ));
+            }
+        }
+
+        self.save_config().await?;
+        history::record(
+            EventKind::ConfigEdited,
+            format!(
+                "Restored workspace manifest '{}' ({} cloned)",
+                manifest.workspace,
+                report.cloned.len()
+            ),
+            None,
+        )
+        .await;
+        Ok(report)
+    }
+
+    /// Merge the shared configuration from the sync repository into this one
+    pub async fn pull_shared_config(&mut self) -> Result<ConfigSyncReport> {
+        let report = config_sync::pull(&mut self.config, &self.config_path).await?;
+        if !report.pulled.is_empty() {
+            history::record(
+                EventKind::ConfigEdited,
+                format!("Pulled {} shared config entries", report.pulled.len()),
+                None,
+            )
+            .await;
+        }
+        Ok(report)
+    }
+
+    /// Merge with the sync repository and publish the result
+    pub async fn push_shared_config(&mut self) -> Result<ConfigSyncReport> {
+        let report = config_sync::push(&mut self.config, &self.config_path).await?;
+        if !report.pulled.is_empty() || !report.pushed.is_empty() {
+            history::record(
+                EventKind::ConfigEdited,
+                format!(
+                    "Synced shared config: {} pulled, {} pushed",
+                    report.pulled.len(),
+                    report.pushed.len()
+                ),
+                None,
+            )
+            .await;
+        }
+        Ok(report)
+    }
+
     pub async fn init_config(
         &mut self,
         name: Option<&str>,
@@ -750,6 +2394,9 @@ impl WorkspaceManager {
                 if let Ok(Some(url)) = get_remote_url(&repo_path) {
                     repo = repo.with_url(url);
                 }
+                if let Ok(Some(upstream)) = get_upstream_url(&repo_path) {
+                    repo = repo.with_upstream(upstream);
+                }
 
                 if let Ok(Some(branch)) = get_current_branch(&repo_path) {
                     repo = repo.with_branch(branch);
@@ -767,7 +2414,7 @@ impl WorkspaceManager {
             warn!("Failed to configure Claude agents: {}", e);
         }
 
-        println!(
+        display_println!(
             "{} Initialized workspace '{}' in {}",
             style("✓").green().bold(),
             style(&self.config.workspace.name).cyan().bold(),
@@ -775,7 +2422,7 @@ impl WorkspaceManager {
         );
 
         if auto_discover && !self.config.repositories.is_empty() {
-            println!(
+            display_println!(
                 "{} Auto-discovered {} repositories",
                 style("📁").green(),
                 self.config.repositories.len()
@@ -786,8 +2433,6 @@ impl WorkspaceManager {
     }
 
     pub async fn edit_config(&self, direct: bool) -> Result<()> {
-        use std::process::Command;
-
         // Get editor from environment
         let editor = std::env::var("EDITOR")
             .or_else(|_| std::env::var("VISUAL"))
@@ -800,24 +2445,36 @@ impl WorkspaceManager {
             });
 
         if !direct {
-            println!(
+            display_println!(
                 "{} Opening config file in {}...",
                 style("📝").blue(),
                 style(&editor).cyan()
             );
         }
 
+        let before = tokio::fs::read_to_string(&self.config_path).await.ok();
+
         // Open editor
         let status = Command::new(&editor)
             .arg(&self.config_path)
             .status()
+            .await
             .with_context(|| format!("Failed to open editor: {editor}"))?;
 
         if !status.success() {
             anyhow::bail!("Editor exited with non-zero status");
         }
 
-        println!(
+        if tokio::fs::read_to_string(&self.config_path).await.ok() != before {
+            history::record(
+                EventKind::ConfigEdited,
+                format!("Edited {}", self.config_path.display()),
+                None,
+            )
+            .await;
+        }
+
+        display_println!(
             "{} Configuration edited successfully",
             style("✓").green().bold()
         );
@@ -825,10 +2482,15 @@ impl WorkspaceManager {
         Ok(())
     }
 
-    pub async fn show_config(&self, format: &str, section: Option<&str>) -> Result<()> {
+    pub async fn show_config(
+        &self,
+        format: &str,
+        section: Option<&str>,
+        show_secrets: bool,
+    ) -> Result<()> {
         let output = match section {
             Some("workspace") => match format {
-                "json" => serde_json::to_string_pretty(&self.config.workspace)?,
+                "json" => render_config(&self.config.workspace, "json", show_secrets)?,
                 "pretty" => format!(
                     "🏗️  Workspace Configuration\n\
                      ━━━━━━━━━━━━━━━━━━━━━━━━\n\
@@ -843,10 +2505,10 @@ impl WorkspaceManager {
                         style("disabled").red()
                     }
                 ),
-                _ => serde_yaml::to_string(&self.config.workspace)?,
+                _ => render_config(&self.config.workspace, "yaml", show_secrets)?,
             },
             Some("repositories") => match format {
-                "json" => serde_json::to_string_pretty(&self.config.repositories)?,
+                "json" => render_config(&self.config.repositories, "json", show_secrets)?,
                 "pretty" => {
                     let mut output = format!(
                         "📁 Repositories ({})\n━━━━━━━━━━━━━━━━━",
@@ -854,19 +2516,27 @@ impl WorkspaceManager {
                     );
                     for repo in &self.config.repositories {
                         output.push_str(&format!(
-                            "\n\n• {}\n  Path: {}\n  URL: {}\n  Branch: {}",
+                            "\n\n• {}\n  Path: {}{}\n  URL: {}\n  Branch: {}{}",
                             style(&repo.name).cyan().bold(),
                             style(repo.path.display()).dim(),
-                            repo.url.as_deref().unwrap_or("(none)"),
-                            repo.branch.as_deref().unwrap_or("(default)")
+                            outside_root_flag(&self.config, repo),
+                            repo.url.as_deref().map_or(Cow::Borrowed("(none)"), |url| {
+                                if show_secrets {
+                                    Cow::Borrowed(url)
+                                } else {
+                                    redact::scrub(url)
+                                }
+                            }),
+                            repo.branch.as_deref().unwrap_or("(default)"),
+                            excluded_line(repo)
                         ));
                     }
                     output
                 }
-                _ => serde_yaml::to_string(&self.config.repositories)?,
+                _ => render_config(&self.config.repositories, "yaml", show_secrets)?,
             },
             Some("groups") => match format {
-                "json" => serde_json::to_string_pretty(&self.config.groups)?,
+                "json" => render_config(&self.config.groups, "json", show_secrets)?,
                 "pretty" => {
                     let mut output =
                         format!("👥 Groups ({})\n━━━━━━━━━━━━", self.config.groups.len());
@@ -876,13 +2546,19 @@ impl WorkspaceManager {
                             style(&group.name).cyan().bold(),
                             group.repos.join(", ")
                         ));
+                        if let Some(app) = &group.default_app {
+                            output.push_str(&format!("\n  Default app: {}", style(app).green()));
+                            if let Some(template) = &group.default_template {
+                                output.push_str(&format!(" (template: {template})"));
+                            }
+                        }
                     }
                     output
                 }
-                _ => serde_yaml::to_string(&self.config.groups)?,
+                _ => render_config(&self.config.groups, "yaml", show_secrets)?,
             },
             Some("apps") => match format {
-                "json" => serde_json::to_string_pretty(&self.config.apps)?,
+                "json" => render_config(&self.config.apps, "json", show_secrets)?,
                 "pretty" => {
                     let mut output = "🔧 App Integrations\n━━━━━━━━━━━━━━━━━".to_string();
 
@@ -936,10 +2612,10 @@ impl WorkspaceManager {
 
                     output
                 }
-                _ => serde_yaml::to_string(&self.config.apps)?,
+                _ => render_config(&self.config.apps, "yaml", show_secrets)?,
             },
             _ => match format {
-                "json" => serde_json::to_string_pretty(&self.config)?,
+                "json" => render_config(&self.config, "json", show_secrets)?,
                 "pretty" => {
                     // Show all sections in pretty format
                     let mut output = String::new();
@@ -967,9 +2643,10 @@ impl WorkspaceManager {
                     ));
                     for repo in &self.config.repositories {
                         output.push_str(&format!(
-                            "\n• {} ({})",
+                            "\n• {} ({}){}",
                             style(&repo.name).cyan(),
-                            style(repo.path.display()).dim()
+                            style(repo.path.display()).dim(),
+                            outside_root_flag(&self.config, repo)
                         ));
                     }
 
@@ -990,11 +2667,11 @@ impl WorkspaceManager {
 
                     output
                 }
-                _ => serde_yaml::to_string(&self.config)?,
+                _ => render_config(&self.config, "yaml", show_secrets)?,
             },
         };
 
-        println!("{output}");
+        data_println!("{output}");
         Ok(())
     }
 
@@ -1007,7 +2684,7 @@ impl WorkspaceManager {
         let mut issues = Vec::new();
         let mut warnings = Vec::new();
 
-        println!(
+        display_println!(
             "{} Validating workspace configuration...",
             style("🔍").blue()
         );
@@ -1022,9 +2699,9 @@ impl WorkspaceManager {
 
         // Check repository paths
         if check_paths {
-            println!("  {} Checking repository paths...", style("→").dim());
+            display_println!("  {} Checking repository paths...", style("→").dim());
             for repo in &self.config.repositories {
-                let repo_path = self.config.workspace.root.join(&repo.path);
+                let repo_path = repo_abs_path(&self.config, repo);
                 if !repo_path.exists() {
                     issues.push(format!(
                         "Repository path does not exist: {} ({})",
@@ -1043,7 +2720,7 @@ impl WorkspaceManager {
 
         // Check remote URLs
         if check_remotes {
-            println!("  {} Checking remote URLs...", style("→").dim());
+            display_println!("  {} Checking remote URLs...", style("→").dim());
             for repo in &self.config.repositories {
                 if let Some(url) = &repo.url {
                     // Basic URL validation
@@ -1062,7 +2739,7 @@ impl WorkspaceManager {
 
         // Check app integrations
         if check_apps {
-            println!("  {} Checking app integrations...", style("→").dim());
+            display_println!("  {} Checking app integrations...", style("→").dim());
 
             if let Some(warp) = &self.config.apps.warp {
                 if warp.enabled && !warp.config_dir.exists() {
@@ -1090,6 +2767,47 @@ impl WorkspaceManager {
                     ));
                 }
             }
+
+            if lfs::lfs_version().await.is_none() {
+                for repo in &self.config.repositories {
+                    let repo_path = repo_abs_path(&self.config, repo);
+                    if lfs::uses_lfs(&repo_path).await {
+                        warnings.push(format!(
+                            "{} uses Git LFS but git-lfs is not installed (install with `{}`)",
+                            repo.name,
+                            lfs::install_hint()
+                        ));
+                    }
+                }
+            }
+        }
+
+        // Check managed hooks match their source scripts
+        if !self.config.hooks.git.is_empty() {
+            display_println!("  {} Checking managed git hooks...", style("→").dim());
+            match self.hook_statuses(None).await {
+                Ok(repos) => {
+                    for repo in &repos {
+                        let drifted: Vec<String> = repo
+                            .drifted()
+                            .map(|hook| match hook.state {
+                                hooks::HookState::NotInstalled => {
+                                    format!("{} (missing)", hook.hook)
+                                }
+                                _ => hook.hook.clone(),
+                            })
+                            .collect();
+                        if !drifted.is_empty() {
+                            warnings.push(format!(
+                                "Git hooks out of sync in {}: {} (run 'vibe git hooks sync')",
+                                repo.repository,
+                                drifted.join(", ")
+                            ));
+                        }
+                    }
+                }
+                Err(e) => issues.push(format!("Managed git hooks: {e:#}")),
+            }
         }
 
         // Check groups reference existing repositories
@@ -1107,24 +2825,39 @@ impl WorkspaceManager {
                     ));
                 }
             }
+            match &group.default_app {
+                Some(app) if !crate::utils::completions::SUPPORTED_APPS.contains(&app.as_str()) => {
+                    issues.push(format!(
+                        "Group '{}' has unknown default app: {app}",
+                        group.name
+                    ));
+                }
+                None if group.default_template.is_some() => {
+                    warnings.push(format!(
+                        "Group '{}' sets default_template without default_app; it is ignored",
+                        group.name
+                    ));
+                }
+                _ => {}
+            }
         }
 
         // Report results
-        println!();
+        display_println!();
         if issues.is_empty() && warnings.is_empty() {
-            println!("{} Configuration is valid!", style("✓").green().bold());
+            display_println!("{} Configuration is valid!", style("✓").green().bold());
         } else {
             if !issues.is_empty() {
-                println!("{} Issues found:", style("❌").red().bold());
+                display_println!("{} Issues found:", style("❌").red().bold());
                 for issue in &issues {
-                    println!("  • {issue}");
+                    display_println!("  • {issue}");
                 }
             }
 
             if !warnings.is_empty() {
-                println!("\n{} Warnings:", style("⚠️").yellow().bold());
+                display_println!("\n{} Warnings:", style("⚠️").yellow().bold());
                 for warning in &warnings {
-                    println!("  • {warning}");
+                    display_println!("  • {warning}");
                 }
             }
 
@@ -1153,7 +2886,7 @@ impl WorkspaceManager {
             .repositories
             .iter_mut()
             .find(|r| r.name == repo_name)
-            .context("Repository not found")?;
+            .ok_or_else(|| CommandError::not_found("Repository", repo_name))?;
 
         // Check if template exists
         let templates = self.template_manager.list_templates(app).await?;
@@ -1176,11 +2909,8 @@ impl WorkspaceManager {
     /// List configured apps for a repository
     pub fn list_apps_for_repo(&self, repo_name: &str) -> Result<Vec<(String, String)>> {
         let repo = self
-            .config
-            .repositories
-            .iter()
-            .find(|r| r.name == repo_name)
-            .context("Repository not found")?;
+            .get_open_target(repo_name)
+            .ok_or_else(|| CommandError::not_found("Repository", repo_name))?;
 
         let mut apps = Vec::new();
         for (app_name, config) in &repo.apps {
@@ -1190,6 +2920,7 @@ impl WorkspaceManager {
                 apps.push((app_name.clone(), "default".to_string()));
             }
         }
+        output::sort_if_deterministic(&mut apps, |(app_name, _)| app_name.clone());
 
         Ok(apps)
     }
@@ -1198,70 +2929,83 @@ impl WorkspaceManager {
     pub async fn get_repos_with_apps_and_status(&self) -> Result<Vec<RepoWithStatus>> {
         let mut repos_with_status = Vec::new();
 
+        let mut with_apps = Vec::new();
         for repo in &self.config.repositories {
             let apps = self.list_apps_for_repo(&repo.name)?;
-
             // Only include repos that have at least one app configured
             if !apps.is_empty() {
-                let repo_path = self.config.workspace.root.join(&repo.path);
-                let git_status = get_git_status(&repo_path)
-                    .await
-                    .unwrap_or_else(|_| GitStatus {
-                        repository_name: repo.name.clone(),
-                        path: repo.path.display().to_string(),
-                        branch: None,
-                        clean: true,
-                        ahead: 0,
-                        behind: 0,
-                        staged: 0,
-                        unstaged: 0,
-                        untracked: 0,
-                        remote_url: None,
-                    });
+                with_apps.push((repo, apps));
+            }
+        }
+        let names: Vec<String> = with_apps
+            .iter()
+            .map(|(repo, _)| repo.name.clone())
+            .collect();
+        let snapshot = self
+            .refresh_statuses(StatusScope::Repos(&names), STATUS_CACHE_TTL)
+            .await;
+
+        for (repo, apps) in with_apps {
+            let git_status = snapshot
+                .get(&repo.name)
+                .cloned()
+                .unwrap_or_else(|| GitStatus {
+                    repository_name: repo.name.clone(),
+                    path: repo.path.display().to_string(),
+                    branch: None,
+                    clean: true,
+                    ahead: 0,
+                    behind: 0,
+                    staged: 0,
+                    unstaged: 0,
+                    untracked: 0,
+                    remote_url: None,
+                    upstream_behind: None,
+                    compare_ref: None,
+                });
 
-                // Create display string with status indicators
-                let status_indicator = if git_status.clean {
-                    "✓".to_string()
+            // Create display string with status indicators
+            let status_indicator = if git_status.clean {
+                "✓".to_string()
+            } else {
+                let mut indicators = Vec::new();
+                if git_status.staged > 0 {
+                    indicators.push(format!("{}S", git_status.staged));
+                }
+                if git_status.unstaged > 0 {
+                    indicators.push(format!("{}U", git_status.unstaged));
+                }
+                if git_status.untracked > 0 {
+                    indicators.push(format!("{}?", git_status.untracked));
+                }
+                if git_status.ahead > 0 {
+                    indicators.push(format!("↑{}", git_status.ahead));
+                }
+                if git_status.behind > 0 {
+                    indicators.push(format!("↓{}", git_status.behind));
+                }
+                if indicators.is_empty() {
+                    "●".to_string()
                 } else {
-                    let mut indicators = Vec::new();
-                    if git_status.staged > 0 {
-                        indicators.push(format!("{}S", git_status.staged));
-                    }
-                    if git_status.unstaged > 0 {
-                        indicators.push(format!("{}U", git_status.unstaged));
-                    }
-                    if git_status.untracked > 0 {
-                        indicators.push(format!("{}?", git_status.untracked));
-                    }
-                    if git_status.ahead > 0 {
-                        indicators.push(format!("↑{}", git_status.ahead));
-                    }
-                    if git_status.behind > 0 {
-                        indicators.push(format!("↓{}", git_status.behind));
-                    }
-                    if indicators.is_empty() {
-                        "●".to_string()
-                    } else {
-                        indicators.join(" ")
-                    }
-                };
+                    indicators.join(" ")
+                }
+            };
 
-                let app_names: Vec<String> = apps.iter().map(|(name, _)| name.clone()).collect();
-                let display_string = format!(
-                    "{} [{}] (apps: {})",
-                    repo.name,
-                    status_indicator,
-                    app_names.join(", ")
-                );
+            let app_names: Vec<String> = apps.iter().map(|(name, _)| name.clone()).collect();
+            let display_string = format!(
+                "{} [{}] (apps: {})",
+                repo.name,
+                status_indicator,
+                app_names.join(", ")
+            );
 
-                repos_with_status.push(RepoWithStatus {
-                    name: repo.name.clone(),
-                    path: repo.path.display().to_string(),
-                    apps,
-                    git_status,
-                    display_string,
-                });
-            }
+            repos_with_status.push(RepoWithStatus {
+                name: repo.name.clone(),
+                path: repo.path.display().to_string(),
+                apps,
+                git_status,
+                display_string,
+            });
         }
 
         Ok(repos_with_status)
@@ -1291,13 +3035,13 @@ impl WorkspaceManager {
     pub async fn init_templates(&self) -> Result<()> {
         self.template_manager.init_default_templates().await?;
 
-        println!(
+        display_println!(
             "{} Initialized default templates in {}",
             style("✓").green().bold(),
-            style(super::constants::CONFIG_DIR_DISPLAY)
-                .cyan()
-                .to_string()
-                + "/templates"
+            style(super::constants::display_path(
+                &super::constants::get_templates_dir()
+            ))
+            .cyan()
         );
 
         Ok(())
@@ -1323,7 +3067,7 @@ impl WorkspaceManager {
             .save_template(app, template_name, &content)
             .await?;
 
-        println!(
+        display_println!(
             "{} Created template '{}' for {}",
             style("✓").green().bold(),
             style(template_name).cyan(),
@@ -1343,7 +3087,7 @@ impl WorkspaceManager {
             .delete_template(app, template_name)
             .await?;
 
-        println!(
+        display_println!(
             "{} Deleted template '{}' for {}",
             style("✓").green().bold(),
             style(template_name).cyan(),
@@ -1353,25 +3097,81 @@ impl WorkspaceManager {
         Ok(())
     }
 
+    /// Install the template pack at `source`, recording it in the
+    /// configuration unless `record` is false
+    pub async fn install_template_pack(
+        &mut self,
+        source: &str,
+        record: bool,
+    ) -> Result<PackInstallReport> {
+        let report = template_packs::install(&self.template_manager, source).await?;
+        if record {
+            let entry = TemplatePackSource {
+                name: report.name.clone(),
+                source: source.to_string(),
+            };
+            match self
+                .config
+                .template_packs
+                .iter_mut()
+                .find(|pack| pack.name == entry.name)
+            {
+                Some(existing) => *existing = entry,
+                None => self.config.template_packs.push(entry),
+            }
+            self.save_config().await?;
+        }
+        history::record(
+            EventKind::ConfigEdited,
+            format!(
+                "Installed template pack '{}' ({} templates)",
+                report.name,
+                report.templates.len()
+            ),
+            None,
+        )
+        .await;
+        Ok(report)
+    }
+
+    /// Remove the templates of pack `name` and its entry in the configuration
+    pub async fn uninstall_template_pack(&mut self, name: &str) -> Result<Vec<PackTemplate>> {
+        let removed = template_packs::uninstall(&self.template_manager, name).await?;
+        let recorded = self.config.template_packs.len();
+        self.config.template_packs.retain(|pack| pack.name != name);
+        if self.config.template_packs.len() != recorded {
+            self.save_config().await?;
+        }
+        history::record(
+            EventKind::ConfigEdited,
+            format!("Uninstalled template pack '{name}'"),
+            None,
+        )
+        .await;
+        Ok(removed)
+    }
+
     /// Show configured apps for all repositories
     pub async fn show_app_configurations(&self) -> Result<()> {
-        println!("\n{} App Configurations:", style("📱").blue());
-        println!();
+        display_println!("\n{} App Configurations:", style("📱").blue());
+        display_println!();
 
         for repo in &self.config.repositories {
             if repo.apps.is_empty() {
                 continue;
             }
 
-            println!("{} {}", style("→").dim(), style(&repo.name).cyan().bold());
-            for (app_name, config) in &repo.apps {
+            display_println!("{} {}", style("→").dim(), style(&repo.name).cyan().bold());
+            let mut apps: Vec<_> = repo.apps.iter().collect();
+            output::sort_if_deterministic(&mut apps, |(app_name, _)| *app_name);
+            for (app_name, config) in apps {
                 if config.is_enabled() {
                     let template = match config {
                         AppConfig::WithTemplate { template } => template.as_str(),
                         AppConfig::WithConfig { template, .. } => template.as_str(),
                         AppConfig::Enabled(_) => "default",
                     };
-                    println!(
+                    display_println!(
                         "    {} {} (template: {})",
                         style("•").dim(),
                         style(app_name).green(),
@@ -1379,7 +3179,7 @@ impl WorkspaceManager {
                     );
                 }
             }
-            println!();
+            display_println!();
         }
         Ok(())
     }
@@ -1423,7 +3223,7 @@ impl WorkspaceManager {
                 "cursor" => crate::workspace::templates::DEFAULT_CURSOR_TEMPLATE,
                 "windsurf" => crate::workspace::templates::DEFAULT_WINDSURF_TEMPLATE,
                 _ => {
-                    println!("{} Unknown app '{}', skipping", style("⚠️").yellow(), app);
+                    display_println!("{} Unknown app '{}', skipping", style("⚠️").yellow(), app);
                     continue;
                 }
             };
@@ -1431,73 +3231,762 @@ impl WorkspaceManager {
             self.template_manager
                 .save_template(&app, "default", default_content)
                 .await?;
-            println!(
+            display_println!(
                 "{} Updated default template for {}",
                 style("✓").green(),
                 style(&app).cyan()
             );
         }
-
-        Ok(())
+
+        Ok(())
+    }
+
+    /// Smart open repository - shows app choice menu with configured and available apps
+    pub async fn smart_open_repository(&self, repo_name: &str) -> Result<()> {
+        let repo = self
+            .get_open_target(repo_name)
+            .ok_or_else(|| CommandError::not_found("Repository", repo_name))?;
+
+        // Get configured apps (if any)
+        let mut configured_apps: Vec<String> = repo.apps.keys().cloned().collect();
+        output::sort_if_deterministic(&mut configured_apps, |app| app.clone());
+
+        // Get all available apps on system
+        let available_apps = self.get_available_apps().await;
+
+        // Build app choice menu
+        let app_choices = self.build_app_choice_menu(&configured_apps, &available_apps);
+
+        if app_choices.is_empty() {
+            anyhow::bail!("No compatible apps found on this system");
+        }
+
+        // Prompt user to choose app
+        let selected_app = self.prompt_app_selection(&app_choices)?;
+
+        // Open with selected app using existing logic
+        self.open_repo_with_app_options(repo_name, &selected_app, false)
+            .await
+    }
+
+    /// The default app `repo_name` gets from its groups. A repository in
+    /// several groups with different defaults takes the first group's and
+    /// gets a warning.
+    pub fn group_defaults(&self, repo_name: &str) -> Option<GroupDefaults> {
+        let defaults = self.config.group_defaults(repo_name)?;
+        if !defaults.overridden.is_empty() {
+            display_println!(
+                "{} '{}' is in groups with different default apps; using {} from '{}' over {}",
+                style("⚠️").yellow(),
+                repo_name,
+                defaults.app,
+                defaults.group,
+                defaults
+                    .overridden
+                    .iter()
+                    .map(|group| format!("'{group}'"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
+        Some(defaults)
+    }
+
+    /// Add `repo_name` to `group`, saving the configuration
+    pub async fn add_repo_to_group(&mut self, repo_name: &str, group: &str) -> Result<()> {
+        let group = self
+            .config
+            .groups
+            .iter_mut()
+            .find(|g| g.name == group)
+            .ok_or_else(|| CommandError::not_found("Group", group))?;
+        if !group.repos.iter().any(|name| name == repo_name) {
+            group.repos.push(repo_name.to_string());
+            self.save_config().await?;
+        }
+        Ok(())
+    }
+
+    /// Open a repository with a configured app
+    pub async fn open_repo_with_app(&self, repo_name: &str, app: &str) -> Result<()> {
+        self.open_repo_with_app_options(repo_name, app, false).await
+    }
+
+    /// Open a repository with a configured app with options
+    pub async fn open_repo_with_app_options(
+        &self,
+        repo_name: &str,
+        app: &str,
+        no_itermocil: bool,
+    ) -> Result<()> {
+        self.open_repo_with_fetch(
+            repo_name,
+            app,
+            no_itermocil,
+            self.is_fetch_on_open_enabled(),
+        )
+        .await
+    }
+
+    /// Open repository with specific app, fetching it alongside the launch
+    /// when `fetch` is set
+    pub async fn open_repo_with_fetch(
+        &self,
+        repo_name: &str,
+        app: &str,
+        no_itermocil: bool,
+        fetch: bool,
+    ) -> Result<()> {
+        let repo = self
+            .get_open_target(repo_name)
+            .ok_or_else(|| CommandError::not_found("Repository", repo_name))?;
+
+        // The file manager and browser need neither configuration nor a fetch
+        if crate::apps::system::is_pseudo_app(app) {
+            return self.open_repo_in_system_app(&repo, app).await;
+        }
+
+        // Start the fetch first so it runs while the app launches
+        let repo_path = repo_abs_path(&self.config, &repo);
+        let compare_ref = repo.compare_ref.clone();
+        let fetch_task = fetch.then(|| {
+            tokio::spawn(async move {
+                GitOperation::Fetch.execute(&repo_path).await?;
+                get_git_status_against(&repo_path, compare_ref.as_deref()).await
+            })
+        });
+
+        // Check pinned toolchains alongside the launch too
+        let env_task = {
+            let name = repo_name.to_string();
+            let path = repo_abs_path(&self.config, &repo);
+            tokio::task::spawn_blocking(move || project_env::probe(&name, &path))
+        };
+
+        // A group's default app opens with the group's template when the
+        // repository has no configuration of its own for it
+        let repo = match self.config.group_defaults(repo_name) {
+            Some(GroupDefaults {
+                app: default_app,
+                template: Some(template),
+                ..
+            }) if default_app == app && !repo.is_app_enabled(app) => {
+                let mut repo = repo;
+                repo.to_mut()
+                    .apps
+                    .insert(app.to_string(), AppConfig::WithTemplate { template });
+                repo
+            }
+            _ => repo,
+        };
+
+        // Use configured opening if available, otherwise fall back to basic opening
+        if repo.is_app_enabled(app) {
+            // Use configured opening with templates and automation
+            self.open_repo_with_configured_app(&repo, app, no_itermocil)
+                .await?;
+        } else {
+            // Use basic opening without configuration
+            self.open_repo_with_basic_app(&repo, app).await?;
+        }
+
+        self.record_app_launch(repo_name, app).await;
+
+        if let Some(task) = fetch_task {
+            self.finish_fetch_on_open(repo_name, task).await;
+        }
+        match env_task.await {
+            Ok(env) => env.print_warnings(),
+            Err(e) => debug!("Toolchain probe failed for {}: {}", repo_name, e),
+        }
+        Ok(())
+    }
+
+    /// Configure `app` with `template` for each of `repo_names`. Repositories
+    /// that already have the app are left alone unless `overwrite` is set.
+    pub async fn configure_app_for_repos(
+        &mut self,
+        repo_names: &[String],
+        app: &str,
+        template: &str,
+        overwrite: bool,
+    ) -> Result<Vec<RepoAppConfigureResult>> {
+        let templates = self.template_manager.list_templates(app).await?;
+        if !templates.iter().any(|t| t == template) {
+            if template != "default" {
+                return Err(
+                    CommandError::not_found("Template", format!("{app}/{template}")).into(),
+                );
+            }
+            // Seed the bundled default so a fresh install can configure in bulk
+            let content = self.get_default_template(app).await?;
+            self.template_manager
+                .save_template(app, "default", &content)
+                .await?;
+        }
+
+        let mut results = Vec::with_capacity(repo_names.len());
+        for name in repo_names {
+            let repo = self
+                .config
+                .get_repository(name)
+                .ok_or_else(|| CommandError::not_found("Repository", name.as_str()))?;
+            let configured = repo.is_app_enabled(app);
+            let previous_template =
+                configured.then(|| repo.get_app_template(app).unwrap_or("default").to_string());
+
+            let outcome = match &previous_template {
+                Some(previous) if previous == template => Some(AppConfigureOutcome::Unchanged),
+                Some(_) if !overwrite => Some(AppConfigureOutcome::AlreadyConfigured),
+                _ => None,
+            };
+            if let Some(outcome) = outcome {
+                results.push(RepoAppConfigureResult {
+                    name: name.clone(),
+                    outcome,
+                    previous_template,
+                    changes: Vec::new(),
+                    error: None,
+                });
+                continue;
+            }
+
+            let selection = AppSelection {
+                app: app.to_string(),
+                selected: true,
+                template: Some(template.to_string()),
+                currently_configured: configured,
+            };
+            let mut result = RepoAppConfigureResult {
+                name: name.clone(),
+                outcome: AppConfigureOutcome::Configured,
+                previous_template,
+                changes: Vec::new(),
+                error: None,
+            };
+            let changes = self.configure_multiple_apps(name, vec![selection]).await;
+            match changes {
+                Ok(changes) => result.changes = changes,
+                Err(e) => {
+                    result.outcome = AppConfigureOutcome::Failed;
+                    result.error = Some(e.to_string());
+                }
+            }
+            results.push(result);
+        }
+        Ok(results)
+    }
+
+    /// Open every repository in `group` with `app`, one after another.
+    /// Repositories without the app configured are skipped, or configured
+    /// with the default template first when `configure_missing` is set.
+    pub async fn open_group(
+        &mut self,
+        group: &str,
+        app: &str,
+        configure_missing: bool,
+        no_itermocil: bool,
+        fetch: bool,
+    ) -> Result<Vec<RepoOpenResult>> {
+        if !self.config.groups.iter().any(|g| g.name == group) {
+            return Err(CommandError::not_found("Group", group).into());
+        }
+        let targets: Vec<(String, bool)> = self
+            .config
+            .get_repositories_in_group(group)
+            .into_iter()
+            .map(|repo| (repo.name.clone(), repo.is_app_enabled(app)))
+            .collect();
+
+        let mut results = Vec::with_capacity(targets.len());
+        let mut launched = false;
+        for (name, enabled) in targets {
+            let mut outcome = GroupOpenOutcome::Opened;
+            if !enabled {
+                if !configure_missing {
+                    results.push(RepoOpenResult {
+                        name,
+                        outcome: GroupOpenOutcome::NotConfigured,
+                        error: None,
+                    });
+                    continue;
+                }
+                if let Err(e) = self.configure_app_for_repo(&name, app, "default").await {
+                    results.push(RepoOpenResult {
+                        name,
+                        outcome: GroupOpenOutcome::Failed,
+                        error: Some(format!("{e:#}")),
+                    });
+                    continue;
+                }
+                outcome = GroupOpenOutcome::ConfiguredAndOpened;
+            }
+
+            if launched {
+                tokio::time::sleep(GROUP_OPEN_DELAY).await;
+            }
+            launched = true;
+            let result = match self
+                .open_repo_with_fetch(&name, app, no_itermocil, fetch)
+                .await
+            {
+                Ok(()) => RepoOpenResult {
+                    name,
+                    outcome,
+                    error: None,
+                },
+                Err(e) => RepoOpenResult {
+                    name,
+                    outcome: GroupOpenOutcome::Failed,
+                    error: Some(format!("{e:#}")),
+                },
+            };
+            results.push(result);
+        }
+
+        Ok(results)
+    }
+
+    fn hook_targets(&self, repos: Option<&str>, group: Option<&str>) -> Vec<(String, PathBuf)> {
+        self.get_target_repositories(repos, group)
+            .into_iter()
+            .map(|repo| (repo.name.clone(), repo_abs_path(&self.config, repo)))
+            .collect()
+    }
+
+    fn load_managed_hooks(&self) -> Result<Vec<hooks::ManagedHook>> {
+        if self.config.hooks.git.is_empty() {
+            anyhow::bail!(
+                "No hooks configured. Add a 'hooks' section to the config mapping hook names to scripts in {}",
+                super::constants::display_path(&super::constants::get_hooks_dir())
+            );
+        }
+        hooks::load_managed_hooks(&self.config.hooks.git, &super::constants::get_hooks_dir())
+    }
+
+    /// Search the contents of the workspace's repositories, or of one group
+    pub async fn search_repositories(
+        &self,
+        group: Option<&str>,
+        options: &search::SearchOptions,
+    ) -> Vec<search::RepoSearchResult> {
+        search::search_repositories(self.hook_targets(None, group), options).await
+    }
+
+    /// The file tree of a repository or `<repo>:<subproject>`
+    pub async fn file_tree(&self, target: &str, depth: usize) -> Result<file_tree::FileTree> {
+        let repo = self
+            .get_open_target(target)
+            .ok_or_else(|| CommandError::not_found("Repository", target))?;
+        let path = repo_abs_path(&self.config, &repo);
+        file_tree::file_tree(repo.name.clone(), path, depth).await
+    }
+
+    /// Record the pnpm and Cargo workspace packages of every repository
+    /// without subprojects as its subprojects. Lists written by hand are kept.
+    pub async fn detect_subprojects(&mut self) -> Result<Vec<SubprojectDetection>> {
+        let candidates: Vec<(String, PathBuf)> = self
+            .config
+            .repositories
+            .iter()
+            .filter(|repo| repo.subprojects.is_empty())
+            .map(|repo| (repo.name.clone(), repo_abs_path(&self.config, repo)))
+            .filter(|(_, path)| path.exists())
+            .collect();
+        let detections: Vec<SubprojectDetection> = tokio::task::spawn_blocking(move || {
+            candidates
+                .into_iter()
+                .map(|(repo, path)| SubprojectDetection {
+                    repo,
+                    subprojects: subprojects::detect(&path),
+                })
+                .filter(|detection| !detection.subprojects.is_empty())
+                .collect()
+        })
+        .await?;
+
+        if detections.is_empty() {
+            display_println!("{} No monorepo subprojects found", style("📦").blue());
+            return Ok(detections);
+        }
+        for detection in &detections {
+            if let Some(repo) = self
+                .config
+                .repositories
+                .iter_mut()
+                .find(|repo| repo.name == detection.repo)
+            {
+                repo.subprojects = detection.subprojects.clone();
+            }
+            let names: Vec<&str> = detection
+                .subprojects
+                .iter()
+                .map(|subproject| subproject.name.as_str())
+                .collect();
+            display_println!(
+                "{} {}: {}",
+                style("📦").blue(),
+                style(&detection.repo).cyan(),
+                names.join(", ")
+            );
+        }
+        self.save_config().await?;
+        Ok(detections)
+    }
+
+    /// Search the contents of one monorepo subproject
+    pub async fn search_subproject(
+        &self,
+        target: &str,
+        options: &search::SearchOptions,
+    ) -> Result<Vec<search::RepoSearchResult>> {
+        let (repo, subproject) = self
+            .config
+            .find_subproject(target)
+            .ok_or_else(|| CommandError::not_found("Subproject", target))?;
+        let view = repo.subproject_view(subproject);
+        let path = repo_abs_path(&self.config, &view);
+        Ok(search::search_repositories(vec![(view.name, path)], options).await)
+    }
+
+    /// Scan the workspace's repositories, or one group, for their licenses.
+    /// `deny` adds to the `denied_licenses` preference.
+    pub async fn scan_licenses(
+        &mut self,
+        group: Option<&str>,
+        deny: &[String],
+    ) -> Result<(LicenseReport, Vec<String>)> {
+        let mut deny_list = self
+            .config
+            .preferences
+            .as_ref()
+            .map(|p| p.denied_licenses.clone())
+            .unwrap_or_default();
+        for entry in deny {
+            if !deny_list.iter().any(|d| d.eq_ignore_ascii_case(entry)) {
+                deny_list.push(entry.clone());
+            }
+        }
+        let targets: Vec<(Repository, PathBuf)> = self
+            .get_target_repositories(None, group)
+            .into_iter()
+            .map(|repo| (repo.clone(), repo_abs_path(&self.config, repo)))
+            .collect();
+        let cache = self.get_repository_cache().await?;
+        Ok(licenses::scan_repositories(targets, deny_list, Some(cache)).await)
+    }
+
+    /// Install the configured hooks in repositories, chaining existing hooks
+    pub async fn sync_hooks(
+        &self,
+        repos: Option<&str>,
+        group: Option<&str>,
+    ) -> Result<Vec<hooks::RepoHookResult>> {
+        let managed = self.load_managed_hooks()?;
+        let targets = self.hook_targets(repos, group);
+        Ok(
+            tokio::task::spawn_blocking(move || hooks::sync_repositories(&targets, &managed))
+                .await?,
+        )
+    }
+
+    /// State of the configured hooks in repositories
+    pub async fn hook_statuses(&self, group: Option<&str>) -> Result<Vec<hooks::RepoHooks>> {
+        let managed = self.load_managed_hooks()?;
+        let targets = self.hook_targets(None, group);
+        Ok(
+            tokio::task::spawn_blocking(move || hooks::repository_statuses(&targets, &managed))
+                .await?,
+        )
+    }
+
+    /// Remove managed hooks from repositories, restoring the hooks they chained
+    pub async fn remove_hooks(
+        &self,
+        repos: Option<&str>,
+        group: Option<&str>,
+    ) -> Result<Vec<hooks::RepoHookResult>> {
+        let targets = self.hook_targets(repos, group);
+        Ok(tokio::task::spawn_blocking(move || hooks::remove_from_repositories(&targets)).await?)
+    }
+
+    /// Apply the configured managed files to repositories, or only report
+    /// what would change when `dry_run` is set
+    pub async fn sync_managed_files(
+        &self,
+        repos: Option<&str>,
+        group: Option<&str>,
+        dry_run: bool,
+    ) -> Result<Vec<managed_files::RepoFiles>> {
+        let managed_dir = super::constants::get_managed_files_dir();
+        if self.config.managed_files.is_empty() {
+            anyhow::bail!(
+                "No managed files configured. Add a 'managed_files' section to the config listing files in {}",
+                super::constants::display_path(&managed_dir)
+            );
+        }
+        let files = managed_files::load_managed_files(&self.config.managed_files, &managed_dir)?;
+        let targets = self.hook_targets(repos, group);
+        Ok(tokio::task::spawn_blocking(move || {
+            managed_files::sync_repositories(&targets, &files, dry_run)
+        })
+        .await?)
+    }
+
+    /// Toolchain versions pinned by a repository, checked against PATH
+    pub async fn project_env(&self, repo_name: &str) -> Result<project_env::ProjectEnv> {
+        let repo = self
+            .get_repository_flexible(repo_name)
+            .ok_or_else(|| CommandError::not_found("Repository", repo_name))?;
+        let name = repo.name.clone();
+        let path = repo_abs_path(&self.config, repo);
+        if !path.exists() {
+            anyhow::bail!("Repository '{}' is not cloned at {}", name, path.display());
+        }
+        Ok(tokio::task::spawn_blocking(move || project_env::probe(&name, &path)).await?)
+    }
+
+    /// Run an allow-listed, read-only git subcommand in a repository; see
+    /// [`crate::git::query`]
+    pub async fn git_query(
+        &self,
+        repo_name: &str,
+        subcommand: &str,
+        args: &[String],
+    ) -> Result<query::QueryOutput> {
+        let command = query::validate(subcommand, args, &self.config.safety.git_query_allow)?;
+        let repo = self
+            .get_repository_flexible(repo_name)
+            .ok_or_else(|| CommandError::not_found("Repository", repo_name))?;
+        let path = repo_abs_path(&self.config, repo);
+        if !path.exists() {
+            anyhow::bail!(
+                "Repository '{}' is not cloned at {}",
+                repo.name,
+                path.display()
+            );
+        }
+        query::run(
+            &repo.name,
+            &path,
+            &command,
+            query::MAX_OUTPUT_BYTES,
+            query::TIMEOUT,
+        )
+        .await
     }
 
-    /// Smart open repository - shows app choice menu with configured and available apps
-    pub async fn smart_open_repository(&self, repo_name: &str) -> Result<()> {
+    /// Recreate `branch` in a repository from its newest backup ref
+    pub async fn restore_branch_backup(&self, repo_name: &str, branch: &str) -> Result<RefBackup> {
         let repo = self
-            .config
-            .repositories
-            .iter()
-            .find(|r| r.name == repo_name)
-            .context("Repository not found")?;
+            .get_repository_flexible(repo_name)
+            .ok_or_else(|| CommandError::not_found("Repository", repo_name))?;
+        let path = repo_abs_path(&self.config, repo);
+        let _lock = repo_lock::acquire(&path, "refs restore").await?;
+        ref_backup::restore(&path, branch).await
+    }
 
-        // Get configured apps (if any)
-        let configured_apps: Vec<String> = repo.apps.keys().cloned().collect();
+    /// Delete backup refs taken more than `older_than` ago in the target
+    /// repositories, with a warning for each repository that failed
+    pub async fn gc_ref_backups(
+        &self,
+        older_than: chrono::Duration,
+        group: Option<&str>,
+    ) -> (Vec<ref_backup::PrunedBackups>, Vec<String>) {
+        let mut pruned = Vec::new();
+        let mut warnings = Vec::new();
+        for repo in self.get_target_repositories(None, group) {
+            let path = repo_abs_path(&self.config, repo);
+            if !path.exists() {
+                continue;
+            }
+            let result = match repo_lock::acquire(&path, "refs gc").await {
+                Ok(_lock) => ref_backup::gc(&path, older_than).await,
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(backups) if backups.is_empty() => {}
+                Ok(backups) => pruned.push(ref_backup::PrunedBackups {
+                    name: repo.name.clone(),
+                    backups,
+                }),
+                Err(e) => warnings.push(format!("{}: {e:#}", repo.name)),
+            }
+        }
+        (pruned, warnings)
+    }
 
-        // Get all available apps on system
-        let available_apps = self.get_available_apps().await;
+    /// Set where subsequent app launches are recorded as coming from
+    pub fn set_launch_source(&mut self, source: LaunchSource) {
+        self.launch_source = source;
+        history::set_source(source);
+    }
 
-        // Build app choice menu
-        let app_choices = self.build_app_choice_menu(&configured_apps, &available_apps);
+    async fn record_app_launch(&self, repo_name: &str, app: &str) {
+        history::record(
+            EventKind::RepoOpened,
+            format!("Opened {repo_name} in {app}"),
+            None,
+        )
+        .await;
+        let Some(cache) = &self.repo_cache else {
+            return;
+        };
+        let launch = AppLaunch {
+            repo: repo_name.to_string(),
+            app: app.to_string(),
+            source: self.launch_source.as_str().to_string(),
+            launched_at: chrono::Utc::now(),
+        };
+        if let Err(e) = cache.record_app_launch(&launch).await {
+            warn!("Failed to record app launch for {}: {}", repo_name, e);
+        }
+    }
 
-        if app_choices.is_empty() {
-            anyhow::bail!("No compatible apps found on this system");
+    /// Recorded app launches at or after `since`
+    pub async fn get_app_launches(
+        &self,
+        since: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<Vec<AppLaunch>> {
+        match &self.repo_cache {
+            Some(cache) => cache.get_app_launches(since).await,
+            None => Ok(Vec::new()),
         }
+    }
 
-        // Prompt user to choose app
-        let selected_app = self.prompt_app_selection(&app_choices)?;
+    /// Whether search results and clones are remembered for suggestions,
+    /// unless `preferences.repo_suggestions` is turned off
+    pub fn suggestions_enabled(&self) -> bool {
+        self.config
+            .preferences
+            .as_ref()
+            .and_then(|p| p.repo_suggestions)
+            .unwrap_or(true)
+    }
 
-        // Open with selected app using existing logic
-        self.open_repo_with_app_options(repo_name, &selected_app, false)
-            .await
+    /// Add search results to the suggestion store
+    pub async fn remember_search_results(&self, results: &[crate::git::Repository]) {
+        if !self.suggestions_enabled() || results.is_empty() {
+            return;
+        }
+        let mut store = VectorStore::load(&VectorStore::default_path()).await;
+        store.record_search_results(results);
+        if let Err(e) = store.save().await {
+            warn!("Failed to save suggestion store: {}", e);
+        }
     }
 
-    /// Open a repository with a configured app
-    pub async fn open_repo_with_app(&self, repo_name: &str, app: &str) -> Result<()> {
-        self.open_repo_with_app_options(repo_name, app, false).await
+    /// Add a cloned repository to the suggestion store
+    pub async fn remember_clone(&self, url: &str) {
+        if !self.suggestions_enabled() {
+            return;
+        }
+        let mut store = VectorStore::load(&VectorStore::default_path()).await;
+        store.record_clone(url);
+        if let Err(e) = store.save().await {
+            warn!("Failed to save suggestion store: {}", e);
+        }
     }
 
-    /// Open a repository with a configured app with options
-    pub async fn open_repo_with_app_options(
+    /// Earlier search results matching `query` that are neither in the
+    /// workspace nor in `exclude`
+    pub async fn previously_seen_repositories(
         &self,
-        repo_name: &str,
-        app: &str,
-        no_itermocil: bool,
-    ) -> Result<()> {
-        let repo = self
+        query: &str,
+        exclude: &[crate::git::Repository],
+        limit: usize,
+    ) -> Vec<crate::git::Repository> {
+        if !self.suggestions_enabled() {
+            return Vec::new();
+        }
+        let store = VectorStore::load(&VectorStore::default_path()).await;
+        if store.is_empty() {
+            return Vec::new();
+        }
+        let cloned: Vec<String> = self
             .config
             .repositories
             .iter()
-            .find(|r| r.name == repo_name)
-            .context("Repository not found")?;
+            .filter_map(|repo| repo.url.as_deref())
+            .map(|url| normalize_git_url(url).to_lowercase())
+            .collect();
+        store
+            .search(query, store.len())
+            .into_iter()
+            .map(|entry| &entry.repository)
+            .filter(|repo| !exclude.iter().any(|e| e.full_name == repo.full_name))
+            .filter(|repo| !cloned.contains(&normalize_git_url(&repo.url).to_lowercase()))
+            .take(limit)
+            .cloned()
+            .collect()
+    }
 
-        // Use configured opening if available, otherwise fall back to basic opening
-        if repo.is_app_enabled(app) {
-            // Use configured opening with templates and automation
-            self.open_repo_with_configured_app(repo, app, no_itermocil)
-                .await
+    /// Workspace repositories and remembered ones ranked by similarity to
+    /// `query`, without network access
+    pub async fn suggest_repositories(&self, query: &str, limit: usize) -> Vec<Suggestion> {
+        let store = if self.suggestions_enabled() {
+            VectorStore::load(&VectorStore::default_path()).await
         } else {
-            // Use basic opening without configuration
-            self.open_repo_with_basic_app(repo, app).await
+            VectorStore::empty()
+        };
+        store.suggest(query, &self.config.repositories, limit)
+    }
+
+    /// The configured app launched most for `repo_name`, unless
+    /// `preferences.prefer_most_used_app` is turned off
+    pub async fn most_used_app(&self, repo_name: &str, candidates: &[String]) -> Option<String> {
+        let enabled = self
+            .config
+            .preferences
+            .as_ref()
+            .and_then(|p| p.prefer_most_used_app)
+            .unwrap_or(true);
+        if !enabled {
+            return None;
+        }
+        let since =
+            chrono::Utc::now() - chrono::Duration::days(launch_stats::MOST_USED_WINDOW_DAYS);
+        let launches = self.get_app_launches(Some(since)).await.ok()?;
+        launch_stats::most_used_app(&launches, repo_name, candidates)
+    }
+
+    /// Cache the status from a fetch started by [`Self::open_repo_with_fetch`]
+    /// and hint when the branch is behind. Failures are only logged.
+    async fn finish_fetch_on_open(
+        &self,
+        repo_name: &str,
+        task: tokio::task::JoinHandle<Result<GitStatus>>,
+    ) {
+        let status = match task.await {
+            Ok(Ok(status)) => status,
+            Ok(Err(e)) => {
+                debug!("Fetch on open failed for {}: {}", repo_name, e);
+                return;
+            }
+            Err(e) => {
+                debug!("Fetch on open task failed for {}: {}", repo_name, e);
+                return;
+            }
+        };
+
+        let behind = status.behind;
+        let branch = status.branch.clone().unwrap_or_else(|| "HEAD".to_string());
+        if let Some(cache) = &self.git_cache {
+            if let Err(e) = cache.cache_git_status(&status.into()).await {
+                debug!("Failed to cache git status for {}: {}", repo_name, e);
+            }
+        }
+
+        if behind > 0 {
+            display_println!(
+                "{} {} is {} commit{} behind on {} — run 'vibe git exec pull --repos {}'",
+                style("↓").yellow(),
+                style(repo_name).cyan(),
+                behind,
+                if behind == 1 { "" } else { "s" },
+                branch,
+                repo_name
+            );
         }
     }
 
@@ -1550,14 +4039,19 @@ impl WorkspaceManager {
     /// Open repository with basic app (no configuration required)
     async fn open_repo_with_basic_app(&self, repo: &Repository, app: &str) -> Result<()> {
         // Get full path to repository
-        let repo_path = self.config.workspace.root.join(&repo.path);
+        let repo_path = repo_abs_path(&self.config, repo);
+        // Editors open a subproject next to its monorepo as a second root
+        let monorepo_root = self
+            .config
+            .find_subproject(&repo.name)
+            .map(|(monorepo, _)| repo_abs_path(&self.config, monorepo));
 
         // Check if app is available on system
         if !self.is_app_available(app).await {
             anyhow::bail!("App '{}' is not available on this system", app);
         }
 
-        println!(
+        display_println!(
             "{} Opening {} with {} (basic mode - no custom templates)",
             style("📂").blue(),
             style(&repo.name).cyan(),
@@ -1567,9 +4061,11 @@ impl WorkspaceManager {
         match app {
             "vscode" => {
                 // Basic: Open folder directly with code command
-                let status = std::process::Command::new("code")
+                let status = Command::new("code")
                     .arg(&repo_path)
+                    .args(&monorepo_root)
                     .status()
+                    .await
                     .context("Failed to execute VS Code")?;
 
                 if !status.success() {
@@ -1578,9 +4074,11 @@ impl WorkspaceManager {
             }
             "cursor" => {
                 // Basic: Open folder directly with cursor command
-                let status = std::process::Command::new("cursor")
+                let status = Command::new("cursor")
                     .arg(&repo_path)
+                    .args(&monorepo_root)
                     .status()
+                    .await
                     .context("Failed to execute Cursor")?;
 
                 if !status.success() {
@@ -1589,9 +4087,11 @@ impl WorkspaceManager {
             }
             "windsurf" => {
                 // Basic: Open folder directly with windsurf command
-                let status = std::process::Command::new("windsurf")
+                let status = Command::new("windsurf")
                     .arg(&repo_path)
+                    .args(&monorepo_root)
                     .status()
+                    .await
                     .context("Failed to execute Windsurf")?;
 
                 if !status.success() {
@@ -1600,9 +4100,10 @@ impl WorkspaceManager {
             }
             "warp" => {
                 // Basic: Open new tab in Warp with cd to repo
-                let status = std::process::Command::new("open")
+                let status = Command::new("open")
                     .args(["-a", "Warp", &format!("--args cd {}", repo_path.display())])
                     .status()
+                    .await
                     .context("Failed to execute Warp")?;
 
                 if !status.success() {
@@ -1624,9 +4125,10 @@ impl WorkspaceManager {
                     repo_path.display()
                 );
 
-                let status = std::process::Command::new("osascript")
+                let status = Command::new("osascript")
                     .args(["-e", &applescript])
                     .status()
+                    .await
                     .context("Failed to execute iTerm2 AppleScript")?;
 
                 if !status.success() {
@@ -1635,9 +4137,10 @@ impl WorkspaceManager {
             }
             "wezterm" => {
                 // Basic: Open new tab in WezTerm with cd to repo
-                let status = std::process::Command::new("wezterm")
+                let status = Command::new("wezterm")
                     .args(["cli", "spawn", "--cwd", &repo_path.to_string_lossy()])
                     .status()
+                    .await
                     .context("Failed to execute WezTerm")?;
 
                 if !status.success() {
@@ -1649,7 +4152,7 @@ impl WorkspaceManager {
             }
         }
 
-        println!(
+        display_println!(
             "{} Successfully opened {} with {}",
             style("✓").green(),
             style(&repo.name).cyan(),
@@ -1659,6 +4162,37 @@ impl WorkspaceManager {
         Ok(())
     }
 
+    /// Open a repository in the file manager or its remote in the browser
+    async fn open_repo_in_system_app(&self, repo: &Repository, app: &str) -> Result<()> {
+        use crate::apps::system;
+
+        let repo_path = repo_abs_path(&self.config, repo);
+        if app == system::BROWSER_APP {
+            let url = system::open_in_browser(&repo.name, &repo_path, repo.url.as_deref()).await?;
+            display_println!(
+                "{} Opened {} in the browser: {}",
+                style("🌐").blue(),
+                style(&repo.name).cyan(),
+                style(url).dim()
+            );
+        } else {
+            if !repo_path.exists() {
+                anyhow::bail!(
+                    "Repository '{}' is not on disk at {}",
+                    repo.name,
+                    repo_path.display()
+                );
+            }
+            system::open_in_file_manager(&repo_path).await?;
+            display_println!(
+                "{} Opened {} in the file manager",
+                style("📁").blue(),
+                style(&repo.name).cyan()
+            );
+        }
+        Ok(())
+    }
+
     /// Get current app configuration states for a repository
     pub fn get_current_app_states(&self, repo_name: &str) -> Result<AppConfigState> {
         let repo = self
@@ -1666,7 +4200,7 @@ impl WorkspaceManager {
             .repositories
             .iter()
             .find(|r| r.name == repo_name)
-            .context("Repository not found")?;
+            .ok_or_else(|| CommandError::not_found("Repository", repo_name))?;
 
         let mut state = AppConfigState::default();
 
@@ -1700,7 +4234,7 @@ impl WorkspaceManager {
             .repositories
             .iter_mut()
             .find(|r| r.name == repo_name)
-            .context("Repository not found")?;
+            .ok_or_else(|| CommandError::not_found("Repository", repo_name))?;
 
         repo.apps.remove(app);
         self.config.save_to_file(&self.config_path).await?;
@@ -1715,7 +4249,7 @@ impl WorkspaceManager {
             .repositories
             .iter()
             .find(|r| r.name == repo_name)
-            .context("Repository not found")?;
+            .ok_or_else(|| CommandError::not_found("Repository", repo_name))?;
 
         match app {
             "warp" => {
@@ -1817,16 +4351,14 @@ impl WorkspaceManager {
             config_files.push(self.config_path.clone());
         }
 
-        let vibe_dir = super::constants::get_config_dir();
-
         // State file (user preferences and recent repos)
-        let state_file = vibe_dir.join("state.json");
+        let state_file = super::constants::get_state_file_path();
         if state_file.exists() {
             config_files.push(state_file);
         }
 
         // Templates directory
-        let templates_dir = vibe_dir.join("templates");
+        let templates_dir = super::constants::get_templates_dir();
         if templates_dir.exists() {
             config_files.push(templates_dir);
         }
@@ -1902,128 +4434,46 @@ impl WorkspaceManager {
         Ok(())
     }
 
-    /// Create a backup archive of all configuration files
+    /// Create a backup archive of all configuration files, encrypted with
+    /// `passphrase` when one is given
     pub async fn create_backup(
         &self,
         output_dir: Option<PathBuf>,
         custom_name: Option<String>,
+        passphrase: Option<SecretString>,
     ) -> Result<PathBuf> {
+        let mut plan = self.plan_backup(output_dir, custom_name).await?;
+        if let Some(passphrase) = passphrase {
+            plan = plan.encrypted(passphrase);
+        }
+        plan.write().await
+    }
+
+    /// Gather everything a backup needs from the workspace. The returned
+    /// plan is written without the manager, so callers sharing it behind a
+    /// lock can release the lock while the archive is created.
+    pub async fn plan_backup(
+        &self,
+        output_dir: Option<PathBuf>,
+        custom_name: Option<String>,
+    ) -> Result<BackupPlan> {
         use chrono::Utc;
-        use std::process::Command;
-
-        // Determine output directory - default to ~/.toolprint/vibe-workspace/backups/
-        let backup_dir = output_dir.unwrap_or_else(|| {
-            dirs::home_dir()
-                .unwrap_or_else(|| PathBuf::from("."))
-                .join(super::constants::CONFIG_DIR_PATH)
-                .join("backups")
-        });
 
-        // Create backup directory if it doesn't exist
-        tokio::fs::create_dir_all(&backup_dir)
-            .await
-            .with_context(|| {
-                format!(
-                    "Failed to create backup directory: {}",
-                    backup_dir.display()
-                )
-            })?;
+        // Determine output directory - default to the data directory's backups/
+        let backup_dir = output_dir.unwrap_or_else(super::constants::get_backups_dir);
 
         // Create timestamped backup name
         let timestamp = Utc::now().format("%Y%m%d-%H%M%S");
         let backup_name = custom_name.unwrap_or_else(|| format!("vibe-backup-{timestamp}"));
-        let backup_filename = format!("{backup_name}.tgz");
-        let backup_path = backup_dir.join(&backup_filename);
-
-        println!("{} Creating backup archive...", style("📦").blue());
-
-        // Discover all configuration files
-        let config_files = self.discover_all_config_files().await?;
-
-        if config_files.is_empty() {
-            println!(
-                "{} No configuration files found to backup",
-                style("⚠️").yellow()
-            );
-            return Ok(backup_path);
-        }
-
-        // Create temporary directory for organizing backup content
-        let temp_dir = tempfile::tempdir().context("Failed to create temporary directory")?;
-        let temp_path = temp_dir.path();
-
-        // Copy files to temporary directory with organized structure
-        for config_file in &config_files {
-            let file_name = config_file
-                .file_name()
-                .context("Invalid file name")?
-                .to_string_lossy();
-
-            if config_file == &self.config_path {
-                // Main config file goes to root
-                let dest_path = temp_path.join("config.yaml");
-                tokio::fs::copy(config_file, &dest_path)
-                    .await
-                    .with_context(|| format!("Failed to copy {}", config_file.display()))?;
-            } else if file_name == "state.json" {
-                // State file goes to root
-                let dest_path = temp_path.join("state.json");
-                tokio::fs::copy(config_file, &dest_path)
-                    .await
-                    .with_context(|| format!("Failed to copy {}", config_file.display()))?;
-            } else if config_file.to_string_lossy().contains("templates") {
-                // Templates directory
-                let dest_dir = temp_path.join("templates");
-                tokio::fs::create_dir_all(&dest_dir).await?;
-                copy_dir_recursive(config_file, &dest_dir)?;
-            } else {
-                // App config files - organize by app type
-                let app_type = if file_name.ends_with(".yaml") {
-                    "warp"
-                } else if file_name.ends_with(".json") && file_name != "state.json" {
-                    "iterm2"
-                } else if file_name.ends_with(".lua") {
-                    "wezterm"
-                } else if file_name.ends_with(".code-workspace") {
-                    "vscode"
-                } else {
-                    "other"
-                };
-
-                let app_dir = temp_path.join("app-configs").join(app_type);
-                tokio::fs::create_dir_all(&app_dir).await?;
-                let dest_path = app_dir.join(file_name.as_ref());
-                tokio::fs::copy(config_file, &dest_path)
-                    .await
-                    .with_context(|| format!("Failed to copy {}", config_file.display()))?;
-            }
-        }
-
-        // Create tar archive
-        let tar_output = Command::new("tar")
-            .args(["-czf"])
-            .arg(&backup_path)
-            .args(["-C"])
-            .arg(temp_path)
-            .arg(".")
-            .output()
-            .context("Failed to execute tar command")?;
-
-        if !tar_output.status.success() {
-            let error_msg = String::from_utf8_lossy(&tar_output.stderr);
-            anyhow::bail!("Tar command failed: {}", error_msg);
-        }
-
-        println!(
-            "{} Backup contains {} configuration files:",
-            style("📋").green(),
-            config_files.len()
-        );
-        for file in &config_files {
-            println!("  {} {}", style("→").dim(), style(file.display()).cyan());
-        }
-
-        Ok(backup_path)
+        let backup_filename = format!("{backup_name}{}", backup_crypto::PLAIN_SUFFIX);
+
+        Ok(BackupPlan {
+            backup_path: backup_dir.join(&backup_filename),
+            backup_dir,
+            config_path: self.config_path.clone(),
+            config_files: self.discover_all_config_files().await?,
+            passphrase: None,
+        })
     }
 
     /// Factory reset - clear all configuration and reinitialize
@@ -2031,56 +4481,97 @@ impl WorkspaceManager {
         self.factory_reset_with_options(force, false).await
     }
 
+    /// What [`Self::factory_reset`] would delete, without deleting anything
+    pub async fn preview_factory_reset(&self) -> Result<FactoryResetPreview> {
+        let trash_items = trash::Trash::open_default()
+            .list()
+            .await
+            .unwrap_or_default()
+            .len();
+        Ok(FactoryResetPreview::new(
+            &self.discover_all_config_files().await?,
+            &super::constants::get_cache_dir(),
+            trash_items,
+        ))
+    }
+
+    /// What [`Self::reset_repositories`] would clear, without clearing it
+    pub fn preview_repository_reset(&self) -> RepositoryResetPreview {
+        let cache_dir = super::constants::get_cache_dir();
+        RepositoryResetPreview {
+            config_path: self.config_path.clone(),
+            repositories: self
+                .config
+                .repositories
+                .iter()
+                .map(|repo| ClearedRepository {
+                    name: repo.name.clone(),
+                    path: repo.path.clone(),
+                    url: repo.url.clone(),
+                    apps: repo.apps.keys().cloned().collect(),
+                })
+                .collect(),
+            cache_databases: ["repositories.db", "git_status.db"]
+                .iter()
+                .filter_map(|name| ResetPath::measure(&cache_dir.join(name)))
+                .collect(),
+        }
+    }
+
     /// Reset repository configuration only (clear all tracked repositories)
     pub async fn reset_repositories(&mut self, force: bool) -> Result<()> {
         let repo_count = self.config.repositories.len();
 
         if repo_count == 0 {
-            println!("{} No repositories to reset", style("ℹ️").blue());
+            display_println!("{} No repositories to reset", style("ℹ️").blue());
             return Ok(());
         }
 
         if !force {
-            println!(
+            display_println!(
                 "{} This will remove all {} tracked repositories from your configuration",
                 style("⚠️").yellow(),
                 style(repo_count).bold()
             );
-            println!(
+            display_println!(
                 "{} This will NOT delete the actual repository folders",
                 style("ℹ️").blue()
             );
-            println!();
+            display_println!();
 
             // Show repositories that will be removed
-            println!(
+            display_println!(
                 "{} Repositories to be removed from config:",
                 style("📋").blue()
             );
             for repo in &self.config.repositories {
-                println!(
+                display_println!(
                     "  {} {} ({})",
                     style("→").dim(),
                     style(&repo.name).cyan(),
                     style(repo.path.display()).dim()
                 );
             }
-            println!();
-
-            use inquire::Confirm;
-            let confirm = Confirm::new("Continue with repository reset?")
-                .with_default(false)
-                .prompt()
-                .context("Failed to get user confirmation")?;
+            display_println!();
 
-            if !confirm {
-                println!("{} Repository reset cancelled", style("✓").green());
+            let level = self.get_confirmation_level();
+            let preview = self.preview_repository_reset();
+            let reviewed =
+                level == ConfirmationLevel::Minimal || offer_full_details(|| preview.print())?;
+            let action = DestructiveAction::new("Continue with repository reset?");
+            if !reviewed || !confirm_destructive(action, level)? {
+                display_println!("{} Repository reset cancelled", style("✓").green());
                 return Ok(());
             }
         }
 
         // Clear repositories from config
-        self.config.repositories.clear();
+        let cleared: Vec<String> = self
+            .config
+            .repositories
+            .drain(..)
+            .map(|repo| repo.name)
+            .collect();
 
         // Save the updated config
         self.config
@@ -2088,12 +4579,26 @@ impl WorkspaceManager {
             .await
             .context("Failed to save updated configuration")?;
 
-        println!(
+        // Drop what the caches hold about the cleared repositories
+        if let Some(cache) = &self.repo_cache {
+            if let Err(e) = cache.cleanup_stale_entries(&[]).await {
+                debug!("Failed to clear repository cache: {}", e);
+            }
+        }
+        if let Some(cache) = &self.git_cache {
+            for name in &cleared {
+                if let Err(e) = cache.invalidate_repository(name).await {
+                    debug!("Failed to clear cached status of {}: {}", name, e);
+                }
+            }
+        }
+
+        display_println!(
             "{} Cleared {} repositories from configuration",
             style("✅").green().bold(),
             style(repo_count).bold()
         );
-        println!(
+        display_println!(
             "{} Use 'vibe git scan --import' to re-discover repositories",
             style("💡").blue()
         );
@@ -2126,13 +4631,8 @@ impl WorkspaceManager {
                 display_println!();
             }
 
-            // Require typing exact confirmation
-            use inquire::Text;
-            let confirmation = Text::new("Type 'reset my vibe' to confirm factory reset:")
-                .prompt()
-                .context("Failed to get user confirmation")?;
-
-            if confirmation != "reset my vibe" {
+            let preview = self.preview_factory_reset().await?;
+            if !offer_full_details(|| preview.print())? {
                 display_println!(
                     "{} Vibe Check: make sure you're ready for irreversable change and try again",
                     style("🔍").yellow()
@@ -2140,18 +4640,21 @@ impl WorkspaceManager {
                 return Ok(());
             }
 
-            // Final confirmation (only if not skipped)
-            if !skip_final_confirmation {
-                use inquire::Confirm;
-                let final_confirm = Confirm::new("Are you absolutely sure? This cannot be undone.")
-                    .with_default(false)
-                    .prompt()
-                    .context("Failed to get final confirmation")?;
+            // The typed phrase is required at every confirmation level
+            let level = if skip_final_confirmation {
+                ConfirmationLevel::Minimal
+            } else {
+                self.get_confirmation_level()
+            };
+            let action = DestructiveAction::new("Permanently delete all vibe configuration?")
+                .with_phrase("reset my vibe");
 
-                if !final_confirm {
-                    display_println!("{} Vibe Check: make sure you're ready for irreversable change and try again", style("🔍").yellow());
-                    return Ok(());
-                }
+            if !confirm_destructive(action, level)? {
+                display_println!(
+                    "{} Vibe Check: make sure you're ready for irreversable change and try again",
+                    style("🔍").yellow()
+                );
+                return Ok(());
             }
         }
 
@@ -2174,8 +4677,7 @@ impl WorkspaceManager {
         }
 
         // Delete templates directory
-        let vibe_dir = super::constants::get_config_dir();
-        let templates_dir = vibe_dir.join("templates");
+        let templates_dir = super::constants::get_templates_dir();
         if templates_dir.exists() {
             tokio::fs::remove_dir_all(&templates_dir)
                 .await
@@ -2189,7 +4691,7 @@ impl WorkspaceManager {
         }
 
         // Delete cache directory
-        let cache_dir = vibe_dir.join("cache");
+        let cache_dir = super::constants::get_cache_dir();
         if cache_dir.exists() {
             tokio::fs::remove_dir_all(&cache_dir)
                 .await
@@ -2200,7 +4702,7 @@ impl WorkspaceManager {
         }
 
         // Delete state.json file
-        let state_file = vibe_dir.join("state.json");
+        let state_file = super::constants::get_state_file_path();
         if state_file.exists() {
             tokio::fs::remove_file(&state_file).await.with_context(|| {
                 format!("Failed to remove state file: {}", state_file.display())
@@ -2208,6 +4710,31 @@ impl WorkspaceManager {
             display_println!("{} Removed state file", style("✓").green());
         }
 
+        // Trash items can still be restored, so they are only deleted after
+        // a question of their own, whatever the confirmation level
+        let trash = trash::Trash::open_default();
+        let items = trash.list().await.unwrap_or_default();
+        if !items.is_empty() {
+            let prompt = format!(
+                "Also empty the trash? {} removed repositories and worktrees in it could still be restored",
+                items.len()
+            );
+            let level = match self.get_confirmation_level() {
+                ConfirmationLevel::Minimal => ConfirmationLevel::Normal,
+                level => level,
+            };
+            if !force && confirm_destructive(DestructiveAction::new(&prompt), level)? {
+                trash.empty(None).await?;
+                display_println!("{} Emptied the trash", style("✓").green());
+            } else {
+                display_println!(
+                    "{} Kept {} items in the trash; 'vibe trash empty' deletes them",
+                    style("ℹ️").blue(),
+                    items.len()
+                );
+            }
+        }
+
         display_println!("{} Factory reset completed", style("✅").green().bold());
         display_println!();
         display_println!(
@@ -2227,6 +4754,18 @@ impl WorkspaceManager {
         self.config.get_repository(name)
     }
 
+    /// What `vibe open` opens for `name`: a repository, or a view of a
+    /// monorepo subproject named `<repo>/<subproject>` or `<repo>:<subproject>`
+    pub fn get_open_target(&self, name: &str) -> Option<Cow<'_, Repository>> {
+        if let Some(repo) = self.config.get_repository(name) {
+            return Some(Cow::Borrowed(repo));
+        }
+        if let Some((repo, subproject)) = self.config.find_subproject(name) {
+            return Some(Cow::Owned(repo.subproject_view(subproject)));
+        }
+        self.config.get_repository_flexible(name).map(Cow::Borrowed)
+    }
+
     /// Get a repository by flexible name lookup (supports owner/repo format)
     pub fn get_repository_flexible(&self, name: &str) -> Option<&Repository> {
         self.config.get_repository_flexible(name)
@@ -2237,96 +4776,30 @@ impl WorkspaceManager {
         &self.config.repositories
     }
 
-    /// Remove a repository from the workspace
-    pub async fn remove_repository(&mut self, name: &str) -> Result<()> {
+    /// Remove a repository from the workspace, putting its entry in the
+    /// trash; returns the trash id
+    pub async fn remove_repository(&mut self, name: &str) -> Result<Option<String>> {
+        let removed = self.get_repository(name).cloned();
         self.config.repositories.retain(|r| r.name != name);
         self.save_config().await?;
-        Ok(())
+        let Some(repository) = removed else {
+            return Ok(None);
+        };
+        Ok(trash::record_removal(
+            None,
+            name,
+            EventKind::RepoRemoved,
+            format!("Removed repository {name} from the workspace"),
+            UndoAction::RestoreRepository {
+                repository: Box::new(repository),
+            },
+        )
+        .await)
     }
 
     /// Check if an app is available on the system
     pub async fn is_app_available(&self, app_name: &str) -> bool {
-        match app_name {
-            "vscode" => {
-                // Check if VS Code is available
-                tokio::process::Command::new("code")
-                    .arg("--version")
-                    .output()
-                    .await
-                    .map(|output| output.status.success())
-                    .unwrap_or(false)
-            }
-            "warp" => {
-                // Check if Warp is available
-                #[cfg(target_os = "macos")]
-                {
-                    tokio::fs::metadata("/Applications/Warp.app").await.is_ok()
-                }
-                #[cfg(not(target_os = "macos"))]
-                {
-                    false
-                }
-            }
-            "iterm2" => {
-                // Check if iTerm2 is available
-                #[cfg(target_os = "macos")]
-                {
-                    tokio::fs::metadata("/Applications/iTerm.app").await.is_ok()
-                }
-                #[cfg(not(target_os = "macos"))]
-                {
-                    false
-                }
-            }
-            "wezterm" => {
-                // Check if WezTerm is available
-                tokio::process::Command::new("wezterm")
-                    .arg("--version")
-                    .output()
-                    .await
-                    .map(|output| output.status.success())
-                    .unwrap_or(false)
-            }
-            "cursor" => {
-                // Check if Cursor is available
-                #[cfg(target_os = "macos")]
-                {
-                    tokio::fs::metadata("/Applications/Cursor.app")
-                        .await
-                        .is_ok()
-                }
-                #[cfg(not(target_os = "macos"))]
-                {
-                    // Try command line for other platforms
-                    tokio::process::Command::new("cursor")
-                        .arg("--version")
-                        .output()
-                        .await
-                        .map(|output| output.status.success())
-                        .unwrap_or(false)
-                }
-            }
-            "windsurf" => {
-                // Check if Windsurf is available
-                #[cfg(target_os = "macos")]
-                {
-                    tokio::fs::metadata("/Applications/Windsurf.app")
-                        .await
-                        .is_ok()
-                }
-                #[cfg(not(target_os = "macos"))]
-                {
-                    // Try command line for other platforms
-                    tokio::process::Command::new("windsurf")
-                        .arg("--version")
-                        .output()
-                        .await
-                        .map(|output| output.status.success())
-                        .unwrap_or(false)
-                }
-            }
-            _ => false,
-        }
+        crate::apps::is_app_available(app_name).await
     }
 
     /// Get all available apps on the system
@@ -2404,7 +4877,10 @@ impl WorkspaceManager {
     async fn init_repository_cache(cache_dir: &Path) -> Result<RepositoryCache> {
         tokio::fs::create_dir_all(cache_dir).await?;
         let repo_cache = RepositoryCache::new(cache_dir.join("repositories.db"));
-        repo_cache.initialize().await?;
+        repo_cache
+            .initialize()
+            .instrument(timings::phase(timings::CACHE, "repositories.db"))
+            .await?;
         Ok(repo_cache)
     }
 
@@ -2412,15 +4888,17 @@ impl WorkspaceManager {
     async fn init_git_status_cache(cache_dir: &Path) -> Result<GitStatusCache> {
         tokio::fs::create_dir_all(cache_dir).await?;
         let git_cache = GitStatusCache::new(cache_dir.join("git_status.db"));
-        git_cache.initialize().await?;
+        git_cache
+            .initialize()
+            .instrument(timings::phase(timings::CACHE, "git_status.db"))
+            .await?;
         Ok(git_cache)
     }
 
     /// Get repository cache (lazy initialization if needed)
     pub async fn get_repository_cache(&mut self) -> Result<&RepositoryCache> {
         if self.repo_cache.is_none() {
-            let vibe_dir = super::constants::get_config_dir();
-            let cache_dir = vibe_dir.join("cache");
+            let cache_dir = super::constants::get_cache_dir();
             self.repo_cache = Some(Self::init_repository_cache(&cache_dir).await?);
         }
         Ok(self.repo_cache.as_ref().unwrap())
@@ -2429,8 +4907,7 @@ impl WorkspaceManager {
     /// Get git status cache (lazy initialization if needed)
     pub async fn get_git_status_cache(&mut self) -> Result<&GitStatusCache> {
         if self.git_cache.is_none() {
-            let vibe_dir = super::constants::get_config_dir();
-            let cache_dir = vibe_dir.join("cache");
+            let cache_dir = super::constants::get_cache_dir();
             self.git_cache = Some(Self::init_git_status_cache(&cache_dir).await?);
         }
         Ok(self.git_cache.as_ref().unwrap())
@@ -2452,42 +4929,118 @@ impl WorkspaceManager {
         Ok(())
     }
 
-    /// Update git status cache for repositories (background operation)
-    pub async fn update_git_status_cache(&mut self, repo_names: &[String]) -> Result<()> {
-        // Clone data to avoid borrowing issues
-        let repositories = self.config.repositories.clone();
-        let workspace_root = self.config.workspace.root.clone();
+    /// Get quick launcher for fast repository selection
+    pub async fn get_quick_launcher(&self) -> Result<crate::ui::quick_launcher::QuickLauncher> {
+        let cache_dir = super::constants::get_cache_dir();
+        crate::ui::quick_launcher::QuickLauncher::new(&cache_dir).await
+    }
 
-        if let Ok(cache) = self.get_git_status_cache().await {
-            for repo_name in repo_names {
-                if let Some(repo_config) = repositories.iter().find(|r| r.name == *repo_name) {
-                    let repo_path = workspace_root.join(&repo_config.path);
+    // Page size access methods
 
-                    match get_git_status(&repo_path).await {
-                        Ok(git_status) => {
-                            let cached_status = git_status.into();
-                            if let Err(e) = cache.cache_git_status(&cached_status).await {
-                                warn!("Failed to cache git status for {}: {}", repo_name, e);
-                            }
-                        }
-                        Err(e) => {
-                            warn!("Failed to get git status for {}: {}", repo_name, e);
-                        }
-                    }
-                }
-            }
+    /// Maximum number of repositories whose git status is read at once, one
+    /// at a time in deterministic mode
+    pub fn get_max_parallel_git(&self) -> usize {
+        if output::is_deterministic() {
+            return 1;
         }
-        Ok(())
+        self.config
+            .preferences
+            .as_ref()
+            .and_then(|p| p.max_parallel_git)
+            .filter(|&n| n > 0)
+            .unwrap_or_else(default_max_parallel_git)
     }
 
-    /// Get quick launcher for fast repository selection
-    pub async fn get_quick_launcher(&self) -> Result<crate::ui::quick_launcher::QuickLauncher> {
-        let vibe_dir = super::constants::get_config_dir();
-        let cache_dir = vibe_dir.join("cache");
-        crate::ui::quick_launcher::QuickLauncher::new(&cache_dir).await
+    /// What to read the git status of for `repo`, counting ahead/behind
+    /// against its `compare_ref` when it has one
+    pub fn status_target(&self, repo: &Repository) -> StatusTarget {
+        StatusTarget::new(repo.name.clone(), repo_abs_path(&self.config, repo))
+            .comparing_to(repo.compare_ref.clone())
     }
 
-    // Page size access methods
+    /// How `vibe git status` buckets repositories without `--group-by`
+    pub fn status_group_by(&self) -> StatusGroupBy {
+        self.config
+            .preferences
+            .as_ref()
+            .map(|p| p.status_group_by)
+            .unwrap_or_default()
+    }
+
+    /// Clone settings, with the layout from `preferences.clone_layout`
+    pub fn git_config(&self) -> GitConfig {
+        let preferences = self.config.preferences.as_ref();
+        GitConfig {
+            clone_layout: preferences.map(|p| p.clone_layout).unwrap_or_default(),
+            lfs_auto_pull: preferences.is_some_and(|p| p.lfs_auto_pull),
+            ..GitConfig::default()
+        }
+    }
+
+    /// Whether `vibe://clone` links may clone from `host`: github.com and
+    /// the hosts listed in `preferences.clone_link_hosts`
+    pub fn allows_clone_link_host(&self, host: &str) -> bool {
+        host.eq_ignore_ascii_case("github.com")
+            || self.config.preferences.as_ref().is_some_and(|p| {
+                p.clone_link_hosts
+                    .iter()
+                    .any(|allowed| allowed.eq_ignore_ascii_case(host))
+            })
+    }
+
+    /// Which backend answers git status and worktree queries
+    pub fn get_git_backend(&self) -> GitBackendKind {
+        self.config
+            .preferences
+            .as_ref()
+            .map(|p| p.git_backend)
+            .unwrap_or_default()
+    }
+
+    /// Whether launched commands load `.envrc` files through direnv
+    pub fn is_direnv_enabled(&self) -> bool {
+        self.config
+            .preferences
+            .as_ref()
+            .map(|p| p.enable_direnv)
+            .unwrap_or_default()
+    }
+
+    /// Whether new worktrees trust their `.envrc` automatically
+    pub fn is_direnv_auto_allow_enabled(&self) -> bool {
+        self.config
+            .preferences
+            .as_ref()
+            .map(|p| p.auto_allow_direnv)
+            .unwrap_or_default()
+    }
+
+    /// Whether opening a repository also fetches it in the background
+    pub fn is_fetch_on_open_enabled(&self) -> bool {
+        self.config
+            .preferences
+            .as_ref()
+            .map(|p| p.fetch_on_open)
+            .unwrap_or_default()
+    }
+
+    /// Icon and color theme for display output
+    pub fn get_theme_preferences(&self) -> ThemePreferences {
+        self.config
+            .preferences
+            .as_ref()
+            .map(|p| p.theme.clone())
+            .unwrap_or_default()
+    }
+
+    /// Confirmation level for destructive operations
+    pub fn get_confirmation_level(&self) -> ConfirmationLevel {
+        self.config
+            .preferences
+            .as_ref()
+            .map(|p| p.confirmations)
+            .unwrap_or_default()
+    }
 
     /// Get page size for main menu
     pub fn get_main_menu_page_size(&self) -> usize {
@@ -2556,10 +5109,7 @@ impl WorkspaceManager {
 
     /// List available backup files in the default backup directory
     pub async fn list_available_backups(&self) -> Result<Vec<BackupInfo>> {
-        let backup_dir = dirs::home_dir()
-            .unwrap_or_else(|| PathBuf::from("."))
-            .join(super::constants::CONFIG_DIR_PATH)
-            .join("backups");
+        let backup_dir = super::constants::get_backups_dir();
 
         if !backup_dir.exists() {
             return Ok(Vec::new());
@@ -2570,27 +5120,31 @@ impl WorkspaceManager {
 
         while let Some(entry) = entries.next_entry().await? {
             let path = entry.path();
-            if let Some(extension) = path.extension() {
-                if extension == "tgz" {
-                    let metadata = entry.metadata().await?;
-                    let file_name = path
-                        .file_name()
-                        .unwrap_or_default()
-                        .to_string_lossy()
-                        .to_string();
-
-                    // Analyze backup contents (optional, for display purposes)
-                    let contents = self.analyze_backup(&path).await.ok();
-
-                    backups.push(BackupInfo {
-                        file_name: file_name.clone(),
-                        path: path.clone(),
-                        size: metadata.len(),
-                        created: metadata.created().unwrap_or(std::time::UNIX_EPOCH),
-                        display_name: self.format_backup_display_name(&file_name),
-                        contents,
-                    });
-                }
+            if backup_crypto::is_backup(&path) {
+                let metadata = entry.metadata().await?;
+                let file_name = path
+                    .file_name()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+                    .to_string();
+                let encrypted = backup_crypto::is_encrypted(&path);
+
+                // Analyze backup contents (optional, for display purposes)
+                let contents = if encrypted {
+                    None
+                } else {
+                    self.analyze_backup(&path).await.ok()
+                };
+
+                backups.push(BackupInfo {
+                    file_name: file_name.clone(),
+                    path: path.clone(),
+                    size: metadata.len(),
+                    created: metadata.created().unwrap_or(std::time::UNIX_EPOCH),
+                    display_name: self.format_backup_display_name(&file_name),
+                    encrypted,
+                    contents,
+                });
             }
         }
 
@@ -2601,8 +5155,8 @@ impl WorkspaceManager {
 
     /// Format backup file name for display
     fn format_backup_display_name(&self, file_name: &str) -> String {
-        // Remove .tgz extension and format timestamp
-        let name_without_ext = file_name.strip_suffix(".tgz").unwrap_or(file_name);
+        // Remove the archive extension and format timestamp
+        let name_without_ext = backup_crypto::backup_stem(file_name);
 
         if let Some(timestamp_part) = name_without_ext.strip_prefix("vibe-backup-") {
             if let Ok(parsed) =
@@ -2653,37 +5207,49 @@ impl WorkspaceManager {
             anyhow::bail!("Backup file does not exist: {}", backup_file.display());
         }
 
+        // Encrypted backups are decrypted up front, so a wrong passphrase
+        // stops the restore before anything is reset
+        let decrypted = if backup_crypto::is_encrypted(&backup_file) {
+            let passphrase = backup_crypto::passphrase_for_decryption(&backup_file)?;
+            Some(backup_crypto::decrypt_to_temp(&backup_file, passphrase).await?)
+        } else {
+            None
+        };
+        let archive = decrypted
+            .as_ref()
+            .map_or(backup_file.as_path(), |decrypted| decrypted.path());
+
         // Analyze backup contents
-        let backup_contents = self.analyze_backup(&backup_file).await?;
+        let backup_contents = self.analyze_backup(archive).await?;
 
         if !force {
             self.confirm_restore(&backup_file, &backup_contents).await?;
         }
 
-        println!("{} Starting restore process...", style("🔄").blue());
+        display_println!("{} Starting restore process...", style("🔄").blue());
 
         // Perform factory reset first
-        println!(
+        display_println!(
             "{} Clearing existing configuration...",
             style("🗑️").yellow()
         );
         self.factory_reset_with_options(true, true).await?;
 
         // Extract and restore backup
-        self.extract_backup(&backup_file).await?;
+        self.extract_backup(archive).await?;
 
         // Reinitialize caches
-        println!("{} Rebuilding cache databases...", style("🔄").blue());
+        display_println!("{} Rebuilding cache databases...", style("🔄").blue());
         self.reinitialize_caches().await?;
 
         // Reload configuration
         self.config = WorkspaceConfig::load_from_file(&self.config_path).await?;
 
-        println!(
+        display_println!(
             "{} Restore completed successfully!",
             style("✅").green().bold()
         );
-        println!(
+        display_println!(
             "{} Run 'vibe menu' to continue using Vibe Workspace",
             style("💡").blue()
         );
@@ -2696,16 +5262,22 @@ impl WorkspaceManager {
         let backups = self.list_available_backups().await?;
 
         if backups.is_empty() {
-            anyhow::bail!("No backup files found in ~/.toolprint/vibe-workspace/backups/");
+            anyhow::bail!(
+                "No backup files found in {}",
+                super::constants::display_path(&super::constants::get_backups_dir())
+            );
         }
 
-        println!("\n{} Available backups:", style("📦").blue());
+        display_println!("\n{} Available backups:", style("📦").blue());
 
         let backup_options: Vec<String> = backups
             .iter()
             .map(|backup| {
                 let size_str = Self::format_file_size(backup.size);
                 let mut details = vec![size_str];
+                if backup.encrypted {
+                    details.push("encrypted".to_string());
+                }
 
                 if let Some(contents) = &backup.contents {
                     let mut content_parts = Vec::new();
@@ -2744,6 +5316,9 @@ impl WorkspaceManager {
             .find(|backup| {
                 let size_str = Self::format_file_size(backup.size);
                 let mut details = vec![size_str];
+                if backup.encrypted {
+                    details.push("encrypted".to_string());
+                }
 
                 if let Some(contents) = &backup.contents {
                     let mut content_parts = Vec::new();
@@ -2776,13 +5351,12 @@ impl WorkspaceManager {
 
     /// Analyze backup contents
     async fn analyze_backup(&self, backup_path: &Path) -> Result<BackupContents> {
-        use std::process::Command;
-
         // List contents of the tar file
         let output = Command::new("tar")
             .args(["-tzf"])
             .arg(backup_path)
             .output()
+            .await
             .context("Failed to analyze backup archive")?;
 
         if !output.status.success() {
@@ -2823,14 +5397,12 @@ impl WorkspaceManager {
 
     /// Confirm restore operation with user
     async fn confirm_restore(&self, backup_path: &Path, contents: &BackupContents) -> Result<()> {
-        use inquire::Confirm;
-
-        println!(
+        display_println!(
             "\n{} {}",
             style("⚠️  RESTORE CONFIRMATION").yellow().bold(),
             style("This will replace ALL current configuration!").yellow()
         );
-        println!();
+        display_println!();
 
         // Get backup file size
         let backup_size = if let Ok(metadata) = std::fs::metadata(backup_path) {
@@ -2839,28 +5411,28 @@ impl WorkspaceManager {
             "unknown".to_string()
         };
 
-        println!(
+        display_println!(
             "{} Backup file: {} ({})",
             style("📦").blue(),
             backup_path.display(),
             backup_size
         );
-        println!("{} Backup contains:", style("📋").blue());
+        display_println!("{} Backup contains:", style("📋").blue());
 
         if contents.has_config {
-            println!("  {} Main configuration (config.yaml)", style("✓").green());
+            display_println!("  {} Main configuration (config.yaml)", style("✓").green());
         }
         if contents.has_state {
-            println!(
+            display_println!(
                 "  {} User state and preferences (state.json)",
                 style("✓").green()
             );
         }
         if contents.has_templates {
-            println!("  {} Template files", style("✓").green());
+            display_println!("  {} Template files", style("✓").green());
         }
         if !contents.app_configs.is_empty() {
-            println!(
+            display_println!(
                 "  {} App configurations: {}",
                 style("✓").green(),
                 contents.app_configs.join(", ")
@@ -2869,34 +5441,31 @@ impl WorkspaceManager {
 
         // Show what's missing from backup (if anything)
         if !contents.has_config {
-            println!("  {} Main configuration (missing)", style("⚠️").yellow());
+            display_println!("  {} Main configuration (missing)", style("⚠️").yellow());
         }
         if !contents.has_state {
-            println!(
+            display_println!(
                 "  {} User state (missing - will use defaults)",
                 style("ℹ️").blue()
             );
         }
 
-        println!(
+        display_println!(
             "  {} Total files: {}",
             style("📊").blue(),
             contents.total_files
         );
-        println!();
-
-        println!("{} This will:", style("⚠️").yellow());
-        println!("  • Delete all current configuration");
-        println!("  • Delete all app-generated files");
-        println!("  • Restore configuration from backup");
-        println!("  • Rebuild cache databases");
-        println!();
+        display_println!();
 
-        let confirm = Confirm::new("Are you sure you want to proceed with the restore?")
-            .with_default(false)
-            .prompt()?;
+        display_println!("{} This will:", style("⚠️").yellow());
+        display_println!("  • Delete all current configuration");
+        display_println!("  • Delete all app-generated files");
+        display_println!("  • Restore configuration from backup");
+        display_println!("  • Rebuild cache databases");
+        display_println!();
 
-        if !confirm {
+        let action = DestructiveAction::new("Are you sure you want to proceed with the restore?");
+        if !confirm_destructive(action, self.get_confirmation_level())? {
             anyhow::bail!("Restore cancelled by user");
         }
 
@@ -2905,20 +5474,19 @@ impl WorkspaceManager {
 
     /// Extract backup archive
     async fn extract_backup(&self, backup_path: &Path) -> Result<()> {
-        use std::process::Command;
-
         // Create temporary extraction directory
         let temp_dir = tempfile::tempdir().context("Failed to create temporary directory")?;
         let temp_path = temp_dir.path();
 
         // Extract archive
-        println!("{} Extracting backup archive...", style("📦").blue());
+        display_println!("{} Extracting backup archive...", style("📦").blue());
         let output = Command::new("tar")
             .args(["-xzf"])
             .arg(backup_path)
             .args(["-C"])
             .arg(temp_path)
             .output()
+            .await
             .context("Failed to extract backup archive")?;
 
         if !output.status.success() {
@@ -2927,33 +5495,36 @@ impl WorkspaceManager {
         }
 
         // Copy files to their proper locations
-        let vibe_dir = super::constants::get_config_dir();
-        tokio::fs::create_dir_all(&vibe_dir).await?;
-
         // Copy main config file
         let config_src = temp_path.join("config.yaml");
         if config_src.exists() {
+            if let Some(parent) = self.config_path.parent() {
+                tokio::fs::create_dir_all(parent).await?;
+            }
             tokio::fs::copy(&config_src, &self.config_path).await?;
-            println!("{} Restored main configuration", style("✓").green());
+            display_println!("{} Restored main configuration", style("✓").green());
         }
 
         // Copy state file
         let state_src = temp_path.join("state.json");
-        let state_dest = vibe_dir.join("state.json");
+        let state_dest = super::constants::get_state_file_path();
         if state_src.exists() {
+            if let Some(parent) = state_dest.parent() {
+                tokio::fs::create_dir_all(parent).await?;
+            }
             tokio::fs::copy(&state_src, &state_dest).await?;
-            println!("{} Restored user state", style("✓").green());
+            display_println!("{} Restored user state", style("✓").green());
         }
 
         // Copy templates directory
         let templates_src = temp_path.join("templates");
-        let templates_dest = vibe_dir.join("templates");
+        let templates_dest = super::constants::get_templates_dir();
         if templates_src.exists() {
             if templates_dest.exists() {
                 tokio::fs::remove_dir_all(&templates_dest).await?;
             }
-            copy_dir_recursive(&templates_src, &templates_dest)?;
-            println!("{} Restored templates", style("✓").green());
+            copy_dir(&templates_src, &templates_dest).await?;
+            display_println!("{} Restored templates", style("✓").green());
         }
 
         // Copy app configuration files
@@ -2987,8 +5558,8 @@ impl WorkspaceManager {
 
             if let Some(dest) = dest_dir {
                 tokio::fs::create_dir_all(dest).await?;
-                copy_dir_recursive(&app_dir, dest)?;
-                println!(
+                copy_dir(&app_dir, dest).await?;
+                display_println!(
                     "{} Restored {} configurations",
                     style("✓").green(),
                     app_type
@@ -3001,8 +5572,7 @@ impl WorkspaceManager {
 
     /// Reinitialize cache databases after restore
     async fn reinitialize_caches(&mut self) -> Result<()> {
-        let vibe_dir = super::constants::get_config_dir();
-        let cache_dir = vibe_dir.join("cache");
+        let cache_dir = super::constants::get_cache_dir();
 
         // Remove existing cache files
         if cache_dir.exists() {
@@ -3023,11 +5593,213 @@ impl WorkspaceManager {
                 .await?;
         }
 
-        println!("{} Cache databases rebuilt", style("✓").green());
+        display_println!("{} Cache databases rebuilt", style("✓").green());
         Ok(())
     }
 }
 
+/// Files to archive for a configuration backup, from
+/// [`WorkspaceManager::plan_backup`]
+#[derive(Debug, Clone)]
+pub struct BackupPlan {
+    pub backup_path: PathBuf,
+    backup_dir: PathBuf,
+    config_path: PathBuf,
+    config_files: Vec<PathBuf>,
+    passphrase: Option<SecretString>,
+}
+
+impl BackupPlan {
+    /// Encrypt the archive with `passphrase`, naming it `.tgz.age`
+    pub fn encrypted(mut self, passphrase: SecretString) -> Self {
+        let file_name = self
+            .backup_path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        self.backup_path.set_file_name(format!(
+            "{}{}",
+            backup_crypto::backup_stem(&file_name),
+            backup_crypto::ENCRYPTED_SUFFIX
+        ));
+        self.passphrase = Some(passphrase);
+        self
+    }
+
+    /// Copy the configuration files into an archive at `backup_path`
+    pub async fn write(self) -> Result<PathBuf> {
+        let BackupPlan {
+            backup_path,
+            backup_dir,
+            config_path,
+            config_files,
+            passphrase,
+        } = self;
+
+        // Create backup directory if it doesn't exist
+        tokio::fs::create_dir_all(&backup_dir)
+            .await
+            .with_context(|| {
+                format!(
+                    "Failed to create backup directory: {}",
+                    backup_dir.display()
+                )
+            })?;
+
+        display_println!("{} Creating backup archive...", style("📦").blue());
+
+        if config_files.is_empty() {
+            display_println!(
+                "{} No configuration files found to backup",
+                style("⚠️").yellow()
+            );
+            return Ok(backup_path);
+        }
+
+        // Create temporary directory for organizing backup content
+        let temp_dir = tempfile::tempdir().context("Failed to create temporary directory")?;
+        let temp_path = temp_dir.path();
+
+        // Copy files to temporary directory with organized structure
+        for config_file in &config_files {
+            let file_name = config_file
+                .file_name()
+                .context("Invalid file name")?
+                .to_string_lossy();
+
+            if config_file == &config_path {
+                // Main config file goes to root
+                let dest_path = temp_path.join("config.yaml");
+                tokio::fs::copy(config_file, &dest_path)
+                    .await
+                    .with_context(|| format!("Failed to copy {}", config_file.display()))?;
+            } else if file_name == "state.json" {
+                // State file goes to root
+                let dest_path = temp_path.join("state.json");
+                tokio::fs::copy(config_file, &dest_path)
+                    .await
+                    .with_context(|| format!("Failed to copy {}", config_file.display()))?;
+            } else if config_file.to_string_lossy().contains("templates") {
+                // Templates directory
+                let dest_dir = temp_path.join("templates");
+                tokio::fs::create_dir_all(&dest_dir).await?;
+                copy_dir(config_file, &dest_dir).await?;
+            } else {
+                // App config files - organize by app type
+                let app_type = if file_name.ends_with(".yaml") {
+                    "warp"
+                } else if file_name.ends_with(".json") && file_name != "state.json" {
+                    "iterm2"
+                } else if file_name.ends_with(".lua") {
+                    "wezterm"
+                } else if file_name.ends_with(".code-workspace") {
+                    "vscode"
+                } else {
+                    "other"
+                };
+
+                let app_dir = temp_path.join("app-configs").join(app_type);
+                tokio::fs::create_dir_all(&app_dir).await?;
+                let dest_path = app_dir.join(file_name.as_ref());
+                tokio::fs::copy(config_file, &dest_path)
+                    .await
+                    .with_context(|| format!("Failed to copy {}", config_file.display()))?;
+            }
+        }
+
+        // Create tar archive, outside the directory being archived when it
+        // still has to be encrypted
+        let plain_archive = match passphrase {
+            Some(_) => Some(
+                tempfile::Builder::new()
+                    .suffix(backup_crypto::PLAIN_SUFFIX)
+                    .tempfile()
+                    .context("Failed to create temporary file")?,
+            ),
+            None => None,
+        };
+        let tar_path = plain_archive
+            .as_ref()
+            .map_or(backup_path.as_path(), |archive| archive.path());
+        let tar_output = Command::new("tar")
+            .args(["-czf"])
+            .arg(tar_path)
+            .args(["-C"])
+            .arg(temp_path)
+            .arg(".")
+            .output()
+            .await
+            .context("Failed to execute tar command")?;
+
+        if !tar_output.status.success() {
+            let error_msg = String::from_utf8_lossy(&tar_output.stderr);
+            anyhow::bail!("Tar command failed: {}", error_msg);
+        }
+
+        if let Some(passphrase) = passphrase {
+            display_println!("{} Encrypting backup archive...", style("🔒").blue());
+            backup_crypto::encrypt_file(tar_path, &backup_path, passphrase).await?;
+        }
+
+        display_println!(
+            "{} Backup contains {} configuration files:",
+            style("📋").green(),
+            config_files.len()
+        );
+        for file in &config_files {
+            display_println!("  {} {}", style("→").dim(), style(file.display()).cyan());
+        }
+
+        Ok(backup_path)
+    }
+}
+
+/// Run [`copy_dir_recursive`] on the blocking thread pool
+pub(crate) async fn copy_dir(src: &Path, dst: &Path) -> Result<()> {
+    let (src, dst) = (src.to_path_buf(), dst.to_path_buf());
+    tokio::task::spawn_blocking(move || copy_dir_recursive(&src, &dst)).await?
+}
+
+/// Marker for repositories configured outside the workspace root
+fn outside_root_flag(config: &WorkspaceConfig, repo: &Repository) -> String {
+    if is_outside_root(config, repo) {
+        format!(" {}", style("[outside root]").magenta())
+    } else {
+        String::new()
+    }
+}
+
+/// The `Excluded from:` line of `repo` in `config show`, if it opts out of
+/// any bulk operation
+fn excluded_line(repo: &Repository) -> String {
+    if repo.exclude.is_empty() {
+        return String::new();
+    }
+    let operations: Vec<&str> = repo.exclude.iter().map(|op| op.as_str()).collect();
+    format!(
+        "\n  Excluded from: {}",
+        style(operations.join(", ")).yellow()
+    )
+}
+
+/// A configuration section as JSON or YAML, with secrets masked unless
+/// `show_secrets`
+fn render_config<T: Serialize>(value: &T, format: &str, show_secrets: bool) -> Result<String> {
+    if format == "json" {
+        let mut value = serde_json::to_value(value)?;
+        if !show_secrets {
+            redact::redact_value(&mut value);
+        }
+        Ok(serde_json::to_string_pretty(&value)?)
+    } else {
+        let mut value = serde_yaml::to_value(value)?;
+        if !show_secrets {
+            redact::redact_yaml(&mut value);
+        }
+        Ok(serde_yaml::to_string(&value)?)
+    }
+}
+
 // Helper function to recursively copy directories using std::fs
 fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<()> {
     use std::fs;
@@ -0,0 +1,503 @@
+//! Portable description of a workspace, for setting it up on another machine
+//!
+//! `vibe config export-manifest` writes every repository's name, remote,
+//! location, and configured apps, plus the groups, to a versioned YAML file.
+//! Locations are relative to the workspace root, or `~/`-relative for
+//! repositories kept elsewhere under the home directory; repositories outside
+//! both are left out, since their path means nothing on another machine.
+//! App-specific settings stay local, only the app and template are exported.
+//! Template packs recorded in the configuration are exported too.
+//!
+//! `vibe restore-workspace <manifest>` clones whatever is missing under the
+//! local workspace root and merges the repositories, apps, and groups into
+//! the configuration, and installs the template packs that aren't installed
+//! yet. Repositories already on disk are taken as they are, so an
+//! interrupted restore is finished by running it again.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use console::style;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Component, Path, PathBuf};
+
+use super::config::{
+    repo_abs_path, AppConfig, Repository, RepositoryGroup, TemplatePackSource, WorkspaceConfig,
+};
+use super::sync_operations::{clone_in_parallel, PlannedRestore, SyncFailure};
+use crate::display_println;
+use crate::git;
+use crate::output::exit::CommandError;
+use crate::utils::fs::{expand_tilde, to_forward_slashes};
+
+/// Manifest format written by this version; newer manifests are refused
+pub const MANIFEST_VERSION: u32 = 1;
+
+const MANIFEST_HEADER: &str =
+    "# vibe workspace manifest, restore it with `vibe restore-workspace <file>`\n";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspaceManifest {
+    pub version: u32,
+    /// Name of the exported workspace
+    pub workspace: String,
+    pub exported_at: DateTime<Utc>,
+    #[serde(default)]
+    pub repositories: Vec<ManifestRepository>,
+    #[serde(default)]
+    pub groups: Vec<ManifestGroup>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub template_packs: Vec<TemplatePackSource>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestRepository {
+    pub name: String,
+    /// Relative to the workspace root, or starting with `~/`
+    pub path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upstream: Option<String>,
+    /// Configured app to its template
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub apps: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestGroup {
+    pub name: String,
+    #[serde(default)]
+    pub repos: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_app: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_template: Option<String>,
+}
+
+/// A repository left out of the export or the restore, and why
+#[derive(Debug, Clone, Serialize)]
+pub struct ManifestSkip {
+    pub name: String,
+    pub reason: String,
+}
+
+impl WorkspaceManifest {
+    /// Describe `config`, with the repositories that couldn't be described
+    /// portably
+    pub fn from_config(config: &WorkspaceConfig) -> (Self, Vec<ManifestSkip>) {
+        let root = expand_tilde(&config.workspace.root);
+        let home = dirs::home_dir();
+        let mut repositories = Vec::new();
+        let mut skipped = Vec::new();
+
+        for repo in &config.repositories {
+            let abs_path = repo_abs_path(config, repo);
+            let path = match portable_path(&abs_path, &root, home.as_deref()) {
+                Some(path) => path,
+                None => {
+                    skipped.push(ManifestSkip {
+                        name: repo.name.clone(),
+                        reason: format!(
+                            "{} is outside the workspace root and home directory",
+                            abs_path.display()
+                        ),
+                    });
+                    continue;
+                }
+            };
+            let apps = repo
+                .apps
+                .iter()
+                .filter_map(|(app, app_config)| {
+                    let template = match app_config {
+                        AppConfig::Enabled(false) => return None,
+                        AppConfig::Enabled(true) => "default",
+                        AppConfig::WithTemplate { template }
+                        | AppConfig::WithConfig { template, .. } => template.as_str(),
+                    };
+                    Some((app.clone(), template.to_string()))
+                })
+                .collect();
+            repositories.push(ManifestRepository {
+                name: repo.name.clone(),
+                path,
+                url: repo.url.clone(),
+                upstream: repo.upstream.clone(),
+                apps,
+            });
+        }
+
+        let groups = config
+            .groups
+            .iter()
+            .map(|group| ManifestGroup {
+                name: group.name.clone(),
+                repos: group
+                    .repos
+                    .iter()
+                    .filter(|name| repositories.iter().any(|repo| &repo.name == *name))
+                    .cloned()
+                    .collect(),
+                default_app: group.default_app.clone(),
+                default_template: group.default_template.clone(),
+            })
+            .collect();
+
+        let manifest = Self {
+            version: MANIFEST_VERSION,
+            workspace: config.workspace.name.clone(),
+            exported_at: Utc::now(),
+            repositories,
+            groups,
+            template_packs: config.template_packs.clone(),
+        };
+        (manifest, skipped)
+    }
+
+    pub fn to_yaml(&self) -> Result<String> {
+        let yaml = serde_yaml::to_string(self).context("Failed to serialize manifest")?;
+        Ok(format!("{MANIFEST_HEADER}{yaml}"))
+    }
+
+    pub async fn save(&self, path: &Path) -> Result<()> {
+        tokio::fs::write(path, self.to_yaml()?)
+            .await
+            .with_context(|| format!("Failed to write manifest {}", path.display()))
+    }
+
+    pub fn parse(contents: &str) -> Result<Self> {
+        let manifest: Self = serde_yaml::from_str(contents)
+            .map_err(|e| CommandError::Usage(format!("Not a valid workspace manifest: {e}")))?;
+        if manifest.version > MANIFEST_VERSION {
+            return Err(CommandError::Usage(format!(
+                "Manifest version {} is newer than this vibe supports ({MANIFEST_VERSION}); upgrade vibe to restore it",
+                manifest.version
+            ))
+            .into());
+        }
+        Ok(manifest)
+    }
+
+    pub async fn load(path: &Path) -> Result<Self> {
+        let contents = tokio::fs::read_to_string(path)
+            .await
+            .with_context(|| format!("Failed to read manifest {}", path.display()))?;
+        Self::parse(&contents)
+    }
+}
+
+/// `abs_path` relative to `root`, or to the home directory as `~/...`
+fn portable_path(abs_path: &Path, root: &Path, home: Option<&Path>) -> Option<String> {
+    if let Ok(relative) = abs_path.strip_prefix(root) {
+        return Some(to_forward_slashes(relative));
+    }
+    let relative = abs_path.strip_prefix(home?).ok()?;
+    Some(format!("~/{}", to_forward_slashes(relative)))
+}
+
+/// Where a manifest path lands on this machine. Absolute paths and paths
+/// climbing out with `..` are refused, so a manifest can't write anywhere
+/// but the workspace root and home directory.
+fn local_path(path: &str, root: &Path) -> Option<PathBuf> {
+    let (base, relative) = match path.strip_prefix("~/") {
+        Some(relative) => (dirs::home_dir()?, relative),
+        None => (root.to_path_buf(), path),
+    };
+    let relative = Path::new(relative);
+    let portable = relative
+        .components()
+        .all(|component| matches!(component, Component::Normal(_)));
+    (portable && !relative.as_os_str().is_empty()).then(|| base.join(relative))
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ManifestRestoreReport {
+    pub cloned: Vec<String>,
+    /// Repositories that were already on disk
+    pub existing: Vec<String>,
+    pub skipped: Vec<ManifestSkip>,
+    pub failed: Vec<SyncFailure>,
+    /// Groups created or given new members
+    pub groups: Vec<String>,
+    /// Template packs installed
+    pub template_packs: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+impl ManifestRestoreReport {
+    pub fn print(&self) {
+        for skip in &self.skipped {
+            display_println!(
+                "  {} {}: {}",
+                style("⚠️").yellow(),
+                style(&skip.name).cyan(),
+                skip.reason
+            );
+        }
+        for warning in &self.warnings {
+            display_println!("  {} {}", style("⚠️").yellow(), warning);
+        }
+        if !self.groups.is_empty() {
+            display_println!(
+                "  {} Groups updated: {}",
+                style("👥").blue(),
+                self.groups.join(", ")
+            );
+        }
+        if !self.template_packs.is_empty() {
+            display_println!(
+                "  {} Template packs installed: {}",
+                style("📦").blue(),
+                self.template_packs.join(", ")
+            );
+        }
+        display_println!(
+            "{} Restore finished: {} cloned, {} already present, {} failed, {} skipped",
+            if self.failed.is_empty() {
+                style("✓").green().bold()
+            } else {
+                style("✗").red().bold()
+            },
+            self.cloned.len(),
+            self.existing.len(),
+            self.failed.len(),
+            self.skipped.len()
+        );
+        if !self.failed.is_empty() {
+            display_println!("   Run the same command again to retry the failed clones");
+        }
+    }
+}
+
+/// Clone what `manifest` lists but the disk lacks, then merge its
+/// repositories, apps, and groups into `config`. Repositories `config`
+/// already knows stay where they are; only failed or skipped ones are left
+/// out of `config`.
+pub async fn restore(
+    manifest: &WorkspaceManifest,
+    config: &mut WorkspaceConfig,
+) -> Result<ManifestRestoreReport> {
+    let root = expand_tilde(&config.workspace.root);
+    let preferences = config.preferences.clone().unwrap_or_default();
+    let mut report = ManifestRestoreReport::default();
+    let mut present = Vec::new();
+    let mut planned = Vec::new();
+
+    for entry in &manifest.repositories {
+        let target = match config.get_repository(&entry.name) {
+            Some(repo) => repo_abs_path(config, repo),
+            None => match local_path(&entry.path, &root) {
+                Some(target) => target,
+                None => {
+                    report.skipped.push(ManifestSkip {
+                        name: entry.name.clone(),
+                        reason: format!("path '{}' leaves the workspace", entry.path),
+                    });
+                    continue;
+                }
+            },
+        };
+
+        if target.join(".git").exists() {
+            report.existing.push(entry.name.clone());
+            present.push((entry, target));
+            continue;
+        }
+        let occupied = match tokio::fs::read_dir(&target).await {
+            Ok(mut entries) => entries.next_entry().await.ok().flatten().is_some(),
+            Err(_) => false,
+        };
+        let reason = match &entry.url {
+            _ if occupied => format!("{} exists and is not a git repository", target.display()),
+            None => "no remote URL in the manifest".to_string(),
+            Some(url) => {
+                planned.push(PlannedRestore {
+                    name: entry.name.clone(),
+                    url: match preferences.clone_protocol {
+                        Some(protocol) => protocol.apply(url),
+                        None => url.clone(),
+                    },
+                    target_path: target,
+                });
+                continue;
+            }
+        };
+        report.skipped.push(ManifestSkip {
+            name: entry.name.clone(),
+            reason,
+        });
+    }
+
+    if !planned.is_empty() {
+        display_println!(
+            "{} Cloning {} repositories into {}:",
+            style("🔄").blue(),
+            planned.len(),
+            root.display()
+        );
+        for (restore, result) in clone_in_parallel(planned, preferences.max_parallel_git).await? {
+            let entry = manifest
+                .repositories
+                .iter()
+                .find(|entry| entry.name == restore.name)
+                .expect("planned from the manifest");
+            match result {
+                Ok(()) => {
+                    git::lfs::check_after_clone(&restore.target_path, preferences.lfs_auto_pull)
+                        .await;
+                    report.cloned.push(restore.name);
+                    present.push((entry, restore.target_path));
+                }
+                Err(e) => report.failed.push(SyncFailure {
+                    name: restore.name,
+                    error: e.to_string(),
+                }),
+            }
+        }
+        display_println!();
+    }
+
+    for (entry, target) in present {
+        merge_repository(config, entry, &target, &root);
+    }
+    report.groups = merge_groups(config, &manifest.groups);
+    for pack in &manifest.template_packs {
+        if !config.template_packs.iter().any(|p| p.name == pack.name) {
+            config.template_packs.push(pack.clone());
+        }
+    }
+    Ok(report)
+}
+
+/// Add `entry` to `config`, or fill in what the existing entry lacks
+fn merge_repository(
+    config: &mut WorkspaceConfig,
+    entry: &ManifestRepository,
+    target: &Path,
+    root: &Path,
+) {
+    let mut repo = match config.get_repository(&entry.name) {
+        Some(repo) => repo.clone(),
+        None => Repository::new(&entry.name, target.strip_prefix(root).unwrap_or(target)),
+    };
+    if repo.url.is_none() {
+        repo.url = entry.url.clone();
+    }
+    if repo.upstream.is_none() {
+        repo.upstream = entry.upstream.clone();
+    }
+    for (app, template) in &entry.apps {
+        repo.apps
+            .entry(app.clone())
+            .or_insert_with(|| AppConfig::WithTemplate {
+                template: template.clone(),
+            });
+    }
+    config.add_repository(repo);
+}
+
+/// Create the manifest's groups, or add their members to existing ones, and
+/// return the names of the groups that changed. Members missing from
+/// `config` are left out until a later restore brings them in.
+fn merge_groups(config: &mut WorkspaceConfig, groups: &[ManifestGroup]) -> Vec<String> {
+    let mut changed = Vec::new();
+    for group in groups {
+        let members: Vec<String> = group
+            .repos
+            .iter()
+            .filter(|name| config.get_repository(name).is_some())
+            .cloned()
+            .collect();
+        let existing = config.groups.iter_mut().find(|g| g.name == group.name);
+        let updated = match existing {
+            Some(existing) => {
+                let mut updated = false;
+                for member in members {
+                    if !existing.repos.contains(&member) {
+                        existing.repos.push(member);
+                        updated = true;
+                    }
+                }
+                if existing.default_app.is_none() && group.default_app.is_some() {
+                    existing.default_app = group.default_app.clone();
+                    existing.default_template = group.default_template.clone();
+                    updated = true;
+                }
+                updated
+            }
+            None => {
+                config.add_group(RepositoryGroup {
+                    name: group.name.clone(),
+                    repos: members,
+                    apps: HashMap::new(),
+                    default_app: group.default_app.clone(),
+                    default_template: group.default_template.clone(),
+                });
+                true
+            }
+        };
+        if updated {
+            changed.push(group.name.clone());
+        }
+    }
+    changed
+}
+
+/// App and template pairs `manifest` refers to, for checking that the
+/// templates exist locally
+pub fn referenced_templates(manifest: &WorkspaceManifest) -> Vec<(String, String)> {
+    let mut pairs: Vec<(String, String)> = manifest
+        .repositories
+        .iter()
+        .flat_map(|repo| repo.apps.iter())
+        .map(|(app, template)| (app.clone(), template.clone()))
+        .chain(manifest.groups.iter().filter_map(|group| {
+            Some((group.default_app.clone()?, group.default_template.clone()?))
+        }))
+        .collect();
+    pairs.sort();
+    pairs.dedup();
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paths_stay_inside_root_or_home() {
+        let root = Path::new("/work");
+        let home = Path::new("/home/me");
+        assert_eq!(
+            portable_path(Path::new("/work/tools/web"), root, Some(home)).as_deref(),
+            Some("tools/web")
+        );
+        assert_eq!(
+            portable_path(Path::new("/home/me/notes"), root, Some(home)).as_deref(),
+            Some("~/notes")
+        );
+        assert_eq!(portable_path(Path::new("/opt/x"), root, Some(home)), None);
+
+        assert_eq!(
+            local_path("tools/web", root),
+            Some(PathBuf::from("/work/tools/web"))
+        );
+        for path in ["../escape", "/etc", "tools/../../x", ""] {
+            assert_eq!(local_path(path, root), None, "{path}");
+        }
+    }
+
+    #[test]
+    fn test_parse_refuses_newer_versions() {
+        let manifest = "version: 1\nworkspace: test\nexported_at: 2024-05-01T00:00:00Z\n\
+                        repositories:\n- name: api\n  path: api\n";
+        let manifest = WorkspaceManifest::parse(manifest).unwrap();
+        assert_eq!(manifest.repositories[0].name, "api");
+        assert!(manifest.groups.is_empty());
+
+        let newer = "version: 2\nworkspace: test\nexported_at: 2024-05-01T00:00:00Z\n";
+        let error = WorkspaceManifest::parse(newer).unwrap_err();
+        assert!(error.to_string().contains("newer than this vibe supports"));
+    }
+}
@@ -1,16 +1,44 @@
+pub mod activity;
+pub mod availability;
+pub mod backup_crypto;
 pub mod claude_agents;
 pub mod config;
+pub mod config_loader;
+pub mod config_sync;
 pub mod config_validator;
+pub mod config_watcher;
 pub mod constants;
+pub mod contributors;
 mod discovery;
+pub mod disk_usage;
+pub mod doctor;
+pub mod events;
+pub mod file_tree;
+pub mod health;
+pub mod history;
+pub mod home_migration;
 pub mod install;
+pub mod launch_stats;
+pub mod layouts;
+pub mod licenses;
 pub mod manager;
+pub mod manifest;
 pub mod operations;
+pub mod project_env;
+pub mod project_types;
+pub mod removed_repos;
 pub mod repo_analyzer;
+pub mod reset_preview;
+pub mod search;
+pub mod status_groups;
+pub mod status_snapshot;
+pub mod subprojects;
 mod sync_operations;
+pub mod template_packs;
 pub mod templates;
+pub mod trash;
 
-pub use config::{Repository, WorkspaceConfig};
+pub use config::{repo_abs_path, BulkOperation, Repository, WorkspaceConfig};
 
 // Test-only exports - these are only used by app module tests
 #[cfg(test)]
@@ -19,4 +47,5 @@ pub use config::{
     WindsurfIntegration, WorkspaceInfo,
 };
 pub use manager::{AppSelection, WorkspaceManager};
+pub use sync_operations::CleanMode;
 pub use templates::TemplateManager;
@@ -1,12 +1,19 @@
 use anyhow::{Context, Result};
 use colored::*;
-use git2::{Repository, StatusOptions};
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use tokio::process::Command as AsyncCommand;
-use tracing::{debug, warn};
+use tokio::sync::Semaphore;
+use tracing::{debug, Instrument};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+use crate::git::backend::{self, GitBackend};
+use crate::git::repo_lock;
+use crate::git::upstream;
+use crate::output::delimited::Tabular;
+use crate::output::{redact, timings};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct GitStatus {
     pub repository_name: String,
     pub path: String,
@@ -18,6 +25,30 @@ pub struct GitStatus {
     pub unstaged: usize,
     pub untracked: usize,
     pub remote_url: Option<String>,
+    /// For forks, commits the default branch is behind `upstream`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upstream_behind: Option<usize>,
+    /// The repository's `compare_ref`, when `ahead` and `behind` count
+    /// against it rather than the branch on `origin`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compare_ref: Option<String>,
+}
+
+impl Tabular for GitStatus {
+    const FIELDS: &'static [&'static str] = &[
+        "repository_name",
+        "path",
+        "branch",
+        "clean",
+        "ahead",
+        "behind",
+        "staged",
+        "unstaged",
+        "untracked",
+        "remote_url",
+        "upstream_behind",
+        "compare_ref",
+    ];
 }
 
 impl GitStatus {
@@ -30,15 +61,14 @@ impl GitStatus {
         parts.push(format!("{name} {path}"));
 
         // Branch information
-        if let Some(ref branch) = self.branch {
-            let branch_display = if self.ahead > 0 || self.behind > 0 {
-                format!("{} [↑{} ↓{}]", branch, self.ahead, self.behind)
-            } else {
-                branch.clone()
-            };
+        if let Some(branch_display) = self.branch_label() {
             parts.push(format!("on {}", branch_display.yellow()));
         }
 
+        if let Some(fork) = self.fork_label() {
+            parts.push(fork.magenta().to_string());
+        }
+
         // Status indicators
         let mut status_parts = Vec::new();
 
@@ -63,11 +93,63 @@ impl GitStatus {
         parts.join(" ")
     }
 
+    /// The branch with its ahead/behind counts, naming the ref they count
+    /// against when it is a configured `compare_ref`: `main [↑1 ↓2 vs
+    /// upstream/main]`
+    pub fn branch_label(&self) -> Option<String> {
+        let branch = self.branch.as_ref()?;
+        let mut counts = Vec::new();
+        if self.ahead > 0 || self.behind > 0 {
+            counts.push(format!("↑{} ↓{}", self.ahead, self.behind));
+        }
+        if let Some(compare_ref) = &self.compare_ref {
+            counts.push(format!("vs {compare_ref}"));
+        }
+        Some(if counts.is_empty() {
+            branch.clone()
+        } else {
+            format!("{branch} [{}]", counts.join(" "))
+        })
+    }
+
+    /// `fork (upstream N behind)` for forks that have fallen behind
+    pub fn fork_label(&self) -> Option<String> {
+        self.upstream_behind
+            .filter(|behind| *behind > 0)
+            .map(|behind| format!("fork (upstream {behind} behind)"))
+    }
+
     pub fn is_dirty(&self) -> bool {
         !self.clean
     }
 }
 
+/// Git subcommands that only read, so `vibe git exec` runs them without
+/// taking the repository's lock
+const READ_ONLY_COMMANDS: &[&str] = &[
+    "blame",
+    "cat-file",
+    "cherry",
+    "count-objects",
+    "describe",
+    "diff",
+    "for-each-ref",
+    "grep",
+    "log",
+    "ls-files",
+    "ls-remote",
+    "ls-tree",
+    "merge-base",
+    "name-rev",
+    "rev-list",
+    "rev-parse",
+    "shortlog",
+    "show",
+    "show-ref",
+    "status",
+    "version",
+];
+
 #[derive(Debug, Clone)]
 pub enum GitOperation {
     Status,
@@ -94,71 +176,182 @@ impl GitOperation {
             }
         }
     }
+
+    /// Whether the operation can change the repository
+    pub fn writes(&self) -> bool {
+        match self {
+            GitOperation::Status => false,
+            GitOperation::Pull | GitOperation::Push | GitOperation::Fetch => true,
+            GitOperation::Custom(command) => command
+                .split_whitespace()
+                .find(|arg| !arg.starts_with('-'))
+                .is_some_and(|subcommand| !READ_ONLY_COMMANDS.contains(&subcommand)),
+        }
+    }
+
+    /// [`Self::execute`], holding the repository's lock when the operation
+    /// writes
+    pub async fn execute_locked<P: AsRef<Path>>(
+        &self,
+        repo_path: P,
+        operation: &str,
+    ) -> Result<String> {
+        let repo_path = repo_path.as_ref();
+        let _lock = if self.writes() {
+            Some(repo_lock::acquire(repo_path, operation).await?)
+        } else {
+            None
+        };
+        self.execute(repo_path).await
+    }
 }
 
 /// Get comprehensive git status for a repository
 pub async fn get_git_status<P: AsRef<Path>>(repo_path: P) -> Result<GitStatus> {
-    let repo_path = repo_path.as_ref();
-    let repo_name = repo_path
-        .file_name()
-        .and_then(|n| n.to_str())
-        .unwrap_or("unknown")
-        .to_string();
+    read_git_status(&*backend::current(), repo_path.as_ref(), None).await
+}
 
-    debug!("Getting git status for repository: {}", repo_path.display());
+/// [`get_git_status`], counting ahead/behind against `compare_ref` when given
+pub async fn get_git_status_against<P: AsRef<Path>>(
+    repo_path: P,
+    compare_ref: Option<&str>,
+) -> Result<GitStatus> {
+    read_git_status(&*backend::current(), repo_path.as_ref(), compare_ref).await
+}
 
-    // Open repository using git2
-    let repo = Repository::open(repo_path)
-        .with_context(|| format!("Failed to open git repository: {}", repo_path.display()))?;
+/// A repository for [`collect_git_statuses`] to read
+#[derive(Debug, Clone)]
+pub struct StatusTarget {
+    pub name: String,
+    pub path: PathBuf,
+    /// Ref to count ahead/behind against instead of the branch on `origin`
+    pub compare_ref: Option<String>,
+}
+
+impl StatusTarget {
+    pub fn new(name: impl Into<String>, path: impl Into<PathBuf>) -> Self {
+        Self {
+            name: name.into(),
+            path: path.into(),
+            compare_ref: None,
+        }
+    }
 
-    // Get current branch
-    let branch = get_current_branch_name(&repo)?;
+    pub fn comparing_to(mut self, compare_ref: Option<String>) -> Self {
+        self.compare_ref = compare_ref;
+        self
+    }
+}
 
-    // Get remote URL
-    let remote_url = get_remote_url(&repo)?;
+/// Status of one repository from [`collect_git_statuses`]
+#[derive(Debug)]
+pub struct RepoStatusResult {
+    pub name: String,
+    pub path: PathBuf,
+    pub status: Result<GitStatus>,
+}
 
-    // Get working directory status
-    let mut status_opts = StatusOptions::new();
-    status_opts.include_untracked(true);
-    status_opts.include_ignored(false);
+/// Default limit for concurrent status reads: one per available CPU, or
+/// one at a time when output must be deterministic
+pub fn default_max_parallel_git() -> usize {
+    if crate::output::is_deterministic() {
+        return 1;
+    }
+    std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(4)
+}
 
-    let statuses = repo
-        .statuses(Some(&mut status_opts))
-        .context("Failed to get repository status")?;
+/// Read the status of many repositories, at most `max_parallel` at a time.
+/// Results come back in the order of `repos`, with per-repository errors
+/// kept alongside instead of aborting the whole collection.
+pub async fn collect_git_statuses(
+    repos: Vec<StatusTarget>,
+    max_parallel: usize,
+) -> Vec<RepoStatusResult> {
+    let semaphore = Arc::new(Semaphore::new(max_parallel.max(1)));
+    let backend = backend::current();
+    let mut handles = Vec::with_capacity(repos.len());
+
+    for target in repos {
+        let semaphore = Arc::clone(&semaphore);
+        let backend = Arc::clone(&backend);
+        let task_path = target.path.clone();
+        let handle = tokio::spawn(async move {
+            let _permit = semaphore.acquire_owned().await.ok();
+            read_git_status(&*backend, &task_path, target.compare_ref.as_deref()).await
+        });
+        handles.push((target.name, target.path, handle));
+    }
 
-    let mut staged = 0;
-    let mut unstaged = 0;
-    let mut untracked = 0;
+    let mut results = Vec::with_capacity(handles.len());
+    for (name, path, handle) in handles {
+        let status = match handle.await {
+            Ok(status) => status,
+            Err(e) => Err(anyhow::anyhow!("Status task failed: {e}")),
+        };
+        results.push(RepoStatusResult { name, path, status });
+    }
+    results
+}
 
-    for entry in statuses.iter() {
-        let flags = entry.status();
+/// Read the status of one repository through `backend`, counting
+/// ahead/behind against `compare_ref` when given
+pub async fn read_git_status(
+    backend: &dyn GitBackend,
+    repo_path: &Path,
+    compare_ref: Option<&str>,
+) -> Result<GitStatus> {
+    let repo_name = repo_path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("unknown")
+        .to_string();
 
-        if flags.intersects(
-            git2::Status::INDEX_NEW
-                | git2::Status::INDEX_MODIFIED
-                | git2::Status::INDEX_DELETED
-                | git2::Status::INDEX_RENAMED
-                | git2::Status::INDEX_TYPECHANGE,
-        ) {
-            staged += 1;
-        }
+    debug!("Getting git status for repository: {}", repo_path.display());
 
-        if flags.intersects(
-            git2::Status::WT_MODIFIED
-                | git2::Status::WT_DELETED
-                | git2::Status::WT_RENAMED
-                | git2::Status::WT_TYPECHANGE,
-        ) {
-            unstaged += 1;
-        }
+    let span = timings::phase(timings::GIT, format!("{repo_name}: status"));
+    read_status_fields(backend, repo_path, repo_name, compare_ref)
+        .instrument(span)
+        .await
+}
 
-        if flags.contains(git2::Status::WT_NEW) {
-            untracked += 1;
-        }
-    }
+async fn read_status_fields(
+    backend: &dyn GitBackend,
+    repo_path: &Path,
+    repo_name: String,
+    compare_ref: Option<&str>,
+) -> Result<GitStatus> {
+    let entries = backend
+        .status(repo_path)
+        .await
+        .with_context(|| format!("Failed to get status for: {}", repo_path.display()))?;
+    let branch = backend.head_branch(repo_path).await?;
+    let remote_url = backend.remote_url(repo_path).await?;
+
+    let staged = entries.iter().filter(|e| e.is_staged()).count();
+    let unstaged = entries.iter().filter(|e| e.is_unstaged()).count();
+    let untracked = entries.iter().filter(|e| e.is_untracked()).count();
+
+    // Ahead/behind against the configured compare ref, otherwise against
+    // the same-named branch on origin
+    let (ahead, behind) = match (compare_ref, branch.as_deref()) {
+        (Some(compare_ref), _) => backend
+            .ahead_behind(repo_path, compare_ref, "HEAD")
+            .await?
+            .unwrap_or_default(),
+        (None, Some(branch)) => backend
+            .ahead_behind(
+                repo_path,
+                &format!("refs/remotes/origin/{branch}"),
+                &format!("refs/heads/{branch}"),
+            )
+            .await?
+            .unwrap_or_default(),
+        (None, None) => (0, 0),
+    };
 
-    // Get ahead/behind information
-    let (ahead, behind) = get_ahead_behind_counts(&repo, branch.as_deref())?;
+    let upstream_behind = upstream::upstream_behind(backend, repo_path).await?;
 
     let clean = staged == 0 && unstaged == 0 && untracked == 0;
 
@@ -173,6 +366,8 @@ pub async fn get_git_status<P: AsRef<Path>>(repo_path: P) -> Result<GitStatus> {
         unstaged,
         untracked,
         remote_url,
+        upstream_behind,
+        compare_ref: compare_ref.map(str::to_string),
     })
 }
 
@@ -180,26 +375,39 @@ pub async fn get_git_status<P: AsRef<Path>>(repo_path: P) -> Result<GitStatus> {
 pub async fn execute_git_command<P: AsRef<Path>>(repo_path: P, args: &[&str]) -> Result<String> {
     let repo_path = repo_path.as_ref();
 
+    let command = redact::command_line("git", args);
     debug!(
-        "Executing git command in {}: git {}",
+        "Executing git command in {}: {}",
         repo_path.display(),
-        args.join(" ")
+        command
     );
 
+    let span = timings::phase(
+        timings::GIT,
+        format!(
+            "{}: {}",
+            repo_path.file_name().map_or_else(
+                || repo_path.display().to_string(),
+                |n| n.to_string_lossy().into_owned()
+            ),
+            command
+        ),
+    );
     let output = AsyncCommand::new("git")
         .args(args)
         .current_dir(repo_path)
         .output()
+        .instrument(span)
         .await
-        .with_context(|| format!("Failed to execute git command: git {}", args.join(" ")))?;
+        .with_context(|| format!("Failed to execute git command: {command}"))?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
         return Err(anyhow::anyhow!(
-            "Git command failed: git {} (exit code: {})\n{}",
-            args.join(" "),
+            "Git command failed: {} (exit code: {})\n{}",
+            command,
             output.status.code().unwrap_or(-1),
-            stderr
+            redact::scrub(&stderr)
         ));
     }
 
@@ -207,74 +415,6 @@ pub async fn execute_git_command<P: AsRef<Path>>(repo_path: P, args: &[&str]) ->
     Ok(stdout.trim().to_string())
 }
 
-/// Get the current branch name
-fn get_current_branch_name(repo: &Repository) -> Result<Option<String>> {
-    let head = match repo.head() {
-        Ok(head) => head,
-        Err(ref e) if e.code() == git2::ErrorCode::UnbornBranch => {
-            debug!("Repository has no commits yet");
-            return Ok(None);
-        }
-        Err(e) => return Err(e.into()),
-    };
-
-    Ok(head.shorthand().map(|s| s.to_string()))
-}
-
-/// Get remote URL for the repository
-fn get_remote_url(repo: &Repository) -> Result<Option<String>> {
-    let remote_name = match repo.find_remote("origin") {
-        Ok(_) => "origin".to_string(),
-        Err(_) => {
-            let remotes = repo.remotes()?;
-            match remotes.get(0) {
-                Some(name) => name.to_string(),
-                None => return Ok(None),
-            }
-        }
-    };
-
-    let remote = repo.find_remote(&remote_name)?;
-    Ok(remote.url().map(|url| url.to_string()))
-}
-
-/// Get ahead/behind counts compared to upstream
-fn get_ahead_behind_counts(repo: &Repository, branch_name: Option<&str>) -> Result<(usize, usize)> {
-    let branch_name = match branch_name {
-        Some(name) => name,
-        None => return Ok((0, 0)),
-    };
-
-    // Get local branch reference
-    let local_ref = format!("refs/heads/{branch_name}");
-    let local_oid = match repo.resolve_reference_from_short_name(&local_ref) {
-        Ok(reference) => reference.target().unwrap_or_else(git2::Oid::zero),
-        Err(_) => return Ok((0, 0)),
-    };
-
-    // Try to find upstream branch
-    let upstream_ref = format!("refs/remotes/origin/{branch_name}");
-    let upstream_oid = match repo.resolve_reference_from_short_name(&upstream_ref) {
-        Ok(reference) => reference.target().unwrap_or_else(git2::Oid::zero),
-        Err(_) => {
-            debug!("No upstream branch found for {}", branch_name);
-            return Ok((0, 0));
-        }
-    };
-
-    // Calculate ahead/behind
-    match repo.graph_ahead_behind(local_oid, upstream_oid) {
-        Ok((ahead, behind)) => Ok((ahead, behind)),
-        Err(e) => {
-            warn!(
-                "Failed to calculate ahead/behind for {}: {}",
-                branch_name, e
-            );
-            Ok((0, 0))
-        }
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
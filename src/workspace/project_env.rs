@@ -0,0 +1,444 @@
+//! Toolchain requirements declared by a project
+//!
+//! Reads the files version managers use to pin tools (`.nvmrc`,
+//! `.node-version`, `rust-toolchain.toml`, `.python-version`, and asdf's
+//! `.tool-versions`) and compares each pinned version with the one found on
+//! PATH. Versions are looked up from the project directory, so shims that
+//! honor those files report what a shell there would get. The probe runs
+//! after `vibe open` and worktree creation, backs `vibe repo env`, and is part
+//! of the MCP `describe_repo` payload.
+//!
+//! Each pinned tool costs one `--version` run, with `RUSTUP_AUTO_INSTALL=0`
+//! so a missing Rust toolchain is reported instead of downloaded.
+
+use console::style;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::display_println;
+use crate::output::theme::{self, Role};
+use crate::utils::platform::resolve_command;
+
+/// A tool vibe can check, with the asdf plugin name `.tool-versions` uses
+struct Tool {
+    name: &'static str,
+    asdf_name: &'static str,
+    command: &'static str,
+    version_args: &'static [&'static str],
+}
+
+const TOOLS: [Tool; 5] = [
+    Tool {
+        name: "node",
+        asdf_name: "nodejs",
+        command: "node",
+        version_args: &["--version"],
+    },
+    Tool {
+        name: "rust",
+        asdf_name: "rust",
+        command: "rustc",
+        version_args: &["--version"],
+    },
+    Tool {
+        name: "python",
+        asdf_name: "python",
+        command: "python3",
+        version_args: &["--version"],
+    },
+    Tool {
+        name: "go",
+        asdf_name: "golang",
+        command: "go",
+        version_args: &["version"],
+    },
+    Tool {
+        name: "ruby",
+        asdf_name: "ruby",
+        command: "ruby",
+        version_args: &["--version"],
+    },
+];
+
+fn tool(name: &str) -> &'static Tool {
+    TOOLS
+        .iter()
+        .find(|tool| tool.name == name)
+        .expect("known tool")
+}
+
+/// How the installed version compares with the project's requirement
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ToolStatus {
+    Ok,
+    Mismatch,
+    /// The tool isn't on PATH, or the pinned toolchain isn't installed
+    Missing,
+    /// The requirement is an alias like `lts/*` that can't be compared
+    Unknown,
+}
+
+/// One pinned tool version
+#[derive(Debug, Clone, Serialize)]
+pub struct ToolRequirement {
+    pub tool: String,
+    pub required: String,
+    /// File the requirement was read from
+    pub source: String,
+    pub installed: Option<String>,
+    pub status: ToolStatus,
+    /// Command that switches to or installs the required version
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fix: Option<String>,
+}
+
+/// Pinned tool versions for one project directory
+#[derive(Debug, Clone, Serialize)]
+pub struct ProjectEnv {
+    pub repository: String,
+    pub path: PathBuf,
+    pub requirements: Vec<ToolRequirement>,
+}
+
+impl ProjectEnv {
+    /// Requirements the current PATH doesn't satisfy
+    pub fn mismatches(&self) -> impl Iterator<Item = &ToolRequirement> {
+        self.requirements
+            .iter()
+            .filter(|r| matches!(r.status, ToolStatus::Mismatch | ToolStatus::Missing))
+    }
+
+    /// Print a warning block when any requirement isn't satisfied
+    pub fn print_warnings(&self) {
+        if self.mismatches().next().is_none() {
+            return;
+        }
+        display_println!(
+            "{} {}",
+            theme::paint(Role::Warning, "⚠ Toolchain mismatch in"),
+            style(&self.repository).cyan()
+        );
+        for requirement in self.mismatches() {
+            let found = match &requirement.installed {
+                Some(version) => format!("found {version}"),
+                None => "not installed".to_string(),
+            };
+            let fix = requirement
+                .fix
+                .as_deref()
+                .map(|fix| format!(" → {}", style(fix).bold()))
+                .unwrap_or_default();
+            display_println!(
+                "    {} {} required by {}, {}{}",
+                requirement.tool,
+                requirement.required,
+                requirement.source,
+                found,
+                fix
+            );
+        }
+    }
+
+    pub fn print_table(&self) {
+        if self.requirements.is_empty() {
+            display_println!(
+                "{} No toolchain version files found in {}",
+                style("ℹ").yellow(),
+                self.path.display()
+            );
+            return;
+        }
+
+        let source_width = self
+            .requirements
+            .iter()
+            .map(|r| r.source.len())
+            .max()
+            .unwrap_or(0)
+            .max("Source".len());
+        display_println!(
+            "{}",
+            style(format!(
+                "{:<8}  {:<12}  {:<source_width$}  {:<16}  Status",
+                "Tool", "Required", "Source", "Installed"
+            ))
+            .bold()
+        );
+        for requirement in &self.requirements {
+            let status = match requirement.status {
+                ToolStatus::Ok => theme::paint(Role::Success, "ok"),
+                ToolStatus::Mismatch => theme::paint(Role::Warning, "mismatch"),
+                ToolStatus::Missing => theme::paint(Role::Error, "missing"),
+                ToolStatus::Unknown => theme::paint(Role::Dim, "unknown"),
+            };
+            let fix = requirement
+                .fix
+                .as_deref()
+                .map(|fix| format!("  {}", theme::paint(Role::Dim, format!("({fix})"))))
+                .unwrap_or_default();
+            display_println!(
+                "{:<8}  {:<12}  {:<source_width$}  {:<16}  {}{}",
+                requirement.tool,
+                requirement.required,
+                requirement.source,
+                requirement.installed.as_deref().unwrap_or("-"),
+                status,
+                fix
+            );
+        }
+    }
+}
+
+/// Read the pinned versions in `path` and check them against PATH
+pub fn probe(repository: &str, path: &Path) -> ProjectEnv {
+    let mut installed: HashMap<&str, Option<String>> = HashMap::new();
+    let requirements = read_requirements(path)
+        .into_iter()
+        .map(|(tool_name, required, source)| {
+            let installed = installed
+                .entry(tool_name)
+                .or_insert_with(|| installed_version(tool(tool_name), path))
+                .clone();
+            let status = match &installed {
+                None => ToolStatus::Missing,
+                Some(version) => match satisfies(tool_name, &required, version) {
+                    Some(true) => ToolStatus::Ok,
+                    Some(false) => ToolStatus::Mismatch,
+                    None => ToolStatus::Unknown,
+                },
+            };
+            let fix = matches!(status, ToolStatus::Mismatch | ToolStatus::Missing)
+                .then(|| fix_command(tool_name, &required, source, status));
+            ToolRequirement {
+                tool: tool_name.to_string(),
+                required,
+                source: source.to_string(),
+                installed,
+                status,
+                fix,
+            }
+        })
+        .collect();
+
+    ProjectEnv {
+        repository: repository.to_string(),
+        path: path.to_path_buf(),
+        requirements,
+    }
+}
+
+/// `(tool, version, source file)` for every pinned version in `path`
+fn read_requirements(path: &Path) -> Vec<(&'static str, String, &'static str)> {
+    let read = |name: &str| std::fs::read_to_string(path.join(name)).ok();
+    let mut requirements = Vec::new();
+
+    for source in [".nvmrc", ".node-version"] {
+        if let Some(version) = read(source).as_deref().and_then(first_value) {
+            requirements.push(("node", version, source));
+        }
+    }
+    if let Some(channel) = read("rust-toolchain.toml")
+        .as_deref()
+        .and_then(toolchain_channel)
+    {
+        requirements.push(("rust", channel, "rust-toolchain.toml"));
+    } else if let Some(content) = read("rust-toolchain") {
+        // The legacy file holds either a bare channel or TOML
+        let channel = toolchain_channel(&content).or_else(|| first_value(&content));
+        if let Some(channel) = channel {
+            requirements.push(("rust", channel, "rust-toolchain"));
+        }
+    }
+    if let Some(version) = read(".python-version").as_deref().and_then(first_value) {
+        requirements.push(("python", version, ".python-version"));
+    }
+    if let Some(content) = read(".tool-versions") {
+        for line in content.lines() {
+            let line = line.split('#').next().unwrap_or("");
+            let mut fields = line.split_whitespace();
+            let (Some(plugin), Some(version)) = (fields.next(), fields.next()) else {
+                continue;
+            };
+            if let Some(tool) = TOOLS.iter().find(|tool| tool.asdf_name == plugin) {
+                requirements.push((tool.name, version.to_string(), ".tool-versions"));
+            }
+        }
+    }
+    requirements
+}
+
+/// The first non-empty, non-comment word of a version file
+fn first_value(content: &str) -> Option<String> {
+    content
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !line.starts_with('#'))
+        .and_then(|line| line.split_whitespace().next())
+        .map(str::to_string)
+}
+
+/// `channel` from the `[toolchain]` table of a rust-toolchain file
+fn toolchain_channel(content: &str) -> Option<String> {
+    let mut in_toolchain = false;
+    for line in content.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_toolchain = line == "[toolchain]";
+            continue;
+        }
+        if !in_toolchain {
+            continue;
+        }
+        if let Some((key, value)) = line.split_once('=') {
+            if key.trim() == "channel" {
+                let value = value.split('#').next().unwrap_or("").trim();
+                return Some(value.trim_matches(['"', '\'']).to_string());
+            }
+        }
+    }
+    None
+}
+
+/// The version of `tool` on PATH when run from `dir`, or `None` when it's
+/// missing or fails
+fn installed_version(tool: &Tool, dir: &Path) -> Option<String> {
+    let output = Command::new(resolve_command(tool.command))
+        .args(tool.version_args)
+        .current_dir(dir)
+        // Never let rustup download a pinned toolchain just to report it
+        .env("RUSTUP_AUTO_INSTALL", "0")
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+    // Older Pythons print the version on stderr
+    let text = format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    regex::Regex::new(r"\d+\.\d+(?:\.\d+)?(?:-[a-z]+)?")
+        .ok()?
+        .find(&text)
+        .map(|m| m.as_str().to_string())
+}
+
+/// Whether `installed` satisfies `required`; `None` for aliases that can't
+/// be compared
+fn satisfies(tool: &str, required: &str, installed: &str) -> Option<bool> {
+    let (installed_number, prerelease) = match installed.split_once('-') {
+        Some((number, channel)) => (number, Some(channel)),
+        None => (installed, None),
+    };
+    if tool == "rust" {
+        let channel = required.split('-').next().unwrap_or(required);
+        match channel {
+            "stable" => return Some(prerelease.is_none()),
+            "beta" | "nightly" => return Some(prerelease == Some(channel)),
+            _ if required.contains('-') => return None,
+            _ => {}
+        }
+    }
+
+    let required = required.strip_prefix('v').unwrap_or(required);
+    if required.is_empty() || !required.chars().all(|c| c.is_ascii_digit() || c == '.') {
+        return None;
+    }
+    let mut installed_parts = installed_number.split('.');
+    Some(
+        required
+            .split('.')
+            .all(|part| installed_parts.next() == Some(part)),
+    )
+}
+
+fn fix_command(tool: &str, required: &str, source: &str, status: ToolStatus) -> String {
+    let missing = status == ToolStatus::Missing;
+    match (tool, source) {
+        (_, ".tool-versions") => "asdf install".to_string(),
+        ("node", ".nvmrc") if missing => "nvm install".to_string(),
+        ("node", ".nvmrc") => "nvm use".to_string(),
+        ("node", _) if missing => format!("nvm install {required}"),
+        ("node", _) => format!("nvm use {required}"),
+        ("rust", _) => format!("rustup toolchain install {required}"),
+        ("python", _) => format!("pyenv install {required}"),
+        _ => format!("install {tool} {required}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_read_requirements() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join(".nvmrc"), "v20\n").unwrap();
+        std::fs::write(
+            dir.path().join("rust-toolchain.toml"),
+            "[toolchain]\nchannel = \"1.75.0\" # pinned\ncomponents = [\"clippy\"]\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join(".python-version"), "3.11\n3.10\n").unwrap();
+        std::fs::write(
+            dir.path().join(".tool-versions"),
+            "nodejs 20.11.1\nterraform 1.5.0\n# golang 1.21\n",
+        )
+        .unwrap();
+
+        let found: Vec<(&str, String, &str)> = read_requirements(dir.path());
+        assert_eq!(
+            found,
+            vec![
+                ("node", "v20".to_string(), ".nvmrc"),
+                ("rust", "1.75.0".to_string(), "rust-toolchain.toml"),
+                ("python", "3.11".to_string(), ".python-version"),
+                ("node", "20.11.1".to_string(), ".tool-versions"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_legacy_rust_toolchain_file() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("rust-toolchain"), "nightly-2024-01-01\n").unwrap();
+        assert_eq!(
+            read_requirements(dir.path()),
+            vec![("rust", "nightly-2024-01-01".to_string(), "rust-toolchain")]
+        );
+    }
+
+    #[test]
+    fn test_satisfies() {
+        assert_eq!(satisfies("node", "v20", "20.11.1"), Some(true));
+        assert_eq!(satisfies("node", "20", "18.19.0"), Some(false));
+        assert_eq!(satisfies("node", "lts/*", "20.11.1"), None);
+        assert_eq!(satisfies("python", "3.11", "3.11.4"), Some(true));
+        assert_eq!(satisfies("python", "3.1", "3.11.4"), Some(false));
+        assert_eq!(satisfies("rust", "stable", "1.88.0"), Some(true));
+        assert_eq!(
+            satisfies("rust", "nightly-2024-01-01", "1.77.0-nightly"),
+            Some(true)
+        );
+        assert_eq!(satisfies("rust", "stable", "1.77.0-nightly"), Some(false));
+        assert_eq!(satisfies("rust", "1.75", "1.88.0"), Some(false));
+    }
+
+    #[test]
+    fn test_missing_tool_gets_fix_command() {
+        assert_eq!(
+            fix_command("rust", "1.75.0", "rust-toolchain.toml", ToolStatus::Missing),
+            "rustup toolchain install 1.75.0"
+        );
+        assert_eq!(
+            fix_command("node", "v20", ".nvmrc", ToolStatus::Mismatch),
+            "nvm use"
+        );
+        assert_eq!(
+            fix_command("node", "20.11.1", ".tool-versions", ToolStatus::Mismatch),
+            "asdf install"
+        );
+    }
+}
@@ -0,0 +1,230 @@
+//! Project types detected from the manifests at a repository's root
+//!
+//! A repository is `rust` when it has a `Cargo.toml`, `node` with a
+//! `package.json`, `python` with a `pyproject.toml` or `requirements.txt`,
+//! and `go` with a `go.mod`; a repository with several manifests has several
+//! types. Detections are cached in `repositories.db` and redone when a
+//! manifest appears, disappears, or is modified. Types back `--type` on
+//! `vibe git exec`, `vibe git sync`, `vibe grep`, and `vibe config repo list`,
+//! and are part of the MCP `describe_repo` payload.
+
+use chrono::Utc;
+use console::style;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use tracing::warn;
+
+use crate::cache::{CachedProjectTypes, RepositoryCache};
+use crate::display_println;
+use crate::output::delimited::Tabular;
+use crate::output::theme::{self, Role};
+
+/// A kind of project, recognized by its manifest
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProjectType {
+    Rust,
+    Node,
+    Python,
+    Go,
+}
+
+impl ProjectType {
+    pub const ALL: [ProjectType; 4] = [Self::Rust, Self::Node, Self::Python, Self::Go];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Rust => "rust",
+            Self::Node => "node",
+            Self::Python => "python",
+            Self::Go => "go",
+        }
+    }
+
+    /// Files at the repository root that mark this type
+    pub fn manifests(self) -> &'static [&'static str] {
+        match self {
+            Self::Rust => &["Cargo.toml"],
+            Self::Node => &["package.json"],
+            Self::Python => &["pyproject.toml", "requirements.txt"],
+            Self::Go => &["go.mod"],
+        }
+    }
+}
+
+impl std::fmt::Display for ProjectType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for ProjectType {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|project_type| project_type.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| {
+                format!("unknown project type '{s}', expected rust, node, python, or go")
+            })
+    }
+}
+
+/// Types of the checkout at `path`, in [`ProjectType::ALL`] order
+pub fn detect(path: &Path) -> Vec<ProjectType> {
+    ProjectType::ALL
+        .into_iter()
+        .filter(|project_type| {
+            project_type
+                .manifests()
+                .iter()
+                .any(|manifest| path.join(manifest).is_file())
+        })
+        .collect()
+}
+
+/// Changes when a manifest at `path` is created, removed, or modified
+pub fn fingerprint(path: &Path) -> String {
+    let mut hasher = DefaultHasher::new();
+    for manifest in ProjectType::ALL
+        .into_iter()
+        .flat_map(|project_type| project_type.manifests())
+    {
+        let modified = std::fs::metadata(path.join(manifest))
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok());
+        manifest.hash(&mut hasher);
+        modified.hash(&mut hasher);
+    }
+    format!("{:016x}", hasher.finish())
+}
+
+/// Types of each repository, reusing cached detections whose fingerprint
+/// still matches and caching fresh ones
+pub async fn load(
+    repos: Vec<(String, PathBuf)>,
+    cache: Option<&RepositoryCache>,
+) -> HashMap<String, Vec<ProjectType>> {
+    let mut cached = match cache {
+        Some(cache) => cache.get_project_types().await.unwrap_or_else(|e| {
+            warn!("Failed to read cached project types: {}", e);
+            HashMap::new()
+        }),
+        None => HashMap::new(),
+    };
+
+    let mut types = HashMap::with_capacity(repos.len());
+    for (name, path) in repos {
+        let fingerprint = fingerprint(&path);
+        let detected = match cached.remove(&name) {
+            Some(entry) if entry.fingerprint == fingerprint => parse_types(&entry.project_types),
+            _ => {
+                let detected = detect(&path);
+                if let Some(cache) = cache {
+                    let entry = CachedProjectTypes {
+                        name: name.clone(),
+                        fingerprint,
+                        project_types: detected.iter().map(|t| t.as_str().to_string()).collect(),
+                        detected_at: Utc::now(),
+                    };
+                    if let Err(e) = cache.cache_project_types(&entry).await {
+                        warn!("Failed to cache project types for {}: {}", name, e);
+                    }
+                }
+                detected
+            }
+        };
+        types.insert(name, detected);
+    }
+    types
+}
+
+fn parse_types(names: &[String]) -> Vec<ProjectType> {
+    names.iter().filter_map(|name| name.parse().ok()).collect()
+}
+
+/// `[rust,node]`, or nothing for a repository without a known manifest
+pub fn badge(types: &[ProjectType]) -> String {
+    if types.is_empty() {
+        return String::new();
+    }
+    let names: Vec<&str> = types.iter().map(|t| t.as_str()).collect();
+    format!("[{}]", names.join(","))
+}
+
+/// A configured repository with its project types, for
+/// `vibe config repo list`
+#[derive(Debug, Clone, Serialize)]
+pub struct TypedRepository {
+    pub name: String,
+    pub path: PathBuf,
+    pub project_types: Vec<ProjectType>,
+}
+
+impl Tabular for TypedRepository {
+    const FIELDS: &'static [&'static str] = &["name", "path", "project_types"];
+}
+
+pub fn print_repositories(repos: &[TypedRepository]) {
+    if repos.is_empty() {
+        display_println!("{} No repositories found", style("ℹ").yellow());
+        return;
+    }
+
+    let name_width = repos.iter().map(|r| r.name.len()).max().unwrap_or(0);
+    for repo in repos {
+        display_println!(
+            "{:<name_width$}  {}  {}",
+            repo.name,
+            theme::paint(Role::Accent, format!("{:<20}", badge(&repo.project_types))),
+            theme::paint(Role::Dim, repo.path.display())
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_detect_mixed_repository() {
+        let dir = tempdir().unwrap();
+        assert!(detect(dir.path()).is_empty());
+
+        std::fs::write(dir.path().join("package.json"), "{}").unwrap();
+        std::fs::write(dir.path().join("Cargo.toml"), "[package]").unwrap();
+        std::fs::write(dir.path().join("requirements.txt"), "").unwrap();
+        assert_eq!(
+            detect(dir.path()),
+            vec![ProjectType::Rust, ProjectType::Node, ProjectType::Python]
+        );
+        assert_eq!(badge(&detect(dir.path())), "[rust,node,python]");
+    }
+
+    #[tokio::test]
+    async fn test_load_redetects_when_a_manifest_changes() {
+        let dir = tempdir().unwrap();
+        let cache = RepositoryCache::new(dir.path().join("repos.db"));
+        cache.initialize().await.unwrap();
+        let repo = dir.path().join("api");
+        std::fs::create_dir(&repo).unwrap();
+        std::fs::write(repo.join("go.mod"), "module api").unwrap();
+        let repos = vec![("api".to_string(), repo.clone())];
+
+        let types = load(repos.clone(), Some(&cache)).await;
+        assert_eq!(types["api"], vec![ProjectType::Go]);
+        let cached = cache.get_project_types().await.unwrap();
+        assert_eq!(cached["api"].project_types, vec!["go".to_string()]);
+
+        std::fs::write(repo.join("pyproject.toml"), "[project]").unwrap();
+        let types = load(repos, Some(&cache)).await;
+        assert_eq!(types["api"], vec![ProjectType::Python, ProjectType::Go]);
+    }
+}
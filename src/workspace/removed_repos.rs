@@ -0,0 +1,89 @@
+//! Config entries `vibe git scan --clean` removed, kept in
+//! `removed-repos.yaml` in the data directory so `vibe config repo
+//! readd-removed` can bring them back once their checkout is available again
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use super::config::Repository;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemovedRepository {
+    pub removed_at: DateTime<Utc>,
+    pub repository: Repository,
+}
+
+/// The removed entries in `path`, oldest first; none when it doesn't exist
+pub async fn load(path: &Path) -> Result<Vec<RemovedRepository>> {
+    match tokio::fs::read_to_string(path).await {
+        Ok(contents) if contents.trim().is_empty() => Ok(Vec::new()),
+        Ok(contents) => serde_yaml::from_str(&contents)
+            .with_context(|| format!("Failed to parse {}", path.display())),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    }
+}
+
+pub async fn save(path: &Path, removed: &[RemovedRepository]) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    let yaml =
+        serde_yaml::to_string(removed).context("Failed to serialize removed repositories")?;
+    tokio::fs::write(path, yaml)
+        .await
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Add `repositories` to `path`, replacing earlier entries of the same name
+pub async fn record(path: &Path, repositories: Vec<Repository>) -> Result<()> {
+    let mut removed = load(path).await?;
+    let removed_at = Utc::now();
+    removed.retain(|entry| {
+        !repositories
+            .iter()
+            .any(|repo| repo.name == entry.repository.name)
+    });
+    removed.extend(
+        repositories
+            .into_iter()
+            .map(|repository| RemovedRepository {
+                removed_at,
+                repository,
+            }),
+    );
+    save(path, &removed).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_record_replaces_entries_by_name() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("removed-repos.yaml");
+        assert!(load(&path).await.unwrap().is_empty());
+
+        record(
+            &path,
+            vec![
+                Repository::new("api", "api").with_url("git@github.com:acme/api.git"),
+                Repository::new("web", "web"),
+            ],
+        )
+        .await
+        .unwrap();
+        record(&path, vec![Repository::new("api", "moved/api")])
+            .await
+            .unwrap();
+
+        let removed = load(&path).await.unwrap();
+        let names: Vec<_> = removed.iter().map(|r| r.repository.name.as_str()).collect();
+        assert_eq!(names, ["web", "api"]);
+        assert_eq!(removed[1].repository.path, Path::new("moved/api"));
+    }
+}
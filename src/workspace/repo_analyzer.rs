@@ -1,11 +1,13 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
+use serde::Serialize;
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
-use super::config::{Repository, WorkspaceConfig};
+use super::config::{resolve_repo_path, Repository, WorkspaceConfig};
 use super::discovery::{discover_git_repositories, get_remote_url, get_repository_name};
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum RepoStatus {
     /// Repository is tracked in config and exists on filesystem
     Tracked,
@@ -15,17 +17,21 @@ pub enum RepoStatus {
     Missing,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct RepoInfo {
     pub name: String,
     pub path: PathBuf,
     pub status: RepoStatus,
     pub remote_url: Option<String>,
     pub organization: Option<String>,
+    /// Configured with an absolute path outside the scanned root; scan
+    /// restore and clean skip it
+    pub outside_root: bool,
+    #[serde(skip)]
     pub config_repo: Option<Repository>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct NonGitFolder {
     pub path: PathBuf,
     pub name: String,
@@ -46,6 +52,7 @@ impl RepoInfo {
             status,
             remote_url: None,
             organization: None,
+            outside_root: false,
             config_repo: None,
         }
     }
@@ -56,6 +63,11 @@ impl RepoInfo {
         self
     }
 
+    pub fn outside_root(mut self) -> Self {
+        self.outside_root = true;
+        self
+    }
+
     pub fn with_config_repo(mut self, repo: Repository) -> Self {
         if let Some(url) = &repo.url {
             self.organization = extract_organization_from_url(url);
@@ -112,6 +124,23 @@ impl WorkspaceAnalysis {
             .collect()
     }
 
+    /// Missing repositories under the scanned root, which scan restore and
+    /// clean act on
+    pub fn get_missing_in_root(&self) -> Vec<&RepoInfo> {
+        self.repositories
+            .iter()
+            .filter(|r| r.status == RepoStatus::Missing && !r.outside_root)
+            .collect()
+    }
+
+    /// Missing repositories configured outside the scanned root
+    pub fn get_missing_outside_root(&self) -> Vec<&RepoInfo> {
+        self.repositories
+            .iter()
+            .filter(|r| r.status == RepoStatus::Missing && r.outside_root)
+            .collect()
+    }
+
     pub fn has_actionable_items(&self) -> bool {
         !self.get_new_repos().is_empty()
             || !self.get_missing_repos().is_empty()
@@ -137,7 +166,12 @@ pub async fn analyze_workspace(
     let config_repos: HashMap<PathBuf, &Repository> = config
         .repositories
         .iter()
-        .map(|repo| (normalize_path(&workspace_root.join(&repo.path)), repo))
+        .map(|repo| {
+            (
+                normalize_path(&resolve_repo_path(workspace_root, &repo.path)),
+                repo,
+            )
+        })
         .collect();
 
     // Process discovered repositories
@@ -166,48 +200,75 @@ pub async fn analyze_workspace(
         analysis.add_repository(repo_info);
     }
 
-    // Process missing repositories from config
+    // Process missing repositories from config. Repositories outside the
+    // root are never discovered, so they count as tracked when they exist.
     for config_repo in &config.repositories {
-        let full_path = workspace_root.join(&config_repo.path);
+        let full_path = resolve_repo_path(workspace_root, &config_repo.path);
         let normalized_full_path = normalize_path(&full_path);
-        if !discovered_paths.contains(&normalized_full_path) {
-            let mut repo_info =
-                RepoInfo::new(config_repo.name.clone(), full_path, RepoStatus::Missing);
-            repo_info = repo_info.with_config_repo(config_repo.clone());
-            analysis.add_repository(repo_info);
+        if discovered_paths.contains(&normalized_full_path) {
+            continue;
         }
+        let outside_root = !normalized_full_path.starts_with(normalize_path(workspace_root));
+        let status = if outside_root && full_path.join(".git").exists() {
+            RepoStatus::Tracked
+        } else {
+            RepoStatus::Missing
+        };
+        let mut repo_info = RepoInfo::new(config_repo.name.clone(), full_path, status)
+            .with_config_repo(config_repo.clone());
+        if outside_root {
+            repo_info = repo_info.outside_root();
+        }
+        analysis.add_repository(repo_info);
     }
 
     // Find non-git folders with improved detection logic
-    if let Ok(entries) = std::fs::read_dir(workspace_root) {
-        for entry in entries.flatten() {
-            let path = entry.path();
-            if path.is_dir() && !path.join(".git").exists() {
-                if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-                    // Skip common system directories
-                    if !name.starts_with('.') && name != "node_modules" && name != "target" {
-                        // Skip organization folders (folders that only contain git repositories)
-                        if is_organization_folder(&path) {
-                            continue;
-                        }
-
-                        // Only flag folders that contain loose files or mixed content
-                        if contains_flaggable_content(&path) {
-                            analysis.add_non_git_folder(NonGitFolder {
-                                path: path.clone(),
-                                name: name.to_string(),
-                            });
-                        }
+    let root = workspace_root.to_path_buf();
+    let non_git_folders = tokio::task::spawn_blocking(move || find_non_git_folders(&root))
+        .await
+        .context("Failed to complete non-git folder scan")?;
+    for folder in non_git_folders {
+        analysis.add_non_git_folder(folder);
+    }
+
+    Ok(analysis)
+}
+
+/// Top-level folders under `workspace_root` that hold loose files rather
+/// than git repositories
+fn find_non_git_folders(workspace_root: &Path) -> Vec<NonGitFolder> {
+    let mut folders = Vec::new();
+    let Ok(entries) = std::fs::read_dir(workspace_root) else {
+        return folders;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() && !path.join(".git").exists() {
+            if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
+                // Skip common system directories
+                if !name.starts_with('.') && name != "node_modules" && name != "target" {
+                    // Skip organization folders (folders that only contain git repositories)
+                    if is_organization_folder(&path) {
+                        continue;
+                    }
+
+                    // Only flag folders that contain loose files or mixed content
+                    if contains_flaggable_content(&path) {
+                        folders.push(NonGitFolder {
+                            path: path.clone(),
+                            name: name.to_string(),
+                        });
                     }
                 }
             }
         }
     }
 
-    Ok(analysis)
+    folders
 }
 
-fn extract_organization_from_url(url: &str) -> Option<String> {
+pub(crate) fn extract_organization_from_url(url: &str) -> Option<String> {
     // Handle common Git URL formats
     if let Some(captures) = regex::Regex::new(r"github\.com[:/]([^/]+)/")
         .ok()?
@@ -243,6 +304,12 @@ fn extract_organization_from_url(url: &str) -> Option<String> {
 
 /// Check if a directory is an organization folder (only contains git repositories)
 fn is_organization_folder(dir_path: &Path) -> bool {
+    is_layout_folder(dir_path, 1)
+}
+
+/// A folder of only git repositories, or with `nesting` > 0 a folder of only
+/// such folders: `{host}` in the host-owner clone layout
+fn is_layout_folder(dir_path: &Path, nesting: usize) -> bool {
     let Ok(entries) = std::fs::read_dir(dir_path) else {
         return false;
     };
@@ -255,8 +322,9 @@ fn is_organization_folder(dir_path: &Path) -> bool {
         let path = entry.path();
         if path.is_dir() {
             has_subdirs = true;
-            // Check if this subdirectory is a git repository
-            if !path.join(".git").exists() {
+            // Check if this subdirectory is a git repository, or a folder of them
+            let is_repo = path.join(".git").exists();
+            if !is_repo && (nesting == 0 || !is_layout_folder(&path, nesting - 1)) {
                 all_subdirs_are_git = false;
             }
         } else {
@@ -268,7 +336,7 @@ fn is_organization_folder(dir_path: &Path) -> bool {
     // It's an organization folder if:
     // 1. It has subdirectories
     // 2. It has no files
-    // 3. All subdirectories are git repositories
+    // 3. All subdirectories are git repositories (or organization folders)
     has_subdirs && !has_files && all_subdirs_are_git
 }
 
@@ -345,6 +413,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_host_folders_are_not_flagged() {
+        let root = tempfile::TempDir::new().unwrap();
+        for repo in ["github.com/acme/api", "github.com/globex/api", "acme/web"] {
+            std::fs::create_dir_all(root.path().join(repo).join(".git")).unwrap();
+        }
+        std::fs::create_dir_all(root.path().join("notes")).unwrap();
+        std::fs::write(root.path().join("notes/todo.md"), "").unwrap();
+
+        let flagged: Vec<_> = find_non_git_folders(root.path())
+            .into_iter()
+            .map(|folder| folder.name)
+            .collect();
+        assert_eq!(flagged, ["notes"]);
+    }
+
     #[test]
     fn test_repo_info_creation() {
         let repo = RepoInfo::new(
@@ -0,0 +1,216 @@
+//! What `vibe config reset` and `vibe git reset` would remove
+//!
+//! Both commands print a preview from here with `--dry-run`, and before the
+//! interactive confirmation when "Show full details" is chosen. Building a
+//! preview only reads the filesystem.
+
+use console::style;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+use crate::display_println;
+use crate::workspace::disk_usage::{dir_size, format_bytes};
+
+/// A file or directory a reset would delete or change
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ResetPath {
+    pub path: PathBuf,
+    pub is_dir: bool,
+    /// Size in bytes, including everything under a directory
+    pub bytes: u64,
+}
+
+impl ResetPath {
+    /// `path` with its size, or `None` when it doesn't exist
+    pub fn measure(path: &Path) -> Option<Self> {
+        let metadata = std::fs::metadata(path).ok()?;
+        Some(Self {
+            path: path.to_path_buf(),
+            is_dir: metadata.is_dir(),
+            bytes: if metadata.is_dir() {
+                dir_size(path)
+            } else {
+                metadata.len()
+            },
+        })
+    }
+
+    /// One line for the path, marked `×` when it is deleted whole
+    fn print(&self, deleted: bool) {
+        let suffix = if self.is_dir { "/" } else { "" };
+        let marker = if deleted {
+            style("×").red()
+        } else {
+            style("•").yellow()
+        };
+        display_println!(
+            "  {} {}{}  {}",
+            marker,
+            self.path.display(),
+            suffix,
+            style(format_bytes(self.bytes)).dim()
+        );
+    }
+}
+
+/// Everything `vibe config reset` would delete
+#[derive(Debug, Clone, Serialize)]
+pub struct FactoryResetPreview {
+    /// Config file, state file, templates, and generated app configs
+    pub config_files: Vec<ResetPath>,
+    /// Every entry of the cache directory, which is deleted whole
+    pub cache_databases: Vec<ResetPath>,
+    /// Items in the trash, only emptied after a question of their own
+    pub trash_items: usize,
+    pub total_bytes: u64,
+}
+
+impl FactoryResetPreview {
+    pub fn new(config_files: &[PathBuf], cache_dir: &Path, trash_items: usize) -> Self {
+        let config_files: Vec<ResetPath> = config_files
+            .iter()
+            .filter_map(|path| ResetPath::measure(path))
+            .collect();
+        let mut cache_databases: Vec<ResetPath> = std::fs::read_dir(cache_dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter_map(|entry| ResetPath::measure(&entry.path()))
+            .collect();
+        cache_databases.sort_by(|a, b| a.path.cmp(&b.path));
+        let total_bytes = config_files
+            .iter()
+            .chain(&cache_databases)
+            .map(|path| path.bytes)
+            .sum();
+        Self {
+            config_files,
+            cache_databases,
+            trash_items,
+            total_bytes,
+        }
+    }
+
+    pub fn print(&self) {
+        display_println!("{} Files to be deleted:", style("🗑️").red());
+        if self.config_files.is_empty() {
+            display_println!("  {}", style("(none)").dim());
+        }
+        for path in &self.config_files {
+            path.print(true);
+        }
+        display_println!();
+        display_println!("{} Cache databases to be deleted:", style("🗑️").red());
+        if self.cache_databases.is_empty() {
+            display_println!("  {}", style("(none)").dim());
+        }
+        for path in &self.cache_databases {
+            path.print(true);
+        }
+        display_println!();
+        if self.trash_items > 0 {
+            display_println!(
+                "{} {} items in the trash are kept unless you also choose to empty it",
+                style("ℹ️").blue(),
+                self.trash_items
+            );
+        }
+        display_println!(
+            "{} {} in total",
+            style("Σ").bold(),
+            format_bytes(self.total_bytes)
+        );
+    }
+}
+
+/// A repository entry `vibe git reset` would clear from the config
+#[derive(Debug, Clone, Serialize)]
+pub struct ClearedRepository {
+    pub name: String,
+    pub path: PathBuf,
+    pub url: Option<String>,
+    pub apps: Vec<String>,
+}
+
+/// Everything `vibe git reset` would change
+#[derive(Debug, Clone, Serialize)]
+pub struct RepositoryResetPreview {
+    pub config_path: PathBuf,
+    pub repositories: Vec<ClearedRepository>,
+    /// Cache databases whose entries for these repositories are removed
+    pub cache_databases: Vec<ResetPath>,
+}
+
+impl RepositoryResetPreview {
+    pub fn print(&self) {
+        display_println!(
+            "{} Entries to be cleared from {}:",
+            style("📋").blue(),
+            self.config_path.display()
+        );
+        for repo in &self.repositories {
+            let mut details = vec![repo.path.display().to_string()];
+            if let Some(url) = &repo.url {
+                details.push(url.clone());
+            }
+            if !repo.apps.is_empty() {
+                details.push(format!("apps: {}", repo.apps.join(", ")));
+            }
+            display_println!(
+                "  {} {} {}",
+                style("→").dim(),
+                style(&repo.name).cyan(),
+                style(format!("({})", details.join(", "))).dim()
+            );
+        }
+        if !self.cache_databases.is_empty() {
+            display_println!();
+            display_println!(
+                "{} Cache databases whose entries for these repositories are removed:",
+                style("🗃️").blue()
+            );
+            for path in &self.cache_databases {
+                path.print(false);
+            }
+        }
+        display_println!();
+        display_println!("{} Repository folders are not deleted", style("ℹ️").blue());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_factory_preview_measures_files_and_caches() {
+        let dir = TempDir::new().unwrap();
+        let config = dir.path().join("config.yaml");
+        std::fs::write(&config, "x".repeat(10)).unwrap();
+        let templates = dir.path().join("templates/vscode");
+        std::fs::create_dir_all(&templates).unwrap();
+        std::fs::write(templates.join("default.json"), "x".repeat(5)).unwrap();
+        let cache = dir.path().join("cache");
+        std::fs::create_dir(&cache).unwrap();
+        std::fs::write(cache.join("git_status.db"), "x".repeat(7)).unwrap();
+
+        let files = [
+            config.clone(),
+            dir.path().join("templates"),
+            dir.path().join("missing.json"),
+        ];
+        let preview = FactoryResetPreview::new(&files, &cache, 2);
+        assert_eq!(preview.config_files.len(), 2);
+        assert_eq!(preview.config_files[0].bytes, 10);
+        assert!(preview.config_files[1].is_dir);
+        assert_eq!(preview.config_files[1].bytes, 5);
+        assert_eq!(preview.cache_databases[0].path, cache.join("git_status.db"));
+        assert_eq!(preview.total_bytes, 22);
+
+        // Nothing to delete when the cache directory doesn't exist
+        let empty = FactoryResetPreview::new(&[], &dir.path().join("none"), 0);
+        assert!(empty.cache_databases.is_empty());
+        assert_eq!(empty.total_bytes, 0);
+    }
+}
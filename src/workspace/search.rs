@@ -0,0 +1,352 @@
+//! Searching repository contents for `vibe grep`
+//!
+//! Each repository is searched with ripgrep when it is installed and with
+//! `git grep` otherwise. Both skip files ignored by the repository's
+//! `.gitignore`. Repositories are searched in parallel, and each keeps at
+//! most `max_per_repo` matches; the rest are only counted.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::process::Command;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+use crate::display_println;
+use crate::output::theme::{paint, Role};
+use crate::utils::platform::resolve_command;
+use crate::workspace::operations::default_max_parallel_git;
+
+/// Matches kept per repository unless `--max-results` says otherwise
+pub const DEFAULT_MAX_PER_REPO: usize = 20;
+
+/// Snippets longer than this are cut, since minified files make huge lines
+const MAX_SNIPPET_CHARS: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchTool {
+    Ripgrep,
+    GitGrep,
+}
+
+impl SearchTool {
+    /// Ripgrep if it is on the PATH, otherwise `git grep`
+    pub async fn detect() -> Self {
+        let ripgrep = Command::new(resolve_command("rg"))
+            .arg("--version")
+            .output()
+            .await
+            .is_ok_and(|output| output.status.success());
+        if ripgrep {
+            SearchTool::Ripgrep
+        } else {
+            SearchTool::GitGrep
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SearchOptions {
+    pub pattern: String,
+    /// Only search files matching this glob, e.g. `*.rs`
+    pub glob: Option<String>,
+    pub max_per_repo: usize,
+}
+
+/// One matching line
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SearchMatch {
+    /// Relative to the repository root
+    pub file: PathBuf,
+    pub line: u64,
+    /// 1-based byte column of the first match on the line
+    pub column: u64,
+    pub snippet: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RepoSearchResult {
+    pub repo: String,
+    pub path: PathBuf,
+    pub matches: Vec<SearchMatch>,
+    /// Matches found beyond `max_per_repo`
+    pub more_matches: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Search `targets` (repository name and checkout path), in the order given
+pub async fn search_repositories(
+    targets: Vec<(String, PathBuf)>,
+    options: &SearchOptions,
+) -> Vec<RepoSearchResult> {
+    let tool = SearchTool::detect().await;
+    let semaphore = Arc::new(Semaphore::new(default_max_parallel_git()));
+    let mut tasks = JoinSet::new();
+
+    for (index, (repo, path)) in targets.into_iter().enumerate() {
+        let semaphore = Arc::clone(&semaphore);
+        let options = options.clone();
+        tasks.spawn(async move {
+            let _permit = semaphore.acquire_owned().await.ok();
+            let result = match search_repository(tool, &path, &options).await {
+                Ok(matches) => {
+                    let more_matches = matches.len().saturating_sub(options.max_per_repo);
+                    RepoSearchResult {
+                        repo,
+                        path,
+                        matches: matches.into_iter().take(options.max_per_repo).collect(),
+                        more_matches,
+                        error: None,
+                    }
+                }
+                Err(e) => RepoSearchResult {
+                    repo,
+                    path,
+                    matches: Vec::new(),
+                    more_matches: 0,
+                    error: Some(e.to_string()),
+                },
+            };
+            (index, result)
+        });
+    }
+
+    let mut results = Vec::new();
+    while let Some(joined) = tasks.join_next().await {
+        if let Ok(result) = joined {
+            results.push(result);
+        }
+    }
+    results.sort_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+async fn search_repository(
+    tool: SearchTool,
+    repo: &Path,
+    options: &SearchOptions,
+) -> Result<Vec<SearchMatch>> {
+    let mut command = match tool {
+        SearchTool::Ripgrep => {
+            let mut command = Command::new(resolve_command("rg"));
+            command.args(["--json", "--no-config"]);
+            if let Some(glob) = &options.glob {
+                command.args(["--glob", glob]);
+            }
+            command.arg("-e").arg(&options.pattern).arg(".");
+            command
+        }
+        SearchTool::GitGrep => {
+            let mut command = Command::new(resolve_command("git"));
+            command
+                .args([
+                    "grep",
+                    "--untracked",
+                    "--exclude-standard",
+                    "-I",
+                    "-n",
+                    "--column",
+                    "-z",
+                    "--no-color",
+                    "-e",
+                ])
+                .arg(&options.pattern);
+            if let Some(glob) = &options.glob {
+                command.arg("--").arg(glob);
+            }
+            command
+        }
+    };
+    let output = command.current_dir(repo).output().await?;
+
+    // Both tools exit with 1 when nothing matched
+    if !output.status.success() && output.status.code() != Some(1) {
+        anyhow::bail!(
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+                .lines()
+                .next()
+                .unwrap_or("search failed")
+        );
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(match tool {
+        SearchTool::Ripgrep => parse_ripgrep(&stdout),
+        SearchTool::GitGrep => parse_git_grep(&stdout),
+    })
+}
+
+#[derive(Deserialize)]
+struct RipgrepMessage {
+    #[serde(rename = "type")]
+    kind: String,
+    data: Option<RipgrepMatch>,
+}
+
+#[derive(Deserialize)]
+struct RipgrepMatch {
+    path: Option<RipgrepText>,
+    lines: Option<RipgrepText>,
+    line_number: Option<u64>,
+    #[serde(default)]
+    submatches: Vec<RipgrepSubmatch>,
+}
+
+#[derive(Deserialize)]
+struct RipgrepText {
+    text: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RipgrepSubmatch {
+    start: u64,
+}
+
+/// Parse `rg --json` output; non-UTF-8 paths and lines are skipped
+fn parse_ripgrep(output: &str) -> Vec<SearchMatch> {
+    output
+        .lines()
+        .filter_map(|line| serde_json::from_str::<RipgrepMessage>(line).ok())
+        .filter(|message| message.kind == "match")
+        .filter_map(|message| {
+            let data = message.data?;
+            let file = data.path?.text?;
+            Some(SearchMatch {
+                file: PathBuf::from(file.strip_prefix("./").unwrap_or(&file)),
+                line: data.line_number?,
+                column: data.submatches.first().map_or(1, |sub| sub.start + 1),
+                snippet: snippet(&data.lines?.text?),
+            })
+        })
+        .collect()
+}
+
+/// Parse `git grep -n --column -z` output: `file\0line\0column\0text`
+fn parse_git_grep(output: &str) -> Vec<SearchMatch> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(4, '\0');
+            let file = fields.next()?;
+            let line_number = fields.next()?.parse().ok()?;
+            let column = fields.next()?.parse().ok()?;
+            Some(SearchMatch {
+                file: PathBuf::from(file),
+                line: line_number,
+                column,
+                snippet: snippet(fields.next().unwrap_or_default()),
+            })
+        })
+        .collect()
+}
+
+fn snippet(line: &str) -> String {
+    let line = line.trim();
+    match line.char_indices().nth(MAX_SNIPPET_CHARS) {
+        Some((end, _)) => format!("{}…", &line[..end]),
+        None => line.to_string(),
+    }
+}
+
+/// Print matches as `repo/file:line:column: snippet`
+pub fn print_results(results: &[RepoSearchResult]) {
+    let mut total = 0;
+    for result in results {
+        if let Some(error) = &result.error {
+            display_println!(
+                "{} {}",
+                paint(Role::Accent, &result.repo),
+                paint(Role::Error, format!("search failed: {error}"))
+            );
+            continue;
+        }
+        for found in &result.matches {
+            display_println!(
+                "{}{} {}",
+                paint(Role::Accent, &result.repo),
+                paint(
+                    Role::Dim,
+                    format!("/{}:{}:{}:", found.file.display(), found.line, found.column)
+                ),
+                found.snippet
+            );
+        }
+        if result.more_matches > 0 {
+            display_println!(
+                "{}",
+                paint(
+                    Role::Dim,
+                    format!(
+                        "  … {} more matches in {}",
+                        result.more_matches, result.repo
+                    )
+                )
+            );
+        }
+        total += result.matches.len() + result.more_matches;
+    }
+
+    let repos = results
+        .iter()
+        .filter(|result| !result.matches.is_empty())
+        .count();
+    if total == 0 {
+        display_println!("No matches");
+    } else {
+        display_println!(
+            "{}",
+            paint(
+                Role::Dim,
+                format!("{total} matches in {repos} repositories")
+            )
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ripgrep() {
+        let output = concat!(
+            r#"{"type":"begin","data":{"path":{"text":"./src/lib.rs"}}}"#,
+            "\n",
+            r#"{"type":"match","data":{"path":{"text":"./src/lib.rs"},"lines":{"text":"    pub fn connect() {\n"},"line_number":12,"absolute_offset":200,"submatches":[{"match":{"text":"connect"},"start":11,"end":18}]}}"#,
+            "\n",
+            r#"{"type":"end","data":{"path":{"text":"./src/lib.rs"}}}"#,
+            "\n",
+        );
+        assert_eq!(
+            parse_ripgrep(output),
+            vec![SearchMatch {
+                file: PathBuf::from("src/lib.rs"),
+                line: 12,
+                column: 12,
+                snippet: "pub fn connect() {".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_git_grep() {
+        let output =
+            "src/a: b.rs\x003\x005\x00let x = connect();\nREADME.md\x001\x001\x00connect\n";
+        let matches = parse_git_grep(output);
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[0].file, PathBuf::from("src/a: b.rs"));
+        assert_eq!((matches[0].line, matches[0].column), (3, 5));
+        assert_eq!(matches[0].snippet, "let x = connect();");
+        assert_eq!(matches[1].file, PathBuf::from("README.md"));
+    }
+
+    #[test]
+    fn test_snippet_is_trimmed_and_capped() {
+        assert_eq!(snippet("  indented\n"), "indented");
+        let long = "é".repeat(MAX_SNIPPET_CHARS + 10);
+        assert_eq!(snippet(&long).chars().count(), MAX_SNIPPET_CHARS + 1);
+    }
+}
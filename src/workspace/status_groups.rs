@@ -0,0 +1,383 @@
+//! Buckets for `vibe git status --group-by`
+//!
+//! [`StatusGrouping`] decides which buckets a repository belongs to: its
+//! parent directory, its configured groups, the owner in its remote URL, or
+//! the language the provider reported for it. Only `group` can put a
+//! repository in more than one bucket.
+
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+use super::config::{repo_abs_path, StatusGroupBy, WorkspaceConfig};
+use super::operations::GitStatus;
+use super::repo_analyzer::extract_organization_from_url;
+use crate::cache::RepositoryCache;
+use crate::git::pull_requests::github_repo_from_url;
+
+/// Bucket of repositories at the workspace root with `dir`
+pub const ROOT_BUCKET: &str = ".";
+/// Bucket of repositories without a recognizable owner with `org`
+pub const OTHER_BUCKET: &str = "Other";
+/// Bucket of repositories in no configured group with `group`
+pub const UNGROUPED_BUCKET: &str = "Ungrouped";
+/// Bucket of repositories the provider reported no language for
+pub const UNKNOWN_LANGUAGE_BUCKET: &str = "Unknown";
+
+/// Decides the buckets of each repository for one grouping
+#[derive(Debug, Clone)]
+pub struct StatusGrouping {
+    group_by: StatusGroupBy,
+    root: PathBuf,
+    /// Repository name to the configured groups it is in
+    memberships: HashMap<String, Vec<String>>,
+    /// Repository name to its primary language
+    languages: HashMap<String, String>,
+}
+
+/// Repositories of one bucket, as indexes into the grouped list
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatusBucket {
+    /// Empty with `none`
+    pub name: String,
+    pub members: Vec<BucketMember>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BucketMember {
+    pub index: usize,
+    /// Other buckets the repository is listed in
+    pub also_in: Vec<String>,
+}
+
+/// Clean, dirty, and ahead/behind counts of a bucket
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct StatusRollup {
+    pub clean: usize,
+    pub dirty: usize,
+    /// Ahead of or behind the upstream branch
+    pub out_of_sync: usize,
+}
+
+impl StatusRollup {
+    pub fn add(&mut self, status: &GitStatus) {
+        if status.clean {
+            self.clean += 1;
+        } else {
+            self.dirty += 1;
+        }
+        if status.ahead > 0 || status.behind > 0 {
+            self.out_of_sync += 1;
+        }
+    }
+}
+
+/// `vibe git status --json --group-by ...`: statuses nested under their
+/// bucket
+#[derive(Debug, Clone, Serialize)]
+pub struct GroupedStatus {
+    pub group_by: StatusGroupBy,
+    pub groups: Vec<StatusGroup>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StatusGroup {
+    pub name: String,
+    #[serde(flatten)]
+    pub rollup: StatusRollup,
+    pub repositories: Vec<GitStatus>,
+}
+
+impl StatusGrouping {
+    /// Grouping from the configuration alone; `language` puts every
+    /// repository under [`UNKNOWN_LANGUAGE_BUCKET`] until languages are loaded
+    pub fn new(group_by: StatusGroupBy, config: &WorkspaceConfig) -> Self {
+        let mut memberships: HashMap<String, Vec<String>> = HashMap::new();
+        for group in &config.groups {
+            for repo in &group.repos {
+                memberships
+                    .entry(repo.clone())
+                    .or_default()
+                    .push(group.name.clone());
+            }
+        }
+        Self {
+            group_by,
+            root: config.workspace.root.clone(),
+            memberships,
+            languages: HashMap::new(),
+        }
+    }
+
+    /// [`Self::new`], with languages from the cached repository listings of
+    /// each GitHub owner when grouping by language
+    pub async fn load(
+        group_by: StatusGroupBy,
+        config: &WorkspaceConfig,
+        cache: Option<&RepositoryCache>,
+    ) -> Self {
+        let mut grouping = Self::new(group_by, config);
+        if group_by == StatusGroupBy::Language {
+            if let Some(cache) = cache {
+                grouping.languages = cached_languages(config, cache).await;
+            }
+        }
+        grouping
+    }
+
+    /// Buckets of the repository called `name`, checked out at `path`
+    pub fn keys(&self, name: &str, path: &Path, url: Option<&str>) -> Vec<String> {
+        let key = match self.group_by {
+            StatusGroupBy::None => String::new(),
+            StatusGroupBy::Dir => match path.strip_prefix(&self.root) {
+                Ok(relative) => relative
+                    .parent()
+                    .filter(|parent| !parent.as_os_str().is_empty())
+                    .map(|parent| parent.display().to_string())
+                    .unwrap_or_else(|| ROOT_BUCKET.to_string()),
+                Err(_) => path
+                    .parent()
+                    .map(|parent| parent.display().to_string())
+                    .unwrap_or_else(|| ROOT_BUCKET.to_string()),
+            },
+            StatusGroupBy::Org => url
+                .and_then(extract_organization_from_url)
+                .unwrap_or_else(|| OTHER_BUCKET.to_string()),
+            StatusGroupBy::Language => self
+                .languages
+                .get(name)
+                .cloned()
+                .unwrap_or_else(|| UNKNOWN_LANGUAGE_BUCKET.to_string()),
+            StatusGroupBy::Group => {
+                return match self.memberships.get(name) {
+                    Some(groups) if !groups.is_empty() => groups.clone(),
+                    _ => vec![UNGROUPED_BUCKET.to_string()],
+                }
+            }
+        };
+        vec![key]
+    }
+
+    /// Sort `repos` (name, checkout, remote URL) into buckets ordered by
+    /// name, keeping the order of `repos` within each bucket
+    pub fn bucket<'a>(
+        &self,
+        repos: impl IntoIterator<Item = (&'a str, &'a Path, Option<&'a str>)>,
+    ) -> Vec<StatusBucket> {
+        let mut buckets: BTreeMap<String, Vec<BucketMember>> = BTreeMap::new();
+        for (index, (name, path, url)) in repos.into_iter().enumerate() {
+            let keys = self.keys(name, path, url);
+            for key in &keys {
+                let also_in = keys.iter().filter(|other| *other != key).cloned().collect();
+                buckets
+                    .entry(key.clone())
+                    .or_default()
+                    .push(BucketMember { index, also_in });
+            }
+        }
+        buckets
+            .into_iter()
+            .map(|(name, members)| StatusBucket { name, members })
+            .collect()
+    }
+
+    /// Nest `statuses` under their buckets, with a rollup per bucket
+    pub fn group_statuses(
+        &self,
+        config: &WorkspaceConfig,
+        statuses: &[GitStatus],
+    ) -> GroupedStatus {
+        let urls: HashMap<&str, Option<&str>> = config
+            .repositories
+            .iter()
+            .map(|repo| (repo.name.as_str(), repo.url.as_deref()))
+            .collect();
+        let paths: Vec<PathBuf> = statuses
+            .iter()
+            .map(|status| {
+                config
+                    .repositories
+                    .iter()
+                    .find(|repo| repo.name == status.repository_name)
+                    .map(|repo| repo_abs_path(config, repo))
+                    .unwrap_or_else(|| PathBuf::from(&status.path))
+            })
+            .collect();
+
+        let buckets = self.bucket(statuses.iter().zip(&paths).map(|(status, path)| {
+            let name = status.repository_name.as_str();
+            (
+                name,
+                path.as_path(),
+                urls.get(name)
+                    .copied()
+                    .flatten()
+                    .or(status.remote_url.as_deref()),
+            )
+        }));
+
+        let groups = buckets
+            .into_iter()
+            .map(|bucket| {
+                let mut rollup = StatusRollup::default();
+                let repositories = bucket
+                    .members
+                    .iter()
+                    .map(|member| {
+                        let status = &statuses[member.index];
+                        rollup.add(status);
+                        status.clone()
+                    })
+                    .collect();
+                StatusGroup {
+                    name: bucket.name,
+                    rollup,
+                    repositories,
+                }
+            })
+            .collect();
+        GroupedStatus {
+            group_by: self.group_by,
+            groups,
+        }
+    }
+}
+
+/// Primary language of each configured GitHub repository whose owner's
+/// listing is cached (by `vibe git clone --all` and similar)
+async fn cached_languages(
+    config: &WorkspaceConfig,
+    cache: &RepositoryCache,
+) -> HashMap<String, String> {
+    cached_remote_repositories(config, cache)
+        .await
+        .into_iter()
+        .filter_map(|(name, remote)| Some((name, remote.language?)))
+        .collect()
+}
+
+/// The cached GitHub listing entry of each configured repository, keyed by
+/// repository name, for owners whose listing is cached
+pub async fn cached_remote_repositories(
+    config: &WorkspaceConfig,
+    cache: &RepositoryCache,
+) -> HashMap<String, crate::git::Repository> {
+    let mut by_owner: HashMap<String, Vec<(&str, String)>> = HashMap::new();
+    for repo in &config.repositories {
+        let Some(full_name) = repo.url.as_deref().and_then(github_repo_from_url) else {
+            continue;
+        };
+        if let Some((owner, _)) = full_name.split_once('/') {
+            by_owner
+                .entry(owner.to_string())
+                .or_default()
+                .push((repo.name.as_str(), full_name.clone()));
+        }
+    }
+
+    let mut remotes = HashMap::new();
+    for (owner, repos) in by_owner {
+        let listing = match cache.get_repo_listing(&owner).await {
+            Ok(Some(listing)) => listing,
+            Ok(None) => continue,
+            Err(e) => {
+                debug!("Failed to read cached repositories of {}: {}", owner, e);
+                continue;
+            }
+        };
+        let Ok(remote) =
+            serde_json::from_str::<Vec<crate::git::Repository>>(&listing.repositories_json)
+        else {
+            continue;
+        };
+        for (name, full_name) in repos {
+            if let Some(found) = remote
+                .iter()
+                .find(|r| r.full_name.eq_ignore_ascii_case(&full_name))
+            {
+                remotes.insert(name.to_string(), found.clone());
+            }
+        }
+    }
+    remotes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workspace::config::RepositoryGroup;
+
+    fn group(name: &str, repos: &[&str]) -> RepositoryGroup {
+        RepositoryGroup {
+            name: name.to_string(),
+            repos: repos.iter().map(|repo| repo.to_string()).collect(),
+            apps: HashMap::new(),
+            default_app: None,
+            default_template: None,
+        }
+    }
+
+    fn config() -> WorkspaceConfig {
+        let mut config = WorkspaceConfig::default();
+        config.workspace.root = PathBuf::from("/ws");
+        config.groups.push(group("web", &["app", "api"]));
+        config.groups.push(group("backend", &["api"]));
+        config
+    }
+
+    fn repos() -> Vec<(&'static str, &'static Path, Option<&'static str>)> {
+        vec![
+            (
+                "app",
+                Path::new("/ws/acme/app"),
+                Some("git@github.com:acme/app.git"),
+            ),
+            (
+                "api",
+                Path::new("/ws/acme/api"),
+                Some("https://github.com/other/api"),
+            ),
+            ("notes", Path::new("/ws/notes"), None),
+        ]
+    }
+
+    fn names(buckets: &[StatusBucket]) -> Vec<&str> {
+        buckets.iter().map(|b| b.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_bucket_by_dir_and_org() {
+        let config = config();
+        let by_dir = StatusGrouping::new(StatusGroupBy::Dir, &config).bucket(repos());
+        assert_eq!(names(&by_dir), vec![ROOT_BUCKET, "acme"]);
+        assert_eq!(by_dir[1].members.len(), 2);
+
+        let by_org = StatusGrouping::new(StatusGroupBy::Org, &config).bucket(repos());
+        assert_eq!(names(&by_org), vec![OTHER_BUCKET, "acme", "other"]);
+
+        let flat = StatusGrouping::new(StatusGroupBy::None, &config).bucket(repos());
+        assert_eq!(names(&flat), vec![""]);
+        assert_eq!(flat[0].members.len(), 3);
+    }
+
+    #[test]
+    fn test_bucket_by_group_lists_repos_in_each_group() {
+        let config = config();
+        let buckets = StatusGrouping::new(StatusGroupBy::Group, &config).bucket(repos());
+        assert_eq!(names(&buckets), vec![UNGROUPED_BUCKET, "backend", "web"]);
+
+        let backend = &buckets[1];
+        assert_eq!(
+            backend.members,
+            vec![BucketMember {
+                index: 1,
+                also_in: vec!["web".to_string()],
+            }]
+        );
+        assert!(buckets[2]
+            .members
+            .iter()
+            .any(|member| member.index == 0 && member.also_in.is_empty()));
+    }
+}
@@ -0,0 +1,239 @@
+//! Git statuses shared by the menu, quick launcher, `vibe git status`, and MCP
+//!
+//! [`WorkspaceManager::refresh_statuses`] reads the git status cache once for
+//! a scope, keeps entries no older than the caller's staleness limit, and
+//! re-reads the rest with bounded concurrency, caching what it reads. Every
+//! consumer gets the same [`RepoStatusSnapshot`], so a repository shows the
+//! same dirty and ahead/behind values wherever it appears.
+//!
+//! [`WorkspaceManager::refresh_statuses`]: super::WorkspaceManager::refresh_statuses
+
+use chrono::Utc;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Duration;
+use tracing::debug;
+
+use crate::cache::{CachedGitStatus, GitStatusCache};
+use crate::workspace::operations::{collect_git_statuses, GitStatus, StatusTarget};
+
+/// How long the menu and launcher trust a cached status, matching the git
+/// status cache's own TTL
+pub const STATUS_CACHE_TTL: Duration = Duration::from_secs(5 * 60);
+
+/// Which repositories a status refresh covers
+#[derive(Debug, Clone, Copy)]
+pub enum StatusScope<'a> {
+    /// Every configured repository
+    All,
+    /// The repositories `vibe git status` covers, optionally in one group
+    Targets(Option<&'a str>),
+    /// The named repositories
+    Repos(&'a [String]),
+}
+
+/// The git status of each repository in a scope
+#[derive(Debug, Clone, Default)]
+pub struct RepoStatusSnapshot {
+    /// Statuses in configuration order
+    pub statuses: Vec<GitStatus>,
+    /// Repositories whose cached status is too old and hasn't been re-read
+    pub pending: Vec<String>,
+    /// Repositories whose status couldn't be read, with the reason
+    pub failures: Vec<(String, String)>,
+}
+
+impl RepoStatusSnapshot {
+    pub fn get(&self, name: &str) -> Option<&GitStatus> {
+        self.statuses
+            .iter()
+            .find(|status| status.repository_name == name)
+    }
+
+    /// Statuses keyed by repository name
+    pub fn by_name(&self) -> HashMap<String, GitStatus> {
+        self.statuses
+            .iter()
+            .map(|status| (status.repository_name.clone(), status.clone()))
+            .collect()
+    }
+
+    pub fn dirty_count(&self) -> usize {
+        self.statuses.iter().filter(|s| s.is_dirty()).count()
+    }
+
+    pub fn behind_count(&self) -> usize {
+        self.statuses.iter().filter(|s| s.behind > 0).count()
+    }
+}
+
+/// A scope's statuses as found in the cache, and the repositories whose
+/// status is too old. Owns everything it needs, so [`Self::run`] can be
+/// spawned as a background task.
+pub struct StatusRefresh {
+    snapshot: RepoStatusSnapshot,
+    stale: Vec<StatusTarget>,
+    order: Vec<String>,
+    cache: Option<PathBuf>,
+    max_parallel: usize,
+}
+
+impl StatusRefresh {
+    /// Sort each target into fresh, stale, or unreadable. With no
+    /// `max_staleness`, as while the workspace root is offline, a cached
+    /// status of any age is used and nothing is re-read.
+    pub(crate) fn new(
+        entries: Vec<(StatusTarget, Option<CachedGitStatus>)>,
+        max_staleness: Option<Duration>,
+        cache: Option<PathBuf>,
+        max_parallel: usize,
+    ) -> Self {
+        let mut snapshot = RepoStatusSnapshot::default();
+        let mut stale = Vec::new();
+        let mut order = Vec::with_capacity(entries.len());
+        for (target, cached) in entries {
+            order.push(target.name.clone());
+            let fresh = cached.filter(|cached| {
+                max_staleness.is_none_or(|max| {
+                    let age = Utc::now().signed_duration_since(cached.last_updated);
+                    age.to_std().unwrap_or_default() <= max
+                })
+            });
+            if let Some(cached) = fresh {
+                let mut status = GitStatus::from(cached);
+                status.compare_ref = target.compare_ref.clone();
+                snapshot.statuses.push(status);
+            } else if !target.path.exists() {
+                snapshot.failures.push((
+                    target.name.clone(),
+                    format!("not found at {}", target.path.display()),
+                ));
+            } else if max_staleness.is_none() {
+                snapshot.failures.push((
+                    target.name.clone(),
+                    "no cached status while offline".to_string(),
+                ));
+            } else {
+                snapshot.pending.push(target.name.clone());
+                stale.push(target);
+            }
+        }
+        Self {
+            snapshot,
+            stale,
+            order,
+            cache,
+            max_parallel,
+        }
+    }
+
+    /// The statuses served from the cache, with stale repositories pending
+    pub fn cached(&self) -> &RepoStatusSnapshot {
+        &self.snapshot
+    }
+
+    /// Whether no status needs re-reading
+    pub fn is_fresh(&self) -> bool {
+        self.stale.is_empty()
+    }
+
+    /// Re-read the stale statuses, cache them, and return the full snapshot
+    pub async fn run(self) -> RepoStatusSnapshot {
+        let Self {
+            mut snapshot,
+            stale,
+            order,
+            cache,
+            max_parallel,
+        } = self;
+        if stale.is_empty() {
+            return snapshot;
+        }
+
+        let cache = cache.map(GitStatusCache::new);
+        for result in collect_git_statuses(stale, max_parallel).await {
+            match result.status {
+                Ok(mut status) => {
+                    status.repository_name = result.name;
+                    if let Some(cache) = &cache {
+                        if let Err(e) = cache.cache_git_status(&status.clone().into()).await {
+                            debug!("Failed to cache git status: {}", e);
+                        }
+                    }
+                    snapshot.statuses.push(status);
+                }
+                Err(e) => snapshot.failures.push((result.name, e.to_string())),
+            }
+        }
+        snapshot.pending.clear();
+
+        let position: HashMap<&str, usize> = order
+            .iter()
+            .enumerate()
+            .map(|(i, name)| (name.as_str(), i))
+            .collect();
+        let rank = |name: &str| position.get(name).copied().unwrap_or(usize::MAX);
+        snapshot
+            .statuses
+            .sort_by_key(|status| rank(&status.repository_name));
+        snapshot.failures.sort_by_key(|(name, _)| rank(name));
+        snapshot
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn cached(name: &str, path: &std::path::Path, age_secs: i64) -> CachedGitStatus {
+        CachedGitStatus {
+            repository_name: name.to_string(),
+            path: path.to_path_buf(),
+            branch: Some("main".to_string()),
+            clean: false,
+            ahead: 1,
+            behind: 2,
+            staged: 0,
+            unstaged: 1,
+            untracked: 0,
+            remote_url: None,
+            last_updated: Utc::now() - chrono::Duration::seconds(age_secs),
+        }
+    }
+
+    #[test]
+    fn test_entries_are_sorted_by_staleness() {
+        let dir = TempDir::new().unwrap();
+        let fresh =
+            StatusTarget::new("fresh", dir.path()).comparing_to(Some("upstream/main".to_string()));
+        let old = StatusTarget::new("old", dir.path());
+        let missing = StatusTarget::new("missing", dir.path().join("missing"));
+        let entries = vec![
+            (fresh, Some(cached("fresh", dir.path(), 10))),
+            (old.clone(), Some(cached("old", dir.path(), 600))),
+            (missing, None),
+        ];
+
+        let refresh = StatusRefresh::new(entries, Some(STATUS_CACHE_TTL), None, 1);
+        let snapshot = refresh.cached();
+        assert_eq!(snapshot.statuses.len(), 1);
+        let status = snapshot.get("fresh").unwrap();
+        assert_eq!((status.ahead, status.behind), (1, 2));
+        assert_eq!(status.compare_ref.as_deref(), Some("upstream/main"));
+        assert_eq!(snapshot.pending, vec!["old".to_string()]);
+        assert_eq!(snapshot.failures[0].0, "missing");
+        assert!(!refresh.is_fresh());
+
+        // Offline, any cached status will do and nothing is re-read
+        let offline = StatusRefresh::new(
+            vec![(old, Some(cached("old", dir.path(), 600)))],
+            None,
+            None,
+            1,
+        );
+        assert!(offline.is_fresh());
+        assert_eq!(offline.cached().dirty_count(), 1);
+        assert_eq!(offline.cached().behind_count(), 1);
+    }
+}
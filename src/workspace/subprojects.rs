@@ -0,0 +1,228 @@
+//! Detecting the packages of a monorepo for `vibe git scan --subprojects`
+//!
+//! pnpm workspaces list package globs under `packages:` in
+//! `pnpm-workspace.yaml`, where `!` patterns exclude matches. Cargo
+//! workspaces list them under `members` in the `[workspace]` table of
+//! `Cargo.toml`, with an optional `exclude`. Matching directories that hold
+//! a `package.json` or `Cargo.toml` become subprojects named after the
+//! directory, or after their relative path when two directories share a name.
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use super::config::Subproject;
+
+/// Subprojects found in one repository
+#[derive(Debug, Clone, Serialize)]
+pub struct SubprojectDetection {
+    pub repo: String,
+    pub subprojects: Vec<Subproject>,
+}
+
+#[derive(Deserialize)]
+struct PnpmWorkspace {
+    #[serde(default)]
+    packages: Vec<String>,
+}
+
+/// Subprojects declared by the pnpm and Cargo workspace files at the root of
+/// `repo`, sorted by name
+pub fn detect(repo: &Path) -> Vec<Subproject> {
+    let mut dirs = Vec::new();
+
+    if let Ok(content) = std::fs::read_to_string(repo.join("pnpm-workspace.yaml")) {
+        if let Ok(workspace) = serde_yaml::from_str::<PnpmWorkspace>(&content) {
+            let (excludes, includes): (Vec<String>, Vec<String>) = workspace
+                .packages
+                .into_iter()
+                .partition(|pattern| pattern.starts_with('!'));
+            let excludes: Vec<String> = excludes
+                .iter()
+                .map(|pattern| pattern.trim_start_matches('!').to_string())
+                .collect();
+            dirs.extend(expand(repo, &includes, &excludes, "package.json"));
+        }
+    }
+
+    if let Ok(content) = std::fs::read_to_string(repo.join("Cargo.toml")) {
+        if let Some(section) = cargo_workspace_section(&content) {
+            let members = toml_string_array(&section, "members");
+            let excludes = toml_string_array(&section, "exclude");
+            dirs.extend(expand(repo, &members, &excludes, "Cargo.toml"));
+        }
+    }
+
+    dirs.sort();
+    dirs.dedup();
+    name_subprojects(dirs)
+}
+
+/// Directories under `repo` matching `includes` but not `excludes` that
+/// hold `manifest`, relative to `repo`
+fn expand(repo: &Path, includes: &[String], excludes: &[String], manifest: &str) -> Vec<PathBuf> {
+    let excludes: Vec<glob::Pattern> = excludes
+        .iter()
+        .filter_map(|pattern| glob::Pattern::new(pattern.trim_end_matches('/')).ok())
+        .collect();
+    let mut dirs = Vec::new();
+
+    for include in includes {
+        let pattern = repo.join(include.trim_end_matches('/'));
+        let Ok(paths) = glob::glob(&pattern.to_string_lossy()) else {
+            continue;
+        };
+        for path in paths.flatten() {
+            if !path.join(manifest).is_file() {
+                continue;
+            }
+            let Ok(relative) = path.strip_prefix(repo) else {
+                continue;
+            };
+            if relative.as_os_str().is_empty()
+                || excludes
+                    .iter()
+                    .any(|exclude| exclude.matches_path(relative))
+            {
+                continue;
+            }
+            dirs.push(relative.to_path_buf());
+        }
+    }
+    dirs
+}
+
+/// Name each directory after its last component, falling back to the whole
+/// relative path for names that would collide
+fn name_subprojects(dirs: Vec<PathBuf>) -> Vec<Subproject> {
+    let short_name = |dir: &Path| {
+        dir.file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    };
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for dir in &dirs {
+        *counts.entry(short_name(dir)).or_default() += 1;
+    }
+
+    let mut subprojects: Vec<Subproject> = dirs
+        .into_iter()
+        .map(|dir| {
+            let name = short_name(&dir);
+            let name = if counts[&name] > 1 {
+                dir.to_string_lossy().replace('\\', "/")
+            } else {
+                name
+            };
+            Subproject { name, path: dir }
+        })
+        .collect();
+    subprojects.sort_by(|a, b| a.name.cmp(&b.name));
+    subprojects
+}
+
+/// The body of the `[workspace]` table of a `Cargo.toml`. Array lines never
+/// start with `[`, so the table ends at the next table header.
+fn cargo_workspace_section(content: &str) -> Option<String> {
+    let mut lines = content
+        .lines()
+        .skip_while(|line| line.trim() != "[workspace]");
+    lines.next()?;
+    Some(
+        lines
+            .take_while(|line| !line.trim_start().starts_with('['))
+            .collect::<Vec<_>>()
+            .join("\n"),
+    )
+}
+
+/// The strings of the array `key = [...]`, which may span several lines
+fn toml_string_array(section: &str, key: &str) -> Vec<String> {
+    let array = Regex::new(&format!(r"(?ms)^\s*{key}\s*=\s*\[(.*?)\]")).expect("valid regex");
+    let string = Regex::new(r#""([^"]*)""#).expect("valid regex");
+    let Some(body) = array.captures(section).and_then(|captures| captures.get(1)) else {
+        return Vec::new();
+    };
+    body.as_str()
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default())
+        .flat_map(|line| string.captures_iter(line).map(|c| c[1].to_string()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn package(repo: &Path, dir: &str, manifest: &str) {
+        std::fs::create_dir_all(repo.join(dir)).unwrap();
+        std::fs::write(repo.join(dir).join(manifest), "").unwrap();
+    }
+
+    #[test]
+    fn test_detect_pnpm_workspace() {
+        let repo = TempDir::new().unwrap();
+        std::fs::write(
+            repo.path().join("pnpm-workspace.yaml"),
+            "packages:\n  - 'apps/*'\n  - 'packages/*'\n  - '!packages/internal'\n",
+        )
+        .unwrap();
+        package(repo.path(), "apps/web", "package.json");
+        package(repo.path(), "packages/ui", "package.json");
+        package(repo.path(), "packages/internal", "package.json");
+        std::fs::create_dir_all(repo.path().join("packages/empty")).unwrap();
+
+        let names: Vec<String> = detect(repo.path()).into_iter().map(|s| s.name).collect();
+        assert_eq!(names, ["ui", "web"]);
+    }
+
+    #[test]
+    fn test_detect_cargo_workspace() {
+        let repo = TempDir::new().unwrap();
+        std::fs::write(
+            repo.path().join("Cargo.toml"),
+            "[workspace]\nmembers = [\n    \"crates/*\", # libraries\n    \"cli\",\n]\n\
+             exclude = [\"crates/scratch\"]\n\n[workspace.package]\nversion = \"0.1.0\"\n",
+        )
+        .unwrap();
+        package(repo.path(), "crates/core", "Cargo.toml");
+        package(repo.path(), "crates/scratch", "Cargo.toml");
+        package(repo.path(), "cli", "Cargo.toml");
+        package(repo.path(), "tools/cli", "Cargo.toml");
+
+        let subprojects = detect(repo.path());
+        assert_eq!(
+            subprojects,
+            [
+                Subproject {
+                    name: "cli".to_string(),
+                    path: PathBuf::from("cli"),
+                },
+                Subproject {
+                    name: "core".to_string(),
+                    path: PathBuf::from("crates/core"),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_colliding_names_use_relative_paths() {
+        let subprojects = name_subprojects(vec![
+            PathBuf::from("apps/api"),
+            PathBuf::from("services/api"),
+            PathBuf::from("web"),
+        ]);
+        let names: Vec<&str> = subprojects.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["apps/api", "services/api", "web"]);
+    }
+
+    #[test]
+    fn test_no_workspace_files() {
+        let repo = TempDir::new().unwrap();
+        std::fs::write(repo.path().join("Cargo.toml"), "[package]\nname = \"x\"\n").unwrap();
+        assert!(detect(repo.path()).is_empty());
+    }
+}
@@ -1,16 +1,46 @@
 use anyhow::{Context, Result};
 use console::style;
-use std::path::Path;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
 
 use super::config::{Repository, WorkspaceConfig};
-use super::discovery::get_current_branch;
-use super::repo_analyzer::WorkspaceAnalysis;
+use super::constants::get_removed_repos_path;
+use super::discovery::{get_current_branch, get_upstream_url};
+use super::events::{self, Event};
+use super::history::{self, EventKind, UndoAction};
+use super::operations::default_max_parallel_git;
+use super::removed_repos;
+use super::repo_analyzer::{RepoInfo, WorkspaceAnalysis};
+use crate::display_println;
 use crate::git;
+use crate::ui::prompts::{confirm_destructive, DestructiveAction};
+use crate::utils::fs::unavailable_mount;
+use crate::utils::platform::resolve_command;
+
+/// Whether `vibe git scan --clean` removes missing repositories from the
+/// config, and whether it asks first
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CleanMode {
+    Off,
+    Confirm,
+    /// Confirmed up front with `--yes`
+    Yes,
+}
 
 pub struct SyncOptions {
     pub import_new: bool,
     pub restore_missing: bool,
     pub clean_missing: bool,
+    /// Remove without asking
+    pub clean_confirmed: bool,
+    /// Restore only these repositories; empty restores every missing one
+    pub restore_only: Vec<String>,
+    /// `--force-online`: a missing root was deleted, not unmounted
+    pub force_online: bool,
 }
 
 impl SyncOptions {
@@ -19,6 +49,9 @@ impl SyncOptions {
             import_new: false,
             restore_missing: false,
             clean_missing: false,
+            clean_confirmed: false,
+            restore_only: Vec::new(),
+            force_online: false,
         }
     }
 
@@ -32,8 +65,23 @@ impl SyncOptions {
         self
     }
 
-    pub fn with_clean(mut self) -> Self {
-        self.clean_missing = true;
+    /// Restore only the named missing repositories
+    pub fn with_restore_only(mut self, names: Vec<String>) -> Self {
+        self.restore_missing = true;
+        self.restore_only = names;
+        self
+    }
+
+    pub fn with_clean_mode(mut self, mode: CleanMode) -> Self {
+        self.clean_missing = mode != CleanMode::Off;
+        self.clean_confirmed = mode == CleanMode::Yes;
+        self
+    }
+
+    /// Treat missing checkouts as deleted even where their volume looks
+    /// unmounted
+    pub fn with_force_online(mut self) -> Self {
+        self.force_online = true;
         self
     }
 
@@ -42,53 +90,94 @@ impl SyncOptions {
     }
 }
 
+/// Repositories affected by the import/restore/clean actions of a scan
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SyncReport {
+    pub imported: Vec<String>,
+    pub restored: Vec<String>,
+    pub removed: Vec<String>,
+    /// Missing repositories `--clean` left in the config, because their
+    /// volume isn't mounted or the removal wasn't confirmed
+    pub kept: Vec<String>,
+    /// Repositories that could not be restored because no remote URL is configured
+    pub skipped: Vec<String>,
+    /// Missing repositories configured outside the scanned root, which
+    /// restore and clean leave alone
+    pub outside_root: Vec<String>,
+    pub failed: Vec<SyncFailure>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SyncFailure {
+    pub name: String,
+    pub error: String,
+}
+
+impl SyncReport {
+    fn has_changes(&self) -> bool {
+        !self.imported.is_empty() || !self.restored.is_empty() || !self.removed.is_empty()
+    }
+}
+
 pub async fn execute_sync_operations(
     workspace_root: &Path,
     config: &mut WorkspaceConfig,
     analysis: &WorkspaceAnalysis,
     options: &SyncOptions,
-) -> Result<()> {
-    let mut changes_made = false;
+) -> Result<SyncReport> {
+    let mut report = SyncReport::default();
 
     if options.import_new {
-        changes_made |= import_new_repositories(workspace_root, config, analysis).await?;
+        import_new_repositories(workspace_root, config, analysis, &mut report).await?;
+    }
+
+    if options.restore_missing || options.clean_missing {
+        skip_outside_root(workspace_root, analysis, &mut report);
     }
 
     if options.restore_missing {
-        changes_made |= restore_missing_repositories(workspace_root, config, analysis).await?;
+        restore_missing_repositories(workspace_root, config, analysis, options, &mut report)
+            .await?;
     }
 
     if options.clean_missing {
-        changes_made |= clean_missing_repositories(config, analysis).await?;
+        clean_missing_repositories(workspace_root, config, analysis, options, &mut report).await?;
     }
 
-    if changes_made {
-        println!(
+    if report.has_changes() {
+        display_println!(
             "{} Configuration updated successfully",
             style("✓").green().bold()
         );
     }
 
-    Ok(())
+    Ok(report)
 }
 
 async fn import_new_repositories(
     workspace_root: &Path,
     config: &mut WorkspaceConfig,
     analysis: &WorkspaceAnalysis,
-) -> Result<bool> {
+    report: &mut SyncReport,
+) -> Result<()> {
     let new_repos = analysis.get_new_repos();
 
     if new_repos.is_empty() {
-        return Ok(false);
+        return Ok(());
     }
 
-    println!(
+    display_println!(
         "{} Importing {} new repositories...",
         style("📥").blue(),
         new_repos.len()
     );
 
+    let mut taken: HashSet<String> = config.repositories.iter().map(|r| r.name.clone()).collect();
+    let mut plain_names: HashMap<&str, usize> = HashMap::new();
+    for repo_info in &new_repos {
+        *plain_names.entry(repo_info.name.as_str()).or_default() += 1;
+    }
+
     for repo_info in new_repos {
         let relative_path = repo_info
             .path
@@ -96,12 +185,22 @@ async fn import_new_repositories(
             .unwrap_or(&repo_info.path)
             .to_path_buf();
 
-        let mut repo = Repository::new(repo_info.name.clone(), relative_path);
+        let name = if taken.contains(&repo_info.name) || plain_names[repo_info.name.as_str()] > 1 {
+            qualified_name(repo_info)
+        } else {
+            repo_info.name.clone()
+        };
+        taken.insert(name.clone());
+
+        let mut repo = Repository::new(name.clone(), relative_path);
 
         // Add remote URL if available
         if let Some(url) = &repo_info.remote_url {
             repo = repo.with_url(url.clone());
         }
+        if let Ok(Some(upstream)) = get_upstream_url(&repo_info.path) {
+            repo = repo.with_upstream(upstream);
+        }
 
         // Try to get current branch
         if let Ok(Some(branch)) = get_current_branch(&repo_info.path) {
@@ -110,132 +209,382 @@ async fn import_new_repositories(
 
         config.add_repository(repo);
 
-        println!(
-            "  {} Added {}",
-            style("✓").green(),
-            style(&repo_info.name).cyan()
+        display_println!("  {} Added {}", style("✓").green(), style(&name).cyan());
+        report.imported.push(name);
+    }
+
+    Ok(())
+}
+
+/// `owner/repo` for repositories whose plain name is already in use. The
+/// owner comes from the remote URL, or else the parent directory, as in the
+/// `owner` and `host-owner` clone layouts.
+fn qualified_name(repo_info: &RepoInfo) -> String {
+    let owner = repo_info.organization.clone().or_else(|| {
+        repo_info
+            .path
+            .parent()
+            .and_then(|parent| parent.file_name())
+            .map(|name| name.to_string_lossy().into_owned())
+    });
+    match owner {
+        Some(owner) => format!("{owner}/{}", repo_info.name),
+        None => repo_info.name.clone(),
+    }
+}
+
+/// Restore and clean can't tell a repository outside the root that was
+/// deleted from one on an unmounted volume, so they leave those alone
+fn skip_outside_root(workspace_root: &Path, analysis: &WorkspaceAnalysis, report: &mut SyncReport) {
+    for repo_info in analysis.get_missing_outside_root() {
+        display_println!(
+            "  {} Skipping {}: {} is outside {}; restore or remove it by hand",
+            style("⚠️").yellow(),
+            style(&repo_info.name).cyan(),
+            repo_info.path.display(),
+            workspace_root.display()
         );
+        report.outside_root.push(repo_info.name.clone());
     }
+}
+
+/// Missing repositories a restore would clone, narrowed to the names given
+/// with `--restore-only`
+fn restore_candidates<'a>(
+    analysis: &'a WorkspaceAnalysis,
+    options: &SyncOptions,
+) -> Vec<&'a RepoInfo> {
+    analysis
+        .get_missing_in_root()
+        .into_iter()
+        .filter(|repo_info| {
+            options.restore_only.is_empty() || options.restore_only.contains(&repo_info.name)
+        })
+        .collect()
+}
 
-    Ok(true)
+/// A repository being re-cloned, with its URL in the preferred protocol
+pub(crate) struct PlannedRestore {
+    pub name: String,
+    pub url: String,
+    pub target_path: PathBuf,
 }
 
 async fn restore_missing_repositories(
     workspace_root: &Path,
-    _config: &WorkspaceConfig,
+    config: &WorkspaceConfig,
     analysis: &WorkspaceAnalysis,
-) -> Result<bool> {
-    let missing_repos = analysis.get_missing_repos();
+    options: &SyncOptions,
+    report: &mut SyncReport,
+) -> Result<()> {
+    let missing_repos = restore_candidates(analysis, options);
 
     if missing_repos.is_empty() {
-        return Ok(false);
+        if !options.restore_only.is_empty() {
+            display_println!(
+                "{} None of {} are missing; nothing to restore",
+                style("ℹ️").blue(),
+                options.restore_only.join(", ")
+            );
+        }
+        return Ok(());
     }
 
-    println!(
-        "{} Restoring {} missing repositories...",
+    let preferences = config.preferences.clone().unwrap_or_default();
+    let mut planned = Vec::new();
+
+    display_println!(
+        "{} Restoring {} missing repositories:",
         style("🔄").blue(),
         missing_repos.len()
     );
-
-    let _git_config = git::GitConfig::default();
-
-    for repo_info in missing_repos {
-        if let Some(config_repo) = &repo_info.config_repo {
-            if let Some(url) = &config_repo.url {
-                let target_path = workspace_root.join(&config_repo.path);
-
-                // Ensure parent directory exists
-                if let Some(parent) = target_path.parent() {
-                    tokio::fs::create_dir_all(parent).await.with_context(|| {
-                        format!("Failed to create parent directory: {}", parent.display())
-                    })?;
-                }
-
-                println!(
-                    "  {} Cloning {} from {}...",
+    for config_repo in missing_repos.iter().filter_map(|r| r.config_repo.as_ref()) {
+        match &config_repo.url {
+            Some(url) => {
+                let url = match preferences.clone_protocol {
+                    Some(protocol) => protocol.apply(url),
+                    None => url.clone(),
+                };
+                display_println!(
+                    "  {} {} {}",
                     style("⬇️").blue(),
                     style(&config_repo.name).cyan(),
-                    style(url).dim()
+                    style(&url).dim()
                 );
-
-                // Use the existing clone functionality
-                match clone_repository(url, &target_path).await {
-                    Ok(_) => {
-                        println!(
-                            "    {} Successfully restored {}",
-                            style("✓").green(),
-                            style(&config_repo.name).cyan()
-                        );
-                    }
-                    Err(e) => {
-                        println!(
-                            "    {} Failed to restore {}: {}",
-                            style("✗").red(),
-                            style(&config_repo.name).cyan(),
-                            e
-                        );
-                    }
-                }
-            } else {
-                println!(
-                    "  {} Skipping {} (no remote URL configured)",
+                planned.push(PlannedRestore {
+                    name: config_repo.name.clone(),
+                    url,
+                    target_path: workspace_root.join(&config_repo.path),
+                });
+            }
+            None => {
+                display_println!(
+                    "  {} {} {}",
                     style("⚠️").yellow(),
-                    style(&config_repo.name).cyan()
+                    style(&config_repo.name).cyan(),
+                    style("no remote URL configured, will be skipped").dim()
                 );
+                report.skipped.push(config_repo.name.clone());
             }
         }
     }
+    display_println!();
+
+    let outcomes = clone_in_parallel(planned, preferences.max_parallel_git).await?;
+    for (restore, result) in outcomes {
+        match result {
+            Ok(()) => {
+                git::lfs::check_after_clone(&restore.target_path, preferences.lfs_auto_pull).await;
+                report.restored.push(restore.name);
+            }
+            Err(e) => report.failed.push(SyncFailure {
+                name: restore.name,
+                error: e.to_string(),
+            }),
+        }
+    }
+
+    print_restore_summary(report);
+    Ok(())
+}
+
+/// Clone each planned repository, at most `max_parallel` at a time (the
+/// `max_parallel_git` preference), printing `[done/total]` as each finishes.
+/// Outcomes come back in the planned order.
+pub(crate) async fn clone_in_parallel(
+    planned: Vec<PlannedRestore>,
+    max_parallel: Option<usize>,
+) -> Result<Vec<(PlannedRestore, Result<()>)>> {
+    let max_parallel = max_parallel
+        .filter(|_| !crate::output::is_deterministic())
+        .unwrap_or_else(default_max_parallel_git);
+    let semaphore = Arc::new(Semaphore::new(max_parallel.max(1)));
+    let total = planned.len();
+    let mut tasks = JoinSet::new();
+    for (index, restore) in planned.into_iter().enumerate() {
+        let semaphore = Arc::clone(&semaphore);
+        tasks.spawn(async move {
+            let _permit = semaphore.acquire_owned().await.ok();
+            let result = clone_repository(&restore.url, &restore.target_path).await;
+            (index, restore, result)
+        });
+    }
+
+    let mut outcomes = Vec::with_capacity(total);
+    let mut done = 0;
+    while let Some(joined) = tasks.join_next().await {
+        let (index, restore, result) = joined.context("Restore task failed")?;
+        done += 1;
+        match &result {
+            Ok(()) => {
+                display_println!(
+                    "  [{done}/{total}] {} {}",
+                    style("✓").green(),
+                    style(&restore.name).cyan()
+                );
+                events::emit(Event::RepoCloned {
+                    name: restore.name.clone(),
+                    path: restore.target_path.clone(),
+                    url: restore.url.clone(),
+                });
+            }
+            Err(e) => display_println!(
+                "  [{done}/{total}] {} {}: {}",
+                style("✗").red(),
+                style(&restore.name).cyan(),
+                e
+            ),
+        }
+        outcomes.push((index, restore, result));
+    }
 
-    Ok(true)
+    // Report in the planned order, whatever order the clones finished in
+    outcomes.sort_by_key(|(index, _, _)| *index);
+    Ok(outcomes
+        .into_iter()
+        .map(|(_, restore, result)| (restore, result))
+        .collect())
+}
+
+fn print_restore_summary(report: &SyncReport) {
+    display_println!();
+    display_println!(
+        "{} Restore: {} restored, {} failed, {} skipped",
+        style("📋").blue(),
+        style(report.restored.len()).green().bold(),
+        style(report.failed.len()).red().bold(),
+        style(report.skipped.len()).yellow().bold()
+    );
+    for failure in &report.failed {
+        display_println!(
+            "  {} {}: {}",
+            style("✗").red(),
+            style(&failure.name).cyan(),
+            failure.error
+        );
+    }
+    for name in &report.skipped {
+        display_println!(
+            "  {} {}: no remote URL configured",
+            style("⚠️").yellow(),
+            style(name).cyan()
+        );
+    }
 }
 
 async fn clean_missing_repositories(
+    workspace_root: &Path,
     config: &mut WorkspaceConfig,
     analysis: &WorkspaceAnalysis,
-) -> Result<bool> {
-    let missing_repos = analysis.get_missing_repos();
+    options: &SyncOptions,
+    report: &mut SyncReport,
+) -> Result<()> {
+    let missing_repos: Vec<&Repository> = analysis
+        .get_missing_in_root()
+        .into_iter()
+        .filter_map(|repo_info| repo_info.config_repo.as_ref())
+        .collect();
 
     if missing_repos.is_empty() {
-        return Ok(false);
+        return Ok(());
     }
 
-    println!(
-        "{} Removing {} missing repositories from config...",
-        style("🧹").blue(),
-        missing_repos.len()
-    );
+    // A checkout on a drive that isn't plugged in looks just like a deleted one
+    let mut to_remove = Vec::new();
+    for config_repo in missing_repos {
+        let path = workspace_root.join(&config_repo.path);
+        let unavailable = if options.force_online {
+            None
+        } else if workspace_root.exists() {
+            unavailable_mount(&path)
+        } else {
+            Some(workspace_root.to_path_buf())
+        };
+        match unavailable {
+            Some(mount) => {
+                display_println!(
+                    "  {} Keeping {}: {} is not mounted",
+                    style("⚠️").yellow(),
+                    style(&config_repo.name).cyan(),
+                    mount.display()
+                );
+                report.kept.push(config_repo.name.clone());
+            }
+            None => to_remove.push(config_repo.clone()),
+        }
+    }
 
-    for repo_info in missing_repos {
-        if let Some(config_repo) = &repo_info.config_repo {
-            // Remove from config
-            config.repositories.retain(|r| r.name != config_repo.name);
+    if !to_remove.is_empty() {
+        display_println!(
+            "{} Config entries to remove ({} missing from disk):",
+            style("🧹").blue(),
+            to_remove.len()
+        );
+        for repo in &to_remove {
+            display_println!(
+                "  {} {} {} {}",
+                style("→").dim(),
+                style(&repo.name).cyan(),
+                style(repo.path.display()).dim(),
+                style(repo.url.as_deref().unwrap_or("(no remote URL)")).dim()
+            );
+        }
 
-            println!(
-                "  {} Removed {}",
-                style("✓").green(),
-                style(&config_repo.name).cyan()
+        let prompt = format!("Remove these {} entries from the config?", to_remove.len());
+        let level = config
+            .preferences
+            .as_ref()
+            .map(|p| p.confirmations)
+            .unwrap_or_default();
+        let confirmed =
+            options.clean_confirmed || confirm_destructive(DestructiveAction::new(&prompt), level)?;
+
+        if confirmed {
+            let removed_path = get_removed_repos_path();
+            removed_repos::record(&removed_path, to_remove.clone()).await?;
+            for repo in to_remove {
+                config.repositories.retain(|r| r.name != repo.name);
+                display_println!(
+                    "  {} Removed {}",
+                    style("✓").green(),
+                    style(&repo.name).cyan()
+                );
+                history::record(
+                    EventKind::RepoRemoved,
+                    format!("Removed missing repository {} from the config", repo.name),
+                    Some(UndoAction::RestoreRepository {
+                        repository: Box::new(repo.clone()),
+                    }),
+                )
+                .await;
+                report.removed.push(repo.name);
+            }
+            display_println!(
+                "  {} Saved to {}; bring them back with `vibe config repo readd-removed`",
+                style("ℹ️").blue(),
+                removed_path.display()
             );
+        } else {
+            display_println!("  {} Nothing removed", style("ℹ️").blue());
+            report
+                .kept
+                .extend(to_remove.into_iter().map(|repo| repo.name));
         }
     }
 
-    Ok(true)
+    display_println!(
+        "{} {} removed, {} missing but kept",
+        style("🧹").blue(),
+        report.removed.len(),
+        report.kept.len()
+    );
+    Ok(())
 }
 
-// Simple clone implementation - in a real implementation, we'd use the git module
+/// Clone `url` into `target_path`, creating its parent directories. The
+/// clone goes to a hidden sibling first and is renamed into place once
+/// complete, so an interrupted clone never looks like a checkout; its
+/// leftovers are cleared by the next attempt.
 async fn clone_repository(url: &str, target_path: &Path) -> Result<()> {
-    use std::process::Command;
+    use tokio::process::Command;
+
+    let parent = target_path
+        .parent()
+        .context("Clone destination has no parent directory")?;
+    tokio::fs::create_dir_all(parent)
+        .await
+        .with_context(|| format!("Failed to create parent directory: {}", parent.display()))?;
+    let staging = partial_clone_path(target_path);
+    if staging.exists() {
+        tokio::fs::remove_dir_all(&staging)
+            .await
+            .with_context(|| format!("Failed to remove {}", staging.display()))?;
+    }
+    git::lfs::warn_before_clone(url).await;
 
-    let output = Command::new("git")
-        .args(["clone", url, &target_path.to_string_lossy()])
+    let output = Command::new(resolve_command("git"))
+        .args(["clone", "--quiet", url, &staging.to_string_lossy()])
         .output()
+        .await
         .with_context(|| "Failed to execute git clone")?;
 
     if !output.status.success() {
         let error_msg = String::from_utf8_lossy(&output.stderr);
-        anyhow::bail!("Git clone failed: {}", error_msg);
+        anyhow::bail!("Git clone failed: {}", error_msg.trim());
     }
 
-    Ok(())
+    tokio::fs::rename(&staging, target_path)
+        .await
+        .with_context(|| format!("Failed to move clone into {}", target_path.display()))
+}
+
+/// Where a clone into `target_path` is staged until it completes
+fn partial_clone_path(target_path: &Path) -> PathBuf {
+    let name = target_path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    target_path.with_file_name(format!(".{name}.vibe-partial"))
 }
 
 pub fn print_sync_summary(analysis: &WorkspaceAnalysis, options: &SyncOptions) {
@@ -243,13 +592,13 @@ pub fn print_sync_summary(analysis: &WorkspaceAnalysis, options: &SyncOptions) {
         return;
     }
 
-    println!("{} Sync Operations Summary", style("📋").blue().bold());
-    println!("{}", "─".repeat(40));
+    display_println!("{} Sync Operations Summary", style("📋").blue().bold());
+    display_println!("{}", "─".repeat(40));
 
     if options.import_new {
         let new_count = analysis.get_new_repos().len();
         if new_count > 0 {
-            println!(
+            display_println!(
                 "• {} new repositories will be imported",
                 style(new_count).green().bold()
             );
@@ -257,9 +606,9 @@ pub fn print_sync_summary(analysis: &WorkspaceAnalysis, options: &SyncOptions) {
     }
 
     if options.restore_missing {
-        let missing_count = analysis.get_missing_repos().len();
+        let missing_count = restore_candidates(analysis, options).len();
         if missing_count > 0 {
-            println!(
+            display_println!(
                 "• {} missing repositories will be restored",
                 style(missing_count).blue().bold()
             );
@@ -267,21 +616,23 @@ pub fn print_sync_summary(analysis: &WorkspaceAnalysis, options: &SyncOptions) {
     }
 
     if options.clean_missing {
-        let missing_count = analysis.get_missing_repos().len();
+        let missing_count = analysis.get_missing_in_root().len();
         if missing_count > 0 {
-            println!(
-                "• {} missing repositories will be removed from config",
+            display_println!(
+                "• {} missing repositories will be reviewed for removal from config",
                 style(missing_count).red().bold()
             );
         }
     }
 
-    println!();
+    display_println!();
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::workspace::repo_analyzer::RepoStatus;
+    use std::path::PathBuf;
 
     #[test]
     fn test_sync_options_creation() {
@@ -302,9 +653,24 @@ mod tests {
         assert!(options.has_actions());
     }
 
+    #[test]
+    fn test_qualified_name() {
+        let repo = RepoInfo::new(
+            "api".to_string(),
+            PathBuf::from("/work/github.com/globex/api"),
+            RepoStatus::New,
+        );
+        assert_eq!(qualified_name(&repo), "globex/api");
+
+        let repo = repo.with_remote_url("git@github.com:acme/api.git".to_string());
+        assert_eq!(qualified_name(&repo), "acme/api");
+    }
+
     #[test]
     fn test_sync_options_conflict() {
-        let options = SyncOptions::new().with_restore().with_clean();
+        let options = SyncOptions::new()
+            .with_restore()
+            .with_clean_mode(CleanMode::Confirm);
 
         assert!(options.restore_missing);
         assert!(options.clean_missing);
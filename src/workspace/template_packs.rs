@@ -0,0 +1,392 @@
+//! Template packs: app templates shared as a git repository or directory
+//!
+//! A pack has a `vibe-pack.yaml` manifest naming it and its templates under
+//! `templates/<app>/<name>.<ext>`. `vibe apps template install` checks every
+//! template against its app's format and installs them all, or none, as
+//! `<pack>-<name>` so they can't collide with local templates. Which
+//! templates came from which pack is kept in `packs.yaml` in the templates
+//! directory; installing a pack again replaces its templates.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use tokio::process::Command;
+
+use super::templates::TemplateManager;
+use crate::git::GitError;
+use crate::output::exit::CommandError;
+
+/// Manifest file at the root of a template pack
+pub const PACK_MANIFEST: &str = "vibe-pack.yaml";
+
+/// Record of installed packs, in the templates directory
+const INSTALLED_FILE: &str = "packs.yaml";
+
+/// Contents of `vibe-pack.yaml`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackManifest {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// A template installed from a pack, by its installed name
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackTemplate {
+    pub app: String,
+    pub name: String,
+}
+
+/// An installed pack in `packs.yaml`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstalledPack {
+    pub source: String,
+    pub installed_at: DateTime<Utc>,
+    #[serde(default)]
+    pub templates: Vec<PackTemplate>,
+}
+
+/// What `install` put in place
+#[derive(Debug, Clone, Serialize)]
+pub struct PackInstallReport {
+    pub name: String,
+    pub source: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub templates: Vec<PackTemplate>,
+    /// Templates of an earlier install that the new one no longer has
+    pub removed: Vec<PackTemplate>,
+}
+
+/// File format of an app's templates
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TemplateFormat {
+    Yaml,
+    Json,
+    Lua,
+}
+
+impl TemplateFormat {
+    fn for_app(app: &str) -> Option<Self> {
+        match app {
+            "warp" => Some(Self::Yaml),
+            "iterm2" | "vscode" | "cursor" | "windsurf" => Some(Self::Json),
+            "wezterm" => Some(Self::Lua),
+            _ => None,
+        }
+    }
+
+    fn accepts_extension(self, extension: &str) -> bool {
+        match self {
+            Self::Yaml => matches!(extension, "yaml" | "yml"),
+            // Editor templates are stored as .yaml, which JSON also parses as
+            Self::Json => matches!(extension, "json" | "yaml"),
+            Self::Lua => extension == "lua",
+        }
+    }
+
+    /// Why `content` isn't a valid template of this format, with its
+    /// `{{placeholders}}` filled in
+    fn check(self, content: &str) -> Option<String> {
+        static PLACEHOLDER: OnceLock<Regex> = OnceLock::new();
+        let placeholder = PLACEHOLDER.get_or_init(|| Regex::new(r"\{\{[^}]*\}\}").unwrap());
+        let filled = placeholder.replace_all(content, "vibe");
+        match self {
+            Self::Yaml => serde_yaml::from_str::<serde_yaml::Value>(&filled)
+                .err()
+                .map(|e| format!("not valid YAML: {e}")),
+            Self::Json => serde_json::from_str::<serde_json::Value>(&filled)
+                .err()
+                .map(|e| format!("not valid JSON: {e}")),
+            Self::Lua => (!filled
+                .lines()
+                .any(|line| line.trim_start().starts_with("return")))
+            .then(|| "a WezTerm config must return its config table".to_string()),
+        }
+    }
+}
+
+/// Names must be usable as file name prefixes
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Installed packs by name
+pub async fn installed(template_root: &Path) -> Result<BTreeMap<String, InstalledPack>> {
+    let path = template_root.join(INSTALLED_FILE);
+    match tokio::fs::read_to_string(&path).await {
+        Ok(contents) => serde_yaml::from_str(&contents)
+            .with_context(|| format!("Failed to parse {}", path.display())),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(BTreeMap::new()),
+        Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    }
+}
+
+async fn save_installed(
+    template_root: &Path,
+    packs: &BTreeMap<String, InstalledPack>,
+) -> Result<()> {
+    tokio::fs::create_dir_all(template_root).await?;
+    let path = template_root.join(INSTALLED_FILE);
+    tokio::fs::write(&path, serde_yaml::to_string(packs)?)
+        .await
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Pack each of `app`'s installed templates came from, by template name
+pub async fn origins(template_root: &Path, app: &str) -> Result<HashMap<String, String>> {
+    Ok(installed(template_root)
+        .await?
+        .into_iter()
+        .flat_map(|(pack, installed)| {
+            installed
+                .templates
+                .into_iter()
+                .filter(|template| template.app == app)
+                .map(move |template| (template.name, pack.clone()))
+        })
+        .collect())
+}
+
+/// A pack's templates read from `root` and checked, as app, name in the
+/// pack, and content
+async fn read_templates(root: &Path) -> Result<Vec<(String, String, String)>> {
+    let templates_dir = root.join("templates");
+    let mut templates = Vec::new();
+    let mut problems = Vec::new();
+
+    let mut apps = tokio::fs::read_dir(&templates_dir).await.map_err(|_| {
+        CommandError::Usage(format!(
+            "Template pack has no templates/ directory at {}",
+            templates_dir.display()
+        ))
+    })?;
+    while let Some(app_dir) = apps.next_entry().await? {
+        if !app_dir.file_type().await?.is_dir() {
+            continue;
+        }
+        let app = app_dir.file_name().to_string_lossy().to_string();
+        let Some(format) = TemplateFormat::for_app(&app) else {
+            problems.push(format!(
+                "templates/{app}: no app named '{app}' has templates"
+            ));
+            continue;
+        };
+
+        let mut files = tokio::fs::read_dir(app_dir.path()).await?;
+        while let Some(file) = files.next_entry().await? {
+            let path = file.path();
+            if !file.file_type().await?.is_file() {
+                continue;
+            }
+            let relative = format!("templates/{app}/{}", file.file_name().to_string_lossy());
+            let (Some(stem), Some(extension)) = (
+                path.file_stem().and_then(|s| s.to_str()),
+                path.extension().and_then(|e| e.to_str()),
+            ) else {
+                problems.push(format!("{relative}: no template name or extension"));
+                continue;
+            };
+            if !is_valid_name(stem) {
+                problems.push(format!(
+                    "{relative}: names may only use letters, digits, '-' and '_'"
+                ));
+                continue;
+            }
+            if !format.accepts_extension(extension) {
+                problems.push(format!("{relative}: .{extension} isn't a {app} template"));
+                continue;
+            }
+            let content = tokio::fs::read_to_string(&path)
+                .await
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            match format.check(&content) {
+                Some(problem) => problems.push(format!("{relative}: {problem}")),
+                None => templates.push((app.clone(), stem.to_string(), content)),
+            }
+        }
+    }
+
+    if !problems.is_empty() {
+        return Err(CommandError::Usage(format!(
+            "Template pack has invalid templates, nothing was installed:\n  {}",
+            problems.join("\n  ")
+        ))
+        .into());
+    }
+    if templates.is_empty() {
+        return Err(CommandError::Usage(format!(
+            "Template pack has no templates under {}",
+            templates_dir.display()
+        ))
+        .into());
+    }
+    templates.sort();
+    Ok(templates)
+}
+
+/// Install the pack at `source`, a git URL, `owner/repo` on GitHub, or a
+/// local directory, replacing an earlier install of the same pack
+pub async fn install(templates: &TemplateManager, source: &str) -> Result<PackInstallReport> {
+    // Keep the clone alive until the templates are read
+    let (_clone, root) = if Path::new(source).is_dir() {
+        (None, PathBuf::from(source))
+    } else {
+        let clone = tempfile::TempDir::new()?;
+        let url = crate::repository::template::template_url(source);
+        let output = Command::new("git")
+            .args(["clone", "--depth", "1", "--quiet", &url])
+            .arg(clone.path())
+            .output()
+            .await
+            .context("Failed to run git clone")?;
+        if !output.status.success() {
+            return Err(GitError::CloneFailed {
+                message: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            }
+            .into());
+        }
+        let root = clone.path().to_path_buf();
+        (Some(clone), root)
+    };
+
+    let manifest_path = root.join(PACK_MANIFEST);
+    let manifest: PackManifest = match tokio::fs::read_to_string(&manifest_path).await {
+        Ok(contents) => serde_yaml::from_str(&contents).map_err(|e| {
+            CommandError::Usage(format!("{PACK_MANIFEST} in {source} isn't valid: {e}"))
+        })?,
+        Err(_) => {
+            return Err(CommandError::Usage(format!(
+                "{source} isn't a template pack: it has no {PACK_MANIFEST}"
+            ))
+            .into())
+        }
+    };
+    if !is_valid_name(&manifest.name) {
+        return Err(CommandError::Usage(format!(
+            "Template pack name '{}' may only use letters, digits, '-' and '_'",
+            manifest.name
+        ))
+        .into());
+    }
+    let pack_templates = read_templates(&root).await?;
+
+    let template_root = templates.template_root();
+    let mut packs = installed(template_root).await?;
+    let previous = packs
+        .get(&manifest.name)
+        .map(|pack| pack.templates.clone())
+        .unwrap_or_default();
+
+    // A prefixed name can still be taken by a local template or another pack
+    let mut planned = Vec::with_capacity(pack_templates.len());
+    for (app, name, content) in pack_templates {
+        let template = PackTemplate {
+            name: format!("{}-{name}", manifest.name),
+            app,
+        };
+        let existing = templates.list_templates(&template.app).await?;
+        if existing.contains(&template.name) && !previous.contains(&template) {
+            return Err(CommandError::Usage(format!(
+                "A {} template named '{}' already exists and isn't from this pack; delete it first",
+                template.app, template.name
+            ))
+            .into());
+        }
+        planned.push((template, content));
+    }
+
+    let mut removed = Vec::new();
+    for template in previous {
+        if !planned.iter().any(|(planned, _)| *planned == template) {
+            let _ = templates
+                .delete_template(&template.app, &template.name)
+                .await;
+            removed.push(template);
+        }
+    }
+    for (template, content) in &planned {
+        templates
+            .save_template(&template.app, &template.name, content)
+            .await?;
+    }
+
+    let installed_templates: Vec<PackTemplate> =
+        planned.into_iter().map(|(template, _)| template).collect();
+    packs.insert(
+        manifest.name.clone(),
+        InstalledPack {
+            source: source.to_string(),
+            installed_at: Utc::now(),
+            templates: installed_templates.clone(),
+        },
+    );
+    save_installed(template_root, &packs).await?;
+
+    Ok(PackInstallReport {
+        name: manifest.name,
+        source: source.to_string(),
+        description: manifest.description,
+        templates: installed_templates,
+        removed,
+    })
+}
+
+/// Delete the templates installed from pack `name` and forget the pack
+pub async fn uninstall(templates: &TemplateManager, name: &str) -> Result<Vec<PackTemplate>> {
+    let template_root = templates.template_root();
+    let mut packs = installed(template_root).await?;
+    let Some(pack) = packs.remove(name) else {
+        return Err(CommandError::not_found_with_hint(
+            "Template pack",
+            name,
+            "list installed packs with `vibe apps template packs`",
+        )
+        .into());
+    };
+    for template in &pack.templates {
+        // Already deleted by hand is fine
+        let _ = templates
+            .delete_template(&template.app, &template.name)
+            .await;
+    }
+    save_installed(template_root, &packs).await?;
+    Ok(pack.templates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_templates_are_checked_with_placeholders_filled() {
+        let yaml = TemplateFormat::for_app("warp").unwrap();
+        assert_eq!(
+            yaml.check("name: {{workspace_name}} - {{repo_name}}\n"),
+            None
+        );
+        assert!(yaml.check("name: [unclosed\n").is_some());
+
+        let json = TemplateFormat::for_app("vscode").unwrap();
+        assert_eq!(
+            json.check(r#"{"folders": [{"path": "{{repo_path}}"}]}"#),
+            None
+        );
+        assert!(json.check("{\"folders\": ").is_some());
+        assert!(json.accepts_extension("yaml") && !json.accepts_extension("lua"));
+
+        let lua = TemplateFormat::for_app("wezterm").unwrap();
+        assert_eq!(lua.check("local config = {}\nreturn config\n"), None);
+        assert!(lua.check("local config = {}\n").is_some());
+
+        assert!(TemplateFormat::for_app("notepad").is_none());
+        assert!(is_valid_name("team-layouts_2") && !is_valid_name("../x"));
+    }
+}
@@ -1,9 +1,10 @@
 use anyhow::{Context, Result};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tokio::fs;
 
-use crate::workspace::{Repository, WorkspaceConfig};
+use crate::utils::direnv;
+use crate::workspace::{repo_abs_path, Repository, WorkspaceConfig};
 
 /// Template manager for app configurations
 pub struct TemplateManager {
@@ -15,6 +16,11 @@ impl TemplateManager {
         Self { template_root }
     }
 
+    /// Directory holding every app's template directory
+    pub fn template_root(&self) -> &Path {
+        &self.template_root
+    }
+
     /// Get the template directory for a specific app
     pub fn get_app_template_dir(&self, app: &str) -> PathBuf {
         self.template_root.join(app)
@@ -190,10 +196,8 @@ impl TemplateManager {
         // Common variables
         vars.insert("workspace_name".to_string(), config.workspace.name.clone());
         vars.insert("repo_name".to_string(), repo.name.clone());
-        vars.insert(
-            "repo_path".to_string(),
-            config.workspace.root.join(&repo.path).display().to_string(),
-        );
+        let repo_path = repo_abs_path(config, repo);
+        vars.insert("repo_path".to_string(), repo_path.display().to_string());
         vars.insert(
             "repo_branch".to_string(),
             repo.branch.clone().unwrap_or_else(|| "main".to_string()),
@@ -205,9 +209,28 @@ impl TemplateManager {
         }
 
         // Add configurable command variables with defaults
-        vars.insert("agent_launcher".to_string(), "claude".to_string());
-        vars.insert("git_manager".to_string(), "gitui".to_string());
-        vars.insert("project_commands".to_string(), "just".to_string());
+        // Pane commands start non-interactively, so direnv's shell hook never
+        // runs; load the repository's .envrc explicitly when enabled
+        let enable_direnv = config.preferences.as_ref().is_some_and(|p| p.enable_direnv);
+        let use_direnv = direnv::applies_to(enable_direnv, &repo_path);
+        let command = |name: &str| {
+            if use_direnv {
+                direnv::wrap_command(&repo_path, name)
+            } else {
+                name.to_string()
+            }
+        };
+        vars.insert("agent_launcher".to_string(), command("claude"));
+        vars.insert("git_manager".to_string(), command("gitui"));
+        vars.insert("project_commands".to_string(), command("just"));
+        vars.insert(
+            "direnv_prelude".to_string(),
+            if use_direnv {
+                direnv::SHELL_PRELUDE.to_string()
+            } else {
+                String::new()
+            },
+        );
 
         vars
     }
@@ -533,4 +556,35 @@ mod tests {
 
         assert_eq!(result, "Hello test, your path is /home/user");
     }
+
+    #[test]
+    fn test_direnv_wraps_pane_commands() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join("app")).unwrap();
+        std::fs::write(temp_dir.path().join("app/.envrc"), "use flake\n").unwrap();
+
+        let mut config = WorkspaceConfig::default();
+        config.workspace.root = temp_dir.path().to_path_buf();
+        let repo = Repository::new("app", "app");
+
+        let vars = TemplateManager::create_variables(&config, &repo);
+        assert_eq!(vars["agent_launcher"], "claude");
+        assert_eq!(vars["direnv_prelude"], "");
+
+        config.preferences = Some(crate::workspace::config::Preferences {
+            enable_direnv: true,
+            ..Default::default()
+        });
+        let vars = TemplateManager::create_variables(&config, &repo);
+        let repo_path = temp_dir.path().join("app");
+        assert_eq!(
+            vars["agent_launcher"],
+            direnv::wrap_command(&repo_path, "claude")
+        );
+        assert_eq!(
+            vars["project_commands"],
+            direnv::wrap_command(&repo_path, "just")
+        );
+        assert_eq!(vars["direnv_prelude"], direnv::SHELL_PRELUDE);
+    }
 }
@@ -0,0 +1,401 @@
+//! Trash for removed repository entries and worktrees
+//!
+//! `vibe config repo remove` and worktree removal (including cleanup) put
+//! what is needed to bring the removal back in `trash/<id>/` in the data
+//! directory. `item.yaml` holds the repository's config entry, or the
+//! worktree's repository, path, branch, and commit; a worktree removed with
+//! uncommitted changes also leaves `changes.tar.gz` next to it. Items are
+//! restored with `vibe trash restore <id>` and deleted once they are older
+//! than `preferences.trash_retention_days` (default 30), checked at startup.
+//! Each item is linked to the `vibe history` event of its removal, so
+//! restoring from either place settles the other.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Local, Utc};
+use console::style;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tracing::{debug, warn};
+
+use crate::display_println;
+use crate::output;
+use crate::output::exit::CommandError;
+use crate::output::theme::{paint, Role};
+use crate::workspace::constants::get_trash_dir;
+use crate::workspace::history::{self, EventKind, EventLog, UndoAction};
+use crate::workspace::WorkspaceManager;
+
+/// Days items stay in the trash unless `preferences.trash_retention_days`
+/// says otherwise
+pub const DEFAULT_RETENTION_DAYS: u32 = 30;
+
+const ITEM_FILE: &str = "item.yaml";
+const CHANGES_FILE: &str = "changes.tar.gz";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrashKind {
+    Repository,
+    Worktree,
+}
+
+impl TrashKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TrashKind::Repository => "repository",
+            TrashKind::Worktree => "worktree",
+        }
+    }
+}
+
+/// A removal that can still be restored
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashItem {
+    pub id: String,
+    pub kind: TrashKind,
+    pub removed_at: DateTime<Utc>,
+    pub summary: String,
+    pub restore: UndoAction,
+    /// The `vibe history` event of the removal
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event: Option<i64>,
+}
+
+/// A trash directory claimed before a removal, so the removal can save
+/// files into it; it becomes an item once [`TrashSlot::fill`] writes
+/// `item.yaml`
+pub struct TrashSlot {
+    pub id: String,
+    dir: PathBuf,
+}
+
+impl TrashSlot {
+    /// Where the uncommitted changes of a removed worktree are archived
+    pub fn changes_path(&self) -> PathBuf {
+        self.dir.join(CHANGES_FILE)
+    }
+
+    pub async fn fill(
+        self,
+        summary: &str,
+        restore: UndoAction,
+        event: Option<i64>,
+    ) -> Result<TrashItem> {
+        let item = TrashItem {
+            id: self.id,
+            kind: match restore {
+                UndoAction::RestoreWorktree { .. } => TrashKind::Worktree,
+                UndoAction::RestoreRepository { .. } => TrashKind::Repository,
+            },
+            removed_at: Utc::now(),
+            summary: summary.to_string(),
+            restore,
+            event,
+        };
+        let yaml = serde_yaml::to_string(&item).context("Failed to serialize trash item")?;
+        tokio::fs::write(self.dir.join(ITEM_FILE), yaml)
+            .await
+            .with_context(|| format!("Failed to write trash item {}", item.id))?;
+        Ok(item)
+    }
+
+    /// Give the directory back when the removal didn't happen
+    pub async fn discard(self) {
+        if let Err(e) = tokio::fs::remove_dir_all(&self.dir).await {
+            debug!("Failed to remove trash slot {}: {}", self.id, e);
+        }
+    }
+}
+
+/// The trash directory
+pub struct Trash {
+    dir: PathBuf,
+}
+
+impl Trash {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// The trash in vibe's data directory
+    pub fn open_default() -> Self {
+        Self::new(get_trash_dir())
+    }
+
+    /// Claim a directory for the removal of `name`, named after the time
+    /// and `name`, e.g. `20260301-142210-vibe-ws-fix-auth`
+    pub async fn reserve(&self, name: &str) -> Result<TrashSlot> {
+        tokio::fs::create_dir_all(&self.dir)
+            .await
+            .with_context(|| format!("Failed to create {}", self.dir.display()))?;
+        let slug: String = name
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+            .collect();
+        let base = format!("{}-{}", Utc::now().format("%Y%m%d-%H%M%S"), slug);
+        for attempt in 1.. {
+            let id = if attempt == 1 {
+                base.clone()
+            } else {
+                format!("{base}-{attempt}")
+            };
+            let dir = self.dir.join(&id);
+            match tokio::fs::create_dir(&dir).await {
+                Ok(()) => return Ok(TrashSlot { id, dir }),
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => continue,
+                Err(e) => {
+                    return Err(e).with_context(|| format!("Failed to create {}", dir.display()))
+                }
+            }
+        }
+        unreachable!("the attempts never run out")
+    }
+
+    /// Items, oldest first
+    pub async fn list(&self) -> Result<Vec<TrashItem>> {
+        let mut entries = match tokio::fs::read_dir(&self.dir).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => {
+                return Err(e).with_context(|| format!("Failed to read {}", self.dir.display()))
+            }
+        };
+        let mut items = Vec::new();
+        while let Some(entry) = entries.next_entry().await? {
+            if let Some(item) = read_item(&entry.path()).await {
+                items.push(item);
+            }
+        }
+        items.sort_by(|a, b| a.removed_at.cmp(&b.removed_at).then(a.id.cmp(&b.id)));
+        Ok(items)
+    }
+
+    pub async fn get(&self, id: &str) -> Result<Option<TrashItem>> {
+        // Ids are directory names; anything else can't name an item
+        if id.is_empty() || id.contains(['/', '\\']) || id.starts_with('.') {
+            return Ok(None);
+        }
+        Ok(read_item(&self.dir.join(id)).await)
+    }
+
+    /// The item of `vibe history` event `event`
+    pub async fn find_by_event(&self, event: i64) -> Result<Option<TrashItem>> {
+        Ok(self
+            .list()
+            .await?
+            .into_iter()
+            .find(|item| item.event == Some(event)))
+    }
+
+    pub async fn delete(&self, id: &str) -> Result<()> {
+        let dir = self.dir.join(id);
+        tokio::fs::remove_dir_all(&dir)
+            .await
+            .with_context(|| format!("Failed to delete {}", dir.display()))
+    }
+
+    /// Delete items removed more than `older_than` ago, or all of them, and
+    /// return what was deleted
+    pub async fn empty(&self, older_than: Option<Duration>) -> Result<Vec<TrashItem>> {
+        let cutoff = older_than.map(|age| Utc::now() - age);
+        let mut deleted = Vec::new();
+        for item in self.list().await? {
+            if cutoff.is_some_and(|cutoff| item.removed_at > cutoff) {
+                continue;
+            }
+            self.delete(&item.id).await?;
+            deleted.push(item);
+        }
+        Ok(deleted)
+    }
+
+    /// Delete expired items and slots left by removals that never finished;
+    /// failures are only logged
+    pub async fn sweep(&self, retention_days: u32) {
+        let retention = Duration::days(retention_days.into());
+        match self.empty(Some(retention)).await {
+            Ok(deleted) if !deleted.is_empty() => {
+                debug!("Deleted {} expired trash items", deleted.len());
+            }
+            Ok(_) => {}
+            Err(e) => warn!("Failed to sweep the trash: {}", e),
+        }
+
+        let Ok(mut entries) = tokio::fs::read_dir(&self.dir).await else {
+            return;
+        };
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let path = entry.path();
+            if path.join(ITEM_FILE).exists() {
+                continue;
+            }
+            let abandoned = entry
+                .metadata()
+                .await
+                .and_then(|metadata| metadata.modified())
+                .ok()
+                .and_then(|modified| modified.elapsed().ok())
+                .is_some_and(|age| age.as_secs() > 24 * 3600);
+            if abandoned {
+                let _ = tokio::fs::remove_dir_all(&path).await;
+            }
+        }
+    }
+}
+
+async fn read_item(dir: &Path) -> Option<TrashItem> {
+    let yaml = tokio::fs::read_to_string(dir.join(ITEM_FILE)).await.ok()?;
+    match serde_yaml::from_str(&yaml) {
+        Ok(item) => Some(item),
+        Err(e) => {
+            warn!("Skipping unreadable trash item {}: {}", dir.display(), e);
+            None
+        }
+    }
+}
+
+/// Record a removal in `vibe history` and in the trash, in `slot` when the
+/// removal already claimed one, and return the trash id. Failures are only
+/// logged.
+pub async fn record_removal(
+    slot: Option<TrashSlot>,
+    name: &str,
+    kind: EventKind,
+    summary: String,
+    restore: UndoAction,
+) -> Option<String> {
+    let event = history::record(kind, &summary, Some(restore.clone())).await;
+    let slot = match slot {
+        Some(slot) => slot,
+        None => match Trash::open_default().reserve(name).await {
+            Ok(slot) => slot,
+            Err(e) => {
+                warn!("Failed to put {} in the trash: {}", name, e);
+                return None;
+            }
+        },
+    };
+    match slot.fill(&summary, restore, event).await {
+        Ok(item) => Some(item.id),
+        Err(e) => {
+            warn!("Failed to put {} in the trash: {}", name, e);
+            None
+        }
+    }
+}
+
+/// Bring item `id` back and delete it from the trash, marking its history
+/// event undone
+pub async fn restore(
+    trash: &Trash,
+    id: &str,
+    workspace: &mut WorkspaceManager,
+) -> Result<TrashItem> {
+    let item = trash.get(id).await?.ok_or_else(|| {
+        CommandError::not_found_with_hint(
+            "Trash item",
+            id,
+            "Run 'vibe trash list' to see what can be restored",
+        )
+    })?;
+
+    history::apply(&item.restore, workspace).await?;
+    if let Some(event) = item.event {
+        if let Err(e) = EventLog::open_default().mark_undone(event).await {
+            warn!("Failed to mark history event {} undone: {}", event, e);
+        }
+    }
+    trash.delete(&item.id).await?;
+    Ok(item)
+}
+
+/// Print items as a table, oldest first
+pub fn print_items(items: &[TrashItem], retention_days: u32) {
+    if items.is_empty() {
+        display_println!("The trash is empty");
+        return;
+    }
+
+    let id_width = items.iter().map(|item| item.id.len()).max().unwrap_or(0);
+    display_println!(
+        "{}",
+        paint(
+            Role::Dim,
+            format!(
+                "{:<id_width$}  {:<16}  {:<10}  {:<8}  {}",
+                "ID", "REMOVED", "KIND", "EXPIRES", "WHAT"
+            )
+        )
+    );
+    for item in items {
+        let removed = output::stable_time(
+            item.removed_at
+                .with_timezone(&Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string(),
+        );
+        let left = Duration::days(retention_days.into()) - (Utc::now() - item.removed_at);
+        let expires = output::stable_time(format!("{}d", left.num_days().max(0)));
+        display_println!(
+            "{:<id_width$}  {:<16}  {:<10}  {:<8}  {}",
+            item.id,
+            removed,
+            item.kind.as_str(),
+            expires,
+            item.summary
+        );
+    }
+    display_println!();
+    display_println!(
+        "{}",
+        style("Restore one with 'vibe trash restore <id>'").dim()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workspace::config::Repository;
+    use tempfile::TempDir;
+
+    fn removed_repository(name: &str) -> UndoAction {
+        UndoAction::RestoreRepository {
+            repository: Box::new(Repository::new(name, name)),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reserve_fill_and_empty() {
+        let dir = TempDir::new().unwrap();
+        let trash = Trash::new(dir.path().join("trash"));
+        assert!(trash.list().await.unwrap().is_empty());
+
+        let first = trash.reserve("vibe-ws/fix-auth").await.unwrap();
+        let second = trash.reserve("vibe-ws/fix-auth").await.unwrap();
+        assert!(first.id.ends_with("-vibe-ws-fix-auth"));
+        assert_eq!(second.id, format!("{}-2", first.id));
+        let abandoned = second.id.clone();
+
+        let item = first
+            .fill("Removed repository api", removed_repository("api"), Some(7))
+            .await
+            .unwrap();
+        assert_eq!(item.kind, TrashKind::Repository);
+
+        // Slots that were never filled aren't items
+        let items = trash.list().await.unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(trash.find_by_event(7).await.unwrap().unwrap().id, item.id);
+        assert!(trash.get(&abandoned).await.unwrap().is_none());
+        assert!(trash.get("../trash").await.unwrap().is_none());
+
+        assert!(trash
+            .empty(Some(Duration::days(1)))
+            .await
+            .unwrap()
+            .is_empty());
+        let deleted = trash.empty(None).await.unwrap();
+        assert_eq!(deleted.len(), 1);
+        assert!(trash.get(&item.id).await.unwrap().is_none());
+    }
+}
@@ -0,0 +1,225 @@
+//! Bringing a worktree's base branch up to date before branching from it
+//!
+//! With `worktree.refresh_base_before_create` on (the default), creating a
+//! worktree first fetches the base branch from its upstream and
+//! fast-forwards the local branch. A base branch no worktree has checked out
+//! is updated with `git fetch <remote> <branch>:<branch>`; a checked-out one
+//! is fast-forwarded only when its checkout has no uncommitted changes. When
+//! the refresh fails, because vibe is offline or the branch has diverged,
+//! the worktree starts from the local branch as it was.
+
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::Duration;
+
+use crate::workspace::operations::execute_git_command;
+
+/// What refreshing the base branch did before a worktree was created
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "result", rename_all = "snake_case")]
+pub enum BaseRefresh {
+    /// The base already matched its upstream
+    UpToDate { upstream: String },
+    /// The base was fast-forwarded to its upstream
+    FastForwarded { upstream: String, commits: usize },
+    /// The base could not be refreshed and was used as it was
+    Stale { reason: String },
+}
+
+impl BaseRefresh {
+    /// Short description for the create summary
+    pub fn describe(&self) -> String {
+        match self {
+            Self::UpToDate { upstream } => format!("up to date with {upstream}"),
+            Self::FastForwarded { upstream, commits } => format!(
+                "fast-forwarded {commits} commit{} from {upstream}",
+                if *commits == 1 { "" } else { "s" }
+            ),
+            Self::Stale { reason } => format!("not refreshed: {reason}"),
+        }
+    }
+}
+
+/// Refresh local `branch` of `repo` from its upstream. `checkout` is the
+/// worktree that has the branch checked out, if any. `None` when the branch
+/// has no remote upstream to refresh from.
+pub async fn refresh(repo: &Path, branch: &str, checkout: Option<&Path>) -> Option<BaseRefresh> {
+    let remote = execute_git_command(repo, &["config", &format!("branch.{branch}.remote")])
+        .await
+        .ok()
+        .filter(|remote| remote != ".")?;
+    let merge = execute_git_command(repo, &["config", &format!("branch.{branch}.merge")])
+        .await
+        .ok()?;
+    let before = execute_git_command(repo, &["rev-parse", &format!("refs/heads/{branch}")])
+        .await
+        .ok()?;
+    let upstream = execute_git_command(
+        repo,
+        &[
+            "rev-parse",
+            "--abbrev-ref",
+            &format!("{branch}@{{upstream}}"),
+        ],
+    )
+    .await
+    .unwrap_or_else(|_| format!("{remote}/{branch}"));
+
+    let refreshed = match checkout {
+        None => {
+            execute_git_command(
+                repo,
+                &[
+                    "fetch",
+                    "--quiet",
+                    &remote,
+                    &format!("{merge}:refs/heads/{branch}"),
+                ],
+            )
+            .await
+        }
+        Some(checkout) => fast_forward_checkout(repo, checkout, branch, &remote, &merge).await,
+    };
+    if let Err(e) = refreshed {
+        return Some(BaseRefresh::Stale {
+            reason: last_line(&e),
+        });
+    }
+
+    let after = execute_git_command(repo, &["rev-parse", &format!("refs/heads/{branch}")])
+        .await
+        .unwrap_or_else(|_| before.clone());
+    let commits = execute_git_command(
+        repo,
+        &["rev-list", "--count", &format!("{before}..{after}")],
+    )
+    .await
+    .ok()
+    .and_then(|count| count.parse().ok())
+    .unwrap_or(0);
+    Some(if commits == 0 {
+        BaseRefresh::UpToDate { upstream }
+    } else {
+        BaseRefresh::FastForwarded { upstream, commits }
+    })
+}
+
+async fn fast_forward_checkout(
+    repo: &Path,
+    checkout: &Path,
+    branch: &str,
+    remote: &str,
+    merge: &str,
+) -> Result<String> {
+    execute_git_command(repo, &["fetch", "--quiet", remote, merge]).await?;
+    let changes =
+        execute_git_command(checkout, &["status", "--porcelain", "--untracked-files=no"]).await?;
+    if !changes.is_empty() {
+        bail!(
+            "{branch} is checked out at {} with uncommitted changes",
+            checkout.display()
+        );
+    }
+    execute_git_command(checkout, &["merge", "--ff-only", "--quiet", "@{upstream}"]).await
+}
+
+/// The most telling line of a git failure, which git prints last
+fn last_line(error: &anyhow::Error) -> String {
+    let message = error.to_string();
+    message
+        .lines()
+        .rev()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or(&message)
+        .trim_start_matches("fatal: ")
+        .trim_start_matches("error: ")
+        .to_string()
+}
+
+/// How long ago `commit` was made
+pub async fn commit_age(repo: &Path, commit: &str) -> Option<Duration> {
+    let timestamp: i64 = execute_git_command(repo, &["show", "-s", "--format=%ct", commit])
+        .await
+        .ok()?
+        .parse()
+        .ok()?;
+    let age = chrono::Utc::now().timestamp() - timestamp;
+    Some(Duration::from_secs(age.max(0) as u64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    use tokio::process::Command;
+
+    async fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .current_dir(dir)
+            .status()
+            .await
+            .unwrap();
+        assert!(status.success(), "git {args:?} failed");
+    }
+
+    /// A clone whose `main` is one commit behind origin
+    async fn behind_clone() -> (TempDir, std::path::PathBuf) {
+        let dir = TempDir::new().unwrap();
+        let origin = dir.path().join("origin");
+        std::fs::create_dir(&origin).unwrap();
+        git(&origin, &["init", "-q", "-b", "main"]).await;
+        git(&origin, &["commit", "-q", "--allow-empty", "-m", "one"]).await;
+        git(dir.path(), &["clone", "-q", "origin", "clone"]).await;
+        git(&origin, &["commit", "-q", "--allow-empty", "-m", "two"]).await;
+        let clone = dir.path().join("clone");
+        (dir, clone)
+    }
+
+    #[tokio::test]
+    async fn test_checked_out_base_is_fast_forwarded_when_clean() {
+        let (_dir, clone) = behind_clone().await;
+        let refreshed = refresh(&clone, "main", Some(&clone)).await.unwrap();
+        assert_eq!(
+            refreshed,
+            BaseRefresh::FastForwarded {
+                upstream: "origin/main".to_string(),
+                commits: 1
+            }
+        );
+        assert_eq!(
+            refresh(&clone, "main", Some(&clone)).await.unwrap(),
+            BaseRefresh::UpToDate {
+                upstream: "origin/main".to_string()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_base_that_is_not_checked_out_is_fetched_into() {
+        let (_dir, clone) = behind_clone().await;
+        git(&clone, &["checkout", "-q", "-b", "other"]).await;
+        let refreshed = refresh(&clone, "main", None).await.unwrap();
+        assert_eq!(
+            refreshed.describe(),
+            "fast-forwarded 1 commit from origin/main"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_diverged_base_is_left_stale() {
+        let (_dir, clone) = behind_clone().await;
+        git(&clone, &["commit", "-q", "--allow-empty", "-m", "local"]).await;
+        let refreshed = refresh(&clone, "main", Some(&clone)).await.unwrap();
+        assert!(
+            matches!(refreshed, BaseRefresh::Stale { .. }),
+            "{refreshed:?}"
+        );
+        // A branch without an upstream has nothing to refresh from
+        git(&clone, &["checkout", "-q", "-b", "local-only"]).await;
+        assert_eq!(refresh(&clone, "local-only", Some(&clone)).await, None);
+    }
+}
@@ -160,6 +160,9 @@ mod tests {
             },
             age: Duration::from_secs(3600),
             is_detached: false,
+            protected: false,
+            metadata: Default::default(),
+            base_refresh: None,
         }
     }
 
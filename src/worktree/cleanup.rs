@@ -11,16 +11,30 @@ use std::time::Duration;
 use tokio::process::Command;
 use tracing::{info, warn};
 
+use crate::git::ref_backup::RefBackup;
+use crate::ui::prompts::{confirm_destructive, DestructiveAction};
+use crate::utils::fs::path_starts_with;
+use crate::workspace::config::ConfirmationLevel;
+use crate::workspace::disk_usage::format_bytes;
+use crate::workspace::events::{self, Event};
+use crate::workspace::history::{self, EventKind};
 use crate::worktree::config::WorktreeConfig;
-use crate::worktree::operations::{RemoveOptions, WorktreeOperations};
+use crate::worktree::metadata::WorktreeMetadata;
+use crate::worktree::operations::{RemoveOptions, RemoveOutcome, WorktreeOperations};
+use crate::worktree::orphans;
+use crate::worktree::protection::ProtectionReason;
 use crate::worktree::status::WorktreeInfo;
 
+/// Branch shown for orphaned directories in cleanup results
+pub const ORPHAN_BRANCH_LABEL: &str = "(orphaned)";
+
 /// Different strategies for cleaning up worktrees
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum CleanupStrategy {
     /// Simply remove the worktree (safest, requires manual verification)
     Discard,
-    /// Merge worktree changes into the feature branch before removal
+    /// Merge worktree changes into the branch it was created from (or the
+    /// feature branch named by its task id) before removal
     MergeToFeature,
     /// Push worktree branch to origin before removal (backup)
     BackupToOrigin,
@@ -46,6 +60,9 @@ pub struct CleanupOptions {
     /// Skip interactive confirmations
     pub auto_confirm: bool,
 
+    /// How much confirmation to ask for when not auto-confirming
+    pub confirmations: ConfirmationLevel,
+
     /// Only clean worktrees with specific branch prefix
     pub branch_prefix_filter: Option<String>,
 
@@ -54,6 +71,12 @@ pub struct CleanupOptions {
 
     /// Minimum merge confidence required (0.0-1.0)
     pub min_merge_confidence: f32,
+
+    /// Also delete orphaned directories left in the base dir by crashed runs
+    pub include_orphans: bool,
+
+    /// Only clean worktrees whose `--expires` deadline has passed
+    pub expired_only: bool,
 }
 
 /// Result of cleanup operations
@@ -89,6 +112,10 @@ pub struct WorktreeCleanupResult {
     /// Branch name
     pub branch: String,
 
+    /// Task description and link, if recorded
+    #[serde(flatten)]
+    pub metadata: WorktreeMetadata,
+
     /// Cleanup action taken
     pub action: CleanupAction,
 
@@ -100,6 +127,14 @@ pub struct WorktreeCleanupResult {
 
     /// Safety violations that were ignored (if force was used)
     pub safety_violations: Vec<SafetyViolation>,
+
+    /// Trash item that restores the removed worktree
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trash_id: Option<String>,
+
+    /// Where the deleted branch was backed up
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backup: Option<RefBackup>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -136,6 +171,14 @@ pub enum ViolationSeverity {
     Critical, // Should never be overridden
 }
 
+/// `reason`, noting a branch kept because it is protected
+fn noting_protected_branch(reason: String, outcome: &RemoveOutcome) -> String {
+    match &outcome.protected_branch {
+        Some(_) => format!("{reason}; branch skipped (protected)"),
+        None => reason,
+    }
+}
+
 /// Main cleanup orchestrator
 pub struct WorktreeCleanup {
     config: WorktreeConfig,
@@ -154,6 +197,11 @@ impl WorktreeCleanup {
             options.strategy
         );
 
+        let _lock = if options.dry_run {
+            None
+        } else {
+            Some(self.operations.lock("worktree cleanup").await?)
+        };
         let all_worktrees = self.operations.list_worktrees().await?;
         let mut report = CleanupReport {
             total_evaluated: all_worktrees.len(),
@@ -201,38 +249,160 @@ impl WorktreeCleanup {
                     report.worktree_results.push(WorktreeCleanupResult {
                         path: worktree.path.clone(),
                         branch: worktree.branch.clone(),
+                        metadata: worktree.metadata.clone(),
                         action: CleanupAction::Failed,
                         reason: "Processing error".to_string(),
                         error: Some(e.to_string()),
                         safety_violations: Vec::new(),
+                        trash_id: None,
+                        backup: None,
                     });
                 }
             }
         }
 
+        if options.include_orphans {
+            self.cleanup_orphans(&options, &mut report).await?;
+        }
+
         info!(
             "Cleanup complete: {} cleaned, {} skipped, {} failed",
             report.cleaned_count, report.skipped_count, report.failed_count
         );
 
+        if !options.dry_run {
+            // Each removal is recorded on its own and can be undone there
+            history::record(
+                EventKind::CleanupRun,
+                format!(
+                    "Cleaned up worktrees: {} cleaned, {} skipped, {} failed",
+                    report.cleaned_count, report.skipped_count, report.failed_count
+                ),
+                None,
+            )
+            .await;
+            if let Ok(repo) = self.operations.main_checkout().await {
+                events::emit(Event::CleanupCompleted {
+                    repo,
+                    cleaned: report.cleaned_count,
+                    skipped: report.skipped_count,
+                    failed: report.failed_count,
+                });
+            }
+        }
+
         Ok(report)
     }
 
+    /// Delete orphaned directories after showing them with their sizes
+    async fn cleanup_orphans(
+        &self,
+        options: &CleanupOptions,
+        report: &mut CleanupReport,
+    ) -> Result<()> {
+        let orphans = self.operations.find_orphaned_worktrees().await?;
+        if orphans.is_empty() {
+            return Ok(());
+        }
+        report.total_evaluated += orphans.len();
+
+        let total: u64 = orphans.iter().map(|orphan| orphan.size_bytes).sum();
+        crate::display_println!(
+            "{} {} orphaned worktree director{} ({}):",
+            "?".yellow(),
+            orphans.len(),
+            if orphans.len() == 1 { "y" } else { "ies" },
+            format_bytes(total)
+        );
+        for orphan in &orphans {
+            crate::display_println!(
+                "  {} {} ({})",
+                orphan.path.display().to_string().blue(),
+                format_bytes(orphan.size_bytes).dimmed(),
+                orphan.reason
+            );
+        }
+
+        let confirmed = options.dry_run
+            || options.auto_confirm
+            || confirm_destructive(
+                DestructiveAction::new("Delete these directories?"),
+                options.confirmations,
+            )?;
+
+        for orphan in orphans {
+            let (action, reason, error) = if options.dry_run {
+                (
+                    CleanupAction::Cleaned,
+                    "Orphaned directory would be deleted (dry run)".to_string(),
+                    None,
+                )
+            } else if !confirmed {
+                (
+                    CleanupAction::Skipped,
+                    "User declined cleanup".to_string(),
+                    None,
+                )
+            } else {
+                match orphans::remove_orphan(&orphan) {
+                    Ok(()) => (
+                        CleanupAction::Cleaned,
+                        format!(
+                            "Orphaned directory deleted, {} freed",
+                            format_bytes(orphan.size_bytes)
+                        ),
+                        None,
+                    ),
+                    Err(e) => (
+                        CleanupAction::Failed,
+                        "Failed to delete orphaned directory".to_string(),
+                        Some(e.to_string()),
+                    ),
+                }
+            };
+
+            match action {
+                CleanupAction::Skipped => report.skipped_count += 1,
+                CleanupAction::Failed => report.failed_count += 1,
+                _ => report.cleaned_count += 1,
+            }
+            report.worktree_results.push(WorktreeCleanupResult {
+                path: orphan.path,
+                branch: ORPHAN_BRANCH_LABEL.to_string(),
+                metadata: Default::default(),
+                action,
+                reason,
+                error,
+                safety_violations: Vec::new(),
+                trash_id: None,
+                backup: None,
+            });
+        }
+
+        Ok(())
+    }
+
     /// Evaluate and potentially clean up a single worktree
     async fn evaluate_and_cleanup_worktree(
         &self,
         worktree: &WorktreeInfo,
         options: &CleanupOptions,
     ) -> Result<WorktreeCleanupResult> {
-        // Skip the main repository worktree
-        if self.is_main_repository_worktree(worktree).await? {
+        // Never touch the main checkout or configured protected paths
+        if let Some(reason) = self.operations.protection(&worktree.path) {
             return Ok(WorktreeCleanupResult {
                 path: worktree.path.clone(),
                 branch: worktree.branch.clone(),
+                metadata: worktree.metadata.clone(),
                 action: CleanupAction::Skipped,
-                reason: "Main repository worktree".to_string(),
+                reason: match reason {
+                    ProtectionReason::MainCheckout => "Main repository worktree".to_string(),
+                    ProtectionReason::Configured => "Protected path".to_string(),
+                },
                 error: None,
                 safety_violations: Vec::new(),
+                trash_id: None,
+                backup: None,
             });
         }
 
@@ -241,10 +411,13 @@ impl WorktreeCleanup {
             return Ok(WorktreeCleanupResult {
                 path: worktree.path.clone(),
                 branch: worktree.branch.clone(),
+                metadata: worktree.metadata.clone(),
                 action: CleanupAction::Skipped,
                 reason: "Does not match cleanup filters".to_string(),
                 error: None,
                 safety_violations: Vec::new(),
+                trash_id: None,
+                backup: None,
             });
         }
 
@@ -261,6 +434,7 @@ impl WorktreeCleanup {
             return Ok(WorktreeCleanupResult {
                 path: worktree.path.clone(),
                 branch: worktree.branch.clone(),
+                metadata: worktree.metadata.clone(),
                 action: CleanupAction::Skipped,
                 reason: format!(
                     "Critical safety violations: {}",
@@ -272,6 +446,8 @@ impl WorktreeCleanup {
                 ),
                 error: None,
                 safety_violations,
+                trash_id: None,
+                backup: None,
             });
         }
 
@@ -284,6 +460,7 @@ impl WorktreeCleanup {
             return Ok(WorktreeCleanupResult {
                 path: worktree.path.clone(),
                 branch: worktree.branch.clone(),
+                metadata: worktree.metadata.clone(),
                 action: CleanupAction::Skipped,
                 reason: format!(
                     "Safety violations (use --force to override): {}",
@@ -295,6 +472,8 @@ impl WorktreeCleanup {
                 ),
                 error: None,
                 safety_violations,
+                trash_id: None,
+                backup: None,
             });
         }
 
@@ -307,10 +486,13 @@ impl WorktreeCleanup {
                 return Ok(WorktreeCleanupResult {
                     path: worktree.path.clone(),
                     branch: worktree.branch.clone(),
+                    metadata: worktree.metadata.clone(),
                     action: CleanupAction::Skipped,
                     reason: "User declined cleanup".to_string(),
                     error: None,
                     safety_violations,
+                    trash_id: None,
+                    backup: None,
                 });
             }
         }
@@ -320,10 +502,13 @@ impl WorktreeCleanup {
             Ok(WorktreeCleanupResult {
                 path: worktree.path.clone(),
                 branch: worktree.branch.clone(),
+                metadata: worktree.metadata.clone(),
                 action: CleanupAction::Cleaned,
                 reason: "Would be cleaned (dry run)".to_string(),
                 error: None,
                 safety_violations,
+                trash_id: None,
+                backup: None,
             })
         } else {
             self.execute_cleanup_strategy(worktree, options, safety_violations)
@@ -369,24 +554,31 @@ impl WorktreeCleanup {
             target: worktree.branch.clone(),
             force: true, // We've already done safety checks
             delete_branch: self.config.cleanup.auto_delete_branch,
+            include_protected: false,
         };
 
         match self.operations.remove_worktree(remove_options).await {
-            Ok(_) => Ok(WorktreeCleanupResult {
+            Ok(outcome) => Ok(WorktreeCleanupResult {
                 path: worktree.path.clone(),
                 branch: worktree.branch.clone(),
+                metadata: worktree.metadata.clone(),
                 action: CleanupAction::Cleaned,
-                reason: "Worktree removed".to_string(),
+                reason: noting_protected_branch("Worktree removed".to_string(), &outcome),
                 error: None,
                 safety_violations,
+                trash_id: outcome.trash_id,
+                backup: outcome.backup,
             }),
             Err(e) => Ok(WorktreeCleanupResult {
                 path: worktree.path.clone(),
                 branch: worktree.branch.clone(),
+                metadata: worktree.metadata.clone(),
                 action: CleanupAction::Failed,
                 reason: "Failed to remove worktree".to_string(),
                 error: Some(e.to_string()),
                 safety_violations,
+                trash_id: None,
+                backup: None,
             }),
         }
     }
@@ -398,18 +590,25 @@ impl WorktreeCleanup {
         _options: &CleanupOptions,
         safety_violations: Vec<SafetyViolation>,
     ) -> Result<WorktreeCleanupResult> {
-        // Determine the target feature branch (remove worktree prefix)
-        let feature_branch = self.extract_feature_branch_name(&worktree.branch)?;
+        // Merge back into the branch the worktree was created from; without a
+        // record of it, into the feature branch named by the task id
+        let feature_branch = match &worktree.metadata.base_branch {
+            Some(base) => base.clone(),
+            None => self.extract_feature_branch_name(&worktree.branch)?,
+        };
 
         // Ensure target branch exists
         if !self.branch_exists(&feature_branch).await? {
             return Ok(WorktreeCleanupResult {
                 path: worktree.path.clone(),
                 branch: worktree.branch.clone(),
+                metadata: worktree.metadata.clone(),
                 action: CleanupAction::Failed,
                 reason: format!("Target feature branch '{}' does not exist", feature_branch),
                 error: None,
                 safety_violations,
+                trash_id: None,
+                backup: None,
             });
         }
 
@@ -424,6 +623,7 @@ impl WorktreeCleanup {
                     Ok(WorktreeCleanupResult {
                         path: worktree.path.clone(),
                         branch: worktree.branch.clone(),
+                        metadata: worktree.metadata.clone(),
                         action: CleanupAction::Failed,
                         reason: format!(
                             "Merge conflicts detected: {}",
@@ -431,6 +631,8 @@ impl WorktreeCleanup {
                         ),
                         error: None,
                         safety_violations,
+                        trash_id: None,
+                        backup: None,
                     })
                 } else {
                     // Merge successful, remove worktree
@@ -438,27 +640,37 @@ impl WorktreeCleanup {
                         target: worktree.branch.clone(),
                         force: true,
                         delete_branch: true, // Remove the worktree branch after successful merge
+                        include_protected: false,
                     };
 
-                    self.operations.remove_worktree(remove_options).await?;
+                    let outcome = self.operations.remove_worktree(remove_options).await?;
 
                     Ok(WorktreeCleanupResult {
                         path: worktree.path.clone(),
                         branch: worktree.branch.clone(),
+                        metadata: worktree.metadata.clone(),
                         action: CleanupAction::MergedToFeature,
-                        reason: format!("Merged to '{}' and cleaned", feature_branch),
+                        reason: noting_protected_branch(
+                            format!("Merged to '{}' and cleaned", feature_branch),
+                            &outcome,
+                        ),
                         error: None,
                         safety_violations,
+                        trash_id: outcome.trash_id,
+                        backup: outcome.backup,
                     })
                 }
             }
             Err(e) => Ok(WorktreeCleanupResult {
                 path: worktree.path.clone(),
                 branch: worktree.branch.clone(),
+                metadata: worktree.metadata.clone(),
                 action: CleanupAction::Failed,
                 reason: "Failed to merge to feature branch".to_string(),
                 error: Some(e.to_string()),
                 safety_violations,
+                trash_id: None,
+                backup: None,
             }),
         }
     }
@@ -478,26 +690,33 @@ impl WorktreeCleanup {
                     target: worktree.branch.clone(),
                     force: true,
                     delete_branch: false, // Keep the branch since it's backed up
+                    include_protected: false,
                 };
 
-                self.operations.remove_worktree(remove_options).await?;
+                let outcome = self.operations.remove_worktree(remove_options).await?;
 
                 Ok(WorktreeCleanupResult {
                     path: worktree.path.clone(),
                     branch: worktree.branch.clone(),
+                    metadata: worktree.metadata.clone(),
                     action: CleanupAction::BackedUpToOrigin,
                     reason: "Backed up to origin and cleaned".to_string(),
                     error: None,
                     safety_violations,
+                    trash_id: outcome.trash_id,
+                    backup: outcome.backup,
                 })
             }
             Err(e) => Ok(WorktreeCleanupResult {
                 path: worktree.path.clone(),
                 branch: worktree.branch.clone(),
+                metadata: worktree.metadata.clone(),
                 action: CleanupAction::Failed,
                 reason: "Failed to backup to origin".to_string(),
                 error: Some(e.to_string()),
                 safety_violations,
+                trash_id: None,
+                backup: None,
             }),
         }
     }
@@ -525,42 +744,53 @@ impl WorktreeCleanup {
                     target: worktree.branch.clone(),
                     force: true,
                     delete_branch: self.config.cleanup.auto_delete_branch,
+                    include_protected: false,
                 };
 
                 match self.operations.remove_worktree(remove_options).await {
-                    Ok(_) => {
+                    Ok(outcome) => {
                         let reason = if stash_created {
                             format!("Stashed changes as '{}' and cleaned", stash_name)
                         } else {
                             "No changes to stash, worktree cleaned".to_string()
                         };
+                        let reason = noting_protected_branch(reason, &outcome);
 
                         Ok(WorktreeCleanupResult {
                             path: worktree.path.clone(),
                             branch: worktree.branch.clone(),
+                            metadata: worktree.metadata.clone(),
                             action: CleanupAction::StashCreated,
                             reason,
                             error: None,
                             safety_violations,
+                            trash_id: outcome.trash_id,
+                            backup: outcome.backup,
                         })
                     }
                     Err(e) => Ok(WorktreeCleanupResult {
                         path: worktree.path.clone(),
                         branch: worktree.branch.clone(),
+                        metadata: worktree.metadata.clone(),
                         action: CleanupAction::Failed,
                         reason: "Stash created but failed to remove worktree".to_string(),
                         error: Some(e.to_string()),
                         safety_violations,
+                        trash_id: None,
+                        backup: None,
                     }),
                 }
             }
             Err(e) => Ok(WorktreeCleanupResult {
                 path: worktree.path.clone(),
                 branch: worktree.branch.clone(),
+                metadata: worktree.metadata.clone(),
                 action: CleanupAction::Failed,
                 reason: "Failed to create stash".to_string(),
                 error: Some(e.to_string()),
                 safety_violations,
+                trash_id: None,
+                backup: None,
             }),
         }
     }
@@ -574,11 +804,14 @@ impl WorktreeCleanup {
     ) -> Vec<SafetyViolation> {
         let mut violations = Vec::new();
 
-        // Check age threshold
-        if let Some(min_hours) = options
-            .min_age_hours
-            .or(Some(self.config.cleanup.age_threshold_hours))
-        {
+        // Check age threshold; when cleaning expired worktrees the deadline
+        // stands in for it unless an age was given explicitly
+        let min_age_hours = match options.min_age_hours {
+            None if options.expired_only => None,
+            None => Some(self.config.cleanup.age_threshold_hours),
+            explicit => explicit,
+        };
+        if let Some(min_hours) = min_age_hours {
             let min_age = Duration::from_secs(min_hours * 3600);
             if worktree.age < min_age {
                 violations.push(SafetyViolation {
@@ -651,7 +884,7 @@ impl WorktreeCleanup {
 
         // Check if worktree is currently in use (current working directory)
         if let Ok(current_dir) = std::env::current_dir() {
-            if current_dir.starts_with(&worktree.path) {
+            if path_starts_with(&current_dir, &worktree.path) {
                 violations.push(SafetyViolation {
                     violation_type: SafetyViolationType::WorktreeInUse,
                     description: "Worktree is currently in use (current directory)".to_string(),
@@ -663,19 +896,20 @@ impl WorktreeCleanup {
         violations
     }
 
-    async fn is_main_repository_worktree(&self, worktree: &WorktreeInfo) -> Result<bool> {
-        // Check if this worktree is the main repository (contains .git directory)
-        Ok(worktree.path.join(".git").is_dir())
-    }
-
     fn matches_filters(&self, worktree: &WorktreeInfo, options: &CleanupOptions) -> bool {
         // Check branch prefix filter
+        // Adopted worktrees whose branch kept its name count as prefixed
         if let Some(ref prefix) = options.branch_prefix_filter {
-            if !worktree.branch.starts_with(prefix) {
+            let adopted = worktree.metadata.task_id.is_some() && *prefix == self.config.prefix;
+            if !worktree.branch.starts_with(prefix) && !adopted {
                 return false;
             }
         }
 
+        if options.expired_only && !worktree.metadata.is_expired(chrono::Utc::now()) {
+            return false;
+        }
+
         true
     }
 
@@ -704,14 +938,7 @@ impl WorktreeCleanup {
             }
         }
 
-        use std::io::{self, Write};
-        print!("  Proceed? (y/N): ");
-        io::stdout().flush()?;
-
-        let mut input = String::new();
-        io::stdin().read_line(&mut input)?;
-
-        Ok(input.trim().to_lowercase() == "y" || input.trim().to_lowercase() == "yes")
+        confirm_destructive(DestructiveAction::new("Proceed?"), options.confirmations)
     }
 
     // Strategy implementation helpers
@@ -855,15 +1082,21 @@ impl Default for CleanupOptions {
             force: false,
             dry_run: false,
             auto_confirm: false,
+            confirmations: ConfirmationLevel::default(),
             branch_prefix_filter: None,
             merged_only: false,
             min_merge_confidence: 0.8,
+            include_orphans: false,
+            expired_only: false,
         }
     }
 }
 
 /// Format a duration for human-readable display
 fn format_duration(duration: Duration) -> String {
+    if crate::output::is_deterministic() {
+        return crate::output::TIME_PLACEHOLDER.to_string();
+    }
     let hours = duration.as_secs() / 3600;
     let days = hours / 24;
 
@@ -3,6 +3,8 @@
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
+use crate::git::protection::DEFAULT_PROTECTED_BRANCHES;
+
 /// Worktree storage mode
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -19,6 +21,9 @@ impl Default for WorktreeMode {
     }
 }
 
+/// Shortest allowed background refresh interval for the MCP server
+pub const MIN_REFRESH_INTERVAL_SECS: u64 = 5;
+
 /// Configuration for worktree management
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorktreeConfig {
@@ -48,6 +53,45 @@ pub struct WorktreeConfig {
 
     /// Status display configuration
     pub status: WorktreeStatusConfig,
+
+    /// How often (seconds) the MCP server refreshes worktree status in the
+    /// background; unset disables the refresher
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refresh_interval_secs: Option<u64>,
+
+    /// Worktrees that removal and cleanup always refuse, even with --force.
+    /// Relative paths are resolved against the repository root.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub protected_paths: Vec<PathBuf>,
+
+    /// Branches that worktree removal, forced recreation, and `--save-dirty`
+    /// never delete or overwrite; globs like `release/*` are allowed
+    #[serde(default = "default_protected_branches")]
+    pub protected_branches: Vec<String>,
+
+    /// Also protect branches GitHub marks protected (asked through `gh`)
+    #[serde(default)]
+    pub check_github_protection: bool,
+
+    /// Pull requests opened by `vibe task finish`
+    #[serde(default)]
+    pub task: WorktreeTaskConfig,
+
+    /// Fetch and fast-forward the base branch from its upstream before
+    /// creating a worktree from it
+    #[serde(default = "default_refresh_base_before_create")]
+    pub refresh_base_before_create: bool,
+}
+
+fn default_refresh_base_before_create() -> bool {
+    true
+}
+
+fn default_protected_branches() -> Vec<String> {
+    DEFAULT_PROTECTED_BRANCHES
+        .iter()
+        .map(|pattern| pattern.to_string())
+        .collect()
 }
 
 /// Configuration for cleanup operations
@@ -79,6 +123,36 @@ pub struct WorktreeMergeDetectionConfig {
     pub main_branches: Vec<String>,
 }
 
+/// Title and body of the pull request `vibe task finish` opens. Both may
+/// use `{{task_id}}`, `{{branch}}`, `{{repo}}`, `{{base_branch}}`,
+/// `{{description}}` (the task id when no description was given), and
+/// `{{link}}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorktreeTaskConfig {
+    #[serde(default = "default_pr_title")]
+    pub pr_title: String,
+
+    #[serde(default = "default_pr_body")]
+    pub pr_body: String,
+}
+
+fn default_pr_title() -> String {
+    "{{description}}".to_string()
+}
+
+fn default_pr_body() -> String {
+    "Task `{{task_id}}`\n\n{{link}}".to_string()
+}
+
+impl Default for WorktreeTaskConfig {
+    fn default() -> Self {
+        Self {
+            pr_title: default_pr_title(),
+            pr_body: default_pr_body(),
+        }
+    }
+}
+
 /// Configuration for status display
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorktreeStatusConfig {
@@ -106,6 +180,12 @@ impl Default for WorktreeConfig {
             cleanup: WorktreeCleanupConfig::default(),
             merge_detection: WorktreeMergeDetectionConfig::default(),
             status: WorktreeStatusConfig::default(),
+            refresh_interval_secs: None,
+            protected_paths: Vec::new(),
+            protected_branches: default_protected_branches(),
+            check_github_protection: false,
+            task: WorktreeTaskConfig::default(),
+            refresh_base_before_create: true,
         }
     }
 }
@@ -136,6 +216,19 @@ impl Default for WorktreeMergeDetectionConfig {
     }
 }
 
+impl WorktreeMergeDetectionConfig {
+    /// These settings with `base_branch` (the branch a worktree was created
+    /// from, when known) checked before the configured main branches
+    pub fn preferring(&self, base_branch: Option<&str>) -> Self {
+        let mut config = self.clone();
+        if let Some(base) = base_branch {
+            config.main_branches.retain(|branch| branch != base);
+            config.main_branches.insert(0, base.to_string());
+        }
+        config
+    }
+}
+
 impl Default for WorktreeStatusConfig {
     fn default() -> Self {
         Self {
@@ -165,13 +258,7 @@ impl WorktreeConfig {
                     self.base_dir.clone()
                 } else {
                     // Resolve to global location (matching operations.rs logic)
-                    if let Some(home) = dirs::home_dir() {
-                        home.join(".toolprint")
-                            .join("vibe-workspace")
-                            .join("worktrees")
-                    } else {
-                        std::env::temp_dir().join("vibe-worktrees")
-                    }
+                    crate::workspace::constants::get_worktrees_dir()
                 }
             }
         }
@@ -311,6 +398,15 @@ impl WorktreeConfig {
             return Err("Max commits shown must be between 1 and 50".to_string());
         }
 
+        if self
+            .refresh_interval_secs
+            .is_some_and(|secs| secs < MIN_REFRESH_INTERVAL_SECS)
+        {
+            return Err(format!(
+                "Refresh interval must be at least {MIN_REFRESH_INTERVAL_SECS} seconds"
+            ));
+        }
+
         // Validate editor command
         if self.default_editor.is_empty() {
             return Err("Default editor cannot be empty".to_string());
@@ -344,8 +440,8 @@ Environment Variables:
   VIBE_WORKTREE_MAX_FILES_SHOWN   Max files to show in status (default: 10)
 
 Configuration File:
-  The worktree configuration is stored in ~/.toolprint/vibe-workspace/config.yaml
-  under the 'worktree' section. Repository-specific overrides can be configured
+  The worktree configuration is stored in config.yaml (~/.config/vibe by
+  default; `vibe doctor` shows the location) under the 'worktree' section. Repository-specific overrides can be configured
   in the 'repositories[].worktree_config' section.
 "#
     }
@@ -540,6 +636,20 @@ mod config_tests {
         assert!(WORKTREE_ENV_VARS.len() > 10); // Should have many env vars documented
     }
 
+    #[test]
+    fn test_merge_detection_prefers_base_branch() {
+        let config = WorktreeMergeDetectionConfig::default();
+        assert_eq!(
+            config.preferring(Some("master")).main_branches,
+            vec!["master", "main"]
+        );
+        assert_eq!(
+            config.preferring(Some("release/2.0")).main_branches,
+            vec!["release/2.0", "main", "master"]
+        );
+        assert_eq!(config.preferring(None).main_branches, config.main_branches);
+    }
+
     #[test]
     fn test_worktree_mode() {
         // Test default mode
@@ -362,6 +362,8 @@ mod tests {
                 name: "test-repo".to_string(),
                 path: temp_dir.path().join("test-repo"),
                 url: None,
+                upstream: None,
+                license: None,
                 branch: None,
                 apps: std::collections::HashMap::new(),
                 worktree_config: Some(RepositoryWorktreeConfig {
@@ -371,7 +373,11 @@ mod tests {
                     cleanup: None,
                     merge_detection: None,
                     disabled: Some(false),
+                    protected_branches: None,
                 }),
+                subprojects: Vec::new(),
+                exclude: Vec::new(),
+                compare_ref: None,
             }],
             groups: Vec::new(),
             apps: AppIntegrations {
@@ -386,6 +392,10 @@ mod tests {
             preferences: None,
             claude_agents: None,
             worktree: WorktreeConfig::default(),
+            hooks: Default::default(),
+            managed_files: Vec::new(),
+            template_packs: Vec::new(),
+            safety: Default::default(),
         };
 
         // Save the config
@@ -441,12 +451,14 @@ mod tests {
             cleanup: None,
             merge_detection: None,
             disabled: Some(false),
+            protected_branches: Some(vec!["trunk".to_string()]),
         };
 
         let merged = repo_config.merge_with_global(&global);
 
         assert_eq!(merged.prefix, "custom-prefix/");
         assert_eq!(merged.base_dir, PathBuf::from("/custom/path"));
+        assert_eq!(merged.protected_branches, vec!["trunk"]);
         // Other settings should come from global
         assert_eq!(merged.auto_gitignore, global.auto_gitignore);
         assert_eq!(merged.default_editor, global.default_editor);
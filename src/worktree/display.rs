@@ -0,0 +1,878 @@
+//! Terminal rendering for worktree listings, status, and cleanup reports
+
+use anyhow::{Context, Result};
+use colored::*;
+use std::io::IsTerminal;
+
+use crate::output::exit::CommandError;
+use crate::output::table::{self, Column, Table};
+use crate::output::theme::{self, paint, Icon, Role};
+use crate::utils::direnv;
+use crate::utils::platform::resolve_command;
+
+use super::cleanup::CleanupReport;
+use super::exec::{ExecOutcome, ExecReport};
+use super::status::{RemoteStatus, WorktreeInfo};
+
+/// Open a worktree in the specified editor, loading its `.envrc` through
+/// direnv when `enable_direnv` is set
+pub async fn open_worktree_in_editor(
+    path: &std::path::Path,
+    editor: &str,
+    enable_direnv: bool,
+) -> Result<()> {
+    println!("Opening worktree in {}: {}", editor, path.display());
+
+    let status = direnv::command(resolve_command(editor), path, enable_direnv)
+        .arg(path)
+        .status()
+        .await
+        .with_context(|| format!("Failed to execute editor: {editor}"))?;
+
+    if !status.success() {
+        return Err(anyhow::anyhow!(
+            "Editor command failed with status: {status}"
+        ));
+    }
+
+    println!(
+        "{} Successfully opened worktree in {editor}",
+        theme::icon(Icon::Success)
+    );
+    Ok(())
+}
+
+/// Start `$SHELL` inside `worktree` with its task exported as `VIBE_TASK_ID`,
+/// `VIBE_WORKTREE_BRANCH`, and `VIBE_REPO_NAME`, returning once it exits.
+/// The shell loads the worktree's `.envrc` through direnv when
+/// `enable_direnv` is set.
+pub async fn open_worktree_shell(
+    worktree: &WorktreeInfo,
+    repo_name: &str,
+    enable_direnv: bool,
+) -> Result<()> {
+    if !std::io::stdin().is_terminal() || !std::io::stdout().is_terminal() {
+        return Err(CommandError::Usage(
+            "--shell needs a terminal; use --print-path to get the worktree path instead"
+                .to_string(),
+        )
+        .into());
+    }
+
+    let shell = std::env::var("SHELL")
+        .ok()
+        .filter(|shell| !shell.is_empty())
+        .unwrap_or_else(|| "/bin/sh".to_string());
+    let task_id = worktree.task_id.as_deref().unwrap_or(&worktree.branch);
+    println!(
+        "{} Entering {} ({}) in {}; exit the shell to return",
+        theme::icon(Icon::Info),
+        task_id.cyan(),
+        worktree.branch,
+        worktree.path.display()
+    );
+
+    direnv::command(resolve_command(&shell), &worktree.path, enable_direnv)
+        .current_dir(&worktree.path)
+        .env("VIBE_TASK_ID", task_id)
+        .env("VIBE_WORKTREE_BRANCH", &worktree.branch)
+        .env("VIBE_REPO_NAME", repo_name)
+        .status()
+        .await
+        .with_context(|| format!("Failed to start shell: {shell}"))?;
+    Ok(())
+}
+
+/// Print worktrees in table format, fitted to the terminal width
+pub fn print_worktrees_table(worktrees: &[WorktreeInfo], verbose: bool) {
+    if worktrees.is_empty() {
+        println!("No worktrees found");
+        return;
+    }
+    for line in worktrees_table(worktrees, verbose).render(table::terminal_width()) {
+        println!("{line}");
+    }
+}
+
+/// Worktree listing with the path truncated first, then the branch, then
+/// the task ID
+fn worktrees_table(worktrees: &[WorktreeInfo], verbose: bool) -> Table {
+    let mut table = if verbose {
+        // TASK ID | STATUS | BRANCH | BASE | PATH | AGE | HEAD
+        Table::new(vec![
+            Column::new("TASK ID").shrink(4, 8),
+            Column::new("STATUS").shrink(3, 12),
+            Column::new("BRANCH").shrink(2, 10),
+            Column::new("BASE").shrink(1, 6),
+            Column::new("PATH").shrink(0, 8),
+            Column::new("AGE"),
+            Column::new("HEAD"),
+        ])
+    } else {
+        Table::new(vec![
+            Column::new("TASK ID").shrink(1, 8),
+            Column::new("STATUS").shrink(0, 12),
+        ])
+    };
+
+    for worktree in worktrees {
+        // Display task_id or indicate main repository
+        let task_id = match &worktree.task_id {
+            Some(task_id) => paint(Role::Success, task_id).to_string(),
+            None => paint(Role::Dim, "(main)").to_string(),
+        };
+        let status = format!(
+            "{} {}{}{}",
+            worktree.status.status_icon(),
+            worktree.status.status_description(),
+            protected_mark(worktree),
+            expiry_mark(worktree)
+        );
+
+        if !verbose {
+            table.add_row(vec![task_id, status]);
+            continue;
+        }
+
+        let path_string = worktree.path.to_string_lossy();
+        let path = worktree
+            .path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or(&path_string);
+        let head = &worktree.head[..worktree.head.len().min(7)];
+        // Unknown for worktrees created outside vibe
+        let base = worktree.metadata.base_branch.as_deref().unwrap_or("-");
+        table.add_row_with_notes(
+            vec![
+                task_id,
+                status,
+                paint(Role::Warning, &worktree.branch).to_string(),
+                paint(Role::Dim, base).to_string(),
+                path.blue().to_string(),
+                paint(Role::Dim, format_age(worktree.age)).to_string(),
+                paint(Role::Dim, head).to_string(),
+            ],
+            metadata_line(worktree)
+                .map(|line| paint(Role::Dim, line).to_string())
+                .into_iter()
+                .collect(),
+        );
+    }
+    table
+}
+
+/// Description and link of an annotated worktree on one line
+fn metadata_line(worktree: &WorktreeInfo) -> Option<String> {
+    let metadata = &worktree.metadata;
+    match (&metadata.description, &metadata.link) {
+        (Some(description), Some(link)) => Some(format!("{description} ({link})")),
+        (Some(value), None) | (None, Some(value)) => Some(value.clone()),
+        (None, None) => None,
+    }
+}
+
+/// Print worktrees in compact format
+pub fn print_worktrees_compact(worktrees: &[WorktreeInfo]) {
+    for worktree in worktrees {
+        // Display task_id or indicate main repository
+        let task_id_display = if let Some(ref task_id) = worktree.task_id {
+            paint(Role::Success, format!("[{task_id}]"))
+        } else {
+            paint(Role::Dim, "[main]".to_string())
+        };
+
+        println!(
+            "{} {}{} {} {}{}",
+            worktree.status.status_icon(),
+            task_id_display,
+            protected_mark(worktree),
+            paint(Role::Warning, &worktree.branch),
+            worktree.path.display().to_string().blue(),
+            expiry_mark(worktree)
+        );
+    }
+}
+
+/// Enhanced status table printing with detailed information, with long
+/// values cut to the terminal width
+pub fn print_detailed_status_table(worktrees: &[WorktreeInfo], show_files: bool) {
+    for line in detailed_status_lines(worktrees, show_files, table::terminal_width()) {
+        println!("{line}");
+    }
+}
+
+fn detailed_status_lines(
+    worktrees: &[WorktreeInfo],
+    show_files: bool,
+    width: usize,
+) -> Vec<String> {
+    let mut lines = Vec::new();
+    for (i, worktree) in worktrees.iter().enumerate() {
+        if i > 0 {
+            lines.push(String::new());
+        }
+
+        // Header
+        lines.push(table::truncate(
+            &format!(
+                "{} {}{}",
+                worktree.status.status_icon().bold(),
+                paint(Role::Accent, &worktree.branch).bold(),
+                protected_mark(worktree)
+            ),
+            width,
+        ));
+
+        let mut fields: Vec<(&str, String)> = vec![(
+            "Path",
+            worktree.path.display().to_string().blue().to_string(),
+        )];
+        if !worktree.head.is_empty() {
+            let short_head = &worktree.head[..worktree.head.len().min(7)];
+            fields.push(("HEAD", paint(Role::Dim, short_head).to_string()));
+        }
+        if let Some(base) = &worktree.metadata.base_branch {
+            let value = match worktree.metadata.base_commit.as_deref() {
+                Some(commit) => format!(
+                    "{} {}",
+                    base,
+                    paint(Role::Dim, &commit[..commit.len().min(7)])
+                ),
+                None => base.clone(),
+            };
+            fields.push(("Base", value));
+        }
+        if let Some(description) = &worktree.metadata.description {
+            fields.push(("Task", description.clone()));
+        }
+        if let Some(link) = &worktree.metadata.link {
+            fields.push(("Link", link.underline().to_string()));
+        }
+        fields.push((
+            "Age",
+            paint(Role::Dim, format_age(worktree.age)).to_string(),
+        ));
+        if let Some(expiry) = worktree.metadata.expiry_label(chrono::Utc::now()) {
+            let role = if worktree.metadata.is_expired(chrono::Utc::now()) {
+                Role::Error
+            } else {
+                Role::Dim
+            };
+            fields.push(("Time box", paint(role, expiry).to_string()));
+        }
+
+        // Remote status
+        let remote = match &worktree.status.remote_status {
+            RemoteStatus::NoRemote => paint(Role::Warning, "No remote tracking").to_string(),
+            RemoteStatus::UpToDate => paint(Role::Success, "Up to date").to_string(),
+            RemoteStatus::Ahead(count) => format!("{} {} ahead", paint(Role::Success, "↑"), count),
+            RemoteStatus::Behind(count) => format!("{} {} behind", paint(Role::Error, "↓"), count),
+            RemoteStatus::Diverged { ahead, behind } => format!(
+                "{} {} ahead, {} {} behind",
+                paint(Role::Success, "↑"),
+                ahead,
+                paint(Role::Error, "↓"),
+                behind
+            ),
+            RemoteStatus::RemoteDeleted => paint(Role::Error, "Remote branch deleted").to_string(),
+        };
+        fields.push(("Remote", remote));
+
+        // Merge information
+        if let Some(merge_info) = &worktree.status.merge_info {
+            if merge_info.is_merged {
+                fields.push((
+                    "Merge Status",
+                    format!(
+                        "{} {} (confidence: {:.0}%)",
+                        theme::icon(Icon::Success),
+                        merge_info.detection_method,
+                        merge_info.confidence * 100.0
+                    ),
+                ));
+                if let Some(details) = &merge_info.details {
+                    fields.push(("Details", paint(Role::Dim, details).to_string()));
+                }
+            } else {
+                fields.push((
+                    "Merge Status",
+                    format!("{} Not merged", theme::icon(Icon::Error)),
+                ));
+            }
+        }
+
+        // Changes summary
+        let changes = [
+            (worktree.status.uncommitted_changes.len(), "uncommitted"),
+            (worktree.status.untracked_files.len(), "untracked"),
+            (worktree.status.unpushed_commits.len(), "unpushed"),
+        ]
+        .into_iter()
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, kind)| format!("{count} {kind}"))
+        .collect::<Vec<_>>();
+        if !changes.is_empty() {
+            fields.push((
+                "Changes",
+                paint(Role::Warning, changes.join(", ")).to_string(),
+            ));
+        }
+
+        lines.extend(table::key_value_lines(&fields, width));
+
+        // Show files if requested and present
+        if show_files {
+            let mut files = Vec::new();
+            if !worktree.status.uncommitted_changes.is_empty() {
+                files.push(format!(
+                    "  {}",
+                    theme::labeled(Icon::Edit, "Uncommitted changes:")
+                ));
+                for file in &worktree.status.uncommitted_changes {
+                    files.push(format!("    {file}"));
+                }
+            }
+
+            if !worktree.status.untracked_files.is_empty() {
+                files.push(format!(
+                    "  {}",
+                    theme::labeled(Icon::Unknown, "Untracked files:")
+                ));
+                for file in worktree.status.untracked_files.iter().take(5) {
+                    files.push(format!("    {file}"));
+                }
+                if worktree.status.untracked_files.len() > 5 {
+                    files.push(format!(
+                        "    {} ... and {} more",
+                        paint(Role::Dim, "⋯"),
+                        worktree.status.untracked_files.len() - 5
+                    ));
+                }
+            }
+
+            if !worktree.status.unpushed_commits.is_empty() {
+                files.push(format!("  {} Unpushed commits:", paint(Role::Dim, "↑")));
+                for commit in worktree.status.unpushed_commits.iter().take(3) {
+                    files.push(format!(
+                        "    {} {} ({})",
+                        paint(Role::Warning, &commit.id),
+                        commit.message,
+                        paint(Role::Dim, &commit.author)
+                    ));
+                }
+                if worktree.status.unpushed_commits.len() > 3 {
+                    files.push(format!(
+                        "    {} ... and {} more commits",
+                        paint(Role::Dim, "⋯"),
+                        worktree.status.unpushed_commits.len() - 3
+                    ));
+                }
+            }
+            lines.extend(files.iter().map(|line| table::truncate(line, width)));
+        }
+    }
+    lines
+}
+
+/// Print status in table format
+pub fn print_status_table(worktrees: &[WorktreeInfo], files_only: bool) {
+    print_detailed_status_table(worktrees, files_only);
+}
+
+/// Print status in compact format
+pub fn print_status_compact(worktrees: &[WorktreeInfo], _files_only: bool) {
+    for worktree in worktrees {
+        let mut status_line = format!(
+            "{} {}{}: {}{}",
+            worktree.status.status_icon(),
+            worktree.branch,
+            protected_mark(worktree),
+            worktree.status.status_description(),
+            expiry_mark(worktree)
+        );
+
+        // Add merge status if available
+        if let Some(merge_info) = &worktree.status.merge_info {
+            if merge_info.is_merged {
+                status_line.push_str(&format!(" [{}]", paint(Role::Success, "merged")));
+            }
+        }
+
+        println!("{status_line}");
+    }
+}
+
+/// Lock glyph after a protected worktree, empty otherwise
+fn protected_mark(worktree: &WorktreeInfo) -> String {
+    if worktree.protected {
+        format!(" {}", theme::icon(Icon::Locked))
+    } else {
+        String::new()
+    }
+}
+
+/// Remaining time of a time-boxed worktree, red once the deadline passed
+fn expiry_mark(worktree: &WorktreeInfo) -> String {
+    let now = chrono::Utc::now();
+    match worktree.metadata.expiry_label(now) {
+        Some(label) if worktree.metadata.is_expired(now) => {
+            format!(" {}", paint(Role::Error, format!("({label})")))
+        }
+        Some(label) => format!(" {}", paint(Role::Dim, format!("({label})"))),
+        None => String::new(),
+    }
+}
+
+/// Format age duration for display
+pub fn format_age(age: std::time::Duration) -> String {
+    if crate::output::is_deterministic() {
+        return crate::output::TIME_PLACEHOLDER.to_string();
+    }
+    let hours = age.as_secs() / 3600;
+    let days = hours / 24;
+
+    if days > 0 {
+        format!("{days}d")
+    } else if hours > 0 {
+        format!("{hours}h")
+    } else {
+        format!("{}m", age.as_secs() / 60)
+    }
+}
+
+/// Print cleanup report
+pub fn print_cleanup_report(report: &CleanupReport) {
+    println!();
+    println!("{}", theme::labeled(Icon::Report, "Cleanup Report"));
+    println!("Strategy: {:?}", report.strategy_used);
+    if report.was_dry_run {
+        println!(
+            "Mode: {} (no changes made)",
+            paint(Role::Warning, "Dry Run")
+        );
+    }
+    println!();
+
+    println!("Results:");
+    println!(
+        "  {} Cleaned: {}",
+        theme::icon(Icon::Success),
+        paint(Role::Success, report.cleaned_count)
+    );
+    println!(
+        "  {} Skipped: {}",
+        theme::icon(Icon::Warning),
+        paint(Role::Warning, report.skipped_count)
+    );
+    println!(
+        "  {} Failed:  {}",
+        theme::icon(Icon::Error),
+        paint(Role::Error, report.failed_count)
+    );
+    println!(
+        "  {}",
+        theme::labeled(
+            Icon::Report,
+            &format!("Total:   {}", report.total_evaluated)
+        )
+    );
+
+    if !report.worktree_results.is_empty() {
+        println!();
+        println!("Details:");
+        for line in cleanup_details_table(report).render(table::terminal_width()) {
+            println!("{line}");
+        }
+    }
+
+    println!();
+    if report.cleaned_count > 0 && !report.was_dry_run {
+        println!(
+            "{}",
+            theme::labeled(Icon::Celebrate, "Cleanup completed successfully!")
+        );
+    } else if report.was_dry_run {
+        println!(
+            "{}",
+            theme::labeled(Icon::Tip, "Run without --dry-run to execute changes")
+        );
+    }
+}
+
+/// Per-worktree results of a cleanup, with the reason truncated before the
+/// branch
+fn cleanup_details_table(report: &CleanupReport) -> Table {
+    use super::cleanup::CleanupAction;
+
+    let mut table = Table::new(vec![
+        Column::new("RESULT"),
+        Column::new("BRANCH").shrink(1, 10),
+        Column::new("REASON").shrink(0, 10),
+    ]);
+    for result in &report.worktree_results {
+        let (icon, label) = match result.action {
+            CleanupAction::Cleaned => (Icon::Success, "cleaned"),
+            CleanupAction::Skipped => (Icon::Warning, "skipped"),
+            CleanupAction::Failed => (Icon::Error, "failed"),
+            CleanupAction::MergedToFeature => (Icon::Merge, "merged"),
+            CleanupAction::BackedUpToOrigin => (Icon::Cloud, "backed up"),
+            CleanupAction::StashCreated => (Icon::Package, "stashed"),
+        };
+        let mut notes = Vec::new();
+        if let Some(description) = &result.metadata.description {
+            notes.push(paint(Role::Dim, description).to_string());
+        }
+        if let Some(error) = &result.error {
+            notes.push(format!("Error: {}", paint(Role::Error, error)));
+        }
+        if let Some(id) = &result.trash_id {
+            notes.push(paint(Role::Dim, format!("In the trash as {id}")).to_string());
+        }
+        if let Some(backup) = &result.backup {
+            notes.push(
+                paint(
+                    Role::Dim,
+                    format!("Branch backed up as {}", backup.backup_ref),
+                )
+                .to_string(),
+            );
+        }
+        table.add_row_with_notes(
+            vec![
+                theme::labeled(icon, label),
+                paint(Role::Accent, &result.branch).to_string(),
+                result.reason.clone(),
+            ],
+            notes,
+        );
+    }
+    table
+}
+
+/// Print the per-worktree results of `vibe git worktree exec`
+pub fn print_exec_report(report: &ExecReport) {
+    let width = report
+        .results
+        .iter()
+        .map(|result| result.label().len())
+        .max()
+        .unwrap_or(0)
+        .max("WORKTREE".len());
+
+    println!();
+    println!(
+        "{}",
+        paint(
+            Role::Dim,
+            format!(
+                "{:<width$} {:<10} {:>5} {:>10}",
+                "WORKTREE", "RESULT", "EXIT", "DURATION"
+            )
+        )
+    );
+    for result in &report.results {
+        let (icon, role, label) = match result.outcome {
+            ExecOutcome::Succeeded => (Icon::Success, Role::Success, "ok"),
+            ExecOutcome::Failed => (Icon::Error, Role::Error, "failed"),
+            ExecOutcome::Skipped => (Icon::Warning, Role::Warning, "skipped"),
+        };
+        let exit = result
+            .exit_code
+            .map(|code| code.to_string())
+            .unwrap_or_else(|| "-".to_string());
+        println!(
+            "{:<width$} {} {:>5} {:>10}",
+            result.label(),
+            paint(role, format!("{:<10}", theme::labeled(icon, label))),
+            exit,
+            format_millis(result.duration_ms)
+        );
+    }
+
+    let failed = report.failed_count();
+    let skipped = report
+        .results
+        .iter()
+        .filter(|result| result.outcome == ExecOutcome::Skipped)
+        .count();
+    println!();
+    if failed == 0 && skipped == 0 {
+        println!(
+            "{}",
+            theme::labeled(
+                Icon::Success,
+                &format!("Succeeded in {} worktrees", report.results.len())
+            )
+        );
+    } else {
+        println!(
+            "{}",
+            theme::labeled(
+                Icon::Error,
+                &format!(
+                    "{failed} failed, {skipped} skipped of {} worktrees",
+                    report.results.len()
+                )
+            )
+        );
+    }
+}
+
+fn format_millis(ms: u64) -> String {
+    if crate::output::is_deterministic() {
+        return crate::output::TIME_PLACEHOLDER.to_string();
+    }
+    if ms < 1000 {
+        format!("{ms}ms")
+    } else if ms < 60_000 {
+        format!("{:.1}s", ms as f64 / 1000.0)
+    } else {
+        format!("{}m{:02}s", ms / 60_000, (ms % 60_000) / 1000)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::worktree::cleanup::{CleanupAction, CleanupStrategy, WorktreeCleanupResult};
+    use crate::worktree::status::{StatusSeverity, WorktreeStatus};
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    fn snapshot(lines: Vec<String>) -> String {
+        lines
+            .iter()
+            .map(|line| console::strip_ansi_codes(line).into_owned())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn worktrees() -> Vec<WorktreeInfo> {
+        let mut clean = WorktreeStatus::new();
+        clean.is_clean = true;
+        clean.severity = StatusSeverity::Clean;
+        clean.remote_status = RemoteStatus::UpToDate;
+        let mut dirty = WorktreeStatus::new();
+        dirty.severity = StatusSeverity::LightWarning;
+        dirty.uncommitted_changes = vec!["src/auth/session.rs".to_string()];
+        dirty.untracked_files = vec!["notes.md".to_string()];
+
+        vec![
+            WorktreeInfo {
+                path: PathBuf::from("/home/dev/code/api"),
+                branch: "main".to_string(),
+                head: "0123456789abcdef".to_string(),
+                task_id: None,
+                status: clean,
+                age: Duration::from_secs(40 * 86_400),
+                is_detached: false,
+                protected: false,
+                metadata: Default::default(),
+                base_refresh: None,
+            },
+            WorktreeInfo {
+                path: PathBuf::from(
+                    "/home/dev/code/api/.worktrees/session-refresh-tokens__1a2b3c4d",
+                ),
+                branch: "vibe-ws/session-refresh-tokens".to_string(),
+                head: "fedcba9876543210".to_string(),
+                task_id: Some("session-refresh-tokens".to_string()),
+                status: dirty,
+                age: Duration::from_secs(3 * 3600),
+                is_detached: false,
+                protected: false,
+                metadata: crate::worktree::metadata::WorktreeMetadata {
+                    description: Some("Refresh session tokens before they expire".to_string()),
+                    base_branch: Some("main".to_string()),
+                    ..Default::default()
+                },
+                base_refresh: None,
+            },
+        ]
+    }
+
+    fn cleanup_report() -> CleanupReport {
+        let result = |branch: &str, action, reason: &str| WorktreeCleanupResult {
+            path: PathBuf::from("/tmp").join(branch),
+            branch: branch.to_string(),
+            metadata: Default::default(),
+            action,
+            reason: reason.to_string(),
+            error: None,
+            safety_violations: Vec::new(),
+            trash_id: None,
+            backup: None,
+        };
+        let mut failed = result(
+            "vibe-ws/flaky-ci",
+            CleanupAction::Failed,
+            "Worktree is locked by another process",
+        );
+        failed.error = Some("fatal: 'remove' is not possible".to_string());
+        CleanupReport {
+            total_evaluated: 2,
+            cleaned_count: 1,
+            skipped_count: 0,
+            failed_count: 1,
+            worktree_results: vec![
+                result(
+                    "vibe-ws/session-refresh-tokens",
+                    CleanupAction::Cleaned,
+                    "Merged into main (squash, 100% confidence) and older than 7 days",
+                ),
+                failed,
+            ],
+            strategy_used: CleanupStrategy::Discard,
+            was_dry_run: false,
+        }
+    }
+
+    /// Every layout in this file at `width` columns, without colors
+    fn render(width: usize) -> String {
+        [
+            snapshot(worktrees_table(&worktrees(), true).render(width)),
+            snapshot(worktrees_table(&worktrees(), false).render(width)),
+            snapshot(detailed_status_lines(&worktrees(), true, width)),
+            snapshot(cleanup_details_table(&cleanup_report()).render(width)),
+        ]
+        .join("\n\n")
+    }
+
+    #[test]
+    fn test_narrow_terminal_stacks_the_listing() {
+        assert_eq!(
+            render(60),
+            r#"TASK ID: (main)
+STATUS:  ✅ Clean
+BRANCH:  main
+BASE:    -
+PATH:    api
+AGE:     40d
+HEAD:    0123456
+
+TASK ID: session-refresh-tokens
+STATUS:  ⚠️ 1 uncommitted, 1 untracked, no remote
+BRANCH:  vibe-ws/session-refresh-tokens
+BASE:    main
+PATH:    session-refresh-tokens__1a2b3c4d
+AGE:     3h
+HEAD:    fedcba9
+Refresh session tokens before they expire
+
+TASK ID                 STATUS
+────────────────────────────────────────────────────────────
+(main)                  ✅ Clean
+session-refresh-tokens  ⚠️ 1 uncommitted, 1 untracked, no re…
+
+✅ main
+Path:   /home/dev/code/api
+HEAD:   0123456
+Age:    40d
+Remote: Up to date
+
+⚠️ vibe-ws/session-refresh-tokens
+Path:    /home/dev/code/api/.worktrees/session-refresh-toke…
+HEAD:    fedcba9
+Base:    main
+Task:    Refresh session tokens before they expire
+Age:     3h
+Remote:  No remote tracking
+Changes: 1 uncommitted, 1 untracked
+  📝 Uncommitted changes:
+    src/auth/session.rs
+  ❓ Untracked files:
+    notes.md
+
+RESULT      BRANCH                          REASON
+────────────────────────────────────────────────────────────
+✅ cleaned  vibe-ws/session-refresh-tokens  Merged into mai…
+❌ failed   vibe-ws/flaky-ci                Worktree is loc…
+            Error: fatal: 'remove' is not possible"#
+        );
+    }
+
+    #[test]
+    fn test_medium_terminal_truncates_path_then_branch() {
+        assert_eq!(
+            render(100),
+            r#"TASK ID                 STATUS                              BRANCH      BASE  PATH      AGE  HEAD
+────────────────────────────────────────────────────────────────────────────────────────────────────
+(main)                  ✅ Clean                            main        -     api       40d  0123456
+session-refresh-tokens  ⚠️ 1 uncommitted, 1 untracked, no …  vibe-ws/s…  main  session…  3h   fedcba9
+                        Refresh session tokens before they expire
+
+TASK ID                 STATUS
+────────────────────────────────────────────────────────────────
+(main)                  ✅ Clean
+session-refresh-tokens  ⚠️ 1 uncommitted, 1 untracked, no remote
+
+✅ main
+Path:   /home/dev/code/api
+HEAD:   0123456
+Age:    40d
+Remote: Up to date
+
+⚠️ vibe-ws/session-refresh-tokens
+Path:    /home/dev/code/api/.worktrees/session-refresh-tokens__1a2b3c4d
+HEAD:    fedcba9
+Base:    main
+Task:    Refresh session tokens before they expire
+Age:     3h
+Remote:  No remote tracking
+Changes: 1 uncommitted, 1 untracked
+  📝 Uncommitted changes:
+    src/auth/session.rs
+  ❓ Untracked files:
+    notes.md
+
+RESULT      BRANCH                          REASON
+────────────────────────────────────────────────────────────────────────────────────────────────────
+✅ cleaned  vibe-ws/session-refresh-tokens  Merged into main (squash, 100% confidence) and older th…
+❌ failed   vibe-ws/flaky-ci                Worktree is locked by another process
+            Error: fatal: 'remove' is not possible"#
+        );
+    }
+
+    #[test]
+    fn test_wide_terminal_fits_everything() {
+        assert_eq!(
+            render(160),
+            r#"TASK ID                 STATUS                                    BRANCH                          BASE  PATH                              AGE  HEAD
+──────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────
+(main)                  ✅ Clean                                  main                            -     api                               40d  0123456
+session-refresh-tokens  ⚠️ 1 uncommitted, 1 untracked, no remote  vibe-ws/session-refresh-tokens  main  session-refresh-tokens__1a2b3c4d  3h   fedcba9
+                        Refresh session tokens before they expire
+
+TASK ID                 STATUS
+────────────────────────────────────────────────────────────────
+(main)                  ✅ Clean
+session-refresh-tokens  ⚠️ 1 uncommitted, 1 untracked, no remote
+
+✅ main
+Path:   /home/dev/code/api
+HEAD:   0123456
+Age:    40d
+Remote: Up to date
+
+⚠️ vibe-ws/session-refresh-tokens
+Path:    /home/dev/code/api/.worktrees/session-refresh-tokens__1a2b3c4d
+HEAD:    fedcba9
+Base:    main
+Task:    Refresh session tokens before they expire
+Age:     3h
+Remote:  No remote tracking
+Changes: 1 uncommitted, 1 untracked
+  📝 Uncommitted changes:
+    src/auth/session.rs
+  ❓ Untracked files:
+    notes.md
+
+RESULT      BRANCH                          REASON
+────────────────────────────────────────────────────────────────────────────────────────────────────────────
+✅ cleaned  vibe-ws/session-refresh-tokens  Merged into main (squash, 100% confidence) and older than 7 days
+❌ failed   vibe-ws/flaky-ci                Worktree is locked by another process
+            Error: fatal: 'remove' is not possible"#
+        );
+    }
+}
@@ -0,0 +1,382 @@
+//! Running a command in one or many worktrees for `vibe git worktree exec`
+//!
+//! The command runs with its working directory set to each worktree and with
+//! `VIBE_WORKTREE_TASK` / `VIBE_WORKTREE_BRANCH` describing the worktree, so
+//! scripts can tell where they are. Sequential runs stream output live;
+//! parallel runs capture it and print each worktree's output as it finishes.
+
+use anyhow::Result;
+use colored::*;
+use serde::Serialize;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+use crate::output::exit::{BatchSummary, CommandError};
+use crate::output::theme::{paint, Role};
+use crate::utils::direnv;
+use crate::utils::platform::resolve_command;
+use crate::workspace::operations::default_max_parallel_git;
+use crate::{display_eprintln, display_println};
+
+use super::status::WorktreeInfo;
+
+/// Task id of the worktree the command runs in (empty for the main checkout)
+pub const TASK_ENV: &str = "VIBE_WORKTREE_TASK";
+
+/// Branch of the worktree the command runs in
+pub const BRANCH_ENV: &str = "VIBE_WORKTREE_BRANCH";
+
+#[derive(Debug, Clone)]
+pub struct ExecOptions {
+    /// Run in several worktrees at once
+    pub parallel: bool,
+    /// Stop starting new runs after the first failure
+    pub fail_fast: bool,
+    /// Capture output into the results instead of printing it
+    pub capture: bool,
+    /// Load each worktree's `.envrc` through direnv
+    pub direnv: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecOutcome {
+    Succeeded,
+    Failed,
+    /// Not run because an earlier run failed with `--fail-fast`
+    Skipped,
+}
+
+/// Result of the command in one worktree
+#[derive(Debug, Clone, Serialize)]
+pub struct ExecResult {
+    pub task_id: Option<String>,
+    pub branch: String,
+    pub path: PathBuf,
+    pub outcome: ExecOutcome,
+    /// Exit code, when the process ran and exited normally
+    pub exit_code: Option<i32>,
+    pub duration_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stdout: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stderr: Option<String>,
+    /// Why the command could not be started
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl ExecResult {
+    fn skipped(worktree: &WorktreeInfo) -> Self {
+        Self {
+            task_id: worktree.task_id.clone(),
+            branch: worktree.branch.clone(),
+            path: worktree.path.clone(),
+            outcome: ExecOutcome::Skipped,
+            exit_code: None,
+            duration_ms: 0,
+            stdout: None,
+            stderr: None,
+            error: None,
+        }
+    }
+
+    /// Task id, or the branch for worktrees without one
+    pub fn label(&self) -> &str {
+        self.task_id.as_deref().unwrap_or(&self.branch)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ExecReport {
+    pub command: Vec<String>,
+    pub results: Vec<ExecResult>,
+}
+
+impl ExecReport {
+    pub fn failed_count(&self) -> usize {
+        self.results
+            .iter()
+            .filter(|result| result.outcome == ExecOutcome::Failed)
+            .count()
+    }
+
+    /// Skipped runs count as failures: the command did not pass there
+    pub fn summary(&self) -> BatchSummary {
+        BatchSummary {
+            total: self.results.len(),
+            failed: self
+                .results
+                .iter()
+                .filter(|result| result.outcome != ExecOutcome::Succeeded)
+                .count(),
+        }
+    }
+}
+
+/// Run `command` (program and arguments) in each of `worktrees`. Results come
+/// back in the order of `worktrees`.
+pub async fn run_in_worktrees(
+    worktrees: &[WorktreeInfo],
+    command: &[String],
+    options: &ExecOptions,
+) -> Result<ExecReport> {
+    if command.is_empty() {
+        return Err(CommandError::Usage("No command given to run".to_string()).into());
+    }
+
+    let results = if options.parallel {
+        run_parallel(worktrees, command, options).await
+    } else {
+        run_sequential(worktrees, command, options).await
+    };
+
+    Ok(ExecReport {
+        command: command.to_vec(),
+        results,
+    })
+}
+
+async fn run_sequential(
+    worktrees: &[WorktreeInfo],
+    command: &[String],
+    options: &ExecOptions,
+) -> Vec<ExecResult> {
+    let mut results = Vec::with_capacity(worktrees.len());
+    let mut failed = false;
+
+    for worktree in worktrees {
+        if failed && options.fail_fast {
+            results.push(ExecResult::skipped(worktree));
+            continue;
+        }
+
+        if !options.capture {
+            print_header(worktree);
+        }
+        let result = run_one(worktree, command, options.capture, options.direnv).await;
+        if !options.capture {
+            if let Some(error) = &result.error {
+                display_eprintln!("{}", paint(Role::Error, error));
+            }
+        }
+        failed |= result.outcome == ExecOutcome::Failed;
+        results.push(result);
+    }
+
+    results
+}
+
+async fn run_parallel(
+    worktrees: &[WorktreeInfo],
+    command: &[String],
+    options: &ExecOptions,
+) -> Vec<ExecResult> {
+    let semaphore = Arc::new(Semaphore::new(default_max_parallel_git()));
+    let stop = Arc::new(AtomicBool::new(false));
+    let mut tasks = JoinSet::new();
+
+    for (index, worktree) in worktrees.iter().cloned().enumerate() {
+        let semaphore = Arc::clone(&semaphore);
+        let stop = Arc::clone(&stop);
+        let command = command.to_vec();
+        let options = options.clone();
+        tasks.spawn(async move {
+            let _permit = semaphore.acquire_owned().await.ok();
+            if options.fail_fast && stop.load(Ordering::SeqCst) {
+                return (index, ExecResult::skipped(&worktree));
+            }
+            // Interleaved output would be unreadable, so always capture
+            let result = run_one(&worktree, &command, true, options.direnv).await;
+            if result.outcome == ExecOutcome::Failed {
+                stop.store(true, Ordering::SeqCst);
+            }
+            (index, result)
+        });
+    }
+
+    let mut results: Vec<Option<ExecResult>> = vec![None; worktrees.len()];
+    while let Some(joined) = tasks.join_next().await {
+        let Ok((index, result)) = joined else {
+            continue;
+        };
+        if !options.capture && result.outcome != ExecOutcome::Skipped {
+            print_captured(&worktrees[index], &result);
+        }
+        results[index] = Some(result);
+    }
+
+    results
+        .into_iter()
+        .zip(worktrees)
+        .map(|(result, worktree)| {
+            result.unwrap_or_else(|| ExecResult {
+                outcome: ExecOutcome::Failed,
+                error: Some("Task panicked".to_string()),
+                ..ExecResult::skipped(worktree)
+            })
+        })
+        .collect()
+}
+
+async fn run_one(
+    worktree: &WorktreeInfo,
+    command: &[String],
+    capture: bool,
+    enable_direnv: bool,
+) -> ExecResult {
+    let mut process = direnv::command(resolve_command(&command[0]), &worktree.path, enable_direnv);
+    process
+        .args(&command[1..])
+        .current_dir(&worktree.path)
+        .env(TASK_ENV, worktree.task_id.as_deref().unwrap_or_default())
+        .env(BRANCH_ENV, &worktree.branch);
+    if capture {
+        process.stdin(Stdio::null());
+    }
+
+    let started = Instant::now();
+    // `output()` pipes stdout and stderr; `status()` leaves them on the terminal
+    let output = if capture {
+        process.output().await.map(|output| {
+            let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+            let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+            (output.status, Some((stdout, stderr)))
+        })
+    } else {
+        process.status().await.map(|status| (status, None))
+    };
+
+    let mut result = ExecResult {
+        duration_ms: started.elapsed().as_millis() as u64,
+        ..ExecResult::skipped(worktree)
+    };
+    match output {
+        Ok((status, captured)) => {
+            result.outcome = if status.success() {
+                ExecOutcome::Succeeded
+            } else {
+                ExecOutcome::Failed
+            };
+            result.exit_code = status.code();
+            if let Some((stdout, stderr)) = captured {
+                result.stdout = Some(stdout);
+                result.stderr = Some(stderr);
+            }
+        }
+        Err(e) => {
+            result.outcome = ExecOutcome::Failed;
+            result.error = Some(format!("Failed to run {}: {e}", command[0]));
+        }
+    }
+    result
+}
+
+fn print_header(worktree: &WorktreeInfo) {
+    display_println!(
+        "{} {} {}",
+        "==>".bold(),
+        paint(
+            Role::Accent,
+            worktree.task_id.as_deref().unwrap_or(&worktree.branch)
+        )
+        .bold(),
+        paint(Role::Dim, worktree.path.display().to_string())
+    );
+}
+
+fn print_captured(worktree: &WorktreeInfo, result: &ExecResult) {
+    print_header(worktree);
+    if let Some(stdout) = result.stdout.as_deref().filter(|s| !s.is_empty()) {
+        display_println!("{}", stdout.trim_end());
+    }
+    if let Some(stderr) = result.stderr.as_deref().filter(|s| !s.is_empty()) {
+        display_eprintln!("{}", stderr.trim_end());
+    }
+    if let Some(error) = &result.error {
+        display_eprintln!("{}", paint(Role::Error, error));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::worktree::status::WorktreeStatus;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    fn worktree(path: PathBuf, task_id: &str) -> WorktreeInfo {
+        WorktreeInfo {
+            path,
+            branch: format!("vibe-ws/{task_id}"),
+            head: String::new(),
+            status: WorktreeStatus::default(),
+            age: Duration::ZERO,
+            task_id: Some(task_id.to_string()),
+            is_detached: false,
+            protected: false,
+            metadata: Default::default(),
+            base_refresh: None,
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_run_in_worktrees() {
+        let dir = TempDir::new().unwrap();
+        let passing = dir.path().join("passing");
+        let failing = dir.path().join("failing");
+        std::fs::create_dir_all(&passing).unwrap();
+        std::fs::create_dir_all(failing.join("broken")).unwrap();
+        let worktrees = vec![
+            worktree(failing, "failing"),
+            worktree(passing.clone(), "passing"),
+        ];
+        // Fails where a `broken` directory exists; echoes the injected env
+        let command: Vec<String> = [
+            "sh",
+            "-c",
+            "test ! -d broken || exit 3; echo \"$VIBE_WORKTREE_TASK $VIBE_WORKTREE_BRANCH $PWD\"",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+
+        for parallel in [false, true] {
+            let options = ExecOptions {
+                parallel,
+                fail_fast: false,
+                capture: true,
+                direnv: false,
+            };
+            let report = run_in_worktrees(&worktrees, &command, &options)
+                .await
+                .unwrap();
+            assert_eq!(report.results[0].outcome, ExecOutcome::Failed);
+            assert_eq!(report.results[0].exit_code, Some(3));
+            assert_eq!(report.results[1].outcome, ExecOutcome::Succeeded);
+            let stdout = report.results[1].stdout.as_deref().unwrap();
+            assert!(stdout.starts_with("passing vibe-ws/passing "));
+            assert!(stdout.trim_end().ends_with("passing"));
+            assert_eq!(report.summary().failed, 1);
+        }
+
+        let options = ExecOptions {
+            parallel: false,
+            fail_fast: true,
+            capture: true,
+            direnv: false,
+        };
+        let report = run_in_worktrees(&worktrees, &command, &options)
+            .await
+            .unwrap();
+        assert_eq!(report.results[1].outcome, ExecOutcome::Skipped);
+        assert_eq!(report.failed_count(), 1);
+        assert_eq!(report.summary().failed, 2);
+    }
+}
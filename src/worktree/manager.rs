@@ -3,12 +3,17 @@
 use anyhow::Result;
 use std::path::PathBuf;
 
-use crate::workspace::WorkspaceManager;
+use crate::git::backend;
+use crate::workspace::{repo_abs_path, BulkOperation, WorkspaceManager};
+use crate::worktree::cleanup::{CleanupOptions, CleanupReport, CleanupStrategy, WorktreeCleanup};
 use crate::worktree::config::WorktreeConfig;
 use crate::worktree::config_manager::{
     ConfigSummary, ConfigValidationError, WorktreeConfigManager,
 };
-use crate::worktree::operations::{CreateOptions, RemoveOptions, WorktreeOperations};
+use crate::worktree::operations::{
+    AdoptOptions, AdoptedWorktree, CreateOptions, RemoveOptions, RemoveOutcome, WorktreeOperations,
+};
+use crate::worktree::orphans::OrphanedWorktree;
 use crate::worktree::status::WorktreeInfo;
 
 /// Main coordinator for all worktree operations
@@ -81,11 +86,14 @@ impl WorktreeManager {
             .unwrap_or(workspace_manager.get_workspace_root())
             .clone();
 
+        // Follow this workspace's git_backend preference even when the
+        // process-wide backend was never selected, as for library callers
         let operations = if let Some(name) = repo_name {
             WorktreeOperations::new_with_repo_name(repo_root.clone(), config.clone(), name)
         } else {
             WorktreeOperations::new(repo_root.clone(), config.clone())
-        };
+        }
+        .with_git_backend(backend::backend_for(workspace_manager.get_git_backend()));
 
         Ok(Self {
             operations,
@@ -101,7 +109,9 @@ impl WorktreeManager {
             task_id,
             base_branch: None,
             force: false,
+            include_protected: false,
             custom_path: None,
+            metadata: Default::default(),
         };
 
         self.operations.create_worktree(options).await
@@ -116,26 +126,66 @@ impl WorktreeManager {
     }
 
     /// Remove a worktree
-    pub async fn remove_worktree(&self, branch_or_path: String, force: bool) -> Result<()> {
+    pub async fn remove_worktree(
+        &self,
+        branch_or_path: String,
+        force: bool,
+    ) -> Result<RemoveOutcome> {
         let options = RemoveOptions {
             target: branch_or_path,
             force,
             delete_branch: false,
+            include_protected: false,
         };
 
         self.operations.remove_worktree(options).await
     }
 
     /// Remove a worktree with custom options
-    pub async fn remove_worktree_with_options(&self, options: RemoveOptions) -> Result<()> {
+    pub async fn remove_worktree_with_options(
+        &self,
+        options: RemoveOptions,
+    ) -> Result<RemoveOutcome> {
         self.operations.remove_worktree(options).await
     }
 
+    /// Set or clear a worktree's task description and link
+    pub async fn annotate_worktree(
+        &self,
+        target: &str,
+        description: Option<String>,
+        link: Option<String>,
+    ) -> Result<WorktreeInfo> {
+        self.operations
+            .annotate_worktree(target, description, link)
+            .await
+    }
+
+    /// Push a worktree's deadline back by `by`
+    pub async fn extend_worktree(
+        &self,
+        target: &str,
+        by: chrono::Duration,
+    ) -> Result<WorktreeInfo> {
+        self.operations.extend_worktree(target, by).await
+    }
+
+    /// Register a worktree created outside vibe
+    pub async fn adopt_worktree(&self, options: AdoptOptions) -> Result<AdoptedWorktree> {
+        self.operations.adopt_worktree(options).await
+    }
+
     /// List all worktrees
     pub async fn list_worktrees(&self) -> Result<Vec<WorktreeInfo>> {
         self.operations.list_worktrees().await
     }
 
+    /// Directories in the worktree base dir that git no longer tracks,
+    /// usually left behind by a crash during creation
+    pub async fn find_orphaned_worktrees(&self) -> Result<Vec<OrphanedWorktree>> {
+        self.operations.find_orphaned_worktrees().await
+    }
+
     /// Get the git repository root
     pub async fn get_git_root(&self) -> Result<PathBuf> {
         self.operations.find_git_root().await
@@ -151,6 +201,33 @@ impl WorktreeManager {
         self.operations.resolve_worktree_target(target).await
     }
 
+    /// Merge a worktree's branch using the given cleanup strategy, ignoring
+    /// age and merge-confidence limits since the worktree was chosen explicitly
+    pub async fn merge_worktree(
+        &self,
+        worktree: &WorktreeInfo,
+        strategy: CleanupStrategy,
+    ) -> Result<CleanupReport> {
+        let options = CleanupOptions {
+            strategy,
+            min_age_hours: None,
+            force: false,
+            dry_run: false,
+            auto_confirm: true,
+            confirmations: Default::default(),
+            // Target this specific branch
+            branch_prefix_filter: Some(worktree.branch.clone()),
+            merged_only: false,
+            min_merge_confidence: 0.0,
+            include_orphans: false,
+            expired_only: false,
+        };
+
+        WorktreeCleanup::new(self.config.clone(), self.get_operations())
+            .cleanup_worktrees(options)
+            .await
+    }
+
     /// Get a clone of the operations (for cleanup)
     pub fn get_operations(&self) -> WorktreeOperations {
         self.operations.clone()
@@ -174,3 +251,49 @@ impl WorktreeManager {
         }
     }
 }
+
+/// Linked worktrees of one configured repository
+#[derive(Debug, Clone)]
+pub struct RepositoryWorktrees {
+    pub repo_name: String,
+    pub repo_path: PathBuf,
+    pub worktrees: Vec<WorktreeInfo>,
+}
+
+/// List the linked worktrees of every configured repository. Works from any
+/// directory; repositories that are missing, not git checkouts, or excluded
+/// from status are skipped.
+pub async fn list_workspace_worktrees(
+    workspace_manager: &WorkspaceManager,
+) -> Vec<RepositoryWorktrees> {
+    let mut listings = Vec::new();
+
+    for repo in workspace_manager.list_repositories() {
+        if repo.is_excluded_from(BulkOperation::Status) {
+            continue;
+        }
+        let repo_path = repo_abs_path(workspace_manager.config(), repo);
+        if !repo_path.join(".git").exists() {
+            continue;
+        }
+
+        let Ok(manager) = WorktreeManager::new(repo_path.clone(), None).await else {
+            continue;
+        };
+        let Ok(worktrees) = manager.list_worktrees().await else {
+            continue;
+        };
+
+        // `git worktree list` always reports the main checkout first
+        let worktrees: Vec<WorktreeInfo> = worktrees.into_iter().skip(1).collect();
+        if !worktrees.is_empty() {
+            listings.push(RepositoryWorktrees {
+                repo_name: repo.name.clone(),
+                repo_path,
+                worktrees,
+            });
+        }
+    }
+
+    listings
+}
@@ -10,6 +10,7 @@ use std::path::Path;
 use tokio::process::Command;
 use tracing::warn;
 
+use crate::git::backend;
 use crate::worktree::config::WorktreeMergeDetectionConfig;
 use crate::worktree::status::MergeInfo;
 
@@ -269,19 +270,12 @@ impl MergeDetector {
         let main_branch = self.find_best_main_branch(worktree_path).await?;
 
         // Get merge base
-        let merge_base_output = Command::new("git")
-            .args(&["merge-base", &main_branch, branch_name])
-            .current_dir(worktree_path)
-            .output()
-            .await?;
-
-        if !merge_base_output.status.success() {
+        let Some(merge_base) = backend::current()
+            .merge_base(worktree_path, &main_branch, branch_name)
+            .await?
+        else {
             return Ok((false, 0.0, Some("Cannot find merge base".to_string())));
-        }
-
-        let merge_base = String::from_utf8_lossy(&merge_base_output.stdout)
-            .trim()
-            .to_string();
+        };
 
         // Check if there are any changes between merge-base and branch tip
         let diff_output = Command::new("git")
@@ -470,17 +464,10 @@ impl MergeDetector {
         main_branch: &str,
         branch_name: &str,
     ) -> Result<String> {
-        let output = Command::new("git")
-            .args(&["merge-base", main_branch, branch_name])
-            .current_dir(worktree_path)
-            .output()
-            .await?;
-
-        if output.status.success() {
-            Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
-        } else {
-            Err(anyhow::anyhow!("Cannot find merge base"))
-        }
+        backend::current()
+            .merge_base(worktree_path, main_branch, branch_name)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Cannot find merge base"))
     }
 
     async fn get_changed_files(
@@ -0,0 +1,305 @@
+//! Task metadata attached to worktrees
+//!
+//! A description and ticket link recorded at creation (or later with
+//! `vibe git worktree annotate`) so a task id still means something a week
+//! on, plus the branch and commit the worktree was created from, when it
+//! was created, and an optional deadline for time-boxed work. Worktrees
+//! adopted from outside vibe also keep their task id here. Stored in the git status cache
+//! database, keyed by the repository's main checkout and the worktree's
+//! branch.
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+use crate::cache::GitStatusCache;
+use crate::output::exit::CommandError;
+use crate::utils::fs::canonicalize;
+use crate::workspace::constants::get_cache_dir;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorktreeMetadata {
+    /// What the task is about
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Ticket, issue, or pull request URL
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link: Option<String>,
+
+    /// Branch the worktree was created from; unknown for worktrees created
+    /// outside vibe
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_branch: Option<String>,
+
+    /// Commit the base branch pointed at when the worktree was created
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_commit: Option<String>,
+
+    /// Deadline set with `--expires`; past it, the worktree is flagged and
+    /// `vibe git worktree clean --expired-only` picks it up
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+
+    /// Task id given to a worktree adopted with `vibe git worktree adopt`
+    /// whose branch lacks the prefix; shown as the worktree's own task id
+    #[serde(default, skip_serializing)]
+    pub task_id: Option<String>,
+
+    /// When the worktree was created, which outlives moving its directory
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<DateTime<Utc>>,
+
+    /// Pull request `vibe task finish` opened for the branch
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pull_request: Option<String>,
+}
+
+impl WorktreeMetadata {
+    /// Metadata from command-line values; the link must be an http(s) URL
+    pub fn new(description: Option<String>, link: Option<String>) -> Result<Self> {
+        let mut metadata = Self::default();
+        metadata.update(description, link)?;
+        Ok(metadata)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.description.is_none()
+            && self.link.is_none()
+            && self.base_branch.is_none()
+            && self.base_commit.is_none()
+            && self.expires_at.is_none()
+            && self.task_id.is_none()
+            && self.created_at.is_none()
+            && self.pull_request.is_none()
+    }
+
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|deadline| deadline <= now)
+    }
+
+    /// Push the deadline back by `by`, counting from now if it already passed
+    /// or was never set
+    pub fn extend(&mut self, by: Duration, now: DateTime<Utc>) -> DateTime<Utc> {
+        let from = self
+            .expires_at
+            .filter(|deadline| *deadline > now)
+            .unwrap_or(now);
+        let deadline = from + by;
+        self.expires_at = Some(deadline);
+        deadline
+    }
+
+    /// "expires in 2d 3h" or "expired 5h ago", if a deadline is set
+    pub fn expiry_label(&self, now: DateTime<Utc>) -> Option<String> {
+        let deadline = self.expires_at?;
+        Some(if deadline > now {
+            format!("expires in {}", format_remaining(deadline - now))
+        } else {
+            format!("expired {} ago", format_remaining(now - deadline))
+        })
+    }
+
+    /// Replace the given fields; an empty value clears that field
+    pub fn update(&mut self, description: Option<String>, link: Option<String>) -> Result<()> {
+        if let Some(link) = &link {
+            validate_link(link)?;
+        }
+        if let Some(description) = description {
+            self.description = non_empty(description);
+        }
+        if let Some(link) = link {
+            self.link = non_empty(link);
+        }
+        Ok(())
+    }
+}
+
+/// Parse a duration like `3d`, `12h`, `30m`, or `1w`
+pub fn parse_duration(value: &str) -> Result<Duration> {
+    let value = value.trim();
+    let invalid = || {
+        CommandError::Usage(format!(
+            "Invalid duration '{value}': use a number followed by m, h, d, or w (e.g. 3d)"
+        ))
+    };
+    let split = value
+        .find(|c: char| !c.is_ascii_digit())
+        .ok_or_else(invalid)?;
+    let (amount, unit) = value.split_at(split);
+    let amount: i64 = amount.parse().map_err(|_| invalid())?;
+    if amount == 0 {
+        return Err(invalid().into());
+    }
+    let duration = match unit.trim() {
+        "m" | "min" | "mins" | "minute" | "minutes" => Duration::try_minutes(amount),
+        "h" | "hour" | "hours" => Duration::try_hours(amount),
+        "d" | "day" | "days" => Duration::try_days(amount),
+        "w" | "week" | "weeks" => Duration::try_weeks(amount),
+        _ => None,
+    };
+    Ok(duration.ok_or_else(invalid)?)
+}
+
+/// Two most significant units of a duration, e.g. "2d 3h" or "45m"
+fn format_remaining(duration: Duration) -> String {
+    if crate::output::is_deterministic() {
+        return crate::output::TIME_PLACEHOLDER.to_string();
+    }
+    let minutes = duration.num_minutes().max(0);
+    let (days, hours, minutes) = (minutes / 1440, minutes / 60 % 24, minutes % 60);
+    match (days, hours) {
+        (0, 0) => format!("{minutes}m"),
+        (0, _) if minutes > 0 => format!("{hours}h {minutes}m"),
+        (0, _) => format!("{hours}h"),
+        (_, 0) => format!("{days}d"),
+        _ => format!("{days}d {hours}h"),
+    }
+}
+
+fn non_empty(value: String) -> Option<String> {
+    let value = value.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+fn validate_link(link: &str) -> Result<()> {
+    let link = link.trim();
+    if link.is_empty() {
+        return Ok(());
+    }
+    match url::Url::parse(link) {
+        Ok(url) if matches!(url.scheme(), "http" | "https") => Ok(()),
+        _ => Err(CommandError::Usage(format!("Link must be an http(s) URL: {link}")).into()),
+    }
+}
+
+fn store() -> GitStatusCache {
+    GitStatusCache::new(get_cache_dir().join("git_status.db"))
+}
+
+/// Key for a repository: its main checkout, resolved
+fn repo_key(main_checkout: &Path) -> String {
+    canonicalize(main_checkout)
+        .unwrap_or_else(|_| main_checkout.to_path_buf())
+        .to_string_lossy()
+        .to_string()
+}
+
+/// Metadata for every annotated branch of a repository. Best effort: a
+/// missing or unreadable cache yields nothing.
+pub async fn load_for_repo(main_checkout: &Path) -> HashMap<String, WorktreeMetadata> {
+    let store = store();
+    if !store.db_path().exists() {
+        return HashMap::new();
+    }
+    match store.get_worktree_metadata(&repo_key(main_checkout)).await {
+        Ok(metadata) => metadata,
+        Err(e) => {
+            debug!("Failed to read worktree metadata: {}", e);
+            HashMap::new()
+        }
+    }
+}
+
+/// How many worktrees vibe created, and hasn't removed, across the given
+/// main checkouts. Best effort like [`load_for_repo`].
+pub async fn count_for_repos(main_checkouts: &[PathBuf]) -> usize {
+    let store = store();
+    if !store.db_path().exists() {
+        return 0;
+    }
+    match store.count_worktree_metadata().await {
+        Ok(counts) => main_checkouts
+            .iter()
+            .filter_map(|path| counts.get(&repo_key(path)))
+            .sum(),
+        Err(e) => {
+            debug!("Failed to count worktree metadata: {}", e);
+            0
+        }
+    }
+}
+
+/// Record metadata for `branch`; empty metadata deletes the entry
+pub async fn save(main_checkout: &Path, branch: &str, metadata: &WorktreeMetadata) -> Result<()> {
+    let store = store();
+    if let Some(parent) = store.db_path().parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    store.initialize().await?;
+    store
+        .set_worktree_metadata(&repo_key(main_checkout), branch, metadata)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_metadata() {
+        let mut metadata = WorktreeMetadata::new(
+            Some("Fix OAuth refresh".to_string()),
+            Some("https://linear.app/acme/issue/AUTH-12".to_string()),
+        )
+        .unwrap();
+        assert_eq!(metadata.description.as_deref(), Some("Fix OAuth refresh"));
+
+        // Only the given field changes; an empty value clears it
+        metadata.update(None, Some(String::new())).unwrap();
+        assert_eq!(metadata.description.as_deref(), Some("Fix OAuth refresh"));
+        assert!(metadata.link.is_none());
+
+        assert!(metadata
+            .update(None, Some("not a url".to_string()))
+            .is_err());
+        assert!(metadata
+            .update(None, Some("file:///etc/passwd".to_string()))
+            .is_err());
+
+        metadata.update(Some("  ".to_string()), None).unwrap();
+        assert!(metadata.is_empty());
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("3d").unwrap(), Duration::days(3));
+        assert_eq!(parse_duration("12h").unwrap(), Duration::hours(12));
+        assert_eq!(parse_duration("30m").unwrap(), Duration::minutes(30));
+        assert_eq!(parse_duration("2 weeks").unwrap(), Duration::weeks(2));
+        for invalid in ["", "d", "3", "0d", "-1d", "3y", "1.5d"] {
+            assert!(parse_duration(invalid).is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_expiry() {
+        let now = Utc::now();
+        let mut metadata = WorktreeMetadata::default();
+        assert!(!metadata.is_expired(now));
+        assert!(metadata.expiry_label(now).is_none());
+
+        // Extending a worktree without a deadline counts from now
+        metadata.extend(Duration::days(2), now);
+        assert!(!metadata.is_expired(now));
+        assert_eq!(
+            metadata.expiry_label(now - Duration::hours(3)).as_deref(),
+            Some("expires in 2d 3h")
+        );
+
+        // A live deadline is pushed back; a passed one restarts from now
+        metadata.extend(Duration::days(1), now);
+        assert_eq!(metadata.expires_at, Some(now + Duration::days(3)));
+        let later = now + Duration::days(3) + Duration::hours(5);
+        assert!(metadata.is_expired(later));
+        assert_eq!(
+            metadata.expiry_label(later).as_deref(),
+            Some("expired 5h ago")
+        );
+        metadata.extend(Duration::hours(12), later);
+        assert_eq!(metadata.expires_at, Some(later + Duration::hours(12)));
+    }
+}
@@ -4,20 +4,28 @@
 //! integrated with vibe-workspace, including creation, status tracking,
 //! automated cleanup, and AI-assisted conflict resolution.
 
+pub mod base_refresh;
 pub mod cache;
 pub mod cleanup;
 pub mod config;
 pub mod config_manager;
+pub mod display;
+pub mod exec;
 pub mod manager;
 pub mod merge_detection;
+pub mod metadata;
 pub mod operations;
+pub mod orphans;
+pub mod protection;
+pub mod resolve;
 pub mod status;
+pub mod task;
 
 // Re-export core types for external use via lib.rs public API and internal module usage
 pub use cleanup::{CleanupOptions, CleanupStrategy};
 pub use config::WorktreeConfig;
-pub use manager::WorktreeManager;
-pub use operations::{CreateOptions, RemoveOptions};
+pub use manager::{list_workspace_worktrees, RepositoryWorktrees, WorktreeManager};
+pub use operations::{AdoptOptions, CreateOptions, RemoveOptions};
 
 use anyhow::Result;
 use std::path::PathBuf;
@@ -2,12 +2,28 @@
 
 use anyhow::{bail, Context, Result};
 use regex::Regex;
+use serde::Serialize;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use tokio::process::Command;
 use tracing::{debug, warn};
 
+use crate::git::backend::{self, GitBackend, WorktreeEntry};
+use crate::git::protection::{self as branch_protection, BranchProtection, BranchProtectionReason};
+use crate::git::ref_backup::{self, RefBackup};
+use crate::git::repo_lock::{self, RepoLock};
+use crate::output::exit::CommandError;
+use crate::output::redact;
+use crate::utils::fs::{canonicalize, paths_equal, strip_verbatim_prefix, to_forward_slashes};
+use crate::workspace::events::{self, Event};
+use crate::workspace::history::{self, EventKind, UndoAction};
+use crate::workspace::trash::{self, Trash};
+use crate::worktree::base_refresh::{self, BaseRefresh};
 use crate::worktree::config::{WorktreeConfig, WorktreeMode};
+use crate::worktree::metadata::{self, WorktreeMetadata};
+use crate::worktree::orphans::{self, OrphanedWorktree};
+use crate::worktree::protection::{protection_reason, ProtectedWorktreeError, ProtectionReason};
 use crate::worktree::status::WorktreeInfo;
 
 /// Options for creating a new worktree
@@ -22,8 +38,15 @@ pub struct CreateOptions {
     /// Force creation even if branch exists
     pub force: bool,
 
+    /// With `force`, recreate the branch even if it matches a protected
+    /// branch pattern
+    pub include_protected: bool,
+
     /// Custom worktree path (overrides default path calculation)
     pub custom_path: Option<PathBuf>,
+
+    /// Task description and link to record with the worktree
+    pub metadata: WorktreeMetadata,
 }
 
 /// Options for removing a worktree
@@ -37,6 +60,21 @@ pub struct RemoveOptions {
 
     /// Also delete the branch after removing worktree
     pub delete_branch: bool,
+
+    /// With `force`, delete the branch even if it matches a protected
+    /// branch pattern
+    pub include_protected: bool,
+}
+
+/// What removing a worktree did with its branch
+#[derive(Debug, Clone, Default)]
+pub struct RemoveOutcome {
+    /// Why the branch was kept although deleting it was asked for
+    pub protected_branch: Option<BranchProtectionReason>,
+    /// Trash item that restores the worktree
+    pub trash_id: Option<String>,
+    /// Where the deleted branch was backed up
+    pub backup: Option<RefBackup>,
 }
 
 impl Default for CreateOptions {
@@ -45,7 +83,9 @@ impl Default for CreateOptions {
             task_id: String::new(),
             base_branch: None,
             force: false,
+            include_protected: false,
             custom_path: None,
+            metadata: WorktreeMetadata::default(),
         }
     }
 }
@@ -56,14 +96,44 @@ impl Default for RemoveOptions {
             target: String::new(),
             force: false,
             delete_branch: false,
+            include_protected: false,
         }
     }
 }
 
+/// Options for adopting a worktree created outside vibe
+#[derive(Debug, Clone, Default)]
+pub struct AdoptOptions {
+    /// Branch name or worktree path of the worktree to adopt
+    pub target: String,
+
+    /// Task identifier to record for it
+    pub task_id: String,
+
+    /// Rename the branch to the configured prefix plus the task id
+    pub rename_branch: bool,
+
+    /// Move the worktree into the managed base directory
+    pub relocate: bool,
+}
+
+/// A worktree after adoption, with what changed to get it there
+#[derive(Debug, Clone, Serialize)]
+pub struct AdoptedWorktree {
+    #[serde(flatten)]
+    pub worktree: WorktreeInfo,
+    /// Branch name before `--rename-branch`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_branch: Option<String>,
+    /// Directory before `--relocate`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_path: Option<PathBuf>,
+}
+
 /// Git worktree operation types
 #[derive(Debug, Clone)]
 pub enum WorktreeOperation {
-    Create(CreateOptions),
+    Create(Box<CreateOptions>),
     Remove(RemoveOptions),
     List,
     Status(String),
@@ -75,6 +145,7 @@ pub struct WorktreeOperations {
     repo_root: PathBuf,
     config: WorktreeConfig,
     repo_name: Option<String>,
+    backend: Arc<dyn GitBackend>,
 }
 
 impl WorktreeOperations {
@@ -90,6 +161,7 @@ impl WorktreeOperations {
             repo_root,
             config,
             repo_name,
+            backend: backend::current(),
         }
     }
 
@@ -103,9 +175,16 @@ impl WorktreeOperations {
             repo_root,
             config,
             repo_name: Some(repo_name),
+            backend: backend::current(),
         }
     }
 
+    /// Query git through `backend` instead of the configured default
+    pub fn with_git_backend(mut self, backend: Arc<dyn GitBackend>) -> Self {
+        self.backend = backend;
+        self
+    }
+
     /// Create a new git worktree
     pub async fn create_worktree(&self, options: CreateOptions) -> Result<WorktreeInfo> {
         // Validate and sanitize the task ID
@@ -114,6 +193,7 @@ impl WorktreeOperations {
 
         // Validate branch name
         validate_branch_name(&branch_name)?;
+        let _lock = self.lock("worktree create").await?;
 
         // Calculate worktree path
         let worktree_path = match options.custom_path {
@@ -138,8 +218,25 @@ impl WorktreeOperations {
             );
         }
 
+        let base_refresh = if self.config.refresh_base_before_create {
+            self.refresh_base(options.base_branch.as_deref()).await
+        } else {
+            None
+        };
+
+        let mut metadata = options.metadata;
+        (metadata.base_branch, metadata.base_commit) =
+            self.resolve_base(options.base_branch.as_deref()).await;
+        metadata.created_at = Some(chrono::Utc::now());
+
         // Create the worktree
         let result = if branch_exists && options.force {
+            self.branch_protection().await.check(
+                &branch_name,
+                options.include_protected,
+                options.force,
+            )?;
+
             // Remove existing worktree first if it exists
             if let Ok(existing_path) = self.find_worktree_path(&branch_name).await {
                 warn!("Removing existing worktree at: {}", existing_path.display());
@@ -152,10 +249,16 @@ impl WorktreeOperations {
                 .await?;
             }
 
-            // Remove and recreate branch
-            self.execute_git_command(&["branch", "-D", &branch_name])
-                .await
-                .ok(); // Ignore errors
+            // Remove and recreate branch, unless it could not be backed up
+            // (which includes it not existing)
+            match ref_backup::backup_branch(&self.repo_root, &branch_name).await {
+                Ok(_) => {
+                    self.execute_git_command(&["branch", "-D", &branch_name])
+                        .await
+                        .ok(); // Ignore errors
+                }
+                Err(e) => debug!("Keeping branch {}: {}", branch_name, e),
+            }
             self.create_branch_and_worktree(
                 &branch_name,
                 &worktree_path,
@@ -177,6 +280,42 @@ impl WorktreeOperations {
             worktree_path.display()
         );
 
+        if !metadata.is_empty() {
+            let main_checkout = self.main_checkout().await?;
+            let saved = metadata::save(&main_checkout, &branch_name, &metadata).await;
+            match saved {
+                // Failing to record the base alone shouldn't fail the create
+                Err(e)
+                    if metadata.description.is_none()
+                        && metadata.link.is_none()
+                        && metadata.expires_at.is_none() =>
+                {
+                    debug!("Failed to record worktree base: {}", e);
+                }
+                saved => saved?,
+            }
+        }
+
+        history::record(
+            EventKind::WorktreeCreated,
+            format!(
+                "Created worktree {} at {}",
+                branch_name,
+                worktree_path.display()
+            ),
+            None,
+        )
+        .await;
+        events::emit(Event::WorktreeCreated {
+            repo: self
+                .main_checkout()
+                .await
+                .unwrap_or_else(|_| self.repo_root.clone()),
+            branch: branch_name.clone(),
+            path: worktree_path.clone(),
+            task_id: options.task_id.clone(),
+        });
+
         // Return worktree info
         Ok(WorktreeInfo {
             path: worktree_path,
@@ -186,15 +325,27 @@ impl WorktreeOperations {
             status: Default::default(),     // Will be filled by status tracking
             age: std::time::Duration::from_secs(0),
             is_detached: false,
+            protected: false,
+            metadata,
+            base_refresh,
         })
     }
 
     /// Remove a git worktree
-    pub async fn remove_worktree(&self, options: RemoveOptions) -> Result<()> {
+    pub async fn remove_worktree(&self, options: RemoveOptions) -> Result<RemoveOutcome> {
+        let _lock = self.lock("worktree remove").await?;
         // Use enhanced resolution that tries task_id first, then path, then branch
         let worktree_info = self.resolve_worktree_target(&options.target).await?;
         let worktree_path = worktree_info.path;
 
+        if let Some(reason) = self.protection(&worktree_path) {
+            return Err(ProtectedWorktreeError {
+                path: worktree_path,
+                reason,
+            }
+            .into());
+        }
+
         // Validate worktree exists
         if !worktree_path.exists() {
             bail!("Worktree path does not exist: {}", worktree_path.display());
@@ -208,13 +359,48 @@ impl WorktreeOperations {
             );
         }
 
-        // Extract branch name BEFORE removing the worktree if needed for deletion
+        // Extract branch name BEFORE removing the worktree if needed for
+        // deletion; a protected branch is kept and reported instead
+        let mut outcome = RemoveOutcome::default();
         let branch_name_for_deletion = if options.delete_branch {
-            Some(worktree_info.branch.clone())
+            match self.branch_protection().await.check(
+                &worktree_info.branch,
+                options.include_protected,
+                options.force,
+            ) {
+                Ok(()) => Some(worktree_info.branch.clone()),
+                Err(e) => {
+                    debug!("Keeping branch: {}", e);
+                    outcome.protected_branch = Some(e.reason);
+                    None
+                }
+            }
         } else {
             None
         };
 
+        // The removal goes to the trash; a forced one discards uncommitted
+        // changes, so they are archived there for restoring
+        let slot = match Trash::open_default().reserve(&worktree_info.branch).await {
+            Ok(slot) => Some(slot),
+            Err(e) => {
+                warn!("Failed to make room in the trash: {}", e);
+                None
+            }
+        };
+        let archive = match &slot {
+            Some(slot) if options.force => {
+                match history::archive_changes(&worktree_path, slot.changes_path()).await {
+                    Ok(archive) => archive,
+                    Err(e) => {
+                        warn!("Failed to save uncommitted changes for undo: {}", e);
+                        None
+                    }
+                }
+            }
+            _ => None,
+        };
+
         // Remove the worktree
         let mut args = vec!["worktree", "remove"];
         if options.force {
@@ -223,25 +409,333 @@ impl WorktreeOperations {
         let path_str = worktree_path.to_string_lossy();
         args.push(&path_str);
 
-        self.execute_git_command(&args).await?;
+        if let Err(e) = self.execute_git_command(&args).await {
+            if let Some(slot) = slot {
+                slot.discard().await;
+            }
+            return Err(e);
+        }
+
+        if !worktree_info.metadata.is_empty() {
+            let main_checkout = self.main_checkout().await?;
+            if let Err(e) = metadata::save(
+                &main_checkout,
+                &worktree_info.branch,
+                &WorktreeMetadata::default(),
+            )
+            .await
+            {
+                debug!("Failed to forget worktree metadata: {}", e);
+            }
+        }
 
         // Delete branch if requested (after worktree removal)
+        let branch_deleted = branch_name_for_deletion.is_some();
         if let Some(branch_name) = branch_name_for_deletion {
+            outcome.backup = ref_backup::backup_branch(&self.repo_root, &branch_name).await?;
             self.execute_git_command(&["branch", "-D", &branch_name])
                 .await?;
             debug!("Deleted branch: {}", branch_name);
         }
 
+        let repo = self
+            .main_checkout()
+            .await
+            .unwrap_or_else(|_| self.repo_root.clone());
+        outcome.trash_id = trash::record_removal(
+            slot,
+            &worktree_info.branch,
+            EventKind::WorktreeRemoved,
+            format!(
+                "Removed worktree {}{}",
+                worktree_info.branch,
+                if branch_deleted {
+                    " and its branch"
+                } else {
+                    ""
+                }
+            ),
+            UndoAction::RestoreWorktree {
+                repo: repo.clone(),
+                path: worktree_path.clone(),
+                branch: (!worktree_info.is_detached).then(|| worktree_info.branch.clone()),
+                head: worktree_info.head.clone(),
+                archive,
+            },
+        )
+        .await;
+        events::emit(Event::WorktreeRemoved {
+            repo,
+            branch: worktree_info.branch.clone(),
+            path: worktree_path.clone(),
+            branch_deleted,
+        });
+
         debug!("Removed worktree: {}", worktree_path.display());
-        Ok(())
+        Ok(outcome)
     }
 
     /// List all git worktrees
     pub async fn list_worktrees(&self) -> Result<Vec<WorktreeInfo>> {
-        let output = self
-            .execute_git_command(&["worktree", "list", "--porcelain"])
+        let entries = self.backend.worktrees(&self.repo_root).await?;
+        let mut annotations = match entries.first() {
+            Some(main) => metadata::load_for_repo(&main.path).await,
+            None => Default::default(),
+        };
+        Ok(entries
+            .into_iter()
+            .map(|entry| {
+                let mut info = self.worktree_info_from_entry(entry);
+                if let Some(metadata) = annotations.remove(&info.branch) {
+                    if info.task_id.is_none() {
+                        info.task_id = metadata.task_id.clone();
+                    }
+                    if let Some(created_at) = metadata.created_at {
+                        info.age = (chrono::Utc::now() - created_at)
+                            .to_std()
+                            .unwrap_or_default();
+                    }
+                    info.metadata = metadata;
+                }
+                info
+            })
+            .collect())
+    }
+
+    /// Set or clear a worktree's task description and link; `None` leaves a
+    /// field unchanged and an empty string clears it
+    pub async fn annotate_worktree(
+        &self,
+        target: &str,
+        description: Option<String>,
+        link: Option<String>,
+    ) -> Result<WorktreeInfo> {
+        let mut info = self.resolve_worktree_target(target).await?;
+        info.metadata.update(description, link)?;
+        let main_checkout = self.main_checkout().await?;
+        metadata::save(&main_checkout, &info.branch, &info.metadata).await?;
+        Ok(info)
+    }
+
+    /// Push a worktree's deadline back by `by`, counting from now if it has
+    /// already passed or was never set
+    pub async fn extend_worktree(
+        &self,
+        target: &str,
+        by: chrono::Duration,
+    ) -> Result<WorktreeInfo> {
+        let mut info = self.resolve_worktree_target(target).await?;
+        info.metadata.extend(by, chrono::Utc::now());
+        let main_checkout = self.main_checkout().await?;
+        metadata::save(&main_checkout, &info.branch, &info.metadata).await?;
+        Ok(info)
+    }
+
+    /// Bring a worktree created with plain `git worktree add` under vibe's
+    /// management: record its task id, base, and creation time, and
+    /// optionally give its branch the configured prefix and move it into the
+    /// managed base directory
+    pub async fn adopt_worktree(&self, options: AdoptOptions) -> Result<AdoptedWorktree> {
+        let sanitized_task_id = sanitize_branch_name(&options.task_id)?;
+        let info = self.resolve_worktree_target(&options.target).await?;
+        let main_checkout = self.main_checkout().await?;
+
+        if paths_equal(&info.path, &main_checkout) {
+            return Err(CommandError::Usage(
+                "The main checkout is not a worktree that can be adopted".to_string(),
+            )
+            .into());
+        }
+        if info.is_detached || info.branch == "(bare)" {
+            return Err(CommandError::Usage(format!(
+                "{} has no branch checked out; check one out before adopting it",
+                info.path.display()
+            ))
+            .into());
+        }
+        if !info.path.is_dir() {
+            bail!(
+                "Worktree directory {} is missing; run 'git worktree prune' instead",
+                info.path.display()
+            );
+        }
+        if let Some(task_id) = &info.task_id {
+            bail!(
+                "Worktree {} is already managed by vibe as task '{}'",
+                info.branch,
+                task_id
+            );
+        }
+        if self
+            .find_worktree_by_task_id(&options.task_id)
+            .await?
+            .is_some()
+        {
+            bail!(
+                "Another worktree already uses task id '{}'",
+                options.task_id
+            );
+        }
+
+        // Keep what's recorded under the old branch, filling in the rest
+        let mut metadata = info.metadata.clone();
+        if metadata.base_branch.is_none() {
+            let (base_branch, _) = self.resolve_base(None).await;
+            if let Some(base) = &base_branch {
+                metadata.base_commit = self
+                    .execute_git_command(&["merge-base", base, &info.branch])
+                    .await
+                    .ok()
+                    .map(|output| output.trim().to_string());
+            }
+            metadata.base_branch = base_branch;
+        }
+        metadata.created_at = metadata.created_at.or_else(|| {
+            fs::metadata(&info.path)
+                .and_then(|metadata| metadata.created())
+                .ok()
+                .map(chrono::DateTime::<chrono::Utc>::from)
+                .or_else(|| Some(chrono::Utc::now()))
+        });
+
+        let mut branch = info.branch.clone();
+        let mut previous_branch = None;
+        if options.rename_branch {
+            let renamed = format!("{}{}", self.config.prefix, sanitized_task_id);
+            validate_branch_name(&renamed)?;
+            if self.branch_exists(&renamed).await? {
+                bail!("Branch '{renamed}' already exists");
+            }
+            self.execute_git_command(&["branch", "-m", &branch, &renamed])
+                .await?;
+            previous_branch = Some(std::mem::replace(&mut branch, renamed));
+        }
+        metadata.task_id =
+            (!branch.starts_with(&self.config.prefix)).then(|| options.task_id.clone());
+
+        let mut path = info.path.clone();
+        let mut previous_path = None;
+        if options.relocate && !path.starts_with(self.managed_base_dir()) {
+            let target = self.calculate_worktree_path(&sanitized_task_id)?;
+            self.ensure_base_directory_exists().await?;
+            if self.config.auto_gitignore {
+                self.update_gitignore().await?;
+            }
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)
+                    .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+            }
+            self.execute_git_command(&[
+                "worktree",
+                "move",
+                &path.to_string_lossy(),
+                &target.to_string_lossy(),
+            ])
             .await?;
-        self.parse_worktree_list(&output).await
+            previous_path = Some(std::mem::replace(&mut path, target));
+        }
+
+        if let Some(previous) = &previous_branch {
+            metadata::save(&main_checkout, previous, &WorktreeMetadata::default()).await?;
+        }
+        metadata::save(&main_checkout, &branch, &metadata).await?;
+
+        history::record(
+            EventKind::WorktreeCreated,
+            format!(
+                "Adopted worktree {} at {} as task {}",
+                branch,
+                path.display(),
+                options.task_id
+            ),
+            None,
+        )
+        .await;
+        events::emit(Event::WorktreeCreated {
+            repo: main_checkout.clone(),
+            branch: branch.clone(),
+            path: path.clone(),
+            task_id: options.task_id.clone(),
+        });
+
+        Ok(AdoptedWorktree {
+            worktree: self.resolve_worktree_target(&branch).await?,
+            previous_branch,
+            previous_path,
+        })
+    }
+
+    /// The branch and commit a new worktree starts from. Without an explicit
+    /// base that's the main checkout's current branch; a detached HEAD has no
+    /// branch to record.
+    /// Bring the local base branch up to date with its upstream, warning
+    /// and carrying on from the stale base when that fails
+    async fn refresh_base(&self, base_branch: Option<&str>) -> Option<BaseRefresh> {
+        let branch = match base_branch {
+            Some(branch) => branch.to_string(),
+            None => self
+                .execute_git_command(&["symbolic-ref", "--short", "HEAD"])
+                .await
+                .ok()?
+                .trim()
+                .to_string(),
+        };
+        // Only local branches are refreshed; a remote-tracking base or a
+        // commit is used as given
+        self.execute_git_command(&[
+            "rev-parse",
+            "--verify",
+            "--quiet",
+            &format!("refs/heads/{branch}"),
+        ])
+        .await
+        .ok()?;
+        let checkout = self
+            .backend
+            .worktrees(&self.repo_root)
+            .await
+            .ok()?
+            .into_iter()
+            .find(|entry| entry.branch.as_deref() == Some(branch.as_str()))
+            .map(|entry| entry.path);
+
+        let refreshed = base_refresh::refresh(&self.repo_root, &branch, checkout.as_deref()).await;
+        if let Some(BaseRefresh::Stale { reason }) = &refreshed {
+            warn!(
+                "Could not refresh {} ({}); creating the worktree from the local branch",
+                branch, reason
+            );
+        }
+        refreshed
+    }
+
+    async fn resolve_base(&self, base_branch: Option<&str>) -> (Option<String>, Option<String>) {
+        let base = base_branch.unwrap_or("HEAD");
+        let commit = self
+            .execute_git_command(&["rev-parse", "--verify", &format!("{base}^{{commit}}")])
+            .await
+            .ok()
+            .map(|output| output.trim().to_string());
+        let branch = match base_branch {
+            Some(branch) => Some(branch.to_string()),
+            None => self
+                .execute_git_command(&["rev-parse", "--abbrev-ref", "HEAD"])
+                .await
+                .ok()
+                .map(|output| output.trim().to_string())
+                .filter(|branch| branch != "HEAD"),
+        };
+        (branch, commit)
+    }
+
+    /// The repository's main checkout, which git always lists first
+    pub(crate) async fn main_checkout(&self) -> Result<PathBuf> {
+        let entries = self.backend.worktrees(&self.repo_root).await?;
+        Ok(entries
+            .into_iter()
+            .next()
+            .map(|entry| entry.path)
+            .unwrap_or_else(|| self.repo_root.clone()))
     }
 
     /// Find git repository root
@@ -273,9 +767,9 @@ impl WorktreeOperations {
         }
 
         // Try as direct path
-        if let Ok(path) = PathBuf::from(target).canonicalize() {
+        if let Ok(path) = canonicalize(target) {
             let worktrees = self.list_worktrees().await?;
-            if let Some(worktree) = worktrees.into_iter().find(|w| w.path == path) {
+            if let Some(worktree) = worktrees.into_iter().find(|w| paths_equal(&w.path, &path)) {
                 return Ok(worktree);
             }
         }
@@ -286,7 +780,60 @@ impl WorktreeOperations {
             return Ok(worktree);
         }
 
-        bail!("Worktree not found: {}", target)
+        Err(CommandError::not_found("Worktree", target).into())
+    }
+
+    /// Hold the repository's lock while `operation` changes its worktrees
+    pub async fn lock(&self, operation: &str) -> Result<RepoLock> {
+        repo_lock::acquire(&self.repo_root, operation).await
+    }
+
+    /// Directory this repository's worktrees are created in
+    pub fn managed_base_dir(&self) -> PathBuf {
+        match self.config.mode {
+            WorktreeMode::Local => {
+                if self.config.base_dir.is_absolute() {
+                    self.config.base_dir.clone()
+                } else {
+                    self.repo_root.join(&self.config.base_dir)
+                }
+            }
+            WorktreeMode::Global => {
+                let base = if self.config.base_dir.is_absolute() {
+                    self.config.base_dir.clone()
+                } else {
+                    crate::workspace::constants::get_worktrees_dir()
+                };
+                // In global mode, each repository has its own subdirectory
+                if let Some(repo_name) = &self.repo_name {
+                    base.join(repo_name)
+                } else {
+                    base
+                }
+            }
+        }
+    }
+
+    /// Directories in the managed base dir that git does not list as
+    /// worktrees and that lack a working `.git` file
+    pub async fn find_orphaned_worktrees(&self) -> Result<Vec<OrphanedWorktree>> {
+        let base_dir = self.managed_base_dir();
+        if !base_dir.is_dir() {
+            return Ok(Vec::new());
+        }
+
+        let registered: Vec<PathBuf> = self
+            .list_worktrees()
+            .await?
+            .into_iter()
+            .map(|worktree| worktree.path)
+            .collect();
+        let mut orphans =
+            tokio::task::spawn_blocking(move || orphans::find_orphans(&base_dir, &registered))
+                .await
+                .context("Orphan scan panicked")?;
+        orphans.retain(|orphan| self.protection(&orphan.path).is_none());
+        Ok(orphans)
     }
 
     // Private implementation methods
@@ -299,16 +846,53 @@ impl WorktreeOperations {
     ) -> Result<CreateResult> {
         let base = base_branch.unwrap_or("HEAD");
 
-        let _output = self
+        // Build under a staging name and rename into place on success, so a
+        // crash never leaves a half-initialized directory at the final path
+        let mut staging_name = worktree_path
+            .file_name()
+            .map(|name| name.to_os_string())
+            .unwrap_or_else(|| "worktree".into());
+        staging_name.push(orphans::STAGING_SUFFIX);
+        let staging_path = worktree_path.with_file_name(staging_name);
+
+        if let Err(e) = self
             .execute_git_command(&[
                 "worktree",
                 "add",
                 "-b",
                 branch_name,
-                &worktree_path.to_string_lossy(),
+                &staging_path.to_string_lossy(),
                 base,
             ])
-            .await?;
+            .await
+        {
+            let _ = fs::remove_dir_all(&staging_path);
+            return Err(e);
+        }
+
+        let repaired = match fs::rename(&staging_path, worktree_path) {
+            Ok(()) => {
+                self.execute_git_command(&["worktree", "repair", &worktree_path.to_string_lossy()])
+                    .await
+            }
+            Err(e) => Err(anyhow::Error::new(e).context(format!(
+                "Failed to move worktree into place at {}",
+                worktree_path.display()
+            ))),
+        };
+        if let Err(e) = repaired {
+            let leftover = if staging_path.exists() {
+                &staging_path
+            } else {
+                worktree_path
+            };
+            let _ = fs::remove_dir_all(leftover);
+            let _ = self.execute_git_command(&["worktree", "prune"]).await;
+            let _ = self
+                .execute_git_command(&["branch", "-D", branch_name])
+                .await;
+            return Err(e);
+        }
 
         // Get the HEAD commit
         let head = self
@@ -366,14 +950,7 @@ impl WorktreeOperations {
                 } else {
                     // If base_dir is relative in global mode, make it relative to home directory
                     // or a central workspace location
-                    if let Some(home) = dirs::home_dir() {
-                        home.join(".toolprint")
-                            .join("vibe-workspace")
-                            .join("worktrees")
-                    } else {
-                        // Fallback to absolute path in temp directory
-                        std::env::temp_dir().join("vibe-worktrees")
-                    }
+                    crate::workspace::constants::get_worktrees_dir()
                 };
 
                 // Get repository name for directory structure
@@ -397,34 +974,7 @@ impl WorktreeOperations {
     }
 
     async fn ensure_base_directory_exists(&self) -> Result<()> {
-        let base_path = match self.config.mode {
-            WorktreeMode::Local => {
-                if self.config.base_dir.is_absolute() {
-                    self.config.base_dir.clone()
-                } else {
-                    self.repo_root.join(&self.config.base_dir)
-                }
-            }
-            WorktreeMode::Global => {
-                let base = if self.config.base_dir.is_absolute() {
-                    self.config.base_dir.clone()
-                } else {
-                    if let Some(home) = dirs::home_dir() {
-                        home.join(".toolprint")
-                            .join("vibe-workspace")
-                            .join("worktrees")
-                    } else {
-                        std::env::temp_dir().join("vibe-worktrees")
-                    }
-                };
-                // In global mode, also create the repository subdirectory
-                if let Some(repo_name) = &self.repo_name {
-                    base.join(repo_name)
-                } else {
-                    base
-                }
-            }
-        };
+        let base_path = self.managed_base_dir();
 
         if !base_path.exists() {
             fs::create_dir_all(&base_path).with_context(|| {
@@ -448,14 +998,15 @@ impl WorktreeOperations {
         };
 
         let gitignore_path = self.repo_root.join(".gitignore");
-        let ignore_pattern = format!("{}/", base_path.display());
+        // Patterns always use forward slashes; a backslash escapes in .gitignore
+        let ignore_pattern = gitignore_pattern(&base_path);
 
         // Check if pattern already exists
         if gitignore_path.exists() {
             let content = fs::read_to_string(&gitignore_path)?;
             if content
                 .lines()
-                .any(|line| line.trim() == ignore_pattern.trim())
+                .any(|line| gitignore_pattern(Path::new(line.trim())) == ignore_pattern)
             {
                 return Ok(()); // Already present
             }
@@ -542,79 +1093,58 @@ impl WorktreeOperations {
         Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
     }
 
-    async fn parse_worktree_list(&self, output: &str) -> Result<Vec<WorktreeInfo>> {
-        let mut worktrees = Vec::new();
-        let lines: Vec<&str> = output.lines().collect();
-        let mut i = 0;
-
-        while i < lines.len() {
-            let line = lines[i];
-            if line.starts_with("worktree ") {
-                let path = PathBuf::from(line.strip_prefix("worktree ").unwrap());
-                let mut branch = String::new();
-                let mut head = String::new();
-                let mut is_detached = false;
-
-                i += 1;
-                while i < lines.len() && !lines[i].starts_with("worktree ") {
-                    let info_line = lines[i];
-                    if info_line.starts_with("HEAD ") {
-                        head = info_line.strip_prefix("HEAD ").unwrap().to_string();
-                    } else if info_line.starts_with("branch ") {
-                        let branch_ref = info_line.strip_prefix("branch ").unwrap();
-                        branch = branch_ref
-                            .strip_prefix("refs/heads/")
-                            .unwrap_or(branch_ref)
-                            .to_string();
-                    } else if info_line == "detached" {
-                        is_detached = true;
-                        branch = "(detached)".to_string();
-                    } else if info_line == "bare" {
-                        branch = "(bare)".to_string();
-                    }
-                    i += 1;
-                }
-
-                // Calculate age
-                let age = if let Ok(metadata) = fs::metadata(&path) {
-                    if let Ok(created) = metadata.created() {
-                        std::time::SystemTime::now()
-                            .duration_since(created)
-                            .unwrap_or_default()
-                    } else {
-                        std::time::Duration::from_secs(0)
-                    }
-                } else {
-                    std::time::Duration::from_secs(0)
-                };
-
-                // Try to extract task_id from branch name by removing prefix
-                let task_id = if branch.starts_with(&self.config.prefix) {
-                    Some(
-                        branch
-                            .strip_prefix(&self.config.prefix)
-                            .unwrap_or(&branch)
-                            .to_string(),
-                    )
-                } else {
-                    None
-                };
+    fn worktree_info_from_entry(&self, entry: WorktreeEntry) -> WorktreeInfo {
+        let branch = if entry.bare {
+            "(bare)".to_string()
+        } else if entry.detached {
+            "(detached)".to_string()
+        } else {
+            entry.branch.unwrap_or_default()
+        };
 
-                worktrees.push(WorktreeInfo {
-                    path,
-                    branch,
-                    head,
-                    task_id,
-                    status: Default::default(),
-                    age,
-                    is_detached,
-                });
-            } else {
-                i += 1;
-            }
+        // Calculate age
+        let age = fs::metadata(&entry.path)
+            .and_then(|metadata| metadata.created())
+            .map(|created| {
+                std::time::SystemTime::now()
+                    .duration_since(created)
+                    .unwrap_or_default()
+            })
+            .unwrap_or_default();
+
+        // Try to extract task_id from branch name by removing prefix
+        let task_id = branch
+            .strip_prefix(&self.config.prefix)
+            .map(|task_id| task_id.to_string());
+
+        WorktreeInfo {
+            path: strip_verbatim_prefix(&entry.path),
+            branch,
+            head: entry.head,
+            task_id,
+            status: Default::default(),
+            age,
+            is_detached: entry.detached,
+            protected: self.protection(&entry.path).is_some(),
+            metadata: WorktreeMetadata::default(),
+            base_refresh: None,
         }
+    }
 
-        Ok(worktrees)
+    /// Protected branches of this repository, including GitHub's when
+    /// `check_github_protection` is set
+    pub async fn branch_protection(&self) -> BranchProtection {
+        branch_protection::load(
+            &self.repo_root,
+            &self.config.protected_branches,
+            self.config.check_github_protection,
+        )
+        .await
+    }
+
+    /// Why `path` must not be removed, if it is protected
+    pub fn protection(&self, path: &Path) -> Option<ProtectionReason> {
+        protection_reason(path, &self.repo_root, &self.config.protected_paths)
     }
 
     async fn execute_git_command(&self, args: &[&str]) -> Result<String> {
@@ -623,14 +1153,19 @@ impl WorktreeOperations {
             .current_dir(&self.repo_root)
             .output()
             .await
-            .with_context(|| format!("Failed to execute git command: git {}", args.join(" ")))?;
+            .with_context(|| {
+                format!(
+                    "Failed to execute git command: {}",
+                    redact::command_line("git", args)
+                )
+            })?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
             bail!(
-                "Git command failed: git {}\nError: {}",
-                args.join(" "),
-                stderr
+                "Git command failed: {}\nError: {}",
+                redact::command_line("git", args),
+                redact::scrub(&stderr)
             );
         }
 
@@ -638,6 +1173,13 @@ impl WorktreeOperations {
     }
 }
 
+/// `.gitignore` entry for a worktree base directory, e.g. `.worktrees/`
+fn gitignore_pattern(base_dir: &Path) -> String {
+    let pattern = to_forward_slashes(base_dir);
+    let pattern = pattern.trim_start_matches("./").trim_end_matches('/');
+    format!("{pattern}/")
+}
+
 #[derive(Debug)]
 struct CreateResult {
     head: String,
@@ -754,12 +1296,18 @@ mod tests {
         let (_temp_dir, repo_path) = setup_test_repo().await?;
         let config = WorktreeConfig::default();
         let ops = WorktreeOperations::new(repo_path, config);
+        let current_branch = ops
+            .execute_git_command(&["rev-parse", "--abbrev-ref", "HEAD"])
+            .await?;
+        let head = ops.execute_git_command(&["rev-parse", "HEAD"]).await?;
 
         let options = CreateOptions {
             task_id: "test-feature".to_string(),
             base_branch: None,
             force: false,
+            include_protected: false,
             custom_path: None,
+            metadata: Default::default(),
         };
 
         let worktree_info = ops.create_worktree(options).await?;
@@ -768,6 +1316,152 @@ mod tests {
         assert!(worktree_info.branch.starts_with("vibe-ws/"));
         assert!(worktree_info.branch.contains("test-feature"));
 
+        // The branch and commit it was created from are recorded
+        assert_eq!(
+            worktree_info.metadata.base_branch.as_deref(),
+            Some(current_branch.trim())
+        );
+        assert_eq!(
+            worktree_info.metadata.base_commit.as_deref(),
+            Some(head.trim())
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_orphaned_worktrees() -> Result<()> {
+        let (_temp_dir, repo_path) = setup_test_repo().await?;
+        let ops = WorktreeOperations::new(repo_path, WorktreeConfig::default());
+
+        let created = ops
+            .create_worktree(CreateOptions {
+                task_id: "healthy".to_string(),
+                ..Default::default()
+            })
+            .await?;
+        let base_dir = ops.managed_base_dir();
+        let mut staging = created.path.file_name().unwrap().to_os_string();
+        staging.push(orphans::STAGING_SUFFIX);
+        assert!(!base_dir.join(staging).exists());
+        assert!(ops.find_orphaned_worktrees().await?.is_empty());
+
+        // A directory from a creation that crashed before git registered it
+        let crashed = base_dir.join("crashed__1a2b");
+        std::fs::create_dir_all(&crashed)?;
+        std::fs::write(crashed.join("partial.txt"), "data")?;
+
+        let orphans = ops.find_orphaned_worktrees().await?;
+        assert_eq!(orphans.len(), 1);
+        assert!(paths_equal(&orphans[0].path, &crashed));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_protected_worktrees_refuse_removal() -> Result<()> {
+        let (_temp_dir, repo_path) = setup_test_repo().await?;
+        let config = WorktreeConfig {
+            protected_paths: vec![PathBuf::from(".worktrees/keep")],
+            ..Default::default()
+        };
+        let ops = WorktreeOperations::new(repo_path.clone(), config);
+
+        let kept = ops
+            .create_worktree(CreateOptions {
+                task_id: "keep".to_string(),
+                custom_path: Some(repo_path.join(".worktrees/keep")),
+                ..Default::default()
+            })
+            .await?;
+        let task = ops
+            .create_worktree(CreateOptions {
+                task_id: "task".to_string(),
+                ..Default::default()
+            })
+            .await?;
+
+        let listed = ops.list_worktrees().await?;
+        let protected: Vec<_> = listed.iter().filter(|w| w.protected).collect();
+        assert_eq!(protected.len(), 2);
+
+        for target in [repo_path.clone(), kept.path.clone()] {
+            let err = ops
+                .remove_worktree(RemoveOptions {
+                    target: target.to_string_lossy().to_string(),
+                    force: true,
+                    delete_branch: false,
+                    include_protected: false,
+                })
+                .await
+                .unwrap_err();
+            assert!(err.downcast_ref::<ProtectedWorktreeError>().is_some());
+            assert!(target.exists());
+        }
+
+        ops.remove_worktree(RemoveOptions {
+            target: task.path.to_string_lossy().to_string(),
+            force: true,
+            delete_branch: false,
+            include_protected: false,
+        })
+        .await?;
+        assert!(!task.path.exists());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_protected_branches_are_kept_on_removal() -> Result<()> {
+        let (_temp_dir, repo_path) = setup_test_repo().await?;
+        let config = WorktreeConfig {
+            protected_branches: vec!["vibe-ws/keep-*".to_string()],
+            ..Default::default()
+        };
+        let ops = WorktreeOperations::new(repo_path, config);
+
+        let kept = ops
+            .create_worktree(CreateOptions {
+                task_id: "keep-me".to_string(),
+                ..Default::default()
+            })
+            .await?;
+        let outcome = ops
+            .remove_worktree(RemoveOptions {
+                target: kept.branch.clone(),
+                force: true,
+                delete_branch: true,
+                include_protected: false,
+            })
+            .await?;
+        assert!(!kept.path.exists());
+        assert_eq!(
+            outcome.protected_branch,
+            Some(BranchProtectionReason::Pattern(
+                "vibe-ws/keep-*".to_string()
+            ))
+        );
+        assert!(ops.branch_exists(&kept.branch).await?);
+
+        let overridden = ops
+            .create_worktree(CreateOptions {
+                task_id: "keep-me".to_string(),
+                force: true,
+                include_protected: true,
+                ..Default::default()
+            })
+            .await?;
+        let outcome = ops
+            .remove_worktree(RemoveOptions {
+                target: overridden.branch.clone(),
+                force: true,
+                delete_branch: true,
+                include_protected: true,
+            })
+            .await?;
+        assert!(outcome.protected_branch.is_none());
+        assert!(!ops.branch_exists(&overridden.branch).await?);
+
         Ok(())
     }
 
@@ -795,7 +1489,9 @@ mod tests {
             task_id: "test-remove".to_string(),
             base_branch: None,
             force: false,
+            include_protected: false,
             custom_path: None,
+            metadata: Default::default(),
         };
 
         let worktree_info = ops.create_worktree(create_options).await?;
@@ -806,6 +1502,7 @@ mod tests {
             target: worktree_info.branch.clone(),
             force: false,
             delete_branch: true,
+            include_protected: false,
         };
 
         ops.remove_worktree(remove_options).await?;
@@ -824,7 +1521,9 @@ mod tests {
             task_id: "feat/new-ui".to_string(),
             base_branch: None,
             force: false,
+            include_protected: false,
             custom_path: None,
+            metadata: Default::default(),
         };
 
         let worktree_info = ops.create_worktree(options).await?;
@@ -836,6 +1535,16 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_gitignore_pattern_uses_forward_slashes() {
+        assert_eq!(gitignore_pattern(Path::new(".worktrees")), ".worktrees/");
+        assert_eq!(
+            gitignore_pattern(Path::new(".\\build\\worktrees\\")),
+            "build/worktrees/"
+        );
+        assert_eq!(gitignore_pattern(Path::new("./trees/")), "trees/");
+    }
+
     #[test]
     fn test_validate_branch_name() {
         // Valid names
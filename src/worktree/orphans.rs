@@ -0,0 +1,193 @@
+//! Orphaned worktree directories
+//!
+//! A crash part-way through creating a worktree can leave a directory in the
+//! managed base dir that git never registered, or one whose `.git` file
+//! points at admin data that has since been pruned. `git worktree prune`
+//! ignores both, so they are found by scanning the base dir instead.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use crate::utils::fs::{canonicalize, paths_equal};
+
+/// Suffix of the directory a worktree is built in before it is renamed into
+/// place, so an interrupted creation never occupies the final path
+pub const STAGING_SUFFIX: &str = ".creating";
+
+/// Why a directory counts as orphaned
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "kind")]
+pub enum OrphanReason {
+    /// No `.git` file at all; creation stopped before git wrote it
+    MissingGitFile,
+    /// The `.git` file points at admin data that no longer exists
+    DanglingGitdir { gitdir: PathBuf },
+}
+
+impl fmt::Display for OrphanReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OrphanReason::MissingGitFile => write!(f, "no .git file"),
+            OrphanReason::DanglingGitdir { gitdir } => {
+                write!(f, "gitdir {} is missing", gitdir.display())
+            }
+        }
+    }
+}
+
+/// A directory in the managed base dir that git does not track
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrphanedWorktree {
+    pub path: PathBuf,
+    pub reason: OrphanReason,
+    pub size_bytes: u64,
+}
+
+/// Scan `base_dir` for orphaned worktree directories. `registered` holds the
+/// paths git lists as worktrees; those and anything inside them are skipped.
+pub fn find_orphans(base_dir: &Path, registered: &[PathBuf]) -> Vec<OrphanedWorktree> {
+    let mut orphans = Vec::new();
+    scan(base_dir, registered, &mut orphans);
+    orphans.sort_by(|a, b| a.path.cmp(&b.path));
+    orphans
+}
+
+fn scan(dir: &Path, registered: &[PathBuf], orphans: &mut Vec<OrphanedWorktree>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !entry.file_type().is_ok_and(|t| t.is_dir()) {
+            continue;
+        }
+        let resolved = canonicalize(&path).unwrap_or_else(|_| path.clone());
+        if registered
+            .iter()
+            .any(|r| paths_equal(r, &path) || paths_equal(r, &resolved))
+        {
+            continue;
+        }
+
+        let git = path.join(".git");
+        let reason = if git.is_dir() {
+            // A standalone clone, not ours to judge
+            continue;
+        } else if git.is_file() {
+            match dangling_gitdir(&path, &git) {
+                Some(gitdir) => OrphanReason::DanglingGitdir { gitdir },
+                None => continue,
+            }
+        } else if is_worktree_dir_name(&entry.file_name().to_string_lossy()) {
+            OrphanReason::MissingGitFile
+        } else {
+            // Intermediate directory from a task id with slashes
+            scan(&path, registered, orphans);
+            continue;
+        };
+
+        orphans.push(OrphanedWorktree {
+            size_bytes: crate::workspace::disk_usage::dir_size(&path),
+            path,
+            reason,
+        });
+    }
+}
+
+/// The gitdir named by a worktree's `.git` file, if it does not exist.
+/// Unreadable files count as dangling since git cannot use them either.
+fn dangling_gitdir(worktree: &Path, git_file: &Path) -> Option<PathBuf> {
+    let contents = std::fs::read_to_string(git_file).unwrap_or_default();
+    let Some(target) = contents
+        .lines()
+        .find_map(|line| line.strip_prefix("gitdir:"))
+        .map(|target| PathBuf::from(target.trim()))
+    else {
+        return Some(git_file.to_path_buf());
+    };
+
+    let target = if target.is_relative() {
+        worktree.join(target)
+    } else {
+        target
+    };
+    (!target.is_dir()).then_some(target)
+}
+
+/// Whether `name` follows the `<task>__<hex timestamp>` layout worktrees are
+/// created with, or is a staging directory left by an interrupted creation
+pub fn is_worktree_dir_name(name: &str) -> bool {
+    if name.ends_with(STAGING_SUFFIX) {
+        return true;
+    }
+    name.rsplit_once("__").is_some_and(|(task, stamp)| {
+        !task.is_empty() && !stamp.is_empty() && stamp.chars().all(|c| c.is_ascii_hexdigit())
+    })
+}
+
+/// Delete an orphaned directory
+pub fn remove_orphan(orphan: &OrphanedWorktree) -> Result<()> {
+    std::fs::remove_dir_all(&orphan.path)
+        .with_context(|| format!("Failed to delete {}", orphan.path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_is_worktree_dir_name() {
+        assert!(is_worktree_dir_name("fix-login__18f3a2b4c"));
+        assert!(is_worktree_dir_name("fix-login__18f3a2b4c.creating"));
+        assert!(!is_worktree_dir_name("feat"));
+        assert!(!is_worktree_dir_name("my__project"));
+        assert!(!is_worktree_dir_name("__abc"));
+    }
+
+    #[test]
+    fn test_find_orphans() {
+        let dir = TempDir::new().unwrap();
+        let base = dir.path();
+
+        // Registered worktree
+        let live = base.join("live__1a");
+        std::fs::create_dir_all(&live).unwrap();
+        std::fs::write(live.join(".git"), "gitdir: /nowhere").unwrap();
+
+        // Never got its .git file, nested under a slashed task id
+        let half = base.join("feat/ui__2b");
+        std::fs::create_dir_all(&half).unwrap();
+        std::fs::write(half.join("file.txt"), "12345").unwrap();
+
+        // Admin data pruned
+        let dangling = base.join("old__3c");
+        std::fs::create_dir_all(&dangling).unwrap();
+        std::fs::write(dangling.join(".git"), "gitdir: ../missing\n").unwrap();
+
+        // Healthy but unregistered here, e.g. another repository's worktree
+        let admin = base.join("admin");
+        std::fs::create_dir_all(&admin).unwrap();
+        let other = base.join("other__4d");
+        std::fs::create_dir_all(&other).unwrap();
+        std::fs::write(other.join(".git"), format!("gitdir: {}", admin.display())).unwrap();
+
+        let orphans = find_orphans(base, &[live]);
+        let found: Vec<_> = orphans
+            .iter()
+            .map(|o| (o.path.strip_prefix(base).unwrap().to_path_buf(), &o.reason))
+            .collect();
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].0, PathBuf::from("feat/ui__2b"));
+        assert_eq!(found[0].1, &OrphanReason::MissingGitFile);
+        assert_eq!(orphans[0].size_bytes, 5);
+        assert_eq!(found[1].0, PathBuf::from("old__3c"));
+        assert!(matches!(found[1].1, OrphanReason::DanglingGitdir { .. }));
+
+        remove_orphan(&orphans[0]).unwrap();
+        assert!(!half.exists());
+    }
+}
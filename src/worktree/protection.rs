@@ -0,0 +1,104 @@
+//! Worktrees that must never be removed
+//!
+//! The main working tree and anything listed in `worktree.protected_paths`
+//! are refused by removal and cleanup even with `--force`.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use crate::utils::fs::{canonicalize, expand_tilde, paths_equal};
+
+/// Why a worktree is protected
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProtectionReason {
+    /// The repository's primary checkout
+    MainCheckout,
+    /// Listed in `worktree.protected_paths`
+    Configured,
+}
+
+impl fmt::Display for ProtectionReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProtectionReason::MainCheckout => write!(f, "it is the main working tree"),
+            ProtectionReason::Configured => write!(f, "it is listed in worktree.protected_paths"),
+        }
+    }
+}
+
+/// Returned when removal targets a protected worktree
+#[derive(Debug, thiserror::Error)]
+#[error("Refusing to remove {}: {reason} (--force does not override this)", path.display())]
+pub struct ProtectedWorktreeError {
+    pub path: PathBuf,
+    pub reason: ProtectionReason,
+}
+
+/// Why `path` is protected, if it is. Relative entries in `protected_paths`
+/// are resolved against `repo_root`.
+pub fn protection_reason(
+    path: &Path,
+    repo_root: &Path,
+    protected_paths: &[PathBuf],
+) -> Option<ProtectionReason> {
+    let resolved = resolve(path);
+    if paths_equal(&resolved, resolve(repo_root)) || path.join(".git").is_dir() {
+        return Some(ProtectionReason::MainCheckout);
+    }
+
+    protected_paths
+        .iter()
+        .map(|protected| {
+            let protected = expand_tilde(protected);
+            if protected.is_relative() {
+                repo_root.join(protected)
+            } else {
+                protected
+            }
+        })
+        .any(|protected| paths_equal(&resolved, resolve(&protected)))
+        .then_some(ProtectionReason::Configured)
+}
+
+fn resolve(path: &Path) -> PathBuf {
+    canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_protection_reason() {
+        let dir = TempDir::new().unwrap();
+        let repo = dir.path().join("repo");
+        let task = dir.path().join("repo-task");
+        let keep = dir.path().join("keep");
+        for path in [&repo, &task, &keep] {
+            std::fs::create_dir_all(path).unwrap();
+            std::fs::write(path.join(".git"), "gitdir: elsewhere").unwrap();
+        }
+
+        let protected = vec![PathBuf::from("../keep")];
+        assert_eq!(
+            protection_reason(&repo, &repo, &protected),
+            Some(ProtectionReason::MainCheckout)
+        );
+        assert_eq!(
+            protection_reason(&keep, &repo, &protected),
+            Some(ProtectionReason::Configured)
+        );
+        assert_eq!(protection_reason(&task, &repo, &protected), None);
+
+        // A primary checkout elsewhere still has a .git directory
+        let other = dir.path().join("other");
+        std::fs::create_dir_all(other.join(".git")).unwrap();
+        assert_eq!(
+            protection_reason(&other, &repo, &[]),
+            Some(ProtectionReason::MainCheckout)
+        );
+    }
+}
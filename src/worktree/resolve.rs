@@ -0,0 +1,300 @@
+//! Resolving merge conflicts left behind by `vibe git worktree merge`
+//!
+//! Everything here reads its state from git rather than keeping any of its
+//! own: the files still to resolve are the unmerged entries in the index, so
+//! an interrupted resolution picks up where it stopped, and aborting is
+//! `git merge --abort`. Only merges are handled; during a rebase "ours" and
+//! "theirs" swap meaning, which would make the choices misleading.
+
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use tokio::process::Command;
+
+use crate::utils::platform::resolve_command;
+use crate::workspace::operations::execute_git_command;
+
+/// A file with unmerged entries in the index
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ConflictedFile {
+    pub path: String,
+    /// Whether the branch being merged into still has the file
+    pub ours: bool,
+    /// Whether the branch being merged still has the file
+    pub theirs: bool,
+}
+
+impl ConflictedFile {
+    /// How the two sides disagree, in `git status` terms
+    pub fn describe(&self) -> &'static str {
+        match (self.ours, self.theirs) {
+            (true, true) => "both modified",
+            (false, true) => "deleted by us",
+            (true, false) => "deleted by them",
+            (false, false) => "deleted by both",
+        }
+    }
+}
+
+/// What to do with one conflicted file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resolution {
+    /// Run `git mergetool` on the file with the repository's configuration
+    MergeTool,
+    /// Keep the version of the branch being merged into
+    Ours,
+    /// Keep the version of the branch being merged
+    Theirs,
+}
+
+/// Parse `git ls-files -u -z` output into one entry per file
+pub fn parse_unmerged(output: &str) -> Vec<ConflictedFile> {
+    let mut files: Vec<ConflictedFile> = Vec::new();
+    for entry in output.split('\0').filter(|entry| !entry.is_empty()) {
+        // "<mode> <object> <stage>\t<path>"
+        let Some((meta, path)) = entry.split_once('\t') else {
+            continue;
+        };
+        let stage = meta.split(' ').nth(2).unwrap_or_default();
+        let file = match files.iter_mut().find(|file| file.path == path) {
+            Some(file) => file,
+            None => {
+                files.push(ConflictedFile {
+                    path: path.to_string(),
+                    ours: false,
+                    theirs: false,
+                });
+                files.last_mut().unwrap()
+            }
+        };
+        match stage {
+            "2" => file.ours = true,
+            "3" => file.theirs = true,
+            _ => {}
+        }
+    }
+    files
+}
+
+/// Files in `repo` that still have conflicts
+pub async fn conflicted_files(repo: &Path) -> Result<Vec<ConflictedFile>> {
+    let output = execute_git_command(repo, &["ls-files", "-u", "-z"]).await?;
+    Ok(parse_unmerged(&output))
+}
+
+/// Whether a merge is in progress in `repo`
+pub async fn merge_in_progress(repo: &Path) -> bool {
+    execute_git_command(repo, &["rev-parse", "-q", "--verify", "MERGE_HEAD"])
+        .await
+        .is_ok()
+}
+
+/// The checkout among `candidates` with a merge in progress. `worktree merge`
+/// runs the merge in the checkout of the target branch, which is not always
+/// the worktree itself, so callers pass every place it may have happened.
+pub async fn find_merge(candidates: &[PathBuf]) -> Option<PathBuf> {
+    for candidate in candidates {
+        let Ok(top) = execute_git_command(candidate, &["rev-parse", "--show-toplevel"]).await
+        else {
+            continue;
+        };
+        let top = PathBuf::from(top);
+        if merge_in_progress(&top).await {
+            return Some(top);
+        }
+    }
+    None
+}
+
+/// Names of the branch being merged into and of the one being merged
+pub async fn merge_sides(repo: &Path) -> (String, String) {
+    let ours = execute_git_command(repo, &["rev-parse", "--abbrev-ref", "HEAD"])
+        .await
+        .unwrap_or_else(|_| "HEAD".to_string());
+    let theirs = execute_git_command(repo, &["name-rev", "--name-only", "--always", "MERGE_HEAD"])
+        .await
+        .unwrap_or_else(|_| "MERGE_HEAD".to_string());
+    (ours, theirs)
+}
+
+/// Resolve one file. Returns whether the file is resolved afterwards, which
+/// for the merge tool depends on what happened in it.
+pub async fn apply(repo: &Path, file: &ConflictedFile, resolution: Resolution) -> Result<bool> {
+    let path = file.path.as_str();
+    match resolution {
+        Resolution::MergeTool => {
+            // Interactive: the tool needs the terminal
+            let status = Command::new(resolve_command("git"))
+                .args(["mergetool", "--no-prompt", "--", path])
+                .current_dir(repo)
+                .status()
+                .await
+                .context("Failed to run git mergetool")?;
+            if !status.success() {
+                return Ok(false);
+            }
+        }
+        Resolution::Ours | Resolution::Theirs => {
+            let (side, present) = match resolution {
+                Resolution::Ours => ("--ours", file.ours),
+                _ => ("--theirs", file.theirs),
+            };
+            if present {
+                execute_git_command(repo, &["checkout", side, "--", path]).await?;
+                execute_git_command(repo, &["add", "--", path]).await?;
+            } else {
+                // That side deleted the file
+                execute_git_command(repo, &["rm", "-q", "--", path]).await?;
+            }
+        }
+    }
+    let remaining = conflicted_files(repo).await?;
+    Ok(!remaining
+        .iter()
+        .any(|remaining| remaining.path == file.path))
+}
+
+/// Abort the merge, restoring the branch and working tree from before it
+pub async fn abort_merge(repo: &Path) -> Result<()> {
+    if !merge_in_progress(repo).await {
+        bail!("No merge in progress in {}", repo.display());
+    }
+    execute_git_command(repo, &["merge", "--abort"]).await?;
+    Ok(())
+}
+
+/// Commit the merge once every conflict is resolved
+pub async fn conclude_merge(repo: &Path) -> Result<()> {
+    let remaining = conflicted_files(repo).await?;
+    if !remaining.is_empty() {
+        bail!("{} files still have conflicts", remaining.len());
+    }
+    execute_git_command(repo, &["commit", "--no-edit"]).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command as StdCommand;
+    use tempfile::TempDir;
+
+    fn git(dir: &Path, args: &[&str]) -> bool {
+        StdCommand::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap()
+            .status
+            .success()
+    }
+
+    fn commit(dir: &Path, files: &[(&str, Option<&str>)], message: &str) {
+        for (file, contents) in files {
+            match contents {
+                Some(contents) => std::fs::write(dir.join(file), contents).unwrap(),
+                None => std::fs::remove_file(dir.join(file)).unwrap(),
+            }
+        }
+        assert!(git(dir, &["add", "-A"]));
+        assert!(git(dir, &["commit", "-qm", message]));
+    }
+
+    /// A repository on `main` in the middle of merging `feature`, with
+    /// `a.txt` changed on both sides and `b.txt` deleted on `feature`
+    fn conflicted_repo() -> TempDir {
+        let temp = TempDir::new().unwrap();
+        let repo = temp.path();
+        assert!(git(repo, &["init", "-q", "-b", "main"]));
+        assert!(git(repo, &["config", "user.email", "test@example.com"]));
+        assert!(git(repo, &["config", "user.name", "Test"]));
+        commit(
+            repo,
+            &[("a.txt", Some("base\n")), ("b.txt", Some("base\n"))],
+            "base",
+        );
+        assert!(git(repo, &["checkout", "-qb", "feature"]));
+        commit(
+            repo,
+            &[("a.txt", Some("feature\n")), ("b.txt", None)],
+            "feature",
+        );
+        assert!(git(repo, &["checkout", "-q", "main"]));
+        commit(
+            repo,
+            &[("a.txt", Some("main\n")), ("b.txt", Some("main\n"))],
+            "main",
+        );
+        assert!(!git(repo, &["merge", "-q", "feature"]));
+        temp
+    }
+
+    #[test]
+    fn test_parse_unmerged() {
+        let output = "100644 aaa 1\ta.txt\x00100644 bbb 2\ta.txt\x00100644 ccc 3\ta.txt\x00100644 ddd 1\tb c.txt\x00100644 eee 2\tb c.txt\0";
+        assert_eq!(
+            parse_unmerged(output),
+            vec![
+                ConflictedFile {
+                    path: "a.txt".to_string(),
+                    ours: true,
+                    theirs: true,
+                },
+                ConflictedFile {
+                    path: "b c.txt".to_string(),
+                    ours: true,
+                    theirs: false,
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resolve_and_conclude_merge() {
+        let temp = conflicted_repo();
+        let repo = temp.path();
+        assert_eq!(
+            find_merge(&[repo.to_path_buf()]).await.as_deref(),
+            Some(repo)
+        );
+
+        let files = conflicted_files(repo).await.unwrap();
+        let names: Vec<_> = files
+            .iter()
+            .map(|f| (f.path.as_str(), f.describe()))
+            .collect();
+        assert_eq!(
+            names,
+            vec![("a.txt", "both modified"), ("b.txt", "deleted by them")]
+        );
+        assert!(conclude_merge(repo).await.is_err());
+
+        assert!(apply(repo, &files[0], Resolution::Theirs).await.unwrap());
+        assert!(apply(repo, &files[1], Resolution::Theirs).await.unwrap());
+        assert_eq!(
+            std::fs::read_to_string(repo.join("a.txt")).unwrap(),
+            "feature\n"
+        );
+        assert!(!repo.join("b.txt").exists());
+
+        conclude_merge(repo).await.unwrap();
+        assert!(!merge_in_progress(repo).await);
+    }
+
+    #[tokio::test]
+    async fn test_abort_restores_branch() {
+        let temp = conflicted_repo();
+        let repo = temp.path();
+        let files = conflicted_files(repo).await.unwrap();
+        assert!(apply(repo, &files[0], Resolution::Ours).await.unwrap());
+
+        abort_merge(repo).await.unwrap();
+        assert!(!merge_in_progress(repo).await);
+        assert!(conflicted_files(repo).await.unwrap().is_empty());
+        assert_eq!(
+            std::fs::read_to_string(repo.join("b.txt")).unwrap(),
+            "main\n"
+        );
+        assert!(abort_merge(repo).await.is_err());
+    }
+}
@@ -7,8 +7,13 @@ use std::time::{Duration, SystemTime};
 use tokio::process::Command;
 use tracing::debug;
 
+use crate::git::backend::{self, GitBackend, StatusEntry};
+use crate::output::delimited::Tabular;
+use crate::output::theme::{self, Icon};
+use crate::worktree::base_refresh::BaseRefresh;
 use crate::worktree::config::WorktreeMergeDetectionConfig;
 use crate::worktree::merge_detection::detect_worktree_merge_status;
+use crate::worktree::metadata::WorktreeMetadata;
 
 /// Comprehensive information about a Git worktree
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -33,6 +38,39 @@ pub struct WorktreeInfo {
 
     /// Whether this worktree is detached HEAD
     pub is_detached: bool,
+
+    /// Whether removal and cleanup refuse this worktree
+    #[serde(default)]
+    pub protected: bool,
+
+    /// Task description and link
+    #[serde(flatten)]
+    pub metadata: WorktreeMetadata,
+
+    /// How the base branch was refreshed, for a worktree just created
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_refresh: Option<BaseRefresh>,
+}
+
+impl Tabular for WorktreeInfo {
+    const FIELDS: &'static [&'static str] = &[
+        "task_id",
+        "branch",
+        "path",
+        "head",
+        "is_detached",
+        "protected",
+        "status.is_clean",
+        "status.severity",
+        "status.ahead_count",
+        "status.behind_count",
+        "description",
+        "link",
+        "base_branch",
+        "base_commit",
+        "expires_at",
+        "created_at",
+    ];
 }
 
 /// Detailed status information for a worktree
@@ -203,11 +241,11 @@ impl WorktreeStatus {
 
     /// Get the appropriate status icon
     pub fn status_icon(&self) -> &'static str {
-        match self.severity {
-            StatusSeverity::Clean => "✅",
-            StatusSeverity::LightWarning => "⚠️",
-            StatusSeverity::Warning => "⚡",
-        }
+        theme::icon(match self.severity {
+            StatusSeverity::Clean => Icon::Success,
+            StatusSeverity::LightWarning => Icon::Warning,
+            StatusSeverity::Warning => Icon::Attention,
+        })
     }
 }
 
@@ -232,6 +270,10 @@ impl WorktreeInfo {
     /// Update status information for this worktree
     pub async fn update_status(&mut self) -> Result<()> {
         self.status = check_worktree_status(&self.path).await?;
+        // A passed deadline needs attention however tidy the worktree is
+        if self.metadata.is_expired(chrono::Utc::now()) {
+            self.status.severity = StatusSeverity::Warning;
+        }
         self.update_age()?;
         Ok(())
     }
@@ -268,6 +310,9 @@ pub struct RepositoryWorktreeSummary {
     /// Number of worktrees with unpushed commits
     pub worktrees_with_unpushed: usize,
 
+    /// Number of worktrees past their `--expires` deadline
+    pub expired_worktrees: usize,
+
     /// Overall health score (0.0 to 1.0)
     pub health_score: f32,
 }
@@ -295,6 +340,11 @@ impl RepositoryWorktreeSummary {
             .iter()
             .filter(|w| !w.status.unpushed_commits.is_empty())
             .count();
+        let now = chrono::Utc::now();
+        let expired = worktrees
+            .iter()
+            .filter(|w| w.metadata.is_expired(now))
+            .count();
 
         // Calculate health score based on cleanliness and remote tracking
         let health_score = if total == 0 {
@@ -313,6 +363,7 @@ impl RepositoryWorktreeSummary {
             worktrees_with_remote: with_remote,
             merged_worktrees: merged,
             worktrees_with_unpushed: with_unpushed,
+            expired_worktrees: expired,
             health_score,
         }
     }
@@ -335,6 +386,10 @@ impl RepositoryWorktreeSummary {
             parts.push(format!("{} merged", self.merged_worktrees));
         }
 
+        if self.expired_worktrees > 0 {
+            parts.push(format!("{} expired", self.expired_worktrees));
+        }
+
         let remote_missing = self.total_worktrees - self.worktrees_with_remote;
         if remote_missing > 0 {
             parts.push(format!("{} no remote", remote_missing));
@@ -375,16 +430,29 @@ pub async fn check_worktree_status(worktree_path: &Path) -> Result<WorktreeStatu
 pub async fn check_worktree_status_with_config(
     worktree_path: &Path,
     merge_config: Option<&WorktreeMergeDetectionConfig>,
+) -> Result<WorktreeStatus> {
+    check_worktree_status_with_backend(&*backend::current(), worktree_path, merge_config).await
+}
+
+/// Check comprehensive status for a worktree, querying git through `backend`
+pub async fn check_worktree_status_with_backend(
+    backend: &dyn GitBackend,
+    worktree_path: &Path,
+    merge_config: Option<&WorktreeMergeDetectionConfig>,
 ) -> Result<WorktreeStatus> {
     let mut status = WorktreeStatus::new();
 
     // Get basic git status (staged, unstaged, untracked files)
-    let git_status = get_git_porcelain_status(worktree_path).await?;
+    let entries = backend
+        .status(worktree_path)
+        .await
+        .with_context(|| format!("Failed to get git status for: {}", worktree_path.display()))?;
+    let git_status = summarize_status_entries(&entries);
     status.uncommitted_changes = git_status.changed_files;
     status.untracked_files = git_status.untracked_files;
 
     // Get remote status and commit information
-    let remote_info = get_remote_status(worktree_path).await?;
+    let remote_info = get_remote_status(backend, worktree_path).await?;
     status.remote_status = remote_info.status;
     status.ahead_count = remote_info.ahead;
     status.behind_count = remote_info.behind;
@@ -396,7 +464,7 @@ pub async fn check_worktree_status_with_config(
 
     // Add merge detection if config is provided
     if let Some(config) = merge_config {
-        if let Ok(current_branch) = get_current_branch(worktree_path).await {
+        if let Ok(Some(current_branch)) = backend.head_branch(worktree_path).await {
             match detect_worktree_merge_status(worktree_path, &current_branch, config).await {
                 Ok(merge_info) => {
                     status.merge_info = Some(merge_info);
@@ -423,122 +491,64 @@ pub async fn check_worktree_status_with_config(
     Ok(status)
 }
 
-/// Get git status in porcelain format for parsing
-async fn get_git_porcelain_status(worktree_path: &Path) -> Result<GitStatusInfo> {
-    let output = Command::new("git")
-        .args(&["status", "--porcelain=v1", "-z"])
-        .current_dir(worktree_path)
-        .output()
-        .await
-        .with_context(|| format!("Failed to get git status for: {}", worktree_path.display()))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(anyhow::anyhow!("Git status failed: {}", stderr));
-    }
-
-    parse_porcelain_status(&output.stdout)
-}
-
-/// Parse git status porcelain output
-fn parse_porcelain_status(output: &[u8]) -> Result<GitStatusInfo> {
-    let output_str = String::from_utf8_lossy(output);
+/// Split porcelain status entries into described changes and untracked files
+fn summarize_status_entries(entries: &[StatusEntry]) -> GitStatusInfo {
     let mut changed_files = Vec::new();
     let mut untracked_files = Vec::new();
 
-    for line in output_str.split('\0') {
-        if line.is_empty() {
-            continue;
-        }
-
-        if line.len() < 3 {
+    for entry in entries {
+        if entry.is_untracked() {
+            untracked_files.push(entry.path.clone());
             continue;
         }
 
-        let status_code = &line[0..2];
-        let file_path = &line[3..];
+        let status_desc = match entry.code.as_str() {
+            "M " => "modified (unstaged)",
+            " M" => "modified (staged)",
+            "MM" => "modified (both staged and unstaged)",
+            "A " => "added (staged)",
+            " A" => "added (unstaged)",
+            "D " => "deleted (staged)",
+            " D" => "deleted (unstaged)",
+            "R " => "renamed (staged)",
+            " R" => "renamed (unstaged)",
+            "C " => "copied (staged)",
+            " C" => "copied (unstaged)",
+            "U " | " U" | "UU" => "unmerged",
+            _ => "unknown",
+        };
 
-        match status_code {
-            "??" => {
-                untracked_files.push(file_path.to_string());
-            }
-            _ => {
-                let status_desc = match status_code {
-                    "M " => "modified (unstaged)",
-                    " M" => "modified (staged)",
-                    "MM" => "modified (both staged and unstaged)",
-                    "A " => "added (staged)",
-                    " A" => "added (unstaged)",
-                    "D " => "deleted (staged)",
-                    " D" => "deleted (unstaged)",
-                    "R " => "renamed (staged)",
-                    " R" => "renamed (unstaged)",
-                    "C " => "copied (staged)",
-                    " C" => "copied (unstaged)",
-                    "U " | " U" | "UU" => "unmerged",
-                    _ => "unknown",
-                };
-
-                changed_files.push(format!("{}: {}", status_desc, file_path));
-            }
-        }
+        changed_files.push(format!("{}: {}", status_desc, entry.path));
     }
 
-    Ok(GitStatusInfo {
+    GitStatusInfo {
         changed_files,
         untracked_files,
-    })
+    }
 }
 
 /// Get remote branch status and ahead/behind counts
-async fn get_remote_status(worktree_path: &Path) -> Result<RemoteInfo> {
+async fn get_remote_status(backend: &dyn GitBackend, worktree_path: &Path) -> Result<RemoteInfo> {
     // First, check if there's a remote tracking branch
-    let upstream_result = Command::new("git")
-        .args(&["rev-parse", "--abbrev-ref", "@{u}"])
-        .current_dir(worktree_path)
-        .output()
-        .await;
-
-    let upstream_branch = match upstream_result {
-        Ok(output) if output.status.success() => {
-            Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
-        }
-        _ => None,
-    };
-
-    if upstream_branch.is_none() {
+    let Some(upstream_branch) = backend.upstream(worktree_path).await? else {
         return Ok(RemoteInfo {
             status: RemoteStatus::NoRemote,
             ahead: 0,
             behind: 0,
         });
-    }
+    };
 
     // Get ahead/behind counts
-    let count_output = Command::new("git")
-        .args(&["rev-list", "--count", "--left-right", "@{u}...HEAD"])
-        .current_dir(worktree_path)
-        .output()
-        .await?;
-
-    if !count_output.status.success() {
+    let Some((ahead, behind)) = backend
+        .ahead_behind(worktree_path, &upstream_branch, "HEAD")
+        .await?
+    else {
         // Remote branch might be deleted
         return Ok(RemoteInfo {
             status: RemoteStatus::RemoteDeleted,
             ahead: 0,
             behind: 0,
         });
-    }
-
-    let count_str = String::from_utf8_lossy(&count_output.stdout);
-    let counts: Vec<&str> = count_str.trim().split_whitespace().collect();
-
-    let (behind, ahead) = if counts.len() >= 2 {
-        let behind = counts[0].parse::<usize>().unwrap_or(0);
-        let ahead = counts[1].parse::<usize>().unwrap_or(0);
-        (behind, ahead)
-    } else {
-        (0, 0)
     };
 
     let status = match (ahead, behind) {
@@ -603,21 +613,6 @@ async fn get_unpushed_commits(worktree_path: &Path) -> Result<Vec<CommitInfo>> {
     Ok(commits)
 }
 
-/// Get the current branch name
-async fn get_current_branch(worktree_path: &Path) -> Result<String> {
-    let output = Command::new("git")
-        .args(&["rev-parse", "--abbrev-ref", "HEAD"])
-        .current_dir(worktree_path)
-        .output()
-        .await?;
-
-    if output.status.success() {
-        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
-    } else {
-        Err(anyhow::anyhow!("Failed to get current branch"))
-    }
-}
-
 /// Classify the overall severity of a worktree's status
 fn classify_status_severity(status: &WorktreeStatus) -> StatusSeverity {
     // If branch is merged with high confidence, it's safer to clean
@@ -876,6 +871,7 @@ pub struct BranchInfo {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::git::backend::subprocess::parse_porcelain_z;
     use tempfile::TempDir;
     use tokio;
 
@@ -1025,7 +1021,7 @@ mod tests {
     #[test]
     fn test_porcelain_status_parsing() {
         let sample_output = b"M  modified.txt\0?? untracked.txt\0A  added.txt\0D  deleted.txt\0";
-        let status = parse_porcelain_status(sample_output).unwrap();
+        let status = summarize_status_entries(&parse_porcelain_z(sample_output));
 
         assert_eq!(status.changed_files.len(), 3); // M, A, D
         assert_eq!(status.untracked_files.len(), 1); // ??
@@ -1051,7 +1047,7 @@ mod tests {
     #[test]
     fn test_empty_porcelain_status() {
         let empty_output = b"";
-        let status = parse_porcelain_status(empty_output).unwrap();
+        let status = summarize_status_entries(&parse_porcelain_z(empty_output));
 
         assert!(status.changed_files.is_empty());
         assert!(status.untracked_files.is_empty());
@@ -1075,6 +1071,9 @@ mod tests {
             status: WorktreeStatus::new(),
             age: Duration::from_secs(0),
             is_detached: false,
+            protected: false,
+            metadata: Default::default(),
+            base_refresh: None,
         };
 
         // Update status should work without errors
@@ -1101,6 +1100,9 @@ mod tests {
                 status: WorktreeStatus::new(),
                 age: Duration::from_secs(0),
                 is_detached: false,
+                protected: false,
+                metadata: Default::default(),
+                base_refresh: None,
             },
             WorktreeInfo {
                 path: path2,
@@ -1110,6 +1112,9 @@ mod tests {
                 status: WorktreeStatus::new(),
                 age: Duration::from_secs(0),
                 is_detached: false,
+                protected: false,
+                metadata: Default::default(),
+                base_refresh: None,
             },
         ];
 
@@ -0,0 +1,622 @@
+//! `vibe task`: the start, finish, and done phases of a task
+//!
+//! `start` creates the task's worktree in a repository and opens it in the
+//! worktree editor, `finish` pushes the branch, opens a pull request with
+//! `gh`, and records its URL on the worktree, and `done` checks that the
+//! branch merged and removes the worktree and its branch. Each phase only
+//! strings together worktree operations, `gh`, and merge detection, and
+//! first looks at what an earlier run already did, so a phase that failed
+//! halfway can be run again.
+
+use anyhow::{Context, Result};
+use console::style;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tokio::process::Command;
+
+use super::display::open_worktree_in_editor;
+use super::manager::WorktreeManager;
+use super::merge_detection::detect_worktree_merge_status;
+use super::metadata::{self, WorktreeMetadata};
+use super::operations::{sanitize_branch_name, CreateOptions, RemoveOptions};
+use super::status::{MergeInfo, WorktreeInfo};
+use crate::display_println;
+use crate::git::pull_requests::is_gh_available;
+use crate::git::ref_backup::{self, RefBackup};
+use crate::output::exit::CommandError;
+use crate::output::theme::{self, Icon, Role};
+use crate::repository::template::substitute;
+use crate::utils::direnv;
+use crate::utils::platform::resolve_command;
+use crate::workspace::operations::execute_git_command;
+use crate::workspace::{repo_abs_path, Repository, WorkspaceManager};
+
+/// Remote `finish` pushes task branches to
+const REMOTE: &str = "origin";
+
+#[derive(Debug, Clone, Default)]
+pub struct StartOptions {
+    pub repo: String,
+    pub task_id: String,
+    pub base_branch: Option<String>,
+    pub description: Option<String>,
+    pub link: Option<String>,
+    /// Open the worktree once it exists
+    pub open: bool,
+    /// Editor to open it in instead of the configured `default_editor`
+    pub editor: Option<String>,
+}
+
+/// What `vibe task start` did
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskStarted {
+    pub repo: String,
+    pub task_id: String,
+    pub branch: String,
+    pub path: PathBuf,
+    /// False when the worktree already existed
+    pub created: bool,
+    /// Editor the worktree was opened in
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub opened_in: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FinishOptions {
+    pub task_id: String,
+    /// Repository of the task, needed when several have one by that id
+    pub repo: Option<String>,
+    /// Pull request title instead of the configured template
+    pub title: Option<String>,
+    /// Pull request body instead of the configured template
+    pub body: Option<String>,
+    pub draft: bool,
+}
+
+/// What `vibe task finish` did
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskFinished {
+    pub repo: String,
+    pub task_id: String,
+    pub branch: String,
+    pub pull_request: String,
+    /// False when the branch already had an open or merged pull request
+    pub created: bool,
+    /// Uncommitted changes in the worktree, which were not pushed
+    pub uncommitted_changes: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DoneOptions {
+    pub task_id: String,
+    /// Repository of the task, needed when several have one by that id
+    pub repo: Option<String>,
+    /// Clean up even if the branch isn't merged, discarding uncommitted
+    /// changes (they are kept for `vibe undo`)
+    pub force: bool,
+}
+
+/// What `vibe task done` did
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskDone {
+    pub task_id: String,
+    /// Repository the task was found in; none when nothing was left
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repo: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub merge: Option<MergeInfo>,
+    pub worktree_removed: bool,
+    pub branch_deleted: bool,
+    /// Why the branch was kept
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub branch_kept: Option<String>,
+    /// Trash item that restores the removed worktree
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trash_id: Option<String>,
+    /// Where the deleted branch was backed up
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backup: Option<RefBackup>,
+}
+
+/// A task's worktree and the repository it belongs to
+struct TaskWorktree {
+    repo: String,
+    manager: WorktreeManager,
+    worktree: WorktreeInfo,
+}
+
+/// Create the task's worktree unless it exists, then open it
+pub async fn start(workspace: &WorkspaceManager, options: StartOptions) -> Result<TaskStarted> {
+    let repo = workspace
+        .get_repository_flexible(&options.repo)
+        .ok_or_else(|| {
+            CommandError::not_found_with_hint(
+                "Repository",
+                &options.repo,
+                "Run 'vibe config repo list' to see configured repositories",
+            )
+        })?
+        .clone();
+    let manager = worktree_manager(workspace, &repo).await?;
+
+    let existing = manager
+        .get_operations()
+        .find_worktree_by_task_id(&sanitize_branch_name(&options.task_id)?)
+        .await?;
+    let (worktree, created) = match existing {
+        Some(worktree) => (worktree, false),
+        None => {
+            let options = CreateOptions {
+                task_id: options.task_id.clone(),
+                base_branch: options.base_branch.clone(),
+                metadata: WorktreeMetadata::new(options.description.clone(), options.link.clone())?,
+                ..Default::default()
+            };
+            let worktree = manager.create_worktree_with_options(options).await?;
+            if let Err(e) =
+                direnv::allow_new_worktree(&worktree.path, workspace.is_direnv_auto_allow_enabled())
+                    .await
+            {
+                tracing::warn!("Failed to allow .envrc in new worktree: {}", e);
+            }
+            (worktree, true)
+        }
+    };
+
+    let opened_in = if options.open {
+        let editor = options
+            .editor
+            .unwrap_or_else(|| manager.get_config().default_editor.clone());
+        open_worktree_in_editor(&worktree.path, &editor, workspace.is_direnv_enabled()).await?;
+        Some(editor)
+    } else {
+        None
+    };
+
+    Ok(TaskStarted {
+        repo: repo.name,
+        task_id: options.task_id,
+        branch: worktree.branch,
+        path: worktree.path,
+        created,
+        opened_in,
+    })
+}
+
+/// Push the task's branch, open a pull request for it unless one exists,
+/// and record the pull request on the worktree
+pub async fn finish(workspace: &WorkspaceManager, options: FinishOptions) -> Result<TaskFinished> {
+    let task = require_task(workspace, &options.task_id, options.repo.as_deref()).await?;
+    if !is_gh_available().await {
+        return Err(CommandError::ToolMissing {
+            tool: "gh".to_string(),
+        }
+        .into());
+    }
+
+    let path = &task.worktree.path;
+    let branch = &task.worktree.branch;
+    let uncommitted_changes = !execute_git_command(path, &["status", "--porcelain"])
+        .await?
+        .trim()
+        .is_empty();
+
+    // Pushing a branch that is already up to date does nothing
+    execute_git_command(path, &["push", "--quiet", "-u", REMOTE, branch])
+        .await
+        .with_context(|| format!("Failed to push {branch} to {REMOTE}"))?;
+
+    let (pull_request, created) = match existing_pull_request(path, branch).await? {
+        Some(url) => (url, false),
+        None => {
+            let metadata = &task.worktree.metadata;
+            let mut variables = BTreeMap::new();
+            variables.insert("task_id".to_string(), options.task_id.clone());
+            variables.insert("branch".to_string(), branch.clone());
+            variables.insert("repo".to_string(), task.repo.clone());
+            variables.insert(
+                "base_branch".to_string(),
+                metadata.base_branch.clone().unwrap_or_default(),
+            );
+            variables.insert(
+                "description".to_string(),
+                metadata
+                    .description
+                    .clone()
+                    .unwrap_or_else(|| options.task_id.clone()),
+            );
+            variables.insert(
+                "link".to_string(),
+                metadata.link.clone().unwrap_or_default(),
+            );
+
+            let templates = &task.manager.get_config().task;
+            let title = options
+                .title
+                .unwrap_or_else(|| substitute(&templates.pr_title, &variables));
+            let body = options
+                .body
+                .unwrap_or_else(|| substitute(&templates.pr_body, &variables));
+            let url = create_pull_request(
+                path,
+                branch,
+                metadata.base_branch.as_deref(),
+                title.trim(),
+                body.trim(),
+                options.draft,
+            )
+            .await?;
+            (url, true)
+        }
+    };
+
+    if task.worktree.metadata.pull_request.as_deref() != Some(pull_request.as_str()) {
+        let mut metadata = task.worktree.metadata.clone();
+        metadata.pull_request = Some(pull_request.clone());
+        let main_checkout = task.manager.get_operations().main_checkout().await?;
+        metadata::save(&main_checkout, branch, &metadata).await?;
+    }
+
+    Ok(TaskFinished {
+        repo: task.repo,
+        task_id: options.task_id,
+        branch: branch.clone(),
+        pull_request,
+        created,
+        uncommitted_changes,
+    })
+}
+
+/// Check the task's branch merged, then remove its worktree and branch. A
+/// branch left behind by an earlier run is deleted on its own.
+pub async fn done(workspace: &WorkspaceManager, options: DoneOptions) -> Result<TaskDone> {
+    let Some(task) = find_task(workspace, &options.task_id, options.repo.as_deref()).await? else {
+        return delete_leftover_branch(workspace, options).await;
+    };
+
+    let path = &task.worktree.path;
+    let branch = task.worktree.branch.clone();
+    let merge_config = task
+        .manager
+        .get_config()
+        .merge_detection
+        .preferring(task.worktree.metadata.base_branch.as_deref());
+    let merge = detect_worktree_merge_status(path, &branch, &merge_config).await?;
+    if !merge.is_merged && !options.force {
+        return Err(not_merged(&options.task_id, &branch, &task.worktree.metadata).into());
+    }
+
+    let outcome = task
+        .manager
+        .remove_worktree_with_options(RemoveOptions {
+            target: path.to_string_lossy().to_string(),
+            force: options.force,
+            delete_branch: true,
+            include_protected: false,
+        })
+        .await?;
+
+    Ok(TaskDone {
+        task_id: options.task_id,
+        repo: Some(task.repo),
+        branch: Some(branch),
+        merge: Some(merge),
+        worktree_removed: true,
+        branch_deleted: outcome.protected_branch.is_none(),
+        branch_kept: outcome.protected_branch.map(|reason| reason.to_string()),
+        trash_id: outcome.trash_id,
+        backup: outcome.backup,
+    })
+}
+
+/// `done` for a task whose worktree is gone: delete its branch if one is
+/// left, or report that nothing is
+async fn delete_leftover_branch(
+    workspace: &WorkspaceManager,
+    options: DoneOptions,
+) -> Result<TaskDone> {
+    let mut result = TaskDone {
+        task_id: options.task_id.clone(),
+        repo: None,
+        branch: None,
+        merge: None,
+        worktree_removed: false,
+        branch_deleted: false,
+        branch_kept: None,
+        trash_id: None,
+        backup: None,
+    };
+
+    let task_branch = sanitize_branch_name(&options.task_id)?;
+    for repo in candidate_repositories(workspace, options.repo.as_deref())? {
+        let manager = worktree_manager(workspace, &repo).await?;
+        let repo_path = repo_abs_path(workspace.config(), &repo);
+        let branch = format!("{}{}", manager.get_config().prefix, task_branch);
+        let reference = format!("refs/heads/{branch}");
+        if execute_git_command(&repo_path, &["show-ref", "--verify", "--quiet", &reference])
+            .await
+            .is_err()
+        {
+            continue;
+        }
+
+        let merge = detect_worktree_merge_status(
+            &repo_path,
+            &branch,
+            &manager.get_config().merge_detection,
+        )
+        .await?;
+        if !merge.is_merged && !options.force {
+            return Err(not_merged(&options.task_id, &branch, &WorktreeMetadata::default()).into());
+        }
+
+        match manager.get_operations().branch_protection().await.check(
+            &branch,
+            false,
+            options.force,
+        ) {
+            Ok(()) => {
+                result.backup = ref_backup::backup_branch(&repo_path, &branch).await?;
+                execute_git_command(&repo_path, &["branch", "-D", &branch]).await?;
+                result.branch_deleted = true;
+            }
+            Err(e) => result.branch_kept = Some(e.reason.to_string()),
+        }
+        result.repo = Some(repo.name);
+        result.branch = Some(branch);
+        result.merge = Some(merge);
+        break;
+    }
+    Ok(result)
+}
+
+fn not_merged(task_id: &str, branch: &str, metadata: &WorktreeMetadata) -> CommandError {
+    let next = match &metadata.pull_request {
+        Some(url) => format!("wait for {url} to merge"),
+        None => format!("run 'vibe task finish {task_id}' to open a pull request"),
+    };
+    CommandError::Usage(format!(
+        "Branch '{branch}' of task '{task_id}' isn't merged yet; {next}, or pass --force to clean up anyway"
+    ))
+}
+
+async fn worktree_manager(
+    workspace: &WorkspaceManager,
+    repo: &Repository,
+) -> Result<WorktreeManager> {
+    WorktreeManager::new_with_workspace_manager(
+        workspace,
+        Some(repo_abs_path(workspace.config(), repo)),
+    )
+    .await
+}
+
+/// The named repository, or every configured checkout
+fn candidate_repositories(
+    workspace: &WorkspaceManager,
+    repo: Option<&str>,
+) -> Result<Vec<Repository>> {
+    match repo {
+        Some(name) => {
+            let repo = workspace
+                .get_repository_flexible(name)
+                .ok_or_else(|| CommandError::not_found("Repository", name))?;
+            Ok(vec![repo.clone()])
+        }
+        None => Ok(workspace
+            .list_repositories()
+            .iter()
+            .filter(|repo| {
+                repo_abs_path(workspace.config(), repo)
+                    .join(".git")
+                    .exists()
+            })
+            .cloned()
+            .collect()),
+    }
+}
+
+/// The worktree of `task_id`, searched for in `repo` or in every repository
+async fn find_task(
+    workspace: &WorkspaceManager,
+    task_id: &str,
+    repo: Option<&str>,
+) -> Result<Option<TaskWorktree>> {
+    let task_branch = sanitize_branch_name(task_id)?;
+    let mut found = Vec::new();
+    for repo in candidate_repositories(workspace, repo)? {
+        let manager = worktree_manager(workspace, &repo).await?;
+        if let Some(worktree) = manager
+            .get_operations()
+            .find_worktree_by_task_id(&task_branch)
+            .await?
+        {
+            found.push(TaskWorktree {
+                repo: repo.name,
+                manager,
+                worktree,
+            });
+        }
+    }
+
+    if found.len() > 1 {
+        let repos: Vec<&str> = found.iter().map(|task| task.repo.as_str()).collect();
+        return Err(CommandError::Usage(format!(
+            "Task '{task_id}' has worktrees in {}; pass --repo to pick one",
+            repos.join(", ")
+        ))
+        .into());
+    }
+    Ok(found.pop())
+}
+
+async fn require_task(
+    workspace: &WorkspaceManager,
+    task_id: &str,
+    repo: Option<&str>,
+) -> Result<TaskWorktree> {
+    find_task(workspace, task_id, repo).await?.ok_or_else(|| {
+        CommandError::not_found_with_hint(
+            "Task",
+            task_id,
+            format!("Start it with 'vibe task start <repo> {task_id}'"),
+        )
+        .into()
+    })
+}
+
+/// URL of the open or merged pull request for `branch`, if any
+async fn existing_pull_request(path: &Path, branch: &str) -> Result<Option<String>> {
+    let output = Command::new(resolve_command("gh"))
+        .args(["pr", "view", branch, "--json", "url,state"])
+        .current_dir(path)
+        .output()
+        .await
+        .context("Failed to run gh pr view")?;
+    // gh exits non-zero when the branch has no pull request
+    if !output.status.success() {
+        return Ok(None);
+    }
+
+    let pull_request: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    if pull_request["state"].as_str() == Some("CLOSED") {
+        return Ok(None);
+    }
+    Ok(pull_request["url"].as_str().map(str::to_string))
+}
+
+async fn create_pull_request(
+    path: &Path,
+    branch: &str,
+    base: Option<&str>,
+    title: &str,
+    body: &str,
+    draft: bool,
+) -> Result<String> {
+    let mut command = Command::new(resolve_command("gh"));
+    command
+        .args([
+            "pr", "create", "--head", branch, "--title", title, "--body", body,
+        ])
+        .current_dir(path);
+    if let Some(base) = base {
+        command.args(["--base", base]);
+    }
+    if draft {
+        command.arg("--draft");
+    }
+
+    let output = command
+        .output()
+        .await
+        .context("Failed to run gh pr create")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "gh pr create failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .rev()
+        .find(|line| line.starts_with("http"))
+        .map(str::to_string)
+        .context("gh pr create printed no pull request URL")
+}
+
+impl TaskStarted {
+    pub fn print(&self) {
+        let verb = if self.created { "Started" } else { "Resumed" };
+        display_println!(
+            "{} {} task {} in {}",
+            theme::icon(Icon::Success),
+            verb,
+            style(&self.task_id).cyan(),
+            style(&self.repo).bold()
+        );
+        display_println!("  Branch: {}", theme::paint(Role::Accent, &self.branch));
+        display_println!("  Path: {}", self.path.display());
+    }
+}
+
+impl TaskFinished {
+    pub fn print(&self) {
+        if self.uncommitted_changes {
+            display_println!(
+                "{} The worktree has uncommitted changes; they were not pushed",
+                theme::icon(Icon::Warning)
+            );
+        }
+        let verb = if self.created {
+            "Opened"
+        } else {
+            "Pushed; existing"
+        };
+        display_println!(
+            "{} {} pull request for {}: {}",
+            theme::icon(Icon::Success),
+            verb,
+            style(&self.branch).yellow(),
+            style(&self.pull_request).underlined()
+        );
+    }
+}
+
+impl TaskDone {
+    pub fn print(&self) {
+        let Some(branch) = &self.branch else {
+            display_println!(
+                "{} Nothing left to clean up for task {}",
+                theme::icon(Icon::Info),
+                style(&self.task_id).cyan()
+            );
+            return;
+        };
+
+        if let Some(merge) = &self.merge {
+            if merge.is_merged {
+                display_println!(
+                    "{} {} is merged ({})",
+                    theme::icon(Icon::Success),
+                    style(branch).yellow(),
+                    merge.details.as_deref().unwrap_or(&merge.detection_method)
+                );
+            } else {
+                display_println!(
+                    "{} {} is not merged; cleaning up anyway (--force)",
+                    theme::icon(Icon::Warning),
+                    style(branch).yellow()
+                );
+            }
+        }
+        if self.worktree_removed {
+            display_println!("{} Removed the worktree", theme::icon(Icon::Success));
+        }
+        if let Some(id) = &self.trash_id {
+            display_println!(
+                "{} In the trash as {id}; 'vibe trash restore {id}' brings it back",
+                theme::icon(Icon::Info)
+            );
+        }
+        if self.branch_deleted {
+            display_println!("{} Deleted branch {}", theme::icon(Icon::Success), branch);
+        }
+        if let (Some(backup), Some(repo)) = (&self.backup, &self.repo) {
+            display_println!(
+                "{} Backed up as {}; 'vibe git refs restore {repo} {branch}' brings it back",
+                theme::icon(Icon::Info),
+                backup.backup_ref
+            );
+        }
+        if let Some(reason) = &self.branch_kept {
+            display_println!(
+                "{} Kept branch {} (protected: {reason})",
+                theme::icon(Icon::Warning),
+                branch
+            );
+        }
+    }
+}
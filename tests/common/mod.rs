@@ -0,0 +1,254 @@
+//! Helpers shared by the integration tests
+//!
+//! Every `vibe` run gets `HOME` pointed at a temporary directory and the
+//! variables that could send config, data, cache, or state elsewhere removed,
+//! so no test reads or writes the caller's workspace.
+
+// Each test binary compiles this module and uses only part of it
+#![allow(dead_code)]
+
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use tempfile::TempDir;
+
+/// Identity for commits made by tests and by vibe during tests
+pub const GIT_NAME: &str = "Test User";
+pub const GIT_EMAIL: &str = "test@example.com";
+
+/// A `vibe` command isolated in `home`
+pub fn vibe(home: &Path) -> Command {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_vibe"));
+    command
+        .env("HOME", home)
+        .env_remove("VIBE_HOME")
+        .env_remove("XDG_CONFIG_HOME")
+        .env_remove("XDG_DATA_HOME")
+        .env_remove("XDG_CACHE_HOME")
+        .env_remove("XDG_STATE_HOME")
+        .env_remove("NO_COLOR")
+        .env_remove("CLICOLOR_FORCE")
+        .env("GIT_AUTHOR_NAME", GIT_NAME)
+        .env("GIT_AUTHOR_EMAIL", GIT_EMAIL)
+        .env("GIT_COMMITTER_NAME", GIT_NAME)
+        .env("GIT_COMMITTER_EMAIL", GIT_EMAIL);
+    command
+}
+
+pub fn run_vibe(home: &Path, args: &[&str]) -> Output {
+    vibe(home)
+        .args(args)
+        .output()
+        .expect("Failed to execute vibe")
+}
+
+/// [`run_vibe`] with `--config config`
+pub fn run_vibe_with_config(home: &Path, config: impl AsRef<Path>, args: &[&str]) -> Output {
+    vibe(home)
+        .arg("--config")
+        .arg(config.as_ref())
+        .args(args)
+        .output()
+        .expect("Failed to execute vibe")
+}
+
+/// [`run_vibe`] from the directory `dir`
+pub fn run_vibe_in(home: &Path, dir: &Path, args: &[&str]) -> Output {
+    vibe(home)
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .expect("Failed to execute vibe")
+}
+
+/// [`run_vibe`] with `home/bin` searched first on `PATH`, for stub
+/// executables such as `gh`
+pub fn run_vibe_with_stubs(home: &Path, args: &[&str]) -> Output {
+    let path = format!(
+        "{}:{}",
+        home.join("bin").display(),
+        std::env::var("PATH").unwrap_or_default()
+    );
+    vibe(home)
+        .args(args)
+        .env("PATH", path)
+        .output()
+        .expect("Failed to execute vibe")
+}
+
+/// The JSON envelope a run printed, whatever its exit code. Anything printed
+/// before the JSON, such as progress from a subcommand, is skipped.
+pub fn json_report(output: &Output) -> serde_json::Value {
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    serde_json::from_str(&stdout[stdout.find('{').expect("no JSON in output")..]).unwrap()
+}
+
+/// The `data` of a successful run's [`json_report`]
+pub fn json_data(output: &Output) -> serde_json::Value {
+    assert!(
+        output.status.success(),
+        "vibe failed: {}\n{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    json_report(output)["data"].clone()
+}
+
+/// [`json_data`] of `vibe --config config --json args`
+pub fn vibe_json(home: &Path, config: impl AsRef<Path>, args: &[&str]) -> serde_json::Value {
+    let mut all = vec!["--json"];
+    all.extend_from_slice(args);
+    json_data(&run_vibe_with_config(home, config, &all))
+}
+
+/// Stdout of a successful run, parsed as JSON
+pub fn stdout_json(output: &Output) -> serde_json::Value {
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+/// Run `git` in `dir` as `name <email>`, returning its trimmed stdout
+pub fn git_as(dir: &Path, name: &str, email: &str, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .args(["-c", &format!("user.name={name}")])
+        .args(["-c", &format!("user.email={email}")])
+        .args(["-c", "init.defaultBranch=main"])
+        .args(["-c", "commit.gpgsign=false"])
+        .args(["-c", "protocol.file.allow=always"])
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .expect("Failed to run git");
+    assert!(
+        output.status.success(),
+        "git {args:?} failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
+/// Run `git` in `dir` as the test identity, returning its trimmed stdout
+pub fn git(dir: &Path, args: &[&str]) -> String {
+    git_as(dir, GIT_NAME, GIT_EMAIL, args)
+}
+
+/// Create `dir` as a repository on `main`
+pub fn git_init(dir: &Path) {
+    std::fs::create_dir_all(dir).unwrap();
+    git(dir, &["init", "--quiet"]);
+}
+
+/// Write `name` in `dir` and commit it
+pub fn commit_file(dir: &Path, name: &str, content: &str) {
+    std::fs::write(dir.join(name), content).unwrap();
+    git(dir, &["add", name]);
+    git(dir, &["commit", "--quiet", "-m", &format!("Update {name}")]);
+}
+
+/// A workspace with one committed repository, `api`, whose `origin` is a
+/// bare repository next to it, and `extra_config` appended to its
+/// config.yaml. Returns the home directory, the config path, and the
+/// repository.
+pub fn workspace_with_origin(extra_config: &str) -> (TempDir, PathBuf, PathBuf) {
+    let home = TempDir::new().unwrap();
+    let root = home.path().join("workspace");
+    let repo = root.join("api");
+    let origin = home.path().join("origin.git");
+    git(home.path(), &["init", "--quiet", "--bare", "origin.git"]);
+    git_init(&repo);
+    commit_file(&repo, "README.md", "api");
+    git(
+        &repo,
+        &["remote", "add", "origin", origin.to_str().unwrap()],
+    );
+    git(&repo, &["push", "--quiet", "-u", "origin", "main"]);
+
+    let config = home.path().join("config.yaml");
+    WorkspaceYaml::new(&root)
+        .repo("api", "api")
+        .extra(extra_config)
+        .write(&config);
+    (home, config, repo)
+}
+
+/// config.yaml for a workspace with auto-discovery off
+pub struct WorkspaceYaml {
+    root: PathBuf,
+    repositories: String,
+    groups: String,
+    apps: String,
+    extra: String,
+}
+
+impl WorkspaceYaml {
+    pub fn new(root: &Path) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            repositories: String::new(),
+            groups: String::new(),
+            apps: String::new(),
+            extra: String::new(),
+        }
+    }
+
+    /// A repository with no apps
+    pub fn repo(self, name: &str, path: impl Display) -> Self {
+        self.repo_with(name, path, "")
+    }
+
+    /// A repository with more `fields`, each line indented two spaces like
+    /// `"  url: https://github.com/acme/api\n"`. Without an `apps:` field it
+    /// gets no apps.
+    pub fn repo_with(mut self, name: &str, path: impl Display, fields: &str) -> Self {
+        self.repositories
+            .push_str(&format!("- name: {name}\n  path: {path}\n{fields}"));
+        if !fields.contains("\n  apps:") && !fields.starts_with("  apps:") {
+            self.repositories.push_str("  apps: {}\n");
+        }
+        self
+    }
+
+    /// Entries of `groups:`, such as `"- name: web\n  repos: [api]\n  apps: {}\n"`
+    pub fn groups(mut self, yaml: &str) -> Self {
+        self.groups.push_str(yaml);
+        self
+    }
+
+    /// The body of `apps:`, indented two spaces
+    pub fn apps(mut self, yaml: &str) -> Self {
+        self.apps.push_str(yaml);
+        self
+    }
+
+    /// Top-level sections after `apps`, such as `preferences:` or `hooks:`
+    pub fn extra(mut self, yaml: &str) -> Self {
+        self.extra.push_str(yaml);
+        self
+    }
+
+    pub fn yaml(&self) -> String {
+        let list = |name: &str, body: &str, empty: &str| {
+            if body.is_empty() {
+                format!("{name}: {empty}\n")
+            } else {
+                format!("{name}:\n{body}")
+            }
+        };
+        format!(
+            "workspace:\n  name: test\n  root: {}\n  auto_discover: false\n{}{}{}{}",
+            self.root.display(),
+            list("repositories", &self.repositories, "[]"),
+            list("groups", &self.groups, "[]"),
+            list("apps", &self.apps, "{}"),
+            self.extra
+        )
+    }
+
+    pub fn write(&self, path: &Path) {
+        std::fs::write(path, self.yaml()).unwrap();
+    }
+}
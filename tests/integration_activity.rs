@@ -0,0 +1,76 @@
+//! `vibe activity`: commits, merges, and new branches across repositories
+
+mod common;
+
+use common::{git, git_as, json_data, run_vibe, WorkspaceYaml};
+use tempfile::TempDir;
+
+/// `api` has two commits by Ada, a branch with a commit by Bob, and a
+/// merge of it; `docs` has no commits
+fn workspace() -> (TempDir, String) {
+    let home = TempDir::new().unwrap();
+    let root = home.path().join("workspace");
+    let api = root.join("api");
+    common::git_init(&root.join("docs"));
+    common::git_init(&api);
+
+    let ada = |args: &[&str]| git_as(&api, "Ada", "ada@example.com", args);
+    for file in ["a.txt", "b.txt"] {
+        std::fs::write(api.join(file), file).unwrap();
+        ada(&["add", file]);
+        ada(&["commit", "--quiet", "-m", file]);
+    }
+    ada(&["checkout", "--quiet", "-b", "feature"]);
+    std::fs::write(api.join("c.txt"), "c").unwrap();
+    git_as(&api, "Bob", "bob@example.com", &["add", "c.txt"]);
+    git_as(
+        &api,
+        "Bob",
+        "bob@example.com",
+        &["commit", "--quiet", "-m", "c"],
+    );
+    git(&api, &["checkout", "--quiet", "main"]);
+    ada(&["merge", "--quiet", "--no-ff", "feature", "-m", "merge"]);
+
+    let config = home.path().join("config.yaml");
+    WorkspaceYaml::new(&root)
+        .repo("api", "api")
+        .repo("docs", "docs")
+        .write(&config);
+    (home, config.to_str().unwrap().to_string())
+}
+
+#[test]
+fn test_activity_counts_and_caches() {
+    let (home, config) = workspace();
+    let args = ["--config", &config, "--json", "activity"];
+
+    let data = json_data(&run_vibe(home.path(), &args));
+    let api = &data["repositories"][0];
+    assert_eq!(api["repository"], "api");
+    assert_eq!(api["commits"], 4);
+    assert_eq!(api["merges"], 1);
+    assert_eq!(api["authors"], serde_json::json!(["Ada", "Bob"]));
+    assert_eq!(api["branches_created"], serde_json::json!(["feature"]));
+    assert_eq!(api["daily"].as_array().unwrap().len(), 7);
+    assert_eq!(api["cached"], false);
+    assert_eq!(data["totals"]["active_repositories"], 1);
+
+    let data = json_data(&run_vibe(home.path(), &args));
+    assert_eq!(data["repositories"][0]["cached"], true);
+    assert_eq!(data["repositories"][0]["commits"], 4);
+}
+
+#[test]
+fn test_activity_author_filter() {
+    let (home, config) = workspace();
+    let data = json_data(&run_vibe(
+        home.path(),
+        &[
+            "--config", &config, "--json", "activity", "--author", "bob", "--days", "1",
+        ],
+    ));
+    assert_eq!(data["days"], 1);
+    assert_eq!(data["repositories"][0]["commits"], 1);
+    assert_eq!(data["totals"]["authors"], serde_json::json!(["Bob"]));
+}
@@ -0,0 +1,105 @@
+//! Configuring an app for many repositories with `vibe apps configure`
+
+mod common;
+
+use common::{json_data, run_vibe, WorkspaceYaml};
+use std::process::Output;
+use tempfile::TempDir;
+
+/// `api` and `docs` have no apps; `web` already uses cursor with `team`.
+/// The `backend` group holds `api` and `web`.
+fn workspace() -> (TempDir, String) {
+    let home = TempDir::new().unwrap();
+    let root = home.path().join("workspace");
+    for name in ["api", "web", "docs"] {
+        std::fs::create_dir_all(root.join(name)).unwrap();
+    }
+    let config = home.path().join("config.yaml");
+    WorkspaceYaml::new(&root)
+        .repo("api", "api")
+        .repo_with("web", "web", "  apps:\n    cursor:\n      template: team\n")
+        .repo("docs", "docs")
+        .groups("- name: backend\n  repos: [api, web]\n  apps: {}\n")
+        .write(&config);
+    (home, config.to_str().unwrap().to_string())
+}
+
+fn outcomes(output: &Output) -> Vec<(String, String)> {
+    json_data(output)
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|r| {
+            (
+                r["name"].as_str().unwrap().to_string(),
+                r["outcome"].as_str().unwrap().to_string(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_all_repos_skips_configured_unless_overwrite() {
+    let (home, config) = workspace();
+    let args = ["--config", &config, "--json", "apps", "configure"];
+
+    let output = run_vibe(
+        home.path(),
+        &[&args[..], &["--all-repos", "cursor"]].concat(),
+    );
+    assert_eq!(
+        outcomes(&output),
+        [
+            ("api".to_string(), "configured".to_string()),
+            ("web".to_string(), "already_configured".to_string()),
+            ("docs".to_string(), "configured".to_string()),
+        ]
+    );
+
+    let output = run_vibe(
+        home.path(),
+        &[&args[..], &["--group", "backend", "cursor", "--overwrite"]].concat(),
+    );
+    assert_eq!(
+        outcomes(&output),
+        [
+            ("api".to_string(), "unchanged".to_string()),
+            ("web".to_string(), "configured".to_string()),
+        ]
+    );
+    assert!(!std::fs::read_to_string(&config)
+        .unwrap()
+        .contains("template: team"));
+}
+
+#[test]
+fn test_bulk_configure_rejects_repo_argument() {
+    let (home, config) = workspace();
+    let output = run_vibe(
+        home.path(),
+        &[
+            "--config",
+            &config,
+            "apps",
+            "configure",
+            "api",
+            "cursor",
+            "--all-repos",
+        ],
+    );
+    assert_eq!(output.status.code(), Some(2));
+
+    let output = run_vibe(
+        home.path(),
+        &[
+            "--config",
+            &config,
+            "apps",
+            "configure",
+            "--group",
+            "nope",
+            "cursor",
+        ],
+    );
+    assert_eq!(output.status.code(), Some(3));
+}
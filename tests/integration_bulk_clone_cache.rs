@@ -0,0 +1,119 @@
+//! Cached org repository lists and rate limits in bulk cloning, against a
+//! stub `gh` that records every API call
+#![cfg(unix)]
+
+mod common;
+
+use common::{run_vibe_with_stubs, stdout_json, WorkspaceYaml};
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use tempfile::TempDir;
+
+const REPO_LINE: &str = r#"{"fullName":"acme/api","name":"api","description":null,"url":"https://github.com/acme/api.git","sshUrl":"git@github.com:acme/api.git","stargazersCount":1,"language":"Rust","fork":false,"archived":false,"topics":[],"size":12,"license":null,"updatedAt":"2024-05-01T00:00:00Z"}"#;
+
+/// Stub `gh` answering the listing, the listing probe, and the rate limit
+/// endpoint. With `RATE_LIMITED` set, listing fails with a rate limit that
+/// resets in an hour.
+fn stub_gh(bin: &Path, rate_limited: bool) {
+    let script = format!(
+        r#"#!/bin/sh
+echo "$*" >> "$(dirname "$0")/calls.log"
+case "$*" in
+  --version) echo "gh version 2.0.0" ;;
+  "api rate_limit"*) echo $(( $(date +%s) + 3600 )) ;;
+  *--paginate*)
+    if [ "{rate_limited}" = "true" ]; then
+      echo "gh: API rate limit exceeded for user ID 1. (HTTP 403)" >&2
+      exit 1
+    fi
+    echo '{REPO_LINE}' ;;
+  *If-None-Match*)
+    printf 'HTTP/2.0 304 Not Modified\r\n\r\n'
+    echo "gh: HTTP 304" >&2
+    exit 1 ;;
+  *per_page=1*)
+    printf 'HTTP/2.0 200 OK\r\nEtag: W/"v1"\r\n\r\n[{{"updated_at":"2024-05-01T00:00:00Z"}}]' ;;
+  *) exit 1 ;;
+esac
+"#
+    );
+    std::fs::create_dir_all(bin).unwrap();
+    let gh = bin.join("gh");
+    std::fs::write(&gh, script).unwrap();
+    std::fs::set_permissions(&gh, std::fs::Permissions::from_mode(0o755)).unwrap();
+}
+
+fn workspace(rate_limited: bool) -> (TempDir, String) {
+    let home = TempDir::new().unwrap();
+    stub_gh(&home.path().join("bin"), rate_limited);
+    let root = home.path().join("workspace");
+    std::fs::create_dir_all(&root).unwrap();
+
+    let config = home.path().join("config.yaml");
+    WorkspaceYaml::new(&root).write(&config);
+
+    (home, config.to_str().unwrap().to_string())
+}
+
+/// Run a bulk clone dry run and count the full listings it fetched
+fn dry_run(home: &Path, config: &str, extra: &[&str]) -> (serde_json::Value, usize) {
+    let log = home.join("bin/calls.log");
+    let _ = std::fs::remove_file(&log);
+    let mut args = vec![
+        "--config",
+        config,
+        "--json",
+        "clone",
+        "acme",
+        "--all",
+        "--dry-run",
+    ];
+    args.extend_from_slice(extra);
+    let output = run_vibe_with_stubs(home, &args);
+    let listings = std::fs::read_to_string(&log)
+        .unwrap_or_default()
+        .lines()
+        .filter(|call| call.contains("--paginate"))
+        .count();
+    (stdout_json(&output), listings)
+}
+
+#[test]
+fn test_dry_runs_reuse_cached_org_listing() {
+    let (home, config) = workspace(false);
+
+    let (plan, listings) = dry_run(home.path(), &config, &[]);
+    assert_eq!(listings, 1);
+    assert_eq!(plan["data"]["to_clone"][0]["name"], "acme/api");
+    assert!(plan["data"].get("cached_at").is_none());
+
+    // The first check matches on updated_at and records the ETag; the next
+    // one gets a 304
+    for _ in 0..2 {
+        let (plan, listings) = dry_run(home.path(), &config, &[]);
+        assert_eq!(listings, 0);
+        assert_eq!(plan["data"]["to_clone"][0]["name"], "acme/api");
+        assert!(plan["data"]["cached_at"].is_string());
+    }
+    let calls = std::fs::read_to_string(home.path().join("bin/calls.log")).unwrap();
+    assert!(calls.contains(r#"If-None-Match: W/"v1""#));
+
+    let (plan, listings) = dry_run(home.path(), &config, &["--refresh"]);
+    assert_eq!(listings, 1);
+    assert!(plan["data"].get("cached_at").is_none());
+}
+
+#[test]
+fn test_rate_limit_reports_reset_time() {
+    let (home, config) = workspace(true);
+    let output = run_vibe_with_stubs(
+        home.path(),
+        &["--config", &config, "clone", "acme", "--all", "--dry-run"],
+    );
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("GitHub API rate limit exceeded; resets at"),
+        "{stderr}"
+    );
+}
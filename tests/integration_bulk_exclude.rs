@@ -0,0 +1,83 @@
+//! Repositories excluded from bulk operations: skipped by workspace-wide
+//! status and exec, still reachable when named explicitly
+
+mod common;
+
+use common::{git_init, json_data, run_vibe, WorkspaceYaml};
+use std::path::Path;
+use tempfile::TempDir;
+
+/// A workspace with the checkouts `api` and `mirror`
+fn workspace() -> (TempDir, String) {
+    let home = TempDir::new().unwrap();
+    let root = home.path().join("workspace");
+    for name in ["api", "mirror"] {
+        git_init(&root.join(name));
+    }
+
+    let config = home.path().join("config.yaml");
+    WorkspaceYaml::new(&root)
+        .repo("api", "api")
+        .repo("mirror", "mirror")
+        .write(&config);
+
+    (home, config.to_str().unwrap().to_string())
+}
+
+fn status_names(home: &Path, config: &str) -> Vec<String> {
+    json_data(&run_vibe(
+        home,
+        &["--config", config, "--json", "git", "status"],
+    ))
+    .as_array()
+    .unwrap()
+    .iter()
+    .map(|status| status["repository_name"].as_str().unwrap().to_string())
+    .collect()
+}
+
+#[test]
+fn test_excluded_repository_is_skipped_unless_named() {
+    let (home, config) = workspace();
+    assert_eq!(status_names(home.path(), &config), ["api", "mirror"]);
+
+    let output = run_vibe(
+        home.path(),
+        &["--config", &config, "config", "repo", "exclude", "mirror"],
+    );
+    assert!(output.status.success());
+    assert!(std::fs::read_to_string(&config)
+        .unwrap()
+        .contains("exclude:\n  - sync\n  - exec\n  - status"));
+    assert_eq!(status_names(home.path(), &config), ["api"]);
+
+    let output = run_vibe(
+        home.path(),
+        &["--config", &config, "git", "status", "--format", "compact"],
+    );
+    assert!(String::from_utf8_lossy(&output.stdout).contains("1 repo excluded from status: mirror"));
+
+    let exec = |extra: &[&str]| {
+        let mut args = vec!["--config", &config, "git", "exec", "status --short"];
+        args.extend_from_slice(extra);
+        String::from_utf8_lossy(&run_vibe(home.path(), &args).stdout).to_string()
+    };
+    assert!(!exec(&[]).contains("Executing on mirror"));
+    assert!(exec(&["--repos", "mirror"]).contains("Executing on mirror"));
+
+    let output = run_vibe(
+        home.path(),
+        &[
+            "--config", &config, "config", "repo", "include", "mirror", "status",
+        ],
+    );
+    assert!(output.status.success());
+    assert_eq!(status_names(home.path(), &config), ["api", "mirror"]);
+    assert!(!exec(&[]).contains("Executing on mirror"));
+
+    let output = run_vibe(
+        home.path(),
+        &["--config", &config, "config", "repo", "exclude", "web"],
+    );
+    assert_eq!(output.status.code(), Some(3));
+}
@@ -0,0 +1,84 @@
+//! `--on-collision` when a clone's name is configured or its directory exists
+
+mod common;
+
+use common::{git, git_init, run_vibe, WorkspaceYaml};
+use tempfile::TempDir;
+
+/// A workspace where `acme/api` is configured as a clone of `globex/api`,
+/// and `acme/web` is cloned but not configured
+fn workspace() -> (TempDir, String) {
+    let home = TempDir::new().unwrap();
+    let root = home.path().join("workspace");
+    std::fs::create_dir_all(root.join("api")).unwrap();
+    let web = root.join("acme/web");
+    git_init(&web);
+    git(
+        &web,
+        &["remote", "add", "origin", "git@github.com:acme/web.git"],
+    );
+
+    let config = home.path().join("config.yaml");
+    WorkspaceYaml::new(&root)
+        .repo_with("acme/api", "api", "  url: https://github.com/globex/api\n")
+        .write(&config);
+
+    (home, config.to_str().unwrap().to_string())
+}
+
+#[test]
+fn test_taken_name_fails_or_refuses_to_open_a_different_repository() {
+    let (home, config) = workspace();
+    let clone = |resolution: &str| {
+        run_vibe(
+            home.path(),
+            &[
+                "--config",
+                &config,
+                "git",
+                "clone",
+                "acme/api",
+                "--on-collision",
+                resolution,
+            ],
+        )
+    };
+
+    let output = clone("fail");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr)
+        .contains("A repository named 'acme/api' is already configured"));
+
+    let output = clone("open-existing");
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("which is a different repository"));
+}
+
+#[test]
+fn test_open_existing_adopts_an_unconfigured_clone_of_the_same_url() {
+    let (home, config) = workspace();
+    let output = run_vibe(
+        home.path(),
+        &[
+            "--config",
+            &config,
+            "git",
+            "clone",
+            "https://github.com/acme/web",
+            "--on-collision",
+            "open-existing",
+        ],
+    );
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let saved = std::fs::read_to_string(&config).unwrap();
+    assert!(saved.contains("name: acme/web"), "{saved}");
+    assert!(
+        saved.contains("url: https://github.com/acme/web"),
+        "{saved}"
+    );
+}
@@ -0,0 +1,80 @@
+//! Integration tests for `vibe clone <template> --as <name>`
+
+mod common;
+
+use common::{git, run_vibe, WorkspaceYaml};
+use tempfile::TempDir;
+
+#[test]
+fn test_clone_as_generates_repository() {
+    let home = TempDir::new().unwrap();
+    let template = home.path().join("service-template");
+    std::fs::create_dir_all(template.join("src")).unwrap();
+    std::fs::write(
+        template.join("template.vibe.yaml"),
+        "variables:\n  port: \"8080\"\n",
+    )
+    .unwrap();
+    std::fs::write(
+        template.join("README.md"),
+        "# {{project_name}} on {{ port }}\n",
+    )
+    .unwrap();
+    std::fs::write(template.join("src/{{project_name_snake}}.rs"), "").unwrap();
+    git(&template, &["init", "-q"]);
+    git(&template, &["add", "."]);
+    git(&template, &["commit", "-q", "-m", "template"]);
+
+    let root = home.path().join("workspace");
+    std::fs::create_dir_all(&root).unwrap();
+    let config = home.path().join("config.yaml");
+    WorkspaceYaml::new(&root).write(&config);
+    let config = config.to_str().unwrap();
+
+    let output = run_vibe(
+        home.path(),
+        &[
+            "--config",
+            config,
+            "clone",
+            template.to_str().unwrap(),
+            "--as",
+            "billing-api",
+            "--var",
+            "port=9000",
+        ],
+    );
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let repo = root.join("billing-api");
+    assert_eq!(
+        std::fs::read_to_string(repo.join("README.md")).unwrap(),
+        "# billing-api on 9000\n"
+    );
+    assert!(repo.join("src/billing_api.rs").exists());
+    assert!(!repo.join("template.vibe.yaml").exists());
+    // A single fresh commit and no remote pointing back at the template
+    assert_eq!(git(&repo, &["rev-list", "--count", "HEAD"]), "1");
+    assert_eq!(git(&repo, &["remote"]), "");
+
+    let saved = std::fs::read_to_string(home.path().join("config.yaml")).unwrap();
+    assert!(saved.contains("name: billing-api"));
+
+    // The name is now taken
+    let output = run_vibe(
+        home.path(),
+        &[
+            "--config",
+            config,
+            "clone",
+            template.to_str().unwrap(),
+            "--as",
+            "billing-api",
+        ],
+    );
+    assert!(!output.status.success());
+}
@@ -0,0 +1,70 @@
+//! Integration tests asserting that piped output never contains ANSI escape codes
+
+mod common;
+
+use std::process::Output;
+use tempfile::TempDir;
+
+fn run_vibe(home: &TempDir, args: &[&str], envs: &[(&str, &str)]) -> Output {
+    common::vibe(home.path())
+        .args(args)
+        .envs(envs.iter().copied())
+        .output()
+        .expect("Failed to execute vibe")
+}
+
+fn assert_no_escape_codes(output: &Output) {
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        !stdout.contains('\u{1b}'),
+        "escape codes in stdout: {stdout:?}"
+    );
+    assert!(
+        !stderr.contains('\u{1b}'),
+        "escape codes in stderr: {stderr:?}"
+    );
+}
+
+#[test]
+fn test_piped_output_has_no_colors() {
+    let home = TempDir::new().unwrap();
+    let output = run_vibe(&home, &["git", "status"], &[]);
+    assert!(output.status.success());
+    assert_no_escape_codes(&output);
+}
+
+#[test]
+fn test_no_color_env_and_flag() {
+    let home = TempDir::new().unwrap();
+    let output = run_vibe(&home, &["config", "show"], &[("NO_COLOR", "1")]);
+    assert_no_escape_codes(&output);
+
+    let output = run_vibe(&home, &["--no-color", "config", "show"], &[]);
+    assert_no_escape_codes(&output);
+}
+
+#[test]
+fn test_plain_output_has_no_icons() {
+    let home = TempDir::new().unwrap();
+    let output = run_vibe(&home, &["--plain", "git", "status"], &[]);
+    assert!(output.status.success());
+    assert_no_escape_codes(&output);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!stdout.contains('🔍'), "icons in plain output: {stdout:?}");
+}
+
+#[test]
+fn test_deterministic_output_is_plain() {
+    let home = TempDir::new().unwrap();
+    let output = run_vibe(&home, &["git", "status"], &[("VIBE_DETERMINISTIC", "1")]);
+    assert!(output.status.success());
+    assert_no_escape_codes(&output);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        !stdout.contains('🔍'),
+        "icons in deterministic output: {stdout:?}"
+    );
+}
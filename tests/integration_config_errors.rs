@@ -0,0 +1,44 @@
+//! Config files that don't load report every bad entry
+
+mod common;
+
+use common::run_vibe;
+use tempfile::TempDir;
+
+#[test]
+fn test_validate_lists_every_bad_entry() {
+    let home = TempDir::new().unwrap();
+    let config = home.path().join("config.yaml");
+    std::fs::write(
+        &config,
+        "workspace:\n  name: test\n  root: /tmp\n  auto_discover: false\n\
+         repositories:\n\
+         - name: frontend\n  path: frontend\n  apps:\n    vscode: code\n\
+         - name: docs\n  apps: {}\n\
+         groups: []\napps: {}\n",
+    )
+    .unwrap();
+    let config = config.to_str().unwrap();
+
+    let output = run_vibe(
+        home.path(),
+        &["--config", config, "--json", "config", "validate"],
+    );
+    assert_eq!(output.status.code(), Some(2));
+    let result: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let errors = result["data"]["config_errors"].as_array().unwrap();
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0]["context"], "repository 'frontend'");
+    assert_eq!(errors[0]["field"], "apps.vscode");
+    assert_eq!(errors[0]["line"], 9);
+    assert_eq!(errors[1]["context"], "repository 'docs'");
+
+    // Other commands fail with the same list
+    let output = run_vibe(home.path(), &["--config", config, "git", "status"]);
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("repository 'docs': missing field `path`"),
+        "{stderr}"
+    );
+}
@@ -0,0 +1,105 @@
+//! `vibe stats contributors`: commits per author across repositories, with
+//! author aliases merged
+
+mod common;
+
+use common::{git_as, git_init, json_data, run_vibe, WorkspaceYaml};
+use std::path::Path;
+use tempfile::TempDir;
+
+/// Commit a new `file` as `name <email>`
+fn commit(dir: &Path, name: &str, email: &str, file: &str) {
+    std::fs::write(dir.join(file), file).unwrap();
+    git_as(dir, name, email, &["add", file]);
+    git_as(dir, name, email, &["commit", "--quiet", "-m", file]);
+}
+
+/// `api` has commits by Ada under two identities and one by Bob, `web` has
+/// one by Ada, and `docs` has no commits at all
+fn workspace() -> (TempDir, String) {
+    let home = TempDir::new().unwrap();
+    let root = home.path().join("workspace");
+    for name in ["api", "web", "docs"] {
+        git_init(&root.join(name));
+    }
+    commit(&root.join("api"), "Ada", "ada@example.com", "a.txt");
+    commit(&root.join("api"), "ada", "ada@personal.dev", "b.txt");
+    commit(&root.join("api"), "Bob", "bob@example.com", "c.txt");
+    commit(&root.join("web"), "Ada", "ada@example.com", "a.txt");
+
+    let config = home.path().join("config.yaml");
+    WorkspaceYaml::new(&root)
+        .repo("api", "api")
+        .repo("web", "web")
+        .repo("docs", "docs")
+        .extra(
+            "preferences:\n  author_aliases:\n\
+             \x20 - author: Ada <ada@example.com>\n\
+             \x20   aliases: [ada@personal.dev]\n",
+        )
+        .write(&config);
+    (home, config.to_str().unwrap().to_string())
+}
+
+#[test]
+fn test_contributors_merge_aliases_and_cache() {
+    let (home, config) = workspace();
+    let args = [
+        "--config",
+        &config,
+        "--json",
+        "stats",
+        "contributors",
+        "--since",
+        "1y",
+    ];
+
+    let data = json_data(&run_vibe(home.path(), &args));
+    assert_eq!(data["total_commits"], 4);
+    let ada = &data["contributors"][0];
+    assert_eq!(ada["name"], "Ada");
+    assert_eq!(ada["email"], "ada@example.com");
+    assert_eq!(ada["commits"], 3);
+    assert_eq!(ada["repositories"]["api"], 2);
+    assert_eq!(ada["repositories"]["web"], 1);
+    assert_eq!(data["contributors"][1]["name"], "Bob");
+    assert_eq!(data["contributors"].as_array().unwrap().len(), 2);
+
+    let docs = data["repositories"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|repo| repo["repository"] == "docs")
+        .unwrap();
+    assert_eq!(docs["commits"], 0);
+
+    let again = json_data(&run_vibe(home.path(), &args));
+    assert!(again["repositories"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|repo| repo["repository"] != "docs")
+        .all(|repo| repo["cached"] == true));
+}
+
+#[test]
+fn test_contributors_csv() {
+    let (home, config) = workspace();
+    let output = run_vibe(
+        home.path(),
+        &[
+            "--config",
+            &config,
+            "stats",
+            "contributors",
+            "--format",
+            "csv",
+        ],
+    );
+    assert!(output.status.success());
+    let csv = String::from_utf8_lossy(&output.stdout);
+    let mut lines = csv.lines();
+    assert_eq!(lines.next(), Some("author,email,repository,commits"));
+    assert!(csv.contains("Ada,ada@example.com,api,2"));
+    assert!(csv.contains("Bob,bob@example.com,api,1"));
+}
@@ -0,0 +1,62 @@
+//! Commands run on vibe events through `hooks.events`
+#![cfg(unix)]
+
+mod common;
+
+use common::{git_init, run_vibe_with_config, WorkspaceYaml};
+use tempfile::TempDir;
+
+/// A workspace with one repository that has no remote, whose event hooks
+/// append `VIBE_EVENT` and the JSON payload to `events.log`
+fn workspace() -> (TempDir, std::path::PathBuf) {
+    let home = TempDir::new().unwrap();
+    let root = home.path().join("workspace");
+    git_init(&root.join("api"));
+
+    let log = home.path().join("events.log");
+    let hook = format!(
+        "echo \"$VIBE_EVENT $VIBE_EVENT_REPOSITORIES\" >> '{}'; cat >> '{}'",
+        log.display(),
+        log.display()
+    );
+    let config = home.path().join("config.yaml");
+    WorkspaceYaml::new(&root)
+        .repo("api", "api")
+        .extra(&format!(
+            "hooks:\n  event_timeout_secs: 10\n  events:\n    sync_completed: {}\n",
+            serde_json::to_string(&hook).unwrap()
+        ))
+        .write(&config);
+    (home, config)
+}
+
+#[test]
+fn test_hooks_test_fires_a_sample_event() {
+    let (home, config) = workspace();
+
+    let output = run_vibe_with_config(home.path(), &config, &["hooks", "test", "sync_completed"]);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let log = std::fs::read_to_string(home.path().join("events.log")).unwrap();
+    assert!(log.starts_with("sync_completed 3\n"), "{log}");
+    assert!(log.contains("\"event\":\"sync_completed\""));
+
+    // No command is configured for this event
+    let output = run_vibe_with_config(home.path(), &config, &["hooks", "test", "repo_cloned"]);
+    assert_eq!(output.status.code(), Some(3));
+}
+
+#[test]
+fn test_sync_fires_sync_completed_even_when_a_repo_fails() {
+    let (home, config) = workspace();
+
+    // The repository has no remote, so fetching it fails
+    run_vibe_with_config(home.path(), &config, &["git", "sync"]);
+
+    let log = std::fs::read_to_string(home.path().join("events.log")).unwrap();
+    assert!(log.starts_with("sync_completed 1\n"), "{log}");
+    assert!(log.contains("\"failed\":1"));
+}
@@ -0,0 +1,188 @@
+//! Integration tests for the documented process exit codes
+
+mod common;
+
+use common::{commit_file, git, git_init, run_vibe, WorkspaceYaml};
+use tempfile::TempDir;
+
+/// A workspace with a single committed repository named `app`
+fn workspace_with_repo() -> (TempDir, std::path::PathBuf) {
+    let home = TempDir::new().unwrap();
+    let root = home.path().join("workspace");
+    let repo = root.join("app");
+    git_init(&repo);
+    commit_file(&repo, "README.md", "app");
+
+    let config = home.path().join("config.yaml");
+    WorkspaceYaml::new(&root).repo("app", "app").write(&config);
+
+    (home, config)
+}
+
+#[test]
+fn test_success_exits_zero() {
+    let home = TempDir::new().unwrap();
+    let output = run_vibe(home.path(), &["git", "status"]);
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn test_usage_errors_exit_two() {
+    let home = TempDir::new().unwrap();
+
+    let output = run_vibe(home.path(), &["--no-such-flag"]);
+    assert_eq!(output.status.code(), Some(2));
+
+    let output = run_vibe(home.path(), &["git", "scan", "--restore", "--clean"]);
+    assert_eq!(output.status.code(), Some(2));
+
+    let config = home.path().join("broken.yaml");
+    std::fs::write(&config, "workspace: [").unwrap();
+    let output = run_vibe(
+        home.path(),
+        &["--config", config.to_str().unwrap(), "git", "status"],
+    );
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn test_missing_repository_exits_three() {
+    let home = TempDir::new().unwrap();
+    let output = run_vibe(home.path(), &["open", "does-not-exist"]);
+    assert_eq!(output.status.code(), Some(3));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("not found"));
+}
+
+#[test]
+fn test_open_group() {
+    let (home, config) = workspace_with_repo();
+    let yaml = std::fs::read_to_string(&config).unwrap().replace(
+        "groups: []",
+        "groups:\n- name: team\n  repos: [app]\n  apps: {}",
+    );
+    std::fs::write(&config, yaml).unwrap();
+    let config = config.to_str().unwrap();
+
+    let output = run_vibe(
+        home.path(),
+        &[
+            "--config", config, "open", "--group", "nope", "--app", "vscode",
+        ],
+    );
+    assert_eq!(output.status.code(), Some(3));
+
+    // Repositories without the app configured are skipped, not failed
+    let output = run_vibe(
+        home.path(),
+        &[
+            "--json", "--config", config, "open", "--group", "team", "--app", "vscode",
+        ],
+    );
+    assert_eq!(output.status.code(), Some(0));
+    let result: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(result["data"][0]["name"], "app");
+    assert_eq!(result["data"][0]["outcome"], "not_configured");
+
+    let output = run_vibe(home.path(), &["open", "--group", "team"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn test_empty_launch_slot_exits_three() {
+    let (home, config) = workspace_with_repo();
+    let config = config.to_str().unwrap();
+
+    let output = run_vibe(home.path(), &["--config", config, "launch", "3"]);
+    assert_eq!(output.status.code(), Some(3));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("slot '3' not found"));
+
+    let output = run_vibe(home.path(), &["--config", config, "launch", "--last"]);
+    assert_eq!(output.status.code(), Some(3));
+
+    let output = run_vibe(home.path(), &["launch", "10"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn test_partial_failure_exits_four() {
+    let (home, config) = workspace_with_repo();
+    let config = config.to_str().unwrap();
+
+    let output = run_vibe(home.path(), &["--config", config, "git", "exec", "status"]);
+    assert_eq!(output.status.code(), Some(0));
+
+    let output = run_vibe(
+        home.path(),
+        &["--config", config, "git", "exec", "checkout no-such-branch"],
+    );
+    assert_eq!(output.status.code(), Some(4));
+}
+
+#[test]
+fn test_json_mode_keeps_exit_code() {
+    let home = TempDir::new().unwrap();
+    let output = run_vibe(home.path(), &["--json", "open", "does-not-exist"]);
+    assert_eq!(output.status.code(), Some(3));
+
+    let result: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(result["ok"], false);
+}
+
+#[test]
+fn test_doctor_fails_on_broken_config() {
+    let (home, config) = workspace_with_repo();
+
+    let output = run_vibe(
+        home.path(),
+        &[
+            "--config",
+            config.to_str().unwrap(),
+            "doctor",
+            "--format",
+            "json",
+        ],
+    );
+    assert_eq!(output.status.code(), Some(0));
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["checks"][0]["id"], "git");
+
+    std::fs::write(&config, "workspace: [").unwrap();
+    let output = run_vibe(
+        home.path(),
+        &[
+            "--config",
+            config.to_str().unwrap(),
+            "doctor",
+            "--format",
+            "json",
+        ],
+    );
+    assert_eq!(output.status.code(), Some(1));
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let config_check = report["checks"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|check| check["id"] == "config")
+        .unwrap();
+    assert_eq!(config_check["status"], "fail");
+}
+
+#[test]
+fn test_double_quiet_keeps_per_repository_errors() {
+    let (home, config) = workspace_with_repo();
+    let repo = home.path().join("workspace/app");
+    let missing = home.path().join("missing.git");
+    git(
+        &repo,
+        &["remote", "add", "origin", missing.to_str().unwrap()],
+    );
+
+    let output = run_vibe(
+        home.path(),
+        &["--config", config.to_str().unwrap(), "-qq", "git", "sync"],
+    );
+    assert_eq!(output.status.code(), Some(4));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("  Error: "), "{stderr}");
+}
@@ -0,0 +1,229 @@
+//! Conformance between the subprocess and native git backends
+//!
+//! Both backends run against the same fixture repositories and must report
+//! identical `GitStatus` and `WorktreeInfo` results.
+
+#![cfg(feature = "native-git")]
+
+mod common;
+
+use common::{commit_file, git};
+use std::path::Path;
+use std::sync::Arc;
+use tempfile::TempDir;
+use vibe_workspace::git::backend::{GitBackend, NativeBackend, SubprocessBackend};
+use vibe_workspace::workspace::operations::read_git_status;
+use vibe_workspace::worktree::operations::WorktreeOperations;
+use vibe_workspace::worktree::status::check_worktree_status_with_backend;
+use vibe_workspace::worktree::WorktreeConfig;
+
+/// A clone three commits ahead of and one behind its upstream, with
+/// every kind of working tree change
+fn fixture_repos(root: &Path) -> Vec<std::path::PathBuf> {
+    let origin = root.join("origin.git");
+    std::fs::create_dir_all(&origin).unwrap();
+    git(&origin, &["init", "--quiet", "--bare"]);
+
+    let seed = root.join("seed");
+    git(
+        root,
+        &["clone", "--quiet", origin.to_str().unwrap(), "seed"],
+    );
+    commit_file(&seed, "README.md", "seed\n");
+    git(&seed, &["push", "--quiet", "origin", "HEAD:main"]);
+
+    let dirty = root.join("dirty");
+    git(
+        root,
+        &["clone", "--quiet", origin.to_str().unwrap(), "dirty"],
+    );
+    commit_file(&seed, "upstream.txt", "upstream\n");
+    git(&seed, &["push", "--quiet", "origin", "HEAD:main"]);
+    git(&dirty, &["fetch", "--quiet"]);
+
+    commit_file(&dirty, "tracked.txt", "one\n");
+    commit_file(&dirty, "renamed.txt", "a file that gets renamed\n");
+    commit_file(&dirty, "deleted.txt", "gone soon\n");
+    std::fs::write(dirty.join("tracked.txt"), "two\n").unwrap();
+    git(&dirty, &["add", "tracked.txt"]);
+    std::fs::write(dirty.join("tracked.txt"), "three\n").unwrap();
+    std::fs::write(dirty.join("README.md"), "edited\n").unwrap();
+    std::fs::write(dirty.join("added.txt"), "new\n").unwrap();
+    git(&dirty, &["add", "added.txt"]);
+    git(&dirty, &["mv", "renamed.txt", "moved.txt"]);
+    std::fs::remove_file(dirty.join("deleted.txt")).unwrap();
+    std::fs::write(dirty.join("untracked.txt"), "loose\n").unwrap();
+    std::fs::create_dir_all(dirty.join("scratch")).unwrap();
+    std::fs::write(dirty.join("scratch/notes.txt"), "loose\n").unwrap();
+
+    let detached = root.join("detached");
+    git(
+        root,
+        &["clone", "--quiet", origin.to_str().unwrap(), "detached"],
+    );
+    git(&detached, &["checkout", "--quiet", "--detach", "HEAD"]);
+
+    let empty = root.join("empty");
+    std::fs::create_dir_all(&empty).unwrap();
+    git(&empty, &["init", "--quiet"]);
+
+    vec![dirty, detached, empty]
+}
+
+fn backends() -> (Arc<dyn GitBackend>, Arc<dyn GitBackend>) {
+    (Arc::new(SubprocessBackend), Arc::new(NativeBackend))
+}
+
+#[tokio::test]
+async fn test_backends_report_identical_git_status() {
+    let root = TempDir::new().unwrap();
+    let (subprocess, native) = backends();
+
+    for repo in fixture_repos(root.path()) {
+        let expected = read_git_status(&*subprocess, &repo, None).await.unwrap();
+        let actual = read_git_status(&*native, &repo, None).await.unwrap();
+        assert_eq!(actual, expected, "status differs for {}", repo.display());
+    }
+
+    let dirty = read_git_status(&*native, &root.path().join("dirty"), None)
+        .await
+        .unwrap();
+    assert_eq!((dirty.ahead, dirty.behind), (3, 1));
+    assert_eq!(dirty.untracked, 2);
+    assert!(dirty.staged > 0 && dirty.unstaged > 0);
+    assert_eq!(dirty.compare_ref, None);
+}
+
+#[tokio::test]
+async fn test_backends_count_against_a_compare_ref() {
+    let root = TempDir::new().unwrap();
+    let (subprocess, native) = backends();
+    let dirty = &fixture_repos(root.path())[0];
+    assert!(dirty.ends_with("dirty"));
+
+    let expected = read_git_status(&*subprocess, dirty, Some("HEAD~2"))
+        .await
+        .unwrap();
+    let actual = read_git_status(&*native, dirty, Some("HEAD~2"))
+        .await
+        .unwrap();
+    assert_eq!(actual, expected);
+    assert_eq!((actual.ahead, actual.behind), (2, 0));
+    assert_eq!(actual.compare_ref.as_deref(), Some("HEAD~2"));
+}
+
+#[tokio::test]
+async fn test_backends_report_identical_queries() {
+    let root = TempDir::new().unwrap();
+    let (subprocess, native) = backends();
+    let repos = fixture_repos(root.path());
+
+    for repo in &repos {
+        assert_eq!(
+            native.status(repo).await.unwrap(),
+            subprocess.status(repo).await.unwrap(),
+            "status entries differ for {}",
+            repo.display()
+        );
+        assert_eq!(
+            native.head_branch(repo).await.unwrap(),
+            subprocess.head_branch(repo).await.unwrap()
+        );
+        assert_eq!(
+            native.upstream(repo).await.unwrap(),
+            subprocess.upstream(repo).await.unwrap()
+        );
+        assert_eq!(
+            native.remote_url(repo).await.unwrap(),
+            subprocess.remote_url(repo).await.unwrap()
+        );
+    }
+
+    let dirty = &repos[0];
+    assert_eq!(
+        native
+            .merge_base(dirty, "origin/main", "HEAD")
+            .await
+            .unwrap(),
+        subprocess
+            .merge_base(dirty, "origin/main", "HEAD")
+            .await
+            .unwrap()
+    );
+    assert_eq!(
+        native
+            .merge_base(dirty, "no-such-ref", "HEAD")
+            .await
+            .unwrap(),
+        None
+    );
+    assert_eq!(
+        subprocess
+            .ahead_behind(dirty, "no-such-ref", "HEAD")
+            .await
+            .unwrap(),
+        None
+    );
+}
+
+#[tokio::test]
+async fn test_backends_report_identical_worktrees() {
+    let root = TempDir::new().unwrap();
+    let (subprocess, native) = backends();
+    let repo = fixture_repos(root.path()).remove(0);
+
+    git(
+        &repo,
+        &[
+            "worktree",
+            "add",
+            "--quiet",
+            "-b",
+            "vibe-ws/feature",
+            "../wt-feature",
+        ],
+    );
+    git(
+        &repo,
+        &["worktree", "add", "--quiet", "--detach", "../wt-detached"],
+    );
+    std::fs::write(root.path().join("wt-feature/new.txt"), "wip\n").unwrap();
+
+    for start in [repo.clone(), root.path().join("wt-feature")] {
+        let config = WorktreeConfig::default();
+        let expected = WorktreeOperations::new(start.clone(), config.clone())
+            .with_git_backend(subprocess.clone())
+            .list_worktrees()
+            .await
+            .unwrap();
+        let actual = WorktreeOperations::new(start.clone(), config)
+            .with_git_backend(native.clone())
+            .list_worktrees()
+            .await
+            .unwrap();
+
+        assert_eq!(actual.len(), 3);
+        assert_eq!(actual.len(), expected.len());
+        for (actual, expected) in actual.iter().zip(&expected) {
+            assert_eq!(actual.path, expected.path);
+            assert_eq!(actual.branch, expected.branch);
+            assert_eq!(actual.head, expected.head);
+            assert_eq!(actual.is_detached, expected.is_detached);
+            assert_eq!(actual.task_id, expected.task_id);
+
+            let expected_status =
+                check_worktree_status_with_backend(&*subprocess, &expected.path, None)
+                    .await
+                    .unwrap();
+            let actual_status = check_worktree_status_with_backend(&*native, &actual.path, None)
+                .await
+                .unwrap();
+            assert_eq!(
+                serde_json::to_value(&actual_status).unwrap(),
+                serde_json::to_value(&expected_status).unwrap(),
+                "worktree status differs for {}",
+                actual.path.display()
+            );
+        }
+    }
+}
@@ -0,0 +1,64 @@
+//! Integration tests for `vibe git hooks`
+
+mod common;
+
+use common::{git_init, run_vibe, stdout_json, WorkspaceYaml};
+use std::path::Path;
+use tempfile::TempDir;
+
+fn status_json(home: &Path, config: &str) -> serde_json::Value {
+    stdout_json(&run_vibe(
+        home,
+        &[
+            "--config", config, "git", "hooks", "status", "--format", "json",
+        ],
+    ))
+}
+
+#[test]
+fn test_hooks_sync_status_and_remove() {
+    let home = TempDir::new().unwrap();
+    let root = home.path().join("workspace");
+    let repo = root.join("app");
+    git_init(&repo);
+    let existing = repo.join(".git/hooks/pre-commit");
+    std::fs::write(&existing, "#!/bin/sh\necho local\n").unwrap();
+
+    let script = home.path().join("pre-commit.sh");
+    std::fs::write(&script, "#!/bin/sh\necho managed\n").unwrap();
+
+    let config = home.path().join("config.yaml");
+    WorkspaceYaml::new(&root)
+        .repo("app", "app")
+        .extra(&format!("hooks:\n  pre-commit: {}\n", script.display()))
+        .write(&config);
+    let config = config.to_str().unwrap();
+
+    let status = status_json(home.path(), config);
+    assert_eq!(status[0]["hooks"][0]["state"], "not_installed");
+
+    let output = run_vibe(home.path(), &["--config", config, "git", "hooks", "sync"]);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let status = status_json(home.path(), config);
+    assert_eq!(status[0]["hooks"][0]["state"], "in_sync");
+    assert_eq!(status[0]["hooks"][0]["chained"], true);
+
+    // Editing the managed source shows up as drift
+    std::fs::write(&script, "#!/bin/sh\necho updated\n").unwrap();
+    let status = status_json(home.path(), config);
+    assert_eq!(status[0]["hooks"][0]["state"], "drifted");
+
+    let output = run_vibe(home.path(), &["--config", config, "config", "validate"]);
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Git hooks out of sync in app"));
+
+    let output = run_vibe(home.path(), &["--config", config, "git", "hooks", "remove"]);
+    assert!(output.status.success());
+    assert_eq!(
+        std::fs::read_to_string(&existing).unwrap(),
+        "#!/bin/sh\necho local\n"
+    );
+}
@@ -0,0 +1,89 @@
+//! Default apps and templates set on repository groups
+
+mod common;
+
+use common::{run_vibe, WorkspaceYaml};
+use tempfile::TempDir;
+
+/// A workspace where `api` is in `infra` and `frontend`, which default to
+/// different apps
+fn workspace(infra_app: &str) -> (TempDir, String) {
+    let home = TempDir::new().unwrap();
+    let root = home.path().join("workspace");
+    std::fs::create_dir_all(root.join("api")).unwrap();
+
+    let config = home.path().join("config.yaml");
+    WorkspaceYaml::new(&root)
+        .repo("api", "api")
+        .groups(&format!(
+            "- name: infra\n  repos: [api]\n  apps: {{}}\n  default_app: {infra_app}\n\
+             - name: frontend\n  repos: [api]\n  apps: {{}}\n  default_app: cursor\n  \
+             default_template: react\n"
+        ))
+        .write(&config);
+
+    (home, config.to_str().unwrap().to_string())
+}
+
+#[test]
+fn test_config_show_renders_group_defaults() {
+    let (home, config) = workspace("warp");
+    let output = run_vibe(
+        home.path(),
+        &[
+            "--config",
+            &config,
+            "config",
+            "show",
+            "--section",
+            "groups",
+            "--format",
+            "pretty",
+        ],
+    );
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Default app: warp"));
+    assert!(stdout.contains("Default app: cursor (template: react)"));
+
+    let output = run_vibe(
+        home.path(),
+        &[
+            "--config",
+            &config,
+            "config",
+            "show",
+            "--section",
+            "groups",
+            "--format",
+            "json",
+        ],
+    );
+    let groups: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(groups[1]["default_template"], "react");
+    assert!(groups[0].get("default_template").is_none());
+}
+
+#[test]
+fn test_validate_rejects_unknown_default_app() {
+    let (home, config) = workspace("terminal");
+    let output = run_vibe(home.path(), &["--config", &config, "config", "validate"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout)
+        .contains("Group 'infra' has unknown default app: terminal"));
+}
+
+#[test]
+fn test_clone_and_create_require_existing_group() {
+    let (home, config) = workspace("warp");
+    for args in [
+        ["clone", "owner/repo", "--group", "nope"],
+        ["create", "repo", "--group", "nope"],
+    ] {
+        let output = run_vibe(
+            home.path(),
+            &[&["--config", config.as_str()][..], &args[..]].concat(),
+        );
+        assert_eq!(output.status.code(), Some(3));
+    }
+}
@@ -0,0 +1,64 @@
+//! `vibe guide` outside a terminal prints the getting-started text with the
+//! interactive guide's progress, and `--reset` forgets that progress
+
+mod common;
+
+use common::WorkspaceYaml;
+use std::path::Path;
+use std::process::Output;
+use tempfile::TempDir;
+
+/// Run vibe with its config and state in `home`
+fn run_vibe(home: &Path, args: &[&str]) -> Output {
+    common::vibe(home)
+        .arg("--config")
+        .arg(home.join("config.yaml"))
+        .args(args)
+        .env("VIBE_HOME", home.join("vibe"))
+        .output()
+        .expect("Failed to execute vibe")
+}
+
+fn setup() -> TempDir {
+    let home = TempDir::new().unwrap();
+    std::fs::create_dir_all(home.path().join("workspace")).unwrap();
+    std::fs::create_dir_all(home.path().join("vibe")).unwrap();
+    WorkspaceYaml::new(&home.path().join("workspace")).write(&home.path().join("config.yaml"));
+    home
+}
+
+#[test]
+fn test_guide_reports_and_resets_progress() {
+    let home = setup();
+    let state = home.path().join("vibe").join("state.json");
+    std::fs::write(
+        &state,
+        r#"{"version": 1, "first_run": null, "guide_progress": ["scan", "clone"]}"#,
+    )
+    .unwrap();
+
+    let output = run_vibe(home.path(), &["guide"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Getting Started"), "{stdout}");
+    assert!(stdout.contains("2 of 6 steps of the interactive guide done"));
+
+    let output = run_vibe(home.path(), &["guide", "--reset"]);
+    assert!(output.status.success());
+    let saved: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&state).unwrap()).unwrap();
+    assert!(saved.get("guide_progress").is_none());
+
+    let output = run_vibe(home.path(), &["guide"]);
+    assert!(String::from_utf8_lossy(&output.stdout).contains("0 of 6 steps"));
+}
+
+#[test]
+fn test_guide_rejects_unknown_topics() {
+    let home = setup();
+    assert!(run_vibe(home.path(), &["guide", "--topic", "worktrees"])
+        .status
+        .success());
+    let output = run_vibe(home.path(), &["guide", "--topic", "plugins"]);
+    assert_eq!(output.status.code(), Some(2));
+}
@@ -42,8 +42,22 @@ impl Assert {
         Ok(())
     }
     
-    /// Assert that output contains specific text
+    /// Assert that output is free of ANSI escapes. The runner sets
+    /// `VIBE_DETERMINISTIC=1`, so any escape means vibe ignored it.
+    pub fn output_is_plain(output: &str) -> Result<()> {
+        if output.contains('\x1b') {
+            anyhow::bail!(
+                "Output contains ANSI escapes although VIBE_DETERMINISTIC is set:\n{}",
+                output
+            );
+        }
+        Ok(())
+    }
+    
+    /// Assert that output contains specific text, compared as-is since
+    /// deterministic output has no color codes splitting it up
     pub fn output_contains(output: &str, expected: &str) -> Result<()> {
+        Self::output_is_plain(output)?;
         if !output.contains(expected) {
             anyhow::bail!(
                 "Output does not contain expected text: '{}'\nActual output:\n{}",
@@ -56,6 +70,7 @@ impl Assert {
     
     /// Assert that output matches a pattern
     pub fn output_matches(output: &str, pattern: &str) -> Result<()> {
+        Self::output_is_plain(output)?;
         let re = regex::Regex::new(pattern)?;
         if !re.is_match(output) {
             anyhow::bail!(
@@ -66,6 +81,26 @@ impl Assert {
         }
         Ok(())
     }
+    
+    /// Assert that `expected` appear in output in the given order. Listings
+    /// are sorted in deterministic mode, so e.g. a repository's apps can be
+    /// checked in alphabetical order.
+    pub fn output_in_order(output: &str, expected: &[&str]) -> Result<()> {
+        Self::output_is_plain(output)?;
+        let mut rest = output;
+        for item in expected {
+            match rest.find(item) {
+                Some(index) => rest = &rest[index + item.len()..],
+                None => anyhow::bail!(
+                    "Output does not list {:?} in order (missing or out of place: '{}')\nActual output:\n{}",
+                    expected,
+                    item,
+                    output
+                ),
+            }
+        }
+        Ok(())
+    }
 }
 
 /// Config-specific assertions
@@ -140,7 +175,11 @@ impl ConfigAssert {
                 "App '{}' not configured for repository '{}'. Configured apps: {:?}",
                 app,
                 repo_name,
-                repo.apps.keys().collect::<Vec<_>>()
+                {
+                    let mut apps: Vec<_> = repo.apps.keys().collect();
+                    apps.sort();
+                    apps
+                }
             );
         }
         Ok(())
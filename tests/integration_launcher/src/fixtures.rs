@@ -66,6 +66,9 @@ impl TestEnvironment {
         // Disable any real app integrations during tests
         env.insert("VIBE_TEST_MODE".to_string(), "1".to_string());
         
+        // Plain, sorted, sequential output with placeholder times
+        env.insert("VIBE_DETERMINISTIC".to_string(), "1".to_string());
+        
         env
     }
     
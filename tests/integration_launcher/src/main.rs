@@ -131,15 +131,19 @@ fn find_vibe_binary(cli: &Cli) -> Result<PathBuf> {
     
     // Find in target directory
     let workspace_root = find_workspace_root()?;
-    let binary_name = if cfg!(windows) { "vibe.exe" } else { "vibe" };
+    let binary_name = format!("vibe{}", env::consts::EXE_SUFFIX);
     
-    let target_dir = workspace_root.join("target");
+    // Respect a relocated target directory (common on Windows CI runners)
+    let target_dir = match env::var_os("CARGO_TARGET_DIR") {
+        Some(dir) => workspace_root.join(dir),
+        None => workspace_root.join("target"),
+    };
     let possible_paths = if cli.release {
-        vec![target_dir.join("release").join(binary_name)]
+        vec![target_dir.join("release").join(&binary_name)]
     } else {
         vec![
-            target_dir.join("debug").join(binary_name),
-            target_dir.join("release").join(binary_name),
+            target_dir.join("debug").join(&binary_name),
+            target_dir.join("release").join(&binary_name),
         ]
     };
     
@@ -0,0 +1,59 @@
+//! Integration tests for `vibe git files sync`
+
+mod common;
+
+use common::{run_vibe, WorkspaceYaml};
+use tempfile::TempDir;
+
+#[test]
+fn test_files_sync_dry_run_then_apply() {
+    let home = TempDir::new().unwrap();
+    let root = home.path().join("workspace");
+    let repo = root.join("app");
+    std::fs::create_dir_all(&repo).unwrap();
+    std::fs::write(repo.join(".gitignore"), "target/\n").unwrap();
+
+    let managed = home.path().join(".local/share/vibe/managed");
+    std::fs::create_dir_all(&managed).unwrap();
+    std::fs::write(managed.join("gitignore"), ".env\n").unwrap();
+
+    let config = home.path().join("config.yaml");
+    WorkspaceYaml::new(&root)
+        .repo("app", "app")
+        .extra(
+            "managed_files:\n- source: gitignore\n  destination: .gitignore\n  strategy: append-block\n",
+        )
+        .write(&config);
+    let config = config.to_str().unwrap();
+
+    let output = run_vibe(
+        home.path(),
+        &["--config", config, "git", "files", "sync", "--dry-run"],
+    );
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(String::from_utf8_lossy(&output.stdout).contains("+ .env"));
+    assert_eq!(
+        std::fs::read_to_string(repo.join(".gitignore")).unwrap(),
+        "target/\n"
+    );
+
+    let output = run_vibe(home.path(), &["--config", config, "git", "files", "sync"]);
+    assert!(output.status.success());
+    let synced = std::fs::read_to_string(repo.join(".gitignore")).unwrap();
+    assert!(synced.contains(".env\n# <<< vibe managed: gitignore <<<"));
+
+    // Local edits inside the block are a conflict and exit with status 4
+    std::fs::write(
+        repo.join(".gitignore"),
+        synced.replace(".env", ".env.local"),
+    )
+    .unwrap();
+    std::fs::write(managed.join("gitignore"), ".env\n*.log\n").unwrap();
+    let output = run_vibe(home.path(), &["--config", config, "git", "files", "sync"]);
+    assert_eq!(output.status.code(), Some(4));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("conflict"));
+}
@@ -0,0 +1,72 @@
+//! `vibe mcp validate`: checking every MCP tool against a workspace, with
+//! mutating tools confined to a sandbox
+
+mod common;
+
+use common::{git_init, json_data, run_vibe_in, WorkspaceYaml};
+use std::path::Path;
+use tempfile::TempDir;
+
+/// A workspace with `api` checked out
+fn workspace() -> (TempDir, String) {
+    let home = TempDir::new().unwrap();
+    let root = home.path().join("workspace");
+    git_init(&root.join("api"));
+
+    let config = home.path().join("config.yaml");
+    WorkspaceYaml::new(&root).repo("api", "api").write(&config);
+
+    (home, config.to_str().unwrap().to_string())
+}
+
+fn validate(home: &Path, config: &str, extra: &[&str]) -> serde_json::Value {
+    let mut args = vec!["--config", config, "--json", "mcp", "validate"];
+    args.extend_from_slice(extra);
+    json_data(&run_vibe_in(home, &home.join("workspace/api"), &args))
+}
+
+fn check<'a>(report: &'a serde_json::Value, tool: &str) -> &'a serde_json::Value {
+    report["tools"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|check| check["tool"] == tool)
+        .unwrap_or_else(|| panic!("{tool} missing from the report"))
+}
+
+fn outcome<'a>(report: &'a serde_json::Value, tool: &str) -> &'a serde_json::Value {
+    &check(report, tool)["outcome"]
+}
+
+#[test]
+fn test_validate_calls_only_read_only_tools() {
+    let (home, config) = workspace();
+    let before = std::fs::read_to_string(&config).unwrap();
+
+    let report = validate(home.path(), &config, &[]);
+    assert_eq!(report["failed"], 0);
+    assert_eq!(outcome(&report, "describe_repo"), "passed");
+    assert_eq!(outcome(&report, "list_worktrees"), "passed");
+    assert_eq!(outcome(&report, "reset_git_config"), "skipped");
+    assert_eq!(outcome(&report, "launch_repo"), "skipped");
+    assert!(check(&report, "describe_repo")["duration_ms"].is_u64());
+    assert!(check(&report, "launch_repo")["duration_ms"].is_null());
+    assert_eq!(std::fs::read_to_string(&config).unwrap(), before);
+}
+
+#[test]
+fn test_deep_validation_stays_in_the_sandbox() {
+    let (home, config) = workspace();
+    let before = std::fs::read_to_string(&config).unwrap();
+
+    let report = validate(home.path(), &config, &["--deep"]);
+    assert_eq!(report["failed"], 0, "{report:#}");
+    for tool in ["reset_git_config", "restore_config", "create_app_template"] {
+        assert_eq!(outcome(&report, tool), "passed", "{tool}");
+    }
+    assert_eq!(outcome(&report, "launch_repo"), "skipped");
+
+    assert_eq!(std::fs::read_to_string(&config).unwrap(), before);
+    assert!(!home.path().join(".local/share/vibe/backups").exists());
+    assert!(!home.path().join("workspace/created").exists());
+}
@@ -0,0 +1,57 @@
+//! Commands against a workspace root that is missing, as when its network
+//! volume isn't mounted
+
+mod common;
+
+use common::{run_vibe_with_config, WorkspaceYaml};
+use tempfile::TempDir;
+
+/// Two repositories configured under a root that doesn't exist
+fn offline_workspace() -> (TempDir, std::path::PathBuf) {
+    let home = TempDir::new().unwrap();
+    let config = home.path().join("config.yaml");
+    WorkspaceYaml::new(&home.path().join("nas/workspace"))
+        .repo("api", "api")
+        .repo("web", "web")
+        .write(&config);
+    (home, config)
+}
+
+#[test]
+fn test_status_warns_once_and_uses_the_cache() {
+    let (home, config) = offline_workspace();
+
+    let output = run_vibe_with_config(home.path(), &config, &["git", "status"]);
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(stderr.matches("is unavailable").count(), 1, "{stderr}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.matches("offline (no cached status)").count(), 2);
+}
+
+#[test]
+fn test_double_quiet_silences_the_warning() {
+    let (home, config) = offline_workspace();
+
+    let output = run_vibe_with_config(home.path(), &config, &["-qq", "git", "status"]);
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stderr), "");
+
+    let output = run_vibe_with_config(home.path(), &config, &["-q", "git", "status"]);
+    assert!(String::from_utf8_lossy(&output.stderr).contains("is unavailable"));
+
+    // The error that ends a command still shows
+    let output = run_vibe_with_config(home.path(), &config, &["-qq", "git", "sync"]);
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--force-online"));
+}
+
+#[test]
+fn test_sync_and_exec_are_refused() {
+    let (home, config) = offline_workspace();
+
+    for args in [&["git", "sync"][..], &["git", "exec", "status"][..]] {
+        let output = run_vibe_with_config(home.path(), &config, args);
+        assert!(!output.status.success(), "{args:?} succeeded");
+        assert!(String::from_utf8_lossy(&output.stderr).contains("--force-online"));
+    }
+}
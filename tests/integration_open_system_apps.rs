@@ -0,0 +1,103 @@
+//! Opening repositories in the file manager and browser pseudo-apps, with a
+//! stub `xdg-open` that records what it was asked to open
+#![cfg(target_os = "linux")]
+
+mod common;
+
+use common::{git, git_init, run_vibe_with_stubs, WorkspaceYaml};
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use tempfile::TempDir;
+
+/// A workspace with `api`, cloned over ssh, and `scratch`, which has no remote
+fn workspace() -> (TempDir, String) {
+    let home = TempDir::new().unwrap();
+    let bin = home.path().join("bin");
+    std::fs::create_dir_all(&bin).unwrap();
+    let stub = bin.join("xdg-open");
+    std::fs::write(
+        &stub,
+        "#!/bin/sh\necho \"$1\" >> \"$(dirname \"$0\")/opened.log\"\n",
+    )
+    .unwrap();
+    std::fs::set_permissions(&stub, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    let root = home.path().join("workspace");
+    for (name, remote) in [
+        ("api", Some("git@github.com:acme/api.git")),
+        ("scratch", None),
+    ] {
+        let dir = root.join(name);
+        git_init(&dir);
+        if let Some(remote) = remote {
+            git(&dir, &["remote", "add", "origin", remote]);
+        }
+    }
+
+    let config = home.path().join("config.yaml");
+    WorkspaceYaml::new(&root)
+        .repo("api", "api")
+        .repo("scratch", "scratch")
+        .write(&config);
+
+    (home, config.to_str().unwrap().to_string())
+}
+
+/// What the stub was asked to open, waiting briefly since it runs detached
+fn opened(home: &Path, count: usize) -> Vec<String> {
+    let log = home.join("bin/opened.log");
+    for _ in 0..50 {
+        let lines: Vec<String> = std::fs::read_to_string(&log)
+            .unwrap_or_default()
+            .lines()
+            .map(str::to_string)
+            .collect();
+        if lines.len() >= count {
+            return lines;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(100));
+    }
+    panic!("xdg-open was not called {count} times");
+}
+
+#[test]
+fn test_open_in_browser_and_file_manager() {
+    let (home, config) = workspace();
+
+    for app in ["browser", "files"] {
+        let output = run_vibe_with_stubs(
+            home.path(),
+            &[
+                "--config",
+                &config,
+                "open",
+                "api",
+                "--app",
+                app,
+                "--no-fetch",
+            ],
+        );
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    // The stub runs detached, so the two calls may land in either order
+    let opened = opened(home.path(), 2);
+    assert!(opened.contains(&"https://github.com/acme/api".to_string()));
+    assert!(opened.iter().any(|path| path.ends_with("workspace/api")));
+}
+
+#[test]
+fn test_browser_needs_a_remote() {
+    let (home, config) = workspace();
+    let output = run_vibe_with_stubs(
+        home.path(),
+        &["--config", &config, "open", "scratch", "--app", "browser"],
+    );
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr)
+        .contains("Repository 'scratch' has no remote to open in the browser"));
+}
@@ -0,0 +1,116 @@
+//! Repositories configured with an absolute path outside the workspace root
+
+mod common;
+
+use common::{git_init, run_vibe, stdout_json, WorkspaceYaml};
+use std::path::PathBuf;
+use tempfile::TempDir;
+
+/// A workspace with `inside` under the root and `mono` on "another volume",
+/// plus `gone`, configured outside the root but not on disk
+fn workspace() -> (TempDir, String, PathBuf) {
+    let home = TempDir::new().unwrap();
+    let root = home.path().join("workspace");
+    git_init(&root.join("inside"));
+    let mono = home.path().join("volume").join("mono");
+    git_init(&mono);
+    std::fs::write(mono.join("dirty.txt"), "wip").unwrap();
+    let gone = home.path().join("volume").join("gone");
+
+    let config = home.path().join("config.yaml");
+    WorkspaceYaml::new(&root)
+        .repo("inside", "inside")
+        .repo("mono", mono.display())
+        .repo_with(
+            "gone",
+            gone.display(),
+            "  url: https://example.com/acme/gone.git\n",
+        )
+        .write(&config);
+
+    let config = config.to_str().unwrap().to_string();
+    (home, config, mono)
+}
+
+#[test]
+fn test_status_reads_repository_outside_root() {
+    let (home, config, mono) = workspace();
+    let output = run_vibe(
+        home.path(),
+        &["--config", &config, "git", "status", "--format", "json"],
+    );
+    let statuses = stdout_json(&output);
+    let mono_status = statuses
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|status| status["path"] == mono.to_str().unwrap())
+        .expect("status for the repository outside the root");
+    assert_eq!(mono_status["untracked"], 1);
+}
+
+#[test]
+fn test_open_and_exec_use_absolute_path() {
+    let (home, config, mono) = workspace();
+
+    let output = run_vibe(
+        home.path(),
+        &["--config", &config, "open", "mono", "--print-path"],
+    );
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        format!("{}\n", mono.display())
+    );
+
+    let output = run_vibe(
+        home.path(),
+        &[
+            "--config",
+            &config,
+            "git",
+            "exec",
+            "status --porcelain",
+            "--repos",
+            "mono",
+        ],
+    );
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(String::from_utf8_lossy(&output.stdout).contains("dirty.txt"));
+}
+
+#[test]
+fn test_scan_flags_and_keeps_repositories_outside_root() {
+    let (home, config, _) = workspace();
+
+    for action in ["--clean", "--restore"] {
+        let output = run_vibe(
+            home.path(),
+            &["--config", &config, "--json", "git", "scan", action],
+        );
+        let report = stdout_json(&output);
+        let repos = report["data"]["repositories"].as_array().unwrap();
+        let find = |name: &str| {
+            repos
+                .iter()
+                .find(|repo| repo["name"] == name)
+                .unwrap_or_else(|| panic!("{name} in scan report"))
+        };
+        assert_eq!(find("mono")["status"], "tracked");
+        assert_eq!(find("mono")["outside_root"], true);
+        assert_eq!(find("gone")["status"], "missing");
+        assert_eq!(find("inside")["outside_root"], false);
+        assert_eq!(
+            report["data"]["sync"]["outside_root"],
+            serde_json::json!(["gone"])
+        );
+    }
+
+    // Neither clean nor restore touched the repository outside the root
+    assert!(std::fs::read_to_string(&config).unwrap().contains("gone"));
+    assert!(!home.path().join("volume").join("gone").exists());
+}
@@ -0,0 +1,64 @@
+//! Integration tests for `vibe path`, `vibe open --print-path`, and `vibe shell-init`
+
+mod common;
+
+use common::{run_vibe, WorkspaceYaml};
+use tempfile::TempDir;
+
+fn workspace() -> (TempDir, std::path::PathBuf, std::path::PathBuf) {
+    let home = TempDir::new().unwrap();
+    let root = home.path().join("workspace");
+    let repo = root.join("my-app");
+    std::fs::create_dir_all(&repo).unwrap();
+
+    let config = home.path().join("config.yaml");
+    WorkspaceYaml::new(&root)
+        .repo("my-app", "my-app")
+        .write(&config);
+
+    (home, config, repo)
+}
+
+#[test]
+fn test_path_prints_only_the_path() {
+    let (home, config, repo) = workspace();
+    let config = config.to_str().unwrap();
+
+    for args in [
+        vec!["--config", config, "path", "my-app"],
+        vec!["--config", config, "open", "my-app", "--print-path"],
+    ] {
+        let output = run_vibe(home.path(), &args);
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        assert_eq!(
+            String::from_utf8_lossy(&output.stdout),
+            format!("{}\n", repo.display())
+        );
+    }
+}
+
+#[test]
+fn test_path_not_found_exits_three() {
+    let (home, config, _) = workspace();
+    let output = run_vibe(
+        home.path(),
+        &["--config", config.to_str().unwrap(), "path", "missing"],
+    );
+    assert_eq!(output.status.code(), Some(3));
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn test_shell_init() {
+    let home = TempDir::new().unwrap();
+    let output = run_vibe(home.path(), &["shell-init", "zsh"]);
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("vcd()"));
+
+    let output = run_vibe(home.path(), &["shell-init", "powershell"]);
+    assert!(!output.status.success());
+}
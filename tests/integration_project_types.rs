@@ -0,0 +1,133 @@
+//! Project types detected from manifests: listed with `vibe config repo list`
+//! and used by `--type` to narrow bulk commands
+
+mod common;
+
+use common::{git_init, json_data, run_vibe, WorkspaceYaml};
+use std::path::Path;
+use tempfile::TempDir;
+
+/// A workspace with a Rust checkout `api`, a mixed Node and Python checkout
+/// `web`, and `docs` without a manifest
+fn workspace() -> (TempDir, String) {
+    let home = TempDir::new().unwrap();
+    let root = home.path().join("workspace");
+    for (name, manifests) in [
+        ("api", &["Cargo.toml"][..]),
+        ("web", &["package.json", "requirements.txt"][..]),
+        ("docs", &[][..]),
+    ] {
+        let repo = root.join(name);
+        git_init(&repo);
+        for manifest in manifests {
+            std::fs::write(repo.join(manifest), "").unwrap();
+        }
+    }
+
+    let config = home.path().join("config.yaml");
+    WorkspaceYaml::new(&root)
+        .repo("api", "api")
+        .repo("docs", "docs")
+        .repo("web", "web")
+        .write(&config);
+
+    (home, config.to_str().unwrap().to_string())
+}
+
+fn listed(home: &Path, config: &str, extra: &[&str]) -> serde_json::Value {
+    let mut args = vec!["--config", config, "--json", "config", "repo", "list"];
+    args.extend_from_slice(extra);
+    json_data(&run_vibe(home, &args))
+}
+
+#[test]
+fn test_repo_list_shows_and_filters_project_types() {
+    let (home, config) = workspace();
+
+    let repos = listed(home.path(), &config, &[]);
+    let types: Vec<(String, serde_json::Value)> = repos
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|repo| {
+            (
+                repo["name"].as_str().unwrap().to_string(),
+                repo["project_types"].clone(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        types,
+        [
+            ("api".to_string(), serde_json::json!(["rust"])),
+            ("docs".to_string(), serde_json::json!([])),
+            ("web".to_string(), serde_json::json!(["node", "python"])),
+        ]
+    );
+
+    let repos = listed(home.path(), &config, &["--type", "python"]);
+    assert_eq!(repos.as_array().unwrap().len(), 1);
+    assert_eq!(repos[0]["name"], "web");
+
+    let output = run_vibe(
+        home.path(),
+        &["--config", &config, "config", "repo", "list"],
+    );
+    assert!(String::from_utf8_lossy(&output.stdout).contains("[node,python]"));
+}
+
+#[test]
+fn test_exec_type_filter() {
+    let (home, config) = workspace();
+    let output = run_vibe(
+        home.path(),
+        &[
+            "--config",
+            &config,
+            "git",
+            "exec",
+            "status --short",
+            "--type",
+            "rust",
+        ],
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{stdout}");
+    assert!(stdout.contains("Executing on api"));
+    assert!(!stdout.contains("Executing on web"));
+    assert!(!stdout.contains("Executing on docs"));
+
+    let output = run_vibe(
+        home.path(),
+        &[
+            "--config", &config, "git", "exec", "status", "--type", "java",
+        ],
+    );
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn test_repo_list_exports_tsv_with_chosen_fields() {
+    let (home, config) = workspace();
+    let export = |fields: &str| {
+        run_vibe(
+            home.path(),
+            &[
+                "--config", &config, "config", "repo", "list", "--format", "tsv", "--fields",
+                fields,
+            ],
+        )
+    };
+
+    let output = export("project_types,name");
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "project_types\tname\nrust\tapi\n\tdocs\nnode;python\tweb\n"
+    );
+
+    let output = export("name,language");
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr)
+        .contains("Unknown field 'language'. Valid fields: name, path, project_types"));
+}
@@ -0,0 +1,116 @@
+//! Branches vibe deletes are first saved under `refs/vibe/backup/`, and
+//! `vibe git refs restore|gc` bring them back or prune old backups
+
+mod common;
+
+use common::{git, run_vibe_with_config, vibe_json, workspace_with_origin};
+use std::path::Path;
+
+fn backup_refs(repo: &Path) -> Vec<String> {
+    git(
+        repo,
+        &["for-each-ref", "--format=%(refname)", "refs/vibe/backup/"],
+    )
+    .lines()
+    .map(str::to_string)
+    .collect()
+}
+
+#[test]
+fn test_deleted_task_branch_is_backed_up_and_restored() {
+    let (home, config, repo) = workspace_with_origin("");
+    vibe_json(home.path(), &config, &["task", "start", "api", "fix-1"]);
+    let sha = git(&repo, &["rev-parse", "vibe-ws/fix-1"]);
+
+    let done = vibe_json(home.path(), &config, &["task", "done", "fix-1", "--force"]);
+    assert_eq!(done["branch_deleted"], true);
+    assert_eq!(done["backup"]["branch"], "vibe-ws/fix-1");
+    assert_eq!(done["backup"]["sha"], sha.as_str());
+    let backups = backup_refs(&repo);
+    assert_eq!(backups.len(), 1);
+    assert!(backups[0].ends_with("/vibe-ws/fix-1"), "{backups:?}");
+
+    let restored = vibe_json(
+        home.path(),
+        &config,
+        &["git", "refs", "restore", "api", "vibe-ws/fix-1"],
+    );
+    assert_eq!(restored["backup_ref"], backups[0].as_str());
+    assert_eq!(git(&repo, &["rev-parse", "vibe-ws/fix-1"]), sha);
+
+    // The branch exists again, so restoring it a second time is refused
+    let output = run_vibe_with_config(
+        home.path(),
+        &config,
+        &["git", "refs", "restore", "api", "vibe-ws/fix-1"],
+    );
+    assert_eq!(output.status.code(), Some(2));
+    let output = run_vibe_with_config(
+        home.path(),
+        &config,
+        &["git", "refs", "restore", "api", "never-existed"],
+    );
+    assert_eq!(output.status.code(), Some(3));
+}
+
+#[test]
+fn test_sync_prune_backs_up_pruned_remote_branches() {
+    let (home, config, repo) = workspace_with_origin("");
+    git(&repo, &["push", "-q", "origin", "main:old"]);
+    git(&repo, &["fetch", "-q"]);
+    git(&home.path().join("origin.git"), &["branch", "-D", "old"]);
+
+    let synced = vibe_json(
+        home.path(),
+        &config,
+        &["git", "sync", "--fetch-only", "--prune"],
+    );
+    assert_eq!(synced[0]["outcome"], "synced");
+    assert_eq!(synced[0]["backups"][0]["branch"], "remotes/origin/old");
+    assert!(git(&repo, &["branch", "-r"])
+        .lines()
+        .all(|b| !b.contains("old")));
+
+    vibe_json(
+        home.path(),
+        &config,
+        &["git", "refs", "restore", "api", "old"],
+    );
+    assert_eq!(
+        git(&repo, &["rev-parse", "old"]),
+        git(&repo, &["rev-parse", "main"])
+    );
+}
+
+#[test]
+fn test_gc_prunes_only_old_backups() {
+    let (home, config, repo) = workspace_with_origin("");
+    git(
+        &repo,
+        &[
+            "update-ref",
+            "refs/vibe/backup/20200101T000000Z/stale",
+            "main",
+        ],
+    );
+    vibe_json(home.path(), &config, &["task", "start", "api", "fix-2"]);
+    vibe_json(home.path(), &config, &["task", "done", "fix-2", "--force"]);
+    assert_eq!(backup_refs(&repo).len(), 2);
+
+    let pruned = vibe_json(home.path(), &config, &["git", "refs", "gc"]);
+    assert_eq!(pruned[0]["name"], "api");
+    assert_eq!(pruned[0]["backups"][0]["branch"], "stale");
+    let left = backup_refs(&repo);
+    assert_eq!(left.len(), 1);
+    assert!(left[0].ends_with("/vibe-ws/fix-2"));
+}
+
+#[test]
+fn test_backups_can_be_turned_off() {
+    let (home, config, repo) = workspace_with_origin("safety:\n  ref_backups: false\n");
+    vibe_json(home.path(), &config, &["task", "start", "api", "fix-3"]);
+    let done = vibe_json(home.path(), &config, &["task", "done", "fix-3", "--force"]);
+    assert_eq!(done["branch_deleted"], true);
+    assert!(done["backup"].is_null());
+    assert!(backup_refs(&repo).is_empty());
+}
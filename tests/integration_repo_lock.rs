@@ -0,0 +1,94 @@
+//! Mutating operations skip a repository another vibe process has locked
+//! through `.git/vibe.lock`, and take over locks left by dead processes
+#![cfg(unix)]
+
+mod common;
+
+use common::{git_init, run_vibe_with_config, WorkspaceYaml};
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// A workspace with one repository without a remote and a lock timeout of
+/// zero, so a held lock is reported at once
+fn workspace() -> (TempDir, PathBuf, PathBuf) {
+    let home = TempDir::new().unwrap();
+    let repo = home.path().join("workspace").join("api");
+    git_init(&repo);
+
+    let config = home.path().join("config.yaml");
+    WorkspaceYaml::new(&home.path().join("workspace"))
+        .repo("api", "api")
+        .extra("preferences:\n  lock_timeout_secs: 0\n")
+        .write(&config);
+    let lock = repo.join(".git").join("vibe.lock");
+    (home, config, lock)
+}
+
+fn hold_lock(lock: &Path, pid: u32) {
+    std::fs::write(
+        lock,
+        format!(
+            "{{\"pid\":{pid},\"operation\":\"worktree cleanup\",\
+             \"acquired_at\":\"2024-01-01T00:00:00Z\"}}"
+        ),
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_sync_skips_a_locked_repository() {
+    let (home, config, lock) = workspace();
+    // pid 1 is always running
+    hold_lock(&lock, 1);
+
+    let output = run_vibe_with_config(home.path(), &config, &["--json", "git", "sync"]);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["data"][0]["outcome"], "locked");
+    assert_eq!(
+        report["warnings"][0],
+        "api: locked by worktree cleanup (pid 1), skipped"
+    );
+    // Someone else's lock is left alone
+    assert!(lock.exists());
+}
+
+#[test]
+fn test_exec_locks_only_for_writing_commands() {
+    let (home, config, lock) = workspace();
+    hold_lock(&lock, 1);
+
+    let output = run_vibe_with_config(home.path(), &config, &["git", "exec", "status"]);
+    assert!(output.status.success());
+
+    let output = run_vibe_with_config(
+        home.path(),
+        &config,
+        &["git", "exec", "config vibe.note wip"],
+    );
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("locked by worktree cleanup (pid 1)"));
+}
+
+#[test]
+fn test_stale_lock_is_taken_over_and_released() {
+    let (home, config, lock) = workspace();
+    // No process has a pid this large
+    hold_lock(&lock, 999_999_999);
+
+    let output = run_vibe_with_config(
+        home.path(),
+        &config,
+        &["git", "exec", "config vibe.note wip"],
+    );
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(!lock.exists());
+}
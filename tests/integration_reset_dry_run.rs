@@ -0,0 +1,132 @@
+//! `vibe config reset --dry-run` and `vibe git reset --dry-run` list what
+//! would be removed and leave every file as it was
+
+mod common;
+
+use common::{run_vibe_with_config, WorkspaceYaml};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// Contents of every file under `dir`
+fn snapshot(dir: &Path) -> BTreeMap<PathBuf, Vec<u8>> {
+    walkdir::WalkDir::new(dir)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| {
+            let contents = std::fs::read(entry.path()).unwrap();
+            (entry.into_path(), contents)
+        })
+        .collect()
+}
+
+/// Files added, removed, or changed between two snapshots
+fn changed(
+    before: &BTreeMap<PathBuf, Vec<u8>>,
+    after: &BTreeMap<PathBuf, Vec<u8>>,
+) -> Vec<PathBuf> {
+    let paths: BTreeSet<&PathBuf> = before.keys().chain(after.keys()).collect();
+    paths
+        .into_iter()
+        .filter(|path| before.get(*path) != after.get(*path))
+        .cloned()
+        .collect()
+}
+
+/// A workspace with two repositories, whose caches exist from an earlier run
+fn workspace() -> (TempDir, PathBuf) {
+    let home = TempDir::new().unwrap();
+    let root = home.path().join("workspace");
+    std::fs::create_dir_all(&root).unwrap();
+    let config = home.path().join("config.yaml");
+    WorkspaceYaml::new(&root)
+        .repo_with(
+            "api",
+            "api",
+            "  url: https://github.com/acme/api\n  apps:\n    cursor:\n      template: default\n",
+        )
+        .repo("web", "web")
+        .write(&config);
+    let output = run_vibe_with_config(home.path(), &config, &["config", "show"]);
+    assert!(output.status.success(), "{output:?}");
+    (home, config)
+}
+
+#[test]
+fn test_factory_reset_dry_run_changes_nothing() {
+    let (home, config) = workspace();
+    let before = snapshot(home.path());
+
+    let output = run_vibe_with_config(home.path(), &config, &["config", "reset", "--dry-run"]);
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Dry run: nothing was changed"), "{stdout}");
+    assert!(stdout.contains("config.yaml"), "{stdout}");
+
+    let output = run_vibe_with_config(
+        home.path(),
+        &config,
+        &["config", "reset", "--dry-run", "--format", "json"],
+    );
+    assert!(output.status.success(), "{output:?}");
+    let preview: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let config_file = &preview["config_files"][0];
+    assert_eq!(config_file["path"], config.display().to_string());
+    assert_eq!(
+        config_file["bytes"],
+        std::fs::metadata(&config).unwrap().len()
+    );
+    let caches: Vec<&str> = preview["cache_databases"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|cache| cache["path"].as_str().unwrap())
+        .collect();
+    assert!(
+        caches.iter().any(|path| path.ends_with("repositories.db")),
+        "{caches:?}"
+    );
+
+    assert_eq!(
+        changed(&before, &snapshot(home.path())),
+        Vec::<PathBuf>::new()
+    );
+}
+
+#[test]
+fn test_repository_reset_dry_run_changes_nothing() {
+    let (home, config) = workspace();
+    let before = snapshot(home.path());
+
+    let output = run_vibe_with_config(
+        home.path(),
+        &config,
+        &["git", "reset", "--dry-run", "--format", "json"],
+    );
+    assert!(output.status.success(), "{output:?}");
+    let preview: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let names: Vec<&str> = preview["repositories"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|repo| repo["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, ["api", "web"]);
+    assert_eq!(preview["repositories"][0]["apps"][0], "cursor");
+    assert_eq!(
+        preview["repositories"][0]["url"],
+        "https://github.com/acme/api"
+    );
+
+    let output = run_vibe_with_config(home.path(), &config, &["git", "reset", "--dry-run"]);
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("api"), "{stdout}");
+    assert!(stdout.contains("Dry run: nothing was changed"), "{stdout}");
+
+    assert_eq!(
+        changed(&before, &snapshot(home.path())),
+        Vec::<PathBuf>::new()
+    );
+}
@@ -0,0 +1,118 @@
+//! `vibe git scan --clean`: confirming removals, keeping repositories whose
+//! volume is gone, and re-adding removed entries
+
+mod common;
+
+use common::{git_init, json_data, run_vibe, WorkspaceYaml};
+use std::path::Path;
+use tempfile::TempDir;
+
+/// A workspace with `api` checked out and `gone` missing from disk
+fn workspace() -> (TempDir, String) {
+    let home = TempDir::new().unwrap();
+    let root = home.path().join("workspace");
+    git_init(&root.join("api"));
+
+    let config = home.path().join("config.yaml");
+    WorkspaceYaml::new(&root)
+        .repo("api", "api")
+        .repo_with("gone", "gone", "  url: git@github.com:acme/gone.git\n")
+        .write(&config);
+
+    (home, config.to_str().unwrap().to_string())
+}
+
+fn scan_clean(home: &Path, config: &str, extra: &[&str]) -> serde_json::Value {
+    let mut args = vec!["--config", config, "--json", "git", "scan", "--clean"];
+    args.extend_from_slice(extra);
+    json_data(&run_vibe(home, &args))["sync"].clone()
+}
+
+fn configured(config: &str) -> Vec<String> {
+    let config: serde_yaml::Value =
+        serde_yaml::from_str(&std::fs::read_to_string(config).unwrap()).unwrap();
+    config["repositories"]
+        .as_sequence()
+        .unwrap()
+        .iter()
+        .map(|repo| repo["name"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn test_clean_needs_confirmation_and_can_be_undone() {
+    let (home, config) = workspace();
+
+    // Without a terminal or --yes nothing is removed
+    let sync = scan_clean(home.path(), &config, &[]);
+    assert_eq!(sync["removed"], serde_json::json!([]));
+    assert_eq!(sync["kept"], serde_json::json!(["gone"]));
+    assert_eq!(configured(&config), ["api", "gone"]);
+
+    let sync = scan_clean(home.path(), &config, &["--yes"]);
+    assert_eq!(sync["removed"], serde_json::json!(["gone"]));
+    assert_eq!(configured(&config), ["api"]);
+    let removed =
+        std::fs::read_to_string(home.path().join(".local/share/vibe/removed-repos.yaml")).unwrap();
+    assert!(removed.contains("git@github.com:acme/gone.git"));
+
+    let output = run_vibe(
+        home.path(),
+        &["--config", &config, "config", "repo", "readd-removed"],
+    );
+    assert!(String::from_utf8_lossy(&output.stdout).contains("gone"));
+
+    let output = run_vibe(
+        home.path(),
+        &[
+            "--config",
+            &config,
+            "config",
+            "repo",
+            "readd-removed",
+            "nope",
+        ],
+    );
+    assert_eq!(output.status.code(), Some(3));
+
+    let output = run_vibe(
+        home.path(),
+        &[
+            "--config",
+            &config,
+            "config",
+            "repo",
+            "readd-removed",
+            "gone",
+        ],
+    );
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(configured(&config), ["api", "gone"]);
+    let removed =
+        std::fs::read_to_string(home.path().join(".local/share/vibe/removed-repos.yaml")).unwrap();
+    assert!(!removed.contains("gone"));
+}
+
+#[test]
+fn test_clean_is_refused_when_the_root_is_gone() {
+    let (home, config) = workspace();
+    std::fs::remove_dir_all(home.path().join("workspace")).unwrap();
+
+    let output = run_vibe(
+        home.path(),
+        &["--config", &config, "git", "scan", "--clean", "--yes"],
+    );
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("is unavailable"), "{stderr}");
+    assert_eq!(configured(&config), ["api", "gone"]);
+
+    // A root that really was deleted can still be cleaned
+    let sync = scan_clean(home.path(), &config, &["--yes", "--force-online"]);
+    assert_eq!(sync["removed"], serde_json::json!(["api", "gone"]));
+    assert!(configured(&config).is_empty());
+}
@@ -0,0 +1,82 @@
+//! Re-cloning missing repositories with `vibe git scan --restore`
+
+mod common;
+
+use common::{commit_file, git_init, run_vibe, WorkspaceYaml};
+use tempfile::TempDir;
+
+/// A workspace where `api` and `web` can be cloned from local origins,
+/// `broken` points at a remote that doesn't exist, and `notes` has no URL
+fn workspace() -> (TempDir, String) {
+    let home = TempDir::new().unwrap();
+    let origins = home.path().join("origins");
+    for name in ["api", "web"] {
+        let origin = origins.join(name);
+        git_init(&origin);
+        commit_file(&origin, "README.md", name);
+    }
+    let root = home.path().join("workspace");
+    std::fs::create_dir_all(&root).unwrap();
+
+    let config = home.path().join("config.yaml");
+    let url = |name: &str| format!("  url: {}\n", origins.join(name).display());
+    WorkspaceYaml::new(&root)
+        .repo_with("api", "api", &url("api"))
+        .repo_with("web", "web", &url("web"))
+        .repo_with("broken", "broken", &url("missing"))
+        .repo("notes", "notes")
+        .extra("preferences:\n  clone_protocol: ssh\n")
+        .write(&config);
+
+    (home, config.to_str().unwrap().to_string())
+}
+
+#[test]
+fn test_restore_reports_restored_failed_and_skipped() {
+    let (home, config) = workspace();
+    let output = run_vibe(
+        home.path(),
+        &["--config", &config, "--json", "git", "scan", "--restore"],
+    );
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let sync = &report["data"]["sync"];
+    assert_eq!(sync["restored"], serde_json::json!(["api", "web"]));
+    assert_eq!(sync["skipped"], serde_json::json!(["notes"]));
+    assert_eq!(sync["failed"][0]["name"], "broken");
+    assert!(home.path().join("workspace/api/README.md").is_file());
+    assert!(home.path().join("workspace/web/README.md").is_file());
+}
+
+#[test]
+fn test_restore_only_clones_named_repositories() {
+    let (home, config) = workspace();
+    let output = run_vibe(
+        home.path(),
+        &[
+            "--config",
+            &config,
+            "--json",
+            "git",
+            "scan",
+            "--restore-only",
+            "web",
+        ],
+    );
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        report["data"]["sync"]["restored"],
+        serde_json::json!(["web"])
+    );
+    assert!(!home.path().join("workspace/api").exists());
+
+    let output = run_vibe(
+        home.path(),
+        &["--config", &config, "git", "scan", "--restore-only", "nope"],
+    );
+    assert_eq!(output.status.code(), Some(3));
+}
@@ -0,0 +1,47 @@
+//! Integration tests for `vibe stats disk`
+
+mod common;
+
+use common::{git, run_vibe, WorkspaceYaml};
+use tempfile::TempDir;
+
+#[test]
+fn test_stats_disk_reports_caches_as_reclaimable() {
+    let home = TempDir::new().unwrap();
+    let root = home.path().join("workspace");
+    let repo = root.join("app");
+    std::fs::create_dir_all(repo.join("node_modules/lib")).unwrap();
+    git(&repo, &["init", "-q"]);
+    std::fs::write(repo.join("README.md"), vec![b'x'; 100]).unwrap();
+    std::fs::write(repo.join("node_modules/lib/index.js"), vec![b'x'; 400]).unwrap();
+
+    let config = home.path().join("config.yaml");
+    WorkspaceYaml::new(&root).repo("app", "app").write(&config);
+    let config = config.to_str().unwrap();
+
+    let output = run_vibe(
+        home.path(),
+        &["--config", config, "stats", "disk", "--format", "json"],
+    );
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let app = &report["repositories"][0];
+    assert_eq!(app["name"], "app");
+    assert_eq!(app["working_tree_bytes"], 100);
+    assert_eq!(app["build_caches"][0]["bytes"], 400);
+    assert_eq!(app["cached"], false);
+    assert_eq!(report["reclaimable_bytes"], 400);
+
+    // The second run is answered from the repository cache
+    let output = run_vibe(
+        home.path(),
+        &["--json", "--config", config, "stats", "disk"],
+    );
+    let envelope: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(envelope["data"]["repositories"][0]["cached"], true);
+    assert_eq!(envelope["data"]["total_bytes"], report["total_bytes"]);
+}
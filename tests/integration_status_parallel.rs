@@ -0,0 +1,84 @@
+//! Parallel git status collection over a fixture workspace
+
+mod common;
+
+use common::git_init;
+use std::path::Path;
+use std::time::{Duration, Instant};
+use tempfile::TempDir;
+use vibe_workspace::workspace::operations::{collect_git_statuses, get_git_status, StatusTarget};
+
+const REPO_COUNT: usize = 50;
+
+/// A tiny repository with a few untracked files so status has work to do
+fn make_repo(path: &Path) {
+    git_init(path);
+    for i in 0..20 {
+        std::fs::write(path.join(format!("file{i}.txt")), "content").unwrap();
+    }
+}
+
+fn fixture_workspace() -> (TempDir, Vec<StatusTarget>) {
+    let root = TempDir::new().unwrap();
+    let repos = (0..REPO_COUNT)
+        .map(|i| {
+            let name = format!("repo-{i:02}");
+            let path = root.path().join(&name);
+            make_repo(&path);
+            StatusTarget::new(name, path)
+        })
+        .collect();
+    (root, repos)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_parallel_status_preserves_order_and_reports_errors() {
+    let (root, mut repos) = fixture_workspace();
+    let missing = root.path().join("missing");
+    repos.insert(10, StatusTarget::new("missing", missing));
+
+    let results = collect_git_statuses(repos.clone(), 4).await;
+
+    let names: Vec<&str> = results.iter().map(|r| r.name.as_str()).collect();
+    let expected: Vec<&str> = repos.iter().map(|target| target.name.as_str()).collect();
+    assert_eq!(names, expected);
+
+    assert!(results[10].status.is_err());
+    let ok = results.iter().filter(|r| r.status.is_ok()).count();
+    assert_eq!(ok, REPO_COUNT);
+    assert_eq!(results[0].status.as_ref().unwrap().untracked, 20);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_parallel_status_is_faster_than_sequential() {
+    let (_root, repos) = fixture_workspace();
+
+    // Warm the filesystem cache so both runs see the same conditions
+    collect_git_statuses(repos.clone(), 8).await;
+
+    let mut sequential = Duration::MAX;
+    let mut parallel = Duration::MAX;
+    for _ in 0..3 {
+        let start = Instant::now();
+        for target in &repos {
+            get_git_status(&target.path).await.unwrap();
+        }
+        sequential = sequential.min(start.elapsed());
+
+        let start = Instant::now();
+        let results = collect_git_statuses(repos.clone(), 8).await;
+        parallel = parallel.min(start.elapsed());
+        assert!(results.iter().all(|r| r.status.is_ok()));
+    }
+
+    println!("sequential: {sequential:?}, parallel: {parallel:?}");
+    let cores = std::thread::available_parallelism().map_or(1, |n| n.get());
+    if cores >= 4 {
+        assert!(
+            parallel * 2 < sequential,
+            "parallel {parallel:?} should be well below sequential {sequential:?}"
+        );
+    } else {
+        assert!(parallel < sequential * 2);
+    }
+}
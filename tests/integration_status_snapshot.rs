@@ -0,0 +1,88 @@
+//! The menu, quick launcher, app list, and MCP git_status tool all read
+//! statuses through `WorkspaceManager::refresh_statuses`, so they agree
+
+mod common;
+
+use common::{git, WorkspaceYaml};
+use serde_json::json;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use tempfile::TempDir;
+use tokio::sync::Mutex;
+use vibe_workspace::mcp::handlers::GitStatusTool;
+use vibe_workspace::mcp::types::VibeToolHandler;
+use vibe_workspace::workspace::status_snapshot::{StatusScope, STATUS_CACHE_TTL};
+use vibe_workspace::WorkspaceManager;
+
+/// A clone of `origin` that is one commit ahead, one behind, and has an
+/// untracked file
+fn diverged_clone(root: &Path, origin: &Path) {
+    std::fs::create_dir_all(origin).unwrap();
+    git(origin, &["init", "-q", "-b", "main"]);
+    git(origin, &["commit", "-q", "--allow-empty", "-m", "one"]);
+    git(root, &["clone", "-q", origin.to_str().unwrap(), "api"]);
+    let clone = root.join("api");
+    git(origin, &["commit", "-q", "--allow-empty", "-m", "two"]);
+    git(&clone, &["fetch", "-q"]);
+    git(&clone, &["commit", "-q", "--allow-empty", "-m", "local"]);
+    std::fs::write(clone.join("notes.txt"), "draft").unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_consumers_report_the_same_status() {
+    let dir = TempDir::new().unwrap();
+    std::env::set_var("VIBE_HOME", dir.path().join("home"));
+    let root = dir.path().join("workspace");
+    std::fs::create_dir_all(&root).unwrap();
+    diverged_clone(&root, &dir.path().join("origin"));
+
+    let config = dir.path().join("config.yaml");
+    WorkspaceYaml::new(&root)
+        .repo_with(
+            "api",
+            "api",
+            "  apps:\n    cursor:\n      template: default\n",
+        )
+        .write(&config);
+    let manager = WorkspaceManager::new(config).await.unwrap();
+
+    // `vibe git status` reads git
+    let read = manager
+        .refresh_statuses(StatusScope::Targets(None), Duration::ZERO)
+        .await;
+    let status = read.get("api").unwrap();
+    let expected = (status.is_dirty(), status.ahead, status.behind);
+    assert_eq!(expected, (true, 1, 1));
+
+    // The menu and quick launcher serve the cached status
+    let refresh = manager
+        .plan_status_refresh(StatusScope::All, STATUS_CACHE_TTL)
+        .await;
+    assert!(refresh.is_fresh());
+    let cached = refresh.cached().get("api").unwrap();
+    assert_eq!((cached.is_dirty(), cached.ahead, cached.behind), expected);
+    assert_eq!(refresh.cached().dirty_count(), 1);
+    assert_eq!(refresh.cached().behind_count(), 1);
+
+    let with_apps = manager.get_repos_with_apps_and_status().await.unwrap();
+    let listed = &with_apps[0].git_status;
+    assert_eq!((listed.is_dirty(), listed.ahead, listed.behind), expected);
+
+    let response = GitStatusTool
+        .handle_call(
+            json!({ "max_age_seconds": 300 }),
+            Arc::new(Mutex::new(manager)),
+        )
+        .await
+        .unwrap();
+    let repo = &response["repositories"][0];
+    assert_eq!(
+        (
+            repo["is_dirty"].as_bool().unwrap(),
+            repo["ahead"].as_u64().unwrap() as usize,
+            repo["behind"].as_u64().unwrap() as usize
+        ),
+        expected
+    );
+}
@@ -0,0 +1,124 @@
+//! Monorepo subprojects: detection, opening, and scoped search
+
+mod common;
+
+use common::{git_init, run_vibe, stdout_json, WorkspaceYaml};
+use tempfile::TempDir;
+
+/// A workspace whose `mono` repository is a pnpm workspace with `web` and `ui`
+fn workspace() -> (TempDir, String) {
+    let home = TempDir::new().unwrap();
+    let root = home.path().join("workspace");
+    let mono = root.join("mono");
+    for (dir, content) in [
+        ("apps/web", "needle in web"),
+        ("packages/ui", "needle in ui"),
+    ] {
+        std::fs::create_dir_all(mono.join(dir)).unwrap();
+        std::fs::write(mono.join(dir).join("package.json"), "{}").unwrap();
+        std::fs::write(mono.join(dir).join("notes.txt"), content).unwrap();
+    }
+    std::fs::write(
+        mono.join("pnpm-workspace.yaml"),
+        "packages:\n  - 'apps/*'\n  - 'packages/*'\n",
+    )
+    .unwrap();
+    git_init(&mono);
+
+    let config = home.path().join("config.yaml");
+    WorkspaceYaml::new(&root)
+        .repo("mono", "mono")
+        .write(&config);
+
+    (home, config.to_str().unwrap().to_string())
+}
+
+#[test]
+fn test_scan_detects_subprojects_and_open_resolves_them() {
+    let (home, config) = workspace();
+
+    let output = run_vibe(
+        home.path(),
+        &[
+            "--config",
+            &config,
+            "--json",
+            "git",
+            "scan",
+            "--subprojects",
+        ],
+    );
+    let report = stdout_json(&output);
+    assert_eq!(report["data"]["subprojects"][0]["repo"], "mono");
+    let names: Vec<&str> = report["data"]["subprojects"][0]["subprojects"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|s| s["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, ["ui", "web"]);
+    assert!(std::fs::read_to_string(&config)
+        .unwrap()
+        .contains("subprojects:"));
+
+    for target in ["mono:web", "mono/web"] {
+        let output = run_vibe(
+            home.path(),
+            &["--config", &config, "open", target, "--print-path"],
+        );
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        assert!(String::from_utf8_lossy(&output.stdout)
+            .trim_end()
+            .ends_with("mono/apps/web"));
+    }
+}
+
+#[test]
+fn test_grep_scoped_to_subproject() {
+    let (home, config) = workspace();
+    let output = run_vibe(
+        home.path(),
+        &["--config", &config, "git", "scan", "--subprojects"],
+    );
+    assert!(output.status.success());
+
+    let output = run_vibe(
+        home.path(),
+        &[
+            "--config",
+            &config,
+            "grep",
+            "needle",
+            "--subproject",
+            "mono:ui",
+            "--format",
+            "json",
+        ],
+    );
+    let results = stdout_json(&output);
+    assert_eq!(results[0]["repo"], "mono/ui");
+    let files: Vec<&str> = results[0]["matches"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|m| m["file"].as_str().unwrap())
+        .collect();
+    assert_eq!(files, ["notes.txt"]);
+
+    let output = run_vibe(
+        home.path(),
+        &[
+            "--config",
+            &config,
+            "grep",
+            "needle",
+            "--subproject",
+            "mono:api",
+        ],
+    );
+    assert_eq!(output.status.code(), Some(3));
+}
@@ -0,0 +1,59 @@
+//! `vibe task start` and `vibe task done`, each safe to run again
+
+mod common;
+
+use common::{commit_file, git, run_vibe_with_config, vibe_json, workspace_with_origin};
+use std::path::Path;
+
+fn task_json(home: &Path, config: &Path, args: &[&str]) -> serde_json::Value {
+    let mut all = vec!["task"];
+    all.extend_from_slice(args);
+    vibe_json(home, config, &all)
+}
+
+#[test]
+fn test_start_and_done_can_be_rerun() {
+    let (home, config, _) = workspace_with_origin("");
+
+    let started = task_json(home.path(), &config, &["start", "api", "fix-1"]);
+    assert_eq!(started["created"], true);
+    assert_eq!(started["branch"], "vibe-ws/fix-1");
+    let path = started["path"].as_str().unwrap().to_string();
+    assert!(Path::new(&path).join("README.md").exists());
+
+    let resumed = task_json(home.path(), &config, &["start", "api", "fix-1"]);
+    assert_eq!(resumed["created"], false);
+    assert_eq!(resumed["path"].as_str(), Some(path.as_str()));
+
+    // Pushed without new commits, so the branch counts as merged into main
+    git(
+        Path::new(&path),
+        &["push", "-q", "-u", "origin", "vibe-ws/fix-1"],
+    );
+    let done = task_json(home.path(), &config, &["done", "fix-1"]);
+    assert_eq!(done["merge"]["is_merged"], true);
+    assert_eq!(done["worktree_removed"], true);
+    assert_eq!(done["branch_deleted"], true);
+    assert!(!Path::new(&path).exists());
+
+    let again = task_json(home.path(), &config, &["done", "fix-1"]);
+    assert_eq!(again["worktree_removed"], false);
+    assert!(again["branch"].is_null());
+}
+
+#[test]
+fn test_done_refuses_an_unmerged_branch() {
+    let (home, config, _) = workspace_with_origin("");
+    let started = task_json(home.path(), &config, &["start", "api", "wip"]);
+    let path = Path::new(started["path"].as_str().unwrap()).to_path_buf();
+    commit_file(&path, "feature.txt", "new");
+
+    let output = run_vibe_with_config(home.path(), &config, &["task", "done", "wip"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("isn't merged yet"));
+    assert!(path.exists());
+
+    let done = task_json(home.path(), &config, &["done", "wip", "--force"]);
+    assert_eq!(done["worktree_removed"], true);
+    assert!(!path.exists());
+}
@@ -0,0 +1,160 @@
+//! `vibe apps template install` installs a pack's templates under its
+//! prefix, and `uninstall-pack` removes them again
+
+mod common;
+
+use common::{git, run_vibe, vibe_json, WorkspaceYaml};
+use std::path::Path;
+use tempfile::TempDir;
+
+fn write(path: &Path, contents: &str) {
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, contents).unwrap();
+}
+
+/// An empty workspace and a pack named `team` committed to a git repository
+fn setup() -> (TempDir, String, String) {
+    let home = TempDir::new().unwrap();
+    std::fs::create_dir_all(home.path().join("workspace")).unwrap();
+    let config = home.path().join("config.yaml");
+    WorkspaceYaml::new(&home.path().join("workspace")).write(&config);
+
+    let pack = home.path().join("pack");
+    write(&pack.join("vibe-pack.yaml"), "name: team\n");
+    write(
+        &pack.join("templates/warp/split.yaml"),
+        "name: {{workspace_name}} - {{repo_name}}\nwindows: []\n",
+    );
+    write(
+        &pack.join("templates/vscode/focus.json"),
+        "{\"folders\": [{\"path\": \"{{repo_path}}\"}]}\n",
+    );
+    git(&pack, &["init", "-q", "-b", "main"]);
+    git(&pack, &["add", "."]);
+    git(&pack, &["commit", "-q", "-m", "pack"]);
+
+    (
+        home,
+        config.to_str().unwrap().to_string(),
+        format!("file://{}", pack.display()),
+    )
+}
+
+#[test]
+fn test_pack_is_installed_listed_and_uninstalled() {
+    let (home, config, url) = setup();
+
+    let report = vibe_json(home.path(), &config, &["apps", "template", "install", &url]);
+    assert_eq!(report["name"], "team");
+    let installed: Vec<String> = report["templates"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| {
+            format!(
+                "{}/{}",
+                t["app"].as_str().unwrap(),
+                t["name"].as_str().unwrap()
+            )
+        })
+        .collect();
+    assert_eq!(installed, ["vscode/team-focus", "warp/team-split"]);
+    assert!(std::fs::read_to_string(&config)
+        .unwrap()
+        .contains("template_packs"));
+
+    let output = run_vibe(
+        home.path(),
+        &["--config", &config, "apps", "template", "list", "warp"],
+    );
+    assert!(String::from_utf8_lossy(&output.stdout).contains("team-split (pack team)"));
+
+    let packs = vibe_json(home.path(), &config, &["apps", "template", "packs"]);
+    assert_eq!(packs["team"]["source"], url.as_str());
+
+    let removed = vibe_json(
+        home.path(),
+        &config,
+        &["apps", "template", "uninstall-pack", "team"],
+    );
+    assert_eq!(removed.as_array().unwrap().len(), 2);
+    assert!(!std::fs::read_to_string(&config)
+        .unwrap()
+        .contains("template_packs"));
+    let output = run_vibe(
+        home.path(),
+        &["--config", &config, "apps", "template", "list", "warp"],
+    );
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("team-split"));
+
+    let output = run_vibe(
+        home.path(),
+        &[
+            "--config",
+            &config,
+            "apps",
+            "template",
+            "uninstall-pack",
+            "team",
+        ],
+    );
+    assert_eq!(output.status.code(), Some(3));
+}
+
+#[test]
+fn test_pack_with_an_invalid_template_installs_nothing() {
+    let (home, config, _) = setup();
+    let pack = home.path().join("pack");
+    write(
+        &pack.join("templates/wezterm/broken.lua"),
+        "local config = {}\n",
+    );
+
+    let output = run_vibe(
+        home.path(),
+        &[
+            "--config",
+            &config,
+            "apps",
+            "template",
+            "install",
+            pack.to_str().unwrap(),
+        ],
+    );
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("templates/wezterm/broken.lua"));
+
+    let packs = vibe_json(home.path(), &config, &["apps", "template", "packs"]);
+    assert!(packs.as_object().unwrap().is_empty());
+}
+
+#[test]
+fn test_restored_workspace_reinstalls_its_packs() {
+    let (home, config, url) = setup();
+    vibe_json(home.path(), &config, &["apps", "template", "install", &url]);
+    let manifest = home.path().join("workspace.yaml");
+    let output = run_vibe(
+        home.path(),
+        &[
+            "--config",
+            &config,
+            "config",
+            "export-manifest",
+            "--output",
+            manifest.to_str().unwrap(),
+        ],
+    );
+    assert!(output.status.success());
+
+    // A new machine: nothing installed yet
+    let (new_home, new_config, _) = setup();
+    let report = vibe_json(
+        new_home.path(),
+        &new_config,
+        &["restore-workspace", manifest.to_str().unwrap()],
+    );
+    assert_eq!(report["template_packs"][0], "team");
+    let packs = vibe_json(new_home.path(), &new_config, &["apps", "template", "packs"]);
+    assert_eq!(packs["team"]["templates"].as_array().unwrap().len(), 2);
+    assert!(std::fs::read_to_string(&new_config).unwrap().contains(&url));
+}
@@ -0,0 +1,104 @@
+//! `vibe apps template render`: previewing a template for a repository, and
+//! errors that list the valid names
+
+mod common;
+
+use common::{json_data, run_vibe, WorkspaceYaml};
+use tempfile::TempDir;
+
+/// A workspace with `api`, configured for VS Code with the `compact` template
+fn workspace() -> (TempDir, String) {
+    let home = TempDir::new().unwrap();
+    let root = home.path().join("workspace");
+    std::fs::create_dir_all(root.join("api")).unwrap();
+
+    let templates = home.path().join(".local/share/vibe/templates/vscode");
+    std::fs::create_dir_all(&templates).unwrap();
+    std::fs::write(
+        templates.join("compact.json"),
+        r#"{"folders": [{"path": "{{repo_path}}", "name": "{{repo_name}}"}]}"#,
+    )
+    .unwrap();
+
+    let config = home.path().join("config.yaml");
+    WorkspaceYaml::new(&root)
+        .repo_with(
+            "api",
+            "api",
+            "  apps:\n    vscode:\n      template: compact\n",
+        )
+        .apps(&format!(
+            "  vscode:\n    enabled: true\n    workspace_dir: {}/vscode\n    template_dir: {}\n    default_template: default\n",
+            home.path().display(),
+            templates.display()
+        ))
+        .write(&config);
+
+    (home, config.to_str().unwrap().to_string())
+}
+
+#[test]
+fn test_render_uses_the_repository_template() {
+    let (home, config) = workspace();
+    let output = run_vibe(
+        home.path(),
+        &[
+            "--config", &config, "--json", "apps", "template", "render", "vscode", "api",
+        ],
+    );
+    let rendered = json_data(&output);
+    assert_eq!(rendered["template"], "compact");
+    assert!(rendered["content"]
+        .as_str()
+        .unwrap()
+        .contains(r#""name": "api""#));
+    assert!(rendered["destination"]
+        .as_str()
+        .unwrap()
+        .ends_with("vscode/vibe-test-api.code-workspace"));
+    assert_eq!(rendered["destination_exists"], false);
+    assert!(!home.path().join("vscode").exists());
+}
+
+#[test]
+fn test_render_errors_list_the_options() {
+    let (home, config) = workspace();
+
+    let output = run_vibe(
+        home.path(),
+        &[
+            "--config",
+            &config,
+            "apps",
+            "template",
+            "render",
+            "vscode",
+            "api",
+            "--template",
+            "nope",
+        ],
+    );
+    assert_eq!(output.status.code(), Some(3));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Templates for vscode: "), "{stderr}");
+    assert!(stderr.contains("compact"), "{stderr}");
+
+    let output = run_vibe(
+        home.path(),
+        &[
+            "--config", &config, "apps", "template", "render", "vscode", "web",
+        ],
+    );
+    assert_eq!(output.status.code(), Some(3));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Repositories: api"), "{stderr}");
+
+    let output = run_vibe(
+        home.path(),
+        &[
+            "--config", &config, "apps", "template", "render", "slack", "api",
+        ],
+    );
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Apps with templates: warp"));
+}
@@ -0,0 +1,88 @@
+//! Removed repository entries and worktrees go to the trash, where
+//! `vibe trash restore` brings them back
+
+mod common;
+
+use common::{git, run_vibe, vibe_json, WorkspaceYaml};
+use std::path::Path;
+use tempfile::TempDir;
+
+/// A workspace with one committed repository, `api`
+fn workspace() -> (TempDir, String) {
+    let home = TempDir::new().unwrap();
+    let root = home.path().join("workspace");
+    let repo = root.join("api");
+    std::fs::create_dir_all(&repo).unwrap();
+    git(&repo, &["init", "-q", "-b", "main"]);
+    std::fs::write(repo.join("README.md"), "api").unwrap();
+    git(&repo, &["add", "."]);
+    git(&repo, &["commit", "-q", "-m", "initial"]);
+
+    let config = home.path().join("config.yaml");
+    WorkspaceYaml::new(&root).repo("api", "api").write(&config);
+
+    (home, config.to_str().unwrap().to_string())
+}
+
+#[test]
+fn test_removed_worktree_is_restored_with_its_changes() {
+    let (home, config) = workspace();
+    let started = vibe_json(home.path(), &config, &["task", "start", "api", "wip"]);
+    let path = Path::new(started["path"].as_str().unwrap()).to_path_buf();
+    std::fs::write(path.join("notes.txt"), "uncommitted").unwrap();
+
+    let done = vibe_json(home.path(), &config, &["task", "done", "wip", "--force"]);
+    assert!(!path.exists());
+    let id = done["trash_id"].as_str().unwrap().to_string();
+    assert!(id.ends_with("-vibe-ws-wip"));
+
+    let items = vibe_json(home.path(), &config, &["trash", "list"]);
+    assert_eq!(items.as_array().unwrap().len(), 1);
+    assert_eq!(items[0]["id"], id.as_str());
+    assert_eq!(items[0]["kind"], "worktree");
+
+    let restored = vibe_json(home.path(), &config, &["trash", "restore", &id]);
+    assert_eq!(restored["id"], id.as_str());
+    assert_eq!(
+        std::fs::read_to_string(path.join("notes.txt")).unwrap(),
+        "uncommitted"
+    );
+    let items = vibe_json(home.path(), &config, &["trash", "list"]);
+    assert!(items.as_array().unwrap().is_empty());
+
+    // Restoring settled the history event too
+    let output = run_vibe(home.path(), &["--config", &config, "history"]);
+    assert!(String::from_utf8_lossy(&output.stdout).contains("(undone)"));
+}
+
+#[test]
+fn test_removed_repository_entry_is_restored() {
+    let (home, config) = workspace();
+
+    let removed = vibe_json(home.path(), &config, &["config", "repo", "remove", "api"]);
+    let id = removed["trash_id"].as_str().unwrap().to_string();
+    assert!(!std::fs::read_to_string(&config)
+        .unwrap()
+        .contains("name: api"));
+
+    let output = run_vibe(
+        home.path(),
+        &["--config", &config, "trash", "restore", "nope"],
+    );
+    assert_eq!(output.status.code(), Some(3));
+
+    vibe_json(home.path(), &config, &["trash", "restore", &id]);
+    assert!(std::fs::read_to_string(&config)
+        .unwrap()
+        .contains("name: api"));
+
+    vibe_json(home.path(), &config, &["config", "repo", "remove", "api"]);
+    let kept = vibe_json(
+        home.path(),
+        &config,
+        &["trash", "empty", "--older-than", "1d", "--yes"],
+    );
+    assert!(kept.as_array().unwrap().is_empty());
+    let deleted = vibe_json(home.path(), &config, &["trash", "empty", "--yes"]);
+    assert_eq!(deleted.as_array().unwrap().len(), 1);
+}
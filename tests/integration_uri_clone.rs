@@ -0,0 +1,32 @@
+//! `vibe uri handle vibe://clone/...` only clones from trusted hosts
+
+mod common;
+
+use common::{run_vibe_with_config, WorkspaceYaml};
+use tempfile::TempDir;
+
+#[test]
+fn test_clone_link_from_an_untrusted_host_is_refused() {
+    let home = TempDir::new().unwrap();
+    let root = home.path().join("workspace");
+    std::fs::create_dir_all(&root).unwrap();
+    let config = home.path().join("config.yaml");
+    WorkspaceYaml::new(&root).write(&config);
+
+    let output = run_vibe_with_config(
+        home.path(),
+        &config,
+        &[
+            "uri",
+            "handle",
+            "vibe://clone/git.example.com/acme/api?open=false",
+        ],
+    );
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("can't clone from 'git.example.com'"),
+        "{stderr}"
+    );
+    assert_eq!(std::fs::read_dir(&root).unwrap().count(), 0);
+}
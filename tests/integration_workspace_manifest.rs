@@ -0,0 +1,121 @@
+//! Exporting a workspace manifest and restoring it into an empty workspace,
+//! with local repositories standing in for the remotes
+
+mod common;
+
+use common::{git, json_data, json_report, run_vibe_with_config, WorkspaceYaml};
+use std::path::Path;
+use tempfile::TempDir;
+
+fn create_origin(path: &Path) {
+    std::fs::create_dir_all(path).unwrap();
+    git(path, &["init", "-q", "-b", "main"]);
+    std::fs::write(path.join("README.md"), "origin").unwrap();
+    git(path, &["add", "."]);
+    git(path, &["commit", "-q", "-m", "initial"]);
+}
+
+/// Export a workspace of `api`, configured for VS Code, and `tools/web`,
+/// grouped as `backend`, whose remotes live under `origins`
+fn export_manifest(origins: &Path) -> (TempDir, String) {
+    let home = TempDir::new().unwrap();
+    let config = home.path().join("config.yaml");
+    WorkspaceYaml::new(&home.path().join("workspace"))
+        .repo_with(
+            "api",
+            "api",
+            &format!(
+                "  url: {}/api\n  branch: main\n  apps:\n    vscode:\n      template: compact\n",
+                origins.display()
+            ),
+        )
+        .repo_with(
+            "web",
+            "tools/web",
+            &format!("  url: {}/web\n", origins.display()),
+        )
+        .repo("scratch", "/opt/scratch")
+        .groups(
+            "- name: backend\n  repos: [api, web, scratch]\n  apps: {}\n  default_app: vscode\n",
+        )
+        .write(&config);
+    let manifest = home.path().join("workspace.yaml");
+    let output = run_vibe_with_config(
+        home.path(),
+        &config,
+        &[
+            "config",
+            "export-manifest",
+            "--output",
+            manifest.to_str().unwrap(),
+        ],
+    );
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Left out scratch"));
+    (home, manifest.to_str().unwrap().to_string())
+}
+
+#[test]
+fn test_export_is_portable() {
+    let origins = TempDir::new().unwrap();
+    let (home, manifest) = export_manifest(origins.path());
+    let contents = std::fs::read_to_string(&manifest).unwrap();
+    assert!(contents.contains("version: 1"));
+    assert!(contents.contains("path: tools/web"));
+    assert!(contents.contains("vscode: compact"));
+    assert!(!contents.contains("scratch"));
+    assert!(!contents.contains(&home.path().join("workspace").display().to_string()));
+}
+
+#[test]
+fn test_restore_clones_and_resumes() {
+    let origins = TempDir::new().unwrap();
+    create_origin(&origins.path().join("api"));
+    let (_exported, manifest) = export_manifest(origins.path());
+
+    let home = TempDir::new().unwrap();
+    let config = home.path().join("config.yaml");
+    WorkspaceYaml::new(&home.path().join("workspace")).write(&config);
+    let restore = || {
+        run_vibe_with_config(
+            home.path(),
+            &config,
+            &["--json", "restore-workspace", &manifest],
+        )
+    };
+
+    // web's remote doesn't exist yet, so only api comes back
+    let output = restore();
+    assert_eq!(output.status.code(), Some(4));
+    let report = json_report(&output)["data"].clone();
+    assert_eq!(report["cloned"], serde_json::json!(["api"]));
+    assert_eq!(report["failed"][0]["name"], "web");
+    let workspace = home.path().join("workspace");
+    assert!(workspace.join("api/README.md").exists());
+    assert!(!workspace.join("tools/web").exists());
+    assert!(!workspace.join("tools/.web.vibe-partial").exists());
+
+    create_origin(&origins.path().join("web"));
+    let report = json_data(&restore());
+    assert_eq!(report["cloned"], serde_json::json!(["web"]));
+    assert_eq!(report["existing"], serde_json::json!(["api"]));
+    assert!(workspace.join("tools/web/README.md").exists());
+
+    let saved = std::fs::read_to_string(&config).unwrap();
+    let saved: serde_yaml::Value = serde_yaml::from_str(&saved).unwrap();
+    let repos = saved["repositories"].as_sequence().unwrap();
+    assert_eq!(repos.len(), 2);
+    assert_eq!(repos[0]["apps"]["vscode"]["template"], "compact");
+    assert_eq!(repos[1]["path"], "tools/web");
+    let backend = &saved["groups"][0];
+    assert_eq!(backend["name"], "backend");
+    assert_eq!(
+        backend["repos"],
+        serde_yaml::from_str::<serde_yaml::Value>("[api, web]").unwrap()
+    );
+    assert_eq!(backend["default_app"], "vscode");
+}
@@ -0,0 +1,103 @@
+//! Bringing worktrees created with plain `git worktree add` under management
+
+mod common;
+
+use common::{git, json_data, run_vibe_in};
+use tempfile::TempDir;
+
+/// A repository with a `hotfix` worktree created outside of vibe
+fn repo_with_hotfix() -> TempDir {
+    let home = TempDir::new().unwrap();
+    let repo = home.path().join("repo");
+    std::fs::create_dir_all(&repo).unwrap();
+    git(&repo, &["init", "-q", "-b", "main"]);
+    std::fs::write(repo.join("README.md"), "repo").unwrap();
+    git(&repo, &["add", "."]);
+    git(&repo, &["commit", "-q", "-m", "initial"]);
+    git(
+        &repo,
+        &["worktree", "add", "-q", "-b", "hotfix", "../hotfix"],
+    );
+    home
+}
+
+#[test]
+fn test_adopt_records_task_id_and_base() {
+    let home = repo_with_hotfix();
+    let repo = home.path().join("repo");
+
+    let output = run_vibe_in(
+        home.path(),
+        &repo,
+        &["--json", "git", "worktree", "adopt", "hotfix", "urgent-fix"],
+    );
+    let adopted = json_data(&output);
+    assert_eq!(adopted["branch"], "hotfix");
+    assert_eq!(adopted["task_id"], "urgent-fix");
+    assert!(adopted["previous_branch"].is_null());
+
+    let output = run_vibe_in(
+        home.path(),
+        &repo,
+        &["git", "worktree", "status", "--all", "--format", "json"],
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let worktrees: Vec<serde_json::Value> =
+        serde_json::from_str(&stdout[stdout.find('[').unwrap()..]).unwrap();
+    let hotfix = worktrees.iter().find(|w| w["branch"] == "hotfix").unwrap();
+    assert_eq!(hotfix["task_id"], "urgent-fix");
+
+    // Adopting the same worktree twice is refused
+    let output = run_vibe_in(
+        home.path(),
+        &repo,
+        &["git", "worktree", "adopt", "hotfix", "again"],
+    );
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_adopt_renames_and_relocates() {
+    let home = repo_with_hotfix();
+    let repo = home.path().join("repo");
+
+    let output = run_vibe_in(
+        home.path(),
+        &repo,
+        &[
+            "--json",
+            "git",
+            "worktree",
+            "adopt",
+            "../hotfix",
+            "urgent-fix",
+            "--rename-branch",
+            "--relocate",
+        ],
+    );
+    let adopted = json_data(&output);
+    assert_eq!(adopted["branch"], "vibe-ws/urgent-fix");
+    assert_eq!(adopted["previous_branch"], "hotfix");
+    assert!(adopted["path"].as_str().unwrap().contains(".worktrees"));
+    assert!(!home.path().join("hotfix").exists());
+}
+
+#[test]
+fn test_adopt_rejects_main_checkout_and_unknown_targets() {
+    let home = repo_with_hotfix();
+    let repo = home.path().join("repo");
+
+    let output = run_vibe_in(
+        home.path(),
+        &repo,
+        &["git", "worktree", "adopt", "main", "task"],
+    );
+    assert_eq!(output.status.code(), Some(2));
+
+    let output = run_vibe_in(
+        home.path(),
+        &repo,
+        &["git", "worktree", "adopt", "nope", "task"],
+    );
+    assert_eq!(output.status.code(), Some(3));
+}
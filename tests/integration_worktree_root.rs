@@ -0,0 +1,115 @@
+//! Worktree commands run from inside a linked worktree or a submodule
+
+mod common;
+
+use common::{commit_file, git, git_init, run_vibe_in};
+use std::path::Path;
+use tempfile::TempDir;
+
+fn init_repo(dir: &Path) {
+    git_init(dir);
+    commit_file(dir, "README.md", "repo");
+}
+
+/// Branches listed by `vibe git worktree status --format json`
+fn status_branches(home: &Path, dir: &Path, extra: &[&str]) -> Vec<String> {
+    let mut args = vec!["git", "worktree", "status", "--format", "json"];
+    args.extend_from_slice(extra);
+    let output = run_vibe_in(home, dir, &args);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "vibe failed: {stdout}\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let json = &stdout[stdout.find('[').expect("no JSON in output")..];
+    let worktrees: Vec<serde_json::Value> = serde_json::from_str(json).unwrap();
+    let mut branches: Vec<String> = worktrees
+        .iter()
+        .map(|w| w["branch"].as_str().unwrap().to_string())
+        .collect();
+    branches.sort();
+    branches
+}
+
+#[test]
+fn test_status_from_linked_worktree_defaults_to_current_task() {
+    let home = TempDir::new().unwrap();
+    let repo = home.path().join("repo");
+    init_repo(&repo);
+    git(
+        &repo,
+        &["worktree", "add", "-q", "-b", "feature", "../feature"],
+    );
+    git(&repo, &["worktree", "add", "-q", "-b", "other", "../other"]);
+    let nested = home.path().join("feature/src");
+    std::fs::create_dir_all(&nested).unwrap();
+
+    assert_eq!(status_branches(home.path(), &nested, &[]), vec!["feature"]);
+    // The main checkout plus both linked worktrees
+    let all = status_branches(home.path(), &nested, &["--all"]);
+    assert_eq!(all.len(), 3);
+    assert!(all.contains(&"feature".to_string()) && all.contains(&"other".to_string()));
+}
+
+#[test]
+fn test_status_from_submodule_uses_submodule_repository() {
+    let home = TempDir::new().unwrap();
+    let library = home.path().join("library");
+    init_repo(&library);
+    let repo = home.path().join("repo");
+    init_repo(&repo);
+    git(
+        &repo,
+        &[
+            "submodule",
+            "add",
+            "-q",
+            library.to_str().unwrap(),
+            "library",
+        ],
+    );
+    let submodule = repo.join("library");
+    git(
+        &submodule,
+        &["worktree", "add", "-q", "-b", "fix", "../library-fix"],
+    );
+
+    // The submodule's own worktrees, not the superproject's
+    let branches = status_branches(home.path(), &submodule, &["--all"]);
+    assert_eq!(branches.len(), 2);
+    assert!(branches.contains(&"fix".to_string()));
+}
+
+#[test]
+fn test_open_shell_needs_a_terminal() {
+    let home = TempDir::new().unwrap();
+    let repo = home.path().join("repo");
+    init_repo(&repo);
+    git(
+        &repo,
+        &["worktree", "add", "-q", "-b", "feature", "../feature"],
+    );
+
+    let output = run_vibe_in(
+        home.path(),
+        &repo,
+        &["git", "worktree", "open", "feature", "--print-path"],
+    );
+    assert!(output.status.success());
+    let printed = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    assert_eq!(
+        std::fs::canonicalize(printed).unwrap(),
+        std::fs::canonicalize(home.path().join("feature")).unwrap()
+    );
+
+    // Output captured by the test harness isn't a terminal
+    let output = run_vibe_in(
+        home.path(),
+        &repo,
+        &["git", "worktree", "open", "feature", "--shell"],
+    );
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--print-path"));
+}
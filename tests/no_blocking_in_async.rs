@@ -0,0 +1,100 @@
+//! Guard against blocking process spawns and directory walks inside async fns
+//!
+//! A blocking `std::process::Command` or `WalkDir` in an async fn stalls the
+//! runtime thread it runs on, which freezes the MCP server for every other
+//! tool call. Use `tokio::process::Command`, or move the work into
+//! `tokio::task::spawn_blocking`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn rust_sources(dir: &Path, out: &mut Vec<PathBuf>) {
+    for entry in fs::read_dir(dir).unwrap().flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            rust_sources(&path, out);
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            out.push(path);
+        }
+    }
+}
+
+/// Whether a `Command::new` on `line` resolves to the std (blocking) type,
+/// going by the closest preceding `use ...::process::Command` import
+fn is_std_command(lines: &[&str], index: usize) -> bool {
+    let line = lines[index];
+    if line.contains("tokio::process::Command::new") {
+        return false;
+    }
+    if line.contains("std::process::Command::new") {
+        return true;
+    }
+    lines[..index]
+        .iter()
+        .rev()
+        .map(|l| l.trim())
+        .find(|l| l.starts_with("use ") && l.contains("process::Command"))
+        .is_some_and(|l| l.contains("std::process"))
+}
+
+/// The nearest enclosing fn header, unless the work is already handed off
+/// to `spawn_blocking` between that header and `index`
+fn enclosing_async_fn<'a>(lines: &[&'a str], index: usize) -> Option<&'a str> {
+    for line in lines[..=index].iter().rev() {
+        let trimmed = line.trim_start();
+        if trimmed.contains("spawn_blocking(") {
+            return None;
+        }
+        if trimmed.starts_with("//") {
+            continue;
+        }
+        let is_fn = trimmed.starts_with("fn ")
+            || trimmed.contains(" fn ")
+            || trimmed.starts_with("async fn ");
+        if is_fn {
+            return trimmed.contains("async fn ").then_some(trimmed);
+        }
+    }
+    None
+}
+
+#[test]
+fn test_no_blocking_calls_in_async_fns() {
+    let mut sources = Vec::new();
+    rust_sources(
+        &Path::new(env!("CARGO_MANIFEST_DIR")).join("src"),
+        &mut sources,
+    );
+
+    let mut violations = Vec::new();
+    for path in sources {
+        let content = fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        for (index, line) in lines.iter().enumerate() {
+            if line.trim_start().starts_with("//") {
+                continue;
+            }
+            let blocking = (line.contains("Command::new") && is_std_command(&lines, index))
+                || line.contains("WalkDir::new")
+                || line.contains("std::fs::read_dir");
+            if !blocking {
+                continue;
+            }
+            if let Some(header) = enclosing_async_fn(&lines, index) {
+                violations.push(format!(
+                    "{}:{} in `{}`: {}",
+                    path.display(),
+                    index + 1,
+                    header,
+                    line.trim()
+                ));
+            }
+        }
+    }
+
+    assert!(
+        violations.is_empty(),
+        "blocking calls inside async fns (use tokio::process::Command or spawn_blocking):\n{}",
+        violations.join("\n")
+    );
+}